@@ -0,0 +1,176 @@
+//! Segment scan with and without late materialization: a selective filter
+//! over wide rows, where every zone is a candidate and only the filter tells
+//! them apart.
+//!
+//! Zone size follows `engine.event_per_zone` of the loaded config
+//! (`SNELDB_CONFIG`, `config/prod.toml` when unset):
+//!
+//! ```text
+//! cargo bench --bench late_materialization
+//! ```
+
+use std::path::Path;
+use std::sync::Arc;
+
+use criterion::{BenchmarkId, Criterion};
+use indexmap::IndexMap;
+use serde_json::json;
+use snel_db::command::parser::parse_command;
+use snel_db::engine::core::read::flow::operators::MemTableSource;
+use snel_db::engine::core::read::flow::{
+    BatchPool, BatchSchema, FlowChannel, FlowContext, FlowMetrics, FlowTelemetry,
+};
+use snel_db::engine::core::{
+    EventBuilder, EventId, ExecutionStep, Flusher, MemTable, QueryPlan, SegmentQueryRunner,
+};
+use snel_db::engine::schema::{FieldType, MiniSchema, SchemaRegistry};
+use snel_db::shared::config::CONFIG;
+use tokio::runtime::Runtime;
+use tokio::sync::{Mutex, RwLock};
+
+const SEGMENT: &str = "shard-0/00001";
+const ZONES: usize = 16;
+// Width of each of the two string columns only matching rows need
+const WIDE_VALUE_LEN: usize = 512;
+
+/// Flushes `ZONES` zones of wide events into one segment under `dir`.
+async fn write_segment(dir: &Path) -> Arc<RwLock<SchemaRegistry>> {
+    let registry = SchemaRegistry::new_with_path(dir.join("schemas.bin")).unwrap();
+    let registry = Arc::new(RwLock::new(registry));
+    let fields = [
+        ("amount", FieldType::I64),
+        ("note", FieldType::String),
+        ("detail", FieldType::String),
+    ];
+    let schema = MiniSchema {
+        fields: fields
+            .into_iter()
+            .map(|(name, ty)| (name.to_string(), ty))
+            .collect::<IndexMap<_, _>>(),
+        time_field: None,
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
+    };
+    registry.write().await.define("wide", schema).unwrap();
+
+    let events = ZONES * CONFIG.engine.event_per_zone;
+    let mut memtable = MemTable::new(events);
+    for i in 0..events {
+        let mut event = EventBuilder::new();
+        event.event_type = "wide".to_string();
+        event.context_id = format!("ctx-{:08}", i);
+        event.timestamp = 1_700_000_000 + i as u64;
+        event.event_id = EventId::from(i as u64 + 1);
+        let mut event = event.build();
+        event.set_payload_json(json!({
+            "amount": i,
+            "note": format!("{}-{}", i, "n".repeat(WIDE_VALUE_LEN)),
+            "detail": format!("{}-{}", i, "d".repeat(WIDE_VALUE_LEN)),
+        }));
+        memtable.insert(event).unwrap();
+    }
+
+    let segment_dir = dir.join(SEGMENT);
+    std::fs::create_dir_all(&segment_dir).unwrap();
+    Flusher::new(
+        memtable,
+        1,
+        &segment_dir,
+        Arc::clone(&registry),
+        Arc::new(Mutex::new(())),
+    )
+    .flush()
+    .await
+    .unwrap();
+    registry
+}
+
+/// Streams the rows the plan selects and returns how many there were and the
+/// bytes the scan decompressed.
+async fn scan(plan: &QueryPlan, late_materialization: bool) -> (usize, u64) {
+    let steps = plan
+        .filter_groups
+        .iter()
+        .map(|f| ExecutionStep::new(f.clone(), plan))
+        .collect();
+    let mandatory: Vec<String> = ["context_id", "event_type", "timestamp", "event_id"]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    let schema = Arc::new(
+        BatchSchema::new(
+            MemTableSource::compute_columns(plan, &mandatory)
+                .await
+                .unwrap(),
+        )
+        .unwrap(),
+    );
+    let ctx = Arc::new(FlowContext::new(
+        1024,
+        BatchPool::new(1024).unwrap(),
+        FlowMetrics::new(),
+        None::<&str>,
+        FlowTelemetry::default(),
+    ));
+    let (tx, mut rx) = FlowChannel::bounded(16, FlowMetrics::new());
+
+    let runner =
+        SegmentQueryRunner::new(plan, steps).with_late_materialization(late_materialization);
+    let drain = async {
+        let mut rows = 0;
+        while let Some(batch) = rx.recv().await {
+            rows += batch.len();
+        }
+        rows
+    };
+    let (streamed, rows) = tokio::join!(runner.stream_into(Arc::clone(&ctx), schema, tx), drain);
+    streamed.unwrap();
+    (rows, ctx.scan_stats().bytes_scanned())
+}
+
+fn bench_late_materialization(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let registry = rt.block_on(write_segment(dir.path()));
+
+    // One row of the middle zone passes; no zone index covers context_id
+    let target = ZONES / 2 * CONFIG.engine.event_per_zone;
+    let command = parse_command(&format!(
+        r#"QUERY wide WHERE context_id = "ctx-{:08}""#,
+        target
+    ))
+    .unwrap();
+    let segment_ids = Arc::new(std::sync::RwLock::new(vec![SEGMENT.to_string()]));
+    let plan = rt
+        .block_on(QueryPlan::new(
+            command,
+            &registry,
+            dir.path(),
+            &segment_ids,
+            None,
+        ))
+        .unwrap();
+
+    let mut group = c.benchmark_group("late_materialization");
+    for (name, enabled) in [("eager", false), ("late", true)] {
+        let (rows, bytes) = rt.block_on(scan(&plan, enabled));
+        assert_eq!(rows, 1);
+        println!("{name}: {bytes} bytes decompressed");
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &enabled,
+            |b, &enabled| b.to_async(&rt).iter(|| scan(&plan, enabled)),
+        );
+    }
+    group.finish();
+}
+
+fn main() {
+    if std::env::var_os("SNELDB_CONFIG").is_none() {
+        // SAFETY: nothing else runs yet, and the config is read on first use below
+        unsafe { std::env::set_var("SNELDB_CONFIG", "config/prod.toml") };
+    }
+    let mut criterion = Criterion::default().configure_from_args();
+    bench_late_materialization(&mut criterion);
+    criterion.final_summary();
+}
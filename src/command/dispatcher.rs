@@ -1,10 +1,13 @@
 use crate::command::handlers::query::QueryCommandHandler;
 use crate::command::handlers::{
-    auth, compare, define, flush, permissions, ping, remember, replay, show, store,
+    auth, batch, compare, define, explain, flush, get_event, inspect_wal, inspect_zone,
+    permissions, ping, rebuild_indexes, remember, replay, schema_catalog, show, show_indexes,
+    show_pinned_segments, show_stats, show_usage, store, union, verify_materialized,
 };
 use crate::command::types::Command;
 use crate::engine::auth::AuthManager;
 use crate::engine::schema::SchemaRegistry;
+use crate::engine::schema::normalization::resolve_event_types;
 use crate::engine::shard::manager::ShardManager;
 use crate::shared::response::render::Renderer;
 use crate::shared::response::{Response, StatusCode};
@@ -26,6 +29,10 @@ pub async fn dispatch_command<W: AsyncWrite + Unpin>(
 
     debug!(target: "sneldb::dispatch", command = ?cmd, "Dispatching command");
 
+    // Event type names spelled differently resolve to their defined spelling
+    let resolved = resolve_event_types(cmd, &*registry.read().await);
+    let cmd = resolved.as_ref().unwrap_or(cmd);
+
     match cmd {
         Store { .. } => {
             store::handle(
@@ -39,6 +46,18 @@ pub async fn dispatch_command<W: AsyncWrite + Unpin>(
             )
             .await
         }
+        Batch(_) => {
+            batch::handle(
+                cmd,
+                shard_manager,
+                registry,
+                auth_manager,
+                user_id,
+                writer,
+                renderer,
+            )
+            .await
+        }
         Define { .. } => {
             define::handle(
                 cmd,
@@ -67,6 +86,18 @@ pub async fn dispatch_command<W: AsyncWrite + Unpin>(
             .handle()
             .await
         }
+        Explain { .. } => {
+            explain::handle(
+                cmd,
+                shard_manager,
+                registry,
+                auth_manager,
+                user_id,
+                writer,
+                renderer,
+            )
+            .await
+        }
         Compare { .. } => {
             compare::ComparisonCommandHandler::new(
                 cmd,
@@ -78,13 +109,93 @@ pub async fn dispatch_command<W: AsyncWrite + Unpin>(
             .handle()
             .await
         }
+        Union { .. } => {
+            union::UnionCommandHandler::new(
+                cmd,
+                shard_manager,
+                Arc::clone(registry),
+                auth_manager,
+                user_id,
+                writer,
+                renderer,
+            )
+            .handle()
+            .await
+        }
         Replay { .. } => replay::handle(cmd, shard_manager, registry, writer, renderer).await,
         ShowMaterialized { .. } => {
             show::handle(cmd, shard_manager, registry, writer, renderer).await
         }
+        GetEvent { .. } => {
+            get_event::handle(
+                cmd,
+                shard_manager,
+                registry,
+                auth_manager,
+                user_id,
+                writer,
+                renderer,
+            )
+            .await
+        }
         Flush { .. } => flush::handle(cmd, shard_manager, registry, writer, renderer).await,
         Ping => ping::handle(cmd, writer, renderer).await,
-        CreateUser { .. } | RevokeKey { .. } | ListUsers => {
+        ShowPinnedSegments => show_pinned_segments::handle(cmd, writer, renderer).await,
+        ShowStats => show_stats::handle(cmd, writer, renderer).await,
+        ShowUsage { .. } => show_usage::handle(cmd, auth_manager, user_id, writer, renderer).await,
+        InspectZone { .. } => {
+            inspect_zone::handle(
+                cmd,
+                shard_manager,
+                registry,
+                auth_manager,
+                user_id,
+                writer,
+                renderer,
+            )
+            .await
+        }
+        InspectWal { .. } => {
+            inspect_wal::handle(cmd, shard_manager, auth_manager, user_id, writer, renderer).await
+        }
+        VerifyMaterialized { .. } => {
+            verify_materialized::handle(cmd, auth_manager, user_id, writer, renderer).await
+        }
+        RebuildIndexes { .. } => {
+            rebuild_indexes::handle(
+                cmd,
+                shard_manager,
+                registry,
+                auth_manager,
+                user_id,
+                writer,
+                renderer,
+            )
+            .await
+        }
+        ShowIndexes { .. } => {
+            show_indexes::handle(
+                cmd,
+                shard_manager,
+                registry,
+                auth_manager,
+                user_id,
+                writer,
+                renderer,
+            )
+            .await
+        }
+        ExportSchemas | ImportSchemas { .. } => {
+            schema_catalog::handle(cmd, registry, auth_manager, user_id, writer, renderer).await
+        }
+        CreateUser { .. }
+        | RevokeKey { .. }
+        | RotateKey { .. }
+        | ListUsers
+        | ShowUsers
+        | DisableUser { .. }
+        | EnableUser { .. }
+        | UnlockUser { .. } => {
             if let Some(auth_mgr) = auth_manager {
                 auth::handle(cmd, auth_mgr, user_id, writer, renderer).await
             } else {
@@ -108,9 +219,5 @@ pub async fn dispatch_command<W: AsyncWrite + Unpin>(
                 Ok(())
             }
         }
-        _ => {
-            error!(target: "sneldb::dispatch", ?cmd, "Unreachable command variant encountered");
-            unreachable!("dispatch_command called with non-command")
-        }
     }
 }
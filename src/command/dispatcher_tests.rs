@@ -4,7 +4,7 @@ use crate::command::parser::error::ParseError;
 use crate::command::types::Command;
 use crate::command::types::FieldSpec;
 use crate::engine::auth::AuthManager;
-use crate::engine::schema::SchemaRegistry;
+use crate::engine::schema::{EventTypeNormalizer, SchemaRegistry};
 use crate::engine::shard::manager::ShardManager;
 use crate::logging::init_for_tests;
 use crate::shared::response::unix::UnixRenderer;
@@ -366,6 +366,60 @@ fn test_parse_command_store_missing_payload_should_fail() {
     );
 }
 
+#[tokio::test]
+async fn test_dispatch_resolves_normalized_event_type_names() {
+    init_for_tests();
+
+    let (shard_manager, _, _auth_manager, _temp_dir) = create_test_components().await;
+    let schema_dir = tempdir().unwrap();
+    let registry = Arc::new(RwLock::new(
+        SchemaRegistry::new_with_path(schema_dir.path().join("schemas.bin"))
+            .unwrap()
+            .with_event_type_normalizer(EventTypeNormalizer {
+                fold_case: true,
+                ignore_separators: true,
+            }),
+    ));
+
+    let run = |input: &'static str| {
+        let shard_manager = Arc::clone(&shard_manager);
+        let registry = Arc::clone(&registry);
+        async move {
+            let cmd = parse_command(input).unwrap();
+            let (mut reader, mut writer) = duplex(4096);
+            dispatch_command(
+                &cmd,
+                &mut writer,
+                &shard_manager,
+                &registry,
+                None,
+                None,
+                &UnixRenderer,
+            )
+            .await
+            .unwrap();
+            drop(writer);
+            let mut response = Vec::new();
+            reader.read_to_end(&mut response).await.unwrap();
+            String::from_utf8(response).unwrap()
+        }
+    };
+
+    let defined = run(r#"DEFINE user_signup FIELDS { "id": "int" }"#).await;
+    assert!(defined.contains("Schema defined"), "got: {}", defined);
+
+    let stored = run(r#"STORE UserSignup FOR ctx1 PAYLOAD { "id": 1 }"#).await;
+    assert!(stored.contains("Event accepted"), "got: {}", stored);
+
+    let collision = run(r#"DEFINE UserSignup FIELDS { "id": "int" }"#).await;
+    assert!(
+        collision.contains("matches existing event type 'user_signup'"),
+        "got: {}",
+        collision
+    );
+    assert!(!registry.read().await.has_schema("UserSignup"));
+}
+
 /// Helper function to create test components
 async fn create_test_components() -> (
     Arc<ShardManager>,
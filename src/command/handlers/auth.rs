@@ -1,8 +1,9 @@
 use crate::command::types::Command;
-use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID, UserKey};
 use crate::shared::response::render::Renderer;
-use crate::shared::response::{Response, StatusCode};
+use crate::shared::response::{ErrorCode, Response, StatusCode};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tracing::{error, info};
@@ -63,7 +64,11 @@ pub async fn handle<W: AsyncWrite + Unpin>(
                 }
                 Err(e) => {
                     error!(target: "sneldb::auth", user_id, error = %e, "Failed to create user");
-                    let resp = Response::error(StatusCode::BadRequest, e.to_string());
+                    let resp = Response::error_with_code(
+                        StatusCode::BadRequest,
+                        ErrorCode::from(&e),
+                        e.to_string(),
+                    );
                     writer.write_all(&renderer.render(&resp)).await?;
                     writer.flush().await?;
                 }
@@ -78,7 +83,43 @@ pub async fn handle<W: AsyncWrite + Unpin>(
             }
             Err(e) => {
                 error!(target: "sneldb::auth", user_id, error = %e, "Failed to revoke key");
-                let resp = Response::error(StatusCode::BadRequest, e.to_string());
+                let resp = Response::error_with_code(
+                    StatusCode::BadRequest,
+                    ErrorCode::from(&e),
+                    e.to_string(),
+                );
+                writer.write_all(&renderer.render(&resp)).await?;
+                writer.flush().await?;
+            }
+        },
+        Command::RotateKey {
+            user_id,
+            secret_key,
+            revoke_sessions,
+        } => match auth_manager
+            .rotate_key(user_id, secret_key.clone(), *revoke_sessions)
+            .await
+        {
+            Ok(key) => {
+                info!(target: "sneldb::auth", user_id, admin_user = authenticated_user_id, revoke_sessions, "User key rotated");
+                let mut lines = vec![
+                    format!("Key rotated for user '{}'", user_id),
+                    format!("Secret key: {}", key),
+                ];
+                if *revoke_sessions {
+                    lines.push("Session tokens revoked".to_string());
+                }
+                let resp = Response::ok_lines(lines);
+                writer.write_all(&renderer.render(&resp)).await?;
+                writer.flush().await?;
+            }
+            Err(e) => {
+                error!(target: "sneldb::auth", user_id, error = %e, "Failed to rotate key");
+                let resp = Response::error_with_code(
+                    StatusCode::BadRequest,
+                    ErrorCode::from(&e),
+                    e.to_string(),
+                );
                 writer.write_all(&renderer.render(&resp)).await?;
                 writer.flush().await?;
             }
@@ -103,6 +144,43 @@ pub async fn handle<W: AsyncWrite + Unpin>(
             writer.write_all(&renderer.render(&resp)).await?;
             writer.flush().await?;
         }
+        Command::ShowUsers => {
+            let users = auth_manager.list_users().await;
+            let resp = if users.is_empty() {
+                Response::ok_lines(vec!["No users found".to_string()])
+            } else {
+                Response::ok_lines(render_user_lines(&users))
+            };
+            writer.write_all(&renderer.render(&resp)).await?;
+            writer.flush().await?;
+        }
+        Command::DisableUser { user_id }
+        | Command::EnableUser { user_id }
+        | Command::UnlockUser { user_id } => {
+            let (result, state) = match cmd {
+                Command::EnableUser { .. } => (auth_manager.enable_user(user_id).await, "enabled"),
+                Command::UnlockUser { .. } => (auth_manager.unlock_user(user_id).await, "unlocked"),
+                _ => (auth_manager.disable_user(user_id).await, "disabled"),
+            };
+            match result {
+                Ok(_) => {
+                    info!(target: "sneldb::auth", user_id, admin_user = authenticated_user_id, state, "User state changed");
+                    let resp = Response::ok_lines(vec![format!("User '{}' {}", user_id, state)]);
+                    writer.write_all(&renderer.render(&resp)).await?;
+                    writer.flush().await?;
+                }
+                Err(e) => {
+                    error!(target: "sneldb::auth", user_id, error = %e, "Failed to change user state");
+                    let resp = Response::error_with_code(
+                        StatusCode::BadRequest,
+                        ErrorCode::from(&e),
+                        e.to_string(),
+                    );
+                    writer.write_all(&renderer.render(&resp)).await?;
+                    writer.flush().await?;
+                }
+            }
+        }
         _ => {
             error!(target: "sneldb::auth", "Invalid command variant for auth handler");
             let resp = Response::error(StatusCode::BadRequest, "Invalid command variant");
@@ -112,3 +190,59 @@ pub async fn handle<W: AsyncWrite + Unpin>(
     }
     Ok(())
 }
+
+/// One line per user, ordered by ID: state, roles, per-event-type permissions and any
+/// active lockout.
+/// Secret keys are never part of the output.
+pub fn render_user_lines(users: &[UserKey]) -> Vec<String> {
+    let mut users: Vec<&UserKey> = users.iter().collect();
+    users.sort_by(|a, b| a.user_id.cmp(&b.user_id));
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    users
+        .into_iter()
+        .map(|user| {
+            let roles = if user.roles.is_empty() {
+                "none".to_string()
+            } else {
+                user.roles.join(", ")
+            };
+
+            let mut event_types: Vec<&String> = user.permissions.keys().collect();
+            event_types.sort();
+            let permissions = if event_types.is_empty() {
+                "none".to_string()
+            } else {
+                event_types
+                    .into_iter()
+                    .map(|event_type| {
+                        let set = &user.permissions[event_type];
+                        let granted = match (set.read, set.write) {
+                            (true, true) => "read, write",
+                            (true, false) => "read",
+                            (false, true) => "write",
+                            (false, false) => "none",
+                        };
+                        format!("{} ({})", event_type, granted)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            let mut line = format!(
+                "{}: {}; roles: {}; permissions: {}",
+                user.user_id,
+                if user.active { "active" } else { "disabled" },
+                roles,
+                permissions
+            );
+            if let Some(until) = user.locked_until.filter(|_| user.is_locked(now)) {
+                line.push_str(&format!("; locked until {}", until));
+            }
+            line
+        })
+        .collect()
+}
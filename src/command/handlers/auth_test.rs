@@ -1,6 +1,6 @@
 use crate::command::handlers::auth::handle;
 use crate::command::types::Command;
-use crate::engine::auth::AuthManager;
+use crate::engine::auth::{AuthManager, LockoutPolicy};
 use crate::engine::shard::manager::ShardManager;
 use crate::logging::init_for_tests;
 use crate::shared::response::unix::UnixRenderer;
@@ -1001,3 +1001,260 @@ async fn test_create_user_without_roles_defaults_to_empty() {
     assert_eq!(created_user.roles, Vec::<String>::new());
     assert!(!auth_manager.is_admin("regular_user").await);
 }
+
+#[tokio::test]
+async fn test_show_users_lists_state_roles_and_permissions_without_secrets() {
+    use crate::engine::auth::PermissionSet;
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+    let admin_id = create_admin_user(&auth_manager).await;
+    auth_manager
+        .create_user("reader".to_string(), Some("reader_secret".to_string()))
+        .await
+        .unwrap();
+    auth_manager
+        .grant_permission("reader", "order_created", PermissionSet::read_only())
+        .await
+        .unwrap();
+    auth_manager
+        .grant_permission("reader", "audit_logged", PermissionSet::read_write())
+        .await
+        .unwrap();
+    auth_manager.disable_user("reader").await.unwrap();
+
+    let (mut reader, mut writer) = duplex(4096);
+    handle(
+        &Command::ShowUsers,
+        &auth_manager,
+        Some(&admin_id),
+        &mut writer,
+        &UnixRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+
+    let mut response = vec![0u8; 4096];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+
+    assert!(msg.contains("200 OK"));
+    assert!(msg.contains(
+        "reader: disabled; roles: none; permissions: audit_logged (read, write), order_created (read)"
+    ));
+    assert!(msg.contains("test_admin: active; roles: admin; permissions: none"));
+    assert!(!msg.contains("reader_secret"));
+    assert!(!msg.contains("admin_secret"));
+}
+
+#[tokio::test]
+async fn test_show_users_requires_admin() {
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+    auth_manager
+        .create_user("regular_user".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+
+    let (mut reader, mut writer) = duplex(1024);
+    handle(
+        &Command::ShowUsers,
+        &auth_manager,
+        Some("regular_user"),
+        &mut writer,
+        &UnixRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+
+    let mut response = vec![0u8; 1024];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(msg.contains("Only admin users can manage users"));
+    assert!(!msg.contains("regular_user:"));
+}
+
+#[tokio::test]
+async fn test_disable_user_invalidates_sessions_and_enable_restores_access() {
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+    let admin_id = create_admin_user(&auth_manager).await;
+    auth_manager
+        .create_user("session_user".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    let token = auth_manager.generate_session_token("session_user").await;
+    assert!(auth_manager.validate_session_token(&token).await.is_some());
+
+    let (mut reader, mut writer) = duplex(1024);
+    handle(
+        &Command::DisableUser {
+            user_id: "session_user".to_string(),
+        },
+        &auth_manager,
+        Some(&admin_id),
+        &mut writer,
+        &UnixRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+    let mut response = vec![0u8; 1024];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(msg.contains("User 'session_user' disabled"));
+    assert!(auth_manager.validate_session_token(&token).await.is_none());
+
+    let (mut reader, mut writer) = duplex(1024);
+    handle(
+        &Command::EnableUser {
+            user_id: "session_user".to_string(),
+        },
+        &auth_manager,
+        Some(&admin_id),
+        &mut writer,
+        &UnixRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+    let mut response = vec![0u8; 1024];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(msg.contains("User 'session_user' enabled"));
+
+    // Old sessions stay revoked; new ones work again
+    assert!(auth_manager.validate_session_token(&token).await.is_none());
+    let fresh = auth_manager.generate_session_token("session_user").await;
+    assert!(auth_manager.validate_session_token(&fresh).await.is_some());
+}
+
+#[tokio::test]
+async fn test_disable_user_error_user_not_found() {
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+    let admin_id = create_admin_user(&auth_manager).await;
+
+    let (mut reader, mut writer) = duplex(1024);
+    handle(
+        &Command::DisableUser {
+            user_id: "ghost".to_string(),
+        },
+        &auth_manager,
+        Some(&admin_id),
+        &mut writer,
+        &UnixRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+
+    let mut response = vec![0u8; 1024];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(msg.contains("400"));
+    assert!(msg.contains("User not found"));
+}
+
+#[tokio::test]
+async fn test_rotate_key_returns_new_secret_once() {
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+    let admin_id = create_admin_user(&auth_manager).await;
+    auth_manager
+        .create_user("rotating".to_string(), Some("old_secret".to_string()))
+        .await
+        .unwrap();
+
+    let (mut reader, mut writer) = duplex(1024);
+    handle(
+        &Command::RotateKey {
+            user_id: "rotating".to_string(),
+            secret_key: Some("new_secret".to_string()),
+            revoke_sessions: true,
+        },
+        &auth_manager,
+        Some(&admin_id),
+        &mut writer,
+        &UnixRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+
+    let mut response = vec![0u8; 1024];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(msg.contains("200 OK"));
+    assert!(msg.contains("Key rotated for user 'rotating'"));
+    assert!(msg.contains("Secret key: new_secret"));
+    assert!(msg.contains("Session tokens revoked"));
+
+    let users = auth_manager.list_users().await;
+    let user = users.iter().find(|u| u.user_id == "rotating").unwrap();
+    assert_eq!(user.secret_key, "new_secret");
+}
+
+#[tokio::test]
+async fn test_show_users_reports_lockout_and_unlock_user_lifts_it() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+    let auth_manager = Arc::new(AuthManager::new(shard_manager).with_lockout(Some(
+        LockoutPolicy {
+            max_failures: 2,
+            window_secs: 300,
+            cooldown_secs: 900,
+            per_source: false,
+        },
+    )));
+    let admin_id = create_admin_user(&auth_manager).await;
+    auth_manager
+        .create_user("locked_user".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    for _ in 0..2 {
+        let _ = auth_manager
+            .verify_signature("PING", "locked_user", "bad", None)
+            .await;
+    }
+
+    let (mut reader, mut writer) = duplex(4096);
+    handle(
+        &Command::ShowUsers,
+        &auth_manager,
+        Some(&admin_id),
+        &mut writer,
+        &UnixRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+    let mut response = vec![0u8; 4096];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(msg.contains("locked_user: active; roles: none; permissions: none; locked until "));
+    assert!(!msg.contains("test_admin: active; roles: admin; permissions: none; locked"));
+
+    let (mut reader, mut writer) = duplex(1024);
+    handle(
+        &Command::UnlockUser {
+            user_id: "locked_user".to_string(),
+        },
+        &auth_manager,
+        Some(&admin_id),
+        &mut writer,
+        &UnixRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+    let mut response = vec![0u8; 1024];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(msg.contains("User 'locked_user' unlocked"));
+
+    let users = auth_manager.list_users().await;
+    let user = users.iter().find(|u| u.user_id == "locked_user").unwrap();
+    assert_eq!(user.locked_until, None);
+}
@@ -0,0 +1,166 @@
+use crate::command::handlers::payload_limits::PayloadLimits;
+use crate::command::handlers::store;
+use crate::command::types::Command;
+use crate::engine::auth::AuthManager;
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{ErrorCode, Response, StatusCode};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Handles `BATCH [ STORE ...; STORE ... ]`.
+///
+/// Every event is validated before any is routed, so a malformed event stores
+/// nothing. Oversized events are the exception: they are dropped and reported
+/// individually while the rest of the batch is stored, unless `strict_batches`
+/// is set, in which case they reject the whole batch too.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    handle_with_limits(
+        cmd,
+        shard_manager,
+        registry,
+        auth_manager,
+        user_id,
+        &PayloadLimits::from_config(),
+        writer,
+        renderer,
+    )
+    .await
+}
+
+/// Like [`handle`], with explicit payload limits instead of those from `[ingest]`.
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_with_limits<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    limits: &PayloadLimits,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::Batch(commands) = cmd else {
+        warn!(target: "sneldb::batch", "Received invalid command variant for Batch");
+        return write_response(
+            writer,
+            renderer,
+            Response::error(StatusCode::BadRequest, "Invalid command variant"),
+        )
+        .await;
+    };
+
+    if commands
+        .iter()
+        .any(|command| !matches!(command, Command::Store { .. }))
+    {
+        warn!(target: "sneldb::batch", "BATCH contains a non-STORE command");
+        return write_response(
+            writer,
+            renderer,
+            Response::error(StatusCode::BadRequest, "BATCH only supports STORE commands"),
+        )
+        .await;
+    }
+
+    let total = commands.len();
+    let mut events = Vec::with_capacity(total);
+    let mut rejected = Vec::new();
+    for (idx, command) in commands.iter().enumerate() {
+        match store::prepare_event(command, registry, auth_manager, user_id, limits).await {
+            Ok(event) => events.push((event, store::payload_bytes(command))),
+            Err(rejection)
+                if rejection.code == ErrorCode::PayloadTooLarge && !limits.strict_batches =>
+            {
+                rejected.push(format!("Rejected event {}: {}", idx + 1, rejection.message));
+            }
+            Err(rejection) => {
+                return write_response(
+                    writer,
+                    renderer,
+                    Response::error_with_code(
+                        rejection.status,
+                        rejection.code,
+                        format!(
+                            "Event {} of batch: {}; no events were stored",
+                            idx + 1,
+                            rejection.message
+                        ),
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    if events.is_empty() {
+        warn!(target: "sneldb::batch", total, "Every event in the batch was rejected");
+        return write_response(
+            writer,
+            renderer,
+            Response::error_with_code(
+                StatusCode::BadRequest,
+                ErrorCode::PayloadTooLarge,
+                format!(
+                    "All {} events in the batch exceed the payload size limit",
+                    total
+                ),
+            ),
+        )
+        .await;
+    }
+
+    let mut stored = 0;
+    let mut stored_bytes = 0;
+    for (event, bytes) in events {
+        if let Err(rejection) = store::route_event(shard_manager, registry, event).await {
+            store::record_write(user_id, stored, stored_bytes);
+            return write_response(
+                writer,
+                renderer,
+                Response::error_with_code(
+                    rejection.status,
+                    rejection.code,
+                    format!(
+                        "{} after storing {} of {} events",
+                        rejection.message, stored, total
+                    ),
+                ),
+            )
+            .await;
+        }
+        stored += 1;
+        stored_bytes += bytes;
+    }
+    store::record_write(user_id, stored, stored_bytes);
+
+    info!(
+        target: "sneldb::batch",
+        stored,
+        rejected = rejected.len(),
+        "Batch accepted"
+    );
+    let mut lines = vec![format!("Stored {} of {} events", stored, total)];
+    lines.extend(rejected);
+    write_response(writer, renderer, Response::ok_lines(lines)).await
+}
+
+async fn write_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    renderer: &dyn Renderer,
+    response: Response,
+) -> std::io::Result<()> {
+    writer.write_all(&renderer.render(&response)).await?;
+    writer.flush().await
+}
@@ -0,0 +1,167 @@
+use crate::command::handlers::batch;
+use crate::command::handlers::payload_limits::PayloadLimits;
+use crate::command::handlers::query::QueryCommandHandler;
+use crate::command::types::Command;
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::logging::init_for_tests;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::{CommandFactory, SchemaRegistryFactory};
+use serde_json::{Value as JsonValue, json};
+use std::sync::Arc;
+use tempfile::tempdir;
+use tokio::io::{AsyncReadExt, duplex};
+use tokio::sync::RwLock;
+use tokio::time::{Duration, sleep};
+
+async fn setup() -> (ShardManager, Arc<RwLock<SchemaRegistry>>) {
+    init_for_tests();
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("batch_evt", &[("id", "int"), ("note", "string")])
+        .await
+        .unwrap();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+    (shard_manager, factory.registry())
+}
+
+fn store(id: i64, note: &str) -> Command {
+    CommandFactory::store()
+        .with_event_type("batch_evt")
+        .with_context_id(&format!("ctx{}", id))
+        .with_payload(json!({ "id": id, "note": note }))
+        .create()
+}
+
+fn small_limits(strict_batches: bool) -> PayloadLimits {
+    PayloadLimits {
+        max_payload_bytes: Some(64),
+        strict_batches,
+        ..Default::default()
+    }
+}
+
+async fn run_batch(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    limits: &PayloadLimits,
+) -> JsonValue {
+    let (mut reader, mut writer) = duplex(4096);
+    batch::handle_with_limits(
+        cmd,
+        shard_manager,
+        registry,
+        None,
+        None,
+        limits,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+    serde_json::from_str(&body).expect("json response")
+}
+
+async fn stored_count(shard_manager: &ShardManager, registry: &Arc<RwLock<SchemaRegistry>>) -> u64 {
+    sleep(Duration::from_millis(100)).await;
+    let cmd = CommandFactory::query()
+        .with_event_type("batch_evt")
+        .create();
+    let (mut reader, mut writer) = duplex(1 << 16);
+    QueryCommandHandler::new(
+        &cmd,
+        shard_manager,
+        Arc::clone(registry),
+        None,
+        None,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .handle()
+    .await
+    .expect("query should succeed");
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+    body.lines()
+        .filter_map(|line| serde_json::from_str::<JsonValue>(line).ok())
+        .find(|frame| frame["type"] == "end")
+        .and_then(|frame| frame["row_count"].as_u64())
+        .expect("end frame")
+}
+
+#[tokio::test]
+async fn test_batch_rejects_only_oversized_events() {
+    let (shard_manager, registry) = setup().await;
+    let cmd = Command::Batch(vec![
+        store(1, "ok"),
+        store(2, &"x".repeat(100)),
+        store(3, "ok"),
+    ]);
+
+    let response = run_batch(&cmd, &shard_manager, &registry, &small_limits(false)).await;
+
+    assert_eq!(response["status"], 200);
+    let lines = response["results"].as_array().unwrap();
+    assert_eq!(lines[0], "Stored 2 of 3 events");
+    let rejection = lines[1].as_str().unwrap();
+    assert!(rejection.starts_with("Rejected event 2:"), "{}", rejection);
+    assert!(rejection.contains("limit of 64 bytes"), "{}", rejection);
+    assert_eq!(stored_count(&shard_manager, &registry).await, 2);
+}
+
+#[tokio::test]
+async fn test_strict_batch_rejects_whole_batch_for_oversized_event() {
+    let (shard_manager, registry) = setup().await;
+    let cmd = Command::Batch(vec![store(1, "ok"), store(2, &"x".repeat(100))]);
+
+    let response = run_batch(&cmd, &shard_manager, &registry, &small_limits(true)).await;
+
+    assert_eq!(response["status"], 400);
+    assert_eq!(response["code"], "PAYLOAD_TOO_LARGE");
+    let message = response["message"].as_str().unwrap();
+    assert!(message.starts_with("Event 2 of batch:"), "{}", message);
+    assert!(message.ends_with("no events were stored"), "{}", message);
+    assert_eq!(stored_count(&shard_manager, &registry).await, 0);
+}
+
+#[tokio::test]
+async fn test_batch_with_invalid_event_stores_nothing() {
+    let (shard_manager, registry) = setup().await;
+    let invalid = CommandFactory::store()
+        .with_event_type("batch_evt")
+        .with_payload(json!({ "id": 2 }))
+        .create();
+    let cmd = Command::Batch(vec![store(1, "ok"), invalid]);
+
+    let response = run_batch(&cmd, &shard_manager, &registry, &PayloadLimits::default()).await;
+
+    assert_eq!(response["status"], 400);
+    assert!(
+        response["message"]
+            .as_str()
+            .unwrap()
+            .contains("Missing field 'note'")
+    );
+    assert_eq!(stored_count(&shard_manager, &registry).await, 0);
+}
+
+#[tokio::test]
+async fn test_batch_rejects_non_store_commands() {
+    let (shard_manager, registry) = setup().await;
+    let cmd = Command::Batch(vec![store(1, "ok"), Command::Flush]);
+
+    let response = run_batch(&cmd, &shard_manager, &registry, &PayloadLimits::default()).await;
+
+    assert_eq!(response["status"], 400);
+    assert_eq!(response["message"], "BATCH only supports STORE commands");
+}
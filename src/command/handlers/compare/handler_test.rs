@@ -95,6 +95,14 @@ async fn test_comparison_handler_rejects_single_query() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let cmd = Command::Compare {
@@ -153,6 +161,14 @@ async fn test_comparison_handler_rejects_when_streaming_disabled() {
         time_bucket: Some(TimeGranularity::Month),
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let query2 = QueryCommand {
@@ -174,6 +190,14 @@ async fn test_comparison_handler_rejects_when_streaming_disabled() {
         time_bucket: Some(TimeGranularity::Month),
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let cmd = Command::Compare {
@@ -717,6 +741,14 @@ async fn test_comparison_handler_pipeline_error_returns_internal_error() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let query2 = QueryCommand {
@@ -738,6 +770,14 @@ async fn test_comparison_handler_pipeline_error_returns_internal_error() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let cmd = Command::Compare {
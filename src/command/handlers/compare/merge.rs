@@ -5,7 +5,9 @@ use crate::command::handlers::query::merge::aggregate_stream::AggregateStreamMer
 use crate::command::handlers::query_batch_stream::QueryBatchStream;
 use crate::command::types::QueryCommand;
 use crate::engine::core::read::aggregate::partial::GroupKey;
-use crate::engine::core::read::aggregate::plan::{AggregateOpSpec, AggregatePlan};
+use crate::engine::core::read::aggregate::plan::{
+    AggregateOpSpec, AggregatePlan, percentile_column,
+};
 use crate::engine::core::read::flow::{
     BatchPool, BatchSchema, BatchSender, ColumnBatch, FlowChannel, FlowMetrics,
 };
@@ -239,6 +241,12 @@ impl ComparisonStreamMerger {
                     AggregateOpSpec::CountAll => "count".to_string(),
                     AggregateOpSpec::CountField { field } => format!("count_{}", field),
                     AggregateOpSpec::CountUnique { field } => format!("count_unique_{}", field),
+                    AggregateOpSpec::CountDistinct { field } => {
+                        format!("count_distinct_{}", field)
+                    }
+                    AggregateOpSpec::Percentile { field, percentile } => {
+                        percentile_column(field, *percentile)
+                    }
                     AggregateOpSpec::Total { field } => format!("total_{}", field),
                     AggregateOpSpec::Avg { field } => format!("avg_{}", field),
                     AggregateOpSpec::Min { field } => format!("min_{}", field),
@@ -248,9 +256,10 @@ impl ComparisonStreamMerger {
                 let logical_type = match spec {
                     AggregateOpSpec::CountAll
                     | AggregateOpSpec::CountField { .. }
-                    | AggregateOpSpec::CountUnique { .. } => "Integer",
+                    | AggregateOpSpec::CountUnique { .. }
+                    | AggregateOpSpec::CountDistinct { .. } => "Integer",
                     AggregateOpSpec::Total { .. } => "Integer",
-                    AggregateOpSpec::Avg { .. } => "Float",
+                    AggregateOpSpec::Avg { .. } | AggregateOpSpec::Percentile { .. } => "Float",
                     AggregateOpSpec::Min { .. } | AggregateOpSpec::Max { .. } => "Integer",
                 };
                 columns.push(ColumnSpec {
@@ -296,7 +305,11 @@ impl ComparisonStreamMerger {
 
             // Add group_by values
             for group_value in &group_key.groups {
-                row.push(ScalarValue::Utf8(group_value.clone()));
+                row.push(
+                    group_value
+                        .clone()
+                        .map_or(ScalarValue::Null, ScalarValue::Utf8),
+                );
             }
 
             // Add metric values for each query (or NULL if missing)
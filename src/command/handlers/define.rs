@@ -1,10 +1,11 @@
+use crate::command::handlers::schema_limits::SchemaLimits;
 use crate::command::types::Command;
 use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
 use crate::engine::define::run as engine_define;
 use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
 use crate::shared::response::render::Renderer;
-use crate::shared::response::{Response, StatusCode};
+use crate::shared::response::{ErrorCode, Response, StatusCode};
 use std::sync::Arc;
 use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
@@ -12,11 +13,36 @@ use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
 pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    handle_with_limits(
+        cmd,
+        shard_manager,
+        registry,
+        auth_manager,
+        user_id,
+        &SchemaLimits::from_config(),
+        writer,
+        renderer,
+    )
+    .await
+}
+
+/// Like [`handle`], with explicit schema limits instead of those from `[schema]`.
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_with_limits<W: AsyncWrite + Unpin>(
     cmd: &Command,
     _shard_manager: &ShardManager,
     registry: &Arc<RwLock<SchemaRegistry>>,
     auth_manager: Option<&Arc<AuthManager>>,
     user_id: Option<&str>,
+    limits: &SchemaLimits,
     writer: &mut W,
     renderer: &dyn Renderer,
 ) -> std::io::Result<()> {
@@ -62,6 +88,20 @@ pub async fn handle<W: AsyncWrite + Unpin>(
 
     let mut registry = registry.write().await;
 
+    // Checked under the write lock so the limit holds against concurrent DEFINEs
+    if let Err(message) = limits.check(&registry, event_type, schema.fields.len()) {
+        warn!(
+            target: "sneldb::define",
+            event_type, error = %message, "Schema limit exceeded"
+        );
+        let resp = Response::error_with_code(
+            StatusCode::BadRequest,
+            ErrorCode::SchemaLimitExceeded,
+            message,
+        );
+        return writer.write_all(&renderer.render(&resp)).await;
+    }
+
     match engine_define::define_schema(
         &mut registry,
         event_type,
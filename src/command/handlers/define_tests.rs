@@ -5,7 +5,7 @@ use crate::engine::schema::registry::SchemaRegistry;
 use crate::engine::schema::{EnumType, FieldType};
 use crate::engine::shard::manager::ShardManager;
 use crate::shared::response::JsonRenderer;
-use std::collections::HashMap;
+use indexmap::IndexMap;
 use std::sync::Arc;
 use tempfile::tempdir;
 use tokio::sync::RwLock;
@@ -29,7 +29,7 @@ async fn test_define_handler_success() {
         event_type: "signup".to_string(),
         version: Some(1),
         schema: MiniSchema {
-            fields: HashMap::from([
+            fields: IndexMap::from([
                 (
                     "field1".to_string(),
                     FieldSpec::Primitive("string".to_string()),
@@ -39,6 +39,9 @@ async fn test_define_handler_success() {
                     FieldSpec::Primitive("u64".to_string()),
                 ),
             ]),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         },
     };
 
@@ -83,10 +86,13 @@ async fn test_define_handler_with_enum_field() {
         event_type: "subscription".to_string(),
         version: None,
         schema: MiniSchema {
-            fields: HashMap::from([(
+            fields: IndexMap::from([(
                 "plan".to_string(),
                 FieldSpec::Enum(vec!["pro".to_string(), "basic".to_string()]),
             )]),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         },
     };
 
@@ -134,10 +140,13 @@ async fn test_define_handler_with_optional_field() {
         event_type: "user_profile".to_string(),
         version: None,
         schema: MiniSchema {
-            fields: HashMap::from([(
+            fields: IndexMap::from([(
                 "nickname".to_string(),
                 FieldSpec::Primitive("string | null".to_string()),
             )]),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         },
     };
 
@@ -179,7 +188,10 @@ async fn test_define_handler_engine_error() {
         event_type: "invalid".to_string(),
         version: Some(1),
         schema: MiniSchema {
-            fields: HashMap::new(),
+            fields: IndexMap::new(),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         },
     };
 
@@ -221,10 +233,13 @@ async fn test_define_handler_bypass_auth_allows_definition() {
         event_type: "bypass_event".to_string(),
         version: None,
         schema: MiniSchema {
-            fields: HashMap::from([(
+            fields: IndexMap::from([(
                 "field1".to_string(),
                 FieldSpec::Primitive("string".to_string()),
             )]),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         },
     };
 
@@ -276,10 +291,13 @@ async fn test_define_handler_bypass_auth_vs_regular_user() {
         event_type: "test_event".to_string(),
         version: None,
         schema: MiniSchema {
-            fields: HashMap::from([(
+            fields: IndexMap::from([(
                 "field1".to_string(),
                 FieldSpec::Primitive("string".to_string()),
             )]),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         },
     };
 
@@ -321,3 +339,77 @@ async fn test_define_handler_bypass_auth_vs_regular_user() {
     let r = registry.read().await;
     assert!(r.get("test_event").is_some());
 }
+
+#[tokio::test]
+async fn test_define_handler_limits_hold_under_concurrent_defines() {
+    use crate::command::handlers::schema_limits::SchemaLimits;
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let schema_dir = tempdir().unwrap();
+    let registry = Arc::new(RwLock::new(
+        SchemaRegistry::new_with_path(schema_dir.path().join("schemas.bin")).unwrap(),
+    ));
+    let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+    let limits = SchemaLimits {
+        max_schemas: Some(3),
+        max_fields_per_schema: Some(2),
+    };
+    let define = |event_type: String, fields: usize| Command::Define {
+        event_type,
+        version: None,
+        schema: MiniSchema {
+            fields: (0..fields)
+                .map(|i| (format!("f{}", i), FieldSpec::Primitive("int".to_string())))
+                .collect(),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
+        },
+    };
+    let run = |cmd: Command| {
+        let shard_manager = Arc::clone(&shard_manager);
+        let registry = Arc::clone(&registry);
+        async move {
+            let mut writer = Vec::new();
+            define::handle_with_limits(
+                &cmd,
+                &shard_manager,
+                &registry,
+                None,
+                None,
+                &limits,
+                &mut writer,
+                &JsonRenderer,
+            )
+            .await
+            .unwrap();
+            String::from_utf8(writer).unwrap()
+        }
+    };
+
+    let wide = run(define("wide".to_string(), 3)).await;
+    assert!(
+        wide.contains("\"code\":\"SCHEMA_LIMIT_EXCEEDED\""),
+        "{}",
+        wide
+    );
+    assert!(wide.contains("declares 3 fields"), "{}", wide);
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| tokio::spawn(run(define(format!("event_{}", i), 1))))
+        .collect();
+    let mut rejected = 0;
+    for handle in handles {
+        let body = handle.await.unwrap();
+        if body.contains("SCHEMA_LIMIT_EXCEEDED") {
+            rejected += 1;
+        } else {
+            assert!(body.contains("Schema defined"), "{}", body);
+        }
+    }
+    assert_eq!(rejected, 5);
+    assert_eq!(registry.read().await.get_all().len(), 3);
+}
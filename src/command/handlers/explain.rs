@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use crate::command::handlers::query::{
+    COMPLEXITY_ERROR_PREFIX, ComplexityLimits, QueryExecutionPipeline, ReadUsageGuard,
+    materialized_views_dir,
+};
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::core::read::flow::{MEMORY_LIMIT_ERROR_PREFIX, QueryMemoryBudget};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{ErrorCode, Response, StatusCode};
+
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::Explain { query, analyze } = cmd else {
+        let resp = Response::error(StatusCode::BadRequest, "Invalid EXPLAIN command");
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    if let Some(auth_mgr) = auth_manager {
+        let event_type = query.event_type();
+        let allowed = match user_id {
+            Some(uid) => uid == BYPASS_USER_ID || auth_mgr.can_read(uid, event_type).await,
+            None => false,
+        };
+        if !allowed {
+            let resp = Response::error(
+                StatusCode::Forbidden,
+                format!("Read permission denied for event type '{event_type}'"),
+            );
+            return writer.write_all(&renderer.render(&resp)).await;
+        }
+    }
+
+    let mut pipeline = QueryExecutionPipeline::new(query, shard_manager, Arc::clone(registry));
+    if let Some(dir) = materialized_views_dir() {
+        pipeline = pipeline.with_materialized_views(dir);
+    }
+    let mut lines = pipeline.explain();
+    debug!(target: "sneldb::explain", plan = ?lines, "Explained query");
+
+    let resp = if *analyze {
+        pipeline = pipeline
+            .with_complexity_limits(ComplexityLimits::resolve(auth_manager, user_id).await)
+            .with_memory_budget(QueryMemoryBudget::from_config(user_id))
+            .with_projection_audit();
+        match run_for_analysis(&pipeline, user_id).await {
+            Ok(analysis) => {
+                lines.extend(analysis);
+                Response::ok_lines(lines)
+            }
+            Err(resp) => resp,
+        }
+    } else {
+        Response::ok_lines(lines)
+    };
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await
+}
+
+/// Runs the query, discarding its rows, and describes what it read: row and
+/// byte counts, then the columns hydrated against those the planner requested.
+pub async fn run_for_analysis(
+    pipeline: &QueryExecutionPipeline<'_>,
+    user_id: Option<&str>,
+) -> Result<Vec<String>, Response> {
+    let started = Instant::now();
+    let stats = pipeline.scan_stats();
+    let mut usage = ReadUsageGuard::new(user_id, Arc::clone(&stats));
+
+    let mut rows = 0usize;
+    let failure = match pipeline.execute_streaming().await {
+        Ok(Some(mut stream)) => {
+            while let Some(batch) = stream.recv().await {
+                rows += batch.len();
+            }
+            stream.failure().map(str::to_string)
+        }
+        Ok(None) => None,
+        Err(error) => Some(error),
+    };
+    if let Some(error) = failure {
+        let code = if error.starts_with(COMPLEXITY_ERROR_PREFIX) {
+            ErrorCode::QueryTooComplex
+        } else if error.contains(MEMORY_LIMIT_ERROR_PREFIX) {
+            ErrorCode::QueryMemoryLimitExceeded
+        } else {
+            return Err(Response::error(
+                StatusCode::InternalError,
+                format!("Query failed: {error}"),
+            ));
+        };
+        return Err(Response::error_with_code(
+            StatusCode::BadRequest,
+            code,
+            error,
+        ));
+    }
+    // Nothing reaches the client, so nothing counts as returned
+    usage.completed(0);
+
+    let mut lines = vec![
+        format!(
+            "analyze: {} rows produced in {:.3} ms",
+            rows,
+            started.elapsed().as_secs_f64() * 1000.0
+        ),
+        format!(
+            "analyze: rows scanned {}, bytes scanned {}, busy {:.3} ms, zone cache hits {} misses {}",
+            stats.rows_scanned(),
+            stats.bytes_scanned(),
+            stats.busy_time().as_secs_f64() * 1000.0,
+            stats.cache_hits(),
+            stats.cache_misses()
+        ),
+    ];
+    if let Some(audit) = stats.projection_audit() {
+        lines.extend(audit.report().lines());
+    }
+    Ok(lines)
+}
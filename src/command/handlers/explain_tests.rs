@@ -0,0 +1,323 @@
+use std::sync::Arc;
+
+use tokio::io::{AsyncReadExt, duplex};
+use tokio::time::{Duration, sleep};
+
+use crate::command::handlers::explain;
+use crate::command::handlers::query::QueryExecutionPipeline;
+use crate::command::handlers::remember::remember_query_with_data_dir;
+use crate::command::handlers::{flush, store};
+use crate::command::parser::command::parse_command;
+use crate::command::parser::commands::{query, remember};
+use crate::command::types::Command;
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::logging::init_for_tests;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::{CommandFactory, SchemaRegistryFactory};
+use tempfile::tempdir;
+
+const VIEW_QUERY: &str = "QUERY explain_orders COUNT, TOTAL amount, AVG amount BY region";
+
+async fn setup() -> (ShardManager, Arc<tokio::sync::RwLock<SchemaRegistry>>) {
+    init_for_tests();
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields(
+            "explain_orders",
+            &[("id", "int"), ("region", "string"), ("amount", "int")],
+        )
+        .await
+        .unwrap();
+    let shard_manager = ShardManager::new(
+        1,
+        tempdir().unwrap().into_path(),
+        tempdir().unwrap().into_path(),
+    )
+    .await;
+    (shard_manager, factory.registry())
+}
+
+async fn store_orders(
+    shard_manager: &ShardManager,
+    registry: &Arc<tokio::sync::RwLock<SchemaRegistry>>,
+    orders: &[(i64, &str, i64)],
+) {
+    for (id, region, amount) in orders {
+        let cmd = CommandFactory::store()
+            .with_event_type("explain_orders")
+            .with_context_id(&format!("o{id}"))
+            .with_payload(serde_json::json!({"id": id, "region": region, "amount": amount}))
+            .create();
+        let (_r, mut w) = duplex(1024);
+        store::handle(
+            &cmd,
+            shard_manager,
+            registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+}
+
+async fn remember_view(
+    shard_manager: &ShardManager,
+    registry: &Arc<tokio::sync::RwLock<SchemaRegistry>>,
+    data_dir: &std::path::Path,
+) {
+    let Command::RememberQuery { spec } =
+        remember::parse(&format!("REMEMBER {VIEW_QUERY} AS orders_by_region")).unwrap()
+    else {
+        panic!("expected REMEMBER command");
+    };
+    remember_query_with_data_dir(spec, shard_manager, registry, data_dir)
+        .await
+        .expect("remember should succeed");
+}
+
+/// Rows of the query result, rendered and sorted so runs compare equal.
+async fn run_rows(pipeline: QueryExecutionPipeline<'_>) -> Vec<String> {
+    let mut stream = pipeline
+        .execute_streaming()
+        .await
+        .expect("query should succeed")
+        .expect("stream");
+    let mut rows = Vec::new();
+    while let Some(batch) = stream.recv().await {
+        for idx in 0..batch.len() {
+            rows.push(format!("{:?}", batch.row(idx).unwrap()));
+        }
+    }
+    assert!(stream.failure().is_none());
+    rows.sort();
+    rows
+}
+
+#[tokio::test]
+async fn aggregate_query_reads_matching_view_topped_up_with_tail() {
+    let (shard_manager, registry) = setup().await;
+    let data_dir = tempdir().unwrap();
+
+    store_orders(
+        &shard_manager,
+        &registry,
+        &[(1, "eu", 10), (2, "us", 5), (3, "eu", 7)],
+    )
+    .await;
+    let (_r, mut w) = duplex(1024);
+    flush::handle(
+        &parse_command("FLUSH").unwrap(),
+        &shard_manager,
+        &registry,
+        &mut w,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+    sleep(Duration::from_millis(200)).await;
+    remember_view(&shard_manager, &registry, data_dir.path()).await;
+
+    // Events use second resolution timestamps; land the tail past the view's mark
+    sleep(Duration::from_secs(1)).await;
+    store_orders(&shard_manager, &registry, &[(4, "eu", 3), (5, "apac", 8)]).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let command = query::parse(&format!("{VIEW_QUERY} ORDER BY count DESC LIMIT 10")).unwrap();
+    let with_view = || {
+        QueryExecutionPipeline::new(&command, &shard_manager, Arc::clone(&registry))
+            .with_materialized_views(data_dir.path())
+    };
+
+    let plan = with_view().explain();
+    assert_eq!(plan[0], "plan: materialized view 'orders_by_region'");
+
+    let from_view = run_rows(with_view()).await;
+    let from_scan = run_rows(QueryExecutionPipeline::new(
+        &command,
+        &shard_manager,
+        Arc::clone(&registry),
+    ))
+    .await;
+    assert_eq!(from_view.len(), 3);
+    assert_eq!(from_view, from_scan);
+}
+
+#[tokio::test]
+async fn view_with_a_different_filter_is_not_used() {
+    let (shard_manager, registry) = setup().await;
+    let data_dir = tempdir().unwrap();
+
+    store_orders(&shard_manager, &registry, &[(1, "eu", 10)]).await;
+    remember_view(&shard_manager, &registry, data_dir.path()).await;
+
+    let command = query::parse(
+        r#"QUERY explain_orders WHERE amount > 5 COUNT, TOTAL amount, AVG amount BY region"#,
+    )
+    .unwrap();
+    let plan = QueryExecutionPipeline::new(&command, &shard_manager, Arc::clone(&registry))
+        .with_materialized_views(data_dir.path())
+        .explain();
+
+    assert_eq!(
+        plan,
+        vec![
+            "plan: shard scan".to_string(),
+            "view 'orders_by_region' not used: different filter".to_string(),
+            "pruners: xor, surf, range, temporal, enum, materialization".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn explain_command_responds_with_the_plan() {
+    let (shard_manager, registry) = setup().await;
+
+    let cmd = parse_command("EXPLAIN QUERY explain_orders COUNT BY region").unwrap();
+    let (mut reader, mut writer) = duplex(4096);
+    explain::handle(
+        &cmd,
+        &shard_manager,
+        &registry,
+        None,
+        None,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+    assert!(body.contains("plan: shard scan"), "{body}");
+}
+
+#[tokio::test]
+async fn explain_lists_disabled_pruners() {
+    let (shard_manager, registry) = setup().await;
+
+    let command =
+        query::parse("QUERY explain_orders WHERE amount > 5 DISABLE PRUNERS surf, range").unwrap();
+    let plan =
+        QueryExecutionPipeline::new(&command, &shard_manager, Arc::clone(&registry)).explain();
+
+    assert_eq!(
+        plan,
+        vec![
+            "plan: shard scan".to_string(),
+            "pruners: xor, temporal, enum, materialization".to_string(),
+            "pruners disabled: surf, range".to_string(),
+        ]
+    );
+}
+
+async fn explain_lines(
+    shard_manager: &ShardManager,
+    registry: &Arc<tokio::sync::RwLock<SchemaRegistry>>,
+    text: &str,
+) -> Vec<String> {
+    let cmd = parse_command(text).unwrap();
+    let (mut reader, mut writer) = duplex(16384);
+    explain::handle(
+        &cmd,
+        shard_manager,
+        registry,
+        None,
+        None,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+    let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+    response["results"]
+        .as_array()
+        .unwrap_or_else(|| panic!("no results: {body}"))
+        .iter()
+        .map(|line| line.as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn explain_analyze_runs_the_query_and_audits_its_projection() {
+    let (shard_manager, registry) = setup().await;
+    store_orders(
+        &shard_manager,
+        &registry,
+        &[(1, "eu", 10), (2, "us", 3), (3, "eu", 7)],
+    )
+    .await;
+
+    let lines = explain_lines(
+        &shard_manager,
+        &registry,
+        "EXPLAIN ANALYZE QUERY explain_orders WHERE amount > 5",
+    )
+    .await;
+    assert_eq!(lines[0], "plan: shard scan");
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.starts_with("analyze: 2 rows produced")),
+        "{lines:?}"
+    );
+    assert!(
+        lines.contains(&"column amount: filter, output (row work)".to_string()),
+        "{lines:?}"
+    );
+    assert!(
+        lines.contains(&"column region: output (output only)".to_string()),
+        "{lines:?}"
+    );
+    assert!(
+        !lines.iter().any(|l| l.starts_with("projection warning")),
+        "{lines:?}"
+    );
+}
+
+#[tokio::test]
+async fn explain_analyze_flags_columns_hydrated_beyond_the_request() {
+    let (shard_manager, registry) = setup().await;
+    store_orders(&shard_manager, &registry, &[(1, "eu", 10), (2, "us", 3)]).await;
+
+    // The memtable scan fills every core column, which an aggregate never asks for
+    let lines = explain_lines(
+        &shard_manager,
+        &registry,
+        "EXPLAIN ANALYZE QUERY explain_orders COUNT BY region",
+    )
+    .await;
+    assert!(
+        lines.contains(&"column region: group (row work)".to_string()),
+        "{lines:?}"
+    );
+    assert!(
+        lines.contains(
+            &"projection warning: hydrated but not requested: context_id, event_type, timestamp"
+                .to_string()
+        ),
+        "{lines:?}"
+    );
+}
+
+#[tokio::test]
+async fn explain_without_analyze_does_not_run_the_query() {
+    let (shard_manager, registry) = setup().await;
+    store_orders(&shard_manager, &registry, &[(1, "eu", 10)]).await;
+
+    let lines = explain_lines(
+        &shard_manager,
+        &registry,
+        "EXPLAIN QUERY explain_orders COUNT BY region",
+    )
+    .await;
+    assert!(!lines.iter().any(|l| l.starts_with("analyze")), "{lines:?}");
+}
@@ -0,0 +1,196 @@
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::core::{Event, EventId};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::engine::shard::message::ShardMessage;
+use crate::engine::types::ScalarValue;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{Response, StatusCode};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::{RwLock, oneshot};
+use tracing::{debug, error, warn};
+
+/// Handles `GET EVENT <id>`: a point read of a single event.
+///
+/// The id encodes the shard that generated it, so only that shard is asked; it
+/// resolves the id from its MemTables or segment index without evaluating any
+/// predicate.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::GetEvent { id } = cmd else {
+        warn!(target: "sneldb::get_event", "Invalid GetEvent command received");
+        return write_response(
+            writer,
+            renderer,
+            Response::error(StatusCode::BadRequest, "Invalid GetEvent command"),
+        )
+        .await;
+    };
+    let id = EventId::from(*id);
+
+    if auth_manager.is_some() && user_id.is_none() {
+        warn!(target: "sneldb::get_event", "Authentication required for GET EVENT command");
+        return write_response(
+            writer,
+            renderer,
+            Response::error(StatusCode::Unauthorized, "Authentication required"),
+        )
+        .await;
+    }
+
+    let Some(shard) = shard_manager
+        .all_shards()
+        .iter()
+        .find(|shard| shard.id == id.shard_id())
+    else {
+        debug!(target: "sneldb::get_event", %id, "Event id refers to an unknown shard");
+        return write_not_found(writer, renderer, id).await;
+    };
+
+    let (tx, rx) = oneshot::channel();
+    if let Err(e) = shard
+        .tx
+        .send(ShardMessage::GetEvent {
+            id,
+            registry: Arc::clone(registry),
+            response: tx,
+        })
+        .await
+    {
+        error!(target: "sneldb::get_event", shard_id = shard.id, error = %e, "GetEvent dispatch failed");
+        return write_response(
+            writer,
+            renderer,
+            Response::error(
+                StatusCode::InternalError,
+                "Failed to route GET EVENT command",
+            ),
+        )
+        .await;
+    }
+
+    let event = match rx.await {
+        Ok(Ok(Some(event))) => event,
+        Ok(Ok(None)) => return write_not_found(writer, renderer, id).await,
+        Ok(Err(e)) => {
+            error!(target: "sneldb::get_event", shard_id = shard.id, %id, error = %e, "Event lookup failed");
+            return write_response(
+                writer,
+                renderer,
+                Response::error(
+                    StatusCode::InternalError,
+                    format!("Event lookup failed: {e}"),
+                ),
+            )
+            .await;
+        }
+        Err(_) => {
+            error!(target: "sneldb::get_event", shard_id = shard.id, "GetEvent response channel dropped");
+            return write_response(
+                writer,
+                renderer,
+                Response::error(StatusCode::InternalError, "Shard did not answer GET EVENT"),
+            )
+            .await;
+        }
+    };
+
+    // Skip permission checks for bypass user
+    if let (Some(auth_mgr), Some(uid)) = (auth_manager, user_id)
+        && uid != BYPASS_USER_ID
+        && !auth_mgr.can_read(uid, &event.event_type).await
+    {
+        warn!(
+            target: "sneldb::get_event",
+            user_id = uid,
+            event_type = %event.event_type,
+            "Read permission denied"
+        );
+        return write_response(
+            writer,
+            renderer,
+            Response::error(
+                StatusCode::Forbidden,
+                format!(
+                    "Read permission denied for event type '{}'",
+                    event.event_type
+                ),
+            ),
+        )
+        .await;
+    }
+
+    write_response(writer, renderer, event_response(event)).await
+}
+
+/// Renders the event as a one-row table: the core fields followed by the payload.
+fn event_response(event: Event) -> Response {
+    let mut columns = vec![
+        ("event_id".to_string(), "Integer".to_string()),
+        ("context_id".to_string(), "String".to_string()),
+        ("event_type".to_string(), "String".to_string()),
+        ("timestamp".to_string(), "Timestamp".to_string()),
+    ];
+    let mut row = vec![
+        ScalarValue::Int64(event.id.raw() as i64),
+        ScalarValue::Utf8(event.context_id),
+        ScalarValue::Utf8(event.event_type),
+        ScalarValue::Timestamp(event.timestamp as i64),
+    ];
+    for (field, value) in event.payload {
+        columns.push((field, logical_type(&value).to_string()));
+        row.push(value);
+    }
+    Response::ok_table(columns, vec![row], 1)
+}
+
+fn logical_type(value: &ScalarValue) -> &'static str {
+    match value {
+        ScalarValue::Boolean(_) => "Boolean",
+        ScalarValue::Int64(_) => "Integer",
+        ScalarValue::Float64(_) => "Float",
+        ScalarValue::Timestamp(_) => "Timestamp",
+        ScalarValue::Decimal(..) => "Decimal",
+        ScalarValue::Date(_) => "Date",
+        ScalarValue::Time(_) => "Time",
+        ScalarValue::Uuid(_) => "Uuid",
+        ScalarValue::Binary(_) => "Binary",
+        ScalarValue::Null | ScalarValue::Utf8(_) => "String",
+    }
+}
+
+async fn write_not_found<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    renderer: &dyn Renderer,
+    id: EventId,
+) -> std::io::Result<()> {
+    write_response(
+        writer,
+        renderer,
+        Response::error(
+            StatusCode::NotFound,
+            format!(
+                "Event {id} not found; it may never have been stored or was removed by compaction"
+            ),
+        ),
+    )
+    .await
+}
+
+async fn write_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    renderer: &dyn Renderer,
+    response: Response,
+) -> std::io::Result<()> {
+    writer.write_all(&renderer.render(&response)).await?;
+    writer.flush().await
+}
@@ -0,0 +1,177 @@
+use crate::command::handlers::{flush, get_event::handle};
+use crate::command::types::Command;
+use crate::engine::core::{EventId, EventIdGenerator};
+use crate::engine::shard::manager::ShardManager;
+use crate::engine::shard::message::ShardMessage;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::{EventFactory, SchemaRegistryFactory};
+use serde_json::json;
+use tempfile::tempdir;
+use tokio::io::{AsyncReadExt, duplex};
+
+async fn get_event(
+    shard_manager: &ShardManager,
+    factory: &SchemaRegistryFactory,
+    id: EventId,
+) -> String {
+    let cmd = Command::GetEvent { id: id.raw() };
+    let (mut reader, mut writer) = duplex(4096);
+    handle(
+        &cmd,
+        shard_manager,
+        &factory.registry(),
+        None,
+        None,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+
+    let mut buf = vec![0; 4096];
+    let n = reader.read(&mut buf).await.unwrap();
+    String::from_utf8_lossy(&buf[..n]).to_string()
+}
+
+#[tokio::test]
+async fn test_get_event_reads_memtable_then_flushed_segment() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("signup", &[("plan", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut generator = EventIdGenerator::new();
+    let ids: Vec<EventId> = (0..3).map(|_| generator.next(1)).collect();
+    for (idx, id) in ids.iter().enumerate() {
+        let event = EventFactory::new()
+            .with("event_type", "signup")
+            .with("context_id", format!("user-{idx}"))
+            .with("timestamp", now)
+            .with("event_id", id.raw())
+            .with("payload", json!({ "plan": format!("plan-{idx}") }))
+            .create();
+        shard_manager.shards[1]
+            .tx
+            .send(ShardMessage::Store(event, registry.clone()))
+            .await
+            .unwrap();
+    }
+
+    let body = get_event(&shard_manager, &factory, ids[1]).await;
+    assert!(body.contains("\"status\":200"), "{body}");
+    assert!(body.contains("user-1") && body.contains("plan-1"), "{body}");
+    assert!(body.contains(&ids[1].raw().to_string()), "{body}");
+    assert!(
+        !body.contains("plan-0") && !body.contains("plan-2"),
+        "{body}"
+    );
+
+    let (_r, mut w) = duplex(1024);
+    flush::handle(
+        &Command::Flush,
+        &shard_manager,
+        &registry,
+        &mut w,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+
+    let body = get_event(&shard_manager, &factory, ids[2]).await;
+    assert!(body.contains("\"status\":200"), "{body}");
+    assert!(body.contains("user-2") && body.contains("plan-2"), "{body}");
+    assert!(body.contains("signup"), "{body}");
+}
+
+#[tokio::test]
+async fn test_get_event_returns_not_found() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+    let mut generator = EventIdGenerator::new();
+
+    // Owning shard has no such event
+    let body = get_event(&shard_manager, &factory, generator.next(0)).await;
+    assert!(body.contains("\"status\":404"), "{body}");
+    assert!(body.contains("NOT_FOUND"), "{body}");
+
+    // Id generated by a shard this node does not have
+    let body = get_event(&shard_manager, &factory, generator.next(7)).await;
+    assert!(body.contains("\"status\":404"), "{body}");
+}
+
+#[tokio::test]
+async fn test_get_event_serves_repeated_lookups_from_value_cache() {
+    use crate::engine::core::read::cache::GlobalColumnValueCache;
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("hot_entity", &[("plan", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    let id = EventIdGenerator::new().next(0);
+    let event = EventFactory::new()
+        .with("event_type", "hot_entity")
+        .with("context_id", "entity-1")
+        .with("event_id", id.raw())
+        .with("payload", json!({ "plan": "pro" }))
+        .create();
+    shard_manager.shards[0]
+        .tx
+        .send(ShardMessage::Store(event, registry.clone()))
+        .await
+        .unwrap();
+    let (_r, mut w) = duplex(1024);
+    flush::handle(
+        &Command::Flush,
+        &shard_manager,
+        &registry,
+        &mut w,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+
+    // Other tests only ever read their own segments, so sharing the cache is safe
+    let cache = GlobalColumnValueCache::instance();
+    if !cache.is_enabled() {
+        cache.resize_bytes(1 << 20);
+    }
+    let first = get_event(&shard_manager, &factory, id).await;
+    let hits_before = cache.stats().hits;
+    let second = get_event(&shard_manager, &factory, id).await;
+
+    assert!(
+        first.contains("entity-1") && first.contains("pro"),
+        "{first}"
+    );
+    assert_eq!(first, second);
+    // context_id, timestamp and plan
+    assert!(cache.stats().hits >= hits_before + 3);
+}
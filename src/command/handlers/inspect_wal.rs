@@ -0,0 +1,140 @@
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::core::{WalEntryState, WalInspection, WalInspector};
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::config::CONFIG;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{Response, StatusCode};
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::{debug, warn};
+
+/// Lists the most recent entries of one shard's WAL, decoded, to confirm an
+/// event was durably logged whether or not it reached a segment yet. Reads
+/// the log files only; the WAL writer and memtables are left alone.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::InspectWal {
+        shard_id,
+        event_type,
+        limit,
+    } = cmd
+    else {
+        let resp = Response::error(StatusCode::BadRequest, "Invalid INSPECT WAL command");
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    if let Some(auth_mgr) = auth_manager {
+        match user_id {
+            Some(uid) if uid == BYPASS_USER_ID || auth_mgr.is_admin(uid).await => {}
+            Some(uid) => {
+                warn!(target: "sneldb::inspect_wal", user_id = uid, "Admin permission denied");
+                let resp = Response::error(
+                    StatusCode::Forbidden,
+                    "Only admin users can inspect the WAL",
+                );
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+            None => {
+                let resp = Response::error(StatusCode::Unauthorized, "Authentication required");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    }
+
+    debug!(
+        target: "sneldb::inspect_wal",
+        shard_id, event_type = ?event_type, limit, "Inspecting WAL"
+    );
+
+    let Some(shard) = shard_manager.all_shards().get(*shard_id) else {
+        let resp = Response::error(
+            StatusCode::NotFound,
+            format!("Shard {} does not exist", shard_id),
+        );
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    // Archives are only written in conservative mode
+    let archive_dir = CONFIG
+        .wal
+        .conservative_mode
+        .then(|| PathBuf::from(&CONFIG.wal.archive_dir).join(format!("shard-{}", shard_id)));
+    let inspector =
+        WalInspector::new(*shard_id, shard.wal_dir.clone()).with_archive_dir(archive_dir);
+    let shard_id = *shard_id;
+    let event_type = event_type.clone();
+    let limit = *limit;
+    let resp =
+        match tokio::task::spawn_blocking(move || inspector.recent(event_type.as_deref(), limit))
+            .await
+        {
+            Ok(Ok(inspection)) => Response::ok_lines(render_lines(shard_id, &inspection, limit)),
+            Ok(Err(e)) => Response::error(
+                StatusCode::InternalError,
+                format!("Failed to read the WAL: {}", e),
+            ),
+            Err(e) => Response::error(StatusCode::InternalError, format!("Inspect failed: {}", e)),
+        };
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// A summary line, then one line per entry, oldest first, with the log it
+/// sits in, whether it is flushed yet, and the entry as JSON.
+pub fn render_lines(shard_id: usize, inspection: &WalInspection, limit: usize) -> Vec<String> {
+    let unflushed = inspection
+        .entries
+        .iter()
+        .filter(|e| e.state == WalEntryState::Unflushed)
+        .count();
+    let mut summary = format!(
+        "WAL of shard {}: {} entries ({} unflushed, {} flushed)",
+        shard_id,
+        inspection.entries.len(),
+        unflushed,
+        inspection.entries.len() - unflushed
+    );
+    if inspection.truncated {
+        summary.push_str(&format!(", older entries past LIMIT {} omitted", limit));
+    }
+    let mut lines = vec![summary];
+    if inspection.skipped_lines > 0 {
+        lines.push(format!(
+            "Skipped {} undecodable lines of live logs",
+            inspection.skipped_lines
+        ));
+    }
+    if inspection.skipped_archives > 0 {
+        lines.push(format!(
+            "Skipped {} unreadable archives",
+            inspection.skipped_archives
+        ));
+    }
+    for inspected in &inspection.entries {
+        let entry = &inspected.entry;
+        let record = json!({
+            "event_id": entry.event_id.raw(),
+            "timestamp": entry.timestamp,
+            "context_id": entry.context_id,
+            "event_type": entry.event_type,
+            "payload": entry.payload_as_json(),
+        });
+        lines.push(format!(
+            "log {:05} {}: {}",
+            inspected.log_id,
+            inspected.state.as_str(),
+            record
+        ));
+    }
+    lines
+}
@@ -0,0 +1,122 @@
+use crate::command::handlers::inspect_wal::{handle, render_lines};
+use crate::command::types::Command;
+use crate::engine::auth::AuthManager;
+use crate::engine::core::{InspectedWalEntry, WalEntryState, WalInspection};
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::WalEntryFactory;
+use serde_json::json;
+use std::io::Write;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+#[test]
+fn test_render_lines_marks_unflushed_entries() {
+    let inspected = |log_id, state, context_id: &str| InspectedWalEntry {
+        log_id,
+        state,
+        entry: WalEntryFactory::new()
+            .with("event_type", "order")
+            .with("context_id", context_id)
+            .with("timestamp", 1700000000)
+            .with("event_id", 42)
+            .with("payload", json!({ "amount": 10 }))
+            .create(),
+    };
+    let inspection = WalInspection {
+        entries: vec![
+            inspected(3, WalEntryState::Flushed, "a"),
+            inspected(4, WalEntryState::Unflushed, "b"),
+        ],
+        truncated: true,
+        skipped_lines: 1,
+        skipped_archives: 0,
+    };
+
+    assert_eq!(
+        render_lines(1, &inspection, 2),
+        vec![
+            "WAL of shard 1: 2 entries (1 unflushed, 1 flushed), older entries past LIMIT 2 omitted",
+            "Skipped 1 undecodable lines of live logs",
+            r#"log 00003 flushed: {"context_id":"a","event_id":42,"event_type":"order","payload":{"amount":10},"timestamp":1700000000}"#,
+            r#"log 00004 unflushed: {"context_id":"b","event_id":42,"event_type":"order","payload":{"amount":10},"timestamp":1700000000}"#,
+        ]
+    );
+    assert_eq!(
+        render_lines(0, &WalInspection::default(), 100),
+        vec!["WAL of shard 0: 0 entries (0 unflushed, 0 flushed)"]
+    );
+}
+
+#[tokio::test]
+async fn test_inspect_wal_requires_admin_and_reads_the_shard_wal() {
+    let base_dir = tempdir().unwrap();
+    let wal_dir = tempdir().unwrap();
+    let shard_manager = Arc::new(
+        ShardManager::new(
+            1,
+            base_dir.path().to_path_buf(),
+            wal_dir.path().to_path_buf(),
+        )
+        .await,
+    );
+    let shard_wal_dir = shard_manager.all_shards()[0].wal_dir.clone();
+    let entry = WalEntryFactory::new()
+        .with("event_type", "order")
+        .with("context_id", "ctx-logged")
+        .create();
+    let mut log = std::fs::File::create(shard_wal_dir.join("wal-00099.log")).unwrap();
+    writeln!(log, "{}", serde_json::to_string(&entry).unwrap()).unwrap();
+
+    let auth_manager = Arc::new(AuthManager::new(Arc::clone(&shard_manager)));
+    auth_manager
+        .create_user("reader".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    auth_manager
+        .create_user_with_roles(
+            "root".to_string(),
+            Some("secret".to_string()),
+            vec!["admin".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let run = |user_id: &'static str, shard_id: usize| {
+        let shard_manager = Arc::clone(&shard_manager);
+        let auth_manager = Arc::clone(&auth_manager);
+        async move {
+            let cmd = Command::InspectWal {
+                shard_id,
+                event_type: Some("order".to_string()),
+                limit: 10,
+            };
+            let mut writer = Vec::new();
+            handle(
+                &cmd,
+                &shard_manager,
+                Some(&auth_manager),
+                Some(user_id),
+                &mut writer,
+                &JsonRenderer,
+            )
+            .await
+            .unwrap();
+            String::from_utf8(writer).unwrap()
+        }
+    };
+
+    let denied = run("reader", 0).await;
+    assert!(denied.contains("Only admin users"), "got: {}", denied);
+
+    let allowed = run("root", 0).await;
+    assert!(allowed.contains("1 unflushed"), "got: {}", allowed);
+    assert!(allowed.contains("ctx-logged"), "got: {}", allowed);
+
+    let missing = run("root", 5).await;
+    assert!(
+        missing.contains("Shard 5 does not exist"),
+        "got: {}",
+        missing
+    );
+}
@@ -0,0 +1,170 @@
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::core::{ColumnReader, ZoneMeta};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::engine::types::ScalarValue;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{Response, StatusCode};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Dumps the decoded values of one column in one zone, straight from the
+/// segment files. Buffered events and query caches are never consulted.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::InspectZone {
+        event_type,
+        field,
+        shard_id,
+        segment_id,
+        zone_id,
+    } = cmd
+    else {
+        let resp = Response::error(StatusCode::BadRequest, "Invalid INSPECT ZONE command");
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    if let Some(auth_mgr) = auth_manager {
+        match user_id {
+            Some(uid) if uid == BYPASS_USER_ID || auth_mgr.is_admin(uid).await => {}
+            Some(uid) => {
+                warn!(target: "sneldb::inspect_zone", user_id = uid, "Admin permission denied");
+                let resp =
+                    Response::error(StatusCode::Forbidden, "Only admin users can inspect zones");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+            None => {
+                let resp = Response::error(StatusCode::Unauthorized, "Authentication required");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    }
+
+    debug!(
+        target: "sneldb::inspect_zone",
+        event_type, field, shard_id, segment_id, zone_id, "Inspecting zone"
+    );
+
+    let Some(uid) = registry.read().await.get_uid(event_type) else {
+        let resp = Response::error(
+            StatusCode::NotFound,
+            format!("No schema defined for event type '{}'", event_type),
+        );
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+    let Some(shard) = shard_manager.all_shards().get(*shard_id) else {
+        let resp = Response::error(
+            StatusCode::NotFound,
+            format!("Shard {} does not exist", shard_id),
+        );
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    let segment_dir = shard.base_dir.join(format!("{:05}", segment_id));
+    let field = field.clone();
+    let zone_id = *zone_id;
+    let resp = match tokio::task::spawn_blocking(move || {
+        render_lines(&segment_dir, &uid, &field, zone_id)
+    })
+    .await
+    {
+        Ok(Ok(lines)) => Response::ok_lines(lines),
+        Ok(Err(resp)) => resp,
+        Err(e) => Response::error(StatusCode::InternalError, format!("Inspect failed: {}", e)),
+    };
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// A header line with the zone's row range and physical type, the null bitmap
+/// (`1` marks a null row), then one line per row with its decoded value.
+pub fn render_lines(
+    segment_dir: &Path,
+    uid: &str,
+    field: &str,
+    zone_id: u32,
+) -> Result<Vec<String>, Response> {
+    let segment = segment_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let zones_path = segment_dir.join(format!("{}.zones", uid));
+    if !zones_path.exists() {
+        return Err(Response::error(
+            StatusCode::NotFound,
+            format!("Segment {} holds no zones for this event type", segment),
+        ));
+    }
+    let metas = ZoneMeta::load(&zones_path).map_err(|e| {
+        Response::error(
+            StatusCode::InternalError,
+            format!("Failed to load zone metadata: {}", e),
+        )
+    })?;
+    let Some(meta) = metas.iter().find(|m| m.zone_id == zone_id) else {
+        return Err(Response::error(
+            StatusCode::NotFound,
+            format!(
+                "Zone {} does not exist in segment {} ({} zones)",
+                zone_id,
+                segment,
+                metas.len()
+            ),
+        ));
+    };
+    if !segment_dir.join(format!("{}_{}.zfc", uid, field)).exists() {
+        return Err(Response::error(
+            StatusCode::NotFound,
+            format!("Column '{}' does not exist in segment {}", field, segment),
+        ));
+    }
+
+    let snapshot =
+        ColumnReader::load_for_zone_snapshot(segment_dir, &segment, uid, field, zone_id, None)
+            .map_err(|e| {
+                Response::error(
+                    StatusCode::InternalError,
+                    format!("Failed to decode zone: {}", e),
+                )
+            })?;
+    let physical_type = snapshot.physical_type();
+    let values = snapshot.into_scalar_values();
+
+    let null_bitmap: String = values
+        .iter()
+        .map(|v| {
+            if matches!(v, ScalarValue::Null) {
+                '1'
+            } else {
+                '0'
+            }
+        })
+        .collect();
+    let mut lines = Vec::with_capacity(values.len() + 2);
+    lines.push(format!(
+        "Zone {} of segment {}: rows {}..={}, {} values, physical type {:?}",
+        zone_id,
+        segment,
+        meta.start_row,
+        meta.end_row,
+        values.len(),
+        physical_type
+    ));
+    lines.push(format!("Null bitmap: {}", null_bitmap));
+    for (row, value) in values.iter().enumerate() {
+        lines.push(format!("  {}: {:?}", row, value));
+    }
+    Ok(lines)
+}
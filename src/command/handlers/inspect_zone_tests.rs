@@ -0,0 +1,157 @@
+use crate::command::handlers::inspect_zone::{handle, render_lines};
+use crate::command::types::Command;
+use crate::engine::auth::AuthManager;
+use crate::engine::core::{ZonePlan, ZoneWriter};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::{JsonRenderer, StatusCode};
+use crate::test_helpers::factories::{EventFactory, SchemaRegistryFactory};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::tempdir;
+use tokio::sync::RwLock;
+
+async fn registry() -> (Arc<RwLock<SchemaRegistry>>, String) {
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("order", &[("context_id", "string"), ("amount", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let uid = registry.read().await.get_uid("order").unwrap();
+    (registry, uid)
+}
+
+/// Writes segment `00001` with two events per zone; the third event has no amount.
+async fn write_segment(segment_dir: &Path, registry: &Arc<RwLock<SchemaRegistry>>, uid: &str) {
+    let amounts = [json!(10), json!(20), json!(null), json!(40)];
+    let events: Vec<_> = amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| {
+            EventFactory::new()
+                .with("event_type", "order")
+                .with("timestamp", (i as u64 + 1) * 10)
+                .with("payload", json!({ "amount": amount }))
+                .create()
+        })
+        .collect();
+    let plans = ZonePlan::build_all(&events, 2, uid.to_string(), 1).unwrap();
+    std::fs::create_dir_all(segment_dir).unwrap();
+    ZoneWriter::new(uid, segment_dir, Arc::clone(registry))
+        .write_all(&plans)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_render_lines_dumps_values_and_null_bitmap() {
+    let (registry, uid) = registry().await;
+    let tmp = tempdir().unwrap();
+    let segment_dir = tmp.path().join("00001");
+    write_segment(&segment_dir, &registry, &uid).await;
+
+    let lines = render_lines(&segment_dir, &uid, "amount", 1).unwrap();
+    assert!(
+        lines[0].starts_with("Zone 1 of segment 00001: rows 2..=3, 2 values"),
+        "got: {:?}",
+        lines
+    );
+    assert_eq!(lines[1], "Null bitmap: 10");
+    assert_eq!(lines[2], "  0: Null");
+    assert_eq!(lines[3], "  1: Int64(40)");
+    assert_eq!(lines.len(), 4);
+}
+
+#[tokio::test]
+async fn test_render_lines_reports_missing_zone_column_and_segment() {
+    let (registry, uid) = registry().await;
+    let tmp = tempdir().unwrap();
+    let segment_dir = tmp.path().join("00001");
+    write_segment(&segment_dir, &registry, &uid).await;
+
+    let err = render_lines(&segment_dir, &uid, "amount", 9).unwrap_err();
+    assert_eq!(err.status, StatusCode::NotFound);
+    assert_eq!(
+        err.message,
+        "Zone 9 does not exist in segment 00001 (2 zones)"
+    );
+
+    let err = render_lines(&segment_dir, &uid, "discount", 0).unwrap_err();
+    assert_eq!(err.status, StatusCode::NotFound);
+    assert_eq!(
+        err.message,
+        "Column 'discount' does not exist in segment 00001"
+    );
+
+    let err = render_lines(&tmp.path().join("00002"), &uid, "amount", 0).unwrap_err();
+    assert_eq!(err.status, StatusCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_inspect_zone_requires_admin() {
+    let (registry, uid) = registry().await;
+    let base_dir = tempdir().unwrap();
+    let wal_dir = tempdir().unwrap();
+    let shard_manager = Arc::new(
+        ShardManager::new(
+            1,
+            base_dir.path().to_path_buf(),
+            wal_dir.path().to_path_buf(),
+        )
+        .await,
+    );
+    let segment_dir = shard_manager.all_shards()[0].base_dir.join("00001");
+    write_segment(&segment_dir, &registry, &uid).await;
+
+    let auth_manager = Arc::new(AuthManager::new(Arc::clone(&shard_manager)));
+    auth_manager
+        .create_user("reader".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    auth_manager
+        .create_user_with_roles(
+            "root".to_string(),
+            Some("secret".to_string()),
+            vec!["admin".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let cmd = Command::InspectZone {
+        event_type: "order".to_string(),
+        field: "amount".to_string(),
+        shard_id: 0,
+        segment_id: 1,
+        zone_id: 0,
+    };
+    let run = |user_id: &'static str| {
+        let cmd = cmd.clone();
+        let shard_manager = Arc::clone(&shard_manager);
+        let registry = Arc::clone(&registry);
+        let auth_manager = Arc::clone(&auth_manager);
+        async move {
+            let mut writer = Vec::new();
+            handle(
+                &cmd,
+                &shard_manager,
+                &registry,
+                Some(&auth_manager),
+                Some(user_id),
+                &mut writer,
+                &JsonRenderer,
+            )
+            .await
+            .unwrap();
+            String::from_utf8(writer).unwrap()
+        }
+    };
+
+    let denied = run("reader").await;
+    assert!(denied.contains("Only admin users"), "got: {}", denied);
+
+    let allowed = run("root").await;
+    assert!(allowed.contains("Null bitmap: 00"), "got: {}", allowed);
+    assert!(allowed.contains("Int64(10)"), "got: {}", allowed);
+}
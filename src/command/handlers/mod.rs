@@ -1,30 +1,57 @@
 pub mod auth;
+pub mod batch;
 pub mod compare;
 pub mod define;
+pub mod explain;
 pub mod flush;
+pub mod get_event;
+pub mod inspect_wal;
+pub mod inspect_zone;
 pub mod kway_merger;
+pub mod payload_limits;
 pub mod permissions;
 pub mod ping;
 pub mod query;
 pub mod query_batch_stream;
+pub mod rebuild_indexes;
 pub mod remember;
 pub mod replay;
 pub mod rlte_coordinator;
 pub mod row_comparator;
+pub mod schema_catalog;
+pub mod schema_limits;
 pub mod segment_discovery;
 pub mod shard_command_builder;
 pub mod show;
+pub mod show_indexes;
+pub mod show_pinned_segments;
+pub mod show_stats;
+pub mod show_usage;
 pub mod store;
+pub mod union;
+pub mod verify_materialized;
 
 #[cfg(test)]
 mod auth_test;
 #[cfg(test)]
+mod batch_tests;
+#[cfg(test)]
 mod define_tests;
 #[cfg(test)]
+mod explain_tests;
+#[cfg(test)]
 mod flush_tests;
 #[cfg(test)]
+mod get_event_tests;
+#[cfg(test)]
+mod inspect_wal_tests;
+#[cfg(test)]
+mod inspect_zone_tests;
+#[cfg(test)]
 mod kway_merger_test;
 #[cfg(test)]
+mod payload_limits_test;
+#[cfg(test)]
 mod permissions_test;
 #[cfg(test)]
 mod ping_tests;
@@ -33,6 +60,8 @@ mod query_batch_stream_test;
 #[cfg(test)]
 mod query_tests;
 #[cfg(test)]
+mod rebuild_indexes_tests;
+#[cfg(test)]
 mod remember_tests;
 #[cfg(test)]
 mod replay_tests;
@@ -41,8 +70,22 @@ mod rlte_coordinator_test;
 #[cfg(test)]
 mod row_comparator_test;
 #[cfg(test)]
+mod schema_catalog_tests;
+#[cfg(test)]
+mod schema_limits_test;
+#[cfg(test)]
 mod segment_discovery_test;
 #[cfg(test)]
 mod shard_command_builder_test;
 #[cfg(test)]
+mod show_indexes_tests;
+#[cfg(test)]
+mod show_pinned_segments_tests;
+#[cfg(test)]
+mod show_stats_tests;
+#[cfg(test)]
+mod show_usage_tests;
+#[cfg(test)]
 mod store_tests;
+#[cfg(test)]
+mod verify_materialized_tests;
@@ -0,0 +1,81 @@
+use crate::shared::config::CONFIG;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io;
+
+/// Payload size limits applied on ingest, before an event reaches the WAL.
+#[derive(Debug, Clone, Default)]
+pub struct PayloadLimits {
+    /// Limit for event types without an override; `None` = unlimited
+    pub max_payload_bytes: Option<usize>,
+    /// Per-event-type limits that replace `max_payload_bytes`
+    pub schemas: HashMap<String, usize>,
+    /// Reject a whole batch when any of its events is oversized
+    pub strict_batches: bool,
+}
+
+impl PayloadLimits {
+    /// Limits from `[ingest]`; unlimited when the section is absent.
+    pub fn from_config() -> Self {
+        let Some(cfg) = CONFIG.ingest.as_ref() else {
+            return Self::default();
+        };
+        Self {
+            max_payload_bytes: cfg.max_payload_bytes,
+            schemas: cfg
+                .schemas
+                .iter()
+                .filter_map(|(event_type, limit)| {
+                    limit
+                        .max_payload_bytes
+                        .map(|bytes| (event_type.clone(), bytes))
+                })
+                .collect(),
+            strict_batches: cfg.strict_batches,
+        }
+    }
+
+    pub fn limit_for(&self, event_type: &str) -> Option<usize> {
+        self.schemas
+            .get(event_type)
+            .copied()
+            .or(self.max_payload_bytes)
+    }
+
+    /// Checks the serialized size of `payload` against the limit for `event_type`.
+    /// The error names both the size and the limit.
+    pub fn check(&self, event_type: &str, payload: &Value) -> Result<(), String> {
+        let Some(limit) = self.limit_for(event_type) else {
+            return Ok(());
+        };
+        let size = payload_size(payload);
+        if size > limit {
+            return Err(format!(
+                "Payload for event type '{}' is {} bytes, exceeding the limit of {} bytes",
+                event_type, size, limit
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Size of the payload as compact JSON, without materializing the encoding.
+pub fn payload_size(payload: &Value) -> usize {
+    let mut counter = ByteCounter(0);
+    // Writing into the counter cannot fail
+    let _ = serde_json::to_writer(&mut counter, payload);
+    counter.0
+}
+
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::command::handlers::payload_limits::{PayloadLimits, payload_size};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[test]
+fn test_payload_size_counts_compact_json() {
+    let payload = json!({ "id": 1, "name": "abc" });
+    assert_eq!(
+        payload_size(&payload),
+        serde_json::to_vec(&payload).unwrap().len()
+    );
+}
+
+#[test]
+fn test_schema_override_replaces_default_limit() {
+    let limits = PayloadLimits {
+        max_payload_bytes: Some(16),
+        schemas: HashMap::from([("blob".to_string(), 1024)]),
+        strict_batches: false,
+    };
+    assert_eq!(limits.limit_for("order"), Some(16));
+    assert_eq!(limits.limit_for("blob"), Some(1024));
+    assert_eq!(PayloadLimits::default().limit_for("order"), None);
+}
+
+#[test]
+fn test_check_rejects_oversized_payload_with_size_and_limit() {
+    let limits = PayloadLimits {
+        max_payload_bytes: Some(16),
+        schemas: HashMap::from([("blob".to_string(), 1024)]),
+        strict_batches: false,
+    };
+    let payload = json!({ "data": "x".repeat(32) });
+    let size = payload_size(&payload);
+
+    let err = limits.check("order", &payload).unwrap_err();
+    assert!(err.contains(&format!("{} bytes", size)), "{}", err);
+    assert!(err.contains("limit of 16 bytes"), "{}", err);
+
+    assert!(limits.check("blob", &payload).is_ok());
+    assert!(limits.check("order", &json!({ "id": 1 })).is_ok());
+}
@@ -2,7 +2,7 @@ use crate::command::types::Command;
 use crate::engine::auth::{AuthManager, BYPASS_USER_ID, PermissionSet};
 use crate::engine::schema::SchemaRegistry;
 use crate::shared::response::render::Renderer;
-use crate::shared::response::{Response, StatusCode};
+use crate::shared::response::{ErrorCode, Response, StatusCode};
 use std::sync::Arc;
 use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
@@ -68,9 +68,10 @@ pub async fn handle<W: AsyncWrite + Unpin>(
                 // Validate that event_type exists in schema registry
                 let schema_registry = registry.read().await;
                 if !schema_registry.has_schema(event_type) {
-                    let resp = Response::error(
+                    let resp = Response::error_with_code(
                         StatusCode::BadRequest,
-                        &format!("No schema defined for event type '{}'", event_type),
+                        ErrorCode::UnknownSchema,
+                        format!("No schema defined for event type '{}'", event_type),
                     );
                     writer.write_all(&renderer.render(&resp)).await?;
                     writer.flush().await?;
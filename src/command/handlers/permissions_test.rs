@@ -6,7 +6,7 @@ use crate::engine::schema::{SchemaRegistry, types::FieldType};
 use crate::engine::shard::manager::ShardManager;
 use crate::logging::init_for_tests;
 use crate::shared::response::JsonRenderer;
-use std::collections::HashMap;
+use indexmap::IndexMap;
 use std::sync::Arc;
 use tempfile::tempdir;
 use tokio::io::{AsyncReadExt, duplex};
@@ -62,7 +62,10 @@ async fn read_response<R: tokio::io::AsyncRead + Unpin>(reader: &mut R) -> Strin
 /// Helper function to convert CommandMiniSchema to MiniSchema
 fn create_mini_schema() -> MiniSchema {
     MiniSchema {
-        fields: HashMap::from([("id".to_string(), FieldType::I64)]),
+        fields: IndexMap::from([("id".to_string(), FieldType::I64)]),
+        time_field: None,
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
     }
 }
 
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use crate::command::types::Command;
+use crate::engine::core::read::flow::{QueryCancellation, QueryMemoryBudget, QueryScanStats};
 use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
 
@@ -13,6 +14,12 @@ pub struct QueryContext<'a> {
     pub shard_manager: &'a ShardManager,
     pub registry: Arc<RwLock<SchemaRegistry>>,
     pub metadata: HashMap<String, String>,
+    /// Memory budget shared by every operator of the query, on all shards.
+    pub memory: Arc<QueryMemoryBudget>,
+    /// Rows read and cache lookups of the query, summed over all shards.
+    pub stats: Arc<QueryScanStats>,
+    /// Stops the query's shard readers, set when it times out.
+    pub cancellation: Arc<QueryCancellation>,
 }
 
 impl<'a> QueryContext<'a> {
@@ -26,6 +33,9 @@ impl<'a> QueryContext<'a> {
             shard_manager,
             registry,
             metadata: HashMap::new(),
+            memory: QueryMemoryBudget::unlimited(),
+            stats: QueryScanStats::new(),
+            cancellation: QueryCancellation::new(),
         }
     }
 
@@ -33,4 +43,9 @@ impl<'a> QueryContext<'a> {
         self.metadata = metadata;
         self
     }
+
+    pub fn with_memory_budget(mut self, memory: Arc<QueryMemoryBudget>) -> Self {
+        self.memory = memory;
+        self
+    }
 }
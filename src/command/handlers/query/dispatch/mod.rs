@@ -2,6 +2,8 @@ mod sequence_streaming;
 mod streaming;
 mod traits;
 
+#[cfg(test)]
+mod sequence_streaming_test;
 #[cfg(test)]
 mod streaming_test;
 #[cfg(test)]
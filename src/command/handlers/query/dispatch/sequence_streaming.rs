@@ -7,9 +7,11 @@ use tracing::info;
 
 use crate::command::handlers::query::context::QueryContext;
 use crate::command::handlers::query::dispatch::StreamingDispatch;
-use crate::command::handlers::query::planner::{PlanOutcome, QueryPlannerBuilder};
+use crate::command::handlers::query::planner::{
+    PlanOutcome, QueryPlannerBuilder, sequence_window_bounds,
+};
 use crate::command::handlers::shard_command_builder::ShardCommandBuilder;
-use crate::command::types::{Command, EventSequence};
+use crate::command::types::{Command, CompareOp, EventSequence, Expr};
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
 use crate::engine::core::read::sequence::utils::transform_where_clause_for_event_type;
 use crate::engine::shard::message::ShardMessage;
@@ -28,19 +30,27 @@ impl SequenceStreamingDispatcher {
         Self
     }
 
-    /// Extracts event types from the sequence.
+    /// Extracts event types from the sequence, once each even when the chain
+    /// repeats a type.
     fn extract_event_types(sequence: &EventSequence) -> Vec<String> {
         let mut event_types = vec![sequence.head.event.clone()];
         for (_link, target) in &sequence.links {
-            event_types.push(target.event.clone());
+            if !event_types.contains(&target.event) {
+                event_types.push(target.event.clone());
+            }
         }
         event_types
     }
 
     /// Creates a sub-query command for a specific event type.
     ///
-    /// Transforms the WHERE clause to only include conditions relevant to this event type.
-    fn create_sub_query(base_command: &Command, event_type: &str) -> Result<Command, String> {
+    /// Transforms the WHERE clause to only include conditions relevant to this event type,
+    /// and keeps only events with a timestamp in `time_range` when given.
+    fn create_sub_query(
+        base_command: &Command,
+        event_type: &str,
+        time_range: Option<(u64, u64)>,
+    ) -> Result<Command, String> {
         let Command::Query {
             context_id: _,
             since,
@@ -50,6 +60,8 @@ impl SequenceStreamingDispatcher {
             offset: _,
             order_by,
             return_fields,
+            disabled_pruners,
+            allow_full_scan,
             ..
         } = base_command
         else {
@@ -63,6 +75,26 @@ impl SequenceStreamingDispatcher {
             .as_ref()
             .and_then(|expr| transform_where_clause_for_event_type(expr, event_type));
 
+        // Timestamp bounds let the temporal pruner skip zones outside the range
+        let transformed_where_clause = match time_range {
+            Some((lo, hi)) => {
+                let bound = |op, ts: u64| Expr::Compare {
+                    field: "timestamp".to_string(),
+                    op,
+                    value: ts.into(),
+                };
+                let range = Expr::And(
+                    Box::new(bound(CompareOp::Gte, lo)),
+                    Box::new(bound(CompareOp::Lte, hi)),
+                );
+                Some(match transformed_where_clause {
+                    Some(expr) => Expr::And(Box::new(expr), Box::new(range)),
+                    None => range,
+                })
+            }
+            None => transformed_where_clause,
+        };
+
         // Create a simple query for this event type without sequence info
         // NOTE: We don't pass LIMIT to sub-queries - they should return all matching events.
         // The LIMIT will be applied when matching sequences, not when collecting events.
@@ -83,6 +115,14 @@ impl SequenceStreamingDispatcher {
             time_bucket: None,
             group_by: None,
             event_sequence: None, // Remove sequence info for sub-queries
+            latest_per: None,
+            dedup: None,
+            omit_nulls: false,
+            with_total: false,
+            raw_strings: false,
+            disabled_pruners: disabled_pruners.clone(),
+            allow_full_scan: *allow_full_scan,
+            unnest: None,
         })
     }
 }
@@ -116,9 +156,21 @@ impl StreamingDispatch for SequenceStreamingDispatcher {
         // Dispatch sub-queries for each event type
         let mut handles_by_type = HashMap::new();
 
+        // Zones no WITHIN window can reach are left out before they are read
+        let bounds = sequence_window_bounds(ctx, &event_types).await;
+
         for event_type in &event_types {
+            let time_range = match bounds.as_ref().map(|bounds| bounds.get(event_type)) {
+                // No match can hold an event of this type
+                Some(None) => {
+                    handles_by_type.insert(event_type.clone(), Vec::new());
+                    continue;
+                }
+                range => range.flatten().copied(),
+            };
+
             // Create sub-query command
-            let sub_command = Self::create_sub_query(ctx.command, event_type)?;
+            let sub_command = Self::create_sub_query(ctx.command, event_type, time_range)?;
 
             // Create new context for sub-query
             let sub_ctx =
@@ -156,6 +208,9 @@ impl StreamingDispatch for SequenceStreamingDispatcher {
                         },
                         response: response_tx,
                         registry: Arc::clone(&ctx.registry),
+                        memory: Arc::clone(&ctx.memory),
+                        stats: Arc::clone(&ctx.stats),
+                        cancellation: Arc::clone(&ctx.cancellation),
                     })
                     .await
                     .map_err(|error| {
@@ -223,9 +278,21 @@ impl SequenceStreamingDispatcher {
         // Dispatch sub-queries for each event type and group handles
         let mut handles_by_type = HashMap::new();
 
+        // Zones no WITHIN window can reach are left out before they are read
+        let bounds = sequence_window_bounds(ctx, &event_types).await;
+
         for event_type in &event_types {
+            let time_range = match bounds.as_ref().map(|bounds| bounds.get(event_type)) {
+                // No match can hold an event of this type
+                Some(None) => {
+                    handles_by_type.insert(event_type.clone(), Vec::new());
+                    continue;
+                }
+                range => range.flatten().copied(),
+            };
+
             // Create sub-query command
-            let sub_command = Self::create_sub_query(ctx.command, event_type)?;
+            let sub_command = Self::create_sub_query(ctx.command, event_type, time_range)?;
 
             // Create new context for sub-query
             let sub_ctx =
@@ -263,6 +330,9 @@ impl SequenceStreamingDispatcher {
                         },
                         response: response_tx,
                         registry: Arc::clone(&ctx.registry),
+                        memory: Arc::clone(&ctx.memory),
+                        stats: Arc::clone(&ctx.stats),
+                        cancellation: Arc::clone(&ctx.cancellation),
                     })
                     .await
                     .map_err(|error| {
@@ -0,0 +1,118 @@
+use crate::command::handlers::flush;
+use crate::command::handlers::query::QueryExecutionPipeline;
+use crate::command::parser::commands::query::parse;
+use crate::command::types::Command;
+use crate::engine::shard::manager::ShardManager;
+use crate::engine::shard::message::ShardMessage;
+use crate::logging::init_for_tests;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::{EventFactory, SchemaRegistryFactory};
+use serde_json::json;
+use tempfile::tempdir;
+use tokio::io::duplex;
+
+/// Runs `query` and returns the rows it produced and the rows it scanned.
+async fn run(
+    query: &str,
+    shard_manager: &ShardManager,
+    factory: &SchemaRegistryFactory,
+) -> (usize, u64) {
+    let command = parse(query).expect("parse");
+    let pipeline = QueryExecutionPipeline::new(&command, shard_manager, factory.registry());
+    let mut stream = pipeline
+        .execute_streaming()
+        .await
+        .expect("query should succeed")
+        .expect("query should stream");
+    let mut rows = 0;
+    while let Some(batch) = stream.recv().await {
+        rows += batch.len();
+    }
+    (rows, pipeline.scan_stats().rows_scanned())
+}
+
+#[tokio::test]
+async fn within_window_never_loads_zones_outside_it() {
+    init_for_tests();
+
+    let factory = SchemaRegistryFactory::new();
+    for event_type in ["page_view", "order_created", "refund"] {
+        factory
+            .define_with_fields(event_type, &[("user_id", "string")])
+            .await
+            .unwrap();
+    }
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(
+        1,
+        tempdir().unwrap().into_path(),
+        tempdir().unwrap().into_path(),
+    )
+    .await;
+
+    // The test config puts each event in a zone of its own
+    for (event_type, user_id, timestamp) in [
+        ("page_view", "user1", 1000),
+        ("page_view", "user2", 1200),
+        ("page_view", "user3", 50_000),
+        ("order_created", "user1", 900),
+        ("order_created", "user1", 1500),
+        ("order_created", "user2", 3000),
+    ] {
+        let event = EventFactory::new()
+            .with("event_type", event_type)
+            .with("context_id", user_id)
+            .with("timestamp", timestamp)
+            .with("payload", json!({ "user_id": user_id }))
+            .create();
+        shard_manager.shards[0]
+            .tx
+            .send(ShardMessage::Store(event, registry.clone()))
+            .await
+            .unwrap();
+    }
+    let (_r, mut w) = duplex(1024);
+    flush::handle(
+        &Command::Flush,
+        &shard_manager,
+        &registry,
+        &mut w,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+
+    let (rows, scanned) = run(
+        "QUERY page_view FOLLOWED BY order_created LINKED BY user_id",
+        &shard_manager,
+        &factory,
+    )
+    .await;
+    assert_eq!((rows, scanned), (4, 6));
+
+    // Same matches, but the page view at 50_000 and the order at 900 cannot
+    // be part of one within 30 minutes, so their zones are never read
+    let (rows, scanned) = run(
+        "QUERY page_view FOLLOWED BY order_created LINKED BY user_id WITHIN 30m",
+        &shard_manager,
+        &factory,
+    )
+    .await;
+    assert_eq!((rows, scanned), (4, 4));
+
+    // Without any refund no match is possible, so nothing is read at all
+    let (rows, scanned) = run(
+        "QUERY page_view FOLLOWED BY refund LINKED BY user_id",
+        &shard_manager,
+        &factory,
+    )
+    .await;
+    assert_eq!((rows, scanned), (0, 3));
+    let (rows, scanned) = run(
+        "QUERY page_view FOLLOWED BY refund LINKED BY user_id WITHIN 30m",
+        &shard_manager,
+        &factory,
+    )
+    .await;
+    assert_eq!((rows, scanned), (0, 0));
+}
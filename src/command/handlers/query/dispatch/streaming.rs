@@ -54,6 +54,9 @@ impl StreamingDispatch for StreamingShardDispatcher {
                     },
                     response: response_tx,
                     registry: Arc::clone(&ctx.registry),
+                    memory: Arc::clone(&ctx.memory),
+                    stats: Arc::clone(&ctx.stats),
+                    cancellation: Arc::clone(&ctx.cancellation),
                 })
                 .await
                 .map_err(|error| {
@@ -1,16 +1,29 @@
+use std::collections::HashMap;
 use std::io;
 use std::sync::Arc;
+use std::time::Instant;
 
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::command::types::Command;
 use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::core::read::deterministic;
+use crate::engine::core::read::flow::{
+    MEMORY_LIMIT_ERROR_PREFIX, QUERY_TIMEOUT_ERROR_PREFIX, QueryCancellation, QueryMemoryBudget,
+    QueryScanStats,
+};
+use crate::engine::core::read::projection::audit as projection_audit;
+use crate::engine::query::streaming::{DETERMINISTIC_METADATA_KEY, PROFILE_METADATA_KEY};
 use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
+use crate::shared::config::CONFIG;
 use crate::shared::response::render::Renderer;
-use crate::shared::response::{Response, StatusCode};
+use crate::shared::response::{ErrorCode, Response, StatusCode};
+use crate::shared::usage::{ReadUsage, UsageLedger};
 
+use super::metrics_events::{self, QueryMetrics};
 use super::orchestrator::QueryExecutionPipeline;
+use super::planner::{COMPLEXITY_ERROR_PREFIX, ComplexityLimits, materialized_views_dir};
 use super::streaming::QueryResponseWriter;
 
 use tokio::sync::RwLock;
@@ -24,6 +37,7 @@ pub struct QueryCommandHandler<'a, W: AsyncWrite + Unpin> {
     user_id: Option<&'a str>,
     writer: &'a mut W,
     renderer: &'a dyn Renderer,
+    metrics_events: bool,
 }
 
 impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
@@ -44,9 +58,16 @@ impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
             user_id,
             writer,
             renderer,
+            metrics_events: metrics_events::enabled(),
         }
     }
 
+    /// Overrides `query.metrics_events`.
+    pub fn with_metrics_events(mut self, enabled: bool) -> Self {
+        self.metrics_events = enabled;
+        self
+    }
+
     pub async fn handle(mut self) -> io::Result<()> {
         let Command::Query {
             event_type,
@@ -55,6 +76,9 @@ impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
             limit,
             offset,
             where_clause,
+            omit_nulls,
+            with_total,
+            raw_strings,
             ..
         } = self.command
         else {
@@ -99,6 +123,37 @@ impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
             }
         }
 
+        // Pruner hints are a diagnostic, reserved for admins
+        if !self.command.disabled_pruners().is_empty()
+            && let (Some(auth_mgr), Some(uid)) = (self.auth_manager, self.user_id)
+            && uid != BYPASS_USER_ID
+            && !auth_mgr.is_admin(uid).await
+        {
+            warn!(
+                target: "sneldb::query",
+                user_id = uid,
+                "DISABLE PRUNERS denied for non-admin user"
+            );
+            return self
+                .write_error(
+                    StatusCode::Forbidden,
+                    "Only admin users can use DISABLE PRUNERS",
+                )
+                .await;
+        }
+
+        // A type that was never defined is an error; a defined type without events is an empty result
+        if let Some(missing) = self.undefined_event_type().await {
+            warn!(target: "sneldb::query", event_type = %missing, "No schema defined for event type");
+            return self
+                .write_error_with_code(
+                    StatusCode::BadRequest,
+                    ErrorCode::UnknownSchema,
+                    &format!("No schema defined for event type '{}'", missing),
+                )
+                .await;
+        }
+
         if offset.is_some() && limit.is_none() {
             warn!(target: "sneldb::query", "OFFSET specified without LIMIT");
             return self
@@ -119,14 +174,48 @@ impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
             "Dispatching Query command to pipeline"
         );
 
-        let pipeline = QueryExecutionPipeline::new(
+        let started = Instant::now();
+        let complexity_limits = ComplexityLimits::resolve(self.auth_manager, self.user_id).await;
+        let mut pipeline = QueryExecutionPipeline::new(
             self.command,
             self.shard_manager,
             Arc::clone(&self.registry),
-        );
+        )
+        .with_complexity_limits(complexity_limits)
+        .with_memory_budget(QueryMemoryBudget::from_config(self.user_id));
+        if let Some(dir) = materialized_views_dir() {
+            pipeline = pipeline.with_materialized_views(dir);
+        }
+        if let Some(timeout) = QueryCancellation::configured_timeout() {
+            pipeline = pipeline.with_timeout(timeout);
+        }
+        if projection_audit::enabled() {
+            pipeline = pipeline.with_projection_audit();
+        }
+        if deterministic::enabled() {
+            pipeline = pipeline.with_metadata(HashMap::from([(
+                DETERMINISTIC_METADATA_KEY.to_string(),
+                "true".to_string(),
+            )]));
+        } else if CONFIG
+            .query
+            .as_ref()
+            .and_then(|cfg| cfg.profile_operators)
+            .unwrap_or(false)
+        {
+            pipeline = pipeline.with_metadata(HashMap::from([(
+                PROFILE_METADATA_KEY.to_string(),
+                "true".to_string(),
+            )]));
+        }
+
+        let mut usage = ReadUsageGuard::new(self.user_id, pipeline.scan_stats());
 
         let limit_value = *limit;
         let offset_value = *offset;
+        let omit_nulls = *omit_nulls;
+        let with_total = *with_total;
+        let raw_strings = *raw_strings;
 
         match pipeline.execute_streaming().await {
             Ok(Some(stream)) => {
@@ -135,24 +224,47 @@ impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
                 // For ordered queries, limit and offset are already applied in the flow merger (OrderedStreamMerger),
                 // so we should not apply them again in QueryResponseWriter to avoid double-application.
                 // For unordered queries, limit and offset need to be applied in QueryResponseWriter.
-                let (response_limit, response_offset) = if pipeline.is_sequence_query() {
-                    (None, None) // Sequence queries handle limits at matcher level
-                } else if let Command::Query {
-                    order_by: Some(_), ..
-                } = self.command
-                {
-                    (None, None) // Offset and limit already applied in flow merger for ordered queries
+                // Sequence queries handle limits at matcher level, the latest-per merger
+                // applies them after the reduction, the dedup operator after deduplication,
+                // the unnest operator after unnesting, and the flow merger for ordered queries.
+                let limits_applied_upstream = pipeline.is_sequence_query()
+                    || pipeline.is_latest_query()
+                    || pipeline.is_dedup_query()
+                    || pipeline.is_unnest_query()
+                    || matches!(
+                        self.command,
+                        Command::Query {
+                            order_by: Some(_),
+                            ..
+                        }
+                    );
+                let (response_limit, response_offset) = if limits_applied_upstream {
+                    (None, None)
                 } else {
                     (limit_value, offset_value) // Apply limit and offset in response writer for unordered queries
                 };
+                let total = if with_total {
+                    pipeline.count_total().await
+                } else {
+                    None
+                };
                 let response_writer = QueryResponseWriter::new(
                     self.writer,
                     self.renderer,
                     stream.schema(),
                     response_limit,
                     response_offset,
-                );
-                response_writer.write(stream).await
+                )
+                // Rows unnested from one event share its event_id
+                .with_deduplication(!pipeline.is_unnest_query())
+                .with_omit_nulls(omit_nulls)
+                .with_raw_strings(raw_strings)
+                .with_total(total);
+                let rows = response_writer.write_counting_rows(stream).await?;
+                usage.completed(rows);
+                self.check_projection(&pipeline);
+                self.record_metrics(&pipeline, started, rows, false).await;
+                Ok(())
             }
             Ok(None) => {
                 // This branch is unreachable - execute_streaming() always returns Ok(Some(stream))
@@ -160,6 +272,8 @@ impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
                 unreachable!("execute_streaming() always returns Some(stream)")
             }
             Err(error) => {
+                self.check_projection(&pipeline);
+                self.record_metrics(&pipeline, started, 0, true).await;
                 // Check if this is a validation error (WHERE clause ambiguity)
                 // Validation errors should return BadRequest, not InternalError
                 if error.contains("WHERE clause validation failed")
@@ -172,6 +286,43 @@ impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
                     );
                     return self.write_error(StatusCode::BadRequest, &error).await;
                 }
+                if error.starts_with(COMPLEXITY_ERROR_PREFIX) {
+                    warn!(
+                        target: "sneldb::query",
+                        error = %error,
+                        "Query rejected by complexity limits"
+                    );
+                    return self
+                        .write_error_with_code(
+                            StatusCode::BadRequest,
+                            ErrorCode::QueryTooComplex,
+                            &error,
+                        )
+                        .await;
+                }
+                if error.starts_with(QUERY_TIMEOUT_ERROR_PREFIX) {
+                    warn!(
+                        target: "sneldb::query",
+                        error = %error,
+                        "Query timed out"
+                    );
+                    return self
+                        .write_error_with_code(
+                            StatusCode::ServiceUnavailable,
+                            ErrorCode::Timeout,
+                            &error,
+                        )
+                        .await;
+                }
+                if error.contains(MEMORY_LIMIT_ERROR_PREFIX) {
+                    return self
+                        .write_error_with_code(
+                            StatusCode::BadRequest,
+                            ErrorCode::QueryMemoryLimitExceeded,
+                            &error,
+                        )
+                        .await;
+                }
                 warn!(
                     target: "sneldb::query",
                     error = %error,
@@ -183,10 +334,126 @@ impl<'a, W: AsyncWrite + Unpin> QueryCommandHandler<'a, W> {
         }
     }
 
+    /// Warns when the readers hydrated other columns than the planner
+    /// requested, if `query.verify_projection` made the query record them.
+    fn check_projection(&self, pipeline: &QueryExecutionPipeline<'_>) {
+        let stats = pipeline.scan_stats();
+        let Some(audit) = stats.projection_audit() else {
+            return;
+        };
+        let report = audit.report();
+        if report.diverges() {
+            warn!(
+                target: "sneldb::projection",
+                query_hash = %metrics_events::query_hash(self.command),
+                not_requested = ?report.not_requested,
+                not_hydrated = ?report.not_hydrated,
+                unneeded = ?report.unneeded(),
+                "Query hydrated other columns than its projection requested"
+            );
+        }
+    }
+
+    /// Stores the query's metrics event when `query.metrics_events` is on.
+    async fn record_metrics(
+        &self,
+        pipeline: &QueryExecutionPipeline<'_>,
+        started: Instant,
+        rows_returned: usize,
+        failed: bool,
+    ) {
+        let Command::Query { event_type, .. } = self.command else {
+            return;
+        };
+        if !self.metrics_events || !metrics_events::records(event_type) {
+            return;
+        }
+        let metrics = QueryMetrics::new(
+            self.command,
+            event_type,
+            started.elapsed(),
+            &pipeline.scan_stats(),
+            rows_returned,
+            failed,
+        );
+        metrics_events::emit(self.shard_manager, &self.registry, &metrics).await;
+    }
+
+    /// The first event type the query names, head or sequence link, that has no
+    /// schema. The `*` wildcard spans whatever is defined and never misses.
+    async fn undefined_event_type(&self) -> Option<String> {
+        let Command::Query {
+            event_type,
+            event_sequence,
+            ..
+        } = self.command
+        else {
+            return None;
+        };
+        let registry = self.registry.read().await;
+        std::iter::once(event_type)
+            .chain(
+                event_sequence
+                    .iter()
+                    .flat_map(|seq| seq.links.iter().map(|(_, target)| &target.event)),
+            )
+            .find(|name| name.as_str() != "*" && !registry.has_schema(name))
+            .cloned()
+    }
+
     async fn write_error(&mut self, status: StatusCode, message: &str) -> io::Result<()> {
         let response = Response::error(status, message);
         self.writer
             .write_all(&self.renderer.render(&response))
             .await
     }
+
+    async fn write_error_with_code(
+        &mut self,
+        status: StatusCode,
+        code: ErrorCode,
+        message: &str,
+    ) -> io::Result<()> {
+        let response = Response::error_with_code(status, code, message);
+        self.writer
+            .write_all(&self.renderer.render(&response))
+            .await
+    }
+}
+
+/// Attributes a query's read work to its user when dropped, so queries that
+/// fail, or are cancelled by the client going away, are accounted too. Rows
+/// returned are only known for queries that finish writing their response.
+pub(crate) struct ReadUsageGuard {
+    user_id: Option<String>,
+    scan_stats: Arc<QueryScanStats>,
+    rows_returned: Option<usize>,
+}
+
+impl ReadUsageGuard {
+    pub(crate) fn new(user_id: Option<&str>, scan_stats: Arc<QueryScanStats>) -> Self {
+        Self {
+            user_id: user_id.map(str::to_string),
+            scan_stats,
+            rows_returned: None,
+        }
+    }
+
+    pub(crate) fn completed(&mut self, rows_returned: usize) {
+        self.rows_returned = Some(rows_returned);
+    }
+}
+
+impl Drop for ReadUsageGuard {
+    fn drop(&mut self) {
+        let usage = ReadUsage {
+            queries: 1,
+            failed_queries: u64::from(self.rows_returned.is_none()),
+            rows_scanned: self.scan_stats.rows_scanned(),
+            bytes_scanned: self.scan_stats.bytes_scanned(),
+            rows_returned: self.rows_returned.unwrap_or(0) as u64,
+            cpu_time: self.scan_stats.busy_time(),
+        };
+        UsageLedger::global().record_read(self.user_id.as_deref(), &usage);
+    }
 }
@@ -1,21 +1,30 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::command::handlers::query::context::QueryContext;
 use crate::command::handlers::query_batch_stream::QueryBatchStream;
-use crate::command::types::{Command, OrderSpec};
-use crate::engine::core::read::aggregate::partial::{AggState, GroupKey};
-use crate::engine::core::read::aggregate::plan::{AggregateOpSpec, AggregatePlan};
+use crate::command::types::{Command, OrderSpec, TimeGranularity};
+use crate::engine::core::read::aggregate::hll::HllSketch;
+use crate::engine::core::read::aggregate::ops::AggregatorImpl;
+use crate::engine::core::read::aggregate::partial::{AggState, GroupKey, snapshot_aggregator};
+use crate::engine::core::read::aggregate::plan::{
+    AggregateOpSpec, AggregatePlan, percentile_column,
+};
+use crate::engine::core::read::aggregate::tdigest::TDigest;
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
 use crate::engine::core::read::flow::{
     BatchPool, BatchReceiver, BatchSchema, BatchSender, ColumnBatch, FlowChannel, FlowMetrics,
+    QueryMemoryBudget,
 };
 use crate::engine::core::read::result::ColumnSpec;
-use crate::engine::types::ScalarValue;
+use crate::engine::types::{ScalarValue, decimal};
+use crate::shared::hash::QueryHashMap;
 use serde_json;
 use tokio::task::JoinHandle;
 
+/// Most empty buckets `FILL` adds to one result.
+const MAX_FILLED_BUCKETS: u64 = 1_000_000;
+
 /// Compares two ScalarValues for sorting.
 fn compare_scalar_values(a: &ScalarValue, b: &ScalarValue) -> Ordering {
     // Try u64 first (existing behavior)
@@ -30,6 +39,12 @@ fn compare_scalar_values(a: &ScalarValue, b: &ScalarValue) -> Ordering {
 /// partial aggregates with the same group key.
 pub struct AggregateStreamMerger {
     aggregate_plan: AggregatePlan,
+    window: ResultWindow,
+}
+
+/// The ORDER BY, LIMIT and OFFSET applied to the merged groups.
+#[derive(Debug, Clone, Default)]
+struct ResultWindow {
     limit: Option<u32>,
     offset: Option<u32>,
     order_by: Option<OrderSpec>,
@@ -40,20 +55,22 @@ impl AggregateStreamMerger {
     pub fn new(command: &Command) -> Self {
         let aggregate_plan = AggregatePlan::from_command(command)
             .expect("AggregateStreamMerger should only be used for aggregate queries");
-        let (limit, offset, order_by) = match command {
+        let window = match command {
             Command::Query {
                 limit,
                 offset,
                 order_by,
                 ..
-            } => (*limit, *offset, order_by.clone()),
-            _ => (None, None, None),
+            } => ResultWindow {
+                limit: *limit,
+                offset: *offset,
+                order_by: order_by.clone(),
+            },
+            _ => ResultWindow::default(),
         };
         Self {
             aggregate_plan,
-            limit,
-            offset,
-            order_by,
+            window,
         }
     }
 
@@ -61,7 +78,7 @@ impl AggregateStreamMerger {
     /// Groups with the same key are merged using AggState::merge.
     pub fn merge(
         &self,
-        _ctx: &QueryContext<'_>,
+        ctx: &QueryContext<'_>,
         handles: Vec<ShardFlowHandle>,
     ) -> Result<QueryBatchStream, String> {
         if handles.is_empty() {
@@ -69,9 +86,6 @@ impl AggregateStreamMerger {
         }
 
         let capacity = handles.len().max(1) * 2;
-        let metrics = FlowMetrics::new();
-        let (tx, rx) = FlowChannel::bounded(capacity, Arc::clone(&metrics));
-
         let mut schema: Option<Arc<BatchSchema>> = None;
         let mut tasks: Vec<JoinHandle<()>> = Vec::new();
         let mut receivers = Vec::new();
@@ -92,27 +106,48 @@ impl AggregateStreamMerger {
         }
 
         let schema = schema.ok_or_else(|| "no shards produced schema".to_string())?;
+        self.spawn_merge(receivers, schema, tasks, capacity, Arc::clone(&ctx.memory))
+    }
+
+    /// Finalizes a single stream of partial aggregate batches, such as the
+    /// stored state of a materialized aggregate view, with this query's
+    /// ordering and limits.
+    pub fn merge_stream(
+        &self,
+        stream: QueryBatchStream,
+        memory: Arc<QueryMemoryBudget>,
+    ) -> Result<QueryBatchStream, String> {
+        let (schema, receiver, tasks) = stream.into_parts();
+        self.spawn_merge(vec![receiver], schema, tasks, 2, memory)
+    }
+
+    fn spawn_merge(
+        &self,
+        receivers: Vec<BatchReceiver>,
+        schema: Arc<BatchSchema>,
+        mut tasks: Vec<JoinHandle<()>>,
+        capacity: usize,
+        memory: Arc<QueryMemoryBudget>,
+    ) -> Result<QueryBatchStream, String> {
+        let metrics = FlowMetrics::new();
+        let (tx, rx) = FlowChannel::bounded(capacity, Arc::clone(&metrics));
 
         // Build final output schema (with average, not sum/count for AVG)
         let output_schema = Self::build_final_output_schema(&self.aggregate_plan)?;
 
         // Spawn merger task that collects and merges aggregate batches
         let aggregate_plan = self.aggregate_plan.clone();
-        let limit = self.limit;
-        let offset = self.offset;
-        let order_by = self.order_by.clone();
+        let window = self.window.clone();
         let merger_metrics = Arc::clone(&metrics);
-        let schema_for_task = Arc::clone(&schema);
         tasks.push(tokio::spawn(async move {
             if let Err(err) = Self::merge_aggregate_batches(
                 receivers,
                 tx,
-                schema_for_task,
+                schema,
                 aggregate_plan,
-                limit,
-                offset,
-                order_by,
+                window,
                 merger_metrics,
+                memory,
             )
             .await
             {
@@ -133,19 +168,22 @@ impl AggregateStreamMerger {
         output: BatchSender,
         schema: Arc<BatchSchema>,
         aggregate_plan: AggregatePlan,
-        limit: Option<u32>,
-        offset: Option<u32>,
-        order_by: Option<OrderSpec>,
+        window: ResultWindow,
         metrics: Arc<FlowMetrics>,
+        memory: Arc<QueryMemoryBudget>,
     ) -> Result<(), String> {
         // Map to store merged groups: GroupKey -> Vec<AggState>
-        let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+        let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
 
-        // Collect all batches from all receivers
+        // Collect all batches from all receivers; they are held until every group is merged
+        let mut reservation = memory.reservation("aggregate merge");
         let mut all_batches = Vec::new();
         let receivers = receivers;
         for mut receiver in receivers {
             while let Some(batch) = receiver.recv().await {
+                reservation
+                    .try_grow(batch.approx_bytes())
+                    .map_err(|e| e.to_string())?;
                 all_batches.push(batch);
             }
         }
@@ -165,9 +203,9 @@ impl AggregateStreamMerger {
             merged_groups,
             schema,
             aggregate_plan,
-            limit,
-            offset,
-            order_by,
+            window.limit,
+            window.offset,
+            window.order_by,
             output,
             metrics,
         )
@@ -179,7 +217,7 @@ impl AggregateStreamMerger {
         batch: &ColumnBatch,
         schema: &Arc<BatchSchema>,
         aggregate_plan: &AggregatePlan,
-        merged_groups: &mut HashMap<GroupKey, Vec<AggState>>,
+        merged_groups: &mut QueryHashMap<GroupKey, Vec<AggState>>,
     ) -> Result<(), String> {
         let column_names: Vec<String> = schema.columns().iter().map(|c| c.name.clone()).collect();
         let mut column_vecs: Vec<Vec<ScalarValue>> = Vec::with_capacity(schema.column_count());
@@ -224,7 +262,7 @@ impl AggregateStreamMerger {
         aggregate_plan: &AggregatePlan,
     ) -> Result<(GroupKey, Vec<AggState>), String> {
         let mut bucket: Option<u64> = None;
-        let mut group_by_values: Vec<String> = Vec::new();
+        let mut group_by_values: Vec<Option<String>> = Vec::new();
         let mut states: Vec<AggState> = Vec::new();
 
         let mut metric_start_idx = 0;
@@ -253,7 +291,7 @@ impl AggregateStreamMerger {
                 let value = column_views[col_idx]
                     .get(row_idx)
                     .ok_or_else(|| format!("missing value for group_by field: {}", field))?;
-                group_by_values.push(Self::scalar_to_string(value));
+                group_by_values.push(Self::scalar_to_group_value(value));
             }
             metric_start_idx += group_by_fields.len();
         }
@@ -306,8 +344,14 @@ impl AggregateStreamMerger {
                         .get(row_idx)
                         .ok_or_else(|| format!("missing values for count_unique_{}", field))?;
 
-                    // Parse JSON array string back to HashSet
+                    // Parse JSON array string back to HashSet, or a {"hll": ...} object to a sketch
                     let json_str = Self::scalar_to_string(json_value);
+                    if json_str.starts_with('{') {
+                        let sketch = Self::parse_sketch(&json_str)?;
+                        states.push(AggState::CountUniqueSketch { sketch });
+                        col_idx += 1;
+                        continue;
+                    }
                     let values: std::collections::HashSet<String> =
                         if json_str.is_empty() || json_str == "[]" {
                             // Empty string or empty JSON array both represent empty HashSet
@@ -323,6 +367,20 @@ impl AggregateStreamMerger {
                     // CountUnique uses 1 column, so we advance by 1
                     col_idx += 1;
                 }
+                AggregateOpSpec::CountDistinct { field } => {
+                    // CountDistinct has one column: the {"hll": ...} sketch of its values
+                    let sketch_col_name = format!("count_distinct_{}_sketch", field);
+                    let sketch_col_idx = column_names
+                        .iter()
+                        .position(|n| n == &sketch_col_name)
+                        .ok_or_else(|| format!("missing {} column", sketch_col_name))?;
+                    let json_value = column_views[sketch_col_idx]
+                        .get(row_idx)
+                        .ok_or_else(|| format!("missing sketch for count_distinct_{}", field))?;
+                    let sketch = Self::parse_sketch(&Self::scalar_to_string(json_value))?;
+                    states.push(AggState::CountUniqueSketch { sketch });
+                    col_idx += 1;
+                }
                 _ => {
                     // Other aggregations use 1 column
                     if col_idx >= column_names.len() {
@@ -346,6 +404,15 @@ impl AggregateStreamMerger {
         Ok((group_key, states))
     }
 
+    /// Decodes a `{"hll": "<hex>"}` partial into its sketch.
+    fn parse_sketch(json_str: &str) -> Result<HllSketch, String> {
+        serde_json::from_str::<serde_json::Value>(json_str)
+            .ok()
+            .and_then(|v| v.get("hll").and_then(|h| h.as_str()).map(str::to_string))
+            .ok_or_else(|| format!("failed to parse sketch '{}'", json_str))
+            .and_then(|hex| HllSketch::from_hex(&hex))
+    }
+
     /// Converts a ScalarValue to AggState based on the aggregate operation spec.
     pub(crate) fn scalar_to_agg_state(
         value: &ScalarValue,
@@ -356,11 +423,19 @@ impl AggregateStreamMerger {
                 let count = Self::scalar_to_i64(value)?;
                 Ok(AggState::CountAll { count })
             }
+            AggregateOpSpec::CountDistinct { .. } => Err(
+                "CountDistinct should be handled directly in parse_aggregate_row, not via scalar_to_agg_state".to_string(),
+            ),
             AggregateOpSpec::CountUnique { .. } => {
                 // CountUnique is handled directly in parse_aggregate_row() since it uses JSON string
                 // This case should never be reached for CountUnique
                 Err("CountUnique should be handled directly in parse_aggregate_row, not via scalar_to_agg_state".to_string())
             }
+            AggregateOpSpec::Percentile { .. } => {
+                // The digest travels as a JSON string
+                let digest = TDigest::from_json(&Self::scalar_to_string(value))?;
+                Ok(AggState::Percentile { digest })
+            }
             AggregateOpSpec::Total { .. } => {
                 let sum = Self::scalar_to_i64(value)?;
                 Ok(AggState::Sum { sum })
@@ -411,11 +486,21 @@ impl AggregateStreamMerger {
             ScalarValue::Float64(f) => f.to_string(),
             ScalarValue::Boolean(b) => b.to_string(),
             ScalarValue::Timestamp(t) => t.to_string(),
+            ScalarValue::Decimal(m, s) => decimal::format(*m, *s),
+            ScalarValue::Date(_) | ScalarValue::Time(_) | ScalarValue::Uuid(_) => {
+                value.to_string_repr()
+            }
             ScalarValue::Null => String::new(),
             ScalarValue::Binary(_) => String::new(), // Binary not supported in aggregates
         }
     }
 
+    /// Converts a group_by column value to a group value: `None` for null,
+    /// and for the empty string a string column stores for a missing value.
+    pub(crate) fn scalar_to_group_value(value: &ScalarValue) -> Option<String> {
+        Some(Self::scalar_to_string(value)).filter(|s| !s.is_empty())
+    }
+
     /// Converts ScalarValue to (Option<i64>, Option<String>) for Min/Max.
     pub(crate) fn scalar_to_min_max(
         value: &ScalarValue,
@@ -429,9 +514,59 @@ impl AggregateStreamMerger {
         }
     }
 
+    /// For `PER <interval> FILL`, adds an empty group for every bucket between
+    /// the first and last one that has no events, for each combination of
+    /// group values, so the result is a contiguous series.
+    pub(crate) fn fill_empty_buckets(
+        merged_groups: &mut QueryHashMap<GroupKey, Vec<AggState>>,
+        aggregate_plan: &AggregatePlan,
+    ) -> Result<(), String> {
+        let Some(TimeGranularity::Interval {
+            seconds,
+            fill: true,
+            ..
+        }) = aggregate_plan.time_bucket
+        else {
+            return Ok(());
+        };
+        let buckets = merged_groups.keys().filter_map(|key| key.bucket);
+        let (Some(first), Some(last)) = (buckets.clone().min(), buckets.max()) else {
+            return Ok(());
+        };
+
+        let mut series: Vec<Vec<Option<String>>> =
+            merged_groups.keys().map(|key| key.groups.clone()).collect();
+        series.sort();
+        series.dedup();
+
+        let per_series = (last - first) / seconds + 1;
+        if per_series.saturating_mul(series.len() as u64) > MAX_FILLED_BUCKETS {
+            return Err(format!(
+                "FILL would emit more than {} buckets; use a wider interval or a narrower time range",
+                MAX_FILLED_BUCKETS
+            ));
+        }
+
+        let empty: Vec<AggState> = aggregate_plan
+            .ops
+            .iter()
+            .map(|spec| snapshot_aggregator(&AggregatorImpl::from_spec(spec)))
+            .collect();
+        for groups in series {
+            for step in 0..per_series {
+                let key = GroupKey {
+                    bucket: Some(first + step * seconds),
+                    groups: groups.clone(),
+                };
+                merged_groups.entry(key).or_insert_with(|| empty.clone());
+            }
+        }
+        Ok(())
+    }
+
     /// Emits merged groups as batches.
     pub(crate) async fn emit_merged_groups(
-        mut merged_groups: HashMap<GroupKey, Vec<AggState>>,
+        mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>>,
         _input_schema: Arc<BatchSchema>, // Input schema has sum/count for AVG
         aggregate_plan: AggregatePlan,
         limit: Option<u32>,
@@ -443,12 +578,12 @@ impl AggregateStreamMerger {
         // Build final output schema (with average, not sum/count)
         let output_schema = Self::build_final_output_schema(&aggregate_plan)?;
 
-        // Filter out empty groups first (before sorting/limiting)
+        // Filter out keyless groups first (before sorting/limiting); groups
+        // with null values stay, as their own rows
         if aggregate_plan.group_by.is_some() {
-            merged_groups.retain(|group_key, _| {
-                !group_key.groups.is_empty() && !group_key.groups.iter().any(|g| g.is_empty())
-            });
+            merged_groups.retain(|group_key, _| !group_key.groups.is_empty());
         }
+        Self::fill_empty_buckets(&mut merged_groups, &aggregate_plan)?;
 
         // Build all rows first
         let mut rows: Vec<Vec<ScalarValue>> = Vec::new();
@@ -470,8 +605,9 @@ impl AggregateStreamMerger {
                     let value = group_key
                         .groups
                         .get(i)
-                        .map(|s| ScalarValue::Utf8(s.clone()))
-                        .unwrap_or(ScalarValue::Utf8(String::new()));
+                        .cloned()
+                        .flatten()
+                        .map_or(ScalarValue::Null, ScalarValue::Utf8);
                     row.push(value);
                 }
             }
@@ -480,12 +616,27 @@ impl AggregateStreamMerger {
             for (spec, state) in aggregate_plan.ops.iter().zip(states.iter()) {
                 let value = Self::agg_state_to_scalar(state, spec)?;
                 row.push(value);
+                if let AggregateOpSpec::CountUnique { .. } = spec {
+                    let estimated = matches!(state, AggState::CountUniqueSketch { .. });
+                    row.push(ScalarValue::Boolean(estimated));
+                }
             }
 
             rows.push(row);
         }
 
-        // Sort rows based on ORDER BY or by group keys for determinism
+        // Sort by group keys (bucket first if present) for deterministic LIMIT
+        let group_start = usize::from(aggregate_plan.time_bucket.is_some());
+        let key_columns = group_start + aggregate_plan.group_by.as_ref().map_or(0, Vec::len);
+        let compare_keys = |a: &[ScalarValue], b: &[ScalarValue]| {
+            (0..key_columns)
+                .map(|idx| compare_scalar_values(&a[idx], &b[idx]))
+                .find(|cmp| *cmp != std::cmp::Ordering::Equal)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        };
+
+        // Sort rows based on ORDER BY, breaking ties by group keys so equal
+        // values do not come out in hash map order
         if let Some(order_spec) = &order_by {
             // Find the column index for the ORDER BY field
             let order_index = Self::find_column_index(&output_schema, &order_spec.field)?;
@@ -494,38 +645,11 @@ impl AggregateStreamMerger {
             // Use sort_unstable_by for better performance
             rows.sort_unstable_by(|a, b| {
                 let ord = compare_scalar_values(&a[order_index], &b[order_index]);
-                if ascending { ord } else { ord.reverse() }
+                let ord = if ascending { ord } else { ord.reverse() };
+                ord.then_with(|| compare_keys(a, b))
             });
         } else {
-            // No ORDER BY: sort by group keys for deterministic LIMIT
-            // Sort by all group_by fields (or bucket if present) for stable ordering
-            rows.sort_unstable_by(|a, b| {
-                // Compare bucket first if present
-                if aggregate_plan.time_bucket.is_some() {
-                    let bucket_cmp = compare_scalar_values(&a[0], &b[0]);
-                    if bucket_cmp != std::cmp::Ordering::Equal {
-                        return bucket_cmp;
-                    }
-                }
-
-                // Then compare group_by fields
-                let group_start = if aggregate_plan.time_bucket.is_some() {
-                    1
-                } else {
-                    0
-                };
-                if let Some(group_by_fields) = &aggregate_plan.group_by {
-                    for i in 0..group_by_fields.len() {
-                        let idx = group_start + i;
-                        let cmp = compare_scalar_values(&a[idx], &b[idx]);
-                        if cmp != std::cmp::Ordering::Equal {
-                            return cmp;
-                        }
-                    }
-                }
-
-                std::cmp::Ordering::Equal
-            });
+            rows.sort_unstable_by(|a, b| compare_keys(a, b));
         }
 
         // Apply OFFSET
@@ -630,10 +754,24 @@ impl AggregateStreamMerger {
                     name: format!("count_{}", field),
                     logical_type: "Integer".to_string(),
                 }),
-                AggregateOpSpec::CountUnique { field } => columns.push(ColumnSpec {
-                    name: format!("count_unique_{}", field),
+                AggregateOpSpec::CountUnique { field } => {
+                    columns.push(ColumnSpec {
+                        name: format!("count_unique_{}", field),
+                        logical_type: "Integer".to_string(),
+                    });
+                    columns.push(ColumnSpec {
+                        name: format!("count_unique_{}_estimated", field),
+                        logical_type: "Boolean".to_string(),
+                    });
+                }
+                AggregateOpSpec::CountDistinct { field } => columns.push(ColumnSpec {
+                    name: format!("count_distinct_{}", field),
                     logical_type: "Integer".to_string(),
                 }),
+                AggregateOpSpec::Percentile { field, percentile } => columns.push(ColumnSpec {
+                    name: percentile_column(field, *percentile),
+                    logical_type: "Float".to_string(),
+                }),
                 AggregateOpSpec::Total { field } => columns.push(ColumnSpec {
                     name: format!("total_{}", field),
                     logical_type: "Integer".to_string(),
@@ -673,6 +811,15 @@ impl AggregateStreamMerger {
             (AggregateOpSpec::CountUnique { .. }, AggState::CountUnique { values }) => {
                 Ok(ScalarValue::Int64(values.len() as i64))
             }
+            (
+                AggregateOpSpec::CountUnique { .. } | AggregateOpSpec::CountDistinct { .. },
+                AggState::CountUniqueSketch { sketch },
+            ) => Ok(ScalarValue::Int64(sketch.estimate() as i64)),
+            (AggregateOpSpec::Percentile { percentile, .. }, AggState::Percentile { digest }) => {
+                Ok(digest
+                    .quantile(percentile / 100.0)
+                    .map_or(ScalarValue::Null, ScalarValue::Float64))
+            }
             (AggregateOpSpec::Total { .. }, AggState::Sum { sum }) => Ok(ScalarValue::Int64(*sum)),
             (AggregateOpSpec::Avg { .. }, AggState::Avg { sum, count }) => {
                 let avg = if *count == 0 {
@@ -1,7 +1,6 @@
-use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::command::types::TimeGranularity;
+use crate::command::types::{OrderSpec, TimeGranularity};
 use crate::engine::core::read::aggregate::partial::{AggState, GroupKey};
 use crate::engine::core::read::aggregate::plan::{AggregateOpSpec, AggregatePlan};
 use crate::engine::core::read::flow::{
@@ -9,6 +8,7 @@ use crate::engine::core::read::flow::{
 };
 use crate::engine::core::read::result::ColumnSpec;
 use crate::engine::types::ScalarValue;
+use crate::shared::hash::QueryHashMap;
 
 use super::aggregate_stream::AggregateStreamMerger;
 
@@ -95,9 +95,11 @@ fn build_final_output_schema_count_unique() {
     );
     let schema = AggregateStreamMerger::build_final_output_schema(&plan).unwrap();
 
-    assert_eq!(schema.column_count(), 1);
+    assert_eq!(schema.column_count(), 2);
     assert_eq!(schema.columns()[0].name, "count_unique_user_id");
     assert_eq!(schema.columns()[0].logical_type, "Integer");
+    assert_eq!(schema.columns()[1].name, "count_unique_user_id_estimated");
+    assert_eq!(schema.columns()[1].logical_type, "Boolean");
 }
 
 #[test]
@@ -205,7 +207,7 @@ fn build_final_output_schema_complex() {
     );
     let schema = AggregateStreamMerger::build_final_output_schema(&plan).unwrap();
 
-    assert_eq!(schema.column_count(), 6); // bucket + country + 4 metrics
+    assert_eq!(schema.column_count(), 7); // bucket + country + 4 metrics + estimated flag
     assert_eq!(schema.columns()[0].name, "bucket");
     assert_eq!(schema.columns()[1].name, "country");
     assert_eq!(schema.columns()[2].name, "count");
@@ -656,7 +658,7 @@ fn parse_aggregate_row_scalar_count() {
         AggregateStreamMerger::parse_aggregate_row(&column_views, &column_names, 0, &plan).unwrap();
 
     assert_eq!(group_key.bucket, None);
-    assert_eq!(group_key.groups, Vec::<String>::new());
+    assert_eq!(group_key.groups, Vec::<Option<String>>::new());
     assert_eq!(states.len(), 1);
     match &states[0] {
         AggState::CountAll { count } => assert_eq!(*count, 10),
@@ -690,10 +692,44 @@ fn parse_aggregate_row_with_group_by() {
     let (group_key, states) =
         AggregateStreamMerger::parse_aggregate_row(&column_views, &column_names, 0, &plan).unwrap();
 
-    assert_eq!(group_key.groups, vec!["US"]);
+    assert_eq!(group_key.groups, vec![Some("US".to_string())]);
     assert_eq!(states.len(), 1);
 }
 
+#[test]
+fn parse_aggregate_row_reads_null_group_values() {
+    let schema = create_batch_schema(vec![
+        ("country", "String"),
+        ("plan", "String"),
+        ("count", "Integer"),
+    ]);
+    let batch = create_column_batch(
+        schema.clone(),
+        vec![vec![
+            ScalarValue::Null,
+            ScalarValue::Utf8("pro".to_string()),
+            ScalarValue::Int64(5),
+        ]],
+    );
+    let plan = create_aggregate_plan(
+        vec![AggregateOpSpec::CountAll],
+        Some(vec!["country".to_string(), "plan".to_string()]),
+        None,
+    );
+
+    let column_names: Vec<String> = schema.columns().iter().map(|c| c.name.clone()).collect();
+    let mut column_vecs: Vec<Vec<ScalarValue>> = Vec::new();
+    for col_idx in 0..schema.column_count() {
+        column_vecs.push(batch.column(col_idx).unwrap());
+    }
+    let column_views: Vec<&[ScalarValue]> = column_vecs.iter().map(|v| v.as_slice()).collect();
+
+    let (group_key, _) =
+        AggregateStreamMerger::parse_aggregate_row(&column_views, &column_names, 0, &plan).unwrap();
+
+    assert_eq!(group_key.groups, vec![None, Some("pro".to_string())]);
+}
+
 #[test]
 fn parse_aggregate_row_with_time_bucket() {
     let schema = create_batch_schema(vec![("bucket", "Timestamp"), ("count", "Integer")]);
@@ -965,7 +1001,10 @@ fn parse_aggregate_row_complex() {
         AggregateStreamMerger::parse_aggregate_row(&column_views, &column_names, 0, &plan).unwrap();
 
     assert_eq!(group_key.bucket, Some(1000));
-    assert_eq!(group_key.groups, vec!["US", "CA"]);
+    assert_eq!(
+        group_key.groups,
+        vec![Some("US".to_string()), Some("CA".to_string())]
+    );
     assert_eq!(states.len(), 3);
 }
 
@@ -1013,14 +1052,14 @@ fn merge_batch_into_groups_single_row() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     AggregateStreamMerger::merge_batch_into_groups(&batch, &schema, &plan, &mut merged_groups)
         .unwrap();
 
     assert_eq!(merged_groups.len(), 1);
     let _group_key = GroupKey {
         bucket: None,
-        groups: vec!["US".to_string()],
+        groups: vec![Some("US".to_string())],
     };
     assert!(merged_groups.contains_key(&_group_key));
 }
@@ -1041,14 +1080,14 @@ fn merge_batch_into_groups_multiple_rows_same_group() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     AggregateStreamMerger::merge_batch_into_groups(&batch, &schema, &plan, &mut merged_groups)
         .unwrap();
 
     assert_eq!(merged_groups.len(), 1);
     let group_key = GroupKey {
         bucket: None,
-        groups: vec!["US".to_string()],
+        groups: vec![Some("US".to_string())],
     };
     let states = merged_groups.get(&group_key).unwrap();
     match &states[0] {
@@ -1073,7 +1112,7 @@ fn merge_batch_into_groups_multiple_rows_different_groups() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     AggregateStreamMerger::merge_batch_into_groups(&batch, &schema, &plan, &mut merged_groups)
         .unwrap();
 
@@ -1110,14 +1149,14 @@ fn merge_batch_into_groups_merges_avg() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     AggregateStreamMerger::merge_batch_into_groups(&batch, &schema, &plan, &mut merged_groups)
         .unwrap();
 
     assert_eq!(merged_groups.len(), 1);
     let group_key = GroupKey {
         bucket: None,
-        groups: vec!["US".to_string()],
+        groups: vec![Some("US".to_string())],
     };
     let states = merged_groups.get(&group_key).unwrap();
     match &states[0] {
@@ -1158,14 +1197,14 @@ fn merge_batch_into_groups_merges_count_unique() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     AggregateStreamMerger::merge_batch_into_groups(&batch, &schema, &plan, &mut merged_groups)
         .unwrap();
 
     assert_eq!(merged_groups.len(), 1);
     let group_key = GroupKey {
         bucket: None,
-        groups: vec!["US".to_string()],
+        groups: vec![Some("US".to_string())],
     };
     let states = merged_groups.get(&group_key).unwrap();
     match &states[0] {
@@ -1185,7 +1224,7 @@ fn merge_batch_into_groups_empty_batch() {
     let batch = create_column_batch(schema.clone(), vec![]);
     let plan = create_aggregate_plan(vec![AggregateOpSpec::CountAll], None, None);
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     AggregateStreamMerger::merge_batch_into_groups(&batch, &schema, &plan, &mut merged_groups)
         .unwrap();
 
@@ -1197,7 +1236,7 @@ fn merge_batch_into_groups_empty_batch() {
 // ============================================================================
 
 #[tokio::test]
-async fn emit_merged_groups_filters_empty_groups() {
+async fn emit_merged_groups_keeps_null_groups() {
     let schema = create_batch_schema(vec![("country", "String"), ("count", "Integer")]);
     let plan = create_aggregate_plan(
         vec![AggregateOpSpec::CountAll],
@@ -1205,20 +1244,20 @@ async fn emit_merged_groups_filters_empty_groups() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     // Add valid group
     merged_groups.insert(
         GroupKey {
             bucket: None,
-            groups: vec!["US".to_string()],
+            groups: vec![Some("US".to_string())],
         },
         vec![AggState::CountAll { count: 5 }],
     );
-    // Add empty group (should be filtered)
+    // Add null group (kept as its own row)
     merged_groups.insert(
         GroupKey {
             bucket: None,
-            groups: vec![String::new()],
+            groups: vec![None],
         },
         vec![AggState::CountAll { count: 3 }],
     );
@@ -1226,7 +1265,7 @@ async fn emit_merged_groups_filters_empty_groups() {
     merged_groups.insert(
         GroupKey {
             bucket: None,
-            groups: vec!["DE".to_string()],
+            groups: vec![Some("DE".to_string())],
         },
         vec![AggState::CountAll { count: 2 }],
     );
@@ -1246,13 +1285,24 @@ async fn emit_merged_groups_filters_empty_groups() {
     .await
     .unwrap();
 
-    // Should only receive batches with non-empty groups
-    let mut total_rows = 0;
+    let mut rows = Vec::new();
     while let Some(batch) = rx.recv().await {
-        total_rows += batch.len();
+        let countries = batch.column(0).unwrap();
+        let counts = batch.column(1).unwrap();
+        for i in 0..batch.len() {
+            rows.push((countries[i].clone(), counts[i].clone()));
+        }
     }
 
-    assert_eq!(total_rows, 2); // Only US and DE, not empty group
+    // Nulls sort first
+    assert_eq!(
+        rows,
+        vec![
+            (ScalarValue::Null, ScalarValue::Int64(3)),
+            (ScalarValue::Utf8("DE".into()), ScalarValue::Int64(2)),
+            (ScalarValue::Utf8("US".into()), ScalarValue::Int64(5)),
+        ]
+    );
 }
 
 #[tokio::test]
@@ -1260,7 +1310,7 @@ async fn emit_merged_groups_scalar_no_filtering() {
     let schema = create_batch_schema(vec![("count", "Integer")]);
     let plan = create_aggregate_plan(vec![AggregateOpSpec::CountAll], None, None);
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     merged_groups.insert(
         GroupKey {
             bucket: None,
@@ -1301,12 +1351,12 @@ async fn emit_merged_groups_applies_limit() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     for (i, country) in ["US", "DE", "FR", "IT", "ES"].iter().enumerate() {
         merged_groups.insert(
             GroupKey {
                 bucket: None,
-                groups: vec![country.to_string()],
+                groups: vec![Some(country.to_string())],
             },
             vec![AggState::CountAll { count: i as i64 }],
         );
@@ -1335,6 +1385,159 @@ async fn emit_merged_groups_applies_limit() {
     assert_eq!(total_rows, 3); // Should be limited to 3 groups
 }
 
+#[test]
+fn fill_empty_buckets_completes_each_series() {
+    let plan = create_aggregate_plan(
+        vec![
+            AggregateOpSpec::CountAll,
+            AggregateOpSpec::Total {
+                field: "amount".into(),
+            },
+        ],
+        Some(vec!["country".to_string()]),
+        Some(TimeGranularity::Interval {
+            seconds: 900,
+            origin: 0,
+            fill: true,
+        }),
+    );
+
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
+    for (bucket, country) in [(900, "US"), (3_600, "US"), (1_800, "DE")] {
+        merged_groups.insert(
+            GroupKey {
+                bucket: Some(bucket),
+                groups: vec![Some(country.to_string())],
+            },
+            vec![AggState::CountAll { count: 4 }, AggState::Sum { sum: 10 }],
+        );
+    }
+
+    AggregateStreamMerger::fill_empty_buckets(&mut merged_groups, &plan).unwrap();
+
+    // Both series span 900..=3600 in 15 minute steps
+    assert_eq!(merged_groups.len(), 8);
+    for country in ["US", "DE"] {
+        for bucket in [900, 1_800, 2_700, 3_600] {
+            let key = GroupKey {
+                bucket: Some(bucket),
+                groups: vec![Some(country.to_string())],
+            };
+            assert!(merged_groups.contains_key(&key), "{} {}", country, bucket);
+        }
+    }
+    let filled = &merged_groups[&GroupKey {
+        bucket: Some(2_700),
+        groups: vec![Some("US".to_string())],
+    }];
+    assert_eq!(
+        filled,
+        &vec![AggState::CountAll { count: 0 }, AggState::Sum { sum: 0 }]
+    );
+
+    // Without FILL nothing is added
+    let mut unfilled: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
+    unfilled.insert(
+        GroupKey {
+            bucket: Some(0),
+            groups: vec![],
+        },
+        vec![AggState::CountAll { count: 1 }],
+    );
+    unfilled.insert(
+        GroupKey {
+            bucket: Some(9_000),
+            groups: vec![],
+        },
+        vec![AggState::CountAll { count: 1 }],
+    );
+    let plan = create_aggregate_plan(
+        vec![AggregateOpSpec::CountAll],
+        None,
+        Some(TimeGranularity::Interval {
+            seconds: 900,
+            origin: 0,
+            fill: false,
+        }),
+    );
+    AggregateStreamMerger::fill_empty_buckets(&mut unfilled, &plan).unwrap();
+    assert_eq!(unfilled.len(), 2);
+}
+
+#[test]
+fn fill_empty_buckets_rejects_huge_ranges() {
+    let plan = create_aggregate_plan(
+        vec![AggregateOpSpec::CountAll],
+        None,
+        Some(TimeGranularity::Interval {
+            seconds: 1,
+            origin: 0,
+            fill: true,
+        }),
+    );
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
+    for bucket in [0, 10_000_000] {
+        merged_groups.insert(
+            GroupKey {
+                bucket: Some(bucket),
+                groups: vec![],
+            },
+            vec![AggState::CountAll { count: 1 }],
+        );
+    }
+    let err = AggregateStreamMerger::fill_empty_buckets(&mut merged_groups, &plan).unwrap_err();
+    assert!(err.contains("FILL"), "{}", err);
+}
+
+#[tokio::test]
+async fn emit_merged_groups_breaks_order_by_ties_by_group_key() {
+    let schema = create_batch_schema(vec![("country", "String"), ("count", "Integer")]);
+    let plan = create_aggregate_plan(
+        vec![AggregateOpSpec::CountAll],
+        Some(vec!["country".to_string()]),
+        None,
+    );
+
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
+    for (country, count) in [("US", 2), ("FR", 1), ("DE", 2), ("IT", 2)] {
+        merged_groups.insert(
+            GroupKey {
+                bucket: None,
+                groups: vec![Some(country.to_string())],
+            },
+            vec![AggState::CountAll { count }],
+        );
+    }
+
+    let (tx, mut rx) = FlowChannel::bounded(10, FlowMetrics::new());
+
+    AggregateStreamMerger::emit_merged_groups(
+        merged_groups,
+        schema,
+        plan,
+        Some(2),
+        None,
+        Some(OrderSpec {
+            field: "count".to_string(),
+            desc: true,
+        }),
+        tx,
+        FlowMetrics::new(),
+    )
+    .await
+    .unwrap();
+
+    let mut countries = Vec::new();
+    while let Some(batch) = rx.recv().await {
+        for value in batch.column(0).unwrap() {
+            countries.push(value.as_str().unwrap().to_string());
+        }
+    }
+
+    // Tied counts come out in group key order, whatever the hash map order
+    assert_eq!(countries, vec!["DE", "IT"]);
+}
+
 #[tokio::test]
 async fn emit_merged_groups_empty_groups_returns_nothing() {
     let schema = create_batch_schema(vec![("country", "String"), ("count", "Integer")]);
@@ -1344,7 +1547,7 @@ async fn emit_merged_groups_empty_groups_returns_nothing() {
         None,
     );
 
-    let merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
 
     let (tx, mut rx) = FlowChannel::bounded(10, FlowMetrics::new());
 
@@ -1394,14 +1597,14 @@ async fn emit_merged_groups_complex_output() {
         Some(TimeGranularity::Hour),
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     let mut values = std::collections::HashSet::new();
     values.insert("user1".to_string());
     values.insert("user2".to_string());
     merged_groups.insert(
         GroupKey {
             bucket: Some(1000),
-            groups: vec!["US".to_string()],
+            groups: vec![Some("US".to_string())],
         },
         vec![
             AggState::CountAll { count: 10 },
@@ -1439,7 +1642,7 @@ async fn emit_merged_groups_complex_output() {
 
     // Verify output schema has final format (avg, not sum/count)
     let output_schema = batch.schema();
-    assert_eq!(output_schema.column_count(), 5); // bucket + country + 3 metrics
+    assert_eq!(output_schema.column_count(), 6); // bucket + country + 3 metrics + estimated flag
     assert_eq!(output_schema.columns()[0].name, "bucket");
     assert_eq!(output_schema.columns()[1].name, "country");
     assert_eq!(output_schema.columns()[2].name, "count");
@@ -1460,6 +1663,7 @@ async fn emit_merged_groups_complex_output() {
     }
     let count_unique_col = batch.column(4).unwrap();
     assert_eq!(count_unique_col[0], ScalarValue::Int64(2)); // 2 unique users
+    assert_eq!(batch.column(5).unwrap()[0], ScalarValue::Boolean(false));
 }
 
 // ============================================================================
@@ -1567,12 +1771,12 @@ fn merge_batch_into_groups_mismatched_state_lengths() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     // Insert a group with wrong number of states
     merged_groups.insert(
         GroupKey {
             bucket: None,
-            groups: vec!["US".to_string()],
+            groups: vec![Some("US".to_string())],
         },
         vec![
             AggState::CountAll { count: 3 },
@@ -1587,7 +1791,7 @@ fn merge_batch_into_groups_mismatched_state_lengths() {
     // The existing group should remain unchanged since lengths don't match
     let group_key = GroupKey {
         bucket: None,
-        groups: vec!["US".to_string()],
+        groups: vec![Some("US".to_string())],
     };
     let states = merged_groups.get(&group_key).unwrap();
     assert_eq!(states.len(), 2); // Original states preserved
@@ -1602,11 +1806,11 @@ async fn emit_merged_groups_limit_exceeds_groups() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     merged_groups.insert(
         GroupKey {
             bucket: None,
-            groups: vec!["US".to_string()],
+            groups: vec![Some("US".to_string())],
         },
         vec![AggState::CountAll { count: 5 }],
     );
@@ -1643,13 +1847,13 @@ async fn emit_merged_groups_large_batch_splits() {
         None,
     );
 
-    let mut merged_groups: HashMap<GroupKey, Vec<AggState>> = HashMap::new();
+    let mut merged_groups: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
     // Create many groups to test batch splitting
     for i in 0..100 {
         merged_groups.insert(
             GroupKey {
                 bucket: None,
-                groups: vec![format!("country_{}", i)],
+                groups: vec![Some(format!("country_{}", i))],
             },
             vec![AggState::CountAll { count: i as i64 }],
         );
@@ -1680,3 +1884,186 @@ async fn emit_merged_groups_large_batch_splits() {
     assert_eq!(total_rows, 100);
     assert!(batch_count > 0); // Should be split into multiple batches
 }
+
+#[tokio::test]
+async fn count_unique_sketch_round_trips_and_is_marked_estimated() {
+    use crate::engine::core::read::aggregate::hll::HllSketch;
+
+    let values: std::collections::HashSet<String> =
+        (0..3_000).map(|i| format!("user-{}", i)).collect();
+    let sketch = HllSketch::from_values(&values);
+    let encoded = serde_json::json!({ "hll": sketch.to_hex() }).to_string();
+
+    let schema = create_batch_schema(vec![("count_unique_user_id_values", "String")]);
+    let batch = create_column_batch(schema.clone(), vec![vec![ScalarValue::Utf8(encoded)]]);
+    let plan = create_aggregate_plan(
+        vec![AggregateOpSpec::CountUnique {
+            field: "user_id".to_string(),
+        }],
+        None,
+        None,
+    );
+    let column_names: Vec<String> = schema.columns().iter().map(|c| c.name.clone()).collect();
+    let column_vecs: Vec<Vec<ScalarValue>> = (0..schema.column_count())
+        .map(|i| batch.column(i).unwrap())
+        .collect();
+    let column_views: Vec<&[ScalarValue]> = column_vecs.iter().map(|v| v.as_slice()).collect();
+
+    let (group_key, states) =
+        AggregateStreamMerger::parse_aggregate_row(&column_views, &column_names, 0, &plan).unwrap();
+    assert_eq!(
+        states[0],
+        AggState::CountUniqueSketch {
+            sketch: sketch.clone()
+        }
+    );
+
+    let metrics = FlowMetrics::new();
+    let (tx, mut rx) = FlowChannel::bounded(16, Arc::clone(&metrics));
+    AggregateStreamMerger::emit_merged_groups(
+        QueryHashMap::from_iter([(group_key, states)]),
+        schema,
+        plan,
+        None,
+        None,
+        None,
+        tx,
+        metrics,
+    )
+    .await
+    .unwrap();
+    let batch = rx.recv().await.unwrap();
+    assert_eq!(
+        batch.column(0).unwrap()[0],
+        ScalarValue::Int64(sketch.estimate() as i64)
+    );
+    assert_eq!(batch.column(1).unwrap()[0], ScalarValue::Boolean(true));
+}
+
+#[tokio::test]
+async fn count_distinct_sketches_merge_across_shards() {
+    use crate::engine::core::read::aggregate::hll::HllSketch;
+
+    let shard_sketch = |range: std::ops::Range<usize>| {
+        let values: Vec<String> = range.map(|i| format!("user-{}", i)).collect();
+        HllSketch::from_values(&values)
+    };
+    let shards = [shard_sketch(0..4_000), shard_sketch(2_000..6_000)];
+
+    let schema = create_batch_schema(vec![("count_distinct_user_id_sketch", "String")]);
+    let plan = create_aggregate_plan(
+        vec![AggregateOpSpec::CountDistinct {
+            field: "user_id".to_string(),
+        }],
+        None,
+        None,
+    );
+    let column_names: Vec<String> = schema.columns().iter().map(|c| c.name.clone()).collect();
+
+    let mut merged: Option<(GroupKey, Vec<AggState>)> = None;
+    for sketch in &shards {
+        let encoded = serde_json::json!({ "hll": sketch.to_hex() }).to_string();
+        let column = [ScalarValue::Utf8(encoded)];
+        let column_views: Vec<&[ScalarValue]> = vec![&column];
+        let (key, states) =
+            AggregateStreamMerger::parse_aggregate_row(&column_views, &column_names, 0, &plan)
+                .unwrap();
+        match &mut merged {
+            Some((_, acc)) => acc[0].merge(&states[0]),
+            None => merged = Some((key, states)),
+        }
+    }
+    let (group_key, states) = merged.unwrap();
+    let mut expected = shards[0].clone();
+    expected.merge(&shards[1]);
+    assert_eq!(states[0], AggState::CountUniqueSketch { sketch: expected });
+
+    let metrics = FlowMetrics::new();
+    let (tx, mut rx) = FlowChannel::bounded(16, Arc::clone(&metrics));
+    AggregateStreamMerger::emit_merged_groups(
+        QueryHashMap::from_iter([(group_key, states)]),
+        schema,
+        plan,
+        None,
+        None,
+        None,
+        tx,
+        metrics,
+    )
+    .await
+    .unwrap();
+    let batch = rx.recv().await.unwrap();
+    assert_eq!(batch.schema().column_count(), 1);
+    assert_eq!(batch.schema().columns()[0].name, "count_distinct_user_id");
+    let ScalarValue::Int64(estimate) = batch.column(0).unwrap()[0] else {
+        panic!("expected an integer count");
+    };
+    assert!((estimate - 6_000).abs() < 180, "estimate {}", estimate);
+}
+
+#[tokio::test]
+async fn percentile_digests_merge_across_shards() {
+    use crate::engine::core::read::aggregate::tdigest::TDigest;
+
+    let shard_digest = |range: std::ops::Range<u32>| {
+        let mut digest = TDigest::new();
+        for i in range {
+            digest.insert(i as f64);
+        }
+        digest.to_json()
+    };
+
+    let schema = create_batch_schema(vec![("route", "String"), ("p95_latency_digest", "String")]);
+    let plan = create_aggregate_plan(
+        vec![AggregateOpSpec::Percentile {
+            field: "latency".to_string(),
+            percentile: 95.0,
+        }],
+        Some(vec!["route".to_string()]),
+        None,
+    );
+    let column_names: Vec<String> = schema.columns().iter().map(|c| c.name.clone()).collect();
+    let rows = [
+        ("/a", shard_digest(0..500)),
+        ("/a", shard_digest(500..1_000)),
+        // A shard whose group saw no numeric values
+        ("/b", TDigest::new().to_json()),
+    ];
+
+    let mut merged: QueryHashMap<GroupKey, Vec<AggState>> = QueryHashMap::default();
+    for (route, digest) in rows {
+        let route = [ScalarValue::Utf8(route.to_string())];
+        let digest = [ScalarValue::Utf8(digest)];
+        let column_views: Vec<&[ScalarValue]> = vec![&route, &digest];
+        let (key, states) =
+            AggregateStreamMerger::parse_aggregate_row(&column_views, &column_names, 0, &plan)
+                .unwrap();
+        match merged.get_mut(&key) {
+            Some(acc) => acc[0].merge(&states[0]),
+            None => {
+                merged.insert(key, states);
+            }
+        }
+    }
+
+    let metrics = FlowMetrics::new();
+    let (tx, mut rx) = FlowChannel::bounded(16, Arc::clone(&metrics));
+    AggregateStreamMerger::emit_merged_groups(merged, schema, plan, None, None, None, tx, metrics)
+        .await
+        .unwrap();
+    let batch = rx.recv().await.unwrap();
+    assert_eq!(batch.schema().columns()[1].name, "p95_latency");
+    assert_eq!(batch.schema().columns()[1].logical_type, "Float");
+
+    let routes = batch.column(0).unwrap();
+    let values = batch.column(1).unwrap();
+    for (route, value) in routes.iter().zip(values.iter()) {
+        match (route, value) {
+            (ScalarValue::Utf8(r), ScalarValue::Float64(p95)) if r == "/a" => {
+                assert!((p95 - 949.05).abs() < 5.0, "p95 {}", p95);
+            }
+            (ScalarValue::Utf8(r), ScalarValue::Null) if r == "/b" => {}
+            other => panic!("unexpected row {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use tracing::error;
+
+use crate::command::handlers::query_batch_stream::QueryBatchStream;
+use crate::engine::core::read::flow::operators::{DedupOp, DedupOpConfig};
+use crate::engine::core::read::flow::{
+    BatchPool, FlowChannel, FlowContext, FlowMetrics, FlowOperator, FlowTelemetry,
+    QueryMemoryBudget,
+};
+
+const DEDUP_BATCH_SIZE: usize = 1024;
+
+/// Runs the merged shard stream of a `DEDUP BY` query through a [`DedupOp`].
+///
+/// Shards return every matching row, so the dedup sees all copies of a key no
+/// matter which shard stored them; ordering and limits are applied by the
+/// operator after deduplication.
+pub struct DedupStreamMerger {
+    config: DedupOpConfig,
+    memory: Arc<QueryMemoryBudget>,
+}
+
+impl DedupStreamMerger {
+    pub fn new(config: DedupOpConfig) -> Self {
+        Self {
+            config,
+            memory: QueryMemoryBudget::unlimited(),
+        }
+    }
+
+    pub fn with_memory_budget(mut self, memory: Arc<QueryMemoryBudget>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Returns an error if a key, time or order column is absent from the stream.
+    pub fn merge(&self, stream: QueryBatchStream) -> Result<QueryBatchStream, String> {
+        let (schema, receiver, mut tasks) = stream.into_parts();
+        let op = DedupOp::new(self.config.clone(), Arc::clone(&schema))?;
+
+        let metrics = FlowMetrics::new();
+        let (tx, rx) = FlowChannel::bounded(2, Arc::clone(&metrics));
+        let pool = BatchPool::new(DEDUP_BATCH_SIZE)
+            .map_err(|e| format!("Failed to create batch pool: {}", e))?;
+        let ctx = Arc::new(
+            FlowContext::new(
+                DEDUP_BATCH_SIZE,
+                pool,
+                metrics,
+                None::<&str>,
+                FlowTelemetry::default(),
+            )
+            .with_memory_budget(Arc::clone(&self.memory)),
+        );
+
+        tasks.push(tokio::spawn(async move {
+            if let Err(err) = op.run(receiver, tx, ctx).await {
+                error!(
+                    target: "sneldb::query::dedup",
+                    error = %err,
+                    "DEDUP BY failed"
+                );
+            }
+        }));
+
+        Ok(QueryBatchStream::new(schema, rx, tasks))
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+
+use crate::command::handlers::query_batch_stream::QueryBatchStream;
+use crate::command::types::OrderSpec;
+use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
+use crate::engine::core::read::flow::{
+    BatchPool, BatchReceiver, BatchSchema, ColumnBatch, FlowChannel, FlowMetrics,
+    MemoryLimitExceeded, MemoryReservation, QueryMemoryBudget,
+};
+use crate::engine::core::read::sequence::ColumnarGrouper;
+use crate::engine::types::ScalarValue;
+
+use super::SequenceStreamMerger;
+
+const LATEST_BATCH_SIZE: usize = 1024;
+/// Label under which all matched rows are grouped; the grouper keys rows by event type.
+const LATEST_GROUP: &str = "latest";
+/// Event ids grow with ingest order, so they resolve rows sharing a timestamp.
+const LATEST_TIEBREAK_FIELD: &str = "event_id";
+
+/// Reduces the matched rows of a query to the most recent row per key
+/// (`LATEST PER key`).
+///
+/// Rows are grouped by key with the sequence [`ColumnarGrouper`] and ordered by
+/// the time field, with ties resolved by `event_id`. The last row of each group
+/// is emitted unchanged; rows without a key value are dropped. Winners are
+/// returned in arrival order unless an `ORDER BY` is given, and `LIMIT`/`OFFSET`
+/// apply to the reduced rows. Collected rows count against the query's memory
+/// budget.
+pub struct LatestStreamMerger {
+    key_field: String,
+    time_field: String,
+    order_by: Option<OrderSpec>,
+    limit: Option<usize>,
+    offset: usize,
+    memory: Arc<QueryMemoryBudget>,
+}
+
+impl LatestStreamMerger {
+    pub fn new(
+        key_field: String,
+        time_field: String,
+        order_by: Option<OrderSpec>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Self {
+        Self {
+            key_field,
+            time_field,
+            order_by,
+            limit: limit.map(|value| value as usize),
+            offset: offset.unwrap_or(0) as usize,
+            memory: QueryMemoryBudget::unlimited(),
+        }
+    }
+
+    pub fn with_memory_budget(mut self, memory: Arc<QueryMemoryBudget>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Drains every shard stream, keeps the latest row per key and streams the
+    /// survivors. Returns an error if schemas diverge or a referenced column is
+    /// absent from the stream.
+    pub fn merge(&self, handles: Vec<ShardFlowHandle>) -> Result<QueryBatchStream, String> {
+        if handles.is_empty() {
+            return Err("no shard handles".to_string());
+        }
+
+        let mut schema: Option<Arc<BatchSchema>> = None;
+        let mut tasks: Vec<JoinHandle<()>> = Vec::new();
+        let mut receivers = Vec::with_capacity(handles.len());
+
+        for handle in handles {
+            let (receiver, handle_schema, mut handle_tasks) = handle.into_parts();
+
+            if let Some(existing) = &schema {
+                if !existing.is_compatible_with(&handle_schema) {
+                    return Err("stream schemas differ across shards".to_string());
+                }
+            } else {
+                schema = Some(Arc::clone(&handle_schema));
+            }
+
+            tasks.append(&mut handle_tasks);
+            receivers.push(receiver);
+        }
+
+        let schema = schema.ok_or_else(|| "no shards produced schema".to_string())?;
+        let key_index = column_index(&schema, &self.key_field, "LATEST PER")?;
+        column_index(&schema, &self.time_field, "LATEST PER time")?;
+        let order = match &self.order_by {
+            Some(order) => Some((column_index(&schema, &order.field, "order by")?, order.desc)),
+            None => None,
+        };
+
+        let metrics = FlowMetrics::new();
+        let (tx, rx) = FlowChannel::bounded(2, metrics);
+        let pool = BatchPool::new(LATEST_BATCH_SIZE)
+            .map_err(|e| format!("Failed to create batch pool: {}", e))?;
+
+        let reducer = LatestReducer {
+            key_field: self.key_field.clone(),
+            time_field: self.time_field.clone(),
+            key_index,
+            order,
+            limit: self.limit,
+            offset: self.offset,
+        };
+        let task_schema = Arc::clone(&schema);
+        let memory = Arc::clone(&self.memory);
+        tasks.push(tokio::spawn(async move {
+            let mut reservation = memory.reservation("LATEST PER");
+            let rows = match collect_batches(receivers, &mut reservation)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|batches| reducer.latest_rows(&batches, &task_schema))
+            {
+                Ok(rows) => rows,
+                Err(err) => {
+                    error!(
+                        target: "sneldb::query::latest",
+                        error = %err,
+                        "LATEST PER reduction failed"
+                    );
+                    return;
+                }
+            };
+
+            debug!(
+                target: "sneldb::query::latest",
+                key_field = %reducer.key_field,
+                row_count = rows.len(),
+                "Streaming latest rows"
+            );
+
+            let mut builder = pool.acquire(Arc::clone(&task_schema));
+            for row in rows {
+                if builder.push_row(&row).is_err() {
+                    error!(target: "sneldb::query::latest", "Failed to push row to builder");
+                    return;
+                }
+                if builder.is_full() {
+                    let full =
+                        std::mem::replace(&mut builder, pool.acquire(Arc::clone(&task_schema)));
+                    match full.finish() {
+                        Ok(batch) => {
+                            if tx.send(Arc::new(batch)).await.is_err() {
+                                return;
+                            }
+                        }
+                        Err(err) => {
+                            error!(
+                                target: "sneldb::query::latest",
+                                error = %err,
+                                "Failed to finish batch"
+                            );
+                            return;
+                        }
+                    }
+                }
+            }
+            if builder.len() > 0
+                && let Ok(batch) = builder.finish()
+            {
+                let _ = tx.send(Arc::new(batch)).await;
+            }
+        }));
+
+        Ok(QueryBatchStream::new(schema, rx, tasks))
+    }
+}
+
+struct LatestReducer {
+    key_field: String,
+    time_field: String,
+    key_index: usize,
+    order: Option<(usize, bool)>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+impl LatestReducer {
+    /// Picks the latest row per key, then applies ordering, `OFFSET` and `LIMIT`.
+    fn latest_rows(
+        &self,
+        batches: &[Arc<ColumnBatch>],
+        schema: &Arc<BatchSchema>,
+    ) -> Result<Vec<Vec<ScalarValue>>, String> {
+        let with_schema = batches
+            .iter()
+            .map(|batch| (Arc::clone(batch), Arc::clone(schema)))
+            .collect();
+        let zones =
+            SequenceStreamMerger::batches_to_zones(with_schema, LATEST_GROUP, &self.time_field)?;
+        if zones.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut zones_by_type = HashMap::with_capacity(1);
+        zones_by_type.insert(LATEST_GROUP.to_string(), zones);
+
+        let grouper = ColumnarGrouper::new(self.key_field.clone(), self.time_field.clone())
+            .with_tiebreak_field(LATEST_TIEBREAK_FIELD);
+        let groups = grouper.group_zones_by_link_field(&zones_by_type);
+
+        // Rows within a group are sorted oldest first, so the winner is the last one.
+        // Zone row indices follow batch order and map back to the source rows.
+        let mut winners: Vec<usize> = groups
+            .values()
+            .filter_map(|group| group.rows_by_type.get(LATEST_GROUP)?.last())
+            .map(|row| row.row_idx)
+            .collect();
+        winners.sort_unstable();
+
+        let mut batch_starts = Vec::with_capacity(batches.len());
+        let mut total = 0usize;
+        for batch in batches {
+            batch_starts.push(total);
+            total += batch.len();
+        }
+
+        let mut rows = Vec::with_capacity(winners.len());
+        for global_idx in winners {
+            let batch_idx = batch_starts.partition_point(|start| *start <= global_idx) - 1;
+            let row = batches[batch_idx]
+                .row(global_idx - batch_starts[batch_idx])
+                .map_err(|e| e.to_string())?;
+            if matches!(row[self.key_index], ScalarValue::Null) {
+                continue;
+            }
+            rows.push(row);
+        }
+
+        if let Some((order_index, desc)) = self.order {
+            rows.sort_by(|a, b| {
+                let ord = a[order_index].compare(&b[order_index]);
+                if desc { ord.reverse() } else { ord }
+            });
+        }
+
+        Ok(rows
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+}
+
+fn column_index(schema: &BatchSchema, field: &str, role: &str) -> Result<usize, String> {
+    schema
+        .columns()
+        .iter()
+        .position(|col| col.name == field)
+        .ok_or_else(|| format!("{} field '{}' not found in stream schema", role, field))
+}
+
+async fn collect_batches(
+    receivers: Vec<BatchReceiver>,
+    reservation: &mut MemoryReservation,
+) -> Result<Vec<Arc<ColumnBatch>>, MemoryLimitExceeded> {
+    let mut batches = Vec::new();
+    for mut receiver in receivers {
+        while let Some(batch) = receiver.recv().await {
+            if !batch.is_empty() {
+                reservation.try_grow(batch.approx_bytes())?;
+                batches.push(batch);
+            }
+        }
+    }
+    Ok(batches)
+}
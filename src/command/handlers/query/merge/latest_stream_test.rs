@@ -0,0 +1,172 @@
+use crate::command::handlers::query::merge::latest_stream::LatestStreamMerger;
+use crate::command::types::OrderSpec;
+use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
+use crate::engine::core::read::flow::{BatchPool, BatchSchema, FlowChannel, FlowMetrics};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::types::ScalarValue;
+use serde_json::json;
+use std::sync::Arc;
+
+fn sample_schema() -> Arc<BatchSchema> {
+    let column = |name: &str, logical_type: &str| ColumnSpec {
+        name: name.to_string(),
+        logical_type: logical_type.to_string(),
+    };
+    Arc::new(
+        BatchSchema::new(vec![
+            column("user_id", "String"),
+            column("timestamp", "Timestamp"),
+            column("event_id", "Integer"),
+            column("plan", "String"),
+        ])
+        .expect("schema"),
+    )
+}
+
+fn build_handle(schema: Arc<BatchSchema>, rows: Vec<Vec<serde_json::Value>>) -> ShardFlowHandle {
+    let metrics = FlowMetrics::new();
+    let (tx, rx) = FlowChannel::bounded(16, Arc::clone(&metrics));
+
+    let pool = BatchPool::new(16).expect("batch pool");
+    let mut builder = pool.acquire(Arc::clone(&schema));
+    for row in rows.into_iter() {
+        let scalar_row: Vec<ScalarValue> = row.into_iter().map(ScalarValue::from).collect();
+        builder.push_row(&scalar_row).expect("push row");
+    }
+    let batch = builder.finish().expect("finish batch");
+
+    let send_task = tokio::spawn(async move {
+        let _ = tx.send(Arc::new(batch)).await;
+    });
+
+    ShardFlowHandle::new(rx, schema, vec![send_task])
+}
+
+fn sample_handles() -> Vec<ShardFlowHandle> {
+    let schema = sample_schema();
+    vec![
+        build_handle(
+            Arc::clone(&schema),
+            vec![
+                vec![json!("u1"), json!(100), json!(1), json!("free")],
+                vec![json!("u1"), json!(200), json!(5), json!("pro")],
+                vec![json!("u2"), json!(100), json!(2), json!("free")],
+            ],
+        ),
+        build_handle(
+            Arc::clone(&schema),
+            vec![
+                vec![json!("u1"), json!(200), json!(4), json!("team")],
+                vec![json!("u3"), json!(50), json!(3), json!("solo")],
+            ],
+        ),
+    ]
+}
+
+async fn collect(
+    merger: LatestStreamMerger,
+    handles: Vec<ShardFlowHandle>,
+) -> Vec<(String, String)> {
+    let mut stream = merger.merge(handles).expect("stream created");
+    let mut observed = Vec::new();
+    while let Some(batch) = stream.recv().await {
+        for row in 0..batch.len() {
+            let user = batch.column(0).unwrap()[row]
+                .as_str()
+                .expect("user")
+                .to_string();
+            let plan = batch.column(3).unwrap()[row]
+                .as_str()
+                .expect("plan")
+                .to_string();
+            observed.push((user, plan));
+        }
+    }
+    observed
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(user, plan)| (user.to_string(), plan.to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn latest_merger_keeps_latest_row_per_key() {
+    let merger = LatestStreamMerger::new(
+        "user_id".to_string(),
+        "timestamp".to_string(),
+        None,
+        None,
+        None,
+    );
+
+    let observed = collect(merger, sample_handles()).await;
+
+    // u1 has a timestamp tie at 200, resolved by the higher event_id;
+    // u2 and u3 have a single event and pass through unchanged.
+    assert_eq!(
+        observed,
+        pairs(&[("u1", "pro"), ("u2", "free"), ("u3", "solo")])
+    );
+}
+
+#[tokio::test]
+async fn latest_merger_orders_and_limits_after_reduction() {
+    let merger = LatestStreamMerger::new(
+        "user_id".to_string(),
+        "timestamp".to_string(),
+        Some(OrderSpec {
+            field: "timestamp".to_string(),
+            desc: false,
+        }),
+        Some(2),
+        Some(1),
+    );
+
+    let observed = collect(merger, sample_handles()).await;
+
+    assert_eq!(observed, pairs(&[("u2", "free"), ("u1", "pro")]));
+}
+
+#[tokio::test]
+async fn latest_merger_skips_rows_without_key() {
+    let schema = sample_schema();
+    let handle = build_handle(
+        Arc::clone(&schema),
+        vec![
+            vec![json!(null), json!(300), json!(7), json!("ghost")],
+            vec![json!("u1"), json!(100), json!(1), json!("free")],
+        ],
+    );
+    let merger = LatestStreamMerger::new(
+        "user_id".to_string(),
+        "timestamp".to_string(),
+        None,
+        None,
+        None,
+    );
+
+    let observed = collect(merger, vec![handle]).await;
+
+    assert_eq!(observed, pairs(&[("u1", "free")]));
+}
+
+#[tokio::test]
+async fn latest_merger_rejects_missing_key_field() {
+    let merger = LatestStreamMerger::new(
+        "account_id".to_string(),
+        "timestamp".to_string(),
+        None,
+        None,
+        None,
+    );
+
+    let result = merger.merge(sample_handles());
+
+    match result {
+        Err(message) => assert!(message.contains("account_id")),
+        Ok(_) => panic!("expected missing key field error"),
+    }
+}
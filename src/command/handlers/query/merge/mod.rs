@@ -1,16 +1,24 @@
 pub mod aggregate_stream;
+mod dedup_stream;
+mod latest_stream;
 mod sequence_stream;
 mod stream_merger;
 mod streaming;
+mod unnest_stream;
 
 #[cfg(test)]
 mod aggregate_stream_test;
 #[cfg(test)]
+mod latest_stream_test;
+#[cfg(test)]
 mod sequence_stream_test;
 #[cfg(test)]
 mod stream_merger_test;
 #[cfg(test)]
 mod streaming_test;
 
+pub use dedup_stream::DedupStreamMerger;
+pub use latest_stream::LatestStreamMerger;
 pub use sequence_stream::SequenceStreamMerger;
 pub use stream_merger::StreamMergerKind;
+pub use unnest_stream::UnnestStreamMerger;
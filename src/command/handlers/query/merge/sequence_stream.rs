@@ -9,7 +9,8 @@ use crate::command::types::{Command, EventSequence};
 use crate::engine::core::read::cache::DecompressedBlock;
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
 use crate::engine::core::read::flow::{
-    BatchPool, BatchSchema, ColumnBatch, FlowChannel, FlowMetrics,
+    BatchPool, BatchSchema, ColumnBatch, FlowChannel, FlowMetrics, MemoryReservation,
+    QueryMemoryBudget,
 };
 use crate::engine::core::read::result::ColumnSpec;
 use crate::engine::core::read::sequence::utils::scalar_to_string;
@@ -38,7 +39,9 @@ impl SequenceStreamMerger {
     ) -> Self {
         let mut event_types = vec![event_sequence.head.event.clone()];
         for (_link, target) in &event_sequence.links {
-            event_types.push(target.event.clone());
+            if !event_types.contains(&target.event) {
+                event_types.push(target.event.clone());
+            }
         }
 
         Self {
@@ -63,8 +66,11 @@ impl SequenceStreamMerger {
             "Starting sequence stream merge"
         );
 
-        // Step 1: Collect batches from all streams and convert to zones grouped by event type
-        let zones_by_type = self.collect_zones_by_type(handles_by_type).await?;
+        // Step 1: Collect batches from all streams and convert to zones grouped by event type.
+        // The reservations account for the collected rows until matching is done.
+        let (zones_by_type, _reservations) = self
+            .collect_zones_by_type(handles_by_type, &ctx.memory)
+            .await?;
 
         // Step 2: Group zones by link_field using columnar grouper
         let grouper =
@@ -151,14 +157,17 @@ impl SequenceStreamMerger {
     async fn collect_zones_by_type(
         &self,
         handles_by_type: HashMap<String, Vec<ShardFlowHandle>>,
-    ) -> Result<HashMap<String, Vec<CandidateZone>>, String> {
+        memory: &Arc<QueryMemoryBudget>,
+    ) -> Result<(HashMap<String, Vec<CandidateZone>>, Vec<MemoryReservation>), String> {
         // Process all event types in parallel
         let mut tasks = Vec::new();
 
         for (event_type, handles) in handles_by_type {
             let event_type_clone = event_type.clone();
+            let memory = Arc::clone(memory);
             let task = tokio::spawn(async move {
                 let mut all_batches = Vec::new();
+                let mut reservations = Vec::new();
 
                 // Collect batches from all handles for this event type in parallel
                 // Keep handle_tasks alive to prevent channel from closing
@@ -172,13 +181,17 @@ impl SequenceStreamMerger {
                     handle_tasks_vec.push(handle_tasks);
 
                     // Spawn task to collect batches from this handle
+                    let mut reservation = memory.reservation("sequence join");
                     let collect_task = tokio::spawn(async move {
                         let mut batches = Vec::new();
                         let mut receiver = receiver;
                         while let Some(batch) = receiver.recv().await {
+                            reservation
+                                .try_grow(batch.approx_bytes())
+                                .map_err(|e| e.to_string())?;
                             batches.push((batch, Arc::clone(&schema)));
                         }
-                        batches
+                        Ok::<_, String>((batches, reservation))
                     });
                     collect_tasks.push(collect_task);
                 }
@@ -186,9 +199,11 @@ impl SequenceStreamMerger {
                 // Wait for all collection tasks to complete
                 for task in collect_tasks {
                     match task.await {
-                        Ok(mut batches) => {
+                        Ok(Ok((mut batches, reservation))) => {
                             all_batches.append(&mut batches);
+                            reservations.push(reservation);
                         }
+                        Ok(Err(e)) => return Err(e),
                         Err(e) => {
                             return Err(format!(
                                 "Failed to collect batches for {}: {}",
@@ -206,18 +221,24 @@ impl SequenceStreamMerger {
                     }
                 }
 
-                Ok((event_type_clone, all_batches))
+                Ok((event_type_clone, all_batches, reservations))
             });
             tasks.push(task);
         }
 
         // Wait for all event types to be processed
         let mut zones_by_type: HashMap<String, Vec<CandidateZone>> = HashMap::new();
+        let mut all_reservations = Vec::new();
         for task in tasks {
             match task.await {
-                Ok(Ok((event_type, batches))) => {
+                Ok(Ok((event_type, batches, mut reservations))) => {
+                    all_reservations.append(&mut reservations);
                     if !batches.is_empty() {
-                        let zones = self.batches_to_zones(batches, &event_type)?;
+                        let zones = Self::batches_to_zones(
+                            batches,
+                            &event_type,
+                            &self.sequence_time_field,
+                        )?;
                         zones_by_type.insert(event_type, zones);
                     }
                 }
@@ -226,17 +247,19 @@ impl SequenceStreamMerger {
             }
         }
 
-        Ok(zones_by_type)
+        Ok((zones_by_type, all_reservations))
     }
 
     /// Converts batches to CandidateZones with columnar data format.
     ///
     /// This is optimized to work directly with columnar data without materializing rows.
-    /// It merges multiple batches into a single zone per event type.
-    fn batches_to_zones(
-        &self,
+    /// It merges multiple batches into a single zone per event type, keeping rows in
+    /// batch order so row indices map back to the source batches. `time_field` is
+    /// stored as typed i64 alongside the timestamp columns.
+    pub(super) fn batches_to_zones(
         batches: Vec<(Arc<ColumnBatch>, Arc<BatchSchema>)>,
         event_type: &str,
+        time_field: &str,
     ) -> Result<Vec<CandidateZone>, String> {
         if batches.is_empty() {
             return Ok(Vec::new());
@@ -284,7 +307,7 @@ impl SequenceStreamMerger {
                         let is_timestamp = col_spec.logical_type == "Timestamp"
                             || col_spec.logical_type == "datetime"
                             || field_name == "timestamp"
-                            || field_name == &self.sequence_time_field;
+                            || field_name == time_field;
                         field_is_timestamp.insert(field_name.clone(), is_timestamp);
                         break;
                     }
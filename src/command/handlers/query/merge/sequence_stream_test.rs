@@ -32,6 +32,14 @@ fn create_context_with_sequence(
         time_bucket: None,
         group_by: None,
         event_sequence: Some(event_sequence),
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     }));
 
     let manager = Box::leak(Box::new(ShardManager { shards: Vec::new() }));
@@ -52,6 +60,7 @@ fn create_followed_by_sequence() -> EventSequence {
                 field: None,
             },
         )],
+        within: None,
     }
 }
 
@@ -68,6 +77,7 @@ fn create_preceded_by_sequence() -> EventSequence {
                 field: None,
             },
         )],
+        within: None,
     }
 }
 
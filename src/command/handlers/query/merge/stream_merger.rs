@@ -4,6 +4,7 @@ use crate::command::handlers::query::context::QueryContext;
 use crate::command::handlers::query_batch_stream::QueryBatchStream;
 use crate::command::types::Command;
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
+use crate::engine::query::streaming::DETERMINISTIC_METADATA_KEY;
 
 /// Merger kind for streaming query results, handling ordered, unordered, and aggregate cases.
 pub enum StreamMergerKind {
@@ -35,7 +36,12 @@ impl StreamMergerKind {
                 *offset,
             ))
         } else {
-            StreamMergerKind::Unordered(UnorderedStreamMerger::new())
+            let deterministic = ctx
+                .metadata
+                .get(DETERMINISTIC_METADATA_KEY)
+                .map(String::as_str)
+                == Some("true");
+            StreamMergerKind::Unordered(UnorderedStreamMerger::new().sequential(deterministic))
         }
     }
 
@@ -90,11 +90,20 @@ impl OrderedStreamMerger {
 
 /// Performs a simple fan-in merge when no ordering is required for the
 /// streaming query.
-pub struct UnorderedStreamMerger;
+pub struct UnorderedStreamMerger {
+    sequential: bool,
+}
 
 impl UnorderedStreamMerger {
     pub fn new() -> Self {
-        Self
+        Self { sequential: false }
+    }
+
+    /// Drains shards one after the other in shard order instead of forwarding
+    /// batches as they arrive, so the output order does not depend on timing.
+    pub fn sequential(mut self, sequential: bool) -> Self {
+        self.sequential = sequential;
+        self
     }
 
     /// Fan-in merge that forwards batches as they arrive without touching
@@ -110,9 +119,10 @@ impl UnorderedStreamMerger {
 
         let mut schema: Option<Arc<BatchSchema>> = None;
         let mut tasks: Vec<JoinHandle<()>> = Vec::new();
+        let mut receivers = Vec::new();
 
         for handle in handles {
-            let (mut receiver, handle_schema, mut handle_tasks) = handle.into_parts();
+            let (receiver, handle_schema, mut handle_tasks) = handle.into_parts();
 
             if let Some(existing) = &schema {
                 if !existing.is_compatible_with(&handle_schema) {
@@ -123,18 +133,33 @@ impl UnorderedStreamMerger {
             }
 
             tasks.append(&mut handle_tasks);
-            let tx_clone = tx.clone();
+            receivers.push(receiver);
+        }
+
+        if self.sequential {
             tasks.push(tokio::spawn(async move {
-                while let Some(batch) = receiver.recv().await {
-                    if tx_clone.send(batch).await.is_err() {
-                        break;
+                for mut receiver in receivers {
+                    while let Some(batch) = receiver.recv().await {
+                        if tx.send(batch).await.is_err() {
+                            return;
+                        }
                     }
                 }
             }));
+        } else {
+            for mut receiver in receivers {
+                let tx_clone = tx.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(batch) = receiver.recv().await {
+                        if tx_clone.send(batch).await.is_err() {
+                            break;
+                        }
+                    }
+                }));
+            }
+            drop(tx);
         }
 
-        drop(tx);
-
         let schema = schema.ok_or_else(|| "no shards produced schema".to_string())?;
         Ok(QueryBatchStream::new(schema, rx, tasks))
     }
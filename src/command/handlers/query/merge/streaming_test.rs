@@ -11,6 +11,7 @@ use crate::engine::types::ScalarValue;
 use crate::test_helpers::factories::SchemaRegistryFactory;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Duration;
 
 fn sample_schema() -> Arc<BatchSchema> {
     Arc::new(
@@ -46,6 +47,14 @@ fn create_context() -> QueryContext<'static> {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     }));
 
     let manager = Box::leak(Box::new(ShardManager { shards: Vec::new() }));
@@ -56,6 +65,15 @@ fn create_context() -> QueryContext<'static> {
 async fn build_handle(
     schema: Arc<BatchSchema>,
     rows: Vec<Vec<serde_json::Value>>,
+) -> ShardFlowHandle {
+    build_delayed_handle(schema, rows, Duration::ZERO).await
+}
+
+/// A shard handle that sends its batch only after `delay`.
+async fn build_delayed_handle(
+    schema: Arc<BatchSchema>,
+    rows: Vec<Vec<serde_json::Value>>,
+    delay: Duration,
 ) -> ShardFlowHandle {
     let metrics = FlowMetrics::new();
     let (tx, rx) = FlowChannel::bounded(16, Arc::clone(&metrics));
@@ -69,6 +87,7 @@ async fn build_handle(
     let batch = builder.finish().expect("finish batch");
 
     let send_task = tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
         let _ = tx.send(Arc::new(batch)).await;
     });
 
@@ -116,6 +135,34 @@ async fn unordered_merger_combines_rows() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn sequential_unordered_merger_keeps_shard_order() {
+    let schema = sample_schema();
+    // Shard 0 is the slowest; sequential merging still emits it first
+    let handle_a = build_delayed_handle(
+        Arc::clone(&schema),
+        vec![vec![json!("ctx-a"), json!(1)]],
+        Duration::from_millis(50),
+    )
+    .await;
+    let handle_b = build_handle(Arc::clone(&schema), vec![vec![json!("ctx-b"), json!(2)]]).await;
+
+    let ctx = create_context();
+    let merger = UnorderedStreamMerger::new().sequential(true);
+    let mut stream = merger
+        .merge(&ctx, vec![handle_a, handle_b])
+        .expect("stream created");
+
+    let mut observed = Vec::new();
+    while let Some(batch) = stream.recv().await {
+        for value in batch.column(1).unwrap() {
+            observed.push(value.as_i64().expect("value"));
+        }
+    }
+
+    assert_eq!(observed, vec![1, 2]);
+}
+
 #[tokio::test]
 async fn unordered_merger_rejects_schema_mismatch() {
     let schema_a = Arc::new(
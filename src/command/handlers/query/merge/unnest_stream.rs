@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use tracing::{debug, error};
+
+use crate::command::handlers::query_batch_stream::QueryBatchStream;
+use crate::engine::core::read::flow::operators::{UnnestOp, UnnestOpConfig};
+use crate::engine::core::read::flow::{
+    BatchPool, FlowChannel, FlowContext, FlowMetrics, FlowOperator, FlowOperatorError,
+    FlowTelemetry, QueryCancellation,
+};
+
+const UNNEST_BATCH_SIZE: usize = 1024;
+
+/// Runs the merged shard stream of an `UNNEST(field)` query through an
+/// [`UnnestOp`].
+///
+/// Shards return whole rows in query order; the operator explodes the array
+/// column without reordering them, then applies `OFFSET`/`LIMIT` to the
+/// exploded rows. A failure, such as a non-array value under
+/// `ON NON ARRAY ERROR`, cancels the query with the operator's message.
+pub struct UnnestStreamMerger {
+    config: UnnestOpConfig,
+    cancellation: Arc<QueryCancellation>,
+}
+
+impl UnnestStreamMerger {
+    pub fn new(config: UnnestOpConfig) -> Self {
+        Self {
+            config,
+            cancellation: QueryCancellation::new(),
+        }
+    }
+
+    pub fn with_cancellation(mut self, cancellation: Arc<QueryCancellation>) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Returns an error if the unnested column is absent from the stream or
+    /// is not a JSON column.
+    pub fn merge(&self, stream: QueryBatchStream) -> Result<QueryBatchStream, String> {
+        let (schema, receiver, mut tasks) = stream.into_parts();
+        let op = UnnestOp::new(self.config.clone(), Arc::clone(&schema))?;
+
+        let metrics = FlowMetrics::new();
+        let (tx, rx) = FlowChannel::bounded(2, Arc::clone(&metrics));
+        let pool = BatchPool::new(UNNEST_BATCH_SIZE)
+            .map_err(|e| format!("Failed to create batch pool: {}", e))?;
+        let ctx = Arc::new(FlowContext::new(
+            UNNEST_BATCH_SIZE,
+            pool,
+            metrics,
+            None::<&str>,
+            FlowTelemetry::default(),
+        ));
+
+        let cancellation = Arc::clone(&self.cancellation);
+        tasks.push(tokio::spawn(async move {
+            match op.run(receiver, tx, ctx).await {
+                Ok(()) => {}
+                Err(FlowOperatorError::ChannelClosed) => {
+                    debug!(target: "sneldb::query::unnest", "UNNEST stopped (channel closed)");
+                }
+                Err(err) => {
+                    error!(
+                        target: "sneldb::query::unnest",
+                        error = %err,
+                        "UNNEST failed"
+                    );
+                    cancellation.cancel(match err {
+                        FlowOperatorError::Operator(message) => message,
+                        other => other.to_string(),
+                    });
+                }
+            }
+        }));
+
+        Ok(QueryBatchStream::new(schema, rx, tasks)
+            .with_cancellation(Arc::clone(&self.cancellation)))
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use indexmap::IndexMap;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::command::handlers::payload_limits::PayloadLimits;
+use crate::command::handlers::store::prepare_event;
+use crate::command::types::Command;
+use crate::engine::core::read::flow::QueryScanStats;
+use crate::engine::schema::registry::MiniSchema;
+use crate::engine::schema::{FieldType, SchemaError, SchemaRegistry};
+use crate::engine::shard::manager::ShardManager;
+use crate::engine::shard::message::ShardMessage;
+use crate::shared::config::CONFIG;
+
+/// Prefix of the event types SnelDB defines for itself. `DEFINE` only accepts
+/// names starting with a letter, so they can never collide with user schemas.
+pub const INTERNAL_EVENT_TYPE_PREFIX: &str = "_sneldb_";
+
+/// Event type receiving one event per completed query when
+/// `query.metrics_events` is on.
+pub const QUERY_METRICS_EVENT_TYPE: &str = "_sneldb_query_metrics";
+
+/// Whether `query.metrics_events` is on.
+pub fn enabled() -> bool {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|cfg| cfg.metrics_events)
+        .unwrap_or(false)
+}
+
+/// Whether a query on `event_type` gets a metrics event. Queries on internal
+/// event types never do, so reading the metrics adds none of its own.
+pub fn records(event_type: &str) -> bool {
+    !event_type.starts_with(INTERNAL_EVENT_TYPE_PREFIX)
+}
+
+/// Stable identifier of a query's shape, equal for repeated runs of the same
+/// command: the first 8 bytes of the SHA-256 of the parsed command, in hex.
+pub fn query_hash(command: &Command) -> String {
+    let digest = Sha256::digest(format!("{:?}", command).as_bytes());
+    digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// What one completed query cost, stored as a `_sneldb_query_metrics` event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryMetrics {
+    pub query_hash: String,
+    pub target_event_type: String,
+    pub latency: Duration,
+    pub rows_scanned: u64,
+    pub rows_returned: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub failed: bool,
+}
+
+impl QueryMetrics {
+    pub fn new(
+        command: &Command,
+        event_type: &str,
+        latency: Duration,
+        stats: &QueryScanStats,
+        rows_returned: usize,
+        failed: bool,
+    ) -> Self {
+        Self {
+            query_hash: query_hash(command),
+            target_event_type: event_type.to_string(),
+            latency,
+            rows_scanned: stats.rows_scanned(),
+            rows_returned: rows_returned as u64,
+            cache_hits: stats.cache_hits(),
+            cache_misses: stats.cache_misses(),
+            failed,
+        }
+    }
+
+    /// The `STORE` command carrying these metrics; events of one query shape
+    /// share a context, keyed by the query hash.
+    pub fn to_store_command(&self) -> Command {
+        Command::Store {
+            event_type: QUERY_METRICS_EVENT_TYPE.to_string(),
+            context_id: self.query_hash.clone(),
+            payload: json!({
+                "query_hash": self.query_hash,
+                "target_event_type": self.target_event_type,
+                "latency_ms": self.latency.as_millis() as u64,
+                "rows_scanned": self.rows_scanned,
+                "rows_returned": self.rows_returned,
+                "cache_hits": self.cache_hits,
+                "cache_misses": self.cache_misses,
+                "failed": self.failed,
+            }),
+        }
+    }
+}
+
+/// Schema of `_sneldb_query_metrics`; events keep their ingest time as timestamp.
+pub fn metrics_schema() -> MiniSchema {
+    let fields: IndexMap<String, FieldType> = [
+        ("query_hash", FieldType::String),
+        ("target_event_type", FieldType::String),
+        ("latency_ms", FieldType::U64),
+        ("rows_scanned", FieldType::U64),
+        ("rows_returned", FieldType::U64),
+        ("cache_hits", FieldType::U64),
+        ("cache_misses", FieldType::U64),
+        ("failed", FieldType::Bool),
+    ]
+    .into_iter()
+    .map(|(name, ty)| (name.to_string(), ty))
+    .collect();
+    MiniSchema {
+        fields,
+        time_field: None,
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
+    }
+}
+
+/// Stores `metrics` through the regular ingest path, defining the internal
+/// schema on first use. Never waits on a busy shard: when its queue is full
+/// the event is dropped rather than slowing down the query that produced it.
+pub async fn emit(
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    metrics: &QueryMetrics,
+) {
+    if let Err(e) = ensure_schema(registry).await {
+        warn!(target: "sneldb::query_metrics", error = %e, "Failed to define query metrics schema");
+        return;
+    }
+
+    let command = metrics.to_store_command();
+    let event = match prepare_event(&command, registry, None, None, &PayloadLimits::default()).await
+    {
+        Ok(event) => event,
+        Err(rejection) => {
+            warn!(target: "sneldb::query_metrics", error = %rejection.message, "Query metrics event rejected");
+            return;
+        }
+    };
+
+    let shard = shard_manager.get_shard(&event.context_id);
+    if shard
+        .tx
+        .try_send(ShardMessage::Store(event, Arc::clone(registry)))
+        .is_err()
+    {
+        debug!(target: "sneldb::query_metrics", shard_id = shard.id, "Shard busy, query metrics event dropped");
+    }
+}
+
+async fn ensure_schema(registry: &Arc<RwLock<SchemaRegistry>>) -> Result<(), SchemaError> {
+    if registry.read().await.has_schema(QUERY_METRICS_EVENT_TYPE) {
+        return Ok(());
+    }
+    let mut registry = registry.write().await;
+    if registry.has_schema(QUERY_METRICS_EVENT_TYPE) {
+        return Ok(());
+    }
+    registry
+        .define_async(QUERY_METRICS_EVENT_TYPE, metrics_schema())
+        .await
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+use tempfile::tempdir;
+use tokio::io::{AsyncReadExt, duplex};
+use tokio::sync::RwLock;
+use tokio::time::{Duration, sleep};
+
+use crate::command::handlers::query::QueryCommandHandler;
+use crate::command::handlers::query::metrics_events::{
+    QUERY_METRICS_EVENT_TYPE, query_hash, records,
+};
+use crate::command::handlers::store;
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::{CommandFactory, SchemaRegistryFactory};
+
+async fn run_query(
+    cmd: &crate::command::types::Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+) -> Vec<Value> {
+    let (mut reader, mut writer) = duplex(1 << 16);
+    QueryCommandHandler::new(
+        cmd,
+        shard_manager,
+        Arc::clone(registry),
+        None,
+        None,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .with_metrics_events(true)
+    .handle()
+    .await
+    .expect("query should succeed");
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+    body.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn metrics_rows(frames: &[Value]) -> Vec<Value> {
+    let columns: Vec<String> = frames[0]["columns"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap().to_string())
+        .collect();
+    frames
+        .iter()
+        .filter(|f| f["type"] == "batch")
+        .flat_map(|f| f["rows"].as_array().unwrap().clone())
+        .map(|row| {
+            columns
+                .iter()
+                .cloned()
+                .zip(row.as_array().unwrap().iter().cloned())
+                .collect::<serde_json::Map<_, _>>()
+                .into()
+        })
+        .collect()
+}
+
+#[test]
+fn query_hash_is_stable_per_query_shape() {
+    let a = CommandFactory::query().with_event_type("order").create();
+    let b = CommandFactory::query().with_event_type("refund").create();
+    assert_eq!(query_hash(&a), query_hash(&a.clone()));
+    assert_ne!(query_hash(&a), query_hash(&b));
+    assert_eq!(query_hash(&a).len(), 16);
+
+    assert!(records("order"));
+    assert!(!records(QUERY_METRICS_EVENT_TYPE));
+}
+
+#[tokio::test]
+async fn completed_queries_are_stored_as_metrics_events() {
+    let base_dir = tempdir().unwrap();
+    let wal_dir = tempdir().unwrap();
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("order", &[("amount", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(
+        1,
+        base_dir.path().to_path_buf(),
+        wal_dir.path().to_path_buf(),
+    )
+    .await;
+
+    for (ctx, amount) in [("c1", 10), ("c2", 20)] {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("order")
+            .with_context_id(ctx)
+            .with_payload(json!({ "amount": amount }))
+            .create();
+        let (_reader, mut writer) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut writer,
+            &JsonRenderer,
+        )
+        .await
+        .unwrap();
+    }
+    sleep(Duration::from_millis(100)).await;
+
+    let query = CommandFactory::query().with_event_type("order").create();
+    run_query(&query, &shard_manager, &registry).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let metrics_query = CommandFactory::query()
+        .with_event_type(QUERY_METRICS_EVENT_TYPE)
+        .create();
+    let rows = metrics_rows(&run_query(&metrics_query, &shard_manager, &registry).await);
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row["query_hash"], json!(query_hash(&query)));
+    assert_eq!(row["context_id"], json!(query_hash(&query)));
+    assert_eq!(row["target_event_type"], "order");
+    assert_eq!(row["rows_scanned"], 2);
+    assert_eq!(row["rows_returned"], 2);
+    assert_eq!(row["failed"], false);
+    sleep(Duration::from_millis(100)).await;
+
+    // Reading the metrics did not record metrics of its own
+    let rows = metrics_rows(&run_query(&metrics_query, &shard_manager, &registry).await);
+    assert_eq!(rows.len(), 1);
+}
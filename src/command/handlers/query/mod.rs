@@ -2,15 +2,20 @@ mod context;
 mod dispatch;
 mod handler;
 pub mod merge;
+pub mod metrics_events;
 mod orchestrator;
 mod planner;
 mod streaming;
 
 #[cfg(test)]
 mod context_test;
+#[cfg(test)]
+mod metrics_events_test;
 
 pub use handler::QueryCommandHandler;
+pub(crate) use handler::ReadUsageGuard;
 pub use orchestrator::QueryExecutionPipeline;
+pub use planner::{COMPLEXITY_ERROR_PREFIX, ComplexityLimits, materialized_views_dir};
 
-// Re-export streaming types for use by comparison handler
-pub use streaming::QueryResponseWriter;
+// Re-export streaming types for use by comparison and union handlers
+pub use streaming::{OutputBatching, QueryResponseWriter};
@@ -1,19 +1,46 @@
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::command::handlers::query_batch_stream::QueryBatchStream;
-use crate::command::types::Command;
+use crate::command::handlers::show::WatermarkDeduplicator;
+use crate::command::types::{Command, PrunerKind};
+use crate::engine::core::read::flow::operators::{
+    DedupOpConfig, PartialConverter, UnnestOpConfig, aggregate_output_schema, dedup_max_bytes,
+};
+use crate::engine::core::read::flow::{
+    BatchPool, BatchSchema, FlowChannel, FlowContext, FlowMetrics, FlowTelemetry,
+    QueryCancellation, QueryMemoryBudget, QueryScanStats,
+};
+use crate::engine::materialize::{
+    AggregateState, HighWaterMark, MaterializedQuerySpecExt, MaterializedStore,
+};
 use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
 use tokio::sync::RwLock;
+use tokio::time::Instant;
+use tracing::{debug, warn};
 
 use super::context::QueryContext;
 use super::dispatch::{SequenceStreamingDispatcher, StreamingDispatch, StreamingShardDispatcher};
-use super::merge::{SequenceStreamMerger, StreamMergerKind};
-use super::planner::{QueryPlanner, QueryPlannerBuilder};
+use super::merge::aggregate_stream::AggregateStreamMerger;
+use super::merge::{
+    DedupStreamMerger, LatestStreamMerger, SequenceStreamMerger, StreamMergerKind,
+    UnnestStreamMerger,
+};
+use super::planner::{
+    ComplexityLimits, PlanOutcome, QueryComplexity, QueryPlanner, QueryPlannerBuilder, TotalCount,
+    ViewLookup, ViewMatch, count_total, estimate_candidate_zones,
+};
+
+const VIEW_BATCH_SIZE: usize = 1024;
 
 pub struct QueryExecutionPipeline<'a> {
     ctx: QueryContext<'a>,
     planner: Box<dyn QueryPlanner>,
+    complexity_limits: ComplexityLimits,
+    views_dir: Option<PathBuf>,
+    timeout: Option<Duration>,
 }
 
 impl<'a> QueryExecutionPipeline<'a> {
@@ -24,7 +51,26 @@ impl<'a> QueryExecutionPipeline<'a> {
     ) -> Self {
         let ctx = QueryContext::new(command, shard_manager, registry);
         let planner = QueryPlannerBuilder::new(command).build();
-        Self { ctx, planner }
+        Self {
+            ctx,
+            planner,
+            complexity_limits: ComplexityLimits::from_config(),
+            views_dir: None,
+            timeout: None,
+        }
+    }
+
+    /// Lets aggregate queries read a matching materialized view under
+    /// `data_dir` instead of scanning raw events.
+    pub fn with_materialized_views(mut self, data_dir: impl Into<PathBuf>) -> Self {
+        self.views_dir = Some(data_dir.into());
+        self
+    }
+
+    /// Overrides the complexity budget, e.g. with limits resolved for the caller.
+    pub fn with_complexity_limits(mut self, limits: ComplexityLimits) -> Self {
+        self.complexity_limits = limits;
+        self
     }
 
     pub fn with_metadata(mut self, metadata: std::collections::HashMap<String, String>) -> Self {
@@ -33,10 +79,39 @@ impl<'a> QueryExecutionPipeline<'a> {
             shard_manager: self.ctx.shard_manager,
             registry: self.ctx.registry,
             metadata,
+            memory: self.ctx.memory,
+            stats: self.ctx.stats,
+            cancellation: self.ctx.cancellation,
         };
         self
     }
 
+    /// Stops the query once it has run for `timeout`, counted from
+    /// [`Self::execute_streaming`]: planning and dispatch fail with a timeout
+    /// error, and a stream already being read ends early, see
+    /// [`QueryBatchStream::timed_out`]. Shard readers are cancelled either way.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Accounts the query's operators against `budget` instead of an unlimited one.
+    pub fn with_memory_budget(mut self, budget: Arc<QueryMemoryBudget>) -> Self {
+        self.ctx = self.ctx.with_memory_budget(budget);
+        self
+    }
+
+    /// Records the columns each reader hydrates, in [`Self::scan_stats`].
+    pub fn with_projection_audit(mut self) -> Self {
+        self.ctx.stats = QueryScanStats::with_projection_audit();
+        self
+    }
+
+    /// Rows read and cache lookups of the query so far, summed over all shards.
+    pub fn scan_stats(&self) -> Arc<QueryScanStats> {
+        Arc::clone(&self.ctx.stats)
+    }
+
     pub fn is_sequence_query(&self) -> bool {
         matches!(
             self.ctx.command,
@@ -48,22 +123,261 @@ impl<'a> QueryExecutionPipeline<'a> {
         )
     }
 
+    pub fn is_latest_query(&self) -> bool {
+        matches!(
+            self.ctx.command,
+            Command::Query {
+                latest_per: Some(_),
+                ..
+            }
+        )
+    }
+
+    pub fn is_dedup_query(&self) -> bool {
+        matches!(self.ctx.command, Command::Query { dedup: Some(_), .. })
+    }
+
+    pub fn is_unnest_query(&self) -> bool {
+        matches!(
+            self.ctx.command,
+            Command::Query {
+                unnest: Some(_),
+                ..
+            }
+        )
+    }
+
+    /// Row count for `WITH TOTAL`, from metadata and shard buffers only.
+    pub async fn count_total(&self) -> Option<TotalCount> {
+        count_total(&self.ctx).await
+    }
+
     pub async fn execute_streaming(&self) -> Result<Option<QueryBatchStream>, String> {
-        // Check if this is a sequence query
-        if self.is_sequence_query() {
-            return self.execute_sequence_streaming().await;
+        let Some(timeout) = self.timeout else {
+            return self.build_stream().await.map(Some);
+        };
+        let deadline = Instant::now() + timeout;
+        match tokio::time::timeout_at(deadline, self.build_stream()).await {
+            Ok(stream) => Ok(Some(stream?.with_deadline(
+                deadline,
+                timeout,
+                Arc::clone(&self.ctx.cancellation),
+            ))),
+            Err(_) => {
+                let message = QueryCancellation::timeout_message(timeout);
+                self.ctx.cancellation.cancel(message.clone());
+                warn!(
+                    target: "sneldb::query",
+                    timeout_ms = timeout.as_millis() as u64,
+                    "Query timed out before streaming its results"
+                );
+                Err(message)
+            }
+        }
+    }
+
+    async fn build_stream(&self) -> Result<QueryBatchStream, String> {
+        let stream = if self.is_unnest_query() {
+            self.execute_unnest_streaming().await?
+        } else if self.is_sequence_query() {
+            self.execute_sequence_streaming().await?
+        } else if self.is_latest_query() {
+            self.execute_latest_streaming().await?
+        } else if self.is_dedup_query() {
+            self.execute_dedup_streaming().await?
+        } else if let Some(view) = self.matching_view() {
+            self.execute_view_streaming(view).await?
+        } else {
+            self.execute_shard_streaming().await?
+        };
+        Ok(stream.with_memory_budget(Arc::clone(&self.ctx.memory)))
+    }
+
+    /// Describes how the query would run, one line per step.
+    pub fn explain(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let lookup = self.view_lookup();
+        match &lookup.matched {
+            Some(view) => {
+                let mark = view.entry.high_water_mark.unwrap_or_default();
+                lines.push(format!("plan: materialized view '{}'", view.entry.name));
+                lines.push(format!(
+                    "view high-water: timestamp {} event_id {}",
+                    mark.timestamp, mark.event_id
+                ));
+                lines.push("tail: events past the high-water mark, from shards".to_string());
+            }
+            None => lines.push("plan: shard scan".to_string()),
+        }
+        for (alias, reason) in &lookup.rejected {
+            lines.push(format!("view '{alias}' not used: {reason}"));
+        }
+        let disabled = self.ctx.command.disabled_pruners();
+        let names = |kinds: Vec<PrunerKind>| {
+            if kinds.is_empty() {
+                return "none".to_string();
+            }
+            kinds
+                .iter()
+                .map(|kind| kind.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let active: Vec<PrunerKind> = PrunerKind::ALL
+            .into_iter()
+            .filter(|kind| !disabled.contains(kind))
+            .collect();
+        lines.push(format!("pruners: {}", names(active)));
+        if !disabled.is_empty() {
+            lines.push(format!("pruners disabled: {}", names(disabled.to_vec())));
+        }
+        lines
+    }
+
+    fn view_lookup(&self) -> ViewLookup {
+        match &self.views_dir {
+            Some(dir) => ViewLookup::find(self.ctx.command, dir),
+            None => ViewLookup::default(),
         }
+    }
 
+    fn matching_view(&self) -> Option<ViewMatch> {
+        self.view_lookup().matched
+    }
+
+    /// Plans, dispatches and merges the query across the shards.
+    async fn execute_shard_streaming(&self) -> Result<QueryBatchStream, String> {
         let plan = self.planner.build_plan(&self.ctx).await?;
+        self.check_complexity(&self.ctx, Some(&plan)).await?;
         let dispatcher = StreamingShardDispatcher::new();
         let handles = dispatcher.dispatch(&self.ctx, &plan).await?;
         let merger = StreamMergerKind::for_context(&self.ctx);
-        let stream = merger.merge(&self.ctx, handles)?;
-        Ok(Some(stream))
+        merger.merge(&self.ctx, handles)
+    }
+
+    /// Answers an aggregate query from a materialized view: the stored state,
+    /// topped up with the raw events past its high-water mark, goes through
+    /// the aggregate merger as a single partial. The view itself is not
+    /// updated. If its state cannot be read (e.g. a refresh swapped the
+    /// frames meanwhile), the query scans the shards instead.
+    async fn execute_view_streaming(&self, view: ViewMatch) -> Result<QueryBatchStream, String> {
+        let loaded = MaterializedStore::open(&view.entry.storage_path)
+            .and_then(|store| AggregateState::from_store(&store, view.plan.clone()));
+        let (mut state, high_water) = match loaded {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                warn!(
+                    target: "sneldb::query",
+                    view = %view.entry.name,
+                    error = %err,
+                    "Failed to read materialized view, scanning shards instead"
+                );
+                return self.execute_shard_streaming().await;
+            }
+        };
+        debug!(
+            target: "sneldb::query",
+            view = %view.entry.name,
+            high_water_ts = high_water.timestamp,
+            high_water_event_id = high_water.event_id,
+            "Answering aggregate query from materialized view"
+        );
+
+        self.absorb_view_tail(&view, &mut state, high_water).await?;
+
+        let schema = Arc::new(
+            BatchSchema::new(aggregate_output_schema(&view.plan)).map_err(|e| e.to_string())?,
+        );
+        let metrics = FlowMetrics::new();
+        let (sender, receiver) = FlowChannel::bounded(2, Arc::clone(&metrics));
+        let pool = BatchPool::new(VIEW_BATCH_SIZE).map_err(|e| e.to_string())?;
+        let ctx = Arc::new(FlowContext::new(
+            VIEW_BATCH_SIZE,
+            pool,
+            metrics,
+            None::<&str>,
+            FlowTelemetry::default(),
+        ));
+        let partial = state.into_partial();
+        let partial_schema = Arc::clone(&schema);
+        let task = tokio::spawn(async move {
+            if let Err(err) =
+                PartialConverter::to_batches(partial, partial_schema, ctx, sender).await
+            {
+                warn!(
+                    target: "sneldb::query",
+                    error = %err,
+                    "Failed to stream materialized view state"
+                );
+            }
+        });
+
+        AggregateStreamMerger::new(self.ctx.command).merge_stream(
+            QueryBatchStream::new(schema, receiver, vec![task]),
+            Arc::clone(&self.ctx.memory),
+        )
+    }
+
+    /// Folds the events newer than `high_water` into `state`. The tail query
+    /// starts at the mark's second; events at or below the mark are dropped
+    /// so none is counted twice.
+    async fn absorb_view_tail(
+        &self,
+        view: &ViewMatch,
+        state: &mut AggregateState,
+        high_water: HighWaterMark,
+    ) -> Result<(), String> {
+        let tail_command = view
+            .entry
+            .spec
+            .delta_command(Some(high_water))
+            .map_err(|e| format!("Failed to build view tail query: {e}"))?;
+        let tail = QueryExecutionPipeline::new(
+            &tail_command,
+            self.ctx.shard_manager,
+            Arc::clone(&self.ctx.registry),
+        )
+        .with_complexity_limits(self.complexity_limits.clone())
+        .with_memory_budget(Arc::clone(&self.ctx.memory));
+        let mut stream = tail.execute_shard_streaming().await?;
+
+        let schema = stream.schema();
+        let position = |name: &str| schema.columns().iter().position(|c| c.name == name);
+        let mut watermark =
+            WatermarkDeduplicator::new(high_water, position("timestamp"), position("event_id"));
+        while let Some(batch) = stream.recv().await {
+            let Some(batch) = watermark.filter(batch) else {
+                continue;
+            };
+            state
+                .absorb(&batch, "timestamp")
+                .map_err(|e| format!("Failed to merge view tail: {e}"))?;
+        }
+        match stream.failure() {
+            Some(failure) => Err(failure.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Rejects the query if it exceeds the complexity budget. Runs after planning
+    /// so the candidate zone estimate can use the zones the planner picked.
+    async fn check_complexity(
+        &self,
+        ctx: &QueryContext<'_>,
+        plan: Option<&PlanOutcome>,
+    ) -> Result<(), String> {
+        if self.complexity_limits.is_unlimited() {
+            return Ok(());
+        }
+        let mut complexity = QueryComplexity::of(ctx.command);
+        if self.complexity_limits.needs_zone_estimate() {
+            complexity = complexity.with_candidate_zones(estimate_candidate_zones(ctx, plan).await);
+        }
+        self.complexity_limits.check(&complexity)
     }
 
     /// Executes sequence queries using the streaming infrastructure.
-    async fn execute_sequence_streaming(&self) -> Result<Option<QueryBatchStream>, String> {
+    async fn execute_sequence_streaming(&self) -> Result<QueryBatchStream, String> {
         let Command::Query {
             event_sequence: Some(event_sequence),
             link_field: Some(link_field),
@@ -80,6 +394,8 @@ impl<'a> QueryExecutionPipeline<'a> {
             .map(|s| s.clone())
             .unwrap_or_else(|| "timestamp".to_string());
 
+        self.check_complexity(&self.ctx, None).await?;
+
         // Use sequence dispatcher to split into sub-queries
         let dispatcher = SequenceStreamingDispatcher::new();
         let sequence_handles = dispatcher.dispatch_grouped_internal(&self.ctx).await?;
@@ -92,9 +408,242 @@ impl<'a> QueryExecutionPipeline<'a> {
             limit.map(|l| l as usize),
         );
 
-        let stream = merger
+        merger
             .merge(&self.ctx, sequence_handles.handles_by_type)
+            .await
+    }
+
+    /// Executes `LATEST PER` queries: the WHERE clause is planned and pruned as
+    /// usual, then the matched rows are reduced to the latest row per key.
+    async fn execute_latest_streaming(&self) -> Result<QueryBatchStream, String> {
+        let Command::Query {
+            latest_per: Some(key_field),
+            sequence_time_field,
+            order_by,
+            limit,
+            offset,
+            aggs,
+            time_bucket,
+            group_by,
+            event_sequence,
+            ..
+        } = self.ctx.command
+        else {
+            return Err("Latest query requires a LATEST PER field".to_string());
+        };
+
+        if aggs.is_some() || time_bucket.is_some() || group_by.is_some() {
+            return Err("LATEST PER cannot be combined with aggregations".to_string());
+        }
+        if event_sequence.is_some() {
+            return Err("LATEST PER cannot be combined with event sequences".to_string());
+        }
+
+        // Shards must return every matching row: ordering and limits only apply
+        // after the reduction.
+        let mut shard_command = self.ctx.command.clone();
+        if let Command::Query {
+            order_by,
+            limit,
+            offset,
+            latest_per,
+            ..
+        } = &mut shard_command
+        {
+            *order_by = None;
+            *limit = None;
+            *offset = None;
+            *latest_per = None;
+        }
+
+        let ctx = QueryContext {
+            command: &shard_command,
+            shard_manager: self.ctx.shard_manager,
+            registry: Arc::clone(&self.ctx.registry),
+            metadata: self.ctx.metadata.clone(),
+            memory: Arc::clone(&self.ctx.memory),
+            stats: Arc::clone(&self.ctx.stats),
+            cancellation: Arc::clone(&self.ctx.cancellation),
+        };
+        let planner = QueryPlannerBuilder::new(&shard_command).build();
+        let plan = planner.build_plan(&ctx).await?;
+        self.check_complexity(&ctx, Some(&plan)).await?;
+        let handles = StreamingShardDispatcher::new()
+            .dispatch(&ctx, &plan)
+            .await?;
+
+        let time_field = sequence_time_field
+            .clone()
+            .unwrap_or_else(|| "timestamp".to_string());
+        let merger = LatestStreamMerger::new(
+            key_field.clone(),
+            time_field,
+            order_by.clone(),
+            *limit,
+            *offset,
+        )
+        .with_memory_budget(Arc::clone(&self.ctx.memory));
+        merger.merge(handles)
+    }
+
+    /// Executes `DEDUP BY` queries: shards stream every matching row and the
+    /// coordinator keeps one row per key before ordering and limits apply.
+    async fn execute_dedup_streaming(&self) -> Result<QueryBatchStream, String> {
+        let Command::Query {
+            dedup: Some(spec),
+            sequence_time_field,
+            order_by,
+            limit,
+            offset,
+            aggs,
+            time_bucket,
+            group_by,
+            event_sequence,
+            latest_per,
+            ..
+        } = self.ctx.command
+        else {
+            return Err("Dedup query requires a DEDUP BY clause".to_string());
+        };
+
+        if aggs.is_some() || time_bucket.is_some() || group_by.is_some() {
+            return Err("DEDUP BY cannot be combined with aggregations".to_string());
+        }
+        if event_sequence.is_some() || latest_per.is_some() {
+            return Err(
+                "DEDUP BY cannot be combined with event sequences or LATEST PER".to_string(),
+            );
+        }
+
+        let mut shard_command = self.ctx.command.clone();
+        if let Command::Query {
+            order_by,
+            limit,
+            offset,
+            dedup,
+            ..
+        } = &mut shard_command
+        {
+            *order_by = None;
+            *limit = None;
+            *offset = None;
+            *dedup = None;
+        }
+
+        let ctx = QueryContext {
+            command: &shard_command,
+            shard_manager: self.ctx.shard_manager,
+            registry: Arc::clone(&self.ctx.registry),
+            metadata: self.ctx.metadata.clone(),
+            memory: Arc::clone(&self.ctx.memory),
+            stats: Arc::clone(&self.ctx.stats),
+            cancellation: Arc::clone(&self.ctx.cancellation),
+        };
+        let planner = QueryPlannerBuilder::new(&shard_command).build();
+        let plan = planner.build_plan(&ctx).await?;
+        self.check_complexity(&ctx, Some(&plan)).await?;
+        let handles = StreamingShardDispatcher::new()
+            .dispatch(&ctx, &plan)
+            .await?;
+        let merged = StreamMergerKind::for_context(&ctx).merge(&ctx, handles)?;
+
+        let config = DedupOpConfig {
+            spec: spec.clone(),
+            time_field: sequence_time_field
+                .clone()
+                .unwrap_or_else(|| "timestamp".to_string()),
+            order_by: order_by.clone(),
+            limit: limit.map(|value| value as usize),
+            offset: offset.unwrap_or(0) as usize,
+            max_bytes: dedup_max_bytes(),
+        };
+        DedupStreamMerger::new(config)
+            .with_memory_budget(Arc::clone(&self.ctx.memory))
+            .merge(merged)
+    }
+
+    /// Executes `UNNEST(field)` queries: shards stream every matching row in
+    /// query order and the coordinator explodes the array column before
+    /// `OFFSET`/`LIMIT` apply, since one row may yield any number of rows.
+    async fn execute_unnest_streaming(&self) -> Result<QueryBatchStream, String> {
+        let Command::Query {
+            unnest: Some(spec),
+            order_by,
+            limit,
+            offset,
+            aggs,
+            time_bucket,
+            group_by,
+            event_sequence,
+            latest_per,
+            dedup,
+            ..
+        } = self.ctx.command
+        else {
+            return Err("Unnest query requires an UNNEST clause".to_string());
+        };
+
+        if aggs.is_some() || time_bucket.is_some() || group_by.is_some() {
+            return Err("UNNEST cannot be combined with aggregations".to_string());
+        }
+        if event_sequence.is_some() || latest_per.is_some() || dedup.is_some() {
+            return Err(
+                "UNNEST cannot be combined with event sequences, LATEST PER or DEDUP BY"
+                    .to_string(),
+            );
+        }
+        if order_by.as_ref().is_some_and(|o| o.field == spec.field) {
+            return Err(format!(
+                "UNNEST cannot be combined with ORDER BY on the unnested field '{}'",
+                spec.field
+            ));
+        }
+
+        // The unnested field is always returned, so RETURN need not list it
+        let mut shard_command = self.ctx.command.clone();
+        if let Command::Query {
+            limit,
+            offset,
+            unnest,
+            return_fields,
+            ..
+        } = &mut shard_command
+        {
+            *limit = None;
+            *offset = None;
+            *unnest = None;
+            if let Some(fields) = return_fields
+                && !fields.is_empty()
+                && !fields.contains(&spec.field)
+            {
+                fields.push(spec.field.clone());
+            }
+        }
+
+        let ctx = QueryContext {
+            command: &shard_command,
+            shard_manager: self.ctx.shard_manager,
+            registry: Arc::clone(&self.ctx.registry),
+            metadata: self.ctx.metadata.clone(),
+            memory: Arc::clone(&self.ctx.memory),
+            stats: Arc::clone(&self.ctx.stats),
+            cancellation: Arc::clone(&self.ctx.cancellation),
+        };
+        let planner = QueryPlannerBuilder::new(&shard_command).build();
+        let plan = planner.build_plan(&ctx).await?;
+        self.check_complexity(&ctx, Some(&plan)).await?;
+        let handles = StreamingShardDispatcher::new()
+            .dispatch(&ctx, &plan)
             .await?;
-        Ok(Some(stream))
+        let merged = StreamMergerKind::for_context(&ctx).merge(&ctx, handles)?;
+
+        let config = UnnestOpConfig {
+            spec: spec.clone(),
+            limit: limit.map(|value| value as usize),
+            offset: offset.unwrap_or(0) as usize,
+        };
+        UnnestStreamMerger::new(config)
+            .with_cancellation(Arc::clone(&self.ctx.cancellation))
+            .merge(merged)
     }
 }
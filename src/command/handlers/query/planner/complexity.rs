@@ -0,0 +1,337 @@
+use std::sync::Arc;
+
+use crate::command::handlers::query::context::QueryContext;
+use crate::command::handlers::segment_discovery::SegmentDiscovery;
+use crate::command::types::{Command, Expr};
+use crate::engine::auth::AuthManager;
+use crate::engine::core::ZoneMeta;
+use crate::shared::config::CONFIG;
+use crate::shared::config::model::{
+    ComplexityLimitsConfig, ExcessZonesAction, QueryComplexityConfig,
+};
+use crate::shared::time::{TimeKind, TimeParser};
+
+use super::PlanOutcome;
+
+/// Prefix of every complexity rejection, so callers can tell it apart from
+/// execution failures.
+pub const COMPLEXITY_ERROR_PREFIX: &str = "Query too complex";
+
+/// Measured cost drivers of a single query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryComplexity {
+    /// Number of predicates in the WHERE clause
+    pub conditions: usize,
+    /// Size of the largest IN list
+    pub largest_in_list: usize,
+    /// Number of events linked to the head event in a sequence query
+    pub joins: usize,
+    /// Estimated number of zones to scan, when it was computed
+    pub candidate_zones: Option<usize>,
+    /// Whether the query carries the `/* allow_full_scan */` hint
+    pub allow_full_scan: bool,
+}
+
+impl QueryComplexity {
+    /// Scores the parts of a query that are known before planning.
+    pub fn of(command: &Command) -> Self {
+        let mut complexity = Self::default();
+        if let Command::Query {
+            where_clause,
+            event_sequence,
+            allow_full_scan,
+            ..
+        } = command
+        {
+            complexity.allow_full_scan = *allow_full_scan;
+            if let Some(expr) = where_clause {
+                complexity.visit(expr);
+            }
+            if let Some(sequence) = event_sequence {
+                complexity.joins = sequence.links.len();
+            }
+        }
+        complexity
+    }
+
+    pub fn with_candidate_zones(mut self, zones: usize) -> Self {
+        self.candidate_zones = Some(zones);
+        self
+    }
+
+    fn visit(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Compare { .. } => self.conditions += 1,
+            Expr::In { values, .. } => {
+                self.conditions += 1;
+                self.largest_in_list = self.largest_in_list.max(values.len());
+            }
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                self.visit(left);
+                self.visit(right);
+            }
+            Expr::Not(inner) => self.visit(inner),
+        }
+    }
+}
+
+/// Complexity budget for a query. `None` means the dimension is unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComplexityLimits {
+    pub max_conditions: Option<usize>,
+    pub max_in_list: Option<usize>,
+    pub max_joins: Option<usize>,
+    pub max_candidate_zones: Option<usize>,
+    /// What happens past `max_candidate_zones`; `None` rejects
+    pub on_excess_zones: Option<ExcessZonesAction>,
+}
+
+impl ComplexityLimits {
+    /// Base limits from `[query.complexity]`, without user or role overrides.
+    pub fn from_config() -> Self {
+        Self::config().map(Self::base).unwrap_or_default()
+    }
+
+    /// Limits for a user from `[query.complexity]`.
+    pub fn for_user(user_id: &str, roles: &[String]) -> Self {
+        Self::config()
+            .map(|cfg| Self::from_settings(cfg, user_id, roles))
+            .unwrap_or_default()
+    }
+
+    /// Role overrides replace the base limits (the most permissive role wins),
+    /// then the user's own override is applied.
+    pub(crate) fn from_settings(
+        cfg: &QueryComplexityConfig,
+        user_id: &str,
+        roles: &[String],
+    ) -> Self {
+        let mut limits = Self::base(cfg);
+        let role_limits = roles
+            .iter()
+            .filter_map(|role| cfg.roles.get(role))
+            .map(Self::from)
+            .reduce(|acc, limits| acc.most_permissive(&limits));
+        if let Some(role_limits) = role_limits {
+            limits.apply(&role_limits);
+        }
+        if let Some(user_cfg) = cfg.users.get(user_id) {
+            limits.apply(&Self::from(user_cfg));
+        }
+        limits
+    }
+
+    /// Resolves the limits for the caller of a command.
+    pub async fn resolve(auth_manager: Option<&Arc<AuthManager>>, user_id: Option<&str>) -> Self {
+        match (auth_manager, user_id) {
+            (Some(auth_manager), Some(user_id)) => {
+                let roles = auth_manager.roles(user_id).await;
+                Self::for_user(user_id, &roles)
+            }
+            (None, Some(user_id)) => Self::for_user(user_id, &[]),
+            _ => Self::from_config(),
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether checking these limits requires a candidate zone estimate.
+    pub fn needs_zone_estimate(&self) -> bool {
+        self.max_candidate_zones.is_some()
+    }
+
+    /// Rejects the query with an explanation of the first limit it exceeds.
+    pub fn check(&self, complexity: &QueryComplexity) -> Result<(), String> {
+        let checks = [
+            (
+                complexity.conditions,
+                self.max_conditions,
+                "max_conditions",
+                "WHERE clause has",
+                "conditions",
+            ),
+            (
+                complexity.largest_in_list,
+                self.max_in_list,
+                "max_in_list",
+                "IN list has",
+                "values",
+            ),
+            (
+                complexity.joins,
+                self.max_joins,
+                "max_joins",
+                "sequence links",
+                "events",
+            ),
+        ];
+
+        for (actual, limit, setting, subject, unit) in checks {
+            if let Some(limit) = limit
+                && actual > limit
+            {
+                return Err(format!(
+                    "{}: {} {} {}, limit is {} ({})",
+                    COMPLEXITY_ERROR_PREFIX, subject, actual, unit, limit, setting
+                ));
+            }
+        }
+
+        if let (Some(limit), Some(zones)) = (self.max_candidate_zones, complexity.candidate_zones)
+            && zones > limit
+        {
+            let action = self.on_excess_zones.unwrap_or_default();
+            if action == ExcessZonesAction::RequireHint && complexity.allow_full_scan {
+                return Ok(());
+            }
+            let mut message = format!(
+                "{}: query would scan an estimated {} zones, limit is {} (max_candidate_zones)",
+                COMPLEXITY_ERROR_PREFIX, zones, limit
+            );
+            if action == ExcessZonesAction::RequireHint {
+                message.push_str("; add /* allow_full_scan */ to run it anyway");
+            }
+            return Err(message);
+        }
+        Ok(())
+    }
+
+    /// Replaces every limit that `other` sets.
+    fn apply(&mut self, other: &ComplexityLimits) {
+        self.max_conditions = other.max_conditions.or(self.max_conditions);
+        self.max_in_list = other.max_in_list.or(self.max_in_list);
+        self.max_joins = other.max_joins.or(self.max_joins);
+        self.max_candidate_zones = other.max_candidate_zones.or(self.max_candidate_zones);
+        self.on_excess_zones = other.on_excess_zones.or(self.on_excess_zones);
+    }
+
+    /// Combines two overrides, keeping the higher value for limits both set.
+    fn most_permissive(&self, other: &ComplexityLimits) -> ComplexityLimits {
+        let max = |a: Option<usize>, b: Option<usize>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        ComplexityLimits {
+            max_conditions: max(self.max_conditions, other.max_conditions),
+            max_in_list: max(self.max_in_list, other.max_in_list),
+            max_joins: max(self.max_joins, other.max_joins),
+            max_candidate_zones: max(self.max_candidate_zones, other.max_candidate_zones),
+            on_excess_zones: self.on_excess_zones.max(other.on_excess_zones),
+        }
+    }
+
+    fn base(cfg: &QueryComplexityConfig) -> Self {
+        Self {
+            max_conditions: cfg.max_conditions,
+            max_in_list: cfg.max_in_list,
+            max_joins: cfg.max_joins,
+            max_candidate_zones: cfg.max_candidate_zones,
+            on_excess_zones: cfg.on_excess_zones,
+        }
+    }
+
+    fn config() -> Option<&'static QueryComplexityConfig> {
+        CONFIG.query.as_ref()?.complexity.as_ref()
+    }
+}
+
+impl From<&ComplexityLimitsConfig> for ComplexityLimits {
+    fn from(cfg: &ComplexityLimitsConfig) -> Self {
+        Self {
+            max_conditions: cfg.max_conditions,
+            max_in_list: cfg.max_in_list,
+            max_joins: cfg.max_joins,
+            max_candidate_zones: cfg.max_candidate_zones,
+            on_excess_zones: cfg.on_excess_zones,
+        }
+    }
+}
+
+/// Estimates how many zones a query will scan. Zones picked by the planner are
+/// counted directly; otherwise every flushed zone of the queried event types is
+/// counted, less those ending before `SINCE` on a plain event query. That is an
+/// upper bound before shard-side pruning.
+pub async fn estimate_candidate_zones(ctx: &QueryContext<'_>, plan: Option<&PlanOutcome>) -> usize {
+    if let Some(picked) = plan.and_then(|plan| plan.picked_zones.as_ref()) {
+        return picked.values().map(|zones| zones.zones.len()).sum();
+    }
+
+    let Command::Query {
+        event_type,
+        event_sequence,
+        since,
+        time_field,
+        aggs,
+        ..
+    } = ctx.command
+    else {
+        return 0;
+    };
+    // Aggregations drop the SINCE filter from zone pruning, so they scan every zone
+    let since = since
+        .as_deref()
+        .filter(|_| aggs.is_none() && time_field.as_deref().unwrap_or("timestamp") == "timestamp")
+        .and_then(since_epoch_seconds);
+
+    let mut event_types = vec![event_type.clone()];
+    if let Some(sequence) = event_sequence {
+        event_types.extend(
+            sequence
+                .links
+                .iter()
+                .map(|(_, target)| target.event.clone()),
+        );
+    }
+    let uids: Vec<String> = {
+        let registry = ctx.registry.read().await;
+        event_types
+            .iter()
+            .filter_map(|event_type| registry.get_uid(event_type))
+            .collect()
+    };
+    if uids.is_empty() {
+        return 0;
+    }
+
+    let shard_info = ctx
+        .shard_manager
+        .all_shards()
+        .iter()
+        .map(|shard| (shard.id, shard.base_dir.clone()))
+        .collect();
+    let shard_data = SegmentDiscovery::discover_all(shard_info).await;
+
+    tokio::task::spawn_blocking(move || {
+        let mut zones = 0;
+        for shard in shard_data.values() {
+            for segment in &shard.segments {
+                let segment_dir = shard.base_dir.join(segment);
+                for uid in &uids {
+                    let path = segment_dir.join(format!("{}.zones", uid));
+                    if path.exists() {
+                        zones += ZoneMeta::load(&path)
+                            .map(|metas| {
+                                metas
+                                    .iter()
+                                    .filter(|meta| since.is_none_or(|ts| meta.timestamp_max >= ts))
+                                    .count()
+                            })
+                            .unwrap_or(0);
+                    }
+                }
+            }
+        }
+        zones
+    })
+    .await
+    .unwrap_or(0)
+}
+
+/// `SINCE` as epoch seconds, read the way the shards' time filter reads it.
+fn since_epoch_seconds(since: &str) -> Option<u64> {
+    TimeParser::parse_str_to_epoch_seconds(since, TimeKind::DateTime)
+        .or_else(|| since.parse::<i64>().ok())
+        .map(|ts| ts.max(0) as u64)
+}
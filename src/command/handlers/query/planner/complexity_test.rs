@@ -0,0 +1,192 @@
+use super::complexity::{COMPLEXITY_ERROR_PREFIX, ComplexityLimits, QueryComplexity};
+use crate::command::parser::commands::query::parse;
+use crate::shared::config::{ComplexityLimitsConfig, ExcessZonesAction, QueryComplexityConfig};
+use std::collections::HashMap;
+
+#[test]
+fn of_counts_conditions_in_lists_and_joins() {
+    let command = parse(
+        r#"QUERY page_view FOLLOWED BY order_created LINKED BY user_id WHERE country IN ("NL", "DE", "FR") AND (plan = "pro" OR NOT amount > 10)"#,
+    )
+    .expect("parse");
+
+    let complexity = QueryComplexity::of(&command);
+
+    assert_eq!(complexity.conditions, 3);
+    assert_eq!(complexity.largest_in_list, 3);
+    assert_eq!(complexity.joins, 1);
+    assert_eq!(complexity.candidate_zones, None);
+}
+
+#[test]
+fn check_explains_which_limit_was_hit() {
+    let limits = ComplexityLimits {
+        max_conditions: Some(10),
+        max_in_list: Some(2),
+        ..Default::default()
+    };
+    let complexity = QueryComplexity {
+        conditions: 4,
+        largest_in_list: 3,
+        ..Default::default()
+    };
+
+    let err = limits.check(&complexity).unwrap_err();
+
+    assert!(err.starts_with(COMPLEXITY_ERROR_PREFIX));
+    assert!(err.contains("IN list has 3 values, limit is 2 (max_in_list)"));
+}
+
+#[test]
+fn check_uses_candidate_zone_estimate() {
+    let limits = ComplexityLimits {
+        max_candidate_zones: Some(100),
+        ..Default::default()
+    };
+    assert!(limits.needs_zone_estimate());
+
+    let within = QueryComplexity::default().with_candidate_zones(100);
+    assert!(limits.check(&within).is_ok());
+
+    let over = QueryComplexity::default().with_candidate_zones(101);
+    let err = limits.check(&over).unwrap_err();
+    assert!(err.contains("estimated 101 zones, limit is 100 (max_candidate_zones)"));
+}
+
+#[test]
+fn unlimited_accepts_any_query() {
+    let limits = ComplexityLimits::default();
+    assert!(limits.is_unlimited());
+    assert!(!limits.needs_zone_estimate());
+
+    let complexity = QueryComplexity {
+        conditions: 10_000,
+        largest_in_list: 10_000,
+        joins: 10,
+        candidate_zones: Some(1_000_000),
+        allow_full_scan: false,
+    };
+    assert!(limits.check(&complexity).is_ok());
+}
+
+#[test]
+fn from_settings_applies_role_then_user_overrides() {
+    let cfg = QueryComplexityConfig {
+        max_conditions: Some(10),
+        max_in_list: Some(100),
+        max_joins: Some(2),
+        max_candidate_zones: Some(1_000),
+        on_excess_zones: None,
+        roles: HashMap::from([
+            (
+                "analyst".to_string(),
+                ComplexityLimitsConfig {
+                    max_candidate_zones: Some(5_000),
+                    ..Default::default()
+                },
+            ),
+            (
+                "admin".to_string(),
+                ComplexityLimitsConfig {
+                    max_candidate_zones: Some(50_000),
+                    max_joins: Some(8),
+                    ..Default::default()
+                },
+            ),
+        ]),
+        users: HashMap::from([(
+            "etl".to_string(),
+            ComplexityLimitsConfig {
+                max_in_list: Some(10_000),
+                ..Default::default()
+            },
+        )]),
+    };
+
+    let base = ComplexityLimits::from_settings(&cfg, "someone", &[]);
+    assert_eq!(base.max_candidate_zones, Some(1_000));
+
+    let roles = vec!["analyst".to_string(), "admin".to_string()];
+    let admin = ComplexityLimits::from_settings(&cfg, "alice", &roles);
+    assert_eq!(admin.max_candidate_zones, Some(50_000));
+    assert_eq!(admin.max_joins, Some(8));
+    assert_eq!(admin.max_conditions, Some(10));
+
+    let etl = ComplexityLimits::from_settings(&cfg, "etl", &["analyst".to_string()]);
+    assert_eq!(etl.max_in_list, Some(10_000));
+    assert_eq!(etl.max_candidate_zones, Some(5_000));
+}
+
+#[test]
+fn require_hint_lets_hinted_queries_past_the_zone_limit() {
+    let limits = ComplexityLimits {
+        max_candidate_zones: Some(100),
+        on_excess_zones: Some(ExcessZonesAction::RequireHint),
+        ..Default::default()
+    };
+    let plain = QueryComplexity::of(&parse("QUERY orders").expect("parse"));
+    let hinted = QueryComplexity::of(&parse("QUERY /* allow_full_scan */ orders").expect("parse"));
+    assert!(hinted.allow_full_scan);
+
+    assert!(
+        limits
+            .check(&plain.clone().with_candidate_zones(100))
+            .is_ok()
+    );
+    let err = limits.check(&plain.with_candidate_zones(250)).unwrap_err();
+    assert!(err.contains("estimated 250 zones, limit is 100 (max_candidate_zones)"));
+    assert!(err.contains("add /* allow_full_scan */"));
+    assert!(
+        limits
+            .check(&hinted.clone().with_candidate_zones(250))
+            .is_ok()
+    );
+
+    // Without require_hint the hint does not bypass the limit
+    let reject = ComplexityLimits {
+        on_excess_zones: None,
+        ..limits
+    };
+    let err = reject.check(&hinted.with_candidate_zones(250)).unwrap_err();
+    assert!(!err.contains("allow_full_scan"));
+}
+
+#[test]
+fn from_settings_resolves_excess_zones_action() {
+    let cfg = QueryComplexityConfig {
+        max_candidate_zones: Some(1_000),
+        on_excess_zones: Some(ExcessZonesAction::Reject),
+        roles: HashMap::from([
+            (
+                "analyst".to_string(),
+                ComplexityLimitsConfig {
+                    on_excess_zones: Some(ExcessZonesAction::RequireHint),
+                    ..Default::default()
+                },
+            ),
+            ("viewer".to_string(), ComplexityLimitsConfig::default()),
+        ]),
+        users: HashMap::from([(
+            "intern".to_string(),
+            ComplexityLimitsConfig {
+                on_excess_zones: Some(ExcessZonesAction::Reject),
+                ..Default::default()
+            },
+        )]),
+        ..Default::default()
+    };
+
+    let base = ComplexityLimits::from_settings(&cfg, "someone", &[]);
+    assert_eq!(base.on_excess_zones, Some(ExcessZonesAction::Reject));
+
+    let roles = vec!["viewer".to_string(), "analyst".to_string()];
+    let analyst = ComplexityLimits::from_settings(&cfg, "alice", &roles);
+    assert_eq!(
+        analyst.on_excess_zones,
+        Some(ExcessZonesAction::RequireHint)
+    );
+    assert_eq!(analyst.max_candidate_zones, Some(1_000));
+
+    let intern = ComplexityLimits::from_settings(&cfg, "intern", &roles);
+    assert_eq!(intern.on_excess_zones, Some(ExcessZonesAction::Reject));
+}
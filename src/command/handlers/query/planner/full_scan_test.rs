@@ -23,6 +23,14 @@ fn create_context() -> QueryContext<'static> {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     }));
 
     let manager = Box::leak(Box::new(ShardManager { shards: Vec::new() }));
@@ -1,20 +1,36 @@
 mod builder;
+mod complexity;
 mod full_scan;
 mod plan_outcome;
 mod rlte;
+mod sequence_window;
+mod total_count;
 mod traits;
+mod view_match;
 
 #[cfg(test)]
 mod builder_test;
 #[cfg(test)]
+mod complexity_test;
+#[cfg(test)]
 mod full_scan_test;
 #[cfg(test)]
 mod plan_outcome_test;
 #[cfg(test)]
 mod rlte_test;
 #[cfg(test)]
+mod total_count_test;
+#[cfg(test)]
 mod traits_test;
+#[cfg(test)]
+mod view_match_test;
 
 pub use builder::QueryPlannerBuilder;
+pub use complexity::{
+    COMPLEXITY_ERROR_PREFIX, ComplexityLimits, QueryComplexity, estimate_candidate_zones,
+};
 pub use plan_outcome::PlanOutcome;
+pub use sequence_window::sequence_window_bounds;
+pub use total_count::{TotalCount, count_total};
 pub use traits::QueryPlanner;
+pub use view_match::{ViewLookup, ViewMatch, materialized_views_dir};
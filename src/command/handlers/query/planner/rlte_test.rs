@@ -31,6 +31,14 @@ fn create_context_with_shard_dirs() -> (QueryContext<'static>, TempDir) {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     }));
 
     let (tx, _rx) = tokio::sync::mpsc::channel(10);
@@ -38,6 +46,7 @@ fn create_context_with_shard_dirs() -> (QueryContext<'static>, TempDir) {
         shards: vec![Shard {
             id: 0,
             base_dir: shard_dir.clone(),
+            wal_dir: shard_dir.clone(),
             tx,
         }],
     }));
@@ -82,6 +91,14 @@ async fn build_plan_handles_empty_shards() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     }));
 
     let manager = Box::leak(Box::new(ShardManager { shards: Vec::new() }));
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use crate::command::handlers::query::context::QueryContext;
+use crate::command::handlers::segment_discovery::SegmentDiscovery;
+use crate::command::types::Command;
+use crate::engine::core::ZoneMeta;
+use crate::engine::core::read::sequence::SequenceMatcher;
+use crate::engine::shard::message::ShardMessage;
+use tokio::sync::oneshot;
+use tracing::warn;
+
+/// Timestamp range the events of each type of a `WITHIN` sequence query must
+/// fall in, worked out from zone metadata and the shards' in-memory buffers
+/// before any zone is read. Types left out cannot take part in any match.
+///
+/// `None` when the query has no window, times its sequence by a field other
+/// than `timestamp`, or a shard or segment could not be read.
+pub async fn sequence_window_bounds(
+    ctx: &QueryContext<'_>,
+    event_types: &[String],
+) -> Option<HashMap<String, (u64, u64)>> {
+    let Command::Query {
+        event_sequence: Some(sequence),
+        sequence_time_field,
+        ..
+    } = ctx.command
+    else {
+        return None;
+    };
+    sequence.within?;
+    if sequence_time_field
+        .as_deref()
+        .is_some_and(|field| field != "timestamp")
+    {
+        return None;
+    }
+
+    let spans = timestamp_spans(ctx, event_types).await?;
+    SequenceMatcher::new(sequence.clone(), "timestamp".to_string()).window_bounds(&spans)
+}
+
+/// Earliest and latest timestamp of each event type; types without any event
+/// are left out.
+async fn timestamp_spans(
+    ctx: &QueryContext<'_>,
+    event_types: &[String],
+) -> Option<HashMap<String, (u64, u64)>> {
+    let shards = ctx.shard_manager.all_shards();
+    let mut spans = HashMap::new();
+
+    // Buffers go first: events flushed meanwhile then show up in a segment
+    // found below instead of slipping between the two
+    for event_type in event_types {
+        for shard in shards {
+            let (tx, rx) = oneshot::channel();
+            let message = ShardMessage::BufferedTimeSpan {
+                event_type: event_type.clone(),
+                response: tx,
+            };
+            if shard.tx.send(message).await.is_err() {
+                warn!(target: "sneldb::query", shard_id = shard.id, "Buffered time span dispatch failed");
+                return None;
+            }
+            if let Some(span) = rx.await.ok()? {
+                widen(&mut spans, event_type, span);
+            }
+        }
+    }
+
+    let uids: Vec<(String, String)> = {
+        let registry = ctx.registry.read().await;
+        event_types
+            .iter()
+            .filter_map(|event_type| Some((event_type.clone(), registry.get_uid(event_type)?)))
+            .collect()
+    };
+    let shard_info = shards
+        .iter()
+        .map(|shard| (shard.id, shard.base_dir.clone()))
+        .collect();
+    let shard_data = SegmentDiscovery::discover_all(shard_info).await;
+    tokio::task::spawn_blocking(move || {
+        for shard in shard_data.values() {
+            for segment in &shard.segments {
+                for (event_type, uid) in &uids {
+                    let path = shard.base_dir.join(segment).join(format!("{}.zones", uid));
+                    if !path.exists() {
+                        continue;
+                    }
+                    // Without the segment's metadata a span could come out too narrow
+                    for meta in ZoneMeta::load(&path).ok()? {
+                        widen(
+                            &mut spans,
+                            event_type,
+                            (meta.timestamp_min, meta.timestamp_max),
+                        );
+                    }
+                }
+            }
+        }
+        Some(spans)
+    })
+    .await
+    .ok()?
+}
+
+fn widen(spans: &mut HashMap<String, (u64, u64)>, event_type: &str, (lo, hi): (u64, u64)) {
+    spans
+        .entry(event_type.to_string())
+        .and_modify(|span| *span = (span.0.min(lo), span.1.max(hi)))
+        .or_insert((lo, hi));
+}
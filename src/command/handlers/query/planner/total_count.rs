@@ -0,0 +1,208 @@
+use crate::command::handlers::query::context::QueryContext;
+use crate::command::handlers::segment_discovery::SegmentDiscovery;
+use crate::command::types::{Command, CompareOp, Expr};
+use crate::engine::core::ZoneMeta;
+use crate::engine::shard::message::{BufferedCount, ShardMessage};
+use tokio::sync::oneshot;
+use tracing::warn;
+
+/// Number of rows a query matches before LIMIT and OFFSET, taken from zone
+/// metadata and the shards' in-memory buffers instead of a scan. An estimate
+/// counts every row of each zone that may match, so it never falls below the
+/// rows the query can return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TotalCount {
+    pub rows: u64,
+    pub exact: bool,
+}
+
+/// The part of a query's filter that metadata can decide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountScope {
+    pub event_type: String,
+    pub context_id: Option<String>,
+    /// Inclusive timestamp bounds implied by the WHERE clause
+    pub ts_min: u64,
+    pub ts_max: u64,
+    /// Whether any filter besides the timestamp bounds and context remains
+    pub residual: bool,
+}
+
+/// How the rows of a zone relate to the timestamp bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneOverlap {
+    Outside,
+    Partial,
+    Inside,
+}
+
+impl CountScope {
+    /// `None` for queries whose result rows are not the matching events of one
+    /// type: aggregates, sequences, `LATEST PER`, `DEDUP BY`, `UNNEST` and
+    /// wildcard event types.
+    pub fn of(command: &Command) -> Option<Self> {
+        let Command::Query {
+            event_type,
+            context_id,
+            since,
+            where_clause,
+            aggs,
+            time_bucket,
+            group_by,
+            event_sequence,
+            latest_per,
+            dedup,
+            unnest,
+            ..
+        } = command
+        else {
+            return None;
+        };
+        if event_type == "*"
+            || aggs.is_some()
+            || time_bucket.is_some()
+            || group_by.is_some()
+            || event_sequence.is_some()
+            || latest_per.is_some()
+            || dedup.is_some()
+            || unnest.is_some()
+        {
+            return None;
+        }
+
+        let mut scope = Self {
+            event_type: event_type.clone(),
+            context_id: context_id.clone(),
+            ts_min: 0,
+            ts_max: u64::MAX,
+            residual: since.is_some(),
+        };
+        if let Some(expr) = where_clause {
+            scope.narrow(expr);
+        }
+        Some(scope)
+    }
+
+    /// Tightens the bounds by the timestamp comparisons among the AND-ed terms
+    /// of `expr`; every other term is left to the scan.
+    fn narrow(&mut self, expr: &Expr) {
+        match expr {
+            Expr::And(left, right) => {
+                self.narrow(left);
+                self.narrow(right);
+            }
+            Expr::Compare { field, op, value } if field == "timestamp" => {
+                let Some(v) = value.as_u64() else {
+                    self.residual = true;
+                    return;
+                };
+                match op {
+                    CompareOp::Gt => self.ts_min = self.ts_min.max(v.saturating_add(1)),
+                    CompareOp::Gte => self.ts_min = self.ts_min.max(v),
+                    CompareOp::Lt => match v.checked_sub(1) {
+                        Some(max) => self.ts_max = self.ts_max.min(max),
+                        None => self.ts_min = u64::MAX,
+                    },
+                    CompareOp::Lte => self.ts_max = self.ts_max.min(v),
+                    CompareOp::Eq => {
+                        self.ts_min = self.ts_min.max(v);
+                        self.ts_max = self.ts_max.min(v);
+                    }
+                    CompareOp::Neq | CompareOp::In => self.residual = true,
+                }
+            }
+            _ => self.residual = true,
+        }
+    }
+
+    pub fn overlap(&self, timestamp_min: u64, timestamp_max: u64) -> ZoneOverlap {
+        if timestamp_max < self.ts_min || timestamp_min > self.ts_max {
+            ZoneOverlap::Outside
+        } else if timestamp_min >= self.ts_min && timestamp_max <= self.ts_max {
+            ZoneOverlap::Inside
+        } else {
+            ZoneOverlap::Partial
+        }
+    }
+
+    /// Adds the rows of flushed zones. Zone metadata cannot tell contexts or
+    /// payload values apart, so those filters make the count an estimate.
+    pub fn count_zones(&self, metas: &[ZoneMeta], total: &mut TotalCount) {
+        let decided = !self.residual && self.context_id.is_none();
+        for meta in metas {
+            let overlap = self.overlap(meta.timestamp_min, meta.timestamp_max);
+            if overlap == ZoneOverlap::Outside {
+                continue;
+            }
+            total.rows += u64::from(meta.end_row - meta.start_row) + 1;
+            if overlap == ZoneOverlap::Partial || !decided {
+                total.exact = false;
+            }
+        }
+    }
+}
+
+/// Counts the rows `ctx.command` matches, or `None` when the query shape has no
+/// cheap count or a shard did not answer.
+pub async fn count_total(ctx: &QueryContext<'_>) -> Option<TotalCount> {
+    let scope = CountScope::of(ctx.command)?;
+    let uid = ctx.registry.read().await.get_uid(&scope.event_type)?;
+
+    // A context lives on a single shard
+    let shards = match &scope.context_id {
+        Some(context_id) => vec![ctx.shard_manager.get_shard(context_id)],
+        None => ctx.shard_manager.all_shards().iter().collect(),
+    };
+
+    let mut total = TotalCount {
+        rows: 0,
+        exact: true,
+    };
+    for shard in &shards {
+        let (tx, rx) = oneshot::channel();
+        let message = ShardMessage::CountBuffered {
+            event_type: scope.event_type.clone(),
+            context_id: scope.context_id.clone(),
+            ts_min: scope.ts_min,
+            ts_max: scope.ts_max,
+            response: tx,
+        };
+        if shard.tx.send(message).await.is_err() {
+            warn!(target: "sneldb::query", shard_id = shard.id, "Total count dispatch failed");
+            return None;
+        }
+        let BufferedCount {
+            rows,
+            flush_pending,
+        } = rx.await.ok()?;
+        total.rows += rows;
+        // Rows of a flush in progress may show up in both its buffer and its segment
+        if scope.residual || flush_pending {
+            total.exact = false;
+        }
+    }
+
+    let shard_info = shards
+        .iter()
+        .map(|shard| (shard.id, shard.base_dir.clone()))
+        .collect();
+    let shard_data = SegmentDiscovery::discover_all(shard_info).await;
+    tokio::task::spawn_blocking(move || {
+        for shard in shard_data.values() {
+            for segment in &shard.segments {
+                let path = shard.base_dir.join(segment).join(format!("{}.zones", uid));
+                if !path.exists() {
+                    continue;
+                }
+                match ZoneMeta::load(&path) {
+                    Ok(metas) => scope.count_zones(&metas, &mut total),
+                    // Without the segment's metadata the count could come out too low
+                    Err(_) => return None,
+                }
+            }
+        }
+        Some(total)
+    })
+    .await
+    .ok()?
+}
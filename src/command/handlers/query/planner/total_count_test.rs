@@ -0,0 +1,99 @@
+use super::total_count::{CountScope, TotalCount, ZoneOverlap};
+use crate::command::parser::commands::query::parse;
+use crate::engine::core::ZoneMeta;
+
+fn scope_of(query: &str) -> Option<CountScope> {
+    CountScope::of(&parse(query).expect("parse"))
+}
+
+fn zone(start_row: u32, end_row: u32, timestamp_min: u64, timestamp_max: u64) -> ZoneMeta {
+    ZoneMeta {
+        zone_id: 0,
+        uid: "uid".to_string(),
+        segment_id: 1,
+        start_row,
+        end_row,
+        timestamp_min,
+        timestamp_max,
+        created_at: 0,
+        event_id_min: 0,
+        event_id_max: u64::MAX,
+    }
+}
+
+#[test]
+fn of_reads_timestamp_bounds_from_and_terms() {
+    let scope = scope_of(r#"QUERY orders WHERE timestamp >= 100 AND timestamp < 200"#).unwrap();
+
+    assert_eq!(scope.event_type, "orders");
+    assert_eq!((scope.ts_min, scope.ts_max), (100, 199));
+    assert!(!scope.residual);
+}
+
+#[test]
+fn of_marks_other_filters_as_residual() {
+    let scope = scope_of(r#"QUERY orders WHERE timestamp > 100 AND amount > 10"#).unwrap();
+    assert_eq!((scope.ts_min, scope.ts_max), (101, u64::MAX));
+    assert!(scope.residual);
+
+    let scope = scope_of(r#"QUERY orders WHERE timestamp > 100 OR timestamp < 50"#).unwrap();
+    assert_eq!((scope.ts_min, scope.ts_max), (0, u64::MAX));
+    assert!(scope.residual);
+}
+
+#[test]
+fn of_skips_queries_without_per_event_rows() {
+    assert!(scope_of(r#"QUERY orders COUNT"#).is_none());
+    assert!(scope_of(r#"QUERY page_view FOLLOWED BY order_created LINKED BY user_id"#).is_none());
+}
+
+#[test]
+fn overlap_classifies_zone_ranges() {
+    let scope = scope_of(r#"QUERY orders WHERE timestamp >= 100 AND timestamp <= 200"#).unwrap();
+
+    assert_eq!(scope.overlap(0, 99), ZoneOverlap::Outside);
+    assert_eq!(scope.overlap(201, 300), ZoneOverlap::Outside);
+    assert_eq!(scope.overlap(100, 200), ZoneOverlap::Inside);
+    assert_eq!(scope.overlap(50, 150), ZoneOverlap::Partial);
+}
+
+#[test]
+fn count_zones_is_exact_only_for_fully_covered_zones() {
+    let scope = scope_of(r#"QUERY orders WHERE timestamp >= 100"#).unwrap();
+    let mut total = TotalCount {
+        rows: 0,
+        exact: true,
+    };
+
+    scope.count_zones(&[zone(0, 9, 100, 150), zone(10, 19, 0, 50)], &mut total);
+    assert_eq!(
+        total,
+        TotalCount {
+            rows: 10,
+            exact: true
+        }
+    );
+
+    scope.count_zones(&[zone(20, 29, 50, 150)], &mut total);
+    assert_eq!(
+        total,
+        TotalCount {
+            rows: 20,
+            exact: false
+        }
+    );
+}
+
+#[test]
+fn count_zones_estimates_when_filters_remain() {
+    let scope = scope_of(r#"QUERY orders FOR ctx-1 WHERE timestamp >= 100"#).unwrap();
+    let mut total = TotalCount {
+        rows: 0,
+        exact: true,
+    };
+
+    scope.count_zones(&[zone(0, 9, 100, 150)], &mut total);
+
+    assert_eq!(total.rows, 10);
+    assert!(!total.exact);
+}
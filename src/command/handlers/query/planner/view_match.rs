@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+
+use crate::command::types::{Command, QueryCommand};
+use crate::engine::core::read::aggregate::plan::AggregatePlan;
+use crate::engine::core::read::deterministic;
+use crate::engine::materialize::{
+    MaterializationCatalog, MaterializationEntry, MaterializedQuerySpecExt,
+};
+use crate::shared::config::CONFIG;
+use crate::shared::path::absolutize;
+
+/// The only time field a view may bucket and refresh on. It is assigned at
+/// ingest, so no event can land behind a view's high-water mark later.
+const INGEST_TIME_FIELD: &str = "timestamp";
+
+/// Data directory whose materialized views may answer queries, unless
+/// `query.use_materialized_views` turns the rewrite off. Deterministic mode
+/// always scans raw events, since a view's contents depend on refresh timing.
+pub fn materialized_views_dir() -> Option<PathBuf> {
+    let enabled = !deterministic::enabled()
+        && CONFIG
+            .query
+            .as_ref()
+            .and_then(|cfg| cfg.use_materialized_views)
+            .unwrap_or(true);
+    enabled.then(|| absolutize(PathBuf::from(CONFIG.engine.data_dir.as_str())))
+}
+
+/// A materialized aggregate view that gives the same answer as the query.
+#[derive(Debug, Clone)]
+pub struct ViewMatch {
+    pub entry: MaterializationEntry,
+    pub plan: AggregatePlan,
+}
+
+/// The views over a query's event type: the one it can be answered from, if
+/// any, and why each of the others cannot be used.
+#[derive(Debug, Default)]
+pub struct ViewLookup {
+    pub matched: Option<ViewMatch>,
+    pub rejected: Vec<(String, &'static str)>,
+}
+
+impl ViewLookup {
+    /// Checks the materializations under `data_dir` against `command`. A
+    /// catalog that cannot be read counts as having no views.
+    pub fn find(command: &Command, data_dir: &Path) -> Self {
+        let mut lookup = Self::default();
+        let Command::Query {
+            event_type,
+            aggs: Some(_),
+            ..
+        } = command
+        else {
+            return lookup;
+        };
+
+        // Loading creates an empty catalog, which a read should not do
+        if !data_dir
+            .join("materializations")
+            .join("catalog.mcat")
+            .exists()
+        {
+            return lookup;
+        }
+        let entries = match MaterializationCatalog::load(data_dir).and_then(|c| c.entries()) {
+            Ok(entries) => entries,
+            Err(err) => {
+                warn!(
+                    target: "sneldb::query",
+                    error = %err,
+                    "Failed to load materialization catalog, skipping view rewrite"
+                );
+                return lookup;
+            }
+        };
+
+        let mut entries: Vec<_> = entries
+            .into_values()
+            .filter(|entry| entry.spec.query().event_type() == event_type)
+            .collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for entry in entries {
+            let verdict = view_answers(entry.spec.query(), command).and_then(|()| {
+                if entry.high_water_mark.is_none_or(|mark| mark.is_zero()) {
+                    Err("view holds no events yet")
+                } else {
+                    Ok(())
+                }
+            });
+            match verdict {
+                Ok(()) if lookup.matched.is_none() => {
+                    let plan = entry
+                        .spec
+                        .aggregate_plan()
+                        .expect("matched views are aggregate views");
+                    lookup.matched = Some(ViewMatch { entry, plan });
+                }
+                Ok(()) => lookup
+                    .rejected
+                    .push((entry.name, "another view already matches")),
+                Err(reason) => lookup.rejected.push((entry.name, reason)),
+            }
+        }
+        lookup
+    }
+}
+
+/// Checks whether the aggregate view remembered from `view` gives exactly the
+/// answer of `query`, once topped up with the events past its high-water
+/// mark. Everything that decides which events land in which group must be
+/// identical; only ORDER BY, LIMIT and OFFSET may differ, since they apply
+/// when the view is read.
+pub fn view_answers(view: &Command, query: &Command) -> Result<(), &'static str> {
+    if !matches!(view, Command::Query { .. }) || !matches!(query, Command::Query { .. }) {
+        return Err("not a query");
+    }
+    let view = QueryCommand::from(view);
+    let query = QueryCommand::from(query);
+
+    if view.aggs.is_none() {
+        return Err("view stores rows, not aggregates");
+    }
+    if query.aggs.is_none() {
+        return Err("query has no aggregates");
+    }
+    if [&view, &query].iter().any(|q| {
+        q.event_sequence.is_some()
+            || q.link_field.is_some()
+            || q.latest_per.is_some()
+            || q.dedup.is_some()
+            || q.picked_zones.is_some()
+    }) {
+        return Err("sequences, LATEST PER and DEDUP BY are not served from views");
+    }
+    if query.with_total {
+        return Err("WITH TOTAL needs a scan");
+    }
+    if !query.disabled_pruners.is_empty() {
+        return Err("DISABLE PRUNERS needs a scan");
+    }
+    if view.event_type != query.event_type {
+        return Err("different event type");
+    }
+    if view.aggs != query.aggs {
+        return Err("different aggregates");
+    }
+    if view.group_by != query.group_by {
+        return Err("different grouping");
+    }
+    if view.time_bucket != query.time_bucket {
+        return Err("different time bucket");
+    }
+    if view.where_clause != query.where_clause {
+        return Err("different filter");
+    }
+    if view.context_id != query.context_id {
+        return Err("different context");
+    }
+    if view.since != query.since {
+        return Err("different SINCE");
+    }
+    if view.return_fields != query.return_fields {
+        return Err("different RETURN fields");
+    }
+    if view.sequence_time_field != query.sequence_time_field {
+        return Err("different time field");
+    }
+    let time_field = |q: &QueryCommand| {
+        q.time_field
+            .clone()
+            .unwrap_or_else(|| INGEST_TIME_FIELD.to_string())
+    };
+    if time_field(&view) != INGEST_TIME_FIELD || time_field(&query) != INGEST_TIME_FIELD {
+        return Err("time field is not the ingest timestamp");
+    }
+    Ok(())
+}
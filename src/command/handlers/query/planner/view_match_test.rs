@@ -0,0 +1,80 @@
+use super::view_match::view_answers;
+use crate::command::parser::commands::query::parse;
+
+const VIEW: &str = r#"QUERY orders WHERE status = "paid" COUNT, TOTAL amount PER HOUR BY region"#;
+
+fn answers(view: &str, query: &str) -> Result<(), &'static str> {
+    view_answers(
+        &parse(view).expect("parse view"),
+        &parse(query).expect("parse query"),
+    )
+}
+
+#[test]
+fn identical_aggregate_query_is_answered() {
+    assert_eq!(answers(VIEW, VIEW), Ok(()));
+}
+
+#[test]
+fn ordering_and_limits_may_differ() {
+    let query = r#"QUERY orders WHERE status = "paid" COUNT, TOTAL amount PER HOUR BY region ORDER BY count DESC LIMIT 5"#;
+    assert_eq!(answers(VIEW, query), Ok(()));
+}
+
+#[test]
+fn anything_that_changes_the_groups_is_rejected() {
+    let cases = [
+        (
+            r#"QUERY orders WHERE status = "open" COUNT, TOTAL amount PER HOUR BY region"#,
+            "different filter",
+        ),
+        (
+            r#"QUERY orders WHERE status = "paid" COUNT, TOTAL amount PER DAY BY region"#,
+            "different time bucket",
+        ),
+        (
+            r#"QUERY orders WHERE status = "paid" COUNT, TOTAL amount PER HOUR BY country"#,
+            "different grouping",
+        ),
+        (
+            r#"QUERY orders WHERE status = "paid" TOTAL amount, COUNT PER HOUR BY region"#,
+            "different aggregates",
+        ),
+        (
+            r#"QUERY orders SINCE "2024-01-01T00:00:00Z" WHERE status = "paid" COUNT, TOTAL amount PER HOUR BY region"#,
+            "different SINCE",
+        ),
+        (
+            r#"QUERY refunds WHERE status = "paid" COUNT, TOTAL amount PER HOUR BY region"#,
+            "different event type",
+        ),
+        (
+            r#"QUERY orders WHERE status = "paid" COUNT, TOTAL amount PER HOUR BY region WITH TOTAL"#,
+            "WITH TOTAL needs a scan",
+        ),
+    ];
+    for (query, reason) in cases {
+        assert_eq!(answers(VIEW, query), Err(reason), "{query}");
+    }
+}
+
+#[test]
+fn views_on_a_payload_time_field_are_never_used() {
+    let view = "QUERY orders COUNT PER HOUR USING created_at";
+    assert_eq!(
+        answers(view, view),
+        Err("time field is not the ingest timestamp")
+    );
+}
+
+#[test]
+fn row_views_and_row_queries_are_rejected() {
+    assert_eq!(
+        answers("QUERY orders", VIEW),
+        Err("view stores rows, not aggregates")
+    );
+    assert_eq!(
+        answers(VIEW, "QUERY orders"),
+        Err("query has no aggregates")
+    );
+}
@@ -3,4 +3,4 @@ mod response_writer;
 #[cfg(test)]
 mod response_writer_test;
 
-pub use response_writer::QueryResponseWriter;
+pub use response_writer::{OutputBatching, QueryResponseWriter};
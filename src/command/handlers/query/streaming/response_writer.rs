@@ -1,15 +1,68 @@
 use std::collections::HashSet;
 use std::io;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
+use tokio::time::Instant;
 
+use crate::command::handlers::query::planner::TotalCount;
 use crate::command::handlers::query_batch_stream::QueryBatchStream;
-use crate::engine::core::read::flow::BatchSchema;
+use crate::engine::core::read::deterministic;
+use crate::engine::core::read::flow::{BatchSchema, ColumnBatch};
 use crate::engine::types::ScalarValue;
 use crate::shared::config::CONFIG;
-use crate::shared::response::ArrowStreamEncoder;
 use crate::shared::response::render::{Renderer, StreamingFormat};
+use crate::shared::response::{ArrowStreamEncoder, ErrorCode, Response, StatusCode};
+
+/// Output framing for streamed responses, independent of the internal `ColumnBatch` size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputBatching {
+    /// Rows per JSON batch frame (0 = one frame per row).
+    pub rows: usize,
+    /// Flush to the client once this many encoded bytes are buffered.
+    pub flush_bytes: usize,
+    /// Max time encoded output may stay buffered before it is flushed.
+    pub max_linger: Option<Duration>,
+}
+
+impl OutputBatching {
+    pub const DEFAULT_ROWS: usize = 1000;
+    pub const DEFAULT_FLUSH_BYTES: usize = 65536;
+
+    pub fn from_config() -> Self {
+        let cfg = CONFIG.query.as_ref();
+        Self {
+            rows: cfg
+                .and_then(|cfg| cfg.streaming_batch_size)
+                .unwrap_or(Self::DEFAULT_ROWS),
+            flush_bytes: cfg
+                .and_then(|cfg| cfg.streaming_flush_bytes)
+                .unwrap_or(Self::DEFAULT_FLUSH_BYTES),
+            // Timed flushes would make frame boundaries depend on timing
+            max_linger: cfg
+                .and_then(|cfg| cfg.streaming_max_linger_ms)
+                .filter(|_| !deterministic::enabled())
+                .map(Duration::from_millis),
+        }
+    }
+}
+
+impl Default for OutputBatching {
+    fn default() -> Self {
+        Self {
+            rows: Self::DEFAULT_ROWS,
+            flush_bytes: Self::DEFAULT_FLUSH_BYTES,
+            max_linger: None,
+        }
+    }
+}
+
+enum NextBatch {
+    Batch(Arc<ColumnBatch>),
+    Linger,
+    End,
+}
 
 pub struct QueryResponseWriter<'a, W: AsyncWrite + Unpin> {
     writer: BufWriter<&'a mut W>,
@@ -17,10 +70,17 @@ pub struct QueryResponseWriter<'a, W: AsyncWrite + Unpin> {
     schema: Arc<BatchSchema>,
     column_metadata: Vec<(String, String)>,
     column_names: Vec<String>,
-    batch_size: usize,
+    batching: OutputBatching,
+    pending_rows: Vec<Vec<ScalarValue>>,
+    unflushed_bytes: usize,
+    buffered_since: Option<Instant>,
     encode_buf: Vec<u8>,
     seen_ids: HashSet<u64>,
     event_id_idx: Option<usize>,
+    deduplicate: bool,
+    omit_nulls: bool,
+    raw_strings: bool,
+    total: Option<TotalCount>,
     limit: Option<usize>,
     offset: Option<usize>,
     emitted: usize,
@@ -50,24 +110,26 @@ impl<'a, W: AsyncWrite + Unpin> QueryResponseWriter<'a, W> {
 
         let event_id_idx = column_names.iter().position(|name| name == "event_id");
 
-        let batch_size = CONFIG
-            .query
-            .as_ref()
-            .and_then(|cfg| cfg.streaming_batch_size)
-            .unwrap_or(1000) as usize;
-
-        let encode_capacity = if batch_size > 0 { 65536 } else { 4096 };
+        let batching = OutputBatching::from_config();
+        let encode_capacity = if batching.rows > 0 { 65536 } else { 4096 };
 
         Self {
-            writer: BufWriter::with_capacity(65536, writer),
+            writer: BufWriter::with_capacity(Self::buffer_capacity(&batching), writer),
             renderer,
             schema,
             column_metadata,
             column_names,
-            batch_size,
+            batching,
+            pending_rows: Vec::new(),
+            unflushed_bytes: 0,
+            buffered_since: None,
             encode_buf: Vec::with_capacity(encode_capacity),
             seen_ids: HashSet::new(),
             event_id_idx,
+            deduplicate: true,
+            omit_nulls: false,
+            raw_strings: false,
+            total: None,
             limit: limit.map(|value| value as usize),
             offset: offset.map(|value| value as usize),
             emitted: 0,
@@ -76,93 +138,152 @@ impl<'a, W: AsyncWrite + Unpin> QueryResponseWriter<'a, W> {
         }
     }
 
-    pub async fn write(mut self, stream: QueryBatchStream) -> io::Result<()> {
-        match self.renderer.streaming_format() {
-            StreamingFormat::Json => self.write_json(stream).await,
-            StreamingFormat::Arrow => self.write_arrow(stream).await,
-        }
+    /// Overrides the configured output framing.
+    pub fn with_output_batching(mut self, batching: OutputBatching) -> Self {
+        let capacity = Self::buffer_capacity(&batching);
+        self.writer = BufWriter::with_capacity(capacity, self.writer.into_inner());
+        self.batching = batching;
+        self
     }
 
-    async fn write_json(&mut self, mut stream: QueryBatchStream) -> io::Result<()> {
-        self.renderer
-            .stream_schema(&self.column_metadata, &mut self.encode_buf);
-        self.writer.write_all(&self.encode_buf).await?;
-        self.encode_buf.clear();
+    /// Enables or disables dropping rows whose `event_id` was already emitted (on by default).
+    pub fn with_deduplication(mut self, enabled: bool) -> Self {
+        self.deduplicate = enabled;
+        self
+    }
 
-        let column_count = self.column_names.len();
+    /// Leaves null fields out of JSON row frames instead of writing explicit
+    /// nulls; the schema frame is flagged so clients can tell the two apart.
+    /// Batch frames, Arrow and CSV output keep nulls in place.
+    pub fn with_omit_nulls(mut self, enabled: bool) -> Self {
+        self.omit_nulls = enabled;
+        self
+    }
 
-        while !self.limit_reached {
-            match stream.recv().await {
-                Some(batch_arc) => {
-                    if batch_arc.is_empty() {
-                        continue;
-                    }
+    /// Writes string values verbatim instead of re-parsing those that look like
+    /// JSON. Arrow output always carries strings as stored.
+    pub fn with_raw_strings(mut self, enabled: bool) -> Self {
+        self.raw_strings = enabled;
+        self
+    }
 
-                    let columns = batch_arc.columns_ref();
-                    let mut valid_row_indices = Vec::new();
+    /// Writes a `total` frame with the query's row count, before LIMIT and
+    /// OFFSET, right after the schema frame. Arrow and CSV output have no such frame.
+    pub fn with_total(mut self, total: Option<TotalCount>) -> Self {
+        self.total = total;
+        self
+    }
 
-                    for row_idx in 0..batch_arc.len() {
-                        let event_id = self.event_id_idx.and_then(|idx| {
-                            columns[idx].get(row_idx).and_then(|value| value.as_u64())
-                        });
+    fn buffer_capacity(batching: &OutputBatching) -> usize {
+        batching.flush_bytes.max(4096)
+    }
 
-                        if self.try_accept_row(event_id) {
-                            valid_row_indices.push(row_idx);
-                        }
+    pub async fn write(self, stream: QueryBatchStream) -> io::Result<()> {
+        self.write_counting_rows(stream).await.map(|_| ())
+    }
 
-                        if self.limit_reached {
-                            break;
-                        }
-                    }
+    /// Like [`Self::write`], returning the number of rows written.
+    pub async fn write_counting_rows(mut self, stream: QueryBatchStream) -> io::Result<usize> {
+        match self.renderer.streaming_format() {
+            StreamingFormat::Json | StreamingFormat::MsgPack | StreamingFormat::Ndjson => {
+                self.write_json(stream).await
+            }
+            StreamingFormat::Arrow => self.write_arrow(stream).await,
+            StreamingFormat::Csv => {
+                // CSV records are positional, so nulls always keep their place
+                self.omit_nulls = false;
+                self.write_json(stream).await
+            }
+        }?;
+        Ok(self.emitted)
+    }
 
-                    if valid_row_indices.is_empty() {
-                        continue;
-                    }
+    /// Writes row-oriented output: schema, row or batch frames and the end
+    /// frame, each encoded by the renderer (JSON, CSV, MessagePack or NDJSON).
+    async fn write_json(&mut self, mut stream: QueryBatchStream) -> io::Result<()> {
+        if self.omit_nulls {
+            self.renderer
+                .stream_schema_omitting_nulls(&self.column_metadata, &mut self.encode_buf);
+        } else {
+            self.renderer
+                .stream_schema(&self.column_metadata, &mut self.encode_buf);
+        }
+        self.write_frame().await?;
+        if let Some(total) = self.total {
+            self.renderer
+                .stream_total(total.rows, !total.exact, &mut self.encode_buf);
+            self.write_frame().await?;
+        }
+
+        let column_names = self.column_names.clone();
+        let column_refs_str: Vec<&str> = column_names.iter().map(|s| s.as_str()).collect();
+        let column_count = column_names.len();
 
-                    // Create column_refs_str after mutable borrows are done
-                    let column_refs_str: Vec<&str> =
-                        self.column_names.iter().map(|s| s.as_str()).collect();
+        while !self.limit_reached {
+            let batch_arc = match self.next_batch(&mut stream).await {
+                NextBatch::Batch(_) if stream.failure().is_some() => break,
+                NextBatch::Batch(batch) => batch,
+                NextBatch::Linger => {
+                    self.emit_pending_rows().await?;
+                    self.flush_output().await?;
+                    continue;
+                }
+                NextBatch::End => break,
+            };
+            if batch_arc.is_empty() {
+                continue;
+            }
 
-                    if self.batch_size > 0 {
-                        let batches: Vec<Vec<ScalarValue>> = valid_row_indices
+            let columns = batch_arc.columns_ref();
+
+            for row_idx in 0..batch_arc.len() {
+                let event_id = self
+                    .event_id_idx
+                    .and_then(|idx| columns[idx].get(row_idx).and_then(|value| value.as_u64()));
+
+                if self.try_accept_row(event_id) {
+                    let row_values: Vec<ScalarValue> = (0..column_count)
+                        .map(|col_idx| columns[col_idx][row_idx].clone())
+                        .collect();
+                    if self.batching.rows > 0 {
+                        self.pending_rows.push(row_values);
+                        self.buffered_since.get_or_insert_with(Instant::now);
+                        if self.pending_rows.len() >= self.batching.rows {
+                            self.emit_pending_rows().await?;
+                        }
+                    } else if self.omit_nulls {
+                        let (names, values): (Vec<&str>, Vec<ScalarValue>) = column_refs_str
                             .iter()
-                            .map(|&row_idx| {
-                                (0..column_count)
-                                    .map(|col_idx| columns[col_idx][row_idx].clone())
-                                    .collect()
-                            })
-                            .collect();
-
-                        self.renderer.stream_batch(
-                            &column_refs_str,
-                            &batches,
-                            &mut self.encode_buf,
-                        );
-                        self.writer.write_all(&self.encode_buf).await?;
-                        self.encode_buf.clear();
+                            .zip(row_values)
+                            .filter(|(_, value)| !value.is_null())
+                            .map(|(name, value)| (*name, value))
+                            .unzip();
+                        self.encode_row(&names, &values);
+                        self.write_frame().await?;
                     } else {
-                        for &row_idx in &valid_row_indices {
-                            let row_values: Vec<ScalarValue> = (0..column_count)
-                                .map(|col_idx| columns[col_idx][row_idx].clone())
-                                .collect();
-                            self.renderer.stream_row(
-                                &column_refs_str,
-                                &row_values,
-                                &mut self.encode_buf,
-                            );
-                            self.writer.write_all(&self.encode_buf).await?;
-                            self.encode_buf.clear();
-                        }
+                        self.encode_row(&column_refs_str, &row_values);
+                        self.write_frame().await?;
                     }
                 }
-                None => break,
+
+                if self.limit_reached {
+                    break;
+                }
+            }
+        }
+
+        if let Some(message) = stream.failure() {
+            if stream.timed_out() {
+                return self.write_truncated(message).await;
             }
+            return self.write_failure(message).await;
         }
 
+        // A final partial batch always goes out before the end frame
+        self.emit_pending_rows().await?;
         self.renderer.stream_end(self.emitted, &mut self.encode_buf);
-        self.writer.write_all(&self.encode_buf).await?;
-        self.encode_buf.clear();
-        self.writer.flush().await
+        self.write_frame().await?;
+        self.flush_output().await
     }
 
     async fn write_arrow(&mut self, mut stream: QueryBatchStream) -> io::Result<()> {
@@ -179,12 +300,13 @@ impl<'a, W: AsyncWrite + Unpin> QueryResponseWriter<'a, W> {
                 format!("Failed to encode Arrow schema: {err}"),
             )
         })?;
-        self.writer.write_all(&self.encode_buf).await?;
-        self.encode_buf.clear();
+        self.write_frame().await?;
 
         while !self.limit_reached {
-            match stream.recv().await {
-                Some(batch_arc) => {
+            match self.next_batch(&mut stream).await {
+                NextBatch::Linger => self.flush_output().await?,
+                NextBatch::Batch(_) if stream.failure().is_some() => break,
+                NextBatch::Batch(batch_arc) => {
                     if batch_arc.is_empty() {
                         continue;
                     }
@@ -237,11 +359,17 @@ impl<'a, W: AsyncWrite + Unpin> QueryResponseWriter<'a, W> {
                                 format!("Failed to encode Arrow batch: {err}"),
                             )
                         })?;
-                    self.writer.write_all(&self.encode_buf).await?;
-                    self.encode_buf.clear();
+                    self.write_frame().await?;
                 }
-                None => break,
+                NextBatch::End => break,
+            }
+        }
+
+        if let Some(message) = stream.failure() {
+            if stream.timed_out() {
+                return self.write_truncated(message).await;
             }
+            return self.write_failure(message).await;
         }
 
         encoder.write_end(&mut self.encode_buf).map_err(|err| {
@@ -250,16 +378,120 @@ impl<'a, W: AsyncWrite + Unpin> QueryResponseWriter<'a, W> {
                 format!("Failed to finalize Arrow stream: {err}"),
             )
         })?;
+        self.write_frame().await?;
+        self.flush_output().await
+    }
+
+    /// Waits for the next batch, giving up at the linger deadline so buffered output
+    /// can be flushed.
+    async fn next_batch(&self, stream: &mut QueryBatchStream) -> NextBatch {
+        let deadline = self
+            .batching
+            .max_linger
+            .zip(self.buffered_since)
+            .map(|(linger, since)| since + linger);
+        let batch = match deadline {
+            Some(deadline) => tokio::select! {
+                batch = stream.recv() => batch,
+                _ = tokio::time::sleep_until(deadline) => return NextBatch::Linger,
+            },
+            None => stream.recv().await,
+        };
+        match batch {
+            Some(batch) => NextBatch::Batch(batch),
+            None => NextBatch::End,
+        }
+    }
+
+    /// Ends an aborted stream with an error frame in place of the end frame, so
+    /// clients never mistake the rows received so far for a complete result.
+    async fn write_failure(&mut self, message: &str) -> io::Result<()> {
+        self.pending_rows.clear();
+        let response = Response::error_with_code(
+            StatusCode::BadRequest,
+            ErrorCode::QueryMemoryLimitExceeded,
+            message,
+        );
+        self.encode_buf = self.renderer.render(&response);
+        self.write_frame().await?;
+        self.flush_output().await
+    }
+
+    /// Ends a stream cut off by the query timeout. The rows received so far are
+    /// complete and go out first; the error frame then marks the result as
+    /// truncated, with the number of rows sent.
+    async fn write_truncated(&mut self, message: &str) -> io::Result<()> {
+        self.emit_pending_rows().await?;
+        let response = Response::error_with_code(
+            StatusCode::ServiceUnavailable,
+            ErrorCode::Timeout,
+            format!("{message}; results truncated after {} rows", self.emitted),
+        );
+        self.encode_buf = self.renderer.render(&response);
+        self.write_frame().await?;
+        self.flush_output().await
+    }
+
+    async fn emit_pending_rows(&mut self) -> io::Result<()> {
+        if self.pending_rows.is_empty() {
+            return Ok(());
+        }
+        let column_refs_str: Vec<&str> = self.column_names.iter().map(|s| s.as_str()).collect();
+        if self.raw_strings {
+            self.renderer.stream_batch_raw_strings(
+                &column_refs_str,
+                &self.pending_rows,
+                &mut self.encode_buf,
+            );
+        } else {
+            self.renderer
+                .stream_batch(&column_refs_str, &self.pending_rows, &mut self.encode_buf);
+        }
+        self.pending_rows.clear();
+        self.write_frame().await
+    }
+
+    fn encode_row(&mut self, columns: &[&str], values: &[ScalarValue]) {
+        if self.raw_strings {
+            self.renderer
+                .stream_row_raw_strings(columns, values, &mut self.encode_buf);
+        } else {
+            self.renderer
+                .stream_row(columns, values, &mut self.encode_buf);
+        }
+    }
+
+    /// Moves the encoded frame into the output buffer, flushing once the byte threshold is hit.
+    async fn write_frame(&mut self) -> io::Result<()> {
+        if self.encode_buf.is_empty() {
+            return Ok(());
+        }
         self.writer.write_all(&self.encode_buf).await?;
+        self.unflushed_bytes += self.encode_buf.len();
         self.encode_buf.clear();
-        self.writer.flush().await
+        self.buffered_since.get_or_insert_with(Instant::now);
+        if self.unflushed_bytes >= self.batching.flush_bytes {
+            self.flush_output().await?;
+        }
+        Ok(())
+    }
+
+    async fn flush_output(&mut self) -> io::Result<()> {
+        self.writer.flush().await?;
+        self.unflushed_bytes = 0;
+        self.buffered_since = if self.pending_rows.is_empty() {
+            None
+        } else {
+            Some(Instant::now())
+        };
+        Ok(())
     }
 
     fn try_accept_row(&mut self, event_id: Option<u64>) -> bool {
-        if let Some(id) = event_id {
-            if !self.seen_ids.insert(id) {
-                return false;
-            }
+        if let Some(id) = event_id.filter(|_| self.deduplicate)
+            && !self.seen_ids.insert(id)
+        {
+            return false;
         }
 
         if let Some(offset) = self.offset {
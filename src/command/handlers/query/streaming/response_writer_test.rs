@@ -1,14 +1,19 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use serde_json::json;
 use tokio::io::{AsyncReadExt, duplex};
 
-use crate::command::handlers::query::streaming::QueryResponseWriter;
+use crate::command::handlers::query::planner::TotalCount;
+use crate::command::handlers::query::streaming::{OutputBatching, QueryResponseWriter};
 use crate::command::handlers::query_batch_stream::QueryBatchStream;
-use crate::engine::core::read::flow::{BatchPool, BatchSchema, FlowChannel, FlowMetrics};
+use crate::engine::core::read::flow::{
+    BatchPool, BatchSchema, BatchSender, FlowChannel, FlowMetrics, QueryCancellation,
+    QueryMemoryBudget,
+};
 use crate::engine::core::read::result::ColumnSpec;
 use crate::engine::types::ScalarValue;
-use crate::shared::response::JsonRenderer;
+use crate::shared::response::{CsvRenderer, JsonRenderer};
 
 fn build_schema() -> Arc<BatchSchema> {
     Arc::new(
@@ -69,3 +74,406 @@ async fn streaming_response_emits_schema_and_rows() {
     assert!(end_line.contains("\"type\":\"end\""));
     assert!(lines.next().is_none());
 }
+
+async fn send_rows(sender: &BatchSender, schema: &Arc<BatchSchema>, ids: std::ops::Range<u64>) {
+    let mut builder = BatchPool::new(16)
+        .expect("pool")
+        .acquire(Arc::clone(schema));
+    for id in ids {
+        let row = vec![
+            ScalarValue::from(json!(format!("ctx-{id}"))),
+            ScalarValue::from(json!(id)),
+        ];
+        builder.push_row(&row).expect("push row should succeed");
+    }
+    let batch = builder.finish().expect("batch finish");
+    sender.send(Arc::new(batch)).await.expect("send batch");
+}
+
+async fn batch_frame_sizes(stream: QueryBatchStream, batching: OutputBatching) -> Vec<usize> {
+    let schema = build_schema();
+    let (mut writer, mut reader) = duplex(1 << 16);
+    let renderer = JsonRenderer;
+    QueryResponseWriter::new(&mut writer, &renderer, schema, None, None)
+        .with_output_batching(batching)
+        .write(stream)
+        .await
+        .expect("streaming write succeeds");
+    drop(writer);
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.expect("read output");
+    let output = String::from_utf8(buf).expect("utf8");
+    assert!(output.lines().last().unwrap().contains("\"type\":\"end\""));
+    output
+        .lines()
+        .filter(|line| line.contains("\"type\":\"batch\""))
+        .map(|line| {
+            let frame: serde_json::Value = serde_json::from_str(line).expect("json frame");
+            frame["rows"].as_array().expect("rows array").len()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn output_batches_split_large_internal_batches() {
+    let schema = build_schema();
+    let (sender, receiver) = FlowChannel::bounded(4, FlowMetrics::new());
+    send_rows(&sender, &schema, 0..5).await;
+    drop(sender);
+
+    let stream = QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new());
+    let batching = OutputBatching {
+        rows: 2,
+        ..OutputBatching::default()
+    };
+    // Final partial batch is flushed before the end frame
+    assert_eq!(batch_frame_sizes(stream, batching).await, vec![2, 2, 1]);
+}
+
+#[tokio::test]
+async fn output_batches_coalesce_small_internal_batches() {
+    let schema = build_schema();
+    let (sender, receiver) = FlowChannel::bounded(4, FlowMetrics::new());
+    send_rows(&sender, &schema, 0..2).await;
+    send_rows(&sender, &schema, 2..3).await;
+    send_rows(&sender, &schema, 3..7).await;
+    drop(sender);
+
+    let stream = QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new());
+    let batching = OutputBatching {
+        rows: 4,
+        flush_bytes: 1,
+        max_linger: None,
+    };
+    assert_eq!(batch_frame_sizes(stream, batching).await, vec![4, 3]);
+}
+
+#[tokio::test]
+async fn max_linger_flushes_partial_batch() {
+    let schema = build_schema();
+    let (sender, receiver) = FlowChannel::bounded(4, FlowMetrics::new());
+    send_rows(&sender, &schema, 0..1).await;
+
+    let stream = QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new());
+    let batching = OutputBatching {
+        rows: 100,
+        flush_bytes: 1 << 20,
+        max_linger: Some(std::time::Duration::from_millis(20)),
+    };
+    let (mut writer, mut reader) = duplex(1 << 16);
+    let task = tokio::spawn(async move {
+        let renderer = JsonRenderer;
+        QueryResponseWriter::new(&mut writer, &renderer, schema, None, None)
+            .with_output_batching(batching)
+            .write(stream)
+            .await
+    });
+
+    // The producer is still open, yet the lingering row reaches the client
+    let mut buf = vec![0u8; 4096];
+    let mut seen = String::new();
+    while !seen.contains("\"type\":\"batch\"") {
+        let n = reader.read(&mut buf).await.expect("read output");
+        assert!(n > 0, "stream closed before linger flush");
+        seen.push_str(std::str::from_utf8(&buf[..n]).expect("utf8"));
+    }
+    assert!(seen.contains("ctx-0"));
+    assert!(!seen.contains("\"type\":\"end\""));
+
+    drop(sender);
+    task.await.unwrap().expect("streaming write succeeds");
+}
+
+#[tokio::test]
+async fn memory_abort_replaces_end_frame_with_error() {
+    let schema = build_schema();
+    let (sender, receiver) = FlowChannel::bounded(4, FlowMetrics::new());
+    send_rows(&sender, &schema, 0..3).await;
+    drop(sender);
+
+    let budget = QueryMemoryBudget::with_limit(1);
+    let mut reservation = budget.reservation("test");
+    assert!(reservation.try_grow(2).is_err());
+
+    let stream =
+        QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new()).with_memory_budget(budget);
+    let (mut writer, mut reader) = duplex(1 << 16);
+    let renderer = JsonRenderer;
+    QueryResponseWriter::new(&mut writer, &renderer, schema, None, None)
+        .write(stream)
+        .await
+        .expect("streaming write succeeds");
+    drop(writer);
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.expect("read output");
+    let output = String::from_utf8(buf).expect("utf8");
+    let last = output.lines().last().expect("error frame");
+    assert!(last.contains("\"code\":\"QUERY_MEMORY_LIMIT_EXCEEDED\""));
+    assert!(!output.contains("\"type\":\"end\""));
+    assert!(!output.contains("ctx-0"));
+}
+
+async fn omit_nulls_frames(batching: OutputBatching) -> Vec<serde_json::Value> {
+    let schema = build_schema();
+    let metrics = FlowMetrics::new();
+    let (sender, receiver) = FlowChannel::bounded(4, Arc::clone(&metrics));
+
+    let mut builder = BatchPool::new(4)
+        .expect("pool")
+        .acquire(Arc::clone(&schema));
+    builder
+        .push_row(&[ScalarValue::Null, ScalarValue::from(json!(1u64))])
+        .expect("push row should succeed");
+    builder
+        .push_row(&[
+            ScalarValue::from(json!("ctx-2")),
+            ScalarValue::from(json!(2u64)),
+        ])
+        .expect("push row should succeed");
+    sender
+        .send(Arc::new(builder.finish().expect("batch finish")))
+        .await
+        .expect("send batch");
+    drop(sender);
+
+    let stream = QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new());
+    let (mut writer, mut reader) = duplex(4096);
+    let renderer = JsonRenderer;
+    QueryResponseWriter::new(&mut writer, &renderer, schema, None, None)
+        .with_output_batching(batching)
+        .with_omit_nulls(true)
+        .write(stream)
+        .await
+        .expect("streaming write succeeds");
+    drop(writer);
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.expect("read output");
+    String::from_utf8(buf)
+        .expect("utf8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("json frame"))
+        .collect()
+}
+
+#[tokio::test]
+async fn omit_nulls_drops_null_fields_from_row_frames() {
+    let frames = omit_nulls_frames(OutputBatching {
+        rows: 0,
+        ..OutputBatching::default()
+    })
+    .await;
+
+    assert_eq!(frames[0]["type"], "schema");
+    assert_eq!(frames[0]["omit_nulls"], true);
+    assert_eq!(frames[0]["columns"][0]["name"], "context_id");
+
+    assert_eq!(frames[1]["values"], json!({ "event_id": 1 }));
+    assert_eq!(
+        frames[2]["values"],
+        json!({ "context_id": "ctx-2", "event_id": 2 })
+    );
+    assert_eq!(frames[3]["type"], "end");
+}
+
+#[tokio::test]
+async fn omit_nulls_keeps_positional_nulls_in_batch_frames() {
+    let frames = omit_nulls_frames(OutputBatching::default()).await;
+
+    assert_eq!(frames[0]["omit_nulls"], true);
+    assert_eq!(frames[1]["type"], "batch");
+    assert_eq!(frames[1]["rows"], json!([[null, 1], ["ctx-2", 2]]));
+}
+
+#[tokio::test]
+async fn with_total_writes_total_frame_after_schema() {
+    let schema = build_schema();
+    let metrics = FlowMetrics::new();
+    let (sender, receiver) = FlowChannel::bounded(4, Arc::clone(&metrics));
+    drop(sender);
+
+    let stream = QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new());
+    let (mut writer, mut reader) = duplex(4096);
+    let renderer = JsonRenderer;
+    QueryResponseWriter::new(&mut writer, &renderer, schema, Some(10), None)
+        .with_total(Some(TotalCount {
+            rows: 1234,
+            exact: false,
+        }))
+        .write(stream)
+        .await
+        .expect("streaming write succeeds");
+    drop(writer);
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.expect("read output");
+    let frames: Vec<serde_json::Value> = String::from_utf8(buf)
+        .expect("utf8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("json frame"))
+        .collect();
+
+    assert_eq!(frames[0]["type"], "schema");
+    assert_eq!(
+        frames[1],
+        json!({ "type": "total", "count": 1234, "estimate": true })
+    );
+    assert_eq!(frames[2]["type"], "end");
+}
+
+#[tokio::test]
+async fn csv_streams_header_then_records_row_by_row() {
+    let schema = build_schema();
+    let (sender, receiver) = FlowChannel::bounded(4, FlowMetrics::new());
+    let mut builder = BatchPool::new(4)
+        .expect("pool")
+        .acquire(Arc::clone(&schema));
+    builder
+        .push_row(&[
+            ScalarValue::from(json!("a,\"b\"")),
+            ScalarValue::from(json!(1u64)),
+        ])
+        .expect("push row should succeed");
+    builder
+        .push_row(&[ScalarValue::Null, ScalarValue::from(json!(2u64))])
+        .expect("push row should succeed");
+    sender
+        .send(Arc::new(builder.finish().expect("batch finish")))
+        .await
+        .expect("send batch");
+    drop(sender);
+
+    let stream = QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new());
+    let (mut writer, mut reader) = duplex(4096);
+    let renderer = CsvRenderer::new("NULL");
+    // Nulls keep their column even when omission was asked for
+    QueryResponseWriter::new(&mut writer, &renderer, schema, None, None)
+        .with_output_batching(OutputBatching {
+            rows: 0,
+            ..OutputBatching::default()
+        })
+        .with_omit_nulls(true)
+        .with_total(Some(TotalCount {
+            rows: 2,
+            exact: true,
+        }))
+        .write(stream)
+        .await
+        .expect("streaming write succeeds");
+    drop(writer);
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.expect("read output");
+    assert_eq!(
+        String::from_utf8(buf).expect("utf8"),
+        "context_id,event_id\r\n\"a,\"\"b\"\"\",1\r\nNULL,2\r\n"
+    );
+}
+
+async fn raw_strings_frames(raw_strings: bool, batching: OutputBatching) -> Vec<serde_json::Value> {
+    let schema = build_schema();
+    let metrics = FlowMetrics::new();
+    let (sender, receiver) = FlowChannel::bounded(4, Arc::clone(&metrics));
+
+    let mut builder = BatchPool::new(4)
+        .expect("pool")
+        .acquire(Arc::clone(&schema));
+    builder
+        .push_row(&[
+            ScalarValue::Utf8("{\"plan\":\"pro\"}".into()),
+            ScalarValue::from(json!(1u64)),
+        ])
+        .expect("push row should succeed");
+    builder
+        .push_row(&[
+            ScalarValue::Utf8("18446744073709551615".into()),
+            ScalarValue::from(json!(2u64)),
+        ])
+        .expect("push row should succeed");
+    sender
+        .send(Arc::new(builder.finish().expect("batch finish")))
+        .await
+        .expect("send batch");
+    drop(sender);
+
+    let stream = QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new());
+    let (mut writer, mut reader) = duplex(4096);
+    let renderer = JsonRenderer;
+    QueryResponseWriter::new(&mut writer, &renderer, schema, None, None)
+        .with_output_batching(batching)
+        .with_raw_strings(raw_strings)
+        .write(stream)
+        .await
+        .expect("streaming write succeeds");
+    drop(writer);
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.expect("read output");
+    String::from_utf8(buf)
+        .expect("utf8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("json frame"))
+        .collect()
+}
+
+#[tokio::test]
+async fn raw_strings_keeps_json_looking_strings_as_strings() {
+    let row_frames = OutputBatching {
+        rows: 0,
+        ..OutputBatching::default()
+    };
+
+    // By default strings holding JSON are re-parsed
+    let frames = raw_strings_frames(false, row_frames).await;
+    assert_eq!(frames[1]["values"]["context_id"], json!({ "plan": "pro" }));
+    assert_eq!(
+        frames[2]["values"]["context_id"],
+        json!(18446744073709551615u64)
+    );
+
+    let frames = raw_strings_frames(true, row_frames).await;
+    assert_eq!(frames[1]["values"]["context_id"], "{\"plan\":\"pro\"}");
+    assert_eq!(frames[2]["values"]["context_id"], "18446744073709551615");
+
+    let frames = raw_strings_frames(true, OutputBatching::default()).await;
+    assert_eq!(frames[1]["type"], "batch");
+    assert_eq!(
+        frames[1]["rows"],
+        json!([["{\"plan\":\"pro\"}", 1], ["18446744073709551615", 2]])
+    );
+}
+
+#[tokio::test]
+async fn timeout_keeps_received_rows_and_marks_truncation() {
+    let schema = build_schema();
+    let (sender, receiver) = FlowChannel::bounded(4, FlowMetrics::new());
+    send_rows(&sender, &schema, 0..3).await;
+
+    // The producer stays open, so only the deadline ends the stream
+    let cancellation = QueryCancellation::new();
+    let stream = QueryBatchStream::new(Arc::clone(&schema), receiver, Vec::new()).with_deadline(
+        tokio::time::Instant::now() + Duration::from_millis(50),
+        Duration::from_millis(50),
+        Arc::clone(&cancellation),
+    );
+    let (mut writer, mut reader) = duplex(1 << 16);
+    let renderer = JsonRenderer;
+    QueryResponseWriter::new(&mut writer, &renderer, schema, None, None)
+        .write(stream)
+        .await
+        .expect("streaming write succeeds");
+    drop(writer);
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.expect("read output");
+    let output = String::from_utf8(buf).expect("utf8");
+    assert!(output.contains("ctx-2"));
+    let last = output.lines().last().expect("error frame");
+    assert!(last.contains("\"code\":\"TIMEOUT\""));
+    assert!(last.contains("Query timed out after 50 ms; results truncated after 3 rows"));
+    assert!(!output.contains("\"type\":\"end\""));
+    assert!(cancellation.is_cancelled());
+    drop(sender);
+}
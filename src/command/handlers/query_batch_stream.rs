@@ -1,6 +1,11 @@
-use crate::engine::core::read::flow::{BatchReceiver, BatchSchema, ColumnBatch};
+use crate::engine::core::read::flow::{
+    BatchReceiver, BatchSchema, ColumnBatch, FlowChannel, FlowMetrics, QUERY_TIMEOUT_ERROR_PREFIX,
+    QueryCancellation, QueryMemoryBudget,
+};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::task::JoinHandle;
+use tokio::time::Instant;
 
 /// A streaming result type that holds schema, receiver, and background tasks.
 ///
@@ -11,6 +16,17 @@ pub struct QueryBatchStream {
     schema: Arc<BatchSchema>,
     receiver: BatchReceiver,
     tasks: Vec<JoinHandle<()>>,
+    memory: Option<Arc<QueryMemoryBudget>>,
+    cancellation: Option<Arc<QueryCancellation>>,
+    timeout: Option<StreamTimeout>,
+}
+
+/// When a stream stops waiting for batches, and the signal that stops the
+/// query's readers once it does.
+struct StreamTimeout {
+    deadline: Instant,
+    limit: Duration,
+    cancellation: Arc<QueryCancellation>,
 }
 
 impl QueryBatchStream {
@@ -29,9 +45,73 @@ impl QueryBatchStream {
             schema,
             receiver,
             tasks,
+            memory: None,
+            cancellation: None,
+            timeout: None,
         }
     }
 
+    /// Attaches the query's memory budget so consumers can tell a stream that
+    /// ended early because the query ran out of memory.
+    pub(crate) fn with_memory_budget(mut self, memory: Arc<QueryMemoryBudget>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Attaches the query's stop signal so consumers can tell a stream that
+    /// ended early because an operator failed and cancelled the query.
+    pub(crate) fn with_cancellation(mut self, cancellation: Arc<QueryCancellation>) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Ends the stream at `deadline`: the query is cancelled through
+    /// `cancellation` and its background tasks are aborted. `limit` is the
+    /// timeout the deadline was derived from, for the failure message.
+    pub(crate) fn with_deadline(
+        mut self,
+        deadline: Instant,
+        limit: Duration,
+        cancellation: Arc<QueryCancellation>,
+    ) -> Self {
+        self.timeout = Some(StreamTimeout {
+            deadline,
+            limit,
+            cancellation,
+        });
+        self
+    }
+
+    /// Returns why the query was aborted, if it exceeded its memory budget,
+    /// an operator cancelled it or it ran past its deadline. Rows received
+    /// after a memory abort or an operator failure are incomplete and must
+    /// not be reported; see [`Self::timed_out`].
+    pub fn failure(&self) -> Option<&str> {
+        self.memory
+            .as_ref()
+            .and_then(|memory| memory.failure())
+            .or_else(|| self.cancellation_reason())
+    }
+
+    fn cancellation_reason(&self) -> Option<&str> {
+        self.cancellation
+            .as_ref()
+            .or(self.timeout.as_ref().map(|timeout| &timeout.cancellation))
+            .and_then(|cancellation| cancellation.reason())
+    }
+
+    /// Whether the stream ended at its deadline. Every row received before is
+    /// complete, but the rows that would have followed are missing.
+    pub fn timed_out(&self) -> bool {
+        self.memory
+            .as_ref()
+            .is_none_or(|memory| !memory.is_exceeded())
+            && self.timeout.is_some()
+            && self
+                .cancellation_reason()
+                .is_some_and(|reason| reason.starts_with(QUERY_TIMEOUT_ERROR_PREFIX))
+    }
+
     /// Returns the schema of the batches in this stream.
     pub fn schema(&self) -> Arc<BatchSchema> {
         Arc::clone(&self.schema)
@@ -39,9 +119,43 @@ impl QueryBatchStream {
 
     /// Receives the next batch from the stream.
     ///
-    /// Returns `None` when the stream is exhausted.
+    /// Returns `None` when the stream is exhausted or its deadline passed.
     pub async fn recv(&mut self) -> Option<Arc<ColumnBatch>> {
-        self.receiver.recv().await
+        let Some(timeout) = &self.timeout else {
+            return self.receiver.recv().await;
+        };
+        if timeout.cancellation.is_cancelled() {
+            return None;
+        }
+        let deadline = timeout.deadline;
+        tokio::select! {
+            batch = self.receiver.recv() => batch,
+            _ = tokio::time::sleep_until(deadline) => {
+                self.expire();
+                None
+            }
+        }
+    }
+
+    /// Cancels the query at its deadline and stops the tasks feeding the stream.
+    fn expire(&mut self) {
+        if let Some(timeout) = &self.timeout {
+            timeout
+                .cancellation
+                .cancel(QueryCancellation::timeout_message(timeout.limit));
+        }
+        while let Some(task) = self.tasks.pop() {
+            task.abort();
+        }
+    }
+
+    /// Splits the stream into its schema, receiver, and background tasks so it
+    /// can be wired into another flow. The caller takes over aborting the tasks.
+    pub(crate) fn into_parts(mut self) -> (Arc<BatchSchema>, BatchReceiver, Vec<JoinHandle<()>>) {
+        let tasks = std::mem::take(&mut self.tasks);
+        let (_, closed) = FlowChannel::bounded(1, FlowMetrics::new());
+        let receiver = std::mem::replace(&mut self.receiver, closed);
+        (Arc::clone(&self.schema), receiver, tasks)
     }
 }
 
@@ -157,6 +157,64 @@ async fn test_query_returns_no_results_when_nothing_matches() {
     );
 }
 
+#[tokio::test]
+async fn test_query_rejects_undefined_event_type_with_unknown_schema() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("signup", &[("user_id", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    for query in [
+        "QUERY refund",
+        "QUERY signup FOLLOWED BY refund LINKED BY user_id",
+    ] {
+        let cmd = parse(query).expect("parse query");
+        let (mut reader, mut writer) = duplex(1024);
+        execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+            .await
+            .unwrap();
+
+        let mut buf = vec![0u8; 1024];
+        let n = reader.read(&mut buf).await.unwrap();
+        let body = String::from_utf8_lossy(&buf[..n]);
+
+        assert!(body.contains("\"status\":400"), "{}: {}", query, body);
+        assert!(
+            body.contains("\"code\":\"UNKNOWN_SCHEMA\""),
+            "{}: {}",
+            query,
+            body
+        );
+        assert!(
+            body.contains("No schema defined for event type 'refund'"),
+            "{}: {}",
+            query,
+            body
+        );
+    }
+
+    // A defined type without events is an empty success
+    let cmd = parse("QUERY signup").expect("parse query");
+    let (mut reader, mut writer) = duplex(1024);
+    execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+        .await
+        .unwrap();
+    let mut buf = vec![0u8; 1024];
+    let n = reader.read(&mut buf).await.unwrap();
+    let body = String::from_utf8_lossy(&buf[..n]);
+    assert!(!body.contains("UNKNOWN_SCHEMA"), "{}", body);
+    let (rows, row_count, _) = parse_streaming_response(&body);
+    assert!(rows.is_empty() && row_count == 0, "{}", body);
+}
+
 #[tokio::test]
 async fn test_query_aggregation_count_unique_by_returns_values() {
     init_for_tests();
@@ -2784,6 +2842,93 @@ async fn test_timestamp_where_gt_asc_limit() {
     assert_eq!(ts_vals, vec![308, 309, 310]);
 }
 
+#[tokio::test]
+async fn test_event_id_range_filters_flushed_events() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("id_evt_range", &[("x", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    for x in 0..10 {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("id_evt_range")
+            .with_context_id(&format!("id{:02}", x))
+            .with_payload(serde_json::json!({ "x": x }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+    let (mut _r, mut w) = duplex(1024);
+    flush::handle(
+        &Command::Flush,
+        &shard_manager,
+        &registry,
+        &mut w,
+        &JsonRenderer,
+    )
+    .await
+    .expect("flush should succeed");
+    sleep(Duration::from_millis(600)).await;
+
+    let query_ids = |query: String| {
+        let shard_manager = &shard_manager;
+        let registry = &registry;
+        async move {
+            let cmd = parse(&query).expect("parse event_id query");
+            let (mut reader, mut writer) = duplex(1 << 16);
+            execute_query(&cmd, shard_manager, registry, &mut writer, &JsonRenderer)
+                .await
+                .unwrap();
+            drop(writer);
+            let mut body = String::new();
+            reader.read_to_string(&mut body).await.unwrap();
+            let (rows, _, column_names) = parse_streaming_response(&body);
+            let idx = find_column_idx(&column_names, "event_id");
+            rows.iter()
+                .map(|r| r[idx].as_u64().unwrap())
+                .collect::<Vec<u64>>()
+        }
+    };
+
+    let all = query_ids("QUERY id_evt_range ORDER BY event_id ASC".to_string()).await;
+    assert_eq!(all.len(), 10);
+
+    let after = query_ids(format!(
+        "QUERY id_evt_range WHERE event_id > {} ORDER BY event_id ASC",
+        all[6]
+    ))
+    .await;
+    assert_eq!(after, all[7..].to_vec());
+
+    let window = query_ids(format!(
+        "QUERY id_evt_range WHERE event_id > {} AND event_id <= {} ORDER BY event_id ASC",
+        all[1], all[4]
+    ))
+    .await;
+    assert_eq!(window, all[2..5].to_vec());
+
+    let exact = query_ids(format!("QUERY id_evt_range WHERE event_id = {}", all[3])).await;
+    assert_eq!(exact, vec![all[3]]);
+}
+
 /// Test for LIMIT functionality with custom datetime fields.
 /// Tests 60 events with explicit created_at timestamps inserted in random order.
 #[tokio::test]
@@ -4729,6 +4874,150 @@ async fn test_sequence_preceded_by_basic() {
     );
 }
 
+/// E2E test for a sequence chain of three event types
+#[tokio::test]
+async fn test_sequence_three_event_chain() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    for event_type in ["page_view", "add_to_cart", "order_created"] {
+        factory
+            .define_with_fields(event_type, &[("user_id", "string")])
+            .await
+            .unwrap();
+    }
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    // u1 goes through the whole funnel, u2 never adds to cart
+    let steps = [
+        ("page_view", "u1"),
+        ("page_view", "u2"),
+        ("add_to_cart", "u1"),
+        ("order_created", "u1"),
+        ("order_created", "u2"),
+    ];
+    for (i, (event_type, user_id)) in steps.into_iter().enumerate() {
+        if i > 0 && steps[i - 1].0 != event_type {
+            sleep(Duration::from_millis(1100)).await;
+        }
+        let store_cmd = CommandFactory::store()
+            .with_event_type(event_type)
+            .with_context_id(user_id)
+            .with_payload(serde_json::json!({ "user_id": user_id }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+
+    sleep(Duration::from_millis(500)).await;
+
+    let cmd_str =
+        "QUERY page_view FOLLOWED BY add_to_cart FOLLOWED BY order_created LINKED BY user_id";
+    let cmd = parse(cmd_str).expect("parse chained sequence query");
+    let (mut reader, mut writer) = duplex(8192);
+    execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+        .await
+        .unwrap();
+
+    let mut buf = vec![0; 8192];
+    let n = reader.read(&mut buf).await.unwrap();
+    let body = String::from_utf8_lossy(&buf[..n]);
+
+    assert!(
+        body.contains("page_view")
+            && body.contains("add_to_cart")
+            && body.contains("order_created"),
+        "Should return every event of the chain: {}",
+        body
+    );
+    assert!(
+        body.contains("u1") && !body.contains("u2"),
+        "Only u1 completes the chain: {}",
+        body
+    );
+}
+
+/// E2E test for WITHIN: a gap of exactly the window matches, one second more does not
+#[tokio::test]
+async fn test_sequence_within_window_edge() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    for event_type in ["page_view", "order_created"] {
+        factory
+            .define_with_fields(
+                event_type,
+                &[("user_id", "string"), ("created_at", "datetime")],
+            )
+            .await
+            .unwrap();
+    }
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    let events = [
+        ("page_view", "u1", 1_700_000_000),
+        ("order_created", "u1", 1_700_001_800),
+        ("page_view", "u2", 1_700_000_000),
+        ("order_created", "u2", 1_700_001_801),
+    ];
+    for (event_type, user_id, created_at) in events {
+        let store_cmd = CommandFactory::store()
+            .with_event_type(event_type)
+            .with_context_id(user_id)
+            .with_payload(serde_json::json!({ "user_id": user_id, "created_at": created_at }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+
+    sleep(Duration::from_millis(500)).await;
+
+    let cmd_str = "QUERY page_view FOLLOWED BY order_created WITHIN 30m LINKED BY user_id USING TIME created_at";
+    let cmd = parse(cmd_str).expect("parse WITHIN query");
+    let (mut reader, mut writer) = duplex(8192);
+    execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+        .await
+        .unwrap();
+
+    let mut buf = vec![0; 8192];
+    let n = reader.read(&mut buf).await.unwrap();
+    let body = String::from_utf8_lossy(&buf[..n]);
+
+    assert!(
+        body.contains("u1") && !body.contains("u2"),
+        "Only u1 ordered within 30 minutes: {}",
+        body
+    );
+}
+
 /// E2E test for sequence query with numeric link field
 #[tokio::test]
 async fn test_sequence_with_numeric_link_field() {
@@ -7633,3 +7922,883 @@ async fn test_query_handler_bypass_auth_vs_regular_user() {
     let body = String::from_utf8_lossy(&buf[..n]);
     assert!(body.contains("123") || body.contains("\"id\":123"));
 }
+
+#[tokio::test]
+async fn test_query_latest_per_returns_latest_event_per_key() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields(
+            "plan_changed",
+            &[
+                ("account", "string"),
+                ("version", "int"),
+                ("plan", "string"),
+            ],
+        )
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+
+    // "acme" has a tie on version 2, resolved by ingest order; "solo" has a single
+    // event; the latest "beta" event is filtered out by the WHERE clause.
+    let events = [
+        ("acme", 1, "free"),
+        ("acme", 2, "pro"),
+        ("acme", 2, "team"),
+        ("solo", 1, "free"),
+        ("beta", 1, "basic"),
+        ("beta", 2, "trial"),
+    ];
+    for (idx, (account, version, plan)) in events.into_iter().enumerate() {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("plan_changed")
+            .with_context_id(&format!("ctx{}", idx))
+            .with_payload(serde_json::json!({
+                "account": account,
+                "version": version,
+                "plan": plan,
+            }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+
+    sleep(Duration::from_millis(300)).await;
+
+    let cmd = parse(
+        r#"QUERY plan_changed WHERE plan IN ("free", "pro", "team", "basic") LATEST PER account USING TIME version ORDER BY account"#,
+    )
+    .expect("parse LATEST PER query");
+    let (mut reader, mut writer) = duplex(8192);
+    execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+        .await
+        .unwrap();
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+
+    let (rows, _, column_names) = parse_streaming_response(&body);
+    let account_idx = find_column_idx(&column_names, "account");
+    let plan_idx = find_column_idx(&column_names, "plan");
+    let observed: Vec<(String, String)> = rows
+        .iter()
+        .map(|row| {
+            (
+                row[account_idx].as_str().unwrap().to_string(),
+                row[plan_idx].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        observed,
+        vec![
+            ("acme".to_string(), "team".to_string()),
+            ("beta".to_string(), "basic".to_string()),
+            ("solo".to_string(), "free".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_query_dedup_by_keeps_first_or_latest_per_key() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields(
+            "payment",
+            &[("key", "string"), ("attempt", "int"), ("amount", "int")],
+        )
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+
+    // Retries of "k1" and "k2" land on different shards through their contexts
+    let events = [
+        ("k1", 2, 10),
+        ("k2", 1, 20),
+        ("k1", 1, 11),
+        ("k1", 3, 12),
+        ("k2", 2, 21),
+    ];
+    for (idx, (key, attempt, amount)) in events.into_iter().enumerate() {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("payment")
+            .with_context_id(&format!("ctx{}", idx))
+            .with_payload(serde_json::json!({
+                "key": key,
+                "attempt": attempt,
+                "amount": amount,
+            }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+
+    sleep(Duration::from_millis(300)).await;
+
+    for (keep, expected) in [("FIRST", vec![11, 20]), ("LATEST", vec![12, 21])] {
+        let cmd = parse(&format!(
+            "QUERY payment DEDUP BY key KEEP {} USING TIME attempt ORDER BY key",
+            keep
+        ))
+        .expect("parse DEDUP BY query");
+        let (mut reader, mut writer) = duplex(8192);
+        execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+            .await
+            .unwrap();
+        drop(writer);
+
+        let mut body = String::new();
+        reader.read_to_string(&mut body).await.unwrap();
+
+        let (rows, _, column_names) = parse_streaming_response(&body);
+        let amount_idx = find_column_idx(&column_names, "amount");
+        let amounts: Vec<i64> = rows
+            .iter()
+            .map(|row| row[amount_idx].as_i64().unwrap())
+            .collect();
+        assert_eq!(amounts, expected, "KEEP {}", keep);
+    }
+}
+
+#[tokio::test]
+async fn test_query_unnest_explodes_array_fields() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("basket", &[("seq", "int"), ("tags", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+
+    // An array, an empty array and a scalar, stored as JSON text
+    let events = [(1, r#"["red","blue"]"#), (2, "[]"), (3, "plain")];
+    for (seq, tags) in events {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("basket")
+            .with_context_id(&format!("ctx{}", seq))
+            .with_payload(serde_json::json!({ "seq": seq, "tags": tags }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+
+    sleep(Duration::from_millis(300)).await;
+
+    let run = |query: &'static str| {
+        let shard_manager = &shard_manager;
+        let registry = &registry;
+        async move {
+            let cmd = parse(query).expect("parse UNNEST query");
+            let (mut reader, mut writer) = duplex(8192);
+            execute_query(&cmd, shard_manager, registry, &mut writer, &JsonRenderer)
+                .await
+                .unwrap();
+            drop(writer);
+            let mut body = String::new();
+            reader.read_to_string(&mut body).await.unwrap();
+            body
+        }
+    };
+    let pairs = |body: &str| -> Vec<(i64, JsonValue)> {
+        let (rows, _, column_names) = parse_streaming_response(body);
+        let seq_idx = find_column_idx(&column_names, "seq");
+        let tags_idx = find_column_idx(&column_names, "tags");
+        rows.iter()
+            .map(|row| (row[seq_idx].as_i64().unwrap(), row[tags_idx].clone()))
+            .collect()
+    };
+
+    let body = run("QUERY basket UNNEST(tags) ON NON ARRAY PASSTHROUGH ORDER BY seq").await;
+    assert_eq!(
+        pairs(&body),
+        vec![
+            (1, JsonValue::from("red")),
+            (1, JsonValue::from("blue")),
+            (3, JsonValue::from("plain")),
+        ]
+    );
+
+    let body =
+        run("QUERY basket UNNEST(tags) ON EMPTY NULL ON NON ARRAY PASSTHROUGH ORDER BY seq").await;
+    assert_eq!(
+        pairs(&body),
+        vec![
+            (1, JsonValue::from("red")),
+            (1, JsonValue::from("blue")),
+            (2, JsonValue::Null),
+            (3, JsonValue::from("plain")),
+        ]
+    );
+
+    // OFFSET and LIMIT count unnested rows; the field is returned even if RETURN omits it
+    let body = run(
+        "QUERY basket RETURN [seq] UNNEST(tags) ON NON ARRAY PASSTHROUGH ORDER BY seq LIMIT 1 OFFSET 1",
+    )
+    .await;
+    assert_eq!(pairs(&body), vec![(1, JsonValue::from("blue"))]);
+
+    // By default a scalar fails the query
+    let body = run("QUERY basket UNNEST(tags) ORDER BY seq").await;
+    assert!(
+        body.contains("UNNEST field 'tags' holds a non-array value"),
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_query_rejected_by_complexity_limits() {
+    use crate::command::handlers::query::{
+        COMPLEXITY_ERROR_PREFIX, ComplexityLimits, QueryExecutionPipeline,
+    };
+    use crate::shared::config::ExcessZonesAction;
+
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("complex_evt", &[("id", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    for i in 0..3 {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("complex_evt")
+            .with_context_id(&format!("ctx{}", i))
+            .with_payload(serde_json::json!({ "id": i }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+    let (mut _r, mut w) = duplex(1024);
+    flush::handle(
+        &Command::Flush,
+        &shard_manager,
+        &registry,
+        &mut w,
+        &JsonRenderer,
+    )
+    .await
+    .expect("flush should succeed");
+    sleep(Duration::from_millis(400)).await;
+
+    let cmd = parse("QUERY complex_evt WHERE id = 1 OR id IN (2, 3)").expect("parse");
+    let run = |limits: ComplexityLimits| {
+        let cmd = &cmd;
+        let shard_manager = &shard_manager;
+        let registry = Arc::clone(&registry);
+        async move {
+            QueryExecutionPipeline::new(cmd, shard_manager, registry)
+                .with_complexity_limits(limits)
+                .execute_streaming()
+                .await
+                .map(|_| ())
+        }
+    };
+
+    let err = run(ComplexityLimits {
+        max_conditions: Some(1),
+        ..Default::default()
+    })
+    .await
+    .unwrap_err();
+    assert!(err.starts_with(COMPLEXITY_ERROR_PREFIX), "{}", err);
+    assert!(err.contains("(max_conditions)"), "{}", err);
+
+    // The flushed segment has at least one zone for the event type
+    let err = run(ComplexityLimits {
+        max_candidate_zones: Some(0),
+        ..Default::default()
+    })
+    .await
+    .unwrap_err();
+    assert!(err.contains("(max_candidate_zones)"), "{}", err);
+
+    run(ComplexityLimits {
+        max_conditions: Some(2),
+        max_in_list: Some(2),
+        max_candidate_zones: Some(1_000),
+        ..Default::default()
+    })
+    .await
+    .expect("query within limits should run");
+
+    // Past the zone limit, require_hint admits only hinted queries
+    let require_hint = ComplexityLimits {
+        max_candidate_zones: Some(0),
+        on_excess_zones: Some(ExcessZonesAction::RequireHint),
+        ..Default::default()
+    };
+    let err = run(require_hint.clone()).await.unwrap_err();
+    assert!(err.contains("add /* allow_full_scan */"), "{}", err);
+    let hinted = parse("QUERY /* allow_full_scan */ complex_evt WHERE id = 1").expect("parse");
+    QueryExecutionPipeline::new(&hinted, &shard_manager, Arc::clone(&registry))
+        .with_complexity_limits(require_hint)
+        .execute_streaming()
+        .await
+        .map(|_| ())
+        .expect("hinted query should run");
+
+    // Zones ending before SINCE are not counted
+    let future = parse(r#"QUERY complex_evt SINCE "2100-01-01T00:00:00Z""#).expect("parse");
+    QueryExecutionPipeline::new(&future, &shard_manager, Arc::clone(&registry))
+        .with_complexity_limits(ComplexityLimits {
+            max_candidate_zones: Some(0),
+            ..Default::default()
+        })
+        .execute_streaming()
+        .await
+        .map(|_| ())
+        .expect("no zone is newer than SINCE");
+}
+
+#[tokio::test]
+async fn test_aggregate_query_aborted_by_memory_budget() {
+    use crate::command::handlers::query::QueryExecutionPipeline;
+    use crate::engine::core::read::flow::{MEMORY_LIMIT_ERROR_PREFIX, QueryMemoryBudget};
+
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("memory_evt", &[("user", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    for i in 0..20 {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("memory_evt")
+            .with_context_id(&format!("ctx{}", i))
+            .with_payload(serde_json::json!({ "user": format!("user-{}", i) }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+
+    let cmd = parse("QUERY memory_evt COUNT UNIQUE user").expect("parse");
+    let drain = |budget: Arc<QueryMemoryBudget>| {
+        let cmd = &cmd;
+        let shard_manager = &shard_manager;
+        let registry = Arc::clone(&registry);
+        async move {
+            let mut stream = QueryExecutionPipeline::new(cmd, shard_manager, registry)
+                .with_memory_budget(budget)
+                .execute_streaming()
+                .await
+                .expect("pipeline starts")
+                .expect("stream");
+            while stream.recv().await.is_some() {}
+            stream.failure().map(str::to_string)
+        }
+    };
+
+    let failure = drain(QueryMemoryBudget::with_limit(64))
+        .await
+        .expect("query should be aborted");
+    assert!(
+        failure.starts_with(MEMORY_LIMIT_ERROR_PREFIX),
+        "{}",
+        failure
+    );
+    assert!(failure.contains("aggregate"), "{}", failure);
+
+    let budget = QueryMemoryBudget::with_limit(64 * 1024 * 1024);
+    assert_eq!(drain(Arc::clone(&budget)).await, None);
+    assert!(budget.peak() > 0);
+}
+
+#[tokio::test]
+async fn test_disable_pruners_is_admin_only_and_keeps_results() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("pruned_evt", &[("id", "int"), ("kind", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+    let auth_manager = Arc::new(AuthManager::new(Arc::clone(&shard_manager)));
+    auth_manager
+        .create_user("reader".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    auth_manager
+        .grant_permission(
+            "reader",
+            "pruned_evt",
+            crate::engine::auth::PermissionSet::read_only(),
+        )
+        .await
+        .unwrap();
+
+    for (id, kind) in [(1, "alpha"), (2, "beta"), (3, "gamma")] {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("pruned_evt")
+            .with_context_id(&format!("ctx{}", id))
+            .with_payload(serde_json::json!({ "id": id, "kind": kind }))
+            .create();
+        let (_r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            shard_manager.as_ref(),
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+    let (_r, mut w) = duplex(1024);
+    flush::handle(
+        &parser::command::parse_command("FLUSH").unwrap(),
+        shard_manager.as_ref(),
+        &registry,
+        &mut w,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+    sleep(Duration::from_millis(300)).await;
+
+    let run = |query: &'static str, user: &'static str| {
+        let shard_manager = Arc::clone(&shard_manager);
+        let registry = Arc::clone(&registry);
+        let auth_manager = Arc::clone(&auth_manager);
+        async move {
+            let cmd = parse(query).expect("parse");
+            let (mut reader, mut writer) = duplex(16 * 1024);
+            QueryCommandHandler::new(
+                &cmd,
+                shard_manager.as_ref(),
+                registry,
+                Some(&auth_manager),
+                Some(user),
+                &mut writer,
+                &JsonRenderer,
+            )
+            .handle()
+            .await
+            .expect("handler should not fail");
+            drop(writer);
+            let mut body = String::new();
+            reader.read_to_string(&mut body).await.unwrap();
+            body
+        }
+    };
+
+    let denied = run(
+        r#"QUERY pruned_evt WHERE kind = "beta" DISABLE PRUNERS xor"#,
+        "reader",
+    )
+    .await;
+    assert!(denied.contains("Only admin users"), "{}", denied);
+
+    let pruned = run(r#"QUERY pruned_evt WHERE kind = "beta""#, "bypass").await;
+    let unpruned = run(
+        r#"QUERY pruned_evt WHERE kind = "beta" DISABLE PRUNERS xor, surf, range, temporal, enum, materialization"#,
+        "bypass",
+    )
+    .await;
+    for body in [&pruned, &unpruned] {
+        assert!(body.contains("beta"), "{}", body);
+        assert!(
+            !body.contains("alpha") && !body.contains("gamma"),
+            "{}",
+            body
+        );
+    }
+}
+
+/// PERCENTILE merges digests from every shard and interpolates between values
+#[tokio::test]
+async fn test_query_aggregation_percentile_across_shards() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields(
+            "percentile_evt",
+            &[("latency_ms", "int"), ("route", "string")],
+        )
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+
+    // Route /a: 10, 20, 30, 40 spread over both shards; route /b: 7 only
+    for (ctx, latency, route) in [
+        ("c1", 10, "/a"),
+        ("c2", 40, "/a"),
+        ("c3", 20, "/a"),
+        ("c4", 30, "/a"),
+        ("c5", 7, "/b"),
+    ] {
+        let store_cmd = crate::test_helpers::factories::CommandFactory::store()
+            .with_event_type("percentile_evt")
+            .with_context_id(ctx)
+            .with_payload(serde_json::json!({ "latency_ms": latency, "route": route }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+    sleep(Duration::from_millis(400)).await;
+
+    let cmd = parse(
+        "QUERY percentile_evt PERCENTILE(latency_ms, 50), PERCENTILE(latency_ms, 95) BY route",
+    )
+    .expect("parse PERCENTILE query");
+    let (mut reader, mut writer) = duplex(4096);
+    execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+        .await
+        .unwrap();
+
+    let mut buf = vec![0; 4096];
+    let n = reader.read(&mut buf).await.unwrap();
+    let body = String::from_utf8_lossy(&buf[..n]);
+    assert!(body.contains("\"p50_latency_ms\"") && body.contains("\"p95_latency_ms\""));
+
+    let mut results: std::collections::HashMap<String, (f64, f64)> =
+        std::collections::HashMap::new();
+    for frame in body
+        .lines()
+        .filter_map(|line| serde_json::from_str::<JsonValue>(line).ok())
+        .filter(|frame| frame["type"] == "batch")
+    {
+        for row in frame["rows"].as_array().unwrap() {
+            results.insert(
+                row[0].as_str().unwrap().to_string(),
+                (row[1].as_f64().unwrap(), row[2].as_f64().unwrap()),
+            );
+        }
+    }
+
+    assert_eq!(results.get("/a"), Some(&(25.0, 38.5)), "{}", body);
+    assert_eq!(results.get("/b"), Some(&(7.0, 7.0)), "{}", body);
+}
+
+#[tokio::test]
+async fn test_query_aggregation_group_by_fields_keeps_null_groups() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields(
+            "grouped_evt",
+            &[("route", "string"), ("region", "string | null")],
+        )
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+
+    for (ctx, route, region) in [
+        ("c1", "/a", Some("eu")),
+        ("c2", "/a", Some("eu")),
+        ("c3", "/a", None),
+        ("c4", "/b", None),
+        ("c5", "/a", None),
+        ("c6", "/b", Some("us")),
+    ] {
+        let payload = match region {
+            Some(region) => serde_json::json!({ "route": route, "region": region }),
+            None => serde_json::json!({ "route": route }),
+        };
+        let store_cmd = crate::test_helpers::factories::CommandFactory::store()
+            .with_event_type("grouped_evt")
+            .with_context_id(ctx)
+            .with_payload(payload)
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+    sleep(Duration::from_millis(400)).await;
+
+    let cmd = parse("QUERY grouped_evt COUNT BY route, region").expect("parse grouped query");
+    let (mut reader, mut writer) = duplex(4096);
+    execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+        .await
+        .unwrap();
+
+    let mut buf = vec![0; 4096];
+    let n = reader.read(&mut buf).await.unwrap();
+    let body = String::from_utf8_lossy(&buf[..n]);
+
+    let mut results: Vec<(JsonValue, JsonValue, i64)> = Vec::new();
+    for frame in body
+        .lines()
+        .filter_map(|line| serde_json::from_str::<JsonValue>(line).ok())
+        .filter(|frame| frame["type"] == "batch")
+    {
+        for row in frame["rows"].as_array().unwrap() {
+            results.push((row[0].clone(), row[1].clone(), row[2].as_i64().unwrap()));
+        }
+    }
+    results.sort_by_key(|(route, region, _)| (route.to_string(), region.to_string()));
+
+    assert_eq!(
+        results,
+        vec![
+            (serde_json::json!("/a"), serde_json::json!("eu"), 2),
+            (serde_json::json!("/a"), JsonValue::Null, 2),
+            (serde_json::json!("/b"), serde_json::json!("us"), 1),
+            (serde_json::json!("/b"), JsonValue::Null, 1),
+        ],
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_query_aggregation_interval_buckets_fill_gaps() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("interval_evt", &[("created_at", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+
+    // Two events in the 00:15 bucket, one in 01:00; 00:30 and 00:45 are empty
+    for (ctx, created_at) in [("c1", 900), ("c2", 1_700), ("c3", 3_700)] {
+        let store_cmd = crate::test_helpers::factories::CommandFactory::store()
+            .with_event_type("interval_evt")
+            .with_context_id(ctx)
+            .with_payload(serde_json::json!({ "created_at": created_at }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+    sleep(Duration::from_millis(400)).await;
+
+    let cmd = parse("QUERY interval_evt COUNT PER 15m FILL USING created_at")
+        .expect("parse interval query");
+    let (mut reader, mut writer) = duplex(4096);
+    execute_query(&cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
+        .await
+        .unwrap();
+
+    let mut buf = vec![0; 4096];
+    let n = reader.read(&mut buf).await.unwrap();
+    let body = String::from_utf8_lossy(&buf[..n]);
+
+    let mut results: Vec<(i64, i64)> = Vec::new();
+    for frame in body
+        .lines()
+        .filter_map(|line| serde_json::from_str::<JsonValue>(line).ok())
+        .filter(|frame| frame["type"] == "batch")
+    {
+        for row in frame["rows"].as_array().unwrap() {
+            results.push((row[0].as_i64().unwrap(), row[1].as_i64().unwrap()));
+        }
+    }
+
+    assert_eq!(
+        results,
+        vec![(900, 2), (1_800, 0), (2_700, 0), (3_600, 1)],
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_query_aggregation_calendar_months_in_timezone() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("monthly_evt", &[("created_at", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(2, base_dir, wal_dir).await;
+
+    // 2024-03-01 00:00 in Amsterdam is 2024-02-29 23:00 UTC
+    let march_1 = 1_709_247_600;
+    for (ctx, created_at) in [("c1", march_1 - 1), ("c2", march_1), ("c3", march_1 + 60)] {
+        let store_cmd = crate::test_helpers::factories::CommandFactory::store()
+            .with_event_type("monthly_evt")
+            .with_context_id(ctx)
+            .with_payload(serde_json::json!({ "created_at": created_at }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+    sleep(Duration::from_millis(400)).await;
+
+    let months = |query: &'static str| {
+        let shard_manager = &shard_manager;
+        let registry = &registry;
+        async move {
+            let cmd = parse(query).expect("parse calendar query");
+            let (mut reader, mut writer) = duplex(4096);
+            execute_query(&cmd, shard_manager, registry, &mut writer, &JsonRenderer)
+                .await
+                .unwrap();
+            let mut buf = vec![0; 4096];
+            let n = reader.read(&mut buf).await.unwrap();
+            let body = String::from_utf8_lossy(&buf[..n]).to_string();
+            let mut results: Vec<(i64, i64)> = Vec::new();
+            for frame in body
+                .lines()
+                .filter_map(|line| serde_json::from_str::<JsonValue>(line).ok())
+                .filter(|frame| frame["type"] == "batch")
+            {
+                for row in frame["rows"].as_array().unwrap() {
+                    results.push((row[0].as_i64().unwrap(), row[1].as_i64().unwrap()));
+                }
+            }
+            results
+        }
+    };
+
+    // February 1 and March 1, Amsterdam midnight
+    assert_eq!(
+        months("QUERY monthly_evt COUNT BUCKET BY month TZ 'Europe/Amsterdam' USING created_at")
+            .await,
+        vec![(1_706_742_000, 1), (march_1 as i64, 2)]
+    );
+    // All three are still February in UTC
+    assert_eq!(
+        months("QUERY monthly_evt COUNT BUCKET BY month TZ 'UTC' USING created_at").await,
+        vec![(1_706_745_600, 3)]
+    );
+}
@@ -0,0 +1,185 @@
+use crate::command::types::{Command, RebuildIndexKind};
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::core::segment::index_rebuild::{RebuildOutcome, RebuildStatus};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::engine::shard::message::ShardMessage;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{Response, StatusCode};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::{RwLock, oneshot};
+use tracing::{debug, warn};
+
+/// Rebuilds zone index artifacts from stored columns, on one segment or on
+/// every segment of every shard, and reports each segment's outcome.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::RebuildIndexes {
+        kinds,
+        shard_id,
+        segment_id,
+    } = cmd
+    else {
+        let resp = Response::error(StatusCode::BadRequest, "Invalid REBUILD INDEXES command");
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    if let Some(auth_mgr) = auth_manager {
+        match user_id {
+            Some(uid) if uid == BYPASS_USER_ID || auth_mgr.is_admin(uid).await => {}
+            Some(uid) => {
+                warn!(target: "sneldb::rebuild", user_id = uid, "Admin permission denied");
+                let resp = Response::error(
+                    StatusCode::Forbidden,
+                    "Only admin users can rebuild indexes",
+                );
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+            None => {
+                let resp = Response::error(StatusCode::Unauthorized, "Authentication required");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    }
+
+    debug!(target: "sneldb::rebuild", ?kinds, ?shard_id, ?segment_id, "Rebuilding indexes");
+
+    let shards = match shard_id {
+        Some(shard_id) => match shard_manager.all_shards().get(*shard_id) {
+            Some(shard) => std::slice::from_ref(shard),
+            None => {
+                let resp = Response::error(
+                    StatusCode::NotFound,
+                    format!("Shard {} does not exist", shard_id),
+                );
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        },
+        None => shard_manager.all_shards(),
+    };
+    let segment = segment_id.map(|id| format!("{:05}", id));
+
+    let mut pending = Vec::with_capacity(shards.len());
+    for shard in shards {
+        let (tx, rx) = oneshot::channel();
+        let message = ShardMessage::RebuildIndexes {
+            kinds: kinds.clone(),
+            segment: segment.clone(),
+            registry: Arc::clone(registry),
+            response: tx,
+        };
+        if let Err(e) = shard.tx.send(message).await {
+            let resp = Response::error(
+                StatusCode::InternalError,
+                format!("Failed to dispatch rebuild to shard {}: {}", shard.id, e),
+            );
+            return writer.write_all(&renderer.render(&resp)).await;
+        }
+        pending.push((shard.id, rx));
+    }
+
+    let mut results = Vec::with_capacity(pending.len());
+    for (shard_id, rx) in pending {
+        match rx.await {
+            Ok(outcomes) => results.push((shard_id, outcomes)),
+            Err(_) => {
+                let resp = Response::error(
+                    StatusCode::InternalError,
+                    format!("Shard {} stopped before finishing the rebuild", shard_id),
+                );
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    }
+
+    let resp = match (&segment, results.first()) {
+        (Some(label), Some((shard_id, outcomes))) if outcomes.is_empty() => Response::error(
+            StatusCode::NotFound,
+            format!("Segment {} does not exist on shard {}", label, shard_id),
+        ),
+        _ => {
+            let registry = registry.read().await;
+            let event_type = |uid: &str| {
+                registry
+                    .get_event_type_by_uid(uid)
+                    .unwrap_or_else(|| uid.to_string())
+            };
+            Response::ok_lines(render_lines(kinds, &results, event_type))
+        }
+    };
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// A summary line, then one line per (shard, segment, event type) with its
+/// outcome; verification misses follow their segment, indented.
+pub fn render_lines<F>(
+    kinds: &[RebuildIndexKind],
+    results: &[(usize, Vec<RebuildOutcome>)],
+    event_type: F,
+) -> Vec<String>
+where
+    F: Fn(&str) -> String,
+{
+    let kinds: Vec<&str> = if kinds.is_empty() {
+        RebuildIndexKind::ALL.iter().map(|k| k.as_str()).collect()
+    } else {
+        kinds.iter().map(|k| k.as_str()).collect()
+    };
+    let outcomes = || results.iter().flat_map(|(_, outcomes)| outcomes);
+    let failed = outcomes()
+        .filter(|o| {
+            matches!(
+                o.status,
+                RebuildStatus::Failed(_) | RebuildStatus::VerificationFailed(_)
+            )
+        })
+        .count();
+
+    let mut lines = vec![format!(
+        "Rebuilt {} for {} segment indexes ({} failed)",
+        kinds.join(", "),
+        outcomes().count(),
+        failed
+    )];
+    for (shard_id, outcomes) in results {
+        for outcome in outcomes {
+            let prefix = format!(
+                "shard {} segment {} {}",
+                shard_id,
+                outcome.segment,
+                event_type(&outcome.uid)
+            );
+            match &outcome.status {
+                RebuildStatus::Rebuilt { zones, files } => lines.push(format!(
+                    "{}: rebuilt ({} zones verified, {} files)",
+                    prefix, zones, files
+                )),
+                RebuildStatus::AlreadyDone => {
+                    lines.push(format!("{}: already rebuilt by an earlier run", prefix))
+                }
+                RebuildStatus::Retired => {
+                    lines.push(format!("{}: retired by compaction, skipped", prefix))
+                }
+                RebuildStatus::VerificationFailed(problems) => {
+                    lines.push(format!(
+                        "{}: verification failed, kept existing indexes",
+                        prefix
+                    ));
+                    lines.extend(problems.iter().map(|p| format!("  {}", p)));
+                }
+                RebuildStatus::Failed(err) => lines.push(format!("{}: failed: {}", prefix, err)),
+            }
+        }
+    }
+    lines
+}
@@ -0,0 +1,153 @@
+use crate::command::handlers::rebuild_indexes::{handle, render_lines};
+use crate::command::types::{Command, RebuildIndexKind};
+use crate::engine::auth::AuthManager;
+use crate::engine::core::segment::index_rebuild::{RebuildOutcome, RebuildStatus};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::JsonRenderer;
+use std::sync::Arc;
+use tempfile::tempdir;
+use tokio::sync::RwLock;
+
+fn outcome(segment: &str, status: RebuildStatus) -> RebuildOutcome {
+    RebuildOutcome {
+        segment: segment.to_string(),
+        uid: "uid-order".to_string(),
+        status,
+    }
+}
+
+#[test]
+fn test_render_lines_reports_each_segment() {
+    let results = vec![
+        (
+            0,
+            vec![
+                outcome("00001", RebuildStatus::Rebuilt { zones: 4, files: 6 }),
+                outcome("00002", RebuildStatus::AlreadyDone),
+            ],
+        ),
+        (
+            1,
+            vec![
+                outcome(
+                    "00003",
+                    RebuildStatus::VerificationFailed(vec![
+                        "uid-order_amount.zxf: zone 1 misses '30'".to_string(),
+                    ]),
+                ),
+                outcome("00004", RebuildStatus::Retired),
+            ],
+        ),
+    ];
+
+    let lines = render_lines(
+        &[RebuildIndexKind::Xor, RebuildIndexKind::Enum],
+        &results,
+        |_| "order".to_string(),
+    );
+
+    assert_eq!(
+        lines,
+        vec![
+            "Rebuilt xor, enum for 4 segment indexes (1 failed)".to_string(),
+            "shard 0 segment 00001 order: rebuilt (4 zones verified, 6 files)".to_string(),
+            "shard 0 segment 00002 order: already rebuilt by an earlier run".to_string(),
+            "shard 1 segment 00003 order: verification failed, kept existing indexes".to_string(),
+            "  uid-order_amount.zxf: zone 1 misses '30'".to_string(),
+            "shard 1 segment 00004 order: retired by compaction, skipped".to_string(),
+        ]
+    );
+
+    let lines = render_lines(&[], &[], |uid| uid.to_string());
+    assert_eq!(
+        lines,
+        vec!["Rebuilt surf, xor, enum, temporal for 0 segment indexes (0 failed)".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_rebuild_indexes_requires_admin() {
+    let registry = Arc::new(RwLock::new(SchemaRegistry::new().unwrap()));
+    let base_dir = tempdir().unwrap();
+    let wal_dir = tempdir().unwrap();
+    let shard_manager = Arc::new(
+        ShardManager::new(
+            1,
+            base_dir.path().to_path_buf(),
+            wal_dir.path().to_path_buf(),
+        )
+        .await,
+    );
+
+    let auth_manager = Arc::new(AuthManager::new(Arc::clone(&shard_manager)));
+    auth_manager
+        .create_user("reader".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    auth_manager
+        .create_user_with_roles(
+            "root".to_string(),
+            Some("secret".to_string()),
+            vec!["admin".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let run = |user_id: &'static str, cmd: Command| {
+        let shard_manager = Arc::clone(&shard_manager);
+        let registry = Arc::clone(&registry);
+        let auth_manager = Arc::clone(&auth_manager);
+        async move {
+            let mut writer = Vec::new();
+            handle(
+                &cmd,
+                &shard_manager,
+                &registry,
+                Some(&auth_manager),
+                Some(user_id),
+                &mut writer,
+                &JsonRenderer,
+            )
+            .await
+            .unwrap();
+            String::from_utf8(writer).unwrap()
+        }
+    };
+    let segment = |shard_id| Command::RebuildIndexes {
+        kinds: vec![],
+        shard_id: Some(shard_id),
+        segment_id: Some(7),
+    };
+
+    let denied = run("reader", segment(0)).await;
+    assert!(denied.contains("Only admin users"), "got: {}", denied);
+
+    let missing = run("root", segment(0)).await;
+    assert!(
+        missing.contains("Segment 00007 does not exist on shard 0"),
+        "got: {}",
+        missing
+    );
+    let missing = run("root", segment(3)).await;
+    assert!(
+        missing.contains("Shard 3 does not exist"),
+        "got: {}",
+        missing
+    );
+
+    let all = run(
+        "root",
+        Command::RebuildIndexes {
+            kinds: vec![RebuildIndexKind::Surf],
+            shard_id: None,
+            segment_id: None,
+        },
+    )
+    .await;
+    assert!(
+        all.contains("Rebuilt surf for 0 segment indexes (0 failed)"),
+        "got: {}",
+        all
+    );
+}
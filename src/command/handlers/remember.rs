@@ -5,6 +5,7 @@ use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::command::handlers::query::QueryExecutionPipeline;
 use crate::command::types::{Command, MaterializedQuerySpec};
+use crate::engine::core::read::aggregate::plan::AggregatePlan;
 use crate::engine::core::read::flow::BatchPool;
 use crate::engine::materialize::{
     HighWaterMark, MaterializationCatalog, MaterializationEntry, MaterializedQuerySpecExt,
@@ -55,6 +56,9 @@ pub(crate) async fn remember_query_with_data_dir(
     if !matches!(query_command, Command::Query { .. }) {
         return Err("REMEMBER only supports QUERY commands".into());
     }
+    if !query_command.disabled_pruners().is_empty() {
+        return Err("REMEMBER does not support DISABLE PRUNERS".into());
+    }
 
     let mut catalog = MaterializationCatalog::load(data_dir)
         .map_err(|e| format!("Failed to load materialization catalog: {e}"))?;
@@ -70,7 +74,65 @@ pub(crate) async fn remember_query_with_data_dir(
     let mut entry = MaterializationEntry::new(spec.clone(), catalog.root_dir())
         .map_err(|e| format!("Failed to create catalog entry: {e}"))?;
 
-    let pipeline = QueryExecutionPipeline::new(&query_command, shard_manager, Arc::clone(registry));
+    let sink = match spec.aggregate_plan() {
+        Some(plan) => remember_aggregate(&spec, plan, &entry, shard_manager, registry).await?,
+        None => remember_rows(&query_command, &entry, shard_manager, registry).await?,
+    };
+
+    entry.schema = sink.schema().to_vec();
+    let high_water = sink.high_water_mark();
+    entry.high_water_mark = if high_water.is_zero() {
+        None
+    } else {
+        Some(high_water)
+    };
+    entry.row_count = sink.total_rows();
+    entry.delta_rows_appended = sink.last_rows_appended();
+    entry.byte_size = sink.total_bytes();
+    entry.delta_bytes_appended = sink.last_bytes_appended();
+    entry.touch();
+
+    catalog
+        .insert(entry)
+        .map_err(|e| format!("Failed to persist catalog: {e}"))?;
+
+    let mut summary = Vec::new();
+    summary.push(format!("remembered query '{}'", spec.alias()));
+    summary.push(format!("rows stored: {}", sink.total_rows()));
+    summary.push(format!("rows appended: {}", sink.last_rows_appended()));
+    summary.push(format!("compressed bytes: {}", sink.total_bytes()));
+    summary.push(format!("bytes appended: {}", sink.last_bytes_appended()));
+    if !high_water.is_zero() {
+        summary.push(format!(
+            "high-water mark: timestamp={} event_id={}",
+            high_water.timestamp, high_water.event_id
+        ));
+        if let Some(age) = high_water_age_seconds(high_water) {
+            summary.push(format!("high-water age (s): {}", age));
+        }
+    }
+
+    tracing::info!(
+        target: "sneldb::remember",
+        alias = spec.alias(),
+        rows = sink.total_rows(),
+        appended = sink.last_rows_appended(),
+        bytes = sink.total_bytes(),
+        appended_bytes = sink.last_bytes_appended(),
+        "Materialized query remembered"
+    );
+
+    Ok(summary)
+}
+
+/// Stores the query's result rows as frames, honoring its LIMIT.
+async fn remember_rows(
+    query_command: &Command,
+    entry: &MaterializationEntry,
+    shard_manager: &ShardManager,
+    registry: &Arc<tokio::sync::RwLock<SchemaRegistry>>,
+) -> Result<MaterializedSink, String> {
+    let pipeline = QueryExecutionPipeline::new(query_command, shard_manager, Arc::clone(registry));
 
     let mut stream = pipeline
         .execute_streaming()
@@ -82,17 +144,20 @@ pub(crate) async fn remember_query_with_data_dir(
     let snapshots = batch_schema_to_snapshots(&schema_arc);
 
     let store = MaterializedStore::open(&entry.storage_path)
-        .map_err(|e| format!("Failed to open materialized store: {e}"))?;
+        .map_err(|e| format!("Failed to open materialized store: {e}"))?
+        .with_frame_codec(entry.codec)
+        .with_frame_size(entry.frame_size);
 
     let mut sink = MaterializedSink::new(store, snapshots)
-        .map_err(|e| format!("Failed to initialize materialized sink: {e}"))?;
+        .map_err(|e| format!("Failed to initialize materialized sink: {e}"))?
+        .with_high_water_policy(entry.high_water_policy);
 
     if let Some(policy) = entry.retention.clone() {
         sink.set_retention_policy(policy);
     }
 
     // Extract LIMIT from query command
-    let limit = if let Command::Query { limit, .. } = &query_command {
+    let limit = if let Command::Query { limit, .. } = query_command {
         limit.map(|l| l as usize)
     } else {
         None
@@ -147,50 +212,54 @@ pub(crate) async fn remember_query_with_data_dir(
         }
     }
 
-    entry.schema = sink.schema().to_vec();
-    let high_water = sink.high_water_mark();
-    entry.high_water_mark = if high_water.is_zero() {
-        None
-    } else {
-        Some(high_water)
-    };
-    entry.row_count = sink.total_rows();
-    entry.delta_rows_appended = sink.last_rows_appended();
-    entry.byte_size = sink.total_bytes();
-    entry.delta_bytes_appended = sink.last_bytes_appended();
-    entry.touch();
-
-    catalog
-        .insert(entry)
-        .map_err(|e| format!("Failed to persist catalog: {e}"))?;
+    sink.commit()
+        .map_err(|e| format!("Failed to persist batch: {e}"))?;
+    Ok(sink)
+}
 
-    let mut summary = Vec::new();
-    summary.push(format!("remembered query '{}'", spec.alias()));
-    summary.push(format!("rows stored: {}", sink.total_rows()));
-    summary.push(format!("rows appended: {}", sink.last_rows_appended()));
-    summary.push(format!("compressed bytes: {}", sink.total_bytes()));
-    summary.push(format!("bytes appended: {}", sink.last_bytes_appended()));
-    if !high_water.is_zero() {
-        summary.push(format!(
-            "high-water mark: timestamp={} event_id={}",
-            high_water.timestamp, high_water.event_id
-        ));
-        if let Some(age) = high_water_age_seconds(high_water) {
-            summary.push(format!("high-water age (s): {}", age));
-        }
+/// Folds every source event of an aggregate query into a single state frame.
+async fn remember_aggregate(
+    spec: &MaterializedQuerySpec,
+    plan: AggregatePlan,
+    entry: &MaterializationEntry,
+    shard_manager: &ShardManager,
+    registry: &Arc<tokio::sync::RwLock<SchemaRegistry>>,
+) -> Result<MaterializedSink, String> {
+    let source_command = spec
+        .delta_command(None)
+        .map_err(|e| format!("Failed to build source query: {e}"))?;
+    let time_field = match &source_command {
+        Command::Query { time_field, .. } => time_field.as_deref().unwrap_or("timestamp"),
+        _ => "timestamp",
     }
+    .to_string();
 
-    tracing::info!(
-        target: "sneldb::remember",
-        alias = spec.alias(),
-        rows = sink.total_rows(),
-        appended = sink.last_rows_appended(),
-        bytes = sink.total_bytes(),
-        appended_bytes = sink.last_bytes_appended(),
-        "Materialized query remembered"
-    );
+    let pipeline =
+        QueryExecutionPipeline::new(&source_command, shard_manager, Arc::clone(registry));
+    let mut stream = pipeline
+        .execute_streaming()
+        .await
+        .map_err(|e| format!("Failed to execute query: {e}"))?
+        .ok_or_else(|| "Query cannot be executed in streaming mode".to_string())?;
 
-    Ok(summary)
+    let store = MaterializedStore::open(&entry.storage_path)
+        .map_err(|e| format!("Failed to open materialized store: {e}"))?
+        .with_frame_codec(entry.codec);
+    let mut sink = MaterializedSink::aggregating(store, plan, time_field)
+        .map_err(|e| format!("Failed to initialize materialized sink: {e}"))?
+        .with_high_water_policy(entry.high_water_policy);
+
+    while let Some(batch) = stream.recv().await {
+        sink.append(&batch)
+            .map_err(|e| format!("Failed to merge batch: {e}"))?;
+    }
+    if let Some(failure) = stream.failure() {
+        return Err(format!("Failed to execute query: {failure}"));
+    }
+
+    sink.commit()
+        .map_err(|e| format!("Failed to persist aggregate state: {e}"))?;
+    Ok(sink)
 }
 
 fn current_timestamp() -> u64 {
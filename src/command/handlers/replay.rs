@@ -1,6 +1,6 @@
 use crate::command::handlers::query::QueryExecutionPipeline;
 use crate::command::handlers::query::QueryResponseWriter;
-use crate::command::types::Command;
+use crate::command::types::{Command, OrderSpec};
 use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
 use crate::shared::response::render::Renderer;
@@ -15,7 +15,7 @@ use tracing::{debug, info, warn};
 /// REPLAY uses the same streaming infrastructure as QUERY, which provides:
 /// - Memory efficiency (no buffering entire result set)
 /// - Backpressure support
-/// - Incremental response delivery
+/// - Incremental response delivery, e.g. one event per line with the NDJSON renderer
 pub async fn handle<W: AsyncWrite + Unpin>(
     cmd: &Command,
     shard_manager: &ShardManager,
@@ -27,6 +27,8 @@ pub async fn handle<W: AsyncWrite + Unpin>(
         event_type,
         context_id,
         since,
+        desc,
+        limit,
         ..
     } = cmd
     else {
@@ -46,11 +48,13 @@ pub async fn handle<W: AsyncWrite + Unpin>(
         event_type = event_type.as_deref().unwrap_or("*"),
         context_id,
         since = ?since,
+        desc,
+        limit = ?limit,
         "Processing Replay command via streaming query path"
     );
 
     // Convert REPLAY to QUERY command
-    let query_cmd = match cmd.to_query_command() {
+    let mut query_cmd = match cmd.to_query_command() {
         Some(q) => q,
         None => {
             warn!(target: "sneldb::replay", "Failed to convert Replay to Query command");
@@ -62,6 +66,40 @@ pub async fn handle<W: AsyncWrite + Unpin>(
         }
     };
 
+    // Events are stored in ingest order; replay in the schema's declared time order instead.
+    // DESC always orders explicitly so the segment scan can walk zones newest-first.
+    if let Command::Query {
+        event_type,
+        time_field,
+        order_by,
+        ..
+    } = &mut query_cmd
+    {
+        let ordering_field = match time_field {
+            Some(field) => Some(field.clone()),
+            None => registry
+                .read()
+                .await
+                .time_field(event_type)
+                .map(str::to_string),
+        };
+        if *desc {
+            *order_by = Some(OrderSpec {
+                field: ordering_field.unwrap_or_else(|| "timestamp".to_string()),
+                desc: true,
+            });
+        } else if let Some(field) = ordering_field.filter(|f| f != "timestamp") {
+            *order_by = Some(OrderSpec { field, desc: false });
+        }
+    }
+    // Ordered replays have the limit applied by the flow merger
+    let response_limit = match &query_cmd {
+        Command::Query {
+            order_by: Some(_), ..
+        } => None,
+        _ => *limit,
+    };
+
     // Create query execution pipeline with the converted command
     let pipeline = QueryExecutionPipeline::new(&query_cmd, shard_manager, Arc::clone(registry));
 
@@ -75,12 +113,11 @@ pub async fn handle<W: AsyncWrite + Unpin>(
             );
 
             // Use QueryResponseWriter to stream results incrementally
-            // No limit/offset for replay - return all matching events
             let response_writer = QueryResponseWriter::new(
                 writer,
                 renderer,
                 stream.schema(),
-                None, // No limit
+                response_limit,
                 None, // No offset
             );
             response_writer.write(stream).await
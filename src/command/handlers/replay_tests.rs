@@ -214,9 +214,15 @@ async fn test_replay_handles_wildcard_event_type() {
         .create();
 
     let (mut reader, mut writer) = duplex(1024);
-    handle(&replay_cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
-        .await
-        .unwrap();
+    handle(
+        &replay_cmd,
+        &shard_manager,
+        &registry,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
 
     let mut buf = vec![0; 4096];
     let n = reader.read(&mut buf).await.unwrap();
@@ -313,9 +319,15 @@ async fn test_replay_with_since_timestamp() {
         .create();
 
     let (mut reader, mut writer) = duplex(1024);
-    handle(&replay_cmd, &shard_manager, &registry, &mut writer, &JsonRenderer)
-        .await
-        .unwrap();
+    handle(
+        &replay_cmd,
+        &shard_manager,
+        &registry,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
 
     let mut buf = vec![0; 4096];
     let n = reader.read(&mut buf).await.unwrap();
@@ -328,3 +340,239 @@ async fn test_replay_with_since_timestamp() {
         body
     );
 }
+
+#[tokio::test]
+async fn test_replay_orders_by_declared_time_field() {
+    use crate::logging::init_for_tests;
+    use crate::test_helpers::factories::MiniSchemaFactory;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    let registry = factory.registry();
+    let schema = MiniSchemaFactory::empty()
+        .with("label", "string")
+        .with("event_time", "u64")
+        .with_time_field("event_time")
+        .create();
+    registry
+        .write()
+        .await
+        .define("timed_event", schema)
+        .unwrap();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    // Stored out of event-time order; the last one gets the ingest time
+    let payloads = [
+        serde_json::json!({ "label": "second_evt", "event_time": 1_700_000_300u64 }),
+        serde_json::json!({ "label": "first_evt", "event_time": 1_700_000_100u64 }),
+        serde_json::json!({ "label": "latest_evt" }),
+    ];
+    for payload in payloads {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("timed_event")
+            .with_context_id("ctx-time")
+            .with_payload(payload)
+            .create();
+        let (mut r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .unwrap();
+        let mut buf = vec![0; 1024];
+        let n = r.read(&mut buf).await.unwrap();
+        let body = String::from_utf8_lossy(&buf[..n]);
+        assert!(body.contains("accepted"), "store failed: {}", body);
+    }
+    // The third event fills the memtable; let its flush complete
+    tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+
+    let replay_cmd = CommandFactory::replay()
+        .with_event_type("timed_event")
+        .with_context_id("ctx-time")
+        .create();
+
+    let (mut reader, mut writer) = duplex(8192);
+    handle(
+        &replay_cmd,
+        &shard_manager,
+        &registry,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+
+    let position = |label: &str| {
+        body.find(label)
+            .unwrap_or_else(|| panic!("{} missing from replay: {}", label, body))
+    };
+    let (first, second, latest) = (
+        position("first_evt"),
+        position("second_evt"),
+        position("latest_evt"),
+    );
+    assert!(
+        first < second && second < latest,
+        "Expected replay in event_time order, got: {}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_replay_desc_returns_newest_events_first() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("desc_event", &[("label", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    for label in ["evt_one", "evt_two", "evt_three", "evt_four"] {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("desc_event")
+            .with_context_id("ctx-desc")
+            .with_payload(serde_json::json!({ "label": label }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .unwrap();
+    }
+    // Let a memtable flush complete so both segment and memtable rows are replayed
+    tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+
+    let replay_cmd = CommandFactory::replay()
+        .with_event_type("desc_event")
+        .with_context_id("ctx-desc")
+        .with_desc(true)
+        .with_limit(2)
+        .create();
+
+    let (mut reader, mut writer) = duplex(8192);
+    handle(
+        &replay_cmd,
+        &shard_manager,
+        &registry,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+
+    let four = body
+        .find("evt_four")
+        .unwrap_or_else(|| panic!("newest event missing from replay: {}", body));
+    let three = body
+        .find("evt_three")
+        .unwrap_or_else(|| panic!("second newest event missing from replay: {}", body));
+    assert!(four < three, "Expected newest first, got: {}", body);
+    assert!(
+        !body.contains("evt_two") && !body.contains("evt_one"),
+        "Expected LIMIT 2 to keep only the newest events, got: {}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_replay_streams_one_ndjson_line_per_event() {
+    use crate::logging::init_for_tests;
+    use crate::shared::response::NdjsonRenderer;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("ndjson_event", &[("id", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    for id in 1..=3 {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("ndjson_event")
+            .with_context_id("ctx-nd")
+            .with_payload(serde_json::json!({ "id": id }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .unwrap();
+    }
+
+    let replay_cmd = CommandFactory::replay()
+        .with_event_type("ndjson_event")
+        .with_context_id("ctx-nd")
+        .create();
+
+    let (mut reader, mut writer) = duplex(64 * 1024);
+    handle(
+        &replay_cmd,
+        &shard_manager,
+        &registry,
+        &mut writer,
+        &NdjsonRenderer,
+    )
+    .await
+    .unwrap();
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+
+    // One event object per line, no schema or end frame around them
+    assert!(body.ends_with("}\n"), "unexpected body: {body}");
+    let events: Vec<serde_json::Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is a JSON object"))
+        .collect();
+    assert_eq!(events.len(), 3, "unexpected body: {body}");
+    for (event, id) in events.iter().zip(1..=3) {
+        assert_eq!(event["id"], id);
+        assert_eq!(event["context_id"], "ctx-nd");
+        assert!(event.get("type").is_none());
+    }
+}
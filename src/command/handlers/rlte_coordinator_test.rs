@@ -23,6 +23,14 @@ fn should_plan_returns_true_with_order_by() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     assert!(RlteCoordinator::should_plan(&cmd));
@@ -47,6 +55,14 @@ fn should_plan_returns_false_without_order_by() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     assert!(!RlteCoordinator::should_plan(&cmd));
@@ -83,6 +99,14 @@ fn should_plan_with_descending_order() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     assert!(RlteCoordinator::should_plan(&cmd));
@@ -113,6 +137,14 @@ fn should_plan_with_various_order_fields() {
             time_bucket: None,
             group_by: None,
             event_sequence: None,
+            latest_per: None,
+            dedup: None,
+            omit_nulls: false,
+            with_total: false,
+            raw_strings: false,
+            disabled_pruners: Vec::new(),
+            allow_full_scan: false,
+            unnest: None,
         };
 
         assert!(RlteCoordinator::should_plan(&cmd));
@@ -0,0 +1,136 @@
+use crate::command::handlers::schema_limits::SchemaLimits;
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::schema::catalog::{ImportAction, SchemaCatalog};
+use crate::engine::schema::{SchemaError, SchemaRegistry};
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{ErrorCode, Response, StatusCode};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+/// Handles `EXPORT SCHEMAS` and `IMPORT SCHEMAS`.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    handle_with_limits(
+        cmd,
+        registry,
+        auth_manager,
+        user_id,
+        &SchemaLimits::from_config(),
+        writer,
+        renderer,
+    )
+    .await
+}
+
+/// Like [`handle`], with explicit schema limits instead of those from `[schema]`.
+pub async fn handle_with_limits<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    limits: &SchemaLimits,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    if let Some(auth_mgr) = auth_manager {
+        match user_id {
+            Some(uid) if uid == BYPASS_USER_ID || auth_mgr.is_admin(uid).await => {}
+            Some(uid) => {
+                warn!(target: "sneldb::schema_catalog", user_id = uid, "Admin permission denied");
+                let resp = Response::error(
+                    StatusCode::Forbidden,
+                    "Only admin users can export or import schemas",
+                );
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+            None => {
+                let resp = Response::error(StatusCode::Unauthorized, "Authentication required");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    }
+
+    let resp = match cmd {
+        Command::ExportSchemas => {
+            let catalog = registry.read().await.export_catalog();
+            match serde_json::to_string(&catalog) {
+                Ok(document) => Response::ok_lines(vec![document]),
+                Err(e) => {
+                    Response::error(StatusCode::InternalError, format!("Export failed: {}", e))
+                }
+            }
+        }
+        Command::ImportSchemas { document } => import(document, registry, limits).await,
+        _ => Response::error(StatusCode::BadRequest, "Invalid schema catalog command"),
+    };
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+async fn import(
+    document: &serde_json::Value,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    limits: &SchemaLimits,
+) -> Response {
+    let catalog = match serde_json::from_value::<SchemaCatalog>(document.clone()) {
+        Ok(catalog) => catalog,
+        Err(e) => {
+            return Response::error(
+                StatusCode::BadRequest,
+                format!("Invalid schema catalog: {}", e),
+            );
+        }
+    };
+
+    // Limits and conflicts are checked under the lock that applies the import
+    let mut registry = registry.write().await;
+    if let Err(message) = limits.check_import(&registry, &catalog) {
+        warn!(target: "sneldb::schema_catalog", error = %message, "Schema limit exceeded");
+        return Response::error_with_code(
+            StatusCode::BadRequest,
+            ErrorCode::SchemaLimitExceeded,
+            message,
+        );
+    }
+
+    match registry.import_catalog(&catalog).await {
+        Ok(actions) => {
+            let count = |action| actions.iter().filter(|(_, a)| *a == action).count();
+            let mut lines = vec![format!(
+                "Imported {} schemas: {} created, {} updated, {} unchanged",
+                actions.len(),
+                count(ImportAction::Created),
+                count(ImportAction::Updated),
+                count(ImportAction::Unchanged)
+            )];
+            lines.extend(
+                actions
+                    .iter()
+                    .map(|(event_type, action)| format!("{}: {}", event_type, action.as_str())),
+            );
+            info!(target: "sneldb::schema_catalog", schemas = actions.len(), "Schema catalog imported");
+            Response::ok_lines(lines)
+        }
+        Err(SchemaError::ImportConflicts(conflicts)) => {
+            warn!(target: "sneldb::schema_catalog", conflicts = conflicts.len(), "Schema import rejected");
+            Response::error(
+                StatusCode::BadRequest,
+                format!("Import rejected, nothing applied: {}", conflicts.join("; ")),
+            )
+        }
+        Err(e) => {
+            error!(target: "sneldb::schema_catalog", error = %e, "Schema import failed");
+            Response::error(StatusCode::InternalError, format!("Import failed: {}", e))
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::command::handlers::schema_catalog::{handle, handle_with_limits};
+use crate::command::handlers::schema_limits::SchemaLimits;
+use crate::command::types::Command;
+use crate::engine::auth::AuthManager;
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::{MiniSchemaFactory, SchemaRegistryFactory};
+use serde_json::Value;
+use std::sync::Arc;
+use tempfile::tempdir;
+use tokio::sync::RwLock;
+
+async fn run(
+    cmd: &Command,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    limits: &SchemaLimits,
+) -> String {
+    let mut writer = Vec::new();
+    handle_with_limits(
+        cmd,
+        registry,
+        None,
+        None,
+        limits,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+    String::from_utf8(writer).unwrap()
+}
+
+/// The exported document, taken from the first line of the response.
+fn exported_document(output: &str) -> Value {
+    let response: Value = serde_json::from_str(output).unwrap();
+    let line = response["results"][0].as_str().unwrap();
+    serde_json::from_str(line).unwrap()
+}
+
+#[tokio::test]
+async fn test_export_then_import_into_another_registry() {
+    let source = SchemaRegistryFactory::new();
+    source
+        .registry()
+        .write()
+        .await
+        .define(
+            "order",
+            MiniSchemaFactory::empty()
+                .with("amount", "int")
+                .with_enum("plan", &["free", "pro"])
+                .create(),
+        )
+        .unwrap();
+    let export = run(
+        &Command::ExportSchemas,
+        &source.registry(),
+        &SchemaLimits::default(),
+    )
+    .await;
+    let document = exported_document(&export);
+    assert_eq!(document["schemas"][0]["event_type"], "order");
+
+    let target = SchemaRegistryFactory::new();
+    let import = Command::ImportSchemas { document };
+    let first = run(&import, &target.registry(), &SchemaLimits::default()).await;
+    assert!(
+        first.contains("Imported 1 schemas: 1 created, 0 updated, 0 unchanged"),
+        "got: {}",
+        first
+    );
+    assert!(first.contains("order: created"), "got: {}", first);
+    assert_eq!(
+        target.registry().read().await.export_catalog(),
+        source.registry().read().await.export_catalog()
+    );
+
+    let again = run(&import, &target.registry(), &SchemaLimits::default()).await;
+    assert!(again.contains("order: unchanged"), "got: {}", again);
+}
+
+#[tokio::test]
+async fn test_import_reports_invalid_documents_conflicts_and_limits() {
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("order", &[("amount", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let mut catalog = registry.read().await.export_catalog();
+
+    let invalid = Command::ImportSchemas {
+        document: serde_json::json!({ "schemas": [] }),
+    };
+    let out = run(&invalid, &registry, &SchemaLimits::default()).await;
+    assert!(out.contains("Invalid schema catalog"), "got: {}", out);
+
+    let mut extra = catalog.schemas[0].clone();
+    extra.event_type = "refund".to_string();
+    extra.uid = "refund-uid".to_string();
+    catalog.schemas.push(extra);
+    catalog.schemas[0].schema = MiniSchemaFactory::empty().with("amount", "string").create();
+    let conflicting = Command::ImportSchemas {
+        document: serde_json::to_value(&catalog).unwrap(),
+    };
+    let out = run(&conflicting, &registry, &SchemaLimits::default()).await;
+    assert!(
+        out.contains("Import rejected, nothing applied: 'order': field 'amount' would change type from i64 to string"),
+        "got: {}",
+        out
+    );
+    assert!(!registry.read().await.has_schema("refund"));
+
+    let limits = SchemaLimits {
+        max_schemas: Some(1),
+        max_fields_per_schema: None,
+    };
+    let out = run(&conflicting, &registry, &limits).await;
+    assert!(out.contains("SCHEMA_LIMIT_EXCEEDED"), "got: {}", out);
+}
+
+#[tokio::test]
+async fn test_schema_catalog_requires_admin() {
+    let factory = SchemaRegistryFactory::new();
+    let registry = factory.registry();
+    let base_dir = tempdir().unwrap();
+    let wal_dir = tempdir().unwrap();
+    let shard_manager = Arc::new(
+        ShardManager::new(
+            1,
+            base_dir.path().to_path_buf(),
+            wal_dir.path().to_path_buf(),
+        )
+        .await,
+    );
+    let auth_manager = Arc::new(AuthManager::new(Arc::clone(&shard_manager)));
+    auth_manager
+        .create_user("reader".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+
+    let mut writer = Vec::new();
+    handle(
+        &Command::ExportSchemas,
+        &registry,
+        Some(&auth_manager),
+        Some("reader"),
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+    let out = String::from_utf8(writer).unwrap();
+    assert!(out.contains("Only admin users"), "got: {}", out);
+}
@@ -0,0 +1,97 @@
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::schema::catalog::SchemaCatalog;
+use crate::shared::config::CONFIG;
+
+/// Schema count limits applied on DEFINE and IMPORT SCHEMAS, to bound what the registry and the
+/// per-schema metadata hold.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchemaLimits {
+    /// Schemas the registry may hold; `None` = unlimited
+    pub max_schemas: Option<usize>,
+    /// Payload fields one schema may declare; `None` = unlimited
+    pub max_fields_per_schema: Option<usize>,
+}
+
+impl SchemaLimits {
+    /// Limits from `[schema]`; unlimited when unset.
+    pub fn from_config() -> Self {
+        Self {
+            max_schemas: CONFIG.schema.max_schemas,
+            max_fields_per_schema: CONFIG.schema.max_fields_per_schema,
+        }
+    }
+
+    /// Checks whether defining `event_type` with `field_count` fields stays within
+    /// the limits. Run it under the registry write lock that also defines the schema,
+    /// so concurrent DEFINEs cannot both take the last slot. Redefining an existing
+    /// name is left to the registry, which rejects it.
+    pub fn check(
+        &self,
+        registry: &SchemaRegistry,
+        event_type: &str,
+        field_count: usize,
+    ) -> Result<(), String> {
+        if let Some(limit) = self.max_fields_per_schema
+            && field_count > limit
+        {
+            return Err(format!(
+                "Schema for event type '{}' declares {} fields, exceeding the limit of {}",
+                event_type, field_count, limit
+            ));
+        }
+        if let Some(limit) = self.max_schemas
+            && !registry.has_schema(event_type)
+            && registry.get_all().len() >= limit
+        {
+            return Err(format!(
+                "Cannot define event type '{}': the registry already holds {} schemas, the limit is {}",
+                event_type,
+                registry.get_all().len(),
+                limit
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks whether importing `catalog` stays within the limits, counting
+    /// each event type the registry does not hold yet as a new schema.
+    pub fn check_import(
+        &self,
+        registry: &SchemaRegistry,
+        catalog: &SchemaCatalog,
+    ) -> Result<(), String> {
+        if let Some(limit) = self.max_fields_per_schema
+            && let Some(entry) = catalog
+                .schemas
+                .iter()
+                .find(|e| e.schema.fields.len() > limit)
+        {
+            return Err(format!(
+                "Schema for event type '{}' declares {} fields, exceeding the limit of {}",
+                entry.event_type,
+                entry.schema.fields.len(),
+                limit
+            ));
+        }
+        if let Some(limit) = self.max_schemas {
+            let mut new_types: Vec<&str> = catalog
+                .schemas
+                .iter()
+                .map(|e| e.event_type.as_str())
+                .filter(|et| !registry.has_schema(et))
+                .collect();
+            new_types.sort_unstable();
+            new_types.dedup();
+            let total = registry.get_all().len() + new_types.len();
+            if !new_types.is_empty() && total > limit {
+                return Err(format!(
+                    "Cannot import {} new event types: the registry would hold {} schemas, the limit is {}",
+                    new_types.len(),
+                    total,
+                    limit
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::command::handlers::schema_limits::SchemaLimits;
+use crate::engine::schema::SchemaRegistry;
+use crate::test_helpers::factories::MiniSchemaFactory;
+use tempfile::tempdir;
+
+#[test]
+fn test_check_enforces_field_and_schema_counts() {
+    let dir = tempdir().unwrap();
+    let mut registry = SchemaRegistry::new_with_path(dir.path().join("schemas.bin")).unwrap();
+    registry
+        .define("order", MiniSchemaFactory::new().create())
+        .unwrap();
+
+    let limits = SchemaLimits {
+        max_schemas: Some(1),
+        max_fields_per_schema: Some(3),
+    };
+
+    let err = limits.check(&registry, "refund", 1).unwrap_err();
+    assert!(err.contains("already holds 1 schemas"), "{}", err);
+    assert!(err.contains("limit is 1"), "{}", err);
+
+    let err = limits.check(&registry, "order", 4).unwrap_err();
+    assert!(err.contains("declares 4 fields"), "{}", err);
+    assert!(err.contains("limit of 3"), "{}", err);
+
+    // Redefining an existing name does not take a new slot
+    assert!(limits.check(&registry, "order", 3).is_ok());
+    assert!(
+        SchemaLimits::default()
+            .check(&registry, "refund", 500)
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_check_import_counts_only_new_event_types() {
+    let dir = tempdir().unwrap();
+    let mut registry = SchemaRegistry::new_with_path(dir.path().join("schemas.bin")).unwrap();
+    registry
+        .define("order", MiniSchemaFactory::new().create())
+        .unwrap();
+    let mut catalog = registry.export_catalog();
+
+    let limits = SchemaLimits {
+        max_schemas: Some(1),
+        max_fields_per_schema: Some(10),
+    };
+    assert!(limits.check_import(&registry, &catalog).is_ok());
+
+    let mut refund = catalog.schemas[0].clone();
+    refund.event_type = "refund".to_string();
+    catalog.schemas.push(refund);
+    let err = limits.check_import(&registry, &catalog).unwrap_err();
+    assert!(err.contains("would hold 2 schemas"), "{}", err);
+
+    let narrow = SchemaLimits {
+        max_schemas: None,
+        max_fields_per_schema: Some(1),
+    };
+    let err = narrow.check_import(&registry, &catalog).unwrap_err();
+    assert!(err.contains("'order' declares"), "{}", err);
+}
@@ -51,6 +51,14 @@ impl<'a> ShardCommandBuilder<'a> {
             time_bucket,
             group_by,
             event_sequence,
+            latest_per,
+            dedup,
+            omit_nulls,
+            with_total,
+            raw_strings,
+            disabled_pruners,
+            allow_full_scan,
+            unnest,
         } = self.base_cmd
         else {
             // Not a Query command, return borrowed
@@ -77,6 +85,14 @@ impl<'a> ShardCommandBuilder<'a> {
                 time_bucket: time_bucket.clone(),
                 group_by: group_by.clone(),
                 event_sequence: event_sequence.clone(),
+                latest_per: latest_per.clone(),
+                dedup: dedup.clone(),
+                omit_nulls: *omit_nulls,
+                with_total: *with_total,
+                raw_strings: *raw_strings,
+                disabled_pruners: disabled_pruners.clone(),
+                allow_full_scan: *allow_full_scan,
+                unnest: unnest.clone(),
             })
         } else {
             // Shard has no zones - send empty picked_zones to enforce zero results
@@ -109,6 +125,14 @@ impl<'a> ShardCommandBuilder<'a> {
             time_bucket,
             group_by,
             event_sequence,
+            latest_per,
+            dedup,
+            omit_nulls,
+            with_total,
+            raw_strings,
+            disabled_pruners,
+            allow_full_scan,
+            unnest,
             ..
         } = base_cmd
         else {
@@ -148,6 +172,14 @@ impl<'a> ShardCommandBuilder<'a> {
             time_bucket: time_bucket.clone(),
             group_by: group_by.clone(),
             event_sequence: event_sequence.clone(),
+            latest_per: latest_per.clone(),
+            dedup: dedup.clone(),
+            omit_nulls: *omit_nulls,
+            with_total: *with_total,
+            raw_strings: *raw_strings,
+            disabled_pruners: disabled_pruners.clone(),
+            allow_full_scan: *allow_full_scan,
+            unnest: unnest.clone(),
         }
     }
 }
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use crate::command::handlers::query_batch_stream::QueryBatchStream;
+use crate::command::handlers::show::errors::{ShowError, ShowResult};
+use crate::engine::core::read::aggregate::plan::AggregatePlan;
+use crate::engine::core::read::flow::operators::aggregate_output_schema;
+use crate::engine::core::read::flow::{
+    BatchPool, BatchSchema, FlowChannel, FlowContext, FlowMetrics, FlowSource, FlowTelemetry,
+};
+use crate::engine::materialize::{
+    HighWaterMark, MaterializationEntry, MaterializedSink, MaterializedSource, MaterializedStore,
+};
+
+use super::watermark::WatermarkDeduplicator;
+
+const SOURCE_BATCH_SIZE: usize = 1024;
+
+/// Refreshes a materialized aggregate view: merges the source events newer
+/// than the stored high-water mark into the stored aggregate state.
+pub struct AggregateRefresher {
+    sink: MaterializedSink,
+    plan: AggregatePlan,
+    timestamp_column: String,
+    initial_high_water: HighWaterMark,
+}
+
+impl AggregateRefresher {
+    pub fn new(
+        entry: &MaterializationEntry,
+        plan: AggregatePlan,
+        timestamp_column: String,
+    ) -> ShowResult<Self> {
+        let store = MaterializedStore::open(&entry.storage_path)
+            .map_err(|err| ShowError::new(format!("Failed to open sink store: {err}")))?
+            .with_frame_codec(entry.codec);
+
+        let sink = MaterializedSink::aggregating(store, plan.clone(), timestamp_column.clone())
+            .map_err(|err| {
+                ShowError::new(format!(
+                    "Failed to load materialized aggregate state: {err}"
+                ))
+            })?
+            .with_high_water_policy(entry.high_water_policy);
+        let initial_high_water = sink.high_water_mark();
+
+        Ok(Self {
+            sink,
+            plan,
+            timestamp_column,
+            initial_high_water,
+        })
+    }
+
+    pub fn initial_high_water(&self) -> HighWaterMark {
+        self.initial_high_water
+    }
+
+    /// Merges the delta stream into the state and persists it. Events at or
+    /// below the stored high-water mark are skipped. If the delta query fails
+    /// partway, only what the entry's high-water policy already persisted is
+    /// kept, so the mark never covers events that were not merged.
+    pub async fn merge(&mut self, mut stream: QueryBatchStream) -> ShowResult<()> {
+        let schema = stream.schema();
+        let position = |name: &str| schema.columns().iter().position(|c| c.name == name);
+        let mut watermark = WatermarkDeduplicator::new(
+            self.initial_high_water,
+            position(&self.timestamp_column),
+            position("event_id"),
+        );
+
+        while let Some(batch) = stream.recv().await {
+            let Some(batch) = watermark.filter(batch) else {
+                continue;
+            };
+            self.sink.append(&batch).map_err(|err| {
+                ShowError::new(format!("Failed to merge delta into aggregate state: {err}"))
+            })?;
+        }
+
+        if let Some(failure) = stream.failure() {
+            return Err(ShowError::new(format!("Delta query failed: {failure}")));
+        }
+
+        self.sink
+            .commit()
+            .map_err(|err| ShowError::new(format!("Failed to persist aggregate state: {err}")))
+    }
+
+    /// Streams the stored state as per-group partials for the aggregate merger.
+    pub fn stream_state(&self, entry: &MaterializationEntry) -> ShowResult<QueryBatchStream> {
+        let store = MaterializedStore::open(&entry.storage_path)
+            .map_err(|err| ShowError::new(format!("Failed to open materialized store: {err}")))?;
+        let source = MaterializedSource::aggregated(store, self.plan.clone());
+        let schema = BatchSchema::new(aggregate_output_schema(&self.plan))
+            .map_err(|err| ShowError::new(format!("Failed to build batch schema: {err}")))?;
+
+        let metrics = FlowMetrics::new();
+        let (sender, receiver) = FlowChannel::bounded(2, Arc::clone(&metrics));
+        let pool = BatchPool::new(SOURCE_BATCH_SIZE)
+            .map_err(|err| ShowError::new(format!("Failed to create batch pool: {err}")))?;
+        let ctx = Arc::new(FlowContext::new(
+            SOURCE_BATCH_SIZE,
+            pool,
+            metrics,
+            None::<&str>,
+            FlowTelemetry::default(),
+        ));
+
+        let task = tokio::spawn(async move {
+            if let Err(err) = source.run(sender, ctx).await {
+                tracing::error!(
+                    target: "sneldb::show",
+                    error = %err,
+                    "Failed to stream materialized aggregate state"
+                );
+            }
+        });
+
+        Ok(QueryBatchStream::new(
+            Arc::new(schema),
+            receiver,
+            vec![task],
+        ))
+    }
+
+    pub fn into_sink(self) -> MaterializedSink {
+        self.sink
+    }
+}
@@ -1,3 +1,4 @@
+mod aggregate;
 mod refresher;
 mod schema;
 mod watermark;
@@ -9,5 +10,7 @@ mod schema_test;
 #[cfg(test)]
 mod watermark_test;
 
+pub use aggregate::AggregateRefresher;
 pub use refresher::DeltaRefresher;
 pub use schema::SchemaBuilder;
+pub use watermark::WatermarkDeduplicator;
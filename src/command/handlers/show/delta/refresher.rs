@@ -27,10 +27,13 @@ impl DeltaRefresher {
         event_idx: Option<usize>,
     ) -> ShowResult<Self> {
         let store = MaterializedStore::open(&entry.storage_path)
-            .map_err(|err| ShowError::new(format!("Failed to open sink store: {err}")))?;
+            .map_err(|err| ShowError::new(format!("Failed to open sink store: {err}")))?
+            .with_frame_codec(entry.codec)
+            .with_frame_size(entry.frame_size);
 
         let mut sink = MaterializedSink::new(store, entry.schema.clone())
-            .map_err(|err| ShowError::new(format!("Failed to create materialized sink: {err}")))?;
+            .map_err(|err| ShowError::new(format!("Failed to create materialized sink: {err}")))?
+            .with_high_water_policy(entry.high_water_policy);
 
         if let Some(policy) = entry.retention.clone() {
             sink.set_retention_policy(policy);
@@ -155,6 +158,15 @@ impl DeltaRefresher {
                 }
             }
 
+            // Write the rows short of a full frame
+            if let Err(err) = sink.lock().await.commit() {
+                tracing::error!(
+                    target: "sneldb::show",
+                    error = %err,
+                    "Failed to append buffered delta rows"
+                );
+            }
+
             let stream_time = stream_start.elapsed();
             if tracing::enabled!(tracing::Level::DEBUG) {
                 tracing::debug!(
@@ -2048,3 +2048,117 @@ async fn test_show_handler_creation_is_idempotent() {
         assert!(handler.is_ok(), "Handler creation {} should succeed", i);
     }
 }
+
+#[tokio::test]
+async fn test_show_aggregate_materialization_merges_deltas_e2e() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let data_dir_temp = tempdir().unwrap();
+    let data_dir = data_dir_temp.path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("agg_test", &[("id", "int"), ("status", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    let store_status = |ctx: &str, id: i64, status: &str| {
+        CommandFactory::store()
+            .with_event_type("agg_test")
+            .with_context_id(ctx)
+            .with_payload(serde_json::json!({"id": id, "status": status}))
+            .create()
+    };
+
+    for (ctx, id, status) in [("a1", 1, "open"), ("a2", 2, "open"), ("a3", 3, "closed")] {
+        let (_r, mut w) = duplex(1024);
+        execute_store(
+            &store_status(ctx, id, status),
+            &shard_manager,
+            &registry,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+
+    sleep(Duration::from_millis(100)).await;
+
+    let flush_cmd = flush::parse(&tokenize("FLUSH")).expect("parse FLUSH");
+    let (_r_flush, mut w_flush) = duplex(1024);
+    execute_flush(
+        &flush_cmd,
+        &shard_manager,
+        &registry,
+        &mut w_flush,
+        &JsonRenderer,
+    )
+    .await
+    .expect("flush should succeed");
+
+    sleep(Duration::from_millis(200)).await;
+
+    let remember_cmd = remember::parse("REMEMBER QUERY agg_test COUNT BY status AS status_counts")
+        .expect("parse REMEMBER command");
+    let (mut r_remember, mut w_remember) = duplex(2048);
+    execute_remember_with_data_dir(
+        &remember_cmd,
+        &shard_manager,
+        &registry,
+        &data_dir,
+        &mut w_remember,
+        &JsonRenderer,
+    )
+    .await
+    .expect("remember should succeed");
+    let mut buf = vec![0u8; 2048];
+    let n = r_remember.read(&mut buf).await.unwrap();
+    let body = String::from_utf8_lossy(&buf[..n]);
+    assert!(body.contains("status_counts"), "REMEMBER failed: {body}");
+
+    // Events use second resolution timestamps, keep the delta past the high-water mark
+    sleep(Duration::from_secs(1)).await;
+
+    for (ctx, id, status) in [("a4", 4, "open"), ("a5", 5, "pending")] {
+        let (_r, mut w) = duplex(1024);
+        execute_store(
+            &store_status(ctx, id, status),
+            &shard_manager,
+            &registry,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+
+    sleep(Duration::from_millis(100)).await;
+
+    // Showing twice must not merge the same delta twice
+    for _ in 0..2 {
+        let (mut reader, mut writer) = duplex(8192);
+        let handler = ShowCommandHandler::new_with_data_dir(
+            "status_counts",
+            &shard_manager,
+            Arc::clone(&registry),
+            &data_dir,
+        )
+        .expect("handler should be created");
+        handler
+            .execute(&mut writer, &JsonRenderer)
+            .await
+            .expect("SHOW should succeed");
+        drop(writer);
+
+        let mut body = String::new();
+        reader.read_to_string(&mut body).await.unwrap();
+        assert!(body.contains("[\"open\",3]"), "open count: {body}");
+        assert!(body.contains("[\"closed\",1]"), "closed count: {body}");
+        assert!(body.contains("[\"pending\",1]"), "pending count: {body}");
+    }
+}
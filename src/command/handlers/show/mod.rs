@@ -21,5 +21,6 @@ mod orchestrator_test;
 #[cfg(test)]
 mod result_test;
 
+pub(crate) use delta::WatermarkDeduplicator;
 pub use handler::{ShowCommandHandler, handle};
 pub use orchestrator::ShowExecutionPipeline;
@@ -5,9 +5,11 @@ use std::time::Instant;
 use tokio::io::AsyncWrite;
 
 use crate::command::handlers::query::QueryExecutionPipeline;
+use crate::command::handlers::query::merge::aggregate_stream::AggregateStreamMerger;
 use crate::command::handlers::query_batch_stream::QueryBatchStream;
 use crate::command::types::Command;
-use crate::engine::core::read::flow::{FlowChannel, FlowMetrics};
+use crate::engine::core::read::aggregate::plan::AggregatePlan;
+use crate::engine::core::read::flow::{FlowChannel, FlowMetrics, QueryMemoryBudget};
 use crate::engine::materialize::{
     HighWaterMark, MaterializationEntry, MaterializedQuerySpecExt, MaterializedSink,
 };
@@ -19,7 +21,7 @@ use crate::shared::response::render::Renderer;
 
 use super::catalog::{CatalogGateway, CatalogHandle, FileCatalogGateway};
 use super::context::ShowContext;
-use super::delta::{DeltaRefresher, SchemaBuilder};
+use super::delta::{AggregateRefresher, DeltaRefresher, SchemaBuilder};
 use super::errors::{ShowError, ShowResult};
 use super::result::ShowRefreshOutcome;
 use super::store::StoredFrameStreamer;
@@ -81,8 +83,9 @@ impl<'a, G: CatalogGateway> ShowExecutionPipeline<'a, G> {
     pub(crate) fn test_build_delta_command(
         &self,
         entry: &MaterializationEntry,
+        stored_high_water: HighWaterMark,
     ) -> ShowResult<Command> {
-        self.build_delta_command(entry)
+        self.build_delta_command(entry, stored_high_water)
     }
 
     pub(crate) fn test_build_outcome(
@@ -109,6 +112,12 @@ impl<'a, G: CatalogGateway> ShowExecutionPipeline<'a, G> {
         self.ensure_schema_present(&entry)?;
         self.wait_for_inflight_flushes().await?;
 
+        if let Some(plan) = entry.spec.aggregate_plan() {
+            return self
+                .run_aggregate(catalog_handle, entry, plan, writer, renderer)
+                .await;
+        }
+
         let schema = SchemaBuilder::build(&entry)?;
         let timestamp_column = self.timestamp_column(&entry);
         let timestamp_idx = schema
@@ -147,7 +156,7 @@ impl<'a, G: CatalogGateway> ShowExecutionPipeline<'a, G> {
             initial_high_water.event_id.to_string(),
         );
 
-        let delta_command = self.build_delta_command(&entry)?;
+        let delta_command = self.build_delta_command(&entry, initial_high_water)?;
 
         let delta_pipeline = QueryExecutionPipeline::new(
             &delta_command,
@@ -221,6 +230,57 @@ impl<'a, G: CatalogGateway> ShowExecutionPipeline<'a, G> {
         Ok(())
     }
 
+    /// Refreshes an aggregate view by merging the delta into its stored state,
+    /// records the new high-water mark, then serves the finalized groups.
+    async fn run_aggregate<W: AsyncWrite + Unpin>(
+        self,
+        mut catalog_handle: CatalogHandle,
+        entry: MaterializationEntry,
+        plan: AggregatePlan,
+        writer: &mut W,
+        renderer: &dyn Renderer,
+    ) -> ShowResult<()> {
+        let show_start = Instant::now();
+        let timestamp_column = self.timestamp_column(&entry);
+        let mut refresher = AggregateRefresher::new(&entry, plan, timestamp_column)?;
+        let initial_high_water = refresher.initial_high_water();
+
+        let delta_command = self.build_delta_command(&entry, initial_high_water)?;
+        let delta_stream = QueryExecutionPipeline::new(
+            &delta_command,
+            self.context.shard_manager(),
+            self.context.registry(),
+        )
+        .execute_streaming()
+        .await
+        .map_err(|err| ShowError::new(format!("Failed to execute delta pipeline: {err}")))?;
+        if let Some(stream) = delta_stream {
+            refresher.merge(stream).await?;
+        }
+
+        let state = refresher.stream_state(&entry)?;
+        let sink = refresher.into_sink();
+        let merged = entry.spec.query().clone();
+        let outcome = self.build_outcome(entry, sink, initial_high_water);
+        self.persist_outcome(&mut catalog_handle, outcome)?;
+
+        let stream = AggregateStreamMerger::new(&merged)
+            .merge_stream(state, QueryMemoryBudget::unlimited())
+            .map_err(|err| ShowError::new(format!("Failed to finalize aggregates: {err}")))?;
+        let response_writer =
+            ShowResponseWriter::new(writer, renderer, stream.schema(), 1, true, None, None);
+        response_writer.write(stream).await?;
+
+        tracing::info!(
+            target: "sneldb::show",
+            alias = self.context.alias(),
+            total_time_ms = show_start.elapsed().as_millis(),
+            "SHOW aggregate view completed"
+        );
+
+        Ok(())
+    }
+
     async fn wait_for_inflight_flushes(&self) -> ShowResult<()> {
         debug!(
             target: "sneldb::show",
@@ -277,10 +337,20 @@ impl<'a, G: CatalogGateway> ShowExecutionPipeline<'a, G> {
         }
     }
 
-    fn build_delta_command(&self, entry: &MaterializationEntry) -> ShowResult<Command> {
+    /// The delta resumes from the newer of the catalog's mark and the mark
+    /// stored with the frames. The stored one is ahead when a refresh
+    /// persisted frames but stopped before updating the catalog, so only the
+    /// events after it are read again.
+    fn build_delta_command(
+        &self,
+        entry: &MaterializationEntry,
+        stored_high_water: HighWaterMark,
+    ) -> ShowResult<Command> {
+        let mut resume = entry.high_water_mark.unwrap_or_default();
+        resume.advance(stored_high_water.timestamp, stored_high_water.event_id);
         entry
             .spec
-            .delta_command(entry.high_water_mark)
+            .delta_command((!resume.is_zero()).then_some(resume))
             .map_err(|err| ShowError::new(format!("Failed to build delta command: {err}")))
     }
 
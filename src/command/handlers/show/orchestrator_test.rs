@@ -120,7 +120,7 @@ fn build_delta_command_updates_since_from_watermark() {
     entry.high_water_mark = Some(HighWaterMark::new(123, 1));
 
     let command = pipeline
-        .test_build_delta_command(&entry)
+        .test_build_delta_command(&entry, HighWaterMark::default())
         .expect("delta command");
 
     if let Command::Query { since, .. } = command {
@@ -130,6 +130,32 @@ fn build_delta_command_updates_since_from_watermark() {
     }
 }
 
+#[test]
+fn build_delta_command_resumes_from_stored_mark_when_ahead() {
+    let (context, temp_dir) = make_context();
+    let pipeline = ShowExecutionPipeline::new_with_gateway(context, StubGateway);
+
+    // A refresh persisted frames up to 456, then stopped before the catalog update
+    let mut entry = make_entry(temp_dir.path());
+    entry.high_water_mark = Some(HighWaterMark::new(123, 1));
+    let command = pipeline
+        .test_build_delta_command(&entry, HighWaterMark::new(456, 9))
+        .expect("delta command");
+    let Command::Query { since, .. } = command else {
+        panic!("expected query command");
+    };
+    assert_eq!(since, Some("456".to_string()));
+
+    // A store emptied by retention does not rewind the catalog's mark
+    let command = pipeline
+        .test_build_delta_command(&entry, HighWaterMark::default())
+        .expect("delta command");
+    let Command::Query { since, .. } = command else {
+        panic!("expected query command");
+    };
+    assert_eq!(since, Some("123".to_string()));
+}
+
 #[test]
 fn build_outcome_updates_entry_metrics() {
     let (context, temp_dir) = make_context();
@@ -152,6 +178,7 @@ fn build_outcome_updates_entry_metrics() {
     )
     .expect("batch");
     sink.append(&batch).expect("append");
+    sink.commit().expect("commit");
 
     let outcome: ShowRefreshOutcome =
         pipeline.test_build_outcome(entry, sink, HighWaterMark::default());
@@ -171,6 +198,7 @@ async fn wait_for_inflight_flushes_propagates_errors() {
         id: 0,
         tx,
         base_dir: PathBuf::new(),
+        wal_dir: PathBuf::new(),
     };
     let shard_manager = Box::leak(Box::new(ShardManager {
         shards: vec![shard],
@@ -212,6 +240,7 @@ async fn wait_for_inflight_flushes_waits_for_completion() {
         id: 0,
         tx,
         base_dir: PathBuf::new(),
+        wal_dir: PathBuf::new(),
     };
     let shard_manager = Box::leak(Box::new(ShardManager {
         shards: vec![shard],
@@ -45,18 +45,21 @@ impl StoredFrameStreamer {
                 }
             };
 
-            let read_tasks: Vec<_> = frames
-                .into_iter()
-                .map(|meta| {
-                    let store = Arc::clone(&store);
-                    tokio::task::spawn_blocking(move || -> Result<Arc<ColumnBatch>, String> {
-                        store.read_frame(&meta).map_err(|err| format!("{err:?}"))
-                    })
+            // Decode one frame ahead of the one being sent, so memory stays
+            // bounded by two frames however large the materialization is
+            let read = |meta: StoredFrameMeta| {
+                let store = Arc::clone(&store);
+                tokio::task::spawn_blocking(move || -> Result<Arc<ColumnBatch>, String> {
+                    store.read_frame(&meta).map_err(|err| format!("{err:?}"))
                 })
-                .collect();
+            };
+            let mut frames = frames.into_iter();
+            let mut pending = frames.next().map(read);
 
-            for task in read_tasks {
-                match task.await {
+            while let Some(task) = pending.take() {
+                let result = task.await;
+                pending = frames.next().map(read);
+                match result {
                     Ok(Ok(batch)) => {
                         if sender.send(batch).await.is_err() {
                             break;
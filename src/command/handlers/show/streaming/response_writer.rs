@@ -91,7 +91,10 @@ impl<'a, W: AsyncWrite + Unpin> ShowResponseWriter<'a, W> {
 
     pub async fn write(self, stream: QueryBatchStream) -> Result<(), ShowError> {
         match self.renderer.streaming_format() {
-            StreamingFormat::Json => self.write_json(stream).await,
+            StreamingFormat::Json
+            | StreamingFormat::Csv
+            | StreamingFormat::MsgPack
+            | StreamingFormat::Ndjson => self.write_json(stream).await,
             StreamingFormat::Arrow => self.write_arrow(stream).await,
         }
     }
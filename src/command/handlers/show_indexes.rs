@@ -0,0 +1,218 @@
+use crate::command::handlers::segment_discovery::SegmentDiscovery;
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::core::segment::index_inventory::{
+    FIELD_ARTIFACTS, HISTOGRAM, IndexArtifact, SegmentIndexInventory,
+};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{Response, StatusCode};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Fields every event type stores next to its payload fields.
+const CORE_FIELDS: [&str; 3] = ["context_id", "event_type", "timestamp"];
+
+/// Lists the index artifacts found on disk for one event type, per segment,
+/// with a per-field count of the segments holding each artifact.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::ShowIndexes {
+        event_type,
+        shard_id,
+        segment_id,
+    } = cmd
+    else {
+        let resp = Response::error(StatusCode::BadRequest, "Invalid SHOW INDEXES command");
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    if let Some(auth_mgr) = auth_manager {
+        match user_id {
+            Some(uid) if uid == BYPASS_USER_ID || auth_mgr.is_admin(uid).await => {}
+            Some(uid) => {
+                warn!(target: "sneldb::show_indexes", user_id = uid, "Admin permission denied");
+                let resp =
+                    Response::error(StatusCode::Forbidden, "Only admin users can show indexes");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+            None => {
+                let resp = Response::error(StatusCode::Unauthorized, "Authentication required");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    }
+
+    debug!(target: "sneldb::show_indexes", event_type, ?shard_id, ?segment_id, "Showing indexes");
+
+    let (uid, fields) = {
+        let registry = registry.read().await;
+        match (registry.get_uid(event_type), registry.get(event_type)) {
+            (Some(uid), Some(schema)) => {
+                let fields = CORE_FIELDS
+                    .iter()
+                    .map(|f| f.to_string())
+                    .chain(
+                        schema
+                            .fields()
+                            .filter(|f| !CORE_FIELDS.contains(&f.as_str()))
+                            .cloned(),
+                    )
+                    .collect::<Vec<_>>();
+                (uid, fields)
+            }
+            _ => {
+                let resp = Response::error(
+                    StatusCode::NotFound,
+                    format!("No schema defined for event type '{}'", event_type),
+                );
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    };
+
+    let shards = match shard_id {
+        Some(shard_id) => match shard_manager.all_shards().get(*shard_id) {
+            Some(shard) => std::slice::from_ref(shard),
+            None => {
+                let resp = Response::error(
+                    StatusCode::NotFound,
+                    format!("Shard {} does not exist", shard_id),
+                );
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        },
+        None => shard_manager.all_shards(),
+    };
+
+    let mut segment_dirs = Vec::new();
+    match segment_id {
+        Some(segment_id) => {
+            for shard in shards {
+                segment_dirs.push((shard.id, shard.base_dir.join(format!("{:05}", segment_id))));
+            }
+        }
+        None => {
+            let shard_info = shards.iter().map(|s| (s.id, s.base_dir.clone())).collect();
+            let mut discovered: Vec<_> = SegmentDiscovery::discover_all(shard_info)
+                .await
+                .into_values()
+                .collect();
+            discovered.sort_by_key(|data| data.shard_id);
+            for data in discovered {
+                for segment in &data.segments {
+                    segment_dirs.push((data.shard_id, data.base_dir.join(segment)));
+                }
+            }
+        }
+    }
+
+    let scanned = tokio::task::spawn_blocking(move || {
+        segment_dirs
+            .into_iter()
+            .filter_map(|(shard_id, dir)| {
+                SegmentIndexInventory::scan(&dir, &uid, &fields).map(|inv| (shard_id, inv))
+            })
+            .collect::<Vec<_>>()
+    })
+    .await;
+
+    let resp = match (scanned, segment_id, shard_id) {
+        (Ok(inventories), Some(segment_id), Some(shard_id)) if inventories.is_empty() => {
+            Response::error(
+                StatusCode::NotFound,
+                format!(
+                    "Segment {:05} on shard {} holds no zones for '{}'",
+                    segment_id, shard_id, event_type
+                ),
+            )
+        }
+        (Ok(inventories), _, _) => Response::ok_lines(render_lines(event_type, &inventories)),
+        (Err(e), _, _) => Response::error(
+            StatusCode::InternalError,
+            format!("Index scan failed: {}", e),
+        ),
+    };
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// A header line, one line per field counting the segments that hold each
+/// artifact, then each segment's shared artifacts followed by its fields,
+/// indented. Sizes are file sizes; `hist` lives inside the shared `.hist` file.
+pub fn render_lines(
+    event_type: &str,
+    inventories: &[(usize, SegmentIndexInventory)],
+) -> Vec<String> {
+    let mut lines = vec![format!(
+        "Indexes of '{}' in {} segments",
+        event_type,
+        inventories.len()
+    )];
+
+    let total = inventories.len();
+    if let Some((_, first)) = inventories.first() {
+        for (i, (field, _)) in first.fields.iter().enumerate() {
+            let counts: Vec<String> = FIELD_ARTIFACTS
+                .iter()
+                .chain(std::iter::once(&HISTOGRAM))
+                .filter_map(|name| {
+                    let held = inventories
+                        .iter()
+                        .filter(|(_, inv)| {
+                            inv.fields.get(i).is_some_and(|(_, artifacts)| {
+                                artifacts.iter().any(|a| a.name == *name)
+                            })
+                        })
+                        .count();
+                    (held > 0).then(|| format!("{} {}/{}", name, held, total))
+                })
+                .collect();
+            lines.push(format!("{}: {}", field, join_or_none(counts)));
+        }
+    }
+
+    for (shard_id, inventory) in inventories {
+        lines.push(format!(
+            "shard {} segment {}: {}",
+            shard_id,
+            inventory.segment,
+            describe(&inventory.segment_artifacts)
+        ));
+        for (field, artifacts) in &inventory.fields {
+            lines.push(format!("  {}: {}", field, describe(artifacts)));
+        }
+    }
+    lines
+}
+
+fn describe(artifacts: &[IndexArtifact]) -> String {
+    join_or_none(
+        artifacts
+            .iter()
+            .map(|a| match a.bytes {
+                Some(bytes) => format!("{} {} bytes", a.name, bytes),
+                None => a.name.to_string(),
+            })
+            .collect(),
+    )
+}
+
+fn join_or_none(parts: Vec<String>) -> String {
+    if parts.is_empty() {
+        "none".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
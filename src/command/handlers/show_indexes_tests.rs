@@ -0,0 +1,165 @@
+use crate::command::handlers::show_indexes::{handle, render_lines};
+use crate::command::types::Command;
+use crate::engine::auth::AuthManager;
+use crate::engine::core::segment::index_inventory::{IndexArtifact, SegmentIndexInventory};
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::SchemaRegistryFactory;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn artifact(name: &'static str, bytes: Option<u64>) -> IndexArtifact {
+    IndexArtifact { name, bytes }
+}
+
+#[test]
+fn test_render_lines_counts_segments_per_artifact() {
+    let inventories = vec![
+        (
+            0,
+            SegmentIndexInventory {
+                segment: "00001".to_string(),
+                segment_artifacts: vec![artifact("idx", Some(120)), artifact("icx", Some(40))],
+                fields: vec![
+                    (
+                        "amount".to_string(),
+                        vec![
+                            artifact("zsrf", Some(812)),
+                            artifact("xf", Some(96)),
+                            artifact("hist", None),
+                        ],
+                    ),
+                    ("note".to_string(), vec![]),
+                ],
+            },
+        ),
+        (
+            1,
+            SegmentIndexInventory {
+                segment: "00002".to_string(),
+                segment_artifacts: vec![],
+                fields: vec![
+                    ("amount".to_string(), vec![artifact("xf", Some(64))]),
+                    ("note".to_string(), vec![]),
+                ],
+            },
+        ),
+    ];
+
+    assert_eq!(
+        render_lines("order", &inventories),
+        vec![
+            "Indexes of 'order' in 2 segments".to_string(),
+            "amount: zsrf 1/2, xf 2/2, hist 1/2".to_string(),
+            "note: none".to_string(),
+            "shard 0 segment 00001: idx 120 bytes, icx 40 bytes".to_string(),
+            "  amount: zsrf 812 bytes, xf 96 bytes, hist".to_string(),
+            "  note: none".to_string(),
+            "shard 1 segment 00002: none".to_string(),
+            "  amount: xf 64 bytes".to_string(),
+            "  note: none".to_string(),
+        ]
+    );
+
+    assert_eq!(
+        render_lines("order", &[]),
+        vec!["Indexes of 'order' in 0 segments".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_show_indexes_requires_admin_and_known_targets() {
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("order", &[("context_id", "string"), ("amount", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let base_dir = tempdir().unwrap();
+    let wal_dir = tempdir().unwrap();
+    let shard_manager = Arc::new(
+        ShardManager::new(
+            1,
+            base_dir.path().to_path_buf(),
+            wal_dir.path().to_path_buf(),
+        )
+        .await,
+    );
+
+    let auth_manager = Arc::new(AuthManager::new(Arc::clone(&shard_manager)));
+    auth_manager
+        .create_user("reader".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    auth_manager
+        .create_user_with_roles(
+            "root".to_string(),
+            Some("secret".to_string()),
+            vec!["admin".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let run = |user_id: &'static str, cmd: Command| {
+        let shard_manager = Arc::clone(&shard_manager);
+        let registry = Arc::clone(&registry);
+        let auth_manager = Arc::clone(&auth_manager);
+        async move {
+            let mut writer = Vec::new();
+            handle(
+                &cmd,
+                &shard_manager,
+                &registry,
+                Some(&auth_manager),
+                Some(user_id),
+                &mut writer,
+                &JsonRenderer,
+            )
+            .await
+            .unwrap();
+            String::from_utf8(writer).unwrap()
+        }
+    };
+    let show = |event_type: &str, shard_id| Command::ShowIndexes {
+        event_type: event_type.to_string(),
+        shard_id: Some(shard_id),
+        segment_id: Some(7),
+    };
+
+    let denied = run("reader", show("order", 0)).await;
+    assert!(denied.contains("Only admin users"), "got: {}", denied);
+
+    let missing = run("root", show("refund", 0)).await;
+    assert!(
+        missing.contains("No schema defined for event type 'refund'"),
+        "got: {}",
+        missing
+    );
+    let missing = run("root", show("order", 3)).await;
+    assert!(
+        missing.contains("Shard 3 does not exist"),
+        "got: {}",
+        missing
+    );
+    let missing = run("root", show("order", 0)).await;
+    assert!(
+        missing.contains("Segment 00007 on shard 0 holds no zones for 'order'"),
+        "got: {}",
+        missing
+    );
+
+    let all = run(
+        "root",
+        Command::ShowIndexes {
+            event_type: "order".to_string(),
+            shard_id: None,
+            segment_id: None,
+        },
+    )
+    .await;
+    assert!(
+        all.contains("Indexes of 'order' in 0 segments"),
+        "got: {}",
+        all
+    );
+}
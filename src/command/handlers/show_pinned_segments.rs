@@ -0,0 +1,69 @@
+use crate::command::types::Command;
+use crate::engine::core::read::cache::GlobalPinnedSegmentCache;
+use crate::shared::response::Response;
+use crate::shared::response::render::Renderer;
+use std::path::Path;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::debug;
+
+pub async fn handle<W: AsyncWrite + Unpin>(
+    _cmd: &Command,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    debug!(target: "sneldb::show_pinned", "Received SHOW PINNED SEGMENTS command");
+
+    let resp = Response::ok_lines(render_lines(GlobalPinnedSegmentCache::instance()));
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Summary of the pinned segment tier followed by one line per pinned segment.
+pub fn render_lines(cache: &GlobalPinnedSegmentCache) -> Vec<String> {
+    if !cache.is_enabled() {
+        return vec!["Pinned segment cache is disabled".to_string()];
+    }
+
+    let stats = cache.stats();
+    let pinned = cache.pinned_segments();
+    let mut lines = vec![
+        format!(
+            "Pinned segments: {} ({} of {} bytes, max {} bytes per segment)",
+            pinned.len(),
+            stats.current_bytes,
+            stats.capacity_bytes,
+            stats.max_segment_bytes
+        ),
+        format!(
+            "Hit ratio: {:.3} (hits={} misses={} bypasses={} evictions={})",
+            stats.hit_ratio(),
+            stats.hits,
+            stats.misses,
+            stats.bypasses,
+            stats.evictions
+        ),
+    ];
+    for info in pinned {
+        lines.push(format!(
+            "  {}: {} bytes",
+            segment_label(&info.segment_dir),
+            info.size_bytes
+        ));
+    }
+    lines
+}
+
+/// `shard-N/segment` when the directory sits under a shard, else the full path.
+fn segment_label(segment_dir: &Path) -> String {
+    let shard = segment_dir
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy());
+    match (shard, segment_dir.file_name()) {
+        (Some(shard), Some(segment)) if shard.starts_with("shard-") => {
+            format!("{}/{}", shard, segment.to_string_lossy())
+        }
+        _ => segment_dir.display().to_string(),
+    }
+}
@@ -0,0 +1,47 @@
+use crate::command::handlers::show_pinned_segments::{handle, render_lines};
+use crate::command::types::Command;
+use crate::engine::core::read::cache::GlobalPinnedSegmentCache;
+use crate::shared::response::JsonRenderer;
+
+#[test]
+fn test_render_lines_reports_disabled_tier() {
+    let cache = GlobalPinnedSegmentCache::new(0, 0);
+    assert_eq!(
+        render_lines(&cache),
+        vec!["Pinned segment cache is disabled".to_string()]
+    );
+}
+
+#[test]
+fn test_render_lines_lists_pinned_segments_and_hit_ratio() {
+    let tmp = tempfile::tempdir().unwrap();
+    let seg_dir = tmp.path().join("shard-2").join("00007");
+    std::fs::create_dir_all(&seg_dir).unwrap();
+    std::fs::write(seg_dir.join("uid.zones"), vec![0u8; 100]).unwrap();
+
+    let cache = GlobalPinnedSegmentCache::new(1024, 4096);
+    for _ in 0..4 {
+        cache.get_or_pin(&seg_dir).unwrap();
+    }
+
+    let lines = render_lines(&cache);
+    assert_eq!(
+        lines,
+        vec![
+            "Pinned segments: 1 (100 of 4096 bytes, max 1024 bytes per segment)".to_string(),
+            "Hit ratio: 0.750 (hits=3 misses=1 bypasses=0 evictions=0)".to_string(),
+            "  shard-2/00007: 100 bytes".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_show_pinned_segments_responds_ok() {
+    let mut writer = Vec::new();
+    handle(&Command::ShowPinnedSegments, &mut writer, &JsonRenderer)
+        .await
+        .expect("handler should not fail");
+
+    let response = String::from_utf8(writer).unwrap();
+    assert!(response.contains("Pinned segment"), "got: {}", response);
+}
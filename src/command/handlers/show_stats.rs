@@ -0,0 +1,117 @@
+use crate::command::types::Command;
+use crate::engine::core::read::cache::{
+    ColumnValueCacheStats, GlobalColumnValueCache, GlobalZoneSurfCache, IdentInterner, InternStats,
+    ZoneSurfCacheStats,
+};
+use crate::engine::core::read::flow::{BatchPool, BatchPoolStats};
+use crate::engine::core::read::sink::{GroupKeyCache, GroupKeyCacheStats};
+use crate::shared::response::Response;
+use crate::shared::response::render::Renderer;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::debug;
+
+pub async fn handle<W: AsyncWrite + Unpin>(
+    _cmd: &Command,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    debug!(target: "sneldb::show_stats", "Received SHOW STATS command");
+
+    let mut lines = render_lines(
+        &IdentInterner::uids().stats(),
+        &IdentInterner::fields().stats(),
+    );
+    lines.push(render_pool_line(&BatchPool::global_stats()));
+    lines.push(render_group_key_cache_line(
+        &GroupKeyCache::global_stats(),
+        GroupKeyCache::configured_max_entries(),
+    ));
+    lines.push(render_zone_surf_cache_line(
+        &GlobalZoneSurfCache::instance().stats(),
+    ));
+    lines.push(render_column_value_cache_line(
+        &GlobalColumnValueCache::instance().stats(),
+    ));
+    let resp = Response::ok_lines(lines);
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// One line per identifier intern table.
+pub fn render_lines(uids: &InternStats, fields: &InternStats) -> Vec<String> {
+    [("uids", uids), ("fields", fields)]
+        .into_iter()
+        .map(|(name, stats)| {
+            format!(
+                "Interned {}: {} of {} entries, hit ratio {:.3} (hits={} misses={} evictions={} bypasses={})",
+                name,
+                stats.entries,
+                stats.capacity,
+                stats.hit_ratio(),
+                stats.hits,
+                stats.misses,
+                stats.evictions,
+                stats.bypasses
+            )
+        })
+        .collect()
+}
+
+/// Batch buffer reuse across every query's batch pools.
+pub fn render_pool_line(stats: &BatchPoolStats) -> String {
+    format!(
+        "Batch pools: {} buffers ({} bytes) pooled, reuse ratio {:.3} (allocations={} reuses={} returns={} discards={})",
+        stats.pooled_buffers,
+        stats.pooled_bytes,
+        stats.reuse_ratio(),
+        stats.allocations,
+        stats.reuses,
+        stats.returns,
+        stats.discards
+    )
+}
+
+/// Group key caches of grouped aggregations, summed over every query.
+pub fn render_group_key_cache_line(stats: &GroupKeyCacheStats, max_entries: usize) -> String {
+    format!(
+        "Group key caches: {} keys held (max {} per query), hit ratio {:.3} (hits={} misses={} evictions={})",
+        stats.entries,
+        max_entries,
+        stats.hit_ratio(),
+        stats.hits,
+        stats.misses,
+        stats.evictions
+    )
+}
+
+/// The global zone SuRF filter cache and its admission policy.
+pub fn render_zone_surf_cache_line(stats: &ZoneSurfCacheStats) -> String {
+    format!(
+        "Zone SuRF cache: {} filters ({} bytes), hit ratio {:.3} (hits={} misses={} reloads={} evictions={} admissions={} rejections={})",
+        stats.current_items,
+        stats.current_bytes,
+        stats.hit_ratio(),
+        stats.hits,
+        stats.misses,
+        stats.reloads,
+        stats.evictions,
+        stats.admissions,
+        stats.rejections
+    )
+}
+
+/// The global column value cache of point lookups; a zero budget means it is off.
+pub fn render_column_value_cache_line(stats: &ColumnValueCacheStats) -> String {
+    format!(
+        "Column value cache: {} values ({} of {} bytes), hit ratio {:.3} (hits={} misses={} evictions={} invalidations={})",
+        stats.current_items,
+        stats.current_bytes,
+        stats.capacity_bytes,
+        stats.hit_ratio(),
+        stats.hits,
+        stats.misses,
+        stats.evictions,
+        stats.invalidations
+    )
+}
@@ -0,0 +1,117 @@
+use crate::command::handlers::show_stats::{
+    handle, render_column_value_cache_line, render_group_key_cache_line, render_lines,
+    render_pool_line, render_zone_surf_cache_line,
+};
+use crate::command::types::Command;
+use crate::engine::core::read::cache::{ColumnValueCacheStats, IdentInterner, ZoneSurfCacheStats};
+use crate::engine::core::read::flow::BatchPoolStats;
+use crate::engine::core::read::sink::GroupKeyCacheStats;
+use crate::shared::config::InternEviction;
+use crate::shared::response::JsonRenderer;
+
+#[test]
+fn test_render_lines_reports_intern_tables() {
+    let uids = IdentInterner::new(16, InternEviction::Lru);
+    for uid in ["u1", "u2", "u1", "u1"] {
+        uids.intern(uid);
+    }
+    let fields = IdentInterner::new(1, InternEviction::Bypass);
+    for field in ["a", "b"] {
+        fields.intern(field);
+    }
+
+    assert_eq!(
+        render_lines(&uids.stats(), &fields.stats()),
+        vec![
+            "Interned uids: 2 of 16 entries, hit ratio 0.500 (hits=2 misses=2 evictions=0 bypasses=0)"
+                .to_string(),
+            "Interned fields: 1 of 1 entries, hit ratio 0.000 (hits=0 misses=2 evictions=0 bypasses=1)"
+                .to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_render_pool_line_reports_reuse() {
+    let stats = BatchPoolStats {
+        allocations: 1,
+        reuses: 3,
+        returns: 4,
+        discards: 1,
+        pooled_buffers: 2,
+        pooled_bytes: 2048,
+    };
+    assert_eq!(
+        render_pool_line(&stats),
+        "Batch pools: 2 buffers (2048 bytes) pooled, reuse ratio 0.750 (allocations=1 reuses=3 returns=4 discards=1)"
+    );
+}
+
+#[test]
+fn test_render_group_key_cache_line_reports_hit_ratio() {
+    let stats = GroupKeyCacheStats {
+        hits: 9,
+        misses: 3,
+        evictions: 1,
+        entries: 2,
+    };
+    assert_eq!(
+        render_group_key_cache_line(&stats, 1000),
+        "Group key caches: 2 keys held (max 1000 per query), hit ratio 0.750 (hits=9 misses=3 evictions=1)"
+    );
+}
+
+#[test]
+fn test_render_zone_surf_cache_line_reports_admission() {
+    let stats = ZoneSurfCacheStats {
+        hits: 6,
+        misses: 3,
+        reloads: 1,
+        evictions: 2,
+        current_bytes: 8192,
+        current_items: 2,
+        admissions: 2,
+        rejections: 1,
+        admission_bytes: 2048,
+    };
+    assert_eq!(
+        render_zone_surf_cache_line(&stats),
+        "Zone SuRF cache: 2 filters (8192 bytes), hit ratio 0.600 (hits=6 misses=3 reloads=1 evictions=2 admissions=2 rejections=1)"
+    );
+}
+
+#[test]
+fn test_render_column_value_cache_line_reports_budget() {
+    let stats = ColumnValueCacheStats {
+        hits: 9,
+        misses: 1,
+        evictions: 2,
+        invalidations: 3,
+        current_items: 4,
+        current_bytes: 512,
+        capacity_bytes: 4096,
+    };
+    assert_eq!(
+        render_column_value_cache_line(&stats),
+        "Column value cache: 4 values (512 of 4096 bytes), hit ratio 0.900 (hits=9 misses=1 evictions=2 invalidations=3)"
+    );
+}
+
+#[tokio::test]
+async fn test_show_stats_responds_ok() {
+    let mut writer = Vec::new();
+    handle(&Command::ShowStats, &mut writer, &JsonRenderer)
+        .await
+        .expect("handler should not fail");
+
+    let response = String::from_utf8(writer).unwrap();
+    assert!(response.contains("Interned uids"), "got: {}", response);
+    assert!(response.contains("Batch pools:"), "got: {}", response);
+    assert!(response.contains("Group key caches:"), "got: {}", response);
+    assert!(response.contains("Zone SuRF cache:"), "got: {}", response);
+    assert!(
+        response.contains("Column value cache:"),
+        "got: {}",
+        response
+    );
+}
@@ -0,0 +1,76 @@
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{Response, StatusCode};
+use crate::shared::usage::{UsageLedger, UserUsage};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::{info, warn};
+
+/// Reports the resource usage of every user, zeroing it for `SHOW USAGE RESET`.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::ShowUsage { reset } = cmd else {
+        let resp = Response::error(StatusCode::BadRequest, "Invalid SHOW USAGE command");
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    if let Some(auth_mgr) = auth_manager {
+        match user_id {
+            Some(uid) if uid == BYPASS_USER_ID || auth_mgr.is_admin(uid).await => {}
+            Some(uid) => {
+                warn!(target: "sneldb::usage", user_id = uid, "Admin permission denied");
+                let resp =
+                    Response::error(StatusCode::Forbidden, "Only admin users can view usage");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+            None => {
+                let resp = Response::error(StatusCode::Unauthorized, "Authentication required");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    }
+
+    let ledger = UsageLedger::global();
+    let lines = if *reset {
+        info!(target: "sneldb::usage", user_id = ?user_id, "Resetting usage");
+        let mut lines = render_lines(&ledger.reset());
+        lines.push("Usage reset".to_string());
+        lines
+    } else {
+        render_lines(&ledger.snapshot())
+    };
+    let resp = Response::ok_lines(lines);
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// One line per user, read work first, then write work.
+pub fn render_lines(users: &[(String, UserUsage)]) -> Vec<String> {
+    if users.is_empty() {
+        return vec!["No usage recorded".to_string()];
+    }
+    users
+        .iter()
+        .map(|(user_id, usage)| {
+            format!(
+                "User {}: read queries={} failed={} rows_scanned={} bytes_scanned={} rows_returned={} cpu_ms={:.3}; write events={} bytes={}",
+                user_id,
+                usage.read.queries,
+                usage.read.failed_queries,
+                usage.read.rows_scanned,
+                usage.read.bytes_scanned,
+                usage.read.rows_returned,
+                usage.read.cpu_time.as_secs_f64() * 1000.0,
+                usage.write.events_stored,
+                usage.write.bytes_written
+            )
+        })
+        .collect()
+}
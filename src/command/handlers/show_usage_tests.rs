@@ -0,0 +1,70 @@
+use crate::command::handlers::show_usage::{handle, render_lines};
+use crate::command::types::Command;
+use crate::engine::auth::AuthManager;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::JsonRenderer;
+use crate::shared::usage::{ReadUsage, UserUsage, WriteUsage};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::tempdir;
+
+#[test]
+fn test_render_lines_separates_read_and_write_work() {
+    let usage = UserUsage {
+        read: ReadUsage {
+            queries: 3,
+            failed_queries: 1,
+            rows_scanned: 1000,
+            bytes_scanned: 65536,
+            rows_returned: 12,
+            cpu_time: Duration::from_micros(2500),
+        },
+        write: WriteUsage {
+            events_stored: 4,
+            bytes_written: 128,
+        },
+    };
+
+    assert_eq!(
+        render_lines(&[("alice".to_string(), usage)]),
+        vec![
+            "User alice: read queries=3 failed=1 rows_scanned=1000 bytes_scanned=65536 rows_returned=12 cpu_ms=2.500; write events=4 bytes=128"
+                .to_string()
+        ]
+    );
+    assert_eq!(render_lines(&[]), vec!["No usage recorded".to_string()]);
+}
+
+#[tokio::test]
+async fn test_show_usage_requires_admin() {
+    let base_dir = tempdir().unwrap();
+    let wal_dir = tempdir().unwrap();
+    let shard_manager = Arc::new(
+        ShardManager::new(
+            1,
+            base_dir.path().to_path_buf(),
+            wal_dir.path().to_path_buf(),
+        )
+        .await,
+    );
+    let auth_manager = Arc::new(AuthManager::new(Arc::clone(&shard_manager)));
+    auth_manager
+        .create_user("reader".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+
+    for reset in [false, true] {
+        let mut writer = Vec::new();
+        handle(
+            &Command::ShowUsage { reset },
+            Some(&auth_manager),
+            Some("reader"),
+            &mut writer,
+            &JsonRenderer,
+        )
+        .await
+        .unwrap();
+        let out = String::from_utf8(writer).unwrap();
+        assert!(out.contains("Only admin users"), "got: {}", out);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::command::handlers::payload_limits::{PayloadLimits, payload_size};
 use crate::command::types::Command;
 use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
 use crate::engine::core::{Event, EventId};
@@ -7,8 +8,10 @@ use crate::engine::schema::SchemaRegistry;
 use crate::engine::schema::registry::MiniSchema;
 use crate::engine::shard::manager::ShardManager;
 use crate::engine::shard::message::ShardMessage;
+use crate::engine::types::{LogicalType, ScalarValue, uuid};
 use crate::shared::response::render::Renderer;
-use crate::shared::response::{Response, StatusCode};
+use crate::shared::response::{ErrorCode, Response, StatusCode};
+use crate::shared::usage::{UsageLedger, WriteUsage};
 // time parsing utilities are used via schema normalizer
 
 use std::collections::{BTreeMap, HashSet};
@@ -28,6 +31,103 @@ pub async fn handle<W: AsyncWrite + Unpin>(
     writer: &mut W,
     renderer: &dyn Renderer,
 ) -> std::io::Result<()> {
+    handle_with_limits(
+        cmd,
+        shard_manager,
+        registry,
+        auth_manager,
+        user_id,
+        &PayloadLimits::from_config(),
+        writer,
+        renderer,
+    )
+    .await
+}
+
+/// Like [`handle`], with explicit payload limits instead of those from `[ingest]`.
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_with_limits<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    limits: &PayloadLimits,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let result = match prepare_event(cmd, registry, auth_manager, user_id, limits).await {
+        Ok(event) => route_event(shard_manager, registry, event).await,
+        Err(rejection) => Err(rejection),
+    };
+    match result {
+        Ok(()) => {
+            record_write(user_id, 1, payload_bytes(cmd));
+            write_ok(writer, renderer, "Event accepted for storage").await
+        }
+        Err(rejection) => {
+            write_coded_error(
+                writer,
+                renderer,
+                rejection.status,
+                rejection.code,
+                &rejection.message,
+            )
+            .await
+        }
+    }
+}
+
+/// Compact JSON size of a `Store` command's payload, as attributed to its user.
+pub fn payload_bytes(cmd: &Command) -> usize {
+    match cmd {
+        Command::Store { payload, .. } => payload_size(payload),
+        _ => 0,
+    }
+}
+
+/// Attributes stored events to the user who sent them.
+pub fn record_write(user_id: Option<&str>, events: u64, bytes: usize) {
+    UsageLedger::global().record_write(
+        user_id,
+        &WriteUsage {
+            events_stored: events,
+            bytes_written: bytes as u64,
+        },
+    );
+}
+
+/// Why an event was not accepted, as reported to the client.
+#[derive(Debug, Clone)]
+pub struct StoreRejection {
+    pub status: StatusCode,
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl StoreRejection {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self::with_code(status, ErrorCode::from_status(status), message)
+    }
+
+    fn with_code(status: StatusCode, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Checks permissions, payload size and schema for a `Store` command and builds
+/// the event to route. Nothing is written when this fails.
+pub async fn prepare_event(
+    cmd: &Command,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    limits: &PayloadLimits,
+) -> Result<Event, StoreRejection> {
     let Command::Store {
         event_type,
         context_id,
@@ -35,13 +135,10 @@ pub async fn handle<W: AsyncWrite + Unpin>(
     } = cmd
     else {
         warn!(target: "sneldb::store", "Received invalid command variant for Store");
-        return write_error(
-            writer,
-            renderer,
+        return Err(StoreRejection::new(
             StatusCode::BadRequest,
             "Invalid command variant",
-        )
-        .await;
+        ));
     };
 
     // Check write permission if auth is enabled
@@ -56,50 +153,52 @@ pub async fn handle<W: AsyncWrite + Unpin>(
                     event_type,
                     "Write permission denied"
                 );
-                return write_error(
-                    writer,
-                    renderer,
+                return Err(StoreRejection::new(
                     StatusCode::Forbidden,
-                    &format!("Write permission denied for event type '{}'", event_type),
-                )
-                .await;
+                    format!("Write permission denied for event type '{}'", event_type),
+                ));
             }
         } else {
             // Authentication required but no user_id provided
             warn!(target: "sneldb::store", "Authentication required for STORE command");
-            return write_error(
-                writer,
-                renderer,
+            return Err(StoreRejection::new(
                 StatusCode::Unauthorized,
                 "Authentication required",
-            )
-            .await;
+            ));
         }
     }
 
     if event_type.trim().is_empty() {
         warn!(target: "sneldb::store", "Missing event_type");
-        return write_error(
-            writer,
-            renderer,
+        return Err(StoreRejection::new(
             StatusCode::BadRequest,
             "event_type cannot be empty",
-        )
-        .await;
+        ));
     }
 
     if context_id.trim().is_empty() {
         warn!(target: "sneldb::store", "Missing context_id");
-        return write_error(
-            writer,
-            renderer,
+        return Err(StoreRejection::new(
             StatusCode::BadRequest,
             "context_id cannot be empty",
-        )
-        .await;
+        ));
+    }
+
+    if let Err(e) = limits.check(event_type, payload) {
+        warn!(
+            target: "sneldb::store",
+            event_type,
+            context_id,
+            error = %e,
+            "Payload exceeds size limit"
+        );
+        return Err(StoreRejection::with_code(
+            StatusCode::BadRequest,
+            ErrorCode::PayloadTooLarge,
+            e,
+        ));
     }
 
-    let registry_clone = Arc::clone(registry);
     let schema_read = registry.read().await;
 
     let Some(mini_schema) = schema_read.get(event_type) else {
@@ -108,16 +207,30 @@ pub async fn handle<W: AsyncWrite + Unpin>(
             event_type,
             "No schema defined for event_type"
         );
-        return write_error(
-            writer,
-            renderer,
+        return Err(StoreRejection::with_code(
             StatusCode::BadRequest,
-            &format!("No schema defined for event type '{}'", event_type),
-        )
-        .await;
+            ErrorCode::UnknownSchema,
+            format!("No schema defined for event type '{}'", event_type),
+        ));
     };
 
-    if let Err(e) = validate_payload(payload, mini_schema) {
+    let mut normalized_payload = payload.clone();
+    if let Err(e) = coerce_payload(&mut normalized_payload, mini_schema) {
+        warn!(
+            target: "sneldb::store",
+            event_type,
+            context_id,
+            error = %e,
+            "Payload coercion failed"
+        );
+        return Err(StoreRejection::with_code(
+            StatusCode::BadRequest,
+            ErrorCode::TypeMismatch,
+            e,
+        ));
+    }
+
+    if let Err((code, e)) = validate_payload(&normalized_payload, mini_schema) {
         warn!(
             target: "sneldb::store",
             event_type,
@@ -125,12 +238,16 @@ pub async fn handle<W: AsyncWrite + Unpin>(
             error = %e,
             "Payload validation failed"
         );
-        return write_error(writer, renderer, StatusCode::BadRequest, &e).await;
+        return Err(StoreRejection::with_code(StatusCode::BadRequest, code, e));
     }
 
+    let ingest_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
     // Normalize logical time fields to epoch seconds in the payload
-    let mut normalized_payload = payload.clone();
-    let time_normalizer = PayloadTimeNormalizer::new(mini_schema);
+    let time_normalizer = PayloadTimeNormalizer::new(mini_schema).with_ingest_time(ingest_time);
     if let Err(e) = time_normalizer.normalize(&mut normalized_payload) {
         warn!(
             target: "sneldb::store",
@@ -139,22 +256,32 @@ pub async fn handle<W: AsyncWrite + Unpin>(
             error = %e,
             "Time normalization failed"
         );
-        return write_error(writer, renderer, StatusCode::BadRequest, &e).await;
+        return Err(StoreRejection::with_code(
+            StatusCode::BadRequest,
+            ErrorCode::TypeMismatch,
+            e,
+        ));
     }
 
     let mut event = Event {
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs(),
+        timestamp: ingest_time,
         event_type: event_type.clone(),
         context_id: context_id.clone(),
         id: EventId::default(),
         payload: BTreeMap::new(),
     };
     event.set_payload_json(normalized_payload);
+    Ok(event)
+}
 
-    let shard = shard_manager.get_shard(context_id);
+/// Sends a prepared event to the shard owning its context.
+pub async fn route_event(
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    event: Event,
+) -> Result<(), StoreRejection> {
+    let shard = shard_manager.get_shard(&event.context_id);
+    let context_id = event.context_id.clone();
     debug!(
         target: "sneldb::store",
         shard_id = shard.id,
@@ -164,7 +291,9 @@ pub async fn handle<W: AsyncWrite + Unpin>(
 
     let send_result = timeout(
         Duration::from_millis(1000),
-        shard.tx.send(ShardMessage::Store(event, registry_clone)),
+        shard
+            .tx
+            .send(ShardMessage::Store(event, Arc::clone(registry))),
     )
     .await;
 
@@ -176,7 +305,7 @@ pub async fn handle<W: AsyncWrite + Unpin>(
                 context_id,
                 "Event accepted and routed to shard"
             );
-            write_ok(writer, renderer, "Event accepted for storage").await
+            Ok(())
         }
         Ok(Err(e)) => {
             error!(
@@ -186,13 +315,10 @@ pub async fn handle<W: AsyncWrite + Unpin>(
                 error = %e,
                 "Failed to send Store message"
             );
-            write_error(
-                writer,
-                renderer,
+            Err(StoreRejection::new(
                 StatusCode::InternalError,
                 "Failed to route store command",
-            )
-            .await
+            ))
         }
         Err(_) => {
             error!(
@@ -201,13 +327,10 @@ pub async fn handle<W: AsyncWrite + Unpin>(
                 context_id,
                 "Timed out sending Store message - shard channel full (backpressure)"
             );
-            write_error(
-                writer,
-                renderer,
+            Err(StoreRejection::new(
                 StatusCode::ServiceUnavailable,
                 "Server is under pressure, please retry later",
-            )
-            .await
+            ))
         }
     }
 }
@@ -221,7 +344,8 @@ fn type_allows_value(ft: &FieldType, v: &serde_json::Value) -> bool {
         FieldType::Bool => v.is_boolean(),
         // For logical time fields, accept both strings and numbers at validation time;
         // normalization to seconds will happen later in the ingest path.
-        FieldType::Timestamp | FieldType::Date => v.is_string() || v.is_number(),
+        FieldType::Timestamp | FieldType::Date | FieldType::Time => v.is_string() || v.is_number(),
+        FieldType::Uuid => v.as_str().and_then(uuid::parse).is_some(),
         FieldType::Optional(inner) => v.is_null() || type_allows_value(inner, v),
         FieldType::Enum(enum_ty) => v
             .as_str()
@@ -230,22 +354,76 @@ fn type_allows_value(ft: &FieldType, v: &serde_json::Value) -> bool {
     }
 }
 
+/// The type values of a field are coerced to before validation. Time fields
+/// take strings and are normalized separately, so only numbers and booleans.
+fn coercion_target(ft: &FieldType) -> Option<LogicalType> {
+    match ft {
+        FieldType::U64 | FieldType::I64 => Some(LogicalType::Integer),
+        FieldType::F64 => Some(LogicalType::Float),
+        FieldType::Bool => Some(LogicalType::Boolean),
+        FieldType::Optional(inner) => coercion_target(inner),
+        _ => None,
+    }
+}
+
+/// Converts values sent as another type, like `"42"` for an int field, to
+/// the field's type. Values that already fit are left as sent; values that
+/// cannot be converted without loss are rejected instead of stored as text.
+fn coerce_payload(payload: &mut serde_json::Value, schema: &MiniSchema) -> Result<(), String> {
+    // Non-object payloads are reported by validate_payload
+    let Some(obj) = payload.as_object_mut() else {
+        return Ok(());
+    };
+    for (field, field_type) in &schema.fields {
+        let Some(value) = obj.get_mut(field) else {
+            continue;
+        };
+        if type_allows_value(field_type, value) {
+            continue;
+        }
+        let Some(target) = coercion_target(field_type) else {
+            continue;
+        };
+        let coerced = ScalarValue::from(value.clone())
+            .coerce_to(target)
+            .map_err(|e| format!("Field '{}' does not match expected type: {}", field, e))?;
+        *value = coerced.to_json();
+    }
+    Ok(())
+}
+
 /// Validates that a JSON payload matches the expected MiniSchema.
-fn validate_payload(payload: &serde_json::Value, schema: &MiniSchema) -> Result<(), String> {
-    let obj = payload
-        .as_object()
-        .ok_or_else(|| "Payload must be a JSON object".to_string())?;
+/// Errors carry the code to report: `TypeMismatch` for wrongly typed values,
+/// `InvalidRequest` for structural problems.
+fn validate_payload(
+    payload: &serde_json::Value,
+    schema: &MiniSchema,
+) -> Result<(), (ErrorCode, String)> {
+    let obj = payload.as_object().ok_or_else(|| {
+        (
+            ErrorCode::InvalidRequest,
+            "Payload must be a JSON object".to_string(),
+        )
+    })?;
 
     for (field, field_type) in &schema.fields {
         match obj.get(field) {
             Some(value) => {
                 if !type_allows_value(field_type, value) {
-                    return Err(format!("Field '{}' does not match expected type", field));
+                    return Err((
+                        ErrorCode::TypeMismatch,
+                        format!("Field '{}' does not match expected type", field),
+                    ));
                 }
             }
             None => {
-                if !matches!(field_type, FieldType::Optional(_)) {
-                    return Err(format!("Missing field '{}' in payload", field));
+                // Missing declared time fields are filled with the ingest time
+                let is_time_field = schema.declared_time_fields().any(|f| f == field);
+                if !matches!(field_type, FieldType::Optional(_)) && !is_time_field {
+                    return Err((
+                        ErrorCode::InvalidRequest,
+                        format!("Missing field '{}' in payload", field),
+                    ));
                 }
             }
         }
@@ -256,13 +434,16 @@ fn validate_payload(payload: &serde_json::Value, schema: &MiniSchema) -> Result<
 
     let extra_keys: Vec<_> = actual_keys.difference(&allowed_keys).cloned().collect();
     if !extra_keys.is_empty() {
-        return Err(format!(
-            "Payload contains fields not defined in schema: {}",
-            extra_keys
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(", ")
+        return Err((
+            ErrorCode::InvalidRequest,
+            format!(
+                "Payload contains fields not defined in schema: {}",
+                extra_keys
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         ));
     }
 
@@ -271,14 +452,15 @@ fn validate_payload(payload: &serde_json::Value, schema: &MiniSchema) -> Result<
 
 // time normalization moved to PayloadTimeNormalizer in schema module
 
-/// Writes an error response to the writer.
-async fn write_error<W: AsyncWrite + Unpin>(
+/// Writes an error response with an explicit error code to the writer.
+async fn write_coded_error<W: AsyncWrite + Unpin>(
     writer: &mut W,
     renderer: &dyn Renderer,
     status: StatusCode,
+    code: ErrorCode,
     message: &str,
 ) -> std::io::Result<()> {
-    let resp = Response::error(status, message.to_string());
+    let resp = Response::error_with_code(status, code, message.to_string());
     writer.write_all(&renderer.render(&resp)).await?;
     writer.flush().await?;
     Ok(())
@@ -490,6 +490,78 @@ async fn test_store_normalizes_date_string_to_midnight() {
     assert_eq!(payload["birthdate"], json!(expected));
 }
 
+#[tokio::test]
+async fn test_store_and_query_date_and_time_fields() {
+    use crate::command::types::{CompareOp, Expr};
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("evt_date_time", &[("birthdate", "date"), ("opens", "time")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    for (ctx, birthdate, opens) in [
+        ("ctx-dt-1", "2024-03-05T17:30:00Z", "09:00"),
+        ("ctx-dt-2", "2024-03-06", "14:30:00"),
+    ] {
+        let cmd = CommandFactory::store()
+            .with_event_type("evt_date_time")
+            .with_context_id(ctx)
+            .with_payload(json!({ "birthdate": birthdate, "opens": opens }))
+            .create();
+        let (mut _reader, mut writer) = duplex(1024);
+        store::handle(
+            &cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut writer,
+            &JsonRenderer,
+        )
+        .await
+        .expect("handler should not fail");
+    }
+    sleep(Duration::from_millis(100)).await;
+
+    let query = |field: &str, op: CompareOp, value: &str| {
+        CommandFactory::query()
+            .with_event_type("evt_date_time")
+            .with_where_clause(Expr::Compare {
+                field: field.to_string(),
+                op,
+                value: json!(value),
+            })
+            .create()
+    };
+
+    // The time of day stored with the first date is dropped
+    let payloads = query_and_get_payload(
+        &query("birthdate", CompareOp::Eq, "2024-03-05"),
+        &shard_manager,
+        &registry,
+    )
+    .await;
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(payloads[0]["opens"], json!(32_400_000));
+
+    let payloads = query_and_get_payload(
+        &query("opens", CompareOp::Gt, "12:00"),
+        &shard_manager,
+        &registry,
+    )
+    .await;
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(payloads[0]["opens"], json!(52_200_000));
+}
+
 #[tokio::test]
 async fn test_store_optional_datetime_null_passes() {
     use crate::logging::init_for_tests;
@@ -581,6 +653,107 @@ async fn test_store_rejects_invalid_time_string() {
     assert!(msg.contains("Invalid time string"));
 }
 
+#[tokio::test]
+async fn test_store_coerces_numeric_strings_to_field_types() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields(
+            "evt_coerce",
+            &[("qty", "int"), ("price", "float"), ("paid", "bool")],
+        )
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    let cmd = CommandFactory::store()
+        .with_event_type("evt_coerce")
+        .with_context_id("ctx-coerce-1")
+        .with_payload(json!({ "qty": "42", "price": "9.5", "paid": "true" }))
+        .create();
+
+    let (mut _reader, mut writer) = duplex(1024);
+    store::handle(
+        &cmd,
+        &shard_manager,
+        &registry,
+        None,
+        None,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .expect("handler should not fail");
+
+    sleep(Duration::from_millis(100)).await;
+
+    let query_cmd = CommandFactory::query()
+        .with_event_type("evt_coerce")
+        .with_context_id("ctx-coerce-1")
+        .create();
+    let payloads = query_and_get_payload(&query_cmd, &shard_manager, &registry).await;
+    assert_eq!(payloads.len(), 1, "Should find stored event");
+    assert_eq!(payloads[0]["qty"], json!(42));
+    assert_eq!(payloads[0]["price"], json!(9.5));
+    assert_eq!(payloads[0]["paid"], json!(true));
+}
+
+#[tokio::test]
+async fn test_store_rejects_integer_string_that_overflows() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("evt_overflow", &[("qty", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    let cmd = CommandFactory::store()
+        .with_event_type("evt_overflow")
+        .with_context_id("ctx-overflow-1")
+        .with_payload(json!({ "qty": "99999999999999999999" }))
+        .create();
+
+    let (mut reader, mut writer) = duplex(1024);
+    store::handle(
+        &cmd,
+        &shard_manager,
+        &registry,
+        None,
+        None,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+
+    let mut response = vec![0u8; 1024];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(
+        msg.contains("Field 'qty' does not match expected type"),
+        "{}",
+        msg
+    );
+    assert!(
+        msg.contains("99999999999999999999 is out of range for Integer"),
+        "{}",
+        msg
+    );
+}
+
 #[tokio::test]
 async fn test_store_handle_rejects_empty_event_type() {
     use crate::logging::init_for_tests;
@@ -701,6 +874,7 @@ async fn test_store_handle_rejects_undefined_schema() {
     let msg = String::from_utf8_lossy(&response[..n]);
 
     assert!(msg.contains("No schema defined for event type"));
+    assert!(msg.contains("\"code\":\"UNKNOWN_SCHEMA\""));
 }
 
 #[tokio::test]
@@ -1047,3 +1221,62 @@ async fn test_store_handler_bypass_auth_vs_regular_user() {
     .await
     .expect("handler should not fail");
 }
+
+#[tokio::test]
+async fn test_store_handle_rejects_oversized_payload_before_routing() {
+    use crate::command::handlers::payload_limits::PayloadLimits;
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("test_event", &[("id", "int"), ("name", "string")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+
+    let limits = PayloadLimits {
+        max_payload_bytes: Some(64),
+        ..Default::default()
+    };
+    let cmd = CommandFactory::store()
+        .with_payload(serde_json::json!({ "id": 1, "name": "x".repeat(100) }))
+        .create();
+
+    let (mut reader, mut writer) = duplex(1024);
+    store::handle_with_limits(
+        &cmd,
+        &shard_manager,
+        &registry,
+        None,
+        None,
+        &limits,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .await
+    .unwrap();
+
+    let mut response = vec![0u8; 1024];
+    let n = reader.read(&mut response).await.unwrap();
+    let msg = String::from_utf8_lossy(&response[..n]);
+    assert!(msg.contains("\"code\":\"PAYLOAD_TOO_LARGE\""), "{}", msg);
+    assert!(msg.contains("limit of 64 bytes"), "{}", msg);
+
+    // Nothing reached the shard
+    assert!(
+        query_and_get_payload(
+            &CommandFactory::query()
+                .with_event_type("test_event")
+                .create(),
+            &shard_manager,
+            &registry,
+        )
+        .await
+        .is_empty()
+    );
+}
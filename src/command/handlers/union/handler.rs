@@ -0,0 +1,193 @@
+use std::io;
+use std::sync::Arc;
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::core::read::flow::{MEMORY_LIMIT_ERROR_PREFIX, QueryMemoryBudget};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{ErrorCode, Response, StatusCode};
+
+use super::orchestrator::UnionExecutionPipeline;
+use crate::command::handlers::query::{
+    COMPLEXITY_ERROR_PREFIX, ComplexityLimits, QueryResponseWriter,
+};
+
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+pub struct UnionCommandHandler<'a, W: AsyncWrite + Unpin> {
+    command: &'a Command,
+    shard_manager: &'a ShardManager,
+    registry: Arc<RwLock<SchemaRegistry>>,
+    auth_manager: Option<&'a Arc<AuthManager>>,
+    user_id: Option<&'a str>,
+    writer: &'a mut W,
+    renderer: &'a dyn Renderer,
+}
+
+impl<'a, W: AsyncWrite + Unpin> UnionCommandHandler<'a, W> {
+    pub fn new(
+        command: &'a Command,
+        shard_manager: &'a ShardManager,
+        registry: Arc<RwLock<SchemaRegistry>>,
+        auth_manager: Option<&'a Arc<AuthManager>>,
+        user_id: Option<&'a str>,
+        writer: &'a mut W,
+        renderer: &'a dyn Renderer,
+    ) -> Self {
+        Self {
+            command,
+            shard_manager,
+            registry,
+            auth_manager,
+            user_id,
+            writer,
+            renderer,
+        }
+    }
+
+    pub async fn handle(mut self) -> io::Result<()> {
+        let Command::Union {
+            queries,
+            order_by,
+            limit,
+            offset,
+        } = self.command
+        else {
+            warn!(target: "sneldb::union", "Invalid Union command received");
+            return self
+                .write_error(StatusCode::BadRequest, "Invalid UNION ALL command")
+                .await;
+        };
+
+        if queries.len() < 2 {
+            warn!(
+                target: "sneldb::union",
+                query_count = queries.len(),
+                "UNION ALL requires at least 2 queries"
+            );
+            return self
+                .write_error(
+                    StatusCode::BadRequest,
+                    "UNION ALL requires at least 2 queries",
+                )
+                .await;
+        }
+
+        // Check read permission for every sub-query if auth is enabled
+        if let Some(auth_mgr) = self.auth_manager {
+            let Some(uid) = self.user_id else {
+                warn!(target: "sneldb::union", "Authentication required for UNION ALL query");
+                return self
+                    .write_error(StatusCode::Unauthorized, "Authentication required")
+                    .await;
+            };
+            if uid != BYPASS_USER_ID {
+                for query in queries {
+                    if !auth_mgr.can_read(uid, &query.event_type).await {
+                        warn!(
+                            target: "sneldb::union",
+                            user_id = uid,
+                            event_type = %query.event_type,
+                            "Read permission denied"
+                        );
+                        return self
+                            .write_error(
+                                StatusCode::Forbidden,
+                                &format!(
+                                    "Read permission denied for event type '{}'",
+                                    query.event_type
+                                ),
+                            )
+                            .await;
+                    }
+                }
+                if queries.iter().any(|q| !q.disabled_pruners.is_empty())
+                    && !auth_mgr.is_admin(uid).await
+                {
+                    warn!(
+                        target: "sneldb::union",
+                        user_id = uid,
+                        "DISABLE PRUNERS denied for non-admin user"
+                    );
+                    return self
+                        .write_error(
+                            StatusCode::Forbidden,
+                            "Only admin users can use DISABLE PRUNERS",
+                        )
+                        .await;
+                }
+            }
+        }
+
+        if offset.is_some() && limit.is_none() {
+            warn!(target: "sneldb::union", "OFFSET specified without LIMIT");
+            return self
+                .write_error(
+                    StatusCode::BadRequest,
+                    "OFFSET requires LIMIT to prevent unbounded results",
+                )
+                .await;
+        }
+
+        debug!(
+            target: "sneldb::union",
+            query_count = queries.len(),
+            order_by = ?order_by,
+            limit = ?limit,
+            "Dispatching UNION ALL command to pipeline"
+        );
+
+        let complexity_limits = ComplexityLimits::resolve(self.auth_manager, self.user_id).await;
+        let pipeline = UnionExecutionPipeline::new(
+            queries,
+            order_by.as_ref(),
+            *limit,
+            *offset,
+            self.shard_manager,
+            Arc::clone(&self.registry),
+        )
+        .with_complexity_limits(complexity_limits)
+        .with_memory_budget(QueryMemoryBudget::from_config(self.user_id));
+
+        match pipeline.execute_streaming().await {
+            Ok(stream) => {
+                let (limit, offset) = if pipeline.applies_limit() {
+                    (None, None)
+                } else {
+                    (*limit, *offset)
+                };
+                // UNION ALL keeps duplicates, so rows are not deduplicated by event_id
+                QueryResponseWriter::new(self.writer, self.renderer, stream.schema(), limit, offset)
+                    .with_deduplication(false)
+                    .write(stream)
+                    .await
+            }
+            Err(error) => {
+                warn!(
+                    target: "sneldb::union",
+                    error = %error,
+                    "UNION ALL query failed"
+                );
+                let code = if error.contains(COMPLEXITY_ERROR_PREFIX) {
+                    ErrorCode::QueryTooComplex
+                } else if error.contains(MEMORY_LIMIT_ERROR_PREFIX) {
+                    ErrorCode::QueryMemoryLimitExceeded
+                } else {
+                    ErrorCode::InvalidRequest
+                };
+                let resp = Response::error_with_code(StatusCode::BadRequest, code, &error);
+                self.writer.write_all(&self.renderer.render(&resp)).await
+            }
+        }
+    }
+
+    async fn write_error(&mut self, status: StatusCode, message: &str) -> io::Result<()> {
+        let resp = Response::error(status, message);
+        self.writer.write_all(&self.renderer.render(&resp)).await
+    }
+}
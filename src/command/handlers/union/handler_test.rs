@@ -0,0 +1,191 @@
+use crate::command::handlers::store;
+use crate::command::handlers::union::UnionCommandHandler;
+use crate::command::parser::parse_command;
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use crate::logging::init_for_tests;
+use crate::shared::response::JsonRenderer;
+use crate::test_helpers::factories::{CommandFactory, SchemaRegistryFactory};
+use serde_json::Value;
+use std::sync::Arc;
+use tempfile::tempdir;
+use tokio::io::{AsyncReadExt, duplex};
+use tokio::sync::RwLock;
+use tokio::time::{Duration, sleep};
+
+async fn setup(refund_amount_type: &str) -> (ShardManager, Arc<RwLock<SchemaRegistry>>) {
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("orders", &[("amount", "int")])
+        .await
+        .unwrap();
+    factory
+        .define_with_fields("refunds", &[("amount", refund_amount_type)])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let shard_manager = ShardManager::new(1, base_dir, wal_dir).await;
+    (shard_manager, registry)
+}
+
+async fn store_amounts(
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    rows: &[(&str, &str, i64)],
+) {
+    for (evt, ctx, amount) in rows {
+        let store_cmd = CommandFactory::store()
+            .with_event_type(evt)
+            .with_context_id(ctx)
+            .with_payload(serde_json::json!({ "amount": amount }))
+            .create();
+        let (mut _r, mut w) = duplex(1024);
+        store::handle(
+            &store_cmd,
+            shard_manager,
+            registry,
+            None,
+            None,
+            &mut w,
+            &JsonRenderer,
+        )
+        .await
+        .expect("store should succeed");
+    }
+    sleep(Duration::from_millis(200)).await;
+}
+
+async fn run_union(
+    input: &str,
+    shard_manager: &ShardManager,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+) -> String {
+    let cmd = parse_command(input).expect("union should parse");
+    let (mut reader, mut writer) = duplex(1 << 16);
+    UnionCommandHandler::new(
+        &cmd,
+        shard_manager,
+        Arc::clone(registry),
+        None,
+        None,
+        &mut writer,
+        &JsonRenderer,
+    )
+    .handle()
+    .await
+    .expect("handler should not fail");
+    drop(writer);
+
+    let mut body = String::new();
+    reader.read_to_string(&mut body).await.unwrap();
+    body
+}
+
+/// Extracts the `amount` column from streamed JSON frames, in output order.
+fn amounts(body: &str) -> Vec<i64> {
+    let mut frames = body.lines().map(|line| {
+        serde_json::from_str::<Value>(line).unwrap_or_else(|_| panic!("bad frame: {line}"))
+    });
+    let schema = frames.next().expect("schema frame");
+    let amount_idx = schema["columns"]
+        .as_array()
+        .expect("columns")
+        .iter()
+        .position(|col| col["name"] == "amount")
+        .expect("amount column");
+    frames
+        .filter(|frame| frame["type"] == "batch")
+        .flat_map(|frame| frame["rows"].as_array().cloned().unwrap_or_default())
+        .map(|row| row[amount_idx].as_i64().expect("amount"))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_union_handler_orders_and_limits_unioned_rows() {
+    init_for_tests();
+    let (shard_manager, registry) = setup("int").await;
+    store_amounts(
+        &shard_manager,
+        &registry,
+        &[
+            ("orders", "o1", 10),
+            ("orders", "o2", 30),
+            ("refunds", "r1", 20),
+            ("refunds", "r2", 40),
+        ],
+    )
+    .await;
+
+    let body = run_union(
+        "QUERY orders UNION ALL QUERY refunds ORDER BY amount DESC LIMIT 3",
+        &shard_manager,
+        &registry,
+    )
+    .await;
+    assert_eq!(amounts(&body), vec![40, 30, 20], "body: {body}");
+
+    let body = run_union(
+        "QUERY orders UNION ALL QUERY refunds ORDER BY amount LIMIT 2 OFFSET 1",
+        &shard_manager,
+        &registry,
+    )
+    .await;
+    assert_eq!(amounts(&body), vec![20, 30], "body: {body}");
+}
+
+#[tokio::test]
+async fn test_union_handler_keeps_duplicate_rows() {
+    init_for_tests();
+    let (shard_manager, registry) = setup("int").await;
+    store_amounts(
+        &shard_manager,
+        &registry,
+        &[("orders", "o1", 10), ("orders", "o2", 30)],
+    )
+    .await;
+
+    let body = run_union(
+        "QUERY orders UNION ALL QUERY orders WHERE amount > 20",
+        &shard_manager,
+        &registry,
+    )
+    .await;
+    let mut values = amounts(&body);
+    values.sort();
+    assert_eq!(values, vec![10, 30, 30], "body: {body}");
+}
+
+#[tokio::test]
+async fn test_union_handler_rejects_mismatched_schemas() {
+    init_for_tests();
+    let (shard_manager, registry) = setup("string").await;
+
+    let body = run_union(
+        "QUERY orders UNION ALL QUERY refunds",
+        &shard_manager,
+        &registry,
+    )
+    .await;
+    assert!(
+        body.contains("UNION ALL schema mismatch") && body.contains("'amount'"),
+        "Expected schema mismatch error, got: {}",
+        body
+    );
+    assert!(!body.contains("\"type\":\"schema\""), "body: {body}");
+}
+
+#[tokio::test]
+async fn test_union_handler_rejects_offset_without_limit() {
+    init_for_tests();
+    let (shard_manager, registry) = setup("int").await;
+
+    let body = run_union(
+        "QUERY orders UNION ALL QUERY refunds OFFSET 2",
+        &shard_manager,
+        &registry,
+    )
+    .await;
+    assert!(body.contains("OFFSET requires LIMIT"), "body: {body}");
+}
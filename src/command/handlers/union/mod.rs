@@ -0,0 +1,9 @@
+pub mod handler;
+pub mod orchestrator;
+
+pub use handler::UnionCommandHandler;
+
+#[cfg(test)]
+mod handler_test;
+#[cfg(test)]
+mod orchestrator_test;
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use crate::command::handlers::query::{ComplexityLimits, QueryExecutionPipeline};
+use crate::command::handlers::query_batch_stream::QueryBatchStream;
+use crate::command::types::{Command, OrderSpec, QueryCommand};
+use crate::engine::core::read::flow::operators::UnionOp;
+use crate::engine::core::read::flow::{
+    BatchPool, FlowChannel, FlowContext, FlowMetrics, FlowOperatorError, FlowSource, FlowTelemetry,
+    OrderedStreamMerger, QueryMemoryBudget,
+};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::shard::manager::ShardManager;
+use futures::future::join_all;
+use tokio::sync::RwLock;
+use tracing::error;
+
+const UNION_BATCH_SIZE: usize = 1024;
+
+/// Executes the sub-queries of a `UNION ALL` and concatenates their streams.
+/// With a union-level `ORDER BY`, each sub-query is ordered by the same key and
+/// the streams are merged instead, applying `LIMIT`/`OFFSET` to the merged rows.
+pub struct UnionExecutionPipeline<'a> {
+    queries: &'a [QueryCommand],
+    order_by: Option<&'a OrderSpec>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    shard_manager: &'a ShardManager,
+    registry: Arc<RwLock<SchemaRegistry>>,
+    complexity_limits: ComplexityLimits,
+    memory: Arc<QueryMemoryBudget>,
+}
+
+impl<'a> UnionExecutionPipeline<'a> {
+    pub fn new(
+        queries: &'a [QueryCommand],
+        order_by: Option<&'a OrderSpec>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        shard_manager: &'a ShardManager,
+        registry: Arc<RwLock<SchemaRegistry>>,
+    ) -> Self {
+        Self {
+            queries,
+            order_by,
+            limit,
+            offset,
+            shard_manager,
+            registry,
+            complexity_limits: ComplexityLimits::from_config(),
+            memory: QueryMemoryBudget::unlimited(),
+        }
+    }
+
+    /// Overrides the complexity budget applied to each sub-query.
+    pub fn with_complexity_limits(mut self, limits: ComplexityLimits) -> Self {
+        self.complexity_limits = limits;
+        self
+    }
+
+    /// Sets the memory budget shared by all sub-queries.
+    pub fn with_memory_budget(mut self, memory: Arc<QueryMemoryBudget>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Whether `LIMIT`/`OFFSET` are applied by the merged stream rather than the writer.
+    pub fn applies_limit(&self) -> bool {
+        self.order_by.is_some()
+    }
+
+    /// Rewrites sub-queries so union-level ordering and limits can be pushed down:
+    /// every sub-query is ordered by the union key, and a sub-query never needs to
+    /// return more than `LIMIT + OFFSET` rows.
+    pub fn plan_sub_queries(&self) -> Result<Vec<QueryCommand>, String> {
+        if self.queries.len() < 2 {
+            return Err("UNION ALL requires at least 2 queries".to_string());
+        }
+
+        let bound = self
+            .limit
+            .map(|limit| limit.saturating_add(self.offset.unwrap_or(0)));
+
+        let mut planned = Vec::with_capacity(self.queries.len());
+        for (idx, query) in self.queries.iter().enumerate() {
+            let mut query = query.clone();
+            let has_own_limit = query.limit.is_some() || query.offset.is_some();
+
+            if let Some(order) = self.order_by {
+                match &query.order_by {
+                    Some(own) if own != order => {
+                        return Err(format!(
+                            "Sub-query {} ORDER BY {} does not match the UNION ALL ORDER BY {}",
+                            idx + 1,
+                            own.field,
+                            order.field
+                        ));
+                    }
+                    Some(_) => {}
+                    None if has_own_limit => {
+                        return Err(format!(
+                            "Sub-query {} uses LIMIT/OFFSET without ORDER BY, which cannot be combined with an ordered UNION ALL",
+                            idx + 1
+                        ));
+                    }
+                    None => query.order_by = Some(order.clone()),
+                }
+            }
+
+            if !has_own_limit {
+                query.limit = bound;
+            }
+            planned.push(query);
+        }
+        Ok(planned)
+    }
+
+    pub async fn execute_streaming(&self) -> Result<QueryBatchStream, String> {
+        let queries = self.plan_sub_queries()?;
+
+        let query_futures = queries.into_iter().map(|query| {
+            let command = Command::from(query);
+            let shard_manager = self.shard_manager;
+            let registry = Arc::clone(&self.registry);
+            let limits = self.complexity_limits.clone();
+            let memory = Arc::clone(&self.memory);
+            async move {
+                QueryExecutionPipeline::new(&command, shard_manager, registry)
+                    .with_complexity_limits(limits)
+                    .with_memory_budget(memory)
+                    .execute_streaming()
+                    .await
+            }
+        });
+
+        let mut streams = Vec::new();
+        for (idx, result) in join_all(query_futures).await.into_iter().enumerate() {
+            match result {
+                Ok(Some(stream)) => streams.push(stream),
+                Ok(None) => return Err(format!("Sub-query {} did not return a stream", idx + 1)),
+                Err(e) => return Err(format!("Sub-query {} failed: {}", idx + 1, e)),
+            }
+        }
+
+        // Reject mismatched schemas before any rows are streamed; dropping the
+        // streams aborts the sub-query tasks.
+        let schemas: Vec<_> = streams.iter().map(QueryBatchStream::schema).collect();
+        let schema = UnionOp::validate_schemas(&schemas).map_err(|e| match e {
+            FlowOperatorError::Operator(message) => {
+                format!("UNION ALL schema mismatch: {}", message)
+            }
+            other => other.to_string(),
+        })?;
+
+        let order_index = match self.order_by {
+            Some(order) => Some(
+                schema
+                    .columns()
+                    .iter()
+                    .position(|col| col.name == order.field)
+                    .ok_or_else(|| {
+                        format!(
+                            "UNION ALL ORDER BY field '{}' is not returned by the sub-queries",
+                            order.field
+                        )
+                    })?,
+            ),
+            None => None,
+        };
+
+        let mut tasks = Vec::new();
+        let mut receivers = Vec::with_capacity(streams.len());
+        for stream in streams {
+            let (_, receiver, mut stream_tasks) = stream.into_parts();
+            tasks.append(&mut stream_tasks);
+            receivers.push(receiver);
+        }
+
+        let metrics = FlowMetrics::new();
+        let (tx, rx) = FlowChannel::bounded(receivers.len() * 2, Arc::clone(&metrics));
+
+        let union_task = match (self.order_by, order_index) {
+            (Some(order), Some(order_index)) => OrderedStreamMerger::spawn(
+                Arc::clone(&schema),
+                receivers,
+                order_index,
+                !order.desc,
+                self.offset.unwrap_or(0) as usize,
+                self.limit.map(|limit| limit as usize),
+                tx,
+                UNION_BATCH_SIZE,
+            )?,
+            _ => {
+                let pool = BatchPool::new(UNION_BATCH_SIZE).map_err(|e| e.to_string())?;
+                let ctx = Arc::new(FlowContext::new(
+                    UNION_BATCH_SIZE,
+                    pool,
+                    metrics,
+                    None::<&str>,
+                    FlowTelemetry {
+                        pipeline_name: Some("union_all".to_string()),
+                        operator_count: Some(1),
+                    },
+                ));
+                let op = UnionOp::new(receivers);
+                tokio::spawn(async move {
+                    if let Err(err) = op.run(tx, ctx).await {
+                        error!(
+                            target: "sneldb::union",
+                            error = %err,
+                            "UNION ALL stream failed"
+                        );
+                    }
+                })
+            }
+        };
+        tasks.push(union_task);
+
+        Ok(QueryBatchStream::new(schema, rx, tasks).with_memory_budget(Arc::clone(&self.memory)))
+    }
+}
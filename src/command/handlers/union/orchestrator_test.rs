@@ -0,0 +1,150 @@
+use crate::command::handlers::union::orchestrator::UnionExecutionPipeline;
+use crate::command::types::{OrderSpec, QueryCommand};
+use crate::engine::shard::manager::ShardManager;
+use crate::logging::init_for_tests;
+use crate::test_helpers::factories::SchemaRegistryFactory;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn query(event_type: &str) -> QueryCommand {
+    QueryCommand {
+        event_type: event_type.to_string(),
+        context_id: None,
+        since: None,
+        time_field: None,
+        sequence_time_field: None,
+        where_clause: None,
+        limit: None,
+        offset: None,
+        order_by: None,
+        picked_zones: None,
+        return_fields: None,
+        link_field: None,
+        aggs: None,
+        time_bucket: None,
+        group_by: None,
+        event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
+    }
+}
+
+fn order(field: &str, desc: bool) -> OrderSpec {
+    OrderSpec {
+        field: field.to_string(),
+        desc,
+    }
+}
+
+async fn shard_manager() -> ShardManager {
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    ShardManager::new(1, base_dir, wal_dir).await
+}
+
+#[tokio::test]
+async fn test_plan_sub_queries_pushes_down_order_and_limit() {
+    init_for_tests();
+    let shard_manager = shard_manager().await;
+    let registry = SchemaRegistryFactory::new().registry();
+
+    let mut capped = query("refunds");
+    capped.limit = Some(2);
+    capped.order_by = Some(order("amount", true));
+    let queries = vec![query("orders"), capped];
+    let union_order = order("amount", true);
+
+    let pipeline = UnionExecutionPipeline::new(
+        &queries,
+        Some(&union_order),
+        Some(10),
+        Some(5),
+        &shard_manager,
+        Arc::clone(&registry),
+    );
+    assert!(pipeline.applies_limit());
+
+    let planned = pipeline.plan_sub_queries().expect("plan");
+    assert_eq!(planned[0].order_by, Some(union_order.clone()));
+    assert_eq!(planned[0].limit, Some(15));
+    // A sub-query's own LIMIT is kept
+    assert_eq!(planned[1].limit, Some(2));
+}
+
+#[tokio::test]
+async fn test_plan_sub_queries_rejects_conflicting_order() {
+    init_for_tests();
+    let shard_manager = shard_manager().await;
+    let registry = SchemaRegistryFactory::new().registry();
+    let union_order = order("amount", false);
+
+    let mut other_order = query("refunds");
+    other_order.order_by = Some(order("timestamp", false));
+    let queries = vec![query("orders"), other_order];
+    let err = UnionExecutionPipeline::new(
+        &queries,
+        Some(&union_order),
+        None,
+        None,
+        &shard_manager,
+        Arc::clone(&registry),
+    )
+    .plan_sub_queries()
+    .unwrap_err();
+    assert!(err.contains("Sub-query 2 ORDER BY timestamp"), "{err}");
+
+    let mut unordered_limit = query("refunds");
+    unordered_limit.limit = Some(3);
+    let queries = vec![query("orders"), unordered_limit];
+    let err = UnionExecutionPipeline::new(
+        &queries,
+        Some(&union_order),
+        None,
+        None,
+        &shard_manager,
+        Arc::clone(&registry),
+    )
+    .plan_sub_queries()
+    .unwrap_err();
+    assert!(err.contains("LIMIT/OFFSET without ORDER BY"), "{err}");
+
+    // Without a union ORDER BY, sub-query limits are fine
+    let pipeline = UnionExecutionPipeline::new(
+        &queries,
+        None,
+        None,
+        None,
+        &shard_manager,
+        Arc::clone(&registry),
+    );
+    assert!(!pipeline.applies_limit());
+    let planned = pipeline.plan_sub_queries().expect("plan");
+    assert_eq!(planned[0].limit, None);
+    assert_eq!(planned[1].limit, Some(3));
+}
+
+#[tokio::test]
+async fn test_plan_sub_queries_requires_two_queries() {
+    init_for_tests();
+    let shard_manager = shard_manager().await;
+    let registry = SchemaRegistryFactory::new().registry();
+    let queries = vec![query("orders")];
+
+    let result = UnionExecutionPipeline::new(
+        &queries,
+        None,
+        None,
+        None,
+        &shard_manager,
+        Arc::clone(&registry),
+    )
+    .execute_streaming()
+    .await;
+    assert!(matches!(result, Err(e) if e.contains("at least 2 queries")));
+}
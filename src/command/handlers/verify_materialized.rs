@@ -0,0 +1,135 @@
+use crate::command::types::Command;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
+use crate::engine::materialize::{IssueSeverity, MaterializationCatalog, verify_store};
+use crate::shared::config::CONFIG;
+use crate::shared::path::absolutize;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{Response, StatusCode};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::{debug, warn};
+
+/// Reads every stored frame of one or all materializations and reports
+/// missing, orphaned, corrupt and schema-drifted frames. Nothing is repaired.
+pub async fn handle<W: AsyncWrite + Unpin>(
+    cmd: &Command,
+    auth_manager: Option<&Arc<AuthManager>>,
+    user_id: Option<&str>,
+    writer: &mut W,
+    renderer: &dyn Renderer,
+) -> std::io::Result<()> {
+    let Command::VerifyMaterialized { name } = cmd else {
+        let resp = Response::error(
+            StatusCode::BadRequest,
+            "Invalid VERIFY MATERIALIZED command",
+        );
+        return writer.write_all(&renderer.render(&resp)).await;
+    };
+
+    if let Some(auth_mgr) = auth_manager {
+        match user_id {
+            Some(uid) if uid == BYPASS_USER_ID || auth_mgr.is_admin(uid).await => {}
+            Some(uid) => {
+                warn!(target: "sneldb::verify", user_id = uid, "Admin permission denied");
+                let resp = Response::error(
+                    StatusCode::Forbidden,
+                    "Only admin users can verify materializations",
+                );
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+            None => {
+                let resp = Response::error(StatusCode::Unauthorized, "Authentication required");
+                return writer.write_all(&renderer.render(&resp)).await;
+            }
+        }
+    }
+
+    debug!(target: "sneldb::verify", name = ?name, "Verifying materializations");
+
+    let name = name.clone();
+    let data_dir = absolutize(PathBuf::from(CONFIG.engine.data_dir.as_str()));
+    let resp =
+        match tokio::task::spawn_blocking(move || verify_with_data_dir(name.as_deref(), &data_dir))
+            .await
+        {
+            Ok(Ok(lines)) => Response::ok_lines(lines),
+            Ok(Err(resp)) => resp,
+            Err(e) => Response::error(StatusCode::InternalError, format!("Verify failed: {}", e)),
+        };
+    writer.write_all(&renderer.render(&resp)).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// One summary line per materialization, followed by its issues.
+pub fn verify_with_data_dir(name: Option<&str>, data_dir: &Path) -> Result<Vec<String>, Response> {
+    let not_found = |name: &str| {
+        Response::error(
+            StatusCode::NotFound,
+            format!("No materialization named '{}'", name),
+        )
+    };
+    let internal = |e: crate::engine::materialize::MaterializationError| {
+        Response::error(StatusCode::InternalError, e.to_string())
+    };
+
+    // Loading creates an empty catalog, which a read-only check should not do
+    if !data_dir
+        .join("materializations")
+        .join("catalog.mcat")
+        .exists()
+    {
+        return match name {
+            Some(name) => Err(not_found(name)),
+            None => Ok(vec!["No materializations".to_string()]),
+        };
+    }
+
+    let catalog = MaterializationCatalog::load(data_dir).map_err(internal)?;
+    let mut entries = match name {
+        Some(name) => vec![
+            catalog
+                .get(name)
+                .map_err(internal)?
+                .ok_or_else(|| not_found(name))?,
+        ],
+        None => catalog.entries().map_err(internal)?.into_values().collect(),
+    };
+    if entries.is_empty() {
+        return Ok(vec!["No materializations".to_string()]);
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut lines = Vec::new();
+    for entry in entries {
+        let report = match verify_store(&entry.storage_path, &entry.schema) {
+            Ok(report) => report,
+            Err(err) => {
+                lines.push(format!("{}: unreadable manifest: {}", entry.name, err));
+                continue;
+            }
+        };
+        let fatal = report.count(IssueSeverity::Fatal);
+        let recoverable = report.count(IssueSeverity::Recoverable);
+        let status = if fatal > 0 {
+            "CORRUPT"
+        } else if recoverable > 0 {
+            "RECOVERABLE"
+        } else {
+            "OK"
+        };
+        lines.push(format!(
+            "{}: {} ({} frames, {} rows, {} fatal, {} recoverable)",
+            entry.name, status, report.frames_checked, report.rows_checked, fatal, recoverable
+        ));
+        for issue in &report.issues {
+            let label = match issue.severity() {
+                IssueSeverity::Fatal => "fatal",
+                IssueSeverity::Recoverable => "recoverable",
+            };
+            lines.push(format!("  {}: {}", label, issue));
+        }
+    }
+    Ok(lines)
+}
@@ -0,0 +1,104 @@
+use crate::command::handlers::verify_materialized::verify_with_data_dir;
+use crate::command::types::MaterializedQuerySpec;
+use crate::engine::core::read::flow::{BatchPool, BatchSchema};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::materialize::{
+    MaterializationCatalog, MaterializationEntry, MaterializedStore, batch_schema_to_snapshots,
+};
+use crate::engine::types::ScalarValue;
+use crate::shared::response::StatusCode;
+use crate::test_helpers::factories::command_factory::CommandFactory;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// Registers `alias` in the catalog under `data_dir` and stores one two-row frame.
+fn remember(data_dir: &Path, alias: &str) -> MaterializationEntry {
+    let schema = Arc::new(
+        BatchSchema::new(vec![
+            ColumnSpec {
+                name: "timestamp".into(),
+                logical_type: "Timestamp".into(),
+            },
+            ColumnSpec {
+                name: "event_id".into(),
+                logical_type: "Integer".into(),
+            },
+        ])
+        .unwrap(),
+    );
+    let mut catalog = MaterializationCatalog::load(data_dir).unwrap();
+    let spec = MaterializedQuerySpec {
+        name: alias.to_string(),
+        query: Box::new(CommandFactory::query().with_event_type("orders").create()),
+    };
+    let mut entry = MaterializationEntry::new(spec, catalog.root_dir()).unwrap();
+    entry.schema = batch_schema_to_snapshots(&schema);
+    catalog.insert(entry.clone()).unwrap();
+
+    let pool = BatchPool::new(2).unwrap();
+    let mut builder = pool.acquire(Arc::clone(&schema));
+    for id in [1u64, 2] {
+        builder
+            .push_row(&[
+                ScalarValue::from(json!(1_700_000_000 + id)),
+                ScalarValue::from(json!(id)),
+            ])
+            .unwrap();
+    }
+    MaterializedStore::open(&entry.storage_path)
+        .unwrap()
+        .append_batch(&entry.schema, &builder.finish().unwrap())
+        .unwrap();
+    entry
+}
+
+#[test]
+fn test_verify_reports_each_materialization_with_its_issues() {
+    let tmp = tempdir().unwrap();
+    remember(tmp.path(), "healthy");
+    let broken = remember(tmp.path(), "broken");
+    let frames = broken.storage_path.join("frames");
+    std::fs::rename(frames.join("000000.mat"), frames.join("000007.mat")).unwrap();
+
+    let lines = verify_with_data_dir(None, tmp.path()).unwrap();
+
+    assert_eq!(
+        lines,
+        vec![
+            "broken: CORRUPT (1 frames, 0 rows, 1 fatal, 1 recoverable)".to_string(),
+            "  fatal: frame 000000.mat is in the manifest but missing on disk".to_string(),
+            "  recoverable: frame 000007.mat is not in the manifest (safe to delete)".to_string(),
+            "healthy: OK (1 frames, 2 rows, 0 fatal, 0 recoverable)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_verify_named_materialization() {
+    let tmp = tempdir().unwrap();
+    remember(tmp.path(), "orders_view");
+    remember(tmp.path(), "other_view");
+
+    let lines = verify_with_data_dir(Some("orders_view"), tmp.path()).unwrap();
+    assert_eq!(
+        lines,
+        vec!["orders_view: OK (1 frames, 2 rows, 0 fatal, 0 recoverable)".to_string()]
+    );
+
+    let err = verify_with_data_dir(Some("missing"), tmp.path()).unwrap_err();
+    assert_eq!(err.status, StatusCode::NotFound);
+}
+
+#[test]
+fn test_verify_without_catalog_does_not_create_one() {
+    let tmp = tempdir().unwrap();
+
+    let lines = verify_with_data_dir(None, tmp.path()).unwrap();
+    assert_eq!(lines, vec!["No materializations".to_string()]);
+
+    let err = verify_with_data_dir(Some("orders"), tmp.path()).unwrap_err();
+    assert_eq!(err.status, StatusCode::NotFound);
+    assert!(!tmp.path().join("materializations").exists());
+}
@@ -1,5 +1,6 @@
 use crate::command::parser::commands;
 use crate::command::parser::error::ParseError;
+use crate::command::parser::limits::ParseLimits;
 use crate::command::parser::tokenizer::{Token, tokenize};
 use crate::command::types::Command;
 use tracing::{debug, warn};
@@ -15,6 +16,10 @@ pub fn parse_command(input: &str) -> Result<Command, ParseError> {
         warn!(target: "sneldb::parse", ?err, "Token validation failed");
         return Err(err);
     }
+    if let Err(err) = ParseLimits::from_config().check(&tokens) {
+        warn!(target: "sneldb::parse", %err, "Command exceeds parse limits");
+        return Err(err);
+    }
 
     match tokens.first() {
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("DEFINE") => {
@@ -27,10 +32,15 @@ pub fn parse_command(input: &str) -> Result<Command, ParseError> {
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("REMEMBER") => {
             commands::remember::parse(input)
         }
+        Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("EXPLAIN") => {
+            commands::explain::parse(input)
+        }
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("QUERY") => {
             commands::query::parse(input)
         }
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("FIND") => commands::query::parse(input),
+        // Parenthesized sub-query of a UNION ALL
+        Some(Token::LeftParen) => commands::query::parse(input),
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("REPLAY") => {
             commands::replay::parse(input)
         }
@@ -43,6 +53,26 @@ pub fn parse_command(input: &str) -> Result<Command, ParseError> {
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("FLUSH") => {
             commands::flush::parse(&tokens)
         }
+        Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("GET") => {
+            commands::get_event::parse(input)
+        }
+        Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("INSPECT") => match tokens.get(1) {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("WAL") => {
+                commands::inspect_wal::parse(&tokens)
+            }
+            _ => commands::inspect_zone::parse(&tokens),
+        },
+        Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("VERIFY") => {
+            commands::verify_materialized::parse(&tokens)
+        }
+        Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("REBUILD") => {
+            commands::rebuild_indexes::parse(&tokens)
+        }
+        Some(Token::Word(cmd))
+            if cmd.eq_ignore_ascii_case("EXPORT") || cmd.eq_ignore_ascii_case("IMPORT") =>
+        {
+            commands::schema_catalog::parse(input)
+        }
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("PLOT") => {
             commands::plotql::parse(input)
         }
@@ -61,14 +91,24 @@ pub fn parse_command(input: &str) -> Result<Command, ParseError> {
             // Try permission revoke parser
             commands::revoke_permission::parse(&tokens)
         }
+        Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("ROTATE") => {
+            commands::rotate_key::parse(&tokens)
+        }
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("LIST") => {
             commands::list_users::parse(&tokens)
         }
+        Some(Token::Word(cmd))
+            if cmd.eq_ignore_ascii_case("DISABLE")
+                || cmd.eq_ignore_ascii_case("ENABLE")
+                || cmd.eq_ignore_ascii_case("UNLOCK") =>
+        {
+            commands::user_state::parse(&tokens)
+        }
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("GRANT") => {
             commands::grant_permission::parse(&tokens)
         }
         Some(Token::Word(cmd)) if cmd.eq_ignore_ascii_case("SHOW") => {
-            // Check if it's SHOW PERMISSIONS or SHOW MATERIALIZED
+            // Check if it's SHOW PERMISSIONS, SHOW USERS, SHOW PINNED SEGMENTS, SHOW STATS, SHOW USAGE, SHOW INDEXES or SHOW MATERIALIZED
             if tokens.len() >= 2 {
                 if let Token::Word(word) = &tokens[1] {
                     if word.eq_ignore_ascii_case("PERMISSIONS") {
@@ -77,11 +117,26 @@ pub fn parse_command(input: &str) -> Result<Command, ParseError> {
                         }
                         return commands::show_permissions::parse(&tokens);
                     }
+                    if word.eq_ignore_ascii_case("USERS") {
+                        return commands::show_users::parse(&tokens);
+                    }
+                    if word.eq_ignore_ascii_case("PINNED") && tokens.len() >= 3 {
+                        return commands::show_pinned_segments::parse(&tokens);
+                    }
+                    if word.eq_ignore_ascii_case("STATS") {
+                        return commands::show_stats::parse(&tokens);
+                    }
+                    if word.eq_ignore_ascii_case("USAGE") {
+                        return commands::show_usage::parse(&tokens);
+                    }
+                    if word.eq_ignore_ascii_case("INDEXES") || word.eq_ignore_ascii_case("INDEX") {
+                        return commands::show_indexes::parse(&tokens);
+                    }
                 }
             }
             // Fall back to show parser (for SHOW MATERIALIZED)
             if tracing::enabled!(tracing::Level::DEBUG) {
-                debug!(target: "sneldb::parse", "Routing to SHOW parser (not PERMISSIONS)");
+                debug!(target: "sneldb::parse", "Routing to SHOW parser (not PERMISSIONS, USERS, PINNED SEGMENTS, STATS, USAGE or INDEXES)");
             }
             commands::show::parse(&tokens)
         }
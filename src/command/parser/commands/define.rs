@@ -1,10 +1,10 @@
 use crate::command::parser::error::ParseError;
 use crate::command::parser::tokenizer::Token;
-use crate::command::types::{Command, FieldSpec, MiniSchema};
+use crate::command::types::{Command, FieldIndexKind, FieldSpec, MiniSchema};
+use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde_json::Value;
-use std::collections::HashMap;
 
 use Token::*;
 
@@ -87,6 +87,66 @@ pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
         return Err(ParseError::EmptySchema);
     }
 
+    // Optional: USING <time_field> [, <temporal_field> ...]
+    let mut time_field = None;
+    let mut temporal_fields = Vec::new();
+    if let Some(Word(using_kw)) = iter.peek()
+        && using_kw.eq_ignore_ascii_case("USING")
+    {
+        iter.next(); // consume USING
+
+        loop {
+            let field = match iter.next() {
+                Some(Word(field)) | Some(StringLiteral(field)) => field,
+                Some(tok) => {
+                    return Err(ParseError::UnexpectedToken(format!(
+                        "Expected time field after USING, found {:?}",
+                        tok
+                    )));
+                }
+                None => {
+                    return Err(ParseError::MissingArgument(
+                        "Expected time field after USING".into(),
+                    ));
+                }
+            };
+            if !fields.contains_key(field) {
+                return Err(ParseError::UnexpectedToken(format!(
+                    "Time field '{}' is not defined in FIELDS",
+                    field
+                )));
+            }
+            if time_field.as_ref() == Some(field) || temporal_fields.contains(field) {
+                return Err(ParseError::UnexpectedToken(format!(
+                    "Time field '{}' is listed twice in USING",
+                    field
+                )));
+            }
+            // The first field is the default time dimension
+            if time_field.is_none() {
+                time_field = Some(field.clone());
+            } else {
+                temporal_fields.push(field.clone());
+            }
+
+            match iter.peek() {
+                Some(Symbol(',')) => {
+                    iter.next();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    // Optional: INDEXES { "<field>": ["<kind>", ...], ... }
+    let mut indexes = IndexMap::new();
+    if let Some(Word(indexes_kw)) = iter.peek()
+        && indexes_kw.eq_ignore_ascii_case("INDEXES")
+    {
+        iter.next(); // consume INDEXES
+        indexes = parse_indexes_block(&mut iter, &fields)?;
+    }
+
     if iter.peek().is_some() {
         return Err(ParseError::UnexpectedToken(format!(
             "Unexpected token after FIELDS block: {:?}",
@@ -97,13 +157,107 @@ pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
     Ok(Command::Define {
         event_type,
         version,
-        schema: MiniSchema { fields },
+        schema: MiniSchema {
+            fields,
+            time_field,
+            temporal_fields,
+            indexes,
+        },
     })
 }
 
 fn parse_fields_block<'a, I>(
     tokens: &mut std::iter::Peekable<I>,
-) -> Result<HashMap<String, FieldSpec>, ParseError>
+) -> Result<IndexMap<String, FieldSpec>, ParseError>
+where
+    I: Iterator<Item = &'a Token>,
+{
+    let map = parse_json_block(tokens)?;
+
+    let mut fields = IndexMap::new();
+    for (key, val) in map {
+        match val {
+            Value::String(s) => {
+                fields.insert(key, FieldSpec::Primitive(s));
+            }
+            Value::Array(arr) => {
+                let mut variants = Vec::with_capacity(arr.len());
+                for v in arr {
+                    if let Value::String(s) = v {
+                        variants.push(s);
+                    } else {
+                        return Err(ParseError::InvalidJson(
+                            "Enum variants must be strings".to_string(),
+                        ));
+                    }
+                }
+                if variants.is_empty() {
+                    return Err(ParseError::InvalidJson(
+                        "Enum must have at least one variant".to_string(),
+                    ));
+                }
+                fields.insert(key, FieldSpec::Enum(variants));
+            }
+            _ => {
+                return Err(ParseError::InvalidJson(
+                    "Field type must be a string or array".to_string(),
+                ));
+            }
+        }
+    }
+    Ok(fields)
+}
+
+/// Index kinds per field; an empty list leaves the field unindexed.
+fn parse_indexes_block<'a, I>(
+    tokens: &mut std::iter::Peekable<I>,
+    fields: &IndexMap<String, FieldSpec>,
+) -> Result<IndexMap<String, Vec<FieldIndexKind>>, ParseError>
+where
+    I: Iterator<Item = &'a Token>,
+{
+    let map = parse_json_block(tokens)?;
+
+    let mut indexes = IndexMap::new();
+    for (field, val) in map {
+        if !fields.contains_key(&field) {
+            return Err(ParseError::UnexpectedToken(format!(
+                "Indexed field '{}' is not defined in FIELDS",
+                field
+            )));
+        }
+        let Value::Array(names) = val else {
+            return Err(ParseError::InvalidJson(format!(
+                "Indexes of '{}' must be a list of index kinds",
+                field
+            )));
+        };
+        let mut kinds = Vec::with_capacity(names.len());
+        for name in names {
+            let Value::String(name) = name else {
+                return Err(ParseError::InvalidJson(
+                    "Index kinds must be strings".to_string(),
+                ));
+            };
+            let kind = FieldIndexKind::parse(&name).ok_or_else(|| {
+                ParseError::UnexpectedToken(format!(
+                    "Unknown index kind '{}' for field '{}'",
+                    name, field
+                ))
+            })?;
+            kinds.push(kind);
+        }
+        kinds.sort();
+        kinds.dedup();
+        indexes.insert(field, kinds);
+    }
+    Ok(indexes)
+}
+
+/// Collects a flat `{ ... }` block of words, strings and lists into a JSON object.
+fn parse_json_block<'a, I>(
+    tokens: &mut std::iter::Peekable<I>,
+) -> Result<IndexMap<String, Value>, ParseError>
 where
     I: Iterator<Item = &'a Token>,
 {
@@ -163,48 +317,8 @@ where
         return Err(ParseError::ExpectedJsonBlock);
     }
 
-    let parsed: Value = serde_json::from_str(&json_string)
-        .map_err(|_| ParseError::InvalidJson(json_string.clone()))?;
-
-    match parsed {
-        Value::Object(map) => {
-            let mut fields = HashMap::new();
-            for (key, val) in map {
-                match val {
-                    Value::String(s) => {
-                        fields.insert(key, FieldSpec::Primitive(s));
-                    }
-                    Value::Array(arr) => {
-                        let mut variants = Vec::with_capacity(arr.len());
-                        for v in arr {
-                            if let Value::String(s) = v {
-                                variants.push(s);
-                            } else {
-                                return Err(ParseError::InvalidJson(
-                                    "Enum variants must be strings".to_string(),
-                                ));
-                            }
-                        }
-                        if variants.is_empty() {
-                            return Err(ParseError::InvalidJson(
-                                "Enum must have at least one variant".to_string(),
-                            ));
-                        }
-                        fields.insert(key, FieldSpec::Enum(variants));
-                    }
-                    _ => {
-                        return Err(ParseError::InvalidJson(
-                            "Field type must be a string or array".to_string(),
-                        ));
-                    }
-                }
-            }
-            Ok(fields)
-        }
-        _ => Err(ParseError::InvalidJson(
-            "FIELDS must be a JSON object".to_string(),
-        )),
-    }
+    // Parsed straight into an IndexMap so fields keep their declared order
+    serde_json::from_str(&json_string).map_err(|_| ParseError::InvalidJson(json_string.clone()))
 }
 
 fn validate_event_type(name: &str) -> Result<(), ParseError> {
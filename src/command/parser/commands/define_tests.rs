@@ -1,7 +1,7 @@
 use crate::command::parser::commands::define;
 use crate::command::parser::error::ParseError;
 use crate::command::parser::tokenizer::tokenize;
-use crate::command::types::{Command, FieldSpec, MiniSchema};
+use crate::command::types::{Command, FieldIndexKind, FieldSpec, MiniSchema};
 
 #[cfg(test)]
 mod define_tests {
@@ -21,7 +21,7 @@ mod define_tests {
                 version: None,
                 schema: MiniSchema {
                     fields: {
-                        let mut map = std::collections::HashMap::new();
+                        let mut map = indexmap::IndexMap::new();
                         map.insert("id".to_string(), FieldSpec::Primitive("int".to_string()));
                         map.insert(
                             "status".to_string(),
@@ -29,6 +29,9 @@ mod define_tests {
                         );
                         map
                     },
+                    time_field: None,
+                    temporal_fields: Vec::new(),
+                    indexes: indexmap::IndexMap::new(),
                 }
             }
         );
@@ -48,7 +51,7 @@ mod define_tests {
                 version: None,
                 schema: MiniSchema {
                     fields: {
-                        let mut map = std::collections::HashMap::new();
+                        let mut map = indexmap::IndexMap::new();
                         map.insert("id".to_string(), FieldSpec::Primitive("int".to_string()));
                         map.insert(
                             "email".to_string(),
@@ -56,6 +59,9 @@ mod define_tests {
                         );
                         map
                     },
+                    time_field: None,
+                    temporal_fields: Vec::new(),
+                    indexes: indexmap::IndexMap::new(),
                 }
             }
         );
@@ -75,13 +81,16 @@ mod define_tests {
                 version: Some(3),
                 schema: MiniSchema {
                     fields: {
-                        let mut map = std::collections::HashMap::new();
+                        let mut map = indexmap::IndexMap::new();
                         map.insert(
                             "invoice_id".to_string(),
                             FieldSpec::Primitive("int".to_string()),
                         );
                         map
                     },
+                    time_field: None,
+                    temporal_fields: Vec::new(),
+                    indexes: indexmap::IndexMap::new(),
                 }
             }
         );
@@ -170,13 +179,16 @@ mod define_tests {
                 version: None,
                 schema: MiniSchema {
                     fields: {
-                        let mut map = std::collections::HashMap::new();
+                        let mut map = indexmap::IndexMap::new();
                         map.insert(
                             "plan".to_string(),
                             FieldSpec::Enum(vec!["pro".to_string(), "basic".to_string()]),
                         );
                         map
                     },
+                    time_field: None,
+                    temporal_fields: Vec::new(),
+                    indexes: indexmap::IndexMap::new(),
                 },
             }
         );
@@ -192,4 +204,138 @@ mod define_tests {
 
         assert!(matches!(result, Err(ParseError::InvalidJson(_))));
     }
+
+    #[test]
+    fn test_parse_define_with_time_field() {
+        let input = r#"DEFINE order_created FIELDS { "id": "int", "event_time": "datetime" } USING event_time"#;
+        let tokens = tokenize(input);
+
+        let command = define::parse(&tokens).expect("Failed to parse DEFINE with USING");
+
+        let Command::Define { schema, .. } = command else {
+            panic!("Expected Define command");
+        };
+        assert_eq!(schema.time_field.as_deref(), Some("event_time"));
+        assert_eq!(schema.fields.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_define_with_unknown_time_field_should_fail() {
+        let input = r#"DEFINE order_created FIELDS { "id": "int" } USING event_time"#;
+        let tokens = tokenize(input);
+
+        let result = define::parse(&tokens);
+
+        assert!(matches!(result, Err(ParseError::UnexpectedToken(_))));
+    }
+
+    #[test]
+    fn test_parse_define_using_without_field_should_fail() {
+        let input = r#"DEFINE order_created FIELDS { "id": "int" } USING"#;
+        let tokens = tokenize(input);
+
+        let result = define::parse(&tokens);
+
+        assert!(matches!(result, Err(ParseError::MissingArgument(_))));
+    }
+
+    #[test]
+    fn test_parse_define_with_multiple_time_fields() {
+        let input = r#"DEFINE shipment FIELDS { "valid_at": "datetime", "recorded_at": "datetime" } USING valid_at, recorded_at"#;
+        let tokens = tokenize(input);
+
+        let command = define::parse(&tokens).expect("Failed to parse DEFINE with two time fields");
+
+        let Command::Define { schema, .. } = command else {
+            panic!("Expected Define command");
+        };
+        assert_eq!(schema.time_field.as_deref(), Some("valid_at"));
+        assert_eq!(schema.temporal_fields, vec!["recorded_at".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_define_with_repeated_time_field_should_fail() {
+        let input = r#"DEFINE shipment FIELDS { "valid_at": "datetime" } USING valid_at, valid_at"#;
+        let tokens = tokenize(input);
+
+        let result = define::parse(&tokens);
+
+        assert!(matches!(result, Err(ParseError::UnexpectedToken(_))));
+    }
+
+    #[test]
+    fn test_parse_define_keeps_declared_field_order() {
+        let input =
+            r#"DEFINE order_created FIELDS { "zeta": "string", "amount": "int", "b": "bool" }"#;
+        let tokens = tokenize(input);
+
+        let Command::Define { schema, .. } = define::parse(&tokens).unwrap() else {
+            panic!("Expected Define command");
+        };
+        let names: Vec<&str> = schema.fields.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["zeta", "amount", "b"]);
+    }
+
+    #[test]
+    fn test_parse_define_with_indexes() {
+        let input = r#"DEFINE order_created FIELDS { "amount": "int", "note": "string", "plan": ["free", "pro"] } INDEXES { "amount": ["surf", "XOR", "surf"], "note": [] }"#;
+        let tokens = tokenize(input);
+
+        let Command::Define { schema, .. } = define::parse(&tokens).unwrap() else {
+            panic!("Expected Define command");
+        };
+        assert_eq!(
+            schema.indexes.get("amount"),
+            Some(&vec![FieldIndexKind::Surf, FieldIndexKind::Xor])
+        );
+        assert_eq!(schema.indexes.get("note"), Some(&Vec::new()));
+        assert!(!schema.indexes.contains_key("plan"));
+    }
+
+    #[test]
+    fn test_parse_define_with_indexes_after_using() {
+        let input = r#"DEFINE shipment FIELDS { "valid_at": "datetime", "amount": "int" } USING valid_at INDEXES { "amount": ["histogram"] }"#;
+        let tokens = tokenize(input);
+
+        let Command::Define { schema, .. } = define::parse(&tokens).unwrap() else {
+            panic!("Expected Define command");
+        };
+        assert_eq!(schema.time_field.as_deref(), Some("valid_at"));
+        assert_eq!(
+            schema.indexes.get("amount"),
+            Some(&vec![FieldIndexKind::Histogram])
+        );
+    }
+
+    #[test]
+    fn test_parse_define_with_invalid_indexes_should_fail() {
+        for input in [
+            r#"DEFINE e FIELDS { "amount": "int" } INDEXES { "other": ["surf"] }"#,
+            r#"DEFINE e FIELDS { "note": "string" } INDEXES { "note": ["trigram"] }"#,
+        ] {
+            let result = define::parse(&tokenize(input));
+            assert!(
+                matches!(result, Err(ParseError::UnexpectedToken(_))),
+                "{}: {:?}",
+                input,
+                result
+            );
+        }
+
+        for input in [
+            r#"DEFINE e FIELDS { "amount": "int" } INDEXES { "amount": "surf" }"#,
+            r#"DEFINE e FIELDS { "amount": "int" } INDEXES { "amount": [1] }"#,
+        ] {
+            let result = define::parse(&tokenize(input));
+            assert!(
+                matches!(result, Err(ParseError::InvalidJson(_))),
+                "{}: {:?}",
+                input,
+                result
+            );
+        }
+
+        let result = define::parse(&tokenize(r#"DEFINE e FIELDS { "amount": "int" } INDEXES"#));
+        assert!(matches!(result, Err(ParseError::ExpectedJsonBlock)));
+    }
 }
@@ -0,0 +1,48 @@
+use crate::command::parser::commands::query;
+use crate::command::parser::error::ParseError;
+use crate::command::types::Command;
+
+/// `EXPLAIN QUERY ...`: describes how the query would run without running it.
+/// `EXPLAIN ANALYZE QUERY ...` runs it and reports what it read as well.
+pub fn parse(input: &str) -> Result<Command, ParseError> {
+    let trimmed = input.trim();
+    let remainder = strip_keyword(trimmed, "EXPLAIN")
+        .ok_or_else(|| ParseError::UnexpectedToken("EXPLAIN".to_string()))?;
+    let (analyze, remainder) = match strip_keyword(remainder, "ANALYZE") {
+        Some(rest) => (true, rest),
+        None => (false, remainder),
+    };
+
+    if remainder.is_empty() {
+        return Err(ParseError::MissingArgument("QUERY to explain".to_string()));
+    }
+    if !remainder.to_ascii_uppercase().starts_with("QUERY") {
+        return Err(ParseError::ExpectedKeyword(
+            "QUERY".to_string(),
+            remainder
+                .split_whitespace()
+                .next()
+                .unwrap_or("")
+                .to_string(),
+        ));
+    }
+
+    match query::parse(remainder)? {
+        query @ Command::Query { .. } => Ok(Command::Explain {
+            query: Box::new(query),
+            analyze,
+        }),
+        _ => Err(ParseError::UnexpectedToken(
+            "EXPLAIN expects a single QUERY command".to_string(),
+        )),
+    }
+}
+
+/// The input after a leading `keyword` and the whitespace following it.
+fn strip_keyword<'a>(input: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = input
+        .get(..keyword.len())
+        .filter(|head| head.eq_ignore_ascii_case(keyword))
+        .map(|_| &input[keyword.len()..])?;
+    (rest.is_empty() || rest.starts_with(char::is_whitespace)).then(|| rest.trim_start())
+}
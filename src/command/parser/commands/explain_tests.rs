@@ -0,0 +1,55 @@
+use super::explain;
+use crate::command::parser::command::parse_command;
+use crate::command::parser::error::ParseError;
+use crate::command::types::Command;
+
+#[test]
+fn parse_explain_wraps_the_query() {
+    let cmd = explain::parse("EXPLAIN QUERY orders COUNT BY region").expect("parse EXPLAIN");
+
+    let Command::Explain { query, analyze } = cmd else {
+        panic!("expected explain command, got {cmd:?}");
+    };
+    let Command::Query {
+        event_type,
+        group_by,
+        aggs,
+        ..
+    } = *query
+    else {
+        panic!("expected inner query");
+    };
+    assert!(!analyze);
+    assert_eq!(event_type, "orders");
+    assert_eq!(group_by, Some(vec!["region".to_string()]));
+    assert!(aggs.is_some());
+}
+
+#[test]
+fn parse_explain_analyze() {
+    let cmd = parse_command("EXPLAIN analyze QUERY orders WHERE amount > 5").expect("parse");
+
+    let Command::Explain { query, analyze } = cmd else {
+        panic!("expected explain command, got {cmd:?}");
+    };
+    assert!(analyze);
+    assert!(matches!(*query, Command::Query { .. }));
+
+    let err = explain::parse("EXPLAIN ANALYZE").unwrap_err();
+    assert!(matches!(err, ParseError::MissingArgument(_)));
+}
+
+#[test]
+fn parse_explain_is_routed_case_insensitively() {
+    let cmd = parse_command("explain QUERY orders").expect("parse EXPLAIN");
+    assert!(matches!(cmd, Command::Explain { .. }));
+}
+
+#[test]
+fn parse_explain_requires_a_query() {
+    let err = explain::parse("EXPLAIN").unwrap_err();
+    assert!(matches!(err, ParseError::MissingArgument(_)));
+
+    let err = explain::parse("EXPLAIN REPLAY FOR ctx1").unwrap_err();
+    assert!(matches!(err, ParseError::ExpectedKeyword(_, _)));
+}
@@ -0,0 +1,55 @@
+use crate::command::parser::error::ParseError;
+use crate::command::types::Command;
+
+/// Parses `GET EVENT [AT OFFSET] <id>`.
+///
+/// Works on the raw input rather than tokens: ids use all 64 bits and would lose
+/// precision as a tokenized number.
+pub fn parse(input: &str) -> Result<Command, ParseError> {
+    let mut words = input.split_whitespace();
+
+    match words.next() {
+        Some(word) if word.eq_ignore_ascii_case("GET") => {}
+        Some(word) => return Err(ParseError::UnexpectedToken(word.to_string())),
+        None => return Err(ParseError::MissingArgument("GET".into())),
+    }
+
+    match words.next() {
+        Some(word) if word.eq_ignore_ascii_case("EVENT") => {}
+        Some(word) => {
+            return Err(ParseError::ExpectedKeyword(
+                "EVENT".into(),
+                word.to_string(),
+            ));
+        }
+        None => return Err(ParseError::MissingArgument("EVENT".into())),
+    }
+
+    let mut word = words.next();
+    if word.is_some_and(|w| w.eq_ignore_ascii_case("AT")) {
+        match words.next() {
+            Some(w) if w.eq_ignore_ascii_case("OFFSET") => {}
+            Some(w) => return Err(ParseError::ExpectedKeyword("OFFSET".into(), w.to_string())),
+            None => return Err(ParseError::MissingArgument("OFFSET".into())),
+        }
+        word = words.next();
+    }
+
+    let id = match word {
+        Some(raw) => raw
+            .parse::<u64>()
+            .ok()
+            .filter(|id| *id > 0)
+            .ok_or_else(|| ParseError::UnexpectedToken(format!("Invalid event id '{}'", raw)))?,
+        None => return Err(ParseError::MissingArgument("event id".into())),
+    };
+
+    if let Some(extra) = words.next() {
+        return Err(ParseError::UnexpectedToken(format!(
+            "Extra tokens after GET EVENT command: {}",
+            extra
+        )));
+    }
+
+    Ok(Command::GetEvent { id })
+}
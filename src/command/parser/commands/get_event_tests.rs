@@ -0,0 +1,34 @@
+use crate::command::parser::commands::get_event;
+use crate::command::parser::parse_command;
+use crate::command::types::Command;
+
+#[test]
+fn test_parse_get_event_by_id() {
+    let command = get_event::parse("GET EVENT 123456789").expect("parse");
+    assert_eq!(command, Command::GetEvent { id: 123456789 });
+}
+
+#[test]
+fn test_parse_get_event_at_offset_keeps_full_precision() {
+    let command = parse_command("get event at offset 18446744073709551557").expect("parse");
+    assert_eq!(
+        command,
+        Command::GetEvent {
+            id: 18446744073709551557
+        }
+    );
+}
+
+#[test]
+fn test_parse_get_event_rejects_invalid_ids() {
+    assert!(get_event::parse("GET EVENT").is_err());
+    assert!(get_event::parse("GET EVENT 0").is_err());
+    assert!(get_event::parse("GET EVENT -5").is_err());
+    assert!(get_event::parse("GET EVENT abc").is_err());
+    assert!(get_event::parse("GET EVENT AT 12").is_err());
+}
+
+#[test]
+fn test_parse_get_event_rejects_trailing_tokens() {
+    assert!(get_event::parse("GET EVENT 12 extra").is_err());
+}
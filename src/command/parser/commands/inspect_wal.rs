@@ -0,0 +1,62 @@
+use crate::command::parser::commands::inspect_zone::{expect_id, expect_keyword, expect_word};
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+/// Entries returned when no LIMIT is given.
+pub const DEFAULT_LIMIT: usize = 100;
+/// Largest LIMIT accepted, so one command cannot decode a whole WAL.
+pub const MAX_LIMIT: usize = 10_000;
+
+/// `INSPECT WAL SHARD <shard_id> [EVENT <event_type>] [LIMIT <n>]`
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    let mut iter = tokens.iter().peekable();
+
+    // INSPECT
+    match iter.next() {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("INSPECT") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("INSPECT".into())),
+    }
+
+    expect_keyword(iter.next(), "WAL")?;
+    expect_keyword(iter.next(), "SHARD")?;
+    let shard_id = expect_id(iter.next(), "shard")?;
+
+    let mut event_type = None;
+    if matches!(iter.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case("EVENT")) {
+        iter.next();
+        event_type = Some(expect_word(iter.next(), "event_type")?);
+    }
+
+    let mut limit = DEFAULT_LIMIT;
+    if matches!(iter.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case("LIMIT")) {
+        iter.next();
+        let n = match iter.next() {
+            Some(Token::Number(n)) if n.fract() == 0.0 && *n >= 1.0 && *n <= MAX_LIMIT as f64 => {
+                *n as usize
+            }
+            Some(tok) => {
+                return Err(ParseError::UnexpectedToken(format!(
+                    "LIMIT must be between 1 and {}, got {:?}",
+                    MAX_LIMIT, tok
+                )));
+            }
+            None => return Err(ParseError::MissingArgument("limit".into())),
+        };
+        limit = n;
+    }
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after INSPECT WAL command".to_string(),
+        ));
+    }
+
+    Ok(Command::InspectWal {
+        shard_id: usize::try_from(shard_id)
+            .map_err(|_| ParseError::UnexpectedToken(format!("Invalid shard id '{}'", shard_id)))?,
+        event_type,
+        limit,
+    })
+}
@@ -0,0 +1,59 @@
+use crate::command::parser::commands::inspect_wal::{self, DEFAULT_LIMIT};
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[test]
+fn test_parse_inspect_wal_defaults_to_every_event_type() {
+    let command = inspect_wal::parse(&tokenize("inspect wal shard 2"))
+        .expect("Failed to parse INSPECT WAL command");
+    assert_eq!(
+        command,
+        Command::InspectWal {
+            shard_id: 2,
+            event_type: None,
+            limit: DEFAULT_LIMIT,
+        }
+    );
+}
+
+#[test]
+fn test_parse_inspect_wal_with_event_type_and_limit() {
+    let command = inspect_wal::parse(&tokenize("INSPECT WAL SHARD 0 EVENT order_created LIMIT 5"))
+        .expect("Failed to parse INSPECT WAL command");
+    assert_eq!(
+        command,
+        Command::InspectWal {
+            shard_id: 0,
+            event_type: Some("order_created".to_string()),
+            limit: 5,
+        }
+    );
+}
+
+#[test]
+fn test_parse_inspect_wal_rejects_unbounded_limits() {
+    for input in [
+        "INSPECT WAL SHARD 0 LIMIT 0",
+        "INSPECT WAL SHARD 0 LIMIT 10001",
+        "INSPECT WAL SHARD 0 LIMIT 2.5",
+        "INSPECT WAL SHARD 0 LIMIT all",
+    ] {
+        let result = inspect_wal::parse(&tokenize(input));
+        assert!(
+            matches!(result, Err(ParseError::UnexpectedToken(_))),
+            "{}: {:?}",
+            input,
+            result
+        );
+    }
+}
+
+#[test]
+fn test_parse_inspect_wal_requires_a_shard() {
+    let result = inspect_wal::parse(&tokenize("INSPECT WAL EVENT order"));
+    assert!(matches!(result, Err(ParseError::ExpectedKeyword(ref kw, _)) if kw == "SHARD"));
+
+    let result = inspect_wal::parse(&tokenize("INSPECT WAL SHARD 0 LIMIT 5 EVENT order"));
+    assert!(matches!(result, Err(ParseError::UnexpectedToken(_))));
+}
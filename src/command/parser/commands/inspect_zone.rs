@@ -0,0 +1,75 @@
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+/// `INSPECT ZONE <zone_id> SEGMENT <segment_id> SHARD <shard_id> EVENT <event_type> FIELD <field>`
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    let mut iter = tokens.iter().peekable();
+
+    // INSPECT
+    match iter.next() {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("INSPECT") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("INSPECT".into())),
+    }
+
+    expect_keyword(iter.next(), "ZONE")?;
+    let zone_id = expect_id(iter.next(), "zone")?;
+    expect_keyword(iter.next(), "SEGMENT")?;
+    let segment_id = expect_id(iter.next(), "segment")?;
+    expect_keyword(iter.next(), "SHARD")?;
+    let shard_id = expect_id(iter.next(), "shard")?;
+    expect_keyword(iter.next(), "EVENT")?;
+    let event_type = expect_word(iter.next(), "event_type")?;
+    expect_keyword(iter.next(), "FIELD")?;
+    let field = expect_word(iter.next(), "field")?;
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after INSPECT ZONE command".to_string(),
+        ));
+    }
+
+    Ok(Command::InspectZone {
+        event_type,
+        field,
+        shard_id: usize::try_from(shard_id)
+            .map_err(|_| ParseError::UnexpectedToken(format!("Invalid shard id '{}'", shard_id)))?,
+        segment_id,
+        zone_id: u32::try_from(zone_id)
+            .map_err(|_| ParseError::UnexpectedToken(format!("Invalid zone id '{}'", zone_id)))?,
+    })
+}
+
+pub(crate) fn expect_keyword(token: Option<&Token>, keyword: &str) -> Result<(), ParseError> {
+    match token {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => Ok(()),
+        Some(tok) => Err(ParseError::ExpectedKeyword(
+            keyword.into(),
+            format!("{:?}", tok),
+        )),
+        None => Err(ParseError::MissingArgument(keyword.into())),
+    }
+}
+
+/// Non-negative integer id; segment ids may be written zero-padded (`00007`).
+pub(crate) fn expect_id(token: Option<&Token>, name: &str) -> Result<u64, ParseError> {
+    match token {
+        Some(Token::Number(n)) if *n >= 0.0 && n.fract() == 0.0 && *n <= u64::MAX as f64 => {
+            Ok(*n as u64)
+        }
+        Some(tok) => Err(ParseError::UnexpectedToken(format!(
+            "Invalid {} id '{:?}'",
+            name, tok
+        ))),
+        None => Err(ParseError::MissingArgument(format!("{} id", name))),
+    }
+}
+
+pub(crate) fn expect_word(token: Option<&Token>, name: &str) -> Result<String, ParseError> {
+    match token {
+        Some(Token::Word(word)) => Ok(word.clone()),
+        Some(tok) => Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => Err(ParseError::MissingArgument(name.into())),
+    }
+}
@@ -0,0 +1,74 @@
+use crate::command::parser::commands::inspect_zone;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[test]
+fn test_parse_inspect_zone_with_padded_segment_id() {
+    let command = inspect_zone::parse(&tokenize(
+        "inspect zone 3 SEGMENT 00007 shard 1 EVENT order_created FIELD amount",
+    ))
+    .expect("Failed to parse INSPECT ZONE command");
+    assert_eq!(
+        command,
+        Command::InspectZone {
+            event_type: "order_created".to_string(),
+            field: "amount".to_string(),
+            shard_id: 1,
+            segment_id: 7,
+            zone_id: 3,
+        }
+    );
+}
+
+#[test]
+fn test_parse_inspect_zone_missing_field() {
+    let result = inspect_zone::parse(&tokenize(
+        "INSPECT ZONE 3 SEGMENT 7 SHARD 0 EVENT order FIELD",
+    ));
+    assert!(matches!(result, Err(ParseError::MissingArgument(ref arg)) if arg == "field"));
+}
+
+#[test]
+fn test_parse_inspect_zone_rejects_non_integer_ids() {
+    for input in [
+        "INSPECT ZONE -1 SEGMENT 7 SHARD 0 EVENT order FIELD amount",
+        "INSPECT ZONE 1.5 SEGMENT 7 SHARD 0 EVENT order FIELD amount",
+        "INSPECT ZONE 1 SEGMENT latest SHARD 0 EVENT order FIELD amount",
+    ] {
+        let result = inspect_zone::parse(&tokenize(input));
+        assert!(
+            matches!(result, Err(ParseError::UnexpectedToken(_))),
+            "{}: {:?}",
+            input,
+            result
+        );
+    }
+}
+
+#[test]
+fn test_parse_inspect_zone_rejects_out_of_order_and_extra_tokens() {
+    let result = inspect_zone::parse(&tokenize(
+        "INSPECT ZONE 1 SHARD 0 SEGMENT 7 EVENT order FIELD amount",
+    ));
+    assert!(matches!(result, Err(ParseError::ExpectedKeyword(ref kw, _)) if kw == "SEGMENT"));
+
+    let result = inspect_zone::parse(&tokenize(
+        "INSPECT ZONE 1 SEGMENT 7 SHARD 0 EVENT order FIELD amount now",
+    ));
+    assert!(matches!(result, Err(ParseError::UnexpectedToken(_))));
+}
+
+#[test]
+fn test_parse_command_routes_inspect_zone() {
+    use crate::command::parser::command::parse_command;
+
+    assert!(matches!(
+        parse_command("INSPECT ZONE 0 SEGMENT 1 SHARD 0 EVENT order FIELD context_id").unwrap(),
+        Command::InspectZone {
+            zone_id: 0,
+            segment_id: 1,
+            ..
+        }
+    ));
+}
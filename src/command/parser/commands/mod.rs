@@ -1,19 +1,33 @@
 pub mod batch;
 pub mod create_user;
 pub mod define;
+pub mod explain;
 pub mod flush;
+pub mod get_event;
 pub mod grant_permission;
+pub mod inspect_wal;
+pub mod inspect_zone;
 pub mod list_users;
 pub mod ping;
 pub mod plotql;
 pub mod query;
+pub mod rebuild_indexes;
 pub mod remember;
 pub mod replay;
 pub mod revoke_key;
 pub mod revoke_permission;
+pub mod rotate_key;
+pub mod schema_catalog;
 pub mod show;
+pub mod show_indexes;
 pub mod show_permissions;
+pub mod show_pinned_segments;
+pub mod show_stats;
+pub mod show_usage;
+pub mod show_users;
 pub mod store;
+pub mod user_state;
+pub mod verify_materialized;
 
 #[cfg(test)]
 mod batch_tests;
@@ -22,16 +36,26 @@ mod create_user_tests;
 #[cfg(test)]
 mod define_tests;
 #[cfg(test)]
+mod explain_tests;
+#[cfg(test)]
 mod flush_tests;
 #[cfg(test)]
+mod get_event_tests;
+#[cfg(test)]
 mod grant_permission_tests;
 #[cfg(test)]
+mod inspect_wal_tests;
+#[cfg(test)]
+mod inspect_zone_tests;
+#[cfg(test)]
 mod list_users_tests;
 #[cfg(test)]
 mod plotql_tests;
 #[cfg(test)]
 mod query_tests;
 #[cfg(test)]
+mod rebuild_indexes_tests;
+#[cfg(test)]
 mod remember_tests;
 #[cfg(test)]
 mod replay_tests;
@@ -40,8 +64,26 @@ mod revoke_key_tests;
 #[cfg(test)]
 mod revoke_permission_tests;
 #[cfg(test)]
+mod rotate_key_tests;
+#[cfg(test)]
+mod schema_catalog_tests;
+#[cfg(test)]
+mod show_indexes_tests;
+#[cfg(test)]
 mod show_permissions_tests;
 #[cfg(test)]
+mod show_pinned_segments_tests;
+#[cfg(test)]
+mod show_stats_tests;
+#[cfg(test)]
 mod show_tests;
 #[cfg(test)]
+mod show_usage_tests;
+#[cfg(test)]
+mod show_users_tests;
+#[cfg(test)]
 mod store_tests;
+#[cfg(test)]
+mod user_state_tests;
+#[cfg(test)]
+mod verify_materialized_tests;
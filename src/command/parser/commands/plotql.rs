@@ -573,6 +573,14 @@ impl PlotQueryParts {
             time_bucket,
             group_by: breakdown,
             event_sequence,
+            latest_per: None,
+            dedup: None,
+            omit_nulls: false,
+            with_total: false,
+            raw_strings: false,
+            disabled_pruners: Vec::new(),
+            allow_full_scan: false,
+            unnest: None,
         }
     }
 
@@ -603,6 +611,7 @@ impl PlotQueryParts {
                     field: None,
                 },
                 links,
+                within: None,
             };
 
             (event_type, Some(event_sequence))
@@ -1,8 +1,10 @@
 use crate::command::parser::error::ParseError;
 use crate::command::types::{
-    AggSpec, Command, CompareOp, EventSequence, EventTarget, Expr, OrderSpec, SequenceLink,
-    TimeGranularity,
+    AggSpec, CalendarUnit, Command, CompareOp, DedupKeep, DedupSpec, EventSequence, EventTarget,
+    Expr, OrderSpec, PrunerKind, QueryCommand, SequenceLink, TimeGranularity, UnnestEmpty,
+    UnnestNonArray, UnnestSpec,
 };
+use chrono_tz::Tz;
 use serde_json::{Number, Value};
 
 peg::parser! {
@@ -24,12 +26,31 @@ peg::parser! {
         // ==========
 
         pub rule query() -> Command
-            = _ query_kw() _ head:event_sequence() _ clauses:( _ c:clause() { c } )* _ {
-                build_command(head, clauses)
+            = _ query_kw() _ hint:( h:allow_full_scan_hint() _ { h } )? head:event_sequence() _
+              clauses:( _ c:clause() { c } )* _ {?
+                build_command(head, hint.into_iter().chain(clauses).collect())
             }
 
         rule query_kw() = ci("QUERY") / ci("FIND")
 
+        // A plain query, or sub-queries joined by UNION ALL. Trailing ORDER BY /
+        // LIMIT / OFFSET apply to the unioned result (on a bare last sub-query
+        // they are hoisted by `build_statement`).
+        pub rule statement() -> Command
+            = first:sub_query() rest:( _ ci("UNION") _ ci("ALL") _ q:sub_query() { q } )*
+              trailing:( _ c:union_clause() { c } )* _ {?
+                build_statement(first, rest, trailing)
+            }
+
+        rule sub_query() -> SubQuery
+            = _ "(" _ q:query() _ ")" { SubQuery { command: q, parenthesized: true } }
+            / q:query() { SubQuery { command: q, parenthesized: false } }
+
+        rule union_clause() -> Clause
+            = limit_clause()
+            / offset_clause()
+            / order_clause()
+
         // ==========
         // EVENT SEQUENCE
         // ==========
@@ -43,6 +64,7 @@ peg::parser! {
                 EventSequence {
                     head: EventTarget { event: head.to_string(), field: None },
                     links,
+                    within: None,
                 }
             }
 
@@ -59,20 +81,31 @@ peg::parser! {
             / since_clause()
             / return_clause()
             / linked_clause()
+            / within_clause()
             / where_clause()
             / using_time_clause()
             / using_clause()
             / agg_clause()
+            / latest_clause()
+            / dedup_clause()
+            / unnest_clause()
             / time_clause()
+            / bucket_clause()
             / group_clause()
             / limit_clause()
             / offset_clause()
             / order_clause()
+            / omit_nulls_clause()
+            / with_total_clause()
+            / raw_strings_clause()
+            / disable_pruners_clause()
+            / allow_full_scan_hint()
 
         rule clause_start()
-            = ci("PER") / ci("BY") / ci("USING") / ci("SINCE") / ci("LIMIT") / ci("OFFSET") / (ci("ORDER") _ ci("BY"))
-            / ci("RETURN") / ci("LINKED") / ci("WHERE") / ci("FOR")
-            / ci("FOLLOWED") / ci("PRECEDED")
+            = ci("PER") / ci("BY") / ci("USING") / ci("SINCE") / ci("LIMIT") / ci("OFFSET") / (ci("ORDER") _ ci("BY")) / (ci("BUCKET") _ ci("BY"))
+            / ci("RETURN") / ci("LINKED") / ci("WITHIN") / ci("WHERE") / ci("FOR")
+            / ci("FOLLOWED") / ci("PRECEDED") / ci("LATEST") / ci("DEDUP") / ci("OMIT") / (ci("WITH") _ ci("TOTAL"))
+            / (ci("RAW") _ ci("STRINGS")) / ci("DISABLE") / ci("UNNEST")
 
         rule for_clause() -> Clause
             = ci("FOR") _ id:(ident() / string_literal()) {
@@ -98,6 +131,12 @@ peg::parser! {
                 Clause::Link(id.to_string())
             }
 
+        // Longest gap between consecutive events of a sequence, edge included
+        rule within_clause() -> Clause
+            = ci("WITHIN") _ seconds:interval() {
+                Clause::Within(seconds)
+            }
+
         rule where_clause() -> Clause
             = ci("WHERE") _ e:expr() {
                 Clause::Where(e)
@@ -123,13 +162,19 @@ peg::parser! {
             }
 
         rule agg_spec() -> AggSpec
-            = ci("COUNT") _ ci("UNIQUE") _ !(clause_start()) fld:field() {
+            = ci("COUNT") _ "(" _ ci("DISTINCT") _ fld:field() _ ")" {
+                AggSpec::CountDistinct { field: fld }
+            }
+            / ci("COUNT") _ ci("UNIQUE") _ !(clause_start()) fld:field() {
                 AggSpec::Count { unique_field: Some(fld) }
             }
             / ci("COUNT") _ !(clause_start()) fld:field() {
                 AggSpec::CountField { field: fld }
             }
             / ci("COUNT") { AggSpec::Count { unique_field: None } }
+            / ci("PERCENTILE") _ "(" _ fld:field() _ "," _ p:percentile() _ ")" {
+                AggSpec::Percentile { field: fld, percentile: p }
+            }
             / ci("TOTAL") _ !(clause_start()) fld:field() {
                 AggSpec::Total { field: fld }
             }
@@ -147,6 +192,44 @@ peg::parser! {
         // TIME & GROUPING
         // ==========
 
+        rule latest_clause() -> Clause
+            = ci("LATEST") _ ci("PER") _ fld:field() {
+                Clause::LatestPer(fld)
+            }
+
+        rule dedup_clause() -> Clause
+            = ci("DEDUP") _ ci("BY") _ keys:(field() ++ (_ "," _)) _ ci("KEEP") _ keep:(
+                  ci("FIRST")  { DedupKeep::First }
+                / ci("LATEST") { DedupKeep::Latest }
+              ) {
+                Clause::Dedup(DedupSpec { keys, keep })
+            }
+
+        // One row per element of a JSON array field; the ON EMPTY and
+        // ON NON ARRAY options may come in either order
+        rule unnest_clause() -> Clause
+            = ci("UNNEST") _ "(" _ fld:field() _ ")"
+              options:( _ o:unnest_option() { o } )* {
+                let mut spec = UnnestSpec::new(fld);
+                for option in options {
+                    match option {
+                        UnnestOption::Empty(on_empty) => spec.on_empty = on_empty,
+                        UnnestOption::NonArray(on_non_array) => spec.on_non_array = on_non_array,
+                    }
+                }
+                Clause::Unnest(spec)
+            }
+
+        rule unnest_option() -> UnnestOption
+            = ci("ON") _ ci("EMPTY") _ e:(
+                  ci("SKIP") { UnnestEmpty::Skip }
+                / ci("NULL") { UnnestEmpty::NullRow }
+              ) { UnnestOption::Empty(e) }
+            / ci("ON") _ ci("NON") _ ci("ARRAY") _ n:(
+                  ci("ERROR")       { UnnestNonArray::Error }
+                / ci("PASSTHROUGH") { UnnestNonArray::Passthrough }
+              ) { UnnestOption::NonArray(n) }
+
         rule time_clause() -> Clause
             = ci("PER") _ tg:(
                   ci("HOUR")  { TimeGranularity::Hour }
@@ -154,11 +237,36 @@ peg::parser! {
                 / ci("WEEK")  { TimeGranularity::Week }
                 / ci("MONTH") { TimeGranularity::Month }
                 / ci("YEAR")  { TimeGranularity::Year }
+                / seconds:interval()
+                  origin:( _ ci("ORIGIN") _ o:$(['0'..='9']+) {? o.parse::<u64>().or(Err("origin in epoch seconds")) } )?
+                  fill:( _ ci("FILL") )? {
+                    TimeGranularity::Interval { seconds, origin: origin.unwrap_or(0), fill: fill.is_some() }
+                }
               )
               _ using:(ci("USING") _ f:field() { f })? {
                 Clause::Time(tg, using)
             }
 
+        rule bucket_clause() -> Clause
+            = ci("BUCKET") _ ci("BY") _ unit:(
+                  ci("DAY")   { CalendarUnit::Day }
+                / ci("WEEK")  { CalendarUnit::Week }
+                / ci("MONTH") { CalendarUnit::Month }
+                / ci("YEAR")  { CalendarUnit::Year }
+              )
+              timezone:( _ ci("TZ") _ z:timezone() { z } )?
+              _ using:(ci("USING") _ f:field() { f })? {
+                Clause::Time(TimeGranularity::Calendar { unit, timezone }, using)
+            }
+
+        // IANA zone name, in single or double quotes
+        rule timezone() -> String
+            = name:( "'" z:$((!"'" [_])*) "'" { z } / string_literal() ) {?
+                name.parse::<Tz>()
+                    .map(|_| name.to_string())
+                    .or(Err("time zone name such as 'Europe/Amsterdam'"))
+            }
+
         rule group_clause() -> Clause
             = ci("BY") _ first:field()
               rest:( _ "," _ f:field() { f } )*
@@ -185,6 +293,37 @@ peg::parser! {
                 Clause::Order(f, desc)
             }
 
+        rule omit_nulls_clause() -> Clause
+            = ci("OMIT") _ ci("NULLS") {
+                Clause::OmitNulls
+            }
+
+        rule with_total_clause() -> Clause
+            = ci("WITH") _ ci("TOTAL") {
+                Clause::WithTotal
+            }
+
+        rule raw_strings_clause() -> Clause
+            = ci("RAW") _ ci("STRINGS") {
+                Clause::RawStrings
+            }
+
+        // Diagnostic hint: run the query with these zone pruners switched off
+        rule disable_pruners_clause() -> Clause
+            = ci("DISABLE") _ (ci("PRUNERS") / ci("PRUNER")) _ kinds:( pruner_kind() ++ (_ "," _) ) {
+                Clause::DisablePruners(kinds)
+            }
+
+        // Lets a query past `max_candidate_zones` when the limit is set to
+        // require a hint; accepted after QUERY or among the clauses
+        rule allow_full_scan_hint() -> Clause
+            = "/*" _ name:$(['a'..='z' | 'A'..='Z' | '_']+) _ "*/" {?
+                if eq_ci(name, "allow_full_scan") { Ok(Clause::AllowFullScan) } else { Err("allow_full_scan hint") }
+            }
+
+        rule pruner_kind() -> PrunerKind
+            = name:ident() {? PrunerKind::parse(name).ok_or("pruner name") }
+
         // ==========
         // EXPRESSIONS
         // ==========
@@ -258,6 +397,20 @@ peg::parser! {
                 }
             }
 
+        rule percentile() -> f64
+            = n:$( ['0'..='9']+ ( "." ['0'..='9']+ )? ) {?
+                n.parse::<f64>()
+                    .ok()
+                    .filter(|p| (0.0..=100.0).contains(p))
+                    .ok_or("percentile between 0 and 100")
+            }
+
+        // Fixed bucket width such as `15m`, `1h` or `7d`, in seconds
+        rule interval() -> u64
+            = n:$(['0'..='9']+) unit:$(['a'..='z' | 'A'..='Z']+) {?
+                interval_seconds(n, unit).ok_or("interval like 30s, 15m, 1h, 7d or 2w")
+            }
+
         rule field() -> String
             = i:ident() "." j:ident() { format!("{}.{}", i, j) }
             / i:ident() { i.to_string() }
@@ -285,6 +438,24 @@ fn eq_ci(a: &str, b: &str) -> bool {
     a.eq_ignore_ascii_case(b)
 }
 
+/// Seconds in `count` of `unit` (s, m, h, d or w); `None` for a zero width,
+/// an unknown unit or an overflow.
+fn interval_seconds(count: &str, unit: &str) -> Option<u64> {
+    let unit_seconds = match unit.to_ascii_lowercase().as_str() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => return None,
+    };
+    count
+        .parse::<u64>()
+        .ok()
+        .filter(|n| *n > 0)
+        .and_then(|n| n.checked_mul(unit_seconds))
+}
+
 // =========
 // STATE/BUILDERS
 // =========
@@ -295,6 +466,7 @@ struct QueryParts {
     since: Option<String>,
     return_fields: Option<Vec<String>>,
     link_field: Option<String>,
+    within: Option<u64>,
     where_clause: Option<Expr>,
     using_field: Option<String>,
     sequence_time_field: Option<String>,
@@ -304,6 +476,14 @@ struct QueryParts {
     limit: Option<u32>,
     offset: Option<u32>,
     order_by: Option<OrderSpec>,
+    latest_per: Option<String>,
+    dedup: Option<DedupSpec>,
+    unnest: Option<UnnestSpec>,
+    omit_nulls: bool,
+    with_total: bool,
+    raw_strings: bool,
+    disabled_pruners: Vec<PrunerKind>,
+    allow_full_scan: bool,
 }
 
 impl QueryParts {
@@ -313,6 +493,7 @@ impl QueryParts {
             Clause::Since(v) => self.since = Some(v),
             Clause::Return(v) => self.return_fields = Some(v),
             Clause::Link(v) => self.link_field = Some(v),
+            Clause::Within(seconds) => self.within = Some(seconds),
             Clause::Where(e) => self.where_clause = Some(e),
             Clause::Using(f) => self.using_field = Some(f),
             Clause::UsingTime(f) => self.sequence_time_field = Some(f),
@@ -332,6 +513,20 @@ impl QueryParts {
             Clause::Limit(n) => self.limit = Some(n),
             Clause::Offset(n) => self.offset = Some(n),
             Clause::Order(f, desc) => self.order_by = Some(OrderSpec { field: f, desc }),
+            Clause::LatestPer(f) => self.latest_per = Some(f),
+            Clause::Dedup(spec) => self.dedup = Some(spec),
+            Clause::Unnest(spec) => self.unnest = Some(spec),
+            Clause::OmitNulls => self.omit_nulls = true,
+            Clause::WithTotal => self.with_total = true,
+            Clause::RawStrings => self.raw_strings = true,
+            Clause::DisablePruners(kinds) => {
+                for kind in kinds {
+                    if !self.disabled_pruners.contains(&kind) {
+                        self.disabled_pruners.push(kind);
+                    }
+                }
+            }
+            Clause::AllowFullScan => self.allow_full_scan = true,
         }
     }
 
@@ -353,11 +548,19 @@ impl QueryParts {
             time_bucket: self.time_bucket,
             group_by: self.group_by,
             event_sequence,
+            latest_per: self.latest_per,
+            dedup: self.dedup,
+            omit_nulls: self.omit_nulls,
+            with_total: self.with_total,
+            raw_strings: self.raw_strings,
+            disabled_pruners: self.disabled_pruners,
+            allow_full_scan: self.allow_full_scan,
+            unnest: self.unnest,
         }
     }
 }
 
-fn build_command(head: EventSequence, clauses: Vec<Clause>) -> Command {
+fn build_command(mut head: EventSequence, clauses: Vec<Clause>) -> Result<Command, &'static str> {
     let mut parts = QueryParts::default();
     for c in clauses {
         parts.apply_clause(c);
@@ -365,12 +568,60 @@ fn build_command(head: EventSequence, clauses: Vec<Clause>) -> Command {
 
     let event_type = head.head.event.clone();
     let event_sequence = if head.links.is_empty() {
+        if parts.within.is_some() {
+            return Err("WITHIN only applies to FOLLOWED BY or PRECEDED BY sequences");
+        }
         None
     } else {
+        head.within = parts.within.take();
         Some(head)
     };
 
-    parts.into_command(event_type, event_sequence)
+    Ok(parts.into_command(event_type, event_sequence))
+}
+
+struct SubQuery {
+    command: Command,
+    parenthesized: bool,
+}
+
+fn build_statement(
+    first: SubQuery,
+    rest: Vec<SubQuery>,
+    trailing: Vec<Clause>,
+) -> Result<Command, &'static str> {
+    if rest.is_empty() {
+        if first.parenthesized || !trailing.is_empty() {
+            return Err("UNION ALL");
+        }
+        return Ok(first.command);
+    }
+
+    let mut queries: Vec<QueryCommand> = Vec::with_capacity(rest.len() + 1);
+    let mut hoisted = QueryParts::default();
+    let last = rest.len();
+    for (idx, sub) in std::iter::once(first).chain(rest).enumerate() {
+        let mut query = QueryCommand::from(&sub.command);
+        // As in SQL, ORDER BY / LIMIT / OFFSET after an unparenthesized last
+        // sub-query belong to the union, not to that sub-query.
+        if idx == last && !sub.parenthesized {
+            hoisted.order_by = query.order_by.take();
+            hoisted.limit = query.limit.take();
+            hoisted.offset = query.offset.take();
+        }
+        queries.push(query);
+    }
+
+    for c in trailing {
+        hoisted.apply_clause(c);
+    }
+
+    Ok(Command::Union {
+        queries,
+        order_by: hoisted.order_by,
+        limit: hoisted.limit,
+        offset: hoisted.offset,
+    })
 }
 
 #[derive(Debug)]
@@ -379,6 +630,7 @@ enum Clause {
     Since(String),
     Return(Vec<String>),
     Link(String),
+    Within(u64),
     Where(Expr),
     Using(String),
     UsingTime(String),
@@ -388,10 +640,23 @@ enum Clause {
     Limit(u32),
     Offset(u32),
     Order(String, bool),
+    LatestPer(String),
+    Dedup(DedupSpec),
+    Unnest(UnnestSpec),
+    OmitNulls,
+    WithTotal,
+    RawStrings,
+    DisablePruners(Vec<PrunerKind>),
+    AllowFullScan,
+}
+
+enum UnnestOption {
+    Empty(UnnestEmpty),
+    NonArray(UnnestNonArray),
 }
 
 pub fn parse(input: &str) -> Result<Command, ParseError> {
-    sneldb_query::query(input).map_err(map_peg_error)
+    sneldb_query::statement(input).map_err(map_peg_error)
 }
 
 fn map_peg_error(e: peg::error::ParseError<peg::str::LineCol>) -> ParseError {
@@ -1,6 +1,8 @@
 use crate::command::parser::commands::query::parse as parse_query_peg;
 use crate::command::types::{
-    AggSpec, Command, CompareOp, EventSequence, EventTarget, Expr, SequenceLink, TimeGranularity,
+    AggSpec, CalendarUnit, Command, CompareOp, DedupKeep, DedupSpec, EventSequence, EventTarget,
+    Expr, OrderSpec, PrunerKind, SequenceLink, TimeGranularity, UnnestEmpty, UnnestNonArray,
+    UnnestSpec,
 };
 use serde_json::Value;
 
@@ -39,6 +41,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -70,6 +80,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -98,6 +116,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -140,7 +166,16 @@ mod query_peg_tests {
                             field: None,
                         }
                     )],
+                    within: None,
                 }),
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -180,7 +215,16 @@ mod query_peg_tests {
                             field: None,
                         }
                     )],
+                    within: None,
                 }),
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -212,6 +256,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -247,6 +299,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -286,6 +346,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -321,6 +389,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -365,6 +441,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -411,6 +495,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -462,6 +554,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -502,6 +602,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -552,6 +660,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -605,6 +721,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -640,6 +764,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -647,6 +779,157 @@ mod query_peg_tests {
     // ─────────────────────────────
     // 7. Aggregations
     // ─────────────────────────────
+    #[test]
+    fn test_parse_query_count_distinct() {
+        let command = parse(r#"QUERY page_view COUNT(DISTINCT user_id), COUNT BY country"#);
+        let Command::Query { aggs, group_by, .. } = command else {
+            panic!("expected a query");
+        };
+        assert_eq!(
+            aggs,
+            Some(vec![
+                AggSpec::CountDistinct {
+                    field: "user_id".to_string()
+                },
+                AggSpec::Count { unique_field: None },
+            ])
+        );
+        assert_eq!(group_by, Some(vec!["country".to_string()]));
+
+        let spaced = parse(r#"QUERY page_view count ( distinct user_id )"#);
+        let Command::Query { aggs, .. } = spaced else {
+            panic!("expected a query");
+        };
+        assert_eq!(
+            aggs,
+            Some(vec![AggSpec::CountDistinct {
+                field: "user_id".to_string()
+            }])
+        );
+        assert!(parse_query_peg(r#"QUERY page_view COUNT(DISTINCT user_id"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_query_percentile() {
+        let command = parse(
+            r#"QUERY request PERCENTILE(latency_ms, 95), percentile(latency_ms, 99.9) BY route"#,
+        );
+        let Command::Query { aggs, .. } = command else {
+            panic!("expected a query");
+        };
+        assert_eq!(
+            aggs,
+            Some(vec![
+                AggSpec::Percentile {
+                    field: "latency_ms".to_string(),
+                    percentile: 95.0
+                },
+                AggSpec::Percentile {
+                    field: "latency_ms".to_string(),
+                    percentile: 99.9
+                },
+            ])
+        );
+        assert!(parse_query_peg(r#"QUERY request PERCENTILE(latency_ms, 101)"#).is_err());
+        assert!(parse_query_peg(r#"QUERY request PERCENTILE(latency_ms)"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_query_interval_buckets() {
+        let command = parse(r#"QUERY request COUNT PER 15m"#);
+        let Command::Query { time_bucket, .. } = command else {
+            panic!("expected a query");
+        };
+        assert_eq!(
+            time_bucket,
+            Some(TimeGranularity::Interval {
+                seconds: 900,
+                origin: 0,
+                fill: false
+            })
+        );
+
+        let command =
+            parse(r#"QUERY request COUNT PER 1H ORIGIN 1800 FILL USING created_at BY route"#);
+        let Command::Query {
+            time_bucket,
+            time_field,
+            group_by,
+            ..
+        } = command
+        else {
+            panic!("expected a query");
+        };
+        assert_eq!(
+            time_bucket,
+            Some(TimeGranularity::Interval {
+                seconds: 3_600,
+                origin: 1_800,
+                fill: true
+            })
+        );
+        assert_eq!(time_field, Some("created_at".to_string()));
+        assert_eq!(group_by, Some(vec!["route".to_string()]));
+
+        let Command::Query { time_bucket, .. } = parse(r#"QUERY request COUNT PER 7d"#) else {
+            panic!("expected a query");
+        };
+        assert!(matches!(
+            time_bucket,
+            Some(TimeGranularity::Interval {
+                seconds: 604_800,
+                ..
+            })
+        ));
+
+        assert!(parse_query_peg(r#"QUERY request COUNT PER 0m"#).is_err());
+        assert!(parse_query_peg(r#"QUERY request COUNT PER 5y"#).is_err());
+        assert!(parse_query_peg(r#"QUERY request COUNT PER 15"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_query_calendar_buckets() {
+        let command = parse(
+            r#"QUERY orders COUNT BUCKET BY month TZ 'Europe/Amsterdam' USING created_at BY country"#,
+        );
+        let Command::Query {
+            time_bucket,
+            time_field,
+            group_by,
+            ..
+        } = command
+        else {
+            panic!("expected a query");
+        };
+        assert_eq!(
+            time_bucket,
+            Some(TimeGranularity::Calendar {
+                unit: CalendarUnit::Month,
+                timezone: Some("Europe/Amsterdam".to_string())
+            })
+        );
+        assert_eq!(time_field, Some("created_at".to_string()));
+        assert_eq!(group_by, Some(vec!["country".to_string()]));
+
+        let Command::Query { time_bucket, .. } = parse(r#"QUERY orders COUNT BUCKET BY WEEK"#)
+        else {
+            panic!("expected a query");
+        };
+        assert_eq!(
+            time_bucket,
+            Some(TimeGranularity::Calendar {
+                unit: CalendarUnit::Week,
+                timezone: None
+            })
+        );
+
+        assert!(parse_query_peg(r#"QUERY orders COUNT BUCKET BY month TZ "Asia/Tokyo""#).is_ok());
+        assert!(
+            parse_query_peg(r#"QUERY orders COUNT BUCKET BY month TZ 'Mars/Olympus'"#).is_err()
+        );
+        assert!(parse_query_peg(r#"QUERY orders COUNT BUCKET BY fortnight"#).is_err());
+    }
+
     #[test]
     fn test_parse_query_count_unique() {
         let input = r#"QUERY order_created COUNT UNIQUE user_id"#;
@@ -673,6 +956,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -703,6 +994,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -734,6 +1033,14 @@ mod query_peg_tests {
                 time_bucket: Some(TimeGranularity::Day),
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -765,6 +1072,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: Some(vec!["country".to_string()]),
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -796,6 +1111,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -844,7 +1167,16 @@ mod query_peg_tests {
                             field: None,
                         }
                     )],
+                    within: None,
                 }),
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -960,6 +1292,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -997,6 +1337,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -1045,11 +1393,50 @@ mod query_peg_tests {
                             }
                         ),
                     ],
+                    within: None,
                 }),
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
 
+    #[test]
+    fn test_parse_query_sequence_within_window() {
+        let command =
+            parse(r#"QUERY page_view FOLLOWED BY order_created WITHIN 30m LINKED BY user_id"#);
+        let Command::Query {
+            event_sequence,
+            link_field,
+            ..
+        } = command
+        else {
+            panic!("expected a query");
+        };
+        assert_eq!(event_sequence.unwrap().within, Some(1_800));
+        assert_eq!(link_field, Some("user_id".to_string()));
+
+        // Clause order is free, and chains share the window
+        let Command::Query { event_sequence, .. } =
+            parse(r#"QUERY a FOLLOWED BY b PRECEDED BY c LINKED BY id WITHIN 2h"#)
+        else {
+            panic!("expected a query");
+        };
+        let event_sequence = event_sequence.unwrap();
+        assert_eq!(event_sequence.links.len(), 2);
+        assert_eq!(event_sequence.within, Some(7_200));
+
+        assert!(parse_query_peg(r#"QUERY page_view WITHIN 30m"#).is_err());
+        assert!(parse_query_peg(r#"QUERY a FOLLOWED BY b WITHIN 0s LINKED BY user_id"#).is_err());
+        assert!(parse_query_peg(r#"QUERY a FOLLOWED BY b WITHIN LINKED BY user_id"#).is_err());
+    }
+
     #[test]
     fn test_parse_query_return_with_string_literals() {
         let input = r#"QUERY order_created RETURN ["id", "total"]"#;
@@ -1074,6 +1461,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -1106,6 +1501,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -1145,6 +1548,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -1175,6 +1586,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -1211,6 +1630,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -1239,6 +1666,14 @@ mod query_peg_tests {
                 time_bucket: None,
                 group_by: Some(vec!["country".to_string(), "city".to_string()]),
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
@@ -1267,7 +1702,369 @@ mod query_peg_tests {
                 time_bucket: Some(TimeGranularity::Month),
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             }
         );
     }
+
+    // ─────────────────────────────
+    // 14. UNION ALL
+    // ─────────────────────────────
+    #[test]
+    fn test_parse_union_all_hoists_trailing_clauses() {
+        let input = r#"QUERY signup WHERE plan="pro" UNION ALL QUERY upgrade ORDER BY timestamp DESC LIMIT 10 OFFSET 5"#;
+        let command = parse(input);
+
+        let Command::Union {
+            queries,
+            order_by,
+            limit,
+            offset,
+        } = command
+        else {
+            panic!("expected Union, got {:?}", command);
+        };
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0].event_type, "signup");
+        assert!(queries[0].where_clause.is_some());
+        assert_eq!(queries[1].event_type, "upgrade");
+        assert_eq!(queries[1].order_by, None);
+        assert_eq!(queries[1].limit, None);
+        assert_eq!(
+            order_by,
+            Some(OrderSpec {
+                field: "timestamp".to_string(),
+                desc: true,
+            })
+        );
+        assert_eq!(limit, Some(10));
+        assert_eq!(offset, Some(5));
+    }
+
+    #[test]
+    fn test_parse_union_all_parenthesized_sub_queries_keep_their_clauses() {
+        let input = r#"(QUERY a ORDER BY amount LIMIT 3) UNION ALL (FIND b LIMIT 2) UNION ALL QUERY c LIMIT 7"#;
+        let command = parse(input);
+
+        let Command::Union {
+            queries,
+            order_by,
+            limit,
+            ..
+        } = command
+        else {
+            panic!("expected Union, got {:?}", command);
+        };
+        assert_eq!(queries.len(), 3);
+        assert_eq!(queries[0].limit, Some(3));
+        assert_eq!(queries[0].order_by.as_ref().unwrap().field, "amount");
+        assert_eq!(queries[1].limit, Some(2));
+        assert_eq!(queries[2].limit, None);
+        assert_eq!(order_by, None);
+        assert_eq!(limit, Some(7));
+
+        let trailing = parse(r#"(QUERY a) UNION ALL (QUERY b) ORDER BY timestamp LIMIT 4"#);
+        let Command::Union {
+            queries,
+            order_by,
+            limit,
+            ..
+        } = trailing
+        else {
+            panic!("expected Union");
+        };
+        assert!(queries.iter().all(|q| q.limit.is_none()));
+        assert_eq!(order_by.unwrap().field, "timestamp");
+        assert_eq!(limit, Some(4));
+    }
+
+    #[test]
+    fn test_parse_union_all_rejects_incomplete_forms() {
+        assert!(parse_query_peg(r#"QUERY a UNION ALL"#).is_err());
+        assert!(parse_query_peg(r#"QUERY a UNION QUERY b"#).is_err());
+        assert!(parse_query_peg(r#"(QUERY a)"#).is_err());
+        assert!(parse_query_peg(r#"(QUERY a UNION ALL QUERY b"#).is_err());
+    }
+
+    // ─────────────────────────────
+    // 15. LATEST PER
+    // ─────────────────────────────
+    #[test]
+    fn test_parse_latest_per_with_where_and_order() {
+        let command = parse(
+            r#"QUERY subscription_changed WHERE plan != "free" LATEST PER account_id USING TIME changed_at ORDER BY changed_at DESC LIMIT 10"#,
+        );
+        let Command::Query {
+            latest_per,
+            where_clause,
+            sequence_time_field,
+            order_by,
+            limit,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert_eq!(latest_per, Some("account_id".to_string()));
+        assert!(where_clause.is_some());
+        assert_eq!(sequence_time_field, Some("changed_at".to_string()));
+        assert!(order_by.unwrap().desc);
+        assert_eq!(limit, Some(10));
+    }
+
+    #[test]
+    fn test_parse_latest_per_does_not_clash_with_time_bucket() {
+        let command = parse(r#"QUERY orders COUNT PER day latest per user_id"#);
+        let Command::Query {
+            latest_per,
+            time_bucket,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert_eq!(latest_per, Some("user_id".to_string()));
+        assert_eq!(time_bucket, Some(TimeGranularity::Day));
+
+        assert!(parse_query_peg(r#"QUERY orders LATEST PER"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_dedup_by_keys_and_keep_policy() {
+        let command = parse(
+            r#"QUERY payment WHERE amount > 10 DEDUP BY merchant_id, idempotency_key KEEP latest LIMIT 5"#,
+        );
+        let Command::Query {
+            dedup,
+            where_clause,
+            limit,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert_eq!(
+            dedup,
+            Some(DedupSpec {
+                keys: vec!["merchant_id".to_string(), "idempotency_key".to_string()],
+                keep: DedupKeep::Latest,
+            })
+        );
+        assert!(where_clause.is_some());
+        assert_eq!(limit, Some(5));
+
+        let Command::Query { dedup, .. } = parse(r#"QUERY payment DEDUP BY key KEEP FIRST"#) else {
+            panic!("expected Query");
+        };
+        assert_eq!(dedup.unwrap().keep, DedupKeep::First);
+    }
+
+    #[test]
+    fn test_parse_dedup_by_requires_keep_policy() {
+        assert!(parse_query_peg(r#"QUERY payment DEDUP BY key"#).is_err());
+        assert!(parse_query_peg(r#"QUERY payment DEDUP BY key KEEP ANY"#).is_err());
+        assert!(parse_query_peg(r#"QUERY payment DEDUP KEEP FIRST"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_unnest_with_options() {
+        let command = parse(
+            r#"QUERY orders WHERE amount > 10 UNNEST(tags) ON EMPTY NULL ON NON ARRAY PASSTHROUGH LIMIT 5"#,
+        );
+        let Command::Query {
+            unnest,
+            where_clause,
+            limit,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert_eq!(
+            unnest,
+            Some(UnnestSpec {
+                field: "tags".to_string(),
+                on_empty: UnnestEmpty::NullRow,
+                on_non_array: UnnestNonArray::Passthrough,
+            })
+        );
+        assert!(where_clause.is_some());
+        assert_eq!(limit, Some(5));
+
+        let Command::Query { unnest, .. } =
+            parse(r#"QUERY orders UNNEST ( tags ) on non array error on empty skip"#)
+        else {
+            panic!("expected Query");
+        };
+        assert_eq!(unnest, Some(UnnestSpec::new("tags")));
+
+        let Command::Query { unnest, .. } = parse(r#"QUERY orders"#) else {
+            panic!("expected Query");
+        };
+        assert!(unnest.is_none());
+    }
+
+    #[test]
+    fn test_parse_unnest_rejects_unknown_options() {
+        assert!(parse_query_peg(r#"QUERY orders UNNEST tags"#).is_err());
+        assert!(parse_query_peg(r#"QUERY orders UNNEST(tags) ON EMPTY KEEP"#).is_err());
+        assert!(parse_query_peg(r#"QUERY orders UNNEST(tags) ON NON ARRAY SKIP"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_raw_strings() {
+        let command = parse(r#"QUERY orders WHERE raw = "x" RAW STRINGS LIMIT 5"#);
+        let Command::Query {
+            raw_strings,
+            limit,
+            where_clause,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert!(raw_strings);
+        assert_eq!(limit, Some(5));
+        assert!(where_clause.is_some());
+
+        let Command::Query { raw_strings, .. } = parse(r#"QUERY orders"#) else {
+            panic!("expected Query");
+        };
+        assert!(!raw_strings);
+    }
+
+    #[test]
+    fn test_parse_omit_nulls() {
+        let command = parse(r#"QUERY orders WHERE amount > 10 OMIT NULLS LIMIT 5"#);
+        let Command::Query {
+            omit_nulls,
+            limit,
+            where_clause,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert!(omit_nulls);
+        assert_eq!(limit, Some(5));
+        assert!(where_clause.is_some());
+
+        let Command::Query { omit_nulls, .. } = parse(r#"QUERY orders"#) else {
+            panic!("expected Query");
+        };
+        assert!(!omit_nulls);
+
+        assert!(parse_query_peg(r#"QUERY orders OMIT"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_with_total() {
+        let command = parse(r#"QUERY orders WHERE amount > 10 WITH TOTAL LIMIT 20 OFFSET 40"#);
+        let Command::Query {
+            with_total,
+            limit,
+            offset,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert!(with_total);
+        assert_eq!(limit, Some(20));
+        assert_eq!(offset, Some(40));
+
+        let Command::Query { with_total, .. } = parse(r#"QUERY orders"#) else {
+            panic!("expected Query");
+        };
+        assert!(!with_total);
+
+        assert!(parse_query_peg(r#"QUERY orders WITH"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_disable_pruners() {
+        let command =
+            parse(r#"QUERY orders WHERE amount > 10 DISABLE PRUNERS surf, Xor, surf LIMIT 5"#);
+        let Command::Query {
+            disabled_pruners,
+            limit,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert_eq!(disabled_pruners, vec![PrunerKind::Surf, PrunerKind::Xor]);
+        assert_eq!(limit, Some(5));
+
+        let Command::Query {
+            disabled_pruners, ..
+        } = parse(r#"QUERY orders DISABLE PRUNER materialization"#)
+        else {
+            panic!("expected Query");
+        };
+        assert_eq!(disabled_pruners, vec![PrunerKind::Materialization]);
+
+        let Command::Query {
+            disabled_pruners, ..
+        } = parse(r#"QUERY orders"#)
+        else {
+            panic!("expected Query");
+        };
+        assert!(disabled_pruners.is_empty());
+
+        assert!(parse_query_peg(r#"QUERY orders DISABLE PRUNERS"#).is_err());
+        assert!(parse_query_peg(r#"QUERY orders DISABLE PRUNERS bloom"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_allow_full_scan_hint() {
+        let command = parse(r#"QUERY /* allow_full_scan */ orders WHERE amount > 10 LIMIT 5"#);
+        let Command::Query {
+            allow_full_scan,
+            limit,
+            ..
+        } = command
+        else {
+            panic!("expected Query, got {:?}", command);
+        };
+        assert!(allow_full_scan);
+        assert_eq!(limit, Some(5));
+
+        let Command::Query {
+            allow_full_scan, ..
+        } = parse(r#"QUERY orders WHERE amount > 10 /*ALLOW_FULL_SCAN*/"#)
+        else {
+            panic!("expected Query");
+        };
+        assert!(allow_full_scan);
+
+        let Command::Query {
+            allow_full_scan, ..
+        } = parse(r#"QUERY orders"#)
+        else {
+            panic!("expected Query");
+        };
+        assert!(!allow_full_scan);
+
+        assert!(parse_query_peg(r#"QUERY /* full_scan */ orders"#).is_err());
+        assert!(parse_query_peg(r#"QUERY orders /* allow_full_scan"#).is_err());
+
+        let Command::Query {
+            allow_full_scan, ..
+        } = crate::command::parser::parse_command(
+            r#"QUERY /* allow_full_scan */ orders WHERE amount > 10"#,
+        )
+        .expect("tokens pass validation")
+        else {
+            panic!("expected Query");
+        };
+        assert!(allow_full_scan);
+    }
 }
@@ -0,0 +1,75 @@
+use crate::command::parser::commands::inspect_zone::{expect_id, expect_keyword};
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::{Command, RebuildIndexKind};
+
+/// `REBUILD INDEXES [<kind>[, <kind>...]] [SEGMENT <segment_id> SHARD <shard_id>]`
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    let mut iter = tokens.iter().peekable();
+
+    // REBUILD
+    match iter.next() {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("REBUILD") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("REBUILD".into())),
+    }
+
+    match iter.next() {
+        Some(Token::Word(word))
+            if word.eq_ignore_ascii_case("INDEXES") || word.eq_ignore_ascii_case("INDEX") => {}
+        Some(tok) => {
+            return Err(ParseError::ExpectedKeyword(
+                "INDEXES".into(),
+                format!("{:?}", tok),
+            ));
+        }
+        None => return Err(ParseError::MissingArgument("INDEXES".into())),
+    }
+
+    // Optional comma-separated kinds; none means every kind
+    let mut kinds = Vec::new();
+    while let Some(Token::Word(word)) = iter.peek() {
+        if word.eq_ignore_ascii_case("SEGMENT") {
+            break;
+        }
+        let kind = RebuildIndexKind::parse(word)
+            .ok_or_else(|| ParseError::UnexpectedToken(format!("Unknown index kind '{}'", word)))?;
+        kinds.push(kind);
+        iter.next();
+        match iter.peek() {
+            Some(Token::Symbol(',')) => {
+                iter.next();
+                if !matches!(iter.peek(), Some(Token::Word(_))) {
+                    return Err(ParseError::MissingArgument("index kind".into()));
+                }
+            }
+            _ => break,
+        }
+    }
+    kinds.sort();
+    kinds.dedup();
+
+    let (segment_id, shard_id) = if iter.peek().is_some() {
+        expect_keyword(iter.next(), "SEGMENT")?;
+        let segment_id = expect_id(iter.next(), "segment")?;
+        expect_keyword(iter.next(), "SHARD")?;
+        let shard_id = expect_id(iter.next(), "shard")?;
+        let shard_id = usize::try_from(shard_id)
+            .map_err(|_| ParseError::UnexpectedToken(format!("Invalid shard id '{}'", shard_id)))?;
+        (Some(segment_id), Some(shard_id))
+    } else {
+        (None, None)
+    };
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after REBUILD INDEXES command".to_string(),
+        ));
+    }
+
+    Ok(Command::RebuildIndexes {
+        kinds,
+        shard_id,
+        segment_id,
+    })
+}
@@ -0,0 +1,91 @@
+use crate::command::parser::commands::rebuild_indexes;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::{Command, RebuildIndexKind};
+
+#[test]
+fn test_parse_rebuild_indexes_all() {
+    let command = rebuild_indexes::parse(&tokenize("rebuild Indexes"))
+        .expect("Failed to parse REBUILD INDEXES command");
+    assert_eq!(
+        command,
+        Command::RebuildIndexes {
+            kinds: vec![],
+            shard_id: None,
+            segment_id: None,
+        }
+    );
+}
+
+#[test]
+fn test_parse_rebuild_indexes_kinds_and_segment() {
+    let command = rebuild_indexes::parse(&tokenize(
+        "REBUILD INDEXES temporal, XOR, surf, xor SEGMENT 00012 SHARD 1",
+    ))
+    .expect("Failed to parse REBUILD INDEXES command");
+    assert_eq!(
+        command,
+        Command::RebuildIndexes {
+            kinds: vec![
+                RebuildIndexKind::Surf,
+                RebuildIndexKind::Xor,
+                RebuildIndexKind::Temporal,
+            ],
+            shard_id: Some(1),
+            segment_id: Some(12),
+        }
+    );
+
+    let command = rebuild_indexes::parse(&tokenize("REBUILD INDEXES SEGMENT 3 SHARD 0"))
+        .expect("Failed to parse REBUILD INDEXES command");
+    assert_eq!(
+        command,
+        Command::RebuildIndexes {
+            kinds: vec![],
+            shard_id: Some(0),
+            segment_id: Some(3),
+        }
+    );
+}
+
+#[test]
+fn test_parse_rebuild_indexes_errors() {
+    assert!(matches!(
+        rebuild_indexes::parse(&tokenize("REBUILD")),
+        Err(ParseError::MissingArgument(ref kw)) if kw == "INDEXES"
+    ));
+    assert!(matches!(
+        rebuild_indexes::parse(&tokenize("REBUILD ZONES")),
+        Err(ParseError::ExpectedKeyword(ref kw, _)) if kw == "INDEXES"
+    ));
+    assert!(matches!(
+        rebuild_indexes::parse(&tokenize("REBUILD INDEXES trigram")),
+        Err(ParseError::UnexpectedToken(ref msg)) if msg.contains("trigram")
+    ));
+    assert!(matches!(
+        rebuild_indexes::parse(&tokenize("REBUILD INDEXES surf,")),
+        Err(ParseError::MissingArgument(_))
+    ));
+    assert!(matches!(
+        rebuild_indexes::parse(&tokenize("REBUILD INDEXES surf SEGMENT 3")),
+        Err(ParseError::MissingArgument(ref kw)) if kw == "SHARD"
+    ));
+    assert!(matches!(
+        rebuild_indexes::parse(&tokenize("REBUILD INDEXES SEGMENT 3 SHARD 0 now")),
+        Err(ParseError::UnexpectedToken(_))
+    ));
+}
+
+#[test]
+fn test_parse_command_routes_rebuild_indexes() {
+    use crate::command::parser::command::parse_command;
+
+    assert_eq!(
+        parse_command("REBUILD INDEXES enum").unwrap(),
+        Command::RebuildIndexes {
+            kinds: vec![RebuildIndexKind::Enum],
+            shard_id: None,
+            segment_id: None,
+        }
+    );
+}
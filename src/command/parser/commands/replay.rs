@@ -38,6 +38,8 @@ peg::parser! {
             = since_clause()
             / return_clause()
             / using_clause()
+            / direction_clause()
+            / limit_clause()
 
         rule since_clause() -> Clause
             = ci("SINCE") _ ts:string_literal() {
@@ -58,6 +60,13 @@ peg::parser! {
                 Clause::Using(fld.to_string())
             }
 
+        rule direction_clause() -> Clause
+            = ci("DESC") { Clause::Desc(true) }
+            / ci("ASC") { Clause::Desc(false) }
+
+        rule limit_clause() -> Clause
+            = ci("LIMIT") _ n:number() { Clause::Limit(n) }
+
         // ==========
         // TERMINALS
         // ==========
@@ -73,6 +82,9 @@ peg::parser! {
             }
             / expected!("identifier")
 
+        rule number() -> u32
+            = n:$(['0'..='9']+) {? n.parse().or(Err("number")) }
+
         rule string_literal() -> &'input str
             = "\"" chars:$((!"\"" [_])*) "\"" { chars }
     }
@@ -95,18 +107,24 @@ enum Clause {
     Since(String),
     Return(Vec<String>),
     Using(String),
+    Desc(bool),
+    Limit(u32),
 }
 
 fn build_command(event_type: Option<&str>, context_id: String, clauses: Vec<Clause>) -> Command {
     let mut since = None;
     let mut return_fields = None;
     let mut time_field = None;
+    let mut desc = false;
+    let mut limit = None;
 
     for clause in clauses {
         match clause {
             Clause::Since(v) => since = Some(v),
             Clause::Return(v) => return_fields = Some(v),
             Clause::Using(v) => time_field = Some(v),
+            Clause::Desc(v) => desc = v,
+            Clause::Limit(v) => limit = Some(v),
         }
     }
 
@@ -116,6 +134,8 @@ fn build_command(event_type: Option<&str>, context_id: String, clauses: Vec<Clau
         since,
         time_field,
         return_fields,
+        desc,
+        limit,
     }
 }
 
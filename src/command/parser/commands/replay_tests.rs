@@ -19,6 +19,8 @@ mod replay_tests {
                 since: None,
                 time_field: None,
                 return_fields: None,
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -37,6 +39,8 @@ mod replay_tests {
                 since: None,
                 time_field: None,
                 return_fields: None,
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -55,6 +59,8 @@ mod replay_tests {
                 since: Some("2024-01-01T00:00:00Z".to_string()),
                 time_field: None,
                 return_fields: None,
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -74,6 +80,8 @@ mod replay_tests {
                 since: Some("2024-01-01T00:00:00Z".to_string()),
                 time_field: None,
                 return_fields: None,
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -93,6 +101,8 @@ mod replay_tests {
                 since: Some("2025-01-01T00:00:00Z".to_string()),
                 time_field: Some("created_at".to_string()),
                 return_fields: None,
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -151,6 +161,8 @@ mod replay_tests {
                     "timestamp".to_string(),
                     "payload".to_string(),
                 ]),
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -169,6 +181,8 @@ mod replay_tests {
                 since: Some("2024-01-01T00:00:00Z".to_string()),
                 time_field: None,
                 return_fields: Some(vec!["plan".to_string(), "country".to_string()]),
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -187,6 +201,8 @@ mod replay_tests {
                 since: None,
                 time_field: None,
                 return_fields: Some(vec![]),
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -209,6 +225,8 @@ mod replay_tests {
                     "country".to_string(),
                     "plan".to_string(),
                 ]),
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -232,6 +250,8 @@ mod replay_tests {
                     "name".to_string(),
                     "name".to_string(),
                 ]),
+                desc: false,
+                limit: None,
             }
         );
     }
@@ -259,4 +279,49 @@ mod replay_tests {
         let result = replay::parse(input);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_replay_desc_with_since_and_limit() {
+        let input =
+            r#"REPLAY order_created FOR user-123 SINCE "2024-01-01T00:00:00Z" DESC LIMIT 10"#;
+
+        let command = replay::parse(input).expect("Failed to parse REPLAY with DESC and LIMIT");
+
+        assert_eq!(
+            command,
+            Command::Replay {
+                event_type: Some("order_created".to_string()),
+                context_id: "user-123".to_string(),
+                since: Some("2024-01-01T00:00:00Z".to_string()),
+                time_field: None,
+                return_fields: None,
+                desc: true,
+                limit: Some(10),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_replay_asc_is_the_default_direction() {
+        let command = replay::parse("REPLAY FOR user-123 limit 5 asc").unwrap();
+        assert!(matches!(
+            command,
+            Command::Replay {
+                desc: false,
+                limit: Some(5),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_replay_with_invalid_limit_should_fail() {
+        for input in [
+            "REPLAY FOR user-123 LIMIT",
+            "REPLAY FOR user-123 LIMIT -1",
+            "REPLAY FOR user-123 LIMIT ten",
+        ] {
+            assert!(replay::parse(input).is_err(), "{}", input);
+        }
+    }
 }
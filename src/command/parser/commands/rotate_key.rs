@@ -0,0 +1,87 @@
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+/// Parses `ROTATE KEY <user_id> [WITH KEY <secret>] [REVOKE SESSIONS]`.
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    use Token::*;
+
+    let mut iter = tokens.iter().peekable();
+
+    match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("ROTATE") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("ROTATE".into())),
+    }
+
+    match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("KEY") => {}
+        Some(tok) => {
+            return Err(ParseError::ExpectedKeyword(
+                "KEY".into(),
+                format!("{:?}", tok),
+            ));
+        }
+        None => return Err(ParseError::MissingArgument("KEY".into())),
+    }
+
+    let user_id = match iter.next() {
+        Some(Word(word)) => word.clone(),
+        Some(StringLiteral(word)) => word.clone(),
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("user_id".into())),
+    };
+
+    // Optional: WITH KEY "secret_key" and/or REVOKE SESSIONS
+    let mut secret_key = None;
+    let mut revoke_sessions = false;
+
+    while let Some(Word(word)) = iter.peek() {
+        if word.eq_ignore_ascii_case("WITH") && secret_key.is_none() {
+            iter.next(); // consume WITH
+            match iter.next() {
+                Some(Word(kw)) if kw.eq_ignore_ascii_case("KEY") => match iter.next() {
+                    Some(StringLiteral(key)) => secret_key = Some(key.clone()),
+                    Some(Word(key)) => secret_key = Some(key.clone()),
+                    Some(tok) => {
+                        return Err(ParseError::UnexpectedToken(format!("{:?}", tok)));
+                    }
+                    None => return Err(ParseError::MissingArgument("secret_key".into())),
+                },
+                Some(tok) => {
+                    return Err(ParseError::ExpectedKeyword(
+                        "KEY".into(),
+                        format!("{:?}", tok),
+                    ));
+                }
+                None => return Err(ParseError::MissingArgument("KEY".into())),
+            }
+        } else if word.eq_ignore_ascii_case("REVOKE") && !revoke_sessions {
+            iter.next(); // consume REVOKE
+            match iter.next() {
+                Some(Word(kw)) if kw.eq_ignore_ascii_case("SESSIONS") => revoke_sessions = true,
+                Some(tok) => {
+                    return Err(ParseError::ExpectedKeyword(
+                        "SESSIONS".into(),
+                        format!("{:?}", tok),
+                    ));
+                }
+                None => return Err(ParseError::MissingArgument("SESSIONS".into())),
+            }
+        } else {
+            break;
+        }
+    }
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after ROTATE KEY command".to_string(),
+        ));
+    }
+
+    Ok(Command::RotateKey {
+        user_id,
+        secret_key,
+        revoke_sessions,
+    })
+}
@@ -0,0 +1,93 @@
+use crate::command::parser::command::parse_command;
+use crate::command::parser::commands::rotate_key;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[cfg(test)]
+mod rotate_key_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rotate_key_simple() {
+        let tokens = tokenize("ROTATE KEY api_client");
+
+        let command = rotate_key::parse(&tokens).expect("Failed to parse ROTATE KEY command");
+
+        assert_eq!(
+            command,
+            Command::RotateKey {
+                user_id: "api_client".to_string(),
+                secret_key: None,
+                revoke_sessions: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rotate_key_with_key_and_revoke_sessions() {
+        let tokens = tokenize(r#"rotate key "service-account" WITH KEY "n3w" REVOKE SESSIONS"#);
+
+        let command = rotate_key::parse(&tokens).expect("Failed to parse ROTATE KEY options");
+
+        assert_eq!(
+            command,
+            Command::RotateKey {
+                user_id: "service-account".to_string(),
+                secret_key: Some("n3w".to_string()),
+                revoke_sessions: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rotate_key_options_in_any_order() {
+        let command = parse_command("ROTATE KEY api_client REVOKE SESSIONS WITH KEY secret2")
+            .expect("Failed to route ROTATE KEY");
+
+        assert_eq!(
+            command,
+            Command::RotateKey {
+                user_id: "api_client".to_string(),
+                secret_key: Some("secret2".to_string()),
+                revoke_sessions: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rotate_key_missing_user_id() {
+        let tokens = tokenize("ROTATE KEY");
+
+        match rotate_key::parse(&tokens) {
+            Err(ParseError::MissingArgument(arg)) => {
+                assert_eq!(arg, "user_id");
+            }
+            other => panic!("Expected MissingArgument error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_rotate_key_revoke_without_sessions() {
+        let tokens = tokenize("ROTATE KEY api_client REVOKE KEYS");
+
+        match rotate_key::parse(&tokens) {
+            Err(ParseError::ExpectedKeyword(expected, _)) => {
+                assert_eq!(expected, "SESSIONS");
+            }
+            other => panic!("Expected ExpectedKeyword error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_rotate_key_repeated_option() {
+        let tokens = tokenize("ROTATE KEY api_client WITH KEY a WITH KEY b");
+
+        match rotate_key::parse(&tokens) {
+            Err(ParseError::UnexpectedToken(msg)) => {
+                assert!(msg.contains("Extra tokens"));
+            }
+            other => panic!("Expected UnexpectedToken error, got {:?}", other),
+        }
+    }
+}
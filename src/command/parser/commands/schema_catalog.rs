@@ -0,0 +1,58 @@
+use crate::command::parser::error::ParseError;
+use crate::command::types::Command;
+
+/// Parses `EXPORT SCHEMAS` and `IMPORT SCHEMAS <catalog JSON>`.
+///
+/// Works on the raw input rather than tokens: the catalog nests objects and
+/// arrays, which JSON blocks in tokenized commands do not allow.
+pub fn parse(input: &str) -> Result<Command, ParseError> {
+    let input = input.trim_start();
+    let (verb, rest) = split_word(input);
+    let import = if verb.eq_ignore_ascii_case("IMPORT") {
+        true
+    } else if verb.eq_ignore_ascii_case("EXPORT") {
+        false
+    } else if verb.is_empty() {
+        return Err(ParseError::MissingArgument("EXPORT or IMPORT".into()));
+    } else {
+        return Err(ParseError::UnexpectedToken(verb.to_string()));
+    };
+
+    let (keyword, rest) = split_word(rest);
+    if !keyword.eq_ignore_ascii_case("SCHEMAS") {
+        return Err(if keyword.is_empty() {
+            ParseError::MissingArgument("SCHEMAS".into())
+        } else {
+            ParseError::ExpectedKeyword("SCHEMAS".into(), keyword.to_string())
+        });
+    }
+
+    let rest = rest.trim();
+    if !import {
+        if !rest.is_empty() {
+            return Err(ParseError::UnexpectedToken(format!(
+                "Extra tokens after EXPORT SCHEMAS command: {}",
+                rest
+            )));
+        }
+        return Ok(Command::ExportSchemas);
+    }
+
+    if rest.is_empty() {
+        return Err(ParseError::MissingArgument("catalog document".into()));
+    }
+    if !rest.starts_with('{') {
+        return Err(ParseError::ExpectedJsonBlock);
+    }
+    let document =
+        serde_json::from_str(rest).map_err(|e| ParseError::InvalidJson(e.to_string()))?;
+    Ok(Command::ImportSchemas { document })
+}
+
+fn split_word(input: &str) -> (&str, &str) {
+    let input = input.trim_start();
+    match input.find(char::is_whitespace) {
+        Some(end) => (&input[..end], &input[end..]),
+        None => (input, ""),
+    }
+}
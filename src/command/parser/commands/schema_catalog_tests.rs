@@ -0,0 +1,66 @@
+use crate::command::parser::command::parse_command;
+use crate::command::parser::commands::schema_catalog;
+use crate::command::parser::error::ParseError;
+use crate::command::types::Command;
+use serde_json::json;
+
+#[test]
+fn test_parse_export_schemas() {
+    assert_eq!(
+        schema_catalog::parse("export schemas").unwrap(),
+        Command::ExportSchemas
+    );
+    assert!(matches!(
+        schema_catalog::parse("EXPORT SCHEMAS now"),
+        Err(ParseError::UnexpectedToken(_))
+    ));
+    assert!(matches!(
+        schema_catalog::parse("EXPORT"),
+        Err(ParseError::MissingArgument(ref arg)) if arg == "SCHEMAS"
+    ));
+    assert!(matches!(
+        schema_catalog::parse("EXPORT USERS"),
+        Err(ParseError::ExpectedKeyword(ref kw, _)) if kw == "SCHEMAS"
+    ));
+}
+
+#[test]
+fn test_parse_import_schemas_keeps_nested_document() {
+    let input = r#"IMPORT SCHEMAS {"version": 1, "schemas": [{"event_type": "order", "uid": "u1",
+        "schema": {"fields": {"plan": {"Enum": {"variants": ["free", "pro"]}}}}}]}"#;
+    let Command::ImportSchemas { document } = schema_catalog::parse(input).unwrap() else {
+        panic!("expected IMPORT SCHEMAS");
+    };
+    assert_eq!(document["version"], json!(1));
+    assert_eq!(
+        document["schemas"][0]["schema"]["fields"]["plan"]["Enum"]["variants"],
+        json!(["free", "pro"])
+    );
+
+    assert!(matches!(
+        schema_catalog::parse("IMPORT SCHEMAS"),
+        Err(ParseError::MissingArgument(_))
+    ));
+    assert!(matches!(
+        schema_catalog::parse("IMPORT SCHEMAS [1]"),
+        Err(ParseError::ExpectedJsonBlock)
+    ));
+    assert!(matches!(
+        schema_catalog::parse("IMPORT SCHEMAS {\"version\": 1"),
+        Err(ParseError::InvalidJson(_))
+    ));
+}
+
+#[test]
+fn test_parse_command_routes_schema_catalog() {
+    assert_eq!(
+        parse_command("EXPORT SCHEMAS").unwrap(),
+        Command::ExportSchemas
+    );
+    assert_eq!(
+        parse_command("IMPORT SCHEMAS {\"version\": 1, \"schemas\": []}").unwrap(),
+        Command::ImportSchemas {
+            document: json!({"version": 1, "schemas": []})
+        }
+    );
+}
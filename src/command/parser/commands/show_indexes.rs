@@ -0,0 +1,58 @@
+use crate::command::parser::commands::inspect_zone::{expect_id, expect_keyword};
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+/// `SHOW INDEXES <event_type> [SEGMENT <segment_id> SHARD <shard_id>]`
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    let mut iter = tokens.iter().peekable();
+
+    // SHOW
+    match iter.next() {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("SHOW") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("SHOW".into())),
+    }
+
+    match iter.next() {
+        Some(Token::Word(word))
+            if word.eq_ignore_ascii_case("INDEXES") || word.eq_ignore_ascii_case("INDEX") => {}
+        Some(tok) => {
+            return Err(ParseError::ExpectedKeyword(
+                "INDEXES".into(),
+                format!("{:?}", tok),
+            ));
+        }
+        None => return Err(ParseError::MissingArgument("INDEXES".into())),
+    }
+
+    let event_type = match iter.next() {
+        Some(Token::Word(word)) | Some(Token::StringLiteral(word)) => word.clone(),
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("event_type".into())),
+    };
+
+    let (segment_id, shard_id) = if iter.peek().is_some() {
+        expect_keyword(iter.next(), "SEGMENT")?;
+        let segment_id = expect_id(iter.next(), "segment")?;
+        expect_keyword(iter.next(), "SHARD")?;
+        let shard_id = expect_id(iter.next(), "shard")?;
+        let shard_id = usize::try_from(shard_id)
+            .map_err(|_| ParseError::UnexpectedToken(format!("Invalid shard id '{}'", shard_id)))?;
+        (Some(segment_id), Some(shard_id))
+    } else {
+        (None, None)
+    };
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after SHOW INDEXES command".to_string(),
+        ));
+    }
+
+    Ok(Command::ShowIndexes {
+        event_type,
+        shard_id,
+        segment_id,
+    })
+}
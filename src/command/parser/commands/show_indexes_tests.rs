@@ -0,0 +1,77 @@
+use crate::command::parser::commands::show_indexes;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[test]
+fn test_parse_show_indexes_event_type() {
+    let command = show_indexes::parse(&tokenize("show Indexes order"))
+        .expect("Failed to parse SHOW INDEXES command");
+    assert_eq!(
+        command,
+        Command::ShowIndexes {
+            event_type: "order".to_string(),
+            shard_id: None,
+            segment_id: None,
+        }
+    );
+
+    let command = show_indexes::parse(&tokenize("SHOW INDEX \"user-created\""))
+        .expect("Failed to parse SHOW INDEX command");
+    assert_eq!(
+        command,
+        Command::ShowIndexes {
+            event_type: "user-created".to_string(),
+            shard_id: None,
+            segment_id: None,
+        }
+    );
+}
+
+#[test]
+fn test_parse_show_indexes_segment() {
+    let command = show_indexes::parse(&tokenize("SHOW INDEXES order SEGMENT 00012 SHARD 1"))
+        .expect("Failed to parse SHOW INDEXES command");
+    assert_eq!(
+        command,
+        Command::ShowIndexes {
+            event_type: "order".to_string(),
+            shard_id: Some(1),
+            segment_id: Some(12),
+        }
+    );
+}
+
+#[test]
+fn test_parse_show_indexes_errors() {
+    assert!(matches!(
+        show_indexes::parse(&tokenize("SHOW INDEXES")),
+        Err(ParseError::MissingArgument(ref arg)) if arg == "event_type"
+    ));
+    assert!(matches!(
+        show_indexes::parse(&tokenize("SHOW INDEXES order SEGMENT 3")),
+        Err(ParseError::MissingArgument(ref kw)) if kw == "SHARD"
+    ));
+    assert!(matches!(
+        show_indexes::parse(&tokenize("SHOW INDEXES order SHARD 0")),
+        Err(ParseError::ExpectedKeyword(ref kw, _)) if kw == "SEGMENT"
+    ));
+    assert!(matches!(
+        show_indexes::parse(&tokenize("SHOW INDEXES order SEGMENT 3 SHARD 0 now")),
+        Err(ParseError::UnexpectedToken(_))
+    ));
+}
+
+#[test]
+fn test_parse_command_routes_show_indexes() {
+    use crate::command::parser::command::parse_command;
+
+    assert_eq!(
+        parse_command("SHOW INDEXES order").unwrap(),
+        Command::ShowIndexes {
+            event_type: "order".to_string(),
+            shard_id: None,
+            segment_id: None,
+        }
+    );
+}
@@ -0,0 +1,38 @@
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    use Token::*;
+
+    let mut iter = tokens.iter().peekable();
+
+    // SHOW
+    match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("SHOW") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("SHOW".into())),
+    }
+
+    // PINNED SEGMENTS
+    for keyword in ["PINNED", "SEGMENTS"] {
+        match iter.next() {
+            Some(Word(word)) if word.eq_ignore_ascii_case(keyword) => {}
+            Some(tok) => {
+                return Err(ParseError::ExpectedKeyword(
+                    keyword.into(),
+                    format!("{:?}", tok),
+                ));
+            }
+            None => return Err(ParseError::MissingArgument(keyword.into())),
+        }
+    }
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after SHOW PINNED SEGMENTS command".to_string(),
+        ));
+    }
+
+    Ok(Command::ShowPinnedSegments)
+}
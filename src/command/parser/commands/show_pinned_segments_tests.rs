@@ -0,0 +1,40 @@
+use crate::command::parser::commands::show_pinned_segments;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[test]
+fn test_parse_show_pinned_segments_case_insensitive() {
+    let command = show_pinned_segments::parse(&tokenize("show Pinned SEGMENTS"))
+        .expect("Failed to parse SHOW PINNED SEGMENTS command");
+    assert_eq!(command, Command::ShowPinnedSegments);
+}
+
+#[test]
+fn test_parse_show_pinned_segments_missing_keyword() {
+    let result = show_pinned_segments::parse(&tokenize("SHOW PINNED"));
+    assert!(matches!(result, Err(ParseError::MissingArgument(ref kw)) if kw == "SEGMENTS"));
+}
+
+#[test]
+fn test_parse_show_pinned_segments_rejects_extra_tokens() {
+    let result = show_pinned_segments::parse(&tokenize("SHOW PINNED SEGMENTS now"));
+    assert!(matches!(result, Err(ParseError::UnexpectedToken(_))));
+}
+
+#[test]
+fn test_parse_command_routes_show_pinned_segments() {
+    use crate::command::parser::command::parse_command;
+
+    assert_eq!(
+        parse_command("SHOW PINNED SEGMENTS").unwrap(),
+        Command::ShowPinnedSegments
+    );
+    // A materialization named `pinned` is still reachable
+    assert_eq!(
+        parse_command("SHOW pinned").unwrap(),
+        Command::ShowMaterialized {
+            name: "pinned".to_string()
+        }
+    );
+}
@@ -0,0 +1,36 @@
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    use Token::*;
+
+    let mut iter = tokens.iter().peekable();
+
+    // SHOW
+    match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("SHOW") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("SHOW".into())),
+    }
+
+    // STATS
+    match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("STATS") => {}
+        Some(tok) => {
+            return Err(ParseError::ExpectedKeyword(
+                "STATS".into(),
+                format!("{:?}", tok),
+            ));
+        }
+        None => return Err(ParseError::MissingArgument("STATS".into())),
+    }
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after SHOW STATS command".to_string(),
+        ));
+    }
+
+    Ok(Command::ShowStats)
+}
@@ -0,0 +1,23 @@
+use crate::command::parser::commands::show_stats;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[test]
+fn test_parse_show_stats_case_insensitive() {
+    let command = show_stats::parse(&tokenize("show Stats")).expect("Failed to parse SHOW STATS");
+    assert_eq!(command, Command::ShowStats);
+}
+
+#[test]
+fn test_parse_show_stats_rejects_extra_tokens() {
+    let result = show_stats::parse(&tokenize("SHOW STATS now"));
+    assert!(matches!(result, Err(ParseError::UnexpectedToken(_))));
+}
+
+#[test]
+fn test_parse_command_routes_show_stats() {
+    use crate::command::parser::command::parse_command;
+
+    assert_eq!(parse_command("SHOW STATS").unwrap(), Command::ShowStats);
+}
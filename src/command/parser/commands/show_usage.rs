@@ -0,0 +1,48 @@
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+/// `SHOW USAGE [RESET]`
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    use Token::*;
+
+    let mut iter = tokens.iter().peekable();
+
+    // SHOW
+    match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("SHOW") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("SHOW".into())),
+    }
+
+    // USAGE
+    match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("USAGE") => {}
+        Some(tok) => {
+            return Err(ParseError::ExpectedKeyword(
+                "USAGE".into(),
+                format!("{:?}", tok),
+            ));
+        }
+        None => return Err(ParseError::MissingArgument("USAGE".into())),
+    }
+
+    let reset = match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("RESET") => true,
+        Some(tok) => {
+            return Err(ParseError::ExpectedKeyword(
+                "RESET".into(),
+                format!("{:?}", tok),
+            ));
+        }
+        None => false,
+    };
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after SHOW USAGE command".to_string(),
+        ));
+    }
+
+    Ok(Command::ShowUsage { reset })
+}
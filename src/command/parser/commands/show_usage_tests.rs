@@ -0,0 +1,36 @@
+use crate::command::parser::commands::show_usage;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[test]
+fn test_parse_show_usage_case_insensitive() {
+    let command = show_usage::parse(&tokenize("show Usage")).expect("Failed to parse SHOW USAGE");
+    assert_eq!(command, Command::ShowUsage { reset: false });
+}
+
+#[test]
+fn test_parse_show_usage_reset() {
+    let command =
+        show_usage::parse(&tokenize("SHOW USAGE reset")).expect("Failed to parse SHOW USAGE RESET");
+    assert_eq!(command, Command::ShowUsage { reset: true });
+}
+
+#[test]
+fn test_parse_show_usage_rejects_unknown_option() {
+    let result = show_usage::parse(&tokenize("SHOW USAGE now"));
+    assert!(matches!(result, Err(ParseError::ExpectedKeyword(_, _))));
+
+    let result = show_usage::parse(&tokenize("SHOW USAGE RESET now"));
+    assert!(matches!(result, Err(ParseError::UnexpectedToken(_))));
+}
+
+#[test]
+fn test_parse_command_routes_show_usage() {
+    use crate::command::parser::command::parse_command;
+
+    assert_eq!(
+        parse_command("SHOW USAGE RESET").unwrap(),
+        Command::ShowUsage { reset: true }
+    );
+}
@@ -0,0 +1,59 @@
+use crate::command::parser::command::parse_command;
+use crate::command::parser::commands::show_users;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[cfg(test)]
+mod show_users_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_show_users_simple() {
+        let tokens = tokenize("SHOW USERS");
+
+        let command = show_users::parse(&tokens).expect("Failed to parse SHOW USERS command");
+
+        assert_eq!(command, Command::ShowUsers);
+    }
+
+    #[test]
+    fn test_parse_show_users_case_insensitive() {
+        let tokens = tokenize("show users");
+
+        let command = show_users::parse(&tokens).expect("Failed to parse SHOW USERS (lowercase)");
+
+        assert_eq!(command, Command::ShowUsers);
+    }
+
+    #[test]
+    fn test_parse_show_users_routed_from_show() {
+        let command = parse_command("SHOW USERS").expect("Failed to route SHOW USERS");
+
+        assert_eq!(command, Command::ShowUsers);
+    }
+
+    #[test]
+    fn test_parse_show_users_with_extra_tokens() {
+        let tokens = tokenize("SHOW USERS admin");
+
+        match show_users::parse(&tokens) {
+            Err(ParseError::UnexpectedToken(msg)) => {
+                assert!(msg.contains("Extra tokens"));
+            }
+            other => panic!("Expected UnexpectedToken error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_show_users_missing_users_keyword() {
+        let tokens = tokenize("SHOW");
+
+        match show_users::parse(&tokens) {
+            Err(ParseError::MissingArgument(arg)) => {
+                assert_eq!(arg, "USERS");
+            }
+            other => panic!("Expected MissingArgument error, got {:?}", other),
+        }
+    }
+}
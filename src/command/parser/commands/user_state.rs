@@ -0,0 +1,57 @@
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+/// Parses `DISABLE USER <user_id>`, `ENABLE USER <user_id>` and `UNLOCK USER <user_id>`.
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    use Token::*;
+
+    let mut iter = tokens.iter().peekable();
+
+    let action = match iter.next() {
+        Some(Word(word))
+            if ["DISABLE", "ENABLE", "UNLOCK"]
+                .iter()
+                .any(|action| word.eq_ignore_ascii_case(action)) =>
+        {
+            word.to_ascii_uppercase()
+        }
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => {
+            return Err(ParseError::MissingArgument(
+                "DISABLE, ENABLE or UNLOCK".into(),
+            ));
+        }
+    };
+
+    match iter.next() {
+        Some(Word(word)) if word.eq_ignore_ascii_case("USER") => {}
+        Some(tok) => {
+            return Err(ParseError::ExpectedKeyword(
+                "USER".into(),
+                format!("{:?}", tok),
+            ));
+        }
+        None => return Err(ParseError::MissingArgument("USER".into())),
+    }
+
+    let user_id = match iter.next() {
+        Some(Word(word)) => word.clone(),
+        Some(StringLiteral(word)) => word.clone(),
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("user_id".into())),
+    };
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(format!(
+            "Extra tokens after {} USER command",
+            action
+        )));
+    }
+
+    match action.as_str() {
+        "ENABLE" => Ok(Command::EnableUser { user_id }),
+        "UNLOCK" => Ok(Command::UnlockUser { user_id }),
+        _ => Ok(Command::DisableUser { user_id }),
+    }
+}
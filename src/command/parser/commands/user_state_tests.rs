@@ -0,0 +1,111 @@
+use crate::command::parser::command::parse_command;
+use crate::command::parser::commands::user_state;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[cfg(test)]
+mod user_state_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_disable_user_simple() {
+        let tokens = tokenize("DISABLE USER api_client");
+
+        let command = user_state::parse(&tokens).expect("Failed to parse DISABLE USER command");
+
+        assert_eq!(
+            command,
+            Command::DisableUser {
+                user_id: "api_client".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_enable_user_with_string_literal() {
+        let tokens = tokenize(r#"enable user "service-account""#);
+
+        let command =
+            user_state::parse(&tokens).expect("Failed to parse ENABLE USER with string literal");
+
+        assert_eq!(
+            command,
+            Command::EnableUser {
+                user_id: "service-account".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_unlock_user() {
+        let tokens = tokenize("UNLOCK USER api_client");
+
+        let command = user_state::parse(&tokens).expect("Failed to parse UNLOCK USER command");
+
+        assert_eq!(
+            command,
+            Command::UnlockUser {
+                user_id: "api_client".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_command("unlock user api_client").unwrap(),
+            Command::UnlockUser {
+                user_id: "api_client".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_user_state_routed_from_command_parser() {
+        assert_eq!(
+            parse_command("DISABLE USER api_client").unwrap(),
+            Command::DisableUser {
+                user_id: "api_client".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_command("ENABLE USER api_client").unwrap(),
+            Command::EnableUser {
+                user_id: "api_client".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_disable_user_missing_user_id() {
+        let tokens = tokenize("DISABLE USER");
+
+        match user_state::parse(&tokens) {
+            Err(ParseError::MissingArgument(arg)) => {
+                assert_eq!(arg, "user_id");
+            }
+            other => panic!("Expected MissingArgument error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_disable_user_wrong_keyword() {
+        let tokens = tokenize("DISABLE KEY api_client");
+
+        match user_state::parse(&tokens) {
+            Err(ParseError::ExpectedKeyword(expected, _)) => {
+                assert_eq!(expected, "USER");
+            }
+            other => panic!("Expected ExpectedKeyword error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_enable_user_with_extra_tokens() {
+        let tokens = tokenize("ENABLE USER api_client now");
+
+        match user_state::parse(&tokens) {
+            Err(ParseError::UnexpectedToken(msg)) => {
+                assert!(msg.contains("Extra tokens after ENABLE USER"));
+            }
+            other => panic!("Expected UnexpectedToken error, got {:?}", other),
+        }
+    }
+}
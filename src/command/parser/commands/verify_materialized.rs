@@ -0,0 +1,51 @@
+use crate::command::parser::commands::remember::is_valid_alias;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::command::types::Command;
+
+/// `VERIFY MATERIALIZED [<name>]`
+pub fn parse(tokens: &[Token]) -> Result<Command, ParseError> {
+    let mut iter = tokens.iter().peekable();
+
+    // VERIFY
+    match iter.next() {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("VERIFY") => {}
+        Some(tok) => return Err(ParseError::UnexpectedToken(format!("{:?}", tok))),
+        None => return Err(ParseError::MissingArgument("VERIFY".into())),
+    }
+
+    match iter.next() {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("MATERIALIZED") => {}
+        Some(tok) => {
+            return Err(ParseError::ExpectedKeyword(
+                "MATERIALIZED".into(),
+                format!("{:?}", tok),
+            ));
+        }
+        None => return Err(ParseError::MissingArgument("MATERIALIZED".into())),
+    }
+
+    let name = match iter.next() {
+        None => None,
+        Some(Token::Word(word)) | Some(Token::StringLiteral(word)) => {
+            if !is_valid_alias(word) {
+                return Err(ParseError::UnexpectedToken(word.clone()));
+            }
+            Some(word.clone())
+        }
+        Some(tok) => {
+            return Err(ParseError::UnexpectedToken(format!(
+                "Invalid materialization name: {:?}",
+                tok
+            )));
+        }
+    };
+
+    if iter.peek().is_some() {
+        return Err(ParseError::UnexpectedToken(
+            "Extra tokens after VERIFY MATERIALIZED command".to_string(),
+        ));
+    }
+
+    Ok(Command::VerifyMaterialized { name })
+}
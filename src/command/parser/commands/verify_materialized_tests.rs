@@ -0,0 +1,55 @@
+use crate::command::parser::commands::verify_materialized;
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::tokenize;
+use crate::command::types::Command;
+
+#[test]
+fn test_parse_verify_materialized_all() {
+    let command = verify_materialized::parse(&tokenize("verify Materialized"))
+        .expect("Failed to parse VERIFY MATERIALIZED command");
+    assert_eq!(command, Command::VerifyMaterialized { name: None });
+}
+
+#[test]
+fn test_parse_verify_materialized_named() {
+    let command = verify_materialized::parse(&tokenize("VERIFY MATERIALIZED daily_orders"))
+        .expect("Failed to parse VERIFY MATERIALIZED command");
+    assert_eq!(
+        command,
+        Command::VerifyMaterialized {
+            name: Some("daily_orders".to_string())
+        }
+    );
+}
+
+#[test]
+fn test_parse_verify_materialized_errors() {
+    assert!(matches!(
+        verify_materialized::parse(&tokenize("VERIFY")),
+        Err(ParseError::MissingArgument(ref kw)) if kw == "MATERIALIZED"
+    ));
+    assert!(matches!(
+        verify_materialized::parse(&tokenize("VERIFY ZONE")),
+        Err(ParseError::ExpectedKeyword(ref kw, _)) if kw == "MATERIALIZED"
+    ));
+    assert!(matches!(
+        verify_materialized::parse(&tokenize("VERIFY MATERIALIZED \"bad name\"")),
+        Err(ParseError::UnexpectedToken(_))
+    ));
+    assert!(matches!(
+        verify_materialized::parse(&tokenize("VERIFY MATERIALIZED a b")),
+        Err(ParseError::UnexpectedToken(_))
+    ));
+}
+
+#[test]
+fn test_parse_command_routes_verify_materialized() {
+    use crate::command::parser::command::parse_command;
+
+    assert_eq!(
+        parse_command("VERIFY MATERIALIZED orders").unwrap(),
+        Command::VerifyMaterialized {
+            name: Some("orders".to_string())
+        }
+    );
+}
@@ -1,3 +1,5 @@
+use crate::shared::response::ErrorCode;
+
 #[derive(Debug)]
 pub enum ParseError {
     /// Unrecognized top-level command
@@ -35,6 +37,19 @@ pub enum ParseError {
 
     /// Empty schema
     EmptySchema,
+
+    /// The command exceeds a parse limit (IN list length, condition depth)
+    LimitExceeded(String),
+}
+
+impl ParseError {
+    /// Error code reported to clients.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ParseError::LimitExceeded(_) => ErrorCode::QueryTooComplex,
+            _ => ErrorCode::ParseError,
+        }
+    }
 }
 
 impl std::fmt::Display for ParseError {
@@ -68,6 +83,9 @@ impl std::fmt::Display for ParseError {
             ParseError::EmptySchema => {
                 write!(f, "Schema cannot be empty")
             }
+            ParseError::LimitExceeded(detail) => {
+                write!(f, "Query exceeds parse limit: {}", detail)
+            }
         }
     }
 }
@@ -0,0 +1,167 @@
+use crate::command::parser::error::ParseError;
+use crate::command::parser::tokenizer::Token;
+use crate::shared::config::CONFIG;
+
+/// Size limits checked on the tokens of a command before the grammar runs, from
+/// `[query.parse_limits]`. The grammar and the planner both recurse along the
+/// condition tree, so oversized conditions are turned away before either sees them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Values in a single IN list
+    pub max_in_list: usize,
+    /// Nesting depth of a condition: a comparison is 1 deep, and every AND, OR,
+    /// NOT and pair of parentheses around it adds a level
+    /// (`a = 1 OR b = 2 OR c = 3` is 3 deep, `NOT (a = 1)` is 3 deep)
+    pub max_condition_depth: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_in_list: 100_000,
+            max_condition_depth: 256,
+        }
+    }
+}
+
+impl ParseLimits {
+    pub fn from_config() -> Self {
+        let defaults = Self::default();
+        let Some(cfg) = CONFIG
+            .query
+            .as_ref()
+            .and_then(|cfg| cfg.parse_limits.as_ref())
+        else {
+            return defaults;
+        };
+        Self {
+            max_in_list: cfg.max_in_list.unwrap_or(defaults.max_in_list),
+            max_condition_depth: cfg
+                .max_condition_depth
+                .unwrap_or(defaults.max_condition_depth),
+        }
+    }
+
+    /// Rejects the command with the first limit it exceeds.
+    pub fn check(&self, tokens: &[Token]) -> Result<(), ParseError> {
+        let in_list = largest_in_list(tokens);
+        if in_list > self.max_in_list {
+            return Err(ParseError::LimitExceeded(format!(
+                "IN list has {} values, limit is {} (query.parse_limits.max_in_list)",
+                in_list, self.max_in_list
+            )));
+        }
+
+        let depth = condition_depth(tokens);
+        if depth > self.max_condition_depth {
+            return Err(ParseError::LimitExceeded(format!(
+                "condition is nested {} levels deep, limit is {} (query.parse_limits.max_condition_depth)",
+                depth, self.max_condition_depth
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn is_keyword(token: &Token, keyword: &str) -> bool {
+    matches!(token, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+}
+
+/// Values in the longest `IN (...)` list.
+fn largest_in_list(tokens: &[Token]) -> usize {
+    let mut largest = 0;
+    let mut i = 0;
+    while i + 1 < tokens.len() {
+        if is_keyword(&tokens[i], "IN") && tokens[i + 1] == Token::LeftParen {
+            let values = tokens[i + 2..]
+                .iter()
+                .take_while(|t| **t != Token::RightParen)
+                .filter(|t| **t != Token::Symbol(','))
+                .count();
+            largest = largest.max(values);
+        }
+        i += 1;
+    }
+    largest
+}
+
+/// One parenthesized level of a condition while its depth is measured.
+#[derive(Default)]
+struct Level {
+    /// ORs seen so far on this level
+    ors: usize,
+    /// ANDs seen so far in the current AND chain
+    ands: usize,
+    /// NOTs waiting for the next operand
+    nots: usize,
+    /// Depth of the operand being read, once it has started
+    operand: Option<usize>,
+    and_depth: usize,
+    or_depth: usize,
+}
+
+impl Level {
+    fn operand(&mut self, depth: usize) {
+        let depth = depth + std::mem::take(&mut self.nots);
+        self.operand = Some(self.operand.map_or(depth, |d| d.max(depth)));
+    }
+
+    fn and(&mut self) {
+        if let Some(operand) = self.operand.take() {
+            self.and_depth = self.and_depth.max(self.ands + 1 + operand);
+        }
+        self.ands += 1;
+    }
+
+    fn end_and_chain(&mut self) -> usize {
+        if let Some(operand) = self.operand.take() {
+            self.and_depth = self.and_depth.max(self.ands + operand);
+        }
+        self.ands = 0;
+        std::mem::take(&mut self.and_depth)
+    }
+
+    fn or(&mut self) {
+        let chain = self.end_and_chain();
+        self.or_depth = self.or_depth.max(self.ors + 1 + chain);
+        self.ors += 1;
+    }
+
+    fn end(mut self) -> usize {
+        let chain = self.end_and_chain();
+        self.or_depth.max(self.ors + chain)
+    }
+}
+
+/// Nesting depth of the deepest condition in `tokens`, following the grammar:
+/// AND and OR chains nest to the right, so each operand sits one level below
+/// the previous one. Parentheses count as a level since the grammar recurses
+/// into them. Measured without recursion, so any input is safe to check.
+fn condition_depth(tokens: &[Token]) -> usize {
+    let mut levels = vec![Level::default()];
+    for token in tokens {
+        match token {
+            Token::LeftParen => levels.push(Level::default()),
+            Token::RightParen if levels.len() > 1 => {
+                let depth = levels.pop().unwrap().end();
+                levels.last_mut().unwrap().operand(depth + 1);
+            }
+            t if is_keyword(t, "AND") => levels.last_mut().unwrap().and(),
+            t if is_keyword(t, "OR") => levels.last_mut().unwrap().or(),
+            t if is_keyword(t, "NOT") => levels.last_mut().unwrap().nots += 1,
+            _ => {
+                let level = levels.last_mut().unwrap();
+                if level.operand.is_none() || level.nots > 0 {
+                    level.operand(1);
+                }
+            }
+        }
+    }
+
+    // Unclosed parentheses are left for the grammar to report
+    while levels.len() > 1 {
+        let depth = levels.pop().unwrap().end();
+        levels.last_mut().unwrap().operand(depth + 1);
+    }
+    levels.pop().unwrap().end()
+}
@@ -0,0 +1,96 @@
+use crate::command::parser::error::ParseError;
+use crate::command::parser::limits::ParseLimits;
+use crate::command::parser::parse_command;
+use crate::command::parser::tokenizer::tokenize;
+use crate::shared::response::ErrorCode;
+
+fn limits(max_in_list: usize, max_condition_depth: usize) -> ParseLimits {
+    ParseLimits {
+        max_in_list,
+        max_condition_depth,
+    }
+}
+
+fn check(limits: ParseLimits, input: &str) -> Result<(), String> {
+    limits.check(&tokenize(input)).map_err(|e| e.to_string())
+}
+
+#[test]
+fn in_list_over_limit_is_rejected() {
+    let query = "QUERY order WHERE id IN (1, 2, 3) AND status IN (\"a\", \"b\")";
+    assert!(check(limits(3, 256), query).is_ok());
+
+    let err = check(limits(2, 256), query).unwrap_err();
+    assert_eq!(
+        err,
+        "Query exceeds parse limit: IN list has 3 values, limit is 2 (query.parse_limits.max_in_list)"
+    );
+}
+
+#[test]
+fn condition_depth_follows_the_grammar() {
+    // A comparison is 1 deep; chained ANDs and ORs nest to the right
+    assert!(check(limits(10, 1), "QUERY order WHERE a = 1").is_ok());
+    assert!(check(limits(10, 3), "QUERY order WHERE a = 1 OR b = 2 OR c = 3").is_ok());
+    assert!(check(limits(10, 2), "QUERY order WHERE a = 1 OR b = 2 OR c = 3").is_err());
+    assert!(check(limits(10, 3), "QUERY order WHERE a = 1 AND b = 2 OR c = 3").is_ok());
+
+    // NOT and parentheses each add a level
+    assert!(check(limits(10, 3), "QUERY order WHERE NOT (a = 1)").is_ok());
+    assert!(check(limits(10, 2), "QUERY order WHERE NOT (a = 1)").is_err());
+    assert!(
+        check(
+            limits(10, 4),
+            "QUERY order WHERE (a = 1 OR b = 2) AND c = 3"
+        )
+        .is_ok()
+    );
+    assert!(
+        check(
+            limits(10, 3),
+            "QUERY order WHERE (a = 1 OR b = 2) AND c = 3"
+        )
+        .is_err()
+    );
+
+    let err = check(limits(10, 8), "QUERY order WHERE ((((((((a = 1))))))))").unwrap_err();
+    assert_eq!(
+        err,
+        "Query exceeds parse limit: condition is nested 9 levels deep, limit is 8 (query.parse_limits.max_condition_depth)"
+    );
+}
+
+#[test]
+fn oversized_conditions_fail_before_the_grammar_runs() {
+    // Deep enough to overflow the stack if it reached the recursive grammar
+    let nested = format!(
+        "QUERY order WHERE {}a = 1{}",
+        "(".repeat(100_000),
+        ")".repeat(100_000)
+    );
+    let err = parse_command(&nested).unwrap_err();
+    assert!(matches!(err, ParseError::LimitExceeded(_)));
+    assert_eq!(err.code(), ErrorCode::QueryTooComplex);
+    assert!(err.to_string().contains("100001 levels deep"));
+
+    let chained = format!(
+        "QUERY order WHERE {}",
+        (0..50_000)
+            .map(|i| format!("id = {}", i))
+            .collect::<Vec<_>>()
+            .join(" OR ")
+    );
+    assert!(matches!(
+        parse_command(&chained),
+        Err(ParseError::LimitExceeded(_))
+    ));
+
+    // Ordinary queries stay well within the defaults
+    let cmd =
+        parse_command("QUERY order WHERE id IN (1, 2) AND NOT (status = \"x\" OR amount > 5)");
+    assert!(cmd.is_ok());
+    assert_eq!(
+        ParseError::UnexpectedToken("x".into()).code(),
+        ErrorCode::ParseError
+    );
+}
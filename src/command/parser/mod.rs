@@ -1,10 +1,13 @@
 pub mod command;
 pub mod commands;
 pub mod error;
+pub mod limits;
 pub mod tokenizer;
 
 pub use command::parse_command;
 pub use error::ParseError;
 
+#[cfg(test)]
+mod limits_tests;
 #[cfg(test)]
 mod tokenizer_tests;
@@ -23,6 +23,10 @@ pub fn tokenize(input: &str) -> Vec<Token> {
             ' ' | '\t' | '\n' | '\r' => {
                 chars.next();
             }
+            // Block comments such as query hints are left to the grammar
+            '/' if chars.clone().nth(1) == Some('*') => {
+                skip_block_comment(&mut chars);
+            }
             '{' => {
                 chars.next();
                 tokens.push(Token::LeftBrace);
@@ -70,6 +74,22 @@ pub fn tokenize(input: &str) -> Vec<Token> {
     tokens
 }
 
+/// Consumes `/* ... */`, or the rest of the input when it is unterminated.
+fn skip_block_comment<I>(chars: &mut std::iter::Peekable<I>)
+where
+    I: Iterator<Item = char>,
+{
+    chars.next();
+    chars.next();
+    let mut previous = None;
+    for c in chars.by_ref() {
+        if previous == Some('*') && c == '/' {
+            break;
+        }
+        previous = Some(c);
+    }
+}
+
 fn parse_string_literal<I>(chars: &mut std::iter::Peekable<I>) -> Token
 where
     I: Iterator<Item = char>,
@@ -1032,4 +1032,16 @@ mod tokenizer_tests {
             ]
         );
     }
+
+    #[test]
+    fn test_tokenize_skips_block_comments() {
+        let tokens = tokenize("QUERY /* allow_full_scan */ orders /* unterminated");
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Word("QUERY".to_string()),
+                Token::Word("orders".to_string()),
+            ]
+        );
+    }
 }
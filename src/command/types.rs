@@ -1,6 +1,6 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Command {
@@ -31,6 +31,21 @@ pub enum Command {
         time_bucket: Option<TimeGranularity>,
         group_by: Option<Vec<String>>,
         event_sequence: Option<EventSequence>,
+        latest_per: Option<String>,
+        #[serde(default)]
+        dedup: Option<DedupSpec>,
+        #[serde(default)]
+        omit_nulls: bool,
+        #[serde(default)]
+        with_total: bool,
+        #[serde(default)]
+        raw_strings: bool,
+        #[serde(default)]
+        disabled_pruners: Vec<PrunerKind>,
+        #[serde(default)]
+        allow_full_scan: bool,
+        #[serde(default)]
+        unnest: Option<UnnestSpec>,
     },
     RememberQuery {
         spec: MaterializedQuerySpec,
@@ -38,19 +53,81 @@ pub enum Command {
     ShowMaterialized {
         name: String,
     },
+    /// Describes how the wrapped query would run, without running it.
+    /// Describes how `query` would run; with `analyze`, runs it and adds
+    /// what it read.
+    Explain {
+        query: Box<Command>,
+        analyze: bool,
+    },
     Replay {
         event_type: Option<String>,
         context_id: String,
         since: Option<String>,
         time_field: Option<String>,
         return_fields: Option<Vec<String>>,
+        desc: bool,
+        limit: Option<u32>,
+    },
+    GetEvent {
+        id: u64,
     },
     Ping,
     Flush,
+    ShowPinnedSegments,
+    ShowStats,
+    /// Per-user resource usage; `reset` zeroes it after reporting it.
+    ShowUsage {
+        reset: bool,
+    },
+    InspectZone {
+        event_type: String,
+        field: String,
+        shard_id: usize,
+        segment_id: u64,
+        zone_id: u32,
+    },
+    /// The last `limit` entries of a shard's WAL, of `event_type` only when given.
+    InspectWal {
+        shard_id: usize,
+        event_type: Option<String>,
+        limit: usize,
+    },
+    /// Checks stored frames against their manifest and catalog entry; all
+    /// materializations when `name` is `None`.
+    VerifyMaterialized {
+        name: Option<String>,
+    },
+    /// Rebuilds the given index kinds (all when empty) from stored columns, for
+    /// one segment or, when `segment_id` is `None`, every segment of every shard.
+    RebuildIndexes {
+        kinds: Vec<RebuildIndexKind>,
+        shard_id: Option<usize>,
+        segment_id: Option<u64>,
+    },
+    /// Lists the index artifacts on disk for `event_type`, in one segment or,
+    /// when `segment_id` is `None`, every segment of every shard.
+    ShowIndexes {
+        event_type: String,
+        shard_id: Option<usize>,
+        segment_id: Option<u64>,
+    },
+    /// Writes every schema as one catalog document.
+    ExportSchemas,
+    /// Creates or updates schemas from a document written by `ExportSchemas`.
+    ImportSchemas {
+        document: Value,
+    },
     Batch(Vec<Command>),
     Compare {
         queries: Vec<QueryCommand>,
     },
+    Union {
+        queries: Vec<QueryCommand>,
+        order_by: Option<OrderSpec>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    },
     CreateUser {
         user_id: String,
         secret_key: Option<String>,
@@ -59,7 +136,22 @@ pub enum Command {
     RevokeKey {
         user_id: String,
     },
+    RotateKey {
+        user_id: String,
+        secret_key: Option<String>,
+        revoke_sessions: bool,
+    },
     ListUsers,
+    ShowUsers,
+    DisableUser {
+        user_id: String,
+    },
+    EnableUser {
+        user_id: String,
+    },
+    UnlockUser {
+        user_id: String,
+    },
     GrantPermission {
         permissions: Vec<String>, // ["read", "write"]
         event_types: Vec<String>, // ["order_created", "payment_succeeded"]
@@ -100,6 +192,21 @@ pub struct QueryCommand {
     pub time_bucket: Option<TimeGranularity>,
     pub group_by: Option<Vec<String>>,
     pub event_sequence: Option<EventSequence>,
+    pub latest_per: Option<String>,
+    #[serde(default)]
+    pub dedup: Option<DedupSpec>,
+    #[serde(default)]
+    pub omit_nulls: bool,
+    #[serde(default)]
+    pub with_total: bool,
+    #[serde(default)]
+    pub raw_strings: bool,
+    #[serde(default)]
+    pub disabled_pruners: Vec<PrunerKind>,
+    #[serde(default)]
+    pub allow_full_scan: bool,
+    #[serde(default)]
+    pub unnest: Option<UnnestSpec>,
 }
 
 impl From<&Command> for QueryCommand {
@@ -122,6 +229,14 @@ impl From<&Command> for QueryCommand {
                 time_bucket,
                 group_by,
                 event_sequence,
+                latest_per,
+                dedup,
+                omit_nulls,
+                with_total,
+                raw_strings,
+                disabled_pruners,
+                allow_full_scan,
+                unnest,
             } => QueryCommand {
                 event_type: event_type.clone(),
                 context_id: context_id.clone(),
@@ -139,6 +254,14 @@ impl From<&Command> for QueryCommand {
                 time_bucket: time_bucket.clone(),
                 group_by: group_by.clone(),
                 event_sequence: event_sequence.clone(),
+                latest_per: latest_per.clone(),
+                dedup: dedup.clone(),
+                omit_nulls: *omit_nulls,
+                with_total: *with_total,
+                raw_strings: *raw_strings,
+                disabled_pruners: disabled_pruners.clone(),
+                allow_full_scan: *allow_full_scan,
+                unnest: unnest.clone(),
             },
             _ => panic!("Command is not a Query"),
         }
@@ -164,6 +287,14 @@ impl From<QueryCommand> for Command {
             time_bucket: qc.time_bucket,
             group_by: qc.group_by,
             event_sequence: qc.event_sequence,
+            latest_per: qc.latest_per,
+            dedup: qc.dedup,
+            omit_nulls: qc.omit_nulls,
+            with_total: qc.with_total,
+            raw_strings: qc.raw_strings,
+            disabled_pruners: qc.disabled_pruners,
+            allow_full_scan: qc.allow_full_scan,
+            unnest: qc.unnest,
         }
     }
 }
@@ -176,6 +307,8 @@ impl Command {
             since,
             time_field,
             return_fields,
+            limit,
+            ..
         } = self
         {
             Some(Command::Query {
@@ -185,7 +318,7 @@ impl Command {
                 time_field: time_field.clone(),
                 sequence_time_field: None,
                 where_clause: None,
-                limit: None,
+                limit: *limit,
                 offset: None,
                 order_by: None,
                 picked_zones: None,
@@ -195,6 +328,14 @@ impl Command {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
             })
         } else {
             None
@@ -217,13 +358,23 @@ impl Command {
         }
     }
 
+    /// Pruners switched off by `DISABLE PRUNERS`; empty for other commands.
+    pub fn disabled_pruners(&self) -> &[PrunerKind] {
+        match self {
+            Command::Query {
+                disabled_pruners, ..
+            } => disabled_pruners,
+            _ => &[],
+        }
+    }
+
     pub fn is_comparison_query(&self) -> bool {
         matches!(self, Command::Compare { .. })
     }
 
     pub fn to_query_commands(&self) -> Option<Vec<QueryCommand>> {
         match self {
-            Command::Compare { queries } => Some(queries.clone()),
+            Command::Compare { queries } | Command::Union { queries, .. } => Some(queries.clone()),
             Command::Query { .. } => Some(vec![QueryCommand::from(self)]),
             _ => None,
         }
@@ -232,7 +383,17 @@ impl Command {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MiniSchema {
-    pub fields: HashMap<String, FieldSpec>,
+    /// Fields in the order they were declared
+    pub fields: IndexMap<String, FieldSpec>,
+    /// Field declared with `USING` as the schema's temporal/ordering field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_field: Option<String>,
+    /// Further time dimensions listed after the first `USING` field
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub temporal_fields: Vec<String>,
+    /// Index artifacts chosen with `INDEXES`; fields not listed get the defaults for their type
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub indexes: IndexMap<String, Vec<FieldIndexKind>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -270,12 +431,33 @@ pub enum CompareOp {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AggSpec {
-    Count { unique_field: Option<String> },
-    CountField { field: String },
-    Total { field: String },
-    Avg { field: String },
-    Min { field: String },
-    Max { field: String },
+    Count {
+        unique_field: Option<String>,
+    },
+    /// `COUNT(DISTINCT field)`: always estimated with a HyperLogLog sketch.
+    CountDistinct {
+        field: String,
+    },
+    CountField {
+        field: String,
+    },
+    /// `PERCENTILE(field, p)` with `p` between 0 and 100.
+    Percentile {
+        field: String,
+        percentile: f64,
+    },
+    Total {
+        field: String,
+    },
+    Avg {
+        field: String,
+    },
+    Min {
+        field: String,
+    },
+    Max {
+        field: String,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -284,6 +466,185 @@ pub struct OrderSpec {
     pub desc: bool,
 }
 
+/// `DEDUP BY key[, ...] KEEP FIRST|LATEST`: one row per distinct key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DedupSpec {
+    pub keys: Vec<String>,
+    pub keep: DedupKeep,
+}
+
+/// Which row of a duplicate group survives, by the query's time field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DedupKeep {
+    First,
+    Latest,
+}
+
+/// `UNNEST(field)`: one row per element of a JSON array field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnnestSpec {
+    pub field: String,
+    #[serde(default)]
+    pub on_empty: UnnestEmpty,
+    #[serde(default)]
+    pub on_non_array: UnnestNonArray,
+}
+
+impl UnnestSpec {
+    pub fn new(field: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            on_empty: UnnestEmpty::default(),
+            on_non_array: UnnestNonArray::default(),
+        }
+    }
+}
+
+/// What `UNNEST` emits for a row whose array is empty or null.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnnestEmpty {
+    /// The row produces no output (`ON EMPTY SKIP`).
+    #[default]
+    Skip,
+    /// The row is emitted once with the unnested column set to null
+    /// (`ON EMPTY NULL`).
+    NullRow,
+}
+
+/// What `UNNEST` does with a value that is not a JSON array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnnestNonArray {
+    /// The query fails (`ON NON ARRAY ERROR`).
+    #[default]
+    Error,
+    /// The row is emitted unchanged, as if the value were a one-element array
+    /// (`ON NON ARRAY PASSTHROUGH`).
+    Passthrough,
+}
+
+/// A zone pruner that `DISABLE PRUNERS` can switch off for one query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PrunerKind {
+    /// XOR filters: zone index for equality and field presence.
+    Xor,
+    /// ZoneSuRF range index.
+    Surf,
+    /// Every range comparison, whether answered by SuRF or the temporal index.
+    Range,
+    /// Temporal calendar and slab index.
+    Temporal,
+    /// Enum bitmaps.
+    Enum,
+    /// Zones already covered by a materialization.
+    Materialization,
+}
+
+impl PrunerKind {
+    pub const ALL: [PrunerKind; 6] = [
+        PrunerKind::Xor,
+        PrunerKind::Surf,
+        PrunerKind::Range,
+        PrunerKind::Temporal,
+        PrunerKind::Enum,
+        PrunerKind::Materialization,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrunerKind::Xor => "xor",
+            PrunerKind::Surf => "surf",
+            PrunerKind::Range => "range",
+            PrunerKind::Temporal => "temporal",
+            PrunerKind::Enum => "enum",
+            PrunerKind::Materialization => "materialization",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+/// A family of zone index artifacts that `REBUILD INDEXES` can rebuild.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RebuildIndexKind {
+    /// ZoneSuRF range filters (`.zsrf`).
+    Surf,
+    /// Segment and per-zone XOR filters (`.xf`, `.zxf`).
+    Xor,
+    /// Enum bitmaps (`.ebm`).
+    Enum,
+    /// Calendars and temporal slabs (`.cal`, `.tfi`).
+    Temporal,
+}
+
+impl RebuildIndexKind {
+    pub const ALL: [RebuildIndexKind; 4] = [
+        RebuildIndexKind::Surf,
+        RebuildIndexKind::Xor,
+        RebuildIndexKind::Enum,
+        RebuildIndexKind::Temporal,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebuildIndexKind::Surf => "surf",
+            RebuildIndexKind::Xor => "xor",
+            RebuildIndexKind::Enum => "enum",
+            RebuildIndexKind::Temporal => "temporal",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+/// An index artifact a schema can choose to build for a field at flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FieldIndexKind {
+    /// ZoneSuRF range filter (`.zsrf`).
+    Surf,
+    /// Segment and per-zone XOR filters (`.xf`, `.zxf`).
+    Xor,
+    /// Enum bitmap (`.ebm`); enum fields only.
+    Enum,
+    /// Calendar and temporal slab (`.cal`, `.tfi`); datetime, date and time fields only.
+    Temporal,
+    /// Value histogram in `{uid}.hist`, used for selectivity estimates.
+    Histogram,
+}
+
+impl FieldIndexKind {
+    pub const ALL: [FieldIndexKind; 5] = [
+        FieldIndexKind::Surf,
+        FieldIndexKind::Xor,
+        FieldIndexKind::Enum,
+        FieldIndexKind::Temporal,
+        FieldIndexKind::Histogram,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FieldIndexKind::Surf => "surf",
+            FieldIndexKind::Xor => "xor",
+            FieldIndexKind::Enum => "enum",
+            FieldIndexKind::Temporal => "temporal",
+            FieldIndexKind::Histogram => "histogram",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PickedZones {
     pub uid: String,
@@ -301,6 +662,40 @@ pub enum TimeGranularity {
     Week,
     Month,
     Year,
+    /// Fixed-width buckets of `seconds`, aligned to `origin` (epoch seconds)
+    /// and ignoring time zones. With `fill`, buckets without events between
+    /// the first and last one come back as empty rows.
+    Interval {
+        seconds: u64,
+        origin: u64,
+        fill: bool,
+    },
+    /// Calendar days, ISO weeks, months or years in `timezone` (the server's
+    /// `[time] timezone` when `None`), whatever `use_calendar_bucketing` says.
+    Calendar {
+        unit: CalendarUnit,
+        timezone: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CalendarUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl CalendarUnit {
+    /// The matching fixed granularity, bucketed by the calendar bucketer.
+    pub fn granularity(&self) -> TimeGranularity {
+        match self {
+            CalendarUnit::Day => TimeGranularity::Day,
+            CalendarUnit::Week => TimeGranularity::Week,
+            CalendarUnit::Month => TimeGranularity::Month,
+            CalendarUnit::Year => TimeGranularity::Year,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -319,4 +714,8 @@ pub struct EventTarget {
 pub struct EventSequence {
     pub head: EventTarget,
     pub links: Vec<(SequenceLink, EventTarget)>,
+    /// Longest gap in seconds allowed between each event and the one matched
+    /// before it (`WITHIN 30m`); a gap of exactly the window still matches.
+    #[serde(default)]
+    pub within: Option<u64>,
 }
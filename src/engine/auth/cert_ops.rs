@@ -0,0 +1,50 @@
+use super::types::{AuthError, AuthResult, ClientCertIdentity, UserCache};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Resolves a client certificate to a SnelDB user id.
+///
+/// The certificate must have passed chain verification in the TLS layer. The mapping is
+/// looked up by full subject DN first, then by common name. The mapped user must exist
+/// and be active.
+pub async fn resolve_client_cert_user(
+    cache: &Arc<RwLock<UserCache>>,
+    mapping: &HashMap<String, String>,
+    identity: &ClientCertIdentity,
+) -> AuthResult<String> {
+    if !identity.verified {
+        warn!(
+            target: "sneldb::auth",
+            subject = %identity.subject,
+            "Rejected client certificate that failed chain verification"
+        );
+        return Err(AuthError::UntrustedClientCertificate);
+    }
+
+    let user_id = mapping
+        .get(&identity.subject)
+        .or_else(|| identity.common_name.as_ref().and_then(|cn| mapping.get(cn)))
+        .ok_or_else(|| {
+            warn!(
+                target: "sneldb::auth",
+                subject = %identity.subject,
+                "No user mapped to client certificate"
+            );
+            AuthError::UnmappedClientCertificate(identity.subject.clone())
+        })?;
+
+    let cache_guard = cache.read().await;
+    let user_key = cache_guard.get(user_id).ok_or_else(|| {
+        debug!(target: "sneldb::auth", user_id, "Mapped certificate user not found");
+        AuthError::AuthenticationFailed
+    })?;
+
+    if !user_key.active {
+        debug!(target: "sneldb::auth", user_id, "Mapped certificate user inactive");
+        return Err(AuthError::AuthenticationFailed);
+    }
+
+    Ok(user_id.clone())
+}
@@ -0,0 +1,134 @@
+use super::cert_ops::resolve_client_cert_user;
+use super::types::{AuthError, ClientCertIdentity, UserCache, UserKey};
+use crate::logging::init_for_tests;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Helper function to create a test user cache with a user
+async fn create_test_cache_with_user(user_id: &str, active: bool) -> Arc<RwLock<UserCache>> {
+    let cache = Arc::new(RwLock::new(UserCache::new()));
+    let user_key = UserKey {
+        user_id: user_id.to_string(),
+        secret_key: "secret".to_string(),
+        active,
+        created_at: 1000,
+        roles: Vec::new(),
+        permissions: HashMap::new(),
+        locked_until: None,
+    };
+    cache.write().await.insert(user_key);
+    cache
+}
+
+fn mapping(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_resolve_by_full_subject() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("ingest_user", true).await;
+    let map = mapping(&[("CN=ingest,O=Acme", "ingest_user")]);
+    let identity = ClientCertIdentity::verified("CN=ingest,O=Acme", Some("ingest".to_string()));
+
+    let user_id = resolve_client_cert_user(&cache, &map, &identity)
+        .await
+        .unwrap();
+    assert_eq!(user_id, "ingest_user");
+}
+
+#[tokio::test]
+async fn test_resolve_by_common_name() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("ingest_user", true).await;
+    let map = mapping(&[("ingest", "ingest_user")]);
+    let identity = ClientCertIdentity::verified("CN=ingest,O=Acme", Some("ingest".to_string()));
+
+    let user_id = resolve_client_cert_user(&cache, &map, &identity)
+        .await
+        .unwrap();
+    assert_eq!(user_id, "ingest_user");
+}
+
+#[tokio::test]
+async fn test_resolve_rejects_unverified_certificate() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("ingest_user", true).await;
+    let map = mapping(&[("ingest", "ingest_user")]);
+    let mut identity = ClientCertIdentity::verified("CN=ingest,O=Acme", Some("ingest".to_string()));
+    identity.verified = false;
+
+    let result = resolve_client_cert_user(&cache, &map, &identity).await;
+    assert!(matches!(result, Err(AuthError::UntrustedClientCertificate)));
+}
+
+#[tokio::test]
+async fn test_resolve_rejects_unmapped_certificate() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("ingest_user", true).await;
+    let map = mapping(&[("ingest", "ingest_user")]);
+    let identity = ClientCertIdentity::verified("CN=other,O=Acme", Some("other".to_string()));
+
+    let result = resolve_client_cert_user(&cache, &map, &identity).await;
+    match result {
+        Err(AuthError::UnmappedClientCertificate(subject)) => {
+            assert_eq!(subject, "CN=other,O=Acme")
+        }
+        other => panic!("expected UnmappedClientCertificate, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_resolve_rejects_inactive_or_missing_user() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("ingest_user", false).await;
+    let map = mapping(&[("ingest", "ingest_user"), ("ghost", "missing_user")]);
+
+    let inactive = ClientCertIdentity::verified("CN=ingest", Some("ingest".to_string()));
+    let result = resolve_client_cert_user(&cache, &map, &inactive).await;
+    assert!(matches!(result, Err(AuthError::AuthenticationFailed)));
+
+    let missing = ClientCertIdentity::verified("CN=ghost", Some("ghost".to_string()));
+    let result = resolve_client_cert_user(&cache, &map, &missing).await;
+    assert!(matches!(result, Err(AuthError::AuthenticationFailed)));
+}
+
+#[tokio::test]
+async fn test_resolve_ignores_cn_inside_another_attribute() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("admin", true).await;
+    let map = mapping(&[("admin", "admin")]);
+    // An organization named "Acme,CN=admin": the subject has no CN at all
+    let identity = ClientCertIdentity::verified("O=Acme\\,CN=admin", None);
+
+    let result = resolve_client_cert_user(&cache, &map, &identity).await;
+    assert!(matches!(
+        result,
+        Err(AuthError::UnmappedClientCertificate(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_resolve_by_common_name_with_comma() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("ingest_user", true).await;
+    let map = mapping(&[("ingest, west", "ingest_user")]);
+    let identity =
+        ClientCertIdentity::verified("CN=ingest\\, west,O=Acme", Some("ingest, west".to_string()));
+
+    let user_id = resolve_client_cert_user(&cache, &map, &identity)
+        .await
+        .unwrap();
+    assert_eq!(user_id, "ingest_user");
+}
@@ -38,6 +38,7 @@ async fn test_store_user_in_db_success() {
         created_at: 1000,
         roles: vec!["admin".to_string()],
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -61,6 +62,7 @@ async fn test_store_user_in_db_with_permissions() {
         created_at: 1000,
         roles: vec!["admin".to_string(), "read-only".to_string()],
         permissions,
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -80,6 +82,7 @@ async fn test_store_user_in_db_inactive_user() {
         created_at: 1000,
         roles: Vec::new(),
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -99,6 +102,7 @@ async fn test_store_user_in_db_empty_roles() {
         created_at: 1000,
         roles: Vec::new(),
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -118,6 +122,7 @@ async fn test_store_user_in_db_empty_permissions() {
         created_at: 1000,
         roles: vec!["admin".to_string()],
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -143,6 +148,7 @@ async fn test_store_user_in_db_multiple_users() {
                 Vec::new()
             },
             permissions: HashMap::new(),
+            locked_until: None,
         };
 
         let result = store_user_in_db(&auth_storage, &user).await;
@@ -166,6 +172,7 @@ async fn test_store_user_in_db_with_special_characters() {
             "role_with_underscore".to_string(),
         ],
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -187,6 +194,7 @@ async fn test_store_user_in_db_large_roles_list() {
         created_at: 1000,
         roles,
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -211,6 +219,7 @@ async fn test_store_user_in_db_large_permissions_map() {
         created_at: 1000,
         roles: Vec::new(),
         permissions,
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -230,6 +239,7 @@ async fn test_store_user_in_db_zero_created_at() {
         created_at: 0,
         roles: Vec::new(),
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -249,6 +259,7 @@ async fn test_store_user_in_db_large_created_at() {
         created_at: u64::MAX,
         roles: Vec::new(),
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -300,6 +311,7 @@ async fn test_store_user_in_db_updates_existing_user() {
         created_at: 1000,
         roles: vec!["role1".to_string()],
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user1).await;
@@ -313,6 +325,7 @@ async fn test_store_user_in_db_updates_existing_user() {
         created_at: 2000,
         roles: vec!["role2".to_string()],
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user2).await;
@@ -338,6 +351,7 @@ async fn test_store_user_in_db_all_permission_types() {
         created_at: 1000,
         roles: Vec::new(),
         permissions,
+        locked_until: None,
     };
 
     let result = store_user_in_db(&auth_storage, &user).await;
@@ -380,6 +394,7 @@ async fn test_load_from_db_populates_caches_with_permissions() {
         created_at: 10,
         roles: vec!["admin".to_string()],
         permissions,
+        locked_until: None,
     };
     store_user_in_db(&auth_storage, &user)
         .await
@@ -418,6 +433,7 @@ async fn test_load_from_db_latest_event_wins() {
         created_at: 1,
         roles: vec![],
         permissions: HashMap::new(),
+        locked_until: None,
     };
     store_user_in_db(&auth_storage, &first)
         .await
@@ -434,6 +450,7 @@ async fn test_load_from_db_latest_event_wins() {
         created_at: 2,
         roles: vec!["auditor".to_string()],
         permissions: updated_perms.clone(),
+        locked_until: None,
     };
     store_user_in_db(&auth_storage, &second)
         .await
@@ -470,6 +487,7 @@ async fn test_load_from_db_skips_corrupted_events() {
         created_at: 3,
         roles: vec![],
         permissions: HashMap::new(),
+        locked_until: None,
     };
     store_user_in_db(&auth_storage, &user)
         .await
@@ -504,6 +522,7 @@ async fn test_load_from_db_resets_old_cache_entries() {
                 created_at: 0,
                 roles: vec![],
                 permissions: HashMap::new(),
+                locked_until: None,
             })
             .into(),
         );
@@ -526,6 +545,7 @@ async fn test_load_from_db_resets_old_cache_entries() {
         created_at: 4,
         roles: vec![],
         permissions: HashMap::new(),
+        locked_until: None,
     };
     store_user_in_db(&auth_storage, &fresh)
         .await
@@ -0,0 +1,114 @@
+use crate::shared::config::CONFIG;
+use std::collections::HashMap;
+
+// Bound on tracked failure windows; stale ones are pruned past it
+const MAX_TRACKED_WINDOWS: usize = 4096;
+
+/// Account lockout settings from `[auth]`. Absent when `lockout_max_failures` is 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockoutPolicy {
+    pub max_failures: u32,
+    pub window_secs: u64,
+    pub cooldown_secs: u64,
+    /// Count failures per (user, client address) instead of per user
+    pub per_source: bool,
+}
+
+impl LockoutPolicy {
+    pub fn from_config() -> Option<Self> {
+        let cfg = CONFIG.auth.as_ref()?;
+        if cfg.lockout_max_failures == 0 {
+            return None;
+        }
+        Some(Self {
+            max_failures: cfg.lockout_max_failures,
+            window_secs: cfg.lockout_window_seconds,
+            cooldown_secs: cfg.lockout_cooldown_seconds,
+            per_source: cfg.lockout_per_source,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FailureWindow {
+    count: u32,
+    started_at: u64,
+}
+
+/// Consecutive signature failures per user (and optionally per source).
+/// Counters are in-memory; the resulting lock is persisted on the user record.
+#[derive(Debug, Default)]
+pub struct FailureTracker {
+    windows: HashMap<(String, Option<String>), FailureWindow>,
+}
+
+impl FailureTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(
+        policy: &LockoutPolicy,
+        user_id: &str,
+        source: Option<&str>,
+    ) -> (String, Option<String>) {
+        let source = if policy.per_source {
+            source.map(str::to_string)
+        } else {
+            None
+        };
+        (user_id.to_string(), source)
+    }
+
+    /// Records a failure at `now` and returns the lock deadline once the limit is reached.
+    /// The user's counters start over after tripping.
+    pub fn record_failure(
+        &mut self,
+        policy: &LockoutPolicy,
+        user_id: &str,
+        source: Option<&str>,
+        now: u64,
+    ) -> Option<u64> {
+        if self.windows.len() >= MAX_TRACKED_WINDOWS {
+            self.windows
+                .retain(|_, w| now.saturating_sub(w.started_at) < policy.window_secs);
+        }
+
+        let window = self
+            .windows
+            .entry(Self::key(policy, user_id, source))
+            .or_insert(FailureWindow {
+                count: 0,
+                started_at: now,
+            });
+        if now.saturating_sub(window.started_at) >= policy.window_secs {
+            *window = FailureWindow {
+                count: 0,
+                started_at: now,
+            };
+        }
+        window.count += 1;
+
+        if window.count < policy.max_failures {
+            return None;
+        }
+        self.clear_user(user_id);
+        Some(now.saturating_add(policy.cooldown_secs))
+    }
+
+    /// Forgets failures after a successful authentication.
+    pub fn reset(&mut self, policy: &LockoutPolicy, user_id: &str, source: Option<&str>) {
+        self.windows.remove(&Self::key(policy, user_id, source));
+    }
+
+    /// Forgets every failure counted against `user_id`.
+    pub fn clear_user(&mut self, user_id: &str) {
+        self.windows.retain(|(user, _), _| user != user_id);
+    }
+
+    pub fn failures(&self, policy: &LockoutPolicy, user_id: &str, source: Option<&str>) -> u32 {
+        self.windows
+            .get(&Self::key(policy, user_id, source))
+            .map_or(0, |w| w.count)
+    }
+}
@@ -0,0 +1,79 @@
+use super::lockout::{FailureTracker, LockoutPolicy};
+
+const NOW: u64 = 1_700_000_000;
+
+fn policy(per_source: bool) -> LockoutPolicy {
+    LockoutPolicy {
+        max_failures: 3,
+        window_secs: 60,
+        cooldown_secs: 900,
+        per_source,
+    }
+}
+
+#[test]
+fn locks_after_max_failures_within_window() {
+    let policy = policy(false);
+    let mut tracker = FailureTracker::new();
+
+    assert_eq!(tracker.record_failure(&policy, "alice", None, NOW), None);
+    assert_eq!(
+        tracker.record_failure(&policy, "alice", None, NOW + 1),
+        None
+    );
+    assert_eq!(
+        tracker.record_failure(&policy, "alice", None, NOW + 2),
+        Some(NOW + 2 + 900)
+    );
+    // Counting starts over once the lock is handed out
+    assert_eq!(tracker.failures(&policy, "alice", None), 0);
+}
+
+#[test]
+fn failures_outside_window_start_a_new_count() {
+    let policy = policy(false);
+    let mut tracker = FailureTracker::new();
+
+    tracker.record_failure(&policy, "alice", None, NOW);
+    tracker.record_failure(&policy, "alice", None, NOW + 1);
+    assert_eq!(
+        tracker.record_failure(&policy, "alice", None, NOW + 60),
+        None
+    );
+    assert_eq!(tracker.failures(&policy, "alice", None), 1);
+}
+
+#[test]
+fn reset_clears_the_counter() {
+    let policy = policy(false);
+    let mut tracker = FailureTracker::new();
+
+    tracker.record_failure(&policy, "alice", Some("10.0.0.1"), NOW);
+    tracker.record_failure(&policy, "alice", Some("10.0.0.2"), NOW);
+    tracker.reset(&policy, "alice", Some("10.0.0.3"));
+    assert_eq!(tracker.failures(&policy, "alice", None), 0);
+    assert_eq!(tracker.record_failure(&policy, "alice", None, NOW), None);
+}
+
+#[test]
+fn per_source_counts_each_address_separately() {
+    let policy = policy(true);
+    let mut tracker = FailureTracker::new();
+
+    tracker.record_failure(&policy, "alice", Some("10.0.0.1"), NOW);
+    tracker.record_failure(&policy, "alice", Some("10.0.0.1"), NOW);
+    assert_eq!(
+        tracker.record_failure(&policy, "alice", Some("10.0.0.2"), NOW),
+        None
+    );
+    assert_eq!(tracker.failures(&policy, "alice", Some("10.0.0.1")), 2);
+    assert_eq!(tracker.failures(&policy, "bob", Some("10.0.0.1")), 0);
+
+    // A success from one address leaves the others' counts alone
+    tracker.reset(&policy, "alice", Some("10.0.0.2"));
+    assert!(
+        tracker
+            .record_failure(&policy, "alice", Some("10.0.0.1"), NOW)
+            .is_some()
+    );
+}
@@ -1,18 +1,29 @@
+use crate::engine::auth::cert_ops::resolve_client_cert_user;
 use crate::engine::auth::db_ops::load_from_db;
+use crate::engine::auth::lockout::{FailureTracker, LockoutPolicy};
 use crate::engine::auth::permission_ops::{get_permissions, grant_permission, revoke_permission};
-use crate::engine::auth::signature::{parse_auth, verify_signature};
+use crate::engine::auth::replay::ReplayGuard;
+use crate::engine::auth::session_token::{SessionClaims, SessionDenyList, StatelessTokenCodec};
+use crate::engine::auth::signature::{
+    ReplayStamp, SignatureFailure, check_signature, check_signature_batch, parse_auth,
+    split_stamped_signature,
+};
 use crate::engine::auth::storage::{AuthStorage, AuthWalStorage};
 use crate::engine::auth::types::{
-    AuthRateLimiter, AuthResult, PermissionCache, PermissionSet, SessionStore, SessionToken,
-    UserCache, UserKey, create_rate_limiter,
+    AuthError, AuthRateLimiter, AuthResult, ClientCertIdentity, MAX_SESSION_TOKEN_LENGTH,
+    PermissionCache, PermissionSet, SessionStore, SessionToken, UserCache, UserKey,
+    create_rate_limiter,
 };
 use crate::engine::auth::user_ops::{
-    bootstrap_admin_user, create_user, create_user_with_roles, list_users, revoke_key,
+    bootstrap_admin_user, create_user, create_user_with_roles, list_users, revoke_key, rotate_key,
+    set_locked_until, set_user_active,
 };
 use crate::engine::shard::manager::ShardManager;
 use crate::shared::config::CONFIG;
+use crate::shared::log_sampler::LogSampler;
 use hex;
 use rand::RngCore;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -21,7 +32,8 @@ use tokio::time::{Duration, interval};
 
 /// Manages authentication (HMAC-SHA256) and authorization (per-event permissions, RBAC).
 /// Uses in-memory caches for O(1) lookups and optional rate limiting.
-/// Also manages session tokens for high-throughput WebSocket authentication.
+/// Also manages session tokens for high-throughput WebSocket authentication, either opaque
+/// ones kept in the session store or signed stateless ones any node sharing the key accepts.
 pub struct AuthManager {
     cache: Arc<RwLock<UserCache>>,
     permission_cache: Arc<RwLock<PermissionCache>>,
@@ -29,6 +41,17 @@ pub struct AuthManager {
     _shard_manager: Arc<ShardManager>,
     storage: Arc<dyn AuthStorage>,
     rate_limiter: Option<Arc<Mutex<AuthRateLimiter>>>,
+    client_cert_users: Arc<RwLock<HashMap<String, String>>>,
+    /// Serializes read-modify-write updates of user records, so a concurrent
+    /// update cannot write back a secret key that was just rotated
+    user_mutations: Mutex<()>,
+    lockout: Option<LockoutPolicy>,
+    failures: RwLock<FailureTracker>,
+    replay_guard: ReplayGuard,
+    /// Set when stateless session tokens are enabled
+    token_codec: Option<StatelessTokenCodec>,
+    /// Revoked stateless tokens, kept until they would have expired anyway
+    deny_list: Arc<RwLock<SessionDenyList>>,
 }
 
 impl AuthManager {
@@ -52,8 +75,16 @@ impl AuthManager {
             }
         });
 
+        let client_cert_users = CONFIG
+            .auth
+            .as_ref()
+            .map(|auth_cfg| auth_cfg.client_cert_users.clone())
+            .unwrap_or_default();
+
         let session_store = Arc::new(RwLock::new(SessionStore::new()));
         let session_store_clone = Arc::clone(&session_store);
+        let deny_list = Arc::new(RwLock::new(SessionDenyList::new()));
+        let deny_list_clone = Arc::clone(&deny_list);
 
         // Spawn background task to cleanup expired sessions every 60 seconds
         tokio::spawn(async move {
@@ -62,6 +93,12 @@ impl AuthManager {
                 interval.tick().await;
                 let mut store = session_store_clone.write().await;
                 let removed = store.cleanup_expired();
+                drop(store);
+                let removed = removed
+                    + deny_list_clone
+                        .write()
+                        .await
+                        .cleanup_expired(unix_now_secs(), session_token_expiry_secs());
                 if removed > 0 {
                     tracing::debug!(
                         target: "sneldb::auth",
@@ -79,10 +116,37 @@ impl AuthManager {
             _shard_manager: shard_manager,
             storage,
             rate_limiter,
+            client_cert_users: Arc::new(RwLock::new(client_cert_users)),
+            user_mutations: Mutex::new(()),
+            lockout: LockoutPolicy::from_config(),
+            failures: RwLock::new(FailureTracker::new()),
+            replay_guard: ReplayGuard::from_config(),
+            token_codec: StatelessTokenCodec::from_config(),
+            deny_list,
         }
     }
 
+    /// Overrides the lockout policy from config (useful for tests).
+    pub fn with_lockout(mut self, lockout: Option<LockoutPolicy>) -> Self {
+        self.lockout = lockout;
+        self
+    }
+
+    /// Overrides the replay protection settings from config (useful for tests).
+    pub fn with_replay_guard(mut self, replay_guard: ReplayGuard) -> Self {
+        self.replay_guard = replay_guard;
+        self
+    }
+
+    /// Switches between stateless (`Some`) and opaque session tokens (useful for tests).
+    pub fn with_stateless_tokens(mut self, codec: Option<StatelessTokenCodec>) -> Self {
+        self.token_codec = codec;
+        self
+    }
+
     /// Verifies HMAC signature. Rate limits only failed attempts per IP.
+    /// With lockout enabled, repeated wrong signatures lock the account.
+    /// Stamped signatures (`<timestamp>.<nonce>.<hmac>`) are also checked for replays.
     pub async fn verify_signature(
         &self,
         message: &str,
@@ -90,28 +154,224 @@ impl AuthManager {
         signature: &str,
         client_ip: Option<&str>,
     ) -> AuthResult<()> {
-        // Verify signature FIRST
-        let result = verify_signature(&self.cache, message, user_id, signature).await;
-
-        // Rate limit only failed attempts; successful auths bypass
-        if result.is_err() {
-            if let (Some(ip), Some(rate_limiter)) = (client_ip, &self.rate_limiter) {
-                let limiter = rate_limiter.lock().await;
-                if limiter.check_key(&ip.to_string()).is_err() {
+        let result = match self.signed_message(message, user_id, signature) {
+            Ok((stamp, message, hmac)) => {
+                // Verify signature FIRST
+                let outcome = check_signature(&self.cache, &message, user_id, hmac).await;
+                self.settle_request(outcome, stamp, user_id, client_ip)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        self.rate_limit_failure(result, user_id, client_ip).await
+    }
+
+    /// Verifies many `(message, signature)` pairs from one user in a single pass; see
+    /// `check_signature_batch`. Results line up with `items`, and a bad signature fails
+    /// only its own entry. Lockout, replay and rate limiting apply per entry as in
+    /// `verify_signature`.
+    pub async fn verify_signature_batch(
+        &self,
+        user_id: &str,
+        items: &[(&str, &str)],
+        client_ip: Option<&str>,
+    ) -> Vec<AuthResult<()>> {
+        let prepared: Vec<_> = items
+            .iter()
+            .map(|(message, signature)| self.signed_message(message, user_id, signature))
+            .collect();
+        let to_check: Vec<(&str, &str)> = prepared
+            .iter()
+            .filter_map(|p| p.as_ref().ok())
+            .map(|(_, message, hmac)| (message.as_ref(), *hmac))
+            .collect();
+        let mut outcomes = check_signature_batch(&self.cache, user_id, &to_check)
+            .await
+            .into_iter();
+
+        let mut results = Vec::with_capacity(items.len());
+        for entry in prepared {
+            let result = match entry {
+                Ok((stamp, _, _)) => {
+                    let outcome = outcomes
+                        .next()
+                        .expect("one batch outcome per prepared entry");
+                    self.settle_request(outcome, stamp, user_id, client_ip)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            results.push(self.rate_limit_failure(result, user_id, client_ip).await);
+        }
+        results
+    }
+
+    /// Splits off a replay stamp and returns (stamp, message the HMAC covers, HMAC).
+    fn signed_message<'a>(
+        &self,
+        message: &'a str,
+        user_id: &str,
+        signature: &'a str,
+    ) -> AuthResult<(Option<ReplayStamp<'a>>, Cow<'a, str>, &'a str)> {
+        let (stamp, hmac) = split_stamped_signature(signature)?;
+        let message = match &stamp {
+            Some(stamp) => Cow::Owned(stamp.signed_message(message)),
+            None if self.replay_guard.require_stamp => {
+                tracing::debug!(target: "sneldb::auth", user_id, "Signature without nonce rejected");
+                return Err(AuthError::AuthenticationFailed);
+            }
+            None => Cow::Borrowed(message),
+        };
+        Ok((stamp, message, hmac))
+    }
+
+    /// Applies lockout bookkeeping and replay checks to a signature check outcome.
+    async fn settle_request(
+        &self,
+        outcome: Result<(), SignatureFailure>,
+        stamp: Option<ReplayStamp<'_>>,
+        user_id: &str,
+        client_ip: Option<&str>,
+    ) -> AuthResult<()> {
+        if let Some(policy) = &self.lockout {
+            match outcome {
+                Ok(()) => self.reset_failures(policy, user_id, client_ip).await,
+                Err(SignatureFailure::Mismatch) => {
+                    self.record_failure(policy, user_id, client_ip).await
+                }
+                Err(SignatureFailure::Rejected | SignatureFailure::Locked) => {}
+            }
+        }
+
+        outcome.map_err(|failure| match failure {
+            SignatureFailure::Locked => AuthError::AccountLocked,
+            _ => AuthError::AuthenticationFailed, // Return generic error
+        })?;
+
+        // Nonces are only remembered for genuine signatures, so others cannot fill the set
+        if let Some(stamp) = stamp {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let checked = self
+                .replay_guard
+                .check(user_id, stamp.timestamp, stamp.nonce, now);
+            if let Err(e) = &checked {
+                tracing::warn!(
+                    target: "sneldb::auth",
+                    user_id,
+                    timestamp = stamp.timestamp,
+                    error = %e,
+                    "Rejected stamped request"
+                );
+            }
+            checked?;
+        }
+        Ok(())
+    }
+
+    /// Rate limits only failed attempts; successful auths bypass.
+    async fn rate_limit_failure(
+        &self,
+        result: AuthResult<()>,
+        user_id: &str,
+        client_ip: Option<&str>,
+    ) -> AuthResult<()> {
+        if result.is_err()
+            && let (Some(ip), Some(rate_limiter)) = (client_ip, &self.rate_limiter)
+        {
+            let limiter = rate_limiter.lock().await;
+            if limiter.check_key(&ip.to_string()).is_err() {
+                static RATE_LIMITED_LOG: LogSampler = LogSampler::new();
+                if let Some(dropped) = RATE_LIMITED_LOG.admit(tracing::Level::WARN) {
                     tracing::warn!(
                         target: "sneldb::auth",
                         user_id,
                         client_ip = ip,
+                        dropped,
                         "Rate limit exceeded for IP after failed authentication"
                     );
-                    return Err(crate::engine::auth::AuthError::RateLimitExceeded);
                 }
+                return Err(AuthError::RateLimitExceeded);
             }
         }
 
         result
     }
 
+    async fn reset_failures(&self, policy: &LockoutPolicy, user_id: &str, source: Option<&str>) {
+        // Checked under the read lock first so successful auths don't serialize
+        if self.failures.read().await.failures(policy, user_id, source) == 0 {
+            return;
+        }
+        self.failures.write().await.reset(policy, user_id, source);
+    }
+
+    async fn record_failure(&self, policy: &LockoutPolicy, user_id: &str, source: Option<&str>) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let Some(locked_until) = self
+            .failures
+            .write()
+            .await
+            .record_failure(policy, user_id, source, now)
+        else {
+            return;
+        };
+
+        let _guard = self.user_mutations.lock().await;
+        match set_locked_until(&self.cache, &self.storage, user_id, Some(locked_until)).await {
+            Ok(()) => tracing::warn!(
+                target: "sneldb::auth",
+                user_id,
+                client_ip = source,
+                locked_until,
+                "Account locked after repeated signature failures"
+            ),
+            Err(e) => tracing::error!(
+                target: "sneldb::auth",
+                user_id,
+                error = %e,
+                "Failed to persist account lockout"
+            ),
+        }
+    }
+
+    /// Lifts a lockout before its cooldown ends and forgets counted failures.
+    pub async fn unlock_user(&self, user_id: &str) -> AuthResult<()> {
+        {
+            let _guard = self.user_mutations.lock().await;
+            set_locked_until(&self.cache, &self.storage, user_id, None).await?;
+        }
+        self.failures.write().await.clear_user(user_id);
+        tracing::info!(target: "sneldb::auth", user_id, "User unlocked");
+        Ok(())
+    }
+
+    /// Authenticates a client certificate presented over mutual TLS.
+    /// Resolves the certificate subject to a user and issues a session token, so no
+    /// password or signature exchange is needed. Returns (user_id, session_token).
+    pub async fn authenticate_client_cert(
+        &self,
+        identity: &ClientCertIdentity,
+    ) -> AuthResult<(String, String)> {
+        let user_id = {
+            let mapping = self.client_cert_users.read().await;
+            resolve_client_cert_user(&self.cache, &mapping, identity).await?
+        };
+        let token = self.generate_session_token(&user_id).await;
+        Ok((user_id, token))
+    }
+
+    /// Maps a client certificate subject DN or common name to a user at runtime.
+    pub async fn map_client_cert(&self, subject: String, user_id: String) {
+        let mut mapping = self.client_cert_users.write().await;
+        mapping.insert(subject, user_id);
+    }
+
     /// Parses auth from "user_id:signature:command" format.
     pub fn parse_auth<'a>(&self, input: &'a str) -> AuthResult<(&'a str, &'a str, &'a str)> {
         parse_auth(input)
@@ -123,6 +383,7 @@ impl AuthManager {
         user_id: String,
         secret_key: Option<String>,
     ) -> AuthResult<String> {
+        let _guard = self.user_mutations.lock().await;
         create_user(
             &self.cache,
             &self.permission_cache,
@@ -140,28 +401,74 @@ impl AuthManager {
     /// 3. Updates permission cache
     pub async fn revoke_key(&self, user_id: &str) -> AuthResult<()> {
         // Revoke the user key (marks inactive)
-        revoke_key(&self.cache, &self.permission_cache, &self.storage, user_id).await?;
+        {
+            let _guard = self.user_mutations.lock().await;
+            revoke_key(&self.cache, &self.permission_cache, &self.storage, user_id).await?;
+        }
 
         // Revoke all session tokens for this user
-        let mut store = self.session_store.write().await;
-        let revoked_count = store.revoke_user_sessions(user_id);
-        drop(store);
+        self.revoke_user_sessions(user_id).await;
 
-        if revoked_count > 0 {
-            tracing::info!(
-                target: "sneldb::auth",
-                user_id,
-                revoked_sessions = revoked_count,
-                "Revoked all session tokens for user"
-            );
-        }
+        Ok(())
+    }
+
+    /// Disables a user: same as `revoke_key`, so their active sessions stop working at once.
+    pub async fn disable_user(&self, user_id: &str) -> AuthResult<()> {
+        self.revoke_key(user_id).await
+    }
 
+    /// Re-enables a disabled user with their existing key, roles and permissions.
+    pub async fn enable_user(&self, user_id: &str) -> AuthResult<()> {
+        let _guard = self.user_mutations.lock().await;
+        set_user_active(
+            &self.cache,
+            &self.permission_cache,
+            &self.storage,
+            user_id,
+            true,
+        )
+        .await?;
+        tracing::info!(target: "sneldb::auth", user_id, "User enabled");
         Ok(())
     }
 
+    /// Rotates a user's secret key and returns the new one. The old key stops working
+    /// as soon as this returns; with `revoke_sessions`, so do the user's session tokens.
+    pub async fn rotate_key(
+        &self,
+        user_id: &str,
+        secret_key: Option<String>,
+        revoke_sessions: bool,
+    ) -> AuthResult<String> {
+        let secret = {
+            let _guard = self.user_mutations.lock().await;
+            rotate_key(
+                &self.cache,
+                &self.permission_cache,
+                &self.storage,
+                user_id,
+                secret_key,
+            )
+            .await?
+        };
+
+        if revoke_sessions {
+            self.revoke_user_sessions(user_id).await;
+        }
+        Ok(secret)
+    }
+
     /// Revokes all session tokens for a specific user without revoking their key.
     /// Useful for forcing re-authentication without disabling the user account.
+    /// Stateless tokens issued so far are deny-listed; the count covers opaque tokens only.
     pub async fn revoke_user_sessions(&self, user_id: &str) -> usize {
+        if self.token_codec.is_some() {
+            self.deny_list
+                .write()
+                .await
+                .revoke_user(user_id, unix_now_millis());
+        }
+
         let mut store = self.session_store.write().await;
         let count = store.revoke_user_sessions(user_id);
         drop(store);
@@ -189,6 +496,15 @@ impl AuthManager {
         perm_cache.is_admin(user_id)
     }
 
+    /// Returns the roles assigned to a user (empty for unknown users).
+    pub async fn roles(&self, user_id: &str) -> Vec<String> {
+        let cache = self.cache.read().await;
+        cache
+            .get(user_id)
+            .map(|user| user.roles.clone())
+            .unwrap_or_default()
+    }
+
     /// Checks if user can read event_type.
     pub async fn can_read(&self, user_id: &str, event_type: &str) -> bool {
         let perm_cache = self.permission_cache.read().await;
@@ -208,6 +524,7 @@ impl AuthManager {
         event_type: &str,
         permission_set: PermissionSet,
     ) -> AuthResult<()> {
+        let _guard = self.user_mutations.lock().await;
         grant_permission(
             &self.cache,
             &self.permission_cache,
@@ -221,6 +538,7 @@ impl AuthManager {
 
     /// Revokes permissions from a user for an event type.
     pub async fn revoke_permission(&self, user_id: &str, event_type: &str) -> AuthResult<()> {
+        let _guard = self.user_mutations.lock().await;
         revoke_permission(
             &self.cache,
             &self.permission_cache,
@@ -246,6 +564,7 @@ impl AuthManager {
         secret_key: Option<String>,
         roles: Vec<String>,
     ) -> AuthResult<String> {
+        let _guard = self.user_mutations.lock().await;
         create_user_with_roles(
             &self.cache,
             &self.permission_cache,
@@ -268,13 +587,10 @@ impl AuthManager {
     }
 
     /// Generate a new session token for an authenticated user.
-    /// Returns the token as a hex-encoded string (32 bytes = 64 hex chars).
+    /// Returns the token as a hex-encoded string (32 bytes = 64 hex chars), or a signed
+    /// stateless token when those are enabled.
     pub async fn generate_session_token(&self, user_id: &str) -> String {
-        let expiry_seconds = CONFIG
-            .auth
-            .as_ref()
-            .map(|a| a.session_token_expiry_seconds)
-            .unwrap_or(300); // Default 5 minutes
+        let expiry_seconds = session_token_expiry_secs();
 
         let expires_at = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -282,6 +598,12 @@ impl AuthManager {
             .as_secs()
             + expiry_seconds;
 
+        if let Some(codec) = &self.token_codec {
+            return self
+                .generate_stateless_token(codec, user_id, expires_at)
+                .await;
+        }
+
         // Generate 32 random bytes (256 bits) for the token
         let mut token_bytes = [0u8; 32];
         rand::thread_rng().fill_bytes(&mut token_bytes);
@@ -306,9 +628,64 @@ impl AuthManager {
         token
     }
 
+    async fn generate_stateless_token(
+        &self,
+        codec: &StatelessTokenCodec,
+        user_id: &str,
+        expires_at: u64,
+    ) -> String {
+        let (roles, permissions) = match self.cache.read().await.get(user_id) {
+            Some(user_key) => (
+                user_key.roles.clone(),
+                user_key.permissions.clone().into_iter().collect(),
+            ),
+            None => (Vec::new(), Default::default()),
+        };
+        let mut claims = SessionClaims {
+            user_id: user_id.to_string(),
+            issued_at_ms: self
+                .deny_list
+                .read()
+                .await
+                .issue_time(user_id, unix_now_millis()),
+            expires_at,
+            token_id: rand::thread_rng().next_u64(),
+            roles,
+            permissions,
+        };
+
+        let mut token = codec.encode(&claims);
+        if token.len() > MAX_SESSION_TOKEN_LENGTH {
+            // Nodes authorize against their own user records, so the token stays usable
+            tracing::warn!(
+                target: "sneldb::auth",
+                user_id,
+                token_len = token.len(),
+                "Session token too long, leaving out per-event permissions"
+            );
+            claims.permissions.clear();
+            token = codec.encode(&claims);
+        }
+
+        tracing::warn!(
+            target: "sneldb::auth",
+            user_id = user_id,
+            token_len = token.len(),
+            expires_at,
+            "Generated stateless session token"
+        );
+        token
+    }
+
     /// Validate a session token and return the associated user_id if valid.
     /// Returns None if token is invalid, expired, or user is inactive.
     pub async fn validate_session_token(&self, token: &str) -> Option<String> {
+        if let Some(codec) = &self.token_codec
+            && StatelessTokenCodec::is_stateless_token(token)
+        {
+            return self.validate_stateless_token(codec, token).await;
+        }
+
         let session = {
             let store = self.session_store.read().await;
             match store.get(token) {
@@ -372,9 +749,85 @@ impl AuthManager {
         Some(session.user_id.clone())
     }
 
+    /// Checks signature, expiry and deny-list. The user only has to exist on this node
+    /// if it has a local record: then a disabled user is refused here too.
+    async fn validate_stateless_token(
+        &self,
+        codec: &StatelessTokenCodec,
+        token: &str,
+    ) -> Option<String> {
+        let claims = match codec.decode(token, unix_now_secs()) {
+            Ok(claims) => claims,
+            Err(reason) => {
+                tracing::warn!(
+                    target: "sneldb::auth",
+                    token_len = token.len(),
+                    reason = ?reason,
+                    "Stateless token rejected"
+                );
+                return None;
+            }
+        };
+
+        if self.deny_list.read().await.is_revoked(&claims) {
+            tracing::warn!(
+                target: "sneldb::auth",
+                user_id = claims.user_id,
+                "Token validation failed: token revoked"
+            );
+            return None;
+        }
+
+        if let Some(user_key) = self.cache.read().await.get(&claims.user_id)
+            && !user_key.active
+        {
+            tracing::warn!(
+                target: "sneldb::auth",
+                user_id = claims.user_id,
+                "Token validation failed: user is inactive"
+            );
+            return None;
+        }
+
+        Some(claims.user_id)
+    }
+
     /// Revoke a session token (useful for logout or security events).
+    /// A stateless token is deny-listed until it expires.
     pub async fn revoke_session_token(&self, token: &str) -> bool {
+        if let Some(codec) = &self.token_codec
+            && StatelessTokenCodec::is_stateless_token(token)
+        {
+            let Ok(claims) = codec.decode(token, unix_now_secs()) else {
+                return false;
+            };
+            self.deny_list.write().await.revoke_token(&claims);
+            return true;
+        }
+
         let mut store = self.session_store.write().await;
         store.remove(token)
     }
 }
+
+fn session_token_expiry_secs() -> u64 {
+    CONFIG
+        .auth
+        .as_ref()
+        .map(|a| a.session_token_expiry_seconds)
+        .unwrap_or(300) // Default 5 minutes
+}
+
+fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn unix_now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -1,5 +1,7 @@
 use crate::engine::auth::storage::AuthWalStorage;
-use crate::engine::auth::{AuthError, AuthManager};
+use crate::engine::auth::{
+    AuthError, AuthManager, LockoutPolicy, ReplayGuard, StatelessTokenCodec,
+};
 use crate::engine::shard::manager::ShardManager;
 use crate::logging::init_for_tests;
 use hmac::{Hmac, Mac};
@@ -856,6 +858,28 @@ async fn test_is_admin() {
     assert!(!auth_manager.is_admin("nonexistent").await);
 }
 
+#[tokio::test]
+async fn test_roles_returns_assigned_roles() {
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+
+    auth_manager
+        .create_user_with_roles(
+            "analyst".to_string(),
+            Some("secret".to_string()),
+            vec!["read-only".to_string(), "analyst".to_string()],
+        )
+        .await
+        .expect("User creation should succeed");
+
+    assert_eq!(
+        auth_manager.roles("analyst").await,
+        vec!["read-only".to_string(), "analyst".to_string()]
+    );
+    assert!(auth_manager.roles("nonexistent").await.is_empty());
+}
+
 #[tokio::test]
 async fn test_create_user_defaults_to_empty_roles() {
     init_for_tests();
@@ -1353,3 +1377,484 @@ async fn test_revoke_user_sessions_nonexistent_user() {
     let revoked = auth_manager.revoke_user_sessions("nonexistent").await;
     assert_eq!(revoked, 0);
 }
+
+#[tokio::test]
+async fn test_authenticate_client_cert_issues_session_token() {
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+    auth_manager
+        .create_user("cert_user".to_string(), None)
+        .await
+        .unwrap();
+    auth_manager
+        .map_client_cert("cert-client".to_string(), "cert_user".to_string())
+        .await;
+
+    let identity = crate::engine::auth::ClientCertIdentity::verified(
+        "CN=cert-client,O=Acme",
+        Some("cert-client".to_string()),
+    );
+    let (user_id, token) = auth_manager
+        .authenticate_client_cert(&identity)
+        .await
+        .unwrap();
+    assert_eq!(user_id, "cert_user");
+    assert_eq!(
+        auth_manager.validate_session_token(&token).await,
+        Some("cert_user".to_string())
+    );
+
+    let unmapped = crate::engine::auth::ClientCertIdentity::verified(
+        "CN=stranger",
+        Some("stranger".to_string()),
+    );
+    let result = auth_manager.authenticate_client_cert(&unmapped).await;
+    assert!(matches!(
+        result,
+        Err(AuthError::UnmappedClientCertificate(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_rotate_key_replaces_secret_immediately() {
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+    auth_manager
+        .create_user("rotating".to_string(), Some("old_secret".to_string()))
+        .await
+        .unwrap();
+    let token = auth_manager.generate_session_token("rotating").await;
+
+    let new_secret = auth_manager
+        .rotate_key("rotating", None, false)
+        .await
+        .unwrap();
+    assert_eq!(new_secret.len(), 64);
+
+    let message = "PING";
+    let old_sig = compute_hmac(message, "old_secret");
+    let new_sig = compute_hmac(message, &new_secret);
+    assert!(matches!(
+        auth_manager
+            .verify_signature(message, "rotating", &old_sig, None)
+            .await,
+        Err(AuthError::AuthenticationFailed)
+    ));
+    assert!(
+        auth_manager
+            .verify_signature(message, "rotating", &new_sig, None)
+            .await
+            .is_ok()
+    );
+
+    // Sessions survive unless asked otherwise
+    assert!(auth_manager.validate_session_token(&token).await.is_some());
+    auth_manager
+        .rotate_key("rotating", Some("third_secret".to_string()), true)
+        .await
+        .unwrap();
+    assert!(auth_manager.validate_session_token(&token).await.is_none());
+}
+
+#[tokio::test]
+async fn test_rotate_key_survives_reload_and_rejects_same_secret() {
+    init_for_tests();
+
+    let (auth_manager, _temp_dir) = create_test_auth_manager().await;
+    auth_manager
+        .create_user("rotating".to_string(), Some("old_secret".to_string()))
+        .await
+        .unwrap();
+    auth_manager
+        .grant_permission(
+            "rotating",
+            "order_created",
+            crate::engine::auth::PermissionSet::read_only(),
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        auth_manager
+            .rotate_key("rotating", Some("old_secret".to_string()), false)
+            .await,
+        Err(AuthError::SecretKeyUnchanged)
+    ));
+    assert!(matches!(
+        auth_manager.rotate_key("ghost", None, false).await,
+        Err(AuthError::UserNotFound(_))
+    ));
+
+    auth_manager
+        .rotate_key("rotating", Some("new_secret".to_string()), false)
+        .await
+        .unwrap();
+    auth_manager.load_from_db().await.unwrap();
+
+    let sig = compute_hmac("PING", "new_secret");
+    assert!(
+        auth_manager
+            .verify_signature("PING", "rotating", &sig, None)
+            .await
+            .is_ok()
+    );
+    assert!(auth_manager.can_read("rotating", "order_created").await);
+}
+
+fn lockout_policy(max_failures: u32) -> LockoutPolicy {
+    LockoutPolicy {
+        max_failures,
+        window_secs: 300,
+        cooldown_secs: 900,
+        per_source: false,
+    }
+}
+
+#[tokio::test]
+async fn test_lockout_after_consecutive_signature_failures() {
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+    let auth_wal_path = tempdir().unwrap().into_path().join("auth.swal");
+    let storage = Arc::new(AuthWalStorage::new(auth_wal_path).unwrap());
+    let auth_manager =
+        AuthManager::with_storage(shard_manager, storage).with_lockout(Some(lockout_policy(3)));
+    auth_manager
+        .create_user("target".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    let good = compute_hmac("PING", "secret");
+
+    // A success in between resets the count
+    for _ in 0..2 {
+        assert!(matches!(
+            auth_manager
+                .verify_signature("PING", "target", "bad", Some("10.0.0.1"))
+                .await,
+            Err(AuthError::AuthenticationFailed)
+        ));
+    }
+    assert!(
+        auth_manager
+            .verify_signature("PING", "target", &good, Some("10.0.0.1"))
+            .await
+            .is_ok()
+    );
+
+    // Unknown users and malformed input never count
+    for _ in 0..5 {
+        let _ = auth_manager
+            .verify_signature("PING", "ghost", "bad", None)
+            .await;
+        let _ = auth_manager
+            .verify_signature("PING", "target", &"a".repeat(300), None)
+            .await;
+    }
+
+    for _ in 0..3 {
+        assert!(matches!(
+            auth_manager
+                .verify_signature("PING", "target", "bad", Some("10.0.0.1"))
+                .await,
+            Err(AuthError::AuthenticationFailed)
+        ));
+    }
+    assert!(matches!(
+        auth_manager
+            .verify_signature("PING", "target", &good, Some("10.0.0.2"))
+            .await,
+        Err(AuthError::AccountLocked)
+    ));
+    let user = auth_manager
+        .list_users()
+        .await
+        .into_iter()
+        .find(|u| u.user_id == "target")
+        .unwrap();
+    assert!(user.locked_until.is_some());
+}
+
+#[tokio::test]
+async fn test_lockout_survives_restart_until_unlocked() {
+    init_for_tests();
+
+    let auth_wal_path = tempdir().unwrap().into_path().join("auth.swal");
+    let new_manager = || async {
+        let base_dir = tempdir().unwrap().into_path();
+        let wal_dir = tempdir().unwrap().into_path();
+        let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+        let storage = Arc::new(AuthWalStorage::new(auth_wal_path.clone()).unwrap());
+        AuthManager::with_storage(shard_manager, storage).with_lockout(Some(lockout_policy(1)))
+    };
+
+    let auth_manager = new_manager().await;
+    auth_manager
+        .create_user("target".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    let _ = auth_manager
+        .verify_signature("PING", "target", "bad", None)
+        .await;
+    drop(auth_manager);
+
+    let restarted = new_manager().await;
+    restarted.load_from_db().await.unwrap();
+    let good = compute_hmac("PING", "secret");
+    assert!(matches!(
+        restarted
+            .verify_signature("PING", "target", &good, None)
+            .await,
+        Err(AuthError::AccountLocked)
+    ));
+
+    restarted.unlock_user("target").await.unwrap();
+    assert!(
+        restarted
+            .verify_signature("PING", "target", &good, None)
+            .await
+            .is_ok()
+    );
+    assert!(matches!(
+        restarted.unlock_user("ghost").await,
+        Err(AuthError::UserNotFound(_))
+    ));
+}
+
+fn stamped_signature(message: &str, secret_key: &str, timestamp: u64, nonce: &str) -> String {
+    let signed = format!("{}:{}:{}", timestamp, nonce, message);
+    format!(
+        "{}.{}.{}",
+        timestamp,
+        nonce,
+        compute_hmac(&signed, secret_key)
+    )
+}
+
+async fn create_replay_auth_manager(require_stamp: bool) -> AuthManager {
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+    let auth_wal_path = tempdir().unwrap().into_path().join("auth.swal");
+    let storage = Arc::new(AuthWalStorage::new(auth_wal_path).unwrap());
+    let auth_manager = AuthManager::with_storage(shard_manager, storage)
+        .with_replay_guard(ReplayGuard::new(30, 1_000, require_stamp));
+    auth_manager
+        .create_user("signer".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    auth_manager
+}
+
+#[tokio::test]
+async fn test_stamped_signature_cannot_be_replayed() {
+    init_for_tests();
+
+    let auth_manager = create_replay_auth_manager(false).await;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let message = "STORE order_created FOR c1 PAYLOAD {\"id\":1}";
+    let signature = stamped_signature(message, "secret", now, "nonce-1");
+
+    assert!(
+        auth_manager
+            .verify_signature(message, "signer", &signature, None)
+            .await
+            .is_ok()
+    );
+    assert!(matches!(
+        auth_manager
+            .verify_signature(message, "signer", &signature, None)
+            .await,
+        Err(AuthError::ReplayedRequest)
+    ));
+
+    // The stamp is covered by the HMAC, so it cannot be swapped for a fresh one
+    let forged = signature.replacen("nonce-1", "nonce-2", 1);
+    assert!(matches!(
+        auth_manager
+            .verify_signature(message, "signer", &forged, None)
+            .await,
+        Err(AuthError::AuthenticationFailed)
+    ));
+
+    let stale = stamped_signature(message, "secret", now - 120, "nonce-3");
+    assert!(matches!(
+        auth_manager
+            .verify_signature(message, "signer", &stale, None)
+            .await,
+        Err(AuthError::StaleRequest)
+    ));
+
+    // Plain signatures keep working unless stamps are required
+    let plain = compute_hmac(message, "secret");
+    assert!(
+        auth_manager
+            .verify_signature(message, "signer", &plain, None)
+            .await
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn test_require_nonce_rejects_plain_signatures() {
+    init_for_tests();
+
+    let auth_manager = create_replay_auth_manager(true).await;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let plain = compute_hmac("PING", "secret");
+    assert!(matches!(
+        auth_manager
+            .verify_signature("PING", "signer", &plain, None)
+            .await,
+        Err(AuthError::AuthenticationFailed)
+    ));
+    let stamped = stamped_signature("PING", "secret", now, "n1");
+    assert!(
+        auth_manager
+            .verify_signature("PING", "signer", &stamped, None)
+            .await
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn test_verify_signature_batch_reports_each_entry() {
+    init_for_tests();
+
+    let auth_manager = create_replay_auth_manager(false).await;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let plain = compute_hmac("PING", "secret");
+    let stamped = stamped_signature("FLUSH", "secret", now, "batch-1");
+    let items = [
+        ("PING", plain.as_str()),
+        ("PING", "deadbeef"),
+        ("FLUSH", stamped.as_str()),
+        ("FLUSH", stamped.as_str()),
+        ("PING", "1.bad nonce.deadbeef"),
+    ];
+    let results = auth_manager
+        .verify_signature_batch("signer", &items, None)
+        .await;
+
+    assert_eq!(results.len(), 5);
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(AuthError::AuthenticationFailed)));
+    assert!(results[2].is_ok());
+    assert!(matches!(results[3], Err(AuthError::ReplayedRequest)));
+    assert!(matches!(results[4], Err(AuthError::AuthenticationFailed)));
+}
+
+#[tokio::test]
+async fn test_verify_signature_batch_counts_mismatches_towards_lockout() {
+    init_for_tests();
+
+    let auth_manager = create_replay_auth_manager(false)
+        .await
+        .with_lockout(Some(lockout_policy(2)));
+    let good = compute_hmac("PING", "secret");
+
+    let results = auth_manager
+        .verify_signature_batch(
+            "signer",
+            &[("PING", "bad"), ("PING", good.as_str()), ("PING", "bad")],
+            None,
+        )
+        .await;
+    assert!(
+        results[1].is_ok(),
+        "a success between failures resets the count"
+    );
+    assert!(
+        auth_manager
+            .verify_signature("PING", "signer", &good, None)
+            .await
+            .is_ok()
+    );
+
+    auth_manager
+        .verify_signature_batch("signer", &[("PING", "bad"), ("PING", "bad")], None)
+        .await;
+    assert!(matches!(
+        auth_manager
+            .verify_signature("PING", "signer", &good, None)
+            .await,
+        Err(AuthError::AccountLocked)
+    ));
+}
+
+const SIGNING_KEY: &[u8] = b"shared-signing-key-for-all-nodes";
+
+async fn create_stateless_auth_manager() -> AuthManager {
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+    let auth_wal_path = tempdir().unwrap().into_path().join("auth.swal");
+    let storage = Arc::new(AuthWalStorage::new(auth_wal_path).unwrap());
+    AuthManager::with_storage(shard_manager, storage)
+        .with_stateless_tokens(StatelessTokenCodec::new(SIGNING_KEY))
+}
+
+#[tokio::test]
+async fn test_stateless_token_is_accepted_by_another_node() {
+    init_for_tests();
+
+    let issuer = create_stateless_auth_manager().await;
+    issuer
+        .create_user("test_user".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    let other_node = create_stateless_auth_manager().await;
+
+    let token = issuer.generate_session_token("test_user").await;
+    assert!(StatelessTokenCodec::is_stateless_token(&token));
+    assert_eq!(
+        other_node.validate_session_token(&token).await.as_deref(),
+        Some("test_user")
+    );
+
+    // A node with opaque tokens or another key does not accept it
+    let (opaque_node, _temp_dir) = create_test_auth_manager().await;
+    assert!(opaque_node.validate_session_token(&token).await.is_none());
+}
+
+#[tokio::test]
+async fn test_stateless_tokens_can_be_revoked() {
+    init_for_tests();
+
+    let auth_manager = create_stateless_auth_manager().await;
+    auth_manager
+        .create_user("test_user".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+    let token1 = auth_manager.generate_session_token("test_user").await;
+    let token2 = auth_manager.generate_session_token("test_user").await;
+
+    assert!(auth_manager.revoke_session_token(&token1).await);
+    assert!(auth_manager.validate_session_token(&token1).await.is_none());
+    assert!(auth_manager.validate_session_token(&token2).await.is_some());
+
+    auth_manager.revoke_user_sessions("test_user").await;
+    assert!(auth_manager.validate_session_token(&token2).await.is_none());
+
+    // Tokens issued after the revocation work
+    let token3 = auth_manager.generate_session_token("test_user").await;
+    assert!(auth_manager.validate_session_token(&token3).await.is_some());
+
+    // Disabling the user refuses its tokens on this node
+    auth_manager.disable_user("test_user").await.unwrap();
+    assert!(auth_manager.validate_session_token(&token3).await.is_none());
+}
@@ -1,25 +1,41 @@
+mod cert_ops;
 mod db_ops;
+mod lockout;
 mod manager;
 mod permission_ops;
+mod replay;
+mod session_token;
 mod signature;
 mod storage;
 mod types;
 mod user_ops;
 
+pub use lockout::LockoutPolicy;
 pub use manager::AuthManager;
+pub use replay::ReplayGuard;
+pub use session_token::{SessionClaims, StatelessTokenCodec};
 pub use types::{
-    AuthError, AuthRateLimiter, AuthResult, BYPASS_USER_ID, MAX_SECRET_KEY_LENGTH,
-    MAX_SIGNATURE_LENGTH, MAX_USER_ID_LENGTH, NO_AUTH_USER_ID, PermissionSet, SessionStore,
-    SessionToken, User, UserCache, UserKey, create_rate_limiter,
+    AuthError, AuthRateLimiter, AuthResult, BYPASS_USER_ID, ClientCertIdentity, MAX_NONCE_LENGTH,
+    MAX_SECRET_KEY_LENGTH, MAX_SESSION_TOKEN_LENGTH, MAX_SIGNATURE_LENGTH, MAX_USER_ID_LENGTH,
+    NO_AUTH_USER_ID, PermissionSet, SessionStore, SessionToken, User, UserCache, UserKey,
+    create_rate_limiter,
 };
 
+#[cfg(test)]
+mod cert_ops_test;
 #[cfg(test)]
 mod db_ops_test;
 #[cfg(test)]
+mod lockout_test;
+#[cfg(test)]
 mod manager_test;
 #[cfg(test)]
 mod permission_ops_test;
 #[cfg(test)]
+mod replay_test;
+#[cfg(test)]
+mod session_token_test;
+#[cfg(test)]
 mod signature_test;
 #[cfg(test)]
 mod storage_test;
@@ -58,6 +58,7 @@ pub async fn grant_permission(
         created_at: user_key.created_at,
         roles: user_key.roles.clone(),
         permissions: updated_permissions.clone(),
+        locked_until: user_key.locked_until,
     };
 
     store_user_in_db(auth_storage, &updated_user).await?;
@@ -70,6 +71,7 @@ pub async fn grant_permission(
         created_at: user_key.created_at,
         roles: user_key.roles.clone(),
         permissions: updated_permissions,
+        locked_until: user_key.locked_until,
     };
     update_caches(cache, permission_cache, updated_key).await;
 
@@ -116,6 +118,7 @@ pub async fn revoke_permission(
         created_at: user_key.created_at,
         roles: user_key.roles.clone(),
         permissions: updated_permissions.clone(),
+        locked_until: user_key.locked_until,
     };
 
     store_user_in_db(auth_storage, &updated_user).await?;
@@ -128,6 +131,7 @@ pub async fn revoke_permission(
         created_at: user_key.created_at,
         roles: user_key.roles.clone(),
         permissions: updated_permissions,
+        locked_until: user_key.locked_until,
     };
     update_caches(cache, permission_cache, updated_key).await;
 
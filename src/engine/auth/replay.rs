@@ -0,0 +1,112 @@
+use super::types::{AuthError, AuthResult};
+use crate::shared::config::CONFIG;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+const DEFAULT_MAX_SKEW_SECS: u64 = 30;
+const DEFAULT_NONCE_CAPACITY: usize = 100_000;
+
+/// Rejects stamped requests whose timestamp is outside the allowed clock skew, or whose
+/// (user, timestamp, nonce) was already accepted.
+///
+/// Seen nonces are kept ordered by timestamp and evicted once they fall out of the skew
+/// window, since such requests are rejected as stale anyway. When `capacity` is reached
+/// the oldest entry is evicted early and its timestamp becomes the floor: requests at or
+/// below it are treated as stale, so eviction never reopens a replay.
+#[derive(Debug)]
+pub struct ReplayGuard {
+    pub max_skew_secs: u64,
+    pub capacity: usize,
+    /// Reject plain signatures without a timestamp and nonce
+    pub require_stamp: bool,
+    state: Mutex<SeenNonces>,
+}
+
+#[derive(Debug, Default)]
+struct SeenNonces {
+    entries: BTreeSet<(u64, String, String)>,
+    /// Highest timestamp evicted for capacity rather than age
+    floor: Option<u64>,
+}
+
+impl ReplayGuard {
+    pub fn new(max_skew_secs: u64, capacity: usize, require_stamp: bool) -> Self {
+        Self {
+            max_skew_secs,
+            capacity: capacity.max(1),
+            require_stamp,
+            state: Mutex::new(SeenNonces::default()),
+        }
+    }
+
+    pub fn from_config() -> Self {
+        match CONFIG.auth.as_ref() {
+            Some(cfg) => Self::new(
+                cfg.replay_max_skew_seconds,
+                cfg.replay_nonce_capacity,
+                cfg.replay_require_nonce,
+            ),
+            None => Self::new(DEFAULT_MAX_SKEW_SECS, DEFAULT_NONCE_CAPACITY, false),
+        }
+    }
+
+    /// Accepts a stamp once per user and remembers it until it leaves the skew window.
+    pub fn check(&self, user_id: &str, timestamp: u64, nonce: &str, now: u64) -> AuthResult<()> {
+        if timestamp.abs_diff(now) > self.max_skew_secs {
+            return Err(AuthError::StaleRequest);
+        }
+
+        let mut seen = self.state.lock().expect("replay guard mutex poisoned");
+        if seen.floor.is_some_and(|floor| timestamp <= floor) {
+            return Err(AuthError::StaleRequest);
+        }
+        seen.evict_expired(now.saturating_sub(self.max_skew_secs));
+        let entry = (timestamp, user_id.to_string(), nonce.to_string());
+        if seen.entries.contains(&entry) {
+            return Err(AuthError::ReplayedRequest);
+        }
+        while seen.entries.len() >= self.capacity {
+            // Evicting an entry at or after this timestamp would make it stale itself
+            if seen
+                .entries
+                .first()
+                .is_some_and(|(oldest, _, _)| *oldest >= timestamp)
+            {
+                return Err(AuthError::StaleRequest);
+            }
+            seen.evict_oldest();
+        }
+        seen.entries.insert(entry);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .expect("replay guard mutex poisoned")
+            .entries
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl SeenNonces {
+    fn evict_expired(&mut self, oldest_valid: u64) {
+        while self
+            .entries
+            .first()
+            .is_some_and(|(timestamp, _, _)| *timestamp < oldest_valid)
+        {
+            self.entries.pop_first();
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some((evicted, _, _)) = self.entries.pop_first() {
+            self.floor = Some(self.floor.map_or(evicted, |floor| floor.max(evicted)));
+        }
+    }
+}
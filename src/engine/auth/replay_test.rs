@@ -0,0 +1,72 @@
+use super::replay::ReplayGuard;
+use super::types::AuthError;
+
+const NOW: u64 = 1_700_000_000;
+
+#[test]
+fn rejects_timestamps_outside_skew() {
+    let guard = ReplayGuard::new(30, 100, false);
+
+    assert!(guard.check("alice", NOW - 30, "a", NOW).is_ok());
+    assert!(guard.check("alice", NOW + 30, "b", NOW).is_ok());
+    assert!(matches!(
+        guard.check("alice", NOW - 31, "c", NOW),
+        Err(AuthError::StaleRequest)
+    ));
+    assert!(matches!(
+        guard.check("alice", NOW + 31, "d", NOW),
+        Err(AuthError::StaleRequest)
+    ));
+}
+
+#[test]
+fn rejects_reused_nonce_per_user() {
+    let guard = ReplayGuard::new(30, 100, false);
+
+    assert!(guard.check("alice", NOW, "n1", NOW).is_ok());
+    assert!(matches!(
+        guard.check("alice", NOW, "n1", NOW + 1),
+        Err(AuthError::ReplayedRequest)
+    ));
+    // Same nonce from another user or at another timestamp is a different request
+    assert!(guard.check("bob", NOW, "n1", NOW).is_ok());
+    assert!(guard.check("alice", NOW + 1, "n1", NOW + 1).is_ok());
+}
+
+#[test]
+fn evicts_nonces_once_they_leave_the_window() {
+    let guard = ReplayGuard::new(30, 100, false);
+
+    guard.check("alice", NOW, "n1", NOW).unwrap();
+    guard.check("alice", NOW + 10, "n2", NOW + 10).unwrap();
+    assert_eq!(guard.len(), 2);
+
+    guard.check("alice", NOW + 40, "n3", NOW + 40).unwrap();
+    assert_eq!(guard.len(), 2, "n1 fell out of the window");
+    // Its replay is now stale rather than remembered
+    assert!(matches!(
+        guard.check("alice", NOW, "n1", NOW + 40),
+        Err(AuthError::StaleRequest)
+    ));
+}
+
+#[test]
+fn capacity_eviction_never_reopens_a_replay() {
+    let guard = ReplayGuard::new(30, 2, false);
+
+    guard.check("alice", NOW, "n1", NOW).unwrap();
+    guard.check("alice", NOW + 1, "n2", NOW + 1).unwrap();
+    guard.check("alice", NOW + 2, "n3", NOW + 2).unwrap();
+    assert_eq!(guard.len(), 2);
+
+    // n1 was evicted early, but anything at or before its timestamp is refused
+    assert!(matches!(
+        guard.check("alice", NOW, "n1", NOW + 2),
+        Err(AuthError::StaleRequest)
+    ));
+    assert!(matches!(
+        guard.check("alice", NOW + 1, "n2", NOW + 2),
+        Err(AuthError::ReplayedRequest)
+    ));
+    assert!(guard.check("alice", NOW + 2, "n4", NOW + 2).is_ok());
+}
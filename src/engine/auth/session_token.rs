@@ -0,0 +1,185 @@
+use super::types::{MAX_SESSION_TOKEN_LENGTH, PermissionSet};
+use crate::shared::config::{CONFIG, SessionTokenFormat};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use bincode::Options;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{BTreeMap, HashMap};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Prefix of stateless tokens; opaque tokens are plain hex and never contain a '.'
+const TOKEN_PREFIX: &str = "v1.";
+pub const MIN_SIGNING_KEY_LENGTH: usize = 32;
+
+/// Everything a node needs to accept a stateless session token without shared state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionClaims {
+    pub user_id: String,
+    /// Milliseconds since epoch, so a revocation in the same second still precedes a new token
+    pub issued_at_ms: u64,
+    /// Seconds since epoch
+    pub expires_at: u64,
+    /// Random id the deny-list revokes a single token by
+    pub token_id: u64,
+    pub roles: Vec<String>,
+    pub permissions: BTreeMap<String, PermissionSet>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenRejection {
+    Malformed,
+    BadSignature,
+    Expired,
+}
+
+/// Signs and verifies stateless session tokens: `v1.<claims>.<hmac>`, both parts
+/// unpadded base64url. Claims are varint bincode to keep tokens short.
+#[derive(Clone)]
+pub struct StatelessTokenCodec {
+    mac: HmacSha256,
+}
+
+impl std::fmt::Debug for StatelessTokenCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatelessTokenCodec")
+            .finish_non_exhaustive()
+    }
+}
+
+impl StatelessTokenCodec {
+    /// Returns None when the key is shorter than `MIN_SIGNING_KEY_LENGTH` bytes.
+    pub fn new(signing_key: &[u8]) -> Option<Self> {
+        if signing_key.len() < MIN_SIGNING_KEY_LENGTH {
+            return None;
+        }
+        let mac = HmacSha256::new_from_slice(signing_key).ok()?;
+        Some(Self { mac })
+    }
+
+    /// The codec for `session_token_format = "stateless"`, if a usable key is configured.
+    pub fn from_config() -> Option<Self> {
+        let cfg = CONFIG.auth.as_ref()?;
+        if cfg.session_token_format != SessionTokenFormat::Stateless {
+            return None;
+        }
+        let codec = cfg
+            .session_signing_key
+            .as_deref()
+            .and_then(|key| Self::new(key.as_bytes()));
+        if codec.is_none() {
+            tracing::error!(
+                target: "sneldb::auth",
+                min_length = MIN_SIGNING_KEY_LENGTH,
+                "Stateless session tokens need a session_signing_key of at least {} bytes; issuing opaque tokens instead",
+                MIN_SIGNING_KEY_LENGTH
+            );
+        }
+        codec
+    }
+
+    pub fn is_stateless_token(token: &str) -> bool {
+        token.starts_with(TOKEN_PREFIX)
+    }
+
+    pub fn encode(&self, claims: &SessionClaims) -> String {
+        let payload = URL_SAFE_NO_PAD.encode(
+            claims_options()
+                .serialize(claims)
+                .expect("session claims serialize"),
+        );
+        let mut mac = self.mac.clone();
+        mac.update(payload.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{}{}.{}", TOKEN_PREFIX, payload, signature)
+    }
+
+    /// Checks the signature before looking at the claims, then the expiry at `now`.
+    pub fn decode(&self, token: &str, now: u64) -> Result<SessionClaims, TokenRejection> {
+        let (payload, signature) = token
+            .strip_prefix(TOKEN_PREFIX)
+            .and_then(|rest| rest.split_once('.'))
+            .ok_or(TokenRejection::Malformed)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| TokenRejection::Malformed)?;
+
+        let mut mac = self.mac.clone();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| TokenRejection::BadSignature)?;
+
+        let claims: SessionClaims = URL_SAFE_NO_PAD
+            .decode(payload)
+            .ok()
+            .and_then(|bytes| claims_options().deserialize(&bytes).ok())
+            .ok_or(TokenRejection::Malformed)?;
+        if claims.expires_at < now {
+            return Err(TokenRejection::Expired);
+        }
+        Ok(claims)
+    }
+}
+
+fn claims_options() -> impl Options {
+    bincode::DefaultOptions::new().with_limit(MAX_SESSION_TOKEN_LENGTH as u64)
+}
+
+/// Revoked stateless tokens. Entries only need to outlive the tokens they revoke,
+/// so the list stays as small as the token expiry allows.
+#[derive(Debug, Default)]
+pub struct SessionDenyList {
+    /// token_id -> expires_at of the revoked token
+    tokens: HashMap<u64, u64>,
+    /// user_id -> tokens issued at or before this time (ms) are revoked
+    users: HashMap<String, u64>,
+}
+
+impl SessionDenyList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn revoke_token(&mut self, claims: &SessionClaims) {
+        self.tokens.insert(claims.token_id, claims.expires_at);
+    }
+
+    /// Revokes every token issued to `user_id` up to `now_ms`.
+    pub fn revoke_user(&mut self, user_id: &str, now_ms: u64) {
+        let revoked_at = self.users.entry(user_id.to_string()).or_default();
+        *revoked_at = (*revoked_at).max(now_ms);
+    }
+
+    /// Issue time for a new token: `now_ms`, but always after the user's last revocation,
+    /// so a token issued in the same millisecond is not revoked already.
+    pub fn issue_time(&self, user_id: &str, now_ms: u64) -> u64 {
+        match self.users.get(user_id) {
+            Some(revoked_at) => now_ms.max(revoked_at + 1),
+            None => now_ms,
+        }
+    }
+
+    pub fn is_revoked(&self, claims: &SessionClaims) -> bool {
+        self.tokens.contains_key(&claims.token_id)
+            || self
+                .users
+                .get(&claims.user_id)
+                .is_some_and(|revoked_at| claims.issued_at_ms <= *revoked_at)
+    }
+
+    /// Drops entries no unexpired token can match, given tokens live at most
+    /// `max_lifetime_secs`. Returns the number of entries removed.
+    pub fn cleanup_expired(&mut self, now: u64, max_lifetime_secs: u64) -> usize {
+        let before = self.len();
+        self.tokens.retain(|_, expires_at| *expires_at >= now);
+        self.users
+            .retain(|_, revoked_at| (*revoked_at / 1000).saturating_add(max_lifetime_secs) >= now);
+        before - self.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len() + self.users.len()
+    }
+}
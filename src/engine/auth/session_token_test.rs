@@ -0,0 +1,112 @@
+use super::session_token::{SessionClaims, SessionDenyList, StatelessTokenCodec, TokenRejection};
+use super::types::PermissionSet;
+use std::collections::BTreeMap;
+
+const NOW: u64 = 1_700_000_000;
+const KEY: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+fn claims(user_id: &str, token_id: u64) -> SessionClaims {
+    SessionClaims {
+        user_id: user_id.to_string(),
+        issued_at_ms: NOW * 1000,
+        expires_at: NOW + 300,
+        token_id,
+        roles: vec!["read-only".to_string()],
+        permissions: BTreeMap::from([(
+            "order_created".to_string(),
+            PermissionSet::new(true, false),
+        )]),
+    }
+}
+
+#[test]
+fn round_trips_claims() {
+    let codec = StatelessTokenCodec::new(KEY).unwrap();
+    let claims = claims("alice", 7);
+
+    let token = codec.encode(&claims);
+    assert!(StatelessTokenCodec::is_stateless_token(&token));
+    assert!(token.len() < 128, "token should stay compact: {}", token);
+    assert_eq!(codec.decode(&token, NOW), Ok(claims));
+}
+
+#[test]
+fn rejects_short_signing_keys() {
+    assert!(StatelessTokenCodec::new(b"too-short").is_none());
+}
+
+#[test]
+fn rejects_tokens_signed_with_another_key_or_tampered() {
+    let codec = StatelessTokenCodec::new(KEY).unwrap();
+    let other = StatelessTokenCodec::new(b"fedcba9876543210fedcba9876543210").unwrap();
+    let token = other.encode(&claims("alice", 7));
+    assert_eq!(codec.decode(&token, NOW), Err(TokenRejection::BadSignature));
+
+    // Claims swapped in from a token for another user keep the original signature
+    let genuine = codec.encode(&claims("alice", 7));
+    let forged_payload = codec.encode(&claims("admin", 7));
+    let forged = format!(
+        "{}.{}",
+        forged_payload.rsplit_once('.').unwrap().0,
+        genuine.rsplit_once('.').unwrap().1
+    );
+    assert_eq!(
+        codec.decode(&forged, NOW),
+        Err(TokenRejection::BadSignature)
+    );
+
+    assert_eq!(
+        codec.decode("deadbeef", NOW),
+        Err(TokenRejection::Malformed)
+    );
+    assert_eq!(
+        codec.decode("v1.no-signature", NOW),
+        Err(TokenRejection::Malformed)
+    );
+}
+
+#[test]
+fn rejects_expired_tokens() {
+    let codec = StatelessTokenCodec::new(KEY).unwrap();
+    let token = codec.encode(&claims("alice", 7));
+
+    assert!(codec.decode(&token, NOW + 300).is_ok());
+    assert_eq!(
+        codec.decode(&token, NOW + 301),
+        Err(TokenRejection::Expired)
+    );
+}
+
+#[test]
+fn deny_list_revokes_single_tokens_and_whole_users() {
+    let mut deny_list = SessionDenyList::new();
+    let first = claims("alice", 1);
+    let second = claims("alice", 2);
+
+    deny_list.revoke_token(&first);
+    assert!(deny_list.is_revoked(&first));
+    assert!(!deny_list.is_revoked(&second));
+
+    deny_list.revoke_user("alice", NOW * 1000);
+    assert!(deny_list.is_revoked(&second));
+    // Tokens issued after the revocation are accepted again
+    let reissued = SessionClaims {
+        issued_at_ms: NOW * 1000 + 1,
+        ..claims("alice", 3)
+    };
+    assert!(!deny_list.is_revoked(&reissued));
+    assert_eq!(deny_list.issue_time("alice", NOW * 1000), NOW * 1000 + 1);
+    assert_eq!(deny_list.issue_time("bob", NOW * 1000), NOW * 1000);
+    assert!(!deny_list.is_revoked(&claims("bob", 4)));
+}
+
+#[test]
+fn deny_list_cleanup_keeps_entries_until_tokens_expire() {
+    let mut deny_list = SessionDenyList::new();
+    deny_list.revoke_token(&claims("alice", 1));
+    deny_list.revoke_user("bob", NOW * 1000);
+
+    assert_eq!(deny_list.cleanup_expired(NOW + 300, 300), 0);
+    assert_eq!(deny_list.cleanup_expired(NOW + 301, 300), 2);
+    assert_eq!(deny_list.len(), 0);
+}
@@ -1,57 +1,187 @@
-use super::types::{AuthError, AuthResult, MAX_SIGNATURE_LENGTH, MAX_USER_ID_LENGTH, UserCache};
+use super::types::{
+    AuthError, AuthResult, MAX_NONCE_LENGTH, MAX_SIGNATURE_LENGTH, MAX_USER_ID_LENGTH, UserCache,
+};
 use hmac::{Hmac, Mac};
+use rayon::prelude::*;
 use sha2::Sha256;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use subtle::ConstantTimeEq;
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
 type HmacSha256 = Hmac<Sha256>;
 
+// Batches at least this large are verified across the rayon pool
+const PARALLEL_BATCH_THRESHOLD: usize = 64;
+
+/// Why a signature check failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureFailure {
+    /// Malformed input, unknown or inactive user
+    Rejected,
+    /// A known, active user presented a wrong signature
+    Mismatch,
+    /// The account is locked out after repeated failures
+    Locked,
+}
+
 /// Verifies HMAC signature. Uses constant-time comparison. Returns generic errors.
+#[allow(dead_code)] // Used in tests; AuthManager goes through `check_signature`
 pub async fn verify_signature(
     cache: &Arc<RwLock<UserCache>>,
     message: &str,
     user_id: &str,
     signature: &str,
 ) -> AuthResult<()> {
+    check_signature(cache, message, user_id, signature)
+        .await
+        .map_err(|_| AuthError::AuthenticationFailed) // Return generic error
+}
+
+/// Like `verify_signature`, but tells a wrong signature apart from other rejections
+/// so only genuine signature failures count towards a lockout.
+pub async fn check_signature(
+    cache: &Arc<RwLock<UserCache>>,
+    message: &str,
+    user_id: &str,
+    signature: &str,
+) -> Result<(), SignatureFailure> {
     // Validate input lengths to prevent DoS
     if signature.len() > MAX_SIGNATURE_LENGTH {
         warn!(target: "sneldb::auth", signature_len = signature.len(), "Signature too long");
-        return Err(AuthError::AuthenticationFailed);
+        return Err(SignatureFailure::Rejected);
+    }
+
+    let mac = {
+        let cache_guard = cache.read().await;
+        user_mac(&cache_guard, user_id)?
+    };
+
+    // Constant-time comparison
+    if signature_matches(mac, message, signature) {
+        Ok(())
+    } else {
+        warn!(target: "sneldb::auth", user_id, "Invalid signature");
+        Err(SignatureFailure::Mismatch)
     }
+}
 
+/// Verifies many (message, signature) pairs signed by one user. The user is looked up
+/// and the HMAC key prepared once for the whole batch, and large batches are spread
+/// across the rayon pool. Every pair gets its own result, so a bad signature is
+/// reported at its index while the rest of the batch still verifies.
+pub async fn check_signature_batch(
+    cache: &Arc<RwLock<UserCache>>,
+    user_id: &str,
+    items: &[(&str, &str)],
+) -> Vec<Result<(), SignatureFailure>> {
+    let mac = {
+        let cache_guard = cache.read().await;
+        match user_mac(&cache_guard, user_id) {
+            Ok(mac) => mac,
+            Err(failure) => return vec![Err(failure); items.len()],
+        }
+    };
+
+    let verify = |(index, (message, signature)): (usize, &(&str, &str))| {
+        if signature.len() > MAX_SIGNATURE_LENGTH {
+            warn!(target: "sneldb::auth", index, signature_len = signature.len(), "Signature too long");
+            return Err(SignatureFailure::Rejected);
+        }
+        if signature_matches(mac.clone(), message, signature) {
+            Ok(())
+        } else {
+            warn!(target: "sneldb::auth", user_id, index, "Invalid signature in batch");
+            Err(SignatureFailure::Mismatch)
+        }
+    };
+    if items.len() >= PARALLEL_BATCH_THRESHOLD {
+        items.par_iter().enumerate().map(verify).collect()
+    } else {
+        items.iter().enumerate().map(verify).collect()
+    }
+}
+
+/// HMAC keyed with the secret of `user_id`, if the user may authenticate at all.
+fn user_mac(cache: &UserCache, user_id: &str) -> Result<HmacSha256, SignatureFailure> {
     if user_id.len() > MAX_USER_ID_LENGTH {
         warn!(target: "sneldb::auth", user_id_len = user_id.len(), "User ID too long");
-        return Err(AuthError::AuthenticationFailed);
+        return Err(SignatureFailure::Rejected);
     }
 
-    let cache_guard = cache.read().await;
-    let user_key = cache_guard.get(user_id).ok_or_else(|| {
+    let user_key = cache.get(user_id).ok_or_else(|| {
         debug!(target: "sneldb::auth", user_id, "User not found in cache");
-        AuthError::AuthenticationFailed // Return generic error
+        SignatureFailure::Rejected
     })?;
 
     if !user_key.active {
         debug!(target: "sneldb::auth", user_id, "User inactive");
-        return Err(AuthError::AuthenticationFailed); // Return generic error
+        return Err(SignatureFailure::Rejected);
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    if user_key.is_locked(now) {
+        debug!(target: "sneldb::auth", user_id, "User locked out");
+        return Err(SignatureFailure::Locked);
     }
 
-    // Compute expected HMAC
-    let mut mac = HmacSha256::new_from_slice(user_key.secret_key.as_bytes()).map_err(|_| {
+    HmacSha256::new_from_slice(user_key.secret_key.as_bytes()).map_err(|_| {
         warn!(target: "sneldb::auth", "Failed to create HMAC");
-        AuthError::AuthenticationFailed
-    })?;
+        SignatureFailure::Rejected
+    })
+}
+
+/// Computes the expected HMAC of `message` and compares it in constant time.
+fn signature_matches(mut mac: HmacSha256, message: &str, signature: &str) -> bool {
     mac.update(message.as_bytes());
     let expected_signature = hex::encode(mac.finalize().into_bytes());
+    constant_time_eq(signature.as_bytes(), expected_signature.as_bytes())
+}
 
-    // Constant-time comparison
-    if constant_time_eq(signature.as_bytes(), expected_signature.as_bytes()) {
-        Ok(())
-    } else {
-        warn!(target: "sneldb::auth", user_id, "Invalid signature");
-        Err(AuthError::AuthenticationFailed) // Return generic error
+/// Timestamp and nonce a client bound into its signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayStamp<'a> {
+    /// Epoch seconds when the request was signed
+    pub timestamp: u64,
+    pub nonce: &'a str,
+}
+
+impl ReplayStamp<'_> {
+    /// The message a stamped signature covers: `<timestamp>:<nonce>:<message>`.
+    pub fn signed_message(&self, message: &str) -> String {
+        format!("{}:{}:{}", self.timestamp, self.nonce, message)
+    }
+}
+
+/// Splits a stamped signature `<timestamp>.<nonce>.<hmac>` into its stamp and HMAC.
+/// Plain HMACs (no `.`) are returned without a stamp. Nonces are 1-64 characters of
+/// `[A-Za-z0-9_-]`.
+pub fn split_stamped_signature(signature: &str) -> AuthResult<(Option<ReplayStamp<'_>>, &str)> {
+    let mut parts = signature.splitn(3, '.');
+    let (Some(timestamp), Some(nonce), Some(hmac)) = (parts.next(), parts.next(), parts.next())
+    else {
+        if signature.contains('.') {
+            return Err(AuthError::AuthenticationFailed);
+        }
+        return Ok((None, signature));
+    };
+
+    let timestamp = timestamp
+        .parse::<u64>()
+        .map_err(|_| AuthError::AuthenticationFailed)?;
+    let nonce_valid = !nonce.is_empty()
+        && nonce.len() <= MAX_NONCE_LENGTH
+        && nonce
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+    if !nonce_valid {
+        return Err(AuthError::AuthenticationFailed);
     }
+    Ok((Some(ReplayStamp { timestamp, nonce }), hmac))
 }
 
 /// Parses "user_id:signature:command" format. Validates lengths.
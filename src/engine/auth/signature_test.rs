@@ -1,4 +1,7 @@
-use super::signature::{parse_auth, verify_signature};
+use super::signature::{
+    ReplayStamp, SignatureFailure, check_signature, check_signature_batch, parse_auth,
+    split_stamped_signature, verify_signature,
+};
 use super::types::{AuthError, UserCache, UserKey};
 use crate::logging::init_for_tests;
 use hmac::{Hmac, Mac};
@@ -23,6 +26,7 @@ async fn create_test_cache_with_user(
         created_at: 1000,
         roles: Vec::new(),
         permissions: HashMap::new(),
+        locked_until: None,
     };
     {
         let mut cache_guard = cache.write().await;
@@ -412,3 +416,178 @@ async fn test_verify_signature_different_secret_keys() {
             .is_err()
     );
 }
+
+#[tokio::test]
+async fn test_check_signature_only_flags_wrong_signatures_as_mismatch() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("test_user", "my_secret_key", true).await;
+    let message = "QUERY test_event";
+    let signature = compute_hmac(message, "my_secret_key");
+
+    assert_eq!(
+        check_signature(&cache, message, "test_user", &signature).await,
+        Ok(())
+    );
+    assert_eq!(
+        check_signature(&cache, message, "test_user", "deadbeef").await,
+        Err(SignatureFailure::Mismatch)
+    );
+    assert_eq!(
+        check_signature(&cache, message, "unknown_user", &signature).await,
+        Err(SignatureFailure::Rejected)
+    );
+    assert_eq!(
+        check_signature(&cache, message, "test_user", &"a".repeat(300)).await,
+        Err(SignatureFailure::Rejected)
+    );
+
+    let inactive = create_test_cache_with_user("test_user", "my_secret_key", false).await;
+    assert_eq!(
+        check_signature(&inactive, message, "test_user", "deadbeef").await,
+        Err(SignatureFailure::Rejected)
+    );
+}
+
+#[tokio::test]
+async fn test_check_signature_rejects_locked_users_until_deadline() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("test_user", "my_secret_key", true).await;
+    let message = "QUERY test_event";
+    let signature = compute_hmac(message, "my_secret_key");
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let lock_until = |locked_until: Option<u64>| {
+        let cache = Arc::clone(&cache);
+        async move {
+            let mut guard = cache.write().await;
+            let mut user = guard.get("test_user").unwrap().clone();
+            user.locked_until = locked_until;
+            guard.insert(user);
+        }
+    };
+
+    lock_until(Some(now + 600)).await;
+    assert_eq!(
+        check_signature(&cache, message, "test_user", &signature).await,
+        Err(SignatureFailure::Locked)
+    );
+    assert!(matches!(
+        verify_signature(&cache, message, "test_user", &signature).await,
+        Err(AuthError::AuthenticationFailed)
+    ));
+
+    // An elapsed lock no longer applies
+    lock_until(Some(now - 1)).await;
+    assert_eq!(
+        check_signature(&cache, message, "test_user", &signature).await,
+        Ok(())
+    );
+}
+
+#[test]
+fn test_split_stamped_signature() {
+    assert_eq!(split_stamped_signature("abc123").unwrap(), (None, "abc123"));
+
+    let (stamp, hmac) = split_stamped_signature("1700000000.n-1_a.abc123").unwrap();
+    let stamp = stamp.unwrap();
+    assert_eq!(
+        stamp,
+        ReplayStamp {
+            timestamp: 1_700_000_000,
+            nonce: "n-1_a",
+        }
+    );
+    assert_eq!(hmac, "abc123");
+    assert_eq!(stamp.signed_message("PING"), "1700000000:n-1_a:PING");
+
+    for malformed in [
+        "1700000000.abc123",
+        "soon.n1.abc123",
+        "1700000000..abc123",
+        "1700000000.n 1.abc123",
+    ] {
+        assert!(
+            matches!(
+                split_stamped_signature(malformed),
+                Err(AuthError::AuthenticationFailed)
+            ),
+            "{malformed} should be rejected"
+        );
+    }
+    let long_nonce = format!("1700000000.{}.abc123", "n".repeat(65));
+    assert!(split_stamped_signature(&long_nonce).is_err());
+}
+
+#[tokio::test]
+async fn test_check_signature_batch_isolates_bad_signatures() {
+    init_for_tests();
+
+    let cache = create_test_cache_with_user("test_user", "my_secret_key", true).await;
+    // Large enough to take the parallel path
+    let messages: Vec<String> = (0..100)
+        .map(|i| format!("STORE test_event FOR ctx{} PAYLOAD {{\"id\":{}}}", i, i))
+        .collect();
+    let mut signatures: Vec<String> = messages
+        .iter()
+        .map(|m| compute_hmac(m, "my_secret_key"))
+        .collect();
+    signatures[3] = compute_hmac(&messages[4], "my_secret_key");
+    signatures[70] = "a".repeat(300);
+
+    let items: Vec<(&str, &str)> = messages
+        .iter()
+        .zip(&signatures)
+        .map(|(m, s)| (m.as_str(), s.as_str()))
+        .collect();
+    let results = check_signature_batch(&cache, "test_user", &items).await;
+
+    assert_eq!(results.len(), 100);
+    assert_eq!(results[3], Err(SignatureFailure::Mismatch));
+    assert_eq!(results[70], Err(SignatureFailure::Rejected));
+    let failed: Vec<usize> = results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.is_err())
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(failed, vec![3, 70]);
+
+    // Small batches verify the same way
+    assert_eq!(
+        check_signature_batch(&cache, "test_user", &items[..5]).await,
+        vec![
+            Ok(()),
+            Ok(()),
+            Ok(()),
+            Err(SignatureFailure::Mismatch),
+            Ok(())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_check_signature_batch_rejects_all_for_unknown_user() {
+    init_for_tests();
+
+    let cache = create_empty_cache();
+    let signature = compute_hmac("PING", "my_secret_key");
+    let results = check_signature_batch(
+        &cache,
+        "ghost",
+        &[("PING", signature.as_str()), ("PING", "x")],
+    )
+    .await;
+    assert_eq!(
+        results,
+        vec![
+            Err(SignatureFailure::Rejected),
+            Err(SignatureFailure::Rejected)
+        ]
+    );
+    assert!(check_signature_batch(&cache, "ghost", &[]).await.is_empty());
+}
@@ -1,4 +1,4 @@
-use crate::engine::auth::types::{AuthError, AuthResult, User};
+use crate::engine::auth::types::{AuthError, AuthResult, PermissionSet, User};
 use crate::shared::config::CONFIG;
 use crate::shared::storage_header::MagicFile;
 use chacha20poly1305::aead::{Aead, KeyInit};
@@ -7,6 +7,7 @@ use crc32fast::Hasher as Crc32Hasher;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
@@ -36,6 +37,42 @@ struct AuthWalRecord {
     user: User,
 }
 
+/// Record layout written before users carried lockout state. bincode is not
+/// self-describing, so these frames need their own decoder.
+#[derive(Deserialize)]
+struct LegacyAuthWalRecord {
+    ts: u64,
+    user: LegacyUser,
+}
+
+#[derive(Deserialize)]
+struct LegacyUser {
+    user_id: String,
+    secret_key: String,
+    active: bool,
+    created_at: u64,
+    roles: Vec<String>,
+    permissions: HashMap<String, PermissionSet>,
+}
+
+impl From<LegacyAuthWalRecord> for AuthWalRecord {
+    fn from(record: LegacyAuthWalRecord) -> Self {
+        let user = record.user;
+        Self {
+            ts: record.ts,
+            user: User {
+                user_id: user.user_id,
+                secret_key: user.secret_key,
+                active: user.active,
+                created_at: user.created_at,
+                roles: user.roles,
+                permissions: user.permissions,
+                locked_until: None,
+            },
+        }
+    }
+}
+
 struct AuthWalHeader;
 
 impl MagicFile for AuthWalHeader {
@@ -130,6 +167,19 @@ impl AuthWalStorage {
             .map_err(|e| AuthError::DatabaseError(format!("serialize auth wal record failed: {e}")))
     }
 
+    /// Decodes a decrypted frame, falling back to the pre-lockout record layout.
+    pub(crate) fn decode_record(plaintext: &[u8]) -> bincode::Result<StoredUser> {
+        let record = bincode::deserialize::<AuthWalRecord>(plaintext).or_else(|e| {
+            bincode::deserialize::<LegacyAuthWalRecord>(plaintext)
+                .map(AuthWalRecord::from)
+                .map_err(|_| e)
+        })?;
+        Ok(StoredUser {
+            user: record.user,
+            persisted_at: record.ts,
+        })
+    }
+
     fn compute_crc(bytes: &[u8]) -> u32 {
         let mut hasher = Crc32Hasher::new();
         hasher.update(bytes);
@@ -265,11 +315,8 @@ impl AuthStorage for AuthWalStorage {
             let (nonce_bytes, ciphertext) = frame.split_at(12);
             let cipher = ChaCha20Poly1305::new(&self.key);
             match cipher.decrypt(Nonce::from_slice(nonce_bytes), ciphertext) {
-                Ok(plaintext) => match bincode::deserialize::<AuthWalRecord>(&plaintext) {
-                    Ok(record) => out.push(StoredUser {
-                        user: record.user,
-                        persisted_at: record.ts,
-                    }),
+                Ok(plaintext) => match Self::decode_record(&plaintext) {
+                    Ok(stored) => out.push(stored),
                     Err(e) => {
                         warn!(target: "sneldb::auth", error = %e, "auth wal decode failed; skipping frame")
                     }
@@ -26,6 +26,7 @@ fn writes_storage_header_and_payload() {
         created_at: 0,
         roles: vec!["admin".to_string()],
         permissions: perms,
+        locked_until: None,
     };
 
     storage.persist_user(&user).expect("persist user");
@@ -33,3 +34,51 @@ fn writes_storage_header_and_payload() {
     let magic = read_header_magic(&path);
     assert_eq!(magic, AUTH_MAGIC);
 }
+
+#[test]
+fn persists_lockout_state() {
+    let dir = tempdir().unwrap();
+    let storage = AuthWalStorage::new(dir.path().join("auth.swal")).expect("create auth storage");
+
+    let user = User {
+        user_id: "locked_user".to_string(),
+        secret_key: "sekret".to_string(),
+        active: true,
+        created_at: 0,
+        roles: Vec::new(),
+        permissions: std::collections::HashMap::new(),
+        locked_until: Some(1_700_000_900),
+    };
+    storage.persist_user(&user).expect("persist user");
+
+    let loaded = storage.load_users().expect("load users");
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].user.locked_until, Some(1_700_000_900));
+}
+
+#[test]
+fn decodes_records_written_before_lockout_state() {
+    // Layout of a record persisted before `locked_until` existed
+    let legacy = bincode::serialize(&(
+        42u64,
+        (
+            "old_user".to_string(),
+            "sekret".to_string(),
+            true,
+            7u64,
+            vec!["admin".to_string()],
+            std::collections::HashMap::from([("events".to_string(), PermissionSet::read_only())]),
+        ),
+    ))
+    .unwrap();
+
+    let stored = AuthWalStorage::decode_record(&legacy).expect("decode legacy record");
+    assert_eq!(stored.persisted_at, 42);
+    assert_eq!(stored.user.user_id, "old_user");
+    assert_eq!(stored.user.roles, vec!["admin".to_string()]);
+    assert_eq!(
+        stored.user.permissions.get("events"),
+        Some(&PermissionSet::read_only())
+    );
+    assert_eq!(stored.user.locked_until, None);
+}
@@ -9,6 +9,9 @@ use thiserror::Error;
 pub const MAX_USER_ID_LENGTH: usize = 64;
 pub const MAX_SECRET_KEY_LENGTH: usize = 512;
 pub const MAX_SIGNATURE_LENGTH: usize = 256;
+pub const MAX_NONCE_LENGTH: usize = 64;
+/// Opaque tokens are 64 hex chars; stateless ones carry the user's roles and permissions
+pub const MAX_SESSION_TOKEN_LENGTH: usize = 4096;
 
 // Special user IDs for bypass modes
 pub const BYPASS_USER_ID: &str = "bypass";
@@ -67,6 +70,9 @@ pub struct User {
     /// Key: event_type, Value: PermissionSet
     #[serde(default)]
     pub permissions: HashMap<String, PermissionSet>,
+    /// Epoch seconds until which signature auth is refused after repeated failures
+    #[serde(default)]
+    pub locked_until: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,6 +83,14 @@ pub struct UserKey {
     pub created_at: u64,
     pub roles: Vec<String>,
     pub permissions: HashMap<String, PermissionSet>,
+    pub locked_until: Option<u64>,
+}
+
+impl UserKey {
+    /// Whether the account is locked out at `now` (epoch seconds).
+    pub fn is_locked(&self, now: u64) -> bool {
+        self.locked_until.is_some_and(|until| until > now)
+    }
 }
 
 impl From<User> for UserKey {
@@ -88,6 +102,7 @@ impl From<User> for UserKey {
             created_at: user.created_at,
             roles: user.roles,
             permissions: user.permissions,
+            locked_until: user.locked_until,
         }
     }
 }
@@ -105,12 +120,24 @@ pub enum AuthError {
     UserIdTooLong { max: usize },
     #[error("Secret key too long (max {max} characters)")]
     SecretKeyTooLong { max: usize },
+    #[error("New secret key must differ from the current one")]
+    SecretKeyUnchanged,
     #[error("Signature too long (max {max} characters)")]
     SignatureTooLong { max: usize },
     #[error("Database operation failed: {0}")]
     DatabaseError(String),
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
+    #[error("Account locked after repeated authentication failures")]
+    AccountLocked,
+    #[error("Request timestamp outside the allowed clock skew")]
+    StaleRequest,
+    #[error("Request nonce already used")]
+    ReplayedRequest,
+    #[error("Client certificate not trusted")]
+    UntrustedClientCertificate,
+    #[error("No user mapped to client certificate: {0}")]
+    UnmappedClientCertificate(String),
 
     // Internal errors (logging only)
     #[error("User not found: {0}")]
@@ -121,6 +148,29 @@ pub enum AuthError {
 
 pub type AuthResult<T> = Result<T, AuthError>;
 
+/// Identity extracted from a client certificate presented over mutual TLS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertIdentity {
+    /// Full subject distinguished name, RFC 4514 escaped (e.g. "CN=ingest,O=Acme").
+    pub subject: String,
+    /// Common name (CN) component of the subject, if present.
+    pub common_name: Option<String>,
+    /// Whether the certificate chain was verified against the trusted client CA.
+    pub verified: bool,
+}
+
+impl ClientCertIdentity {
+    /// Builds an identity from a verified certificate's subject DN and the CN
+    /// taken from its parsed subject.
+    pub fn verified(subject: impl Into<String>, common_name: Option<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            common_name,
+            verified: true,
+        }
+    }
+}
+
 /// In-memory cache for user keys.
 #[derive(Debug, Clone)]
 pub struct UserCache {
@@ -16,6 +16,7 @@ fn create_user_key(
         created_at: 1000,
         roles: roles.iter().map(|s| s.to_string()).collect(),
         permissions,
+        locked_until: None,
     }
 }
 
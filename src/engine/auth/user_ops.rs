@@ -113,6 +113,7 @@ pub async fn create_user_with_roles(
         created_at,
         roles,
         permissions: HashMap::new(),
+        locked_until: None,
     };
 
     // Store in database first
@@ -144,6 +145,19 @@ pub async fn revoke_key(
     permission_cache: &Arc<RwLock<PermissionCache>>,
     auth_storage: &Arc<dyn AuthStorage>,
     user_id: &str,
+) -> AuthResult<()> {
+    set_user_active(cache, permission_cache, auth_storage, user_id, false).await?;
+    info!(target: "sneldb::auth", user_id, "User key revoked");
+    Ok(())
+}
+
+/// Marks a user active or inactive, keeping their key, roles and permissions.
+pub async fn set_user_active(
+    cache: &Arc<RwLock<UserCache>>,
+    permission_cache: &Arc<RwLock<PermissionCache>>,
+    auth_storage: &Arc<dyn AuthStorage>,
+    user_id: &str,
+    active: bool,
 ) -> AuthResult<()> {
     // Use read lock first to get user data
     let user_key = {
@@ -151,7 +165,7 @@ pub async fn revoke_key(
         cache_guard
             .get(user_id)
             .ok_or_else(|| {
-                debug!(target: "sneldb::auth", user_id, "User not found during revoke");
+                debug!(target: "sneldb::auth", user_id, "User not found while changing active state");
                 AuthError::UserNotFound(user_id.to_string())
             })?
             .clone()
@@ -161,37 +175,129 @@ pub async fn revoke_key(
     let updated_user = User {
         user_id: user_id.to_string(),
         secret_key: user_key.secret_key.clone(),
-        active: false,
+        active,
         created_at: user_key.created_at, // Preserve timestamp
         roles: user_key.roles.clone(),
         permissions: user_key.permissions.clone(),
+        locked_until: user_key.locked_until,
     };
 
     store_user_in_db(auth_storage, &updated_user).await?;
 
     // Update user cache
-    let updated_key = UserKey {
+    let updated_key = UserKey::from(updated_user);
+
+    {
+        let mut cache_guard = cache.write().await;
+        cache_guard.insert(updated_key.clone());
+    } // Drop write lock on user cache
+
+    // Update permission cache
+    {
+        let mut perm_cache_guard = permission_cache.write().await;
+        perm_cache_guard.update_user(&updated_key);
+    } // Drop write lock on permission cache
+
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        debug!(target: "sneldb::auth", user_id, active, "User active state changed");
+    }
+    Ok(())
+}
+
+/// Sets or clears a user's lockout deadline (epoch seconds) and persists it.
+pub async fn set_locked_until(
+    cache: &Arc<RwLock<UserCache>>,
+    auth_storage: &Arc<dyn AuthStorage>,
+    user_id: &str,
+    locked_until: Option<u64>,
+) -> AuthResult<()> {
+    let user_key = {
+        let cache_guard = cache.read().await;
+        cache_guard
+            .get(user_id)
+            .ok_or_else(|| {
+                debug!(target: "sneldb::auth", user_id, "User not found while changing lockout");
+                AuthError::UserNotFound(user_id.to_string())
+            })?
+            .clone()
+    }; // Drop read lock
+
+    let updated_user = User {
         user_id: user_id.to_string(),
-        secret_key: user_key.secret_key.clone(),
-        active: false,
+        secret_key: user_key.secret_key,
+        active: user_key.active,
+        created_at: user_key.created_at,
+        roles: user_key.roles,
+        permissions: user_key.permissions,
+        locked_until,
+    };
+
+    store_user_in_db(auth_storage, &updated_user).await?;
+
+    // Lockout does not affect permissions, so only the user cache changes
+    let mut cache_guard = cache.write().await;
+    cache_guard.insert(UserKey::from(updated_user));
+    Ok(())
+}
+
+/// Replaces a user's secret key (generated when `secret_key` is None) and returns it.
+/// The user cache entry is swapped in one step, so signatures are checked against either
+/// the old key or the new one, never both. Sessions are left to the caller.
+pub async fn rotate_key(
+    cache: &Arc<RwLock<UserCache>>,
+    permission_cache: &Arc<RwLock<PermissionCache>>,
+    auth_storage: &Arc<dyn AuthStorage>,
+    user_id: &str,
+    secret_key: Option<String>,
+) -> AuthResult<String> {
+    let user_key = {
+        let cache_guard = cache.read().await;
+        cache_guard
+            .get(user_id)
+            .ok_or_else(|| {
+                debug!(target: "sneldb::auth", user_id, "User not found during key rotation");
+                AuthError::UserNotFound(user_id.to_string())
+            })?
+            .clone()
+    }; // Drop read lock
+
+    let secret = match secret_key {
+        Some(key) => {
+            validate_secret_key(&key)?;
+            if key == user_key.secret_key {
+                return Err(AuthError::SecretKeyUnchanged);
+            }
+            key
+        }
+        None => generate_secret_key(),
+    };
+
+    let updated_user = User {
+        user_id: user_id.to_string(),
+        secret_key: secret.clone(),
+        active: user_key.active,
         created_at: user_key.created_at,
         roles: user_key.roles.clone(),
         permissions: user_key.permissions.clone(),
+        locked_until: user_key.locked_until,
     };
 
+    // Persist first: if storage fails, the old key stays in force everywhere
+    store_user_in_db(auth_storage, &updated_user).await?;
+
+    let updated_key = UserKey::from(updated_user);
     {
         let mut cache_guard = cache.write().await;
         cache_guard.insert(updated_key.clone());
     } // Drop write lock on user cache
 
-    // Update permission cache
     {
         let mut perm_cache_guard = permission_cache.write().await;
         perm_cache_guard.update_user(&updated_key);
     } // Drop write lock on permission cache
 
-    info!(target: "sneldb::auth", user_id, "User key revoked");
-    Ok(())
+    info!(target: "sneldb::auth", user_id, "User key rotated");
+    Ok(secret)
 }
 
 /// Lists all users
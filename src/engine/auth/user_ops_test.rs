@@ -4,6 +4,7 @@ use super::types::{
 };
 use super::user_ops::{
     bootstrap_admin_user, create_user, create_user_with_roles, list_users, revoke_key,
+    set_user_active,
 };
 use crate::logging::init_for_tests;
 use crate::shared::config::CONFIG;
@@ -1185,3 +1186,83 @@ async fn test_revoke_key_twice_still_inactive() {
     assert_eq!(users.len(), 1);
     assert!(!users[0].active);
 }
+
+#[tokio::test]
+async fn test_set_user_active_persists_and_keeps_key_and_roles() {
+    init_for_tests();
+
+    let (cache, permission_cache, auth_storage) = create_test_deps().await;
+    let secret = create_user_with_roles(
+        &cache,
+        &permission_cache,
+        &auth_storage,
+        "toggled".to_string(),
+        None,
+        vec!["editor".to_string()],
+    )
+    .await
+    .unwrap();
+
+    set_user_active(&cache, &permission_cache, &auth_storage, "toggled", false)
+        .await
+        .unwrap();
+    assert!(!list_users(&cache).await[0].active);
+
+    set_user_active(&cache, &permission_cache, &auth_storage, "toggled", true)
+        .await
+        .unwrap();
+    let users = list_users(&cache).await;
+    assert!(users[0].active);
+    assert_eq!(users[0].secret_key, secret);
+    assert_eq!(users[0].roles, vec!["editor".to_string()]);
+
+    // The latest record in storage carries the new state
+    let stored = auth_storage.load_users().unwrap();
+    let latest = stored
+        .iter()
+        .filter(|s| s.user.user_id == "toggled")
+        .last()
+        .unwrap();
+    assert!(latest.user.active);
+
+    let missing = set_user_active(&cache, &permission_cache, &auth_storage, "ghost", true).await;
+    assert!(matches!(missing, Err(AuthError::UserNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_rotate_key_keeps_state_and_validates_secret() {
+    use super::user_ops::rotate_key;
+    init_for_tests();
+
+    let (cache, permission_cache, auth_storage) = create_test_deps().await;
+    create_user_with_roles(
+        &cache,
+        &permission_cache,
+        &auth_storage,
+        "rotating".to_string(),
+        Some("old_secret".to_string()),
+        vec!["read-only".to_string()],
+    )
+    .await
+    .unwrap();
+
+    let too_long = "k".repeat(MAX_SECRET_KEY_LENGTH + 1);
+    let result = rotate_key(
+        &cache,
+        &permission_cache,
+        &auth_storage,
+        "rotating",
+        Some(too_long),
+    )
+    .await;
+    assert!(matches!(result, Err(AuthError::SecretKeyTooLong { .. })));
+
+    let secret = rotate_key(&cache, &permission_cache, &auth_storage, "rotating", None)
+        .await
+        .unwrap();
+    let users = list_users(&cache).await;
+    assert_eq!(users[0].secret_key, secret);
+    assert_ne!(secret, "old_secret");
+    assert!(users[0].active);
+    assert_eq!(users[0].roles, vec!["read-only".to_string()]);
+}
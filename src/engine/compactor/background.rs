@@ -1,8 +1,10 @@
 use crate::engine::core::compaction::{
     handover::CompactionHandover,
     policy::{CompactionPolicy, KWayCountPolicy},
+    retention::RetentionPolicy,
 };
 use crate::engine::core::utils::system_info_cache::get_system_info_cache;
+use crate::engine::core::utils::worker_pools::spawn_background;
 use crate::engine::core::{CompactionWorker, IoMonitor, MemoryMonitor, SegmentIndex};
 use crate::engine::schema::SchemaRegistry;
 use crate::shared::config::CONFIG;
@@ -24,7 +26,7 @@ pub async fn start_background_compactor(
     segment_ids: Arc<StdRwLock<Vec<String>>>,
     flush_lock: Arc<tokio::sync::Mutex<()>>,
 ) {
-    tokio::spawn(async move {
+    spawn_background(async move {
         // Get cached system info (refreshed in background)
         let system_info_cache = get_system_info_cache();
 
@@ -42,6 +44,8 @@ pub async fn start_background_compactor(
             Arc::clone(&flush_lock),
         ));
 
+        let retention_enabled = !RetentionPolicy::from_config().is_empty();
+
         loop {
             sleep(Duration::from_secs(CONFIG.engine.compaction_interval)).await;
 
@@ -68,10 +72,11 @@ pub async fn start_background_compactor(
             match SegmentIndex::load(&shard_dir).await {
                 Ok(segment_index) => {
                     warn!(shard_id, "Segment index loaded");
-                    // Policy-based trigger: run only if there are plans
+                    // Policy-based trigger: run only if there are plans,
+                    // or if retention may have expired data to drop
                     let policy = KWayCountPolicy::default();
                     let plans = CompactionPolicy::plan(&policy, &segment_index);
-                    if !plans.is_empty() {
+                    if !plans.is_empty() || retention_enabled {
                         warn!(shard_id, "Background compaction triggered");
                         let registry = Arc::new(tokio::sync::RwLock::new(
                             SchemaRegistry::new().expect("Failed to initialize SchemaRegistry"),
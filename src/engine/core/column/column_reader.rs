@@ -3,7 +3,7 @@ use crate::engine::core::column::column_block_snapshot::ColumnBlockSnapshot;
 use crate::engine::core::column::column_values::ColumnValues;
 use crate::engine::core::column::compression::{CompressedColumnIndex, ZoneBlockEntry};
 use crate::engine::core::column::format::{ColumnBlockHeader, PhysicalType};
-use crate::engine::core::column::reader::{decompress, io, view::ColumnBlockView};
+use crate::engine::core::column::reader::{checksum, decompress, io, view::ColumnBlockView};
 use crate::engine::core::read::cache::DecompressedBlock;
 use crate::engine::errors::QueryExecutionError;
 use std::path::Path;
@@ -213,8 +213,16 @@ impl ColumnReader {
         let mmap = io::map_column_file(segment_dir, uid, field)?;
         let (start, end) = io::compressed_range(entry, mmap.len())?;
         let compressed = &mmap[start..end];
+        checksum::verify_block(
+            checksum::configured_mode(),
+            &segment_dir.join(format!("{}_{}.col", uid, field)),
+            entry,
+            compressed,
+        )
+        .map_err(|e| QueryExecutionError::ColRead(e.to_string()))?;
 
-        let decompressed = decompress::decompress_block(compressed, entry.uncomp_len as usize)?;
+        let decompressed =
+            decompress::decompress_block(compressed, entry.uncomp_len as usize, entry.codec)?;
 
         let block = Arc::new(DecompressedBlock::from_bytes(decompressed));
         let (phys, values) = Self::build_zero_copy_values(entry, &block)?;
@@ -1,3 +1,4 @@
+use crate::engine::core::column::compression::CompressedColumnIndex;
 use crate::engine::core::column::format::PhysicalType;
 use crate::engine::core::{ColumnReader, ColumnWriter, ZonePlan};
 use crate::test_helpers::factories::{EventFactory, SchemaRegistryFactory};
@@ -248,3 +249,54 @@ async fn load_snapshot_reports_physical_types() {
     assert_eq!(note_snapshot.physical_type(), PhysicalType::VarBytes);
     assert_eq!(note_snapshot.to_strings(), vec!["first", "second"]);
 }
+
+#[tokio::test]
+async fn corrupt_block_fails_the_read() {
+    // config/test.toml verifies block checksums in fail mode
+    let dir = tempdir().unwrap();
+    let segment_dir = dir.path().to_path_buf();
+    let registry_factory = SchemaRegistryFactory::new();
+    let registry = registry_factory.registry();
+    registry_factory
+        .define_with_fields("login", &[("device", "string")])
+        .await
+        .unwrap();
+
+    let events = EventFactory::new()
+        .with("event_type", "login")
+        .with("payload", json!({ "device": "laptop" }))
+        .create_list(3);
+    let uid = registry.read().await.get_uid("login").unwrap();
+    let zone = ZonePlan {
+        id: 5,
+        start_index: 0,
+        end_index: 2,
+        events,
+        uid: uid.clone(),
+        event_type: "login".into(),
+        segment_id: 9,
+        created_at: 0,
+    };
+    let writer = ColumnWriter::new(segment_dir.clone(), Arc::clone(&registry));
+    writer.write_all(&[zone]).await.unwrap();
+    assert!(ColumnReader::load_for_zone(&segment_dir, "9", &uid, "device", 5).is_ok());
+
+    // Flip the last byte of the zone's compressed block
+    let index = CompressedColumnIndex::load_from_path(&CompressedColumnIndex::path_for(
+        &uid,
+        "device",
+        &segment_dir,
+    ))
+    .unwrap();
+    let entry = index.entries.get(&5).unwrap();
+    let col_path = segment_dir.join(format!("{}_device.col", uid));
+    let mut bytes = std::fs::read(&col_path).unwrap();
+    let last = (entry.block_start + u64::from(entry.comp_len) - 1) as usize;
+    bytes[last] ^= 0xff;
+    std::fs::write(&col_path, &bytes).unwrap();
+
+    let err = ColumnReader::load_for_zone(&segment_dir, "9", &uid, "device", 5).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("corrupt block"), "{msg}");
+    assert!(msg.contains("zone 5"), "{msg}");
+}
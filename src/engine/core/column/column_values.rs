@@ -2,9 +2,13 @@ use std::sync::{Arc, OnceLock};
 
 use crate::engine::core::column::format::PhysicalType;
 use crate::engine::core::read::cache::DecompressedBlock;
+use crate::engine::types::uuid;
 use std::simd::Simd;
 use std::simd::prelude::*;
 
+/// Typed view into a block: (payload_start, row_count, optional (nulls_start, nulls_len)).
+type TypedView = (usize, usize, Option<(usize, usize)>);
+
 /// Zero-copy view over values stored inside a decompressed column block.
 #[derive(Clone, Debug)]
 pub struct ColumnValues {
@@ -14,14 +18,18 @@ pub struct ColumnValues {
     numeric_cache: Arc<OnceLock<Arc<Vec<Option<i64>>>>>,
     // Cached result indicating whether all entries are valid UTF-8
     utf8_validated: Arc<OnceLock<bool>>,
-    // Optional typed i64 view: (payload_start, row_count, optional (nulls_start, nulls_len))
-    typed_i64: Option<(usize, usize, Option<(usize, usize)>)>,
+    // Optional typed i64 view
+    typed_i64: Option<TypedView>,
     // Optional typed u64 view
-    typed_u64: Option<(usize, usize, Option<(usize, usize)>)>,
+    typed_u64: Option<TypedView>,
     // Optional typed f64 view
-    typed_f64: Option<(usize, usize, Option<(usize, usize)>)>,
+    typed_f64: Option<TypedView>,
     // Optional typed bool view: (payload_start for bitset, row_count, optional nulls bitset)
-    typed_bool: Option<(usize, usize, Option<(usize, usize)>)>,
+    typed_bool: Option<TypedView>,
+    // Optional typed UUID view: 16 bytes per row
+    typed_uuid: Option<TypedView>,
+    // Canonical text of every UUID row, built on first string access
+    uuid_text: Arc<OnceLock<String>>,
 }
 
 impl ColumnValues {
@@ -41,6 +49,8 @@ impl ColumnValues {
             typed_u64: None,
             typed_f64: None,
             typed_bool: None,
+            typed_uuid: None,
+            uuid_text: Arc::new(OnceLock::new()),
         }
     }
 
@@ -59,6 +69,8 @@ impl ColumnValues {
             typed_u64: None,
             typed_f64: None,
             typed_bool: None,
+            typed_uuid: None,
+            uuid_text: Arc::new(OnceLock::new()),
         }
     }
 
@@ -77,6 +89,8 @@ impl ColumnValues {
             typed_u64: Some((payload_start, row_count, nulls)),
             typed_f64: None,
             typed_bool: None,
+            typed_uuid: None,
+            uuid_text: Arc::new(OnceLock::new()),
         }
     }
 
@@ -95,6 +109,8 @@ impl ColumnValues {
             typed_u64: None,
             typed_f64: Some((payload_start, row_count, nulls)),
             typed_bool: None,
+            typed_uuid: None,
+            uuid_text: Arc::new(OnceLock::new()),
         }
     }
 
@@ -113,6 +129,28 @@ impl ColumnValues {
             typed_u64: None,
             typed_f64: None,
             typed_bool: Some((payload_start, row_count, nulls)),
+            typed_uuid: None,
+            uuid_text: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn new_typed_uuid(
+        block: Arc<DecompressedBlock>,
+        payload_start: usize,
+        row_count: usize,
+        nulls: Option<(usize, usize)>,
+    ) -> Self {
+        Self {
+            block,
+            ranges: Vec::new(),
+            numeric_cache: Arc::new(OnceLock::new()),
+            utf8_validated: Arc::new(OnceLock::new()),
+            typed_i64: None,
+            typed_u64: None,
+            typed_f64: None,
+            typed_bool: None,
+            typed_uuid: Some((payload_start, row_count, nulls)),
+            uuid_text: Arc::new(OnceLock::new()),
         }
     }
 
@@ -130,6 +168,9 @@ impl ColumnValues {
         if let Some((_, rc, _)) = self.typed_bool {
             return rc;
         }
+        if let Some((_, rc, _)) = self.typed_uuid {
+            return rc;
+        }
         self.ranges.len()
     }
 
@@ -149,6 +190,8 @@ impl ColumnValues {
             Some(PhysicalType::F64)
         } else if self.typed_bool.is_some() {
             Some(PhysicalType::Bool)
+        } else if self.typed_uuid.is_some() {
+            Some(PhysicalType::Uuid)
         } else {
             None // VarBytes or untyped
         }
@@ -161,6 +204,7 @@ impl ColumnValues {
             || self.typed_u64.is_some()
             || self.typed_f64.is_some()
             || self.typed_bool.is_some()
+            || self.typed_uuid.is_some()
     }
 
     /// Check if this column is typed as i64 (for SIMD-optimized aggregation)
@@ -175,6 +219,9 @@ impl ColumnValues {
             // Typed numeric column has no string view
             return None;
         }
+        if self.typed_uuid.is_some() {
+            return self.get_uuid_str_at(index);
+        }
         let (start, len) = *self.ranges.get(index)?;
         let bytes = &self.block.bytes[start..start + len];
         // Values are UTF-8 encoded when written; if invalid, return None.
@@ -227,6 +274,9 @@ impl ColumnValues {
     /// Unsafe fast-path: assumes the column has been validated via `validate_utf8`.
     #[inline]
     pub fn get_str_at_unchecked(&self, index: usize) -> Option<&str> {
+        if self.typed_uuid.is_some() {
+            return self.get_uuid_str_at(index);
+        }
         let (start, len) = *self.ranges.get(index)?;
         let bytes = &self.block.bytes[start..start + len];
         Some(unsafe { std::str::from_utf8_unchecked(bytes) })
@@ -321,6 +371,43 @@ impl ColumnValues {
         None
     }
 
+    /// The 16 bytes of a UUID row, read in place; `None` for nulls and for
+    /// columns that are not UUIDs.
+    #[inline]
+    pub fn get_uuid_at(&self, index: usize) -> Option<&[u8; 16]> {
+        let (payload_start, row_count, nulls) = self.typed_uuid?;
+        if index >= row_count {
+            return None;
+        }
+        if let Some((ns, _nl)) = nulls {
+            let nb = &self.block.bytes[ns..];
+            let bit = nb[index / 8] & (1 << (index % 8));
+            if bit != 0 {
+                return None;
+            }
+        }
+        let base = payload_start + index * 16;
+        self.block.bytes.get(base..base + 16)?.try_into().ok()
+    }
+
+    /// Canonical text of a UUID row. The text of the whole column is built
+    /// once, on first use, so filters comparing bytes never pay for it.
+    fn get_uuid_str_at(&self, index: usize) -> Option<&str> {
+        self.get_uuid_at(index)?;
+        let text = self.uuid_text.get_or_init(|| {
+            let rows = self.typed_uuid.map_or(0, |(_, rc, _)| rc);
+            let mut text = String::with_capacity(rows * uuid::CANONICAL_LEN);
+            for i in 0..rows {
+                // Null rows keep their slot so every row sits at a fixed offset
+                let bytes = self.get_uuid_at(i).copied().unwrap_or_default();
+                uuid::write_canonical(&bytes, &mut text);
+            }
+            text
+        });
+        let start = index * uuid::CANONICAL_LEN;
+        text.get(start..start + uuid::CANONICAL_LEN)
+    }
+
     /// Pre-builds the numeric cache for this column if any numeric access is expected.
     /// This avoids first-touch contention and amortizes parsing cost outside the hot loop.
     pub fn warm_numeric_cache(&self) {
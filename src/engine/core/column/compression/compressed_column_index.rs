@@ -5,8 +5,13 @@ use std::collections::HashMap;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use crate::engine::core::column::compression::{LeSliceReader, SIZE_U32, SIZE_U64};
-const MIN_ENTRY_SIZE: usize = SIZE_U32 + SIZE_U64 + SIZE_U32 + SIZE_U32 + SIZE_U32;
+use crate::engine::core::column::compression::compression_codec::ALGO_LZ4;
+use crate::engine::core::column::compression::{LeSliceReader, SIZE_U16, SIZE_U32, SIZE_U64};
+/// Entry size of version 1 files, which predate the per-block codec id.
+const V1_ENTRY_SIZE: usize = SIZE_U32 + SIZE_U64 + SIZE_U32 + SIZE_U32 + SIZE_U32;
+/// `.zfc` format version; version 1 entries have no codec id and are LZ4,
+/// version 2 entries have no checksum.
+const ZFC_VERSION: u16 = 3;
 
 #[derive(Clone, Debug)]
 pub struct ZoneBlockEntry {
@@ -15,6 +20,10 @@ pub struct ZoneBlockEntry {
     pub comp_len: u32,
     pub uncomp_len: u32,
     pub num_rows: u32,
+    /// Codec id the block was compressed with
+    pub codec: u16,
+    /// CRC32 of the compressed block; `None` in version 1 and 2 files
+    pub checksum: Option<u32>,
 }
 
 #[derive(Debug, Default)]
@@ -27,10 +36,22 @@ impl CompressedColumnIndex {
         segment_dir.join(format!("{}_{}.zfc", uid, field))
     }
 
+    /// Indexes with an entry lacking a checksum are written as version 2,
+    /// so that no reader checks a block against a made-up value.
+    fn write_version(&self) -> u16 {
+        if self.entries.values().all(|e| e.checksum.is_some()) {
+            ZFC_VERSION
+        } else {
+            2
+        }
+    }
+
     pub fn write_to_path(&self, path: &Path) -> Result<(), StoreError> {
         let file = std::fs::File::create(path)?;
         let mut writer = std::io::BufWriter::new(file);
-        BinaryHeader::new(FileKind::ZoneCompressedOffsets.magic(), 1, 0).write_to(&mut writer)?;
+        let version = self.write_version();
+        BinaryHeader::new(FileKind::ZoneCompressedOffsets.magic(), version, 0)
+            .write_to(&mut writer)?;
 
         let mut entries: Vec<_> = self.entries.values().cloned().collect();
         entries.sort_by_key(|e| e.zone_id);
@@ -41,6 +62,10 @@ impl CompressedColumnIndex {
             writer.write_all(&e.comp_len.to_le_bytes())?;
             writer.write_all(&e.uncomp_len.to_le_bytes())?;
             writer.write_all(&e.num_rows.to_le_bytes())?;
+            writer.write_all(&e.codec.to_le_bytes())?;
+            if version >= 3 {
+                writer.write_all(&e.checksum.unwrap_or_default().to_le_bytes())?;
+            }
         }
         writer.flush()?;
         Ok(())
@@ -54,7 +79,8 @@ impl CompressedColumnIndex {
             .map_err(|e| StoreError::FlushFailed(format!("Failed to create index file: {}", e)))?;
 
         // Write header
-        let header = BinaryHeader::new(FileKind::ZoneCompressedOffsets.magic(), 1, 0);
+        let version = self.write_version();
+        let header = BinaryHeader::new(FileKind::ZoneCompressedOffsets.magic(), version, 0);
         let mut header_buf = Vec::with_capacity(BinaryHeader::TOTAL_LEN);
         header.write_to(&mut header_buf)?;
         file.write_all(&header_buf)
@@ -71,6 +97,11 @@ impl CompressedColumnIndex {
             file.write_all(&e.comp_len.to_le_bytes()).await?;
             file.write_all(&e.uncomp_len.to_le_bytes()).await?;
             file.write_all(&e.num_rows.to_le_bytes()).await?;
+            file.write_all(&e.codec.to_le_bytes()).await?;
+            if version >= 3 {
+                file.write_all(&e.checksum.unwrap_or_default().to_le_bytes())
+                    .await?;
+            }
         }
 
         file.sync_all()
@@ -83,11 +114,34 @@ impl CompressedColumnIndex {
         let (file, header_offset) =
             open_and_header_offset(path, FileKind::ZoneCompressedOffsets.magic())?;
         let mmap = unsafe { MmapOptions::new().map(&file)? };
-        let slice = &mmap[header_offset..];
+        let version = BinaryHeader::read_from(&mmap[..])?.version;
+        Ok(Self::parse_entries(&mmap[header_offset..], version))
+    }
+
+    /// Parses a `.zfc` file already held in memory, header included.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let header = BinaryHeader::read_from(bytes)?;
+        if header.magic != FileKind::ZoneCompressedOffsets.magic() {
+            return Err(
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid magic").into(),
+            );
+        }
+        Ok(Self::parse_entries(
+            &bytes[BinaryHeader::TOTAL_LEN..],
+            header.version,
+        ))
+    }
+
+    fn parse_entries(slice: &[u8], version: u16) -> Self {
+        let entry_size = match version {
+            0..=1 => V1_ENTRY_SIZE,
+            2 => V1_ENTRY_SIZE + SIZE_U16,
+            _ => V1_ENTRY_SIZE + SIZE_U16 + SIZE_U32,
+        };
         let mut reader = LeSliceReader::new(slice);
         let mut entries = HashMap::new();
         loop {
-            if reader.remaining() < MIN_ENTRY_SIZE {
+            if reader.remaining() < entry_size {
                 break;
             }
             let zone_id = match reader.read_u32() {
@@ -110,6 +164,22 @@ impl CompressedColumnIndex {
                 Some(v) => v,
                 None => break,
             };
+            let codec = if version < 2 {
+                ALGO_LZ4
+            } else {
+                match reader.read_u16() {
+                    Some(v) => v,
+                    None => break,
+                }
+            };
+            let checksum = if version < 3 {
+                None
+            } else {
+                match reader.read_u32() {
+                    Some(v) => Some(v),
+                    None => break,
+                }
+            };
 
             entries.insert(
                 zone_id,
@@ -119,9 +189,11 @@ impl CompressedColumnIndex {
                     comp_len,
                     uncomp_len,
                     num_rows,
+                    codec,
+                    checksum,
                 },
             );
         }
-        Ok(Self { entries })
+        Self { entries }
     }
 }
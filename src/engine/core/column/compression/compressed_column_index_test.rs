@@ -5,6 +5,7 @@ use tempfile::tempdir;
 
 use crate::engine::core::column::compression::compressed_column_index::CompressedColumnIndex;
 use crate::engine::core::column::compression::compressed_column_index::ZoneBlockEntry;
+use crate::engine::core::column::compression::compression_codec::{ALGO_LZ4, ALGO_ZSTD};
 use crate::shared::storage_header::{BinaryHeader, FileKind};
 
 #[test]
@@ -21,6 +22,8 @@ fn compressed_index_write_and_load_roundtrip() {
             comp_len: 64,
             uncomp_len: 256,
             num_rows: 3,
+            codec: ALGO_LZ4,
+            checksum: Some(0xdead_beef),
         },
     );
     idx.entries.insert(
@@ -31,6 +34,8 @@ fn compressed_index_write_and_load_roundtrip() {
             comp_len: 40,
             uncomp_len: 100,
             num_rows: 2,
+            codec: ALGO_ZSTD,
+            checksum: Some(7),
         },
     );
 
@@ -50,6 +55,82 @@ fn compressed_index_write_and_load_roundtrip() {
     assert_eq!(z2.comp_len, 40);
     assert_eq!(z2.uncomp_len, 100);
     assert_eq!(z2.num_rows, 2);
+    assert_eq!(z2.codec, ALGO_ZSTD);
+    assert_eq!(z1.checksum, Some(0xdead_beef));
+    assert_eq!(z2.checksum, Some(7));
+}
+
+#[test]
+fn compressed_index_version_2_entries_have_no_checksum() {
+    // Version 2 entries end after the codec
+    let mut bytes: Vec<u8> = Vec::new();
+    BinaryHeader::new(FileKind::ZoneCompressedOffsets.magic(), 2, 0)
+        .write_to(&mut bytes)
+        .expect("write header");
+    for (zone_id, block_start) in [(0u32, 20u64), (1, 60)] {
+        bytes.extend_from_slice(&zone_id.to_le_bytes());
+        bytes.extend_from_slice(&block_start.to_le_bytes());
+        bytes.extend_from_slice(&40u32.to_le_bytes());
+        bytes.extend_from_slice(&80u32.to_le_bytes());
+        bytes.extend_from_slice(&5u32.to_le_bytes());
+        bytes.extend_from_slice(&ALGO_ZSTD.to_le_bytes());
+    }
+
+    let loaded = CompressedColumnIndex::from_bytes(&bytes).expect("parse zfc failed");
+    assert_eq!(loaded.entries.len(), 2);
+    let z1 = loaded.entries.get(&1).expect("missing zone 1");
+    assert_eq!(z1.block_start, 60);
+    assert_eq!(z1.codec, ALGO_ZSTD);
+    assert_eq!(z1.checksum, None);
+
+    // Rewriting entries without checksums keeps them unchecked
+    let tmp = tempdir().unwrap();
+    let path = CompressedColumnIndex::path_for("001", "device", tmp.path());
+    loaded.write_to_path(&path).expect("write zfc failed");
+    let reloaded = CompressedColumnIndex::load_from_path(&path).expect("load zfc failed");
+    assert_eq!(
+        reloaded.entries.get(&0).expect("missing zone 0").checksum,
+        None
+    );
+    assert_eq!(
+        reloaded
+            .entries
+            .get(&1)
+            .expect("missing zone 1")
+            .block_start,
+        60
+    );
+}
+
+#[test]
+fn compressed_index_version_1_entries_are_lz4() {
+    // Version 1 entries end after num_rows
+    let mut bytes: Vec<u8> = Vec::new();
+    BinaryHeader::new(FileKind::ZoneCompressedOffsets.magic(), 1, 0)
+        .write_to(&mut bytes)
+        .expect("write header");
+    for (zone_id, block_start) in [(0u32, 20u64), (1, 60)] {
+        bytes.extend_from_slice(&zone_id.to_le_bytes());
+        bytes.extend_from_slice(&block_start.to_le_bytes());
+        bytes.extend_from_slice(&40u32.to_le_bytes());
+        bytes.extend_from_slice(&80u32.to_le_bytes());
+        bytes.extend_from_slice(&5u32.to_le_bytes());
+    }
+
+    let tmp = tempdir().unwrap();
+    let path = CompressedColumnIndex::path_for("001", "device", tmp.path());
+    std::fs::write(&path, &bytes).unwrap();
+
+    for loaded in [
+        CompressedColumnIndex::load_from_path(&path).expect("load zfc failed"),
+        CompressedColumnIndex::from_bytes(&bytes).expect("parse zfc failed"),
+    ] {
+        assert_eq!(loaded.entries.len(), 2);
+        let z1 = loaded.entries.get(&1).expect("missing zone 1");
+        assert_eq!(z1.block_start, 60);
+        assert_eq!(z1.num_rows, 5);
+        assert_eq!(z1.codec, ALGO_LZ4);
+    }
 }
 
 #[test]
@@ -67,6 +148,8 @@ fn compressed_index_ignores_trailing_partial_record() {
             comp_len: 11,
             uncomp_len: 22,
             num_rows: 1,
+            codec: ALGO_LZ4,
+            checksum: Some(1),
         },
     );
     let path = CompressedColumnIndex::path_for("001", "ip", segment_dir);
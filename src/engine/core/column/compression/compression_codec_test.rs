@@ -1,4 +1,7 @@
-use crate::engine::core::column::compression::{CompressionCodec, Lz4Codec};
+use crate::engine::core::column::compression::compression_codec::ALGO_ZSTD;
+use crate::engine::core::column::compression::{
+    BlockCodec, CompressionCodec, Lz4Codec, codec_for_algo,
+};
 
 #[test]
 fn lz4_roundtrip_prepend_size() {
@@ -20,3 +23,19 @@ fn lz4_decompress_into_exact_size() {
     CompressionCodec::decompress_into(&codec, &comp[4..], &mut out).expect("decompress_into");
     assert_eq!(out, data);
 }
+
+#[test]
+fn zstd_blocks_share_the_size_prefixed_layout() {
+    let data = b"zstd zstd zstd zstd zstd".to_vec();
+    let codec = BlockCodec::Zstd { level: 19 };
+    let comp = codec.compress(&data).expect("compress");
+    assert_eq!(&comp[..4], &(data.len() as u32).to_le_bytes());
+    assert_eq!(codec.algo_id(), ALGO_ZSTD);
+
+    // Any zstd decoder reads the block, whatever level wrote it
+    let decoder = codec_for_algo(ALGO_ZSTD).expect("zstd decoder");
+    assert_eq!(decoder.decompress(&comp, data.len()).unwrap(), data);
+    let mut out = vec![0u8; data.len()];
+    decoder.decompress_into(&comp[4..], &mut out).unwrap();
+    assert_eq!(out, data);
+}
@@ -1,3 +1,4 @@
+pub const SIZE_U16: usize = 2;
 pub const SIZE_U32: usize = 4;
 pub const SIZE_U64: usize = 8;
 
@@ -19,6 +20,15 @@ impl<'a> LeSliceReader<'a> {
         self.remaining() >= n
     }
 
+    pub fn read_u16(&mut self) -> Option<u16> {
+        if !self.has_bytes(SIZE_U16) {
+            return None;
+        }
+        let val = u16::from_le_bytes(self.buf[self.pos..self.pos + SIZE_U16].try_into().ok()?);
+        self.pos += SIZE_U16;
+        Some(val)
+    }
+
     pub fn read_u32(&mut self) -> Option<u32> {
         if !self.has_bytes(SIZE_U32) {
             return None;
@@ -3,8 +3,8 @@ pub mod compression_codec;
 pub mod le_slice_reader;
 
 pub use compressed_column_index::{CompressedColumnIndex, ZoneBlockEntry};
-pub use compression_codec::{CompressionCodec, Lz4Codec};
-pub use le_slice_reader::{LeSliceReader, SIZE_U32, SIZE_U64};
+pub use compression_codec::{BlockCodec, CompressionCodec, Lz4Codec, ZstdCodec, codec_for_algo};
+pub use le_slice_reader::{LeSliceReader, SIZE_U16, SIZE_U32, SIZE_U64};
 
 #[cfg(test)]
 mod compressed_column_index_test;
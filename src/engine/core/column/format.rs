@@ -7,6 +7,8 @@ pub enum PhysicalType {
     F64 = 3,
     Bool = 4,
     I32Date = 5,
+    /// Fixed-width 16-byte UUIDs
+    Uuid = 6,
 }
 
 impl From<u8> for PhysicalType {
@@ -17,6 +19,7 @@ impl From<u8> for PhysicalType {
             3 => PhysicalType::F64,
             4 => PhysicalType::Bool,
             5 => PhysicalType::I32Date,
+            6 => PhysicalType::Uuid,
             _ => PhysicalType::VarBytes,
         }
     }
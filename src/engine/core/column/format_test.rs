@@ -9,6 +9,7 @@ fn physical_type_from_u8_and_into_u8() {
     assert_eq!(u8::from(PhysicalType::F64), 3);
     assert_eq!(u8::from(PhysicalType::Bool), 4);
     assert_eq!(u8::from(PhysicalType::I32Date), 5);
+    assert_eq!(u8::from(PhysicalType::Uuid), 6);
 
     // from<u8>
     assert_eq!(PhysicalType::from(0u8), PhysicalType::VarBytes);
@@ -17,9 +18,10 @@ fn physical_type_from_u8_and_into_u8() {
     assert_eq!(PhysicalType::from(3u8), PhysicalType::F64);
     assert_eq!(PhysicalType::from(4u8), PhysicalType::Bool);
     assert_eq!(PhysicalType::from(5u8), PhysicalType::I32Date);
+    assert_eq!(PhysicalType::from(6u8), PhysicalType::Uuid);
 
     // Unknown values map to VarBytes by design
-    for v in [6u8, 200u8, 255u8] {
+    for v in [7u8, 200u8, 255u8] {
         assert_eq!(PhysicalType::from(v), PhysicalType::VarBytes);
     }
 }
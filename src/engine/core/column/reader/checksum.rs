@@ -0,0 +1,85 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::engine::core::column::compression::ZoneBlockEntry;
+use crate::shared::config::{CONFIG, ChecksumVerification};
+use tracing::error;
+
+/// `query.verify_block_checksums`, off when unset.
+pub fn configured_mode() -> ChecksumVerification {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|cfg| cfg.verify_block_checksums)
+        .unwrap_or(ChecksumVerification::Off)
+}
+
+/// A column block whose bytes no longer match the checksum written with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub col_path: PathBuf,
+    pub zone_id: u32,
+    pub block_start: u64,
+    pub comp_len: u32,
+    pub expected: u32,
+    pub actual: u32,
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "corrupt block in {}: zone {}, bytes {}..{}, crc32 {:08x} expected {:08x}",
+            self.col_path.display(),
+            self.zone_id,
+            self.block_start,
+            self.block_start + u64::from(self.comp_len),
+            self.actual,
+            self.expected
+        )
+    }
+}
+
+/// Checks a compressed block read from `col_path` against its entry's CRC32.
+/// Blocks of files written before checksums pass unchecked. A mismatch is
+/// logged under `sneldb::checksum`; only `Fail` turns it into an error.
+pub fn verify_block(
+    mode: ChecksumVerification,
+    col_path: &Path,
+    entry: &ZoneBlockEntry,
+    compressed: &[u8],
+) -> Result<(), ChecksumMismatch> {
+    let Some(expected) = entry.checksum else {
+        return Ok(());
+    };
+    if mode == ChecksumVerification::Off {
+        return Ok(());
+    }
+    let actual = crc32fast::hash(compressed);
+    if actual == expected {
+        return Ok(());
+    }
+    let mismatch = ChecksumMismatch {
+        col_path: col_path.to_path_buf(),
+        zone_id: entry.zone_id,
+        block_start: entry.block_start,
+        comp_len: entry.comp_len,
+        expected,
+        actual,
+    };
+    error!(
+        target: "sneldb::checksum",
+        col_path = %mismatch.col_path.display(),
+        zone_id = mismatch.zone_id,
+        block_start = mismatch.block_start,
+        comp_len = mismatch.comp_len,
+        expected = mismatch.expected,
+        actual = mismatch.actual,
+        failing = mode == ChecksumVerification::Fail,
+        "Column block checksum mismatch"
+    );
+    match mode {
+        ChecksumVerification::Fail => Err(mismatch),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+
+use crate::engine::core::column::compression::ZoneBlockEntry;
+use crate::engine::core::column::compression::compression_codec::ALGO_LZ4;
+use crate::engine::core::column::reader::checksum::verify_block;
+use crate::shared::config::ChecksumVerification;
+
+fn entry_for(block: &[u8], checksum: Option<u32>) -> ZoneBlockEntry {
+    ZoneBlockEntry {
+        zone_id: 4,
+        block_start: 256,
+        comp_len: block.len() as u32,
+        uncomp_len: 64,
+        num_rows: 8,
+        codec: ALGO_LZ4,
+        checksum,
+    }
+}
+
+#[test]
+fn verify_block_accepts_intact_blocks() {
+    let block = b"compressed zone bytes";
+    let entry = entry_for(block, Some(crc32fast::hash(block)));
+    for mode in [
+        ChecksumVerification::Off,
+        ChecksumVerification::Warn,
+        ChecksumVerification::Fail,
+    ] {
+        assert!(verify_block(mode, Path::new("seg/u_f.col"), &entry, block).is_ok());
+    }
+}
+
+#[test]
+fn verify_block_fails_only_in_fail_mode() {
+    let block = b"compressed zone bytes";
+    let entry = entry_for(block, Some(crc32fast::hash(block)));
+    let corrupt = b"compressed zone bytez";
+    let path = Path::new("seg/u_f.col");
+
+    assert!(verify_block(ChecksumVerification::Off, path, &entry, corrupt).is_ok());
+    assert!(verify_block(ChecksumVerification::Warn, path, &entry, corrupt).is_ok());
+
+    let err = verify_block(ChecksumVerification::Fail, path, &entry, corrupt).unwrap_err();
+    assert_eq!(err.zone_id, 4);
+    assert_eq!(err.block_start, 256);
+    assert_eq!(err.expected, crc32fast::hash(block));
+    assert_eq!(err.actual, crc32fast::hash(corrupt));
+    let msg = err.to_string();
+    assert!(msg.contains("seg/u_f.col"), "{msg}");
+    assert!(msg.contains("zone 4"), "{msg}");
+    assert!(msg.contains("bytes 256..277"), "{msg}");
+}
+
+#[test]
+fn verify_block_skips_blocks_without_checksum() {
+    let entry = entry_for(b"anything", None);
+    assert!(
+        verify_block(
+            ChecksumVerification::Fail,
+            Path::new("seg/u_f.col"),
+            &entry,
+            b"other"
+        )
+        .is_ok()
+    );
+}
@@ -49,6 +49,7 @@ pub struct I64Decoder;
 pub struct U64Decoder;
 pub struct F64Decoder;
 pub struct BoolDecoder;
+pub struct UuidDecoder;
 
 impl ColumnDecoder for VarBytesDecoder {
     fn build_values(
@@ -208,11 +209,43 @@ impl ColumnDecoder for BoolDecoder {
     }
 }
 
+impl ColumnDecoder for UuidDecoder {
+    fn build_values(
+        &self,
+        view: &ColumnBlockView<'_>,
+        entry_rows: usize,
+        block: Arc<DecompressedBlock>,
+    ) -> Result<ColumnValues, QueryExecutionError> {
+        let mut rows = view.header.row_count as usize;
+        if rows == 0 {
+            rows = entry_rows;
+        }
+        let need = rows
+            .checked_mul(16)
+            .ok_or_else(|| QueryExecutionError::ColRead("size overflow".into()))?;
+        if view.payload_start + need > view.bytes.len() {
+            return Err(QueryExecutionError::ColRead("uuid payload OOB".into()));
+        }
+        let nulls = if (view.header.flags & ColumnBlockHeader::FLAG_HAS_NULLS) != 0 {
+            Some((view.aux_start, view.aux_end - view.aux_start))
+        } else {
+            None
+        };
+        Ok(ColumnValues::new_typed_uuid(
+            block,
+            view.payload_start,
+            rows,
+            nulls,
+        ))
+    }
+}
+
 static VARBYTES_DECODER: VarBytesDecoder = VarBytesDecoder;
 static I64_DECODER: I64Decoder = I64Decoder;
 static U64_DECODER: U64Decoder = U64Decoder;
 static F64_DECODER: F64Decoder = F64Decoder;
 static BOOL_DECODER: BoolDecoder = BoolDecoder;
+static UUID_DECODER: UuidDecoder = UuidDecoder;
 
 pub fn decoder_for(phys: PhysicalType) -> &'static dyn ColumnDecoder {
     match phys {
@@ -221,6 +254,7 @@ pub fn decoder_for(phys: PhysicalType) -> &'static dyn ColumnDecoder {
         PhysicalType::U64 => &U64_DECODER,
         PhysicalType::F64 => &F64_DECODER,
         PhysicalType::Bool => &BOOL_DECODER,
+        PhysicalType::Uuid => &UUID_DECODER,
         _ => &VARBYTES_DECODER,
     }
 }
@@ -70,3 +70,31 @@ fn decode_i64() {
     assert_eq!(vals.get_i64_at(0), Some(10));
     assert_eq!(vals.get_i64_at(2), Some(30));
 }
+
+#[test]
+fn decode_uuid() {
+    let rows = 2u32;
+    let aux = vec![0b10u8]; // second row null
+    let mut payload = vec![0xabu8; 16];
+    payload.extend_from_slice(&[0u8; 16]);
+    let block = make_block(
+        PhysicalType::Uuid,
+        ColumnBlockHeader::FLAG_HAS_NULLS,
+        rows,
+        aux,
+        payload,
+    );
+    let view = ColumnBlockView::parse(&block.bytes).unwrap();
+    let vals = decoder_for(view.phys)
+        .build_values(&view, rows as usize, Arc::clone(&block))
+        .unwrap();
+    assert_eq!(vals.len(), 2);
+    assert_eq!(vals.physical_type(), Some(PhysicalType::Uuid));
+    assert_eq!(vals.get_uuid_at(0), Some(&[0xab; 16]));
+    assert_eq!(
+        vals.get_str_at(0),
+        Some("abababab-abab-abab-abab-abababababab")
+    );
+    assert_eq!(vals.get_uuid_at(1), None);
+    assert_eq!(vals.get_str_at(1), None);
+}
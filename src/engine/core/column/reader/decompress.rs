@@ -1,11 +1,14 @@
-use crate::engine::core::column::compression::{CompressionCodec, Lz4Codec};
+use crate::engine::core::column::compression::codec_for_algo;
 use crate::engine::errors::QueryExecutionError;
 
 use super::buffer::decompress_into_pool;
 
+/// Decompresses a block written with codec `algo_id`, as recorded in its
+/// `.zfc` entry.
 pub fn decompress_block(
     compressed: &[u8],
     expected_uncomp_len: usize,
+    algo_id: u16,
 ) -> Result<Vec<u8>, QueryExecutionError> {
     // Blocks are size-prepended by our writer; extract expected out_len and strip header if present
     let (payload, out_len) = if compressed.len() >= 4 {
@@ -16,9 +19,11 @@ pub fn decompress_block(
     } else {
         (compressed, expected_uncomp_len)
     };
-    let codec = Lz4Codec;
+    let codec =
+        codec_for_algo(algo_id).map_err(|e| QueryExecutionError::ColRead(format!("{e}")))?;
     decompress_into_pool(out_len, |dst| {
-        CompressionCodec::decompress_into(&codec, payload, dst)
+        codec
+            .decompress_into(payload, dst)
             .map_err(|e| QueryExecutionError::ColRead(format!("decompress: {e}")))
     })
 }
@@ -1,4 +1,5 @@
-use crate::engine::core::column::compression::{CompressionCodec, Lz4Codec};
+use crate::engine::core::column::compression::compression_codec::{ALGO_LZ4, ALGO_ZSTD};
+use crate::engine::core::column::compression::{CompressionCodec, Lz4Codec, ZstdCodec};
 use crate::engine::core::column::reader::decompress::decompress_block;
 
 #[test]
@@ -6,6 +7,20 @@ fn decompress_block_reads_size_prefix() {
     let codec = Lz4Codec;
     let data = b"abcdef".to_vec();
     let comp = CompressionCodec::compress(&codec, &data).expect("compress");
-    let out = decompress_block(&comp, data.len()).expect("decompress");
+    let out = decompress_block(&comp, data.len(), ALGO_LZ4).expect("decompress");
     assert_eq!(out, data);
 }
+
+#[test]
+fn decompress_block_uses_the_recorded_codec() {
+    let data = b"abcdefabcdefabcdef".to_vec();
+    let comp = ZstdCodec { level: 19 }.compress(&data).expect("compress");
+    let out = decompress_block(&comp, data.len(), ALGO_ZSTD).expect("decompress");
+    assert_eq!(out, data);
+
+    // Blocks from before codec ids were recorded are LZ4
+    let comp = Lz4Codec.compress(&data).expect("compress");
+    assert_eq!(decompress_block(&comp, data.len(), 0).unwrap(), data);
+
+    assert!(decompress_block(&comp, data.len(), 0x7f).is_err());
+}
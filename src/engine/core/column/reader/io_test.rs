@@ -5,6 +5,7 @@ use std::path::PathBuf;
 use tempfile::tempdir;
 
 use crate::engine::core::column::compression::ZoneBlockEntry;
+use crate::engine::core::column::compression::compression_codec::ALGO_LZ4;
 use crate::engine::core::column::reader::io::{compressed_range, map_column_file};
 use crate::engine::errors::QueryExecutionError;
 use crate::shared::storage_header::{BinaryHeader, FileKind};
@@ -65,6 +66,8 @@ fn compressed_range_success_and_oob() {
         comp_len: 64,
         uncomp_len: 256,
         num_rows: 10,
+        codec: ALGO_LZ4,
+        checksum: None,
     };
     let (start, end) = compressed_range(&entry, 256).expect("in bounds");
     assert_eq!((start, end), (128, 192));
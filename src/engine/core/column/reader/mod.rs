@@ -1,4 +1,5 @@
 pub mod buffer;
+pub mod checksum;
 pub mod decoders;
 pub mod decompress;
 pub mod io;
@@ -10,6 +11,8 @@ pub use buffer::decompress_into_pool;
 #[cfg(test)]
 mod buffer_test;
 #[cfg(test)]
+mod checksum_test;
+#[cfg(test)]
 mod decoders_test;
 #[cfg(test)]
 mod decompress_test;
@@ -0,0 +1,46 @@
+use crate::engine::core::column::compression::BlockCodec;
+use crate::shared::config::{CONFIG, CompactionCodecConfig, SegmentCodec};
+
+/// Column block codec per segment level, so colder levels can trade encode time
+/// for size. Each block records its codec, so reads never depend on this policy.
+#[derive(Debug, Clone, Default)]
+pub struct CodecPolicy {
+    /// `(from_level, codec)`, sorted by level
+    tiers: Vec<(u32, BlockCodec)>,
+}
+
+impl CodecPolicy {
+    /// Tiers from `engine.compaction_codecs`, validated when the config loaded.
+    pub fn from_config() -> Self {
+        Self::from_tiers(&CONFIG.engine.compaction_codecs)
+    }
+
+    pub fn from_tiers(tiers: &[CompactionCodecConfig]) -> Self {
+        let mut tiers: Vec<(u32, BlockCodec)> = tiers
+            .iter()
+            .map(|tier| {
+                let codec = match tier.codec {
+                    SegmentCodec::Lz4 => BlockCodec::Lz4,
+                    SegmentCodec::Zstd => BlockCodec::Zstd {
+                        level: tier
+                            .compression_level
+                            .unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL),
+                    },
+                };
+                (tier.from_level, codec)
+            })
+            .collect();
+        tiers.sort_by_key(|(level, _)| *level);
+        Self { tiers }
+    }
+
+    /// Codec of the highest tier at or below `level`; LZ4 below the first tier.
+    pub fn codec_for_level(&self, level: u32) -> BlockCodec {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|(from_level, _)| *from_level <= level)
+            .map(|(_, codec)| *codec)
+            .unwrap_or_default()
+    }
+}
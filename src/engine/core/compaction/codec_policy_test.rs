@@ -0,0 +1,50 @@
+use crate::engine::core::column::compression::BlockCodec;
+use crate::engine::core::compaction::codec_policy::CodecPolicy;
+use crate::shared::config::{CompactionCodecConfig, parse_compaction_codecs};
+use serde_json::{Value, json};
+
+fn parse(tiers: Value) -> Result<Vec<CompactionCodecConfig>, String> {
+    parse_compaction_codecs(tiers).map_err(|e| e.to_string())
+}
+
+#[test]
+fn levels_use_the_nearest_tier_at_or_below() {
+    let tiers = parse(json!([
+        { "from_level": 3, "codec": "zstd", "compression_level": 19 },
+        { "from_level": 1, "codec": "zstd" },
+        { "from_level": 2, "codec": "lz4" },
+    ]))
+    .unwrap();
+    let policy = CodecPolicy::from_tiers(&tiers);
+
+    assert_eq!(policy.codec_for_level(0), BlockCodec::Lz4);
+    assert_eq!(policy.codec_for_level(1), BlockCodec::Zstd { level: 3 });
+    assert_eq!(policy.codec_for_level(2), BlockCodec::Lz4);
+    assert_eq!(policy.codec_for_level(3), BlockCodec::Zstd { level: 19 });
+    assert_eq!(policy.codec_for_level(9), BlockCodec::Zstd { level: 19 });
+
+    assert_eq!(CodecPolicy::default().codec_for_level(5), BlockCodec::Lz4);
+}
+
+#[test]
+fn invalid_tiers_are_rejected_when_parsed() {
+    let err = parse(json!([
+        { "from_level": 1, "codec": "lz4" },
+        { "from_level": 1, "codec": "zstd" },
+    ]))
+    .unwrap_err();
+    assert!(err.contains("level 1 is configured twice"), "{err}");
+
+    let err =
+        parse(json!([{ "from_level": 0, "codec": "lz4", "compression_level": 1 }])).unwrap_err();
+    assert!(err.contains("lz4 takes no compression_level"), "{err}");
+
+    let err =
+        parse(json!([{ "from_level": 2, "codec": "zstd", "compression_level": 40 }])).unwrap_err();
+    assert!(
+        err.contains("zstd compression_level 40 is outside"),
+        "{err}"
+    );
+
+    assert!(parse(json!([{ "from_level": 2, "codec": "brotli" }])).is_err());
+}
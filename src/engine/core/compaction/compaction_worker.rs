@@ -2,12 +2,15 @@ use super::handover::CompactionHandover;
 use super::merge_plan::MergePlan;
 use super::multi_uid_compactor::MultiUidCompactor;
 use super::policy::{CompactionPolicy, KWayCountPolicy};
+use super::retention::{RetentionCutoff, RetentionPolicy, expired_segments};
 use super::segment_batch::SegmentBatch;
 use crate::engine::core::{SegmentEntry, SegmentIndex};
 use crate::engine::errors::CompactorError;
 use crate::engine::schema::SchemaRegistry;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
@@ -24,6 +27,7 @@ pub struct CompactionWorker {
     shard_dir: PathBuf,
     registry: Arc<RwLock<SchemaRegistry>>,
     handover: Arc<CompactionHandover>,
+    retention: RetentionPolicy,
 }
 
 impl CompactionWorker {
@@ -38,13 +42,20 @@ impl CompactionWorker {
             shard_dir,
             registry,
             handover,
+            retention: RetentionPolicy::from_config(),
         }
     }
 
+    /// Overrides the retention windows read from `[retention]`.
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
     /// Runs compaction for the shard.
     /// Groups plans by input segments and processes all UIDs from shared segments together.
     pub async fn run(&self) -> Result<(), CompactorError> {
-        let segment_index = SegmentIndex::load(&self.shard_dir)
+        let mut segment_index = SegmentIndex::load(&self.shard_dir)
             .await
             .map_err(|e| CompactorError::SegmentIndex(e.to_string()))?;
         info!(
@@ -54,6 +65,13 @@ impl CompactionWorker {
             "Loaded segment index"
         );
 
+        let cutoffs = self.retention_cutoffs().await;
+        if !cutoffs.is_empty() && self.drop_expired_segments(&segment_index, &cutoffs).await? {
+            segment_index = SegmentIndex::load(&self.shard_dir)
+                .await
+                .map_err(|e| CompactorError::SegmentIndex(e.to_string()))?;
+        }
+
         let policy = KWayCountPolicy::default();
         let plans: Vec<MergePlan> = policy.plan(&segment_index);
         if plans.is_empty() {
@@ -103,7 +121,7 @@ impl CompactionWorker {
                     "Starting batch processing"
                 );
             }
-            let drained = self.process_batch(batch).await?;
+            let drained = self.process_batch(batch, &cutoffs).await?;
             all_drained_segments.extend(drained);
         }
 
@@ -144,7 +162,11 @@ impl CompactionWorker {
 
     /// Processes a batch of UIDs from shared input segments.
     /// Returns drained segment labels that can be deleted.
-    async fn process_batch(&self, batch: SegmentBatch) -> Result<Vec<String>, CompactorError> {
+    async fn process_batch(
+        &self,
+        batch: SegmentBatch,
+        cutoffs: &HashMap<String, RetentionCutoff>,
+    ) -> Result<Vec<String>, CompactorError> {
         if tracing::enabled!(tracing::Level::DEBUG) {
             debug!(
                 target: "compaction_worker::process_batch",
@@ -163,7 +185,8 @@ impl CompactionWorker {
             self.shard_dir.clone(),
             self.shard_dir.clone(),
             Arc::clone(&self.registry),
-        );
+        )
+        .with_retention(cutoffs.clone());
         let results = compactor
             .run()
             .await
@@ -215,4 +238,45 @@ impl CompactionWorker {
 
         Ok(drained)
     }
+
+    /// Retention boundaries per UID as of now; empty when retention is not configured.
+    async fn retention_cutoffs(&self) -> HashMap<String, RetentionCutoff> {
+        if self.retention.is_empty() {
+            return HashMap::new();
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.retention.cutoffs(&self.registry, now).await
+    }
+
+    /// Removes UIDs from segments whose data is entirely past retention, without
+    /// rewriting anything. Returns whether the segment index changed.
+    async fn drop_expired_segments(
+        &self,
+        segment_index: &SegmentIndex,
+        cutoffs: &HashMap<String, RetentionCutoff>,
+    ) -> Result<bool, CompactorError> {
+        let expired = expired_segments(segment_index, &self.shard_dir, cutoffs);
+        if expired.is_empty() {
+            return Ok(false);
+        }
+
+        info!(
+            target: "compaction_worker::run",
+            shard = self.shard_id,
+            expired = ?expired,
+            "Dropping segments past retention"
+        );
+        let drained = self
+            .handover
+            .commit_retention(&expired)
+            .await
+            .map_err(|e| CompactorError::SegmentIndex(e.to_string()))?;
+        if !drained.is_empty() {
+            self.handover.schedule_reclaim(drained);
+        }
+        Ok(true)
+    }
 }
@@ -516,3 +516,108 @@ async fn compaction_leaves_leftover_l0_when_not_multiple_of_k() {
         }
     }
 }
+
+#[tokio::test]
+async fn retention_drops_expired_segments_and_trims_compacted_ones() {
+    use crate::engine::core::compaction::retention::RetentionPolicy;
+    use crate::logging::init_for_tests;
+    use std::collections::HashMap;
+    init_for_tests();
+
+    let tmp_dir = tempdir().unwrap();
+    let shard_dir = tmp_dir.path().join("shard-retention");
+    std::fs::create_dir_all(&shard_dir).unwrap();
+
+    let schema_factory = SchemaRegistryFactory::new();
+    let registry = schema_factory.registry();
+    let event_type = "retained_login";
+    schema_factory
+        .define_with_fields(event_type, &[("context_id", "string"), ("score", "int")])
+        .await
+        .unwrap();
+    let uid = registry.read().await.get_uid(event_type).unwrap();
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let old = now - 90 * 86_400;
+
+    // Segment 1 only holds expired events; segments 2 and 3 mix expired and live ones
+    let segment_ids = Arc::new(StdRwLock::new(Vec::new()));
+    let flush_lock = Arc::new(tokio::sync::Mutex::new(()));
+    let layouts: [(u64, &[u64]); 3] = [(1, &[old, old]), (2, &[old, now]), (3, &[now, old])];
+    for (segment_id, timestamps) in layouts {
+        let segment_dir = shard_dir.join(format!("{:05}", segment_id));
+        std::fs::create_dir_all(&segment_dir).unwrap();
+
+        let events = timestamps
+            .iter()
+            .enumerate()
+            .map(|(i, ts)| {
+                EventFactory::new()
+                    .with("event_type", event_type)
+                    .with("context_id", format!("ctx{}_{}", segment_id, i))
+                    .with("timestamp", *ts)
+                    .with("payload", json!({ "score": i }))
+                    .create()
+            })
+            .collect();
+        let memtable = MemTableFactory::new()
+            .with_capacity(4)
+            .with_events(events)
+            .create()
+            .unwrap();
+        Flusher::new(
+            memtable,
+            segment_id,
+            &segment_dir,
+            registry.clone(),
+            Arc::clone(&flush_lock),
+        )
+        .flush()
+        .await
+        .unwrap();
+        segment_ids
+            .write()
+            .unwrap()
+            .push(format!("{:05}", segment_id));
+    }
+
+    let handover = Arc::new(CompactionHandover::new(
+        0,
+        shard_dir.clone(),
+        Arc::clone(&segment_ids),
+        Arc::clone(&flush_lock),
+    ));
+    let worker = CompactionWorker::new(
+        0,
+        shard_dir.clone(),
+        registry.clone(),
+        Arc::clone(&handover),
+    )
+    .with_retention(RetentionPolicy::new(HashMap::from([(
+        event_type.to_string(),
+        30,
+    )])));
+    worker.run().await.unwrap();
+
+    // Segment 1 is dropped outright, 2 and 3 are merged without their expired events
+    let index = SegmentIndex::load(&shard_dir).await.unwrap();
+    assert_eq!(index.len(), 1);
+    let entry = index.iter_all().next().unwrap();
+    assert!(entry.id >= 10_000);
+    assert!(!segment_ids.read().unwrap().contains(&"00001".to_string()));
+
+    let label = format!("{:05}", entry.id);
+    let out_dir = shard_dir.join(&label);
+    let zones = ZoneMeta::load(&out_dir.join(format!("{}.zones", uid))).unwrap();
+    let mut ctx_ids = Vec::new();
+    for z in &zones {
+        ctx_ids.extend(
+            ColumnReader::load_for_zone(&out_dir, &label, &uid, "context_id", z.zone_id).unwrap(),
+        );
+    }
+    ctx_ids.sort();
+    assert_eq!(ctx_ids, vec!["ctx2_1".to_string(), "ctx3_0".to_string()]);
+}
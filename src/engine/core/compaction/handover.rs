@@ -1,12 +1,13 @@
 use super::segment_batch::SegmentBatch;
 use crate::engine::core::read::cache::{
-    GlobalColumnBlockCache, GlobalColumnHandleCache, GlobalIndexCatalogCache, GlobalZoneIndexCache,
-    GlobalZoneSurfCache,
+    GlobalColumnBlockCache, GlobalColumnHandleCache, GlobalColumnValueCache,
+    GlobalIndexCatalogCache, GlobalPinnedSegmentCache, GlobalZoneIndexCache, GlobalZoneSurfCache,
 };
 use crate::engine::core::segment::segment_id::SegmentId;
+use crate::engine::core::utils::worker_pools::spawn_background;
 use crate::engine::core::{SegmentEntry, SegmentIndex};
 use crate::engine::errors::StoreError;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -56,6 +57,22 @@ impl SegmentCache for ColumnBlockCacheAdapter {
     }
 }
 
+struct ColumnValueCacheAdapter(&'static GlobalColumnValueCache);
+
+impl SegmentCache for ColumnValueCacheAdapter {
+    fn invalidate_segment(&self, segment_label: &str) {
+        self.0.invalidate_segment(segment_label);
+    }
+}
+
+struct PinnedSegmentCacheAdapter(&'static GlobalPinnedSegmentCache);
+
+impl SegmentCache for PinnedSegmentCacheAdapter {
+    fn invalidate_segment(&self, segment_label: &str) {
+        self.0.invalidate_segment(segment_label);
+    }
+}
+
 pub struct CompactionHandover {
     shard_id: u32,
     shard_dir: PathBuf,
@@ -66,6 +83,8 @@ pub struct CompactionHandover {
     zone_index_cache: Arc<dyn SegmentCache>,
     index_catalog_cache: Arc<dyn SegmentCache>,
     column_block_cache: Arc<dyn SegmentCache>,
+    pinned_segment_cache: Arc<dyn SegmentCache>,
+    column_value_cache: Arc<dyn SegmentCache>,
 }
 
 impl CompactionHandover {
@@ -89,6 +108,12 @@ impl CompactionHandover {
             column_block_cache: Arc::new(ColumnBlockCacheAdapter(
                 GlobalColumnBlockCache::instance(),
             )),
+            pinned_segment_cache: Arc::new(PinnedSegmentCacheAdapter(
+                GlobalPinnedSegmentCache::instance(),
+            )),
+            column_value_cache: Arc::new(ColumnValueCacheAdapter(
+                GlobalColumnValueCache::instance(),
+            )),
         }
     }
 
@@ -114,9 +139,27 @@ impl CompactionHandover {
             zone_index_cache,
             index_catalog_cache,
             column_block_cache,
+            pinned_segment_cache: Arc::new(PinnedSegmentCacheAdapter(
+                GlobalPinnedSegmentCache::instance(),
+            )),
+            column_value_cache: Arc::new(ColumnValueCacheAdapter(
+                GlobalColumnValueCache::instance(),
+            )),
         }
     }
 
+    #[cfg(test)]
+    pub fn with_pinned_segment_cache(mut self, cache: Arc<dyn SegmentCache>) -> Self {
+        self.pinned_segment_cache = cache;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_column_value_cache(mut self, cache: Arc<dyn SegmentCache>) -> Self {
+        self.column_value_cache = cache;
+        self
+    }
+
     /// Commits a batch of UIDs compacted from the same input segments.
     /// Returns the list of drained segment labels that can be deleted later.
     pub async fn commit_batch(
@@ -309,10 +352,96 @@ impl CompactionHandover {
         );
 
         self.invalidate_caches(&drained_labels);
+        // Input segments that keep other UIDs stay listed; values cached from
+        // them for the compacted UIDs must not outlive the handover either
+        for label in input_labels
+            .iter()
+            .filter(|l| !retired_set.contains(l.as_str()))
+        {
+            self.column_value_cache.invalidate_segment(label);
+        }
 
         Ok(drained_labels)
     }
 
+    /// Retires UIDs from segments whose data for them is entirely past retention.
+    /// Their files are removed from segments still holding other UIDs; segments left
+    /// without UIDs are returned as drained, to be reclaimed like compaction inputs.
+    pub async fn commit_retention(
+        &self,
+        expired: &HashMap<String, Vec<String>>,
+    ) -> Result<Vec<String>, StoreError> {
+        if expired.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (drained_labels, touched_labels) = {
+            let _guard = self.flush_lock.lock().await;
+            let mut index = SegmentIndex::load(&self.shard_dir).await?;
+
+            let mut drained_labels: Vec<String> = Vec::new();
+            for (uid, labels) in expired {
+                let drained = index.retire_uid_from_labels(uid, labels.iter().map(|s| s.as_str()));
+                for entry in drained {
+                    let label = entry.label();
+                    if !drained_labels.contains(&label) {
+                        drained_labels.push(label);
+                    }
+                }
+            }
+            index.save(&self.shard_dir).await?;
+
+            // Segments that keep other UIDs stay listed, so the expired files must go now
+            for (uid, labels) in expired {
+                for label in labels {
+                    if drained_labels.contains(label) {
+                        continue;
+                    }
+                    Self::remove_uid_files(&self.shard_dir.join(label), uid)
+                        .map_err(|e| StoreError::FlushFailed(e.to_string()))?;
+                }
+            }
+
+            let touched_labels: HashSet<String> = expired.values().flatten().cloned().collect();
+            (drained_labels, touched_labels)
+        };
+
+        info!(
+            target: "compaction_handover::commit_retention",
+            shard = self.shard_id,
+            expired_uids = expired.len(),
+            drained_count = drained_labels.len(),
+            drained_labels = ?drained_labels,
+            "Dropped segments past retention"
+        );
+
+        let drained_set: HashSet<&str> = drained_labels.iter().map(|s| s.as_str()).collect();
+        self.segment_ids
+            .write()
+            .unwrap()
+            .retain(|label| !drained_set.contains(label.as_str()));
+
+        let touched: Vec<String> = touched_labels.into_iter().collect();
+        self.invalidate_caches(&touched);
+
+        Ok(drained_labels)
+    }
+
+    /// Deletes every file of `uid` from a segment directory.
+    fn remove_uid_files(segment_dir: &std::path::Path, uid: &str) -> std::io::Result<()> {
+        let dot_prefix = format!("{}.", uid);
+        let field_prefix = format!("{}_", uid);
+        for entry in std::fs::read_dir(segment_dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with(&dot_prefix) || name.starts_with(&field_prefix) {
+                std::fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+
     fn invalidate_caches(&self, retired: &[String]) {
         for label in retired {
             self.column_cache.invalidate_segment(label);
@@ -320,6 +449,8 @@ impl CompactionHandover {
             self.zone_index_cache.invalidate_segment(label);
             self.index_catalog_cache.invalidate_segment(label);
             self.column_block_cache.invalidate_segment(label);
+            self.pinned_segment_cache.invalidate_segment(label);
+            self.column_value_cache.invalidate_segment(label);
             debug!(
                 target: "compaction_handover::cache",
                 shard = self.shard_id,
@@ -336,7 +467,7 @@ impl CompactionHandover {
 
         let shard_dir = self.shard_dir.clone();
         let shard_id = self.shard_id;
-        tokio::spawn(async move {
+        spawn_background(async move {
             match tokio::task::spawn_blocking(move || {
                 Self::move_to_reclaim(shard_id, shard_dir, retired)
             })
@@ -70,6 +70,8 @@ async fn commit_updates_index_segment_ids_and_reclaims() {
     let zone_index_stub = StubCache::new("zone_index");
     let catalog_stub = StubCache::new("index_catalog");
     let column_block_stub = StubCache::new("column_block");
+    let pinned_stub = StubCache::new("pinned_segment");
+    let value_stub = StubCache::new("column_value");
     let handover = CompactionHandover::with_caches(
         0,
         shard_path.clone(),
@@ -80,7 +82,9 @@ async fn commit_updates_index_segment_ids_and_reclaims() {
         Arc::new(zone_index_stub.clone()),
         Arc::new(catalog_stub.clone()),
         Arc::new(column_block_stub.clone()),
-    );
+    )
+    .with_pinned_segment_cache(Arc::new(pinned_stub.clone()))
+    .with_column_value_cache(Arc::new(value_stub.clone()));
 
     let batch = SegmentBatch {
         input_segment_labels: vec!["00001".into(), "00002".into()],
@@ -142,7 +146,79 @@ async fn commit_updates_index_segment_ids_and_reclaims() {
                 .recorded()
                 .contains(&format!("column_block:{}", label))
         );
+        assert!(
+            pinned_stub
+                .recorded()
+                .contains(&format!("pinned_segment:{}", label))
+        );
+        assert!(
+            value_stub
+                .recorded()
+                .contains(&format!("column_value:{}", label))
+        );
+    }
+}
+
+#[tokio::test]
+async fn commit_batch_drops_cached_values_of_segments_that_keep_other_uids() {
+    let shard_dir = tempdir().unwrap();
+    let shard_path = shard_dir.path().to_path_buf();
+    for label in ["00001", "00002", "10000"] {
+        std::fs::create_dir_all(shard_path.join(label)).unwrap();
     }
+
+    let mut index = SegmentIndex::load(&shard_path).await.unwrap();
+    index.insert_entry(SegmentEntry {
+        id: 1,
+        uids: vec!["uidA".to_string()],
+    });
+    index.insert_entry(SegmentEntry {
+        id: 2,
+        uids: vec!["uidA".to_string(), "uidB".to_string()],
+    });
+    index.save(&shard_path).await.unwrap();
+
+    let segment_ids = Arc::new(StdRwLock::new(vec!["00001".into(), "00002".into()]));
+    let column_block_stub = StubCache::new("column_block");
+    let value_stub = StubCache::new("column_value");
+    let handover = CompactionHandover::with_caches(
+        0,
+        shard_path.clone(),
+        Arc::clone(&segment_ids),
+        Arc::new(tokio::sync::Mutex::new(())),
+        Arc::new(StubCache::new("column_handle")),
+        Arc::new(StubCache::new("zone_surf")),
+        Arc::new(StubCache::new("zone_index")),
+        Arc::new(StubCache::new("index_catalog")),
+        Arc::new(column_block_stub.clone()),
+    )
+    .with_pinned_segment_cache(Arc::new(StubCache::new("pinned_segment")))
+    .with_column_value_cache(Arc::new(value_stub.clone()));
+
+    let batch = SegmentBatch {
+        input_segment_labels: vec!["00001".into(), "00002".into()],
+        uid_plans: vec![UidPlan {
+            uid: "uidA".to_string(),
+            output_segment_id: 10_000,
+        }],
+    };
+    let drained = handover
+        .commit_batch(
+            &batch,
+            vec![SegmentEntry {
+                id: 10_000,
+                uids: vec!["uidA".to_string()],
+            }],
+        )
+        .await
+        .unwrap();
+
+    // 00002 still serves uidB, so only its cached uidA values are stale
+    assert_eq!(drained, vec!["00001".to_string()]);
+    assert_eq!(column_block_stub.recorded(), vec!["column_block:00001"]);
+    let mut values = value_stub.recorded();
+    values.sort();
+    assert_eq!(values, vec!["column_value:00001", "column_value:00002"]);
 }
 
 #[tokio::test]
@@ -1,12 +1,18 @@
+pub mod codec_policy;
 pub mod compaction_worker;
 pub mod handover;
 pub mod merge_plan;
 pub mod multi_uid_compactor;
 pub mod policy;
+pub mod retention;
 pub mod segment_batch;
 
+#[cfg(test)]
+mod codec_policy_test;
 #[cfg(test)]
 mod policy_test;
+#[cfg(test)]
+mod retention_test;
 
 #[cfg(test)]
 mod compaction_worker_test;
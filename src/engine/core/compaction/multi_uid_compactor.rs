@@ -1,11 +1,13 @@
+use super::retention::RetentionCutoff;
 use super::segment_batch::{SegmentBatch, UidPlan};
+use crate::engine::core::filter::fuse_filter::XorFilterSizing;
 use crate::engine::core::segment::segment_id::SegmentId;
 use crate::engine::core::zone::zone_batch_sizer::ZoneBatchSizer;
 use crate::engine::core::zone::zone_cursor_loader::LoadedZoneCursors;
 use crate::engine::core::{ZoneCursorLoader, ZoneMerger, ZonePlan, ZoneWriter};
 use crate::engine::errors::CompactorError;
 use crate::engine::schema::SchemaRegistry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -21,6 +23,8 @@ pub struct MultiUidCompactor {
     input_dir: PathBuf,
     shard_dir: PathBuf,
     registry: Arc<RwLock<SchemaRegistry>>,
+    /// Retention boundaries by UID; expired zones and rows are not carried over
+    retention: HashMap<String, RetentionCutoff>,
 }
 
 impl MultiUidCompactor {
@@ -35,9 +39,16 @@ impl MultiUidCompactor {
             input_dir,
             shard_dir,
             registry,
+            retention: HashMap::new(),
         }
     }
 
+    /// Drops data past these retention boundaries while merging.
+    pub fn with_retention(mut self, retention: HashMap<String, RetentionCutoff>) -> Self {
+        self.retention = retention;
+        self
+    }
+
     /// Processes all UIDs in the batch, compacting them from the shared input segments.
     /// All UIDs are written to the same output segment directory.
     /// Returns a map of UID to its compaction result.
@@ -131,13 +142,32 @@ impl MultiUidCompactor {
             "Compacting UID to shared output segment"
         );
 
+        // Zones the calendar places entirely past retention are not even loaded
+        let cutoff = self.retention.get(&uid_plan.uid);
+        let skipped_zones: HashMap<String, HashSet<u32>> = match cutoff {
+            Some(cutoff) => self
+                .batch
+                .input_segment_labels
+                .iter()
+                .map(|label| {
+                    let segment_dir = self.input_dir.join(label);
+                    (
+                        label.clone(),
+                        cutoff.expired_zones(&uid_plan.uid, &segment_dir),
+                    )
+                })
+                .collect(),
+            None => HashMap::new(),
+        };
+
         // Load zone cursors for this UID
         let loader = ZoneCursorLoader::new(
             uid_plan.uid.clone(),
             self.batch.input_segment_labels.clone(),
             Arc::clone(&self.registry),
             self.input_dir.clone(),
-        );
+        )
+        .with_skipped_zones(skipped_zones);
         let LoadedZoneCursors {
             cursors,
             type_catalog,
@@ -172,7 +202,14 @@ impl MultiUidCompactor {
         let level = SegmentId::from(output_segment_id as u32).level();
         let target_rows = ZoneBatchSizer::target_rows(level);
 
-        while let Some((batch, max_created_at)) = merger.next_zone(target_rows) {
+        while let Some((mut batch, max_created_at)) = merger.next_zone(target_rows) {
+            // Zones straddling the boundary are trimmed row by row
+            if let Some(cutoff) = cutoff {
+                batch.retain(|row| !cutoff.is_expired(row));
+                if batch.is_empty() {
+                    continue;
+                }
+            }
             let plan = ZonePlan::from_rows(
                 batch,
                 uid_plan.uid.clone(),
@@ -197,9 +234,11 @@ impl MultiUidCompactor {
             });
         }
 
-        // Write merged zones to the shared output directory
+        // Write merged zones to the shared output directory. The inputs' XOR
+        // filters are rebuilt sized for the merged value counts.
         let writer = ZoneWriter::new(&uid_plan.uid, &output_dir, Arc::clone(&self.registry))
-            .with_type_catalog(type_catalog);
+            .with_type_catalog(type_catalog)
+            .with_xor_sizing(XorFilterSizing::ByDistinctCount);
         writer
             .write_all(&zone_plans)
             .await
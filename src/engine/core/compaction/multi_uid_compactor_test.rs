@@ -610,3 +610,106 @@ async fn handles_overlapping_segment_sets() {
     assert_eq!(batches[0].uid_count(), 1);
     assert_eq!(batches[1].uid_count(), 1);
 }
+
+#[tokio::test]
+async fn compaction_sizes_xor_filters_for_merged_cardinality() {
+    use crate::engine::core::FieldXorFilter;
+    use crate::engine::core::filter::fuse_filter::XorFingerprint;
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let tmp_dir = tempdir().unwrap();
+    let shard_dir = tmp_dir.path().join("shard-xor");
+    std::fs::create_dir_all(&shard_dir).unwrap();
+
+    let schema_factory = SchemaRegistryFactory::new();
+    let registry = schema_factory.registry();
+    schema_factory
+        .define_with_fields("sku_event", &[("context_id", "string"), ("sku", "string")])
+        .await
+        .unwrap();
+    let uid = registry.read().await.get_uid("sku_event").unwrap();
+    let xf_name = format!("{}_sku.xf", uid);
+
+    // Two segments of 200 distinct SKUs each: 8-bit filters on their own,
+    // 400 distinct values once merged
+    let segment_ids = Arc::new(StdRwLock::new(Vec::new()));
+    let flush_lock = Arc::new(tokio::sync::Mutex::new(()));
+    let mut pre_merge = Vec::new();
+    let mut present = Vec::new();
+    for segment_id in 1..=2_u64 {
+        let label = format!("{:05}", segment_id);
+        let segment_dir = shard_dir.join(&label);
+        std::fs::create_dir_all(&segment_dir).unwrap();
+
+        let events: Vec<_> = (0..200)
+            .map(|i| {
+                let sku = format!("sku-{}-{}", segment_id, i);
+                present.push(sku.clone());
+                EventFactory::new()
+                    .with("event_type", "sku_event")
+                    .with("context_id", format!("ctx-{}", i))
+                    .with("payload", json!({ "sku": sku }))
+                    .create()
+            })
+            .collect();
+        let memtable = MemTableFactory::new()
+            .with_capacity(200)
+            .with_events(events)
+            .create()
+            .unwrap();
+        Flusher::new(
+            memtable,
+            segment_id,
+            &segment_dir,
+            registry.clone(),
+            Arc::clone(&flush_lock),
+        )
+        .flush()
+        .await
+        .unwrap();
+
+        let filter = FieldXorFilter::load(&segment_dir.join(&xf_name)).unwrap();
+        assert_eq!(filter.fingerprint(), XorFingerprint::Bits8);
+        pre_merge.push(filter);
+        segment_ids.write().unwrap().push(label);
+    }
+
+    let handover = Arc::new(CompactionHandover::with_caches(
+        0,
+        shard_dir.clone(),
+        Arc::clone(&segment_ids),
+        Arc::clone(&flush_lock),
+        Arc::new(StubCache::new("column_handle")),
+        Arc::new(StubCache::new("zone_surf")),
+        Arc::new(StubCache::new("zone_index")),
+        Arc::new(StubCache::new("index_catalog")),
+        Arc::new(StubCache::new("column_block")),
+    ));
+    CompactionWorker::new(0, shard_dir.clone(), registry.clone(), handover)
+        .run()
+        .await
+        .unwrap();
+
+    let index_after = SegmentIndex::load(&shard_dir).await.unwrap();
+    assert_eq!(index_after.len(), 1);
+    let merged_label = index_after.iter_all().next().unwrap().label();
+    let merged = FieldXorFilter::load(&shard_dir.join(merged_label).join(&xf_name)).unwrap();
+    assert_eq!(merged.fingerprint(), XorFingerprint::Bits16);
+
+    assert!(present.iter().all(|sku| merged.contains(sku)));
+    let false_positives = |filter: &FieldXorFilter| {
+        (0..20_000)
+            .filter(|i| filter.contains(&format!("absent-{}", i)))
+            .count()
+    };
+    let merged_fp = false_positives(&merged);
+    for filter in &pre_merge {
+        assert!(
+            merged_fp <= false_positives(filter),
+            "merged filter admitted {} absent values, an input admitted {}",
+            merged_fp,
+            false_positives(filter)
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::command::types::CompareOp;
+use crate::engine::core::time::{FieldIndex, TemporalCalendarIndex};
+use crate::engine::core::{SegmentIndex, ZoneMeta, ZoneRow};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::types::ScalarValue;
+use crate::shared::config::CONFIG;
+use roaring::RoaringBitmap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Per-event-type retention windows enforced during compaction.
+/// Empty unless `[retention]` is explicitly enabled, in which case nothing is dropped.
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    /// Maximum age in days, keyed by event type
+    pub max_age_days: HashMap<String, u64>,
+}
+
+impl RetentionPolicy {
+    /// Windows from `[retention]`; empty when the section is absent or not enabled.
+    pub fn from_config() -> Self {
+        match CONFIG.retention.as_ref() {
+            Some(cfg) if cfg.enabled => Self::new(
+                cfg.schemas
+                    .iter()
+                    .map(|(event_type, window)| (event_type.clone(), window.max_age_days))
+                    .collect(),
+            ),
+            _ => Self::default(),
+        }
+    }
+
+    /// Builds a policy, ignoring zero-day windows which would drop everything.
+    pub fn new(max_age_days: HashMap<String, u64>) -> Self {
+        let max_age_days = max_age_days
+            .into_iter()
+            .filter(|(event_type, days)| {
+                if *days == 0 {
+                    warn!(
+                        target: "compaction::retention",
+                        event_type,
+                        "Ignoring retention window of 0 days"
+                    );
+                }
+                *days > 0
+            })
+            .collect();
+        Self { max_age_days }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max_age_days.is_empty()
+    }
+
+    /// Retention boundary per UID at `now` (epoch seconds), evaluated against each
+    /// schema's declared time field. Event types without a schema are skipped.
+    pub async fn cutoffs(
+        &self,
+        registry: &Arc<RwLock<SchemaRegistry>>,
+        now: u64,
+    ) -> HashMap<String, RetentionCutoff> {
+        let registry = registry.read().await;
+        let mut cutoffs = HashMap::new();
+        for (event_type, days) in &self.max_age_days {
+            let Some(uid) = registry.get_uid(event_type) else {
+                continue;
+            };
+            let time_field = registry.time_field(event_type).unwrap_or("timestamp");
+            cutoffs.insert(
+                uid,
+                RetentionCutoff {
+                    time_field: time_field.to_string(),
+                    cutoff: now.saturating_sub(days.saturating_mul(SECONDS_PER_DAY)),
+                },
+            );
+        }
+        cutoffs
+    }
+}
+
+/// Retention boundary of one UID: events whose `time_field` is below `cutoff` are expired.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetentionCutoff {
+    pub time_field: String,
+    pub cutoff: u64,
+}
+
+impl RetentionCutoff {
+    /// Zones of `uid` that the temporal calendar places entirely before the cutoff.
+    /// The calendar is day-grained, so zones sharing the cutoff's day are kept and
+    /// trimmed row by row instead. Zones the calendar does not know are kept.
+    pub fn expired_zones(&self, uid: &str, segment_dir: &Path) -> HashSet<u32> {
+        let Ok(calendar) = TemporalCalendarIndex::load(uid, &self.time_field, segment_dir) else {
+            return HashSet::new();
+        };
+        let live = calendar.zones_intersecting(CompareOp::Gte, self.cutoff as i64);
+        let mut known = RoaringBitmap::new();
+        for zones in calendar.day.values() {
+            known |= zones;
+        }
+        (&known - &live).iter().collect()
+    }
+
+    /// Whether every zone of `uid` in the segment is expired, so the segment's data for
+    /// `uid` can be dropped without rewriting it.
+    pub fn segment_expired(&self, uid: &str, segment_dir: &Path) -> bool {
+        let Ok(zones) = ZoneMeta::load(&segment_dir.join(format!("{}.zones", uid))) else {
+            return false;
+        };
+        if zones.is_empty() {
+            return false;
+        }
+        let expired = self.expired_zones(uid, segment_dir);
+        zones.iter().all(|zone| expired.contains(&zone.zone_id))
+    }
+
+    /// Whether the row's time is before the cutoff. Rows without a time value are kept.
+    pub fn is_expired(&self, row: &ZoneRow) -> bool {
+        let time = if self.time_field == "timestamp" {
+            row.timestamp.parse::<i64>().ok()
+        } else {
+            row.payload
+                .get(&self.time_field)
+                .and_then(ScalarValue::as_i64)
+        };
+        time.is_some_and(|t| t < self.cutoff as i64)
+    }
+}
+
+/// Segment labels whose data for a UID is entirely past retention, keyed by UID.
+pub fn expired_segments(
+    index: &SegmentIndex,
+    shard_dir: &Path,
+    cutoffs: &HashMap<String, RetentionCutoff>,
+) -> HashMap<String, Vec<String>> {
+    let mut expired: HashMap<String, Vec<String>> = HashMap::new();
+    for entry in index.iter_all() {
+        let label = entry.label();
+        let segment_dir = shard_dir.join(&label);
+        for uid in &entry.uids {
+            let Some(cutoff) = cutoffs.get(uid) else {
+                continue;
+            };
+            if cutoff.segment_expired(uid, &segment_dir) {
+                expired.entry(uid.clone()).or_default().push(label.clone());
+            }
+        }
+    }
+    expired
+}
@@ -0,0 +1,116 @@
+use crate::engine::core::compaction::retention::{RetentionCutoff, RetentionPolicy};
+use crate::engine::core::time::TemporalCalendarIndex;
+use crate::engine::types::ScalarValue;
+use crate::test_helpers::factories::*;
+use std::collections::{HashMap, HashSet};
+use tempfile::tempdir;
+
+const DAY: u64 = 86_400;
+const NOW: u64 = 1_700_000_000;
+
+fn cutoff(time_field: &str, cutoff: u64) -> RetentionCutoff {
+    RetentionCutoff {
+        time_field: time_field.to_string(),
+        cutoff,
+    }
+}
+
+#[tokio::test]
+async fn cutoffs_use_declared_time_field_per_event_type() {
+    let factory = SchemaRegistryFactory::new();
+    let registry = factory.registry();
+    factory
+        .define_with_fields("page_viewed", &[("url", "string")])
+        .await
+        .unwrap();
+    let schema = MiniSchemaFactory::new()
+        .with("happened_at", "u64")
+        .with_time_field("happened_at")
+        .create();
+    registry
+        .write()
+        .await
+        .define("sensor_read", schema)
+        .unwrap();
+
+    let policy = RetentionPolicy::new(HashMap::from([
+        ("page_viewed".to_string(), 30),
+        ("sensor_read".to_string(), 7),
+        ("undefined_type".to_string(), 1),
+    ]));
+    let cutoffs = policy.cutoffs(&registry, NOW).await;
+
+    let (page_uid, sensor_uid) = {
+        let guard = registry.read().await;
+        (
+            guard.get_uid("page_viewed").unwrap(),
+            guard.get_uid("sensor_read").unwrap(),
+        )
+    };
+    assert_eq!(cutoffs.len(), 2, "event types without a schema are skipped");
+    assert_eq!(cutoffs[&page_uid], cutoff("timestamp", NOW - 30 * DAY));
+    assert_eq!(cutoffs[&sensor_uid], cutoff("happened_at", NOW - 7 * DAY));
+}
+
+#[test]
+fn zero_day_windows_are_ignored() {
+    let policy = RetentionPolicy::new(HashMap::from([
+        ("page_viewed".to_string(), 0),
+        ("order_created".to_string(), 90),
+    ]));
+    assert_eq!(policy.max_age_days.len(), 1);
+    assert_eq!(policy.max_age_days["order_created"], 90);
+    assert!(RetentionPolicy::new(HashMap::new()).is_empty());
+}
+
+#[test]
+fn expired_zones_come_from_the_calendar() {
+    let dir = tempdir().unwrap();
+    let uid = "uid_ret";
+    let boundary = NOW - 30 * DAY;
+
+    let mut calendar = TemporalCalendarIndex::new("timestamp");
+    calendar.add_zone_range(0, boundary - 10 * DAY, boundary - 5 * DAY);
+    calendar.add_zone_range(1, boundary - 2 * DAY, boundary + DAY);
+    calendar.add_zone_range(2, NOW - DAY, NOW);
+    calendar.save(uid, dir.path()).unwrap();
+
+    let expired = cutoff("timestamp", boundary).expired_zones(uid, dir.path());
+    // Zone 1 straddles the boundary and is left for row-level trimming
+    assert_eq!(expired, HashSet::from([0]));
+
+    // Without a calendar for the field nothing is considered expired
+    assert!(
+        cutoff("happened_at", boundary)
+            .expired_zones(uid, dir.path())
+            .is_empty()
+    );
+}
+
+#[test]
+fn rows_are_expired_by_the_configured_time_field() {
+    let boundary = NOW - DAY;
+    let old = ZoneRowFactory::new()
+        .with_timestamp(&(boundary - 1).to_string())
+        .create();
+    let fresh = ZoneRowFactory::new()
+        .with_timestamp(&boundary.to_string())
+        .create();
+    let by_timestamp = cutoff("timestamp", boundary);
+    assert!(by_timestamp.is_expired(&old));
+    assert!(!by_timestamp.is_expired(&fresh));
+
+    let by_payload = cutoff("happened_at", boundary);
+    let row_with = |value: ScalarValue| {
+        ZoneRowFactory::new()
+            .with_timestamp(&NOW.to_string())
+            .with_payload_map(HashMap::from([("happened_at".to_string(), value)]))
+            .create()
+    };
+    assert!(by_payload.is_expired(&row_with(ScalarValue::Int64(boundary as i64 - 1))));
+    assert!(!by_payload.is_expired(&row_with(ScalarValue::Int64(NOW as i64))));
+    assert!(
+        !by_payload.is_expired(&old),
+        "rows without the time field are kept"
+    );
+}
@@ -1,6 +1,6 @@
 use crate::engine::core::event::event_id::EventId;
 use crate::engine::errors::StoreError;
-use crate::engine::types::ScalarValue;
+use crate::engine::types::{ScalarValue, decimal};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -153,6 +153,10 @@ impl Event {
             ScalarValue::Int64(i) => i.to_string(),
             ScalarValue::Float64(f) => f.to_string(),
             ScalarValue::Timestamp(ts) => ts.to_string(),
+            ScalarValue::Decimal(m, s) => decimal::format(*m, *s),
+            ScalarValue::Date(_) | ScalarValue::Time(_) | ScalarValue::Uuid(_) => {
+                value.to_string_repr()
+            }
             ScalarValue::Binary(bytes) => BASE64_STANDARD.encode(bytes),
             ScalarValue::Null => "null".to_string(),
         }
@@ -248,6 +252,17 @@ impl Event {
                     escape_json_string(s, &mut result);
                     result.push('"');
                 }
+                ScalarValue::Decimal(m, s) => {
+                    // A string, like `ScalarValue::to_json`, so no digit is lost to f64
+                    result.push('"');
+                    result.push_str(&decimal::format(*m, *s));
+                    result.push('"');
+                }
+                ScalarValue::Date(_) | ScalarValue::Time(_) | ScalarValue::Uuid(_) => {
+                    result.push('"');
+                    result.push_str(&v.to_string_repr());
+                    result.push('"');
+                }
                 ScalarValue::Binary(bytes) => {
                     result.push('"');
                     let encoded = BASE64_STANDARD.encode(bytes);
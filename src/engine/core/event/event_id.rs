@@ -32,6 +32,18 @@ impl EventId {
     pub fn is_zero(self) -> bool {
         self.0 == 0
     }
+
+    /// Shard that generated (and stores) the event.
+    #[inline]
+    pub fn shard_id(self) -> usize {
+        ((self.0 >> SEQUENCE_BITS) & SHARD_ID_MASK) as usize
+    }
+
+    /// Unix time in milliseconds at which the id was generated.
+    #[inline]
+    pub fn created_at_millis(self) -> u64 {
+        (self.0 >> (SHARD_ID_BITS + SEQUENCE_BITS)) + CUSTOM_EPOCH_MILLIS
+    }
 }
 
 impl fmt::Display for EventId {
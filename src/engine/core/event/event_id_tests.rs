@@ -55,3 +55,14 @@ fn generator_timestamp_never_decreases() {
         previous_timestamp = ts;
     }
 }
+
+#[test]
+fn event_id_decodes_shard_and_creation_time() {
+    let mut generator = EventIdGenerator::new();
+    let id = generator.next(5);
+
+    assert_eq!(id.shard_id(), shard_bits(id.raw()) as usize);
+    assert_eq!(id.shard_id(), 5);
+    assert_eq!(id.created_at_millis(), timestamp_millis(id.raw()));
+    assert!(id.created_at_millis() > CUSTOM_EPOCH_MILLIS);
+}
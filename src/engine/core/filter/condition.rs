@@ -2,6 +2,8 @@ use crate::command::types::CompareOp as CommandCompareOp;
 use crate::command::types::Expr;
 use crate::engine::core::column::column_values::ColumnValues;
 use crate::engine::core::filter::direct_event_accessor::DirectEventAccessor;
+use crate::engine::types::uuid;
+use crate::shared::hash::QueryHashSet;
 use std::any::Any;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
@@ -32,6 +34,9 @@ pub trait Condition: Send + Sync + Debug {
     /// Collects the names of fields that require numeric access for this condition.
     fn collect_numeric_fields(&self, _out: &mut HashSet<String>) {}
 
+    /// Collects the names of every field this condition reads.
+    fn collect_fields(&self, out: &mut HashSet<String>);
+
     /// Used for downcasting to concrete condition types when needed for
     /// SIMD fast-paths or specialized handling.
     fn as_any(&self) -> &dyn Any;
@@ -43,6 +48,7 @@ pub trait FieldAccessor {
     fn get_i64_at(&self, field: &str, index: usize) -> Option<i64>;
     fn get_u64_at(&self, field: &str, index: usize) -> Option<u64>;
     fn get_f64_at(&self, field: &str, index: usize) -> Option<f64>;
+    fn get_uuid_at(&self, field: &str, index: usize) -> Option<&[u8; 16]>;
     fn event_count(&self) -> usize;
 }
 
@@ -258,6 +264,12 @@ impl<'a> FieldAccessor for PreparedAccessor<'a> {
             .and_then(|col| col.get_f64_at(index))
     }
 
+    fn get_uuid_at(&self, field: &str, index: usize) -> Option<&[u8; 16]> {
+        self.columns
+            .get(field)
+            .and_then(|col| col.get_uuid_at(index))
+    }
+
     fn event_count(&self) -> usize {
         self.event_count
     }
@@ -454,6 +466,10 @@ impl Condition for NumericCondition {
         out.insert(self.field.clone());
     }
 
+    fn collect_fields(&self, out: &mut HashSet<String>) {
+        out.insert(self.field.clone());
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -465,11 +481,14 @@ pub struct StringCondition {
     field: String,
     operation: CompareOp,
     value: String,
+    /// Bytes of the value when it is a UUID, compared directly against UUID columns
+    uuid: Option<[u8; 16]>,
 }
 
 impl StringCondition {
     pub fn new(field: String, operation: CompareOp, value: String) -> Self {
         Self {
+            uuid: uuid::parse(&value),
             field,
             operation,
             value,
@@ -495,6 +514,16 @@ impl Condition for StringCondition {
     }
 
     fn evaluate_at(&self, accessor: &dyn FieldAccessor, index: usize) -> bool {
+        // UUID columns compare their bytes in place, without building text
+        if let Some(expected) = &self.uuid
+            && let Some(actual) = accessor.get_uuid_at(&self.field, index)
+        {
+            return match self.operation {
+                CompareOp::Eq => actual == expected,
+                CompareOp::Neq => actual != expected,
+                _ => false,
+            };
+        }
         if let Some(val) = accessor.get_str_at(&self.field, index) {
             match self.operation {
                 CompareOp::Eq => val == self.value,
@@ -523,6 +552,10 @@ impl Condition for StringCondition {
         }
     }
 
+    fn collect_fields(&self, out: &mut HashSet<String>) {
+        out.insert(self.field.clone());
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -532,7 +565,7 @@ impl Condition for StringCondition {
 #[derive(Debug)]
 pub struct InNumericCondition {
     field: String,
-    values: HashSet<i64>,
+    values: QueryHashSet<i64>,
 }
 
 impl InNumericCondition {
@@ -608,6 +641,10 @@ impl Condition for InNumericCondition {
         out.insert(self.field.clone());
     }
 
+    fn collect_fields(&self, out: &mut HashSet<String>) {
+        out.insert(self.field.clone());
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -617,7 +654,7 @@ impl Condition for InNumericCondition {
 #[derive(Debug)]
 pub struct InStringCondition {
     field: String,
-    values: HashSet<String>,
+    values: QueryHashSet<String>,
 }
 
 impl InStringCondition {
@@ -661,6 +698,10 @@ impl Condition for InStringCondition {
         self.values.contains(&val)
     }
 
+    fn collect_fields(&self, out: &mut HashSet<String>) {
+        out.insert(self.field.clone());
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -729,6 +770,12 @@ impl Condition for LogicalCondition {
         }
     }
 
+    fn collect_fields(&self, out: &mut HashSet<String>) {
+        for condition in &self.conditions {
+            condition.collect_fields(out);
+        }
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
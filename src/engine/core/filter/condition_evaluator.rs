@@ -96,134 +96,169 @@ impl ConditionEvaluator {
     ) -> Vec<Event> {
         let mut results: Vec<Event> = Vec::new();
 
-        'zones: for zone in zones.into_iter() {
+        for zone in zones.into_iter() {
             if let Some(lim) = limit {
                 if results.len() >= lim {
-                    break 'zones;
+                    break;
                 }
             }
 
-            let accessor = PreparedAccessor::new(&zone.values);
-            let event_count = accessor.event_count();
-            if event_count == 0 {
-                if tracing::enabled!(tracing::Level::DEBUG) && !zone.values.is_empty() {
-                    tracing::debug!(
-                        target: "sneldb::condition_eval",
-                        zone_id = zone.zone_id,
-                        segment_id = %zone.segment_id,
-                        column_count = zone.values.len(),
-                        "Skipping zone because hydrated columns are empty"
-                    );
-                }
-                continue;
-            }
-            if self.has_numeric_conditions() {
-                accessor.warm_numeric_cache(&self.numeric_fields);
+            let selection = self.select(&zone);
+            self.materialize_into(&zone, &selection, limit, &mut results);
+        }
+
+        results
+    }
+
+    /// Every field the conditions read: the columns a zone needs loaded
+    /// before `select` can decide on its rows.
+    pub fn fields(&self) -> HashSet<String> {
+        let mut fields = HashSet::new();
+        for condition in &self.conditions {
+            condition.collect_fields(&mut fields);
+        }
+        fields
+    }
+
+    /// Evaluates the conditions over a zone's loaded columns. Only the columns
+    /// the conditions read need to be loaded; the rows are sized by the
+    /// longest of them.
+    pub fn select(&self, zone: &CandidateZone) -> RowSelection {
+        let accessor = PreparedAccessor::new(&zone.values);
+        let event_count = accessor.event_count();
+        if event_count == 0 {
+            if tracing::enabled!(tracing::Level::DEBUG) && !zone.values.is_empty() {
+                tracing::debug!(
+                    target: "sneldb::condition_eval",
+                    zone_id = zone.zone_id,
+                    segment_id = %zone.segment_id,
+                    column_count = zone.values.len(),
+                    "Skipping zone because hydrated columns are empty"
+                );
             }
+            return RowSelection::from_mask(Vec::new());
+        }
+        if self.has_numeric_conditions() {
+            accessor.warm_numeric_cache(&self.numeric_fields);
+        }
 
-            // Boolean keep-mask for this zone
-            let mut mask = vec![true; event_count];
+        // Boolean keep-mask for this zone
+        let mut mask = vec![true; event_count];
 
-            // SIMD for numeric; scalar for the rest
-            for condition in &self.conditions {
-                if let Some(nc) = condition.as_any().downcast_ref::<NumericCondition>() {
-                    Self::evaluate_numeric_simd(nc, &accessor, 0, event_count, &mut mask);
-                } else {
-                    for i in 0..event_count {
-                        if mask[i] && !condition.evaluate_at(&accessor, i) {
-                            mask[i] = false;
-                        }
+        // SIMD for numeric; scalar for the rest
+        for condition in &self.conditions {
+            if let Some(nc) = condition.as_any().downcast_ref::<NumericCondition>() {
+                Self::evaluate_numeric_simd(nc, &accessor, 0, event_count, &mut mask);
+            } else {
+                for (i, keep) in mask.iter_mut().enumerate() {
+                    if *keep && !condition.evaluate_at(&accessor, i) {
+                        *keep = false;
                     }
                 }
             }
+        }
 
-            // Materialize passing rows
-            // Check once per zone if event_id column is missing/empty to avoid per-event checks
-            let event_id_missing = zone
-                .values
-                .get("event_id")
-                .map(|vals| vals.len() == 0)
-                .unwrap_or(true);
-
-            // OPTIMIZATION: Pre-classify fields by type to avoid repeated physical_type() calls
-            // in the hot inner loop. physical_type() doesn't change between rows.
-            let mut u64_fields: Vec<(&String, &_)> = Vec::new();
-            let mut i64_fields: Vec<(&String, &_)> = Vec::new();
-            let mut f64_fields: Vec<(&String, &_)> = Vec::new();
-            let mut bool_fields: Vec<(&String, &_)> = Vec::new();
-            let mut str_fields: Vec<(&String, &_)> = Vec::new();
-
-            for (field, values) in &zone.values {
-                match values.physical_type() {
-                    Some(PhysicalType::U64) => u64_fields.push((field, values)),
-                    Some(PhysicalType::I64) => i64_fields.push((field, values)),
-                    Some(PhysicalType::F64) => f64_fields.push((field, values)),
-                    Some(PhysicalType::Bool) => bool_fields.push((field, values)),
-                    _ => str_fields.push((field, values)),
-                }
+        RowSelection::from_mask(mask)
+    }
+
+    /// Builds an event from every column loaded for the zone, for each row of
+    /// `selection`, until `results` holds `limit` events. Columns loaded after
+    /// `select` ran are included, which is what lets a scan load the columns
+    /// only surviving rows need once it knows some rows survive.
+    pub fn materialize_into(
+        &self,
+        zone: &CandidateZone,
+        selection: &RowSelection,
+        limit: Option<usize>,
+        results: &mut Vec<Event>,
+    ) {
+        if selection.is_empty() {
+            return;
+        }
+
+        // Materialize passing rows
+        // Check once per zone if event_id column is missing/empty to avoid per-event checks
+        // `is_empty` only sees string values; typed columns count their rows in `len`
+        #[allow(clippy::len_zero)]
+        let event_id_missing = zone
+            .values
+            .get("event_id")
+            .map(|vals| vals.len() == 0)
+            .unwrap_or(true);
+
+        // OPTIMIZATION: Pre-classify fields by type to avoid repeated physical_type() calls
+        // in the hot inner loop. physical_type() doesn't change between rows.
+        let mut u64_fields: Vec<(&String, &_)> = Vec::new();
+        let mut i64_fields: Vec<(&String, &_)> = Vec::new();
+        let mut f64_fields: Vec<(&String, &_)> = Vec::new();
+        let mut bool_fields: Vec<(&String, &_)> = Vec::new();
+        let mut str_fields: Vec<(&String, &_)> = Vec::new();
+
+        for (field, values) in &zone.values {
+            match values.physical_type() {
+                Some(PhysicalType::U64) => u64_fields.push((field, values)),
+                Some(PhysicalType::I64) => i64_fields.push((field, values)),
+                Some(PhysicalType::F64) => f64_fields.push((field, values)),
+                Some(PhysicalType::Bool) => bool_fields.push((field, values)),
+                _ => str_fields.push((field, values)),
             }
+        }
 
-            for i in 0..event_count {
-                if !mask[i] {
-                    continue;
-                }
-                if let Some(lim) = limit {
-                    if results.len() >= lim {
-                        break 'zones;
-                    }
-                }
-                let mut builder = EventBuilder::new();
-
-                // Optimized: Use pre-classified field lists instead of matching every field
-                for (field, values) in &u64_fields {
-                    if let Some(n) = values.get_u64_at(i) {
-                        builder.add_field_u64(field, n);
-                    } else {
-                        builder.add_field_null(field);
-                    }
+        for i in selection.rows() {
+            if let Some(lim) = limit
+                && results.len() >= lim
+            {
+                return;
+            }
+            let mut builder = EventBuilder::new();
+
+            // Optimized: Use pre-classified field lists instead of matching every field
+            for (field, values) in &u64_fields {
+                if let Some(n) = values.get_u64_at(i) {
+                    builder.add_field_u64(field, n);
+                } else {
+                    builder.add_field_null(field);
                 }
-                for (field, values) in &i64_fields {
-                    if let Some(n) = values.get_i64_at(i) {
-                        builder.add_field_i64(field, n);
-                    } else {
-                        builder.add_field_null(field);
-                    }
+            }
+            for (field, values) in &i64_fields {
+                if let Some(n) = values.get_i64_at(i) {
+                    builder.add_field_i64(field, n);
+                } else {
+                    builder.add_field_null(field);
                 }
-                for (field, values) in &f64_fields {
-                    if let Some(f) = values.get_f64_at(i) {
-                        builder.add_field_f64(field, f);
-                    } else {
-                        builder.add_field_null(field);
-                    }
+            }
+            for (field, values) in &f64_fields {
+                if let Some(f) = values.get_f64_at(i) {
+                    builder.add_field_f64(field, f);
+                } else {
+                    builder.add_field_null(field);
                 }
-                for (field, values) in &bool_fields {
-                    if let Some(b) = values.get_bool_at(i) {
-                        builder.add_field_bool(field, b);
-                    } else {
-                        builder.add_field_null(field);
-                    }
+            }
+            for (field, values) in &bool_fields {
+                if let Some(b) = values.get_bool_at(i) {
+                    builder.add_field_bool(field, b);
+                } else {
+                    builder.add_field_null(field);
                 }
-                for (field, values) in &str_fields {
-                    if let Some(value) = values.get_str_at(i) {
-                        builder.add_field(field, value);
-                    } else {
-                        builder.add_field_null(field);
-                    }
+            }
+            for (field, values) in &str_fields {
+                if let Some(value) = values.get_str_at(i) {
+                    builder.add_field(field, value);
+                } else {
+                    builder.add_field_null(field);
                 }
+            }
 
-                let mut event = builder.build();
-                // If event_id column is missing/empty, generate a unique ID based on zone and row index
-                // This prevents deduplication from incorrectly removing valid events
-                // Only check/update if we know the column is missing (optimization)
-                if event_id_missing || event.event_id().is_zero() {
-                    let synthetic_id = (zone.zone_id as u64) << 32 | (i as u64);
-                    event.set_event_id(EventId::from(synthetic_id));
-                }
-                results.push(event);
+            let mut event = builder.build();
+            // If event_id column is missing/empty, generate a unique ID based on zone and row index
+            // This prevents deduplication from incorrectly removing valid events
+            // Only check/update if we know the column is missing (optimization)
+            if event_id_missing || event.event_id().is_zero() {
+                let synthetic_id = (zone.zone_id as u64) << 32 | (i as u64);
+                event.set_event_id(EventId::from(synthetic_id));
             }
+            results.push(event);
         }
-
-        results
     }
 
     /// SIMD numeric evaluator – tries u64 then i64 then f64
@@ -284,6 +319,42 @@ impl ConditionEvaluator {
     }
 }
 
+/// The rows of one zone that passed a `ConditionEvaluator`, one flag per row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowSelection {
+    mask: Vec<bool>,
+    selected: usize,
+}
+
+impl RowSelection {
+    pub fn from_mask(mask: Vec<bool>) -> Self {
+        let selected = mask.iter().filter(|keep| **keep).count();
+        Self { mask, selected }
+    }
+
+    /// Rows the conditions were evaluated over, passing or not.
+    pub fn row_count(&self) -> usize {
+        self.mask.len()
+    }
+
+    /// Rows that passed.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected == 0
+    }
+
+    /// Indices of the rows that passed, in zone order.
+    pub fn rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.mask
+            .iter()
+            .enumerate()
+            .filter_map(|(i, keep)| keep.then_some(i))
+    }
+}
+
 /* ------------------------- SIMD helpers (u64 / i64 / f64) ------------------------ */
 
 #[inline]
@@ -728,3 +728,80 @@ fn in_string_condition_single_value() {
     let out = ev.evaluate_zones(vec![zone]);
     assert_eq!(out.len(), 2, "should match 2 rows with status='active'");
 }
+
+#[test]
+fn fields_collects_every_field_read_including_nested_ones() {
+    use crate::engine::core::filter::condition::{LogicalCondition, LogicalOp, StringCondition};
+
+    let mut evaluator = ConditionEvaluator::new();
+    evaluator.add_numeric_condition("amount".into(), CompareOp::Gt, 5);
+    evaluator.add_logical_condition(LogicalCondition::new(
+        vec![
+            Box::new(StringCondition::new(
+                "status".into(),
+                CompareOp::Eq,
+                "ok".into(),
+            )),
+            Box::new(StringCondition::new(
+                "region".into(),
+                CompareOp::Eq,
+                "eu".into(),
+            )),
+        ],
+        LogicalOp::Or,
+    ));
+
+    let mut fields: Vec<String> = evaluator.fields().into_iter().collect();
+    fields.sort();
+    assert_eq!(fields, vec!["amount", "region", "status"]);
+}
+
+#[test]
+fn select_then_materialize_includes_columns_loaded_after_selecting() {
+    let mut values = HashMap::new();
+    values.insert(
+        "status".to_string(),
+        vec!["ok".into(), "fail".into(), "ok".into()],
+    );
+    let mut zone = CandidateZoneFactory::new()
+        .with("zone_id", 2)
+        .with("segment_id", "seg-late")
+        .with_values(values)
+        .create();
+
+    let mut evaluator = ConditionEvaluator::new();
+    evaluator.add_string_condition("status".into(), CompareOp::Eq, "ok".into());
+
+    let selection = evaluator.select(&zone);
+    assert_eq!(selection.row_count(), 3);
+    assert_eq!(selection.selected(), 2);
+    assert_eq!(selection.rows().collect::<Vec<_>>(), vec![0, 2]);
+
+    let late = CandidateZoneFactory::new()
+        .with_values(HashMap::from([(
+            "note".to_string(),
+            vec!["first".into(), "second".into(), "third".into()],
+        )]))
+        .create();
+    zone.values.extend(late.values);
+
+    let mut results = Vec::new();
+    evaluator.materialize_into(&zone, &selection, None, &mut results);
+    let notes: Vec<String> = results.iter().map(|e| e.get_field_value("note")).collect();
+    assert_eq!(notes, vec!["first", "third"]);
+
+    let mut limited = Vec::new();
+    evaluator.materialize_into(&zone, &selection, Some(1), &mut limited);
+    assert_eq!(limited.len(), 1);
+}
+
+#[test]
+fn select_on_a_zone_without_rows_selects_nothing() {
+    let zone = CandidateZone::new(3, "seg-empty".into());
+    let mut evaluator = ConditionEvaluator::new();
+    evaluator.add_string_condition("status".into(), CompareOp::Eq, "ok".into());
+
+    let selection = evaluator.select(&zone);
+    assert_eq!(selection.row_count(), 0);
+    assert!(selection.is_empty());
+}
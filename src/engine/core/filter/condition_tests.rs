@@ -612,3 +612,44 @@ fn in_string_condition_empty_set() {
     let in_condition = InStringCondition::new("status".into(), vec![]);
     assert!(!in_condition.evaluate(&values)); // Empty set never matches
 }
+
+#[test]
+fn string_condition_compares_uuid_columns_by_bytes() {
+    let first = [0x11u8; 16];
+    let second = [0x22u8; 16];
+    let block = Arc::new(DecompressedBlock::from_bytes([first, second].concat()));
+    let mut zone = CandidateZone::new(0, "seg".into());
+    let mut cols = HashMap::new();
+    cols.insert(
+        "visitor".to_string(),
+        ColumnValues::new_typed_uuid(block, 0, 2, None),
+    );
+    zone.set_values(cols);
+    let accessor = PreparedAccessor::new(&zone.values);
+
+    // Case does not matter: the literal's bytes are compared, not its text
+    let eq = StringCondition::new(
+        "visitor".into(),
+        CompareOp::Eq,
+        "22222222-2222-2222-2222-222222222222".into(),
+    );
+    assert!(!eq.evaluate_at(&accessor, 0));
+    assert!(eq.evaluate_at(&accessor, 1));
+    let neq = StringCondition::new(
+        "visitor".into(),
+        CompareOp::Neq,
+        "11111111-1111-1111-1111-11111111111A".into(),
+    );
+    assert!(neq.evaluate_at(&accessor, 0));
+    assert!(neq.evaluate_at(&accessor, 1));
+    let eq_upper = StringCondition::new(
+        "visitor".into(),
+        CompareOp::Eq,
+        "11111111-1111-1111-1111-111111111111".to_uppercase(),
+    );
+    assert!(eq_upper.evaluate_at(&accessor, 0));
+    assert_eq!(
+        accessor.get_str_at("visitor", 1),
+        Some("22222222-2222-2222-2222-222222222222")
+    );
+}
@@ -1,5 +1,9 @@
 use crate::engine::core::ZonePlan;
-use crate::engine::types::ScalarValue;
+use crate::engine::core::filter::fuse_filter::{
+    FuseFilter, XOR_FILE_VERSION_FIXED, XOR_FILE_VERSION_SIZED, XOR_FILE_VERSION_UUID_KEYS,
+    XorFilterSizing, XorFingerprint, fingerprint_from_byte,
+};
+use crate::engine::types::{ScalarValue, uuid};
 use crate::shared::hash::stable_hash64;
 use crate::shared::storage_header::{BinaryHeader, FileKind};
 use std::collections::HashSet;
@@ -7,16 +11,24 @@ use std::fs::OpenOptions;
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
-use xorf::{BinaryFuse8, Filter};
 
-/// A wrapper around the xorf BinaryFuse8 implementation
+/// A wrapper around the xorf binary fuse filters
 #[derive(Clone, Debug)]
 pub struct FieldXorFilter {
-    inner: BinaryFuse8,
+    inner: FuseFilter,
+    /// Whether values in canonical UUID form are keyed by their bytes; only
+    /// set when some were, so filters without UUIDs keep their old layout
+    uuid_keys: bool,
 }
 
 impl FieldXorFilter {
     pub fn new(values: &[String]) -> Result<Self, String> {
+        Self::with_sizing(values, XorFilterSizing::Fixed)
+    }
+
+    /// Builds a filter whose fingerprint width is chosen by `sizing` from the
+    /// number of distinct values.
+    pub fn with_sizing(values: &[String], sizing: XorFilterSizing) -> Result<Self, String> {
         debug!(
             target: "sneldb::xorfilter",
             "Creating XOR filter from {} values",
@@ -29,19 +41,38 @@ impl FieldXorFilter {
 
         // Hash all values (values should already be unique from collect_field_values,
         // but we deduplicate hashes to handle hash collisions)
+        let uuid_keys = values.iter().any(|value| uuid::is_canonical(value));
         let mut unique_hashes: HashSet<u64> = HashSet::with_capacity(values.len());
         for value in values {
-            let hash = stable_hash64(value);
-            unique_hashes.insert(hash);
+            unique_hashes.insert(Self::key_hash(value, uuid_keys));
         }
 
         // Convert HashSet to Vec for iterator (order doesn't matter for filter construction)
         let hashes_vec: Vec<u64> = unique_hashes.into_iter().collect();
+        let fingerprint = sizing.fingerprint_for(hashes_vec.len());
 
-        let filter = BinaryFuse8::try_from_iterator(hashes_vec.into_iter())
+        let filter = FuseFilter::build(&hashes_vec, fingerprint)
             .map_err(|e| format!("Failed to construct binary fuse filter: {:?}", e))?;
 
-        Ok(Self { inner: filter })
+        Ok(Self {
+            inner: filter,
+            uuid_keys,
+        })
+    }
+
+    /// UUIDs stored as text hash their 16 bytes, like UUID columns store them.
+    fn key_hash(value: &str, uuid_keys: bool) -> u64 {
+        if uuid_keys
+            && uuid::is_canonical(value)
+            && let Some(bytes) = uuid::parse(value)
+        {
+            return stable_hash64(&bytes);
+        }
+        stable_hash64(&value)
+    }
+
+    pub fn fingerprint(&self) -> XorFingerprint {
+        self.inner.fingerprint()
     }
 
     pub fn value_to_string(value: &ScalarValue) -> Option<String> {
@@ -51,6 +82,7 @@ impl FieldXorFilter {
             ScalarValue::Timestamp(ts) => Some(ts.to_string()),
             ScalarValue::Float64(f) => Some(f.to_string()),
             ScalarValue::Boolean(b) => Some(b.to_string()),
+            ScalarValue::Uuid(u) => Some(uuid::format(u)),
             _ => {
                 info!(
                     target: "sneldb::xorfilter",
@@ -62,7 +94,7 @@ impl FieldXorFilter {
     }
 
     pub fn contains(&self, value: &str) -> bool {
-        let h = stable_hash64(&value);
+        let h = Self::key_hash(value, self.uuid_keys);
         self.inner.contains(&h)
     }
 
@@ -92,11 +124,23 @@ impl FieldXorFilter {
         let file = OpenOptions::new().create(true).write(true).open(path)?;
         let mut writer = BufWriter::new(file);
 
-        let data = bincode::serialize(&self.inner)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        let data = self.inner.to_bytes()?;
 
-        let header = BinaryHeader::new(FileKind::XorFilter.magic(), 1, 0);
-        header.write_to(&mut writer)?;
+        // 8-bit filters keep the original layout; wider ones, and those keyed
+        // by UUID bytes, record their width
+        let fingerprint = self.inner.fingerprint();
+        if fingerprint == XorFingerprint::Bits8 && !self.uuid_keys {
+            BinaryHeader::new(FileKind::XorFilter.magic(), XOR_FILE_VERSION_FIXED, 0)
+                .write_to(&mut writer)?;
+        } else {
+            let version = if self.uuid_keys {
+                XOR_FILE_VERSION_UUID_KEYS
+            } else {
+                XOR_FILE_VERSION_SIZED
+            };
+            BinaryHeader::new(FileKind::XorFilter.magic(), version, 0).write_to(&mut writer)?;
+            writer.write_all(&[fingerprint.bits()])?;
+        }
         writer.write_all(&data)?;
         writer.flush()?;
 
@@ -121,8 +165,25 @@ impl FieldXorFilter {
                 "invalid xorfilter magic",
             ));
         }
-        let filter = bincode::deserialize(&data[BinaryHeader::TOTAL_LEN..])
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        let body = &data[BinaryHeader::TOTAL_LEN..];
+        let filter = match header.version {
+            XOR_FILE_VERSION_FIXED => FuseFilter::from_bytes(XorFingerprint::Bits8, body)?,
+            XOR_FILE_VERSION_SIZED | XOR_FILE_VERSION_UUID_KEYS => {
+                let (&bits, blob) = body.split_first().ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "missing xorfilter fingerprint width",
+                    )
+                })?;
+                FuseFilter::from_bytes(fingerprint_from_byte(bits)?, blob)?
+            }
+            other => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("unsupported xorfilter version: {}", other),
+                ));
+            }
+        };
 
         info!(
             target: "sneldb::xorfilter",
@@ -131,7 +192,10 @@ impl FieldXorFilter {
             data.len()
         );
 
-        Ok(Self { inner: filter })
+        Ok(Self {
+            inner: filter,
+            uuid_keys: header.version == XOR_FILE_VERSION_UUID_KEYS,
+        })
     }
 
     fn get_filter_path(uid: &str, field: &str, segment_dir: &Path) -> PathBuf {
@@ -144,6 +208,21 @@ impl FieldXorFilter {
         zone_plans: &[ZonePlan],
         segment_dir: &Path,
         allowed_fields: &HashSet<String>,
+    ) -> std::io::Result<()> {
+        Self::build_all_filtered_with_sizing(
+            zone_plans,
+            segment_dir,
+            allowed_fields,
+            XorFilterSizing::Fixed,
+        )
+    }
+
+    /// Like [`Self::build_all_filtered`], sizing each filter with `sizing`.
+    pub fn build_all_filtered_with_sizing(
+        zone_plans: &[ZonePlan],
+        segment_dir: &Path,
+        allowed_fields: &HashSet<String>,
+        sizing: XorFilterSizing,
     ) -> std::io::Result<()> {
         debug!(
             target: "sneldb::xorfilter",
@@ -156,7 +235,7 @@ impl FieldXorFilter {
                 continue;
             }
             let values_vec: Vec<String> = values.into_iter().collect();
-            match Self::new(&values_vec) {
+            match Self::with_sizing(&values_vec, sizing) {
                 Ok(filter) => {
                     let path = Self::get_filter_path(&uid, &field, segment_dir);
                     if let Err(e) = filter.save(&path) {
@@ -76,3 +76,60 @@ async fn builds_all_creates_filters_on_disk() {
     assert!(filter.contains("login"));
     assert!(filter.contains("logout"));
 }
+
+#[test]
+fn sized_filter_persists_its_fingerprint_width() {
+    use crate::engine::core::filter::fuse_filter::{XorFilterSizing, XorFingerprint};
+    use crate::shared::storage_header::BinaryHeader;
+
+    let dir = tempdir().unwrap();
+    let values: Vec<String> = (0..300).map(|i| format!("v{}", i)).collect();
+
+    let sized_path = dir.path().join("sized.xf");
+    FieldXorFilter::with_sizing(&values, XorFilterSizing::ByDistinctCount)
+        .unwrap()
+        .save(&sized_path)
+        .unwrap();
+    let loaded = FieldXorFilter::load(&sized_path).unwrap();
+    assert_eq!(loaded.fingerprint(), XorFingerprint::Bits16);
+    assert!(values.iter().all(|v| loaded.contains(v)));
+
+    // Fixed-size filters keep the version 1 layout
+    let fixed_path = dir.path().join("fixed.xf");
+    FieldXorFilter::new(&values)
+        .unwrap()
+        .save(&fixed_path)
+        .unwrap();
+    let mut file = std::fs::File::open(&fixed_path).unwrap();
+    assert_eq!(BinaryHeader::read_from(&mut file).unwrap().version, 1);
+    assert_eq!(
+        FieldXorFilter::load(&fixed_path).unwrap().fingerprint(),
+        XorFingerprint::Bits8
+    );
+}
+
+#[test]
+fn canonical_uuids_are_keyed_by_their_bytes() {
+    use crate::engine::core::filter::fuse_filter::XOR_FILE_VERSION_UUID_KEYS;
+    use crate::engine::types::uuid;
+    use crate::shared::storage_header::BinaryHeader;
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("uuids.xf");
+    let canonical = "550e8400-e29b-41d4-a716-446655440000";
+    FieldXorFilter::new(&[canonical.to_string(), "not-a-uuid".to_string()])
+        .unwrap()
+        .save(&path)
+        .unwrap();
+
+    let mut file = std::fs::File::open(&path).unwrap();
+    assert_eq!(
+        BinaryHeader::read_from(&mut file).unwrap().version,
+        XOR_FILE_VERSION_UUID_KEYS
+    );
+    let loaded = FieldXorFilter::load(&path).unwrap();
+    assert!(loaded.contains(canonical));
+    assert!(loaded.contains("not-a-uuid"));
+    assert!(loaded.contains_value(&ScalarValue::Uuid(uuid::parse(canonical).unwrap())));
+    assert!(!loaded.contains("650e8400-e29b-41d4-a716-446655440000"));
+}
@@ -1,7 +1,7 @@
 use crate::command::types::CompareOp;
 use crate::engine::core::filter::condition::LogicalOp;
 use crate::engine::core::read::index_strategy::IndexStrategy;
-use crate::engine::types::ScalarValue;
+use crate::engine::types::{ScalarValue, decimal, uuid};
 use std::collections::HashSet;
 use std::fmt::Write;
 
@@ -96,6 +96,20 @@ pub fn filter_key(
         Some(ScalarValue::Timestamp(t)) => {
             let _ = write!(key, "Timestamp({})", t);
         }
+        Some(ScalarValue::Decimal(m, s)) => {
+            // Equal values of different scales share a key
+            let (m, s) = decimal::normalize(*m, *s);
+            let _ = write!(key, "Decimal({})", decimal::format(m, s));
+        }
+        Some(ScalarValue::Date(d)) => {
+            let _ = write!(key, "Date({})", d);
+        }
+        Some(ScalarValue::Time(t)) => {
+            let _ = write!(key, "Time({})", t);
+        }
+        Some(ScalarValue::Uuid(u)) => {
+            let _ = write!(key, "Uuid({})", uuid::format(u));
+        }
         Some(ScalarValue::Utf8(s)) => {
             key.push_str("Utf8(");
             key.push_str(s);
@@ -3,8 +3,8 @@ use crate::engine::core::filter::filter_group::{FilterGroup, FilterPriority};
 use crate::engine::core::filter::in_expansion::InExpansion;
 use crate::engine::schema::FieldType;
 use crate::engine::schema::registry::{MiniSchema, SchemaRegistry};
-use crate::engine::types::ScalarValue;
-use crate::shared::time::{TimeKind, TimeParser};
+use crate::engine::types::{ScalarValue, uuid};
+use crate::shared::time::TimeParser;
 use serde_json::{Number, Value as JsonValue};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -54,9 +54,9 @@ impl FilterGroupBuilder {
                 if tracing::enabled!(tracing::Level::DEBUG) {
                     debug!(target: "query::planner", ?expr, "Processing where clause");
                 }
-                // Normalize temporal literals using schema if available
+                // Normalize temporal and UUID literals using schema if available
                 let normalized_expr = if let Some(schema) = registry.read().await.get(event_type) {
-                    Self::normalize_temporal_literals(expr, schema)
+                    Self::normalize_literals(expr, schema)
                 } else {
                     expr.clone()
                 };
@@ -321,70 +321,44 @@ impl FilterGroupBuilder {
             .or_insert_with(|| filter);
     }
 
-    /// Normalizes temporal literals in an Expr to epoch seconds
-    fn normalize_temporal_literals(expr: &Expr, schema: &MiniSchema) -> Expr {
+    /// Normalizes literals in an Expr to what their fields store: epoch
+    /// seconds, or milliseconds since midnight for times, and the canonical
+    /// lowercase form for UUIDs
+    pub(crate) fn normalize_literals(expr: &Expr, schema: &MiniSchema) -> Expr {
         match expr {
+            Expr::Compare { field, op, value }
+                if schema.field_type(field).is_some_and(FieldType::is_uuid) =>
+            {
+                Expr::Compare {
+                    field: field.clone(),
+                    op: op.clone(),
+                    value: Self::canonical_uuid(value),
+                }
+            }
+            Expr::In { field, values }
+                if schema.field_type(field).is_some_and(FieldType::is_uuid) =>
+            {
+                Expr::In {
+                    field: field.clone(),
+                    values: values.iter().map(Self::canonical_uuid).collect(),
+                }
+            }
             Expr::Compare { field, op, value } => {
-                let is_temporal = schema
-                    .field_type(field)
-                    .map(|ft| match ft {
-                        FieldType::Timestamp | FieldType::Date => true,
-                        FieldType::Optional(inner) => {
-                            matches!(**inner, FieldType::Timestamp | FieldType::Date)
-                        }
-                        _ => false,
-                    })
-                    .unwrap_or(false);
-
-                if is_temporal {
-                    let scalar_value = ScalarValue::from(value.clone());
-                    if let ScalarValue::Utf8(s) = &scalar_value {
-                        let kind = match schema.field_type(field) {
-                            Some(FieldType::Date) => TimeKind::Date,
-                            Some(FieldType::Optional(inner)) => {
-                                if matches!(*inner.as_ref(), FieldType::Date) {
-                                    TimeKind::Date
-                                } else {
-                                    TimeKind::DateTime
-                                }
-                            }
-                            _ => TimeKind::DateTime,
-                        };
-                        if let Some(parsed) = TimeParser::parse_str_to_epoch_seconds(s, kind) {
-                            return Expr::Compare {
-                                field: field.clone(),
-                                op: op.clone(),
-                                value: JsonValue::Number(Number::from(parsed)),
-                            };
-                        }
-                    }
+                let kind = schema.field_type(field).and_then(FieldType::time_kind);
+                if let Some(kind) = kind
+                    && let ScalarValue::Utf8(s) = ScalarValue::from(value.clone())
+                    && let Some(parsed) = TimeParser::parse_str_to_epoch_seconds(&s, kind)
+                {
+                    return Expr::Compare {
+                        field: field.clone(),
+                        op: op.clone(),
+                        value: JsonValue::Number(Number::from(parsed)),
+                    };
                 }
                 expr.clone()
             }
             Expr::In { field, values } => {
-                let is_temporal = schema
-                    .field_type(field)
-                    .map(|ft| match ft {
-                        FieldType::Timestamp | FieldType::Date => true,
-                        FieldType::Optional(inner) => {
-                            matches!(**inner, FieldType::Timestamp | FieldType::Date)
-                        }
-                        _ => false,
-                    })
-                    .unwrap_or(false);
-
-                if is_temporal {
-                    let kind = match schema.field_type(field) {
-                        Some(FieldType::Date) => TimeKind::Date,
-                        Some(FieldType::Optional(inner)) => {
-                            if matches!(**inner, FieldType::Date) {
-                                TimeKind::Date
-                            } else {
-                                TimeKind::DateTime
-                            }
-                        }
-                        _ => TimeKind::DateTime,
-                    };
+                if let Some(kind) = schema.field_type(field).and_then(FieldType::time_kind) {
                     let mut normalized_values = Vec::new();
                     let mut all_normalized = true;
 
@@ -412,14 +386,22 @@ impl FilterGroupBuilder {
                 expr.clone()
             }
             Expr::And(l, r) => Expr::And(
-                Box::new(Self::normalize_temporal_literals(l, schema)),
-                Box::new(Self::normalize_temporal_literals(r, schema)),
+                Box::new(Self::normalize_literals(l, schema)),
+                Box::new(Self::normalize_literals(r, schema)),
             ),
             Expr::Or(l, r) => Expr::Or(
-                Box::new(Self::normalize_temporal_literals(l, schema)),
-                Box::new(Self::normalize_temporal_literals(r, schema)),
+                Box::new(Self::normalize_literals(l, schema)),
+                Box::new(Self::normalize_literals(r, schema)),
             ),
-            Expr::Not(x) => Expr::Not(Box::new(Self::normalize_temporal_literals(x, schema))),
+            Expr::Not(x) => Expr::Not(Box::new(Self::normalize_literals(x, schema))),
+        }
+    }
+
+    /// A UUID string literal in canonical form; anything else unchanged.
+    fn canonical_uuid(value: &JsonValue) -> JsonValue {
+        match value.as_str().and_then(uuid::parse) {
+            Some(bytes) => JsonValue::String(uuid::format(&bytes)),
+            None => value.clone(),
         }
     }
 
@@ -0,0 +1,165 @@
+use std::io::{Error, ErrorKind};
+use xorf::{BinaryFuse8, BinaryFuse16, BinaryFuse32, Filter};
+
+/// Fingerprint width of a binary fuse filter. A filter with `n`-bit
+/// fingerprints has a false-positive rate of about 2^-n.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum XorFingerprint {
+    #[default]
+    Bits8,
+    Bits16,
+    Bits32,
+}
+
+impl XorFingerprint {
+    pub fn bits(self) -> u8 {
+        match self {
+            XorFingerprint::Bits8 => 8,
+            XorFingerprint::Bits16 => 16,
+            XorFingerprint::Bits32 => 32,
+        }
+    }
+
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            8 => Some(XorFingerprint::Bits8),
+            16 => Some(XorFingerprint::Bits16),
+            32 => Some(XorFingerprint::Bits32),
+            _ => None,
+        }
+    }
+
+    pub fn false_positive_rate(self) -> f64 {
+        2f64.powi(-(self.bits() as i32))
+    }
+
+    /// Narrowest width whose false-positive rate stays at or below
+    /// `1 / distinct`, so a probe for an absent value passes no more often
+    /// than an equality match on any single present value would.
+    pub fn for_distinct_count(distinct: usize) -> Self {
+        if distinct <= 1 << 8 {
+            XorFingerprint::Bits8
+        } else if distinct <= 1 << 16 {
+            XorFingerprint::Bits16
+        } else {
+            XorFingerprint::Bits32
+        }
+    }
+}
+
+/// How a segment writer picks the fingerprint width of its XOR filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum XorFilterSizing {
+    /// 8-bit fingerprints regardless of content (flush)
+    #[default]
+    Fixed,
+    /// Width derived from the number of distinct values (compaction)
+    ByDistinctCount,
+}
+
+impl XorFilterSizing {
+    pub fn fingerprint_for(self, distinct: usize) -> XorFingerprint {
+        match self {
+            XorFilterSizing::Fixed => XorFingerprint::Bits8,
+            XorFilterSizing::ByDistinctCount => XorFingerprint::for_distinct_count(distinct),
+        }
+    }
+}
+
+/// A binary fuse filter over value hashes, in one of the supported widths.
+#[derive(Clone, Debug)]
+pub enum FuseFilter {
+    Bits8(BinaryFuse8),
+    Bits16(BinaryFuse16),
+    Bits32(BinaryFuse32),
+}
+
+impl FuseFilter {
+    /// Builds a filter from deduplicated hashes.
+    pub fn build(hashes: &[u64], fingerprint: XorFingerprint) -> Result<Self, &'static str> {
+        let keys = hashes.iter().copied();
+        Ok(match fingerprint {
+            XorFingerprint::Bits8 => FuseFilter::Bits8(BinaryFuse8::try_from_iterator(keys)?),
+            XorFingerprint::Bits16 => FuseFilter::Bits16(BinaryFuse16::try_from_iterator(keys)?),
+            XorFingerprint::Bits32 => FuseFilter::Bits32(BinaryFuse32::try_from_iterator(keys)?),
+        })
+    }
+
+    pub fn fingerprint(&self) -> XorFingerprint {
+        match self {
+            FuseFilter::Bits8(_) => XorFingerprint::Bits8,
+            FuseFilter::Bits16(_) => XorFingerprint::Bits16,
+            FuseFilter::Bits32(_) => XorFingerprint::Bits32,
+        }
+    }
+
+    pub fn contains(&self, hash: &u64) -> bool {
+        match self {
+            FuseFilter::Bits8(filter) => filter.contains(hash),
+            FuseFilter::Bits16(filter) => filter.contains(hash),
+            FuseFilter::Bits32(filter) => filter.contains(hash),
+        }
+    }
+
+    /// Bincode encoding of the inner filter; the width is stored by the caller.
+    pub fn to_bytes(&self) -> std::io::Result<Vec<u8>> {
+        match self {
+            FuseFilter::Bits8(filter) => bincode::serialize(filter),
+            FuseFilter::Bits16(filter) => bincode::serialize(filter),
+            FuseFilter::Bits32(filter) => bincode::serialize(filter),
+        }
+        .map_err(Error::other)
+    }
+
+    pub fn from_bytes(fingerprint: XorFingerprint, bytes: &[u8]) -> std::io::Result<Self> {
+        let invalid = |e| Error::new(ErrorKind::InvalidData, e);
+        Ok(match fingerprint {
+            XorFingerprint::Bits8 => {
+                FuseFilter::Bits8(bincode::deserialize(bytes).map_err(invalid)?)
+            }
+            XorFingerprint::Bits16 => {
+                FuseFilter::Bits16(bincode::deserialize(bytes).map_err(invalid)?)
+            }
+            XorFingerprint::Bits32 => {
+                FuseFilter::Bits32(bincode::deserialize(bytes).map_err(invalid)?)
+            }
+        })
+    }
+}
+
+impl From<BinaryFuse8> for FuseFilter {
+    fn from(filter: BinaryFuse8) -> Self {
+        FuseFilter::Bits8(filter)
+    }
+}
+
+impl From<BinaryFuse16> for FuseFilter {
+    fn from(filter: BinaryFuse16) -> Self {
+        FuseFilter::Bits16(filter)
+    }
+}
+
+impl From<BinaryFuse32> for FuseFilter {
+    fn from(filter: BinaryFuse32) -> Self {
+        FuseFilter::Bits32(filter)
+    }
+}
+
+/// XOR filter file version when every filter is 8-bit; these files stay
+/// readable by builds that predate sized filters.
+pub const XOR_FILE_VERSION_FIXED: u16 = 1;
+/// XOR filter file version that records the fingerprint width of each filter.
+pub const XOR_FILE_VERSION_SIZED: u16 = 2;
+/// Like version 2, but values in canonical UUID form were keyed by their 16
+/// bytes rather than their text.
+pub const XOR_FILE_VERSION_UUID_KEYS: u16 = 3;
+
+/// Decodes a fingerprint width byte from a version 2 filter file.
+pub fn fingerprint_from_byte(bits: u8) -> std::io::Result<XorFingerprint> {
+    XorFingerprint::from_bits(bits).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("unsupported XOR fingerprint width: {}", bits),
+        )
+    })
+}
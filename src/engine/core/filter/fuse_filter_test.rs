@@ -0,0 +1,56 @@
+use crate::engine::core::filter::fuse_filter::{FuseFilter, XorFilterSizing, XorFingerprint};
+
+#[test]
+fn fingerprint_width_grows_with_distinct_count() {
+    assert_eq!(XorFingerprint::for_distinct_count(1), XorFingerprint::Bits8);
+    assert_eq!(
+        XorFingerprint::for_distinct_count(256),
+        XorFingerprint::Bits8
+    );
+    assert_eq!(
+        XorFingerprint::for_distinct_count(257),
+        XorFingerprint::Bits16
+    );
+    assert_eq!(
+        XorFingerprint::for_distinct_count(1 << 16),
+        XorFingerprint::Bits16
+    );
+    assert_eq!(
+        XorFingerprint::for_distinct_count((1 << 16) + 1),
+        XorFingerprint::Bits32
+    );
+    for distinct in [10, 300, 70_000] {
+        let fingerprint = XorFingerprint::for_distinct_count(distinct);
+        assert!(fingerprint.false_positive_rate() <= 1.0 / distinct as f64);
+    }
+}
+
+#[test]
+fn fixed_sizing_always_uses_eight_bits() {
+    assert_eq!(
+        XorFilterSizing::Fixed.fingerprint_for(1_000_000),
+        XorFingerprint::Bits8
+    );
+    assert_eq!(
+        XorFilterSizing::ByDistinctCount.fingerprint_for(1_000),
+        XorFingerprint::Bits16
+    );
+}
+
+#[test]
+fn filter_bytes_roundtrip_for_each_width() {
+    let hashes: Vec<u64> = (0..500u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .collect();
+    for fingerprint in [
+        XorFingerprint::Bits8,
+        XorFingerprint::Bits16,
+        XorFingerprint::Bits32,
+    ] {
+        let filter = FuseFilter::build(&hashes, fingerprint).unwrap();
+        let bytes = filter.to_bytes().unwrap();
+        let loaded = FuseFilter::from_bytes(fingerprint, &bytes).unwrap();
+        assert_eq!(loaded.fingerprint(), fingerprint);
+        assert!(hashes.iter().all(|h| loaded.contains(h)));
+    }
+}
@@ -3,6 +3,7 @@ pub mod condition_evaluator;
 pub mod condition_evaluator_builder;
 pub mod direct_event_accessor;
 pub mod field_xor_filter;
+pub mod fuse_filter;
 pub mod filter_group;
 pub mod filter_group_builder;
 pub mod in_expansion;
@@ -21,6 +22,8 @@ pub mod direct_event_accessor_test;
 #[cfg(test)]
 pub mod field_xor_filter_tests;
 #[cfg(test)]
+pub mod fuse_filter_test;
+#[cfg(test)]
 pub mod filter_group_builder_test;
 #[cfg(test)]
 pub mod filter_group_test;
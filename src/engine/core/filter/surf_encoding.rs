@@ -1,4 +1,4 @@
-use crate::engine::types::ScalarValue;
+use crate::engine::types::{ScalarValue, decimal};
 
 pub fn encode_value(value: &ScalarValue) -> Option<Vec<u8>> {
     match value {
@@ -51,6 +51,8 @@ pub fn encode_value(value: &ScalarValue) -> Option<Vec<u8>> {
             Some(encode_f64(*f))
         }
         ScalarValue::Boolean(b) => Some(if *b { vec![1u8] } else { vec![0u8] }),
+        // Scale-independent, so a column holding several scales stays ordered
+        ScalarValue::Decimal(m, s) => Some(decimal::encode_ordered(*m, *s)),
         _ => None,
     }
 }
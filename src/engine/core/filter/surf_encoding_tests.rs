@@ -31,3 +31,31 @@ fn float_encoding_is_order_preserving() {
     let expected = vec![-3.5, -1.0, -0.0, 0.0, 2.25, 10.5];
     assert_eq!(sorted_by_bytes, expected);
 }
+
+#[test]
+fn decimal_encoding_orders_values_across_scales() {
+    // Numeric order: -2.5 < -0.05 < 0 < 1.4999 < 1.5 == 1.50 < 12
+    let values = [
+        ScalarValue::Decimal(1500, 3),
+        ScalarValue::Decimal(-5, 2),
+        ScalarValue::Decimal(12, 0),
+        ScalarValue::Decimal(14999, 4),
+        ScalarValue::Decimal(0, 2),
+        ScalarValue::Decimal(-25, 1),
+    ];
+    let mut pairs: Vec<(Vec<u8>, &ScalarValue)> = values
+        .iter()
+        .map(|v| (encode_value(v).unwrap(), v))
+        .collect();
+    pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    let sorted: Vec<String> = pairs.iter().map(|(_, v)| v.to_string_repr()).collect();
+
+    assert_eq!(
+        sorted,
+        vec!["-2.5", "-0.05", "0.00", "1.4999", "1.500", "12"]
+    );
+    assert_eq!(
+        encode_value(&ScalarValue::Decimal(15, 1)),
+        encode_value(&ScalarValue::Decimal(150, 2))
+    );
+}
@@ -32,6 +32,7 @@ fn is_field_numeric_consistent(zone_plans: &[ZonePlan], key: &str) -> bool {
         I,
         U,
         F,
+        D,
         Unknown,
     }
     let mut kind = Kind::Unknown;
@@ -43,6 +44,8 @@ fn is_field_numeric_consistent(zone_plans: &[ZonePlan], key: &str) -> bool {
             let this = match v {
                 ScalarValue::Int64(_) | ScalarValue::Timestamp(_) => Kind::I,
                 ScalarValue::Float64(_) => Kind::F,
+                // Any scale: decimal keys encode the value, not the mantissa
+                ScalarValue::Decimal(..) => Kind::D,
                 // JSON numbers are now Utf8 strings - parse to determine type
                 ScalarValue::Utf8(s) => {
                     if s.parse::<i64>().is_ok() {
@@ -629,3 +629,58 @@ fn zonesurf_no_panic_ge_simd_tail_alignment() {
     // We have keys up to 20, so should include the zone
     assert_eq!(out, vec![3u32]);
 }
+
+#[test]
+fn zone_surf_decimal_ranges_across_scales() {
+    // z0 was written at scale 2, z1 at scale 4 after the column widened
+    let zone = |zone_id: u32, values: &[(i128, u8)]| {
+        let mut plan = Factory::zone_plan()
+            .with("id", zone_id)
+            .with("uid", "uidD")
+            .with("segment_id", 11u64)
+            .with("events", json!([]))
+            .with("start_index", 0usize)
+            .with("end_index", values.len() - 1)
+            .create();
+        plan.events = values
+            .iter()
+            .map(|(m, s)| {
+                let mut event = Factory::event().with("payload", json!({})).create();
+                event
+                    .payload
+                    .insert("amount".to_string(), ScalarValue::Decimal(*m, *s));
+                event
+            })
+            .collect();
+        plan
+    };
+    let z0 = zone(0, &[(1050, 2), (1999, 2)]);
+    let z1 = zone(1, &[(200_000, 4), (250_050, 4)]);
+
+    let dir = tempdir().unwrap();
+    let allowed: HashSet<String> = ["amount".to_string()].into_iter().collect();
+    ZoneSurfFilter::build_all_filtered(&[z0, z1], dir.path(), &allowed).unwrap();
+    let zsf = ZoneSurfFilter::load(&dir.path().join("uidD_amount.zsrf")).unwrap();
+    let ids = |zones: Vec<crate::engine::core::CandidateZone>| {
+        zones.into_iter().map(|z| z.zone_id).collect::<Vec<_>>()
+    };
+
+    // >= 20.0 at scale 1 selects z1 only, though both zones store other scales
+    let b = encode_value(&ScalarValue::Decimal(200, 1)).unwrap();
+    assert_eq!(ids(zsf.zones_overlapping_ge(&b, true, "segD")), vec![1u32]);
+
+    // > 19.99 at scale 3 is exclusive of z0's maximum
+    let b = encode_value(&ScalarValue::Decimal(19_990, 3)).unwrap();
+    assert_eq!(ids(zsf.zones_overlapping_ge(&b, false, "segD")), vec![1u32]);
+
+    // <= 19.99 selects z0 only
+    let b = encode_value(&ScalarValue::Decimal(1999, 2)).unwrap();
+    assert_eq!(ids(zsf.zones_overlapping_le(&b, true, "segD")), vec![0u32]);
+
+    // <= 20 at scale 0 reaches z1's minimum
+    let b = encode_value(&ScalarValue::Decimal(20, 0)).unwrap();
+    assert_eq!(
+        ids(zsf.zones_overlapping_le(&b, true, "segD")),
+        vec![0u32, 1u32]
+    );
+}
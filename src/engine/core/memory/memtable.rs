@@ -1,14 +1,34 @@
 use crate::engine::core::Event;
 use crate::engine::errors::StoreError;
-use std::collections::BTreeMap;
+use crate::shared::config::{CONFIG, MemTableLayout};
+use indexmap::IndexMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
-/// In-memory sorted buffer of events keyed by `context_id`.
+/// How a query reads the events of one event type out of a MemTable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemTableAccess {
+    /// Every event of the type: in context order on ordered layouts, in
+    /// arrival order of the contexts on hash layouts
+    Scan,
+    /// The events of one context: a tree lookup on ordered layouts, a hash
+    /// lookup on hash layouts
+    Context(String),
+}
+
+/// In-memory buffer of events keyed by `context_id`, laid out per event
+/// type as configured under `engine.memtable_layouts`.
 /// Flushes to disk as a segment when full.
 #[derive(Debug, Clone)]
 pub struct MemTable {
-    /// Sorted map of events by context_id (lexicographic order).
+    /// Events of `ordered` event types, sorted by context_id (lexicographic order).
     pub events: BTreeMap<String, Vec<Event>>,
 
+    /// Events of `hash` event types, by event type, then by context_id in arrival order.
+    hashed: BTreeMap<String, IndexMap<String, Vec<Event>>>,
+
+    layouts: Arc<HashMap<String, MemTableLayout>>,
+
     /// Maximum number of total events allowed before flush is triggered.
     pub capacity: usize,
 
@@ -17,15 +37,28 @@ pub struct MemTable {
 }
 
 impl MemTable {
-    /// Create a new empty MemTable with a given capacity.
+    /// Create a new empty MemTable with a given capacity and the configured layouts.
     pub fn new(capacity: usize) -> Self {
         Self {
             events: BTreeMap::new(),
+            hashed: BTreeMap::new(),
+            layouts: Arc::new(CONFIG.engine.memtable_layouts.clone()),
             capacity,
             count: 0,
         }
     }
 
+    /// Replaces the configured layouts; only valid while the table is empty.
+    pub fn with_layouts(mut self, layouts: HashMap<String, MemTableLayout>) -> Self {
+        debug_assert!(self.is_empty(), "layouts must be set before inserting");
+        self.layouts = Arc::new(layouts);
+        self
+    }
+
+    pub fn layout_of(&self, event_type: &str) -> MemTableLayout {
+        self.layouts.get(event_type).copied().unwrap_or_default()
+    }
+
     pub fn capacity(&self) -> usize {
         self.capacity
     }
@@ -45,18 +78,96 @@ impl MemTable {
         self.count == 0
     }
 
-    /// Iterate over all events in `context_id` sorted order.
+    /// Iterate over all events: those of ordered event types in `context_id`
+    /// sorted order, then those of hash event types in arrival order.
     pub fn iter(&self) -> impl Iterator<Item = &Event> {
-        self.events.values().flat_map(|bucket| bucket.iter())
+        self.events.values().flat_map(|bucket| bucket.iter()).chain(
+            self.hashed
+                .values()
+                .flat_map(|contexts| contexts.values().flatten()),
+        )
     }
 
-    /// Moves all events out for flushing.
+    /// The events of `event_type` that `access` selects, read the way the
+    /// type's layout allows.
+    pub fn events_for(
+        &self,
+        event_type: &str,
+        access: MemTableAccess,
+    ) -> Box<dyn Iterator<Item = &Event> + Send + '_> {
+        match self.layout_of(event_type) {
+            MemTableLayout::Ordered => {
+                // Buckets hold every ordered type
+                let event_type = event_type.to_string();
+                let of_type = move |event: &&Event| event.event_type == event_type;
+                match access {
+                    MemTableAccess::Scan => {
+                        Box::new(self.events.values().flatten().filter(of_type))
+                    }
+                    MemTableAccess::Context(context_id) => Box::new(
+                        self.events
+                            .get(&context_id)
+                            .into_iter()
+                            .flatten()
+                            .filter(of_type),
+                    ),
+                }
+            }
+            MemTableLayout::Hash => {
+                let Some(contexts) = self.hashed.get(event_type) else {
+                    return Box::new(std::iter::empty());
+                };
+                match access {
+                    MemTableAccess::Context(context_id) => {
+                        Box::new(contexts.get(&context_id).into_iter().flatten())
+                    }
+                    MemTableAccess::Scan => Box::new(contexts.values().flatten()),
+                }
+            }
+        }
+    }
+
+    /// Number of events of `event_type` with a timestamp in `[ts_min, ts_max]`,
+    /// optionally only those of one context.
+    pub fn count_matching(
+        &self,
+        event_type: &str,
+        context_id: Option<&str>,
+        ts_min: u64,
+        ts_max: u64,
+    ) -> u64 {
+        let access = context_id.map_or(MemTableAccess::Scan, |c| {
+            MemTableAccess::Context(c.to_string())
+        });
+        self.events_for(event_type, access)
+            .filter(|event| (ts_min..=ts_max).contains(&event.timestamp))
+            .count() as u64
+    }
+
+    /// Earliest and latest timestamp of the events of `event_type`.
+    pub fn time_span(&self, event_type: &str) -> Option<(u64, u64)> {
+        self.events_for(event_type, MemTableAccess::Scan)
+            .map(|event| event.timestamp)
+            .fold(None, |span, ts| {
+                Some(span.map_or((ts, ts), |(lo, hi): (u64, u64)| (lo.min(ts), hi.max(ts))))
+            })
+    }
+
+    /// Moves all events out for flushing, grouped by context_id in sorted
+    /// order whatever the layout, so segments come out the same.
     pub fn take(self) -> BTreeMap<String, Vec<Event>> {
-        self.events
+        let mut events = self.events;
+        for contexts in self.hashed.into_values() {
+            for (context_id, bucket) in contexts {
+                events.entry(context_id).or_default().extend(bucket);
+            }
+        }
+        events
     }
 
     pub fn flush(&mut self) {
         self.events.clear();
+        self.hashed.clear();
         self.count = 0;
     }
 
@@ -72,17 +183,26 @@ impl MemTable {
             return Err(StoreError::InvalidEventType);
         }
 
-        // Insert into memtable (keeps sorted by context_id)
         self.insert_internal(event);
         Ok(())
     }
 
     /// Internal insertion logic
     pub(crate) fn insert_internal(&mut self, event: Event) {
-        self.events
-            .entry(event.context_id.clone())
-            .or_default()
-            .push(event);
+        match self.layout_of(&event.event_type) {
+            MemTableLayout::Ordered => self
+                .events
+                .entry(event.context_id.clone())
+                .or_default()
+                .push(event),
+            MemTableLayout::Hash => self
+                .hashed
+                .entry(event.event_type.clone())
+                .or_default()
+                .entry(event.context_id.clone())
+                .or_default()
+                .push(event),
+        }
         self.count += 1;
     }
 }
@@ -1,5 +1,6 @@
-use crate::engine::core::MemTable;
+use crate::engine::core::{Event, MemTable, MemTableAccess};
 use crate::engine::errors::StoreError;
+use crate::shared::config::MemTableLayout;
 use crate::test_helpers::factories::EventFactory;
 
 #[test]
@@ -77,3 +78,114 @@ fn test_memtable_take_flushes_and_empties() {
     let taken = memtable.take();
     assert_eq!(taken.len(), 2);
 }
+
+#[test]
+fn test_memtable_count_matching_filters_type_context_and_time() {
+    let mut memtable = MemTable::new(10);
+    for (context_id, event_type, timestamp) in [
+        ("ctx_a", "order", 100),
+        ("ctx_a", "order", 200),
+        ("ctx_b", "order", 150),
+        ("ctx_b", "refund", 150),
+    ] {
+        let event = EventFactory::new()
+            .with("context_id", context_id)
+            .with("event_type", event_type)
+            .with("timestamp", timestamp)
+            .create();
+        memtable.insert(event).unwrap();
+    }
+
+    assert_eq!(memtable.count_matching("order", None, 0, u64::MAX), 3);
+    assert_eq!(memtable.count_matching("order", None, 150, 200), 2);
+    assert_eq!(memtable.count_matching("order", Some("ctx_a"), 0, 150), 1);
+    assert_eq!(
+        memtable.count_matching("order", Some("ctx_c"), 0, u64::MAX),
+        0
+    );
+    assert_eq!(memtable.time_span("order"), Some((100, 200)));
+    assert_eq!(memtable.time_span("refund"), Some((150, 150)));
+    assert_eq!(memtable.time_span("signup"), None);
+}
+
+fn layered_memtable() -> MemTable {
+    let layouts = [("order".to_string(), MemTableLayout::Hash)].into();
+    let mut memtable = MemTable::new(10).with_layouts(layouts);
+    for (context_id, event_type) in [
+        ("ctx_c", "order"),
+        ("ctx_a", "order"),
+        ("ctx_b", "refund"),
+        ("ctx_c", "refund"),
+        ("ctx_a", "order"),
+    ] {
+        let event = EventFactory::new()
+            .with("context_id", context_id)
+            .with("event_type", event_type)
+            .create();
+        memtable.insert(event).unwrap();
+    }
+    memtable
+}
+
+fn contexts<'a>(events: impl Iterator<Item = &'a Event>) -> Vec<String> {
+    events.map(|e| e.context_id.clone()).collect()
+}
+
+#[test]
+fn test_memtable_hash_layout_reads_contexts_in_arrival_order() {
+    let memtable = layered_memtable();
+    assert_eq!(memtable.layout_of("order"), MemTableLayout::Hash);
+    assert_eq!(memtable.layout_of("refund"), MemTableLayout::Ordered);
+    assert_eq!(memtable.len(), 5);
+
+    assert_eq!(
+        contexts(memtable.events_for("order", MemTableAccess::Scan)),
+        vec!["ctx_c", "ctx_a", "ctx_a"]
+    );
+    assert_eq!(
+        contexts(memtable.events_for("order", MemTableAccess::Context("ctx_a".into()))),
+        vec!["ctx_a", "ctx_a"]
+    );
+    assert_eq!(
+        memtable.count_matching("order", Some("ctx_c"), 0, u64::MAX),
+        1
+    );
+    assert_eq!(memtable.iter().count(), 5);
+}
+
+#[test]
+fn test_memtable_ordered_layout_reads_contexts_in_sorted_order() {
+    let memtable = layered_memtable();
+
+    assert_eq!(
+        contexts(memtable.events_for("refund", MemTableAccess::Scan)),
+        vec!["ctx_b", "ctx_c"]
+    );
+    // A context's bucket holds every ordered type; only the asked one comes back
+    assert_eq!(
+        contexts(memtable.events_for("refund", MemTableAccess::Context("ctx_c".into()))),
+        vec!["ctx_c"]
+    );
+    assert_eq!(
+        memtable
+            .events_for("order", MemTableAccess::Context("ctx_b".into()))
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn test_memtable_take_sorts_contexts_whatever_the_layout() {
+    let taken = layered_memtable().take();
+
+    assert_eq!(
+        taken.keys().collect::<Vec<_>>(),
+        vec!["ctx_a", "ctx_b", "ctx_c"]
+    );
+    assert_eq!(taken["ctx_a"].len(), 2);
+    let ctx_c: Vec<_> = taken["ctx_c"]
+        .iter()
+        .map(|e| e.event_type.as_str())
+        .collect();
+    assert_eq!(ctx_c, vec!["refund", "order"]);
+}
@@ -28,10 +28,11 @@ pub use filter::condition::LogicalOp;
 pub use filter::condition::NumericCondition;
 pub use filter::condition::StringCondition;
 pub use filter::condition_evaluator::ConditionEvaluator;
+pub use filter::condition_evaluator::RowSelection;
 pub use filter::condition_evaluator_builder::ConditionEvaluatorBuilder;
 pub use filter::field_xor_filter::FieldXorFilter;
 pub use filter::filter_group::FilterGroup;
-pub use memory::memtable::MemTable;
+pub use memory::memtable::{MemTable, MemTableAccess};
 pub use read::cache::{ColumnHandle, ColumnProvider, QueryCaches, ZoneIndexProvider};
 pub use read::event_sorter::EventSorter;
 pub use read::execution_step::ExecutionStep;
@@ -59,7 +60,7 @@ pub use time::ZoneTemporalIndex;
 pub use utils::io_monitor::IoMonitor;
 pub use utils::memory_monitor::MemoryMonitor;
 pub use utils::uid_resolver::UidResolver;
-pub use wal::inner_wal_writer::InnerWalWriter;
+pub use wal::inner_wal_writer::{InnerWalWriter, WalRotationPolicy};
 pub use wal::wal_archive::WalArchive;
 pub use wal::wal_archive::WalArchiveBody;
 pub use wal::wal_archive::WalArchiveHeader;
@@ -69,6 +70,7 @@ pub use wal::wal_cleaner::WalCleaner;
 pub use wal::wal_entry::WalEntry;
 pub use wal::wal_handle::WalHandle;
 pub use wal::wal_handle::WalMessage;
+pub use wal::wal_inspector::{InspectedWalEntry, WalEntryState, WalInspection, WalInspector};
 pub use wal::wal_recovery::WalRecovery;
 pub use write::column_writer::ColumnWriter;
 pub use write::flush_manager::FlushManager;
@@ -0,0 +1,200 @@
+use crate::shared::config::CONFIG;
+use serde::{Deserialize, Serialize};
+
+/// Distinct values a `COUNT UNIQUE` group keeps exactly before switching to a sketch.
+pub const DEFAULT_COUNT_UNIQUE_EXACT_LIMIT: usize = 10_000;
+
+/// Register index bits when no error target is configured; 2^14 registers
+/// give a standard error of about 0.8%.
+pub const DEFAULT_PRECISION: u32 = 14;
+const MIN_PRECISION: u32 = 4;
+const MAX_PRECISION: u32 = 18;
+
+/// Per-group exact set size above which `COUNT UNIQUE` is estimated.
+pub fn exact_limit() -> usize {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|q| q.count_unique_exact_limit)
+        .unwrap_or(DEFAULT_COUNT_UNIQUE_EXACT_LIMIT)
+}
+
+/// Register index bits for new sketches, from `count_distinct_relative_error`.
+pub fn configured_precision() -> u32 {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|q| q.count_distinct_relative_error)
+        .map(precision_for_error)
+        .unwrap_or(DEFAULT_PRECISION)
+}
+
+/// Smallest precision whose standard error (1.04 / sqrt(2^p)) is at most
+/// `error`, clamped to the supported range.
+pub fn precision_for_error(error: f64) -> u32 {
+    if !(error > 0.0 && error < 1.0) {
+        return DEFAULT_PRECISION;
+    }
+    let registers = (1.04 / error).powi(2);
+    (registers.log2().ceil() as u32).clamp(MIN_PRECISION, MAX_PRECISION)
+}
+
+/// HyperLogLog sketch of distinct string values.
+///
+/// Values are hashed with a fixed function so sketches built on different
+/// shards (or processes) merge by taking the register-wise maximum. Sketches
+/// of different precisions merge at the smaller one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HllSketch {
+    registers: Vec<u8>,
+}
+
+impl Default for HllSketch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HllSketch {
+    pub fn new() -> Self {
+        Self::with_precision(configured_precision())
+    }
+
+    pub fn with_precision(precision: u32) -> Self {
+        let precision = precision.clamp(MIN_PRECISION, MAX_PRECISION);
+        Self {
+            registers: vec![0; 1 << precision],
+        }
+    }
+
+    pub fn precision(&self) -> u32 {
+        self.registers.len().trailing_zeros()
+    }
+
+    pub fn from_values<'a>(values: impl IntoIterator<Item = &'a String>) -> Self {
+        let mut sketch = Self::new();
+        for v in values {
+            sketch.insert(v);
+        }
+        sketch
+    }
+
+    pub fn insert(&mut self, value: &str) {
+        let precision = self.precision();
+        let hash = hash64(value.as_bytes());
+        let index = (hash >> (64 - precision)) as usize;
+        let rest = hash << precision;
+        let rank = (rest.leading_zeros().min(64 - precision) + 1) as u8;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn merge(&mut self, other: &HllSketch) {
+        if other.precision() < self.precision() {
+            *self = self.folded(other.precision());
+        } else if other.precision() > self.precision() {
+            self.merge(&other.folded(self.precision()));
+            return;
+        }
+        for (a, b) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *b > *a {
+                *a = *b;
+            }
+        }
+    }
+
+    /// The same sketch at a lower precision: the dropped index bits become
+    /// the leading bits of each value's rank.
+    fn folded(&self, precision: u32) -> Self {
+        let dropped = self.precision() - precision;
+        let mut out = Self::with_precision(precision);
+        for (index, &rank) in self.registers.iter().enumerate() {
+            if rank == 0 {
+                continue;
+            }
+            let low = index & ((1 << dropped) - 1);
+            let rank = if low == 0 {
+                (rank as u32 + dropped).min(64 - precision + 1) as u8
+            } else {
+                (dropped - (usize::BITS - low.leading_zeros()) + 1) as u8
+            };
+            let slot = &mut out.registers[index >> dropped];
+            if rank > *slot {
+                *slot = rank;
+            }
+        }
+        out
+    }
+
+    pub fn estimate(&self) -> usize {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let mut sum = 0.0;
+        let mut zeros = 0usize;
+        for &r in &self.registers {
+            sum += 1.0 / (1u64 << r) as f64;
+            if r == 0 {
+                zeros += 1;
+            }
+        }
+        let raw = alpha * m * m / sum;
+        // Linear counting is more accurate while many registers are still empty
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        };
+        estimate.round() as usize
+    }
+
+    pub fn heap_bytes(&self) -> usize {
+        self.registers.capacity()
+    }
+
+    /// Hex encoding of the registers, used in partial aggregate batches.
+    pub fn to_hex(&self) -> String {
+        let mut out = String::with_capacity(self.registers.len() * 2);
+        for r in &self.registers {
+            out.push_str(&format!("{:02x}", r));
+        }
+        out
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let registers = hex.len() / 2;
+        let valid = hex.len().is_multiple_of(2)
+            && registers.is_power_of_two()
+            && (MIN_PRECISION..=MAX_PRECISION).contains(&registers.trailing_zeros());
+        if !valid {
+            return Err(format!(
+                "expected 2^{}..2^{} registers ({}..{} hex digits) for sketch, got {} hex digits",
+                MIN_PRECISION,
+                MAX_PRECISION,
+                2 << MIN_PRECISION,
+                2 << MAX_PRECISION,
+                hex.len()
+            ));
+        }
+        let registers = (0..registers)
+            .map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|e| format!("invalid sketch register: {}", e))?;
+        Ok(Self { registers })
+    }
+}
+
+/// FNV-1a followed by a murmur3 finalizer so nearby inputs spread over all bits.
+fn hash64(bytes: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^= h >> 33;
+    h
+}
@@ -0,0 +1,102 @@
+use crate::engine::core::read::aggregate::hll::HllSketch;
+
+fn sketch_of(range: std::ops::Range<usize>) -> HllSketch {
+    let mut sketch = HllSketch::new();
+    for i in range {
+        sketch.insert(&format!("user-{}", i));
+    }
+    sketch
+}
+
+fn assert_close(estimate: usize, actual: usize) {
+    let error = (estimate as f64 - actual as f64).abs() / actual as f64;
+    assert!(
+        error < 0.03,
+        "estimate {} is {:.2}% off {}",
+        estimate,
+        error * 100.0,
+        actual
+    );
+}
+
+#[test]
+fn estimate_is_close_for_small_and_large_cardinalities() {
+    assert_eq!(HllSketch::new().estimate(), 0);
+    assert_close(sketch_of(0..1_000).estimate(), 1_000);
+    assert_close(sketch_of(0..200_000).estimate(), 200_000);
+}
+
+#[test]
+fn repeated_values_do_not_change_the_estimate() {
+    let mut sketch = sketch_of(0..500);
+    let before = sketch.estimate();
+    for i in 0..500 {
+        sketch.insert(&format!("user-{}", i));
+    }
+    assert_eq!(sketch.estimate(), before);
+}
+
+#[test]
+fn merge_counts_overlapping_values_once() {
+    let mut a = sketch_of(0..30_000);
+    let b = sketch_of(20_000..50_000);
+    a.merge(&b);
+    assert_close(a.estimate(), 50_000);
+}
+
+#[test]
+fn hex_round_trip_preserves_registers() {
+    let sketch = sketch_of(0..100);
+    let decoded = HllSketch::from_hex(&sketch.to_hex()).unwrap();
+    assert_eq!(decoded, sketch);
+    assert!(HllSketch::from_hex("00ff").is_err());
+}
+
+#[test]
+fn precision_follows_the_error_target() {
+    use crate::engine::core::read::aggregate::hll::{DEFAULT_PRECISION, precision_for_error};
+
+    // 1.04 / sqrt(2^14) is about 0.81%
+    assert_eq!(precision_for_error(0.0082), 14);
+    assert_eq!(precision_for_error(0.01), 14);
+    assert_eq!(precision_for_error(0.02), 12);
+    assert_eq!(precision_for_error(0.5), 4);
+    assert_eq!(precision_for_error(0.0001), 18);
+    assert_eq!(precision_for_error(0.0), DEFAULT_PRECISION);
+    assert_eq!(precision_for_error(f64::NAN), DEFAULT_PRECISION);
+
+    let mut sketch = HllSketch::with_precision(12);
+    for i in 0..50_000 {
+        sketch.insert(&format!("user-{}", i));
+    }
+    assert_eq!(sketch.precision(), 12);
+    assert_close(sketch.estimate(), 50_000);
+}
+
+#[test]
+fn merge_folds_to_the_smaller_precision() {
+    let mut fine = HllSketch::with_precision(14);
+    let mut coarse = HllSketch::with_precision(10);
+    let mut expected = HllSketch::with_precision(10);
+    for i in 0..40_000 {
+        let value = format!("user-{}", i);
+        if i < 25_000 {
+            fine.insert(&value);
+        }
+        if i >= 15_000 {
+            coarse.insert(&value);
+        }
+        expected.insert(&value);
+    }
+
+    let mut a = fine.clone();
+    a.merge(&coarse);
+    let mut b = coarse.clone();
+    b.merge(&fine);
+    // Folding is exact: the result is the sketch a 2^10 register count would build
+    assert_eq!(a, expected);
+    assert_eq!(b, expected);
+
+    let decoded = HllSketch::from_hex(&coarse.to_hex()).unwrap();
+    assert_eq!(decoded.precision(), 10);
+}
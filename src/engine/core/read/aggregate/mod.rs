@@ -1,10 +1,19 @@
+pub mod hll;
 pub mod ops;
 pub mod partial;
 pub mod plan;
+pub mod tdigest;
+pub mod zone_summary_plan;
 
+#[cfg(test)]
+mod hll_test;
 #[cfg(test)]
 mod ops_test;
 #[cfg(test)]
 mod partial_test;
 #[cfg(test)]
 mod plan_test;
+#[cfg(test)]
+mod tdigest_test;
+#[cfg(test)]
+mod zone_summary_plan_test;
@@ -1,10 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use crate::engine::core::Event;
 use crate::engine::core::column::column_values::ColumnValues;
 use crate::engine::core::column::format::PhysicalType;
+use crate::engine::core::read::aggregate::hll::{self, HllSketch};
 use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::engine::core::read::aggregate::tdigest::TDigest;
 use crate::engine::types::ScalarValue;
+use crate::shared::hash::QueryHashSet;
 use std::simd::Simd;
 use std::simd::prelude::*;
 
@@ -15,6 +18,10 @@ const SIMD_LANES: usize = 4;
 pub enum AggOutput {
     Count(i64),
     CountUnique(usize),
+    /// Distinct count estimated from a sketch once the exact set grew too large
+    CountUniqueEstimate(usize),
+    /// `None` for a group without numeric values
+    Percentile(Option<f64>),
     Sum(i64),
     Min(String),
     Max(String),
@@ -27,6 +34,7 @@ pub enum AggregatorImpl {
     CountAll(CountAll),
     CountField(CountField),
     CountUnique(CountUnique),
+    Percentile(Percentile),
     Sum(Sum),
     Min(Min),
     Max(Max),
@@ -43,6 +51,12 @@ impl AggregatorImpl {
             AggregateOpSpec::CountUnique { field } => {
                 Self::CountUnique(CountUnique::new(field.clone()))
             }
+            AggregateOpSpec::CountDistinct { field } => {
+                Self::CountUnique(CountUnique::estimated(field.clone()))
+            }
+            AggregateOpSpec::Percentile { field, percentile } => {
+                Self::Percentile(Percentile::new(field.clone(), *percentile))
+            }
             AggregateOpSpec::Total { field } => Self::Sum(Sum::new(field.clone())),
             AggregateOpSpec::Avg { field } => Self::Avg(Avg::new(field.clone())),
             AggregateOpSpec::Min { field } => Self::Min(Min::new(field.clone())),
@@ -55,6 +69,7 @@ impl AggregatorImpl {
             AggregatorImpl::CountAll(_) => None,
             AggregatorImpl::CountField(a) => Some(&a.field),
             AggregatorImpl::CountUnique(a) => Some(&a.field),
+            AggregatorImpl::Percentile(a) => Some(&a.field),
             AggregatorImpl::Sum(a) => Some(&a.field),
             AggregatorImpl::Min(a) => Some(&a.field),
             AggregatorImpl::Max(a) => Some(&a.field),
@@ -68,6 +83,7 @@ impl AggregatorImpl {
             AggregatorImpl::CountAll(a) => a.update(),
             AggregatorImpl::CountField(a) => a.update(row_idx, columns),
             AggregatorImpl::CountUnique(a) => a.update(row_idx, columns),
+            AggregatorImpl::Percentile(a) => a.update(row_idx, columns),
             AggregatorImpl::Sum(a) => a.update(row_idx, columns),
             AggregatorImpl::Min(a) => a.update(row_idx, columns),
             AggregatorImpl::Max(a) => a.update(row_idx, columns),
@@ -100,6 +116,11 @@ impl AggregatorImpl {
                     a.update(row_idx, columns);
                 }
             }
+            AggregatorImpl::Percentile(a) => {
+                for row_idx in start..end {
+                    a.update(row_idx, columns);
+                }
+            }
             AggregatorImpl::Sum(a) => {
                 a.update_column_simd(start, end, columns);
             }
@@ -127,6 +148,7 @@ impl AggregatorImpl {
             (AggregatorImpl::CountAll(a), AggregatorImpl::CountAll(b)) => a.merge(b),
             (AggregatorImpl::CountField(a), AggregatorImpl::CountField(b)) => a.merge(b),
             (AggregatorImpl::CountUnique(a), AggregatorImpl::CountUnique(b)) => a.merge(b),
+            (AggregatorImpl::Percentile(a), AggregatorImpl::Percentile(b)) => a.merge(b),
             (AggregatorImpl::Sum(a), AggregatorImpl::Sum(b)) => a.merge(b),
             (AggregatorImpl::Min(a), AggregatorImpl::Min(b)) => a.merge(b),
             (AggregatorImpl::Max(a), AggregatorImpl::Max(b)) => a.merge(b),
@@ -140,6 +162,7 @@ impl AggregatorImpl {
             AggregatorImpl::CountAll(a) => a.finalize(),
             AggregatorImpl::CountField(a) => a.finalize(),
             AggregatorImpl::CountUnique(a) => a.finalize(),
+            AggregatorImpl::Percentile(a) => a.finalize(),
             AggregatorImpl::Sum(a) => a.finalize(),
             AggregatorImpl::Min(a) => a.finalize(),
             AggregatorImpl::Max(a) => a.finalize(),
@@ -147,6 +170,18 @@ impl AggregatorImpl {
         }
     }
 
+    /// Approximate memory held by this aggregator, for query memory accounting.
+    pub fn approx_bytes(&self) -> usize {
+        let heap = match self {
+            AggregatorImpl::CountUnique(a) => a.approx_heap_bytes(),
+            AggregatorImpl::Percentile(a) => a.digest.heap_bytes(),
+            AggregatorImpl::Min(a) => a.min_str.as_ref().map_or(0, String::len),
+            AggregatorImpl::Max(a) => a.max_str.as_ref().map_or(0, String::len),
+            _ => 0,
+        };
+        std::mem::size_of::<Self>() + heap
+    }
+
     /// Update aggregator using a row from a full `Event` (row-based path)
     /// Optimized to use get_field_scalar() to avoid string allocations
     pub fn update_from_event(&mut self, event: &Event) {
@@ -177,6 +212,21 @@ impl AggregatorImpl {
                 };
                 a.update_value_str(&s);
             }
+            AggregatorImpl::Percentile(a) => {
+                let n = match a.field.as_str() {
+                    "timestamp" => Some(event.timestamp as f64),
+                    other => match event.get_field_scalar(other) {
+                        Some(ScalarValue::Int64(i)) => Some(i as f64),
+                        Some(ScalarValue::Float64(f)) => Some(f),
+                        Some(ScalarValue::Timestamp(ts)) => Some(ts as f64),
+                        Some(ScalarValue::Utf8(s)) => s.parse::<f64>().ok(),
+                        _ => None,
+                    },
+                };
+                if let Some(v) = n {
+                    a.update_value_f64(v);
+                }
+            }
             AggregatorImpl::Sum(a) => {
                 let n = match a.field.as_str() {
                     "timestamp" => Some(event.timestamp as i64),
@@ -308,50 +358,171 @@ impl CountField {
     }
 }
 
+/// Distinct values of a field, kept exactly until there are more than
+/// `exact_limit` of them and estimated with a HyperLogLog sketch from then on.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CountUnique {
     pub field: String,
-    uniq: HashSet<String>,
+    uniq: QueryHashSet<String>,
+    // Total length of the distinct values, kept for memory accounting
+    value_bytes: usize,
+    sketch: Option<HllSketch>,
+    exact_limit: usize,
 }
 
 impl CountUnique {
     pub fn new(field: String) -> Self {
+        Self::with_exact_limit(field, hll::exact_limit())
+    }
+
+    /// Counts with a sketch from the first value, as `COUNT(DISTINCT ...)` does.
+    pub fn estimated(field: String) -> Self {
+        let mut agg = Self::with_exact_limit(field, 0);
+        agg.sketch = Some(HllSketch::new());
+        agg
+    }
+
+    pub fn with_exact_limit(field: String, exact_limit: usize) -> Self {
         Self {
             field,
-            uniq: HashSet::new(),
+            uniq: QueryHashSet::default(),
+            value_bytes: 0,
+            sketch: None,
+            exact_limit,
         }
     }
 
-    pub fn values(&self) -> &HashSet<String> {
+    /// Distinct values seen so far; empty once the count is estimated.
+    pub fn values(&self) -> &QueryHashSet<String> {
         &self.uniq
     }
 
+    pub fn sketch(&self) -> Option<&HllSketch> {
+        self.sketch.as_ref()
+    }
+
     pub fn update(&mut self, row_idx: usize, columns: &HashMap<String, ColumnValues>) {
         if let Some(col) = columns.get(&self.field) {
             if let Some(s) = col.get_str_at(row_idx) {
-                self.uniq.insert(s.to_string());
+                self.insert(s);
             } else {
                 // Missing value in column: treat as empty string (consistent with update_from_event)
-                self.uniq.insert(String::new());
+                self.insert("");
             }
         } else {
             // Missing column: treat as empty string (consistent with update_from_event)
-            self.uniq.insert(String::new());
+            self.insert("");
         }
     }
 
     pub fn update_value_str(&mut self, s: &str) {
-        self.uniq.insert(s.to_string());
+        self.insert(s);
     }
 
     pub fn merge(&mut self, other: &CountUnique) {
-        for v in &other.uniq {
-            self.uniq.insert(v.clone());
+        match (&mut self.sketch, &other.sketch) {
+            (Some(a), Some(b)) => a.merge(b),
+            (Some(a), None) => {
+                for v in &other.uniq {
+                    a.insert(v);
+                }
+            }
+            (None, Some(b)) => {
+                let mut sketch = b.clone();
+                for v in &self.uniq {
+                    sketch.insert(v);
+                }
+                self.switch_to_sketch(sketch);
+            }
+            (None, None) => {
+                for v in &other.uniq {
+                    self.insert(v);
+                }
+            }
+        }
+    }
+
+    /// Approximate heap memory held by the distinct values.
+    pub fn approx_heap_bytes(&self) -> usize {
+        self.uniq.capacity() * (std::mem::size_of::<String>() + 1)
+            + self.value_bytes
+            + self.sketch.as_ref().map_or(0, HllSketch::heap_bytes)
+    }
+
+    fn insert(&mut self, value: &str) {
+        if let Some(sketch) = &mut self.sketch {
+            sketch.insert(value);
+            return;
         }
+        if !self.uniq.contains(value) {
+            self.value_bytes += value.len();
+            self.uniq.insert(value.to_string());
+            if self.uniq.len() > self.exact_limit {
+                let sketch = HllSketch::from_values(&self.uniq);
+                self.switch_to_sketch(sketch);
+            }
+        }
+    }
+
+    fn switch_to_sketch(&mut self, sketch: HllSketch) {
+        self.uniq = QueryHashSet::default();
+        self.value_bytes = 0;
+        self.sketch = Some(sketch);
+    }
+
+    pub fn finalize(&self) -> AggOutput {
+        match &self.sketch {
+            Some(sketch) => AggOutput::CountUniqueEstimate(sketch.estimate()),
+            None => AggOutput::CountUnique(self.uniq.len()),
+        }
+    }
+}
+
+/// Percentile of a numeric field, estimated from a t-digest of its values.
+/// Non-numeric and missing values are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Percentile {
+    pub field: String,
+    /// Between 0 and 100
+    pub percentile: f64,
+    digest: TDigest,
+}
+
+impl Percentile {
+    pub fn new(field: String, percentile: f64) -> Self {
+        Self {
+            field,
+            percentile,
+            digest: TDigest::new(),
+        }
+    }
+
+    pub fn digest(&self) -> &TDigest {
+        &self.digest
+    }
+
+    pub fn update(&mut self, row_idx: usize, columns: &HashMap<String, ColumnValues>) {
+        if let Some(col) = columns.get(&self.field) {
+            let v = col
+                .get_f64_at(row_idx)
+                .or_else(|| col.get_i64_at(row_idx).map(|i| i as f64))
+                .or_else(|| col.get_str_at(row_idx)?.parse::<f64>().ok());
+            if let Some(v) = v {
+                self.digest.insert(v);
+            }
+        }
+    }
+
+    pub fn update_value_f64(&mut self, v: f64) {
+        self.digest.insert(v);
+    }
+
+    pub fn merge(&mut self, other: &Percentile) {
+        self.digest.merge(&other.digest);
     }
 
     pub fn finalize(&self) -> AggOutput {
-        AggOutput::CountUnique(self.uniq.len())
+        AggOutput::Percentile(self.digest.quantile(self.percentile / 100.0))
     }
 }
 
@@ -220,6 +220,69 @@ fn count_unique_collects_unique_values() {
     assert_eq!(agg.finalize(), AggOutput::CountUnique(3)); // "u1", "u2", and ""
 }
 
+#[test]
+fn count_distinct_estimates_from_the_first_value() {
+    let mut agg = AggregatorImpl::from_spec(&AggregateOpSpec::CountDistinct {
+        field: "user".into(),
+    });
+    let cols = make_columns(&[("user", vec!["u1", "u2", "u1"])]);
+    agg.update_column(0, 3, &cols);
+    assert_eq!(agg.finalize(), AggOutput::CountUniqueEstimate(2));
+
+    let mut other = AggregatorImpl::from_spec(&AggregateOpSpec::CountDistinct {
+        field: "user".into(),
+    });
+    let cols = make_columns(&[("user", vec!["u2", "u3"])]);
+    other.update_column(0, 2, &cols);
+    agg.merge(&other);
+    assert_eq!(agg.finalize(), AggOutput::CountUniqueEstimate(3));
+
+    // Fixed memory: the sketch does not grow with distinct values
+    let before = agg.approx_bytes();
+    if let AggregatorImpl::CountUnique(a) = &mut agg {
+        for i in 0..5_000 {
+            a.update_value_str(&format!("user-{}", i));
+        }
+    }
+    assert_eq!(agg.approx_bytes(), before);
+}
+
+#[test]
+fn percentile_interpolates_numeric_values() {
+    let mut agg = AggregatorImpl::from_spec(&AggregateOpSpec::Percentile {
+        field: "latency".into(),
+        percentile: 50.0,
+    });
+    assert_eq!(agg.finalize(), AggOutput::Percentile(None));
+
+    let cols = make_columns(&[("latency", vec!["10", "abc", "20", "", "40"])]);
+    agg.update_column(0, 5, &cols);
+    assert_eq!(agg.finalize(), AggOutput::Percentile(Some(20.0)));
+
+    let mut other = AggregatorImpl::from_spec(&AggregateOpSpec::Percentile {
+        field: "latency".into(),
+        percentile: 50.0,
+    });
+    let cols = make_columns(&[("latency", vec!["30"])]);
+    other.update(0, &cols);
+    agg.merge(&other);
+    assert_eq!(agg.finalize(), AggOutput::Percentile(Some(25.0)));
+}
+
+#[test]
+fn count_unique_memory_grows_only_with_new_values() {
+    let mut agg = CountUnique::new("user".into());
+    agg.update_value_str("alpha");
+    let after_first = agg.approx_heap_bytes();
+    agg.update_value_str("alpha");
+    assert_eq!(agg.approx_heap_bytes(), after_first);
+
+    for i in 0..100 {
+        agg.update_value_str(&format!("user-{}", i));
+    }
+    assert!(agg.approx_heap_bytes() > after_first + 100 * "user-0".len());
+}
+
 #[test]
 fn sum_ignores_non_numeric_and_sums_numeric() {
     let mut agg = AggregatorImpl::from_spec(&AggregateOpSpec::Total {
@@ -996,3 +1059,41 @@ fn count_field_typed_i64_real_world_scenario() {
     // Should count 2 (from NL), not 0
     assert_eq!(agg.finalize(), AggOutput::Count(2));
 }
+
+#[test]
+fn count_unique_switches_to_estimate_past_exact_limit() {
+    let mut agg = CountUnique::with_exact_limit("user".into(), 100);
+    for i in 0..100 {
+        agg.update_value_str(&format!("user-{}", i));
+    }
+    assert_eq!(agg.finalize(), AggOutput::CountUnique(100));
+
+    // Crossing the limit mid-stream carries the exact values into the sketch
+    for i in 0..1_000 {
+        agg.update_value_str(&format!("user-{}", i));
+    }
+    assert!(agg.values().is_empty());
+    match agg.finalize() {
+        AggOutput::CountUniqueEstimate(n) => assert!((980..=1_020).contains(&n), "got {}", n),
+        other => panic!("expected estimate, got {:?}", other),
+    }
+}
+
+#[test]
+fn merge_count_unique_exact_into_estimate() {
+    let mut exact = CountUnique::with_exact_limit("user".into(), 1_000);
+    for i in 0..500 {
+        exact.update_value_str(&format!("user-{}", i));
+    }
+    let mut sketched = CountUnique::with_exact_limit("user".into(), 10);
+    for i in 400..1_400 {
+        sketched.update_value_str(&format!("user-{}", i));
+    }
+
+    exact.merge(&sketched);
+    assert!(exact.sketch().is_some());
+    match exact.finalize() {
+        AggOutput::CountUniqueEstimate(n) => assert!((1_372..=1_428).contains(&n), "got {}", n),
+        other => panic!("expected estimate, got {:?}", other),
+    }
+}
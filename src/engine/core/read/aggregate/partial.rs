@@ -1,16 +1,20 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::command::types::TimeGranularity;
+use crate::engine::core::read::aggregate::hll::{self, HllSketch};
 use crate::engine::core::read::aggregate::ops::{AggOutput, AggregatorImpl};
 use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::engine::core::read::aggregate::tdigest::TDigest;
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct GroupKey {
     pub bucket: Option<u64>,
-    pub groups: Vec<String>,
+    /// One value per grouping field; `None` for events without a value
+    pub groups: Vec<Option<String>>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum AggState {
     CountAll {
         count: i64,
@@ -19,6 +23,14 @@ pub enum AggState {
     CountUnique {
         values: HashSet<String>,
     },
+    /// COUNT UNIQUE state of a group that outgrew the exact limit
+    CountUniqueSketch {
+        sketch: HllSketch,
+    },
+    /// Digest of a PERCENTILE field; the percentile itself comes from the spec
+    Percentile {
+        digest: TDigest,
+    },
     Sum {
         sum: i64,
     },
@@ -38,13 +50,34 @@ pub enum AggState {
 
 impl AggState {
     pub fn merge(&mut self, other: &AggState) {
-        match (self, other) {
+        match (&mut *self, other) {
             (AggState::CountAll { count: a }, AggState::CountAll { count: b }) => *a += *b,
             (AggState::CountUnique { values: a }, AggState::CountUnique { values: b }) => {
                 for v in b {
                     a.insert(v.clone());
                 }
+                if a.len() > hll::exact_limit() {
+                    let sketch = HllSketch::from_values(a.iter());
+                    *self = AggState::CountUniqueSketch { sketch };
+                }
+            }
+            (AggState::CountUnique { values: a }, AggState::CountUniqueSketch { sketch: b }) => {
+                let mut sketch = b.clone();
+                for v in a.iter() {
+                    sketch.insert(v);
+                }
+                *self = AggState::CountUniqueSketch { sketch };
+            }
+            (AggState::CountUniqueSketch { sketch: a }, AggState::CountUnique { values: b }) => {
+                for v in b {
+                    a.insert(v);
+                }
             }
+            (
+                AggState::CountUniqueSketch { sketch: a },
+                AggState::CountUniqueSketch { sketch: b },
+            ) => a.merge(b),
+            (AggState::Percentile { digest: a }, AggState::Percentile { digest: b }) => a.merge(b),
             (AggState::Sum { sum: a }, AggState::Sum { sum: b }) => *a += *b,
             (AggState::Avg { sum: a1, count: c1 }, AggState::Avg { sum: a2, count: c2 }) => {
                 *a1 += *a2;
@@ -120,10 +153,16 @@ pub struct AggPartial {
 impl AggPartial {
     pub fn merge(&mut self, other: &AggPartial) {
         for (k, v) in &other.groups {
-            let entry = self.groups.entry(k.clone()).or_insert_with(|| v.clone());
-            if entry.len() == v.len() {
-                for (a, b) in entry.iter_mut().zip(v.iter()) {
-                    a.merge(b);
+            match self.groups.get_mut(k) {
+                Some(entry) => {
+                    if entry.len() == v.len() {
+                        for (a, b) in entry.iter_mut().zip(v.iter()) {
+                            a.merge(b);
+                        }
+                    }
+                }
+                None => {
+                    self.groups.insert(k.clone(), v.clone());
                 }
             }
         }
@@ -141,8 +180,13 @@ pub fn snapshot_aggregator(agg: &AggregatorImpl) -> AggState {
                 AggState::CountAll { count: 0 }
             }
         }
-        AggregatorImpl::CountUnique(a) => AggState::CountUnique {
-            values: a.values().clone(),
+        AggregatorImpl::CountUnique(a) => match a.sketch() {
+            Some(sketch) => AggState::CountUniqueSketch {
+                sketch: sketch.clone(),
+            },
+            None => AggState::CountUnique {
+                values: a.values().iter().cloned().collect(),
+            },
         },
         AggregatorImpl::CountField(a) => {
             // finalize gives Count(i64); represent as CountAll { count }
@@ -152,6 +196,9 @@ pub fn snapshot_aggregator(agg: &AggregatorImpl) -> AggState {
                 AggState::CountAll { count: 0 }
             }
         }
+        AggregatorImpl::Percentile(a) => AggState::Percentile {
+            digest: a.digest().clone(),
+        },
         AggregatorImpl::Sum(a) => {
             if let AggOutput::Sum(s) = a.finalize() {
                 AggState::Sum { sum: s }
@@ -361,7 +361,7 @@ fn agg_partial_merge_merges_matching_keys_and_states() {
 
     let key = GroupKey {
         bucket: Some(1),
-        groups: vec!["US".into()],
+        groups: vec![Some("US".into())],
     };
 
     let mut left = AggPartial {
@@ -515,6 +515,36 @@ fn agg_partial_merge_ignores_mismatched_state_lengths() {
     assert_eq!(merged, &vec![AggState::CountAll { count: 1 }]);
 }
 
+#[test]
+fn agg_partial_merge_adds_missing_groups_once() {
+    let specs = vec![AggregateOpSpec::CountAll];
+    let us = GroupKey {
+        bucket: None,
+        groups: vec![Some("US".into())],
+    };
+    let de = GroupKey {
+        bucket: None,
+        groups: vec![Some("DE".into())],
+    };
+
+    let mut left = AggPartial {
+        specs: specs.clone(),
+        group_by: Some(vec!["country".into()]),
+        time_bucket: None,
+        groups: HashMap::from([(us.clone(), vec![AggState::CountAll { count: 1 }])]),
+    };
+    let right = AggPartial {
+        specs,
+        group_by: Some(vec!["country".into()]),
+        time_bucket: None,
+        groups: HashMap::from([(de.clone(), vec![AggState::CountAll { count: 4 }])]),
+    };
+
+    left.merge(&right);
+    assert_eq!(left.groups[&us], vec![AggState::CountAll { count: 1 }]);
+    assert_eq!(left.groups[&de], vec![AggState::CountAll { count: 4 }]);
+}
+
 // Typed column tests for snapshot_aggregator ------------------------------
 
 #[test]
@@ -713,7 +743,7 @@ fn agg_partial_merge_countfield_typed_columns() {
 
     let key = GroupKey {
         bucket: None,
-        groups: vec!["NL".into()],
+        groups: vec![Some("NL".into())],
     };
 
     // Left partial: 2 non-null values
@@ -765,7 +795,7 @@ fn agg_partial_merge_sum_typed_columns() {
 
     let key = GroupKey {
         bucket: None,
-        groups: vec!["US".into()],
+        groups: vec![Some("US".into())],
     };
 
     let mut left_agg = AggregatorImpl::from_spec(&AggregateOpSpec::Total {
@@ -814,7 +844,7 @@ fn agg_partial_merge_avg_typed_columns() {
 
     let key = GroupKey {
         bucket: None,
-        groups: vec!["EU".into()],
+        groups: vec![Some("EU".into())],
     };
 
     let mut left_agg = AggregatorImpl::from_spec(&AggregateOpSpec::Avg {
@@ -873,7 +903,7 @@ fn agg_partial_merge_min_max_typed_columns() {
 
     let key = GroupKey {
         bucket: None,
-        groups: vec!["A".into()],
+        groups: vec![Some("A".into())],
     };
 
     // Left: min=5, max=10
@@ -942,3 +972,52 @@ fn agg_partial_merge_min_max_typed_columns() {
         }
     );
 }
+
+fn distinct_values(range: std::ops::Range<usize>) -> HashSet<String> {
+    range.map(|i| format!("user-{}", i)).collect()
+}
+
+#[test]
+fn count_unique_state_switches_to_sketch_when_union_exceeds_exact_limit() {
+    use crate::engine::core::read::aggregate::hll::DEFAULT_COUNT_UNIQUE_EXACT_LIMIT;
+
+    let half = DEFAULT_COUNT_UNIQUE_EXACT_LIMIT / 2 + 1;
+    let mut a = AggState::CountUnique {
+        values: distinct_values(0..half),
+    };
+    a.merge(&AggState::CountUnique {
+        values: distinct_values(half..half * 2),
+    });
+    match a {
+        AggState::CountUniqueSketch { sketch } => {
+            let n = sketch.estimate() as f64;
+            assert!((n - (half * 2) as f64).abs() / ((half * 2) as f64) < 0.03);
+        }
+        other => panic!("expected sketch state, got {:?}", other),
+    }
+}
+
+#[test]
+fn count_unique_state_merges_mixed_exact_and_sketch_in_either_order() {
+    use crate::engine::core::read::aggregate::hll::HllSketch;
+
+    let exact = AggState::CountUnique {
+        values: distinct_values(0..2_000),
+    };
+    let sketch = AggState::CountUniqueSketch {
+        sketch: HllSketch::from_values(&distinct_values(1_000..5_000)),
+    };
+
+    let mut left = exact.clone();
+    left.merge(&sketch);
+    let mut right = sketch.clone();
+    right.merge(&exact);
+    assert_eq!(left, right);
+    match left {
+        AggState::CountUniqueSketch { sketch } => {
+            let n = sketch.estimate();
+            assert!((4_850..=5_150).contains(&n), "got {}", n);
+        }
+        other => panic!("expected sketch state, got {:?}", other),
+    }
+}
@@ -1,7 +1,8 @@
 use crate::command::types::{AggSpec, Command, QueryCommand, TimeGranularity};
+use serde::{Deserialize, Serialize};
 
 /// Describes a single aggregate operation requested by the query
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AggregateOpSpec {
     /// COUNT of all matching rows
     CountAll,
@@ -9,6 +10,10 @@ pub enum AggregateOpSpec {
     CountField { field: String },
     /// COUNT UNIQUE over a specific field
     CountUnique { field: String },
+    /// COUNT(DISTINCT field), estimated with a HyperLogLog sketch
+    CountDistinct { field: String },
+    /// PERCENTILE over a numeric field, estimated with a t-digest
+    Percentile { field: String, percentile: f64 },
     /// SUM over a numeric field
     Total { field: String },
     /// AVG over a numeric field
@@ -19,6 +24,12 @@ pub enum AggregateOpSpec {
     Max { field: String },
 }
 
+/// Output column name of a percentile: `p95_<field>`, or `p99_9_<field>` for
+/// fractional percentiles.
+pub fn percentile_column(field: &str, percentile: f64) -> String {
+    format!("p{}_{}", percentile.to_string().replace('.', "_"), field)
+}
+
 /// Aggregate plan derived from the Query command
 #[derive(Debug, Clone, PartialEq)]
 pub struct AggregatePlan {
@@ -48,9 +59,18 @@ impl AggregatePlan {
                         Some(f) => ops.push(AggregateOpSpec::CountUnique { field: f.clone() }),
                         None => ops.push(AggregateOpSpec::CountAll),
                     },
+                    AggSpec::CountDistinct { field } => ops.push(AggregateOpSpec::CountDistinct {
+                        field: field.clone(),
+                    }),
                     AggSpec::CountField { field } => ops.push(AggregateOpSpec::CountField {
                         field: field.clone(),
                     }),
+                    AggSpec::Percentile { field, percentile } => {
+                        ops.push(AggregateOpSpec::Percentile {
+                            field: field.clone(),
+                            percentile: *percentile,
+                        })
+                    }
                     AggSpec::Total { field } => ops.push(AggregateOpSpec::Total {
                         field: field.clone(),
                     }),
@@ -89,9 +109,18 @@ impl AggregatePlan {
                     Some(f) => ops.push(AggregateOpSpec::CountUnique { field: f.clone() }),
                     None => ops.push(AggregateOpSpec::CountAll),
                 },
+                AggSpec::CountDistinct { field } => ops.push(AggregateOpSpec::CountDistinct {
+                    field: field.clone(),
+                }),
                 AggSpec::CountField { field } => ops.push(AggregateOpSpec::CountField {
                     field: field.clone(),
                 }),
+                AggSpec::Percentile { field, percentile } => {
+                    ops.push(AggregateOpSpec::Percentile {
+                        field: field.clone(),
+                        percentile: *percentile,
+                    })
+                }
                 AggSpec::Total { field } => ops.push(AggregateOpSpec::Total {
                     field: field.clone(),
                 }),
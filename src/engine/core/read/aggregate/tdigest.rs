@@ -0,0 +1,185 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// Compression of new digests: about how many centroids a digest keeps once
+/// compressed, trading memory for accuracy.
+pub const DEFAULT_COMPRESSION: f64 = 100.0;
+
+/// Merging t-digest of numeric values, used by `PERCENTILE`.
+///
+/// Values are buffered, then folded into weighted centroids that stay small
+/// near the tails and grow towards the median, so memory is bounded however
+/// many values a group sees while high percentiles stay accurate. Digests
+/// built on different shards merge by folding their centroids together.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TDigest {
+    compression: f64,
+    /// Compressed `(mean, weight)` centroids, ordered by mean
+    centroids: Vec<(f64, f64)>,
+    /// Values and centroids not folded in yet
+    unmerged: Vec<(f64, f64)>,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TDigest {
+    pub fn new() -> Self {
+        Self::with_compression(DEFAULT_COMPRESSION)
+    }
+
+    pub fn with_compression(compression: f64) -> Self {
+        Self {
+            compression: compression.max(10.0),
+            centroids: Vec::new(),
+            unmerged: Vec::new(),
+            count: 0,
+            min: 0.0,
+            max: 0.0,
+        }
+    }
+
+    /// Number of values the digest has seen.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Adds a value; non-finite values are ignored.
+    pub fn insert(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        self.observe(value, value, 1);
+        self.unmerged.push((value, 1.0));
+        if self.unmerged.len() >= self.buffer_limit() {
+            self.compress();
+        }
+    }
+
+    pub fn merge(&mut self, other: &TDigest) {
+        if other.count == 0 {
+            return;
+        }
+        self.observe(other.min, other.max, other.count);
+        self.unmerged.extend_from_slice(&other.centroids);
+        self.unmerged.extend_from_slice(&other.unmerged);
+        if self.unmerged.len() >= self.buffer_limit() {
+            self.compress();
+        }
+    }
+
+    fn observe(&mut self, min: f64, max: f64, count: u64) {
+        if self.count == 0 {
+            self.min = min;
+            self.max = max;
+        } else {
+            self.min = self.min.min(min);
+            self.max = self.max.max(max);
+        }
+        self.count += count;
+    }
+
+    fn buffer_limit(&self) -> usize {
+        self.compression as usize * 5
+    }
+
+    /// Folds buffered values into the centroids.
+    pub fn compress(&mut self) {
+        if self.unmerged.is_empty() {
+            return;
+        }
+        let mut all = std::mem::take(&mut self.centroids);
+        all.append(&mut self.unmerged);
+        self.centroids = fold_centroids(all, self.count as f64, self.compression);
+    }
+
+    /// Value at quantile `q` (0.0..=1.0), interpolated linearly between the
+    /// centroids around it; `None` when the digest is empty. With every value
+    /// still in its own centroid this matches linear interpolation between
+    /// the sorted values.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        let centroids = if self.unmerged.is_empty() {
+            Cow::Borrowed(&self.centroids)
+        } else {
+            let mut all = self.centroids.clone();
+            all.extend_from_slice(&self.unmerged);
+            Cow::Owned(fold_centroids(all, self.count as f64, self.compression))
+        };
+
+        // Centroid centers sit at the rank of their middle value, with ranks
+        // running from 0 (min) to count - 1 (max)
+        let last = (self.count - 1) as f64;
+        let target = q.clamp(0.0, 1.0) * last;
+        let mut before = 0.0;
+        let mut prev = (0.0, self.min);
+        for &(mean, weight) in centroids.iter() {
+            let center = (before + (weight - 1.0) / 2.0, mean);
+            if target <= center.0 {
+                return Some(interpolate(prev, center, target).clamp(self.min, self.max));
+            }
+            prev = center;
+            before += weight;
+        }
+        Some(interpolate(prev, (last, self.max), target).clamp(self.min, self.max))
+    }
+
+    pub fn heap_bytes(&self) -> usize {
+        (self.centroids.capacity() + self.unmerged.capacity()) * std::mem::size_of::<(f64, f64)>()
+    }
+
+    /// JSON encoding of the compressed digest, used in partial aggregate batches.
+    pub fn to_json(&self) -> String {
+        let mut digest = self.clone();
+        digest.compress();
+        serde_json::to_string(&digest).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("invalid digest: {}", e))
+    }
+}
+
+/// Sorts `centroids` and merges neighbours while the result stays within the
+/// size bound for its quantile, which shrinks towards both tails.
+fn fold_centroids(mut centroids: Vec<(f64, f64)>, total: f64, compression: f64) -> Vec<(f64, f64)> {
+    centroids.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut out = Vec::with_capacity(compression as usize * 2);
+    let mut iter = centroids.into_iter();
+    let Some(mut current) = iter.next() else {
+        return out;
+    };
+    let mut before = 0.0;
+    for next in iter {
+        let weight = current.1 + next.1;
+        let q0 = before / total;
+        let q2 = (before + weight) / total;
+        let limit = total * (q0 * (1.0 - q0)).min(q2 * (1.0 - q2)) * 4.0 / compression;
+        if weight <= limit {
+            current.0 += (next.0 - current.0) * next.1 / weight;
+            current.1 = weight;
+        } else {
+            before += current.1;
+            out.push(current);
+            current = next;
+        }
+    }
+    out.push(current);
+    out
+}
+
+fn interpolate(a: (f64, f64), b: (f64, f64), at: f64) -> f64 {
+    if b.0 <= a.0 {
+        return b.1;
+    }
+    a.1 + (b.1 - a.1) * (at - a.0) / (b.0 - a.0)
+}
@@ -0,0 +1,73 @@
+use crate::engine::core::read::aggregate::tdigest::TDigest;
+
+fn digest_of(values: impl IntoIterator<Item = f64>) -> TDigest {
+    let mut digest = TDigest::new();
+    for v in values {
+        digest.insert(v);
+    }
+    digest
+}
+
+fn assert_near(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{} is not within {} of {}",
+        actual,
+        tolerance,
+        expected
+    );
+}
+
+#[test]
+fn empty_and_single_value_digests() {
+    let empty = TDigest::new();
+    assert_eq!(empty.quantile(0.5), None);
+
+    let single = digest_of([42.0]);
+    assert_eq!(single.quantile(0.0), Some(42.0));
+    assert_eq!(single.quantile(0.5), Some(42.0));
+    assert_eq!(single.quantile(1.0), Some(42.0));
+}
+
+#[test]
+fn small_digests_interpolate_between_values() {
+    let digest = digest_of([4.0, 1.0, 3.0, 2.0]);
+    assert_eq!(digest.quantile(0.0), Some(1.0));
+    assert_eq!(digest.quantile(0.5), Some(2.5));
+    assert_near(digest.quantile(0.95).unwrap(), 3.85, 1e-9);
+    assert_eq!(digest.quantile(1.0), Some(4.0));
+}
+
+#[test]
+fn large_digests_stay_bounded_and_accurate() {
+    // 0..100_000 shuffled deterministically
+    let digest = digest_of((0..100_000u64).map(|i| ((i * 7_919) % 100_000) as f64));
+    assert_eq!(digest.count(), 100_000);
+    assert!(digest.heap_bytes() < 64 * 1024, "{}", digest.heap_bytes());
+
+    assert_near(digest.quantile(0.5).unwrap(), 50_000.0, 500.0);
+    assert_near(digest.quantile(0.95).unwrap(), 95_000.0, 200.0);
+    assert_near(digest.quantile(0.99).unwrap(), 99_000.0, 50.0);
+    assert_eq!(digest.quantile(1.0), Some(99_999.0));
+}
+
+#[test]
+fn merged_digests_match_one_digest_of_all_values() {
+    let mut a = digest_of((0..30_000).map(|i| i as f64));
+    let b = digest_of((30_000..60_000).map(|i| i as f64));
+    a.merge(&b);
+    a.merge(&TDigest::new());
+    assert_eq!(a.count(), 60_000);
+    assert_near(a.quantile(0.5).unwrap(), 30_000.0, 300.0);
+    assert_near(a.quantile(0.99).unwrap(), 59_400.0, 60.0);
+    assert_eq!(a.quantile(0.0), Some(0.0));
+}
+
+#[test]
+fn json_round_trip_keeps_quantiles() {
+    let digest = digest_of((0..5_000).map(|i| (i % 250) as f64));
+    let decoded = TDigest::from_json(&digest.to_json()).unwrap();
+    assert_eq!(decoded.count(), digest.count());
+    assert_eq!(decoded.quantile(0.9), digest.quantile(0.9));
+    assert!(TDigest::from_json("{}").is_err());
+}
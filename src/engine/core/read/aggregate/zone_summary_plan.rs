@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::command::types::{Command, CompareOp, Expr, TimeGranularity};
+use crate::engine::core::read::aggregate::partial::{AggPartial, AggState, GroupKey};
+use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::engine::core::read::event_scope::EventScope;
+use crate::engine::core::read::sink::bucket_of;
+use crate::engine::core::zone::enum_bitmap_index::EnumBitmapIndex;
+use crate::engine::core::zone::zone_summary::ZoneSummaryIndex;
+use crate::engine::core::{CandidateZone, QueryCaches, QueryPlan, ZoneMeta};
+use tracing::{debug, info};
+
+/// Partial aggregate of the zones answered from their summaries, handed from the
+/// segment scan to the aggregate operator of the same shard.
+pub type ZoneSummarySlot = Arc<Mutex<Option<AggPartial>>>;
+
+/// Decides which candidate zones of an aggregate query can be answered from the
+/// `.zsum` summaries written at flush instead of hydrating their columns.
+///
+/// Only queries whose predicates can be decided per zone qualify: a WHERE clause
+/// made only of `timestamp` range comparisons joined by AND, and no GROUP BY
+/// (group keys vary within a zone) except a COUNT-only GROUP BY on one field,
+/// whose per-zone counts come from the field's `.ebm` enum bitmap index. A zone
+/// is summarized when its whole time range satisfies the predicate and, with a
+/// time bucket, falls in one bucket; every other zone is scanned as usual.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneSummaryPlan {
+    specs: Vec<AggregateOpSpec>,
+    /// Group field counted from its enum bitmap index instead of zone summaries
+    enum_group: Option<String>,
+    time_bucket: Option<TimeGranularity>,
+    /// Inclusive timestamp bounds implied by the WHERE clause
+    ts_min: u64,
+    ts_max: u64,
+}
+
+impl ZoneSummaryPlan {
+    pub fn from_plan(plan: &QueryPlan) -> Option<Self> {
+        let aggregate = plan.aggregate_plan.as_ref()?;
+        let enum_group = match aggregate.group_by.as_deref() {
+            None => None,
+            Some([field])
+                if aggregate
+                    .ops
+                    .iter()
+                    .all(|op| matches!(op, AggregateOpSpec::CountAll)) =>
+            {
+                Some(field.clone())
+            }
+            Some(_) => return None,
+        };
+        if aggregate.ops.iter().any(|op| {
+            matches!(
+                op,
+                AggregateOpSpec::CountUnique { .. }
+                    | AggregateOpSpec::CountDistinct { .. }
+                    | AggregateOpSpec::Percentile { .. }
+            )
+        }) {
+            return None;
+        }
+        let Command::Query {
+            context_id,
+            since,
+            time_field,
+            where_clause,
+            link_field,
+            event_sequence,
+            latest_per,
+            ..
+        } = &plan.command
+        else {
+            return None;
+        };
+        if context_id.is_some()
+            || since.is_some()
+            || link_field.is_some()
+            || event_sequence.is_some()
+            || latest_per.is_some()
+            || time_field.as_deref().is_some_and(|f| f != "timestamp")
+        {
+            return None;
+        }
+        if !matches!(
+            plan.event_scope(),
+            EventScope::Specific { uid: Some(_), .. }
+        ) {
+            return None;
+        }
+
+        let (mut ts_min, mut ts_max) = (0, u64::MAX);
+        if let Some(expr) = where_clause {
+            Self::narrow(expr, &mut ts_min, &mut ts_max)?;
+        }
+        Some(Self {
+            specs: aggregate.ops.clone(),
+            enum_group,
+            time_bucket: aggregate.time_bucket.clone(),
+            ts_min,
+            ts_max,
+        })
+    }
+
+    /// Tightens the bounds by `expr`; `None` when it is not a conjunction of timestamp ranges.
+    fn narrow(expr: &Expr, ts_min: &mut u64, ts_max: &mut u64) -> Option<()> {
+        match expr {
+            Expr::And(left, right) => {
+                Self::narrow(left, ts_min, ts_max)?;
+                Self::narrow(right, ts_min, ts_max)
+            }
+            Expr::Compare { field, op, value } if field == "timestamp" => {
+                let v = value.as_u64()?;
+                match op {
+                    CompareOp::Gt => *ts_min = (*ts_min).max(v.checked_add(1)?),
+                    CompareOp::Gte => *ts_min = (*ts_min).max(v),
+                    CompareOp::Lt => *ts_max = (*ts_max).min(v.checked_sub(1)?),
+                    CompareOp::Lte => *ts_max = (*ts_max).min(v),
+                    CompareOp::Eq => {
+                        *ts_min = (*ts_min).max(v);
+                        *ts_max = (*ts_max).min(v);
+                    }
+                    CompareOp::Neq | CompareOp::In => return None,
+                }
+                Some(())
+            }
+            _ => None,
+        }
+    }
+
+    /// Group a zone spanning `[min, max]` contributes to, if it contributes to a single one.
+    fn group_key(&self, meta: &ZoneMeta) -> Option<GroupKey> {
+        if meta.timestamp_min < self.ts_min || meta.timestamp_max > self.ts_max {
+            return None;
+        }
+        let bucket = match &self.time_bucket {
+            Some(gran) => {
+                let bucket = bucket_of(meta.timestamp_min, gran);
+                if bucket_of(meta.timestamp_max, gran) != bucket {
+                    return None;
+                }
+                Some(bucket)
+            }
+            None => None,
+        };
+        Some(GroupKey {
+            bucket,
+            groups: Vec::new(),
+        })
+    }
+
+    /// Removes the zones answerable from summaries and returns their combined partial.
+    /// Zones of segments without a usable `.zsum` stay in `zones` and are scanned.
+    pub fn apply(
+        &self,
+        zones: &mut Vec<CandidateZone>,
+        base_dir: &Path,
+        uid: &str,
+        caches: Option<&QueryCaches>,
+    ) -> Option<AggPartial> {
+        let mut partial = AggPartial {
+            specs: self.specs.clone(),
+            group_by: None,
+            time_bucket: self.time_bucket.clone(),
+            groups: HashMap::new(),
+        };
+        let mut segments: HashMap<String, Option<LoadedSegment>> = HashMap::new();
+        let before = zones.len();
+
+        zones.retain(|zone| {
+            let segment = segments
+                .entry(zone.segment_id.clone())
+                .or_insert_with(|| self.load_segment(&zone.segment_id, base_dir, uid, caches));
+            let Some((metas, summaries)) = segment else {
+                return true;
+            };
+            let Some(meta) = metas.iter().find(|m| m.zone_id == zone.zone_id) else {
+                return true;
+            };
+            let Some(key) = self.group_key(meta) else {
+                return true;
+            };
+
+            match summaries {
+                SegmentSummaries::Zone { index, positions } => {
+                    let Some(states) = index.zone(zone.zone_id) else {
+                        return true;
+                    };
+                    fold_group(
+                        &mut partial.groups,
+                        key,
+                        positions.iter().map(|&i| &states[i]),
+                    );
+                }
+                SegmentSummaries::EnumBitmap(index) => {
+                    let Some(counts) = index.zone_counts(zone.zone_id) else {
+                        return true;
+                    };
+                    // Rows holding a value outside the variants appear in no bitmap
+                    let rows = u64::from(meta.end_row - meta.start_row) + 1;
+                    if counts.iter().sum::<u64>() != rows {
+                        return true;
+                    }
+                    for (variant, count) in index.variants.iter().zip(counts) {
+                        if count == 0 {
+                            continue;
+                        }
+                        let states = vec![
+                            AggState::CountAll {
+                                count: count as i64
+                            };
+                            self.specs.len()
+                        ];
+                        let key = GroupKey {
+                            bucket: key.bucket,
+                            groups: vec![Some(variant.clone())],
+                        };
+                        fold_group(&mut partial.groups, key, states.iter());
+                    }
+                }
+            }
+            false
+        });
+
+        let summarized = before - zones.len();
+        if tracing::enabled!(tracing::Level::INFO) {
+            info!(
+                target: "sneldb::zone_summary",
+                summarized,
+                scanned = zones.len(),
+                source = if self.enum_group.is_some() { "enum_index" } else { "zone_summary" },
+                "Answered zones from zone summaries"
+            );
+        }
+        (summarized > 0).then_some(partial)
+    }
+
+    fn load_segment(
+        &self,
+        segment_id: &str,
+        base_dir: &Path,
+        uid: &str,
+        caches: Option<&QueryCaches>,
+    ) -> Option<LoadedSegment> {
+        let segment_dir = base_dir.join(segment_id);
+        let summaries = match &self.enum_group {
+            Some(field) => {
+                let index = match caches {
+                    Some(caches) => caches.get_or_load_enum(segment_id, uid, field),
+                    None => {
+                        EnumBitmapIndex::load(&segment_dir.join(format!("{}_{}.ebm", uid, field)))
+                            .map(Arc::new)
+                    }
+                };
+                // Fields without an enum index are grouped by scanning their column
+                let Ok(index) = index else {
+                    if tracing::enabled!(tracing::Level::DEBUG) {
+                        debug!(target: "sneldb::zone_summary", %segment_id, %field, "No enum index for group field");
+                    }
+                    return None;
+                };
+                SegmentSummaries::EnumBitmap(index)
+            }
+            None => {
+                let index = ZoneSummaryIndex::load(uid, &segment_dir).ok()?;
+                let Some(positions) = index.positions(&self.specs) else {
+                    if tracing::enabled!(tracing::Level::DEBUG) {
+                        debug!(target: "sneldb::zone_summary", %segment_id, "Zone summaries lack requested aggregates");
+                    }
+                    return None;
+                };
+                SegmentSummaries::Zone { index, positions }
+            }
+        };
+        let metas = match caches {
+            Some(caches) => caches.get_or_load_zone_meta(segment_id, uid).ok()?,
+            None => Arc::new(ZoneMeta::load(&segment_dir.join(format!("{}.zones", uid))).ok()?),
+        };
+        Some((metas, summaries))
+    }
+}
+
+/// Zone metadata of a segment and what it answers zones from.
+type LoadedSegment = (Arc<Vec<ZoneMeta>>, SegmentSummaries);
+
+/// What a segment answers zones from.
+enum SegmentSummaries {
+    /// `.zsum` summaries and the positions of the query's specs in them
+    Zone {
+        index: ZoneSummaryIndex,
+        positions: Vec<usize>,
+    },
+    /// Per-variant row counts of the GROUP BY field
+    EnumBitmap(Arc<EnumBitmapIndex>),
+}
+
+/// Adds the zones answered from summaries to the partial aggregate of the scanned rows.
+pub fn absorb_summarized(partial: &mut AggPartial, summarized: AggPartial) {
+    for (key, states) in summarized.groups {
+        fold_group(&mut partial.groups, key, states.iter());
+    }
+}
+
+fn fold_group<'s>(
+    groups: &mut HashMap<GroupKey, Vec<AggState>>,
+    key: GroupKey,
+    states: impl Iterator<Item = &'s AggState>,
+) {
+    match groups.get_mut(&key) {
+        Some(acc) => acc.iter_mut().zip(states).for_each(|(a, b)| a.merge(b)),
+        None => {
+            groups.insert(key, states.cloned().collect());
+        }
+    }
+}
@@ -0,0 +1,528 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::command::types::{Command, CompareOp, Expr, TimeGranularity};
+use crate::engine::core::read::aggregate::partial::{AggState, GroupKey};
+use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::engine::core::read::aggregate::zone_summary_plan::ZoneSummaryPlan;
+use crate::engine::core::read::cache::QueryCaches;
+use crate::engine::core::read::flow::shard_pipeline::build_segment_stream;
+use crate::engine::core::read::flow::{BatchPool, FlowContext, FlowMetrics, FlowTelemetry};
+use crate::engine::core::{CandidateZone, QueryPlan, ZonePlan, ZoneWriter};
+use crate::engine::schema::registry::SchemaRegistry;
+use crate::engine::schema::{EnumType, FieldType};
+use crate::engine::types::ScalarValue;
+use crate::test_helpers::factories::{
+    CommandFactory, EventFactory, QueryPlanFactory, SchemaRegistryFactory,
+};
+use tokio::sync::RwLock;
+
+const SEGMENT: &str = "00001";
+
+fn ts(op: CompareOp, value: u64) -> Expr {
+    Expr::Compare {
+        field: "timestamp".into(),
+        op,
+        value: json!(value),
+    }
+}
+
+fn and(left: Expr, right: Expr) -> Expr {
+    Expr::And(Box::new(left), Box::new(right))
+}
+
+async fn registry() -> (Arc<RwLock<SchemaRegistry>>, String) {
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("order", &[("context_id", "string"), ("amount", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let uid = registry.read().await.get_uid("order").unwrap();
+    (registry, uid)
+}
+
+const TIMESTAMPS: [u64; 6] = [10, 20, 30, 40, 50, 60];
+/// Zone (3595, 3610) crosses an hour boundary
+const HOUR_CROSSING: [u64; 6] = [3500, 3590, 3595, 3610, 3700, 3800];
+
+/// Writes one segment of two events per zone with amounts equal to the
+/// timestamps; summaries cover COUNT, SUM and MAX of amount.
+async fn write_segment(
+    base: &Path,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    uid: &str,
+    timestamps: &[u64],
+) {
+    let events: Vec<_> = timestamps
+        .iter()
+        .map(|&t| {
+            EventFactory::new()
+                .with("event_type", "order")
+                .with("timestamp", t)
+                .with("payload", json!({ "amount": t }))
+                .create()
+        })
+        .collect();
+    let plans = ZonePlan::build_all(&events, 2, uid.to_string(), 1).unwrap();
+    let segment_dir = base.join(SEGMENT);
+    std::fs::create_dir_all(&segment_dir).unwrap();
+    ZoneWriter::new(uid, &segment_dir, Arc::clone(registry))
+        .with_zone_summaries(vec![
+            AggregateOpSpec::Total {
+                field: "amount".into(),
+            },
+            AggregateOpSpec::Max {
+                field: "amount".into(),
+            },
+        ])
+        .write_all(&plans)
+        .await
+        .unwrap();
+}
+
+async fn plan_for(
+    command: Command,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    base: &Path,
+) -> QueryPlan {
+    QueryPlanFactory::new()
+        .with_command(command)
+        .with_registry(Arc::clone(registry))
+        .with_segment_base_dir(base)
+        .with_segment_ids(vec![SEGMENT.into()])
+        .create()
+        .await
+}
+
+fn all_zones() -> Vec<CandidateZone> {
+    (0..3)
+        .map(|id| CandidateZone::new(id, SEGMENT.to_string()))
+        .collect()
+}
+
+fn remaining(zones: &[CandidateZone]) -> Vec<u32> {
+    zones.iter().map(|z| z.zone_id).collect()
+}
+
+#[tokio::test]
+async fn only_zone_decidable_queries_qualify() {
+    let (registry, _) = registry().await;
+    let base = tempfile::tempdir().unwrap();
+    let sum = || {
+        CommandFactory::query()
+            .with_event_type("order")
+            .add_total("amount")
+    };
+
+    let qualifying = [
+        sum().create(),
+        sum().with_where_clause(ts(CompareOp::Gte, 30)).create(),
+        sum()
+            .with_where_clause(and(ts(CompareOp::Gt, 10), ts(CompareOp::Lte, 40)))
+            .with_time_bucket(TimeGranularity::Hour)
+            .create(),
+    ];
+    for command in qualifying {
+        let plan = plan_for(command, &registry, base.path()).await;
+        assert!(
+            ZoneSummaryPlan::from_plan(&plan).is_some(),
+            "{:?}",
+            plan.command
+        );
+    }
+
+    let amount_filter = Expr::Compare {
+        field: "amount".into(),
+        op: CompareOp::Gt,
+        value: json!(5),
+    };
+    let rejected = [
+        // GROUP BY keys vary within a zone
+        sum().with_group_by(vec!["context_id"]).create(),
+        // A predicate on a payload field can only be decided row by row
+        sum().with_where_clause(amount_filter.clone()).create(),
+        sum()
+            .with_where_clause(and(ts(CompareOp::Gte, 30), amount_filter))
+            .create(),
+        sum()
+            .with_where_clause(Expr::Or(
+                Box::new(ts(CompareOp::Lt, 20)),
+                Box::new(ts(CompareOp::Gt, 40)),
+            ))
+            .create(),
+        sum().with_where_clause(ts(CompareOp::Neq, 30)).create(),
+        sum().with_context_id("ctx1").create(),
+        sum().with_time_field("created_at").create(),
+        CommandFactory::query()
+            .with_event_type("order")
+            .add_count_unique("amount")
+            .create(),
+        // Not an aggregate
+        CommandFactory::query().with_event_type("order").create(),
+    ];
+    for command in rejected {
+        let plan = plan_for(command, &registry, base.path()).await;
+        assert!(
+            ZoneSummaryPlan::from_plan(&plan).is_none(),
+            "{:?}",
+            plan.command
+        );
+    }
+}
+
+#[tokio::test]
+async fn apply_summarizes_zones_fully_inside_the_time_range() {
+    let (registry, uid) = registry().await;
+    let base = tempfile::tempdir().unwrap();
+    write_segment(base.path(), &registry, &uid, &TIMESTAMPS).await;
+
+    // Zone (10, 20) straddles the lower bound and must be scanned
+    let command = CommandFactory::query()
+        .with_event_type("order")
+        .add_count()
+        .add_total("amount")
+        .with_where_clause(ts(CompareOp::Gte, 15))
+        .create();
+    let plan = plan_for(command, &registry, base.path()).await;
+    let summary_plan = ZoneSummaryPlan::from_plan(&plan).unwrap();
+
+    let mut zones = all_zones();
+    let partial = summary_plan
+        .apply(&mut zones, base.path(), &uid, None)
+        .unwrap();
+
+    assert_eq!(remaining(&zones), vec![0]);
+    let key = GroupKey {
+        bucket: None,
+        groups: vec![],
+    };
+    assert_eq!(
+        partial.groups[&key],
+        vec![AggState::CountAll { count: 4 }, AggState::Sum { sum: 180 }]
+    );
+}
+
+#[tokio::test]
+async fn apply_scans_zones_spanning_several_time_buckets() {
+    let (registry, uid) = registry().await;
+    let base = tempfile::tempdir().unwrap();
+    write_segment(base.path(), &registry, &uid, &HOUR_CROSSING).await;
+
+    let command = CommandFactory::query()
+        .with_event_type("order")
+        .add_max("amount")
+        .with_time_bucket(TimeGranularity::Hour)
+        .create();
+    let plan = plan_for(command, &registry, base.path()).await;
+    let summary_plan = ZoneSummaryPlan::from_plan(&plan).unwrap();
+
+    let mut zones = all_zones();
+    let partial = summary_plan
+        .apply(&mut zones, base.path(), &uid, None)
+        .unwrap();
+
+    assert_eq!(remaining(&zones), vec![1]);
+    let mut maxima: Vec<_> = partial.groups.values().cloned().collect();
+    maxima.sort_by_key(|states| match states[0] {
+        AggState::Max { max_num, .. } => max_num,
+        _ => None,
+    });
+    assert_eq!(
+        maxima,
+        vec![
+            vec![AggState::Max {
+                max_num: Some(3590),
+                max_str: None
+            }],
+            vec![AggState::Max {
+                max_num: Some(3800),
+                max_str: None
+            }],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn apply_keeps_zones_when_summaries_lack_an_aggregate() {
+    let (registry, uid) = registry().await;
+    let base = tempfile::tempdir().unwrap();
+    write_segment(base.path(), &registry, &uid, &TIMESTAMPS).await;
+
+    // MIN was not summarized at flush
+    let command = CommandFactory::query()
+        .with_event_type("order")
+        .add_min("amount")
+        .create();
+    let plan = plan_for(command, &registry, base.path()).await;
+    let summary_plan = ZoneSummaryPlan::from_plan(&plan).unwrap();
+
+    let mut zones = all_zones();
+    assert!(
+        summary_plan
+            .apply(&mut zones, base.path(), &uid, None)
+            .is_none()
+    );
+    assert_eq!(remaining(&zones), vec![0, 1, 2]);
+}
+
+fn flow_context() -> Arc<FlowContext> {
+    Arc::new(FlowContext::new(
+        4,
+        BatchPool::new(4).unwrap(),
+        FlowMetrics::new(),
+        None::<&str>,
+        FlowTelemetry::default(),
+    ))
+}
+
+async fn run_aggregate(plan: QueryPlan, base: &Path) -> Vec<Vec<ScalarValue>> {
+    let caches = Arc::new(QueryCaches::new(base.to_path_buf()));
+    let handle = build_segment_stream(Arc::new(plan), flow_context(), caches, None)
+        .await
+        .unwrap()
+        .unwrap();
+    let (mut rx, schema, _tasks) = handle.into_parts();
+    let mut rows = Vec::new();
+    while let Some(batch) = rx.recv().await {
+        for row in 0..batch.len() {
+            rows.push(
+                (0..schema.column_count())
+                    .map(|col| batch.column(col).unwrap()[row].clone())
+                    .collect(),
+            );
+        }
+    }
+    rows
+}
+
+/// Runs the query with and without the segment's summaries and returns both results.
+async fn run_with_and_without_summaries(
+    command: impl Fn() -> Command,
+    timestamps: &[u64],
+) -> (Vec<Vec<ScalarValue>>, Vec<Vec<ScalarValue>>) {
+    let (registry, uid) = registry().await;
+    let base = tempfile::tempdir().unwrap();
+    write_segment(base.path(), &registry, &uid, timestamps).await;
+
+    let summarized = run_aggregate(
+        plan_for(command(), &registry, base.path()).await,
+        base.path(),
+    )
+    .await;
+    std::fs::remove_file(base.path().join(SEGMENT).join(format!("{}.zsum", uid))).unwrap();
+    let scanned = run_aggregate(
+        plan_for(command(), &registry, base.path()).await,
+        base.path(),
+    )
+    .await;
+    (summarized, scanned)
+}
+
+#[tokio::test]
+async fn summarized_results_match_a_full_scan() {
+    let (summarized, scanned) = run_with_and_without_summaries(
+        || {
+            CommandFactory::query()
+                .with_event_type("order")
+                .add_count()
+                .add_total("amount")
+                .add_max("amount")
+                .with_where_clause(ts(CompareOp::Gte, 15))
+                .create()
+        },
+        &TIMESTAMPS,
+    )
+    .await;
+
+    assert_eq!(summarized, scanned);
+    assert_eq!(
+        scanned,
+        vec![vec![
+            ScalarValue::from(json!(5)),
+            ScalarValue::from(json!(200)),
+            ScalarValue::from(json!(60)),
+        ]]
+    );
+}
+
+#[tokio::test]
+async fn bucketed_results_match_a_full_scan_when_zones_cross_buckets() {
+    let (mut summarized, mut scanned) = run_with_and_without_summaries(
+        || {
+            CommandFactory::query()
+                .with_event_type("order")
+                .add_count()
+                .add_total("amount")
+                .with_time_bucket(TimeGranularity::Hour)
+                .create()
+        },
+        &HOUR_CROSSING,
+    )
+    .await;
+
+    let by_bucket = |a: &Vec<ScalarValue>, b: &Vec<ScalarValue>| a[0].compare(&b[0]);
+    summarized.sort_by(by_bucket);
+    scanned.sort_by(by_bucket);
+    assert_eq!(scanned.len(), 2);
+    assert_eq!(summarized, scanned);
+}
+
+/// Plans of the events at `TIMESTAMPS`; "trial" is not a variant of the enum.
+const PLANS: [&str; 6] = ["free", "pro", "pro", "pro", "free", "trial"];
+
+/// Writes one segment of two `subscription` events per zone, indexed by an
+/// enum bitmap on `plan`.
+async fn write_enum_segment(base: &Path) -> (Arc<RwLock<SchemaRegistry>>, String) {
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_field_types(
+            "subscription",
+            &[
+                ("context_id", FieldType::String),
+                (
+                    "plan",
+                    FieldType::Enum(EnumType {
+                        variants: vec!["free".into(), "pro".into(), "premium".into()],
+                    }),
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let uid = registry.read().await.get_uid("subscription").unwrap();
+
+    let events: Vec<_> = TIMESTAMPS
+        .iter()
+        .zip(PLANS)
+        .map(|(&t, plan)| {
+            EventFactory::new()
+                .with("event_type", "subscription")
+                .with("timestamp", t)
+                .with("payload", json!({ "plan": plan }))
+                .create()
+        })
+        .collect();
+    let plans = ZonePlan::build_all(&events, 2, uid.clone(), 1).unwrap();
+    let segment_dir = base.join(SEGMENT);
+    std::fs::create_dir_all(&segment_dir).unwrap();
+    ZoneWriter::new(&uid, &segment_dir, Arc::clone(&registry))
+        .write_all(&plans)
+        .await
+        .unwrap();
+    assert!(segment_dir.join(format!("{}_plan.ebm", uid)).exists());
+    (registry, uid)
+}
+
+fn count_by_plan() -> Command {
+    CommandFactory::query()
+        .with_event_type("subscription")
+        .add_count()
+        .with_group_by(vec!["plan"])
+        .create()
+}
+
+#[tokio::test]
+async fn only_count_by_one_field_qualifies_for_grouping() {
+    let base = tempfile::tempdir().unwrap();
+    let (registry, _) = write_enum_segment(base.path()).await;
+
+    let plan = plan_for(count_by_plan(), &registry, base.path()).await;
+    assert!(ZoneSummaryPlan::from_plan(&plan).is_some());
+
+    let rejected = [
+        CommandFactory::query()
+            .with_event_type("subscription")
+            .add_count()
+            .add_total("amount")
+            .with_group_by(vec!["plan"])
+            .create(),
+        CommandFactory::query()
+            .with_event_type("subscription")
+            .add_count()
+            .with_group_by(vec!["plan", "context_id"])
+            .create(),
+    ];
+    for command in rejected {
+        let plan = plan_for(command, &registry, base.path()).await;
+        assert!(
+            ZoneSummaryPlan::from_plan(&plan).is_none(),
+            "{:?}",
+            plan.command
+        );
+    }
+}
+
+#[tokio::test]
+async fn apply_counts_groups_from_the_enum_index() {
+    let base = tempfile::tempdir().unwrap();
+    let (registry, uid) = write_enum_segment(base.path()).await;
+    let plan = plan_for(count_by_plan(), &registry, base.path()).await;
+    let summary_plan = ZoneSummaryPlan::from_plan(&plan).unwrap();
+
+    let mut zones = all_zones();
+    let partial = summary_plan
+        .apply(&mut zones, base.path(), &uid, None)
+        .unwrap();
+
+    // Zone 2 holds a value outside the variants, which no bitmap counts
+    assert_eq!(remaining(&zones), vec![2]);
+    let count_of = |plan: &str| {
+        partial.groups[&GroupKey {
+            bucket: None,
+            groups: vec![Some(plan.to_string())],
+        }]
+            .clone()
+    };
+    assert_eq!(partial.groups.len(), 2);
+    assert_eq!(count_of("free"), vec![AggState::CountAll { count: 1 }]);
+    assert_eq!(count_of("pro"), vec![AggState::CountAll { count: 3 }]);
+}
+
+#[tokio::test]
+async fn apply_scans_group_fields_without_an_enum_index() {
+    let base = tempfile::tempdir().unwrap();
+    let (registry, uid) = write_enum_segment(base.path()).await;
+    let command = CommandFactory::query()
+        .with_event_type("subscription")
+        .add_count()
+        .with_group_by(vec!["context_id"])
+        .create();
+    let plan = plan_for(command, &registry, base.path()).await;
+    let summary_plan = ZoneSummaryPlan::from_plan(&plan).unwrap();
+
+    let mut zones = all_zones();
+    assert!(
+        summary_plan
+            .apply(&mut zones, base.path(), &uid, None)
+            .is_none()
+    );
+    assert_eq!(remaining(&zones), vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn enum_grouped_counts_match_a_full_scan() {
+    let base = tempfile::tempdir().unwrap();
+    let (registry, uid) = write_enum_segment(base.path()).await;
+    let by_plan = |a: &Vec<ScalarValue>, b: &Vec<ScalarValue>| a[0].compare(&b[0]);
+
+    let mut indexed = run_aggregate(
+        plan_for(count_by_plan(), &registry, base.path()).await,
+        base.path(),
+    )
+    .await;
+    std::fs::remove_file(base.path().join(SEGMENT).join(format!("{}_plan.ebm", uid))).unwrap();
+    let mut scanned = run_aggregate(
+        plan_for(count_by_plan(), &registry, base.path()).await,
+        base.path(),
+    )
+    .await;
+
+    indexed.sort_by(by_plan);
+    scanned.sort_by(by_plan);
+    assert_eq!(scanned.len(), 3);
+    assert_eq!(indexed, scanned);
+}
@@ -0,0 +1,23 @@
+/// Counters of a cache, read from its atomics alone. Taking them never waits
+/// on the cache's locks, so they can be polled while queries are running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub misses: u64,
+    pub reloads: u64,
+    pub evictions: u64,
+    /// Bytes held; `None` for caches bounded by entry count only
+    pub current_bytes: Option<usize>,
+    pub capacity_bytes: Option<usize>,
+}
+
+impl CacheCounters {
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses + self.reloads;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::cache_counters::CacheCounters;
 use super::column_block_cache_key::ColumnBlockCacheKey;
 use super::column_block_cache_stats::ColumnBlockCacheStats;
 use super::decompressed_block::DecompressedBlock;
@@ -76,6 +77,18 @@ impl GlobalColumnBlockCache {
         }
     }
 
+    pub fn counters(&self) -> CacheCounters {
+        let stats = self.stats();
+        CacheCounters {
+            hits: stats.hits,
+            misses: stats.misses,
+            reloads: stats.reloads,
+            evictions: stats.evictions,
+            current_bytes: Some(stats.current_bytes),
+            capacity_bytes: Some(stats.capacity_bytes),
+        }
+    }
+
     pub fn invalidate_segment(&self, segment_label: &str) {
         if let Ok(mut guard) = self.inner.lock() {
             let keys: Vec<_> = guard
@@ -1,14 +1,34 @@
+use std::ops::Deref;
 use std::path::Path;
 use std::sync::Arc;
 
 use memmap2::{Mmap, MmapOptions};
 
+use super::pinned_segment::PinnedSegment;
 use crate::engine::core::column::compression::CompressedColumnIndex;
 use crate::shared::storage_header::{BinaryHeader, FileKind};
 
+/// Backing bytes of a `.col` file: mapped from disk, or a pinned in-memory copy.
+#[derive(Debug)]
+pub enum ColumnBytes {
+    Mapped(Mmap),
+    Pinned(Arc<[u8]>),
+}
+
+impl Deref for ColumnBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            ColumnBytes::Mapped(mmap) => mmap,
+            ColumnBytes::Pinned(bytes) => bytes,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ColumnHandle {
-    pub col_mmap: Arc<Mmap>,
+    pub col_bytes: Arc<ColumnBytes>,
     pub zfc_index: Arc<CompressedColumnIndex>,
     pub col_path: std::path::PathBuf,
 }
@@ -35,9 +55,40 @@ impl ColumnHandle {
         let mmap = unsafe { MmapOptions::new().map(&file)? };
 
         Ok(Self {
-            col_mmap: Arc::new(mmap),
+            col_bytes: Arc::new(ColumnBytes::Mapped(mmap)),
             zfc_index: Arc::new(zfc_index),
             col_path,
         })
     }
+
+    /// Builds a handle over a pinned segment's in-memory copy, without touching disk.
+    /// Returns `Ok(None)` when the segment holds no such column.
+    pub fn from_pinned(
+        pinned: &PinnedSegment,
+        uid: &str,
+        field: &str,
+    ) -> std::io::Result<Option<Self>> {
+        let col_name = format!("{}_{}.col", uid, field);
+        let (Some(zfc), Some(col)) = (
+            pinned.file(&format!("{}_{}.zfc", uid, field)),
+            pinned.file(&col_name),
+        ) else {
+            return Ok(None);
+        };
+
+        let zfc_index = CompressedColumnIndex::from_bytes(zfc).map_err(std::io::Error::other)?;
+        let header = BinaryHeader::read_from(&col[..])?;
+        if header.magic != FileKind::SegmentColumn.magic() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid magic for .col",
+            ));
+        }
+
+        Ok(Some(Self {
+            col_bytes: Arc::new(ColumnBytes::Pinned(Arc::clone(col))),
+            zfc_index: Arc::new(zfc_index),
+            col_path: pinned.segment_dir().join(col_name),
+        }))
+    }
 }
@@ -21,7 +21,7 @@ fn open_succeeds_with_minimal_files() {
         .write_minimal();
 
     let handle = ColumnHandle::open(&seg_dir, uid, field).expect("open handle");
-    assert!(handle.col_mmap.len() >= BinaryHeader::TOTAL_LEN);
+    assert!(handle.col_bytes.len() >= BinaryHeader::TOTAL_LEN);
     assert!(handle.zfc_index.entries.is_empty());
 }
 
@@ -0,0 +1,39 @@
+use std::path::{Path, PathBuf};
+
+/// One decoded value: the row of a zone in the column of one event type
+/// (`uid`) and field, in one segment directory.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ColumnValueCacheKey {
+    pub segment_dir: PathBuf,
+    pub uid: String,
+    pub field: String,
+    pub zone_id: u32,
+    pub row: usize,
+}
+
+impl ColumnValueCacheKey {
+    pub fn new(segment_dir: &Path, uid: &str, field: &str, zone_id: u32, row: usize) -> Self {
+        Self {
+            segment_dir: segment_dir.to_path_buf(),
+            uid: uid.to_string(),
+            field: field.to_string(),
+            zone_id,
+            row,
+        }
+    }
+
+    /// Whether the key belongs to a segment directory named `segment_label`.
+    pub fn in_segment(&self, segment_label: &str) -> bool {
+        self.segment_dir
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy() == segment_label)
+    }
+
+    /// Approximate heap and inline bytes the key holds.
+    pub fn size_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.segment_dir.as_os_str().len()
+            + self.uid.len()
+            + self.field.len()
+    }
+}
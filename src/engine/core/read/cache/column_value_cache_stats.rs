@@ -0,0 +1,22 @@
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnValueCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// Values dropped because their segment was retired
+    pub invalidations: u64,
+    pub current_items: usize,
+    pub current_bytes: usize,
+    pub capacity_bytes: usize,
+}
+
+impl ColumnValueCacheStats {
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
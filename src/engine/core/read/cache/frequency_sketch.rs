@@ -0,0 +1,103 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const ROWS: usize = 4;
+const COUNTERS_PER_WORD: usize = 16;
+const MAX_COUNT: u64 = 15;
+/// Odd multipliers spreading one key hash over the rows
+const SEEDS: [u64; ROWS] = [
+    0x9E37_79B9_7F4A_7C15,
+    0xC2B2_AE3D_27D4_EB4F,
+    0x1656_67B1_9E37_79F9,
+    0xFF51_AFD7_ED55_8CCD,
+];
+
+/// Approximate access counts of recently seen keys, TinyLFU style: a
+/// count-min sketch of four rows of 4-bit counters. Every counter is halved
+/// once the sketch has counted ten accesses per counter of a row, so counts
+/// describe a sliding window and keys seen long ago fade out.
+///
+/// Estimates never undercount an access within the window, short of
+/// saturating at 15; collisions can only overcount. Memory is two bytes per
+/// expected key, rounded up to a power of two: 2 KiB for 1000 keys.
+#[derive(Debug, Clone)]
+pub struct FrequencySketch {
+    table: Vec<u64>,
+    width_mask: usize,
+    words_per_row: usize,
+    additions: usize,
+    sample_size: usize,
+}
+
+impl FrequencySketch {
+    /// A sketch sized for about `expected_keys` distinct keys per window.
+    pub fn new(expected_keys: usize) -> Self {
+        let width = expected_keys.max(COUNTERS_PER_WORD).next_power_of_two();
+        let words_per_row = width / COUNTERS_PER_WORD;
+        Self {
+            table: vec![0; ROWS * words_per_row],
+            width_mask: width - 1,
+            words_per_row,
+            additions: 0,
+            sample_size: width.saturating_mul(10),
+        }
+    }
+
+    /// Counts one access to `key` and returns its estimated count, this one included.
+    pub fn increment<K: Hash>(&mut self, key: &K) -> u8 {
+        let hash = hash_of(key);
+        let mut estimate = MAX_COUNT;
+        for row in 0..ROWS {
+            let (word, shift) = self.slot(row, hash);
+            let count = (self.table[word] >> shift) & MAX_COUNT;
+            if count < MAX_COUNT {
+                self.table[word] += 1 << shift;
+            }
+            estimate = estimate.min((count + 1).min(MAX_COUNT));
+        }
+        self.additions += 1;
+        if self.additions >= self.sample_size {
+            self.age();
+        }
+        estimate as u8
+    }
+
+    /// Estimated accesses to `key` within the window.
+    pub fn frequency<K: Hash>(&self, key: &K) -> u8 {
+        let hash = hash_of(key);
+        (0..ROWS)
+            .map(|row| {
+                let (word, shift) = self.slot(row, hash);
+                (self.table[word] >> shift) & MAX_COUNT
+            })
+            .min()
+            .unwrap_or(0) as u8
+    }
+
+    /// Heap bytes held by the counters.
+    pub fn memory_bytes(&self) -> usize {
+        self.table.len() * std::mem::size_of::<u64>()
+    }
+
+    /// Halves every counter: accesses counted before now weigh half as much.
+    fn age(&mut self) {
+        for word in &mut self.table {
+            *word = (*word >> 1) & 0x7777_7777_7777_7777;
+        }
+        self.additions /= 2;
+    }
+
+    fn slot(&self, row: usize, hash: u64) -> (usize, u32) {
+        let mixed = hash.wrapping_mul(SEEDS[row]);
+        let index = ((mixed ^ (mixed >> 32)) as usize) & self.width_mask;
+        let word = row * self.words_per_row + index / COUNTERS_PER_WORD;
+        let shift = ((index % COUNTERS_PER_WORD) * 4) as u32;
+        (word, shift)
+    }
+}
+
+fn hash_of<K: Hash>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
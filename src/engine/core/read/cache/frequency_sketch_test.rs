@@ -0,0 +1,41 @@
+use super::FrequencySketch;
+
+#[test]
+fn increment_counts_each_access() {
+    let mut sketch = FrequencySketch::new(1000);
+    assert_eq!(sketch.frequency(&"a"), 0);
+    assert_eq!(sketch.increment(&"a"), 1);
+    assert_eq!(sketch.increment(&"a"), 2);
+    assert_eq!(sketch.increment(&"b"), 1);
+    assert_eq!(sketch.frequency(&"a"), 2);
+    assert_eq!(sketch.frequency(&"b"), 1);
+}
+
+#[test]
+fn counts_saturate_at_fifteen() {
+    let mut sketch = FrequencySketch::new(16);
+    for _ in 0..40 {
+        sketch.increment(&7u64);
+    }
+    assert_eq!(sketch.frequency(&7u64), 15);
+}
+
+#[test]
+fn counts_halve_once_the_window_fills() {
+    let mut sketch = FrequencySketch::new(16);
+    for _ in 0..8 {
+        sketch.increment(&"hot");
+    }
+    // The 160th access (ten per counter of a 16 wide row) halves every counter
+    for _ in 0..152 {
+        sketch.increment(&"cold");
+    }
+    assert_eq!(sketch.frequency(&"hot"), 4);
+    assert_eq!(sketch.frequency(&"cold"), 7);
+}
+
+#[test]
+fn memory_is_two_bytes_per_expected_key() {
+    assert_eq!(FrequencySketch::new(1000).memory_bytes(), 2048);
+    assert_eq!(FrequencySketch::new(1).memory_bytes(), 32);
+}
@@ -46,6 +46,24 @@ impl GlobalCalendarCache {
         }
         Ok(arc)
     }
+
+    /// Drops every cached calendar of one segment, e.g. after its indexes were rebuilt.
+    pub fn invalidate_segment(&self, segment_label: &str) {
+        if let Ok(mut guard) = self.inner.lock() {
+            let keys: Vec<_> = guard
+                .iter()
+                .filter(|(key, _)| {
+                    key.path
+                        .parent()
+                        .is_some_and(|dir| dir.ends_with(segment_label))
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in keys {
+                guard.pop(&key);
+            }
+        }
+    }
 }
 
 pub static GLOBAL_CALENDAR_CACHE: Lazy<GlobalCalendarCache> =
@@ -97,6 +115,24 @@ impl GlobalFieldCalendarCache {
         }
         Ok(arc)
     }
+
+    /// Drops every cached field calendar of one segment, e.g. after its indexes were rebuilt.
+    pub fn invalidate_segment(&self, segment_label: &str) {
+        if let Ok(mut guard) = self.inner.lock() {
+            let keys: Vec<_> = guard
+                .iter()
+                .filter(|(key, _)| {
+                    key.path
+                        .parent()
+                        .is_some_and(|dir| dir.ends_with(segment_label))
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in keys {
+                guard.pop(&key);
+            }
+        }
+    }
 }
 
 pub static GLOBAL_FIELD_CALENDAR_CACHE: Lazy<GlobalFieldCalendarCache> =
@@ -58,3 +58,39 @@ fn field_calendar_global_cache_reuses_arc_and_contains_zone() {
         "expected same Arc from global field calendar cache"
     );
 }
+
+#[test]
+fn field_calendar_global_cache_reloads_after_segment_invalidation() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base_dir = tmp.path().to_path_buf();
+    let segment_id = "00002";
+    let uid = "uid_fcal_inv";
+    let field = "created_at";
+    let seg_dir = base_dir.join(segment_id);
+    std::fs::create_dir_all(&seg_dir).unwrap();
+
+    let mut cal = TemporalCalendarIndex::new(field.to_string());
+    cal.add_zone_range(1, 1_700_000_000, 1_700_000_359);
+    cal.save(uid, &seg_dir).expect("save field cal");
+
+    let cache = GlobalFieldCalendarCache::instance();
+    let c1 = cache
+        .get_or_load(&base_dir, segment_id, uid, field)
+        .expect("load1");
+
+    // Another segment's label must not match
+    cache.invalidate_segment("00020");
+    let c2 = cache
+        .get_or_load(&base_dir, segment_id, uid, field)
+        .expect("load2");
+    assert!(Arc::ptr_eq(&c1, &c2));
+
+    cache.invalidate_segment(segment_id);
+    let c3 = cache
+        .get_or_load(&base_dir, segment_id, uid, field)
+        .expect("load3");
+    assert!(
+        !Arc::ptr_eq(&c1, &c3),
+        "expected a reload after invalidation"
+    );
+}
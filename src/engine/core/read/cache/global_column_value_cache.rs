@@ -0,0 +1,141 @@
+use super::column_value_cache_key::ColumnValueCacheKey;
+use super::column_value_cache_stats::ColumnValueCacheStats;
+use crate::engine::types::ScalarValue;
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Process-wide cache of single decoded values, for point lookups that fetch
+/// the same rows again and again. It sits above the column block cache: a hit
+/// skips reading and decoding the row's columns altogether. Scans never go
+/// through it, so they cannot evict the hot rows. Disabled until a byte
+/// budget is set; least recently used values are dropped first.
+#[derive(Debug)]
+pub struct GlobalColumnValueCache {
+    inner: Mutex<LruCache<ColumnValueCacheKey, (ScalarValue, usize)>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+    current_bytes: AtomicUsize,
+    capacity_bytes: AtomicUsize,
+}
+
+impl GlobalColumnValueCache {
+    pub fn new(capacity_bytes: usize) -> Self {
+        Self {
+            inner: Mutex::new(LruCache::unbounded()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+            current_bytes: AtomicUsize::new(0),
+            capacity_bytes: AtomicUsize::new(capacity_bytes),
+        }
+    }
+
+    pub fn instance() -> &'static Self {
+        &GLOBAL_COLUMN_VALUE_CACHE
+    }
+
+    pub fn resize_bytes(&self, new_capacity_bytes: usize) {
+        self.capacity_bytes
+            .store(new_capacity_bytes, Ordering::Relaxed);
+        self.evict_until_within_cap();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity_bytes.load(Ordering::Relaxed) > 0
+    }
+
+    pub fn stats(&self) -> ColumnValueCacheStats {
+        ColumnValueCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            current_items: self.inner.lock().map(|guard| guard.len()).unwrap_or(0),
+            current_bytes: self.current_bytes.load(Ordering::Relaxed),
+            capacity_bytes: self.capacity_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the cached value of `key`, or loads and caches it. When the
+    /// cache is disabled the loader runs every time and nothing is counted.
+    pub fn get_or_load<E, F>(&self, key: ColumnValueCacheKey, loader: F) -> Result<ScalarValue, E>
+    where
+        F: FnOnce() -> Result<ScalarValue, E>,
+    {
+        if !self.is_enabled() {
+            return loader();
+        }
+
+        if let Ok(mut guard) = self.inner.lock()
+            && let Some((value, _)) = guard.get(&key)
+        {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(value.clone());
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = loader()?;
+        let size = key.size_bytes() + value_size_bytes(&value);
+        if size > self.capacity_bytes.load(Ordering::Relaxed) {
+            return Ok(value);
+        }
+        if let Ok(mut guard) = self.inner.lock() {
+            if let Some((_, previous)) = guard.put(key, (value.clone(), size)) {
+                self.current_bytes.fetch_sub(previous, Ordering::Relaxed);
+            }
+            self.current_bytes.fetch_add(size, Ordering::Relaxed);
+        }
+        self.evict_until_within_cap();
+        Ok(value)
+    }
+
+    /// Drops every value read from a segment directory named `segment_label`.
+    pub fn invalidate_segment(&self, segment_label: &str) {
+        if let Ok(mut guard) = self.inner.lock() {
+            let keys: Vec<_> = guard
+                .iter()
+                .filter(|(key, _)| key.in_segment(segment_label))
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in keys {
+                if let Some((_, size)) = guard.pop(&key) {
+                    self.current_bytes.fetch_sub(size, Ordering::Relaxed);
+                    self.invalidations.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    fn evict_until_within_cap(&self) {
+        let cap = self.capacity_bytes.load(Ordering::Relaxed);
+        if let Ok(mut guard) = self.inner.lock() {
+            while self.current_bytes.load(Ordering::Relaxed) > cap {
+                match guard.pop_lru() {
+                    Some((_, (_, size))) => {
+                        self.current_bytes.fetch_sub(size, Ordering::Relaxed);
+                        self.evictions.fetch_add(1, Ordering::Relaxed);
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+}
+
+/// Approximate bytes a cached value holds, inline and on the heap.
+fn value_size_bytes(value: &ScalarValue) -> usize {
+    std::mem::size_of::<ScalarValue>()
+        + match value {
+            ScalarValue::Utf8(s) => s.len(),
+            ScalarValue::Binary(b) => b.len(),
+            _ => 0,
+        }
+}
+
+static GLOBAL_COLUMN_VALUE_CACHE: Lazy<GlobalColumnValueCache> =
+    Lazy::new(|| GlobalColumnValueCache::new(0));
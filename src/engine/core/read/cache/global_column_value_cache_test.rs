@@ -0,0 +1,104 @@
+use crate::engine::core::read::cache::{ColumnValueCacheKey, GlobalColumnValueCache};
+use crate::engine::types::ScalarValue;
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::path::Path;
+
+fn key(segment: &str, field: &str, row: usize) -> ColumnValueCacheKey {
+    ColumnValueCacheKey::new(
+        &Path::new("/data/shard-0").join(segment),
+        "uid_a",
+        field,
+        0,
+        row,
+    )
+}
+
+fn load(cache: &GlobalColumnValueCache, key: ColumnValueCacheKey, value: &str) -> ScalarValue {
+    cache
+        .get_or_load(key, || Ok::<_, Infallible>(ScalarValue::Utf8(value.into())))
+        .unwrap()
+}
+
+#[test]
+fn disabled_without_budget() {
+    let cache = GlobalColumnValueCache::new(0);
+    let loads = Cell::new(0);
+    for _ in 0..2 {
+        cache
+            .get_or_load(key("00001", "plan", 0), || {
+                loads.set(loads.get() + 1);
+                Ok::<_, Infallible>(ScalarValue::Int64(1))
+            })
+            .unwrap();
+    }
+    assert_eq!(loads.get(), 2);
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.current_items), (0, 0, 0));
+}
+
+#[test]
+fn serves_repeated_lookups_from_memory() {
+    let cache = GlobalColumnValueCache::new(1 << 20);
+    assert_eq!(
+        load(&cache, key("00001", "plan", 3), "pro"),
+        ScalarValue::Utf8("pro".into())
+    );
+    // A hit never runs the loader
+    let value = cache
+        .get_or_load(key("00001", "plan", 3), || -> Result<_, &str> {
+            Err("loaded again")
+        })
+        .unwrap();
+    assert_eq!(value, ScalarValue::Utf8("pro".into()));
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!(stats.hit_ratio(), 0.5);
+    assert_eq!(stats.current_items, 1);
+    assert!(stats.current_bytes > 0);
+}
+
+#[test]
+fn evicts_least_recently_used_values_past_the_budget() {
+    let probe = GlobalColumnValueCache::new(1 << 20);
+    load(&probe, key("00001", "plan", 0), "x");
+    let entry_bytes = probe.stats().current_bytes;
+
+    let cache = GlobalColumnValueCache::new(entry_bytes * 2);
+    load(&cache, key("00001", "plan", 0), "a");
+    load(&cache, key("00001", "plan", 1), "b");
+    load(&cache, key("00001", "plan", 0), "a");
+    load(&cache, key("00001", "plan", 2), "c");
+
+    let stats = cache.stats();
+    assert_eq!(stats.current_items, 2);
+    assert_eq!(stats.evictions, 1);
+    assert!(stats.current_bytes <= stats.capacity_bytes);
+    // Row 1 was least recently used
+    let reloaded = load(&cache, key("00001", "plan", 1), "reloaded");
+    assert_eq!(reloaded, ScalarValue::Utf8("reloaded".into()));
+}
+
+#[test]
+fn invalidation_drops_only_the_retired_segment() {
+    let cache = GlobalColumnValueCache::new(1 << 20);
+    load(&cache, key("00001", "plan", 0), "old");
+    load(&cache, key("00001", "country", 0), "NL");
+    load(&cache, key("00002", "plan", 0), "kept");
+
+    cache.invalidate_segment("00001");
+
+    let stats = cache.stats();
+    assert_eq!(stats.current_items, 1);
+    assert_eq!(stats.invalidations, 2);
+    assert_eq!(
+        load(&cache, key("00001", "plan", 0), "fresh"),
+        ScalarValue::Utf8("fresh".into())
+    );
+    assert_eq!(
+        load(&cache, key("00002", "plan", 0), "unused"),
+        ScalarValue::Utf8("kept".into())
+    );
+}
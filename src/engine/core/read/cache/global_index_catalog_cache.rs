@@ -68,7 +68,7 @@ impl GlobalIndexCatalogCache {
         if let Ok(mut guard) = self.inner.lock() {
             let keys: Vec<_> = guard
                 .iter()
-                .filter(|(key, _)| key.path.ends_with(segment_label))
+                .filter(|(key, _)| in_segment(&key.path, segment_label))
                 .map(|(key, _)| key.clone())
                 .collect();
             for key in keys {
@@ -77,7 +77,7 @@ impl GlobalIndexCatalogCache {
         }
 
         if let Ok(mut inflight) = self.inflight.lock() {
-            inflight.retain(|key, _| !key.path.ends_with(segment_label));
+            inflight.retain(|key, _| !in_segment(&key.path, segment_label));
         }
     }
 
@@ -161,3 +161,9 @@ impl GlobalIndexCatalogCache {
 
 pub static GLOBAL_INDEX_CATALOG_CACHE: Lazy<GlobalIndexCatalogCache> =
     Lazy::new(|| GlobalIndexCatalogCache::new(2048));
+
+/// Keys are `.icx` file paths, so the segment is their parent directory.
+fn in_segment(path: &Path, segment_label: &str) -> bool {
+    path.parent()
+        .is_some_and(|dir| dir.ends_with(segment_label))
+}
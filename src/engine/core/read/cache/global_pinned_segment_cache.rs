@@ -0,0 +1,216 @@
+use super::pinned_segment::PinnedSegment;
+use super::pinned_segment_cache_stats::{PinnedSegmentCacheStats, PinnedSegmentInfo};
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// Segments recently found too large to pin, so repeated reads skip the size scan.
+const OVERSIZED_MEMO_ENTRIES: usize = 4096;
+
+/// Process-wide tier that keeps whole small segments in memory after their
+/// first read. Disabled until both a per-segment size threshold and a total
+/// byte budget are set; least recently used segments are unpinned first.
+#[derive(Debug)]
+pub struct GlobalPinnedSegmentCache {
+    inner: Mutex<LruCache<PathBuf, Arc<PinnedSegment>>>,
+    oversized: Mutex<LruCache<PathBuf, ()>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    bypasses: AtomicU64,
+    evictions: AtomicU64,
+    current_bytes: AtomicUsize,
+    capacity_bytes: AtomicUsize,
+    max_segment_bytes: AtomicUsize,
+}
+
+impl GlobalPinnedSegmentCache {
+    pub fn new(max_segment_bytes: usize, capacity_bytes: usize) -> Self {
+        Self {
+            inner: Mutex::new(LruCache::unbounded()),
+            oversized: Mutex::new(LruCache::new(
+                NonZeroUsize::new(OVERSIZED_MEMO_ENTRIES).unwrap(),
+            )),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            bypasses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            current_bytes: AtomicUsize::new(0),
+            capacity_bytes: AtomicUsize::new(capacity_bytes),
+            max_segment_bytes: AtomicUsize::new(max_segment_bytes),
+        }
+    }
+
+    pub fn instance() -> &'static Self {
+        &GLOBAL_PINNED_SEGMENT_CACHE
+    }
+
+    pub fn resize_bytes(&self, new_capacity_bytes: usize) {
+        self.capacity_bytes
+            .store(new_capacity_bytes, Ordering::Relaxed);
+        self.evict_until_within_cap();
+    }
+
+    /// Sets the largest segment (total bytes on disk) eligible for pinning.
+    pub fn set_max_segment_bytes(&self, max_segment_bytes: usize) {
+        self.max_segment_bytes
+            .store(max_segment_bytes, Ordering::Relaxed);
+        if let Ok(mut guard) = self.oversized.lock() {
+            guard.clear();
+        }
+        if let Ok(mut guard) = self.inner.lock() {
+            let keys: Vec<_> = guard
+                .iter()
+                .filter(|(_, seg)| seg.size_bytes() > max_segment_bytes)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in keys {
+                self.remove_locked(&mut guard, &key);
+            }
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity_bytes.load(Ordering::Relaxed) > 0
+            && self.max_segment_bytes.load(Ordering::Relaxed) > 0
+    }
+
+    pub fn stats(&self) -> PinnedSegmentCacheStats {
+        PinnedSegmentCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            bypasses: self.bypasses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            current_bytes: self.current_bytes.load(Ordering::Relaxed),
+            capacity_bytes: self.capacity_bytes.load(Ordering::Relaxed),
+            max_segment_bytes: self.max_segment_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Currently pinned segments, most recently used first.
+    pub fn pinned_segments(&self) -> Vec<PinnedSegmentInfo> {
+        match self.inner.lock() {
+            Ok(guard) => guard
+                .iter()
+                .map(|(dir, seg)| PinnedSegmentInfo {
+                    segment_dir: dir.clone(),
+                    size_bytes: seg.size_bytes(),
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the pinned copy of `segment_dir`, pinning it on first read when
+    /// it is small enough. `None` means the caller should read from disk.
+    pub fn get_or_pin(&self, segment_dir: &Path) -> Option<Arc<PinnedSegment>> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        if let Ok(mut guard) = self.inner.lock()
+            && let Some(seg) = guard.get(segment_dir)
+        {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Some(Arc::clone(seg));
+        }
+
+        if let Ok(mut guard) = self.oversized.lock()
+            && guard.get(segment_dir).is_some()
+        {
+            self.bypasses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+
+        let dir_size = PinnedSegment::dir_size_bytes(segment_dir).ok()?;
+        let max_segment_bytes = self.max_segment_bytes.load(Ordering::Relaxed);
+        if dir_size > max_segment_bytes || dir_size > self.capacity_bytes.load(Ordering::Relaxed) {
+            self.bypasses.fetch_add(1, Ordering::Relaxed);
+            if let Ok(mut guard) = self.oversized.lock() {
+                guard.put(segment_dir.to_path_buf(), ());
+            }
+            return None;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let pinned = match PinnedSegment::load(segment_dir) {
+            Ok(seg) => Arc::new(seg),
+            Err(e) => {
+                warn!(target: "cache::pinned_segment", dir = %segment_dir.display(), error = %e, "Failed to pin segment");
+                return None;
+            }
+        };
+
+        if let Ok(mut guard) = self.inner.lock() {
+            if let Some(previous) = guard.put(segment_dir.to_path_buf(), Arc::clone(&pinned)) {
+                self.current_bytes
+                    .fetch_sub(previous.size_bytes(), Ordering::Relaxed);
+            }
+            self.current_bytes
+                .fetch_add(pinned.size_bytes(), Ordering::Relaxed);
+        }
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            debug!(target: "cache::pinned_segment", dir = %segment_dir.display(), bytes = pinned.size_bytes(), "Pinned segment");
+        }
+        self.evict_until_within_cap();
+        Some(pinned)
+    }
+
+    /// Drops the pinned copy of every segment directory named `segment_label`.
+    pub fn invalidate_segment(&self, segment_label: &str) {
+        let matches = |dir: &PathBuf| {
+            dir.file_name()
+                .is_some_and(|name| name.to_string_lossy() == segment_label)
+        };
+        if let Ok(mut guard) = self.inner.lock() {
+            let keys: Vec<_> = guard
+                .iter()
+                .filter(|(dir, _)| matches(dir))
+                .map(|(dir, _)| dir.clone())
+                .collect();
+            for key in keys {
+                self.remove_locked(&mut guard, &key);
+            }
+        }
+        if let Ok(mut guard) = self.oversized.lock() {
+            let keys: Vec<_> = guard
+                .iter()
+                .filter(|(dir, _)| matches(dir))
+                .map(|(dir, _)| dir.clone())
+                .collect();
+            for key in keys {
+                guard.pop(&key);
+            }
+        }
+    }
+
+    fn remove_locked(&self, guard: &mut LruCache<PathBuf, Arc<PinnedSegment>>, key: &PathBuf) {
+        if let Some(removed) = guard.pop(key) {
+            self.current_bytes
+                .fetch_sub(removed.size_bytes(), Ordering::Relaxed);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn evict_until_within_cap(&self) {
+        let cap = self.capacity_bytes.load(Ordering::Relaxed);
+        if let Ok(mut guard) = self.inner.lock() {
+            while self.current_bytes.load(Ordering::Relaxed) > cap {
+                match guard.pop_lru() {
+                    Some((_, removed)) => {
+                        self.current_bytes
+                            .fetch_sub(removed.size_bytes(), Ordering::Relaxed);
+                        self.evictions.fetch_add(1, Ordering::Relaxed);
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+}
+
+static GLOBAL_PINNED_SEGMENT_CACHE: Lazy<GlobalPinnedSegmentCache> =
+    Lazy::new(|| GlobalPinnedSegmentCache::new(0, 0));
@@ -0,0 +1,116 @@
+use crate::engine::core::read::cache::GlobalPinnedSegmentCache;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn write_segment(base: &Path, label: &str, col_bytes: usize) -> PathBuf {
+    let dir = base.join(label);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("uid_a.zones"), b"zones").unwrap();
+    fs::write(dir.join("uid_a_field.col"), vec![7u8; col_bytes]).unwrap();
+    fs::write(dir.join("uid_a_field.zfc"), b"offsets").unwrap();
+    // Not served from the pinned copy, but still counted towards the size threshold
+    fs::write(dir.join("uid_a.idx"), b"index").unwrap();
+    dir
+}
+
+#[test]
+fn disabled_until_threshold_and_budget_are_set() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = write_segment(tmp.path(), "00001", 16);
+
+    let cache = GlobalPinnedSegmentCache::new(0, 1024);
+    assert!(cache.get_or_pin(&dir).is_none());
+    let cache = GlobalPinnedSegmentCache::new(1024, 0);
+    assert!(cache.get_or_pin(&dir).is_none());
+
+    let stats = cache.stats();
+    assert_eq!(stats.hits + stats.misses + stats.bypasses, 0);
+}
+
+#[test]
+fn pins_small_segment_on_first_read_and_serves_it_after() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = write_segment(tmp.path(), "00001", 16);
+    let cache = GlobalPinnedSegmentCache::new(1024, 4096);
+
+    let first = cache.get_or_pin(&dir).expect("pinned on first read");
+    let second = cache.get_or_pin(&dir).expect("served from memory");
+    assert!(std::sync::Arc::ptr_eq(&first, &second));
+
+    assert_eq!(first.file("uid_a_field.col").unwrap().len(), 16);
+    assert_eq!(&first.file("uid_a.zones").unwrap()[..], b"zones");
+    assert!(first.file("uid_a.idx").is_none());
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.bypasses), (1, 1, 0));
+    assert_eq!(stats.hit_ratio(), 0.5);
+    assert_eq!(stats.current_bytes, first.size_bytes());
+
+    let pinned = cache.pinned_segments();
+    assert_eq!(pinned.len(), 1);
+    assert_eq!(pinned[0].segment_dir, dir);
+}
+
+#[test]
+fn segments_over_threshold_are_bypassed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = write_segment(tmp.path(), "00001", 2048);
+    let cache = GlobalPinnedSegmentCache::new(1024, 1 << 20);
+
+    assert!(cache.get_or_pin(&dir).is_none());
+    assert!(cache.get_or_pin(&dir).is_none());
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.bypasses), (0, 0, 2));
+    assert_eq!(stats.hit_ratio(), 0.0);
+    assert!(cache.pinned_segments().is_empty());
+}
+
+#[test]
+fn budget_unpins_least_recently_used_segment() {
+    let tmp = tempfile::tempdir().unwrap();
+    let a = write_segment(tmp.path(), "00001", 400);
+    let b = write_segment(tmp.path(), "00002", 400);
+    let c = write_segment(tmp.path(), "00003", 400);
+    let cache = GlobalPinnedSegmentCache::new(1024, 1000);
+
+    cache.get_or_pin(&a).unwrap();
+    cache.get_or_pin(&b).unwrap();
+    // Touch `a` so `b` becomes the least recently used
+    cache.get_or_pin(&a).unwrap();
+    cache.get_or_pin(&c).unwrap();
+
+    let pinned: Vec<_> = cache
+        .pinned_segments()
+        .into_iter()
+        .map(|info| info.segment_dir)
+        .collect();
+    assert_eq!(pinned, vec![c, a]);
+
+    let stats = cache.stats();
+    assert_eq!(stats.evictions, 1);
+    assert!(stats.current_bytes <= stats.capacity_bytes);
+}
+
+#[test]
+fn invalidate_segment_drops_pinned_copy() {
+    let tmp = tempfile::tempdir().unwrap();
+    let a = write_segment(tmp.path(), "00001", 16);
+    let b = write_segment(tmp.path(), "00002", 16);
+    let cache = GlobalPinnedSegmentCache::new(1024, 4096);
+
+    cache.get_or_pin(&a).unwrap();
+    let kept = cache.get_or_pin(&b).unwrap();
+
+    cache.invalidate_segment("00001");
+
+    let pinned = cache.pinned_segments();
+    assert_eq!(pinned.len(), 1);
+    assert_eq!(pinned[0].segment_dir, b);
+    assert_eq!(cache.stats().current_bytes, kept.size_bytes());
+
+    // A segment rewritten under the same label is read afresh
+    fs::write(a.join("uid_a.zones"), b"rewritten").unwrap();
+    let reloaded = cache.get_or_pin(&a).unwrap();
+    assert_eq!(&reloaded.file("uid_a.zones").unwrap()[..], b"rewritten");
+}
@@ -102,6 +102,24 @@ impl GlobalFieldTemporalIndexCache {
         }
         Ok(arc)
     }
+
+    /// Drops every cached temporal slab entry of one segment, e.g. after its indexes were rebuilt.
+    pub fn invalidate_segment(&self, segment_label: &str) {
+        if let Ok(mut guard) = self.inner.lock() {
+            let keys: Vec<_> = guard
+                .iter()
+                .filter(|(key, _)| {
+                    key.path
+                        .parent()
+                        .is_some_and(|dir| dir.ends_with(segment_label))
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in keys {
+                guard.pop(&key);
+            }
+        }
+    }
 }
 
 pub static GLOBAL_FIELD_TEMPORAL_INDEX_CACHE: Lazy<GlobalFieldTemporalIndexCache> =
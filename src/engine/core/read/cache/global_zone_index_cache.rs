@@ -1,3 +1,4 @@
+use super::cache_counters::CacheCounters;
 use super::zone_index_cache_types::{ZoneIndexCacheKey, ZoneIndexEntry};
 use crate::engine::core::zone::zone_index::ZoneIndex;
 use lru::LruCache;
@@ -89,6 +90,27 @@ impl GlobalZoneIndexCache {
         }
     }
 
+    /// Entries are not sized, so no byte usage is reported.
+    pub fn counters(&self) -> CacheCounters {
+        let stats = self.stats();
+        CacheCounters {
+            hits: stats.hits,
+            misses: stats.misses,
+            reloads: stats.reloads,
+            evictions: stats.evictions,
+            current_bytes: None,
+            capacity_bytes: None,
+        }
+    }
+
+    /// Cached entries, most recently used first. Does not touch recency.
+    pub fn entries_by_recency(&self) -> Vec<Arc<ZoneIndexEntry>> {
+        match self.inner.lock() {
+            Ok(guard) => guard.iter().map(|(_, entry)| Arc::clone(entry)).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
     pub fn invalidate_segment(&self, segment_label: &str) {
         if let Ok(mut guard) = self.inner.lock() {
             let keys: Vec<_> = guard
@@ -1,7 +1,10 @@
+use super::cache_counters::CacheCounters;
+use super::frequency_sketch::FrequencySketch;
 use super::seg_id::parse_segment_id_u64;
 use super::zone_surf_cache_entry::ZoneSurfCacheEntry;
 use super::zone_surf_cache_key::ZoneSurfCacheKey;
 use crate::engine::core::filter::zone_surf_filter::ZoneSurfFilter;
+use crate::shared::config::CacheAdmission;
 use lru::LruCache;
 use once_cell::sync::Lazy;
 use std::io;
@@ -25,6 +28,23 @@ pub struct ZoneSurfCacheStats {
     pub evictions: u64,
     pub current_bytes: usize,
     pub current_items: usize,
+    /// Loaded filters the admission policy let into the cache
+    pub admissions: u64,
+    /// Loaded filters the admission policy kept out, served once and dropped
+    pub rejections: u64,
+    /// Bytes held by the admission policy's access counts
+    pub admission_bytes: usize,
+}
+
+impl ZoneSurfCacheStats {
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses + self.reloads;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -37,6 +57,10 @@ pub struct GlobalZoneSurfCache {
     evictions: AtomicU64,
     current_bytes: AtomicUsize,
     capacity_bytes: AtomicUsize,
+    /// Access counts gating inserts under `CacheAdmission::Frequency`; `None` admits all
+    admission: Mutex<Option<FrequencySketch>>,
+    admissions: AtomicU64,
+    rejections: AtomicU64,
 }
 
 impl GlobalZoneSurfCache {
@@ -48,7 +72,7 @@ impl GlobalZoneSurfCache {
         by_bytes.max(1000)
     }
 
-    fn new(capacity_bytes: usize) -> Self {
+    pub(crate) fn new(capacity_bytes: usize) -> Self {
         Self {
             inner: Mutex::new(LruCache::new(
                 NonZeroUsize::new(Self::compute_item_cap(capacity_bytes)).unwrap(),
@@ -60,6 +84,9 @@ impl GlobalZoneSurfCache {
             evictions: AtomicU64::new(0),
             current_bytes: AtomicUsize::new(0),
             capacity_bytes: AtomicUsize::new(capacity_bytes),
+            admission: Mutex::new(None),
+            admissions: AtomicU64::new(0),
+            rejections: AtomicU64::new(0),
         }
     }
 
@@ -71,6 +98,18 @@ impl GlobalZoneSurfCache {
         &INSTANCE
     }
 
+    /// Like [`Self::stats`] without the entry count, which needs the cache lock.
+    pub fn counters(&self) -> CacheCounters {
+        CacheCounters {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            reloads: self.reloads.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            current_bytes: Some(self.current_bytes.load(Ordering::Relaxed)),
+            capacity_bytes: Some(self.capacity_bytes.load(Ordering::Relaxed)),
+        }
+    }
+
     pub fn stats(&self) -> ZoneSurfCacheStats {
         let current_bytes = self.current_bytes.load(Ordering::Relaxed);
         let current_items = if let Ok(guard) = self.inner.lock() {
@@ -79,6 +118,11 @@ impl GlobalZoneSurfCache {
             0
         };
 
+        let admission_bytes = match self.admission.lock() {
+            Ok(guard) => guard.as_ref().map_or(0, FrequencySketch::memory_bytes),
+            Err(_) => 0,
+        };
+
         ZoneSurfCacheStats {
             hits: self.hits.load(Ordering::Relaxed),
             misses: self.misses.load(Ordering::Relaxed),
@@ -86,7 +130,48 @@ impl GlobalZoneSurfCache {
             evictions: self.evictions.load(Ordering::Relaxed),
             current_bytes,
             current_items,
+            admissions: self.admissions.load(Ordering::Relaxed),
+            rejections: self.rejections.load(Ordering::Relaxed),
+            admission_bytes,
+        }
+    }
+
+    /// Sets which loaded filters are inserted. `Frequency` only admits a
+    /// filter loaded at least twice within the recent window, so a scan that
+    /// touches each filter once leaves the cached hot set alone. Switching
+    /// policy forgets the access counts.
+    pub fn set_admission(&self, policy: CacheAdmission) {
+        let sketch = match policy {
+            CacheAdmission::Always => None,
+            CacheAdmission::Frequency => Some(self.new_sketch()),
+        };
+        *self.admission.lock().unwrap_or_else(|p| p.into_inner()) = sketch;
+    }
+
+    /// Sized so the window spans about ten cache fills.
+    fn new_sketch(&self) -> FrequencySketch {
+        FrequencySketch::new(Self::compute_item_cap(
+            self.capacity_bytes.load(Ordering::Relaxed),
+        ))
+    }
+
+    /// Counts a load of `key` and decides whether to cache it.
+    fn admit(&self, key: &ZoneSurfCacheKey) -> bool {
+        let admitted = match self
+            .admission
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .as_mut()
+        {
+            Some(sketch) => sketch.increment(key) >= 2,
+            None => true,
+        };
+        if admitted {
+            self.admissions.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.rejections.fetch_add(1, Ordering::Relaxed);
         }
+        admitted
     }
 
     /// Resize the cache capacity in bytes
@@ -98,6 +183,11 @@ impl GlobalZoneSurfCache {
             let new_items = Self::compute_item_cap(new_capacity_bytes);
             guard.resize(NonZeroUsize::new(new_items).unwrap());
         }
+        if let Ok(mut admission) = self.admission.lock()
+            && admission.is_some()
+        {
+            *admission = Some(self.new_sketch());
+        }
         // Evict entries if we're over the new capacity
         self.evict_until_within_cap();
     }
@@ -125,6 +215,19 @@ impl GlobalZoneSurfCache {
         key: ZoneSurfCacheKey,
         loader: F,
     ) -> Result<(Arc<ZoneSurfFilter>, CacheOutcome), io::Error>
+    where
+        F: FnOnce() -> Result<ZoneSurfCacheEntry, io::Error>,
+    {
+        self.get_or_load_with(key, true, loader)
+    }
+
+    /// `get_or_load`, with `gated = false` inserting past the admission policy.
+    fn get_or_load_with<F>(
+        &self,
+        key: ZoneSurfCacheKey,
+        gated: bool,
+        loader: F,
+    ) -> Result<(Arc<ZoneSurfFilter>, CacheOutcome), io::Error>
     where
         F: FnOnce() -> Result<ZoneSurfCacheEntry, io::Error>,
     {
@@ -170,7 +273,13 @@ impl GlobalZoneSurfCache {
         let filter = Arc::clone(&entry_arc.filter);
 
         // Insert and manage eviction
-        let outcome = if let Ok(mut guard) = self.inner.lock() {
+        let outcome = if gated && !self.admit(&key) {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            if tracing::enabled!(tracing::Level::DEBUG) {
+                tracing::debug!(target: "cache::zone_surf", shard_id = key.shard_id, segment_id = key.segment_id, uid_id = key.uid_id, field_id = key.field_id, "ZoneSuRF cache MISS not admitted (seen once in window)");
+            }
+            CacheOutcome::Miss
+        } else if let Ok(mut guard) = self.inner.lock() {
             let estimated_size = entry_arc.estimated_size();
             let capacity_bytes = self.capacity_bytes.load(Ordering::Relaxed);
             let mut prospective = self.current_bytes.load(Ordering::Relaxed);
@@ -244,6 +353,14 @@ impl GlobalZoneSurfCache {
         Ok((filter, outcome))
     }
 
+    /// Cached entries, most recently used first. Does not touch recency.
+    pub fn entries_by_recency(&self) -> Vec<Arc<ZoneSurfCacheEntry>> {
+        match self.inner.lock() {
+            Ok(guard) => guard.iter().map(|(_, entry)| Arc::clone(entry)).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
     pub fn invalidate_segment(&self, segment_label: &str) {
         let segment_id = parse_segment_id_u64(segment_label);
         if let Ok(mut guard) = self.inner.lock() {
@@ -275,7 +392,32 @@ impl GlobalZoneSurfCache {
         field: &str,
         path: &Path,
     ) -> Result<(Arc<ZoneSurfFilter>, CacheOutcome), io::Error> {
-        self.get_or_load(key, || {
+        self.load_from_file_with(key, true, segment_id, uid, field, path)
+    }
+
+    /// `load_from_file` for filters known to be hot, such as those cached at
+    /// the last shutdown: inserts them whatever the admission policy.
+    pub fn preload_from_file(
+        &self,
+        key: ZoneSurfCacheKey,
+        segment_id: &str,
+        uid: &str,
+        field: &str,
+        path: &Path,
+    ) -> Result<(Arc<ZoneSurfFilter>, CacheOutcome), io::Error> {
+        self.load_from_file_with(key, false, segment_id, uid, field, path)
+    }
+
+    fn load_from_file_with(
+        &self,
+        key: ZoneSurfCacheKey,
+        gated: bool,
+        segment_id: &str,
+        uid: &str,
+        field: &str,
+        path: &Path,
+    ) -> Result<(Arc<ZoneSurfFilter>, CacheOutcome), io::Error> {
+        self.get_or_load_with(key, gated, || {
             // Get file metadata for validation
             let (ino, mtime, size) = match file_identity(path) {
                 Ok(meta) => meta,
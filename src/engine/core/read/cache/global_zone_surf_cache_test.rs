@@ -202,3 +202,62 @@ fn test_zone_surf_cache_hysteresis_reduces_thrashing() {
     let s = cache.stats();
     assert!(s.current_items > 0, "hysteresis should retain some items");
 }
+
+/// Hits of a workload alternating point lookups of 5 hot filters with a scan
+/// of 10 filters never read again, on a cache with room for 10 filters.
+fn mixed_workload_stats(admission: crate::shared::config::CacheAdmission) -> ZoneSurfCacheStats {
+    let cache = global_zone_surf_cache::GlobalZoneSurfCache::new(10 * 4096);
+    cache.set_admission(admission);
+    let load = |key: ZoneSurfCacheKey| {
+        cache
+            .get_or_load(key, || {
+                Ok(ZoneSurfCacheEntry::new(
+                    Arc::new(ZoneSurfFilter { entries: vec![] }),
+                    std::path::PathBuf::new(),
+                    0,
+                    "uid".into(),
+                    "f".into(),
+                    0,
+                    0,
+                    0,
+                ))
+            })
+            .unwrap()
+    };
+    let key = |uid: u64| ZoneSurfCacheKey {
+        shard_id: 0,
+        segment_id: 7,
+        uid_id: uid,
+        field_id: 1,
+    };
+
+    let mut scanned = 1000;
+    for _round in 0..50 {
+        for hot in 0..5 {
+            load(key(hot));
+        }
+        for _ in 0..10 {
+            load(key(scanned));
+            scanned += 1;
+        }
+    }
+    cache.stats()
+}
+
+#[test]
+fn test_zone_surf_cache_frequency_admission_keeps_hot_filters_through_scans() {
+    use crate::shared::config::CacheAdmission;
+
+    let always = mixed_workload_stats(CacheAdmission::Always);
+    let frequency = mixed_workload_stats(CacheAdmission::Frequency);
+
+    assert_eq!(always.rejections, 0);
+    assert!(frequency.rejections >= 500, "scan filters are seen once");
+    assert!(
+        frequency.hit_ratio() > always.hit_ratio() + 0.2,
+        "frequency {:.3} vs always {:.3}",
+        frequency.hit_ratio(),
+        always.hit_ratio()
+    );
+    assert!(frequency.admission_bytes > 0 && frequency.admission_bytes <= 4096);
+}
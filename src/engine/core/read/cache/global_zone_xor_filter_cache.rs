@@ -1,3 +1,4 @@
+use super::cache_counters::CacheCounters;
 use super::seg_id::parse_segment_id_u64;
 use super::zone_xor_filter_cache_entry::ZoneXorFilterCacheEntry;
 use super::zone_xor_filter_cache_key::ZoneXorFilterCacheKey;
@@ -71,6 +72,18 @@ impl GlobalZoneXorFilterCache {
         &INSTANCE
     }
 
+    /// Like [`Self::stats`] without the entry count, which needs the cache lock.
+    pub fn counters(&self) -> CacheCounters {
+        CacheCounters {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            reloads: self.reloads.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            current_bytes: Some(self.current_bytes.load(Ordering::Relaxed)),
+            capacity_bytes: Some(self.capacity_bytes.load(Ordering::Relaxed)),
+        }
+    }
+
     pub fn stats(&self) -> ZoneXorFilterCacheStats {
         let current_bytes = self.current_bytes.load(Ordering::Relaxed);
         let current_items = if let Ok(guard) = self.inner.lock() {
@@ -254,6 +267,14 @@ impl GlobalZoneXorFilterCache {
         Ok((index, outcome))
     }
 
+    /// Cached entries, most recently used first. Does not touch recency.
+    pub fn entries_by_recency(&self) -> Vec<Arc<ZoneXorFilterCacheEntry>> {
+        match self.inner.lock() {
+            Ok(guard) => guard.iter().map(|(_, entry)| Arc::clone(entry)).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
     pub fn invalidate_segment(&self, segment_label: &str) {
         let segment_id = parse_segment_id_u64(segment_label);
         if let Ok(mut guard) = self.inner.lock() {
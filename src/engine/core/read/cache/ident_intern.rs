@@ -1,45 +1,146 @@
+use crate::shared::config::InternEviction;
+use lru::LruCache;
 use once_cell::sync::Lazy;
-use std::collections::HashMap;
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
-/// Simple process-global string interner for UIDs and fields.
-/// Not persisted; IDs are stable only within a process lifetime.
-struct StringInterner {
-    map: HashMap<String, u32>,
-    rev: Vec<String>,
+/// Default number of identifiers each table keeps interned.
+pub const DEFAULT_INTERN_MAX_ENTRIES: usize = 65_536;
+
+/// Process-global string interner for UIDs and fields, used to build compact
+/// cache keys. Not persisted; IDs are stable only within a process lifetime.
+///
+/// IDs are never reused: an identifier that is evicted and interned again gets
+/// a fresh ID, so a key built from an old ID can go stale (a cache miss) but
+/// never aliases another identifier.
+#[derive(Debug)]
+pub struct IdentInterner {
+    inner: Mutex<InternTable>,
+    capacity: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    /// Identifiers handed a one-off ID because the table was full
+    bypasses: AtomicU64,
+}
+
+#[derive(Debug)]
+struct InternTable {
+    ids: LruCache<String, u64>,
+    next_id: u64,
+    eviction: InternEviction,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InternStats {
+    pub entries: usize,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub bypasses: u64,
+}
+
+impl InternStats {
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
 }
 
-impl StringInterner {
-    fn new() -> Self {
+impl IdentInterner {
+    pub fn new(capacity: usize, eviction: InternEviction) -> Self {
         Self {
-            map: HashMap::new(),
-            rev: Vec::new(),
+            inner: Mutex::new(InternTable {
+                ids: LruCache::unbounded(),
+                next_id: 0,
+                eviction,
+            }),
+            capacity: AtomicUsize::new(capacity.max(1)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            bypasses: AtomicU64::new(0),
         }
     }
 
-    fn intern(&mut self, s: &str) -> u32 {
-        if let Some(&id) = self.map.get(s) {
+    pub fn uids() -> &'static Self {
+        &UID_INTERNER
+    }
+
+    pub fn fields() -> &'static Self {
+        &FIELD_INTERNER
+    }
+
+    /// Sets the max number of interned identifiers, evicting least recently
+    /// used ones if the table is over the new size.
+    pub fn resize(&self, capacity: usize) {
+        let capacity = capacity.max(1);
+        self.capacity.store(capacity, Ordering::Relaxed);
+        let mut table = self.inner.lock().unwrap();
+        while table.ids.len() > capacity {
+            table.ids.pop_lru();
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn set_eviction(&self, eviction: InternEviction) {
+        self.inner.lock().unwrap().eviction = eviction;
+    }
+
+    pub fn intern(&self, s: &str) -> u64 {
+        let mut table = self.inner.lock().unwrap();
+        if let Some(&id) = table.ids.get(s) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
             return id;
         }
-        let id = self.rev.len() as u32;
-        self.rev.push(s.to_owned());
-        self.map.insert(s.to_owned(), id);
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let id = table.next_id;
+        table.next_id += 1;
+
+        if table.ids.len() >= self.capacity.load(Ordering::Relaxed) {
+            match table.eviction {
+                InternEviction::Lru => {
+                    table.ids.pop_lru();
+                    self.evictions.fetch_add(1, Ordering::Relaxed);
+                }
+                InternEviction::Bypass => {
+                    self.bypasses.fetch_add(1, Ordering::Relaxed);
+                    return id;
+                }
+            }
+        }
+        table.ids.put(s.to_owned(), id);
         id
     }
+
+    pub fn stats(&self) -> InternStats {
+        InternStats {
+            entries: self.inner.lock().unwrap().ids.len(),
+            capacity: self.capacity.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            bypasses: self.bypasses.load(Ordering::Relaxed),
+        }
+    }
 }
 
-static UID_INTERNER: Lazy<Mutex<StringInterner>> = Lazy::new(|| Mutex::new(StringInterner::new()));
-static FIELD_INTERNER: Lazy<Mutex<StringInterner>> =
-    Lazy::new(|| Mutex::new(StringInterner::new()));
+static UID_INTERNER: Lazy<IdentInterner> =
+    Lazy::new(|| IdentInterner::new(DEFAULT_INTERN_MAX_ENTRIES, InternEviction::Lru));
+static FIELD_INTERNER: Lazy<IdentInterner> =
+    Lazy::new(|| IdentInterner::new(DEFAULT_INTERN_MAX_ENTRIES, InternEviction::Lru));
 
 #[inline]
-pub fn intern_uid(uid: &str) -> u32 {
-    let mut guard = UID_INTERNER.lock().unwrap();
-    guard.intern(uid)
+pub fn intern_uid(uid: &str) -> u64 {
+    UID_INTERNER.intern(uid)
 }
 
 #[inline]
-pub fn intern_field(field: &str) -> u32 {
-    let mut guard = FIELD_INTERNER.lock().unwrap();
-    guard.intern(field)
+pub fn intern_field(field: &str) -> u64 {
+    FIELD_INTERNER.intern(field)
 }
@@ -0,0 +1,85 @@
+use crate::engine::core::read::cache::IdentInterner;
+use crate::shared::config::InternEviction;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[test]
+fn repeated_identifiers_share_an_id_and_count_as_hits() {
+    let interner = IdentInterner::new(8, InternEviction::Lru);
+    let a = interner.intern("amount");
+    let b = interner.intern("region");
+    assert_ne!(a, b);
+    assert_eq!(interner.intern("amount"), a);
+
+    let stats = interner.stats();
+    assert_eq!(stats.entries, 2);
+    assert_eq!((stats.hits, stats.misses), (1, 2));
+    assert!((stats.hit_ratio() - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn lru_eviction_bounds_entries_and_never_reuses_ids() {
+    let interner = IdentInterner::new(2, InternEviction::Lru);
+    let a = interner.intern("a");
+    let b = interner.intern("b");
+    interner.intern("a"); // "b" is now least recently used
+    let c = interner.intern("c");
+
+    let stats = interner.stats();
+    assert_eq!(stats.entries, 2);
+    assert_eq!(stats.evictions, 1);
+    assert_eq!(interner.intern("a"), a);
+
+    // An evicted identifier comes back with a fresh id rather than one in use
+    let b_again = interner.intern("b");
+    assert_ne!(b_again, b);
+    assert_ne!(b_again, a);
+    assert_ne!(b_again, c);
+}
+
+#[test]
+fn bypass_keeps_the_table_and_hands_out_one_off_ids() {
+    let interner = IdentInterner::new(1, InternEviction::Bypass);
+    let a = interner.intern("a");
+    let b1 = interner.intern("b");
+    let b2 = interner.intern("b");
+    assert_ne!(b1, b2);
+    assert_ne!(b1, a);
+    assert_eq!(interner.intern("a"), a);
+
+    let stats = interner.stats();
+    assert_eq!(stats.entries, 1);
+    assert_eq!(stats.bypasses, 2);
+    assert_eq!(stats.evictions, 0);
+}
+
+#[test]
+fn resize_evicts_down_to_the_new_capacity() {
+    let interner = IdentInterner::new(4, InternEviction::Lru);
+    for field in ["a", "b", "c", "d"] {
+        interner.intern(field);
+    }
+    interner.resize(2);
+    let stats = interner.stats();
+    assert_eq!((stats.entries, stats.capacity, stats.evictions), (2, 2, 2));
+}
+
+#[test]
+fn concurrent_interning_agrees_on_ids() {
+    let interner = Arc::new(IdentInterner::new(1024, InternEviction::Lru));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let interner = Arc::clone(&interner);
+            std::thread::spawn(move || {
+                (0..100)
+                    .map(|i| interner.intern(&format!("field_{}", i)))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    assert!(results.iter().all(|ids| ids == &results[0]));
+    assert_eq!(results[0].iter().collect::<HashSet<_>>().len(), 100);
+    assert_eq!(interner.stats().misses, 100);
+}
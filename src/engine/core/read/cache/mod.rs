@@ -1,16 +1,22 @@
+pub mod cache_counters;
 pub mod column_block_cache;
 pub mod column_block_cache_key;
 pub mod column_block_cache_stats;
 pub mod column_handle;
 pub mod column_handle_key;
+pub mod column_value_cache_key;
+pub mod column_value_cache_stats;
 pub mod decompressed_block;
 pub mod enum_cache_entry;
 pub mod enum_cache_key;
+pub mod frequency_sketch;
 pub mod global_calendar_cache;
 pub mod global_column_handle_cache;
+pub mod global_column_value_cache;
 pub mod global_enum_cache;
 pub mod global_index_catalog_cache;
 pub mod global_materialized_frame_cache;
+pub mod global_pinned_segment_cache;
 pub mod global_temporal_index_cache;
 pub mod global_zone_index_cache;
 pub mod global_zone_surf_cache;
@@ -19,9 +25,12 @@ pub mod ident_intern;
 pub mod materialized_frame_cache_entry;
 pub mod materialized_frame_cache_key;
 pub mod materialized_frame_cache_stats;
+pub mod pinned_segment;
+pub mod pinned_segment_cache_stats;
 pub mod providers;
 pub mod query_caches;
 pub mod seg_id;
+pub mod warm_start;
 pub mod zone_index_cache_types;
 pub mod zone_index_key;
 pub mod zone_surf_cache_entry;
@@ -29,15 +38,20 @@ pub mod zone_surf_cache_key;
 pub mod zone_xor_filter_cache_entry;
 pub mod zone_xor_filter_cache_key;
 
+pub use cache_counters::CacheCounters;
 pub use column_block_cache::GlobalColumnBlockCache;
 pub use column_block_cache_key::ColumnBlockCacheKey;
 pub use column_block_cache_stats::ColumnBlockCacheStats;
-pub use column_handle::ColumnHandle;
+pub use column_handle::{ColumnBytes, ColumnHandle};
 pub use column_handle_key::ColumnHandleKey;
+pub use column_value_cache_key::ColumnValueCacheKey;
+pub use column_value_cache_stats::ColumnValueCacheStats;
 pub use decompressed_block::DecompressedBlock;
 pub use enum_cache_entry::EnumCacheEntry;
 pub use enum_cache_key::EnumCacheKey;
+pub use frequency_sketch::FrequencySketch;
 pub use global_column_handle_cache::GlobalColumnHandleCache;
+pub use global_column_value_cache::GlobalColumnValueCache;
 pub use global_enum_cache::{CacheOutcome as EnumCacheOutcome, EnumCacheStats, GlobalEnumCache};
 pub use global_index_catalog_cache::{
     CacheOutcome as IndexCatalogCacheOutcome, GlobalIndexCatalogCache, IndexCatalogCacheStats,
@@ -45,6 +59,7 @@ pub use global_index_catalog_cache::{
 pub use global_materialized_frame_cache::{
     CacheOutcome as MaterializedFrameCacheOutcome, GlobalMaterializedFrameCache,
 };
+pub use global_pinned_segment_cache::GlobalPinnedSegmentCache;
 pub use global_zone_index_cache::{CacheOutcome, GlobalZoneIndexCache, ZoneIndexCacheStats};
 pub use global_zone_surf_cache::{
     CacheOutcome as SurfCacheOutcome, GlobalZoneSurfCache, ZoneSurfCacheStats,
@@ -52,9 +67,12 @@ pub use global_zone_surf_cache::{
 pub use global_zone_xor_filter_cache::{
     CacheOutcome as XorFilterCacheOutcome, GlobalZoneXorFilterCache, ZoneXorFilterCacheStats,
 };
+pub use ident_intern::{IdentInterner, InternStats};
 pub use materialized_frame_cache_entry::MaterializedFrameCacheEntry;
 pub use materialized_frame_cache_key::MaterializedFrameCacheKey;
 pub use materialized_frame_cache_stats::MaterializedFrameCacheStats;
+pub use pinned_segment::PinnedSegment;
+pub use pinned_segment_cache_stats::{PinnedSegmentCacheStats, PinnedSegmentInfo};
 pub use providers::{CachedZoneSurfProvider, DirectZoneSurfProvider, ZoneSurfProvider};
 pub use providers::{ColumnProvider, ZoneIndexProvider};
 pub use query_caches::QueryCaches;
@@ -70,11 +88,21 @@ mod column_block_cache_test;
 #[cfg(test)]
 mod column_handle_test;
 #[cfg(test)]
+mod frequency_sketch_test;
+#[cfg(test)]
+mod global_column_value_cache_test;
+#[cfg(test)]
+mod global_pinned_segment_cache_test;
+#[cfg(test)]
 mod global_zone_index_cache_test;
 #[cfg(test)]
 mod global_zone_surf_cache_test;
 #[cfg(test)]
+mod ident_intern_test;
+#[cfg(test)]
 mod query_caches_test;
+#[cfg(test)]
+mod warm_start_test;
 
 #[cfg(test)]
 mod global_calendar_cache_test;
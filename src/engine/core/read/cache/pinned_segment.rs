@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// File extensions the query path reads from a pinned copy instead of disk.
+/// Index and filter files are served by their own process-wide caches.
+const PINNED_EXTENSIONS: [&str; 3] = ["zones", "zfc", "col"];
+
+/// A small segment's zone metadata and column files, held in memory.
+#[derive(Debug)]
+pub struct PinnedSegment {
+    segment_dir: PathBuf,
+    files: HashMap<String, Arc<[u8]>>,
+    size_bytes: usize,
+}
+
+impl PinnedSegment {
+    /// Reads the pinnable files of `segment_dir` into memory.
+    pub fn load(segment_dir: &Path) -> io::Result<Self> {
+        let mut files = HashMap::new();
+        let mut size_bytes = 0usize;
+        for entry in fs::read_dir(segment_dir)? {
+            let path = entry?.path();
+            let pinnable = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| PINNED_EXTENSIONS.contains(&ext));
+            if !pinnable || !path.is_file() {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let bytes: Arc<[u8]> = fs::read(&path)?.into();
+            size_bytes += bytes.len();
+            files.insert(name.to_string(), bytes);
+        }
+        Ok(Self {
+            segment_dir: segment_dir.to_path_buf(),
+            files,
+            size_bytes,
+        })
+    }
+
+    /// Total on-disk size of every regular file in `segment_dir`.
+    pub fn dir_size_bytes(segment_dir: &Path) -> io::Result<usize> {
+        let mut total = 0usize;
+        for entry in fs::read_dir(segment_dir)? {
+            let meta = entry?.metadata()?;
+            if meta.is_file() {
+                total += meta.len() as usize;
+            }
+        }
+        Ok(total)
+    }
+
+    pub fn segment_dir(&self) -> &Path {
+        &self.segment_dir
+    }
+
+    pub fn file(&self, name: &str) -> Option<&Arc<[u8]>> {
+        self.files.get(name)
+    }
+
+    /// Bytes held in memory for this segment.
+    pub fn size_bytes(&self) -> usize {
+        self.size_bytes
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PinnedSegmentCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Lookups of segments too large to pin, served from disk
+    pub bypasses: u64,
+    pub evictions: u64,
+    pub current_bytes: usize,
+    pub capacity_bytes: usize,
+    pub max_segment_bytes: usize,
+}
+
+impl PinnedSegmentCacheStats {
+    /// Share of lookups on pinnable segments that were served from memory.
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedSegmentInfo {
+    pub segment_dir: PathBuf,
+    pub size_bytes: usize,
+}
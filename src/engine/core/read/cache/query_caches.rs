@@ -5,8 +5,9 @@ use std::sync::{Arc, Mutex};
 
 use crate::engine::core::ZoneMeta;
 use crate::engine::core::column::compression::{
-    CompressionCodec, Lz4Codec, compressed_column_index::ZoneBlockEntry,
+    codec_for_algo, compressed_column_index::ZoneBlockEntry,
 };
+use crate::engine::core::column::reader::checksum;
 use crate::engine::core::read::cache::{DecompressedBlock, GlobalColumnBlockCache};
 use crate::engine::core::zone::zone_index::ZoneIndex;
 
@@ -15,10 +16,12 @@ use super::column_handle_key::ColumnHandleKey;
 use super::global_calendar_cache::GlobalFieldCalendarCache;
 use super::global_column_handle_cache::GlobalColumnHandleCache;
 use super::global_enum_cache::GlobalEnumCache;
+use super::global_pinned_segment_cache::GlobalPinnedSegmentCache;
 use super::global_temporal_index_cache::GlobalFieldTemporalIndexCache;
 use super::global_zone_index_cache::{CacheOutcome, GlobalZoneIndexCache};
 use super::global_zone_surf_cache::GlobalZoneSurfCache;
 use super::global_zone_xor_filter_cache::GlobalZoneXorFilterCache;
+use super::pinned_segment::PinnedSegment;
 use super::zone_surf_cache_key::ZoneSurfCacheKey;
 use super::zone_xor_filter_cache_key::ZoneXorFilterCacheKey;
 use crate::engine::core::filter::zone_surf_filter::ZoneSurfFilter;
@@ -31,6 +34,7 @@ use crate::shared::path::absolutize;
 pub struct QueryCaches {
     pub(crate) base_dir: PathBuf,
     shard_id: Option<usize>,
+    pinned_segments: &'static GlobalPinnedSegmentCache,
     // Per-query counters
     zone_index_hits: AtomicU64,
     zone_index_misses: AtomicU64,
@@ -53,6 +57,8 @@ pub struct QueryCaches {
     zone_meta_by_key: Mutex<HashMap<(String, String), Arc<Vec<ZoneMeta>>>>,
     // Per-query memoization for enum bitmap indexes: (segment, uid, field) -> EnumBitmapIndex
     enum_by_key: Mutex<HashMap<(String, String, String), Arc<EnumBitmapIndex>>>,
+    // Per-query memoization of the pinned-segment lookup: segment -> pinned copy, if any
+    pinned_by_segment: Mutex<HashMap<String, Option<Arc<PinnedSegment>>>>,
 }
 
 impl QueryCaches {
@@ -68,6 +74,7 @@ impl QueryCaches {
         Self {
             base_dir: abs_base_dir,
             shard_id,
+            pinned_segments: GlobalPinnedSegmentCache::instance(),
             zone_index_hits: AtomicU64::new(0),
             zone_index_misses: AtomicU64::new(0),
             zone_index_reloads: AtomicU64::new(0),
@@ -80,14 +87,38 @@ impl QueryCaches {
             field_temporal_index_by_key: Mutex::new(HashMap::new()),
             zone_meta_by_key: Mutex::new(HashMap::new()),
             enum_by_key: Mutex::new(HashMap::new()),
+            pinned_by_segment: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Serve small segments from the given pinned tier instead of the process-wide one.
+    pub fn with_pinned_segment_cache(mut self, cache: &'static GlobalPinnedSegmentCache) -> Self {
+        self.pinned_segments = cache;
+        self
+    }
+
     #[inline]
     fn segment_dir(&self, segment_id: &str) -> PathBuf {
         self.base_dir.join(segment_id)
     }
 
+    /// Pinned in-memory copy of a segment, looked up at most once per query.
+    fn pinned_segment(&self, segment_id: &str) -> Option<Arc<PinnedSegment>> {
+        if !self.pinned_segments.is_enabled() {
+            return None;
+        }
+        let mut map = self
+            .pinned_by_segment
+            .lock()
+            .unwrap_or_else(|p| p.into_inner());
+        map.entry(segment_id.to_string())
+            .or_insert_with(|| {
+                self.pinned_segments
+                    .get_or_pin(&self.segment_dir(segment_id))
+            })
+            .clone()
+    }
+
     #[inline]
     pub fn shard_id_opt(&self) -> Option<usize> {
         self.shard_id
@@ -123,22 +154,23 @@ impl QueryCaches {
             GlobalColumnBlockCache::instance().get_or_load(&handle.col_path, zone_id, || {
                 let start = entry.block_start as usize;
                 let end = start + entry.comp_len as usize;
-                if end > handle.col_mmap.len() {
+                if end > handle.col_bytes.len() {
                     return Err(std::io::Error::new(
                         std::io::ErrorKind::Other,
                         "Compressed block out of bounds",
                     ));
                 }
-                let compressed = &handle.col_mmap[start..end];
-                let codec = Lz4Codec;
-                let decompressed =
-                    CompressionCodec::decompress(&codec, compressed, entry.uncomp_len as usize)
-                        .map_err(|e| {
-                            std::io::Error::new(
-                                std::io::ErrorKind::Other,
-                                format!("decompress: {}", e),
-                            )
-                        })?;
+                let compressed = &handle.col_bytes[start..end];
+                checksum::verify_block(
+                    checksum::configured_mode(),
+                    &handle.col_path,
+                    entry,
+                    compressed,
+                )
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+                let decompressed = codec_for_algo(entry.codec)
+                    .and_then(|codec| codec.decompress(compressed, entry.uncomp_len as usize))
+                    .map_err(|e| std::io::Error::other(format!("decompress: {}", e)))?;
                 Ok(decompressed)
             })?;
 
@@ -169,10 +201,14 @@ impl QueryCaches {
             return Ok(v);
         }
 
-        // Load from disk
-        let path = self.segment_dir(segment_id).join(format!("{}.zones", uid));
-        let metas =
-            ZoneMeta::load(&path).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        // Load from the pinned copy when the segment is pinned, otherwise from disk
+        let file_name = format!("{}.zones", uid);
+        let pinned = self.pinned_segment(segment_id);
+        let metas = match pinned.as_ref().and_then(|seg| seg.file(&file_name)) {
+            Some(bytes) => ZoneMeta::from_bytes(bytes),
+            None => ZoneMeta::load(&self.segment_dir(segment_id).join(file_name)),
+        }
+        .map_err(std::io::Error::other)?;
         let arc = Arc::new(metas);
 
         // Memoize
@@ -248,6 +284,19 @@ impl QueryCaches {
             return Ok(v);
         }
 
+        let pinned_handle = match self.pinned_segment(segment_id) {
+            Some(seg) => ColumnHandle::from_pinned(&seg, uid, field)?,
+            None => None,
+        };
+        if let Some(handle) = pinned_handle {
+            let mut map = self
+                .column_handle_by_key
+                .lock()
+                .unwrap_or_else(|p| p.into_inner());
+            let entry = map.entry(compact_key).or_insert_with(|| Arc::new(handle));
+            return Ok(Arc::clone(entry));
+        }
+
         let segment_dir = self.segment_dir(segment_id);
         let (arc, _outcome) = GlobalColumnHandleCache::instance().get_or_open(
             compact_key,
@@ -320,6 +369,27 @@ impl QueryCaches {
         Ok(arc)
     }
 
+    /// Loads a zone SuRF filter into the global cache past its admission
+    /// policy, for warming it with filters known to be hot.
+    pub fn preload_zone_surf(
+        &self,
+        segment_id: &str,
+        uid: &str,
+        field: &str,
+    ) -> Result<Arc<ZoneSurfFilter>, std::io::Error> {
+        let compact_key = ZoneSurfCacheKey::from_context(self.shard_id, segment_id, uid, field);
+        let segment_dir = self.segment_dir(segment_id);
+        GlobalZoneSurfCache::instance()
+            .preload_from_file(
+                compact_key,
+                segment_id,
+                uid,
+                field,
+                &segment_dir.join(format!("{}_{}.zsrf", uid, field)),
+            )
+            .map(|(arc, _)| arc)
+    }
+
     pub fn get_or_load_zone_xor_filter(
         &self,
         segment_id: &str,
@@ -377,6 +447,15 @@ impl QueryCaches {
         Ok(arc)
     }
 
+    /// Zone index lookups of this query served from a cache, and those read from disk.
+    pub fn zone_index_lookups(&self) -> (u64, u64) {
+        (
+            self.zone_index_hits.load(Ordering::Relaxed),
+            self.zone_index_misses.load(Ordering::Relaxed)
+                + self.zone_index_reloads.load(Ordering::Relaxed),
+        )
+    }
+
     pub fn zone_index_summary_line(&self) -> String {
         let h = self.zone_index_hits.load(Ordering::Relaxed);
         let m = self.zone_index_misses.load(Ordering::Relaxed);
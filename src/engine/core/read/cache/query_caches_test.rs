@@ -3,7 +3,9 @@ use crate::engine::core::column::column_reader::ColumnReader;
 use crate::engine::core::column::compression::{CompressionCodec, Lz4Codec};
 use crate::engine::core::column::format::{ColumnBlockHeader, PhysicalType};
 use crate::engine::core::filter::zone_surf_filter::ZoneSurfFilter;
-use crate::engine::core::read::cache::QueryCaches;
+use crate::engine::core::read::cache::{
+    ColumnBytes, GlobalColumnBlockCache, GlobalPinnedSegmentCache, QueryCaches,
+};
 use crate::engine::core::time::{TemporalCalendarIndex, ZoneTemporalIndex};
 use crate::shared::storage_header::BinaryHeader;
 use crate::test_helpers::factories::column_factory::ColumnFactory;
@@ -708,3 +710,83 @@ fn zone_surf_cache_different_fields_same_uid() {
         assert_eq!(filter.entries.len(), 0);
     }
 }
+
+#[test]
+fn pinned_segment_serves_zone_meta_and_columns_without_disk() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base_dir = tmp.path().to_path_buf();
+    let segment_id = "seg-pinned";
+    let uid = "uid_p";
+    let field = "f";
+    let seg_dir = base_dir.join(segment_id);
+    create_dir_all(&seg_dir).unwrap();
+
+    let meta = ZoneMetaFactory::new()
+        .with("zone_id", 3)
+        .with("uid", uid)
+        .create();
+    ZoneMeta::save(uid, &[meta.clone()], &seg_dir).unwrap();
+
+    let (decomp, offsets) = write_typed_varbytes_block(&["ref", "data"]);
+    let comp = CompressionCodec::compress(&Lz4Codec, &decomp).expect("compress");
+    let block_start = BinaryHeader::TOTAL_LEN as u64;
+    let zone_id = 3u32;
+    let _ = ColumnFactory::new()
+        .with_segment_dir(&seg_dir)
+        .with_uid(uid)
+        .with_field(field)
+        .with_zfc_entry(
+            zone_id,
+            block_start,
+            comp.len() as u32,
+            decomp.len() as u32,
+            offsets.len() as u32,
+            offsets.clone(),
+        )
+        .write_minimal();
+    let mut f = OpenOptions::new()
+        .write(true)
+        .open(seg_dir.join(format!("{}_{}.col", uid, field)))
+        .unwrap();
+    f.seek(SeekFrom::Start(block_start)).unwrap();
+    f.write_all(&comp).unwrap();
+    f.flush().unwrap();
+
+    let pinned: &'static GlobalPinnedSegmentCache =
+        Box::leak(Box::new(GlobalPinnedSegmentCache::new(1 << 20, 1 << 20)));
+
+    // First query pins the segment
+    let caches = QueryCaches::new(base_dir.clone()).with_pinned_segment_cache(pinned);
+    assert_eq!(
+        *caches.get_or_load_zone_meta(segment_id, uid).unwrap(),
+        vec![meta.clone()]
+    );
+    let handle = caches
+        .get_or_load_column_handle(segment_id, uid, field)
+        .unwrap();
+    assert!(matches!(*handle.col_bytes, ColumnBytes::Pinned(_)));
+
+    // Later queries keep working with the files gone from disk
+    std::fs::remove_dir_all(&seg_dir).unwrap();
+    GlobalColumnBlockCache::instance().invalidate_segment(segment_id);
+
+    let caches = QueryCaches::new(base_dir.clone()).with_pinned_segment_cache(pinned);
+    assert_eq!(
+        *caches.get_or_load_zone_meta(segment_id, uid).unwrap(),
+        vec![meta]
+    );
+    let snapshot = ColumnReader::load_for_zone_snapshot(
+        &seg_dir,
+        segment_id,
+        uid,
+        field,
+        zone_id,
+        Some(&caches),
+    )
+    .expect("served from pinned copy");
+    assert_eq!(snapshot.into_strings(), vec!["ref", "data"]);
+
+    let stats = pinned.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!(pinned.pinned_segments().len(), 1);
+}
@@ -0,0 +1,228 @@
+use super::query_caches::QueryCaches;
+use super::{GlobalZoneIndexCache, GlobalZoneSurfCache, GlobalZoneXorFilterCache};
+use crate::engine::core::segment::segment_id_loader::SegmentIdLoader;
+use crate::shared::config::{CONFIG, CacheWarmStart};
+use crate::shared::storage_header::{BinaryHeader, FileKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// File under `engine.data_dir` holding the keys saved at shutdown.
+pub const WARM_START_FILE: &str = "cache_warm_start.bin";
+
+/// `query.cache_warm_start`; off unless configured.
+pub fn configured_mode() -> CacheWarmStart {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|cfg| cfg.cache_warm_start)
+        .unwrap_or(CacheWarmStart::Off)
+}
+
+pub fn configured_path() -> PathBuf {
+    Path::new(&CONFIG.engine.data_dir).join(WARM_START_FILE)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WarmStartKind {
+    ZoneIndex,
+    ZoneSurf,
+    ZoneXorFilter,
+}
+
+/// A cached file, named by its location rather than by the cache key: cache keys
+/// hold interned ids that mean nothing to the next process.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmStartKey {
+    pub kind: WarmStartKind,
+    pub shard_dir: PathBuf,
+    pub segment_id: String,
+    pub uid: String,
+    /// Indexed field; set for SuRF and XOR filter keys
+    pub field: Option<String>,
+}
+
+impl WarmStartKey {
+    /// Key of a file cached from `<shard_dir>/<segment_id>/...`.
+    pub fn from_cached_path(
+        kind: WarmStartKind,
+        path: &Path,
+        uid: &str,
+        field: Option<&str>,
+    ) -> Option<Self> {
+        let segment_dir = path.parent()?;
+        let segment_id = segment_dir.file_name()?.to_str()?.to_string();
+        let shard_dir = segment_dir.parent()?.to_path_buf();
+        Some(Self {
+            kind,
+            shard_dir,
+            segment_id,
+            uid: uid.to_string(),
+            field: field.map(str::to_string),
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WarmStartSummary {
+    pub loaded: usize,
+    /// Keys whose segment is gone, typically compacted away since the save
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// Keys of everything in the zone index, SuRF and XOR filter caches, hottest first.
+pub fn collect_hot_keys() -> Vec<WarmStartKey> {
+    let mut keys = Vec::new();
+    keys.extend(
+        GlobalZoneIndexCache::instance()
+            .entries_by_recency()
+            .iter()
+            .filter_map(|e| {
+                WarmStartKey::from_cached_path(WarmStartKind::ZoneIndex, &e.path, &e.uid, None)
+            }),
+    );
+    keys.extend(
+        GlobalZoneSurfCache::instance()
+            .entries_by_recency()
+            .iter()
+            .filter_map(|e| {
+                WarmStartKey::from_cached_path(
+                    WarmStartKind::ZoneSurf,
+                    &e.path,
+                    &e.uid,
+                    Some(&e.field),
+                )
+            }),
+    );
+    keys.extend(
+        GlobalZoneXorFilterCache::instance()
+            .entries_by_recency()
+            .iter()
+            .filter_map(|e| {
+                WarmStartKey::from_cached_path(
+                    WarmStartKind::ZoneXorFilter,
+                    &e.path,
+                    &e.uid,
+                    Some(&e.field),
+                )
+            }),
+    );
+    keys
+}
+
+/// Writes the keys atomically: a crash mid-save leaves the previous file in place.
+pub fn save(path: &Path, keys: &[WarmStartKey]) -> io::Result<()> {
+    let tmp_path = path.with_extension("bin.tmp");
+    let file = File::create(&tmp_path)?;
+    let mut writer = BufWriter::new(file);
+    BinaryHeader::new(FileKind::CacheWarmStart.magic(), 1, 0).write_to(&mut writer)?;
+    bincode::serialize_into(&mut writer, keys).map_err(io::Error::other)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    drop(writer);
+    std::fs::rename(&tmp_path, path)
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<WarmStartKey>> {
+    let mut file = File::open(path)?;
+    let header = BinaryHeader::read_from(&mut file)?;
+    if header.magic != FileKind::CacheWarmStart.magic() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid magic"));
+    }
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    bincode::deserialize(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Loads the keys into the global caches. Keys of segments no longer on disk are
+/// skipped; files that fail to load are counted and otherwise ignored.
+pub fn warm(keys: &[WarmStartKey]) -> WarmStartSummary {
+    let mut summary = WarmStartSummary::default();
+    let mut live_segments: HashMap<&Path, HashSet<String>> = HashMap::new();
+    let mut caches: HashMap<&Path, QueryCaches> = HashMap::new();
+
+    // Coldest first, so the hottest keys end up most recently used again
+    for key in keys.iter().rev() {
+        let live = live_segments
+            .entry(key.shard_dir.as_path())
+            .or_insert_with(|| {
+                SegmentIdLoader::new(key.shard_dir.clone())
+                    .load()
+                    .into_iter()
+                    .collect()
+            });
+        if !live.contains(&key.segment_id) {
+            summary.skipped += 1;
+            continue;
+        }
+
+        let caches = caches
+            .entry(key.shard_dir.as_path())
+            .or_insert_with(|| QueryCaches::new_abs(key.shard_dir.clone()));
+        let result = match (key.kind, key.field.as_deref()) {
+            (WarmStartKind::ZoneIndex, _) => caches
+                .get_or_load_zone_index(&key.segment_id, &key.uid)
+                .map(|_| ()),
+            (WarmStartKind::ZoneSurf, Some(field)) => caches
+                .preload_zone_surf(&key.segment_id, &key.uid, field)
+                .map(|_| ()),
+            (WarmStartKind::ZoneXorFilter, Some(field)) => caches
+                .get_or_load_zone_xor_filter(&key.segment_id, &key.uid, field)
+                .map(|_| ()),
+            (_, None) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "filter key without a field",
+            )),
+        };
+        match result {
+            Ok(()) => summary.loaded += 1,
+            Err(e) => {
+                debug!(target: "cache::warm_start", ?key, error = %e, "Failed to warm cache key");
+                summary.failed += 1;
+            }
+        }
+    }
+    summary
+}
+
+/// Saves the current hot keys; failures are logged and otherwise ignored.
+pub fn save_hot_keys(path: &Path) {
+    let keys = collect_hot_keys();
+    match save(path, &keys) {
+        Ok(()) => {
+            info!(target: "cache::warm_start", ?path, count = keys.len(), "Saved cache warm-start keys")
+        }
+        Err(e) => {
+            warn!(target: "cache::warm_start", ?path, error = %e, "Failed to save cache warm-start keys")
+        }
+    }
+}
+
+/// Warms the caches from a saved file. A missing or unreadable file only means
+/// starting cold.
+pub fn warm_from_file(path: &Path) -> WarmStartSummary {
+    let keys = match load(path) {
+        Ok(keys) => keys,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            info!(target: "cache::warm_start", ?path, "No cache warm-start keys saved; starting cold");
+            return WarmStartSummary::default();
+        }
+        Err(e) => {
+            warn!(target: "cache::warm_start", ?path, error = %e, "Ignoring unreadable cache warm-start file");
+            return WarmStartSummary::default();
+        }
+    };
+    let summary = warm(&keys);
+    info!(
+        target: "cache::warm_start",
+        loaded = summary.loaded,
+        skipped = summary.skipped,
+        failed = summary.failed,
+        "Warmed caches from saved keys"
+    );
+    summary
+}
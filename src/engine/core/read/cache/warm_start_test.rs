@@ -0,0 +1,135 @@
+use crate::engine::core::read::cache::warm_start::{
+    self, WarmStartKey, WarmStartKind, WarmStartSummary,
+};
+use crate::shared::storage_header::{BinaryHeader, FileKind};
+use crate::test_helpers::factories::zone_index_factory::ZoneIndexFactory;
+use std::path::Path;
+
+fn key(
+    kind: WarmStartKind,
+    shard_dir: &Path,
+    segment_id: &str,
+    field: Option<&str>,
+) -> WarmStartKey {
+    WarmStartKey {
+        kind,
+        shard_dir: shard_dir.to_path_buf(),
+        segment_id: segment_id.to_string(),
+        uid: "uid-w".to_string(),
+        field: field.map(str::to_string),
+    }
+}
+
+#[test]
+fn key_is_derived_from_the_cached_file_path() {
+    let key = WarmStartKey::from_cached_path(
+        WarmStartKind::ZoneSurf,
+        Path::new("/data/shard-3/00042/uid-w_plan.zsrf"),
+        "uid-w",
+        Some("plan"),
+    )
+    .unwrap();
+    assert_eq!(key.shard_dir, Path::new("/data/shard-3"));
+    assert_eq!(key.segment_id, "00042");
+    assert_eq!(key.field.as_deref(), Some("plan"));
+
+    assert!(
+        WarmStartKey::from_cached_path(WarmStartKind::ZoneIndex, Path::new("x.idx"), "u", None)
+            .is_none()
+    );
+}
+
+#[test]
+fn saved_keys_load_back_in_order() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join(warm_start::WARM_START_FILE);
+    let keys = vec![
+        key(WarmStartKind::ZoneIndex, tmp.path(), "00002", None),
+        key(
+            WarmStartKind::ZoneXorFilter,
+            tmp.path(),
+            "00001",
+            Some("plan"),
+        ),
+        key(
+            WarmStartKind::ZoneSurf,
+            tmp.path(),
+            "00001",
+            Some("country"),
+        ),
+    ];
+
+    warm_start::save(&path, &keys).unwrap();
+    assert_eq!(warm_start::load(&path).unwrap(), keys);
+
+    // Saving again replaces the file
+    warm_start::save(&path, &keys[..1]).unwrap();
+    assert_eq!(warm_start::load(&path).unwrap(), keys[..1]);
+}
+
+#[test]
+fn missing_or_corrupt_file_starts_cold() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join(warm_start::WARM_START_FILE);
+    assert_eq!(
+        warm_start::warm_from_file(&path),
+        WarmStartSummary::default()
+    );
+
+    std::fs::write(&path, b"not a warm-start file").unwrap();
+    assert!(warm_start::load(&path).is_err());
+    assert_eq!(
+        warm_start::warm_from_file(&path),
+        WarmStartSummary::default()
+    );
+
+    // Right header, truncated body
+    let mut bytes = Vec::new();
+    BinaryHeader::new(FileKind::CacheWarmStart.magic(), 1, 0)
+        .write_to(&mut bytes)
+        .unwrap();
+    bytes.extend_from_slice(&[7, 0, 0]);
+    std::fs::write(&path, bytes).unwrap();
+    assert!(warm_start::load(&path).is_err());
+    assert_eq!(
+        warm_start::warm_from_file(&path),
+        WarmStartSummary::default()
+    );
+}
+
+#[test]
+fn warming_skips_segments_that_are_gone() {
+    let tmp = tempfile::tempdir().unwrap();
+    let shard_dir = tmp.path().join("shard-0");
+    let segment_dir = shard_dir.join("00001");
+    std::fs::create_dir_all(&segment_dir).unwrap();
+    ZoneIndexFactory::new()
+        .with_entry("ev", "ctx", 1)
+        .create()
+        .write_to_path(&segment_dir.join("uid-w.idx"))
+        .unwrap();
+
+    let keys = vec![
+        key(WarmStartKind::ZoneIndex, &shard_dir, "00001", None),
+        // Compacted away since the save
+        key(WarmStartKind::ZoneIndex, &shard_dir, "00002", None),
+        // Segment still there, filter file is not
+        key(
+            WarmStartKind::ZoneXorFilter,
+            &shard_dir,
+            "00001",
+            Some("plan"),
+        ),
+    ];
+    let path = tmp.path().join(warm_start::WARM_START_FILE);
+    warm_start::save(&path, &keys).unwrap();
+
+    assert_eq!(
+        warm_start::warm_from_file(&path),
+        WarmStartSummary {
+            loaded: 1,
+            skipped: 1,
+            failed: 1,
+        }
+    );
+}
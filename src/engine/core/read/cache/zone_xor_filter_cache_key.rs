@@ -5,8 +5,8 @@ use super::seg_id::{compact_shard_id, parse_segment_id_u64};
 pub struct ZoneXorFilterCacheKey {
     pub shard_id: u16,
     pub segment_id: u64,
-    pub uid_id: u32,
-    pub field_id: u32,
+    pub uid_id: u64,
+    pub field_id: u64,
 }
 
 impl ZoneXorFilterCacheKey {
@@ -0,0 +1,15 @@
+use crate::shared::config::CONFIG;
+
+/// Whether `query.deterministic` is on. In that mode the same data and query
+/// give the same rows in the same order on every run: outputs of shards and of
+/// memtable and segment sources are read one after the other instead of as
+/// they arrive, ORDER BY ties always break by event id, and features driven by
+/// sampling or wall-clock timing are off. It trades away parallel fan-in and
+/// is meant for debugging and property tests.
+pub fn enabled() -> bool {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|cfg| cfg.deterministic)
+        .unwrap_or(false)
+}
@@ -1,4 +1,6 @@
 use crate::engine::core::Event;
+use crate::engine::core::read::order_tiebreak;
+use crate::shared::config::OrderTiebreaker;
 use std::cmp::Ordering;
 
 /// Represents a cached sort key to avoid repeated parsing during sort.
@@ -270,12 +272,19 @@ impl FieldComparator {
             .collect();
 
         // Phase 2: Sort by cached keys (O(n log n), no parsing!)
-        // Use sort_unstable_by for better performance - maintains relative order of equal elements
-        if ascending {
-            decorated.sort_unstable_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
-        } else {
-            decorated.sort_unstable_by(|(key_a, _), (key_b, _)| key_b.cmp(key_a));
-        }
+        // Equal keys fall back to ingest order so ties do not depend on the sort
+        let tiebreak = field != order_tiebreak::TIEBREAK_COLUMN
+            && order_tiebreak::configured() == OrderTiebreaker::EventId;
+        decorated.sort_unstable_by(|(key_a, idx_a), (key_b, idx_b)| {
+            let ord = key_a.cmp(key_b).then_with(|| {
+                if tiebreak {
+                    events[*idx_a].event_id().cmp(&events[*idx_b].event_id())
+                } else {
+                    Ordering::Equal
+                }
+            });
+            if ascending { ord } else { ord.reverse() }
+        });
 
         // Phase 3: Undecorate - Reorder events in-place using permutation (O(n))
         Self::apply_permutation(events, &decorated);
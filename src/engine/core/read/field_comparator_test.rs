@@ -345,3 +345,25 @@ fn case_sensitive_string_comparison() {
     // "Active" < "active" (uppercase letters come before lowercase in ASCII)
     assert_eq!(FieldComparator::compare(&e1, &e2, "status"), Ordering::Less);
 }
+
+#[test]
+fn sort_by_field_breaks_ties_by_event_id() {
+    use crate::engine::core::event::event_id::EventId;
+
+    let mut events: Vec<Event> = [3_u64, 1, 2]
+        .into_iter()
+        .map(|id| {
+            let mut event = Factory::event().with("timestamp", 1000_u64).create();
+            event.set_event_id(EventId::from_raw(id));
+            event
+        })
+        .collect();
+
+    FieldComparator::sort_by_field(&mut events, "timestamp", true);
+    let ids: Vec<u64> = events.iter().map(|e| e.event_id().raw()).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    FieldComparator::sort_by_field(&mut events, "timestamp", false);
+    let ids: Vec<u64> = events.iter().map(|e| e.event_id().raw()).collect();
+    assert_eq!(ids, vec![3, 2, 1]);
+}
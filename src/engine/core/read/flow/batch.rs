@@ -202,6 +202,25 @@ impl ColumnBatch {
         &self.columns
     }
 
+    /// Approximate memory held by the batch: the value slots plus string and
+    /// binary payloads. Used for query memory accounting.
+    pub fn approx_bytes(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|column| {
+                let heap: usize = column
+                    .iter()
+                    .map(|value| match value {
+                        ScalarValue::Utf8(s) => s.len(),
+                        ScalarValue::Binary(b) => b.len(),
+                        _ => 0,
+                    })
+                    .sum();
+                column.len() * std::mem::size_of::<ScalarValue>() + heap
+            })
+            .sum()
+    }
+
     // Note: columns_iter() removed - use columns() and convert to slices as needed
     // The iterator pattern is incompatible with owned Vec return from columns()
 
@@ -214,8 +233,8 @@ impl ColumnBatch {
 impl Drop for ColumnBatch {
     fn drop(&mut self) {
         if let Some(pool) = self.pool.take() {
-            // Direct access - no conversion needed
-            pool.recycle(Arc::clone(&self.schema), self.columns.clone());
+            // The batch is going away, so its columns can move to the pool without a copy
+            pool.recycle(Arc::clone(&self.schema), std::mem::take(&mut self.columns));
         }
     }
 }
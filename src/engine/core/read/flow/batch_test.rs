@@ -1013,3 +1013,19 @@ fn batch_error_display_messages() {
     let err6 = BatchError::BatchFull(100);
     assert!(err6.to_string().contains("batch capacity 100"));
 }
+
+#[test]
+fn column_batch_approx_bytes_counts_slots_and_strings() {
+    let schema = make_schema();
+    let columns: Vec<Vec<ScalarValue>> = vec![
+        vec![ScalarValue::from(json!(1)), ScalarValue::from(json!(2))],
+        vec![
+            ScalarValue::from(json!("alpha")),
+            ScalarValue::from(json!("be")),
+        ],
+    ];
+    let batch = ColumnBatch::new(Arc::clone(&schema), columns, 2, None).expect("batch");
+
+    let slots = 4 * std::mem::size_of::<ScalarValue>();
+    assert_eq!(batch.approx_bytes(), slots + "alpha".len() + "be".len());
+}
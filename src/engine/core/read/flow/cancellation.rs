@@ -0,0 +1,57 @@
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use crate::shared::config::CONFIG;
+
+/// Prefix of the error reported when a query runs past its timeout.
+pub const QUERY_TIMEOUT_ERROR_PREFIX: &str = "Query timed out";
+
+/// Stop signal shared by every operator of a single query, across all shards.
+///
+/// Set once, when the query runs past its timeout or an operator fails it
+/// midway (such as `UNNEST` meeting a non-array value). Aborting the flow tasks
+/// only takes effect at their next await, so segment readers also check the
+/// signal between zones and stop loading blocks for a result no one reads.
+#[derive(Debug, Default)]
+pub struct QueryCancellation {
+    reason: OnceLock<String>,
+}
+
+impl QueryCancellation {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Timeout for queries from `query.query_timeout_ms`; `None` when unset or 0.
+    pub fn configured_timeout() -> Option<Duration> {
+        CONFIG
+            .query
+            .as_ref()
+            .and_then(|cfg| cfg.query_timeout_ms)
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis)
+    }
+
+    /// The message reported for a query stopped after `timeout`.
+    pub fn timeout_message(timeout: Duration) -> String {
+        format!(
+            "{} after {} ms",
+            QUERY_TIMEOUT_ERROR_PREFIX,
+            timeout.as_millis()
+        )
+    }
+
+    /// Stops the query. Only the first reason is kept.
+    pub fn cancel(&self, reason: impl Into<String>) {
+        let _ = self.reason.set(reason.into());
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.reason.get().is_some()
+    }
+
+    /// Why the query was stopped, if it was.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.get().map(String::as_str)
+    }
+}
@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use super::{QUERY_TIMEOUT_ERROR_PREFIX, QueryCancellation};
+
+#[test]
+fn starts_not_cancelled() {
+    let cancellation = QueryCancellation::new();
+    assert!(!cancellation.is_cancelled());
+    assert_eq!(cancellation.reason(), None);
+}
+
+#[test]
+fn first_reason_wins() {
+    let cancellation = QueryCancellation::new();
+    cancellation.cancel("first");
+    cancellation.cancel("second");
+    assert!(cancellation.is_cancelled());
+    assert_eq!(cancellation.reason(), Some("first"));
+}
+
+#[test]
+fn timeout_message_names_the_limit() {
+    let message = QueryCancellation::timeout_message(Duration::from_millis(1500));
+    assert!(message.starts_with(QUERY_TIMEOUT_ERROR_PREFIX));
+    assert_eq!(message, "Query timed out after 1500 ms");
+}
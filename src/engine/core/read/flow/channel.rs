@@ -16,13 +16,16 @@ impl BatchSender {
         &self,
         batch: Arc<ColumnBatch>,
     ) -> Result<(), mpsc::error::SendError<Arc<ColumnBatch>>> {
+        // Count the batch before it becomes visible, or the receiver can
+        // take it off the pending count first and wrap it below zero
         let rows = batch.len() as u64;
-        match self.inner.send(batch).await {
-            Ok(()) => {
+        match self.inner.reserve().await {
+            Ok(permit) => {
                 self.metrics.on_send_success(rows);
+                permit.send(batch);
                 Ok(())
             }
-            Err(err) => Err(err),
+            Err(_) => Err(mpsc::error::SendError(batch)),
         }
     }
 
@@ -31,16 +34,17 @@ impl BatchSender {
         batch: Arc<ColumnBatch>,
     ) -> Result<(), mpsc::error::TrySendError<Arc<ColumnBatch>>> {
         let rows = batch.len() as u64;
-        match self.inner.try_send(batch) {
-            Ok(()) => {
+        match self.inner.try_reserve() {
+            Ok(permit) => {
                 self.metrics.on_send_success(rows);
+                permit.send(batch);
                 Ok(())
             }
-            Err(mpsc::error::TrySendError::Full(batch)) => {
+            Err(mpsc::error::TrySendError::Full(())) => {
                 self.metrics.record_backpressure();
                 Err(mpsc::error::TrySendError::Full(batch))
             }
-            Err(mpsc::error::TrySendError::Closed(batch)) => {
+            Err(mpsc::error::TrySendError::Closed(())) => {
                 Err(mpsc::error::TrySendError::Closed(batch))
             }
         }
@@ -49,6 +53,10 @@ impl BatchSender {
     pub fn capacity(&self) -> usize {
         self.inner.max_capacity()
     }
+
+    pub fn metrics(&self) -> &Arc<FlowMetrics> {
+        &self.metrics
+    }
 }
 
 pub struct BatchReceiver {
@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use super::{BatchPool, FlowMetrics};
+use super::{BatchPool, FlowMetrics, QueryCancellation, QueryMemoryBudget, QueryScanStats};
 
 /// Lightweight metadata captured when constructing a flow, used for observability
 /// and debugging of streaming pipelines.
@@ -12,8 +12,8 @@ pub struct FlowTelemetry {
 }
 
 /// Runtime configuration shared by all operators participating in a streaming
-/// flow. Carries batch sizing, shared buffers, metrics collectors, and optional
-/// spill locations.
+/// flow. Carries batch sizing, shared buffers, metrics collectors, the query's
+/// memory budget, scan statistics and stop signal, and optional spill locations.
 #[derive(Debug, Clone)]
 pub struct FlowContext {
     batch_size: usize,
@@ -21,6 +21,9 @@ pub struct FlowContext {
     metrics: Arc<FlowMetrics>,
     spill_dir: Option<PathBuf>,
     telemetry: FlowTelemetry,
+    memory: Arc<QueryMemoryBudget>,
+    scan_stats: Arc<QueryScanStats>,
+    cancellation: Arc<QueryCancellation>,
 }
 
 impl FlowContext {
@@ -45,9 +48,30 @@ impl FlowContext {
             metrics,
             spill_dir,
             telemetry,
+            memory: QueryMemoryBudget::unlimited(),
+            scan_stats: QueryScanStats::new(),
+            cancellation: QueryCancellation::new(),
         }
     }
 
+    /// Accounts operator memory against `budget` instead of an unlimited one.
+    pub fn with_memory_budget(mut self, budget: Arc<QueryMemoryBudget>) -> Self {
+        self.memory = budget;
+        self
+    }
+
+    /// Records rows read and cache lookups into `stats`, shared with the coordinator.
+    pub fn with_scan_stats(mut self, stats: Arc<QueryScanStats>) -> Self {
+        self.scan_stats = stats;
+        self
+    }
+
+    /// Stops the flow's readers once `cancellation` is set, e.g. on query timeout.
+    pub fn with_cancellation(mut self, cancellation: Arc<QueryCancellation>) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
     pub fn batch_size(&self) -> usize {
         self.batch_size
     }
@@ -67,4 +91,16 @@ impl FlowContext {
     pub fn telemetry(&self) -> &FlowTelemetry {
         &self.telemetry
     }
+
+    pub fn memory(&self) -> &Arc<QueryMemoryBudget> {
+        &self.memory
+    }
+
+    pub fn scan_stats(&self) -> &Arc<QueryScanStats> {
+        &self.scan_stats
+    }
+
+    pub fn cancellation(&self) -> &Arc<QueryCancellation> {
+        &self.cancellation
+    }
 }
@@ -0,0 +1,254 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use tracing::warn;
+
+use crate::shared::config::CONFIG;
+use crate::shared::log_sampler::LogSampler;
+
+use super::FlowOperatorError;
+
+/// Prefix of the error reported when a query is aborted for exceeding its memory budget.
+pub const MEMORY_LIMIT_ERROR_PREFIX: &str = "Query memory limit exceeded";
+
+/// Memory accounting shared by every operator of a single query, across all shards.
+///
+/// Operators never touch the budget directly: they hold a [`MemoryReservation`]
+/// that acquires memory from the budget in chunks, so the shared counter is only
+/// updated once per [`MemoryReservation::CHUNK_BYTES`] of growth. Once any
+/// reservation is refused the budget is marked exceeded and every later request
+/// fails too, so the remaining operators of the query stop early.
+#[derive(Debug, Default)]
+pub struct QueryMemoryBudget {
+    limit: Option<usize>,
+    reserved: AtomicUsize,
+    peak: AtomicUsize,
+    failure: OnceLock<String>,
+}
+
+impl QueryMemoryBudget {
+    pub fn unlimited() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub fn with_limit(limit_bytes: usize) -> Arc<Self> {
+        Arc::new(Self {
+            limit: Some(limit_bytes),
+            ..Self::default()
+        })
+    }
+
+    /// Budget for a query from `[query.memory]`, applying the user's override if any.
+    pub fn from_config(user_id: Option<&str>) -> Arc<Self> {
+        let Some(cfg) = CONFIG.query.as_ref().and_then(|cfg| cfg.memory.as_ref()) else {
+            return Self::unlimited();
+        };
+        let limit = user_id
+            .and_then(|user_id| cfg.users.get(user_id))
+            .and_then(|user_cfg| user_cfg.max_bytes)
+            .or(cfg.max_bytes);
+        match limit {
+            Some(limit) => Self::with_limit(limit),
+            None => Self::unlimited(),
+        }
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Bytes currently reserved by all consumers.
+    pub fn reserved(&self) -> usize {
+        self.reserved.load(Ordering::Relaxed)
+    }
+
+    /// Highest number of bytes reserved at once.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.failure.get().is_some()
+    }
+
+    /// Message of the first refused reservation, if the query ran out of memory.
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.get().map(String::as_str)
+    }
+
+    /// Opens a reservation for an operator; `consumer` names it in error messages.
+    pub fn reservation(self: &Arc<Self>, consumer: &'static str) -> MemoryReservation {
+        MemoryReservation {
+            budget: Arc::clone(self),
+            consumer,
+            size: 0,
+            acquired: 0,
+            cap: None,
+        }
+    }
+
+    fn try_acquire(&self, bytes: usize) -> bool {
+        if self.is_exceeded() {
+            return false;
+        }
+        let previous = self.reserved.fetch_add(bytes, Ordering::Relaxed);
+        let total = previous + bytes;
+        if self.limit.is_some_and(|limit| total > limit) {
+            self.reserved.fetch_sub(bytes, Ordering::Relaxed);
+            return false;
+        }
+        self.peak.fetch_max(total, Ordering::Relaxed);
+        true
+    }
+
+    fn release(&self, bytes: usize) {
+        if bytes > 0 {
+            self.reserved.fetch_sub(bytes, Ordering::Relaxed);
+        }
+    }
+
+    fn fail(&self, error: &MemoryLimitExceeded) {
+        if self.failure.set(error.to_string()).is_ok() {
+            static MEMORY_LIMIT_LOG: LogSampler = LogSampler::new();
+            if let Some(dropped) = MEMORY_LIMIT_LOG.admit(tracing::Level::WARN) {
+                warn!(
+                    target: "sneldb::query::memory",
+                    consumer = error.consumer,
+                    requested = error.requested,
+                    limit = error.limit,
+                    dropped,
+                    "Aborting query: memory limit exceeded"
+                );
+            }
+        }
+    }
+}
+
+/// Memory held by one operator, accounted against the query's [`QueryMemoryBudget`].
+///
+/// Growth within already acquired chunks is a local addition. A refused
+/// [`try_grow`](Self::try_grow) leaves the reservation unchanged: an operator
+/// that can spill (see `FlowContext::spill_dir`) may free memory, `shrink`, and
+/// retry, while operators that cannot spill abort with the returned error.
+/// Dropping the reservation returns its memory to the budget.
+#[derive(Debug)]
+pub struct MemoryReservation {
+    budget: Arc<QueryMemoryBudget>,
+    consumer: &'static str,
+    size: usize,
+    acquired: usize,
+    cap: Option<usize>,
+}
+
+impl MemoryReservation {
+    /// Granularity at which memory is taken from the shared budget.
+    pub const CHUNK_BYTES: usize = 256 * 1024;
+
+    /// Refuses growth past `cap_bytes` even while the query budget has room.
+    /// A refusal fails the query like an exhausted budget, reporting the cap as
+    /// the limit.
+    pub fn with_cap(mut self, cap_bytes: usize) -> Self {
+        self.cap = Some(cap_bytes);
+        self
+    }
+
+    /// Bytes currently accounted by this reservation.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn try_grow(&mut self, bytes: usize) -> Result<(), MemoryLimitExceeded> {
+        let size = self.size.saturating_add(bytes);
+        if let Some(cap) = self.cap
+            && size > cap
+        {
+            let error = MemoryLimitExceeded {
+                consumer: self.consumer,
+                requested: bytes,
+                reserved: self.size,
+                limit: cap,
+            };
+            self.budget.fail(&error);
+            return Err(error);
+        }
+        if size > self.acquired {
+            let missing = size - self.acquired;
+            let chunked = missing.next_multiple_of(Self::CHUNK_BYTES);
+            // Near the limit a full chunk may not fit while the exact amount still does
+            let granted = [chunked, missing]
+                .into_iter()
+                .find(|amount| self.budget.try_acquire(*amount));
+            match granted {
+                Some(amount) => self.acquired += amount,
+                None => {
+                    let error = MemoryLimitExceeded {
+                        consumer: self.consumer,
+                        requested: bytes,
+                        reserved: self.budget.reserved(),
+                        limit: self.budget.limit.unwrap_or(usize::MAX),
+                    };
+                    self.budget.fail(&error);
+                    return Err(error);
+                }
+            }
+        }
+        self.size = size;
+        Ok(())
+    }
+
+    /// Gives back memory, returning whole chunks to the budget once more than one
+    /// chunk is unused.
+    pub fn shrink(&mut self, bytes: usize) {
+        self.size = self.size.saturating_sub(bytes);
+        let spare = self.acquired - self.size;
+        if spare > Self::CHUNK_BYTES {
+            let released = spare - spare % Self::CHUNK_BYTES;
+            self.budget.release(released);
+            self.acquired -= released;
+        }
+    }
+
+    /// Grows or shrinks the reservation to `bytes`.
+    pub fn resize(&mut self, bytes: usize) -> Result<(), MemoryLimitExceeded> {
+        if bytes >= self.size {
+            self.try_grow(bytes - self.size)
+        } else {
+            self.shrink(self.size - bytes);
+            Ok(())
+        }
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.budget.release(self.acquired);
+    }
+}
+
+/// A reservation was refused because the query would exceed its memory budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryLimitExceeded {
+    pub consumer: &'static str,
+    pub requested: usize,
+    pub reserved: usize,
+    pub limit: usize,
+}
+
+impl fmt::Display for MemoryLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} needed {} more bytes with {} of {} bytes in use",
+            MEMORY_LIMIT_ERROR_PREFIX, self.consumer, self.requested, self.reserved, self.limit
+        )
+    }
+}
+
+impl std::error::Error for MemoryLimitExceeded {}
+
+impl From<MemoryLimitExceeded> for FlowOperatorError {
+    fn from(value: MemoryLimitExceeded) -> Self {
+        FlowOperatorError::Operator(value.to_string())
+    }
+}
@@ -0,0 +1,92 @@
+use super::{MEMORY_LIMIT_ERROR_PREFIX, MemoryReservation, QueryMemoryBudget};
+
+const CHUNK: usize = MemoryReservation::CHUNK_BYTES;
+
+#[test]
+fn reservation_acquires_whole_chunks_from_budget() {
+    let budget = QueryMemoryBudget::with_limit(10 * CHUNK);
+    let mut reservation = budget.reservation("test");
+
+    reservation.try_grow(10).unwrap();
+    reservation.try_grow(CHUNK - 10).unwrap();
+    assert_eq!(reservation.size(), CHUNK);
+    assert_eq!(budget.reserved(), CHUNK);
+
+    reservation.try_grow(1).unwrap();
+    assert_eq!(budget.reserved(), 2 * CHUNK);
+}
+
+#[test]
+fn reservation_falls_back_to_exact_amount_near_limit() {
+    let budget = QueryMemoryBudget::with_limit(100);
+    let mut reservation = budget.reservation("test");
+
+    reservation.try_grow(60).unwrap();
+    assert_eq!(budget.reserved(), 60);
+
+    let err = reservation.try_grow(50).unwrap_err();
+    assert_eq!(err.consumer, "test");
+    assert_eq!(reservation.size(), 60);
+    assert!(err.to_string().starts_with(MEMORY_LIMIT_ERROR_PREFIX));
+}
+
+#[test]
+fn refused_reservation_fails_the_whole_query() {
+    let budget = QueryMemoryBudget::with_limit(CHUNK);
+    let mut aggregate = budget.reservation("aggregate");
+    let mut sort = budget.reservation("sort");
+
+    assert!(aggregate.try_grow(CHUNK + 1).is_err());
+    assert!(budget.is_exceeded());
+    assert!(budget.failure().unwrap().contains("aggregate needed"));
+
+    // Memory is still available, but the query is already aborted
+    assert!(sort.try_grow(1).is_err());
+    assert!(budget.failure().unwrap().contains("aggregate needed"));
+}
+
+#[test]
+fn shrink_and_drop_return_memory() {
+    let budget = QueryMemoryBudget::with_limit(100 * CHUNK);
+    let mut reservation = budget.reservation("test");
+
+    reservation.resize(10 * CHUNK).unwrap();
+    assert_eq!(budget.reserved(), 10 * CHUNK);
+
+    reservation.resize(CHUNK / 2).unwrap();
+    assert_eq!(reservation.size(), CHUNK / 2);
+    assert_eq!(budget.reserved(), CHUNK);
+
+    drop(reservation);
+    assert_eq!(budget.reserved(), 0);
+    assert_eq!(budget.peak(), 10 * CHUNK);
+}
+
+#[test]
+fn unlimited_budget_tracks_usage() {
+    let budget = QueryMemoryBudget::unlimited();
+    let mut reservation = budget.reservation("test");
+
+    reservation.try_grow(usize::MAX / 2).unwrap();
+
+    assert_eq!(budget.limit(), None);
+    assert!(!budget.is_exceeded());
+    assert!(budget.reserved() >= usize::MAX / 2);
+}
+
+#[test]
+fn capped_reservation_fails_below_an_unlimited_budget() {
+    let budget = QueryMemoryBudget::unlimited();
+    let mut reservation = budget.reservation("DEDUP BY").with_cap(100);
+
+    reservation.try_grow(100).unwrap();
+    let err = reservation.try_grow(1).unwrap_err();
+    assert_eq!((err.reserved, err.limit), (100, 100));
+    assert_eq!(reservation.size(), 100);
+    assert!(
+        budget
+            .failure()
+            .unwrap()
+            .contains("DEDUP BY needed 1 more bytes")
+    );
+}
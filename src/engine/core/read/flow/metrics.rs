@@ -1,5 +1,9 @@
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use tracing::info;
+
+use super::profiler::{OperatorKind, OperatorProfiler, OperatorScope};
 
 #[derive(Debug, Default)]
 pub struct FlowMetrics {
@@ -10,6 +14,7 @@ pub struct FlowMetrics {
     pending_batches: AtomicU64,
     backpressure_events: AtomicU64,
     peak_pending: AtomicU64,
+    profiler: OnceLock<Arc<OperatorProfiler>>,
 }
 
 impl FlowMetrics {
@@ -61,6 +66,20 @@ impl FlowMetrics {
         self.backpressure_events.load(Ordering::Relaxed)
     }
 
+    /// Attaches a sampling profiler to this flow. Only the first profiler is kept.
+    pub fn enable_profiling(&self, profiler: Arc<OperatorProfiler>) {
+        let _ = self.profiler.set(profiler);
+    }
+
+    pub fn profiler(&self) -> Option<&Arc<OperatorProfiler>> {
+        self.profiler.get()
+    }
+
+    /// Marks an operator of `kind` active for the profiler, if profiling is enabled.
+    pub fn operator_scope(&self, kind: OperatorKind) -> Option<OperatorScope<'_>> {
+        self.profiler.get().map(|profiler| profiler.enter(kind))
+    }
+
     fn pending_inc(&self) {
         let pending = self.pending_batches.fetch_add(1, Ordering::Relaxed) + 1;
         loop {
@@ -82,3 +101,20 @@ impl FlowMetrics {
         self.pending_batches.fetch_sub(1, Ordering::Relaxed);
     }
 }
+
+impl Drop for FlowMetrics {
+    fn drop(&mut self) {
+        if let Some(profiler) = self.profiler.get() {
+            profiler.stop();
+            let report = profiler.report();
+            info!(
+                target: "sneldb::query::profile",
+                samples = report.total_samples,
+                idle_samples = report.idle_samples,
+                rows = self.total_received_rows(),
+                "Flow operator profile: {}",
+                report
+            );
+        }
+    }
+}
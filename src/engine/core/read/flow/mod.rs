@@ -1,29 +1,43 @@
 mod batch;
+mod cancellation;
 mod channel;
 mod context;
+mod memory;
 mod metrics;
 mod operator;
 pub mod operators;
 mod ordered_merger;
 mod pool;
+mod profiler;
+mod scan_stats;
 
 pub mod shard_pipeline;
 
 pub use batch::{BatchError, BatchSchema, ColumnBatch, ColumnBatchBuilder};
+pub use cancellation::{QUERY_TIMEOUT_ERROR_PREFIX, QueryCancellation};
 pub use channel::{BatchReceiver, BatchSender, FlowChannel};
 pub use context::{FlowContext, FlowTelemetry};
+pub use memory::{
+    MEMORY_LIMIT_ERROR_PREFIX, MemoryLimitExceeded, MemoryReservation, QueryMemoryBudget,
+};
 pub use metrics::FlowMetrics;
 pub use operator::{FlowOperator, FlowOperatorError, FlowSource};
 pub use ordered_merger::OrderedStreamMerger;
-pub use pool::BatchPool;
+pub use pool::{BatchPool, BatchPoolStats};
+pub use profiler::{OperatorKind, OperatorProfiler, OperatorScope, ProfileReport};
+pub use scan_stats::QueryScanStats;
 
 #[cfg(test)]
 mod batch_test;
 #[cfg(test)]
+mod cancellation_test;
+#[cfg(test)]
 mod channel_test;
 #[cfg(test)]
 mod context_test;
 #[cfg(test)]
+mod memory_test;
+#[cfg(test)]
 mod metrics_test;
 #[cfg(test)]
 mod operator_test;
@@ -32,4 +46,8 @@ mod ordered_merger_test;
 #[cfg(test)]
 mod pool_test;
 #[cfg(test)]
+mod profiler_test;
+#[cfg(test)]
+mod scan_stats_test;
+#[cfg(test)]
 mod shard_pipeline_test;
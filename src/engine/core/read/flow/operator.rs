@@ -24,6 +24,8 @@ pub trait FlowOperator: Send {
 #[derive(Debug)]
 pub enum FlowOperatorError {
     ChannelClosed,
+    /// The query was stopped, e.g. because it ran past its timeout
+    Cancelled,
     Operator(String),
     Batch(String),
 }
@@ -38,6 +40,7 @@ impl fmt::Display for FlowOperatorError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             FlowOperatorError::ChannelClosed => write!(f, "downstream channel closed"),
+            FlowOperatorError::Cancelled => write!(f, "query cancelled"),
             FlowOperatorError::Operator(message) => write!(f, "operator error: {}", message),
             FlowOperatorError::Batch(message) => write!(f, "batch error: {}", message),
         }
@@ -4,7 +4,7 @@ use crate::engine::core::read::cache::DecompressedBlock;
 use crate::engine::core::read::flow::FlowOperatorError;
 use crate::engine::core::read::flow::batch::ColumnBatch;
 use crate::engine::core::read::sink::AggregateSink;
-use crate::engine::types::ScalarValue;
+use crate::engine::types::{ScalarValue, decimal};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
@@ -58,7 +58,9 @@ impl ColumnConverter {
                 | AggregateOpSpec::Avg { field }
                 | AggregateOpSpec::Min { field }
                 | AggregateOpSpec::Max { field }
-                | AggregateOpSpec::CountUnique { field } => {
+                | AggregateOpSpec::CountUnique { field }
+                | AggregateOpSpec::CountDistinct { field }
+                | AggregateOpSpec::Percentile { field, .. } => {
                     needed.insert(field.clone());
                 }
             }
@@ -145,6 +147,13 @@ impl ColumnConverter {
                     ranges.push((start, s.len()));
                     continue;
                 }
+                ScalarValue::Decimal(m, scale) => {
+                    let s = decimal::format(*m, *scale);
+                    let start = bytes.len();
+                    bytes.extend_from_slice(s.as_bytes());
+                    ranges.push((start, s.len()));
+                    continue;
+                }
                 ScalarValue::Timestamp(ts) => {
                     let s = ts.to_string();
                     let start = bytes.len();
@@ -152,6 +161,13 @@ impl ColumnConverter {
                     ranges.push((start, s.len()));
                     continue;
                 }
+                ScalarValue::Date(_) | ScalarValue::Time(_) | ScalarValue::Uuid(_) => {
+                    let s = value.to_string_repr();
+                    let start = bytes.len();
+                    bytes.extend_from_slice(s.as_bytes());
+                    ranges.push((start, s.len()));
+                    continue;
+                }
                 ScalarValue::Binary(_) => "",
                 ScalarValue::Null => "",
             };
@@ -74,7 +74,7 @@ impl PartialConverter {
 
         if group_by.is_some() {
             for val in &group_key.groups {
-                row.push(ScalarValue::Utf8(val.clone()));
+                row.push(val.clone().map_or(ScalarValue::Null, ScalarValue::Utf8));
             }
         }
 
@@ -113,6 +113,17 @@ impl PartialConverter {
                 })?;
                 Ok(ScalarValue::Utf8(json_str))
             }
+            (
+                AggregateOpSpec::CountUnique { .. } | AggregateOpSpec::CountDistinct { .. },
+                AggState::CountUniqueSketch { sketch },
+            ) => {
+                // An object rather than an array tells the merger this is a sketch
+                let json_str = serde_json::json!({ "hll": sketch.to_hex() }).to_string();
+                Ok(ScalarValue::Utf8(json_str))
+            }
+            (AggregateOpSpec::Percentile { .. }, AggState::Percentile { digest }) => {
+                Ok(ScalarValue::Utf8(digest.to_json()))
+            }
             (AggregateOpSpec::Total { .. }, AggState::Sum { sum }) => Ok(ScalarValue::Int64(*sum)),
             (AggregateOpSpec::Avg { .. }, AggState::Avg { .. }) => {
                 // Avg is handled separately in build_row
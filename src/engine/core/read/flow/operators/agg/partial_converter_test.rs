@@ -38,7 +38,10 @@ fn create_schema(columns: Vec<(&str, &str)>) -> Arc<BatchSchema> {
 }
 
 fn make_group_key(bucket: Option<u64>, groups: Vec<String>) -> GroupKey {
-    GroupKey { bucket, groups }
+    GroupKey {
+        bucket,
+        groups: groups.into_iter().map(Some).collect(),
+    }
 }
 
 // Basic conversion tests -----------------------------------------------------
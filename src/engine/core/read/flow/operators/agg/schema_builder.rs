@@ -1,4 +1,6 @@
-use crate::engine::core::read::aggregate::plan::{AggregateOpSpec, AggregatePlan};
+use crate::engine::core::read::aggregate::plan::{
+    AggregateOpSpec, AggregatePlan, percentile_column,
+};
 use crate::engine::core::read::result::ColumnSpec;
 
 /// Builds output schema for aggregate operations
@@ -52,6 +54,18 @@ impl SchemaBuilder {
                     logical_type: "String".to_string(),
                 });
             }
+            AggregateOpSpec::CountDistinct { field } => {
+                columns.push(ColumnSpec {
+                    name: format!("count_distinct_{}_sketch", field),
+                    logical_type: "String".to_string(),
+                });
+            }
+            AggregateOpSpec::Percentile { field, percentile } => {
+                columns.push(ColumnSpec {
+                    name: format!("{}_digest", percentile_column(field, *percentile)),
+                    logical_type: "String".to_string(),
+                });
+            }
             AggregateOpSpec::Total { field } => {
                 columns.push(ColumnSpec {
                     name: format!("total_{}", field),
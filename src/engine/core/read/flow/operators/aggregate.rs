@@ -3,7 +3,10 @@ use std::sync::Arc;
 use super::super::{BatchReceiver, BatchSender};
 use crate::engine::core::QueryPlan;
 use crate::engine::core::read::aggregate::plan::AggregatePlan;
-use crate::engine::core::read::flow::{BatchSchema, FlowContext, FlowOperator, FlowOperatorError};
+use crate::engine::core::read::aggregate::zone_summary_plan::{ZoneSummarySlot, absorb_summarized};
+use crate::engine::core::read::flow::{
+    BatchSchema, FlowContext, FlowOperator, FlowOperatorError, OperatorKind,
+};
 use crate::engine::core::read::result::ColumnSpec;
 use crate::engine::core::read::sink::AggregateSink;
 
@@ -19,6 +22,7 @@ pub struct AggregateOpConfig {
 pub struct AggregateOp {
     config: AggregateOpConfig,
     cached_output_schema: Option<Arc<BatchSchema>>,
+    zone_summaries: Option<ZoneSummarySlot>,
 }
 
 impl AggregateOp {
@@ -26,9 +30,17 @@ impl AggregateOp {
         Self {
             config,
             cached_output_schema: None,
+            zone_summaries: None,
         }
     }
 
+    /// Folds in the zones the segment scan answered from zone summaries once
+    /// the input is exhausted.
+    pub fn with_zone_summaries(mut self, slot: Option<ZoneSummarySlot>) -> Self {
+        self.zone_summaries = slot;
+        self
+    }
+
     fn get_output_schema(&mut self) -> Result<Arc<BatchSchema>, FlowOperatorError> {
         if let Some(ref schema) = self.cached_output_schema {
             return Ok(Arc::clone(schema));
@@ -59,11 +71,14 @@ impl FlowOperator for AggregateOp {
 
         // Cache needed columns once - they don't change per batch
         let needed_columns = ColumnConverter::determine_needed_columns(&sink);
+        let mut memory = ctx.memory().reservation("aggregate");
+        let mut rows_since_measure = 0usize;
 
         while let Some(batch_arc) = input.recv().await {
             if batch_arc.is_empty() {
                 continue;
             }
+            let _scope = ctx.metrics().operator_scope(OperatorKind::Aggregate);
 
             let schema = batch_arc.schema();
             let column_names: Vec<String> =
@@ -75,9 +90,27 @@ impl FlowOperator for AggregateOp {
             let row_count = batch_arc.len();
 
             sink.on_column_slice(0, row_count, &columns_map);
+
+            // Measuring walks every group, so re-measure only after absorbing at least
+            // as many rows as there are groups: amortized O(1) per row.
+            rows_since_measure += row_count;
+            if rows_since_measure >= sink.group_count_debug() {
+                memory.resize(sink.approx_memory_bytes())?;
+                rows_since_measure = 0;
+            }
         }
 
-        let partial = sink.into_partial();
+        let metrics = Arc::clone(ctx.metrics());
+        let _scope = metrics.operator_scope(OperatorKind::Aggregate);
+        let mut partial = sink.into_partial();
+        // The scan fills the slot before closing the input, so it is complete here
+        if let Some(summarized) = self
+            .zone_summaries
+            .as_ref()
+            .and_then(|slot| slot.lock().unwrap_or_else(|p| p.into_inner()).take())
+        {
+            absorb_summarized(&mut partial, summarized);
+        }
         let schema = self.get_output_schema()?;
 
         if partial.groups.is_empty() {
@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+use super::super::{BatchReceiver, BatchSender};
+use crate::command::types::{DedupKeep, DedupSpec, OrderSpec};
+use crate::engine::core::read::flow::{BatchSchema, FlowContext, FlowOperator, FlowOperatorError};
+use crate::engine::core::read::order_tiebreak;
+use crate::engine::types::ScalarValue;
+use crate::shared::config::CONFIG;
+
+/// Memory a `DEDUP BY` may hold when `query.dedup_max_bytes` is not set.
+pub const DEFAULT_DEDUP_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+/// Name the operator's memory is reported under, including in limit errors.
+const DEDUP_CONSUMER: &str = "DEDUP BY";
+
+pub fn dedup_max_bytes() -> usize {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|q| q.dedup_max_bytes)
+        .unwrap_or(DEFAULT_DEDUP_MAX_BYTES)
+}
+
+#[derive(Clone)]
+pub struct DedupOpConfig {
+    pub spec: DedupSpec,
+    pub time_field: String,
+    pub order_by: Option<OrderSpec>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+    pub max_bytes: usize,
+}
+
+/// Keeps one row per distinct key (`DEDUP BY key[, ...] KEEP FIRST|LATEST`).
+///
+/// Rows are hashed on their key columns as they stream in, and only the
+/// current winner of each key is held: the earliest row by the time field for
+/// `KEEP FIRST`, the most recent for `KEEP LATEST`, with ties resolved by the
+/// order tiebreaker. Rows with a null key value are never duplicates and are
+/// kept. Winners are emitted in the order their key was first seen unless an
+/// `ORDER BY` is given, then `OFFSET`/`LIMIT` apply.
+///
+/// Held rows count against the query's memory budget and are capped at
+/// `max_bytes`; going over either fails the query rather than emitting rows
+/// that might still have duplicates.
+pub struct DedupOp {
+    config: DedupOpConfig,
+    schema: Arc<BatchSchema>,
+    key_indices: Vec<usize>,
+    time_index: usize,
+    tiebreak: Option<usize>,
+    order: Option<(usize, bool)>,
+}
+
+impl DedupOp {
+    /// Resolves the key, time and order columns against the input schema.
+    pub fn new(config: DedupOpConfig, schema: Arc<BatchSchema>) -> Result<Self, String> {
+        let key_indices = config
+            .spec
+            .keys
+            .iter()
+            .map(|key| column_index(&schema, key, "DEDUP BY"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let time_index = column_index(&schema, &config.time_field, "DEDUP BY time")?;
+        let order = match &config.order_by {
+            Some(order) => Some((column_index(&schema, &order.field, "order by")?, order.desc)),
+            None => None,
+        };
+        let tiebreak = order_tiebreak::tiebreak_index(&schema, time_index);
+        Ok(Self {
+            config,
+            schema,
+            key_indices,
+            time_index,
+            tiebreak,
+            order,
+        })
+    }
+
+    /// Whether `row` should replace `held` as the winner of their key.
+    fn wins_over(&self, row: &[ScalarValue], held: &[ScalarValue]) -> bool {
+        let ord = row[self.time_index]
+            .compare(&held[self.time_index])
+            .then_with(|| order_tiebreak::break_tie(row, held, self.tiebreak));
+        match self.config.spec.keep {
+            DedupKeep::First => ord == Ordering::Less,
+            DedupKeep::Latest => ord == Ordering::Greater,
+        }
+    }
+
+    fn into_output(self, mut rows: Vec<Vec<ScalarValue>>) -> Vec<Vec<ScalarValue>> {
+        if let Some((order_index, desc)) = self.order {
+            let tiebreak = order_tiebreak::tiebreak_index(&self.schema, order_index);
+            rows.sort_by(|a, b| {
+                let ord = a[order_index]
+                    .compare(&b[order_index])
+                    .then_with(|| order_tiebreak::break_tie(a, b, tiebreak));
+                if desc { ord.reverse() } else { ord }
+            });
+        }
+        rows.into_iter()
+            .skip(self.config.offset)
+            .take(self.config.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl FlowOperator for DedupOp {
+    async fn run(
+        self,
+        mut input: BatchReceiver,
+        output: BatchSender,
+        ctx: Arc<FlowContext>,
+    ) -> Result<(), FlowOperatorError> {
+        let mut memory = ctx
+            .memory()
+            .reservation(DEDUP_CONSUMER)
+            .with_cap(self.config.max_bytes);
+        let mut rows: Vec<Vec<ScalarValue>> = Vec::new();
+        let mut slots: HashMap<Vec<ScalarValue>, usize> = HashMap::new();
+
+        while let Some(batch) = input.recv().await {
+            for row_idx in 0..batch.len() {
+                let row = batch.row(row_idx)?;
+                let key: Vec<ScalarValue> =
+                    self.key_indices.iter().map(|&i| row[i].clone()).collect();
+
+                if key.iter().any(ScalarValue::is_null) {
+                    memory.try_grow(row_bytes(&row))?;
+                    rows.push(row);
+                    continue;
+                }
+
+                match slots.entry(key) {
+                    Entry::Occupied(slot) => {
+                        let held = &mut rows[*slot.get()];
+                        if self.wins_over(&row, held) {
+                            memory.try_grow(row_bytes(&row))?;
+                            memory.shrink(row_bytes(held));
+                            *held = row;
+                        }
+                    }
+                    Entry::Vacant(slot) => {
+                        memory.try_grow(row_bytes(&row) + row_bytes(slot.key()))?;
+                        slot.insert(rows.len());
+                        rows.push(row);
+                    }
+                }
+            }
+        }
+        drop(slots);
+
+        let schema = Arc::clone(&self.schema);
+        let mut builder = ctx.pool().acquire(Arc::clone(&schema));
+        for row in self.into_output(rows) {
+            builder.push_row(&row)?;
+            if builder.is_full() {
+                let full = std::mem::replace(&mut builder, ctx.pool().acquire(Arc::clone(&schema)));
+                output
+                    .send(Arc::new(full.finish()?))
+                    .await
+                    .map_err(|_| FlowOperatorError::ChannelClosed)?;
+            }
+        }
+        if builder.len() > 0 {
+            output
+                .send(Arc::new(builder.finish()?))
+                .await
+                .map_err(|_| FlowOperatorError::ChannelClosed)?;
+        }
+        Ok(())
+    }
+}
+
+fn column_index(schema: &BatchSchema, field: &str, role: &str) -> Result<usize, String> {
+    schema
+        .columns()
+        .iter()
+        .position(|col| col.name == field)
+        .ok_or_else(|| format!("{} field '{}' not found in stream schema", role, field))
+}
+
+/// Approximate memory of a held row, counted like `ColumnBatch::approx_bytes`.
+fn row_bytes(row: &[ScalarValue]) -> usize {
+    row.iter()
+        .map(|value| {
+            std::mem::size_of::<ScalarValue>()
+                + match value {
+                    ScalarValue::Utf8(s) => s.len(),
+                    ScalarValue::Binary(b) => b.len(),
+                    _ => 0,
+                }
+        })
+        .sum()
+}
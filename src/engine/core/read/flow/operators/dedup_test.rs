@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use crate::command::types::{DedupKeep, DedupSpec, OrderSpec};
+use crate::engine::core::read::flow::{
+    BatchPool, BatchSchema, FlowChannel, FlowContext, FlowMetrics, FlowOperator, FlowTelemetry,
+    QueryMemoryBudget,
+};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::types::ScalarValue;
+
+use super::{DedupOp, DedupOpConfig};
+
+fn test_context(memory: Arc<QueryMemoryBudget>) -> Arc<FlowContext> {
+    let metrics = FlowMetrics::new();
+    let pool = BatchPool::new(4).unwrap();
+    Arc::new(
+        FlowContext::new(4, pool, metrics, None::<&str>, FlowTelemetry::default())
+            .with_memory_budget(memory),
+    )
+}
+
+fn build_schema() -> Arc<BatchSchema> {
+    let column = |name: &str, logical_type: &str| ColumnSpec {
+        name: name.into(),
+        logical_type: logical_type.into(),
+    };
+    Arc::new(
+        BatchSchema::new(vec![
+            column("key", "String"),
+            column("timestamp", "Timestamp"),
+            column("event_id", "Integer"),
+            column("amount", "Integer"),
+        ])
+        .unwrap(),
+    )
+}
+
+fn config(keep: DedupKeep) -> DedupOpConfig {
+    DedupOpConfig {
+        spec: DedupSpec {
+            keys: vec!["key".into()],
+            keep,
+        },
+        time_field: "timestamp".into(),
+        order_by: None,
+        limit: None,
+        offset: 0,
+        max_bytes: usize::MAX,
+    }
+}
+
+/// Rows of (key, timestamp, event_id, amount); a `None` key is null.
+fn row(key: Option<&str>, ts: i64, event_id: i64, amount: i64) -> Vec<ScalarValue> {
+    vec![
+        key.map_or(ScalarValue::Null, |k| ScalarValue::Utf8(k.into())),
+        ScalarValue::Timestamp(ts),
+        ScalarValue::Int64(event_id),
+        ScalarValue::Int64(amount),
+    ]
+}
+
+async fn run_dedup(
+    config: DedupOpConfig,
+    rows: Vec<Vec<ScalarValue>>,
+    memory: Arc<QueryMemoryBudget>,
+) -> (Result<(), String>, Vec<i64>) {
+    let ctx = test_context(memory);
+    let schema = build_schema();
+    let op = DedupOp::new(config, Arc::clone(&schema)).unwrap();
+    let (tx, rx) = FlowChannel::bounded(16, Arc::clone(ctx.metrics()));
+    let (out_tx, mut out_rx) = FlowChannel::bounded(16, Arc::clone(ctx.metrics()));
+
+    // Split the input over batches so duplicates arrive in different batches
+    for chunk in rows.chunks(3) {
+        let mut builder = ctx.pool().acquire(Arc::clone(&schema));
+        for row in chunk {
+            builder.push_row(row).unwrap();
+        }
+        tx.send(Arc::new(builder.finish().unwrap())).await.unwrap();
+    }
+    drop(tx);
+
+    let result = op.run(rx, out_tx, ctx).await.map_err(|e| e.to_string());
+    let mut amounts = Vec::new();
+    while let Some(batch) = out_rx.recv().await {
+        for idx in 0..batch.len() {
+            amounts.push(batch.row(idx).unwrap()[3].as_i64().unwrap());
+        }
+    }
+    (result, amounts)
+}
+
+fn payments() -> Vec<Vec<ScalarValue>> {
+    vec![
+        row(Some("b"), 20, 5, 1),
+        row(Some("a"), 30, 1, 2),
+        row(Some("a"), 10, 2, 3),
+        row(None, 5, 3, 4),
+        row(Some("b"), 20, 4, 5),
+        row(Some("a"), 40, 6, 6),
+        row(None, 5, 7, 8),
+    ]
+}
+
+#[tokio::test]
+async fn keep_first_picks_earliest_row_per_key_with_event_id_tiebreak() {
+    let (result, amounts) = run_dedup(
+        config(DedupKeep::First),
+        payments(),
+        QueryMemoryBudget::unlimited(),
+    )
+    .await;
+    result.unwrap();
+    // Keys in first-seen order; rows without a key are all kept
+    assert_eq!(amounts, vec![5, 3, 4, 8]);
+}
+
+#[tokio::test]
+async fn keep_latest_picks_most_recent_row_per_key() {
+    let (result, amounts) = run_dedup(
+        config(DedupKeep::Latest),
+        payments(),
+        QueryMemoryBudget::unlimited(),
+    )
+    .await;
+    result.unwrap();
+    assert_eq!(amounts, vec![1, 6, 4, 8]);
+}
+
+#[tokio::test]
+async fn order_offset_and_limit_apply_after_dedup() {
+    let mut config = config(DedupKeep::Latest);
+    config.order_by = Some(OrderSpec {
+        field: "amount".into(),
+        desc: true,
+    });
+    config.offset = 1;
+    config.limit = Some(2);
+    let (result, amounts) = run_dedup(config, payments(), QueryMemoryBudget::unlimited()).await;
+    result.unwrap();
+    assert_eq!(amounts, vec![6, 4]);
+}
+
+#[tokio::test]
+async fn exceeding_the_cap_fails_the_query_without_output() {
+    let memory = QueryMemoryBudget::unlimited();
+    let mut config = config(DedupKeep::First);
+    config.max_bytes = 256;
+    let (result, amounts) = run_dedup(config, payments(), Arc::clone(&memory)).await;
+
+    assert!(result.unwrap_err().contains("DEDUP BY"));
+    assert!(amounts.is_empty());
+    assert!(memory.failure().unwrap().contains("of 256 bytes in use"));
+}
+
+#[test]
+fn unknown_key_column_is_rejected() {
+    let mut config = config(DedupKeep::First);
+    config.spec.keys.push("missing".into());
+    let err = DedupOp::new(config, build_schema()).err().unwrap();
+    assert!(err.contains("DEDUP BY field 'missing'"));
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::engine::core::read::flow::{FlowContext, FlowOperator, FlowOperatorError};
+use crate::engine::core::read::flow::{FlowContext, FlowOperator, FlowOperatorError, OperatorKind};
 use crate::engine::types::ScalarValue;
 
 use super::super::{BatchReceiver, BatchSender};
@@ -29,6 +29,7 @@ impl FlowOperator for FilterOp {
             if batch_arc.is_empty() {
                 continue;
             }
+            let _scope = ctx.metrics().operator_scope(OperatorKind::Filter);
             let schema = Arc::new(batch_arc.schema().clone());
             let mut builder = ctx.pool().acquire(Arc::clone(&schema));
             let column_count = schema.column_count();
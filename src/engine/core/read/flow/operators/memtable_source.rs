@@ -4,8 +4,10 @@ use std::sync::Arc;
 use crate::engine::core::ConditionEvaluator;
 use crate::engine::core::MemTable;
 use crate::engine::core::read::flow::{
-    BatchSchema, ColumnBatchBuilder, FlowContext, FlowOperatorError, FlowSource,
+    BatchSchema, ColumnBatchBuilder, FlowContext, FlowOperatorError, FlowSource, OperatorKind,
 };
+use crate::engine::core::read::memtable_query::candidate_events;
+use crate::engine::core::read::order_tiebreak;
 use crate::engine::core::read::result::ColumnSpec;
 use crate::engine::core::{ConditionEvaluatorBuilder, QueryContext, QueryPlan};
 use crate::engine::schema::types::FieldType;
@@ -95,13 +97,15 @@ impl MemTableSource {
             "Scanning memtable"
         );
 
-        for event in memtable.iter() {
+        let mut scanned = 0u64;
+        for event in candidate_events(memtable, &self.config.plan) {
             if let Some(lim) = limit {
                 if emitted >= lim {
                     break;
                 }
             }
 
+            scanned += 1;
             if !evaluator.evaluate_event(event) {
                 continue;
             }
@@ -136,6 +140,7 @@ impl MemTableSource {
             }
         }
 
+        ctx.scan_stats().add_rows_scanned(scanned);
         Ok(emitted)
     }
 
@@ -147,14 +152,16 @@ impl MemTableSource {
         evaluator: &ConditionEvaluator,
         rows: &mut Vec<Vec<ScalarValue>>,
         emitted: &mut usize,
-    ) -> Result<(), FlowOperatorError> {
-        for event in memtable.iter() {
+    ) -> Result<u64, FlowOperatorError> {
+        let mut scanned = 0u64;
+        for event in candidate_events(memtable, &self.config.plan) {
             if let Some(lim) = limit {
                 if *emitted >= lim {
                     break;
                 }
             }
 
+            scanned += 1;
             if !evaluator.evaluate_event(event) {
                 continue;
             }
@@ -176,7 +183,7 @@ impl MemTableSource {
             }
         }
 
-        Ok(())
+        Ok(scanned)
     }
 }
 
@@ -187,12 +194,23 @@ impl FlowSource for MemTableSource {
         output: BatchSender,
         ctx: Arc<FlowContext>,
     ) -> Result<(), FlowOperatorError> {
+        let _scope = ctx.metrics().operator_scope(OperatorKind::Source);
         let columns = self.resolve_columns().await?;
         let schema = Arc::new(
             BatchSchema::new(columns.clone())
                 .map_err(|e| FlowOperatorError::Batch(format!("failed to build schema: {}", e)))?,
         );
 
+        // Every column of the schema is materialized for each scanned row
+        if let Some(audit) = ctx.scan_stats().projection_audit()
+            && (self.config.memtable.as_ref().is_some_and(|m| !m.is_empty())
+                || !self.config.passive_memtables.is_empty())
+        {
+            audit
+                .record(&self.config.plan, columns.iter().map(|c| c.name.as_str()))
+                .await;
+        }
+
         let evaluator = ConditionEvaluatorBuilder::build_from_plan(&self.config.plan);
         let query_ctx = QueryContext::from_command(&self.config.plan.command);
         let limit = self.determine_limit(&query_ctx);
@@ -211,20 +229,23 @@ impl FlowSource for MemTableSource {
                     ))
                 })?;
             let ascending = !order_spec.desc;
+            let tiebreak = order_tiebreak::tiebreak_index(&schema, order_index);
 
             let mut rows: Vec<Vec<ScalarValue>> = Vec::new();
             let mut emitted = 0usize;
 
             if let Some(active) = self.config.memtable.clone() {
-                self.collect_rows_from_memtable(
-                    &active,
-                    limit,
-                    &columns,
-                    &evaluator,
-                    &mut rows,
-                    &mut emitted,
-                )
-                .await?;
+                let scanned = self
+                    .collect_rows_from_memtable(
+                        &active,
+                        limit,
+                        &columns,
+                        &evaluator,
+                        &mut rows,
+                        &mut emitted,
+                    )
+                    .await?;
+                ctx.scan_stats().add_rows_scanned(scanned);
             }
 
             for passive in self.config.passive_memtables.iter() {
@@ -235,20 +256,22 @@ impl FlowSource for MemTableSource {
                 }
 
                 let guard = passive.lock().await;
-                self.collect_rows_from_memtable(
-                    &*guard,
-                    limit,
-                    &columns,
-                    &evaluator,
-                    &mut rows,
-                    &mut emitted,
-                )
-                .await?;
+                let scanned = self
+                    .collect_rows_from_memtable(
+                        &guard,
+                        limit,
+                        &columns,
+                        &evaluator,
+                        &mut rows,
+                        &mut emitted,
+                    )
+                    .await?;
+                ctx.scan_stats().add_rows_scanned(scanned);
             }
 
-            // Use sort_unstable_by for better performance - maintains relative order of equal elements
             rows.sort_unstable_by(|a, b| {
-                let ord = compare_scalar_values(&a[order_index], &b[order_index]);
+                let ord = compare_scalar_values(&a[order_index], &b[order_index])
+                    .then_with(|| order_tiebreak::break_tie(a, b, tiebreak));
                 if ascending { ord } else { ord.reverse() }
             });
 
@@ -402,6 +425,8 @@ fn field_type_to_logical(field_type: &FieldType) -> String {
         FieldType::Bool => "Boolean".into(),
         FieldType::Timestamp => "Timestamp".into(),
         FieldType::Date => "Date".into(),
+        FieldType::Time => "Time".into(),
+        FieldType::Uuid => "Uuid".into(),
         FieldType::Optional(inner) => field_type_to_logical(inner),
         FieldType::Enum(_) => "Enum".into(),
     }
@@ -1,19 +1,28 @@
 mod agg;
 mod aggregate;
+mod dedup;
 mod filter;
 mod memtable_source;
 mod project;
 mod segment_source;
+mod union;
+mod unnest;
 
+pub use agg::{ColumnConverter, PartialConverter};
 pub use aggregate::{AggregateOp, AggregateOpConfig, aggregate_output_schema};
+pub use dedup::{DedupOp, DedupOpConfig, dedup_max_bytes};
 pub use filter::{FilterOp, FilterPredicate};
 pub use memtable_source::{MemTableSource, MemTableSourceConfig};
 pub use project::{ProjectOp, Projection};
 pub use segment_source::{SegmentSource, SegmentSourceConfig};
+pub use union::UnionOp;
+pub use unnest::{UnnestOp, UnnestOpConfig};
 
 #[cfg(test)]
 mod aggregate_test;
 #[cfg(test)]
+mod dedup_test;
+#[cfg(test)]
 mod filter_test;
 #[cfg(test)]
 mod memtable_source_test;
@@ -21,3 +30,7 @@ mod memtable_source_test;
 mod project_test;
 #[cfg(test)]
 mod segment_source_test;
+#[cfg(test)]
+mod union_test;
+#[cfg(test)]
+mod unnest_test;
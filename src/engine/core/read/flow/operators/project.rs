@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use crate::engine::core::read::flow::{BatchSchema, FlowContext, FlowOperator, FlowOperatorError};
+use crate::engine::core::read::flow::{
+    BatchSchema, FlowContext, FlowOperator, FlowOperatorError, OperatorKind,
+};
 use crate::engine::types::ScalarValue;
 
 use super::super::{BatchReceiver, BatchSender};
@@ -48,6 +50,7 @@ impl FlowOperator for ProjectOp {
             if batch_arc.is_empty() {
                 continue;
             }
+            let _scope = ctx.metrics().operator_scope(OperatorKind::Project);
 
             let mut builder = ctx.pool().acquire(Arc::clone(&target_schema));
             let mut row_values: Vec<ScalarValue> =
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use crate::engine::core::Event;
-use crate::engine::core::read::flow::{BatchSchema, FlowContext, FlowOperatorError, FlowSource};
+use crate::engine::core::read::flow::{
+    BatchSchema, FlowContext, FlowOperatorError, FlowSource, OperatorKind,
+};
 use crate::engine::types::ScalarValue;
 
 use super::super::BatchSender;
@@ -32,6 +34,7 @@ impl FlowSource for SegmentSource {
             return Ok(());
         }
 
+        let _scope = ctx.metrics().operator_scope(OperatorKind::Source);
         let mut builder = ctx.pool().acquire(Arc::clone(&self.config.schema));
         let mut row_values: Vec<ScalarValue> =
             Vec::with_capacity(self.config.schema.column_count());
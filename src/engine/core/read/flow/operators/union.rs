@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use crate::engine::core::read::flow::{
+    BatchSchema, FlowContext, FlowOperatorError, FlowSource, OperatorKind,
+};
+
+use super::super::{BatchReceiver, BatchSender};
+
+/// Concatenates the outputs of several pipelines (UNION ALL), draining each
+/// input to completion before moving on to the next.
+pub struct UnionOp {
+    inputs: Vec<BatchReceiver>,
+}
+
+impl UnionOp {
+    pub fn new(inputs: Vec<BatchReceiver>) -> Self {
+        Self { inputs }
+    }
+
+    /// Checks that every input produces the same columns, in the same order and
+    /// with the same logical types, and returns the shared schema.
+    pub fn validate_schemas(
+        schemas: &[Arc<BatchSchema>],
+    ) -> Result<Arc<BatchSchema>, FlowOperatorError> {
+        let Some(first) = schemas.first() else {
+            return Err(FlowOperatorError::operator(
+                "union requires at least one input",
+            ));
+        };
+
+        for (idx, schema) in schemas.iter().enumerate().skip(1) {
+            if first.is_compatible_with(schema) {
+                continue;
+            }
+            if schema.column_count() != first.column_count() {
+                return Err(FlowOperatorError::operator(format!(
+                    "input {} returns {} columns, expected {}",
+                    idx + 1,
+                    schema.column_count(),
+                    first.column_count()
+                )));
+            }
+            let (col_idx, (expected, actual)) = first
+                .columns()
+                .iter()
+                .zip(schema.columns())
+                .enumerate()
+                .find(|(_, (left, right))| {
+                    left.name != right.name || left.logical_type != right.logical_type
+                })
+                .expect("incompatible schemas differ in at least one column");
+            return Err(FlowOperatorError::operator(format!(
+                "input {} column {} is '{}' ({}), expected '{}' ({})",
+                idx + 1,
+                col_idx + 1,
+                actual.name,
+                actual.logical_type,
+                expected.name,
+                expected.logical_type
+            )));
+        }
+
+        Ok(Arc::clone(first))
+    }
+}
+
+#[async_trait::async_trait]
+impl FlowSource for UnionOp {
+    async fn run(
+        self,
+        output: BatchSender,
+        ctx: Arc<FlowContext>,
+    ) -> Result<(), FlowOperatorError> {
+        for mut input in self.inputs {
+            while let Some(batch) = input.recv().await {
+                if batch.is_empty() {
+                    continue;
+                }
+                let _scope = ctx.metrics().operator_scope(OperatorKind::Merge);
+                output
+                    .send(batch)
+                    .await
+                    .map_err(|_| FlowOperatorError::ChannelClosed)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::engine::core::read::flow::{
+    BatchPool, BatchSchema, FlowChannel, FlowContext, FlowMetrics, FlowSource, FlowTelemetry,
+};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::types::ScalarValue;
+
+use super::UnionOp;
+
+fn test_context() -> Arc<FlowContext> {
+    let metrics = FlowMetrics::new();
+    let pool = BatchPool::new(8).unwrap();
+    Arc::new(FlowContext::new(
+        8,
+        pool,
+        metrics,
+        None::<&str>,
+        FlowTelemetry::default(),
+    ))
+}
+
+fn build_schema(columns: &[(&str, &str)]) -> Arc<BatchSchema> {
+    Arc::new(
+        BatchSchema::new(
+            columns
+                .iter()
+                .map(|(name, logical_type)| ColumnSpec {
+                    name: name.to_string(),
+                    logical_type: logical_type.to_string(),
+                })
+                .collect(),
+        )
+        .unwrap(),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn union_op_concatenates_inputs_in_order() {
+    let ctx = test_context();
+    let schema = build_schema(&[("value", "Integer")]);
+
+    let mut receivers = Vec::new();
+    for values in [vec![1, 2, 3], vec![], vec![4, 5]] {
+        let (tx, rx) = FlowChannel::bounded(4, Arc::clone(ctx.metrics()));
+        let mut builder = ctx.pool().acquire(Arc::clone(&schema));
+        for value in values {
+            builder
+                .push_row(&[ScalarValue::from(json!(value))])
+                .expect("row inserted");
+        }
+        tx.send(Arc::new(builder.finish().expect("batch builds")))
+            .await
+            .expect("send batch");
+        receivers.push(rx);
+    }
+
+    let (out_tx, mut out_rx) = FlowChannel::bounded(8, Arc::clone(ctx.metrics()));
+    UnionOp::new(receivers)
+        .run(out_tx, Arc::clone(&ctx))
+        .await
+        .expect("union runs");
+
+    let mut values = Vec::new();
+    while let Some(batch) = out_rx.recv().await {
+        let column = batch.column(0).unwrap();
+        values.extend(column.iter().map(|v| v.as_i64().unwrap()));
+    }
+    assert_eq!(values, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn union_op_validates_schemas() {
+    let base = build_schema(&[("context_id", "String"), ("amount", "Integer")]);
+    let same = build_schema(&[("context_id", "String"), ("amount", "Integer")]);
+    let schema = UnionOp::validate_schemas(&[Arc::clone(&base), same]).expect("compatible");
+    assert!(Arc::ptr_eq(&schema, &base));
+
+    let retyped = build_schema(&[("context_id", "String"), ("amount", "Float")]);
+    let err = UnionOp::validate_schemas(&[Arc::clone(&base), retyped])
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("input 2 column 2 is 'amount' (Float), expected 'amount' (Integer)"),
+        "{err}"
+    );
+
+    let narrower = build_schema(&[("context_id", "String")]);
+    let err = UnionOp::validate_schemas(&[base, narrower])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("returns 1 columns, expected 2"), "{err}");
+
+    assert!(UnionOp::validate_schemas(&[]).is_err());
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use serde_json::Value as JsonValue;
+
+use super::super::{BatchReceiver, BatchSender};
+use crate::command::types::{UnnestEmpty, UnnestNonArray, UnnestSpec};
+use crate::engine::core::read::flow::{BatchSchema, FlowContext, FlowOperator, FlowOperatorError};
+use crate::engine::types::{LogicalType, ScalarValue};
+
+#[derive(Debug, Clone)]
+pub struct UnnestOpConfig {
+    pub spec: UnnestSpec,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+impl UnnestOpConfig {
+    pub fn new(spec: UnnestSpec) -> Self {
+        Self {
+            spec,
+            limit: None,
+            offset: 0,
+        }
+    }
+}
+
+/// Explodes a JSON array column into rows (`UNNEST(field)`).
+///
+/// Each element of the array becomes one output row, with the other columns
+/// copied from the input row. The column holds JSON text: a JSON column, or a
+/// string field storing arrays as text since payload fields have no JSON
+/// type. The schema is unchanged: elements are written back into the column
+/// the way JSON values are held in batches, so
+/// strings become plain text, numbers and booleans keep their type, nested
+/// arrays and objects stay serialized and a JSON `null` element is null.
+///
+/// A null value counts as an empty array. Values that do not parse as a JSON
+/// array fail the query unless `on_non_array` is `Passthrough`. `OFFSET` and
+/// `LIMIT` count output rows; once the limit is reached the input is dropped.
+pub struct UnnestOp {
+    config: UnnestOpConfig,
+    schema: Arc<BatchSchema>,
+    index: usize,
+}
+
+impl UnnestOp {
+    /// Resolves the unnested column, which must be a JSON or string column.
+    pub fn new(config: UnnestOpConfig, schema: Arc<BatchSchema>) -> Result<Self, String> {
+        let field = &config.spec.field;
+        let (index, column) = schema
+            .columns()
+            .iter()
+            .enumerate()
+            .find(|(_, col)| col.name == *field)
+            .ok_or_else(|| format!("UNNEST field '{}' not found in stream schema", field))?;
+        if !matches!(
+            column.logical_type.parse::<LogicalType>(),
+            Ok(LogicalType::Json | LogicalType::String)
+        ) {
+            return Err(format!(
+                "UNNEST field '{}' is {}, expected a JSON array",
+                field, column.logical_type
+            ));
+        }
+        Ok(Self {
+            config,
+            schema,
+            index,
+        })
+    }
+
+    /// Elements of the value, or `None` for a non-array value.
+    fn elements(&self, value: &ScalarValue) -> Option<Vec<ScalarValue>> {
+        let text = match value {
+            ScalarValue::Null => return Some(Vec::new()),
+            ScalarValue::Utf8(text) => text,
+            _ => return None,
+        };
+        match serde_json::from_str::<JsonValue>(text) {
+            Ok(JsonValue::Array(items)) => Some(items.into_iter().map(ScalarValue::from).collect()),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl FlowOperator for UnnestOp {
+    async fn run(
+        self,
+        mut input: BatchReceiver,
+        output: BatchSender,
+        ctx: Arc<FlowContext>,
+    ) -> Result<(), FlowOperatorError> {
+        let schema = Arc::clone(&self.schema);
+        let mut builder = ctx.pool().acquire(Arc::clone(&schema));
+        let mut to_skip = self.config.offset;
+        let mut remaining = self.config.limit.unwrap_or(usize::MAX);
+
+        'batches: while let Some(batch) = input.recv().await {
+            for row_idx in 0..batch.len() {
+                let mut row = batch.row(row_idx)?;
+                let elements = match self.elements(&row[self.index]) {
+                    Some(elements) if elements.is_empty() => match self.config.spec.on_empty {
+                        UnnestEmpty::Skip => continue,
+                        UnnestEmpty::NullRow => vec![ScalarValue::Null],
+                    },
+                    Some(elements) => elements,
+                    None => match self.config.spec.on_non_array {
+                        UnnestNonArray::Error => {
+                            return Err(FlowOperatorError::Operator(format!(
+                                "UNNEST field '{}' holds a non-array value: {}",
+                                self.config.spec.field,
+                                row[self.index].to_string_repr()
+                            )));
+                        }
+                        UnnestNonArray::Passthrough => vec![row[self.index].clone()],
+                    },
+                };
+
+                for element in elements {
+                    if to_skip > 0 {
+                        to_skip -= 1;
+                        continue;
+                    }
+                    if remaining == 0 {
+                        break 'batches;
+                    }
+                    remaining -= 1;
+                    row[self.index] = element;
+                    builder.push_row(&row)?;
+                    if builder.is_full() {
+                        let full = std::mem::replace(
+                            &mut builder,
+                            ctx.pool().acquire(Arc::clone(&schema)),
+                        );
+                        output
+                            .send(Arc::new(full.finish()?))
+                            .await
+                            .map_err(|_| FlowOperatorError::ChannelClosed)?;
+                    }
+                }
+            }
+        }
+
+        if builder.len() > 0 {
+            output
+                .send(Arc::new(builder.finish()?))
+                .await
+                .map_err(|_| FlowOperatorError::ChannelClosed)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use crate::command::types::{UnnestEmpty, UnnestNonArray, UnnestSpec};
+use crate::engine::core::read::flow::{
+    BatchPool, BatchSchema, FlowChannel, FlowContext, FlowMetrics, FlowOperator, FlowTelemetry,
+};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::types::ScalarValue;
+
+use super::{UnnestOp, UnnestOpConfig};
+
+fn test_context() -> Arc<FlowContext> {
+    let metrics = FlowMetrics::new();
+    let pool = BatchPool::new(4).unwrap();
+    Arc::new(FlowContext::new(
+        4,
+        pool,
+        metrics,
+        None::<&str>,
+        FlowTelemetry::default(),
+    ))
+}
+
+fn build_schema() -> Arc<BatchSchema> {
+    let column = |name: &str, logical_type: &str| ColumnSpec {
+        name: name.into(),
+        logical_type: logical_type.into(),
+    };
+    Arc::new(
+        BatchSchema::new(vec![
+            column("user", "String"),
+            column("tags", "JSON"),
+            column("amount", "Integer"),
+        ])
+        .unwrap(),
+    )
+}
+
+fn unnest_config(field: &str) -> UnnestOpConfig {
+    UnnestOpConfig::new(UnnestSpec::new(field))
+}
+
+/// Rows of (user, tags, amount); a `None` tags value is null.
+fn row(user: &str, tags: Option<&str>, amount: i64) -> Vec<ScalarValue> {
+    vec![
+        ScalarValue::Utf8(user.into()),
+        tags.map_or(ScalarValue::Null, |t| ScalarValue::Utf8(t.into())),
+        ScalarValue::Int64(amount),
+    ]
+}
+
+async fn run_unnest(
+    config: UnnestOpConfig,
+    rows: Vec<Vec<ScalarValue>>,
+) -> (Result<(), String>, Vec<Vec<ScalarValue>>) {
+    let ctx = test_context();
+    let schema = build_schema();
+    let op = UnnestOp::new(config, Arc::clone(&schema)).unwrap();
+    let (tx, rx) = FlowChannel::bounded(16, Arc::clone(ctx.metrics()));
+    let (out_tx, mut out_rx) = FlowChannel::bounded(16, Arc::clone(ctx.metrics()));
+
+    for chunk in rows.chunks(2) {
+        let mut builder = ctx.pool().acquire(Arc::clone(&schema));
+        for row in chunk {
+            builder.push_row(row).unwrap();
+        }
+        tx.send(Arc::new(builder.finish().unwrap())).await.unwrap();
+    }
+    drop(tx);
+
+    let result = op.run(rx, out_tx, ctx).await.map_err(|e| e.to_string());
+    let mut out = Vec::new();
+    while let Some(batch) = out_rx.recv().await {
+        for idx in 0..batch.len() {
+            out.push(batch.row(idx).unwrap());
+        }
+    }
+    (result, out)
+}
+
+#[tokio::test]
+async fn emits_one_row_per_element_copying_other_columns() {
+    let (result, out) = run_unnest(
+        unnest_config("tags"),
+        vec![
+            row("ana", Some(r#"["red","blue"]"#), 1),
+            row("bob", Some(r#"[3,{"k":1},null]"#), 2),
+        ],
+    )
+    .await;
+    result.unwrap();
+    assert_eq!(
+        out,
+        vec![
+            row("ana", Some("red"), 1),
+            row("ana", Some("blue"), 1),
+            vec![
+                ScalarValue::Utf8("bob".into()),
+                ScalarValue::Int64(3),
+                ScalarValue::Int64(2),
+            ],
+            row("bob", Some(r#"{"k":1}"#), 2),
+            row("bob", None, 2),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn empty_and_null_arrays_are_skipped_or_kept_as_null_rows() {
+    let rows = vec![
+        row("ana", Some("[]"), 1),
+        row("bob", None, 2),
+        row("cy", Some(r#"["x"]"#), 3),
+    ];
+
+    let (result, out) = run_unnest(unnest_config("tags"), rows.clone()).await;
+    result.unwrap();
+    assert_eq!(out, vec![row("cy", Some("x"), 3)]);
+
+    let mut config = unnest_config("tags");
+    config.spec.on_empty = UnnestEmpty::NullRow;
+    let (result, out) = run_unnest(config, rows).await;
+    result.unwrap();
+    assert_eq!(
+        out,
+        vec![
+            row("ana", None, 1),
+            row("bob", None, 2),
+            row("cy", Some("x"), 3)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn non_array_values_fail_or_pass_through() {
+    let rows = vec![
+        row("ana", Some(r#"["x"]"#), 1),
+        row("bob", Some(r#"{"k":1}"#), 2),
+        row("cy", Some("plain"), 3),
+    ];
+
+    let (result, _) = run_unnest(unnest_config("tags"), rows.clone()).await;
+    let err = result.unwrap_err();
+    assert!(err.contains("non-array"), "{}", err);
+
+    let mut config = unnest_config("tags");
+    config.spec.on_non_array = UnnestNonArray::Passthrough;
+    let (result, out) = run_unnest(config, rows.clone()).await;
+    result.unwrap();
+    assert_eq!(
+        out,
+        vec![row("ana", Some("x"), 1), rows[1].clone(), rows[2].clone()]
+    );
+}
+
+#[tokio::test]
+async fn offset_and_limit_count_unnested_rows() {
+    let rows = vec![
+        row("ana", Some(r#"["a","b","c"]"#), 1),
+        row("bob", Some("[]"), 2),
+        row("cy", Some(r#"["d","e"]"#), 3),
+    ];
+
+    let mut config = unnest_config("tags");
+    config.offset = 2;
+    config.limit = Some(2);
+    let (result, out) = run_unnest(config, rows).await;
+    result.unwrap();
+    assert_eq!(out, vec![row("ana", Some("c"), 1), row("cy", Some("d"), 3)]);
+}
+
+#[tokio::test]
+async fn string_columns_holding_json_arrays_are_unnested() {
+    let mut config = unnest_config("user");
+    config.spec.on_non_array = UnnestNonArray::Passthrough;
+    let (result, out) = run_unnest(
+        config,
+        vec![row(r#"["ana","bob"]"#, None, 1), row("cy", None, 2)],
+    )
+    .await;
+    result.unwrap();
+    assert_eq!(
+        out,
+        vec![row("ana", None, 1), row("bob", None, 1), row("cy", None, 2)]
+    );
+}
+
+#[test]
+fn rejects_missing_or_non_text_columns() {
+    let err = UnnestOp::new(unnest_config("missing"), build_schema())
+        .err()
+        .unwrap();
+    assert!(err.contains("not found"), "{}", err);
+
+    let err = UnnestOp::new(unnest_config("amount"), build_schema())
+        .err()
+        .unwrap();
+    assert!(err.contains("expected a JSON array"), "{}", err);
+}
@@ -2,11 +2,12 @@ use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::sync::Arc;
 
+use crate::engine::core::read::order_tiebreak;
 use crate::engine::types::ScalarValue;
 use tokio::task::JoinHandle;
 use tracing::error;
 
-use super::{BatchPool, BatchReceiver, BatchSchema, BatchSender, ColumnBatch};
+use super::{BatchPool, BatchReceiver, BatchSchema, BatchSender, ColumnBatch, OperatorKind};
 
 /// Coordinates ordered merging of shard batch streams into a single ordered stream.
 pub struct OrderedStreamMerger;
@@ -28,6 +29,7 @@ impl OrderedStreamMerger {
         }
 
         let pool = BatchPool::new(batch_size).map_err(|e| e.to_string())?;
+        let tiebreak_index = order_tiebreak::tiebreak_index(&schema, order_index);
 
         let streams: Vec<RowStream> = receivers.into_iter().map(RowStream::new).collect();
 
@@ -35,6 +37,7 @@ impl OrderedStreamMerger {
             schema,
             streams,
             order_index,
+            tiebreak_index,
             ascending,
             offset,
             limit,
@@ -58,6 +61,7 @@ struct MergerState {
     schema: Arc<BatchSchema>,
     streams: Vec<RowStream>,
     order_index: usize,
+    tiebreak_index: Option<usize>,
     ascending: bool,
     offset: usize,
     limit: Option<usize>,
@@ -67,6 +71,8 @@ struct MergerState {
 
 impl MergerState {
     async fn run(mut self) -> Result<(), String> {
+        let metrics = Arc::clone(self.sender.metrics());
+        let _scope = metrics.operator_scope(OperatorKind::Merge);
         let mut heap = BinaryHeap::new();
 
         for (idx, stream) in self.streams.iter_mut().enumerate() {
@@ -79,6 +85,7 @@ impl MergerState {
                     shard_idx: idx,
                     row,
                     order_index: self.order_index,
+                    tiebreak_index: self.tiebreak_index,
                     ascending: self.ascending,
                 });
             }
@@ -187,6 +194,7 @@ struct HeapItem {
     shard_idx: usize,
     row: Vec<ScalarValue>,
     order_index: usize,
+    tiebreak_index: Option<usize>,
     ascending: bool,
 }
 
@@ -208,8 +216,9 @@ impl Ord for HeapItem {
     fn cmp(&self, other: &Self) -> Ordering {
         let lhs = &self.row[self.order_index];
         let rhs = &other.row[self.order_index];
-        let ord =
-            compare_scalar_values(lhs, rhs).then_with(|| other.shard_idx.cmp(&self.shard_idx));
+        let ord = compare_scalar_values(lhs, rhs)
+            .then_with(|| order_tiebreak::break_tie(&self.row, &other.row, self.tiebreak_index))
+            .then_with(|| other.shard_idx.cmp(&self.shard_idx));
         if self.ascending { ord.reverse() } else { ord }
     }
 }
@@ -416,3 +416,75 @@ async fn ordered_merger_handles_three_streams() {
 
     assert_eq!(results, vec![1, 2, 3, 4, 5, 6]);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn ordered_merger_breaks_timestamp_ties_by_event_id() {
+    let schema = Arc::new(
+        BatchSchema::new(vec![
+            ColumnSpec {
+                name: "timestamp".into(),
+                logical_type: "Timestamp".into(),
+            },
+            ColumnSpec {
+                name: "event_id".into(),
+                logical_type: "Integer".into(),
+            },
+        ])
+        .unwrap(),
+    );
+    let ctx = flow_context(4);
+    let metrics = Arc::clone(ctx.metrics());
+
+    for ascending in [true, false] {
+        let (tx1, rx1) = FlowChannel::bounded(4, Arc::clone(&metrics));
+        let (tx2, rx2) = FlowChannel::bounded(4, Arc::clone(&metrics));
+        let (out_tx, mut out_rx) = FlowChannel::bounded(4, Arc::clone(&metrics));
+
+        // Every row shares a timestamp; each stream is already in output order
+        let (first, second) = if ascending {
+            (vec![2_i64, 4], vec![1_i64, 3])
+        } else {
+            (vec![4_i64, 2], vec![3_i64, 1])
+        };
+        for (tx, ids) in [(tx1, first), (tx2, second)] {
+            let schema = Arc::clone(&schema);
+            tokio::spawn(async move {
+                let mut builder = BatchPool::new(4).unwrap().acquire(schema);
+                for id in ids {
+                    builder
+                        .push_row(&[ScalarValue::Int64(100), ScalarValue::Int64(id)])
+                        .unwrap();
+                }
+                tx.send(Arc::new(builder.finish().unwrap())).await.unwrap();
+            });
+        }
+
+        let handle = OrderedStreamMerger::spawn(
+            Arc::clone(&schema),
+            vec![rx1, rx2],
+            0,
+            ascending,
+            0,
+            None,
+            out_tx,
+            4,
+        )
+        .unwrap();
+
+        let mut ids = Vec::new();
+        while let Some(batch) = out_rx.recv().await {
+            let column = batch.column(1).unwrap();
+            for row_idx in 0..batch.len() {
+                ids.push(column[row_idx].as_u64().unwrap());
+            }
+        }
+        handle.await.unwrap();
+
+        let expected: Vec<u64> = if ascending {
+            vec![1, 2, 3, 4]
+        } else {
+            vec![4, 3, 2, 1]
+        };
+        assert_eq!(ids, expected);
+    }
+}
@@ -1,7 +1,9 @@
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 use crate::engine::types::ScalarValue;
+use crate::shared::config::CONFIG;
 
 use super::batch::{BatchError, BatchSchema, ColumnBatchBuilder};
 
@@ -10,8 +12,33 @@ pub struct BatchPool {
     inner: Arc<BatchPoolInner>,
 }
 
+/// Buffers each pool keeps for reuse when not configured.
+pub const DEFAULT_POOL_MAX_BUFFERS: usize = 16;
+/// Largest buffer a pool keeps for reuse when not configured; larger ones are freed.
+pub const DEFAULT_POOL_MAX_BUFFER_BYTES: usize = 16 * 1024 * 1024;
+
+/// Counters summed over every batch pool in the process, reported by SHOW STATS.
+static GLOBAL_COUNTERS: PoolCounters = PoolCounters::new();
+
 impl BatchPool {
     pub fn new(batch_size: usize) -> Result<Self, BatchError> {
+        let config = CONFIG.query.as_ref();
+        Self::with_limits(
+            batch_size,
+            config
+                .and_then(|q| q.batch_pool_max_buffers)
+                .unwrap_or(DEFAULT_POOL_MAX_BUFFERS),
+            config
+                .and_then(|q| q.batch_pool_max_buffer_bytes)
+                .unwrap_or(DEFAULT_POOL_MAX_BUFFER_BYTES),
+        )
+    }
+
+    pub fn with_limits(
+        batch_size: usize,
+        max_buffers: usize,
+        max_buffer_bytes: usize,
+    ) -> Result<Self, BatchError> {
         if batch_size == 0 {
             return Err(BatchError::InvalidPoolConfig(
                 "batch size must be greater than zero".into(),
@@ -19,7 +46,11 @@ impl BatchPool {
         }
 
         Ok(Self {
-            inner: Arc::new(BatchPoolInner::new(batch_size)),
+            inner: Arc::new(BatchPoolInner::new(
+                batch_size,
+                max_buffers,
+                max_buffer_bytes,
+            )),
         })
     }
 
@@ -27,6 +58,16 @@ impl BatchPool {
         self.inner.batch_size
     }
 
+    /// Counters for this pool only.
+    pub fn stats(&self) -> BatchPoolStats {
+        self.inner.counters.snapshot()
+    }
+
+    /// Counters summed over every pool in the process.
+    pub fn global_stats() -> BatchPoolStats {
+        GLOBAL_COUNTERS.snapshot()
+    }
+
     pub fn acquire(&self, schema: Arc<BatchSchema>) -> ColumnBatchBuilder {
         let mut guard = self.inner.free.lock().expect("batch pool mutex poisoned");
 
@@ -37,6 +78,8 @@ impl BatchPool {
             let mut reusable = guard
                 .remove(idx)
                 .expect("batch pool entry should exist for schema");
+            drop(guard);
+            self.inner.counters.reused(reusable.bytes);
             for column in &mut reusable.columns {
                 column.clear();
                 column.reserve(self.inner.batch_size);
@@ -49,6 +92,8 @@ impl BatchPool {
                 Some(Arc::clone(&self.inner)),
             )
         } else {
+            drop(guard);
+            self.inner.counters.allocated();
             let mut columns = Vec::with_capacity(schema.column_count());
             for _ in 0..schema.column_count() {
                 columns.push(Vec::with_capacity(self.inner.batch_size));
@@ -67,27 +112,61 @@ impl BatchPool {
 #[derive(Debug)]
 pub(crate) struct BatchPoolInner {
     pub(crate) batch_size: usize,
+    max_buffers: usize,
+    max_buffer_bytes: usize,
     free: Mutex<VecDeque<ReusableColumns>>,
+    counters: PoolCounters,
 }
 
 impl BatchPoolInner {
-    pub(crate) fn new(batch_size: usize) -> Self {
+    pub(crate) fn new(batch_size: usize, max_buffers: usize, max_buffer_bytes: usize) -> Self {
         Self {
             batch_size,
+            max_buffers,
+            max_buffer_bytes,
             free: Mutex::new(VecDeque::new()),
+            counters: PoolCounters::new(),
         }
     }
 
+    /// Takes back the columns of a batch or builder that is being dropped, so
+    /// nothing else can still reference them.
     pub(crate) fn recycle(&self, schema: Arc<BatchSchema>, mut columns: Vec<Vec<ScalarValue>>) {
         for column in &mut columns {
             column.clear();
             column.shrink_to(self.batch_size);
         }
-
-        let reusable = ReusableColumns { schema, columns };
+        let bytes = columns
+            .iter()
+            .map(|c| c.capacity() * std::mem::size_of::<ScalarValue>())
+            .sum::<usize>() as u64;
+        if bytes > self.max_buffer_bytes as u64 {
+            self.counters.discarded();
+            return;
+        }
 
         let mut guard = self.free.lock().expect("batch pool mutex poisoned");
-        guard.push_back(reusable);
+        if guard.len() >= self.max_buffers {
+            drop(guard);
+            self.counters.discarded();
+            return;
+        }
+        guard.push_back(ReusableColumns {
+            schema,
+            columns,
+            bytes,
+        });
+        drop(guard);
+        self.counters.returned(bytes);
+    }
+}
+
+impl Drop for BatchPoolInner {
+    fn drop(&mut self) {
+        let free = self.free.get_mut().map(std::mem::take).unwrap_or_default();
+        for reusable in free {
+            self.counters.released(reusable.bytes);
+        }
     }
 }
 
@@ -95,4 +174,101 @@ impl BatchPoolInner {
 struct ReusableColumns {
     schema: Arc<BatchSchema>,
     columns: Vec<Vec<ScalarValue>>,
+    // Capacity of the cleared columns, counted in the pooled bytes gauge
+    bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchPoolStats {
+    /// Builders handed out with freshly allocated columns
+    pub allocations: u64,
+    /// Builders handed out with pooled columns
+    pub reuses: u64,
+    /// Dropped batches whose columns went back to the pool
+    pub returns: u64,
+    /// Dropped batches whose columns were freed: oversized, or the pool was full
+    pub discards: u64,
+    pub pooled_buffers: u64,
+    pub pooled_bytes: u64,
+}
+
+impl BatchPoolStats {
+    pub fn reuse_ratio(&self) -> f64 {
+        let total = self.allocations + self.reuses;
+        if total == 0 {
+            0.0
+        } else {
+            self.reuses as f64 / total as f64
+        }
+    }
+}
+
+/// Relaxed atomics: the counters are statistics and order nothing else.
+#[derive(Debug)]
+struct PoolCounters {
+    allocations: AtomicU64,
+    reuses: AtomicU64,
+    returns: AtomicU64,
+    discards: AtomicU64,
+    pooled_buffers: AtomicU64,
+    pooled_bytes: AtomicU64,
+}
+
+impl PoolCounters {
+    const fn new() -> Self {
+        Self {
+            allocations: AtomicU64::new(0),
+            reuses: AtomicU64::new(0),
+            returns: AtomicU64::new(0),
+            discards: AtomicU64::new(0),
+            pooled_buffers: AtomicU64::new(0),
+            pooled_bytes: AtomicU64::new(0),
+        }
+    }
+
+    fn allocated(&self) {
+        for c in [self, &GLOBAL_COUNTERS] {
+            c.allocations.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn reused(&self, bytes: u64) {
+        for c in [self, &GLOBAL_COUNTERS] {
+            c.reuses.fetch_add(1, Ordering::Relaxed);
+        }
+        self.released(bytes);
+    }
+
+    fn returned(&self, bytes: u64) {
+        for c in [self, &GLOBAL_COUNTERS] {
+            c.returns.fetch_add(1, Ordering::Relaxed);
+            c.pooled_buffers.fetch_add(1, Ordering::Relaxed);
+            c.pooled_bytes.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    fn discarded(&self) {
+        for c in [self, &GLOBAL_COUNTERS] {
+            c.discards.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// A pooled buffer left the pool, either reused or freed with the pool.
+    fn released(&self, bytes: u64) {
+        for c in [self, &GLOBAL_COUNTERS] {
+            c.pooled_buffers.fetch_sub(1, Ordering::Relaxed);
+            c.pooled_bytes.fetch_sub(bytes, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> BatchPoolStats {
+        BatchPoolStats {
+            allocations: self.allocations.load(Ordering::Relaxed),
+            reuses: self.reuses.load(Ordering::Relaxed),
+            returns: self.returns.load(Ordering::Relaxed),
+            discards: self.discards.load(Ordering::Relaxed),
+            pooled_buffers: self.pooled_buffers.load(Ordering::Relaxed),
+            pooled_bytes: self.pooled_bytes.load(Ordering::Relaxed),
+        }
+    }
 }
@@ -44,3 +44,78 @@ fn pool_recycles_builders() {
         .push_row(&[ScalarValue::from(json!(2_i64))])
         .expect("reused builder still works");
 }
+
+fn push_and_finish(pool: &BatchPool, schema: &Arc<BatchSchema>) -> super::ColumnBatch {
+    let mut builder = pool.acquire(Arc::clone(schema));
+    builder
+        .push_row(&[ScalarValue::from(json!(1_i64))])
+        .expect("row stored");
+    builder.finish().expect("batch builds")
+}
+
+#[test]
+fn pool_counts_allocations_reuses_and_pooled_bytes() {
+    let schema = make_schema(1);
+    let pool = BatchPool::with_limits(4, 8, usize::MAX).expect("pool builds");
+
+    drop(push_and_finish(&pool, &schema));
+    let stats = pool.stats();
+    assert_eq!((stats.allocations, stats.returns), (1, 1));
+    assert_eq!(stats.pooled_buffers, 1);
+    assert!(stats.pooled_bytes > 0);
+
+    let builder = pool.acquire(Arc::clone(&schema));
+    let stats = pool.stats();
+    assert_eq!(
+        (stats.reuses, stats.pooled_buffers, stats.pooled_bytes),
+        (1, 0, 0)
+    );
+    assert!((stats.reuse_ratio() - 0.5).abs() < 1e-9);
+    drop(builder);
+    assert_eq!(pool.stats().pooled_buffers, 1);
+}
+
+#[test]
+fn pool_discards_oversized_buffers_and_caps_pooled_count() {
+    let schema = make_schema(1);
+    let tiny = BatchPool::with_limits(4, 8, 1).expect("pool builds");
+    drop(push_and_finish(&tiny, &schema));
+    let stats = tiny.stats();
+    assert_eq!(
+        (stats.returns, stats.discards, stats.pooled_bytes),
+        (0, 1, 0)
+    );
+
+    let capped = BatchPool::with_limits(4, 1, usize::MAX).expect("pool builds");
+    let first = push_and_finish(&capped, &schema);
+    let second = push_and_finish(&capped, &schema);
+    drop(first);
+    drop(second);
+    let stats = capped.stats();
+    assert_eq!(
+        (stats.returns, stats.discards, stats.pooled_buffers),
+        (1, 1, 1)
+    );
+}
+
+#[test]
+fn pool_takes_back_shared_batches_only_after_the_last_reference() {
+    let schema = make_schema(1);
+    let pool = BatchPool::with_limits(4, 8, usize::MAX).expect("pool builds");
+
+    let batch = Arc::new(push_and_finish(&pool, &schema));
+    let reader = Arc::clone(&batch);
+    drop(batch);
+    assert_eq!(pool.stats().returns, 0);
+
+    // A builder acquired now cannot share columns with the live batch
+    let builder = pool.acquire(Arc::clone(&schema));
+    assert_eq!(pool.stats().reuses, 0);
+    assert_eq!(
+        reader.column(0).unwrap()[0],
+        ScalarValue::from(json!(1_i64))
+    );
+    drop(builder);
+    drop(reader);
+    assert_eq!(pool.stats().returns, 2);
+}
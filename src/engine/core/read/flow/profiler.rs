@@ -0,0 +1,222 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+/// Operator categories tracked by the sampling profiler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperatorKind {
+    Source,
+    Filter,
+    Project,
+    Aggregate,
+    Merge,
+}
+
+impl OperatorKind {
+    pub const ALL: [OperatorKind; 5] = [
+        OperatorKind::Source,
+        OperatorKind::Filter,
+        OperatorKind::Project,
+        OperatorKind::Aggregate,
+        OperatorKind::Merge,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperatorKind::Source => "source",
+            OperatorKind::Filter => "filter",
+            OperatorKind::Project => "project",
+            OperatorKind::Aggregate => "aggregate",
+            OperatorKind::Merge => "merge",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Sampling profiler for flow operators. Operators mark themselves active through
+/// `FlowMetrics::operator_scope`; a background sampler periodically records which
+/// operator kinds are active. Sampling stops once the profiler is dropped or stopped.
+#[derive(Debug)]
+pub struct OperatorProfiler {
+    interval: Duration,
+    active: [AtomicU32; 5],
+    samples: [AtomicU64; 5],
+    ticks: AtomicU64,
+    idle_ticks: AtomicU64,
+    stopped: AtomicBool,
+}
+
+impl OperatorProfiler {
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(1);
+
+    pub fn new(interval: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            interval: interval.max(Duration::from_micros(100)),
+            active: Default::default(),
+            samples: Default::default(),
+            ticks: AtomicU64::new(0),
+            idle_ticks: AtomicU64::new(0),
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    /// Creates a profiler and spawns its sampler on the current runtime.
+    pub fn start(interval: Duration) -> Arc<Self> {
+        let profiler = Self::new(interval);
+        let weak: Weak<Self> = Arc::downgrade(&profiler);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                match weak.upgrade() {
+                    Some(profiler) if !profiler.is_stopped() => profiler.sample(),
+                    _ => break,
+                }
+            }
+        });
+        profiler
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Marks an operator of `kind` active until the returned scope is dropped.
+    pub fn enter(&self, kind: OperatorKind) -> OperatorScope<'_> {
+        self.active[kind.index()].fetch_add(1, Ordering::Relaxed);
+        OperatorScope {
+            profiler: self,
+            kind,
+        }
+    }
+
+    /// Records one sample of the currently active operator kinds.
+    pub fn sample(&self) {
+        self.ticks.fetch_add(1, Ordering::Relaxed);
+        let mut any_active = false;
+        for kind in OperatorKind::ALL {
+            if self.active[kind.index()].load(Ordering::Relaxed) > 0 {
+                self.samples[kind.index()].fetch_add(1, Ordering::Relaxed);
+                any_active = true;
+            }
+        }
+        if !any_active {
+            self.idle_ticks.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+
+    pub fn report(&self) -> ProfileReport {
+        let operators = OperatorKind::ALL
+            .iter()
+            .map(|kind| OperatorSamples {
+                kind: *kind,
+                samples: self.samples[kind.index()].load(Ordering::Relaxed),
+            })
+            .collect();
+        ProfileReport {
+            interval: self.interval,
+            total_samples: self.ticks.load(Ordering::Relaxed),
+            idle_samples: self.idle_ticks.load(Ordering::Relaxed),
+            operators,
+        }
+    }
+}
+
+/// RAII guard that keeps an operator kind marked active.
+pub struct OperatorScope<'a> {
+    profiler: &'a OperatorProfiler,
+    kind: OperatorKind,
+}
+
+impl Drop for OperatorScope<'_> {
+    fn drop(&mut self) {
+        self.profiler.active[self.kind.index()].fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorSamples {
+    pub kind: OperatorKind,
+    pub samples: u64,
+}
+
+/// Aggregated per-operator breakdown. Operators may run concurrently, so the
+/// per-operator shares can add up to more than 100%.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileReport {
+    pub interval: Duration,
+    pub total_samples: u64,
+    pub idle_samples: u64,
+    pub operators: Vec<OperatorSamples>,
+}
+
+impl ProfileReport {
+    /// Share of samples in which `kind` was active, in percent.
+    pub fn share(&self, kind: OperatorKind) -> f64 {
+        if self.total_samples == 0 {
+            return 0.0;
+        }
+        let samples = self
+            .operators
+            .iter()
+            .find(|op| op.kind == kind)
+            .map(|op| op.samples)
+            .unwrap_or(0);
+        samples as f64 * 100.0 / self.total_samples as f64
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let operators: serde_json::Map<String, serde_json::Value> = self
+            .operators
+            .iter()
+            .map(|op| {
+                (
+                    op.kind.as_str().to_string(),
+                    serde_json::json!({
+                        "samples": op.samples,
+                        "pct": (self.share(op.kind) * 10.0).round() / 10.0,
+                    }),
+                )
+            })
+            .collect();
+        serde_json::json!({
+            "interval_us": self.interval.as_micros() as u64,
+            "samples": self.total_samples,
+            "idle_samples": self.idle_samples,
+            "operators": operators,
+        })
+    }
+}
+
+impl fmt::Display for ProfileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "samples={} idle={}",
+            self.total_samples, self.idle_samples
+        )?;
+        for op in &self.operators {
+            write!(
+                f,
+                " {}={} ({:.1}%)",
+                op.kind.as_str(),
+                op.samples,
+                self.share(op.kind)
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use super::{FlowMetrics, OperatorKind, OperatorProfiler};
+
+#[test]
+fn sample_counts_active_operators() {
+    let profiler = OperatorProfiler::new(Duration::from_millis(1));
+
+    {
+        let _filter = profiler.enter(OperatorKind::Filter);
+        profiler.sample();
+        {
+            let _aggregate = profiler.enter(OperatorKind::Aggregate);
+            profiler.sample();
+        }
+    }
+    profiler.sample();
+
+    let report = profiler.report();
+    assert_eq!(report.total_samples, 3);
+    assert_eq!(report.idle_samples, 1);
+    let samples = |kind: OperatorKind| {
+        report
+            .operators
+            .iter()
+            .find(|op| op.kind == kind)
+            .map(|op| op.samples)
+            .unwrap()
+    };
+    assert_eq!(samples(OperatorKind::Filter), 2);
+    assert_eq!(samples(OperatorKind::Aggregate), 1);
+    assert_eq!(samples(OperatorKind::Source), 0);
+}
+
+#[test]
+fn report_share_and_json() {
+    let profiler = OperatorProfiler::new(Duration::from_millis(1));
+    {
+        let _source = profiler.enter(OperatorKind::Source);
+        profiler.sample();
+    }
+    profiler.sample();
+
+    let report = profiler.report();
+    assert_eq!(report.share(OperatorKind::Source), 50.0);
+    assert_eq!(report.share(OperatorKind::Merge), 0.0);
+
+    let json = report.to_json();
+    assert_eq!(json["samples"], 2);
+    assert_eq!(json["interval_us"], 1000);
+    assert_eq!(json["operators"]["source"]["samples"], 1);
+    assert_eq!(json["operators"]["source"]["pct"], 50.0);
+    assert!(report.to_string().contains("source=1 (50.0%)"));
+}
+
+#[test]
+fn interval_is_clamped_to_minimum() {
+    let profiler = OperatorProfiler::new(Duration::from_nanos(1));
+    assert_eq!(profiler.interval(), Duration::from_micros(100));
+}
+
+#[test]
+fn operator_scope_is_noop_without_profiler() {
+    let metrics = FlowMetrics::new();
+    assert!(metrics.profiler().is_none());
+    assert!(metrics.operator_scope(OperatorKind::Filter).is_none());
+}
+
+#[tokio::test]
+async fn started_profiler_samples_until_stopped() {
+    let metrics = FlowMetrics::new();
+    metrics.enable_profiling(OperatorProfiler::start(Duration::from_millis(1)));
+
+    {
+        let _scope = metrics.operator_scope(OperatorKind::Project);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    let profiler = metrics.profiler().unwrap();
+    profiler.stop();
+    let report = profiler.report();
+    assert!(report.total_samples > 0);
+    assert!(report.share(OperatorKind::Project) > 0.0);
+
+    let frozen = report.total_samples;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(profiler.report().total_samples <= frozen + 1);
+}
@@ -0,0 +1,90 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::engine::core::read::projection::ProjectionAudit;
+
+/// Work done by one query across all of its shards: rows read from
+/// memtables and segment zones, column bytes loaded from segments, time spent
+/// running shard flow tasks, and zone index cache lookups. Shared by the
+/// coordinator and the shard flows like the query's memory budget.
+#[derive(Debug, Default)]
+pub struct QueryScanStats {
+    rows_scanned: AtomicU64,
+    bytes_scanned: AtomicU64,
+    busy_nanos: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    projection: Option<ProjectionAudit>,
+}
+
+impl QueryScanStats {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Like [`Self::new`], also recording the columns each reader hydrates.
+    pub fn with_projection_audit() -> Arc<Self> {
+        Arc::new(Self {
+            projection: Some(ProjectionAudit::new()),
+            ..Self::default()
+        })
+    }
+
+    /// The projection audit, when the query records one.
+    pub fn projection_audit(&self) -> Option<&ProjectionAudit> {
+        self.projection.as_ref()
+    }
+
+    pub fn add_rows_scanned(&self, rows: u64) {
+        self.rows_scanned.fetch_add(rows, Ordering::Relaxed);
+    }
+
+    pub fn add_bytes_scanned(&self, bytes: u64) {
+        self.bytes_scanned.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn add_busy(&self, elapsed: Duration) {
+        self.busy_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Runs `future`, counting the time spent polling it as busy time. Time
+    /// spent waiting on channels or I/O readiness is not counted.
+    pub async fn track_busy<F: Future>(self: Arc<Self>, future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        std::future::poll_fn(|cx| {
+            let started = Instant::now();
+            let poll = future.as_mut().poll(cx);
+            self.add_busy(started.elapsed());
+            poll
+        })
+        .await
+    }
+
+    pub fn add_cache_lookups(&self, hits: u64, misses: u64) {
+        self.cache_hits.fetch_add(hits, Ordering::Relaxed);
+        self.cache_misses.fetch_add(misses, Ordering::Relaxed);
+    }
+
+    pub fn rows_scanned(&self) -> u64 {
+        self.rows_scanned.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_scanned(&self) -> u64 {
+        self.bytes_scanned.load(Ordering::Relaxed)
+    }
+
+    pub fn busy_time(&self) -> Duration {
+        Duration::from_nanos(self.busy_nanos.load(Ordering::Relaxed))
+    }
+
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    pub fn cache_misses(&self) -> u64 {
+        self.cache_misses.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::QueryScanStats;
+
+#[test]
+fn scan_stats_sum_contributions_from_every_shard() {
+    let stats = QueryScanStats::new();
+    let shards: Vec<_> = (0..3)
+        .map(|_| {
+            let stats = Arc::clone(&stats);
+            std::thread::spawn(move || {
+                stats.add_rows_scanned(100);
+                stats.add_bytes_scanned(4096);
+                stats.add_cache_lookups(2, 1);
+            })
+        })
+        .collect();
+    for shard in shards {
+        shard.join().unwrap();
+    }
+
+    assert_eq!(stats.rows_scanned(), 300);
+    assert_eq!(stats.bytes_scanned(), 3 * 4096);
+    assert_eq!(stats.cache_hits(), 6);
+    assert_eq!(stats.cache_misses(), 3);
+}
+
+#[tokio::test]
+async fn busy_time_counts_polling_but_not_waiting() {
+    let stats = QueryScanStats::new();
+    let output = Arc::clone(&stats)
+        .track_busy(async {
+            std::thread::sleep(Duration::from_millis(20));
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            7
+        })
+        .await;
+
+    assert_eq!(output, 7);
+    let busy = stats.busy_time();
+    assert!(busy >= Duration::from_millis(20), "busy: {:?}", busy);
+    assert!(busy < Duration::from_millis(200), "busy: {:?}", busy);
+}
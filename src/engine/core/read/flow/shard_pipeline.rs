@@ -1,4 +1,5 @@
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 
 use tokio::task::JoinHandle;
 use tracing::{debug, error};
@@ -8,6 +9,7 @@ use crate::engine::core::Event;
 use crate::engine::core::MemTable;
 use crate::engine::core::QueryCaches;
 use crate::engine::core::QueryPlan;
+use crate::engine::core::read::aggregate::zone_summary_plan::ZoneSummarySlot;
 use crate::engine::core::read::execution_step::ExecutionStep;
 use crate::engine::core::read::flow::operators::{
     AggregateOp, AggregateOpConfig, MemTableSource, MemTableSourceConfig, ProjectOp, Projection,
@@ -17,92 +19,45 @@ use crate::engine::core::read::flow::{
     BatchReceiver, BatchSchema, FlowChannel, FlowContext, FlowOperator, FlowOperatorError,
     FlowSource,
 };
+use crate::engine::core::read::late_materialization;
+use crate::engine::core::read::projection::ProjectionContext;
 use crate::engine::core::read::segment_query_runner::SegmentQueryRunner;
 use crate::engine::schema::registry::SchemaRegistry;
 
 pub const DEFAULT_MEMTABLE_COLUMNS: &[&str] =
     &["context_id", "event_type", "timestamp", "event_id"];
 
-/// Computes the output schema and projection indices for RETURN fields.
+/// Computes the output schema and projection indices for a selection result.
 /// Returns (output_schema, projection_indices) where projection_indices maps
 /// from input column positions to output column positions.
+///
+/// Columns come out in the same order on every shard and run regardless of
+/// the order they were loaded in: see `ProjectionContext::output_columns`.
 fn compute_return_projection(
     input_schema: &BatchSchema,
     return_fields: Option<&[String]>,
     registry: &SchemaRegistry,
     event_type: &str,
 ) -> Result<(Arc<BatchSchema>, Vec<usize>), FlowOperatorError> {
-    let return_fields = match return_fields {
-        None | Some([]) => {
-            // No RETURN fields specified - identity projection
-            let indices: Vec<usize> = (0..input_schema.column_count()).collect();
-            return Ok((Arc::new(input_schema.clone()), indices));
-        }
-        Some(fields) => fields,
-    };
-
-    // Core fields that are always included
-    let core_fields = vec![
-        "context_id".to_string(),
-        "event_type".to_string(),
-        "timestamp".to_string(),
-        "event_id".to_string(),
-    ];
-
-    // Get payload fields from schema
-    // Handle wildcard event_type by collecting fields from all schemas
-    let payload_fields: Vec<String> = if event_type == "*" {
-        let mut all_fields: std::collections::HashSet<String> = std::collections::HashSet::new();
-        for schema in registry.get_all().values() {
-            for field in schema.fields() {
-                all_fields.insert(field.clone());
-            }
-        }
-        let mut fields: Vec<String> = all_fields.into_iter().collect();
-        fields.sort();
-        fields
-    } else {
-        registry
-            .get(event_type)
-            .map(|schema| {
-                let mut fields: Vec<String> = schema.fields().cloned().collect();
-                fields.sort();
-                fields
-            })
-            .unwrap_or_default()
-    };
+    let payload_fields = ProjectionContext::payload_fields_of(registry, event_type);
+    let ordered = ProjectionContext::output_columns(return_fields, &payload_fields);
 
-    let payload_set: std::collections::HashSet<String> = payload_fields.iter().cloned().collect();
-
-    // Build output column list: core fields first, then RETURN payload fields
     let mut output_columns = Vec::new();
     let mut output_indices = Vec::new();
-
-    // Add core fields (always in order: context_id, event_type, timestamp, event_id)
-    for core_field in &core_fields {
-        if let Some(idx) = input_schema
-            .columns()
-            .iter()
-            .position(|c| c.name == *core_field)
-        {
+    for name in &ordered {
+        if let Some(idx) = input_schema.columns().iter().position(|c| c.name == *name) {
             output_columns.push(input_schema.columns()[idx].clone());
             output_indices.push(idx);
         }
     }
 
-    // Add RETURN payload fields
-    for return_field in return_fields {
-        if payload_set.contains(return_field) {
-            if let Some(idx) = input_schema
-                .columns()
-                .iter()
-                .position(|c| c.name == *return_field)
-            {
-                // Avoid duplicates
-                if !output_indices.contains(&idx) {
-                    output_columns.push(input_schema.columns()[idx].clone());
-                    output_indices.push(idx);
-                }
+    // Without RETURN every loaded column is kept; any the schema does not
+    // declare go last, in load order
+    if matches!(return_fields, None | Some([])) {
+        for (idx, column) in input_schema.columns().iter().enumerate() {
+            if !output_indices.contains(&idx) {
+                output_columns.push(column.clone());
+                output_indices.push(idx);
             }
         }
     }
@@ -115,6 +70,40 @@ fn compute_return_projection(
     Ok((output_schema, output_indices))
 }
 
+/// Projection applied to a shard flow's output. Aggregate output is already
+/// in select order (buckets, groups, then metrics as written) and passes
+/// through unchanged.
+async fn result_projection(
+    plan: &QueryPlan,
+    schema: &Arc<BatchSchema>,
+) -> Result<Projection, FlowOperatorError> {
+    if let Command::Query {
+        return_fields,
+        event_type,
+        ..
+    } = &plan.command
+        && plan.aggregate_plan.is_none()
+    {
+        let registry = plan.registry.read().await;
+        let (schema, indices) =
+            compute_return_projection(schema, return_fields.as_deref(), &registry, event_type)?;
+        return Ok(Projection { indices, schema });
+    }
+    Ok(Projection {
+        indices: (0..schema.column_count()).collect(),
+        schema: Arc::clone(schema),
+    })
+}
+
+/// Spawns one task of a shard flow, counting its running time toward the
+/// query's busy time.
+fn spawn_flow_task<F>(ctx: &FlowContext, task: F) -> JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(Arc::clone(ctx.scan_stats()).track_busy(task))
+}
+
 /// Handle returned by shard pipeline builders. Owns the downstream receiver,
 /// resulting batch schema, and any background tasks driving the flow.
 pub struct ShardFlowHandle {
@@ -177,7 +166,7 @@ pub async fn build_memtable_flow(
     let mut tasks: Vec<JoinHandle<()>> = Vec::new();
 
     let source_ctx = Arc::clone(&ctx);
-    tasks.push(tokio::spawn(async move {
+    tasks.push(spawn_flow_task(&ctx, async move {
         if let Err(err) = source.run(source_tx, source_ctx).await {
             // ChannelClosed is expected when LIMIT is reached early - don't log as error
             match &err {
@@ -201,7 +190,7 @@ pub async fn build_memtable_flow(
         let aggregate = AggregateOp::new(aggregate_config);
         let (agg_tx, agg_rx) = FlowChannel::bounded(ctx.batch_size(), Arc::clone(&metrics));
         let agg_ctx = Arc::clone(&ctx);
-        tasks.push(tokio::spawn(async move {
+        tasks.push(spawn_flow_task(&ctx, async move {
             if let Err(err) = aggregate.run(current_rx, agg_tx, agg_ctx).await {
                 // ChannelClosed is expected when LIMIT is reached early - don't log as error
                 match &err {
@@ -221,37 +210,8 @@ pub async fn build_memtable_flow(
         );
     }
 
-    // Compute projection for RETURN fields if specified
-    let projection = if let Command::Query {
-        return_fields,
-        event_type,
-        ..
-    } = &plan.command
-    {
-        let registry = plan.registry.read().await;
-        match compute_return_projection(
-            &final_schema,
-            return_fields.as_deref(),
-            &registry,
-            event_type,
-        ) {
-            Ok((output_schema, indices)) => {
-                final_schema = output_schema;
-                Projection {
-                    indices,
-                    schema: Arc::clone(&final_schema),
-                }
-            }
-            Err(e) => {
-                return Err(e);
-            }
-        }
-    } else {
-        Projection {
-            indices: (0..final_schema.column_count()).collect(),
-            schema: Arc::clone(&final_schema),
-        }
-    };
+    let projection = result_projection(&plan, &final_schema).await?;
+    final_schema = Arc::clone(&projection.schema);
 
     // Optimize: Skip ProjectOp if it's an identity projection (all columns in same order)
     // This avoids unnecessary cloning of all values
@@ -262,7 +222,7 @@ pub async fn build_memtable_flow(
         let projector = ProjectOp::new(projection);
         let (proj_tx, proj_rx) = FlowChannel::bounded(ctx.batch_size(), Arc::clone(&metrics));
         let proj_ctx = Arc::clone(&ctx);
-        tasks.push(tokio::spawn(async move {
+        tasks.push(spawn_flow_task(&ctx, async move {
             if let Err(err) = projector.run(current_rx, proj_tx, proj_ctx).await {
                 // ChannelClosed is expected when LIMIT is reached early - don't log as error
                 match &err {
@@ -317,7 +277,7 @@ pub async fn build_segment_flow(
     let mut tasks: Vec<JoinHandle<()>> = Vec::new();
 
     let source_ctx = Arc::clone(&ctx);
-    tasks.push(tokio::spawn(async move {
+    tasks.push(spawn_flow_task(&ctx, async move {
         if let Err(err) = source.run(source_tx, source_ctx).await {
             // ChannelClosed is expected when LIMIT is reached early - don't log as error
             match &err {
@@ -331,10 +291,8 @@ pub async fn build_segment_flow(
         }
     }));
 
-    let projection = Projection {
-        indices: (0..schema.column_count()).collect(),
-        schema: Arc::clone(&schema),
-    };
+    let projection = result_projection(&plan, &schema).await?;
+    let schema = Arc::clone(&projection.schema);
 
     // Optimize: Skip ProjectOp if it's an identity projection (all columns in same order)
     if projection.is_identity() {
@@ -344,7 +302,7 @@ pub async fn build_segment_flow(
         let projector = ProjectOp::new(projection);
         let (proj_tx, proj_rx) = FlowChannel::bounded(ctx.batch_size(), Arc::clone(&metrics));
         let proj_ctx = Arc::clone(&ctx);
-        tasks.push(tokio::spawn(async move {
+        tasks.push(spawn_flow_task(&ctx, async move {
             if let Err(err) = projector.run(current_rx, proj_tx, proj_ctx).await {
                 // ChannelClosed is expected when LIMIT is reached early - don't log as error
                 match &err {
@@ -370,7 +328,8 @@ pub async fn build_segment_stream(
     caches: Arc<QueryCaches>,
     limit_override: Option<usize>,
 ) -> Result<Option<ShardFlowHandle>, FlowOperatorError> {
-    if plan.limit() == Some(0) {
+    // A query cancelled while shards were still being dispatched reads nothing
+    if plan.limit() == Some(0) || ctx.cancellation().is_cancelled() {
         return Ok(None);
     }
 
@@ -391,11 +350,18 @@ pub async fn build_segment_stream(
     let (source_tx, mut current_rx) = FlowChannel::bounded(ctx.batch_size(), Arc::clone(&metrics));
     let mut tasks: Vec<JoinHandle<()>> = Vec::new();
 
+    // Zones answered from zone summaries bypass the row stream into the aggregate
+    let summary_slot: Option<ZoneSummarySlot> = plan
+        .aggregate_plan
+        .as_ref()
+        .map(|_| Arc::new(Mutex::new(None)));
+
     let plan_for_task = Arc::clone(&plan);
     let schema_for_task = Arc::clone(&schema);
     let ctx_for_task = Arc::clone(&ctx);
     let caches_for_task = Arc::clone(&caches);
-    tasks.push(tokio::spawn(async move {
+    let slot_for_task = summary_slot.clone();
+    tasks.push(spawn_flow_task(&ctx, async move {
         let steps: Vec<ExecutionStep<'_>> = plan_for_task
             .filter_groups
             .iter()
@@ -403,7 +369,9 @@ pub async fn build_segment_stream(
             .collect();
         let runner = SegmentQueryRunner::new(plan_for_task.as_ref(), steps)
             .with_caches(Some(Arc::as_ref(&caches_for_task)))
-            .with_limit(limit_override.or_else(|| plan_for_task.limit()));
+            .with_limit(limit_override.or_else(|| plan_for_task.limit()))
+            .with_zone_summary_slot(slot_for_task)
+            .with_late_materialization(late_materialization::enabled());
         if let Err(err) = runner
             .stream_into(ctx_for_task, Arc::clone(&schema_for_task), source_tx)
             .await
@@ -413,6 +381,9 @@ pub async fn build_segment_stream(
                 FlowOperatorError::ChannelClosed => {
                     debug!(target: "sneldb::flow", "Segment stream stopped (channel closed, likely LIMIT reached)");
                 }
+                FlowOperatorError::Cancelled => {
+                    debug!(target: "sneldb::flow", "Segment stream stopped (query cancelled)");
+                }
                 _ => {
                     error!(target: "sneldb::flow", error = %err, "Segment stream failed");
                 }
@@ -427,10 +398,10 @@ pub async fn build_segment_stream(
             plan: Arc::clone(&plan),
             aggregate: aggregate_plan.clone(),
         };
-        let aggregate = AggregateOp::new(aggregate_config);
+        let aggregate = AggregateOp::new(aggregate_config).with_zone_summaries(summary_slot);
         let (agg_tx, agg_rx) = FlowChannel::bounded(ctx.batch_size(), Arc::clone(&metrics));
         let agg_ctx = Arc::clone(&ctx);
-        tasks.push(tokio::spawn(async move {
+        tasks.push(spawn_flow_task(&ctx, async move {
             if let Err(err) = aggregate.run(current_rx, agg_tx, agg_ctx).await {
                 // ChannelClosed is expected when LIMIT is reached early - don't log as error
                 match &err {
@@ -450,37 +421,8 @@ pub async fn build_segment_stream(
         );
     }
 
-    // Compute projection for RETURN fields if specified
-    let projection = if let Command::Query {
-        return_fields,
-        event_type,
-        ..
-    } = &plan.command
-    {
-        let registry = plan.registry.read().await;
-        match compute_return_projection(
-            &final_schema,
-            return_fields.as_deref(),
-            &registry,
-            event_type,
-        ) {
-            Ok((output_schema, indices)) => {
-                final_schema = output_schema;
-                Projection {
-                    indices,
-                    schema: Arc::clone(&final_schema),
-                }
-            }
-            Err(e) => {
-                return Err(e);
-            }
-        }
-    } else {
-        Projection {
-            indices: (0..final_schema.column_count()).collect(),
-            schema: Arc::clone(&final_schema),
-        }
-    };
+    let projection = result_projection(&plan, &final_schema).await?;
+    final_schema = Arc::clone(&projection.schema);
 
     // Optimize: Skip ProjectOp if it's an identity projection (all columns in same order)
     if projection.is_identity() {
@@ -490,7 +432,7 @@ pub async fn build_segment_stream(
         let projector = ProjectOp::new(projection);
         let (proj_tx, proj_rx) = FlowChannel::bounded(ctx.batch_size(), Arc::clone(&metrics));
         let proj_ctx = Arc::clone(&ctx);
-        tasks.push(tokio::spawn(async move {
+        tasks.push(spawn_flow_task(&ctx, async move {
             if let Err(err) = projector.run(current_rx, proj_tx, proj_ctx).await {
                 // ChannelClosed is expected when LIMIT is reached early - don't log as error
                 match &err {
@@ -1336,3 +1336,95 @@ async fn memtable_flow_identity_projection_optimization() {
 
     assert!(!batches.is_empty());
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn memtable_and_segment_flows_share_column_order() {
+    use crate::command::types::{CompareOp, Expr};
+
+    let registry = SchemaRegistryFactory::new();
+    registry
+        .define_with_fields(
+            "flow_event",
+            &[("zeta", "string"), ("amount", "int"), ("region", "string")],
+        )
+        .await
+        .unwrap();
+
+    let cases: [(Option<Vec<&str>>, Vec<&str>); 2] = [
+        (
+            None,
+            vec![
+                "context_id",
+                "event_type",
+                "timestamp",
+                "event_id",
+                "zeta",
+                "amount",
+                "region",
+            ],
+        ),
+        (
+            Some(vec!["region", "timestamp", "amount"]),
+            vec![
+                "context_id",
+                "event_type",
+                "event_id",
+                "region",
+                "timestamp",
+                "amount",
+            ],
+        ),
+    ];
+
+    for (return_fields, expected) in cases {
+        let mut command = CommandFactory::query()
+            .with_event_type("flow_event")
+            .with_where_clause(Expr::Compare {
+                field: "region".into(),
+                op: CompareOp::Eq,
+                value: json!("eu"),
+            });
+        if let Some(fields) = return_fields {
+            command = command.with_return_fields(fields);
+        }
+        let plan = Arc::new(
+            QueryPlanFactory::new()
+                .with_command(command.create())
+                .with_registry(registry.registry())
+                .create()
+                .await,
+        );
+        let event = || {
+            EventFactory::new()
+                .with("context_id", "ctx1")
+                .with("timestamp", 10)
+                .with("event_type", "flow_event")
+                .with("payload", json!({"zeta": "z", "amount": 5, "region": "eu"}))
+                .create()
+        };
+
+        let memtable = MemTableFactory::new()
+            .with_events(vec![event()])
+            .create()
+            .unwrap();
+        let memtable_handle = build_memtable_flow(
+            Arc::clone(&plan),
+            Some(Arc::new(memtable)),
+            Vec::new(),
+            create_flow_context(),
+            None,
+        )
+        .await
+        .unwrap();
+        let segment_handle =
+            build_segment_flow(Arc::clone(&plan), vec![event()], create_flow_context())
+                .await
+                .unwrap()
+                .expect("segment flow available");
+
+        for schema in [&memtable_handle.schema, &segment_handle.schema] {
+            let names: Vec<&str> = schema.columns().iter().map(|c| c.name.as_str()).collect();
+            assert_eq!(names, expected);
+        }
+    }
+}
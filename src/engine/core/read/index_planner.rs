@@ -3,14 +3,19 @@ use crate::engine::core::filter::filter_group::FilterGroup;
 use crate::engine::core::read::catalog::{IndexKind, IndexRegistry};
 use crate::engine::core::read::event_scope::EventScope;
 use crate::engine::core::read::index_strategy::IndexStrategy;
-use crate::engine::schema::{FieldType, registry::SchemaRegistry};
+use crate::engine::core::read::selectivity::SelectivityEstimator;
+use crate::engine::schema::registry::SchemaRegistry;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Range predicates estimated to keep at least this fraction of rows skip index probing.
+pub const FULL_SCAN_SELECTIVITY: f64 = 0.95;
+
 pub struct IndexPlanner<'a> {
     pub registry: &'a Arc<RwLock<SchemaRegistry>>,
     pub index_registry: &'a IndexRegistry,
     pub event_scope: &'a EventScope,
+    pub selectivity: Option<&'a SelectivityEstimator>,
 }
 
 impl<'a> IndexPlanner<'a> {
@@ -23,18 +28,30 @@ impl<'a> IndexPlanner<'a> {
             registry,
             index_registry,
             event_scope,
+            selectivity: None,
         }
     }
 
+    pub fn with_selectivity(mut self, selectivity: &'a SelectivityEstimator) -> Self {
+        self.selectivity = Some(selectivity);
+        self
+    }
+
     pub async fn choose(&self, plan: &FilterGroup, segment_id: &str) -> IndexStrategy {
         // Only single filters can have index strategies
-        let (column, operation, index_strategy) = match plan {
+        let (column, operation, value, index_strategy) = match plan {
             FilterGroup::Filter {
                 column,
                 operation,
+                value,
                 index_strategy,
                 ..
-            } => (column.clone(), operation.clone(), index_strategy.clone()),
+            } => (
+                column.clone(),
+                operation.clone(),
+                value.clone(),
+                index_strategy.clone(),
+            ),
             _ => return IndexStrategy::FullScan, // Logical groups use FullScan
         };
 
@@ -50,6 +67,17 @@ impl<'a> IndexPlanner<'a> {
         let Some(uid_ref) = self.event_scope.primary_uid() else {
             return IndexStrategy::FullScan;
         };
+        // Event ids are ranged in zone metadata, which every segment has
+        if field == "event_id"
+            && matches!(
+                operation,
+                Some(
+                    CompareOp::Eq | CompareOp::Gt | CompareOp::Gte | CompareOp::Lt | CompareOp::Lte
+                )
+            )
+        {
+            return IndexStrategy::EventIdRange { field };
+        }
         // If no catalog for this segment, avoid choosing any index to prevent fs probing
         if !self.index_registry.has_catalog(segment_id) {
             return IndexStrategy::FullScan;
@@ -64,18 +92,13 @@ impl<'a> IndexPlanner<'a> {
                     .read()
                     .await
                     .get_schema_by_uid(uid_ref)
-                    .and_then(|s| s.field_type(&field).cloned())
-                    .map(|ft| match ft {
-                        FieldType::Timestamp | FieldType::Date => true,
-                        FieldType::Optional(inner) => {
-                            matches!(*inner, FieldType::Timestamp | FieldType::Date)
-                        }
-                        _ => false,
-                    })
-                    .unwrap_or(false)
+                    .is_some_and(|s| s.is_temporal_field(&field))
             }
         };
-        if is_temporal {
+        // Schemas may leave a time field without calendars; it then falls through
+        // to whatever other index the segment built for it
+        let has_temporal_index = field == "timestamp" || kinds.contains(IndexKind::FIELD_CALENDAR);
+        if is_temporal && has_temporal_index {
             // IN operations require checking multiple values, which temporal range indexes can't efficiently handle.
             // Use FullScan and let the condition evaluator filter events.
             if matches!(operation, Some(CompareOp::In)) {
@@ -102,6 +125,15 @@ impl<'a> IndexPlanner<'a> {
         if let Some(op) = &operation {
             use CompareOp::*;
             if matches!(op, Gt | Gte | Lt | Lte) && kinds.contains(IndexKind::ZONE_SURF) {
+                // Probing SuRF cannot prune much when nearly every row matches
+                let keeps_most_rows = self
+                    .selectivity
+                    .filter(|s| s.has_histograms())
+                    .map(|s| s.estimate(&field, Some(op), value.as_ref()) >= FULL_SCAN_SELECTIVITY)
+                    .unwrap_or(false);
+                if keeps_most_rows {
+                    return IndexStrategy::FullScan;
+                }
                 return IndexStrategy::ZoneSuRF { field };
             }
             // IN operations require checking multiple values, which XOR filters can't efficiently handle.
@@ -6,6 +6,8 @@ use crate::engine::core::read::index_planner::IndexPlanner;
 use crate::engine::core::read::index_strategy::IndexStrategy;
 use crate::engine::schema::registry::{MiniSchema, SchemaRegistry};
 use crate::engine::schema::types::{EnumType, FieldType};
+use crate::engine::types::ScalarValue;
+use indexmap::IndexMap;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -16,7 +18,7 @@ fn make_registry_with_schema(
     event_type: &str,
 ) -> (Arc<RwLock<SchemaRegistry>>, String) {
     let mut reg = SchemaRegistry::new_with_path(path).expect("registry");
-    let mut fields: HashMap<String, FieldType> = HashMap::new();
+    let mut fields: IndexMap<String, FieldType> = IndexMap::new();
     fields.insert("id".to_string(), FieldType::I64);
     fields.insert("timestamp".to_string(), FieldType::Timestamp);
     fields.insert("created_at".to_string(), FieldType::Timestamp);
@@ -26,7 +28,12 @@ fn make_registry_with_schema(
             variants: vec!["A".to_string(), "B".to_string()],
         }),
     );
-    let schema = MiniSchema { fields };
+    let schema = MiniSchema {
+        fields,
+        time_field: None,
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
+    };
     reg.define(event_type, schema).expect("define");
     let uid = reg.get_uid(event_type).expect("uid");
     (Arc::new(RwLock::new(reg)), uid)
@@ -111,7 +118,12 @@ async fn planner_temporal_for_non_timestamp_field_uses_schema() {
     let (registry, uid) = make_registry_with_schema(path, "ev");
 
     let mut idx = IndexRegistry::new();
-    idx.insert_catalog(make_catalog(&uid, "S1", |_| {}));
+    idx.insert_catalog(make_catalog(&uid, "S1", |c| {
+        c.set_field_kind(
+            "created_at",
+            IndexKind::FIELD_CALENDAR | IndexKind::FIELD_ZTI,
+        );
+    }));
     let scope = EventScope::Specific {
         event_type: "ev".to_string(),
         uid: Some(uid.clone()),
@@ -130,6 +142,44 @@ async fn planner_temporal_for_non_timestamp_field_uses_schema() {
     assert!(matches!(s, IndexStrategy::TemporalRange { .. }));
 }
 
+#[tokio::test]
+async fn planner_temporal_field_without_calendar_falls_back() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("schemas.bin");
+    let (registry, uid) = make_registry_with_schema(path, "ev");
+
+    // S1 was flushed with no indexes for created_at, S2 with only SuRF
+    let mut idx = IndexRegistry::new();
+    idx.insert_catalog(make_catalog(&uid, "S1", |c| {
+        c.set_field_kind("created_at", IndexKind::empty());
+    }));
+    idx.insert_catalog(make_catalog(&uid, "S2", |c| {
+        c.set_field_kind("created_at", IndexKind::ZONE_SURF);
+    }));
+    let scope = EventScope::Specific {
+        event_type: "ev".to_string(),
+        uid: Some(uid.clone()),
+    };
+    let planner = IndexPlanner::new(&registry, &idx, &scope);
+
+    let fp = FilterGroup::Filter {
+        column: "created_at".to_string(),
+        operation: Some(CompareOp::Gt),
+        value: None,
+        priority: 0,
+        uid: None,
+        index_strategy: None,
+    };
+    assert!(matches!(
+        planner.choose(&fp, "S1").await,
+        IndexStrategy::FullScan
+    ));
+    assert!(matches!(
+        planner.choose(&fp, "S2").await,
+        IndexStrategy::ZoneSuRF { .. }
+    ));
+}
+
 #[tokio::test]
 async fn planner_enum_bitmap_when_available() {
     let tmp = tempfile::tempdir().unwrap();
@@ -186,6 +236,53 @@ async fn planner_range_prefers_surf_when_available() {
     assert!(matches!(s, IndexStrategy::ZoneSuRF { .. }));
 }
 
+#[tokio::test]
+async fn planner_range_skips_surf_when_histogram_says_most_rows_match() {
+    use crate::engine::core::read::selectivity::SelectivityEstimator;
+    use crate::engine::core::zone::field_histogram::{FieldHistogram, FieldHistogramIndex};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("schemas.bin");
+    let (registry, uid) = make_registry_with_schema(path, "ev");
+
+    let mut idx = IndexRegistry::new();
+    idx.insert_catalog(make_catalog(&uid, "S1", |c| {
+        c.set_field_kind("id", IndexKind::ZONE_SURF);
+    }));
+    let scope = EventScope::Specific {
+        event_type: "ev".to_string(),
+        uid: Some(uid.clone()),
+    };
+    let mut selectivity = SelectivityEstimator::new();
+    let values: Vec<ScalarValue> = (0..100).map(ScalarValue::Int64).collect();
+    selectivity.insert(
+        "S1",
+        FieldHistogramIndex {
+            row_count: 100,
+            fields: HashMap::from([(
+                "id".to_string(),
+                FieldHistogram::build(values, 100, 32).unwrap(),
+            )]),
+        },
+    );
+    let planner = IndexPlanner::new(&registry, &idx, &scope).with_selectivity(&selectivity);
+
+    let range = |value: i64| FilterGroup::Filter {
+        column: "id".to_string(),
+        operation: Some(CompareOp::Gte),
+        value: Some(ScalarValue::Int64(value)),
+        priority: 0,
+        uid: None,
+        index_strategy: None,
+    };
+    // id >= 0 keeps every row: probing SuRF is wasted work
+    let s = planner.choose(&range(0), "S1").await;
+    assert!(matches!(s, IndexStrategy::FullScan));
+    // id >= 90 keeps ~10% of rows: SuRF still pays off
+    let s = planner.choose(&range(90), "S1").await;
+    assert!(matches!(s, IndexStrategy::ZoneSuRF { .. }));
+}
+
 #[tokio::test]
 async fn planner_equality_prefers_zxf_then_xf_then_fullscan() {
     let tmp = tempfile::tempdir().unwrap();
@@ -236,3 +333,38 @@ async fn planner_equality_prefers_zxf_then_xf_then_fullscan() {
     let s3 = planner3.choose(&fp, "S3").await;
     assert!(matches!(s3, IndexStrategy::FullScan));
 }
+
+#[tokio::test]
+async fn planner_event_id_range_without_catalog() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("schemas.bin");
+    let (registry, uid) = make_registry_with_schema(path, "ev");
+
+    let index_registry = IndexRegistry::new();
+    let scope = EventScope::Specific {
+        event_type: "ev".to_string(),
+        uid: Some(uid),
+    };
+    let planner = IndexPlanner::new(&registry, &index_registry, &scope);
+
+    let filter = |op: CompareOp| FilterGroup::Filter {
+        column: "event_id".to_string(),
+        operation: Some(op),
+        value: Some(ScalarValue::Int64(42)),
+        priority: 0,
+        uid: None,
+        index_strategy: None,
+    };
+    for op in [
+        CompareOp::Eq,
+        CompareOp::Gt,
+        CompareOp::Gte,
+        CompareOp::Lt,
+        CompareOp::Lte,
+    ] {
+        let strat = planner.choose(&filter(op), "S1").await;
+        assert!(matches!(strat, IndexStrategy::EventIdRange { .. }));
+    }
+    let strat = planner.choose(&filter(CompareOp::Neq), "S1").await;
+    assert!(matches!(strat, IndexStrategy::FullScan));
+}
@@ -6,5 +6,6 @@ pub enum IndexStrategy {
     ZoneSuRF { field: String },
     ZoneXorIndex { field: String },
     XorPresence { field: String },
+    EventIdRange { field: String },
     FullScan,
 }
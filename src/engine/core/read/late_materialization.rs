@@ -0,0 +1,98 @@
+use crate::engine::core::{
+    CandidateZone, ConditionEvaluator, QueryCaches, QueryPlan, RowSelection, ZoneValueLoader,
+};
+use crate::shared::config::CONFIG;
+
+/// Whether `query.late_materialization` is on. Defaults to true.
+pub fn enabled() -> bool {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|cfg| cfg.late_materialization)
+        .unwrap_or(true)
+}
+
+/// Splits the columns a segment scan loads into the ones its filter reads,
+/// loaded for every candidate zone, and the rest, loaded only into zones where
+/// some row passed the filter. A selective filter over wide rows then never
+/// decompresses the other columns of the zones it rules out.
+pub struct LateMaterialization<'a> {
+    plan: &'a QueryPlan,
+    caches: Option<&'a QueryCaches>,
+    plan_uid: Option<String>,
+    early: Vec<String>,
+    late: Vec<String>,
+}
+
+impl<'a> LateMaterialization<'a> {
+    /// `None` when nothing can be deferred: the filter reads none of the
+    /// columns the plan loads, or all of them.
+    pub async fn for_plan(plan: &'a QueryPlan, evaluator: &ConditionEvaluator) -> Option<Self> {
+        let filter_fields = evaluator.fields();
+        let (early, late): (Vec<String>, Vec<String>) = plan
+            .columns_to_load()
+            .await
+            .into_iter()
+            .partition(|column| filter_fields.contains(column));
+        if early.is_empty() || late.is_empty() {
+            return None;
+        }
+        Some(Self {
+            plan,
+            caches: None,
+            plan_uid: plan.event_type_uid().await,
+            early,
+            late,
+        })
+    }
+
+    pub fn with_caches(mut self, caches: Option<&'a QueryCaches>) -> Self {
+        self.caches = caches;
+        self
+    }
+
+    /// Columns to hydrate every candidate zone with: the ones the filter reads.
+    pub fn early_columns(&self) -> &[String] {
+        &self.early
+    }
+
+    /// Columns loaded only into zones with a row passing the filter.
+    pub fn late_columns(&self) -> &[String] {
+        &self.late
+    }
+
+    /// Evaluates `evaluator` over a zone hydrated with the early columns and,
+    /// unless it rules out every row, loads the late columns so the selected
+    /// rows can be materialized. Returns the selection and the decompressed
+    /// bytes the late columns added.
+    pub fn select(
+        &self,
+        evaluator: &ConditionEvaluator,
+        zone: &mut CandidateZone,
+    ) -> (RowSelection, usize) {
+        let selection = evaluator.select(zone);
+        if selection.row_count() > 0 && selection.is_empty() {
+            return (selection, 0);
+        }
+        let bytes = self.load_late(zone);
+        // Filter columns the segment predates hold no rows, which leaves the
+        // row count to the other columns, as when every column loads up front
+        if selection.row_count() == 0 {
+            return (evaluator.select(zone), bytes);
+        }
+        (selection, bytes)
+    }
+
+    fn load_late(&self, zone: &mut CandidateZone) -> usize {
+        let Some(uid) = zone
+            .uid()
+            .map(str::to_string)
+            .or_else(|| self.plan_uid.clone())
+        else {
+            return 0;
+        };
+        ZoneValueLoader::new(self.plan.segment_base_dir.clone(), uid)
+            .with_caches(self.caches)
+            .load_more_values(zone, &self.late)
+    }
+}
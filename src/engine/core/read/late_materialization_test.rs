@@ -0,0 +1,161 @@
+use crate::command::types::{CompareOp, Expr};
+use crate::engine::core::read::late_materialization::LateMaterialization;
+use crate::engine::core::{
+    CandidateZone, ConditionEvaluatorBuilder, Flusher, QueryPlan, ZoneValueLoader,
+};
+use crate::test_helpers::factories::{
+    CommandFactory, EventFactory, MemTableFactory, QueryPlanFactory, SchemaRegistryFactory,
+};
+use serde_json::json;
+use std::sync::Arc;
+use tempfile::{TempDir, tempdir};
+
+fn status_is_ok() -> Expr {
+    Expr::Compare {
+        field: "status".into(),
+        op: CompareOp::Eq,
+        value: json!("ok"),
+    }
+}
+
+/// Flushes one `ticket` event per status into segment `shard-0/00301`, one
+/// zone each, and plans `command` over it.
+async fn plan_over_tickets(
+    statuses: &[&str],
+    command: crate::command::types::Command,
+) -> (TempDir, QueryPlan) {
+    let tmp_dir = tempdir().unwrap();
+    let segment_dir = tmp_dir.path().join("shard-0").join("00301");
+    std::fs::create_dir_all(&segment_dir).unwrap();
+
+    let schema_factory = SchemaRegistryFactory::new();
+    let registry = schema_factory.registry();
+    schema_factory
+        .define_with_fields("ticket", &[("status", "string"), ("note", "string")])
+        .await
+        .unwrap();
+
+    let events = statuses
+        .iter()
+        .enumerate()
+        .map(|(i, status)| {
+            EventFactory::new()
+                .with("event_type", "ticket")
+                .with("context_id", format!("ctx-{}", i))
+                .with(
+                    "payload",
+                    json!({"status": status, "note": format!("note-{}", i)}),
+                )
+                .create()
+        })
+        .collect();
+    let memtable = MemTableFactory::new()
+        .with_capacity(10)
+        .with_events(events)
+        .create()
+        .unwrap();
+    Flusher::new(
+        memtable,
+        301,
+        &segment_dir,
+        Arc::clone(&registry),
+        Arc::new(tokio::sync::Mutex::new(())),
+    )
+    .flush()
+    .await
+    .expect("Flush failed");
+
+    let plan = QueryPlanFactory::new()
+        .with_command(command)
+        .with_registry(registry)
+        .with_segment_base_dir(tmp_dir.path())
+        .with_segment_ids(vec!["shard-0/00301".into()])
+        .create()
+        .await;
+    (tmp_dir, plan)
+}
+
+#[tokio::test]
+async fn for_plan_defers_the_columns_the_filter_does_not_read() {
+    let command = CommandFactory::query()
+        .with_event_type("ticket")
+        .with_where_clause(status_is_ok())
+        .create();
+    let (_dir, plan) = plan_over_tickets(&["ok"], command).await;
+    let evaluator = ConditionEvaluatorBuilder::build_from_plan(&plan);
+
+    let late = LateMaterialization::for_plan(&plan, &evaluator)
+        .await
+        .expect("status filter leaves columns to defer");
+
+    let mut early = late.early_columns().to_vec();
+    early.sort();
+    assert_eq!(early, vec!["event_type", "status"]);
+    assert!(late.late_columns().contains(&"note".to_string()));
+    assert!(!late.late_columns().contains(&"status".to_string()));
+
+    let mut all: Vec<String> = late
+        .early_columns()
+        .iter()
+        .chain(late.late_columns())
+        .cloned()
+        .collect();
+    all.sort();
+    let mut planned = plan.columns_to_load().await;
+    planned.sort();
+    assert_eq!(all, planned);
+}
+
+#[tokio::test]
+async fn for_plan_is_none_without_a_filter() {
+    let command = CommandFactory::query()
+        .with_event_type("ticket")
+        .add_count()
+        .create();
+    let (_dir, plan) = plan_over_tickets(&["ok"], command).await;
+    let evaluator = ConditionEvaluatorBuilder::build_from_plan(&plan);
+
+    assert!(
+        LateMaterialization::for_plan(&plan, &evaluator)
+            .await
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn select_loads_late_columns_only_into_zones_with_a_passing_row() {
+    let command = CommandFactory::query()
+        .with_event_type("ticket")
+        .with_where_clause(status_is_ok())
+        .create();
+    let (_dir, plan) = plan_over_tickets(&["fail", "ok", "fail"], command).await;
+    let evaluator = ConditionEvaluatorBuilder::build_from_plan(&plan);
+    let late = LateMaterialization::for_plan(&plan, &evaluator)
+        .await
+        .unwrap();
+
+    let uid = plan.event_type_uid().await.unwrap();
+    let mut zones = CandidateZone::create_all_zones_for_segment_from_meta(
+        &plan.segment_base_dir,
+        "shard-0/00301",
+        &uid,
+    );
+    ZoneValueLoader::new(plan.segment_base_dir.clone(), uid)
+        .load_zone_values(&mut zones, late.early_columns());
+
+    let mut notes = Vec::new();
+    for mut zone in zones {
+        assert!(!zone.values.contains_key("note"));
+        let (selection, bytes) = late.select(&evaluator, &mut zone);
+        if selection.is_empty() {
+            assert_eq!(bytes, 0);
+            assert!(!zone.values.contains_key("note"));
+            continue;
+        }
+        assert!(bytes > 0);
+        let mut events = Vec::new();
+        evaluator.materialize_into(&zone, &selection, None, &mut events);
+        notes.extend(events.iter().map(|e| e.get_field_value("note")));
+    }
+    assert_eq!(notes, vec!["note-1"]);
+}
@@ -1,7 +1,21 @@
 use crate::engine::core::ConditionEvaluatorBuilder;
+use crate::engine::core::read::event_scope::EventScope;
 use crate::engine::core::{Event, MemTable, QueryPlan};
 use tracing::{debug, info};
 
+/// The events of `memtable` a query can match, read with the access method
+/// the plan picks, the way the event type's layout serves it. Wildcard
+/// queries scan everything.
+pub(crate) fn candidate_events<'m>(
+    memtable: &'m MemTable,
+    plan: &QueryPlan,
+) -> Box<dyn Iterator<Item = &'m Event> + Send + 'm> {
+    let EventScope::Specific { event_type, .. } = plan.event_scope() else {
+        return Box::new(memtable.iter());
+    };
+    memtable.events_for(event_type, plan.memtable_access())
+}
+
 pub struct MemTableQuery<'a> {
     memtable: &'a MemTable,
     plan: &'a QueryPlan,
@@ -45,7 +59,7 @@ impl<'a> MemTableQuery<'a> {
 
         let mut events = Vec::new();
 
-        for event in self.memtable.iter() {
+        for event in candidate_events(self.memtable, self.plan) {
             if let Some(lim) = limit {
                 if events.len() >= lim {
                     break;
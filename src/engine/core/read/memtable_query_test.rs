@@ -1,5 +1,6 @@
 use crate::command::types::{CompareOp, Expr};
-use crate::engine::core::MemTableQuery;
+use crate::engine::core::{MemTable, MemTableQuery};
+use crate::shared::config::MemTableLayout;
 use crate::test_helpers::factories::{
     CommandFactory, EventFactory, MemTableFactory, QueryPlanFactory, SchemaRegistryFactory,
 };
@@ -164,3 +165,87 @@ async fn memtable_query_enforces_limit_mid_iteration() {
     let result = MemTableQuery::new(&memtable, &plan).query();
     assert_eq!(result.len(), 3);
 }
+
+#[tokio::test]
+async fn memtable_query_returns_the_same_events_for_every_layout() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let schema_factory = SchemaRegistryFactory::new();
+    let registry = schema_factory.registry();
+    schema_factory
+        .define_with_fields("visit", &[("status", "string")])
+        .await
+        .unwrap();
+
+    let context = |op, value: &str| Expr::Compare {
+        field: "context_id".into(),
+        op,
+        value: json!(value),
+    };
+    let status_ok = Expr::Compare {
+        field: "status".into(),
+        op: CompareOp::Eq,
+        value: json!("ok"),
+    };
+    let where_clauses = [
+        status_ok.clone(),
+        Expr::And(
+            Box::new(context(CompareOp::Eq, "c-4")),
+            Box::new(status_ok.clone()),
+        ),
+        Expr::And(
+            Box::new(status_ok),
+            Box::new(Expr::Not(Box::new(context(CompareOp::Eq, "c-2")))),
+        ),
+    ];
+
+    let events: Vec<_> = (0..20)
+        .map(|i| {
+            EventFactory::new()
+                .with("event_type", "visit")
+                .with("context_id", format!("c-{}", (i * 7) % 9))
+                .with(
+                    "payload",
+                    json!({"status": if i % 3 == 0 { "fail" } else { "ok" }}),
+                )
+                .create()
+        })
+        .collect();
+    let memtable_with = |layout| {
+        let mut memtable = MemTable::new(100).with_layouts([("visit".to_string(), layout)].into());
+        for event in &events {
+            memtable.insert(event.clone()).unwrap();
+        }
+        memtable
+    };
+    let ordered = memtable_with(MemTableLayout::Ordered);
+    let hashed = memtable_with(MemTableLayout::Hash);
+
+    let tmp_dir = tempdir().unwrap();
+    for where_clause in where_clauses {
+        let command = CommandFactory::query()
+            .with_event_type("visit")
+            .with_where_clause(where_clause)
+            .create();
+        let plan = QueryPlanFactory::new()
+            .with_command(command)
+            .with_registry(Arc::clone(&registry))
+            .with_segment_base_dir(tmp_dir.path())
+            .create()
+            .await;
+
+        let ids = |memtable: &MemTable| {
+            let mut ids: Vec<_> = MemTableQuery::new(memtable, &plan)
+                .query()
+                .iter()
+                .map(|e| e.event_id().raw())
+                .collect();
+            ids.sort();
+            ids
+        };
+        let expected = ids(&ordered);
+        assert!(!expected.is_empty());
+        assert_eq!(ids(&hashed), expected);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod aggregate;
 pub mod cache;
 pub mod catalog;
+pub mod deterministic;
 pub mod event_scope;
 pub mod event_sorter;
 pub mod execution_step;
@@ -8,8 +9,10 @@ pub mod field_comparator;
 pub mod flow;
 pub mod index_planner;
 pub mod index_strategy;
+pub mod late_materialization;
 pub mod memtable_query;
 pub mod memtable_query_runner;
+pub mod order_tiebreak;
 pub mod projection;
 pub mod query_context;
 pub mod query_execution;
@@ -17,8 +20,10 @@ pub mod query_plan;
 pub mod range_query_handler;
 pub mod result;
 pub mod segment_query_runner;
+pub mod selectivity;
 pub mod sequence;
 pub mod sink;
+pub mod top_rows;
 
 #[cfg(test)]
 mod event_scope_test;
@@ -31,10 +36,14 @@ mod field_comparator_test;
 #[cfg(test)]
 mod index_planner_test;
 #[cfg(test)]
+mod late_materialization_test;
+#[cfg(test)]
 mod memtable_query_runner_test;
 #[cfg(test)]
 mod memtable_query_test;
 #[cfg(test)]
+mod order_tiebreak_test;
+#[cfg(test)]
 mod query_context_test;
 #[cfg(test)]
 mod query_execution_test;
@@ -46,3 +55,7 @@ mod range_query_handler_test;
 mod result_test;
 #[cfg(test)]
 mod segment_query_runner_test;
+#[cfg(test)]
+mod selectivity_test;
+#[cfg(test)]
+mod top_rows_test;
@@ -0,0 +1,49 @@
+use crate::engine::core::read::deterministic;
+use crate::engine::core::read::flow::BatchSchema;
+use crate::engine::types::ScalarValue;
+use crate::shared::config::{CONFIG, OrderTiebreaker};
+use std::cmp::Ordering;
+
+/// Core column that orders events with equal ORDER BY values. Event ids are
+/// assigned at ingest from (millisecond, shard id, per-shard sequence), kept
+/// through WAL recovery and compaction, and never repeat, so equal timestamps
+/// resolve the same way before and after a flush or compaction. Across shards
+/// ties fall to the ingest millisecond first, then the shard id.
+pub const TIEBREAK_COLUMN: &str = "event_id";
+
+/// The configured tiebreak; deterministic mode always breaks ties by event id.
+pub fn configured() -> OrderTiebreaker {
+    if deterministic::enabled() {
+        return OrderTiebreaker::EventId;
+    }
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|cfg| cfg.order_tiebreaker)
+        .unwrap_or(OrderTiebreaker::EventId)
+}
+
+/// Position of the tiebreak column among rows ordered by `order_index`, or
+/// `None` when tiebreaking is off, the column is absent, or it is the order
+/// column itself.
+pub fn tiebreak_index(schema: &BatchSchema, order_index: usize) -> Option<usize> {
+    if configured() == OrderTiebreaker::None {
+        return None;
+    }
+    schema
+        .columns()
+        .iter()
+        .position(|column| column.name == TIEBREAK_COLUMN)
+        .filter(|&index| index != order_index)
+}
+
+/// Compares two rows by the tiebreak column; rows stay equal without one.
+pub fn break_tie(a: &[ScalarValue], b: &[ScalarValue], tiebreak_index: Option<usize>) -> Ordering {
+    let Some(index) = tiebreak_index else {
+        return Ordering::Equal;
+    };
+    match (a[index].as_u64(), b[index].as_u64()) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => a[index].compare(&b[index]),
+    }
+}
@@ -0,0 +1,38 @@
+use crate::engine::core::read::flow::BatchSchema;
+use crate::engine::core::read::order_tiebreak::{break_tie, tiebreak_index};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::types::ScalarValue;
+use std::cmp::Ordering;
+
+fn schema(names: &[&str]) -> BatchSchema {
+    BatchSchema::new(
+        names
+            .iter()
+            .map(|name| ColumnSpec {
+                name: (*name).into(),
+                logical_type: "Integer".into(),
+            })
+            .collect(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn tiebreak_index_finds_event_id_unless_it_is_the_order_column() {
+    let schema = schema(&["timestamp", "event_id", "amount"]);
+    assert_eq!(tiebreak_index(&schema, 0), Some(1));
+    assert_eq!(tiebreak_index(&schema, 1), None);
+
+    let without = self::schema(&["timestamp", "amount"]);
+    assert_eq!(tiebreak_index(&without, 0), None);
+}
+
+#[test]
+fn break_tie_orders_by_event_id() {
+    let a = vec![ScalarValue::Int64(100), ScalarValue::Int64(7)];
+    let b = vec![ScalarValue::Int64(100), ScalarValue::Int64(9)];
+    assert_eq!(break_tie(&a, &b, Some(1)), Ordering::Less);
+    assert_eq!(break_tie(&b, &a, Some(1)), Ordering::Greater);
+    assert_eq!(break_tie(&a, &a, Some(1)), Ordering::Equal);
+    assert_eq!(break_tie(&a, &b, None), Ordering::Equal);
+}
@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Mutex;
+
+use super::context::ProjectionContext;
+use super::planner::ProjectionPlanner;
+use crate::command::types::Command;
+use crate::engine::core::QueryPlan;
+use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::shared::config::CONFIG;
+
+/// Whether `query.verify_projection` is on.
+pub fn enabled() -> bool {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|cfg| cfg.verify_projection)
+        .unwrap_or(false)
+}
+
+/// Why a query needs a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColumnRole {
+    /// Evaluated by a filter: WHERE, FOR, SINCE
+    Filter,
+    /// Orders rows: ORDER BY, or the time field of sequences, LATEST and DEDUP
+    Sort,
+    /// Keys rows: BY, time buckets, LATEST PER and DEDUP BY
+    Group,
+    /// Input of an aggregate
+    Aggregate,
+    /// Joins the events of a sequence
+    Link,
+    /// Written to the result
+    Output,
+    /// Identifies events when shard results are merged
+    Identity,
+}
+
+impl ColumnRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColumnRole::Filter => "filter",
+            ColumnRole::Sort => "sort",
+            ColumnRole::Group => "group",
+            ColumnRole::Aggregate => "aggregate",
+            ColumnRole::Link => "link",
+            ColumnRole::Output => "output",
+            ColumnRole::Identity => "identity",
+        }
+    }
+
+    /// Roles that make a column worth loading before rows are discarded.
+    pub fn is_row_work(&self) -> bool {
+        !matches!(self, ColumnRole::Output)
+    }
+}
+
+impl fmt::Display for ColumnRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Every column the plan needs, with the roles it needs it for. Derived from
+/// the command itself rather than the `ProjectionPlanner`, so the two can be
+/// checked against each other.
+pub async fn column_roles(plan: &QueryPlan) -> BTreeMap<String, BTreeSet<ColumnRole>> {
+    let mut roles: BTreeMap<String, BTreeSet<ColumnRole>> = BTreeMap::new();
+    let mut add = |column: &str, role: ColumnRole| {
+        roles.entry(column.to_string()).or_default().insert(role);
+    };
+
+    let ctx = ProjectionContext::new(plan);
+    for column in ctx.filter_columns() {
+        add(&column, ColumnRole::Filter);
+    }
+    add("event_id", ColumnRole::Identity);
+
+    let Command::Query {
+        time_field,
+        sequence_time_field,
+        order_by,
+        return_fields,
+        link_field,
+        latest_per,
+        dedup,
+        ..
+    } = &plan.command
+    else {
+        return roles;
+    };
+    let time_field = time_field.as_deref().unwrap_or("timestamp");
+
+    if let Some(link_field) = link_field {
+        add(link_field, ColumnRole::Link);
+        add(
+            sequence_time_field.as_deref().unwrap_or("timestamp"),
+            ColumnRole::Sort,
+        );
+    }
+    if let Some(field) = latest_per {
+        add(field, ColumnRole::Group);
+        add(time_field, ColumnRole::Sort);
+    }
+    if let Some(dedup) = dedup {
+        for key in &dedup.keys {
+            add(key, ColumnRole::Group);
+        }
+        add(time_field, ColumnRole::Sort);
+    }
+
+    match &plan.aggregate_plan {
+        Some(agg) => {
+            for column in agg.group_by.iter().flatten() {
+                add(column, ColumnRole::Group);
+            }
+            if agg.time_bucket.is_some() {
+                add(time_field, ColumnRole::Group);
+            }
+            for op in &agg.ops {
+                match op {
+                    AggregateOpSpec::CountAll => {}
+                    AggregateOpSpec::CountField { field }
+                    | AggregateOpSpec::CountUnique { field }
+                    | AggregateOpSpec::CountDistinct { field }
+                    | AggregateOpSpec::Percentile { field, .. }
+                    | AggregateOpSpec::Total { field }
+                    | AggregateOpSpec::Avg { field }
+                    | AggregateOpSpec::Min { field }
+                    | AggregateOpSpec::Max { field } => add(field, ColumnRole::Aggregate),
+                }
+            }
+            // COUNT ALL alone counts rows of any one loaded column
+            if agg
+                .ops
+                .iter()
+                .all(|op| matches!(op, AggregateOpSpec::CountAll))
+                && agg.group_by.is_none()
+                && agg.time_bucket.is_none()
+            {
+                add("timestamp", ColumnRole::Aggregate);
+            }
+        }
+        None => {
+            if let Some(order) = order_by {
+                add(&order.field, ColumnRole::Sort);
+            }
+            let payload_fields = ctx.payload_fields().await;
+            for column in
+                ProjectionContext::output_columns(return_fields.as_deref(), &payload_fields)
+            {
+                add(&column, ColumnRole::Output);
+            }
+        }
+    }
+    roles
+}
+
+/// Columns a query's readers hydrated, recorded next to the columns the
+/// `ProjectionPlanner` requested for them. One per query, shared by its shards.
+#[derive(Debug, Default)]
+pub struct ProjectionAudit {
+    state: Mutex<ProjectionReport>,
+}
+
+impl ProjectionAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one reader: the columns it hydrated for `plan`.
+    pub async fn record<I, S>(&self, plan: &QueryPlan, hydrated: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let requested: BTreeSet<String> = ProjectionPlanner::new(plan)
+            .columns_to_load()
+            .await
+            .into_iter()
+            .collect();
+        let hydrated: BTreeSet<String> = hydrated.into_iter().map(Into::into).collect();
+        let roles = column_roles(plan).await;
+
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        state.readers += 1;
+        // Compared per reader: another reader hydrating a column does not make up for this one
+        state
+            .not_requested
+            .extend(hydrated.difference(&requested).cloned());
+        state
+            .not_hydrated
+            .extend(requested.difference(&hydrated).cloned());
+        state.requested.extend(requested);
+        state.hydrated.extend(hydrated);
+        for (column, column_roles) in roles {
+            state.roles.entry(column).or_default().extend(column_roles);
+        }
+    }
+
+    pub fn report(&self) -> ProjectionReport {
+        self.state.lock().unwrap_or_else(|p| p.into_inner()).clone()
+    }
+}
+
+/// What a `ProjectionAudit` saw, summed over the readers it recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectionReport {
+    pub readers: usize,
+    pub requested: BTreeSet<String>,
+    pub hydrated: BTreeSet<String>,
+    /// Hydrated by some reader that did not request them: a pushdown gap
+    pub not_requested: BTreeSet<String>,
+    /// Requested by some reader that did not hydrate them, typically because
+    /// the segments read predate the field
+    pub not_hydrated: BTreeSet<String>,
+    pub roles: BTreeMap<String, BTreeSet<ColumnRole>>,
+}
+
+impl ProjectionReport {
+    /// Requested or hydrated without the query needing them for anything.
+    pub fn unneeded(&self) -> Vec<&str> {
+        self.requested
+            .union(&self.hydrated)
+            .filter(|column| !self.roles.contains_key(*column))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Whether the readers hydrated a different set than was requested, or
+    /// loaded columns nothing needs.
+    pub fn diverges(&self) -> bool {
+        !self.not_requested.is_empty()
+            || !self.not_hydrated.is_empty()
+            || !self.unneeded().is_empty()
+    }
+
+    /// One line per loaded column with its roles, then one per divergence.
+    pub fn lines(&self) -> Vec<String> {
+        if self.readers == 0 {
+            return vec!["projection: no columns hydrated".to_string()];
+        }
+        let join = |columns: Vec<&str>| columns.join(", ");
+        let mut lines = vec![
+            format!(
+                "projection requested: {}",
+                join(self.requested.iter().map(String::as_str).collect())
+            ),
+            format!(
+                "projection hydrated: {}",
+                join(self.hydrated.iter().map(String::as_str).collect())
+            ),
+        ];
+        for column in self.requested.union(&self.hydrated) {
+            let roles = match self.roles.get(column) {
+                Some(roles) => {
+                    let names: Vec<&str> = roles.iter().map(ColumnRole::as_str).collect();
+                    let kind = if roles.iter().any(ColumnRole::is_row_work) {
+                        "row work"
+                    } else {
+                        "output only"
+                    };
+                    format!("{} ({})", names.join(", "), kind)
+                }
+                None => "unneeded".to_string(),
+            };
+            lines.push(format!("column {}: {}", column, roles));
+        }
+        if !self.not_requested.is_empty() {
+            lines.push(format!(
+                "projection warning: hydrated but not requested: {}",
+                join(self.not_requested.iter().map(String::as_str).collect())
+            ));
+        }
+        if !self.not_hydrated.is_empty() {
+            lines.push(format!(
+                "projection warning: requested but not hydrated: {}",
+                join(self.not_hydrated.iter().map(String::as_str).collect())
+            ));
+        }
+        let unneeded = self.unneeded();
+        if !unneeded.is_empty() {
+            lines.push(format!(
+                "projection warning: loaded but unneeded: {}",
+                join(unneeded)
+            ));
+        }
+        lines
+    }
+}
@@ -0,0 +1,133 @@
+use super::audit::{ColumnRole, ProjectionAudit, column_roles};
+use crate::command::parser::commands::query;
+use crate::engine::core::read::query_plan::QueryPlan;
+use crate::test_helpers::factories::{QueryPlanFactory, SchemaRegistryFactory};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+async fn plan_for(text: &str) -> QueryPlan {
+    let schema = SchemaRegistryFactory::new();
+    let registry = schema.registry();
+    schema
+        .define_with_fields(
+            "order",
+            &[("country", "string"), ("plan", "string"), ("amount", "int")],
+        )
+        .await
+        .unwrap();
+    QueryPlanFactory::new()
+        .with_command(query::parse(text).unwrap())
+        .with_registry(Arc::clone(&registry))
+        .with_segment_base_dir(tempdir().unwrap().path())
+        .create()
+        .await
+}
+
+fn roles(list: &[ColumnRole]) -> BTreeSet<ColumnRole> {
+    list.iter().copied().collect()
+}
+
+#[tokio::test]
+async fn selection_roles_separate_row_work_from_output() {
+    let plan = plan_for("QUERY order WHERE amount > 5 RETURN [country, plan] ORDER BY plan").await;
+    let roles_by_column = column_roles(&plan).await;
+
+    assert_eq!(roles_by_column["amount"], roles(&[ColumnRole::Filter]));
+    assert_eq!(
+        roles_by_column["plan"],
+        roles(&[ColumnRole::Sort, ColumnRole::Output])
+    );
+    assert_eq!(roles_by_column["country"], roles(&[ColumnRole::Output]));
+    assert_eq!(
+        roles_by_column["event_id"],
+        roles(&[ColumnRole::Output, ColumnRole::Identity])
+    );
+}
+
+#[tokio::test]
+async fn aggregation_roles_need_no_output_columns() {
+    let plan = plan_for("QUERY order TOTAL amount BY country").await;
+    let roles_by_column = column_roles(&plan).await;
+
+    assert_eq!(roles_by_column["amount"], roles(&[ColumnRole::Aggregate]));
+    assert_eq!(roles_by_column["country"], roles(&[ColumnRole::Group]));
+    assert!(!roles_by_column.contains_key("plan"));
+    assert!(!roles_by_column.contains_key("context_id"));
+}
+
+#[tokio::test]
+async fn audit_matches_when_readers_hydrate_what_was_requested() {
+    let plan = plan_for("QUERY order TOTAL amount BY country").await;
+    let requested = plan.columns_to_load().await;
+
+    let audit = ProjectionAudit::new();
+    audit
+        .record(&plan, requested.iter().map(String::as_str))
+        .await;
+    let report = audit.report();
+
+    assert_eq!(report.readers, 1);
+    assert!(!report.diverges(), "{:?}", report.lines());
+    assert!(
+        report
+            .lines()
+            .contains(&"column amount: aggregate (row work)".to_string()),
+        "{:?}",
+        report.lines()
+    );
+}
+
+#[tokio::test]
+async fn audit_reports_columns_hydrated_beyond_the_request() {
+    let plan = plan_for("QUERY order TOTAL amount BY country").await;
+    let mut hydrated = plan.columns_to_load().await;
+    hydrated.push("context_id".to_string());
+
+    let audit = ProjectionAudit::new();
+    audit.record(&plan, hydrated).await;
+    // A second reader hydrating only the request does not hide the first one's gap
+    audit.record(&plan, plan.columns_to_load().await).await;
+    let report = audit.report();
+
+    assert!(report.diverges());
+    assert_eq!(
+        report.not_requested,
+        BTreeSet::from(["context_id".to_string()])
+    );
+    assert!(report.not_hydrated.is_empty());
+    assert_eq!(report.unneeded(), vec!["context_id"]);
+    let lines = report.lines();
+    assert!(lines.contains(&"column context_id: unneeded".to_string()));
+    assert!(
+        lines.contains(&"projection warning: hydrated but not requested: context_id".to_string())
+    );
+}
+
+#[tokio::test]
+async fn audit_reports_requested_columns_no_reader_hydrated() {
+    let plan = plan_for("QUERY order RETURN [country]").await;
+    let hydrated: Vec<String> = plan
+        .columns_to_load()
+        .await
+        .into_iter()
+        .filter(|column| column != "country")
+        .collect();
+
+    let audit = ProjectionAudit::new();
+    audit.record(&plan, hydrated).await;
+    let report = audit.report();
+
+    assert_eq!(report.not_hydrated, BTreeSet::from(["country".to_string()]));
+    assert!(report.not_requested.is_empty());
+}
+
+#[test]
+fn audit_without_readers_reports_nothing_hydrated() {
+    let report = ProjectionAudit::new().report();
+    assert!(!report.diverges());
+    assert_eq!(
+        report.lines(),
+        vec!["projection: no columns hydrated".to_string()]
+    );
+}
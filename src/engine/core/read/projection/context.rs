@@ -1,4 +1,10 @@
+use super::columns::ProjectionColumns;
 use crate::engine::core::QueryPlan;
+use crate::engine::schema::SchemaRegistry;
+
+/// Columns every event carries, in the order results list them.
+pub const CORE_FIELDS: &[&str] = &["context_id", "event_type", "timestamp", "event_id"];
+
 pub struct ProjectionContext<'a> {
     pub plan: &'a QueryPlan,
 }
@@ -9,12 +15,7 @@ impl<'a> ProjectionContext<'a> {
     }
 
     pub fn core_fields(&self) -> Vec<String> {
-        vec![
-            "context_id".to_string(),
-            "event_type".to_string(),
-            "timestamp".to_string(),
-            "event_id".to_string(),
-        ]
+        CORE_FIELDS.iter().map(|f| f.to_string()).collect()
     }
 
     pub fn is_core_field(name: &str) -> bool {
@@ -39,27 +40,56 @@ impl<'a> ProjectionContext<'a> {
         cols
     }
 
+    /// Payload fields in schema declaration order. A wildcard event type takes
+    /// every schema in event type name order, keeping each field's first position.
     pub async fn payload_fields(&self) -> Vec<String> {
         let registry = self.plan.registry.read().await;
+        Self::payload_fields_of(&registry, self.plan.event_type())
+    }
 
-        // Handle wildcard event_type by collecting fields from all schemas
-        if self.plan.event_type() == "*" {
-            let mut all_fields: std::collections::HashSet<String> =
-                std::collections::HashSet::new();
-            for schema in registry.get_all().values() {
-                for field in schema.fields() {
-                    all_fields.insert(field.clone());
-                }
+    pub fn payload_fields_of(registry: &SchemaRegistry, event_type: &str) -> Vec<String> {
+        if event_type == "*" {
+            let mut event_types: Vec<&String> = registry.get_all().keys().collect();
+            event_types.sort();
+            let mut fields = ProjectionColumns::new();
+            for name in event_types {
+                fields.add_many(registry.get_all()[name].fields().cloned());
             }
-            let mut fields: Vec<String> = all_fields.into_iter().collect();
-            fields.sort();
-            fields
-        } else if let Some(schema) = registry.get(self.plan.event_type()) {
-            let mut fields: Vec<String> = schema.fields().cloned().collect();
-            fields.sort();
-            fields
+            fields.into_vec()
+        } else if let Some(schema) = registry.get(event_type) {
+            schema.fields().cloned().collect()
         } else {
             Vec::new()
         }
     }
+
+    /// Column order of a selection result: core fields, then payload fields in
+    /// declaration order. With RETURN, the listed fields keep their listed
+    /// position after the core fields the list leaves out.
+    pub fn output_columns(
+        return_fields: Option<&[String]>,
+        payload_fields: &[String],
+    ) -> Vec<String> {
+        let mut columns = ProjectionColumns::new();
+        match return_fields {
+            None | Some([]) => {
+                columns.add_many(CORE_FIELDS.iter().map(|f| f.to_string()));
+                columns.add_many(payload_fields.iter().cloned());
+            }
+            Some(list) => {
+                columns.add_many(
+                    CORE_FIELDS
+                        .iter()
+                        .filter(|f| !list.iter().any(|r| r == *f))
+                        .map(|f| f.to_string()),
+                );
+                columns.add_many(
+                    list.iter()
+                        .filter(|f| Self::is_core_field(f) || payload_fields.contains(f))
+                        .cloned(),
+                );
+            }
+        }
+        columns.into_vec()
+    }
 }
@@ -91,7 +91,8 @@ async fn payload_fields_returns_defined_schema_fields() {
 
     let ctx = ProjectionContext { plan: &plan };
     let fields = ctx.payload_fields().await;
-    assert_eq!(fields, vec!["amount".to_string(), "country".to_string()]);
+    // Declaration order, not alphabetical
+    assert_eq!(fields, vec!["country".to_string(), "amount".to_string()]);
 }
 
 #[tokio::test]
@@ -136,9 +137,41 @@ async fn payload_fields_wildcard_includes_all_schemas() {
 
     let ctx = ProjectionContext::new(&plan);
     let fields = ctx.payload_fields().await;
-    // Should include all unique fields from all schemas: field1, field2, field3
-    assert_eq!(fields.len(), 3);
-    assert!(fields.contains(&"field1".to_string()));
-    assert!(fields.contains(&"field2".to_string()));
-    assert!(fields.contains(&"field3".to_string()));
+    // Every unique field, schemas taken in event type order
+    assert_eq!(fields, vec!["field1", "field2", "field3"]);
+}
+
+#[test]
+fn output_columns_follow_return_order_after_unlisted_core_fields() {
+    let payload = vec!["country".to_string(), "amount".to_string()];
+
+    assert_eq!(
+        ProjectionContext::output_columns(None, &payload),
+        vec![
+            "context_id",
+            "event_type",
+            "timestamp",
+            "event_id",
+            "country",
+            "amount"
+        ]
+    );
+
+    let return_fields = vec![
+        "amount".to_string(),
+        "timestamp".to_string(),
+        "missing".to_string(),
+        "country".to_string(),
+    ];
+    assert_eq!(
+        ProjectionContext::output_columns(Some(&return_fields), &payload),
+        vec![
+            "context_id",
+            "event_type",
+            "event_id",
+            "amount",
+            "timestamp",
+            "country"
+        ]
+    );
 }
@@ -1,12 +1,17 @@
+pub mod audit;
 pub mod columns;
 pub mod context;
 pub mod planner;
 pub mod strategies;
 
+pub use audit::{ColumnRole, ProjectionAudit, ProjectionReport};
 pub use columns::ProjectionColumns;
+pub use context::{CORE_FIELDS, ProjectionContext};
 pub use planner::ProjectionPlanner;
 pub use strategies::{AggregationProjection, ProjectionStrategy, SelectionProjection};
 
+#[cfg(test)]
+mod audit_test;
 #[cfg(test)]
 mod columns_test;
 #[cfg(test)]
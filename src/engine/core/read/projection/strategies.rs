@@ -20,17 +20,6 @@ impl<'a> ProjectionStrategy for SelectionProjection<'a> {
         let mut set = ProjectionColumns::new();
         let ctx = ProjectionContext::new(self.plan);
         set.add_many(ctx.core_fields());
-        set.add_many(ctx.filter_columns());
-
-        // For sequence queries, include the link_field in columns to load
-        // This is needed for grouping events by the link field value
-        if let Command::Query {
-            link_field: Some(link_field),
-            ..
-        } = &self.plan.command
-        {
-            set.add(link_field.clone());
-        }
 
         let mode_all = match &self.plan.command {
             Command::Query { return_fields, .. } => match return_fields {
@@ -49,15 +38,25 @@ impl<'a> ProjectionStrategy for SelectionProjection<'a> {
             ..
         } = &self.plan.command
         {
+            // Loaded in RETURN order so the result projection rarely has to move them
             let payload_set: HashSet<String> = all_payload.into_iter().collect();
-            let projected: HashSet<String> = list
-                .iter()
-                .filter(|f| {
-                    ProjectionContext::is_core_field(f) || payload_set.contains(&f.to_string())
-                })
-                .cloned()
-                .collect();
-            set.add_many(projected);
+            set.add_many(
+                list.iter()
+                    .filter(|f| ProjectionContext::is_core_field(f) || payload_set.contains(*f))
+                    .cloned(),
+            );
+        }
+
+        set.add_many(ctx.filter_columns());
+
+        // For sequence queries, include the link_field in columns to load
+        // This is needed for grouping events by the link field value
+        if let Command::Query {
+            link_field: Some(link_field),
+            ..
+        } = &self.plan.command
+        {
+            set.add(link_field.clone());
         }
 
         set.add("event_id");
@@ -76,10 +75,13 @@ impl<'a> ProjectionStrategy for AggregationProjection<'a> {
         let mut set = ProjectionColumns::new();
         let ctx = ProjectionContext::new(self.plan);
 
+        // Core filters are resolved by zone pruning, except WHERE timestamp ranges,
+        // which only prune whole zones and are re-checked row by row.
+        let has_where = self.plan.where_clause().is_some();
         let filter_cols = ctx.filter_columns();
         let filtered = filter_cols
             .into_iter()
-            .filter(|c| !ProjectionContext::is_core_field(c))
+            .filter(|c| !ProjectionContext::is_core_field(c) || (has_where && c == "timestamp"))
             .collect::<Vec<String>>();
         set.add_many(filtered);
 
@@ -105,6 +107,8 @@ impl<'a> ProjectionStrategy for AggregationProjection<'a> {
                 AggregateOpSpec::CountAll => {}
                 AggregateOpSpec::CountField { field }
                 | AggregateOpSpec::CountUnique { field }
+                | AggregateOpSpec::CountDistinct { field }
+                | AggregateOpSpec::Percentile { field, .. }
                 | AggregateOpSpec::Total { field }
                 | AggregateOpSpec::Avg { field }
                 | AggregateOpSpec::Min { field }
@@ -325,3 +325,37 @@ async fn aggregation_filter_on_core_is_ignored_but_fallback_timestamp_applies()
     // fallback should still add timestamp since otherwise set would be empty
     assert!(out.contains("timestamp"));
 }
+
+#[tokio::test]
+async fn aggregation_where_on_timestamp_loads_timestamp_alongside_inputs() {
+    let schema = SchemaRegistryFactory::new();
+    let registry = schema.registry();
+    schema
+        .define_with_fields("evt", &[("x", "int")])
+        .await
+        .unwrap();
+
+    let cmd = CommandFactory::query()
+        .with_event_type("evt")
+        .with_where_clause(Expr::Compare {
+            field: "timestamp".into(),
+            op: CompareOp::Gte,
+            value: serde_json::json!(100),
+        })
+        .add_total("x")
+        .create();
+
+    let plan: QueryPlan = QueryPlanFactory::new()
+        .with_command(cmd)
+        .with_registry(Arc::clone(&registry))
+        .with_segment_base_dir(tempdir().unwrap().path())
+        .create()
+        .await;
+
+    let agg = plan.aggregate_plan.as_ref().unwrap();
+    let s = AggregationProjection { plan: &plan, agg };
+    let out = to_set(s.compute().await.into_vec());
+    // Rows of zones straddling the range are filtered on timestamp after loading
+    assert!(out.contains("timestamp"));
+    assert!(out.contains("x"));
+}
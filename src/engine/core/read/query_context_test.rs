@@ -45,6 +45,14 @@ fn creates_from_query_without_options() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx = QueryContext::from_command(&cmd);
@@ -75,6 +83,14 @@ fn creates_from_query_with_order_by() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx = QueryContext::from_command(&cmd);
@@ -119,6 +135,14 @@ fn creates_from_query_with_picked_zones() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx = QueryContext::from_command(&cmd);
@@ -163,6 +187,14 @@ fn creates_from_query_with_both_order_and_zones() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx = QueryContext::from_command(&cmd);
@@ -212,6 +244,14 @@ fn zone_filter_count_returns_correct_count() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx = QueryContext::from_command(&cmd);
@@ -247,6 +287,14 @@ fn clone_works() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx = QueryContext::from_command(&cmd);
@@ -288,6 +336,14 @@ fn display_format_is_readable() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx_with_order = QueryContext::from_command(&cmd);
@@ -330,6 +386,14 @@ fn picked_zones_deduplicates_automatically() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx = QueryContext::from_command(&cmd);
@@ -365,6 +429,14 @@ fn empty_picked_zones_list() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx = QueryContext::from_command(&cmd);
@@ -397,6 +469,14 @@ fn should_defer_limit_only_with_ordering() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     let ctx_with_order = QueryContext::from_command(&cmd_with_order);
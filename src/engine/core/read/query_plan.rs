@@ -1,5 +1,4 @@
-use crate::command::types::{Command, CompareOp, Expr, OrderSpec};
-use crate::engine::core::InflightSegments;
+use crate::command::types::{Command, CompareOp, Expr, OrderSpec, PrunerKind};
 use crate::engine::core::filter::filter_group::FilterGroup;
 use crate::engine::core::filter::filter_group_builder::FilterGroupBuilder;
 use crate::engine::core::read::aggregate::plan::AggregatePlan;
@@ -8,8 +7,11 @@ use crate::engine::core::read::catalog::IndexRegistry;
 use crate::engine::core::read::event_scope::EventScope;
 use crate::engine::core::read::index_planner::IndexPlanner;
 use crate::engine::core::read::projection::ProjectionPlanner;
+use crate::engine::core::read::selectivity::SelectivityEstimator;
+use crate::engine::core::{InflightSegments, MemTableAccess};
 use crate::engine::schema::registry::SchemaRegistry;
 use crate::engine::types::ScalarValue;
+use crate::shared::time::{TimeKind, TimeParser};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -29,6 +31,7 @@ pub struct QueryPlan {
     pub segment_ids: Arc<std::sync::RwLock<Vec<String>>>,
     pub aggregate_plan: Option<AggregatePlan>,
     pub index_registry: IndexRegistry,
+    pub selectivity: SelectivityEstimator,
     event_scope: EventScope,
     inflight_segments: Option<InflightSegments>,
 }
@@ -36,12 +39,40 @@ pub struct QueryPlan {
 impl QueryPlan {
     /// Creates a new query plan from a Query command and schema registry
     pub async fn new(
-        command: Command,
+        mut command: Command,
         registry: &Arc<RwLock<SchemaRegistry>>,
         segment_base_dir: &Path,
         segment_ids: &Arc<std::sync::RwLock<Vec<String>>>,
         inflight_segments: Option<InflightSegments>,
     ) -> Option<Self> {
+        // Without USING, time filters and buckets follow the schema's declared time field
+        if let Command::Query {
+            event_type,
+            time_field: time_field @ None,
+            ..
+        } = &mut command
+        {
+            *time_field = registry
+                .read()
+                .await
+                .time_field(event_type)
+                .map(str::to_string);
+        }
+        // Temporal and UUID literals become what their fields store, so a date
+        // compares by its day, a time by its time of day and a UUID whatever its
+        // case, in filters and pruning alike
+        if let Command::Query {
+            event_type,
+            where_clause: Some(expr),
+            ..
+        } = &mut command
+        {
+            let guard = registry.read().await;
+            if let Some(schema) = guard.get(event_type) {
+                *expr = FilterGroupBuilder::normalize_literals(expr, schema);
+            }
+        }
+
         match &command {
             Command::Query {
                 where_clause,
@@ -117,6 +148,7 @@ impl QueryPlan {
                     segment_ids: Arc::clone(segment_ids),
                     aggregate_plan,
                     index_registry: IndexRegistry::new(),
+                    selectivity: SelectivityEstimator::new(),
                     event_scope,
                     inflight_segments,
                 };
@@ -130,6 +162,10 @@ impl QueryPlan {
                     let _ = GlobalIndexCatalogCache::instance();
                     plan.index_registry
                         .load_for_segments(&plan.segment_base_dir, &segs, &uid);
+                    if !plan.filter_groups.is_empty() {
+                        plan.selectivity
+                            .load_for_segments(&plan.segment_base_dir, &segs, &uid);
+                    }
                     // Assign index strategies using a segment that has a loaded catalog as representative
                     // Prefer a segment that has a catalog entry
                     let rep_seg = segs
@@ -139,7 +175,8 @@ impl QueryPlan {
                     if let Some(rep) = rep_seg {
                         let scope_clone = plan.event_scope.clone();
                         let planner =
-                            IndexPlanner::new(&plan.registry, &plan.index_registry, &scope_clone);
+                            IndexPlanner::new(&plan.registry, &plan.index_registry, &scope_clone)
+                                .with_selectivity(&plan.selectivity);
                         let mut filters = std::mem::take(&mut plan.filter_groups);
                         for fg in &mut filters {
                             let strat = planner.choose(fg, rep).await;
@@ -226,6 +263,40 @@ impl QueryPlan {
         self.order_by()
     }
 
+    /// How to read a memtable for this query: a single context when the
+    /// query names one, with `FOR` or an `AND`ed `context_id = '...'` the
+    /// row filter compares as a string, a scan otherwise.
+    pub fn memtable_access(&self) -> MemTableAccess {
+        if let Some(context_id) = self.context_id() {
+            return MemTableAccess::Context(context_id.to_string());
+        }
+        let mut conjuncts: Vec<&Expr> = self.where_clause().into_iter().collect();
+        while let Some(expr) = conjuncts.pop() {
+            match expr {
+                Expr::And(left, right) => conjuncts.extend([&**left, &**right]),
+                Expr::Compare {
+                    field,
+                    op: CompareOp::Eq,
+                    value,
+                } if field == "context_id" => {
+                    // Numeric and temporal literals compare as numbers, matching other spellings
+                    if let Some(context_id) = value.as_str()
+                        && context_id.parse::<i64>().is_err()
+                        && TimeParser::parse_str_to_epoch_seconds(context_id, TimeKind::DateTime)
+                            .or_else(|| {
+                                TimeParser::parse_str_to_epoch_seconds(context_id, TimeKind::Date)
+                            })
+                            .is_none()
+                    {
+                        return MemTableAccess::Context(context_id.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        MemTableAccess::Scan
+    }
+
     pub fn context_id_plan(&self) -> Option<&FilterGroup> {
         self.filter_groups.iter().find(|plan| plan.is_context_id())
     }
@@ -305,6 +376,7 @@ impl QueryPlan {
             segment_ids: Arc::new(std::sync::RwLock::new(Vec::new())),
             aggregate_plan,
             index_registry: IndexRegistry::new(),
+            selectivity: SelectivityEstimator::new(),
             event_scope,
             inflight_segments: None,
         }
@@ -329,6 +401,11 @@ impl QueryPlan {
             .unwrap_or(false)
     }
 
+    /// False when the query switched `kind` off with `DISABLE PRUNERS`.
+    pub fn pruner_enabled(&self, kind: PrunerKind) -> bool {
+        !self.command.disabled_pruners().contains(&kind)
+    }
+
     /// Returns true if the given segment is expected to contain data for the provided uid.
     pub fn segment_maybe_contains_uid(&self, segment_id: &str, uid: &str) -> bool {
         if self.index_registry.has_catalog(segment_id) {
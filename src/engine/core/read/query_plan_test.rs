@@ -1,21 +1,28 @@
 use crate::command::types::{CompareOp, Expr};
+use crate::engine::core::MemTableAccess;
 use crate::engine::core::filter::filter_group::FilterGroup;
 use crate::engine::core::read::catalog::{IndexKind, SegmentIndexCatalog};
 use crate::engine::core::read::index_strategy::IndexStrategy;
 use crate::engine::core::read::query_plan::QueryPlan;
 use crate::engine::schema::registry::{MiniSchema, SchemaRegistry};
 use crate::engine::schema::types::FieldType;
-use std::collections::HashMap;
+use crate::test_helpers::factories::CommandFactory;
+use indexmap::IndexMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 async fn registry_with_schema_at(path: PathBuf) -> Arc<RwLock<SchemaRegistry>> {
     let mut reg = SchemaRegistry::new_with_path(path).expect("registry");
-    let mut fields: HashMap<String, FieldType> = HashMap::new();
+    let mut fields: IndexMap<String, FieldType> = IndexMap::new();
     fields.insert("id".to_string(), FieldType::I64);
     fields.insert("timestamp".to_string(), FieldType::Timestamp);
-    let schema = MiniSchema { fields };
+    let schema = MiniSchema {
+        fields,
+        time_field: None,
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
+    };
     reg.define("ev", schema).expect("define");
     Arc::new(RwLock::new(reg))
 }
@@ -142,3 +149,110 @@ async fn query_plan_aggregates_remove_implicit_since_filter() {
     });
     assert!(!has_implicit_since);
 }
+
+#[tokio::test]
+async fn query_plan_defaults_time_field_to_declared_schema_field() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut reg = SchemaRegistry::new_with_path(tmp.path().join("schemas.bin")).unwrap();
+    let mut fields: IndexMap<String, FieldType> = IndexMap::new();
+    fields.insert("id".to_string(), FieldType::I64);
+    fields.insert("event_time".to_string(), FieldType::Timestamp);
+    let schema = MiniSchema {
+        fields,
+        time_field: Some("event_time".to_string()),
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
+    };
+    reg.define("ev", schema).expect("define");
+    let registry = Arc::new(RwLock::new(reg));
+
+    let base_dir = tempfile::tempdir().unwrap();
+    let seg_ids = Arc::new(std::sync::RwLock::new(vec![]));
+
+    let cmd = crate::test_helpers::factories::command_factory::CommandFactory::query()
+        .with_event_type("ev")
+        .with_since("2020-01-01T00:00:00Z")
+        .create();
+
+    let plan = QueryPlan::new(cmd, &registry, base_dir.path(), &seg_ids, None)
+        .await
+        .unwrap();
+
+    let crate::command::types::Command::Query { time_field, .. } = &plan.command else {
+        panic!("expected a Query command");
+    };
+    assert_eq!(time_field.as_deref(), Some("event_time"));
+    // The implicit since filter applies to the declared field, not the core timestamp
+    assert!(
+        plan.filter_groups
+            .iter()
+            .any(|fg| fg.column() == Some("event_time")
+                && matches!(fg.operation(), Some(CompareOp::Gte)))
+    );
+    assert!(
+        !plan
+            .filter_groups
+            .iter()
+            .any(|fg| fg.column() == Some("timestamp"))
+    );
+}
+
+#[tokio::test]
+async fn memtable_access_looks_up_contexts_compared_as_strings() {
+    let tmp = tempfile::tempdir().unwrap();
+    let registry = registry_with_schema_at(tmp.path().join("schemas.bin")).await;
+    let seg_ids = Arc::new(std::sync::RwLock::new(Vec::new()));
+    let context_is = |value: &str| Expr::Compare {
+        field: "context_id".to_string(),
+        op: CompareOp::Eq,
+        value: serde_json::json!(value),
+    };
+    let id_is_one = Expr::Compare {
+        field: "id".to_string(),
+        op: CompareOp::Eq,
+        value: serde_json::json!(1),
+    };
+    let access = |context_id: Option<&str>, where_clause: Option<Expr>| {
+        let mut query = CommandFactory::query().with_event_type("ev");
+        if let Some(context_id) = context_id {
+            query = query.with_context_id(context_id);
+        }
+        if let Some(expr) = where_clause {
+            query = query.with_where_clause(expr);
+        }
+        let command = query.create();
+        let (registry, base_dir, seg_ids) = (&registry, tmp.path(), &seg_ids);
+        async move {
+            QueryPlan::new(command, registry, base_dir, seg_ids, None)
+                .await
+                .unwrap()
+                .memtable_access()
+        }
+    };
+
+    assert_eq!(
+        access(Some("c1"), None).await,
+        MemTableAccess::Context("c1".into())
+    );
+    let and = Expr::And(
+        Box::new(id_is_one.clone()),
+        Box::new(Expr::And(
+            Box::new(id_is_one.clone()),
+            Box::new(context_is("c2")),
+        )),
+    );
+    assert_eq!(
+        access(None, Some(and)).await,
+        MemTableAccess::Context("c2".into())
+    );
+
+    // The row filter compares these as numbers, so "0042" would match too
+    for literal in ["42", "2024-01-01"] {
+        let and = Expr::And(Box::new(id_is_one.clone()), Box::new(context_is(literal)));
+        assert_eq!(access(None, Some(and)).await, MemTableAccess::Scan);
+    }
+    // A context under OR does not restrict the rows
+    let or = Expr::Or(Box::new(id_is_one.clone()), Box::new(context_is("c2")));
+    assert_eq!(access(None, Some(or)).await, MemTableAccess::Scan);
+    assert_eq!(access(None, Some(id_is_one)).await, MemTableAccess::Scan);
+}
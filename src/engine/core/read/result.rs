@@ -99,10 +99,24 @@ impl AggregateResult {
                     name: format!("count_{}", field),
                     logical_type: "Integer".to_string(),
                 }),
-                AggregateOpSpec::CountUnique { field } => columns.push(ColumnSpec {
-                    name: format!("count_unique_{}", field),
+                AggregateOpSpec::CountUnique { field } => {
+                    columns.push(ColumnSpec {
+                        name: format!("count_unique_{}", field),
+                        logical_type: "Integer".to_string(),
+                    });
+                    columns.push(ColumnSpec {
+                        name: format!("count_unique_{}_estimated", field),
+                        logical_type: "Boolean".to_string(),
+                    });
+                }
+                AggregateOpSpec::CountDistinct { field } => columns.push(ColumnSpec {
+                    name: format!("count_distinct_{}", field),
                     logical_type: "Integer".to_string(),
                 }),
+                AggregateOpSpec::Percentile { field, percentile } => columns.push(ColumnSpec {
+                    name: super::aggregate::plan::percentile_column(field, *percentile),
+                    logical_type: "Float".to_string(),
+                }),
                 AggregateOpSpec::Total { field } => columns.push(ColumnSpec {
                     name: format!("total_{}", field),
                     logical_type: "Integer".to_string(),
@@ -134,8 +148,9 @@ impl AggregateResult {
                     let v = k
                         .groups
                         .get(i)
-                        .map(|s| ScalarValue::Utf8(s.clone()))
-                        .unwrap_or(ScalarValue::Utf8(String::new()));
+                        .cloned()
+                        .flatten()
+                        .map_or(ScalarValue::Null, ScalarValue::Utf8);
                     row.push(v);
                 }
             }
@@ -152,7 +167,29 @@ impl AggregateResult {
                     (
                         AggregateOpSpec::CountUnique { .. },
                         super::aggregate::partial::AggState::CountUnique { values },
-                    ) => row.push(ScalarValue::Int64(values.len() as i64)),
+                    ) => {
+                        row.push(ScalarValue::Int64(values.len() as i64));
+                        row.push(ScalarValue::Boolean(false));
+                    }
+                    (
+                        AggregateOpSpec::CountUnique { .. },
+                        super::aggregate::partial::AggState::CountUniqueSketch { sketch },
+                    ) => {
+                        row.push(ScalarValue::Int64(sketch.estimate() as i64));
+                        row.push(ScalarValue::Boolean(true));
+                    }
+                    (
+                        AggregateOpSpec::CountDistinct { .. },
+                        super::aggregate::partial::AggState::CountUniqueSketch { sketch },
+                    ) => row.push(ScalarValue::Int64(sketch.estimate() as i64)),
+                    (
+                        AggregateOpSpec::Percentile { percentile, .. },
+                        super::aggregate::partial::AggState::Percentile { digest },
+                    ) => row.push(
+                        digest
+                            .quantile(percentile / 100.0)
+                            .map_or(ScalarValue::Null, ScalarValue::Float64),
+                    ),
                     (
                         AggregateOpSpec::Total { .. },
                         super::aggregate::partial::AggState::Sum { sum },
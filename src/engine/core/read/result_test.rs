@@ -111,13 +111,13 @@ fn aggregate_finalize_outputs_rows_matching_group_keys_and_states() {
         .with_bucket(1725148800)
         .with_groups(&["US"])
         .create();
-    let gk_us_groups: Vec<&str> = gk_us.groups.iter().map(|s| s.as_str()).collect();
+    let gk_us_groups: Vec<&str> = gk_us.groups.iter().flatten().map(|s| s.as_str()).collect();
 
     let gk_de = GroupKeyFactory::new()
         .with_bucket(1725148800)
         .with_groups(&["DE"])
         .create();
-    let gk_de_groups: Vec<&str> = gk_de.groups.iter().map(|s| s.as_str()).collect();
+    let gk_de_groups: Vec<&str> = gk_de.groups.iter().flatten().map(|s| s.as_str()).collect();
 
     let agg = AggregateResultFactory::new()
         .with_group_by(vec!["country"])
@@ -1,10 +1,18 @@
-use crate::engine::core::read::flow::{BatchSchema, BatchSender, FlowContext, FlowOperatorError};
+use crate::engine::core::read::aggregate::partial::AggPartial;
+use crate::engine::core::read::aggregate::zone_summary_plan::{ZoneSummaryPlan, ZoneSummarySlot};
+use crate::engine::core::read::flow::{
+    BatchSchema, BatchSender, FlowContext, FlowOperatorError, QueryScanStats,
+};
+use crate::engine::core::read::late_materialization::LateMaterialization;
+use crate::engine::core::read::order_tiebreak;
+use crate::engine::core::read::top_rows::TopRows;
+use crate::engine::core::zone::zone_artifacts::ZoneArtifacts;
 use crate::engine::core::{
-    CandidateZone, ConditionEvaluatorBuilder, Event, EventSorter, ExecutionStep, QueryCaches,
-    QueryContext, QueryPlan, ZoneHydrator,
+    CandidateZone, ConditionEvaluator, ConditionEvaluatorBuilder, Event, EventSorter,
+    ExecutionStep, QueryCaches, QueryContext, QueryPlan, ZoneHydrator,
 };
 use crate::engine::types::ScalarValue;
-use std::cmp::Ordering;
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use tracing::info;
 
@@ -13,6 +21,8 @@ pub struct SegmentQueryRunner<'a> {
     steps: Vec<ExecutionStep<'a>>,
     caches: Option<&'a QueryCaches>,
     limit: Option<usize>,
+    summary_slot: Option<ZoneSummarySlot>,
+    late_materialization: bool,
 }
 
 impl<'a> SegmentQueryRunner<'a> {
@@ -22,6 +32,8 @@ impl<'a> SegmentQueryRunner<'a> {
             steps,
             caches: None,
             limit: None,
+            summary_slot: None,
+            late_materialization: false,
         }
     }
 
@@ -32,7 +44,7 @@ impl<'a> SegmentQueryRunner<'a> {
         let ctx = QueryContext::from_command(&self.plan.command);
 
         // Hydrate zones with optional zone filtering
-        let mut candidate_zones = self.hydrate_zones(&ctx).await;
+        let (mut candidate_zones, _) = self.hydrate_zones(&ctx, None, None).await;
 
         // Sort zones deterministically by (segment_id, zone_id) to ensure consistent processing order
         // This prevents flaky tests due to non-deterministic HashMap iteration order
@@ -62,11 +74,20 @@ impl<'a> SegmentQueryRunner<'a> {
     }
 
     /// Hydrates candidate zones, applying zone filtering if present in context.
-    async fn hydrate_zones(&self, ctx: &QueryContext) -> Vec<CandidateZone> {
+    /// Zones answered from zone summaries come back as a partial aggregate instead.
+    /// `columns` narrows the columns loaded, every column the plan needs by default.
+    async fn hydrate_zones(
+        &self,
+        ctx: &QueryContext,
+        summaries: Option<&ZoneSummaryPlan>,
+        columns: Option<Vec<String>>,
+    ) -> (Vec<CandidateZone>, Option<AggPartial>) {
         ZoneHydrator::new(self.plan, self.steps.clone())
             .with_caches(self.caches)
             .with_allowed_zones(ctx.picked_zones.clone())
-            .hydrate()
+            .with_zone_summaries(summaries)
+            .with_columns(columns)
+            .hydrate_with_summaries()
             .await
     }
 
@@ -88,11 +109,89 @@ impl<'a> SegmentQueryRunner<'a> {
         evaluator.evaluate_zones_with_limit(zones, limit)
     }
 
+    /// Matching events of one zone. With late materialization the zone arrives
+    /// holding only its filter columns; the others are loaded once a row passes.
+    fn zone_events(
+        &self,
+        evaluator: &ConditionEvaluator,
+        late: Option<&LateMaterialization<'_>>,
+        mut zone: CandidateZone,
+        limit: Option<usize>,
+        scan_stats: &QueryScanStats,
+    ) -> Vec<Event> {
+        let Some(late) = late else {
+            return evaluator.evaluate_zones_with_limit(vec![zone], limit);
+        };
+        let (selection, bytes) = late.select(evaluator, &mut zone);
+        scan_stats.add_bytes_scanned(bytes as u64);
+        let mut events = Vec::with_capacity(selection.selected());
+        evaluator.materialize_into(&zone, &selection, limit, &mut events);
+        events
+    }
+
     /// Creates an EventSorter if ORDER BY is present in context.
     fn create_sorter(&self, ctx: &QueryContext) -> Option<EventSorter> {
         ctx.order_by.as_ref().map(EventSorter::from_order_spec)
     }
 
+    /// Orders zones for an ORDER BY on a time field so the ones holding the
+    /// first rows are scanned first: newest-first for descending order. Each
+    /// zone comes with its (min, max) from the temporal index; zones without
+    /// one are scanned up front as they cannot be ruled out.
+    ///
+    /// Only fields every event carries qualify (the core `timestamp` and the
+    /// schema's `USING` fields): a null sorts outside the indexed range, so a
+    /// zone holding one could not be skipped. Without a limit every zone is
+    /// read anyway and the scan order is left as is.
+    async fn order_zones_by_time(
+        &self,
+        zones: Vec<CandidateZone>,
+        field: &str,
+        ascending: bool,
+    ) -> Vec<(CandidateZone, Option<(i64, i64)>)> {
+        let never_null = field == "timestamp"
+            || self
+                .plan
+                .registry
+                .read()
+                .await
+                .get(self.plan.event_type())
+                .is_some_and(|schema| schema.declared_time_fields().any(|f| f == field));
+        if self.limit.is_none() || !never_null {
+            return zones.into_iter().map(|zone| (zone, None)).collect();
+        }
+        let plan_uid = self.plan.event_type_uid().await;
+        let artifacts = ZoneArtifacts::new(&self.plan.segment_base_dir, self.caches);
+
+        let (mut bounded, mut ordered): (Vec<_>, Vec<_>) = zones
+            .into_iter()
+            .map(|zone| {
+                let bounds = zone
+                    .uid()
+                    .map(str::to_string)
+                    .or_else(|| plan_uid.clone())
+                    .and_then(|uid| {
+                        artifacts
+                            .load_field_temporal_index(&zone.segment_id, &uid, field, zone.zone_id)
+                            .ok()
+                            .map(|zti| (zti.min_ts, zti.max_ts))
+                    });
+                (zone, bounds)
+            })
+            .partition(|(_, bounds)| bounds.is_some());
+
+        bounded.sort_by(|(_, a), (_, b)| {
+            let (a, b) = (a.unwrap_or_default(), b.unwrap_or_default());
+            if ascending {
+                a.0.cmp(&b.0)
+            } else {
+                b.1.cmp(&a.1)
+            }
+        });
+        ordered.append(&mut bounded);
+        ordered
+    }
+
     pub fn with_caches(mut self, caches: Option<&'a QueryCaches>) -> Self {
         self.caches = caches;
         self
@@ -103,6 +202,20 @@ impl<'a> SegmentQueryRunner<'a> {
         self
     }
 
+    /// Lets `stream_into` answer zones from zone summaries, leaving their partial
+    /// aggregate in `slot` for the aggregate operator instead of streaming rows.
+    pub fn with_zone_summary_slot(mut self, slot: Option<ZoneSummarySlot>) -> Self {
+        self.summary_slot = slot;
+        self
+    }
+
+    /// Loads the columns only matching rows need after filtering each zone,
+    /// rather than with the filter columns. Applies to `stream_into`.
+    pub fn with_late_materialization(mut self, enabled: bool) -> Self {
+        self.late_materialization = enabled;
+        self
+    }
+
     pub async fn stream_into(
         &self,
         flow_ctx: Arc<FlowContext>,
@@ -110,9 +223,59 @@ impl<'a> SegmentQueryRunner<'a> {
         sender: BatchSender,
     ) -> Result<(), FlowOperatorError> {
         let query_ctx = QueryContext::from_command(&self.plan.command);
-        let candidate_zones = self.hydrate_zones(&query_ctx).await;
         let eval_limit = self.determine_eval_limit(&query_ctx);
+        // A row limit cuts the scan short, which summaries cannot reproduce
+        let summary_plan = match (&self.summary_slot, eval_limit) {
+            (Some(_), None) => ZoneSummaryPlan::from_plan(self.plan),
+            _ => None,
+        };
         let evaluator = ConditionEvaluatorBuilder::build_from_plan(self.plan);
+        let late = match self.late_materialization {
+            true => LateMaterialization::for_plan(self.plan, &evaluator)
+                .await
+                .map(|late| late.with_caches(self.caches)),
+            false => None,
+        };
+        let (candidate_zones, summarized) = self
+            .hydrate_zones(
+                &query_ctx,
+                summary_plan.as_ref(),
+                late.as_ref().map(|late| late.early_columns().to_vec()),
+            )
+            .await;
+        if let (Some(slot), Some(partial)) = (&self.summary_slot, summarized) {
+            *slot.lock().unwrap_or_else(|p| p.into_inner()) = Some(partial);
+        }
+        let cancellation = flow_ctx.cancellation();
+        if cancellation.is_cancelled() {
+            return Err(FlowOperatorError::Cancelled);
+        }
+        let scan_stats = flow_ctx.scan_stats();
+        scan_stats.add_rows_scanned(candidate_zones.iter().map(|z| z.row_count() as u64).sum());
+        scan_stats.add_bytes_scanned(
+            candidate_zones
+                .iter()
+                .map(|z| z.loaded_bytes() as u64)
+                .sum(),
+        );
+        if let Some(audit) = scan_stats.projection_audit()
+            && !candidate_zones.is_empty()
+        {
+            // Late columns count as hydrated: any zone with a passing row loads them
+            let hydrated: BTreeSet<&str> = candidate_zones
+                .iter()
+                .flat_map(|zone| zone.values.keys().map(String::as_str))
+                .chain(
+                    late.iter()
+                        .flat_map(|late| late.late_columns().iter().map(String::as_str)),
+                )
+                .collect();
+            audit.record(self.plan, hydrated).await;
+        }
+        if let Some(caches) = self.caches {
+            let (hits, misses) = caches.zone_index_lookups();
+            scan_stats.add_cache_lookups(hits, misses);
+        }
 
         // For aggregate queries, ordering happens after aggregation in AggregateStreamMerger.
         // For non-aggregate queries, ordering can happen at the shard level.
@@ -123,27 +286,40 @@ impl<'a> SegmentQueryRunner<'a> {
                 .position(|col| col.name == order_spec.field)
                 .ok_or_else(|| FlowOperatorError::Operator("order column missing".to_string()))?;
             let ascending = !order_spec.desc;
+            let tiebreak = order_tiebreak::tiebreak_index(&schema, order_index);
 
-            let mut rows: Vec<Vec<ScalarValue>> = Vec::new();
-            let mut emitted = 0usize;
+            let mut top = TopRows::new(order_index, tiebreak, ascending, self.limit);
+            let zones = self
+                .order_zones_by_time(candidate_zones, &order_spec.field, ascending)
+                .await;
 
             // OPTIMIZATION: Pre-allocate row buffer to avoid per-event allocation
             let mut row_buffer = Vec::with_capacity(schema.column_count());
 
-            for zone in candidate_zones {
-                let remaining_limit = eval_limit.map(|lim| lim.saturating_sub(emitted));
-                if matches!(remaining_limit, Some(0)) {
-                    break;
+            for (zone, bounds) in zones {
+                // Rows are only collected here, so a timeout would otherwise
+                // wait for every zone to be read
+                if cancellation.is_cancelled() {
+                    return Err(FlowOperatorError::Cancelled);
                 }
 
-                let events = evaluator.evaluate_zones_with_limit(vec![zone], remaining_limit);
-                for event in events {
-                    if let Some(limit) = eval_limit {
-                        if emitted >= limit {
-                            break;
-                        }
+                // Zones come nearest-first, so once the limit is filled by rows
+                // that all sort before this zone, no later zone can contribute
+                if let (Some((min_ts, max_ts)), Some(cutoff)) =
+                    (bounds, top.cutoff().and_then(ScalarValue::as_i64))
+                {
+                    let beyond = if ascending {
+                        min_ts > cutoff
+                    } else {
+                        max_ts < cutoff
+                    };
+                    if beyond {
+                        break;
                     }
+                }
 
+                let events = self.zone_events(&evaluator, late.as_ref(), zone, None, scan_stats);
+                for event in events {
                     // OPTIMIZATION: Reuse row_buffer instead of allocating new Vec each time
                     row_buffer.clear();
                     for column in schema.columns() {
@@ -153,38 +329,21 @@ impl<'a> SegmentQueryRunner<'a> {
                                 .unwrap_or(ScalarValue::Null),
                         );
                     }
-                    rows.push(row_buffer.clone());
-                    emitted += 1;
-                }
-
-                if let Some(limit) = eval_limit {
-                    if emitted >= limit {
-                        break;
-                    }
+                    top.push(row_buffer.clone());
                 }
             }
 
-            // Use sort_unstable_by for better performance - maintains relative order of equal elements
-            rows.sort_unstable_by(|a, b| {
-                let ord = compare_scalar_values(&a[order_index], &b[order_index]);
-                if ascending { ord } else { ord.reverse() }
-            });
+            let rows = top.into_sorted();
 
             info!(
                 target: "sneldb::segment_source",
                 rows = rows.len(),
-                limit = eval_limit,
+                limit = self.limit,
                 order_index = order_index,
                 ascending = ascending,
                 "Segment source collected ordered rows"
             );
 
-            if let Some(limit) = eval_limit {
-                if rows.len() > limit {
-                    rows.truncate(limit);
-                }
-            }
-
             let mut builder = flow_ctx.pool().acquire(Arc::clone(&schema));
             for row in rows.iter() {
                 builder
@@ -220,12 +379,16 @@ impl<'a> SegmentQueryRunner<'a> {
             let mut emitted = 0usize;
 
             for zone in candidate_zones {
+                if cancellation.is_cancelled() {
+                    return Err(FlowOperatorError::Cancelled);
+                }
                 let remaining_limit = eval_limit.map(|lim| lim.saturating_sub(emitted));
                 if matches!(remaining_limit, Some(0)) {
                     break;
                 }
 
-                let events = evaluator.evaluate_zones_with_limit(vec![zone], remaining_limit);
+                let events =
+                    self.zone_events(&evaluator, late.as_ref(), zone, remaining_limit, scan_stats);
                 for event in events {
                     row.clear();
                     // OPTIMIZATION: Batch field lookups to reduce overhead
@@ -281,12 +444,3 @@ impl<'a> SegmentQueryRunner<'a> {
         }
     }
 }
-
-fn compare_scalar_values(a: &ScalarValue, b: &ScalarValue) -> Ordering {
-    // Try u64 first (existing behavior)
-    if let (Some(va), Some(vb)) = (a.as_u64(), b.as_u64()) {
-        return va.cmp(&vb);
-    }
-    // Use the efficient direct comparison method
-    a.compare(b)
-}
@@ -1,5 +1,10 @@
 use crate::command::types::{CompareOp, Expr};
-use crate::engine::core::{ExecutionStep, Flusher, SegmentQueryRunner};
+use crate::engine::core::read::flow::operators::MemTableSource;
+use crate::engine::core::read::flow::{
+    BatchPool, BatchSchema, FlowChannel, FlowContext, FlowMetrics, FlowTelemetry,
+};
+use crate::engine::core::{ExecutionStep, Flusher, QueryPlan, SegmentQueryRunner};
+use crate::engine::types::ScalarValue;
 use crate::test_helpers::factories::{
     CommandFactory, EventFactory, MemTableFactory, QueryPlanFactory, SchemaRegistryFactory,
 };
@@ -230,3 +235,128 @@ async fn segment_query_runner_limit_zero_returns_empty() {
     let results = runner.run().await;
     assert_eq!(results.len(), 0);
 }
+
+/// Streams the rows of a plan over its segments, with the bytes scanned.
+async fn stream_rows(plan: &QueryPlan, late_materialization: bool) -> (Vec<Vec<ScalarValue>>, u64) {
+    let steps: Vec<ExecutionStep> = plan
+        .filter_groups
+        .iter()
+        .map(|f| ExecutionStep::new(f.clone(), plan))
+        .collect();
+    let mandatory: Vec<String> = ["context_id", "event_type", "timestamp", "event_id"]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    let schema = Arc::new(
+        BatchSchema::new(
+            MemTableSource::compute_columns(plan, &mandatory)
+                .await
+                .unwrap(),
+        )
+        .unwrap(),
+    );
+    let ctx = Arc::new(FlowContext::new(
+        16,
+        BatchPool::new(16).unwrap(),
+        FlowMetrics::new(),
+        None::<&str>,
+        FlowTelemetry::default(),
+    ));
+    let (tx, mut rx) = FlowChannel::bounded(16, FlowMetrics::new());
+
+    SegmentQueryRunner::new(plan, steps)
+        .with_late_materialization(late_materialization)
+        .stream_into(Arc::clone(&ctx), schema, tx)
+        .await
+        .unwrap();
+
+    let mut rows = Vec::new();
+    while let Some(batch) = rx.recv().await {
+        for i in 0..batch.len() {
+            rows.push(batch.row(i).unwrap());
+        }
+    }
+    (rows, ctx.scan_stats().bytes_scanned())
+}
+
+#[tokio::test]
+async fn late_materialization_reads_fewer_bytes_for_the_same_rows() {
+    crate::logging::init_for_tests();
+
+    let tmp_dir = tempdir().unwrap();
+    let segment_dir = tmp_dir.path().join("shard-0").join("00201");
+    std::fs::create_dir_all(&segment_dir).unwrap();
+
+    let schema_factory = SchemaRegistryFactory::new();
+    let registry = schema_factory.registry();
+    schema_factory
+        .define_with_fields(
+            "wide",
+            &[("amount", "int"), ("note", "string"), ("detail", "string")],
+        )
+        .await
+        .unwrap();
+
+    // Wide rows: two long strings per event
+    let events = (0..6)
+        .map(|i| {
+            EventFactory::new()
+                .with("event_type", "wide")
+                .with("context_id", format!("ctx-{}", i))
+                .with(
+                    "payload",
+                    json!({
+                        "amount": i * 10,
+                        "note": format!("note-{}-{}", i, "n".repeat(256)),
+                        "detail": format!("detail-{}-{}", i, "d".repeat(256)),
+                    }),
+                )
+                .create()
+        })
+        .collect();
+    let memtable = MemTableFactory::new()
+        .with_capacity(10)
+        .with_events(events)
+        .create()
+        .unwrap();
+    Flusher::new(
+        memtable,
+        201,
+        &segment_dir,
+        Arc::clone(&registry),
+        Arc::new(tokio::sync::Mutex::new(())),
+    )
+    .flush()
+    .await
+    .expect("Flush failed");
+
+    let plan = QueryPlanFactory::new()
+        .with_command(
+            CommandFactory::query()
+                .with_event_type("wide")
+                .with_where_clause(Expr::Compare {
+                    field: "context_id".into(),
+                    op: CompareOp::Eq,
+                    value: json!("ctx-5"),
+                })
+                .create(),
+        )
+        .with_registry(Arc::clone(&registry))
+        .with_segment_base_dir(tmp_dir.path())
+        .with_segment_ids(vec!["shard-0/00201".into()])
+        .create()
+        .await;
+
+    // No zone index covers context_id, so every zone is a candidate and only
+    // the filter tells them apart
+    let (eager_rows, eager_bytes) = stream_rows(&plan, false).await;
+    let (late_rows, late_bytes) = stream_rows(&plan, true).await;
+
+    assert_eq!(late_rows.len(), 1);
+    assert_eq!(late_rows, eager_rows);
+    // Five of the six zones only decompress their context_id column
+    assert!(
+        late_bytes * 2 < eager_bytes,
+        "late materialization read {late_bytes} bytes, eager {eager_bytes}"
+    );
+}
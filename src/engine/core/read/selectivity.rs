@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::command::types::CompareOp;
+use crate::engine::core::filter::filter_group::FilterGroup;
+use crate::engine::core::zone::field_histogram::FieldHistogramIndex;
+use crate::engine::types::ScalarValue;
+
+/// Estimates the fraction of rows a predicate keeps, using per-segment field
+/// histograms written at flush. Segments or fields without histograms fall back
+/// to fixed uniform-distribution defaults.
+#[derive(Debug, Default, Clone)]
+pub struct SelectivityEstimator {
+    // segment_id -> histograms (for a given uid)
+    histograms_by_segment: HashMap<String, FieldHistogramIndex>,
+}
+
+impl SelectivityEstimator {
+    pub const DEFAULT_EQ: f64 = 0.1;
+    pub const DEFAULT_RANGE: f64 = 1.0 / 3.0;
+    pub const DEFAULT_UNKNOWN: f64 = 0.5;
+
+    pub fn new() -> Self {
+        Self {
+            histograms_by_segment: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, segment_id: &str, histograms: FieldHistogramIndex) {
+        self.histograms_by_segment
+            .insert(segment_id.to_string(), histograms);
+    }
+
+    /// Loads `{uid}.hist` for each segment; missing or unreadable files are skipped.
+    pub fn load_for_segments(&mut self, base_dir: &Path, segment_ids: &[String], uid: &str) {
+        for seg in segment_ids {
+            if self.histograms_by_segment.contains_key(seg) {
+                continue;
+            }
+            if let Ok(histograms) = FieldHistogramIndex::load(uid, &base_dir.join(seg)) {
+                self.histograms_by_segment.insert(seg.clone(), histograms);
+            }
+        }
+    }
+
+    pub fn has_histograms(&self) -> bool {
+        !self.histograms_by_segment.is_empty()
+    }
+
+    /// Estimated selectivity of `column <op> value`, weighted by segment row counts.
+    pub fn estimate(
+        &self,
+        column: &str,
+        op: Option<&CompareOp>,
+        value: Option<&ScalarValue>,
+    ) -> f64 {
+        let (Some(op), Some(value)) = (op, value) else {
+            return Self::DEFAULT_UNKNOWN;
+        };
+
+        let mut matched = 0.0;
+        let mut rows = 0u64;
+        for histograms in self.histograms_by_segment.values() {
+            let Some(histogram) = histograms.get(column) else {
+                continue;
+            };
+            if let Some(estimate) = histogram.selectivity(op, value) {
+                matched += estimate * histogram.row_count as f64;
+                rows += histogram.row_count;
+            }
+        }
+
+        if rows == 0 {
+            return Self::default_for(op);
+        }
+        (matched / rows as f64).clamp(0.0, 1.0)
+    }
+
+    /// Estimated selectivity of a filter tree, assuming independent predicates.
+    pub fn estimate_group(&self, group: &FilterGroup) -> f64 {
+        match group {
+            FilterGroup::Filter {
+                column,
+                operation,
+                value,
+                ..
+            } => self.estimate(column, operation.as_ref(), value.as_ref()),
+            FilterGroup::And(children) => children.iter().map(|c| self.estimate_group(c)).product(),
+            FilterGroup::Or(children) => {
+                1.0 - children
+                    .iter()
+                    .map(|c| 1.0 - self.estimate_group(c))
+                    .product::<f64>()
+            }
+            FilterGroup::Not(child) => 1.0 - self.estimate_group(child),
+        }
+    }
+
+    fn default_for(op: &CompareOp) -> f64 {
+        match op {
+            CompareOp::Eq | CompareOp::In => Self::DEFAULT_EQ,
+            CompareOp::Neq => 1.0 - Self::DEFAULT_EQ,
+            CompareOp::Gt | CompareOp::Gte | CompareOp::Lt | CompareOp::Lte => Self::DEFAULT_RANGE,
+        }
+    }
+}
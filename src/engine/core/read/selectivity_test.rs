@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crate::command::types::CompareOp;
+use crate::engine::core::filter::filter_group::FilterGroup;
+use crate::engine::core::read::selectivity::SelectivityEstimator;
+use crate::engine::core::zone::field_histogram::{FieldHistogram, FieldHistogramIndex};
+use crate::engine::types::ScalarValue;
+use tempfile::tempdir;
+
+fn index_with(field: &str, values: std::ops::Range<i64>) -> FieldHistogramIndex {
+    let rows = (values.end - values.start) as u64;
+    let hist = FieldHistogram::build(
+        values.map(ScalarValue::Int64).collect(),
+        rows,
+        FieldHistogram::DEFAULT_BUCKETS,
+    )
+    .unwrap();
+    FieldHistogramIndex {
+        row_count: rows,
+        fields: HashMap::from([(field.to_string(), hist)]),
+    }
+}
+
+fn filter(column: &str, op: CompareOp, value: i64) -> FilterGroup {
+    FilterGroup::new_filter(
+        column.to_string(),
+        Some(op),
+        Some(ScalarValue::Int64(value)),
+        None,
+    )
+}
+
+#[test]
+fn falls_back_to_defaults_without_histograms() {
+    let estimator = SelectivityEstimator::new();
+    assert!(!estimator.has_histograms());
+    let v = ScalarValue::Int64(1);
+    assert_eq!(
+        estimator.estimate("amount", Some(&CompareOp::Eq), Some(&v)),
+        SelectivityEstimator::DEFAULT_EQ
+    );
+    assert_eq!(
+        estimator.estimate("amount", Some(&CompareOp::Gt), Some(&v)),
+        SelectivityEstimator::DEFAULT_RANGE
+    );
+    assert_eq!(
+        estimator.estimate("amount", None, None),
+        SelectivityEstimator::DEFAULT_UNKNOWN
+    );
+}
+
+#[test]
+fn weights_segments_by_row_count() {
+    let mut estimator = SelectivityEstimator::new();
+    // Segment A: 0..100, all below 100; segment B: 100..400, all at or above 100
+    estimator.insert("00001", index_with("amount", 0..100));
+    estimator.insert("00002", index_with("amount", 100..400));
+
+    let v = ScalarValue::Int64(100);
+    let lt = estimator.estimate("amount", Some(&CompareOp::Lt), Some(&v));
+    assert!((lt - 0.25).abs() < 0.02, "got {lt}");
+
+    // Unknown field degrades to defaults even when histograms exist
+    assert_eq!(
+        estimator.estimate("other", Some(&CompareOp::Eq), Some(&v)),
+        SelectivityEstimator::DEFAULT_EQ
+    );
+}
+
+#[test]
+fn combines_filter_groups() {
+    let mut estimator = SelectivityEstimator::new();
+    estimator.insert("00001", index_with("amount", 0..1000));
+
+    let half = filter("amount", CompareOp::Lt, 500);
+    let tenth = filter("amount", CompareOp::Gte, 900);
+    let and = FilterGroup::And(vec![half.clone(), tenth.clone()]);
+    let or = FilterGroup::Or(vec![half.clone(), tenth.clone()]);
+    let not = FilterGroup::Not(Box::new(half.clone()));
+
+    let and_est = estimator.estimate_group(&and);
+    let or_est = estimator.estimate_group(&or);
+    assert!((and_est - 0.05).abs() < 0.02, "got {and_est}");
+    assert!((or_est - 0.55).abs() < 0.02, "got {or_est}");
+    assert!((estimator.estimate_group(&not) - 0.5).abs() < 0.02);
+}
+
+#[test]
+fn load_skips_missing_segments() {
+    let dir = tempdir().unwrap();
+    let seg_dir = dir.path().join("00001");
+    std::fs::create_dir_all(&seg_dir).unwrap();
+    index_with("amount", 0..10).save("uid1", &seg_dir).unwrap();
+
+    let mut estimator = SelectivityEstimator::new();
+    estimator.load_for_segments(
+        dir.path(),
+        &["00001".to_string(), "00002".to_string()],
+        "uid1",
+    );
+    assert!(estimator.has_histograms());
+    let v = ScalarValue::Int64(100);
+    assert_eq!(
+        estimator.estimate("amount", Some(&CompareOp::Gt), Some(&v)),
+        0.0
+    );
+}
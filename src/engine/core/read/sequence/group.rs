@@ -1,6 +1,7 @@
 use crate::engine::core::CandidateZone;
 use crate::engine::core::filter::condition::{FieldAccessor, PreparedAccessor};
-use crate::engine::types::ScalarValue;
+use crate::engine::types::{ScalarValue, decimal, uuid};
+use crate::shared::hash::{QueryHashMap, QueryHashState};
 use std::collections::HashMap;
 use tracing::{debug, info, trace, warn};
 
@@ -43,6 +44,8 @@ pub struct ColumnarGrouper {
     link_field: String,
     /// The field name to use for time-based sorting (default: "timestamp")
     time_field: String,
+    /// Optional field used to order rows that share the same time value
+    tiebreak_field: Option<String>,
 }
 
 impl ColumnarGrouper {
@@ -56,9 +59,17 @@ impl ColumnarGrouper {
         Self {
             link_field,
             time_field,
+            tiebreak_field: None,
         }
     }
 
+    /// Orders rows with equal time values by `field` (e.g., "event_id") instead of
+    /// leaving them in arrival order.
+    pub fn with_tiebreak_field(mut self, field: impl Into<String>) -> Self {
+        self.tiebreak_field = Some(field.into());
+        self
+    }
+
     /// Groups row indices by link field value across all zones.
     ///
     /// This method extracts the link field value from each row in the columnar data
@@ -75,7 +86,7 @@ impl ColumnarGrouper {
     pub fn group_zones_by_link_field(
         &self,
         zones_by_event_type: &HashMap<String, Vec<CandidateZone>>,
-    ) -> HashMap<String, GroupedRowIndices> {
+    ) -> QueryHashMap<String, GroupedRowIndices> {
         if tracing::enabled!(tracing::Level::INFO) {
             info!(
                 target: "sneldb::sequence::group",
@@ -97,8 +108,8 @@ impl ColumnarGrouper {
             .sum();
 
         let estimated_groups = (total_events / 10).max(1);
-        let mut groups: HashMap<String, GroupedRowIndices> =
-            HashMap::with_capacity(estimated_groups);
+        let mut groups: QueryHashMap<String, GroupedRowIndices> =
+            QueryHashMap::with_capacity_and_hasher(estimated_groups, QueryHashState::default());
 
         if tracing::enabled!(tracing::Level::DEBUG) {
             debug!(
@@ -145,7 +156,7 @@ impl ColumnarGrouper {
         &self,
         event_type: &str,
         zones: &[CandidateZone],
-        groups: &mut HashMap<String, GroupedRowIndices>,
+        groups: &mut QueryHashMap<String, GroupedRowIndices>,
     ) {
         for (zone_idx, zone) in zones.iter().enumerate() {
             let accessor = PreparedAccessor::new(&zone.values);
@@ -227,7 +238,7 @@ impl ColumnarGrouper {
     /// OPTIMIZATION: Pre-extract timestamps to avoid repeated PreparedAccessor creation during sorting.
     fn sort_groups_by_timestamp(
         &self,
-        groups: &mut HashMap<String, GroupedRowIndices>,
+        groups: &mut QueryHashMap<String, GroupedRowIndices>,
         zones_by_event_type: &HashMap<String, Vec<CandidateZone>>,
     ) {
         if tracing::enabled!(tracing::Level::TRACE) {
@@ -247,21 +258,22 @@ impl ColumnarGrouper {
 
                 if let Some(zones) = zones_by_event_type.get(event_type) {
                     // OPTIMIZATION: Pre-extract all timestamps to avoid repeated accessor creation
-                    // Create a vector of (timestamp, original_index) pairs
-                    let mut timestamped_indices: Vec<(u64, usize)> =
+                    // Create a vector of (timestamp, tiebreak, original_index) tuples
+                    let mut timestamped_indices: Vec<(u64, u64, usize)> =
                         Vec::with_capacity(row_indices.len());
                     for (idx, row_index) in row_indices.iter().enumerate() {
                         let ts = self.get_timestamp(zones, row_index);
-                        timestamped_indices.push((ts, idx));
+                        let tiebreak = self.get_tiebreak(zones, row_index);
+                        timestamped_indices.push((ts, tiebreak, idx));
                     }
 
-                    // Sort by timestamp
-                    timestamped_indices.sort_by_key(|(ts, _)| *ts);
+                    // Sort by timestamp, then by tiebreak (stable for equal keys)
+                    timestamped_indices.sort_by_key(|(ts, tiebreak, _)| (*ts, *tiebreak));
 
                     // Rebuild row_indices in sorted order
                     let sorted_indices: Vec<RowIndex> = timestamped_indices
                         .into_iter()
-                        .map(|(_, idx)| row_indices[idx].clone())
+                        .map(|(_, _, idx)| row_indices[idx].clone())
                         .collect();
                     *row_indices = sorted_indices;
 
@@ -377,6 +389,23 @@ impl ColumnarGrouper {
         }
     }
 
+    /// Gets the tiebreak value for a specific row index.
+    ///
+    /// Returns 0 when no tiebreak field is configured, so sorting falls back to
+    /// arrival order for equal timestamps.
+    fn get_tiebreak(&self, zones: &[CandidateZone], row_index: &RowIndex) -> u64 {
+        let Some(field) = self.tiebreak_field.as_deref() else {
+            return 0;
+        };
+        zones
+            .get(row_index.zone_idx)
+            .and_then(|zone| {
+                PreparedAccessor::new(&zone.values).get_i64_at(field, row_index.row_idx)
+            })
+            .map(|value| value as u64)
+            .unwrap_or(0)
+    }
+
     /// Converts a ScalarValue to a string key for HashMap usage.
     ///
     /// This allows us to use ScalarValue as HashMap keys without implementing Hash.
@@ -387,6 +416,14 @@ impl ColumnarGrouper {
             ScalarValue::Int64(i) => format!("i64:{}", i),
             ScalarValue::Float64(f) => format!("f64:{}", f),
             ScalarValue::Timestamp(ts) => format!("ts:{}", ts),
+            ScalarValue::Decimal(m, s) => {
+                let (m, s) = decimal::normalize(*m, *s);
+                format!("dec:{}", decimal::format(m, s))
+            }
+            ScalarValue::Date(d) => format!("date:{}", d),
+            ScalarValue::Time(t) => format!("time:{}", t),
+            // Keyed like the canonical string, which is how UUIDs arrive from memtables
+            ScalarValue::Uuid(u) => format!("str:{}", uuid::format(u)),
             ScalarValue::Utf8(s) => format!("str:{}", s),
             ScalarValue::Binary(bytes) => {
                 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
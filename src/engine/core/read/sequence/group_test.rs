@@ -132,6 +132,37 @@ mod tests {
         assert!(ts2 <= ts3);
     }
 
+    #[test]
+    fn test_group_breaks_timestamp_ties_with_tiebreak_field() {
+        use std::sync::Arc;
+
+        let mut zone = create_test_zone(
+            0,
+            "seg1",
+            &["ctx1", "ctx2", "ctx3"],
+            &["user1", "user1", "user1"],
+            &[1000, 1000, 500],
+        );
+        let mut values = zone.values.clone();
+        let bytes: Vec<u8> = b"9070".to_vec();
+        let ranges = vec![(0, 2), (2, 1), (3, 1)];
+        let block = Arc::new(DecompressedBlock::from_bytes(bytes));
+        values.insert("event_id".to_string(), ColumnValues::new(block, ranges));
+        zone.set_values(values);
+
+        let mut zones_by_type = HashMap::new();
+        zones_by_type.insert("page_view".to_string(), vec![zone]);
+
+        let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string())
+            .with_tiebreak_field("event_id");
+        let groups = grouper.group_zones_by_link_field(&zones_by_type);
+
+        let row_indices = &groups["str:user1"].rows_by_type["page_view"];
+        let order: Vec<usize> = row_indices.iter().map(|r| r.row_idx).collect();
+        // ts 500 first, then the tie at ts 1000 ordered by event_id (7 before 90)
+        assert_eq!(order, vec![2, 1, 0]);
+    }
+
     #[test]
     fn test_group_handles_missing_link_field() {
         let mut zones_by_type = HashMap::new();
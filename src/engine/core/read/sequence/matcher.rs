@@ -4,6 +4,7 @@ use crate::engine::core::filter::condition::{FieldAccessor, PreparedAccessor};
 use crate::engine::core::read::sequence::group::{GroupedRowIndices, RowIndex};
 use crate::engine::core::read::sequence::where_evaluator::SequenceWhereEvaluator;
 use crate::engine::types::ScalarValue;
+use crate::shared::hash::QueryHashMap;
 use std::collections::HashMap;
 use tracing::{debug, info, trace, warn};
 
@@ -19,15 +20,15 @@ pub struct MatchedSequenceIndices {
     pub matched_rows: Vec<(String, RowIndex)>,
 }
 
-/// Matches sequences using two-pointer technique on columnar data.
+/// Matches sequences by sweeping sorted row indices on columnar data.
 ///
-/// This class implements efficient sequence matching using a two-pointer algorithm
-/// that operates on row indices rather than materialized events. It supports both
-/// FOLLOWED BY and PRECEDED BY sequence links.
+/// This class implements efficient sequence matching over chains of any length
+/// (`A FOLLOWED BY B PRECEDED BY C ...`), keeping one forward-only cursor per
+/// link and operating on row indices rather than materialized events.
 ///
 /// # Performance
 ///
-/// - O(n+m) complexity using two-pointer technique (instead of O(n*m))
+/// - Linear in the rows of each group (one cursor per link instead of nested loops)
 /// - Works with columnar data (no premature materialization)
 /// - Supports limit short-circuiting
 /// - Applies WHERE clause filtering during matching (before materialization)
@@ -40,6 +41,24 @@ pub struct SequenceMatcher {
     time_field: String,
 }
 
+/// One link of the chain being swept: the rows of its event type in the group
+/// and a cursor into them.
+struct ChainStep<'a> {
+    link: SequenceLink,
+    event_type: &'a str,
+    rows: &'a [RowIndex],
+    zones: &'a [CandidateZone],
+    cursor: usize,
+}
+
+/// An event picked for the match being built; `pos` indexes the rows of its type.
+struct ChainEvent<'a> {
+    ts: u64,
+    event_type: &'a str,
+    pos: usize,
+    row: &'a RowIndex,
+}
+
 impl SequenceMatcher {
     /// Creates a new `SequenceMatcher` for the specified sequence.
     ///
@@ -68,7 +87,7 @@ impl SequenceMatcher {
     /// Matches sequences in grouped row indices.
     ///
     /// This is the main entry point for sequence matching. It processes each group
-    /// and finds matching sequences using the two-pointer technique.
+    /// and finds matching sequences by sweeping its sorted row indices.
     ///
     /// # Arguments
     ///
@@ -81,7 +100,7 @@ impl SequenceMatcher {
     /// Vector of matched sequence indices
     pub fn match_sequences(
         &self,
-        groups: HashMap<String, GroupedRowIndices>,
+        groups: QueryHashMap<String, GroupedRowIndices>,
         zones_by_event_type: &HashMap<String, Vec<CandidateZone>>,
         limit: Option<usize>,
     ) -> Vec<MatchedSequenceIndices> {
@@ -124,8 +143,10 @@ impl SequenceMatcher {
             groups_with_timestamps.push((earliest_ts, link_key, group));
         }
 
-        // Sort by earliest timestamp
-        groups_with_timestamps.sort_by_key(|(ts, _, _)| *ts);
+        // Sort by earliest timestamp, then link value so ties don't follow hash order
+        groups_with_timestamps.sort_by(|(ts_a, key_a, _), (ts_b, key_b, _)| {
+            ts_a.cmp(ts_b).then_with(|| key_a.cmp(key_b))
+        });
         let sorted_groups: Vec<(String, GroupedRowIndices)> = groups_with_timestamps
             .into_iter()
             .map(|(_, key, group)| (key, group))
@@ -141,16 +162,15 @@ impl SequenceMatcher {
 
         let mut groups_processed = 0usize;
         let mut groups_with_matches = 0usize;
-        let mut groups_empty_a = 0usize;
-        let mut groups_empty_b = 0usize;
+        let mut groups_incomplete = 0usize;
 
-        // Get event types from sequence
-        let event_type_a = &self.sequence.head.event;
-        let event_type_b = if let Some((_, target)) = self.sequence.links.first() {
-            &target.event
-        } else {
+        // Every event type of the chain must appear in a group for it to match
+        if self.sequence.links.is_empty() {
             return all_matches;
-        };
+        }
+        let chain_types: Vec<&String> = std::iter::once(&self.sequence.head.event)
+            .chain(self.sequence.links.iter().map(|(_, target)| &target.event))
+            .collect();
 
         for (_link_key, group) in sorted_groups {
             groups_processed += 1;
@@ -171,33 +191,21 @@ impl SequenceMatcher {
                 }
             }
 
-            // Check if group has events of both types
-            let a_indices = group.rows_by_type.get(event_type_a);
-            let b_indices = group.rows_by_type.get(event_type_b);
-
-            if a_indices.is_none() || a_indices.unwrap().is_empty() {
-                groups_empty_a += 1;
-                if tracing::enabled!(tracing::Level::DEBUG) && groups_empty_a <= 10 {
-                    debug!(
-                        target: "sneldb::sequence::matcher",
-                        group_index = groups_processed,
-                        link_value = ?group.link_value,
-                        event_type_a = %event_type_a,
-                        "Group has no events of type A, skipping"
-                    );
-                }
-                continue;
-            }
-
-            if b_indices.is_none() || b_indices.unwrap().is_empty() {
-                groups_empty_b += 1;
-                if tracing::enabled!(tracing::Level::DEBUG) && groups_empty_b <= 10 {
+            let missing_type = chain_types.iter().find(|event_type| {
+                group
+                    .rows_by_type
+                    .get(event_type.as_str())
+                    .is_none_or(|rows| rows.is_empty())
+            });
+            if let Some(missing_type) = missing_type {
+                groups_incomplete += 1;
+                if tracing::enabled!(tracing::Level::DEBUG) && groups_incomplete <= 10 {
                     debug!(
                         target: "sneldb::sequence::matcher",
                         group_index = groups_processed,
                         link_value = ?group.link_value,
-                        event_type_b = %event_type_b,
-                        "Group has no events of type B, skipping"
+                        missing_type = %missing_type,
+                        "Group has no events of a sequence type, skipping"
                     );
                 }
                 continue;
@@ -252,8 +260,7 @@ impl SequenceMatcher {
                 matches_found = all_matches.len(),
                 groups_processed = groups_processed,
                 groups_with_matches = groups_with_matches,
-                groups_empty_a = groups_empty_a,
-                groups_empty_b = groups_empty_b,
+                groups_incomplete = groups_incomplete,
                 "Sequence matching completed"
             );
         }
@@ -261,448 +268,263 @@ impl SequenceMatcher {
         all_matches
     }
 
-    /// Matches sequences within a single group.
+    /// Time range the events of each type must fall in to take part in a
+    /// match under the `WITHIN` window, given the earliest and latest time of
+    /// each type. Types left out cannot take part in any match, so their
+    /// events need not be read at all. `None` without a window.
     ///
-    /// This method handles the sequence matching logic for a single link field value.
-    /// It delegates to specific matchers based on the sequence link type.
-    fn match_in_group(
+    /// Events outside these ranges are never the nearest one a link picks
+    /// for a match that holds, so leaving them out keeps every match.
+    pub fn window_bounds(
         &self,
-        group: &GroupedRowIndices,
-        zones_by_event_type: &HashMap<String, Vec<CandidateZone>>,
-    ) -> Vec<MatchedSequenceIndices> {
-        // Handle single link (A FOLLOWED BY B or A PRECEDED BY B)
-        if self.sequence.links.len() == 1 {
-            let (link_type, target) = &self.sequence.links[0];
-            let event_type_a = &self.sequence.head.event;
-            let event_type_b = &target.event;
+        spans: &HashMap<String, (u64, u64)>,
+    ) -> Option<HashMap<String, (u64, u64)>> {
+        let window = self.sequence.within?;
+        let mut bounds: HashMap<String, (u64, u64)> = HashMap::new();
+        let Some(ranges) = self.feasible_ranges(window, spans) else {
+            return Some(bounds);
+        };
+        let chain_types = std::iter::once(&self.sequence.head.event)
+            .chain(self.sequence.links.iter().map(|(_, target)| &target.event));
+        for (event_type, (lo, hi)) in chain_types.zip(ranges) {
+            bounds
+                .entry(event_type.clone())
+                .and_modify(|range| *range = (range.0.min(lo), range.1.max(hi)))
+                .or_insert((lo, hi));
+        }
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            debug!(
+                target: "sneldb::sequence::matcher",
+                window = window,
+                bounds = ?bounds,
+                "Narrowed event time ranges to the WITHIN window"
+            );
+        }
+        Some(bounds)
+    }
 
-            if tracing::enabled!(tracing::Level::TRACE) {
-                trace!(
-                    target: "sneldb::sequence::matcher",
-                    link_type = ?link_type,
-                    event_type_a = %event_type_a,
-                    event_type_b = %event_type_b,
-                    "Matching sequence in group"
-                );
-            }
+    /// Time range each step of the chain can take given `window` and the time
+    /// span of each event type; `None` when no match fits.
+    ///
+    /// Only the head range is narrowed back from the last step: a head event
+    /// outside it has no chain of events fitting every window. The other steps
+    /// keep the range reachable from the head, since narrowing them further
+    /// could drop the nearest event a link picks and let a farther one match.
+    fn feasible_ranges(
+        &self,
+        window: u64,
+        spans: &HashMap<String, (u64, u64)>,
+    ) -> Option<Vec<(u64, u64)>> {
+        let head = *spans.get(&self.sequence.head.event)?;
+        let ranges = self.forward_ranges(window, head, spans)?;
+        let mut reach = ranges[ranges.len() - 1];
+        for (i, (link, _)) in self.sequence.links.iter().enumerate().rev() {
+            let (lo, hi) = reach;
+            let reaching = match link {
+                SequenceLink::FollowedBy => (lo.saturating_sub(window), hi),
+                SequenceLink::PrecededBy => (lo.saturating_add(1), hi.saturating_add(window)),
+            };
+            reach = intersect(ranges[i], reaching)?;
+        }
+        self.forward_ranges(window, reach, spans)
+    }
 
-            match link_type {
-                SequenceLink::FollowedBy => {
-                    self.match_followed_by(group, event_type_a, event_type_b, zones_by_event_type)
-                }
-                SequenceLink::PrecededBy => {
-                    self.match_preceded_by(group, event_type_a, event_type_b, zones_by_event_type)
-                }
-            }
-        } else {
-            // Multiple links - for now, return empty (Phase 4 feature)
-            if tracing::enabled!(tracing::Level::WARN) {
-                warn!(
-                    target: "sneldb::sequence::matcher",
-                    link_count = self.sequence.links.len(),
-                    "Multiple sequence links not yet supported"
-                );
-            }
-            Vec::new()
+    /// Time range each step can reach from head events in `head`, one window
+    /// at a time along the chain.
+    fn forward_ranges(
+        &self,
+        window: u64,
+        head: (u64, u64),
+        spans: &HashMap<String, (u64, u64)>,
+    ) -> Option<Vec<(u64, u64)>> {
+        let mut ranges = vec![head];
+        for (link, target) in &self.sequence.links {
+            let (lo, hi) = ranges[ranges.len() - 1];
+            let reachable = match link {
+                SequenceLink::FollowedBy => (lo, hi.saturating_add(window)),
+                SequenceLink::PrecededBy => (lo.saturating_sub(window), hi.checked_sub(1)?),
+            };
+            ranges.push(intersect(*spans.get(&target.event)?, reachable)?);
         }
+        Some(ranges)
     }
 
-    /// Matches A FOLLOWED BY B using two-pointer technique.
+    /// Matches the whole chain within a single group.
     ///
-    /// This implements O(n+m) matching by using two pointers that advance
-    /// through sorted row indices. When event B's timestamp is greater than or equal to
-    /// event A's timestamp, we have a match. Events with the same timestamp are considered matches.
-    fn match_followed_by(
+    /// Each link relates its event to the one matched for the step before it:
+    /// `FOLLOWED BY` takes the earliest event at the same timestamp or later,
+    /// `PRECEDED BY` the latest event strictly before. Head events are swept in
+    /// time order and, because each step only ever looks later as the head
+    /// moves on, every link keeps one cursor into its sorted rows that never
+    /// moves back. Head events in overlapping windows may share later events,
+    /// but an event never fills two positions of the same match.
+    fn match_in_group(
         &self,
         group: &GroupedRowIndices,
-        event_type_a: &str,
-        event_type_b: &str,
         zones_by_event_type: &HashMap<String, Vec<CandidateZone>>,
     ) -> Vec<MatchedSequenceIndices> {
-        let a_indices = group
-            .rows_by_type
-            .get(event_type_a)
-            .map(|v| v.as_slice())
-            .unwrap_or(&[]);
-        let b_indices = group
-            .rows_by_type
-            .get(event_type_b)
-            .map(|v| v.as_slice())
-            .unwrap_or(&[]);
-
-        if a_indices.is_empty() || b_indices.is_empty() {
+        let head_type = self.sequence.head.event.as_str();
+        let (Some(head_rows), Some(head_zones)) = (
+            group.rows_by_type.get(head_type),
+            zones_by_event_type.get(head_type),
+        ) else {
             return Vec::new();
-        }
-
-        let zones_a = zones_by_event_type.get(event_type_a).unwrap();
-        let zones_b = zones_by_event_type.get(event_type_b).unwrap();
+        };
 
-        let mut results = Vec::new();
-        let mut a_ptr = 0;
-        let mut b_ptr = 0;
-        let mut comparisons = 0usize;
-        let mut timestamp_passed = 0usize;
-        let mut where_failed = 0usize;
-        let mut where_passed = 0usize;
+        let mut steps = Vec::with_capacity(self.sequence.links.len());
+        for (link, target) in &self.sequence.links {
+            let (Some(rows), Some(zones)) = (
+                group.rows_by_type.get(&target.event),
+                zones_by_event_type.get(&target.event),
+            ) else {
+                return Vec::new();
+            };
+            steps.push(ChainStep {
+                link: link.clone(),
+                event_type: target.event.as_str(),
+                rows,
+                zones,
+                cursor: 0,
+            });
+        }
 
-        // Log group statistics
-        if tracing::enabled!(tracing::Level::DEBUG) {
-            debug!(
+        if tracing::enabled!(tracing::Level::TRACE) {
+            trace!(
                 target: "sneldb::sequence::matcher",
-                event_type_a = %event_type_a,
-                event_type_b = %event_type_b,
-                a_count = a_indices.len(),
-                b_count = b_indices.len(),
+                head_type = %head_type,
+                head_count = head_rows.len(),
+                link_count = steps.len(),
                 link_value = ?group.link_value,
-                "Starting two-pointer matching for FOLLOWED BY"
+                "Matching sequence chain in group"
             );
         }
 
-        // Two-pointer matching: advance both pointers through sorted indices
-        while a_ptr < a_indices.len() && b_ptr < b_indices.len() {
-            comparisons += 1;
-            let row_a = &a_indices[a_ptr];
-            let row_b = &b_indices[b_ptr];
-
-            let ts_a = self.get_timestamp(zones_a, row_a);
-            let ts_b = self.get_timestamp(zones_b, row_b);
-
-            if tracing::enabled!(tracing::Level::DEBUG) && comparisons <= 20 {
-                debug!(
-                    target: "sneldb::sequence::matcher",
-                    comparison_num = comparisons,
-                    ts_a = ts_a,
-                    ts_b = ts_b,
-                    ts_b_ge_ts_a = ts_b >= ts_a,
-                    link_value = ?group.link_value,
-                    "Timestamp comparison"
-                );
+        let mut results = Vec::new();
+        let mut where_failed = 0usize;
+        'heads: for (head_pos, head_row) in head_rows.iter().enumerate() {
+            if !self.matches_where_clause(head_type, head_zones, head_row) {
+                where_failed += 1;
+                continue;
             }
-
-            if ts_b >= ts_a {
-                timestamp_passed += 1;
-                // Match found: event B follows event A (or happens at the same time)
-                // Apply WHERE clause filtering if present
-                let passes_where = self.matches_where_clause(
-                    event_type_a,
-                    zones_a,
-                    row_a,
-                    event_type_b,
-                    zones_b,
-                    row_b,
-                );
-
-                if tracing::enabled!(tracing::Level::DEBUG) && timestamp_passed <= 20 {
-                    debug!(
-                        target: "sneldb::sequence::matcher",
-                        ts_a = ts_a,
-                        ts_b = ts_b,
-                        passes_where = passes_where,
-                        link_value = ?group.link_value,
-                        "Potential match found, WHERE clause result"
-                    );
-                }
-
-                if passes_where {
-                    where_passed += 1;
-                    if tracing::enabled!(tracing::Level::DEBUG) {
-                        debug!(
-                            target: "sneldb::sequence::matcher",
-                            event_type_a = %event_type_a,
-                            event_type_b = %event_type_b,
-                            ts_a = ts_a,
-                            ts_b = ts_b,
-                            link_value = ?group.link_value,
-                            "Match found and passed WHERE clause"
-                        );
-                    }
-                    results.push(MatchedSequenceIndices {
-                        link_value: group.link_value.clone(),
-                        matched_rows: vec![
-                            (event_type_a.to_string(), row_a.clone()),
-                            (event_type_b.to_string(), row_b.clone()),
-                        ],
-                    });
-                } else {
+            let head_ts = self.get_timestamp(head_zones, head_row);
+
+            let mut chain: Vec<ChainEvent> = Vec::with_capacity(steps.len() + 1);
+            chain.push(ChainEvent {
+                ts: head_ts,
+                event_type: head_type,
+                pos: head_pos,
+                row: head_row,
+            });
+
+            for step in steps.iter_mut() {
+                let prev_ts = chain[chain.len() - 1].ts;
+                let Some((pos, ts)) = self.next_in_chain(step, prev_ts, &chain) else {
+                    continue 'heads;
+                };
+                let row = &step.rows[pos];
+                if !self.matches_where_clause(step.event_type, step.zones, row) {
                     where_failed += 1;
-                    if tracing::enabled!(tracing::Level::DEBUG) && where_failed <= 20 {
-                        debug!(
-                            target: "sneldb::sequence::matcher",
-                            event_type_a = %event_type_a,
-                            event_type_b = %event_type_b,
-                            ts_a = ts_a,
-                            ts_b = ts_b,
-                            link_value = ?group.link_value,
-                            "Match filtered out by WHERE clause"
-                        );
-                    }
+                    continue 'heads;
                 }
-                a_ptr += 1;
-            } else {
-                // Event B is not after event A, advance b_ptr
-                b_ptr += 1;
+                chain.push(ChainEvent {
+                    ts,
+                    event_type: step.event_type,
+                    pos,
+                    row,
+                });
             }
+
+            // Report events in time order; the sort is stable so ties keep chain order
+            chain.sort_by_key(|event| event.ts);
+            results.push(MatchedSequenceIndices {
+                link_value: group.link_value.clone(),
+                matched_rows: chain
+                    .into_iter()
+                    .map(|event| (event.event_type.to_string(), event.row.clone()))
+                    .collect(),
+            });
         }
 
         if tracing::enabled!(tracing::Level::DEBUG) {
             debug!(
                 target: "sneldb::sequence::matcher",
-                event_type_a = %event_type_a,
-                event_type_b = %event_type_b,
+                head_type = %head_type,
                 matches = results.len(),
-                comparisons = comparisons,
-                timestamp_passed = timestamp_passed,
-                where_passed = where_passed,
                 where_failed = where_failed,
                 link_value = ?group.link_value,
-                "Completed FOLLOWED BY matching"
+                "Completed sequence chain matching"
             );
         }
 
         results
     }
 
-    /// Matches A PRECEDED BY B using reverse two-pointer technique.
+    /// Finds the event for `step` given the timestamp of the event matched just
+    /// before it, skipping rows the chain already holds.
     ///
-    /// This is similar to FOLLOWED BY but works backwards from the end of
-    /// the sorted indices.
-    fn match_preceded_by(
+    /// The cursor tracks the first row at or after `prev_ts`. As the head moves
+    /// on, `prev_ts` only grows, except when a skipped row shifts a timestamp
+    /// tie, so the cursor steps back at most over rows sharing one timestamp.
+    /// The candidate is the nearest event in time, so when it lies outside the
+    /// `WITHIN` window every other one does too.
+    fn next_in_chain(
         &self,
-        group: &GroupedRowIndices,
-        event_type_a: &str,
-        event_type_b: &str,
-        zones_by_event_type: &HashMap<String, Vec<CandidateZone>>,
-    ) -> Vec<MatchedSequenceIndices> {
-        let a_indices = group
-            .rows_by_type
-            .get(event_type_a)
-            .map(|v| v.as_slice())
-            .unwrap_or(&[]);
-        let b_indices = group
-            .rows_by_type
-            .get(event_type_b)
-            .map(|v| v.as_slice())
-            .unwrap_or(&[]);
-
-        if a_indices.is_empty() || b_indices.is_empty() {
-            return Vec::new();
+        step: &mut ChainStep<'_>,
+        prev_ts: u64,
+        chain: &[ChainEvent<'_>],
+    ) -> Option<(usize, u64)> {
+        while step.cursor > 0
+            && self.get_timestamp(step.zones, &step.rows[step.cursor - 1]) >= prev_ts
+        {
+            step.cursor -= 1;
         }
-
-        let zones_a = zones_by_event_type.get(event_type_a).unwrap();
-        let zones_b = zones_by_event_type.get(event_type_b).unwrap();
-
-        if tracing::enabled!(tracing::Level::TRACE) {
-            trace!(
-                target: "sneldb::sequence::matcher",
-                event_type_a = %event_type_a,
-                event_type_b = %event_type_b,
-                a_count = a_indices.len(),
-                b_count = b_indices.len(),
-                "Starting reverse two-pointer matching for PRECEDED BY"
-            );
+        while step.cursor < step.rows.len()
+            && self.get_timestamp(step.zones, &step.rows[step.cursor]) < prev_ts
+        {
+            step.cursor += 1;
         }
 
-        let mut results = Vec::new();
-        let mut a_ptr = 0;
-        let mut b_ptr = 0;
-        let mut comparisons = 0usize;
-
-        // Two-pointer matching: for each event A, find the latest event B that precedes it
-        // We iterate through A events and for each, find the most recent B that happened before it
-        while a_ptr < a_indices.len() && b_ptr < b_indices.len() {
-            comparisons += 1;
-            let row_a = &a_indices[a_ptr];
-            let row_b = &b_indices[b_ptr];
-
-            let ts_a = self.get_timestamp(zones_a, row_a);
-            let ts_b = self.get_timestamp(zones_b, row_b);
-
-            if tracing::enabled!(tracing::Level::TRACE) {
-                trace!(
-                    target: "sneldb::sequence::matcher",
-                    ts_a = ts_a,
-                    ts_b = ts_b,
-                    ts_b_lt_ts_a = ts_b < ts_a,
-                    "Comparing timestamps for PRECEDED BY"
-                );
-            }
-
-            if ts_b < ts_a {
-                // Found a B that precedes A - advance b_ptr to find the latest one
-                // Keep advancing b_ptr while B still precedes A (two-pointer optimization)
-                let mut latest_b_ptr = b_ptr;
-                while latest_b_ptr + 1 < b_indices.len() {
-                    let next_b = &b_indices[latest_b_ptr + 1];
-                    let ts_next_b = self.get_timestamp(zones_b, next_b);
-                    if ts_next_b < ts_a {
-                        latest_b_ptr += 1;
-                    } else {
-                        break;
-                    }
-                }
-
-                // Use the latest B that precedes A
-                let latest_row_b = &b_indices[latest_b_ptr];
-
-                // Apply WHERE clause filtering if present
-                let passes_where = self.matches_where_clause(
-                    event_type_b,
-                    zones_b,
-                    latest_row_b,
-                    event_type_a,
-                    zones_a,
-                    row_a,
-                );
-
-                if tracing::enabled!(tracing::Level::TRACE) {
-                    trace!(
-                        target: "sneldb::sequence::matcher",
-                        ts_a = ts_a,
-                        ts_b = self.get_timestamp(zones_b, latest_row_b),
-                        passes_where = passes_where,
-                        "Potential match found (PRECEDED BY)"
-                    );
-                }
-
-                if passes_where {
-                    results.push(MatchedSequenceIndices {
-                        link_value: group.link_value.clone(),
-                        matched_rows: vec![
-                            (event_type_b.to_string(), latest_row_b.clone()),
-                            (event_type_a.to_string(), row_a.clone()),
-                        ],
-                    });
-                } else if tracing::enabled!(tracing::Level::TRACE) {
-                    trace!(
-                        target: "sneldb::sequence::matcher",
-                        "Match filtered out by WHERE clause (PRECEDED BY)"
-                    );
-                }
-
-                // Move to next A event
-                a_ptr += 1;
-                // Optimize: Since indices are sorted, keep b_ptr at latest_b_ptr for next iteration
-                // This maintains the two-pointer invariant (b_ptr points to the last B that could match)
-                b_ptr = latest_b_ptr;
-            } else {
-                // B is not before A (ts_b >= ts_a), advance b_ptr to find earlier B events
-                // Since indices are sorted by timestamp, we need to advance b_ptr
-                b_ptr += 1;
-            }
-        }
-
-        if tracing::enabled!(tracing::Level::DEBUG) {
-            debug!(
-                target: "sneldb::sequence::matcher",
-                event_type_a = %event_type_a,
-                event_type_b = %event_type_b,
-                matches = results.len(),
-                comparisons = comparisons,
-                "Completed PRECEDED BY matching"
-            );
+        let taken = |pos: usize| {
+            chain
+                .iter()
+                .any(|event| event.pos == pos && event.event_type == step.event_type)
+        };
+        let pos = match step.link {
+            SequenceLink::FollowedBy => (step.cursor..step.rows.len()).find(|&pos| !taken(pos)),
+            SequenceLink::PrecededBy => (0..step.cursor).rev().find(|&pos| !taken(pos)),
+        }?;
+        let ts = self.get_timestamp(step.zones, &step.rows[pos]);
+        if let Some(window) = self.sequence.within
+            && ts.abs_diff(prev_ts) > window
+        {
+            return None;
         }
-
-        results
+        Some((pos, ts))
     }
 
-    /// Checks if a matched sequence passes WHERE clause conditions.
-    ///
-    /// Evaluates WHERE clause conditions for both events in the sequence.
-    /// Returns true if all conditions pass, false otherwise.
+    /// Checks if one event of a candidate sequence passes WHERE clause conditions.
     fn matches_where_clause(
         &self,
-        event_type_a: &str,
-        zones_a: &[CandidateZone],
-        row_a: &RowIndex,
-        event_type_b: &str,
-        zones_b: &[CandidateZone],
-        row_b: &RowIndex,
+        event_type: &str,
+        zones: &[CandidateZone],
+        row: &RowIndex,
     ) -> bool {
-        if let Some(ref evaluator) = self.where_evaluator {
-            if tracing::enabled!(tracing::Level::DEBUG) {
-                debug!(
-                    target: "sneldb::sequence::matcher",
-                    event_type_a = %event_type_a,
-                    event_type_b = %event_type_b,
-                    "Evaluating WHERE clause for sequence match"
-                );
-            }
-
-            // Check WHERE clause for event A
-            let zone_a = &zones_a[row_a.zone_idx];
-            let passes_a = evaluator.evaluate_row(event_type_a, zone_a, row_a);
-            if tracing::enabled!(tracing::Level::DEBUG) {
-                // Extract id field value for debugging
-                let accessor_a = PreparedAccessor::new(&zone_a.values);
-                let id_value_a = accessor_a.get_i64_at("id", row_a.row_idx);
-                debug!(
-                    target: "sneldb::sequence::matcher",
-                    event_type = %event_type_a,
-                    row_idx = row_a.row_idx,
-                    zone_idx = row_a.zone_idx,
-                    passes = passes_a,
-                    id_value = ?id_value_a,
-                    available_fields = ?zone_a.values.keys().collect::<Vec<_>>(),
-                    "WHERE clause evaluation for event A"
-                );
-            }
-            if !passes_a {
-                if tracing::enabled!(tracing::Level::DEBUG) {
-                    debug!(
-                        target: "sneldb::sequence::matcher",
-                        event_type = %event_type_a,
-                        row_idx = row_a.row_idx,
-                        "WHERE clause failed for event A"
-                    );
-                }
-                return false;
-            }
-
-            // Check WHERE clause for event B
-            let zone_b = &zones_b[row_b.zone_idx];
-            let passes_b = evaluator.evaluate_row(event_type_b, zone_b, row_b);
-            if tracing::enabled!(tracing::Level::DEBUG) {
-                // Extract id field value for debugging
-                let accessor_b = PreparedAccessor::new(&zone_b.values);
-                let id_value_b = accessor_b.get_i64_at("id", row_b.row_idx);
-                debug!(
-                    target: "sneldb::sequence::matcher",
-                    event_type = %event_type_b,
-                    row_idx = row_b.row_idx,
-                    zone_idx = row_b.zone_idx,
-                    passes = passes_b,
-                    id_value = ?id_value_b,
-                    available_fields = ?zone_b.values.keys().collect::<Vec<_>>(),
-                    "WHERE clause evaluation for event B"
-                );
-            }
-            if !passes_b {
-                if tracing::enabled!(tracing::Level::DEBUG) {
-                    debug!(
-                        target: "sneldb::sequence::matcher",
-                        event_type = %event_type_b,
-                        row_idx = row_b.row_idx,
-                        "WHERE clause failed for event B"
-                    );
-                }
-                return false;
-            }
-
-            if tracing::enabled!(tracing::Level::DEBUG) {
-                debug!(
-                    target: "sneldb::sequence::matcher",
-                    "Both events passed WHERE clause"
-                );
-            }
-        } else {
-            if tracing::enabled!(tracing::Level::TRACE) {
-                trace!(
-                    target: "sneldb::sequence::matcher",
-                    "No WHERE evaluator, match passes"
-                );
-            }
+        let Some(evaluator) = &self.where_evaluator else {
+            return true;
+        };
+        let zone = &zones[row.zone_idx];
+        let passes = evaluator.evaluate_row(event_type, zone, row);
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            debug!(
+                target: "sneldb::sequence::matcher",
+                event_type = %event_type,
+                row_idx = row.row_idx,
+                zone_idx = row.zone_idx,
+                passes = passes,
+                "WHERE clause evaluation for sequence event"
+            );
         }
-        true
+        passes
     }
 
     /// Gets the timestamp for a specific row index from columnar data.
@@ -742,3 +564,9 @@ impl SequenceMatcher {
         }
     }
 }
+
+/// Overlap of two inclusive time ranges.
+fn intersect(a: (u64, u64), b: (u64, u64)) -> Option<(u64, u64)> {
+    let range = (a.0.max(b.0), a.1.min(b.1));
+    (range.0 <= range.1).then_some(range)
+}
@@ -7,6 +7,7 @@ use crate::engine::core::read::sequence::matcher::SequenceMatcher;
 use crate::engine::core::read::sequence::where_evaluator::SequenceWhereEvaluator;
 use crate::engine::schema::registry::{MiniSchema, SchemaRegistry};
 use crate::engine::schema::types::FieldType;
+use indexmap::IndexMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tempfile::tempdir;
@@ -21,12 +22,15 @@ async fn create_test_registry(
     let mut registry = SchemaRegistry::new_with_path(path).expect("Failed to create registry");
 
     for (event_type, fields) in event_types_and_fields {
-        let mut schema_fields = HashMap::new();
+        let mut schema_fields = IndexMap::new();
         for field in *fields {
             schema_fields.insert(field.to_string(), FieldType::String);
         }
         let schema = MiniSchema {
             fields: schema_fields,
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         };
         registry
             .define(event_type, schema)
@@ -101,6 +105,7 @@ fn test_match_followed_by_basic() {
                 field: None,
             },
         )],
+        within: None,
     };
 
     let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
@@ -143,6 +148,7 @@ fn test_match_followed_by_no_match() {
                 field: None,
             },
         )],
+        within: None,
     };
 
     let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
@@ -178,6 +184,7 @@ fn test_match_preceded_by_basic() {
                 field: None,
             },
         )],
+        within: None,
     };
 
     let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
@@ -226,6 +233,7 @@ fn test_match_with_limit() {
                 field: None,
             },
         )],
+        within: None,
     };
 
     let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
@@ -274,6 +282,7 @@ fn test_match_preceded_by_no_match_when_no_preceding_event() {
                 field: None,
             },
         )],
+        within: None,
     };
 
     let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
@@ -319,6 +328,7 @@ fn test_match_preceded_by_same_timestamp_no_match() {
                 field: None,
             },
         )],
+        within: None,
     };
 
     let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
@@ -387,6 +397,7 @@ async fn test_match_followed_by_with_where_clause() {
                 field: None,
             },
         )],
+        within: None,
     };
 
     let matcher = SequenceMatcher::new(sequence, "timestamp".to_string())
@@ -465,6 +476,7 @@ async fn test_match_preceded_by_with_where_clause() {
                 field: None,
             },
         )],
+        within: None,
     };
 
     let matcher = SequenceMatcher::new(sequence, "timestamp".to_string())
@@ -525,3 +537,534 @@ fn create_test_zone_with_payload(
     zone.set_values(values_map);
     zone
 }
+
+#[test]
+fn test_match_limit_breaks_timestamp_ties_by_link_value() {
+    // Three users start at the same time; LIMIT keeps the lowest link value
+    // rather than whichever group the hash map yields first
+    let mut zones_by_type = HashMap::new();
+    zones_by_type.insert(
+        "page_view".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["ctx1", "ctx2", "ctx3"],
+            &["user3", "user1", "user2"],
+            &[1000, 1000, 1000],
+        )],
+    );
+    zones_by_type.insert(
+        "order_created".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["ctx1", "ctx2", "ctx3"],
+            &["user3", "user1", "user2"],
+            &[1500, 1500, 1500],
+        )],
+    );
+
+    let sequence = EventSequence {
+        head: EventTarget {
+            event: "page_view".to_string(),
+            field: None,
+        },
+        links: vec![(
+            SequenceLink::FollowedBy,
+            EventTarget {
+                event: "order_created".to_string(),
+                field: None,
+            },
+        )],
+        within: None,
+    };
+    let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
+
+    for _ in 0..4 {
+        let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+        let groups = grouper.group_zones_by_link_field(&zones_by_type);
+        let matches = matcher.match_sequences(groups, &zones_by_type, Some(2));
+        let users: Vec<&str> = matches
+            .iter()
+            .map(|m| m.link_value.as_str().unwrap())
+            .collect();
+        assert_eq!(users, vec!["user1", "user2"]);
+    }
+}
+
+fn chain(head: &str, links: &[(SequenceLink, &str)]) -> EventSequence {
+    EventSequence {
+        head: EventTarget {
+            event: head.to_string(),
+            field: None,
+        },
+        links: links
+            .iter()
+            .map(|(link, event)| {
+                (
+                    link.clone(),
+                    EventTarget {
+                        event: event.to_string(),
+                        field: None,
+                    },
+                )
+            })
+            .collect(),
+        within: None,
+    }
+}
+
+/// (event_type, row_idx) of each matched event, in match order
+fn matched_rows(
+    matches: &[crate::engine::core::read::sequence::matcher::MatchedSequenceIndices],
+) -> Vec<Vec<(String, usize)>> {
+    matches
+        .iter()
+        .map(|m| {
+            m.matched_rows
+                .iter()
+                .map(|(event_type, row)| (event_type.clone(), row.row_idx))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_match_three_event_chain_with_overlapping_windows() {
+    // user1: view(1000) cart(2000) view(3000) order(4000) cart(5000) order(6000)
+    // user2: view(1000) cart(2000) and no order
+    let mut zones_by_type = HashMap::new();
+    zones_by_type.insert(
+        "page_view".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c", "c"],
+            &["user1", "user1", "user2"],
+            &[1000, 3000, 1000],
+        )],
+    );
+    zones_by_type.insert(
+        "add_to_cart".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c", "c"],
+            &["user1", "user1", "user2"],
+            &[2000, 5000, 2000],
+        )],
+    );
+    zones_by_type.insert(
+        "order_created".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c"],
+            &["user1", "user1"],
+            &[4000, 6000],
+        )],
+    );
+
+    let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+    let groups = grouper.group_zones_by_link_field(&zones_by_type);
+
+    let sequence = chain(
+        "page_view",
+        &[
+            (SequenceLink::FollowedBy, "add_to_cart"),
+            (SequenceLink::FollowedBy, "order_created"),
+        ],
+    );
+    let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
+    let matches = matcher.match_sequences(groups, &zones_by_type, None);
+
+    // Each window links to the next cart and order after it, not to the first ones seen
+    assert_eq!(
+        matched_rows(&matches),
+        vec![
+            vec![
+                ("page_view".to_string(), 0),
+                ("add_to_cart".to_string(), 0),
+                ("order_created".to_string(), 0),
+            ],
+            vec![
+                ("page_view".to_string(), 1),
+                ("add_to_cart".to_string(), 1),
+                ("order_created".to_string(), 1),
+            ],
+        ]
+    );
+    assert!(
+        matches
+            .iter()
+            .all(|m| m.link_value.as_str() == Some("user1"))
+    );
+}
+
+#[test]
+fn test_match_chain_with_mixed_links_orders_events_by_time() {
+    // signup(1000) support_ticket(1500) refund(2000) login(3000)
+    let mut zones_by_type = HashMap::new();
+    for (event_type, ts) in [
+        ("signup", 1000),
+        ("support_ticket", 1500),
+        ("refund", 2000),
+        ("login", 3000),
+    ] {
+        zones_by_type.insert(
+            event_type.to_string(),
+            vec![create_test_zone(0, "seg1", &["c"], &["user1"], &[ts])],
+        );
+    }
+
+    let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+    let groups = grouper.group_zones_by_link_field(&zones_by_type);
+
+    // refund must come before login, the ticket before that refund
+    let sequence = chain(
+        "signup",
+        &[
+            (SequenceLink::FollowedBy, "login"),
+            (SequenceLink::PrecededBy, "refund"),
+            (SequenceLink::PrecededBy, "support_ticket"),
+        ],
+    );
+    let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
+    let matches = matcher.match_sequences(groups, &zones_by_type, None);
+
+    let order: Vec<Vec<String>> = matched_rows(&matches)
+        .into_iter()
+        .map(|rows| rows.into_iter().map(|(event_type, _)| event_type).collect())
+        .collect();
+    assert_eq!(
+        order,
+        vec![vec!["signup", "support_ticket", "refund", "login"]]
+    );
+}
+
+#[test]
+fn test_match_preceded_by_skips_heads_without_earlier_event() {
+    // order_created(1000) has nothing before it, order_created(3000) has payment_failed(2000)
+    let mut zones_by_type = HashMap::new();
+    zones_by_type.insert(
+        "order_created".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c"],
+            &["user1", "user1"],
+            &[1000, 3000],
+        )],
+    );
+    zones_by_type.insert(
+        "payment_failed".to_string(),
+        vec![create_test_zone(0, "seg1", &["c"], &["user1"], &[2000])],
+    );
+
+    let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+    let groups = grouper.group_zones_by_link_field(&zones_by_type);
+
+    let sequence = chain(
+        "order_created",
+        &[(SequenceLink::PrecededBy, "payment_failed")],
+    );
+    let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
+    let matches = matcher.match_sequences(groups, &zones_by_type, None);
+
+    assert_eq!(
+        matched_rows(&matches),
+        vec![vec![
+            ("payment_failed".to_string(), 0),
+            ("order_created".to_string(), 1),
+        ]]
+    );
+}
+
+#[test]
+fn test_match_repeated_event_type_never_matches_itself() {
+    let mut zones_by_type = HashMap::new();
+    zones_by_type.insert(
+        "login".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c", "c"],
+            &["user1", "user1", "user1"],
+            &[1000, 2000, 2000],
+        )],
+    );
+
+    let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+    let groups = grouper.group_zones_by_link_field(&zones_by_type);
+
+    let sequence = chain(
+        "login",
+        &[
+            (SequenceLink::FollowedBy, "login"),
+            (SequenceLink::FollowedBy, "login"),
+        ],
+    );
+    let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
+    let matches = matcher.match_sequences(groups, &zones_by_type, None);
+
+    // Only the first login starts three distinct logins; ties at 2000 may swap
+    // places but a row is never reused within a match
+    assert_eq!(matches.len(), 1);
+    let rows: Vec<usize> = matched_rows(&matches)[0]
+        .iter()
+        .map(|(_, row)| *row)
+        .collect();
+    assert_eq!(rows, vec![0, 1, 2]);
+}
+
+fn within(window: u64, sequence: EventSequence) -> EventSequence {
+    EventSequence {
+        within: Some(window),
+        ..sequence
+    }
+}
+
+#[test]
+fn test_match_within_window_includes_the_edge() {
+    // user1: view(1000) order(2800), a gap of exactly 30 minutes
+    // user2: view(1000) order(2801), one second over
+    let mut zones_by_type = HashMap::new();
+    zones_by_type.insert(
+        "page_view".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c"],
+            &["user1", "user2"],
+            &[1000, 1000],
+        )],
+    );
+    zones_by_type.insert(
+        "order_created".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c"],
+            &["user1", "user2"],
+            &[2800, 2801],
+        )],
+    );
+
+    let sequence = chain("page_view", &[(SequenceLink::FollowedBy, "order_created")]);
+    let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+
+    let matcher = SequenceMatcher::new(within(1800, sequence.clone()), "timestamp".to_string());
+    let matches = matcher.match_sequences(
+        grouper.group_zones_by_link_field(&zones_by_type),
+        &zones_by_type,
+        None,
+    );
+    let users: Vec<&str> = matches
+        .iter()
+        .map(|m| m.link_value.as_str().unwrap())
+        .collect();
+    assert_eq!(users, vec!["user1"]);
+
+    let matcher = SequenceMatcher::new(within(1799, sequence), "timestamp".to_string());
+    let matches = matcher.match_sequences(
+        grouper.group_zones_by_link_field(&zones_by_type),
+        &zones_by_type,
+        None,
+    );
+    assert!(matches.is_empty());
+}
+
+#[test]
+fn test_match_within_window_applies_to_each_link() {
+    // view(1000) cart(1500) order(2600): the cart is in time, the order is not
+    // within 1000s of the cart; payment(1400) precedes the order by 1200s
+    let mut zones_by_type = HashMap::new();
+    for (event_type, ts) in [
+        ("page_view", 1000),
+        ("add_to_cart", 1500),
+        ("payment_failed", 1400),
+        ("order_created", 2600),
+    ] {
+        zones_by_type.insert(
+            event_type.to_string(),
+            vec![create_test_zone(0, "seg1", &["c"], &["user1"], &[ts])],
+        );
+    }
+    let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+
+    let funnel = chain(
+        "page_view",
+        &[
+            (SequenceLink::FollowedBy, "add_to_cart"),
+            (SequenceLink::FollowedBy, "order_created"),
+        ],
+    );
+    let matcher = SequenceMatcher::new(within(1000, funnel.clone()), "timestamp".to_string());
+    let matches = matcher.match_sequences(
+        grouper.group_zones_by_link_field(&zones_by_type),
+        &zones_by_type,
+        None,
+    );
+    assert!(matches.is_empty());
+
+    // The whole funnel spans 1600s, but no single gap exceeds 1100s
+    let matcher = SequenceMatcher::new(within(1100, funnel), "timestamp".to_string());
+    let matches = matcher.match_sequences(
+        grouper.group_zones_by_link_field(&zones_by_type),
+        &zones_by_type,
+        None,
+    );
+    assert_eq!(matches.len(), 1);
+
+    let preceded = chain(
+        "order_created",
+        &[(SequenceLink::PrecededBy, "payment_failed")],
+    );
+    for (window, expected) in [(1199, 0), (1200, 1)] {
+        let matcher =
+            SequenceMatcher::new(within(window, preceded.clone()), "timestamp".to_string());
+        let matches = matcher.match_sequences(
+            grouper.group_zones_by_link_field(&zones_by_type),
+            &zones_by_type,
+            None,
+        );
+        assert_eq!(matches.len(), expected, "window {}", window);
+    }
+}
+
+fn spans(entries: &[(&str, (u64, u64))]) -> HashMap<String, (u64, u64)> {
+    entries
+        .iter()
+        .map(|(event_type, span)| (event_type.to_string(), *span))
+        .collect()
+}
+
+#[test]
+fn test_window_bounds_drop_events_outside_window() {
+    let sequence = chain("page_view", &[(SequenceLink::FollowedBy, "order_created")]);
+    let type_spans = spans(&[
+        ("page_view", (1000, 50_000)),
+        ("order_created", (900, 3000)),
+    ]);
+
+    // Without a window nothing is narrowed
+    let matcher = SequenceMatcher::new(sequence.clone(), "timestamp".to_string());
+    assert_eq!(matcher.window_bounds(&type_spans), None);
+
+    // Orders must land in [1000, 3000] to follow a page view within 1800s, and
+    // only page views up to the last order can have one after them
+    let matcher = SequenceMatcher::new(within(1800, sequence.clone()), "timestamp".to_string());
+    let bounds = matcher.window_bounds(&type_spans).unwrap();
+    assert_eq!(bounds["page_view"], (1000, 3000));
+    assert_eq!(bounds["order_created"], (1000, 3000));
+
+    // Leaving out the events outside the bounds keeps every match
+    let mut zones_by_type = HashMap::new();
+    zones_by_type.insert(
+        "page_view".to_string(),
+        vec![
+            create_test_zone(0, "seg1", &["c", "c"], &["user1", "user2"], &[1000, 1200]),
+            create_test_zone(1, "seg2", &["c"], &["user3"], &[50_000]),
+        ],
+    );
+    zones_by_type.insert(
+        "order_created".to_string(),
+        vec![
+            create_test_zone(0, "seg1", &["c"], &["user1"], &[900]),
+            create_test_zone(1, "seg2", &["c"], &["user1"], &[1500]),
+            create_test_zone(2, "seg3", &["c"], &["user2"], &[3000]),
+        ],
+    );
+    let mut bounded = HashMap::new();
+    bounded.insert(
+        "page_view".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c"],
+            &["user1", "user2"],
+            &[1000, 1200],
+        )],
+    );
+    bounded.insert(
+        "order_created".to_string(),
+        vec![
+            create_test_zone(1, "seg2", &["c"], &["user1"], &[1500]),
+            create_test_zone(2, "seg3", &["c"], &["user2"], &[3000]),
+        ],
+    );
+    let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+    let before = matcher.match_sequences(
+        grouper.group_zones_by_link_field(&zones_by_type),
+        &zones_by_type,
+        None,
+    );
+    let after =
+        matcher.match_sequences(grouper.group_zones_by_link_field(&bounded), &bounded, None);
+    assert_eq!(before.len(), 2);
+    assert_eq!(after.len(), 2);
+
+    // No order can follow any page view within 100s
+    let type_spans = spans(&[("page_view", (1000, 1000)), ("order_created", (1101, 1101))]);
+    let bounds = SequenceMatcher::new(within(100, sequence.clone()), "timestamp".to_string())
+        .window_bounds(&type_spans)
+        .unwrap();
+    assert!(bounds.is_empty());
+
+    // Nor without any order at all
+    let type_spans = spans(&[("page_view", (1000, 1000))]);
+    let bounds = SequenceMatcher::new(within(100, sequence), "timestamp".to_string())
+        .window_bounds(&type_spans)
+        .unwrap();
+    assert!(bounds.is_empty());
+}
+
+#[test]
+fn test_window_bounds_keep_the_nearest_event_of_each_link() {
+    let sequence = within(
+        100,
+        chain(
+            "a",
+            &[
+                (SequenceLink::FollowedBy, "b"),
+                (SequenceLink::FollowedBy, "c"),
+            ],
+        ),
+    );
+    let matcher = SequenceMatcher::new(sequence, "timestamp".to_string());
+
+    // Only the `b` at 100 reaches the `c` at 150, but the head picks the `b`
+    // at 10 and misses the window: dropping it would make up a match
+    let type_spans = spans(&[("a", (0, 0)), ("b", (10, 100)), ("c", (150, 150))]);
+    let bounds = matcher.window_bounds(&type_spans).unwrap();
+    assert_eq!(bounds["a"], (0, 0));
+    assert_eq!(bounds["b"], (10, 100));
+    assert_eq!(bounds["c"], (150, 150));
+
+    let mut zones_by_type = HashMap::new();
+    zones_by_type.insert(
+        "a".to_string(),
+        vec![create_test_zone(0, "seg1", &["c"], &["user1"], &[0])],
+    );
+    zones_by_type.insert(
+        "b".to_string(),
+        vec![create_test_zone(
+            0,
+            "seg1",
+            &["c", "c"],
+            &["user1", "user1"],
+            &[10, 100],
+        )],
+    );
+    zones_by_type.insert(
+        "c".to_string(),
+        vec![create_test_zone(0, "seg1", &["c"], &["user1"], &[150])],
+    );
+    let grouper = ColumnarGrouper::new("user_id".to_string(), "timestamp".to_string());
+    let matches = matcher.match_sequences(
+        grouper.group_zones_by_link_field(&zones_by_type),
+        &zones_by_type,
+        None,
+    );
+    assert!(matches.is_empty());
+}
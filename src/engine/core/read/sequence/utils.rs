@@ -1,6 +1,6 @@
 /// Utility functions for sequence query processing.
 use crate::command::types::Expr;
-use crate::engine::types::ScalarValue;
+use crate::engine::types::{ScalarValue, decimal};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
 
 /// Converts a ScalarValue to a string representation.
@@ -11,6 +11,10 @@ pub(crate) fn scalar_to_string(value: &ScalarValue) -> String {
         ScalarValue::Float64(f) => f.to_string(),
         ScalarValue::Boolean(b) => b.to_string(),
         ScalarValue::Timestamp(ts) => ts.to_string(),
+        ScalarValue::Decimal(m, s) => decimal::format(*m, *s),
+        ScalarValue::Date(_) | ScalarValue::Time(_) | ScalarValue::Uuid(_) => {
+            value.to_string_repr()
+        }
         ScalarValue::Binary(bytes) => BASE64_STANDARD.encode(bytes),
         ScalarValue::Null => "null".to_string(),
     }
@@ -7,6 +7,7 @@ use crate::engine::core::read::sequence::where_evaluator::SequenceWhereEvaluator
 use crate::engine::schema::registry::{MiniSchema, SchemaRegistry};
 use crate::engine::schema::types::FieldType;
 use serde_json::json;
+use indexmap::IndexMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tempfile::tempdir;
@@ -21,12 +22,15 @@ async fn create_test_registry(
     let mut registry = SchemaRegistry::new_with_path(path).expect("Failed to create registry");
 
     for (event_type, fields) in event_types_and_fields {
-        let mut schema_fields = HashMap::new();
+        let mut schema_fields = IndexMap::new();
         for field in *fields {
             schema_fields.insert(field.to_string(), FieldType::String);
         }
         let schema = MiniSchema {
             fields: schema_fields,
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         };
         registry
             .define(event_type, schema)
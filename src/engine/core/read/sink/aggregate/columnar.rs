@@ -3,12 +3,22 @@ use crate::command::types::TimeGranularity;
 use crate::engine::core::column::column_values::ColumnValues;
 use crate::engine::core::read::aggregate::ops::AggregatorImpl;
 use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
-use ahash::RandomState as AHashRandomState;
+use crate::shared::hash::{QueryHashMap, QueryHashState};
 use std::collections::HashMap;
 
 /// Columnar processing logic for aggregate sink
 pub(crate) struct ColumnarProcessor;
 
+/// What a row's group key is made of: the query's hasher, time bucket and
+/// BY fields, and where their columns sit.
+pub(crate) struct GroupingContext<'a> {
+    pub hash_state: &'a QueryHashState,
+    pub time_bucket: Option<&'a TimeGranularity>,
+    pub group_by: Option<&'a [String]>,
+    pub time_field: &'a str,
+    pub column_indices: Option<&'a HashMap<String, usize>>,
+}
+
 impl ColumnarProcessor {
     /// Check if columnar processing can be used for this slice
     pub(crate) fn can_use_columnar_processing(
@@ -50,7 +60,7 @@ impl ColumnarProcessor {
 
     /// Process a column slice using columnar/SIMD operations (no grouping case)
     pub(crate) fn process_columnar_slice(
-        groups: &mut std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState>,
+        groups: &mut QueryHashMap<GroupKey, Vec<AggregatorImpl>>,
         specs: &[AggregateOpSpec],
         start: usize,
         end: usize,
@@ -77,27 +87,17 @@ impl ColumnarProcessor {
 
     /// Process a column slice with grouping using columnar/SIMD operations
     pub(crate) fn process_columnar_slice_with_grouping(
-        groups: &mut std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState>,
+        groups: &mut QueryHashMap<GroupKey, Vec<AggregatorImpl>>,
         specs: &[AggregateOpSpec],
-        time_bucket: Option<&TimeGranularity>,
-        group_by: Option<&[String]>,
-        time_field: &str,
-        column_indices: Option<&HashMap<String, usize>>,
+        grouping: &GroupingContext<'_>,
         start: usize,
         end: usize,
         columns: &HashMap<String, ColumnValues>,
         group_limit: Option<usize>,
     ) {
         // Step 1: Compute group keys and partition by prehash
-        let partitioned_groups = Self::compute_and_partition_by_prehash(
-            time_bucket,
-            group_by,
-            time_field,
-            column_indices,
-            start,
-            end,
-            columns,
-        );
+        let partitioned_groups =
+            Self::compute_and_partition_by_prehash(grouping, start, end, columns);
 
         // Step 2: Process each group's rows using columnar processing
         Self::process_groups_columnarly_by_prehash(
@@ -111,25 +111,31 @@ impl ColumnarProcessor {
 
     /// Compute group keys for all rows and partition by prehash
     /// Optimized to compute prehash first without allocating GroupKey, only creating
-    /// GroupKey when encountering a new unique prehash
+    /// GroupKey when a row matches none of the keys seen under its prehash
     fn compute_and_partition_by_prehash(
-        time_bucket: Option<&TimeGranularity>,
-        group_by: Option<&[String]>,
-        time_field: &str,
-        column_indices: Option<&HashMap<String, usize>>,
+        grouping: &GroupingContext<'_>,
         start: usize,
         end: usize,
         columns: &HashMap<String, ColumnValues>,
-    ) -> HashMap<u64, (GroupKey, Vec<usize>)> {
+    ) -> QueryHashMap<u64, Vec<(GroupKey, Vec<usize>)>> {
+        let GroupingContext {
+            hash_state,
+            time_bucket,
+            group_by,
+            time_field,
+            column_indices,
+        } = *grouping;
         let slice_len = end - start;
         let estimated_groups = (slice_len / 8).max(1).min(1000);
-        let mut groups: HashMap<u64, (GroupKey, Vec<usize>)> =
-            HashMap::with_capacity(estimated_groups);
-        let mut prehash_to_key: HashMap<u64, GroupKey> = HashMap::with_capacity(estimated_groups);
+        // Keys that collide on their prehash share a slot and are told apart by value
+        let mut groups: QueryHashMap<u64, Vec<(GroupKey, Vec<usize>)>> =
+            QueryHashMap::with_capacity_and_hasher(estimated_groups, hash_state.clone());
+        let estimated_capacity = (slice_len / estimated_groups.max(1)).max(4).min(slice_len);
 
         for row_idx in start..end {
             // Compute prehash without allocating GroupKey
             let prehash = GroupKey::compute_prehash_from_columns(
+                hash_state,
                 time_bucket,
                 group_by,
                 time_field,
@@ -138,52 +144,49 @@ impl ColumnarProcessor {
                 row_idx,
             );
 
-            let estimated_capacity = (slice_len / estimated_groups.max(1)).max(4).min(slice_len);
-
-            groups
-                .entry(prehash)
-                .and_modify(|(_, row_indices)| {
-                    row_indices.push(row_idx);
-                })
-                .or_insert_with(|| {
-                    // Only create GroupKey when we encounter a new unique prehash
-                    let key = prehash_to_key
-                        .entry(prehash)
-                        .or_insert_with(|| {
-                            GroupKey::from_row_with_indices(
-                                time_bucket,
-                                group_by,
-                                time_field,
-                                columns,
-                                column_indices,
-                                row_idx,
-                            )
-                        })
-                        .clone();
-
+            let slot = groups.entry(prehash).or_default();
+            let existing = slot.iter_mut().find(|(key, _)| {
+                key.matches_row(
+                    time_bucket,
+                    group_by,
+                    time_field,
+                    columns,
+                    column_indices,
+                    row_idx,
+                )
+            });
+            match existing {
+                Some((_, row_indices)) => row_indices.push(row_idx),
+                None => {
+                    let key = GroupKey::from_row_with_indices(
+                        hash_state,
+                        time_bucket,
+                        group_by,
+                        time_field,
+                        columns,
+                        column_indices,
+                        row_idx,
+                    );
                     let mut row_indices = Vec::with_capacity(estimated_capacity);
                     row_indices.push(row_idx);
-                    (key, row_indices)
-                });
-        }
-
-        // Sort row indices within each group for better cache locality
-        for (_, (_, row_indices)) in groups.iter_mut() {
-            row_indices.sort_unstable();
+                    slot.push((key, row_indices));
+                }
+            }
         }
 
+        // Rows were visited in order, so each group's row indices are already sorted
         groups
     }
 
     /// Process each group's rows using columnar processing
     fn process_groups_columnarly_by_prehash(
-        groups: &mut std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState>,
+        groups: &mut QueryHashMap<GroupKey, Vec<AggregatorImpl>>,
         specs: &[AggregateOpSpec],
-        partitioned_groups: HashMap<u64, (GroupKey, Vec<usize>)>,
+        partitioned_groups: QueryHashMap<u64, Vec<(GroupKey, Vec<usize>)>>,
         columns: &HashMap<String, ColumnValues>,
         group_limit: Option<usize>,
     ) {
-        for (_prehash, (key, row_indices)) in partitioned_groups {
+        for (key, row_indices) in partitioned_groups.into_values().flatten() {
             // Enforce group limit
             if !groups.contains_key(&key) {
                 if let Some(max) = group_limit {
@@ -1,11 +1,12 @@
-use super::columnar::ColumnarProcessor;
+use super::columnar::{ColumnarProcessor, GroupingContext};
 use super::group_key::GroupKey;
 use crate::command::types::TimeGranularity;
 use crate::engine::core::column::column_values::ColumnValues;
-use crate::engine::core::read::aggregate::ops::AggregatorImpl;
+use crate::engine::core::read::aggregate::ops::{AggOutput, AggregatorImpl};
 use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
 use crate::engine::core::read::cache::DecompressedBlock;
-use ahash::RandomState as AHashRandomState;
+use crate::shared::config::QueryHasher;
+use crate::shared::hash::{QueryHashMap, QueryHashState};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -157,8 +158,7 @@ fn columnar_cannot_use_columnar_processing_mixed_unsupported_ops() {
 #[test]
 fn columnar_process_columnar_slice_no_grouping() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     columns.insert("dummy".to_string(), make_string_column(&["a", "b", "c"]));
 
@@ -169,7 +169,7 @@ fn columnar_process_columnar_slice_no_grouping() {
         prehash: 0,
         bucket: None,
         groups: Vec::new(),
-        groups_str: Some(Vec::<String>::new()),
+        groups_str: Some(Vec::<Option<String>>::new()),
     };
     let aggs = groups.get(&default_key).unwrap();
     assert_eq!(aggs.len(), 1);
@@ -180,8 +180,7 @@ fn columnar_process_columnar_slice_with_total() {
     let specs = vec![AggregateOpSpec::Total {
         field: "amount".into(),
     }];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     columns.insert("amount".to_string(), make_typed_i64_column(&[10, 20, 30]));
 
@@ -193,8 +192,7 @@ fn columnar_process_columnar_slice_with_total() {
 #[test]
 fn columnar_process_columnar_slice_partial_range() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     columns.insert(
         "dummy".to_string(),
@@ -211,8 +209,7 @@ fn columnar_process_columnar_slice_partial_range() {
 #[test]
 fn columnar_process_columnar_slice_with_grouping_single_group() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     columns.insert(
         "country".to_string(),
@@ -222,10 +219,13 @@ fn columnar_process_columnar_slice_with_grouping_single_group() {
     ColumnarProcessor::process_columnar_slice_with_grouping(
         &mut groups,
         &specs,
-        None,
-        Some(&["country".to_string()]),
-        "timestamp",
-        None,
+        &GroupingContext {
+            hash_state: &QueryHashState::default(),
+            time_bucket: None,
+            group_by: Some(&["country".to_string()]),
+            time_field: "timestamp",
+            column_indices: None,
+        },
         0,
         3,
         &columns,
@@ -238,8 +238,7 @@ fn columnar_process_columnar_slice_with_grouping_single_group() {
 #[test]
 fn columnar_process_columnar_slice_with_grouping_multiple_groups() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     columns.insert(
         "country".to_string(),
@@ -249,10 +248,13 @@ fn columnar_process_columnar_slice_with_grouping_multiple_groups() {
     ColumnarProcessor::process_columnar_slice_with_grouping(
         &mut groups,
         &specs,
-        None,
-        Some(&["country".to_string()]),
-        "timestamp",
-        None,
+        &GroupingContext {
+            hash_state: &QueryHashState::default(),
+            time_bucket: None,
+            group_by: Some(&["country".to_string()]),
+            time_field: "timestamp",
+            column_indices: None,
+        },
         0,
         3,
         &columns,
@@ -265,8 +267,7 @@ fn columnar_process_columnar_slice_with_grouping_multiple_groups() {
 #[test]
 fn columnar_process_columnar_slice_with_grouping_and_time_bucket() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     let timestamps: Vec<i64> = vec![86400, 86401, 172800];
     columns.insert("timestamp".to_string(), make_typed_i64_column(&timestamps));
@@ -278,10 +279,13 @@ fn columnar_process_columnar_slice_with_grouping_and_time_bucket() {
     ColumnarProcessor::process_columnar_slice_with_grouping(
         &mut groups,
         &specs,
-        Some(&TimeGranularity::Day),
-        Some(&["country".to_string()]),
-        "timestamp",
-        None,
+        &GroupingContext {
+            hash_state: &QueryHashState::default(),
+            time_bucket: Some(&TimeGranularity::Day),
+            group_by: Some(&["country".to_string()]),
+            time_field: "timestamp",
+            column_indices: None,
+        },
         0,
         3,
         &columns,
@@ -295,8 +299,7 @@ fn columnar_process_columnar_slice_with_grouping_and_time_bucket() {
 #[test]
 fn columnar_process_columnar_slice_with_grouping_respects_group_limit() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     columns.insert(
         "country".to_string(),
@@ -306,10 +309,13 @@ fn columnar_process_columnar_slice_with_grouping_respects_group_limit() {
     ColumnarProcessor::process_columnar_slice_with_grouping(
         &mut groups,
         &specs,
-        None,
-        Some(&["country".to_string()]),
-        "timestamp",
-        None,
+        &GroupingContext {
+            hash_state: &QueryHashState::default(),
+            time_bucket: None,
+            group_by: Some(&["country".to_string()]),
+            time_field: "timestamp",
+            column_indices: None,
+        },
         0,
         4,
         &columns,
@@ -322,8 +328,7 @@ fn columnar_process_columnar_slice_with_grouping_respects_group_limit() {
 #[test]
 fn columnar_process_columnar_slice_with_grouping_uses_column_indices() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     columns.insert("country".to_string(), make_string_column(&["US", "DE"]));
 
@@ -333,10 +338,13 @@ fn columnar_process_columnar_slice_with_grouping_uses_column_indices() {
     ColumnarProcessor::process_columnar_slice_with_grouping(
         &mut groups,
         &specs,
-        None,
-        Some(&["country".to_string()]),
-        "timestamp",
-        Some(&column_indices),
+        &GroupingContext {
+            hash_state: &QueryHashState::default(),
+            time_bucket: None,
+            group_by: Some(&["country".to_string()]),
+            time_field: "timestamp",
+            column_indices: Some(&column_indices),
+        },
         0,
         2,
         &columns,
@@ -349,17 +357,19 @@ fn columnar_process_columnar_slice_with_grouping_uses_column_indices() {
 #[test]
 fn columnar_process_columnar_slice_with_grouping_empty_range() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let columns = HashMap::new();
 
     ColumnarProcessor::process_columnar_slice_with_grouping(
         &mut groups,
         &specs,
-        None,
-        None,
-        "timestamp",
-        None,
+        &GroupingContext {
+            hash_state: &QueryHashState::default(),
+            time_bucket: None,
+            group_by: None,
+            time_field: "timestamp",
+            column_indices: None,
+        },
         0,
         0,
         &columns,
@@ -374,8 +384,7 @@ fn columnar_process_columnar_slice_with_grouping_contiguous_rows_optimized() {
     let specs = vec![AggregateOpSpec::Total {
         field: "amount".into(),
     }];
-    let mut groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        std::collections::HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let mut columns = HashMap::new();
     columns.insert(
         "country".to_string(),
@@ -389,10 +398,13 @@ fn columnar_process_columnar_slice_with_grouping_contiguous_rows_optimized() {
     ColumnarProcessor::process_columnar_slice_with_grouping(
         &mut groups,
         &specs,
-        None,
-        Some(&["country".to_string()]),
-        "timestamp",
-        None,
+        &GroupingContext {
+            hash_state: &QueryHashState::default(),
+            time_bucket: None,
+            group_by: Some(&["country".to_string()]),
+            time_field: "timestamp",
+            column_indices: None,
+        },
         0,
         5,
         &columns,
@@ -401,3 +413,67 @@ fn columnar_process_columnar_slice_with_grouping_contiguous_rows_optimized() {
 
     assert_eq!(groups.len(), 2);
 }
+
+#[test]
+fn columnar_grouping_gives_the_same_groups_with_either_hasher() {
+    let specs = vec![
+        AggregateOpSpec::CountAll,
+        AggregateOpSpec::Total {
+            field: "amount".into(),
+        },
+    ];
+    let mut columns = HashMap::new();
+    columns.insert(
+        "country".to_string(),
+        make_string_column(&["US", "DE", "US", "FR", "DE", "US"]),
+    );
+    columns.insert(
+        "amount".to_string(),
+        make_typed_i64_column(&[10, 20, 30, 40, 50, 60]),
+    );
+
+    let totals: Vec<Vec<(String, i64)>> = [QueryHasher::Ahash, QueryHasher::Sip]
+        .into_iter()
+        .map(|hasher| {
+            let hash_state = QueryHashState::new(hasher);
+            let mut groups = QueryHashMap::with_hasher(hash_state.clone());
+            ColumnarProcessor::process_columnar_slice_with_grouping(
+                &mut groups,
+                &specs,
+                &GroupingContext {
+                    hash_state: &hash_state,
+                    time_bucket: None,
+                    group_by: Some(&["country".to_string()]),
+                    time_field: "timestamp",
+                    column_indices: None,
+                },
+                0,
+                6,
+                &columns,
+                None,
+            );
+            let mut totals: Vec<(String, i64)> = groups
+                .into_iter()
+                .map(|(mut key, aggs)| {
+                    let total = match aggs[1].finalize() {
+                        AggOutput::Sum(v) => v,
+                        other => panic!("unexpected output {:?}", other),
+                    };
+                    (key.groups_str()[0].clone().unwrap(), total)
+                })
+                .collect();
+            totals.sort();
+            totals
+        })
+        .collect();
+
+    assert_eq!(
+        totals[0],
+        vec![
+            ("DE".to_string(), 70),
+            ("FR".to_string(), 40),
+            ("US".to_string(), 100)
+        ]
+    );
+    assert_eq!(totals[0], totals[1]);
+}
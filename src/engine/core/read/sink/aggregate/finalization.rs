@@ -4,23 +4,22 @@ use crate::engine::core::read::aggregate::ops::{AggOutput, AggregatorImpl};
 use crate::engine::core::read::aggregate::partial::{
     AggPartial, AggState, GroupKey as PartialKey, snapshot_aggregator,
 };
-use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::engine::core::read::aggregate::plan::{AggregateOpSpec, percentile_column};
 use crate::engine::core::{Event, EventId, QueryPlan};
 use crate::engine::types::ScalarValue;
-use ahash::RandomState as AHashRandomState;
+use crate::shared::hash::QueryHashMap;
 use std::collections::{BTreeMap, HashMap};
 
 /// Converts aggregated groups into Events
 pub(crate) fn into_events(
-    groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState>,
+    groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>>,
     specs: Vec<AggregateOpSpec>,
     group_by: Option<Vec<String>>,
     plan: &QueryPlan,
 ) -> Vec<Event> {
     // If no grouping/bucketing, synthesize a single default key
     let groups = if groups.is_empty() {
-        let mut m: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-            HashMap::with_hasher(AHashRandomState::new());
+        let mut m: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
         let key = GroupKey {
             prehash: 0,
             bucket: None,
@@ -45,7 +44,10 @@ pub(crate) fn into_events(
             let groups_str_vec = gk.groups_str();
             for (i, name) in gb.iter().enumerate() {
                 if let Some(val) = groups_str_vec.get(i) {
-                    payload.insert(name.clone(), ScalarValue::Utf8(val.clone()));
+                    payload.insert(
+                        name.clone(),
+                        val.clone().map_or(ScalarValue::Null, ScalarValue::Utf8),
+                    );
                 }
             }
         }
@@ -58,10 +60,34 @@ pub(crate) fn into_events(
                 (AggregateOpSpec::CountField { field }, AggOutput::Count(v)) => {
                     (format!("count_{}", field), ScalarValue::Int64(v as i64))
                 }
-                (AggregateOpSpec::CountUnique { field }, AggOutput::CountUnique(v)) => (
-                    format!("count_unique_{}", field),
+                (AggregateOpSpec::CountUnique { field }, AggOutput::CountUnique(v)) => {
+                    payload.insert(
+                        format!("count_unique_{}_estimated", field),
+                        ScalarValue::Boolean(false),
+                    );
+                    (
+                        format!("count_unique_{}", field),
+                        ScalarValue::Int64(v as i64),
+                    )
+                }
+                (AggregateOpSpec::CountUnique { field }, AggOutput::CountUniqueEstimate(v)) => {
+                    payload.insert(
+                        format!("count_unique_{}_estimated", field),
+                        ScalarValue::Boolean(true),
+                    );
+                    (
+                        format!("count_unique_{}", field),
+                        ScalarValue::Int64(v as i64),
+                    )
+                }
+                (AggregateOpSpec::CountDistinct { field }, AggOutput::CountUniqueEstimate(v)) => (
+                    format!("count_distinct_{}", field),
                     ScalarValue::Int64(v as i64),
                 ),
+                (AggregateOpSpec::Percentile { field, percentile }, AggOutput::Percentile(v)) => (
+                    percentile_column(field, *percentile),
+                    v.map_or(ScalarValue::Null, ScalarValue::Float64),
+                ),
                 (AggregateOpSpec::Total { field }, AggOutput::Sum(v)) => {
                     (format!("total_{}", field), ScalarValue::Int64(v))
                 }
@@ -78,7 +104,12 @@ pub(crate) fn into_events(
                     "metric".to_string(),
                     match other {
                         AggOutput::Count(v) => ScalarValue::Int64(v as i64),
-                        AggOutput::CountUnique(v) => ScalarValue::Int64(v as i64),
+                        AggOutput::CountUnique(v) | AggOutput::CountUniqueEstimate(v) => {
+                            ScalarValue::Int64(v as i64)
+                        }
+                        AggOutput::Percentile(v) => {
+                            v.map_or(ScalarValue::Null, ScalarValue::Float64)
+                        }
                         AggOutput::Sum(v) => ScalarValue::Int64(v),
                         AggOutput::Min(v) => ScalarValue::Utf8(v),
                         AggOutput::Max(v) => ScalarValue::Utf8(v),
@@ -103,7 +134,7 @@ pub(crate) fn into_events(
 
 /// Converts aggregated groups into partial aggregation state
 pub(crate) fn into_partial(
-    groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState>,
+    groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>>,
     specs: Vec<AggregateOpSpec>,
     group_by: Option<Vec<String>>,
     time_bucket: Option<TimeGranularity>,
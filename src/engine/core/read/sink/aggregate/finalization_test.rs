@@ -4,8 +4,8 @@ use crate::command::types::TimeGranularity;
 use crate::engine::core::read::aggregate::ops::AggregatorImpl;
 use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
 use crate::engine::core::{Event, QueryPlan};
+use crate::shared::hash::QueryHashMap;
 use crate::test_helpers::factories::{CommandFactory, QueryPlanFactory, SchemaRegistryFactory};
-use ahash::RandomState as AHashRandomState;
 use serde_json::{Value, json};
 use std::collections::HashMap;
 
@@ -23,7 +23,7 @@ fn make_group_key(prehash: u64, bucket: Option<u64>, groups: Vec<String>) -> Gro
                 }
             })
             .collect(),
-        groups_str: Some(groups),
+        groups_str: Some(groups.into_iter().map(Some).collect()),
     }
 }
 
@@ -62,8 +62,7 @@ fn payload_map(event: &Event) -> serde_json::Map<String, Value> {
 #[tokio::test]
 async fn finalization_into_events_empty_groups_creates_default_event() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let plan = make_plan("test_event", None).await;
 
     let events = into_events(groups, specs, None, &plan);
@@ -77,8 +76,7 @@ async fn finalization_into_events_empty_groups_creates_default_event() {
 #[tokio::test]
 async fn finalization_into_events_single_group_count_all() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let key = make_group_key(1, None, vec![]);
     let mut agg = make_aggregator(&specs[0]);
     // Simulate some updates
@@ -98,8 +96,7 @@ async fn finalization_into_events_single_group_count_all() {
 #[tokio::test]
 async fn finalization_into_events_with_bucket() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let key = make_group_key(1, Some(86400), vec![]);
     let agg = make_aggregator(&specs[0]);
     groups.insert(key, vec![agg]);
@@ -115,8 +112,7 @@ async fn finalization_into_events_with_bucket() {
 #[tokio::test]
 async fn finalization_into_events_with_group_by() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let key = make_group_key(1, None, vec!["US".to_string()]);
     let agg = make_aggregator(&specs[0]);
     groups.insert(key, vec![agg]);
@@ -133,8 +129,7 @@ async fn finalization_into_events_with_group_by() {
 #[tokio::test]
 async fn finalization_into_events_multiple_groups() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
 
     let key1 = make_group_key(1, None, vec!["US".to_string()]);
     let key2 = make_group_key(2, None, vec!["DE".to_string()]);
@@ -180,8 +175,7 @@ async fn finalization_into_events_all_aggregation_types() {
             field: "name".into(),
         },
     ];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let key = make_group_key(1, None, vec![]);
     let aggs: Vec<AggregatorImpl> = specs.iter().map(|s| make_aggregator(s)).collect();
     groups.insert(key, aggs);
@@ -203,8 +197,7 @@ async fn finalization_into_events_all_aggregation_types() {
 #[tokio::test]
 async fn finalization_into_events_with_context_id() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let plan = make_plan("test_event", Some("ctx123")).await;
 
     let events = into_events(groups, specs, None, &plan);
@@ -216,8 +209,7 @@ async fn finalization_into_events_with_context_id() {
 #[tokio::test]
 async fn finalization_into_events_multiple_group_by_fields() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let key = make_group_key(1, None, vec!["US".to_string(), "CA".to_string()]);
     groups.insert(key, vec![make_aggregator(&specs[0])]);
 
@@ -236,8 +228,7 @@ async fn finalization_into_events_multiple_group_by_fields() {
 #[test]
 fn finalization_into_partial_empty_groups() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
 
     let partial = into_partial(groups, specs.clone(), None, None);
 
@@ -250,8 +241,7 @@ fn finalization_into_partial_empty_groups() {
 #[test]
 fn finalization_into_partial_single_group() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let key = make_group_key(1, Some(86400), vec!["US".to_string()]);
     groups.insert(key, vec![make_aggregator(&specs[0])]);
 
@@ -263,14 +253,13 @@ fn finalization_into_partial_single_group() {
 
     let partial_key = partial.groups.keys().next().unwrap();
     assert_eq!(partial_key.bucket, Some(86400));
-    assert_eq!(partial_key.groups, vec!["US".to_string()]);
+    assert_eq!(partial_key.groups, vec![Some("US".to_string())]);
 }
 
 #[test]
 fn finalization_into_partial_multiple_groups() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
 
     let key1 = make_group_key(1, None, vec!["US".to_string()]);
     let key2 = make_group_key(2, None, vec!["DE".to_string()]);
@@ -292,8 +281,7 @@ fn finalization_into_partial_preserves_specs() {
             field: "amount".into(),
         },
     ];
-    let groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
 
     let partial = into_partial(groups, specs.clone(), None, None);
 
@@ -305,8 +293,7 @@ fn finalization_into_partial_preserves_specs() {
 #[test]
 fn finalization_into_partial_converts_group_key_correctly() {
     let specs = vec![AggregateOpSpec::CountAll];
-    let mut groups: HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState> =
-        HashMap::with_hasher(AHashRandomState::new());
+    let mut groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>> = QueryHashMap::default();
     let key = make_group_key(100, Some(172800), vec!["US".to_string(), "NY".to_string()]);
     groups.insert(key, vec![make_aggregator(&specs[0])]);
 
@@ -314,5 +301,8 @@ fn finalization_into_partial_converts_group_key_correctly() {
 
     let partial_key = partial.groups.keys().next().unwrap();
     assert_eq!(partial_key.bucket, Some(172800));
-    assert_eq!(partial_key.groups, vec!["US".to_string(), "NY".to_string()]);
+    assert_eq!(
+        partial_key.groups,
+        vec![Some("US".to_string()), Some("NY".to_string())]
+    );
 }
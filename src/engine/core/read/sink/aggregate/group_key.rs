@@ -1,41 +1,55 @@
 use std::collections::HashMap;
 use std::hash::{BuildHasher, Hash, Hasher};
 
-use ahash::RandomState as AHashRandomState;
-
 use crate::command::types::TimeGranularity;
 use crate::engine::core::Event;
 use crate::engine::core::column::column_values::ColumnValues;
 use crate::engine::types::ScalarValue;
+use crate::shared::hash::QueryHashState;
 
 use super::time_bucketing::bucket_of;
 
-/// Group value that can hold either an integer or a string to avoid string allocations
+/// Group value that can hold either an integer or a string to avoid string allocations.
+/// Rows without a value for a grouping field share the `Null` group.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum GroupValue {
+    Null,
     Int(i64),
     Str(String),
 }
 
 impl GroupValue {
-    /// Convert to string representation (for output)
-    pub fn to_string(&self) -> String {
+    /// Group value of a string; string columns store a missing value as the
+    /// empty string, so it groups with the other missing values.
+    #[inline]
+    fn of_str(s: &str) -> Self {
+        if s.is_empty() {
+            GroupValue::Null
+        } else {
+            GroupValue::Str(s.to_string())
+        }
+    }
+
+    /// Convert to string representation (for output); `None` for the null group
+    pub fn to_string(&self) -> Option<String> {
         match self {
-            GroupValue::Int(i) => i.to_string(),
-            GroupValue::Str(s) => s.clone(),
+            GroupValue::Null => None,
+            GroupValue::Int(i) => Some(i.to_string()),
+            GroupValue::Str(s) => Some(s.clone()),
         }
     }
 }
 
 #[derive(Clone, Debug, Eq)]
 pub struct GroupKey {
-    // Precomputed 64-bit hash to speed up HashMap lookups and reduce per-insert hashing cost
+    // Precomputed 64-bit hash to speed up HashMap lookups and reduce per-insert hashing cost.
+    // Computed with the hash state of the table the key goes into.
     pub(crate) prehash: u64,
     pub(crate) bucket: Option<u64>,
     // Use GroupValue to avoid string allocations for numeric values
     pub(crate) groups: Vec<GroupValue>,
     // String representation for finalization output (lazy - only computed when needed)
-    pub(crate) groups_str: Option<Vec<String>>,
+    pub(crate) groups_str: Option<Vec<Option<String>>>,
 }
 
 impl PartialEq for GroupKey {
@@ -55,6 +69,7 @@ impl Hash for GroupKey {
 impl GroupKey {
     /// Construct a GroupKey from a columnar row using column indices to avoid HashMap lookups
     pub fn from_row_with_indices(
+        hash_state: &QueryHashState,
         bucket: Option<&TimeGranularity>,
         group_by: Option<&[String]>,
         time_field: &str,
@@ -97,20 +112,20 @@ impl GroupKey {
                         // For floats, we still need strings for exact representation
                         GroupValue::Str(f.to_string())
                     } else if let Some(s) = col.get_str_at(row_idx) {
-                        GroupValue::Str(s.to_string())
+                        GroupValue::of_str(s)
                     } else if let Some(b) = col.get_bool_at(row_idx) {
                         GroupValue::Str(b.to_string())
                     } else {
-                        GroupValue::Str(String::new())
+                        GroupValue::Null
                     };
                     groups.push(val);
                 } else {
-                    groups.push(GroupValue::Str(String::new()));
+                    groups.push(GroupValue::Null);
                 }
             }
         }
 
-        let prehash = Self::compute_prehash(bucket_val, &groups);
+        let prehash = Self::compute_prehash(hash_state, bucket_val, &groups);
         Self {
             prehash,
             bucket: bucket_val,
@@ -121,6 +136,7 @@ impl GroupKey {
 
     /// Construct a GroupKey from a row-based Event
     pub fn from_event(
+        hash_state: &QueryHashState,
         bucket: Option<&TimeGranularity>,
         group_by: Option<&[String]>,
         time_field: &str,
@@ -143,17 +159,18 @@ impl GroupKey {
             for name in gb.iter() {
                 // Use get_field_scalar to avoid string allocation when possible
                 let val = match event.get_field_scalar(name) {
-                    Some(ScalarValue::Utf8(s)) => GroupValue::Str(s.clone()),
+                    Some(ScalarValue::Utf8(s)) if s.is_empty() => GroupValue::Null,
+                    Some(ScalarValue::Utf8(s)) => GroupValue::Str(s),
                     Some(ScalarValue::Int64(i)) => GroupValue::Int(i),
                     Some(ScalarValue::Float64(f)) => GroupValue::Str(f.to_string()),
                     Some(ScalarValue::Boolean(b)) => GroupValue::Str(b.to_string()),
                     Some(ScalarValue::Timestamp(ts)) => GroupValue::Int(ts),
-                    _ => GroupValue::Str(String::new()),
+                    _ => GroupValue::Null,
                 };
                 groups.push(val);
             }
         }
-        let prehash = Self::compute_prehash(bucket_val, &groups);
+        let prehash = Self::compute_prehash(hash_state, bucket_val, &groups);
         Self {
             prehash,
             bucket: bucket_val,
@@ -163,8 +180,12 @@ impl GroupKey {
     }
 
     #[inline]
-    fn compute_prehash(bucket_val: Option<u64>, groups: &[GroupValue]) -> u64 {
-        let mut hasher = AHashRandomState::with_seeds(0, 0, 0, 0).build_hasher();
+    fn compute_prehash(
+        hash_state: &QueryHashState,
+        bucket_val: Option<u64>,
+        groups: &[GroupValue],
+    ) -> u64 {
+        let mut hasher = hash_state.build_hasher();
         bucket_val.hash(&mut hasher);
         for g in groups {
             g.hash(&mut hasher);
@@ -176,6 +197,7 @@ impl GroupKey {
     /// This is optimized for the columnar path where we only need the hash initially
     #[inline]
     pub(crate) fn compute_prehash_from_columns(
+        hash_state: &QueryHashState,
         time_bucket: Option<&TimeGranularity>,
         group_by: Option<&[String]>,
         time_field: &str,
@@ -183,9 +205,7 @@ impl GroupKey {
         column_indices: Option<&HashMap<String, usize>>,
         row_idx: usize,
     ) -> u64 {
-        use super::time_bucketing::bucket_of;
-
-        let mut hasher = AHashRandomState::with_seeds(0, 0, 0, 0).build_hasher();
+        let mut hasher = hash_state.build_hasher();
 
         // Hash bucket value if time bucketing is enabled
         let bucket_val: Option<u64> = if let Some(gr) = time_bucket {
@@ -221,13 +241,13 @@ impl GroupKey {
                     } else if let Some(f) = col.get_f64_at(row_idx) {
                         // For floats, hash the bits directly to avoid string allocation
                         f.to_bits().hash(&mut hasher);
-                    } else if let Some(s) = col.get_str_at(row_idx) {
+                    } else if let Some(s) = col.get_str_at(row_idx).filter(|s| !s.is_empty()) {
                         // Hash string directly without cloning
                         s.hash(&mut hasher);
                     } else if let Some(b) = col.get_bool_at(row_idx) {
                         b.hash(&mut hasher);
                     } else {
-                        // Hash empty value
+                        // Hash missing value, including a stored empty string
                         Option::<i64>::None.hash(&mut hasher);
                     }
                 } else {
@@ -240,12 +260,89 @@ impl GroupKey {
         hasher.finish()
     }
 
+    /// Whether the row would build a key equal to this one, checked without
+    /// building it. Rows with equal prehashes may still differ when their hashes collide.
+    pub(crate) fn matches_row(
+        &self,
+        time_bucket: Option<&TimeGranularity>,
+        group_by: Option<&[String]>,
+        time_field: &str,
+        columns: &HashMap<String, ColumnValues>,
+        column_indices: Option<&HashMap<String, usize>>,
+        row_idx: usize,
+    ) -> bool {
+        let bucket_val = time_bucket.and_then(|gr| {
+            columns
+                .get(time_field)
+                .and_then(|ts_col| ts_col.get_i64_at(row_idx))
+                .map(|ts| bucket_of(ts as u64, gr))
+        });
+        if bucket_val != self.bucket {
+            return false;
+        }
+
+        let names = group_by.unwrap_or(&[]);
+        if names.len() != self.groups.len() {
+            return false;
+        }
+        names.iter().zip(&self.groups).all(|(name, value)| {
+            let col = if let Some(indices) = column_indices {
+                indices.get(name).and_then(|_| columns.get(name))
+            } else {
+                columns.get(name)
+            };
+            // Same value precedence as from_row_with_indices
+            match (col, value) {
+                (None, value) => *value == GroupValue::Null,
+                (Some(col), value) => {
+                    if let Some(u) = col.get_u64_at(row_idx) {
+                        *value == GroupValue::Int(u as i64)
+                    } else if let Some(i) = col.get_i64_at(row_idx) {
+                        *value == GroupValue::Int(i)
+                    } else if let Some(f) = col.get_f64_at(row_idx) {
+                        matches!(value, GroupValue::Str(s) if *s == f.to_string())
+                    } else if let Some(s) = col.get_str_at(row_idx) {
+                        match value {
+                            GroupValue::Str(v) => v == s,
+                            GroupValue::Null => s.is_empty(),
+                            GroupValue::Int(_) => false,
+                        }
+                    } else if let Some(b) = col.get_bool_at(row_idx) {
+                        matches!(value, GroupValue::Str(v) if *v == b.to_string())
+                    } else {
+                        *value == GroupValue::Null
+                    }
+                }
+            }
+        })
+    }
+
     /// Get groups_str, computing it lazily if not already computed
     /// This avoids allocating strings during aggregation - they're only created when finalizing
-    pub(crate) fn groups_str(&mut self) -> &Vec<String> {
+    /// The null group has no string: `None`
+    pub(crate) fn groups_str(&mut self) -> &Vec<Option<String>> {
         if self.groups_str.is_none() {
             self.groups_str = Some(self.groups.iter().map(|g| g.to_string()).collect());
         }
         self.groups_str.as_ref().unwrap()
     }
+
+    /// Approximate heap memory held by the key's values.
+    pub(crate) fn approx_heap_bytes(&self) -> usize {
+        let values: usize = self
+            .groups
+            .iter()
+            .map(|value| match value {
+                GroupValue::Str(s) => s.len(),
+                GroupValue::Int(_) | GroupValue::Null => 0,
+            })
+            .sum();
+        let strings: usize = self
+            .groups_str
+            .iter()
+            .flatten()
+            .map(|s| std::mem::size_of::<Option<String>>() + s.as_ref().map_or(0, String::len))
+            .sum();
+        self.groups.capacity() * std::mem::size_of::<GroupValue>() + values + strings
+    }
 }
@@ -1,40 +1,180 @@
 use super::group_key::GroupKey;
-use std::collections::HashMap;
+use crate::shared::config::CONFIG;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-/// Cache for group keys to avoid recomputing identical keys
-/// Uses a simple HashMap with size-based eviction
-pub(crate) struct GroupKeyCache {
-    cache: HashMap<u64, GroupKey>, // keyed by prehash
-    max_size: usize,
+/// Keys each cache holds when `query.group_key_cache_max_entries` is not set.
+pub const DEFAULT_GROUP_KEY_CACHE_MAX_ENTRIES: usize = 1000;
+
+/// Lookups between two publishes of a cache's counters to the process-wide ones.
+const PUBLISH_EVERY: u64 = 4096;
+
+/// Counters summed over every group key cache in the process, reported by SHOW STATS.
+static GLOBAL_COUNTERS: CacheCounters = CacheCounters::new();
+
+/// Cache for group keys to avoid rebuilding identical keys row after row.
+/// Bounded: once full, the least recently used key is evicted. A miss only
+/// means building the key again, so eviction never changes results.
+pub struct GroupKeyCache {
+    // None when the capacity is 0: never cache, always rebuild
+    cache: Option<LruCache<u64, GroupKey>>, // keyed by row hash
+    // Counted locally and published in chunks to keep atomics off the row path
+    local: GroupKeyCacheStats,
+    published: GroupKeyCacheStats,
 }
 
 impl GroupKeyCache {
     pub(crate) fn new(max_size: usize) -> Self {
         Self {
-            cache: HashMap::with_capacity(max_size),
-            max_size,
+            cache: NonZeroUsize::new(max_size).map(LruCache::new),
+            local: GroupKeyCacheStats::default(),
+            published: GroupKeyCacheStats::default(),
         }
     }
 
+    /// Sized by `query.group_key_cache_max_entries`.
+    pub(crate) fn from_config() -> Self {
+        Self::new(Self::configured_max_entries())
+    }
+
+    /// Capacity of each cache, from `query.group_key_cache_max_entries`.
+    pub fn configured_max_entries() -> usize {
+        CONFIG
+            .query
+            .as_ref()
+            .and_then(|q| q.group_key_cache_max_entries)
+            .unwrap_or(DEFAULT_GROUP_KEY_CACHE_MAX_ENTRIES)
+    }
+
+    #[cfg(test)]
     pub(crate) fn get_or_insert<F>(&mut self, prehash: u64, f: F) -> GroupKey
     where
         F: FnOnce() -> GroupKey,
     {
-        // If max_size is 0, never cache - always recompute
-        if self.max_size == 0 {
+        self.get_or_insert_matching(prehash, |_| true, f)
+    }
+
+    /// Cached key for `hash` if `matches` accepts it, else the key built by `f`,
+    /// which then replaces it. `matches` guards against hash collisions.
+    pub(crate) fn get_or_insert_matching<M, F>(&mut self, hash: u64, matches: M, f: F) -> GroupKey
+    where
+        M: FnOnce(&GroupKey) -> bool,
+        F: FnOnce() -> GroupKey,
+    {
+        let Some(cache) = &mut self.cache else {
             return f();
+        };
+
+        let key = match cache.get(&hash).filter(|key| matches(key)) {
+            Some(key) => {
+                self.local.hits += 1;
+                key.clone()
+            }
+            None => {
+                self.local.misses += 1;
+                let key = f();
+                // push hands back the displaced entry: the old key under this hash or the LRU one
+                if let Some((old_hash, _)) = cache.push(hash, key.clone())
+                    && old_hash != hash
+                {
+                    self.local.evictions += 1;
+                }
+                key
+            }
+        };
+        self.local.entries = cache.len() as u64;
+
+        if self.local.hits + self.local.misses - self.published.hits - self.published.misses
+            >= PUBLISH_EVERY
+        {
+            self.publish();
         }
+        key
+    }
+
+    /// Counters for this cache only.
+    pub fn stats(&self) -> GroupKeyCacheStats {
+        self.local
+    }
+
+    /// Counters summed over every cache in the process; entries counts the keys
+    /// held by caches still alive.
+    pub fn global_stats() -> GroupKeyCacheStats {
+        GLOBAL_COUNTERS.snapshot()
+    }
 
-        if let Some(key) = self.cache.get(&prehash) {
-            key.clone()
+    fn publish(&mut self) {
+        GLOBAL_COUNTERS.add(&self.local, &self.published);
+        self.published = self.local;
+    }
+}
+
+impl Drop for GroupKeyCache {
+    fn drop(&mut self) {
+        // The keys go away with the cache
+        self.local.entries = 0;
+        self.publish();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GroupKeyCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: u64,
+}
+
+impl GroupKeyCacheStats {
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
         } else {
-            let key = f();
-            // Simple eviction: if cache is full, clear it
-            if self.cache.len() >= self.max_size {
-                self.cache.clear();
-            }
-            self.cache.insert(prehash, key.clone());
-            key
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Relaxed atomics: the counters are statistics and order nothing else.
+#[derive(Debug)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    entries: AtomicU64,
+}
+
+impl CacheCounters {
+    const fn new() -> Self {
+        Self {
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            entries: AtomicU64::new(0),
+        }
+    }
+
+    /// Adds what changed between two snapshots of one cache's counters.
+    fn add(&self, now: &GroupKeyCacheStats, before: &GroupKeyCacheStats) {
+        self.hits
+            .fetch_add(now.hits - before.hits, Ordering::Relaxed);
+        self.misses
+            .fetch_add(now.misses - before.misses, Ordering::Relaxed);
+        self.evictions
+            .fetch_add(now.evictions - before.evictions, Ordering::Relaxed);
+        // entries is a gauge and may shrink
+        self.entries
+            .fetch_add(now.entries.wrapping_sub(before.entries), Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> GroupKeyCacheStats {
+        GroupKeyCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: self.entries.load(Ordering::Relaxed),
         }
     }
 }
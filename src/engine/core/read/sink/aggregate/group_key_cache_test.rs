@@ -15,7 +15,7 @@ fn make_group_key(prehash: u64, bucket: Option<u64>, groups: Vec<String>) -> Gro
                 }
             })
             .collect(),
-        groups_str: Some(groups),
+        groups_str: Some(groups.into_iter().map(Some).collect()),
     }
 }
 
@@ -42,7 +42,7 @@ fn group_key_cache_get_or_insert_caches_key() {
     });
     assert_eq!(compute_count, 1);
     let mut result1_mut = result1;
-    assert_eq!(result1_mut.groups_str(), &vec!["US".to_string()]);
+    assert_eq!(result1_mut.groups_str(), &vec![Some("US".to_string())]);
 
     // Second call with same prehash should return cached value
     let result2 = cache.get_or_insert(100, || {
@@ -51,7 +51,7 @@ fn group_key_cache_get_or_insert_caches_key() {
     });
     assert_eq!(compute_count, 1); // Should not recompute
     let mut result2_mut = result2;
-    assert_eq!(result2_mut.groups_str(), &vec!["US".to_string()]); // Should return cached value
+    assert_eq!(result2_mut.groups_str(), &vec![Some("US".to_string())]); // Should return cached value
 }
 
 #[test]
@@ -65,8 +65,8 @@ fn group_key_cache_different_prehashes_are_cached_separately() {
 
     let mut result1_mut = result1;
     let mut result2_mut = result2.clone();
-    assert_eq!(result1_mut.groups_str(), &vec!["US".to_string()]);
-    assert_eq!(result2_mut.groups_str(), &vec!["DE".to_string()]);
+    assert_eq!(result1_mut.groups_str(), &vec![Some("US".to_string())]);
+    assert_eq!(result2_mut.groups_str(), &vec![Some("DE".to_string())]);
     assert_eq!(result2.bucket, Some(1000));
 }
 
@@ -81,7 +81,7 @@ fn group_key_cache_evicts_when_full() {
     cache.get_or_insert(100, || key1.clone());
     cache.get_or_insert(200, || key2.clone());
 
-    // This should trigger eviction and clear the cache
+    // This should evict the least recently used key (100)
     let mut compute_count = 0;
     cache.get_or_insert(300, || {
         compute_count += 1;
@@ -89,13 +89,13 @@ fn group_key_cache_evicts_when_full() {
     });
     assert_eq!(compute_count, 1);
 
-    // After eviction, previous keys should not be cached
+    // After eviction, the evicted key is no longer cached
     let mut compute_count2 = 0;
     cache.get_or_insert(100, || {
         compute_count2 += 1;
         make_group_key(100, None, vec!["US_NEW".to_string()])
     });
-    assert_eq!(compute_count2, 1); // Should recompute since cache was cleared
+    assert_eq!(compute_count2, 1); // Should recompute since 100 was evicted
 }
 
 #[test]
@@ -153,6 +153,65 @@ fn group_key_cache_large_capacity() {
         });
         assert_eq!(compute_count, 0, "Key {} should be cached", i);
         let mut result_mut = result;
-        assert_eq!(result_mut.groups_str()[0], format!("key_{}", i));
+        assert_eq!(result_mut.groups_str()[0], Some(format!("key_{}", i)));
     }
 }
+
+#[test]
+fn group_key_cache_evicts_the_least_recently_used_key() {
+    let mut cache = GroupKeyCache::new(2);
+    let key = |prehash: u64, value: &str| make_group_key(prehash, None, vec![value.to_string()]);
+
+    cache.get_or_insert(100, || key(100, "US"));
+    cache.get_or_insert(200, || key(200, "DE"));
+    // Touch 100 so 200 becomes the least recently used
+    cache.get_or_insert(100, || panic!("100 should be cached"));
+    cache.get_or_insert(300, || key(300, "FR"));
+
+    cache.get_or_insert(100, || panic!("100 should still be cached"));
+    let mut rebuilt = false;
+    cache.get_or_insert(200, || {
+        rebuilt = true;
+        key(200, "DE")
+    });
+    assert!(rebuilt);
+
+    let stats = cache.stats();
+    assert_eq!(stats.hits, 2);
+    assert_eq!(stats.misses, 4);
+    assert_eq!(stats.evictions, 2);
+    assert_eq!(stats.entries, 2);
+}
+
+#[test]
+fn group_key_cache_rebuilds_keys_that_do_not_match() {
+    let mut cache = GroupKeyCache::new(10);
+    let us = make_group_key(7, None, vec!["US".to_string()]);
+    let de = make_group_key(7, None, vec!["DE".to_string()]);
+
+    cache.get_or_insert(7, || us.clone());
+    // Same hash, different row: the cached key is rejected and replaced
+    let got = cache.get_or_insert_matching(7, |k| *k == de, || de.clone());
+    assert_eq!(got, de);
+    let got = cache.get_or_insert_matching(7, |k| *k == de, || panic!("DE should be cached"));
+    assert_eq!(got, de);
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 2, 0));
+    assert_eq!(stats.entries, 1);
+}
+
+#[test]
+fn group_key_cache_publishes_counters_when_dropped() {
+    let before = GroupKeyCache::global_stats();
+    {
+        let mut cache = GroupKeyCache::new(4);
+        let key = make_group_key(1, None, vec!["US".to_string()]);
+        cache.get_or_insert(1, || key.clone());
+        cache.get_or_insert(1, || key.clone());
+    }
+    // Other tests run caches concurrently, so only a lower bound holds
+    let after = GroupKeyCache::global_stats();
+    assert!(after.hits > before.hits);
+    assert!(after.misses > before.misses);
+}
@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use crate::command::types::TimeGranularity;
 use crate::engine::core::column::column_values::ColumnValues;
-use crate::engine::core::read::sink::aggregate::group_key::GroupKey;
+use crate::engine::core::read::sink::aggregate::group_key::{GroupKey, GroupValue};
+use crate::shared::hash::QueryHashState;
 use crate::test_helpers::factories::{DecompressedBlockFactory, EventFactory};
 
 fn make_columns(field_rows: &[(&str, Vec<&str>)]) -> HashMap<String, ColumnValues> {
@@ -23,6 +24,7 @@ fn group_key_from_row_with_bucket_and_groups() {
         ("plan", vec!["pro"]),
     ]);
     let key = GroupKey::from_row_with_indices(
+        &QueryHashState::default(),
         Some(&TimeGranularity::Month),
         Some(&["country".to_string(), "plan".to_string()][..]),
         "timestamp",
@@ -33,14 +35,15 @@ fn group_key_from_row_with_bucket_and_groups() {
     assert!(key.bucket.is_some());
     let mut key_mut = key;
     let groups_str = key_mut.groups_str();
-    let groups: Vec<&str> = groups_str.iter().map(|s| s.as_str()).collect();
-    assert_eq!(groups, vec!["US", "pro"]);
+    let groups: Vec<Option<&str>> = groups_str.iter().map(|s| s.as_deref()).collect();
+    assert_eq!(groups, vec![Some("US"), Some("pro")]);
 }
 
 #[test]
-fn group_key_from_row_missing_group_field_uses_empty_string() {
+fn group_key_from_row_missing_group_field_is_null() {
     let cols = make_columns(&[("country", vec!["US"])]);
     let key = GroupKey::from_row_with_indices(
+        &QueryHashState::default(),
         None,
         Some(&["country".to_string(), "plan".to_string()][..]),
         "timestamp",
@@ -50,9 +53,10 @@ fn group_key_from_row_missing_group_field_uses_empty_string() {
     );
     assert_eq!(key.bucket, None);
     let mut key_mut = key;
+    assert_eq!(key_mut.groups[1], GroupValue::Null);
     let groups_str = key_mut.groups_str();
-    let groups: Vec<&str> = groups_str.iter().map(|s| s.as_str()).collect();
-    assert_eq!(groups, vec!["US", ""]);
+    let groups: Vec<Option<&str>> = groups_str.iter().map(|s| s.as_deref()).collect();
+    assert_eq!(groups, vec![Some("US"), None]);
 }
 
 #[test]
@@ -63,12 +67,110 @@ fn group_key_from_event_with_custom_time_field() {
         .with("payload", json!({"country":"US","created_at": 86_450}))
         .create();
     let key = GroupKey::from_event(
+        &QueryHashState::default(),
         Some(&TimeGranularity::Day),
         Some(&["country".to_string()][..]),
         "created_at",
         &e,
     );
     let mut key_mut = key;
-    assert_eq!(key_mut.groups_str(), &vec!["US".to_string()]);
+    assert_eq!(key_mut.groups_str(), &vec![Some("US".to_string())]);
     assert_eq!(key_mut.bucket, Some(86_400));
 }
+
+#[test]
+fn group_key_matches_only_rows_with_equal_values() {
+    let hash_state = QueryHashState::default();
+    let group_by = ["country".to_string(), "plan".to_string()];
+    let cols = make_columns(&[
+        ("timestamp", vec!["86450", "86460", "172900"]),
+        ("country", vec!["US", "US", "US"]),
+        ("plan", vec!["pro", "free", "pro"]),
+    ]);
+    let key = GroupKey::from_row_with_indices(
+        &hash_state,
+        Some(&TimeGranularity::Day),
+        Some(&group_by[..]),
+        "timestamp",
+        &cols,
+        None,
+        0,
+    );
+
+    let matches = |row_idx| {
+        key.matches_row(
+            Some(&TimeGranularity::Day),
+            Some(&group_by[..]),
+            "timestamp",
+            &cols,
+            None,
+            row_idx,
+        )
+    };
+    assert!(matches(0));
+    // Other plan, same day
+    assert!(!matches(1));
+    // Same values, next day
+    assert!(!matches(2));
+
+    // Missing columns compare as null, like from_row_with_indices builds them
+    let missing = ["region".to_string()];
+    let empty_key = GroupKey::from_row_with_indices(
+        &hash_state,
+        None,
+        Some(&missing[..]),
+        "timestamp",
+        &cols,
+        None,
+        0,
+    );
+    assert!(empty_key.matches_row(None, Some(&missing[..]), "timestamp", &cols, None, 1));
+}
+
+#[test]
+fn group_key_null_in_any_field_is_its_own_group() {
+    use serde_json::json;
+    let hash_state = QueryHashState::default();
+    let group_by = ["country".to_string(), "plan".to_string()];
+    // Missing strings are stored as empty strings
+    let cols = make_columns(&[
+        ("country", vec!["US", "", "US", ""]),
+        ("plan", vec!["pro", "pro", "", "pro"]),
+    ]);
+    let keys: Vec<GroupKey> = (0..4)
+        .map(|row_idx| {
+            GroupKey::from_row_with_indices(
+                &hash_state,
+                None,
+                Some(&group_by[..]),
+                "timestamp",
+                &cols,
+                None,
+                row_idx,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        keys[1].groups,
+        vec![GroupValue::Null, GroupValue::Str("pro".into())]
+    );
+    assert_eq!(
+        keys[2].groups,
+        vec![GroupValue::Str("US".into()), GroupValue::Null]
+    );
+    assert_ne!(keys[0], keys[1]);
+    assert_ne!(keys[0], keys[2]);
+    assert_ne!(keys[1], keys[2]);
+    assert_eq!(keys[1], keys[3]);
+    assert!(keys[1].matches_row(None, Some(&group_by[..]), "timestamp", &cols, None, 3));
+    assert!(!keys[1].matches_row(None, Some(&group_by[..]), "timestamp", &cols, None, 0));
+
+    // Events without the field land in the same null group
+    let event = EventFactory::new()
+        .with("payload", json!({"plan": "pro"}))
+        .create();
+    let from_event =
+        GroupKey::from_event(&hash_state, None, Some(&group_by[..]), "timestamp", &event);
+    assert_eq!(from_event, keys[1]);
+}
@@ -6,7 +6,9 @@ mod schema;
 mod sink;
 mod time_bucketing;
 
+pub use group_key_cache::{GroupKeyCache, GroupKeyCacheStats};
 pub use sink::AggregateSink;
+pub(crate) use time_bucketing::bucket_of;
 
 #[cfg(test)]
 mod columnar_test;
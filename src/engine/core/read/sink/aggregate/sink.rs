@@ -1,5 +1,5 @@
 use super::super::ResultSink;
-use super::columnar::ColumnarProcessor;
+use super::columnar::{ColumnarProcessor, GroupingContext};
 use super::finalization::{into_events, into_partial};
 use super::group_key::GroupKey;
 use super::group_key_cache::GroupKeyCache;
@@ -10,7 +10,8 @@ use crate::engine::core::read::aggregate::ops::AggregatorImpl;
 use crate::engine::core::read::aggregate::partial::AggPartial;
 use crate::engine::core::read::aggregate::plan::{AggregateOpSpec, AggregatePlan};
 use crate::engine::core::{Event, EventId, QueryPlan};
-use ahash::RandomState as AHashRandomState;
+use crate::shared::config::QueryHasher;
+use crate::shared::hash::{QueryHashMap, QueryHashSet, QueryHashState};
 use std::collections::HashMap;
 
 pub struct AggregateSink {
@@ -18,38 +19,44 @@ pub struct AggregateSink {
     pub(crate) group_by: Option<Vec<String>>,
     pub(crate) time_bucket: Option<TimeGranularity>,
     pub(crate) time_field: String,
-    groups: std::collections::HashMap<GroupKey, Vec<AggregatorImpl>, AHashRandomState>,
+    // Hashes group keys, their prehashes and event ids alike
+    hash_state: QueryHashState,
+    groups: QueryHashMap<GroupKey, Vec<AggregatorImpl>>,
     group_limit: Option<usize>,
-    seen_event_ids: std::collections::HashSet<EventId, AHashRandomState>,
+    seen_event_ids: QueryHashSet<EventId>,
     group_key_cache: Option<GroupKeyCache>,
     schema_cache: SchemaCache,
 }
 
 impl AggregateSink {
     pub fn new(specs: Vec<AggregateOpSpec>) -> Self {
+        let hash_state = QueryHashState::default();
         Self {
             specs,
             group_by: None,
             time_bucket: None,
             time_field: "timestamp".to_string(),
-            groups: std::collections::HashMap::with_hasher(AHashRandomState::new()),
+            groups: QueryHashMap::with_hasher(hash_state.clone()),
             group_limit: None,
-            seen_event_ids: std::collections::HashSet::with_hasher(AHashRandomState::new()),
-            group_key_cache: Some(GroupKeyCache::new(1000)),
+            seen_event_ids: QueryHashSet::with_hasher(hash_state.clone()),
+            hash_state,
+            group_key_cache: Some(GroupKeyCache::from_config()),
             schema_cache: SchemaCache::new(),
         }
     }
 
     pub fn from_plan(plan: &AggregatePlan) -> Self {
+        let hash_state = QueryHashState::default();
         Self {
             specs: plan.ops.clone(),
             group_by: plan.group_by.clone(),
             time_bucket: plan.time_bucket.clone(),
             time_field: "timestamp".to_string(),
-            groups: std::collections::HashMap::with_hasher(AHashRandomState::new()),
+            groups: QueryHashMap::with_hasher(hash_state.clone()),
             group_limit: None,
-            seen_event_ids: std::collections::HashSet::with_hasher(AHashRandomState::new()),
-            group_key_cache: Some(GroupKeyCache::new(1000)),
+            seen_event_ids: QueryHashSet::with_hasher(hash_state.clone()),
+            hash_state,
+            group_key_cache: Some(GroupKeyCache::from_config()),
             schema_cache: SchemaCache::new(),
         }
     }
@@ -62,15 +69,17 @@ impl AggregateSink {
                 .unwrap_or_else(|| "timestamp".to_string()),
             _ => "timestamp".to_string(),
         };
+        let hash_state = QueryHashState::default();
         Self {
             specs: agg.ops.clone(),
             group_by: agg.group_by.clone(),
             time_bucket: agg.time_bucket.clone(),
             time_field,
-            groups: std::collections::HashMap::with_hasher(AHashRandomState::new()),
+            groups: QueryHashMap::with_hasher(hash_state.clone()),
             group_limit: None,
-            seen_event_ids: std::collections::HashSet::with_hasher(AHashRandomState::new()),
-            group_key_cache: Some(GroupKeyCache::new(1000)),
+            seen_event_ids: QueryHashSet::with_hasher(hash_state.clone()),
+            hash_state,
+            group_key_cache: Some(GroupKeyCache::from_config()),
             schema_cache: SchemaCache::new(),
         }
     }
@@ -80,6 +89,22 @@ impl AggregateSink {
         self.schema_cache.initialize_column_indices(column_names);
     }
 
+    /// Hash groups and event ids with `hasher` instead of `query.hasher`.
+    /// Must be set before the first row arrives.
+    pub fn with_hasher(mut self, hasher: QueryHasher) -> Self {
+        self.hash_state = QueryHashState::new(hasher);
+        self.groups = QueryHashMap::with_hasher(self.hash_state.clone());
+        self.seen_event_ids = QueryHashSet::with_hasher(self.hash_state.clone());
+        self
+    }
+
+    /// Cache at most `max_entries` group keys instead of
+    /// `query.group_key_cache_max_entries`; 0 disables the cache.
+    pub fn with_group_key_cache(mut self, max_entries: usize) -> Self {
+        self.group_key_cache = Some(GroupKeyCache::new(max_entries));
+        self
+    }
+
     /// Limit the number of distinct groups produced by this sink
     pub fn with_group_limit(mut self, limit: Option<usize>) -> Self {
         self.group_limit = limit;
@@ -124,6 +149,22 @@ impl AggregateSink {
         self.groups.len()
     }
 
+    /// Approximate memory held by the groups and the event id dedup set.
+    /// Walks every group, so callers should sample it rather than call it per batch.
+    pub fn approx_memory_bytes(&self) -> usize {
+        let slot = std::mem::size_of::<GroupKey>() + std::mem::size_of::<Vec<AggregatorImpl>>();
+        let groups: usize = self
+            .groups
+            .iter()
+            .map(|(key, aggs)| {
+                key.approx_heap_bytes()
+                    + aggs.iter().map(AggregatorImpl::approx_bytes).sum::<usize>()
+            })
+            .sum();
+        let seen_ids = self.seen_event_ids.capacity() * (std::mem::size_of::<EventId>() + 1);
+        self.groups.capacity() * (slot + 1) + groups + seen_ids
+    }
+
     fn has_grouping(&self) -> bool {
         self.group_by.is_some() || self.time_bucket.is_some()
     }
@@ -133,9 +174,45 @@ impl AggregateSink {
         columns: &HashMap<String, ColumnValues>,
         row_idx: usize,
     ) -> GroupKey {
-        if let Some(cache) = &mut self.group_key_cache {
-            let compute_key = || {
+        let Some(cache) = &mut self.group_key_cache else {
+            return GroupKey::from_row_with_indices(
+                &self.hash_state,
+                self.time_bucket.as_ref(),
+                self.group_by.as_deref(),
+                &self.time_field,
+                columns,
+                self.schema_cache.column_indices(),
+                row_idx,
+            );
+        };
+
+        // Hashing the row allocates nothing; the key is only built on a miss. A
+        // cached key is checked against the row, so a colliding or evicted key
+        // just means building it again.
+        let row_hash = GroupKey::compute_prehash_from_columns(
+            &self.hash_state,
+            self.time_bucket.as_ref(),
+            self.group_by.as_deref(),
+            &self.time_field,
+            columns,
+            self.schema_cache.column_indices(),
+            row_idx,
+        );
+        cache.get_or_insert_matching(
+            row_hash,
+            |key| {
+                key.matches_row(
+                    self.time_bucket.as_ref(),
+                    self.group_by.as_deref(),
+                    &self.time_field,
+                    columns,
+                    self.schema_cache.column_indices(),
+                    row_idx,
+                )
+            },
+            || {
                 GroupKey::from_row_with_indices(
+                    &self.hash_state,
                     self.time_bucket.as_ref(),
                     self.group_by.as_deref(),
                     &self.time_field,
@@ -143,19 +220,8 @@ impl AggregateSink {
                     self.schema_cache.column_indices(),
                     row_idx,
                 )
-            };
-            let temp_key = compute_key();
-            cache.get_or_insert(temp_key.prehash, || temp_key)
-        } else {
-            GroupKey::from_row_with_indices(
-                self.time_bucket.as_ref(),
-                self.group_by.as_deref(),
-                &self.time_field,
-                columns,
-                self.schema_cache.column_indices(),
-                row_idx,
-            )
-        }
+            },
+        )
     }
 
     fn ensure_group_entry(&mut self, key: GroupKey) -> Option<&mut Vec<AggregatorImpl>> {
@@ -196,6 +262,7 @@ impl ResultSink for AggregateSink {
             return;
         }
         let key = GroupKey::from_event(
+            &self.hash_state,
             self.time_bucket.as_ref(),
             self.group_by.as_deref(),
             &self.time_field,
@@ -231,13 +298,17 @@ impl AggregateSink {
 
         if can_use_columnar {
             if self.has_grouping() {
+                let grouping = GroupingContext {
+                    hash_state: &self.hash_state,
+                    time_bucket: self.time_bucket.as_ref(),
+                    group_by: self.group_by.as_deref(),
+                    time_field: &self.time_field,
+                    column_indices: self.schema_cache.column_indices(),
+                };
                 ColumnarProcessor::process_columnar_slice_with_grouping(
                     &mut self.groups,
                     &self.specs,
-                    self.time_bucket.as_ref(),
-                    self.group_by.as_deref(),
-                    &self.time_field,
-                    self.schema_cache.column_indices(),
+                    &grouping,
                     start,
                     end,
                     columns,
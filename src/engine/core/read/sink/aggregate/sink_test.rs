@@ -5,6 +5,7 @@ use crate::engine::core::read::sink::AggregateSink as ReExportedAggregateSink;
 use crate::engine::core::read::sink::ResultSink;
 use crate::engine::core::read::sink::aggregate::sink::AggregateSink;
 use crate::engine::core::{Event, QueryPlan};
+use crate::shared::config::QueryHasher;
 use crate::test_helpers::factories::{
     CommandFactory, DecompressedBlockFactory, EventFactory, QueryPlanFactory, SchemaRegistryFactory,
 };
@@ -220,7 +221,7 @@ async fn aggregate_sink_row_month_bucket_using_created_at() {
 }
 
 #[tokio::test]
-async fn aggregate_sink_row_missing_groupby_field_emits_empty_string() {
+async fn aggregate_sink_row_missing_groupby_field_emits_null() {
     let specs = vec![AggregateOpSpec::CountAll];
     let plan_spec = AggregatePlan {
         ops: specs.clone(),
@@ -235,7 +236,7 @@ async fn aggregate_sink_row_missing_groupby_field_emits_empty_string() {
     assert_eq!(events.len(), 1);
     let p = payload_map(&events[0]);
     assert_eq!(p["country"], json!("US"));
-    assert_eq!(p["plan"], json!(""));
+    assert_eq!(p["plan"], Value::Null);
 }
 
 #[test]
@@ -632,3 +633,96 @@ async fn aggregate_sink_deduplicates_rows_by_event_id_column() {
     let payload = payload_map(&events[0]);
     assert_eq!(payload["count"], json!(1));
 }
+
+#[tokio::test]
+async fn aggregate_sink_results_do_not_depend_on_the_hasher() {
+    let plan_spec = AggregatePlan {
+        ops: vec![
+            AggregateOpSpec::CountAll,
+            AggregateOpSpec::CountUnique {
+                field: "user".into(),
+            },
+            AggregateOpSpec::Total {
+                field: "amount".into(),
+            },
+        ],
+        group_by: Some(vec!["country".into()]),
+        time_bucket: None,
+    };
+    let columns = make_columns(&[
+        ("event_id", vec!["1", "2", "3", "4", "5", "6"]),
+        ("country", vec!["US", "DE", "US", "FR", "DE", "US"]),
+        ("user", vec!["a", "b", "a", "c", "d", "e"]),
+        ("amount", vec!["10", "20", "30", "40", "50", "60"]),
+    ]);
+    let plan = make_plan("evt_hasher", None).await;
+
+    let mut results = Vec::new();
+    for hasher in [QueryHasher::Ahash, QueryHasher::Sip] {
+        let mut sink = AggregateSink::from_plan(&plan_spec).with_hasher(hasher);
+        for row_idx in 0..6 {
+            sink.on_row(row_idx, &columns);
+        }
+        // Already counted
+        sink.on_row(2, &columns);
+
+        let mut rows: Vec<_> = sink.into_events(&plan).iter().map(payload_map).collect();
+        rows.sort_by_key(|p| p["country"].as_str().unwrap().to_string());
+        results.push(rows);
+    }
+
+    assert_eq!(results[0], results[1]);
+    let us = &results[0][2];
+    assert_eq!(us["country"], json!("US"));
+    assert_eq!(us["count"], json!(3));
+    assert_eq!(us["count_unique_user"], json!(2));
+    assert_eq!(us["total_amount"], json!(100));
+}
+
+#[tokio::test]
+async fn aggregate_sink_results_do_not_depend_on_the_group_key_cache_size() {
+    let plan_spec = AggregatePlan {
+        ops: vec![
+            AggregateOpSpec::CountAll,
+            AggregateOpSpec::Total {
+                field: "amount".into(),
+            },
+        ],
+        group_by: Some(vec!["country".into(), "plan".into()]),
+        time_bucket: None,
+    };
+    let countries = ["US", "DE", "FR", "NL", "US", "DE", "FR", "NL", "US", "US"];
+    let plans = ["a", "b", "a", "b", "b", "b", "a", "a", "a", "a"];
+    let ids: Vec<String> = (1..=10).map(|i| i.to_string()).collect();
+    let amounts: Vec<String> = (1..=10).map(|i| (i * 10).to_string()).collect();
+    let columns = make_columns(&[
+        ("event_id", ids.iter().map(String::as_str).collect()),
+        ("country", countries.to_vec()),
+        ("plan", plans.to_vec()),
+        ("amount", amounts.iter().map(String::as_str).collect()),
+    ]);
+    let plan = make_plan("evt_group_key_cache", None).await;
+
+    // Disabled, smaller than the group count (evicting all the time), and roomy
+    let mut results = Vec::new();
+    for max_entries in [0, 1, 3, 1000] {
+        let mut sink = AggregateSink::from_plan(&plan_spec).with_group_key_cache(max_entries);
+        for row_idx in 0..10 {
+            sink.on_row(row_idx, &columns);
+        }
+        let mut rows: Vec<_> = sink.into_events(&plan).iter().map(payload_map).collect();
+        rows.sort_by_key(|p| format!("{}/{}", p["country"], p["plan"]));
+        results.push(rows);
+    }
+
+    for rows in &results[1..] {
+        assert_eq!(*rows, results[0]);
+    }
+    assert_eq!(results[0].len(), 6);
+    let us_a = results[0]
+        .iter()
+        .find(|p| p["country"] == json!("US") && p["plan"] == json!("a"))
+        .unwrap();
+    assert_eq!(us_a["count"], json!(3));
+    assert_eq!(us_a["total_amount"], json!(10 + 90 + 100));
+}
@@ -1,7 +1,7 @@
 use crate::command::types::TimeGranularity;
 use crate::shared::datetime::time::TimeConfig;
 use crate::shared::datetime::time_bucketing::{
-    CalendarTimeBucketer, naive_bucket_of as naive_bucket,
+    CalendarTimeBucketer, calendar_bucket_of, interval_bucket_of, naive_bucket_of as naive_bucket,
 };
 use std::sync::OnceLock;
 
@@ -10,6 +10,17 @@ use std::sync::OnceLock;
 static CALENDAR_BUCKETER_CACHE: OnceLock<(TimeConfig, CalendarTimeBucketer)> = OnceLock::new();
 
 pub fn bucket_of(ts: u64, gran: &TimeGranularity) -> u64 {
+    // Fixed-width intervals and explicit calendar units ignore the configured mode
+    match gran {
+        TimeGranularity::Interval {
+            seconds, origin, ..
+        } => return interval_bucket_of(ts, *seconds, *origin),
+        TimeGranularity::Calendar { unit, timezone } => {
+            return calendar_bucket_of(ts, *unit, timezone.as_deref());
+        }
+        _ => {}
+    }
+
     // Cache the config check as well to avoid repeated calls
     static USE_CALENDAR: OnceLock<bool> = OnceLock::new();
     let use_calendar =
@@ -67,6 +67,10 @@ impl ResultSink for EventSink {
                         builder.add_field_null(field);
                     }
                 }
+                Some(PhysicalType::Uuid) => match values.get_str_at(row_idx) {
+                    Some(val) => builder.add_field(field, val),
+                    None => builder.add_field_null(field),
+                },
                 Some(PhysicalType::VarBytes) | None => {
                     // VarBytes or untyped: check all types (legacy path)
                     if let Some(n) = values.get_i64_at(row_idx) {
@@ -2,7 +2,8 @@ mod aggregate;
 mod event_sink;
 mod result_sink;
 
-pub use aggregate::AggregateSink;
+pub use aggregate::{AggregateSink, GroupKeyCache, GroupKeyCacheStats};
+pub(crate) use aggregate::bucket_of;
 pub use event_sink::EventSink;
 pub use result_sink::ResultSink;
 
@@ -0,0 +1,90 @@
+use crate::engine::core::read::order_tiebreak;
+use crate::engine::types::ScalarValue;
+use std::cmp::Ordering;
+
+/// Collects rows for an ORDER BY scan. With a limit, only the first `limit`
+/// rows in result order are kept: the buffer is sorted and cut back each time
+/// it doubles, so memory stays bounded by the limit rather than the number of
+/// rows scanned.
+pub struct TopRows {
+    rows: Vec<Vec<ScalarValue>>,
+    order_index: usize,
+    tiebreak: Option<usize>,
+    ascending: bool,
+    limit: Option<usize>,
+}
+
+impl TopRows {
+    pub fn new(
+        order_index: usize,
+        tiebreak: Option<usize>,
+        ascending: bool,
+        limit: Option<usize>,
+    ) -> Self {
+        Self {
+            rows: Vec::new(),
+            order_index,
+            tiebreak,
+            ascending,
+            limit,
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<ScalarValue>) {
+        self.rows.push(row);
+        if let Some(limit) = self.limit
+            && self.rows.len() >= limit.max(1) * 2
+        {
+            self.compact(limit);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Order value of the last row the limit still admits, once `limit` rows
+    /// have been seen. A row that sorts after it can never make the result.
+    pub fn cutoff(&mut self) -> Option<&ScalarValue> {
+        let limit = self.limit?;
+        if limit == 0 || self.rows.len() < limit {
+            return None;
+        }
+        self.compact(limit);
+        self.rows.last().map(|row| &row[self.order_index])
+    }
+
+    /// Rows in result order, cut to the limit.
+    pub fn into_sorted(mut self) -> Vec<Vec<ScalarValue>> {
+        self.sort();
+        if let Some(limit) = self.limit {
+            self.rows.truncate(limit);
+        }
+        self.rows
+    }
+
+    fn compact(&mut self, limit: usize) {
+        self.sort();
+        self.rows.truncate(limit);
+    }
+
+    fn sort(&mut self) {
+        let (order_index, tiebreak, ascending) = (self.order_index, self.tiebreak, self.ascending);
+        self.rows.sort_unstable_by(|a, b| {
+            let ord = compare_scalar_values(&a[order_index], &b[order_index])
+                .then_with(|| order_tiebreak::break_tie(a, b, tiebreak));
+            if ascending { ord } else { ord.reverse() }
+        });
+    }
+}
+
+fn compare_scalar_values(a: &ScalarValue, b: &ScalarValue) -> Ordering {
+    if let (Some(va), Some(vb)) = (a.as_u64(), b.as_u64()) {
+        return va.cmp(&vb);
+    }
+    a.compare(b)
+}
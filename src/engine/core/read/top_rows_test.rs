@@ -0,0 +1,44 @@
+use crate::engine::core::read::top_rows::TopRows;
+use crate::engine::types::ScalarValue;
+
+fn row(ts: i64, event_id: i64) -> Vec<ScalarValue> {
+    vec![ScalarValue::Int64(ts), ScalarValue::Int64(event_id)]
+}
+
+fn timestamps(rows: &[Vec<ScalarValue>]) -> Vec<i64> {
+    rows.iter().map(|r| r[0].as_i64().unwrap()).collect()
+}
+
+#[test]
+fn keeps_newest_rows_when_descending_with_limit() {
+    let mut top = TopRows::new(0, Some(1), false, Some(3));
+    for (i, ts) in [5, 1, 9, 3, 7, 2, 8].into_iter().enumerate() {
+        top.push(row(ts, i as i64));
+    }
+    assert!(top.len() <= 6, "buffer should stay bounded by the limit");
+    assert_eq!(timestamps(&top.into_sorted()), vec![9, 8, 7]);
+}
+
+#[test]
+fn cutoff_reports_the_last_admitted_value_once_full() {
+    let mut top = TopRows::new(0, None, true, Some(2));
+    top.push(row(4, 0));
+    assert!(top.cutoff().is_none());
+    top.push(row(10, 1));
+    top.push(row(1, 2));
+    assert_eq!(top.cutoff(), Some(&ScalarValue::Int64(4)));
+}
+
+#[test]
+fn ties_follow_event_id_in_the_scan_direction() {
+    let mut top = TopRows::new(0, Some(1), false, None);
+    for event_id in [2, 0, 1] {
+        top.push(row(5, event_id));
+    }
+    let ids: Vec<i64> = top
+        .into_sorted()
+        .iter()
+        .map(|r| r[1].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![2, 1, 0]);
+}
@@ -0,0 +1,89 @@
+//! Lists the index artifacts a segment actually holds for one uid, by
+//! looking at the files on disk rather than the schema or the `.icx` catalog.
+
+use crate::engine::core::zone::field_histogram::FieldHistogramIndex;
+use std::path::Path;
+
+/// Per-field artifacts, as `{uid}_{field}.{ext}`, in listing order.
+pub const FIELD_ARTIFACTS: [&str; 6] = ["zsrf", "xf", "zxf", "ebm", "cal", "tfi"];
+
+/// Artifacts shared by every field of a uid, as `{uid}.{ext}`, in listing order.
+pub const SEGMENT_ARTIFACTS: [&str; 5] = ["idx", "icx", "cal", "rlte", "hist"];
+
+/// Per-field entry for a histogram stored inside the shared `{uid}.hist`.
+pub const HISTOGRAM: &str = "hist";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexArtifact {
+    /// File extension, or `hist` for a histogram entry.
+    pub name: &'static str,
+    /// File size; `None` for entries inside a shared file.
+    pub bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentIndexInventory {
+    pub segment: String,
+    pub segment_artifacts: Vec<IndexArtifact>,
+    /// Every requested field, in the order given, with the artifacts found.
+    pub fields: Vec<(String, Vec<IndexArtifact>)>,
+}
+
+impl SegmentIndexInventory {
+    /// Scans `segment_dir` for the artifacts of `uid` and `fields`. `None`
+    /// when the segment holds no zones for `uid`.
+    pub fn scan(segment_dir: &Path, uid: &str, fields: &[String]) -> Option<Self> {
+        if !segment_dir.join(format!("{}.zones", uid)).exists() {
+            return None;
+        }
+        let size = |file: String| {
+            std::fs::metadata(segment_dir.join(file))
+                .ok()
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+        };
+
+        let segment_artifacts = SEGMENT_ARTIFACTS
+            .iter()
+            .filter_map(|ext| {
+                size(format!("{}.{}", uid, ext)).map(|bytes| IndexArtifact {
+                    name: ext,
+                    bytes: Some(bytes),
+                })
+            })
+            .collect();
+
+        // A histogram that fails to load is reported as missing for every field
+        let histograms = FieldHistogramIndex::load(uid, segment_dir).ok();
+        let fields = fields
+            .iter()
+            .map(|field| {
+                let mut artifacts: Vec<IndexArtifact> = FIELD_ARTIFACTS
+                    .iter()
+                    .filter_map(|ext| {
+                        size(format!("{}_{}.{}", uid, field, ext)).map(|bytes| IndexArtifact {
+                            name: ext,
+                            bytes: Some(bytes),
+                        })
+                    })
+                    .collect();
+                if histograms.as_ref().is_some_and(|h| h.get(field).is_some()) {
+                    artifacts.push(IndexArtifact {
+                        name: HISTOGRAM,
+                        bytes: None,
+                    });
+                }
+                (field.clone(), artifacts)
+            })
+            .collect();
+
+        Some(Self {
+            segment: segment_dir
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            segment_artifacts,
+            fields,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+use crate::command::types::FieldIndexKind;
+use crate::engine::core::segment::index_inventory::{IndexArtifact, SegmentIndexInventory};
+use crate::engine::core::{ZonePlanner, ZoneWriter};
+use crate::test_helpers::factories::{EventFactory, MiniSchemaFactory, SchemaRegistryFactory};
+use serde_json::json;
+
+fn names(artifacts: &[IndexArtifact]) -> Vec<&str> {
+    artifacts.iter().map(|a| a.name).collect()
+}
+
+#[tokio::test]
+async fn scan_lists_artifacts_found_on_disk() {
+    let factory = SchemaRegistryFactory::new();
+    let registry = factory.registry();
+    let schema = MiniSchemaFactory::empty()
+        .with("amount", "int")
+        .with("score", "int")
+        .with_indexes("amount", &[FieldIndexKind::Xor])
+        .create();
+    registry.write().await.define("order", schema).unwrap();
+    let uid = registry.read().await.get_uid("order").unwrap();
+
+    let events: Vec<_> = (0..3)
+        .map(|i| {
+            EventFactory::new()
+                .with("event_type", "order")
+                .with("context_id", format!("ctx{}", i))
+                .with("payload", json!({ "amount": i * 10, "score": i }))
+                .create()
+        })
+        .collect();
+    let tmp = tempfile::tempdir().unwrap();
+    let segment_dir = tmp.path().join("00003");
+    std::fs::create_dir_all(&segment_dir).unwrap();
+    let plans = ZonePlanner::new(&uid, 3).plan(&events).unwrap();
+    ZoneWriter::new(&uid, &segment_dir, registry.clone())
+        .write_all(&plans)
+        .await
+        .unwrap();
+    // Lost after the flush, e.g. by a failed backfill
+    std::fs::remove_file(segment_dir.join(format!("{}_score.zsrf", uid))).unwrap();
+
+    let fields = ["amount", "score", "missing"].map(String::from);
+    let inventory = SegmentIndexInventory::scan(&segment_dir, &uid, &fields).unwrap();
+
+    assert_eq!(inventory.segment, "00003");
+    assert_eq!(
+        names(&inventory.segment_artifacts),
+        vec!["idx", "icx", "cal", "rlte", "hist"]
+    );
+    let idx_len = std::fs::metadata(segment_dir.join(format!("{}.idx", uid)))
+        .unwrap()
+        .len();
+    assert_eq!(inventory.segment_artifacts[0].bytes, Some(idx_len));
+
+    let (field, amount) = &inventory.fields[0];
+    assert_eq!(field, "amount");
+    assert_eq!(names(amount), vec!["xf", "zxf"]);
+    assert!(amount.iter().all(|a| a.bytes.is_some_and(|b| b > 0)));
+
+    let (_, score) = &inventory.fields[1];
+    assert_eq!(names(score), vec!["xf", "zxf", "hist"]);
+    assert_eq!(score[2].bytes, None);
+
+    assert_eq!(inventory.fields[2], ("missing".to_string(), Vec::new()));
+}
+
+#[test]
+fn scan_skips_segments_without_zones_for_uid() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("other.zones"), b"").unwrap();
+
+    assert!(SegmentIndexInventory::scan(tmp.path(), "uid", &["a".to_string()]).is_none());
+}
@@ -0,0 +1,483 @@
+//! Rebuilds zone index artifacts of segments that are already on disk.
+//!
+//! Artifacts are built from the segment's stored columns into a staging
+//! directory inside the segment and checked for false negatives. They are
+//! then renamed over the live files while the shard's flush lock is held, so
+//! compaction and retention cannot retire the segment halfway. The `.icx`
+//! catalog is replaced last. Each rename is atomic: a query sees either the
+//! old artifact or the new one, and a crash leaves only verified files
+//! behind. The staging directory of an interrupted run is cleared on the next.
+//!
+//! Finished (segment, uid) pairs are journaled in the shard directory, so a
+//! rerun with the same kinds resumes where an interrupted one stopped.
+
+use crate::command::types::RebuildIndexKind;
+use crate::engine::core::FieldXorFilter;
+use crate::engine::core::filter::fuse_filter::XorFilterSizing;
+use crate::engine::core::filter::zone_surf_filter::ZoneSurfFilter;
+use crate::engine::core::read::cache::global_calendar_cache::{
+    GlobalCalendarCache, GlobalFieldCalendarCache,
+};
+use crate::engine::core::read::cache::global_temporal_index_cache::GlobalFieldTemporalIndexCache;
+use crate::engine::core::read::cache::{
+    GlobalEnumCache, GlobalIndexCatalogCache, GlobalZoneSurfCache, GlobalZoneXorFilterCache,
+};
+use crate::engine::core::read::catalog::{IndexKind, SegmentIndexCatalog};
+use crate::engine::core::segment::index_rebuild_verifier::RebuiltIndexVerifier;
+use crate::engine::core::time::{CalendarDir, TemporalIndexBuilder};
+use crate::engine::core::zone::enum_bitmap_index::EnumBitmapBuilder;
+use crate::engine::core::zone::index_build_planner::{BuildPlan, IndexBuildPlanner};
+use crate::engine::core::zone::index_build_policy::IndexBuildPolicy;
+use crate::engine::core::zone::zone_cursor_loader::LoadedZoneCursors;
+use crate::engine::core::zone::zone_xor_index::build_all_zxf_filtered_with_sizing;
+use crate::engine::core::{ZoneCursorLoader, ZoneMeta, ZonePlan};
+use crate::engine::schema::SchemaRegistry;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock as StdRwLock};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+/// Staging directory inside the segment being rebuilt.
+pub const STAGING_DIR: &str = "rebuild.tmp";
+
+/// Progress of the current rebuild, in the shard directory.
+pub const JOURNAL_FILE: &str = "index_rebuild.journal";
+
+/// Rebuilds read whole segments; running one at a time keeps them from
+/// crowding out queries and from sharing a staging directory.
+static REBUILD_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebuildStatus {
+    /// New artifacts passed verification and replaced the live ones.
+    Rebuilt {
+        zones: usize,
+        files: usize,
+    },
+    /// Finished by an earlier, interrupted run with the same kinds.
+    AlreadyDone,
+    /// Compaction or retention retired the segment before it was published.
+    Retired,
+    /// The new artifacts would prune zones holding matching rows; the live
+    /// files were left untouched.
+    VerificationFailed(Vec<String>),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebuildOutcome {
+    pub segment: String,
+    pub uid: String,
+    pub status: RebuildStatus,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RebuildJournal {
+    kinds: Vec<RebuildIndexKind>,
+    /// `{segment}/{uid}` pairs already published.
+    done: BTreeSet<String>,
+}
+
+impl RebuildJournal {
+    /// The journal left by an interrupted run with the same kinds, else an empty one.
+    fn resume(path: &Path, kinds: &[RebuildIndexKind]) -> Self {
+        let previous = fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<RebuildJournal>(&bytes).ok());
+        match previous {
+            Some(journal) if journal.kinds == kinds => journal,
+            _ => Self {
+                kinds: kinds.to_vec(),
+                done: BTreeSet::new(),
+            },
+        }
+    }
+
+    fn key(segment: &str, uid: &str) -> String {
+        format!("{}/{}", segment, uid)
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        let bytes = serde_json::to_vec(self).map_err(std::io::Error::other)?;
+        let tmp = path.with_extension("journal.tmp");
+        fs::write(&tmp, bytes)?;
+        fs::File::open(&tmp)?.sync_all()?;
+        fs::rename(&tmp, path)
+    }
+}
+
+/// Catalog bits a rebuild of `kinds` rewrites.
+pub fn catalog_bits(kinds: &[RebuildIndexKind]) -> IndexKind {
+    kinds.iter().fold(IndexKind::empty(), |bits, kind| {
+        bits | match kind {
+            RebuildIndexKind::Surf => IndexKind::ZONE_SURF,
+            RebuildIndexKind::Xor => IndexKind::XOR_FIELD_FILTER | IndexKind::ZONE_XOR_INDEX,
+            RebuildIndexKind::Enum => IndexKind::ENUM_BITMAP,
+            RebuildIndexKind::Temporal => {
+                IndexKind::TS_CALENDAR
+                    | IndexKind::TS_ZTI
+                    | IndexKind::FIELD_CALENDAR
+                    | IndexKind::FIELD_ZTI
+            }
+        }
+    })
+}
+
+/// Rebuilds index artifacts for the segments of one shard.
+pub struct SegmentIndexRebuilder {
+    shard_dir: PathBuf,
+    segment_ids: Arc<StdRwLock<Vec<String>>>,
+    flush_lock: Arc<Mutex<()>>,
+    registry: Arc<RwLock<SchemaRegistry>>,
+    kinds: Vec<RebuildIndexKind>,
+}
+
+impl SegmentIndexRebuilder {
+    /// An empty `kinds` rebuilds every kind.
+    pub fn new(
+        shard_dir: PathBuf,
+        segment_ids: Arc<StdRwLock<Vec<String>>>,
+        flush_lock: Arc<Mutex<()>>,
+        registry: Arc<RwLock<SchemaRegistry>>,
+        kinds: &[RebuildIndexKind],
+    ) -> Self {
+        let mut kinds = if kinds.is_empty() {
+            RebuildIndexKind::ALL.to_vec()
+        } else {
+            kinds.to_vec()
+        };
+        kinds.sort();
+        kinds.dedup();
+        Self {
+            shard_dir,
+            segment_ids,
+            flush_lock,
+            registry,
+            kinds,
+        }
+    }
+
+    /// Rebuilds `segment`, or every live segment when `None`. A named segment
+    /// the shard does not hold yields no outcomes.
+    pub async fn run(&self, segment: Option<&str>) -> Vec<RebuildOutcome> {
+        let _running = REBUILD_LOCK.lock().await;
+
+        let live = self.live_segments();
+        let labels: Vec<String> = match segment {
+            Some(label) => live.into_iter().filter(|l| l == label).collect(),
+            None => live,
+        };
+
+        let journal_path = self.shard_dir.join(JOURNAL_FILE);
+        let mut journal = RebuildJournal::resume(&journal_path, &self.kinds);
+        let mut outcomes = Vec::new();
+
+        for label in &labels {
+            let segment_dir = self.shard_dir.join(label);
+            let staging = segment_dir.join(STAGING_DIR);
+            if staging.exists()
+                && let Err(e) = fs::remove_dir_all(&staging)
+            {
+                warn!(target: "sneldb::rebuild", segment = %label, error = %e, "Failed to clear stale staging directory");
+            }
+
+            for uid in Self::segment_uids(&segment_dir) {
+                let key = RebuildJournal::key(label, &uid);
+                let status = if journal.done.contains(&key) {
+                    RebuildStatus::AlreadyDone
+                } else {
+                    let status = match self.rebuild_uid(label, &segment_dir, &uid).await {
+                        Ok(status) => status,
+                        Err(e) => RebuildStatus::Failed(e),
+                    };
+                    if staging.exists() {
+                        let _ = fs::remove_dir_all(&staging);
+                    }
+                    if matches!(
+                        status,
+                        RebuildStatus::Rebuilt { .. } | RebuildStatus::Retired
+                    ) {
+                        journal.done.insert(key);
+                        if let Err(e) = journal.save(&journal_path) {
+                            warn!(target: "sneldb::rebuild", error = %e, "Failed to save rebuild journal");
+                        }
+                    }
+                    status
+                };
+                info!(target: "sneldb::rebuild", segment = %label, uid = %uid, status = ?status, "Segment index rebuild finished");
+                outcomes.push(RebuildOutcome {
+                    segment: label.clone(),
+                    uid,
+                    status,
+                });
+            }
+        }
+
+        // A full run that failed nowhere leaves nothing to resume
+        let clean = outcomes.iter().all(|o| {
+            !matches!(
+                o.status,
+                RebuildStatus::Failed(_) | RebuildStatus::VerificationFailed(_)
+            )
+        });
+        if segment.is_none() && clean {
+            let _ = fs::remove_file(&journal_path);
+        }
+        outcomes
+    }
+
+    fn live_segments(&self) -> Vec<String> {
+        let mut labels = self
+            .segment_ids
+            .read()
+            .map(|ids| ids.clone())
+            .unwrap_or_default();
+        labels.sort();
+        labels
+    }
+
+    /// Uids with zone metadata in the segment.
+    fn segment_uids(segment_dir: &Path) -> Vec<String> {
+        let mut uids: Vec<String> = fs::read_dir(segment_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                name.strip_suffix(".zones").map(str::to_string)
+            })
+            .collect();
+        uids.sort();
+        uids
+    }
+
+    async fn rebuild_uid(
+        &self,
+        label: &str,
+        segment_dir: &Path,
+        uid: &str,
+    ) -> Result<RebuildStatus, String> {
+        let zone_plans = self.load_zone_plans(label, uid).await?;
+        let Some(first) = zone_plans.first() else {
+            return Ok(RebuildStatus::Rebuilt { zones: 0, files: 0 });
+        };
+        let schema = self
+            .registry
+            .read()
+            .await
+            .get(&first.event_type)
+            .cloned()
+            .ok_or_else(|| format!("No schema for event type '{}'", first.event_type))?;
+        let plan = IndexBuildPlanner::new(uid, label, &schema, IndexBuildPolicy::default()).plan();
+
+        let staging = segment_dir.join(STAGING_DIR);
+        fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+        self.build_staged(segment_dir, &staging, uid, &zone_plans, &plan)
+            .await?;
+
+        let problems = RebuiltIndexVerifier::new(&staging, uid, &zone_plans).verify(&self.kinds);
+        if !problems.is_empty() {
+            return Ok(RebuildStatus::VerificationFailed(problems));
+        }
+
+        match self
+            .publish(label, segment_dir, &staging, uid, &plan)
+            .await?
+        {
+            Some(files) => Ok(RebuildStatus::Rebuilt {
+                zones: zone_plans.len(),
+                files,
+            }),
+            None => Ok(RebuildStatus::Retired),
+        }
+    }
+
+    /// One plan per stored zone, keeping zone ids so artifacts line up with `.zones`.
+    async fn load_zone_plans(&self, label: &str, uid: &str) -> Result<Vec<ZonePlan>, String> {
+        let segment_id = label
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid segment label '{}'", label))?;
+        let LoadedZoneCursors { cursors, .. } = ZoneCursorLoader::new(
+            uid.to_string(),
+            vec![label.to_string()],
+            Arc::clone(&self.registry),
+            self.shard_dir.clone(),
+        )
+        .load_all()
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut plans = Vec::with_capacity(cursors.len());
+        for mut cursor in cursors {
+            let (zone_id, created_at) = (cursor.zone_id, cursor.created_at);
+            let mut rows = Vec::with_capacity(cursor.len());
+            while let Some(row) = cursor.next_row() {
+                rows.push(row);
+            }
+            if rows.is_empty() {
+                continue;
+            }
+            let plan = ZonePlan::from_rows(rows, uid.to_string(), segment_id, zone_id, created_at)
+                .map_err(|e| e.to_string())?;
+            plans.push(plan);
+        }
+        plans.sort_by_key(|plan| plan.id);
+        Ok(plans)
+    }
+
+    /// Writes the selected artifacts with the same builders a flush uses.
+    async fn build_staged(
+        &self,
+        segment_dir: &Path,
+        staging: &Path,
+        uid: &str,
+        zone_plans: &[ZonePlan],
+        plan: &BuildPlan,
+    ) -> Result<(), String> {
+        let fields_with = |kind: IndexKind| -> HashSet<String> {
+            plan.per_field
+                .iter()
+                .filter(|(_, kinds)| kinds.contains(kind))
+                .map(|(field, _)| field.clone())
+                .collect()
+        };
+
+        for kind in &self.kinds {
+            match kind {
+                RebuildIndexKind::Surf => {
+                    ZoneSurfFilter::build_all_filtered(
+                        zone_plans,
+                        staging,
+                        &fields_with(IndexKind::ZONE_SURF),
+                    )
+                    .map_err(|e| format!("Failed to build SuRF filters: {}", e))?;
+                }
+                RebuildIndexKind::Xor => {
+                    FieldXorFilter::build_all_filtered_with_sizing(
+                        zone_plans,
+                        staging,
+                        &fields_with(IndexKind::XOR_FIELD_FILTER),
+                        XorFilterSizing::ByDistinctCount,
+                    )
+                    .map_err(|e| format!("Failed to build XOR filters: {}", e))?;
+                    build_all_zxf_filtered_with_sizing(
+                        zone_plans,
+                        staging,
+                        &fields_with(IndexKind::ZONE_XOR_INDEX),
+                        XorFilterSizing::ByDistinctCount,
+                    )
+                    .map_err(|e| format!("Failed to build zone XOR filters: {}", e))?;
+                }
+                RebuildIndexKind::Enum => {
+                    EnumBitmapBuilder::build_all(zone_plans, staging, &self.registry)
+                        .await
+                        .map_err(|e| format!("Failed to build enum bitmaps: {}", e))?;
+                }
+                RebuildIndexKind::Temporal => {
+                    let metas = ZoneMeta::load(&segment_dir.join(format!("{}.zones", uid)))
+                        .map_err(|e| format!("Failed to load zone meta: {}", e))?;
+                    let mut calendar = CalendarDir::new();
+                    for meta in &metas {
+                        calendar.add_zone_range(
+                            meta.zone_id,
+                            meta.timestamp_min,
+                            meta.timestamp_max,
+                        );
+                    }
+                    calendar
+                        .save(uid, staging)
+                        .map_err(|e| format!("Failed to save calendar: {}", e))?;
+                    TemporalIndexBuilder::new(uid, staging, Arc::clone(&self.registry))
+                        .build_for_zone_plans(zone_plans)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the staged artifacts into the segment and updates its catalog.
+    /// `None` when the segment was retired meanwhile.
+    async fn publish(
+        &self,
+        label: &str,
+        segment_dir: &Path,
+        staging: &Path,
+        uid: &str,
+        plan: &BuildPlan,
+    ) -> Result<Option<usize>, String> {
+        let io = |e: std::io::Error| format!("Failed to publish rebuilt indexes: {}", e);
+        let published = {
+            let _guard = self.flush_lock.lock().await;
+            let live = self
+                .segment_ids
+                .read()
+                .map(|ids| ids.iter().any(|id| id == label))
+                .unwrap_or(false);
+            if !live || !segment_dir.join(format!("{}.zones", uid)).exists() {
+                return Ok(None);
+            }
+
+            let mut staged: Vec<PathBuf> = fs::read_dir(staging)
+                .map_err(io)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<_, _>>()
+                .map_err(io)?;
+            staged.sort();
+            for path in &staged {
+                fs::File::open(path)
+                    .and_then(|f| f.sync_all())
+                    .map_err(io)?;
+            }
+            for path in &staged {
+                let Some(name) = path.file_name() else {
+                    continue;
+                };
+                fs::rename(path, segment_dir.join(name)).map_err(io)?;
+            }
+
+            // Only segments flushed with a catalog have one to update
+            let icx_path = segment_dir.join(format!("{}.icx", uid));
+            if icx_path.exists() {
+                let mut catalog = SegmentIndexCatalog::load(&icx_path).map_err(io)?;
+                let bits = catalog_bits(&self.kinds);
+                for (field, planned) in &plan.per_field {
+                    let current = catalog
+                        .field_kinds
+                        .get(field)
+                        .copied()
+                        .unwrap_or(IndexKind::empty());
+                    catalog.set_field_kind(field, (current - bits) | (*planned & bits));
+                }
+                let tmp = staging.join(format!("{}.icx", uid));
+                catalog.save(&tmp).map_err(io)?;
+                fs::File::open(&tmp)
+                    .and_then(|f| f.sync_all())
+                    .map_err(io)?;
+                fs::rename(&tmp, &icx_path).map_err(io)?;
+            }
+            fs::File::open(segment_dir)
+                .and_then(|dir| dir.sync_all())
+                .map_err(io)?;
+            staged.len()
+        };
+
+        Self::invalidate_caches(label);
+        Ok(Some(published))
+    }
+
+    fn invalidate_caches(label: &str) {
+        GlobalZoneSurfCache::instance().invalidate_segment(label);
+        GlobalZoneXorFilterCache::instance().invalidate_segment(label);
+        GlobalEnumCache::instance().invalidate_segment(label);
+        GlobalIndexCatalogCache::instance().invalidate_segment(label);
+        GlobalCalendarCache::instance().invalidate_segment(label);
+        GlobalFieldCalendarCache::instance().invalidate_segment(label);
+        GlobalFieldTemporalIndexCache::instance().invalidate_segment(label);
+    }
+}
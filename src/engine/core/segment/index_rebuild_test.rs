@@ -0,0 +1,188 @@
+use crate::command::types::RebuildIndexKind;
+use crate::engine::core::read::catalog::{IndexKind, SegmentIndexCatalog};
+use crate::engine::core::segment::index_rebuild::{
+    JOURNAL_FILE, RebuildStatus, STAGING_DIR, SegmentIndexRebuilder,
+};
+use crate::engine::core::{ZonePlan, ZoneWriter};
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::schema::types::{EnumType, FieldType};
+use crate::test_helpers::factories::{EventFactory, SchemaRegistryFactory};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock as StdRwLock};
+use tempfile::{TempDir, tempdir};
+use tokio::sync::{Mutex, RwLock};
+
+struct Shard {
+    _tmp: TempDir,
+    dir: PathBuf,
+    registry: Arc<RwLock<SchemaRegistry>>,
+    uid: String,
+    segment_ids: Arc<StdRwLock<Vec<String>>>,
+}
+
+impl Shard {
+    fn rebuilder(&self, kinds: &[RebuildIndexKind]) -> SegmentIndexRebuilder {
+        SegmentIndexRebuilder::new(
+            self.dir.clone(),
+            Arc::clone(&self.segment_ids),
+            Arc::new(Mutex::new(())),
+            Arc::clone(&self.registry),
+            kinds,
+        )
+    }
+
+    fn segment_file(&self, suffix: &str) -> PathBuf {
+        self.dir
+            .join("00001")
+            .join(format!("{}{}", self.uid, suffix))
+    }
+}
+
+/// A shard holding segment `00001` of "order": two zones of two events.
+async fn flushed_shard() -> Shard {
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_field_types(
+            "order",
+            &[
+                ("context_id", FieldType::String),
+                ("amount", FieldType::I64),
+                (
+                    "plan",
+                    FieldType::Enum(EnumType {
+                        variants: vec!["free".to_string(), "pro".to_string()],
+                    }),
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let uid = registry.read().await.get_uid("order").unwrap();
+
+    let events: Vec<_> = [(10, "free"), (20, "pro"), (30, "pro"), (40, "free")]
+        .iter()
+        .enumerate()
+        .map(|(i, (amount, plan))| {
+            EventFactory::new()
+                .with("event_type", "order")
+                .with("context_id", format!("ctx{}", i))
+                .with("timestamp", 1_700_000_000 + i as u64 * 60)
+                .with("payload", json!({ "amount": amount, "plan": plan }))
+                .create()
+        })
+        .collect();
+    let tmp = tempdir().unwrap();
+    let dir = tmp.path().join("shard-0");
+    let segment_dir = dir.join("00001");
+    std::fs::create_dir_all(&segment_dir).unwrap();
+    let plans = ZonePlan::build_all(&events, 2, uid.clone(), 1).unwrap();
+    ZoneWriter::new(&uid, &segment_dir, Arc::clone(&registry))
+        .write_all(&plans)
+        .await
+        .unwrap();
+
+    Shard {
+        _tmp: tmp,
+        dir,
+        registry,
+        uid,
+        segment_ids: Arc::new(StdRwLock::new(vec!["00001".to_string()])),
+    }
+}
+
+fn catalog(path: &Path) -> SegmentIndexCatalog {
+    SegmentIndexCatalog::load(path).unwrap()
+}
+
+#[tokio::test]
+async fn rebuild_restores_missing_artifacts_and_clears_stale_staging() {
+    let shard = flushed_shard().await;
+    for suffix in ["_amount.zxf", "_amount.zsrf", "_plan.ebm", "_timestamp.tfi"] {
+        std::fs::remove_file(shard.segment_file(suffix)).unwrap();
+    }
+    let stale = shard.dir.join("00001").join(STAGING_DIR);
+    std::fs::create_dir_all(&stale).unwrap();
+    std::fs::write(stale.join("leftover.zxf"), b"partial").unwrap();
+
+    let outcomes = shard.rebuilder(&[]).run(None).await;
+
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].segment, "00001");
+    assert_eq!(outcomes[0].uid, shard.uid);
+    assert!(
+        matches!(outcomes[0].status, RebuildStatus::Rebuilt { zones: 2, files } if files > 0),
+        "got: {:?}",
+        outcomes[0].status
+    );
+    for suffix in ["_amount.zxf", "_amount.zsrf", "_plan.ebm", "_timestamp.tfi"] {
+        assert!(
+            shard.segment_file(suffix).exists(),
+            "{} not rebuilt",
+            suffix
+        );
+    }
+    assert!(!stale.exists());
+    assert!(!shard.dir.join("00001").join("leftover.zxf").exists());
+    assert!(!shard.dir.join(JOURNAL_FILE).exists());
+
+    let kinds = catalog(&shard.segment_file(".icx"));
+    assert!(kinds.field_kinds["amount"].contains(IndexKind::ZONE_SURF));
+    assert!(kinds.field_kinds["plan"].contains(IndexKind::ENUM_BITMAP));
+}
+
+#[tokio::test]
+async fn rebuild_only_rewrites_catalog_bits_of_selected_kinds() {
+    let shard = flushed_shard().await;
+    let icx = shard.segment_file(".icx");
+    let mut stale = catalog(&icx);
+    stale.set_field_kind("amount", IndexKind::RLTE);
+    stale.save(&icx).unwrap();
+
+    let outcomes = shard.rebuilder(&[RebuildIndexKind::Surf]).run(None).await;
+    assert!(matches!(outcomes[0].status, RebuildStatus::Rebuilt { .. }));
+
+    assert_eq!(
+        catalog(&icx).field_kinds["amount"],
+        IndexKind::RLTE | IndexKind::ZONE_SURF
+    );
+}
+
+#[tokio::test]
+async fn rebuild_resumes_from_journal_with_same_kinds() {
+    let shard = flushed_shard().await;
+    let kinds = [RebuildIndexKind::Xor];
+
+    // A single-segment run keeps its progress for a later full run
+    let outcomes = shard.rebuilder(&kinds).run(Some("00001")).await;
+    assert!(matches!(outcomes[0].status, RebuildStatus::Rebuilt { .. }));
+    assert!(shard.dir.join(JOURNAL_FILE).exists());
+
+    let outcomes = shard
+        .rebuilder(&[RebuildIndexKind::Enum])
+        .run(Some("00001"))
+        .await;
+    assert!(
+        matches!(outcomes[0].status, RebuildStatus::Rebuilt { .. }),
+        "other kinds start over"
+    );
+
+    let outcomes = shard.rebuilder(&kinds).run(Some("00001")).await;
+    assert!(matches!(outcomes[0].status, RebuildStatus::Rebuilt { .. }));
+    let outcomes = shard.rebuilder(&kinds).run(None).await;
+    assert_eq!(outcomes[0].status, RebuildStatus::AlreadyDone);
+    assert!(!shard.dir.join(JOURNAL_FILE).exists());
+
+    let outcomes = shard.rebuilder(&kinds).run(None).await;
+    assert!(matches!(outcomes[0].status, RebuildStatus::Rebuilt { .. }));
+}
+
+#[tokio::test]
+async fn rebuild_ignores_segments_the_shard_does_not_hold() {
+    let shard = flushed_shard().await;
+    shard.segment_ids.write().unwrap().clear();
+
+    assert!(shard.rebuilder(&[]).run(Some("00001")).await.is_empty());
+    assert!(shard.rebuilder(&[]).run(None).await.is_empty());
+}
@@ -0,0 +1,266 @@
+use crate::command::types::{CompareOp, RebuildIndexKind};
+use crate::engine::core::FieldXorFilter;
+use crate::engine::core::filter::surf_encoding::encode_value;
+use crate::engine::core::filter::zone_surf_filter::ZoneSurfFilter;
+use crate::engine::core::time::calendar_dir::GranularityPref;
+use crate::engine::core::time::{
+    CalendarDir, FieldIndex, TemporalCalendarIndex, ZoneTemporalIndex,
+};
+use crate::engine::core::zone::candidate_zone::CandidateZone;
+use crate::engine::core::zone::enum_bitmap_index::EnumBitmapIndex;
+use crate::engine::core::zone::zone_xor_index::ZoneXorFilterIndex;
+use crate::engine::core::{Event, ZonePlan};
+use std::path::Path;
+
+/// Checks freshly built index artifacts for false negatives: every value
+/// stored in a zone must keep that zone a candidate, or a query would
+/// silently skip rows. Each artifact reports at most its first miss.
+pub struct RebuiltIndexVerifier<'a> {
+    dir: &'a Path,
+    uid: &'a str,
+    zone_plans: &'a [ZonePlan],
+}
+
+impl<'a> RebuiltIndexVerifier<'a> {
+    pub fn new(dir: &'a Path, uid: &'a str, zone_plans: &'a [ZonePlan]) -> Self {
+        Self {
+            dir,
+            uid,
+            zone_plans,
+        }
+    }
+
+    /// One line per artifact that would prune a zone holding a matching row.
+    pub fn verify(&self, kinds: &[RebuildIndexKind]) -> Vec<String> {
+        let mut problems = Vec::new();
+        for kind in kinds {
+            match kind {
+                RebuildIndexKind::Surf => self.verify_surf(&mut problems),
+                RebuildIndexKind::Xor => {
+                    self.verify_field_xor(&mut problems);
+                    self.verify_zone_xor(&mut problems);
+                }
+                RebuildIndexKind::Enum => self.verify_enum(&mut problems),
+                RebuildIndexKind::Temporal => self.verify_temporal(&mut problems),
+            }
+        }
+        problems
+    }
+
+    /// Fields with a `{uid}_{field}{suffix}` file in the directory.
+    fn fields_with(&self, suffix: &str) -> Vec<String> {
+        let prefix = format!("{}_", self.uid);
+        let mut fields: Vec<String> = std::fs::read_dir(self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                name.strip_prefix(&prefix)?
+                    .strip_suffix(suffix)
+                    .map(str::to_string)
+            })
+            .collect();
+        fields.sort();
+        fields
+    }
+
+    fn file_name(&self, field: &str, suffix: &str) -> String {
+        format!("{}_{}{}", self.uid, field, suffix)
+    }
+
+    /// First (zone, event) pair for which `matches` fails.
+    fn first_miss<F>(&self, mut matches: F) -> Option<(u32, &Event)>
+    where
+        F: FnMut(u32, &Event) -> bool,
+    {
+        self.zone_plans.iter().find_map(|zone| {
+            zone.events
+                .iter()
+                .find(|event| !matches(zone.id, event))
+                .map(|event| (zone.id, event))
+        })
+    }
+
+    fn verify_field_xor(&self, problems: &mut Vec<String>) {
+        for field in self.fields_with(".xf") {
+            let name = self.file_name(&field, ".xf");
+            let filter = match FieldXorFilter::load(&self.dir.join(&name)) {
+                Ok(filter) => filter,
+                Err(e) => {
+                    problems.push(format!("{}: unreadable: {}", name, e));
+                    continue;
+                }
+            };
+            // Built from the same string form
+            if let Some((zone_id, event)) =
+                self.first_miss(|_, event| filter.contains(&event.get_field_value(&field)))
+            {
+                problems.push(format!(
+                    "{}: misses '{}' stored in zone {}",
+                    name,
+                    event.get_field_value(&field),
+                    zone_id
+                ));
+            }
+        }
+    }
+
+    fn verify_zone_xor(&self, problems: &mut Vec<String>) {
+        for field in self.fields_with(".zxf") {
+            let name = self.file_name(&field, ".zxf");
+            let index = match ZoneXorFilterIndex::load(&self.dir.join(&name)) {
+                Ok(index) => index,
+                Err(e) => {
+                    problems.push(format!("{}: unreadable: {}", name, e));
+                    continue;
+                }
+            };
+            if let Some((zone_id, event)) =
+                self.first_miss(|zone_id, event| match event.payload.get(&field) {
+                    Some(value) if FieldXorFilter::value_to_string(value).is_some() => {
+                        index.contains_in_zone(zone_id, value)
+                    }
+                    _ => true,
+                })
+            {
+                problems.push(format!(
+                    "{}: zone {} misses '{}'",
+                    name,
+                    zone_id,
+                    event.get_field_value(&field)
+                ));
+            }
+        }
+    }
+
+    fn verify_surf(&self, problems: &mut Vec<String>) {
+        for field in self.fields_with(".zsrf") {
+            let name = self.file_name(&field, ".zsrf");
+            let filter = match ZoneSurfFilter::load(&self.dir.join(&name)) {
+                Ok(filter) => filter,
+                Err(e) => {
+                    problems.push(format!("{}: unreadable: {}", name, e));
+                    continue;
+                }
+            };
+            if let Some((zone_id, event)) = self.first_miss(|zone_id, event| {
+                let Some(bytes) = event.payload.get(&field).and_then(encode_value) else {
+                    return true;
+                };
+                // An equality probe is a >= and a <= probe on the same bytes
+                let in_zone =
+                    |zones: Vec<CandidateZone>| zones.iter().any(|c| c.zone_id == zone_id);
+                in_zone(filter.zones_overlapping_ge(&bytes, true, ""))
+                    && in_zone(filter.zones_overlapping_le(&bytes, true, ""))
+            }) {
+                problems.push(format!(
+                    "{}: zone {} misses '{}'",
+                    name,
+                    zone_id,
+                    event.get_field_value(&field)
+                ));
+            }
+        }
+    }
+
+    fn verify_enum(&self, problems: &mut Vec<String>) {
+        for field in self.fields_with(".ebm") {
+            let name = self.file_name(&field, ".ebm");
+            let index = match EnumBitmapIndex::load(&self.dir.join(&name)) {
+                Ok(index) => index,
+                Err(e) => {
+                    problems.push(format!("{}: unreadable: {}", name, e));
+                    continue;
+                }
+            };
+            if let Some((zone_id, event)) = self.first_miss(|zone_id, event| {
+                let value = event.get_field_value(&field);
+                match index.variants.iter().position(|v| *v == value) {
+                    Some(variant_id) => index.has_any(zone_id, variant_id),
+                    None => true,
+                }
+            }) {
+                problems.push(format!(
+                    "{}: zone {} misses variant '{}'",
+                    name,
+                    zone_id,
+                    event.get_field_value(&field)
+                ));
+            }
+        }
+    }
+
+    fn verify_temporal(&self, problems: &mut Vec<String>) {
+        let name = format!("{}.cal", self.uid);
+        if self.dir.join(&name).exists() {
+            match CalendarDir::load(self.uid, self.dir) {
+                Ok(calendar) => {
+                    if let Some((zone_id, event)) = self.first_miss(|zone_id, event| {
+                        calendar
+                            .zones_for(event.timestamp, GranularityPref::Hour)
+                            .contains(zone_id)
+                    }) {
+                        problems.push(format!(
+                            "{}: zone {} misses timestamp {}",
+                            name, zone_id, event.timestamp
+                        ));
+                    }
+                }
+                Err(e) => problems.push(format!("{}: unreadable: {}", name, e)),
+            }
+        }
+
+        for field in self.fields_with(".cal") {
+            let name = self.file_name(&field, ".cal");
+            let calendar = match TemporalCalendarIndex::load(self.uid, &field, self.dir) {
+                Ok(calendar) => calendar,
+                Err(e) => {
+                    problems.push(format!("{}: unreadable: {}", name, e));
+                    continue;
+                }
+            };
+            let timestamp_of = |event: &Event| -> Option<i64> {
+                if field == "timestamp" {
+                    return Some(event.timestamp as i64);
+                }
+                let value = event.payload.get(&field)?;
+                value.as_i64().or_else(|| value.as_u64().map(|u| u as i64))
+            };
+
+            // Negative values are never indexed, so they cannot be missed
+            let mut calendar_miss = None;
+            let mut slab_miss = None;
+            for zone in self.zone_plans {
+                let slab =
+                    ZoneTemporalIndex::load_for_field(self.uid, &field, zone.id, self.dir).ok();
+                for ts in zone.events.iter().filter_map(timestamp_of) {
+                    if ts < 0 {
+                        continue;
+                    }
+                    if calendar_miss.is_none()
+                        && !calendar
+                            .zones_intersecting(CompareOp::Eq, ts)
+                            .contains(zone.id)
+                    {
+                        calendar_miss = Some((zone.id, ts));
+                    }
+                    if slab_miss.is_none() && !slab.as_ref().is_some_and(|s| s.contains_ts(ts)) {
+                        slab_miss = Some((zone.id, ts));
+                    }
+                }
+            }
+            if let Some((zone_id, ts)) = calendar_miss {
+                problems.push(format!("{}: zone {} misses {}", name, zone_id, ts));
+            }
+            if let Some((zone_id, ts)) = slab_miss {
+                problems.push(format!(
+                    "{}: zone {} misses {}",
+                    self.file_name(&field, ".tfi"),
+                    zone_id,
+                    ts
+                ));
+            }
+        }
+    }
+}
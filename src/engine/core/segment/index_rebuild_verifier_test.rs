@@ -0,0 +1,69 @@
+use crate::command::types::RebuildIndexKind;
+use crate::engine::core::segment::index_rebuild_verifier::RebuiltIndexVerifier;
+use crate::engine::core::zone::zone_xor_index::ZoneXorFilterIndex;
+use crate::engine::core::{ZonePlan, ZoneWriter};
+use crate::test_helpers::factories::{EventFactory, SchemaRegistryFactory};
+use serde_json::json;
+use tempfile::tempdir;
+
+/// Writes "order" events with amounts 10, 20, 30, 40 into two zones.
+async fn write_segment(segment_dir: &std::path::Path) -> (String, Vec<ZonePlan>) {
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("order", &[("context_id", "string"), ("amount", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+    let uid = registry.read().await.get_uid("order").unwrap();
+
+    let events: Vec<_> = (0..4u64)
+        .map(|i| {
+            EventFactory::new()
+                .with("event_type", "order")
+                .with("timestamp", 1_700_000_000 + i * 60)
+                .with("payload", json!({ "amount": (i + 1) * 10 }))
+                .create()
+        })
+        .collect();
+    let plans = ZonePlan::build_all(&events, 2, uid.clone(), 1).unwrap();
+    std::fs::create_dir_all(segment_dir).unwrap();
+    ZoneWriter::new(&uid, segment_dir, registry)
+        .write_all(&plans)
+        .await
+        .unwrap();
+    (uid, plans)
+}
+
+#[tokio::test]
+async fn verify_accepts_artifacts_built_from_the_zones() {
+    let tmp = tempdir().unwrap();
+    let (uid, plans) = write_segment(tmp.path()).await;
+
+    let problems =
+        RebuiltIndexVerifier::new(tmp.path(), &uid, &plans).verify(&RebuildIndexKind::ALL);
+    assert!(problems.is_empty(), "got: {:?}", problems);
+}
+
+#[tokio::test]
+async fn verify_reports_a_zone_the_index_would_prune() {
+    let tmp = tempdir().unwrap();
+    let (uid, plans) = write_segment(tmp.path()).await;
+
+    // Only zone 0 gets a filter, so lookups of 30 or 40 would skip zone 1
+    let partial = ZoneXorFilterIndex::build_for_field(&uid, "amount", &plans[..1]).unwrap();
+    partial
+        .save(&ZoneXorFilterIndex::file_path(tmp.path(), &uid, "amount"))
+        .unwrap();
+
+    let problems =
+        RebuiltIndexVerifier::new(tmp.path(), &uid, &plans).verify(&[RebuildIndexKind::Xor]);
+    assert_eq!(
+        problems,
+        vec![format!("{}_amount.zxf: zone 1 misses '30'", uid)]
+    );
+    assert!(
+        RebuiltIndexVerifier::new(tmp.path(), &uid, &plans)
+            .verify(&[RebuildIndexKind::Surf])
+            .is_empty()
+    );
+}
@@ -1,3 +1,6 @@
+pub mod index_inventory;
+pub mod index_rebuild;
+pub mod index_rebuild_verifier;
 pub mod inflight;
 pub mod lifecycle;
 pub mod range_allocator;
@@ -7,6 +10,12 @@ pub mod segment_index;
 pub mod segment_index_builder;
 pub mod verifier;
 
+#[cfg(test)]
+mod index_inventory_test;
+#[cfg(test)]
+mod index_rebuild_test;
+#[cfg(test)]
+mod index_rebuild_verifier_test;
 #[cfg(test)]
 mod inflight_test;
 #[cfg(test)]
@@ -1,10 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::command::types::FieldIndexKind;
 use crate::engine::core::time::{TemporalCalendarIndex, ZoneTemporalIndex};
 use crate::engine::core::zone::zone_plan::ZonePlan;
 use crate::engine::errors::StoreError;
 use crate::engine::schema::FieldType;
 use crate::engine::schema::registry::SchemaRegistry;
+use crate::shared::time::TimeKind;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -37,21 +39,17 @@ impl<'a> TemporalIndexBuilder<'a> {
         };
 
         let mut calendars: HashMap<String, TemporalCalendarIndex> = HashMap::new();
-        let temporal_fields: Vec<String> = schema
+        // Datetime/date fields plus the declared time field, whatever its type,
+        // unless the schema configured them without a temporal index
+        let temporal_field_set: HashSet<String> = schema
             .fields
-            .iter()
-            .filter_map(|(name, ty)| match ty {
-                FieldType::Timestamp | FieldType::Date => Some(name.clone()),
-                FieldType::Optional(inner)
-                    if matches!(**inner, FieldType::Timestamp | FieldType::Date) =>
-                {
-                    Some(name.clone())
-                }
-                _ => None,
+            .keys()
+            .filter(|name| {
+                schema.is_temporal_field(name)
+                    && schema.builds_index(name, FieldIndexKind::Temporal)
             })
-            .map(|s| s.to_string())
+            .cloned()
             .collect();
-        let temporal_field_set: HashSet<String> = temporal_fields.iter().cloned().collect();
 
         // Accumulate per-field slab entries across all zones
         let mut field_entries: HashMap<String, Vec<(u32, ZoneTemporalIndex)>> = HashMap::new();
@@ -113,7 +111,13 @@ impl<'a> TemporalIndexBuilder<'a> {
                     let entry = calendars
                         .entry(field.clone())
                         .or_insert_with(|| TemporalCalendarIndex::new(field.clone()));
-                    entry.add_zone_range(zp.id, min_ts as u64, max_ts as u64);
+                    if schema.field_type(&field).and_then(FieldType::time_kind)
+                        == Some(TimeKind::Date)
+                    {
+                        entry.add_zone_days(zp.id, min_ts as u64, max_ts as u64);
+                    } else {
+                        entry.add_zone_range(zp.id, min_ts as u64, max_ts as u64);
+                    }
                 }
             }
         }
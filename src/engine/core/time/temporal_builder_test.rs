@@ -198,3 +198,62 @@ async fn temporal_builder_handles_multiple_temporal_fields() {
         }
     }
 }
+
+#[tokio::test]
+async fn temporal_builder_indexes_each_declared_time_dimension() {
+    use crate::test_helpers::factories::MiniSchemaFactory;
+
+    let tmp_dir = tempfile::tempdir().expect("tmpdir");
+    let segment_dir = tmp_dir.path();
+
+    let schema_factory = SchemaRegistryFactory::new();
+    let registry = schema_factory.registry();
+    let event_type = "evt_bitemporal";
+    let schema = MiniSchemaFactory::empty()
+        .with("valid_at", "u64")
+        .with("recorded_at", "u64")
+        .with("amount", "u64")
+        .with_time_field("valid_at")
+        .with_temporal_field("recorded_at")
+        .create();
+    registry.write().await.define(event_type, schema).unwrap();
+    let uid = registry.read().await.get_uid(event_type).unwrap();
+
+    let events = vec![
+        EventFactory::new()
+            .with("event_type", event_type)
+            .with("context_id", "b1")
+            .with(
+                "payload",
+                json!({ "valid_at": 1_600_000_000u64, "recorded_at": 1_700_000_000u64, "amount": 5u64 }),
+            )
+            .create(),
+        EventFactory::new()
+            .with("event_type", event_type)
+            .with("context_id", "b2")
+            .with(
+                "payload",
+                json!({ "valid_at": 1_600_000_100u64, "recorded_at": 1_700_000_100u64, "amount": 7u64 }),
+            )
+            .create(),
+    ];
+
+    let planner = ZonePlanner::new(&uid, 2);
+    let plans = planner.plan(&events).expect("plan");
+
+    TemporalIndexBuilder::new(&uid, segment_dir, registry.clone())
+        .build_for_zone_plans(&plans)
+        .await
+        .expect("build temporal");
+
+    // One calendar and slab per declared dimension, none for plain integers
+    for field in ["valid_at", "recorded_at"] {
+        assert!(segment_dir.join(format!("{}_{}.cal", uid, field)).exists());
+        let zti = ZoneTemporalIndex::load_for_field(&uid, field, plans[0].id, segment_dir)
+            .expect("load zti from slab");
+        let ts = plans[0].events[0].payload[field].as_u64().unwrap();
+        assert!(zti.contains_ts(ts as i64));
+    }
+    assert!(!segment_dir.join(format!("{}_amount.cal", uid)).exists());
+    assert!(!segment_dir.join(format!("{}_amount.tfi", uid)).exists());
+}
@@ -42,7 +42,13 @@ impl TemporalCalendarIndex {
             self.hour.entry(b).or_default().insert(zone_id);
             t += 3600;
         }
-        // Day buckets
+        self.add_zone_days(zone_id, min_ts, max_ts);
+    }
+
+    /// Record the zone in the day buckets of [min_ts, max_ts] only. Date fields
+    /// hold midnights, so hour buckets would repeat the day buckets; lookups
+    /// fall back to days when no hour bucket exists.
+    pub fn add_zone_days(&mut self, zone_id: u32, min_ts: u64, max_ts: u64) {
         let mut td = naive_bucket_of(min_ts, &TimeGranularity::Day);
         let end_day = naive_bucket_of(max_ts, &TimeGranularity::Day);
         while td <= end_day {
@@ -208,3 +208,25 @@ fn save_and_load_multi_zone_multi_bucket() {
     assert!(zr.contains(1));
     assert!(zr.contains(2));
 }
+
+#[test]
+fn date_zones_fill_day_buckets_only() {
+    let day = 86_400u64;
+    let mut idx = TemporalCalendarIndex::new("due_date");
+    idx.add_zone_days(1, 19_000 * day, 19_002 * day);
+    idx.add_zone_days(2, 19_005 * day, 19_005 * day);
+    assert!(idx.hour.is_empty());
+    assert_eq!(idx.day.len(), 4);
+
+    // Equality finds the day bucket without hour buckets
+    let eq = idx.zones_intersecting(CompareOp::Eq, (19_001 * day) as i64);
+    assert_eq!(eq.iter().collect::<Vec<_>>(), vec![1]);
+    let eq = idx.zones_intersecting(CompareOp::Eq, (19_005 * day) as i64);
+    assert_eq!(eq.iter().collect::<Vec<_>>(), vec![2]);
+    assert!(
+        idx.zones_intersecting(CompareOp::Eq, (19_003 * day) as i64)
+            .is_empty()
+    );
+    let ge = idx.zones_intersecting(CompareOp::Gte, (19_002 * day) as i64);
+    assert_eq!(ge.iter().collect::<Vec<_>>(), vec![1, 2]);
+}
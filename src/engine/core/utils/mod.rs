@@ -2,6 +2,9 @@ pub mod io_monitor;
 pub mod memory_monitor;
 pub mod system_info_cache;
 pub mod uid_resolver;
+pub mod worker_pools;
 
 #[cfg(test)]
 mod uid_resolver_test;
+#[cfg(test)]
+mod worker_pools_test;
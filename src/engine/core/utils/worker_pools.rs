@@ -0,0 +1,133 @@
+use crate::shared::config::CONFIG;
+use std::future::Future;
+use std::sync::OnceLock;
+use tokio::runtime::{Builder, Handle, Runtime};
+use tokio::task::JoinHandle;
+use tracing::info;
+
+static POOLS: OnceLock<WorkerPools> = OnceLock::new();
+
+/// Separate runtimes for foreground query work and background flush/compaction,
+/// so a burst of background work cannot starve query execution of threads.
+///
+/// Until [`WorkerPools::init`] is called (tests, embedded use) every task runs on
+/// the caller's runtime.
+pub struct WorkerPools {
+    query: Runtime,
+    background: Runtime,
+}
+
+impl WorkerPools {
+    pub fn new(query_threads: usize, background_threads: usize) -> std::io::Result<Self> {
+        Ok(Self {
+            query: build_runtime("sneldb-query", query_threads)?,
+            background: build_runtime("sneldb-background", background_threads)?,
+        })
+    }
+
+    /// Builds the process-wide pools from `[engine]`; later calls return the same pools.
+    pub fn init() -> std::io::Result<&'static WorkerPools> {
+        if let Some(pools) = POOLS.get() {
+            return Ok(pools);
+        }
+        let ThreadCounts {
+            io: io_threads,
+            query: query_threads,
+            background: background_threads,
+        } = ThreadCounts::configured();
+        let pools = Self::new(query_threads, background_threads)?;
+        info!(
+            target: "sneldb::worker_pools",
+            io_threads, query_threads, background_threads, "Worker pools initialized"
+        );
+        Ok(POOLS.get_or_init(|| pools))
+    }
+
+    pub fn query(&self) -> &Handle {
+        self.query.handle()
+    }
+
+    pub fn background(&self) -> &Handle {
+        self.background.handle()
+    }
+}
+
+/// Worker thread counts of the three runtimes: the main runtime (frontends and
+/// connections), the query pool and the background pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThreadCounts {
+    pub io: usize,
+    pub query: usize,
+    pub background: usize,
+}
+
+impl ThreadCounts {
+    /// Resolves the counts from `[engine]` and the number of available cores.
+    pub fn configured() -> Self {
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        thread_counts(
+            cores,
+            CONFIG.engine.io_threads,
+            CONFIG.engine.query_threads,
+            CONFIG.engine.background_threads,
+        )
+    }
+}
+
+/// Resolves runtime sizes, deriving unset ones from the number of cores: an
+/// eighth of the cores for I/O, a quarter for background work and the rest for
+/// queries, each at least 1. With defaults the three add up to the core count
+/// (or 3 on machines with fewer than 3 cores).
+pub fn thread_counts(
+    cores: usize,
+    io_threads: Option<usize>,
+    query_threads: Option<usize>,
+    background_threads: Option<usize>,
+) -> ThreadCounts {
+    let cores = cores.max(1);
+    let io = io_threads.unwrap_or(cores / 8).max(1);
+    let background = background_threads.unwrap_or(cores / 4).max(1);
+    let query = query_threads
+        .unwrap_or(cores.saturating_sub(io + background))
+        .max(1);
+    ThreadCounts {
+        io,
+        query,
+        background,
+    }
+}
+
+/// Spawns foreground query work on the query pool.
+pub fn spawn_query<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match POOLS.get() {
+        Some(pools) => pools.query().spawn(future),
+        None => tokio::spawn(future),
+    }
+}
+
+/// Spawns flush or compaction work on the background pool. Tasks and blocking
+/// calls spawned from inside it stay on that pool.
+pub fn spawn_background<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match POOLS.get() {
+        Some(pools) => pools.background().spawn(future),
+        None => tokio::spawn(future),
+    }
+}
+
+fn build_runtime(name: &str, threads: usize) -> std::io::Result<Runtime> {
+    Builder::new_multi_thread()
+        .worker_threads(threads)
+        .thread_name(name)
+        .enable_all()
+        .build()
+}
@@ -0,0 +1,89 @@
+use crate::engine::core::utils::worker_pools::{
+    ThreadCounts, WorkerPools, spawn_background, thread_counts,
+};
+
+fn counts(io: usize, query: usize, background: usize) -> ThreadCounts {
+    ThreadCounts {
+        io,
+        query,
+        background,
+    }
+}
+
+#[test]
+fn test_thread_counts_derive_defaults_from_cores() {
+    assert_eq!(thread_counts(16, None, None, None), counts(2, 10, 4));
+    assert_eq!(thread_counts(8, None, None, None), counts(1, 5, 2));
+    assert_eq!(thread_counts(4, None, None, None), counts(1, 2, 1));
+    assert_eq!(thread_counts(1, None, None, None), counts(1, 1, 1));
+    assert_eq!(thread_counts(0, None, None, None), counts(1, 1, 1));
+}
+
+#[test]
+fn test_thread_counts_default_total_matches_cores() {
+    for cores in 3..=64 {
+        let c = thread_counts(cores, None, None, None);
+        assert_eq!(c.io + c.query + c.background, cores, "cores = {}", cores);
+    }
+}
+
+#[test]
+fn test_thread_counts_respect_configured_values() {
+    assert_eq!(thread_counts(16, None, Some(6), None), counts(2, 6, 4));
+    assert_eq!(thread_counts(16, None, None, Some(2)), counts(2, 12, 2));
+    assert_eq!(thread_counts(16, Some(4), None, None), counts(4, 8, 4));
+    assert_eq!(
+        thread_counts(16, Some(0), Some(0), Some(0)),
+        counts(1, 1, 1)
+    );
+}
+
+#[test]
+fn test_pools_run_tasks_on_named_threads() {
+    let pools = WorkerPools::new(2, 1).unwrap();
+
+    let query_thread = pools
+        .query()
+        .block_on(
+            pools
+                .query()
+                .spawn(async { std::thread::current().name().map(str::to_string) }),
+        )
+        .unwrap();
+    let background_thread = pools
+        .background()
+        .block_on(
+            pools
+                .background()
+                .spawn(async { std::thread::current().name().map(str::to_string) }),
+        )
+        .unwrap();
+
+    assert_eq!(query_thread.as_deref(), Some("sneldb-query"));
+    assert_eq!(background_thread.as_deref(), Some("sneldb-background"));
+}
+
+#[test]
+fn test_background_work_does_not_block_query_pool() {
+    let pools = WorkerPools::new(1, 1).unwrap();
+
+    // Saturate the only background thread
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+    pools.background().spawn_blocking(move || release_rx.recv());
+    pools.background().spawn(async {
+        std::thread::sleep(std::time::Duration::from_millis(200));
+    });
+
+    let answer = pools
+        .query()
+        .block_on(async { tokio::spawn(async { 42 }).await })
+        .unwrap();
+    assert_eq!(answer, 42);
+    release_tx.send(()).unwrap();
+}
+
+#[tokio::test]
+async fn test_spawn_falls_back_to_current_runtime_without_pools() {
+    let value = spawn_background(async { 7 }).await.unwrap();
+    assert_eq!(value, 7);
+}
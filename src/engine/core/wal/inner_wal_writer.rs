@@ -5,24 +5,61 @@ use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
+/// Decides when the WAL writer closes its current segment and starts a new one.
+/// A segment rotates as soon as either configured limit is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalRotationPolicy {
+    pub max_segment_bytes: Option<u64>,
+    pub max_segment_entries: Option<u64>,
+}
+
+impl WalRotationPolicy {
+    /// Policy from `[wal]`. Without any segment limit configured, segments
+    /// rotate every MemTable's worth of entries.
+    pub fn from_config() -> Self {
+        let max_segment_bytes = CONFIG.wal.segment_size_bytes.map(|bytes| bytes as u64);
+        let max_segment_entries = match (CONFIG.wal.segment_max_entries, max_segment_bytes) {
+            (Some(entries), _) => Some(entries as u64),
+            (None, Some(_)) => None,
+            (None, None) => Some((CONFIG.engine.fill_factor * CONFIG.engine.event_per_zone) as u64),
+        };
+        Self {
+            max_segment_bytes,
+            max_segment_entries,
+        }
+    }
+
+    pub fn should_rotate(&self, entries: u64, bytes: u64) -> bool {
+        self.max_segment_entries.is_some_and(|max| entries >= max)
+            || self.max_segment_bytes.is_some_and(|max| bytes >= max)
+    }
+}
+
 pub struct InnerWalWriter {
     pub dir: PathBuf,
     pub file: Option<BufWriter<File>>,
     pub current_log_id: u64,
     pub entries_written: u64,
+    /// Bytes in the current segment, including buffered writes
+    pub bytes_written: u64,
+    policy: WalRotationPolicy,
     current_path: PathBuf,
 }
 
 impl InnerWalWriter {
     pub fn new(dir: PathBuf) -> std::io::Result<Self> {
+        Self::with_policy(dir, WalRotationPolicy::from_config())
+    }
+
+    pub fn with_policy(dir: PathBuf, policy: WalRotationPolicy) -> std::io::Result<Self> {
         std::fs::create_dir_all(&dir)?;
-        let next_id = Self::find_next_wal_id(&dir);
+        let next_id = Self::find_next_wal_id(&dir, &policy);
         let entries_written = Self::count_entries(&dir);
         let current_path = dir.join(format!("wal-{:05}.log", next_id));
 
         info!(
             target: "inner_wal_writer::new",
-            ?dir, next_id, entries_written,
+            ?dir, next_id, entries_written, ?policy,
             "Initialized WAL writer"
         );
 
@@ -31,10 +68,18 @@ impl InnerWalWriter {
             file: None,
             current_log_id: next_id,
             entries_written,
+            bytes_written: 0,
+            policy,
             current_path,
         })
     }
 
+    /// Whether the current segment has reached the rotation policy's limits.
+    pub fn should_rotate(&self) -> bool {
+        self.policy
+            .should_rotate(self.entries_written, self.bytes_written)
+    }
+
     fn count_entries(dir: &Path) -> u64 {
         let latest_id = Self::last_wal_id(dir);
         let path = dir.join(format!("wal-{:05}.log", latest_id));
@@ -90,6 +135,9 @@ impl InnerWalWriter {
 
         self.file = Some(writer);
         self.entries_written = Self::count_entries(&self.dir);
+        self.bytes_written = std::fs::metadata(&self.current_path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
         Ok(())
     }
 
@@ -128,6 +176,7 @@ impl InnerWalWriter {
             }
 
             self.entries_written += 1;
+            self.bytes_written += json.len() as u64 + 1;
             debug!(
                 target: "inner_wal_writer::append_immediate",
                 timestamp = entry.timestamp, total = self.entries_written,
@@ -175,32 +224,31 @@ impl InnerWalWriter {
         Ok(())
     }
 
-    fn find_next_wal_id(wal_dir: &Path) -> u64 {
+    fn find_next_wal_id(wal_dir: &Path, policy: &WalRotationPolicy) -> u64 {
         let last_log_id = Self::last_wal_id(wal_dir);
         debug!(target: "inner_wal_writer::find_next_wal_id", last_log_id, "Finding next WAL ID");
 
-        if last_log_id == 0 {
-            return 0;
-        }
-
+        // No log yet: start at `last_log_id`, which is 0
         let last_log_path = wal_dir.join(format!("wal-{:05}.log", last_log_id));
-        if let Ok(file) = File::open(&last_log_path) {
-            let reader = BufReader::new(file);
-            let line_count = reader.lines().count();
-            let capacity = CONFIG.engine.fill_factor * CONFIG.engine.event_per_zone;
-
-            if tracing::enabled!(tracing::Level::DEBUG) {
-                debug!(
-                    target: "inner_wal_writer::find_next_wal_id",
-                    last_log_id, line_count, threshold = capacity,
-                    "Checking if rollover needed"
-                );
-            }
-            if line_count < capacity as usize {
-                return last_log_id;
-            }
-        }
+        let Ok(file) = File::open(&last_log_path) else {
+            return last_log_id;
+        };
 
-        last_log_id + 1
+        let size = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        let reader = BufReader::new(file);
+        let line_count = reader.lines().count() as u64;
+
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            debug!(
+                target: "inner_wal_writer::find_next_wal_id",
+                last_log_id, line_count, size, ?policy,
+                "Checking if rollover needed"
+            );
+        }
+        if policy.should_rotate(line_count, size) {
+            last_log_id + 1
+        } else {
+            last_log_id
+        }
     }
 }
@@ -1,5 +1,5 @@
-use crate::engine::core::InnerWalWriter;
 use crate::engine::core::WalEntry;
+use crate::engine::core::{InnerWalWriter, WalRotationPolicy};
 use crate::test_helpers::factories::WalEntryFactory;
 use std::fs;
 use tempfile::tempdir;
@@ -64,3 +64,28 @@ fn test_log_rotation_creates_new_file() {
 
     assert_eq!(files.len(), 2);
 }
+
+#[test]
+fn test_rotation_policy_limits_segment_bytes() {
+    let dir = tempdir().unwrap();
+    let wal_dir = dir.path().to_path_buf();
+    let entry = WalEntryFactory::new().create();
+    let line_len = serde_json::to_string(&entry).unwrap().len() as u64 + 1;
+    let policy = WalRotationPolicy {
+        max_segment_bytes: Some(line_len * 2),
+        max_segment_entries: None,
+    };
+
+    let mut writer = InnerWalWriter::with_policy(wal_dir.clone(), policy).unwrap();
+    writer.start_next_log_file().unwrap();
+    writer.append_immediate(&entry).unwrap();
+    assert!(!writer.should_rotate());
+    writer.append_immediate(&entry).unwrap();
+    assert_eq!(writer.bytes_written, line_len * 2);
+    assert!(writer.should_rotate());
+    writer.flush_and_close().unwrap();
+
+    // A reopened writer starts a new segment instead of growing the full one
+    let reopened = InnerWalWriter::with_policy(wal_dir, policy).unwrap();
+    assert_eq!(reopened.current_log_id, writer.current_log_id + 1);
+}
@@ -5,6 +5,7 @@ pub mod wal_archiver;
 pub mod wal_cleaner;
 pub mod wal_entry;
 pub mod wal_handle;
+pub mod wal_inspector;
 pub mod wal_recovery;
 
 #[cfg(test)]
@@ -22,4 +23,6 @@ mod wal_entry_test;
 #[cfg(test)]
 mod wal_handle_test;
 #[cfg(test)]
+mod wal_inspector_test;
+#[cfg(test)]
 mod wal_recovery_test;
@@ -1,12 +1,15 @@
 use std::path::{Path, PathBuf};
 use tokio::sync::mpsc::{self, Sender};
+use tokio::sync::oneshot;
 
-use crate::engine::core::{InnerWalWriter, WalEntry};
-use crate::shared::config::CONFIG;
+use crate::engine::core::{InnerWalWriter, WalEntry, WalRotationPolicy};
 use tracing::{debug, error, info};
 
 pub enum WalMessage {
     Entry(WalEntry),
+    /// Closes the current segment; replies with the id of the segment that
+    /// receives the next entry.
+    Rotate(oneshot::Sender<u64>),
     Shutdown,
 }
 
@@ -15,6 +18,7 @@ pub struct WalHandle {
     sender: Option<Sender<WalMessage>>,
     base_dir: PathBuf,
     shard_id: usize,
+    policy: WalRotationPolicy,
 }
 
 impl WalHandle {
@@ -31,9 +35,16 @@ impl WalHandle {
             sender: None,
             base_dir: wal_dir,
             shard_id,
+            policy: WalRotationPolicy::from_config(),
         })
     }
 
+    /// Overrides the rotation policy taken from `[wal]`.
+    pub fn with_rotation_policy(mut self, policy: WalRotationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     pub async fn append(&self, entry: WalEntry) {
         if let Some(sender) = &self.sender {
             debug!(
@@ -51,6 +62,18 @@ impl WalHandle {
         }
     }
 
+    /// Ends the current segment after every entry appended before this call.
+    ///
+    /// Returns the id of the first segment holding entries appended afterwards:
+    /// once the entries appended so far are persisted elsewhere, every log below
+    /// that id can be cleaned up. Returns `None` when no writer is running.
+    pub async fn rotate(&self) -> Option<u64> {
+        let sender = self.sender.as_ref()?;
+        let (tx, rx) = oneshot::channel();
+        sender.send(WalMessage::Rotate(tx)).await.ok()?;
+        rx.await.ok()
+    }
+
     pub async fn shutdown(&self) {
         if let Some(sender) = &self.sender {
             info!(
@@ -66,6 +89,7 @@ impl WalHandle {
         let (tx, mut rx) = mpsc::channel(4096);
         let shard_id = self.shard_id;
         let base_dir = self.base_dir.clone();
+        let policy = self.policy;
 
         info!(
             target: "wal_handle::spawn_wal_thread",
@@ -75,7 +99,7 @@ impl WalHandle {
 
         tokio::spawn(async move {
             debug!(target: "wal_handle::spawn_wal_thread", shard_id, "Initializing WAL writer");
-            let mut writer = match InnerWalWriter::new(base_dir.clone()) {
+            let mut writer = match InnerWalWriter::with_policy(base_dir.clone(), policy) {
                 Ok(w) => w,
                 Err(e) => {
                     error!(
@@ -113,8 +137,7 @@ impl WalHandle {
                             "Entry appended to WAL"
                         );
 
-                        let capacity = CONFIG.engine.fill_factor * CONFIG.engine.event_per_zone;
-                        if writer.entries_written >= capacity as u64 {
+                        if writer.should_rotate() {
                             if let Err(err) = writer.rotate_log_file() {
                                 error!(
                                     target: "wal_handle::spawn_wal_thread",
@@ -130,6 +153,27 @@ impl WalHandle {
                             }
                         }
                     }
+                    WalMessage::Rotate(reply) => {
+                        // An empty segment holds nothing older than this call
+                        if writer.entries_written > 0 {
+                            if let Err(err) = writer.rotate_log_file() {
+                                error!(
+                                    target: "wal_handle::spawn_wal_thread",
+                                    shard_id, err = ?err,
+                                    "Requested WAL rotation failed"
+                                );
+                                // Without a boundary nothing may be cleaned up
+                                drop(reply);
+                                continue;
+                            }
+                            info!(
+                                target: "wal_handle::spawn_wal_thread",
+                                shard_id, new_log_id = writer.current_log_id,
+                                "WAL log rotated on request"
+                            );
+                        }
+                        let _ = reply.send(writer.current_log_id);
+                    }
                     WalMessage::Shutdown => {
                         info!(
                             target: "wal_handle::spawn_wal_thread",
@@ -149,6 +193,7 @@ impl WalHandle {
             sender: Some(tx),
             base_dir: self.base_dir.clone(),
             shard_id: self.shard_id,
+            policy: self.policy,
         })
     }
 }
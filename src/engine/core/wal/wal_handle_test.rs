@@ -1,6 +1,7 @@
-use crate::engine::core::{WalEntry, WalHandle};
-use crate::test_helpers::factories::WalEntryFactory;
+use crate::engine::core::{WalEntry, WalHandle, WalRecovery, WalRotationPolicy};
+use crate::test_helpers::factories::{ShardContextFactory, WalEntryFactory};
 use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::time::{Duration, sleep};
 use tracing::info;
@@ -69,3 +70,94 @@ async fn test_spawn_wal_thread_writes_entries_and_shuts_down() {
     // Clean up
     fs::remove_dir_all(&wal_dir).unwrap();
 }
+
+fn logs_by_id(dir: &Path) -> Vec<(u64, Vec<WalEntry>)> {
+    let mut logs: Vec<(u64, Vec<WalEntry>)> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let id = name
+                .strip_prefix("wal-")?
+                .strip_suffix(".log")?
+                .parse()
+                .ok()?;
+            let entries = fs::read_to_string(&path)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect();
+            Some((id, entries))
+        })
+        .collect();
+    logs.sort_by_key(|(id, _)| *id);
+    logs
+}
+
+#[tokio::test]
+async fn test_rotation_mid_batch_keeps_every_entry_exactly_once() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let wal_dir = tempfile::tempdir().unwrap();
+    let entries: Vec<WalEntry> = (0..8u64)
+        .map(|i| {
+            WalEntryFactory::new()
+                .with("timestamp", 1_000 + i)
+                .with("context_id", "ctx-rotate")
+                .create()
+        })
+        .collect();
+    let line_len = serde_json::to_string(&entries[0]).unwrap().len() as u64 + 1;
+
+    // Segments fill up after three entries, so the batch crosses size-based
+    // rotations as well as the requested one
+    let wal = WalHandle::new(0, wal_dir.path())
+        .unwrap()
+        .with_rotation_policy(WalRotationPolicy {
+            max_segment_bytes: Some(line_len * 3),
+            max_segment_entries: None,
+        })
+        .spawn_wal_thread()
+        .unwrap();
+
+    for entry in &entries[..5] {
+        wal.append(entry.clone()).await;
+    }
+    let cutoff = wal.rotate().await.expect("WAL writer is running");
+    for entry in &entries[5..] {
+        wal.append(entry.clone()).await;
+    }
+    wal.rotate().await.expect("WAL writer is running");
+    wal.shutdown().await;
+
+    let logs = logs_by_id(wal_dir.path());
+    assert!(
+        logs.len() > 2,
+        "expected several segments, got {}",
+        logs.len()
+    );
+    for (_, segment) in &logs {
+        assert!(segment.len() <= 3);
+    }
+
+    let timestamps = |keep: &dyn Fn(u64) -> bool| -> Vec<u64> {
+        logs.iter()
+            .filter(|(id, _)| keep(*id))
+            .flat_map(|(_, segment)| segment.iter().map(|e| e.timestamp))
+            .collect()
+    };
+    assert_eq!(
+        timestamps(&|id| id < cutoff),
+        vec![1_000, 1_001, 1_002, 1_003, 1_004]
+    );
+    assert_eq!(timestamps(&|id| id >= cutoff), vec![1_005, 1_006, 1_007]);
+
+    // Recovery replays the segments back into one ordered stream
+    let mut ctx = ShardContextFactory::new().with_capacity(100).create();
+    WalRecovery::new(0, &wal_dir.path().to_path_buf())
+        .recover(&mut ctx)
+        .expect("WAL recovery failed");
+    let recovered: Vec<u64> = ctx.memtable.iter().map(|e| e.timestamp).collect();
+    assert_eq!(recovered, (1_000..1_008).collect::<Vec<_>>());
+}
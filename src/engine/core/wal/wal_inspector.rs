@@ -0,0 +1,167 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::engine::core::wal::wal_archive::WalArchive;
+use crate::engine::core::wal::wal_archive_recovery::WalArchiveRecovery;
+use crate::engine::core::wal::wal_entry::WalEntry;
+use crate::engine::core::wal::wal_recovery::WalRecovery;
+use tracing::{debug, warn};
+
+/// Where a WAL entry stands relative to the shard's segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalEntryState {
+    /// In a live log: no completed flush covers it yet, so it may only be in memory
+    Unflushed,
+    /// In an archived log, which is only written once its events are flushed and verified
+    Flushed,
+}
+
+impl WalEntryState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WalEntryState::Unflushed => "unflushed",
+            WalEntryState::Flushed => "flushed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InspectedWalEntry {
+    pub log_id: u64,
+    pub state: WalEntryState,
+    pub entry: WalEntry,
+}
+
+/// The most recent entries of a shard's WAL, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct WalInspection {
+    pub entries: Vec<InspectedWalEntry>,
+    /// Whether older matching entries were left out to respect the limit
+    pub truncated: bool,
+    /// Lines of live logs that did not decode, such as one being appended
+    pub skipped_lines: usize,
+    /// Archives that failed to decompress or decode
+    pub skipped_archives: usize,
+}
+
+/// Reads a shard's WAL without touching the writer: the live logs, then,
+/// when an archive directory is given, the compressed archives of logs that
+/// were already flushed.
+#[derive(Debug, Clone)]
+pub struct WalInspector {
+    shard_id: usize,
+    wal_dir: PathBuf,
+    archive_dir: Option<PathBuf>,
+}
+
+impl WalInspector {
+    pub fn new(shard_id: usize, wal_dir: PathBuf) -> Self {
+        Self {
+            shard_id,
+            wal_dir,
+            archive_dir: None,
+        }
+    }
+
+    pub fn with_archive_dir(mut self, archive_dir: Option<PathBuf>) -> Self {
+        self.archive_dir = archive_dir;
+        self
+    }
+
+    /// The last `limit` entries, of `event_type` only when given. Logs are
+    /// read newest first and reading stops once `limit` entries are found,
+    /// so older logs and archives are never opened.
+    pub fn recent(&self, event_type: Option<&str>, limit: usize) -> std::io::Result<WalInspection> {
+        let mut inspection = WalInspection::default();
+        let mut newest_first = Vec::new();
+        let mut collect = |log_id: u64, state: WalEntryState, entries: Vec<WalEntry>| -> bool {
+            for entry in entries.into_iter().rev() {
+                if event_type.is_some_and(|t| t != entry.event_type) {
+                    continue;
+                }
+                if newest_first.len() == limit {
+                    return false;
+                }
+                newest_first.push(InspectedWalEntry {
+                    log_id,
+                    state,
+                    entry,
+                });
+            }
+            true
+        };
+
+        let logs = if self.wal_dir.exists() {
+            WalRecovery::list_log_ids(&self.wal_dir)?
+        } else {
+            Vec::new()
+        };
+        let mut more = true;
+        for (log_id, path) in logs.into_iter().rev() {
+            let (entries, skipped) = match read_log(&path) {
+                Ok(read) => read,
+                // Cleaned up since listing: its entries were flushed
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            inspection.skipped_lines += skipped;
+            more = collect(log_id, WalEntryState::Unflushed, entries);
+            if !more {
+                break;
+            }
+        }
+
+        if more && let Some(archive_dir) = &self.archive_dir {
+            let recovery = WalArchiveRecovery::new(self.shard_id, archive_dir.clone());
+            for path in recovery.list_archives()?.into_iter().rev() {
+                let Ok(archive) = WalArchive::read_from_file(&path) else {
+                    warn!(
+                        target: "wal_inspector::recent",
+                        shard_id = self.shard_id, ?path,
+                        "Skipping unreadable WAL archive"
+                    );
+                    inspection.skipped_archives += 1;
+                    continue;
+                };
+                more = collect(
+                    archive.header.log_id,
+                    WalEntryState::Flushed,
+                    archive.body.entries,
+                );
+                if !more {
+                    break;
+                }
+            }
+        }
+
+        debug!(
+            target: "wal_inspector::recent",
+            shard_id = self.shard_id,
+            entries = newest_first.len(),
+            truncated = !more,
+            "Inspected WAL"
+        );
+        newest_first.reverse();
+        inspection.entries = newest_first;
+        inspection.truncated = !more;
+        Ok(inspection)
+    }
+}
+
+/// Decodes one live log, counting the lines that do not decode.
+fn read_log(path: &Path) -> std::io::Result<(Vec<WalEntry>, usize)> {
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<WalEntry>(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(_) => skipped += 1,
+        }
+    }
+    Ok((entries, skipped))
+}
@@ -0,0 +1,152 @@
+use crate::engine::core::WalEntry;
+use crate::engine::core::wal::wal_archiver::WalArchiver;
+use crate::engine::core::wal::wal_inspector::{WalEntryState, WalInspector};
+use crate::test_helpers::factories::WalEntryFactory;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn entry(event_type: &str, context_id: &str) -> WalEntry {
+    WalEntryFactory::new()
+        .with("event_type", event_type)
+        .with("context_id", context_id)
+        .create()
+}
+
+fn write_log(dir: &Path, log_id: u64, entries: &[WalEntry]) {
+    let mut file = File::create(dir.join(format!("wal-{:05}.log", log_id))).unwrap();
+    for entry in entries {
+        writeln!(file, "{}", serde_json::to_string(entry).unwrap()).unwrap();
+    }
+}
+
+fn contexts(inspector: &WalInspector, event_type: Option<&str>, limit: usize) -> Vec<String> {
+    inspector
+        .recent(event_type, limit)
+        .unwrap()
+        .entries
+        .into_iter()
+        .map(|e| e.entry.context_id)
+        .collect()
+}
+
+#[test]
+fn recent_returns_the_newest_entries_oldest_first() {
+    let wal_dir = TempDir::new().unwrap();
+    write_log(
+        wal_dir.path(),
+        1,
+        &[entry("order", "a"), entry("order", "b")],
+    );
+    write_log(wal_dir.path(), 2, &[entry("order", "c")]);
+    let inspector = WalInspector::new(0, wal_dir.path().to_path_buf());
+
+    let inspection = inspector.recent(None, 2).unwrap();
+    let logged: Vec<(u64, &str)> = inspection
+        .entries
+        .iter()
+        .map(|e| (e.log_id, e.entry.context_id.as_str()))
+        .collect();
+    assert_eq!(logged, vec![(1, "b"), (2, "c")]);
+    assert!(inspection.truncated);
+    assert!(
+        inspection
+            .entries
+            .iter()
+            .all(|e| e.state == WalEntryState::Unflushed)
+    );
+
+    let inspection = inspector.recent(None, 3).unwrap();
+    assert_eq!(inspection.entries.len(), 3);
+    assert!(!inspection.truncated);
+}
+
+#[test]
+fn recent_filters_by_event_type_before_applying_the_limit() {
+    let wal_dir = TempDir::new().unwrap();
+    write_log(
+        wal_dir.path(),
+        1,
+        &[
+            entry("order", "a"),
+            entry("click", "b"),
+            entry("order", "c"),
+        ],
+    );
+    write_log(wal_dir.path(), 2, &[entry("click", "d")]);
+    let inspector = WalInspector::new(0, wal_dir.path().to_path_buf());
+
+    assert_eq!(contexts(&inspector, Some("order"), 2), vec!["a", "c"]);
+    assert_eq!(contexts(&inspector, Some("click"), 1), vec!["d"]);
+    assert!(contexts(&inspector, Some("refund"), 10).is_empty());
+}
+
+#[test]
+fn recent_reads_compressed_archives_as_flushed_entries() {
+    let wal_dir = TempDir::new().unwrap();
+    let archive_dir = TempDir::new().unwrap();
+    write_log(
+        wal_dir.path(),
+        1,
+        &[entry("order", "a"), entry("order", "b")],
+    );
+    WalArchiver::with_dirs(
+        0,
+        wal_dir.path().to_path_buf(),
+        archive_dir.path().to_path_buf(),
+        3,
+    )
+    .archive_log(1)
+    .unwrap();
+    fs::remove_file(wal_dir.path().join("wal-00001.log")).unwrap();
+    write_log(wal_dir.path(), 2, &[entry("order", "c")]);
+
+    let inspector = WalInspector::new(0, wal_dir.path().to_path_buf())
+        .with_archive_dir(Some(archive_dir.path().to_path_buf()));
+    let inspection = inspector.recent(None, 10).unwrap();
+    let states: Vec<(&str, WalEntryState)> = inspection
+        .entries
+        .iter()
+        .map(|e| (e.entry.context_id.as_str(), e.state))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            ("a", WalEntryState::Flushed),
+            ("b", WalEntryState::Flushed),
+            ("c", WalEntryState::Unflushed),
+        ]
+    );
+    assert_eq!(inspection.entries[0].log_id, 1);
+
+    // The live log alone fills the limit, so the archive is never opened
+    fs::write(archive_dir.path().join("wal-00000-0-0.wal.zst"), b"garbage").unwrap();
+    let inspection = inspector.recent(None, 1).unwrap();
+    assert_eq!(inspection.skipped_archives, 0);
+    let inspection = inspector.recent(None, 10).unwrap();
+    assert_eq!(inspection.skipped_archives, 1);
+    assert_eq!(inspection.entries.len(), 3);
+}
+
+#[test]
+fn recent_counts_undecodable_lines_and_tolerates_a_missing_directory() {
+    let wal_dir = TempDir::new().unwrap();
+    write_log(wal_dir.path(), 1, &[entry("order", "a")]);
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .open(wal_dir.path().join("wal-00001.log"))
+        .unwrap();
+    write!(file, "{{\"timestamp\": 12").unwrap();
+
+    let inspection = WalInspector::new(0, wal_dir.path().to_path_buf())
+        .recent(None, 10)
+        .unwrap();
+    assert_eq!(inspection.entries.len(), 1);
+    assert_eq!(inspection.skipped_lines, 1);
+
+    let missing = WalInspector::new(0, wal_dir.path().join("shard-9"))
+        .recent(None, 10)
+        .unwrap();
+    assert!(missing.entries.is_empty());
+}
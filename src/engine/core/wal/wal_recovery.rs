@@ -47,14 +47,33 @@ impl WalRecovery {
         Ok(())
     }
 
+    /// WAL logs ordered by log id. Ids are compared numerically, since their
+    /// zero padding stops lining up names once an id outgrows it.
     fn list_sorted_log_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
-        let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        Ok(Self::list_log_ids(dir)?
+            .into_iter()
+            .map(|(_, path)| path)
+            .collect())
+    }
+
+    /// WAL logs with their log ids, ordered by id.
+    pub(crate) fn list_log_ids(dir: &Path) -> std::io::Result<Vec<(u64, PathBuf)>> {
+        let mut files: Vec<(u64, PathBuf)> = fs::read_dir(dir)?
             .flatten()
             .map(|e| e.path())
-            .filter(|p| p.extension().map(|ext| ext == "log").unwrap_or(false))
+            .filter_map(|p| {
+                let id = p
+                    .file_name()?
+                    .to_str()?
+                    .strip_prefix("wal-")?
+                    .strip_suffix(".log")?
+                    .parse::<u64>()
+                    .ok()?;
+                Some((id, p))
+            })
             .collect();
 
-        files.sort();
+        files.sort_by_key(|(id, _)| *id);
         Ok(files)
     }
 
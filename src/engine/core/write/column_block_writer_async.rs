@@ -98,6 +98,8 @@ impl ColumnBlockWriterAsync {
                 comp_len: compressed.len() as u32,
                 uncomp_len: uncompressed_len,
                 num_rows: row_count,
+                codec: codec.algo_id(),
+                checksum: Some(crc32fast::hash(&compressed)),
             },
         );
 
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use crate::engine::core::column::format::{ColumnBlockHeader, PhysicalType};
 use crate::engine::core::{ColumnKey, WriteJob};
-use crate::engine::types::ScalarValue;
+use crate::engine::types::{ScalarValue, decimal, temporal, uuid};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
 
 pub struct ColumnGroupBuilder {
@@ -48,6 +48,11 @@ impl ColumnGroupBuilder {
             ScalarValue::Timestamp(ts) => ts.to_string(),
             ScalarValue::Float64(f) => f.to_string(),
             ScalarValue::Boolean(b) => b.to_string(),
+            ScalarValue::Decimal(m, s) => decimal::format(*m, *s),
+            // In the units Date and Time fields store
+            ScalarValue::Date(d) => temporal::epoch_seconds_of_day(*d).to_string(),
+            ScalarValue::Time(t) => t.to_string(),
+            ScalarValue::Uuid(u) => uuid::format(u),
             ScalarValue::Null => String::new(),
             ScalarValue::Binary(bytes) => BASE64_STANDARD.encode(bytes),
         };
@@ -210,6 +215,37 @@ impl ColumnGroupBuilder {
                     let lengths = vec![0u32; row_count as usize];
                     out.insert(key_zone, (buf, lengths, values));
                 }
+                PhysicalType::Uuid => {
+                    // Fixed 16 bytes per row; nulls bitset in aux if any
+                    let mut nulls: Vec<u8> = vec![0u8; (row_count as usize).div_ceil(8)];
+                    let mut payload: Vec<u8> = Vec::with_capacity((row_count as usize) * 16);
+                    let mut any_nulls = false;
+                    for (i, s) in values.iter().enumerate() {
+                        if let Some(bytes) = uuid::parse(s) {
+                            payload.extend_from_slice(&bytes);
+                        } else {
+                            any_nulls = true;
+                            nulls[i / 8] |= 1 << (i % 8);
+                            payload.extend_from_slice(&[0u8; 16]);
+                        }
+                    }
+                    let aux_len = if any_nulls { nulls.len() } else { 0 };
+                    let mut buf: Vec<u8> =
+                        Vec::with_capacity(ColumnBlockHeader::LEN + aux_len + payload.len());
+                    let header = ColumnBlockHeader::new(
+                        PhysicalType::Uuid,
+                        any_nulls,
+                        row_count,
+                        aux_len as u32,
+                    );
+                    header.write_to(&mut buf);
+                    if any_nulls {
+                        buf.extend_from_slice(&nulls);
+                    }
+                    buf.extend_from_slice(&payload);
+                    let lengths = vec![0u32; row_count as usize];
+                    out.insert(key_zone, (buf, lengths, values));
+                }
                 _ => {
                     // VarBytes (default)
                     let mut lengths: Vec<u32> = Vec::with_capacity(values.len());
@@ -1,5 +1,5 @@
+use crate::engine::core::column::compression::BlockCodec;
 use crate::engine::core::column::compression::compressed_column_index::CompressedColumnIndex;
-use crate::engine::core::column::compression::compression_codec::Lz4Codec;
 use crate::engine::core::column::format::PhysicalType;
 use crate::engine::core::column::type_catalog::ColumnTypeCatalog;
 use crate::engine::core::write::column_block_writer_async::ColumnBlockWriterAsync;
@@ -17,6 +17,7 @@ pub struct ColumnWriter {
     pub segment_dir: PathBuf,
     pub registry: Arc<RwLock<SchemaRegistry>>,
     type_hints: Option<ColumnTypeCatalog>,
+    codec: BlockCodec,
 }
 
 impl ColumnWriter {
@@ -25,9 +26,16 @@ impl ColumnWriter {
             segment_dir,
             registry,
             type_hints: None,
+            codec: BlockCodec::default(),
         }
     }
 
+    /// Sets the codec blocks are compressed with; LZ4 by default.
+    pub fn with_codec(mut self, codec: BlockCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
     pub fn with_type_hints(mut self, hints: ColumnTypeCatalog) -> Self {
         self.type_hints = Some(hints);
         self
@@ -72,17 +80,21 @@ impl ColumnWriter {
                     match schema.field_type(field) {
                         Some(FieldType::I64)
                         | Some(FieldType::Timestamp)
-                        | Some(FieldType::Date) => PhysicalType::I64,
+                        | Some(FieldType::Date)
+                        | Some(FieldType::Time) => PhysicalType::I64,
                         Some(FieldType::U64) => PhysicalType::U64,
                         Some(FieldType::F64) => PhysicalType::F64,
                         Some(FieldType::Bool) => PhysicalType::Bool,
+                        Some(FieldType::Uuid) => PhysicalType::Uuid,
                         Some(FieldType::Optional(inner)) => match inner.as_ref() {
-                            FieldType::I64 | FieldType::Timestamp | FieldType::Date => {
-                                PhysicalType::I64
-                            }
+                            FieldType::I64
+                            | FieldType::Timestamp
+                            | FieldType::Date
+                            | FieldType::Time => PhysicalType::I64,
                             FieldType::U64 => PhysicalType::U64,
                             FieldType::F64 => PhysicalType::F64,
                             FieldType::Bool => PhysicalType::Bool,
+                            FieldType::Uuid => PhysicalType::Uuid,
                             _ => PhysicalType::VarBytes,
                         },
                         _ => PhysicalType::VarBytes,
@@ -104,7 +116,7 @@ impl ColumnWriter {
         let groups = builder.finish();
 
         // Now do async file I/O
-        let codec = Lz4Codec;
+        let codec = self.codec;
         let mut indexes_by_key: std::collections::HashMap<(String, String), CompressedColumnIndex> =
             std::collections::HashMap::new();
         // Precompute exact .col paths from jobs to ensure the same paths used in tests
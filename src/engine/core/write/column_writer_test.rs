@@ -1,4 +1,8 @@
-use crate::engine::core::column::compression::{CompressedColumnIndex, CompressionCodec, Lz4Codec};
+use crate::engine::core::column::column_reader::ColumnReader;
+use crate::engine::core::column::compression::compression_codec::ALGO_ZSTD;
+use crate::engine::core::column::compression::{
+    BlockCodec, CompressedColumnIndex, CompressionCodec, Lz4Codec,
+};
 use crate::engine::core::column::format::{ColumnBlockHeader, PhysicalType};
 use crate::engine::core::column::type_catalog::ColumnTypeCatalog;
 use crate::engine::core::{ColumnWriter, ZonePlan};
@@ -173,3 +177,119 @@ async fn uses_type_hints_when_schema_falls_back_to_varbytes() {
         ColumnBlockHeader::read_from(&decompressed[..ColumnBlockHeader::LEN]).expect("column hdr");
     assert_eq!(PhysicalType::from(col_hdr.phys), PhysicalType::U64);
 }
+
+#[tokio::test]
+async fn records_the_block_codec_and_reads_back_through_it() {
+    let dir = tempdir().unwrap();
+    let registry_factory = SchemaRegistryFactory::new();
+    let registry = registry_factory.registry();
+    registry_factory
+        .define_with_fields("archived", &[("device", "string")])
+        .await
+        .expect("schema define");
+
+    let events = EventFactory::new()
+        .with("event_type", "archived")
+        .with("payload", json!({ "device": "mobile" }))
+        .create_list(3);
+    let zone = ZonePlan {
+        id: 0,
+        start_index: 0,
+        end_index: 2,
+        events,
+        uid: "uid-archived".into(),
+        event_type: "archived".into(),
+        segment_id: 30_000,
+        created_at: 0,
+    };
+
+    let writer = ColumnWriter::new(dir.path().to_path_buf(), registry)
+        .with_codec(BlockCodec::Zstd { level: 19 });
+    writer.write_all(&[zone]).await.expect("write_all");
+
+    // Files are named after the uid the registry assigned to the event type
+    let zfc = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| p.to_string_lossy().ends_with("_device.zfc"))
+        .expect("device zfc");
+    let index = CompressedColumnIndex::load_from_path(&zfc).expect("load zfc");
+    assert_eq!(index.entries.get(&0).expect("zfc entry").codec, ALGO_ZSTD);
+
+    let file_name = zfc.file_name().unwrap().to_string_lossy().into_owned();
+    let uid = file_name.trim_end_matches("_device.zfc");
+    let values = ColumnReader::load_for_zone(dir.path(), "30000", uid, "device", 0)
+        .expect("read zstd block");
+    assert_eq!(values, vec!["mobile"; 3]);
+}
+
+#[tokio::test]
+async fn writes_uuid_fields_as_sixteen_byte_blocks() {
+    let dir = tempdir().unwrap();
+    let registry_factory = SchemaRegistryFactory::new();
+    let registry = registry_factory.registry();
+    registry_factory
+        .define_with_fields("visit", &[("visitor", "uuid | null")])
+        .await
+        .expect("schema define");
+
+    let visitors = [
+        json!("550e8400-e29b-41d4-a716-446655440000"),
+        json!(null),
+        json!("00000000-0000-0000-0000-0000000000ff"),
+    ];
+    let events = visitors
+        .iter()
+        .map(|visitor| {
+            EventFactory::new()
+                .with("event_type", "visit")
+                .with("payload", json!({ "visitor": visitor }))
+                .create()
+        })
+        .collect();
+    let zone = ZonePlan {
+        id: 0,
+        start_index: 0,
+        end_index: 2,
+        events,
+        uid: "uid-visit".into(),
+        event_type: "visit".into(),
+        segment_id: 40_000,
+        created_at: 0,
+    };
+    ColumnWriter::new(dir.path().to_path_buf(), registry)
+        .write_all(&[zone])
+        .await
+        .expect("write_all");
+
+    let zfc = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| p.to_string_lossy().ends_with("_visitor.zfc"))
+        .expect("visitor zfc");
+    let index = CompressedColumnIndex::load_from_path(&zfc).expect("load zfc");
+    // Header, one byte of null bits, then 16 bytes a row
+    let entry = index.entries.get(&0).expect("zfc entry");
+    assert_eq!(
+        entry.uncomp_len as usize,
+        ColumnBlockHeader::LEN + 1 + 3 * 16
+    );
+
+    let file_name = zfc.file_name().unwrap().to_string_lossy().into_owned();
+    let uid = file_name.trim_end_matches("_visitor.zfc");
+    let snapshot =
+        ColumnReader::load_for_zone_snapshot(dir.path(), "40000", uid, "visitor", 0, None)
+            .expect("read uuid block");
+    assert_eq!(snapshot.physical_type(), PhysicalType::Uuid);
+    let values = snapshot.values();
+    assert_eq!(values.get_uuid_at(2).map(|u| u[15]), Some(0xff));
+    assert_eq!(values.get_uuid_at(1), None);
+    assert_eq!(
+        snapshot.to_strings(),
+        vec![
+            "550e8400-e29b-41d4-a716-446655440000",
+            "",
+            "00000000-0000-0000-0000-0000000000ff"
+        ]
+    );
+}
@@ -1,3 +1,4 @@
+use crate::engine::core::utils::worker_pools::spawn_background;
 use crate::engine::core::{FlushWorker, InflightSegments, MemTable, SegmentLifecycleTracker};
 use crate::engine::errors::StoreError;
 use crate::engine::schema::registry::SchemaRegistry;
@@ -17,6 +18,7 @@ pub struct FlushManager {
         Arc<TokioRwLock<SchemaRegistry>>,
         Arc<Mutex<MemTable>>,
         u64,
+        Option<u64>,
         Option<oneshot::Sender<Result<(), StoreError>>>,
     )>,
     segment_ids: Arc<RwLock<Vec<String>>>,
@@ -36,7 +38,7 @@ impl FlushManager {
     ) -> Self {
         let (tx, rx) = tokio::sync::mpsc::channel(4096);
 
-        // Spawn the flush worker task
+        // Spawn the flush worker task on the background pool
         let worker = FlushWorker::new(
             shard_id,
             base_dir.clone(),
@@ -47,7 +49,7 @@ impl FlushManager {
             inflight_segments.clone(),
         );
 
-        let worker_handle = tokio::spawn(async move {
+        let worker_handle = spawn_background(async move {
             let result = worker.run(rx).await;
             match &result {
                 Ok(()) => {
@@ -107,7 +109,11 @@ impl FlushManager {
         }
     }
 
-    /// Queues a full MemTable for flushing
+    /// Queues a full MemTable for flushing.
+    ///
+    /// `wal_cutoff` is the first WAL log holding events newer than
+    /// `full_memtable`; older logs are cleaned up once the segment is verified.
+    #[allow(clippy::too_many_arguments)]
     pub async fn queue_for_flush(
         &self,
         full_memtable: MemTable,
@@ -115,6 +121,7 @@ impl FlushManager {
         segment_id: u64,
         passive_memtable: Arc<Mutex<MemTable>>,
         flush_id: u64,
+        wal_cutoff: Option<u64>,
         completion: Option<oneshot::Sender<Result<(), StoreError>>>,
     ) -> Result<(), StoreError> {
         if tracing::enabled!(tracing::Level::DEBUG) {
@@ -135,6 +142,7 @@ impl FlushManager {
                 Arc::clone(&schema_registry),
                 Arc::clone(&passive_memtable),
                 flush_id,
+                wal_cutoff,
                 completion,
             ))
             .await
@@ -62,6 +62,7 @@ async fn test_flush_manager_queues_and_flushes_memtable() {
             Arc::new(tokio::sync::Mutex::new(memtable_clone)),
             flush_id,
             None,
+            None,
         )
         .await
         .expect("FlushManager failed");
@@ -156,6 +157,7 @@ async fn test_flush_manager_inserts_segment_into_inflight_on_queue() {
             Arc::new(tokio::sync::Mutex::new(memtable_clone)),
             flush_id,
             None,
+            None,
         )
         .await
         .expect("FlushManager failed");
@@ -55,11 +55,19 @@ impl FlushWorker {
             Arc<TokioRwLock<SchemaRegistry>>,
             Arc<tokio::sync::Mutex<MemTable>>,
             u64,
+            Option<u64>,
             Option<oneshot::Sender<Result<(), StoreError>>>,
         )>,
     ) -> Result<(), StoreError> {
-        while let Some((segment_id, memtable, registry, passive_memtable, flush_id, completion)) =
-            rx.recv().await
+        while let Some((
+            segment_id,
+            memtable,
+            registry,
+            passive_memtable,
+            flush_id,
+            wal_cutoff,
+            completion,
+        )) = rx.recv().await
         {
             let inflight_guard = self.inflight_segments.guard(format!("{:05}", segment_id));
             let segment_dir = SegmentId::from(segment_id as u32).join_dir(&self.base_dir);
@@ -205,17 +213,19 @@ impl FlushWorker {
                         // Note: Passive buffer is now empty and will be filtered out by
                         // PassiveBufferSet::non_empty() in subsequent queries
 
-                        // Clean up WAL files
-                        if tracing::enabled!(tracing::Level::DEBUG) {
-                            debug!(
-                                target: "sneldb::flush",
-                                shard_id,
-                                wal_cutoff = segment_id + 1,
-                                "Cleaning up WAL files"
-                            );
+                        // Clean up WAL logs that only hold events of this segment
+                        if let Some(wal_cutoff) = wal_cutoff {
+                            if tracing::enabled!(tracing::Level::DEBUG) {
+                                debug!(
+                                    target: "sneldb::flush",
+                                    shard_id,
+                                    wal_cutoff,
+                                    "Cleaning up WAL files"
+                                );
+                            }
+                            let cleaner = WalCleaner::new(shard_id);
+                            cleaner.cleanup_up_to(wal_cutoff);
                         }
-                        let cleaner = WalCleaner::new(shard_id);
-                        cleaner.cleanup_up_to(segment_id + 1);
                     }
                 }
 
@@ -69,6 +69,7 @@ async fn test_flush_worker_processes_memtable() {
         Arc::new(tokio::sync::Mutex::new(memtable_clone)),
         flush_id,
         None,
+        None,
     ))
     .await
     .expect("Send failed");
@@ -198,6 +199,7 @@ async fn test_flush_worker_skips_cleanup_for_empty_memtable() {
         Arc::clone(&registry),
         Arc::clone(&passive_memtable_arc),
         flush_id,
+        None,
         Some(completion_tx),
     ))
     .await
@@ -326,6 +328,7 @@ async fn test_flush_worker_ticket_completed_even_on_failure() {
         Arc::clone(&registry),
         Arc::new(tokio::sync::Mutex::new(memtable_clone)),
         flush_id,
+        None,
         Some(completion_tx),
     ))
     .await
@@ -401,6 +404,7 @@ async fn test_flush_worker_segment_ids_not_updated_on_verification_failure() {
         Arc::clone(&registry),
         Arc::new(tokio::sync::Mutex::new(memtable_clone)),
         flush_id,
+        None,
         Some(completion_tx),
     ))
     .await
@@ -517,6 +521,7 @@ async fn test_flush_worker_passive_buffer_preserved_on_failure() {
         Arc::clone(&registry),
         Arc::clone(&passive_arc),
         flush_id,
+        None,
         Some(completion_tx),
     ))
     .await
@@ -621,6 +626,7 @@ async fn test_inflight_guard_drops_on_flush_failure() {
         Arc::clone(&registry),
         Arc::new(tokio::sync::Mutex::new(memtable_clone)),
         flush_id,
+        None,
         Some(completion_tx),
     ))
     .await
@@ -41,6 +41,16 @@ impl CandidateZone {
         self.uid.as_deref()
     }
 
+    /// Rows loaded for this zone; every loaded column holds one value per row.
+    pub fn row_count(&self) -> usize {
+        self.values.values().next().map_or(0, ColumnValues::len)
+    }
+
+    /// Decompressed bytes of the columns loaded for this zone.
+    pub fn loaded_bytes(&self) -> usize {
+        self.values.values().map(|values| values.block.size).sum()
+    }
+
     pub fn create_all_zones_for_segment(segment_id: &str) -> Vec<Self> {
         let count = CONFIG.engine.fill_factor;
         if tracing::enabled!(tracing::Level::INFO) {
@@ -1,3 +1,4 @@
+use crate::command::types::FieldIndexKind;
 use crate::engine::core::ZonePlan;
 use crate::engine::schema::FieldType;
 use crate::engine::schema::registry::SchemaRegistry;
@@ -115,6 +116,16 @@ impl EnumBitmapIndex {
             .map(|bytes| bytes.iter().any(|b| *b != 0))
             .unwrap_or(false)
     }
+
+    /// Number of rows per variant in a zone, in `variants` order.
+    pub fn zone_counts(&self, zone_id: u32) -> Option<Vec<u64>> {
+        self.zone_bitmaps.get(&zone_id).map(|bitsets| {
+            bitsets
+                .iter()
+                .map(|bits| bits.iter().map(|b| u64::from(b.count_ones())).sum())
+                .collect()
+        })
+    }
 }
 
 pub struct EnumBitmapBuilder<'a> {
@@ -186,7 +197,9 @@ impl<'a> EnumBitmapBuilder<'a> {
             .fields
             .iter()
             .filter_map(|(k, v)| match v {
-                FieldType::Enum(et) => Some((k.clone(), et.variants.clone())),
+                FieldType::Enum(et) if schema.builds_index(k, FieldIndexKind::Enum) => {
+                    Some((k.clone(), et.variants.clone()))
+                }
                 _ => None,
             })
             .collect();
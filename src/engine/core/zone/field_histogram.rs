@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+use crate::command::types::CompareOp;
+use crate::engine::core::ZonePlan;
+use crate::engine::types::ScalarValue;
+use crate::shared::storage_header::{BinaryHeader, FileKind};
+
+/// Equi-depth bucket boundaries. `bounds[0]` is the minimum, `bounds[i]` the upper
+/// bound of bucket `i - 1`; every bucket holds roughly the same number of rows.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum HistogramBounds {
+    Numeric(Vec<f64>),
+    Text(Vec<String>),
+}
+
+impl HistogramBounds {
+    fn buckets(&self) -> usize {
+        match self {
+            HistogramBounds::Numeric(b) => b.len().saturating_sub(1),
+            HistogramBounds::Text(b) => b.len().saturating_sub(1),
+        }
+    }
+}
+
+/// Value distribution of one field within a segment, used for selectivity estimates.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FieldHistogram {
+    /// Rows in the segment for this uid, including rows where the field is missing.
+    pub row_count: u64,
+    pub non_null: u64,
+    pub distinct: u64,
+    pub bounds: HistogramBounds,
+}
+
+impl FieldHistogram {
+    pub const DEFAULT_BUCKETS: usize = 32;
+
+    /// Builds a histogram from the non-null values of a field. Values are numeric only
+    /// when every value is a number; otherwise their string representation is used.
+    pub fn build(values: Vec<ScalarValue>, row_count: u64, buckets: usize) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let non_null = values.len() as u64;
+        let numeric: Option<Vec<f64>> = values
+            .iter()
+            .map(|v| match v {
+                ScalarValue::Int64(_) | ScalarValue::Float64(_) | ScalarValue::Timestamp(_) => {
+                    v.as_f64().filter(|f| f.is_finite())
+                }
+                _ => None,
+            })
+            .collect();
+
+        let (distinct, bounds) = match numeric {
+            Some(mut nums) => {
+                nums.sort_by(|a, b| a.total_cmp(b));
+                let mut distinct = nums.clone();
+                distinct.dedup();
+                (
+                    distinct.len() as u64,
+                    HistogramBounds::Numeric(Self::pick_bounds(&nums, buckets)),
+                )
+            }
+            None => {
+                let mut texts: Vec<String> = values.iter().map(|v| v.to_string_repr()).collect();
+                texts.sort_unstable();
+                let mut distinct = texts.clone();
+                distinct.dedup();
+                (
+                    distinct.len() as u64,
+                    HistogramBounds::Text(Self::pick_bounds(&texts, buckets)),
+                )
+            }
+        };
+
+        Some(Self {
+            row_count: row_count.max(non_null),
+            non_null,
+            distinct,
+            bounds,
+        })
+    }
+
+    fn pick_bounds<T: Clone>(sorted: &[T], buckets: usize) -> Vec<T> {
+        let buckets = buckets.min(sorted.len().saturating_sub(1)).max(1);
+        let last = sorted.len() - 1;
+        (0..=buckets)
+            .map(|k| sorted[(k * last + buckets / 2) / buckets].clone())
+            .collect()
+    }
+
+    /// Estimated fraction of rows (0.0..=1.0) matching `field <op> value`.
+    /// Returns `None` when the histogram cannot answer (e.g. IN lists or type mismatch).
+    pub fn selectivity(&self, op: &CompareOp, value: &ScalarValue) -> Option<f64> {
+        if self.row_count == 0 {
+            return None;
+        }
+        let (below, eq) = match &self.bounds {
+            HistogramBounds::Numeric(bounds) => {
+                let v = value.as_f64()?;
+                Self::numeric_position(bounds, v, self.distinct)
+            }
+            HistogramBounds::Text(bounds) => {
+                let v = value.to_string_repr();
+                Self::text_position(bounds, &v, self.distinct)
+            }
+        };
+        let matched = match op {
+            CompareOp::Eq => eq,
+            CompareOp::Neq => 1.0 - eq,
+            CompareOp::Lt => below,
+            CompareOp::Lte => below + eq,
+            CompareOp::Gt => 1.0 - below - eq,
+            CompareOp::Gte => 1.0 - below,
+            CompareOp::In => return None,
+        };
+        let present = self.non_null as f64 / self.row_count as f64;
+        Some((matched.clamp(0.0, 1.0)) * present)
+    }
+
+    /// Returns (fraction strictly below `v`, fraction equal to `v`) among non-null values.
+    fn numeric_position(bounds: &[f64], v: f64, distinct: u64) -> (f64, f64) {
+        let buckets = (bounds.len() - 1) as f64;
+        let (min, max) = (bounds[0], bounds[bounds.len() - 1]);
+        if v < min {
+            return (0.0, 0.0);
+        }
+        if v > max {
+            return (1.0, 0.0);
+        }
+        let eq = Self::equal_fraction(
+            bounds.iter().filter(|b| **b == v).count(),
+            buckets,
+            distinct,
+        );
+        let first_ge = bounds.partition_point(|b| *b < v);
+        let below = if first_ge == 0 {
+            0.0
+        } else {
+            let lo = bounds[first_ge - 1];
+            let hi = bounds[first_ge];
+            let within = if hi > lo { (v - lo) / (hi - lo) } else { 0.0 };
+            (first_ge as f64 - 1.0 + within) / buckets
+        };
+        ((below - eq / 2.0).max(0.0), eq)
+    }
+
+    fn text_position(bounds: &[String], v: &str, distinct: u64) -> (f64, f64) {
+        let buckets = (bounds.len() - 1) as f64;
+        if v < bounds[0].as_str() {
+            return (0.0, 0.0);
+        }
+        if v > bounds[bounds.len() - 1].as_str() {
+            return (1.0, 0.0);
+        }
+        let eq = Self::equal_fraction(bounds.iter().filter(|b| *b == v).count(), buckets, distinct);
+        let first_ge = bounds.partition_point(|b| b.as_str() < v);
+        // No interpolation for strings: assume the value sits mid-bucket.
+        let below = if first_ge == 0 {
+            0.0
+        } else {
+            (first_ge as f64 - 0.5) / buckets
+        };
+        ((below - eq / 2.0).max(0.0), eq)
+    }
+
+    /// A value repeated across several boundaries spans that many buckets; otherwise
+    /// assume every distinct value is equally frequent.
+    fn equal_fraction(boundary_hits: usize, buckets: f64, distinct: u64) -> f64 {
+        let uniform = 1.0 / distinct.max(1) as f64;
+        let spanned = boundary_hits.saturating_sub(1) as f64 / buckets;
+        uniform.max(spanned).min(1.0)
+    }
+
+    pub fn buckets(&self) -> usize {
+        self.bounds.buckets()
+    }
+}
+
+/// Per-field histograms for one uid in a segment, persisted as `{uid}.hist`.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FieldHistogramIndex {
+    pub row_count: u64,
+    /// field_name -> histogram
+    pub fields: HashMap<String, FieldHistogram>,
+}
+
+impl FieldHistogramIndex {
+    /// Build histograms for every field except context_id in a single pass over the zones.
+    pub fn build_from_zones(zones: &[ZonePlan]) -> Self {
+        Self::build_from_zones_where(zones, |_| true)
+    }
+
+    /// Like `build_from_zones`, limited to the fields `keep` accepts.
+    pub fn build_from_zones_where<F>(zones: &[ZonePlan], keep: F) -> Self
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut values: HashMap<String, Vec<ScalarValue>> = HashMap::new();
+        let mut row_count = 0u64;
+        for zone in zones {
+            for event in &zone.events {
+                row_count += 1;
+                for field in event.collect_all_fields() {
+                    if field == "context_id" || !keep(&field) {
+                        continue;
+                    }
+                    match event.get_field_scalar(&field) {
+                        Some(ScalarValue::Null) | None => {}
+                        Some(value) => values.entry(field).or_default().push(value),
+                    }
+                }
+            }
+        }
+
+        let fields = values
+            .into_iter()
+            .filter_map(|(field, vals)| {
+                FieldHistogram::build(vals, row_count, FieldHistogram::DEFAULT_BUCKETS)
+                    .map(|h| (field, h))
+            })
+            .collect();
+        Self { row_count, fields }
+    }
+
+    pub fn get(&self, field: &str) -> Option<&FieldHistogram> {
+        self.fields.get(field)
+    }
+
+    /// Persist histograms for a uid as `{uid}.hist` with header and a bincode payload.
+    pub fn save(&self, uid: &str, segment_dir: &Path) -> std::io::Result<()> {
+        let path = segment_dir.join(format!("{}.hist", uid));
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)?;
+        BinaryHeader::new(FileKind::FieldHistogram.magic(), 1, 0).write_to(&mut f)?;
+        let bytes = bincode::serialize(self).map_err(std::io::Error::other)?;
+        f.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Load the histogram file for uid.
+    pub fn load(uid: &str, segment_dir: &Path) -> std::io::Result<Self> {
+        let path = segment_dir.join(format!("{}.hist", uid));
+        let mut reader = std::fs::File::open(&path)?;
+        let header = BinaryHeader::read_from(&mut reader)?;
+        if header.magic != FileKind::FieldHistogram.magic() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid magic for .hist",
+            ));
+        }
+        let bytes = std::fs::read(path)?;
+        let data = &bytes[BinaryHeader::TOTAL_LEN..];
+        bincode::deserialize(data).map_err(std::io::Error::other)
+    }
+}
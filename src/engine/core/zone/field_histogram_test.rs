@@ -0,0 +1,142 @@
+use crate::command::types::CompareOp;
+use crate::engine::core::ZonePlan;
+use crate::engine::core::zone::field_histogram::{
+    FieldHistogram, FieldHistogramIndex, HistogramBounds,
+};
+use crate::engine::types::ScalarValue;
+use crate::test_helpers::factories::EventFactory;
+use tempfile::tempdir;
+
+fn ints(range: std::ops::Range<i64>) -> Vec<ScalarValue> {
+    range.map(ScalarValue::Int64).collect()
+}
+
+fn approx(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 0.05,
+        "expected ~{expected}, got {actual}"
+    );
+}
+
+#[test]
+fn build_numeric_equi_depth_bounds() {
+    let hist = FieldHistogram::build(ints(0..1000), 1000, 10).unwrap();
+    assert_eq!(hist.non_null, 1000);
+    assert_eq!(hist.distinct, 1000);
+    assert_eq!(hist.buckets(), 10);
+    match &hist.bounds {
+        HistogramBounds::Numeric(bounds) => {
+            assert_eq!(bounds.len(), 11);
+            assert_eq!(bounds[0], 0.0);
+            assert_eq!(bounds[10], 999.0);
+        }
+        other => panic!("expected numeric bounds, got {:?}", other),
+    }
+}
+
+#[test]
+fn build_caps_buckets_for_small_inputs() {
+    let hist = FieldHistogram::build(ints(0..3), 3, 32).unwrap();
+    assert_eq!(hist.buckets(), 2);
+    assert!(FieldHistogram::build(vec![], 10, 32).is_none());
+}
+
+#[test]
+fn numeric_range_selectivity_interpolates() {
+    let hist = FieldHistogram::build(ints(0..1000), 1000, 32).unwrap();
+    let v = ScalarValue::Int64(250);
+    approx(hist.selectivity(&CompareOp::Lt, &v).unwrap(), 0.25);
+    approx(hist.selectivity(&CompareOp::Gte, &v).unwrap(), 0.75);
+    approx(
+        hist.selectivity(&CompareOp::Gt, &ScalarValue::Int64(900))
+            .unwrap(),
+        0.1,
+    );
+    assert_eq!(
+        hist.selectivity(&CompareOp::Gt, &ScalarValue::Int64(5000)),
+        Some(0.0)
+    );
+    assert_eq!(
+        hist.selectivity(&CompareOp::Lt, &ScalarValue::Int64(-5)),
+        Some(0.0)
+    );
+}
+
+#[test]
+fn equality_uses_distinct_count_and_heavy_hitters() {
+    let hist = FieldHistogram::build(ints(0..100), 100, 10).unwrap();
+    approx(
+        hist.selectivity(&CompareOp::Eq, &ScalarValue::Int64(42))
+            .unwrap(),
+        0.01,
+    );
+    assert_eq!(
+        hist.selectivity(&CompareOp::Eq, &ScalarValue::Int64(1000)),
+        Some(0.0)
+    );
+
+    // 80% of rows share one value: it spans most buckets
+    let mut skewed = vec![ScalarValue::Int64(7); 80];
+    skewed.extend(ints(100..120));
+    let hist = FieldHistogram::build(skewed, 100, 10).unwrap();
+    let eq = hist
+        .selectivity(&CompareOp::Eq, &ScalarValue::Int64(7))
+        .unwrap();
+    assert!(eq >= 0.6, "heavy hitter estimate too low: {eq}");
+    let neq = hist
+        .selectivity(&CompareOp::Neq, &ScalarValue::Int64(7))
+        .unwrap();
+    approx(eq + neq, 1.0);
+}
+
+#[test]
+fn text_histogram_and_missing_rows() {
+    let values: Vec<ScalarValue> = ["apple", "banana", "cherry", "date"]
+        .iter()
+        .map(|s| ScalarValue::Utf8(s.to_string()))
+        .collect();
+    // Field present on half of the rows
+    let hist = FieldHistogram::build(values, 8, 32).unwrap();
+    assert!(matches!(hist.bounds, HistogramBounds::Text(_)));
+    let eq = hist
+        .selectivity(&CompareOp::Eq, &ScalarValue::Utf8("banana".into()))
+        .unwrap();
+    approx(eq, 0.125);
+    assert_eq!(
+        hist.selectivity(&CompareOp::Eq, &ScalarValue::Utf8("zzz".into())),
+        Some(0.0)
+    );
+    assert_eq!(
+        hist.selectivity(&CompareOp::In, &ScalarValue::Utf8("apple".into())),
+        None
+    );
+}
+
+#[test]
+fn index_builds_from_zones_and_roundtrips() {
+    let events: Vec<_> = (0..50)
+        .map(|i| {
+            EventFactory::new()
+                .with("event_type", "evt")
+                .with("context_id", format!("ctx{}", i))
+                .with("timestamp", 1000 + i)
+                .with("payload", serde_json::json!({ "amount": i, "plan": "pro" }))
+                .create()
+        })
+        .collect();
+    let zones = ZonePlan::build_all(&events, 10, "uid1".to_string(), 1).unwrap();
+
+    let index = FieldHistogramIndex::build_from_zones(&zones);
+    assert_eq!(index.row_count, 50);
+    assert!(index.get("context_id").is_none());
+    assert!(index.get("amount").is_some());
+    assert!(index.get("timestamp").is_some());
+    assert_eq!(index.get("plan").unwrap().distinct, 1);
+
+    let dir = tempdir().unwrap();
+    index.save("uid1", dir.path()).unwrap();
+    let loaded = FieldHistogramIndex::load("uid1", dir.path()).unwrap();
+    assert_eq!(loaded.row_count, 50);
+    assert_eq!(loaded.get("amount"), index.get("amount"));
+    assert!(FieldHistogramIndex::load("missing", dir.path()).is_err());
+}
@@ -1,4 +1,4 @@
-use super::index_build_policy::IndexBuildPolicy;
+use super::index_build_policy::{FieldCategory, IndexBuildPolicy};
 use crate::engine::core::read::catalog::{IndexKind, SegmentIndexCatalog};
 use crate::engine::schema::registry::MiniSchema;
 use crate::engine::schema::types::FieldType;
@@ -57,8 +57,15 @@ impl<'a> IndexBuildPlanner<'a> {
         }
 
         for (name, ty) in &self.schema.fields {
-            let cat = IndexBuildPolicy::categorize(name, ty);
-            let kinds = self.policy.kinds_for_category(cat);
+            let cat = if self.schema.declared_time_fields().any(|f| f == name) {
+                FieldCategory::Temporal
+            } else {
+                IndexBuildPolicy::categorize(name, ty)
+            };
+            let kinds = match self.schema.configured_indexes(name) {
+                Some(configured) => self.policy.kinds_for_configured(cat, configured),
+                None => self.policy.kinds_for_category(cat),
+            };
             plan.per_field.insert(name.clone(), kinds);
         }
 
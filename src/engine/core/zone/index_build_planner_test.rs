@@ -1,14 +1,18 @@
+use crate::command::types::FieldIndexKind;
 use crate::engine::core::read::catalog::{IndexKind, SegmentIndexCatalog};
 use crate::engine::core::zone::{
     index_build_planner::IndexBuildPlanner, index_build_policy::IndexBuildPolicy,
 };
 use crate::engine::schema::registry::MiniSchema;
 use crate::engine::schema::types::{EnumType, FieldType};
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 fn schema(fields: Vec<(&str, FieldType)>) -> MiniSchema {
     let mut s = MiniSchema {
-        fields: HashMap::new(),
+        fields: IndexMap::new(),
+        time_field: None,
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
     };
     for (name, ty) in fields {
         s.fields.insert(name.to_string(), ty);
@@ -130,3 +134,35 @@ fn to_catalog_reflects_plan_exactly() {
     // Verify global kinds match
     assert_eq!(catalog.global_kinds, plan.global);
 }
+
+#[test]
+fn planner_uses_configured_indexes_over_category_defaults() {
+    let mut sch = schema(vec![
+        ("id", FieldType::I64),
+        ("note", FieldType::String),
+        ("created_at", FieldType::Timestamp),
+        ("price", FieldType::F64),
+    ]);
+    sch.indexes
+        .insert("id".to_string(), vec![FieldIndexKind::Surf]);
+    sch.indexes.insert("note".to_string(), Vec::new());
+    sch.indexes.insert(
+        "created_at".to_string(),
+        vec![FieldIndexKind::Xor, FieldIndexKind::Histogram],
+    );
+    let policy = IndexBuildPolicy {
+        enable_rlte_for_primitives: false,
+    };
+    let plan = IndexBuildPlanner::new("u", "00004", &sch, policy).plan();
+
+    assert_eq!(plan.per_field["id"], IndexKind::ZONE_SURF);
+    assert_eq!(plan.per_field["note"], IndexKind::empty());
+    assert_eq!(
+        plan.per_field["created_at"],
+        IndexKind::XOR_FIELD_FILTER | IndexKind::ZONE_XOR_INDEX
+    );
+    assert_eq!(
+        plan.per_field["price"],
+        IndexKind::ZONE_SURF | IndexKind::ZONE_XOR_INDEX | IndexKind::XOR_FIELD_FILTER
+    );
+}
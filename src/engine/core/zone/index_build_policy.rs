@@ -1,3 +1,4 @@
+use crate::command::types::FieldIndexKind;
 use crate::engine::core::read::catalog::IndexKind;
 use crate::engine::schema::types::FieldType;
 
@@ -44,6 +45,24 @@ impl IndexBuildPolicy {
         }
     }
 
+    /// Catalog kinds for indexes a schema configured explicitly. Histograms live in
+    /// `{uid}.hist` rather than the catalog; RLTE still follows the policy.
+    pub fn kinds_for_configured(&self, cat: FieldCategory, kinds: &[FieldIndexKind]) -> IndexKind {
+        let mut out = kinds.iter().fold(IndexKind::empty(), |bits, kind| {
+            bits | match kind {
+                FieldIndexKind::Surf => IndexKind::ZONE_SURF,
+                FieldIndexKind::Xor => IndexKind::XOR_FIELD_FILTER | IndexKind::ZONE_XOR_INDEX,
+                FieldIndexKind::Enum => IndexKind::ENUM_BITMAP,
+                FieldIndexKind::Temporal => self.kinds_for_category(FieldCategory::Temporal),
+                FieldIndexKind::Histogram => IndexKind::empty(),
+            }
+        });
+        if cat == FieldCategory::Primitive && self.enable_rlte_for_primitives {
+            out |= IndexKind::RLTE;
+        }
+        out
+    }
+
     pub fn kinds_for_category(&self, cat: FieldCategory) -> IndexKind {
         match cat {
             FieldCategory::Temporal => {
@@ -1,6 +1,7 @@
 pub mod candidate_zone;
 pub mod enum_bitmap_index;
 pub mod enum_zone_pruner;
+pub mod field_histogram;
 pub mod index_build_planner;
 pub mod index_build_policy;
 pub mod rlte_index;
@@ -25,6 +26,7 @@ pub mod zone_planner;
 pub mod zone_row;
 pub mod zone_step_planner;
 pub mod zone_step_runner;
+pub mod zone_summary;
 pub mod zone_value_loader;
 pub mod zone_writer;
 pub mod zone_xor_index;
@@ -36,6 +38,8 @@ mod enum_bitmap_index_test;
 #[cfg(test)]
 mod enum_zone_pruner_test;
 #[cfg(test)]
+mod field_histogram_test;
+#[cfg(test)]
 mod index_build_planner_test;
 #[cfg(test)]
 mod index_build_policy_test;
@@ -78,6 +82,8 @@ mod zone_step_planner_tests;
 #[cfg(test)]
 mod zone_step_runner_tests;
 #[cfg(test)]
+mod zone_summary_test;
+#[cfg(test)]
 mod zone_writer_test;
 #[cfg(test)]
 mod zone_xor_index_test;
@@ -2,6 +2,7 @@ use crate::engine::core::filter::filter_group::FilterGroup;
 use crate::engine::core::zone::selector::field_selector::FieldSelector;
 use crate::engine::core::zone::selector::index_selector::{IndexZoneSelector, MissingIndexPolicy};
 use crate::engine::core::zone::selector::pruner::enum_pruner::EnumPruner;
+use crate::engine::core::zone::selector::pruner::event_id_pruner::EventIdPruner;
 use crate::engine::core::zone::selector::pruner::range_pruner::RangePruner;
 use crate::engine::core::zone::selector::pruner::temporal_pruner::TemporalPruner;
 use crate::engine::core::zone::selector::pruner::xor_pruner::XorPruner;
@@ -37,6 +38,10 @@ impl<'a> ZoneSelectorBuilder<'a> {
             artifacts: self.make_artifacts(),
         };
         let xor_pruner = XorPruner { artifacts };
+        let event_id_pruner = EventIdPruner {
+            base_dir: &self.inputs.query_plan.segment_base_dir,
+            caches: self.inputs.caches,
+        };
         FieldSelector {
             plan: self.inputs.plan,
             qplan: self.inputs.query_plan,
@@ -45,6 +50,7 @@ impl<'a> ZoneSelectorBuilder<'a> {
             temporal_pruner,
             enum_pruner,
             xor_pruner,
+            event_id_pruner,
         }
     }
 
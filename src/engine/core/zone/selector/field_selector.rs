@@ -1,7 +1,9 @@
 use super::scope::collect_zones_for_scope;
+use crate::command::types::PrunerKind;
 use crate::engine::core::filter::filter_group::FilterGroup;
 use crate::engine::core::read::index_strategy::IndexStrategy;
 use crate::engine::core::zone::selector::pruner::enum_pruner::EnumPruner;
+use crate::engine::core::zone::selector::pruner::event_id_pruner::EventIdPruner;
 use crate::engine::core::zone::selector::pruner::materialization_pruner::MaterializationPruner;
 use crate::engine::core::zone::selector::pruner::range_pruner::RangePruner;
 use crate::engine::core::zone::selector::pruner::temporal_pruner::TemporalPruner;
@@ -19,9 +21,30 @@ pub struct FieldSelector<'a> {
     pub temporal_pruner: TemporalPruner<'a>,
     pub enum_pruner: EnumPruner<'a>,
     pub xor_pruner: XorPruner<'a>,
+    pub event_id_pruner: EventIdPruner<'a>,
 }
 
-impl<'a> FieldSelector<'a> {}
+impl<'a> FieldSelector<'a> {
+    /// False when a pruner the strategy relies on is disabled for this query.
+    fn strategy_enabled(&self, strategy: &IndexStrategy) -> bool {
+        let enabled = |kind| self.qplan.pruner_enabled(kind);
+        match strategy {
+            IndexStrategy::TemporalEq { .. } => enabled(PrunerKind::Temporal),
+            IndexStrategy::TemporalRange { .. } => {
+                enabled(PrunerKind::Temporal) && enabled(PrunerKind::Range)
+            }
+            IndexStrategy::EnumBitmap { .. } => enabled(PrunerKind::Enum),
+            IndexStrategy::ZoneSuRF { .. } => {
+                enabled(PrunerKind::Surf) && enabled(PrunerKind::Range)
+            }
+            IndexStrategy::ZoneXorIndex { .. } | IndexStrategy::XorPresence { .. } => {
+                enabled(PrunerKind::Xor)
+            }
+            IndexStrategy::EventIdRange { .. } => enabled(PrunerKind::Range),
+            IndexStrategy::FullScan => true,
+        }
+    }
+}
 
 impl<'a> ZoneSelector for FieldSelector<'a> {
     fn select_for_segment(&self, segment_id: &str) -> Vec<CandidateZone> {
@@ -65,6 +88,18 @@ impl<'a> ZoneSelector for FieldSelector<'a> {
         // If a strategy is assigned, dispatch directly to the corresponding executor
         if let Some(strategy) = index_strategy {
             match strategy {
+                // A disabled pruner scans every zone; the row filter still applies
+                _ if !self.strategy_enabled(strategy) => {
+                    debug!(
+                        target: "sneldb::query",
+                        segment = %segment_id,
+                        column = %column,
+                        strategy = ?strategy,
+                        "Pruner disabled by query hint; scanning all zones"
+                    );
+                    candidate_zones =
+                        collect_zones_for_scope(self.qplan, self.caches, segment_id, Some(uid));
+                }
                 IndexStrategy::TemporalEq { .. } | IndexStrategy::TemporalRange { .. } => {
                     if let Some(z) = self.temporal_pruner.apply_temporal_only(&args) {
                         candidate_zones = z;
@@ -117,6 +152,11 @@ impl<'a> ZoneSelector for FieldSelector<'a> {
                         return Vec::new();
                     }
                 }
+                IndexStrategy::EventIdRange { .. } => {
+                    candidate_zones = self.event_id_pruner.apply(&args).unwrap_or_else(|| {
+                        collect_zones_for_scope(self.qplan, self.caches, segment_id, Some(uid))
+                    });
+                }
                 IndexStrategy::FullScan => {
                     candidate_zones =
                         collect_zones_for_scope(self.qplan, self.caches, segment_id, Some(uid));
@@ -127,6 +167,9 @@ impl<'a> ZoneSelector for FieldSelector<'a> {
         }
 
         // Apply materialization pruning if materialization_created_at is set in query metadata
+        if !self.qplan.pruner_enabled(PrunerKind::Materialization) {
+            return candidate_zones;
+        }
         if let Some(created_at_str) = self.qplan.metadata.get("materialization_created_at") {
             if let Ok(materialization_created_at) = created_at_str.parse::<u64>() {
                 let high_water_ts = self
@@ -4,7 +4,7 @@ use std::sync::Arc;
 use serde_json::json;
 use tempfile::tempdir;
 
-use crate::command::types::{Command, CompareOp, Expr};
+use crate::command::types::{Command, CompareOp, Expr, PrunerKind};
 use crate::engine::core::ZoneMeta;
 use crate::engine::core::read::cache::QueryCaches;
 use crate::engine::core::read::index_strategy::IndexStrategy;
@@ -1625,3 +1625,161 @@ async fn materialization_pruner_filters_zones_with_equal_created_at() {
     );
     assert_eq!(result[0].zone_id, 1, "Zone 1 should be retained");
 }
+
+#[tokio::test]
+async fn disabled_xor_pruner_keeps_every_zone() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let tmp = tempdir().unwrap();
+    let shard_dir = tmp.path().join("shard-0");
+    let seg1 = shard_dir.join("001");
+    std::fs::create_dir_all(&seg1).unwrap();
+
+    let reg_fac = SchemaRegistryFactory::new();
+    let registry = reg_fac.registry();
+    let event_type = "purchase";
+    reg_fac
+        .define_with_fields(event_type, &[("context_id", "string"), ("key", "string")])
+        .await
+        .unwrap();
+    let event = EventFactory::new()
+        .with("event_type", event_type)
+        .with("context_id", "c1")
+        .with("payload", json!({"key": "a"}))
+        .create();
+    let mem = MemTableFactory::new()
+        .with_capacity(1)
+        .with_events(vec![event])
+        .create()
+        .unwrap();
+    Flusher::new(
+        mem,
+        1,
+        &seg1,
+        registry.clone(),
+        Arc::new(tokio::sync::Mutex::new(())),
+    )
+    .flush()
+    .await
+    .unwrap();
+
+    let mut filter = FilterGroupFactory::new()
+        .with_column("key")
+        .with_operation(CompareOp::Eq)
+        .with_uid(&registry.read().await.get_uid(event_type).unwrap())
+        .with_value(json!("b"))
+        .create();
+    if let Some(strategy) = filter.index_strategy_mut() {
+        *strategy = Some(IndexStrategy::ZoneXorIndex {
+            field: "key".to_string(),
+        });
+    }
+
+    let select = |disabled: Vec<PrunerKind>| {
+        let registry = Arc::clone(&registry);
+        let shard_dir = shard_dir.clone();
+        let filter = filter.clone();
+        async move {
+            let command = CommandFactory::query()
+                .with_event_type(event_type)
+                .with_disabled_pruners(disabled)
+                .create();
+            let qplan = QueryPlanFactory::new()
+                .with_registry(registry)
+                .with_command(command)
+                .with_segment_base_dir(&shard_dir)
+                .with_segment_ids(vec!["001".into()])
+                .create()
+                .await;
+            let ctx = SelectionContext {
+                plan: &filter,
+                query_plan: &qplan,
+                base_dir: &shard_dir,
+                caches: None,
+            };
+            ZoneSelectorBuilder::new(ctx)
+                .build()
+                .select_for_segment("001")
+        }
+    };
+
+    // "b" is not in the segment, so the XOR filter drops its only zone
+    assert!(select(vec![]).await.is_empty());
+    // Other pruners do not affect a XOR strategy
+    assert!(select(vec![PrunerKind::Surf]).await.is_empty());
+    assert_eq!(select(vec![PrunerKind::Xor]).await.len(), 1);
+}
+
+#[tokio::test]
+async fn disabled_materialization_pruner_keeps_materialized_zones() {
+    use crate::logging::init_for_tests;
+    init_for_tests();
+
+    let tmp = tempdir().unwrap();
+    let shard_dir = tmp.path().join("shard-0");
+    let seg1 = shard_dir.join("001");
+    std::fs::create_dir_all(&seg1).unwrap();
+
+    let reg_fac = SchemaRegistryFactory::new();
+    let registry = reg_fac.registry();
+    let event_type = "materialization_test";
+    reg_fac
+        .define_with_fields(event_type, &[("context_id", "string"), ("key", "string")])
+        .await
+        .unwrap();
+
+    let uid = registry.read().await.get_uid(event_type).unwrap();
+    let zones: Vec<ZoneMeta> = [(0u32, 1000u64), (1, 2000)]
+        .into_iter()
+        .map(|(zone_id, created_at)| {
+            crate::test_helpers::factory::Factory::zone_meta()
+                .with("zone_id", zone_id)
+                .with("uid", uid.as_str())
+                .with("segment_id", 1u64)
+                .with("start_row", zone_id * 100)
+                .with("end_row", zone_id * 100 + 99)
+                .with("timestamp_min", 1_000_000u64)
+                .with("timestamp_max", 1_000_999u64)
+                .with("created_at", created_at)
+                .create()
+        })
+        .collect();
+    ZoneMeta::save(&uid, &zones, &seg1).unwrap();
+
+    let cmd = CommandFactory::query()
+        .with_event_type(event_type)
+        .with_disabled_pruners(vec![PrunerKind::Materialization])
+        .create();
+    let mut q = QueryPlanFactory::new()
+        .with_registry(Arc::clone(&registry))
+        .with_command(cmd)
+        .with_segment_base_dir(&shard_dir)
+        .with_segment_ids(vec!["001".to_string()])
+        .create()
+        .await;
+    q.set_metadata("materialization_created_at".to_string(), "1500".to_string());
+
+    let mut filter = FilterGroupFactory::new()
+        .with_column("key")
+        .with_operation(CompareOp::Eq)
+        .with_uid(&uid)
+        .with_value(json!("test"))
+        .create();
+    if let Some(strategy) = filter.index_strategy_mut() {
+        *strategy = Some(IndexStrategy::FullScan);
+    }
+
+    let caches = QueryCaches::new(shard_dir.clone());
+    let ctx = SelectionContext {
+        plan: &filter,
+        query_plan: &q,
+        base_dir: &shard_dir,
+        caches: Some(&caches),
+    };
+    let result = ZoneSelectorBuilder::new(ctx)
+        .build()
+        .select_for_segment("001");
+
+    assert_eq!(result.len(), 2);
+}
@@ -1,3 +1,4 @@
+use crate::command::types::PrunerKind;
 use crate::engine::core::zone::selector::pruner::materialization_pruner::MaterializationPruner;
 use crate::engine::core::zone::selector::selector_kind::ZoneSelector;
 use crate::engine::core::zone::{zone_artifacts::ZoneArtifacts, zone_meta::ZoneMeta};
@@ -123,6 +124,9 @@ struct MaterializationGuard {
 
 impl MaterializationGuard {
     fn from_plan(plan: &QueryPlan) -> Option<Self> {
+        if !plan.pruner_enabled(PrunerKind::Materialization) {
+            return None;
+        }
         let created_at_str = plan.metadata.get("materialization_created_at")?;
         let created_at = match created_at_str.parse::<u64>() {
             Ok(value) => value,
@@ -47,6 +47,8 @@ async fn index_selector_no_context_returns_all_zones_on_missing_index() {
         timestamp_min: 0,
         timestamp_max: 0,
         created_at: 0,
+        event_id_min: 0,
+        event_id_max: u64::MAX,
     }];
     ZoneMeta::save(&uid, &metas, &seg1).unwrap();
 
@@ -260,6 +262,8 @@ async fn index_selector_keeps_segment_when_high_water_timestamp_present() {
             timestamp_min: 100,
             timestamp_max: 100,
             created_at: 100,
+            event_id_min: 0,
+            event_id_max: u64::MAX,
         },
         ZoneMeta {
             zone_id: 1,
@@ -270,6 +274,8 @@ async fn index_selector_keeps_segment_when_high_water_timestamp_present() {
             timestamp_min: 100,
             timestamp_max: 100,
             created_at: 100,
+            event_id_min: 0,
+            event_id_max: u64::MAX,
         },
     ];
     ZoneMeta::save(&uid, &metas, &seg1).unwrap();
@@ -0,0 +1,53 @@
+use crate::engine::core::CandidateZone;
+use crate::engine::core::read::cache::QueryCaches;
+use crate::engine::core::zone::selector::pruner::{PruneArgs, ZonePruner};
+use crate::engine::core::zone::zone_meta::ZoneMeta;
+use crate::engine::types::ScalarValue;
+use std::path::PathBuf;
+
+/// Prunes zones on `event_id` comparisons using the id range kept in zone
+/// metadata. Ids only grow within a shard, so the zones of a flushed segment
+/// cover narrow, mostly disjoint ranges.
+pub struct EventIdPruner<'a> {
+    pub base_dir: &'a PathBuf,
+    pub caches: Option<&'a QueryCaches>,
+}
+
+impl<'a> EventIdPruner<'a> {
+    fn load_metas(&self, segment_id: &str, uid: &str) -> Option<Vec<ZoneMeta>> {
+        match self.caches {
+            Some(caches) => caches
+                .get_or_load_zone_meta(segment_id, uid)
+                .ok()
+                .map(|arc| (*arc).clone()),
+            None => ZoneMeta::load(
+                &self
+                    .base_dir
+                    .join(segment_id)
+                    .join(format!("{}.zones", uid)),
+            )
+            .ok(),
+        }
+    }
+}
+
+impl<'a> ZonePruner for EventIdPruner<'a> {
+    /// Returns `None` when the value is not an id or the metadata is missing,
+    /// leaving the caller to scan every zone.
+    fn apply(&self, args: &PruneArgs) -> Option<Vec<CandidateZone>> {
+        let op = args.op?;
+        let id = match args.value? {
+            ScalarValue::Int64(i) if *i >= 0 => *i as u64,
+            ScalarValue::Utf8(s) => s.parse::<u64>().ok()?,
+            _ => return None,
+        };
+        let metas = self.load_metas(args.segment_id, args.uid)?;
+        Some(
+            metas
+                .iter()
+                .filter(|meta| meta.may_contain_event_id(op, id))
+                .map(|meta| CandidateZone::new(meta.zone_id, args.segment_id.to_string()))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,84 @@
+use tempfile::tempdir;
+
+use crate::command::types::CompareOp;
+use crate::engine::core::ZoneMeta;
+use crate::engine::core::zone::selector::pruner::event_id_pruner::EventIdPruner;
+use crate::engine::core::zone::selector::pruner::{PruneArgs, ZonePruner};
+use crate::engine::types::ScalarValue;
+use crate::test_helpers::factory::Factory;
+
+fn zone_ids(pruner: &EventIdPruner, op: CompareOp, value: ScalarValue) -> Option<Vec<u32>> {
+    let args = PruneArgs {
+        segment_id: "001",
+        uid: "test_uid",
+        column: "event_id",
+        value: Some(&value),
+        op: Some(&op),
+    };
+    pruner
+        .apply(&args)
+        .map(|zones| zones.into_iter().map(|z| z.zone_id).collect())
+}
+
+#[test]
+fn keeps_only_zones_whose_id_range_can_match() {
+    let tmp = tempdir().unwrap();
+    let shard_dir = tmp.path().join("shard-0");
+    let seg = shard_dir.join("001");
+    std::fs::create_dir_all(&seg).unwrap();
+
+    let zone = |zone_id: u32, min: u64, max: u64| {
+        Factory::zone_meta()
+            .with("zone_id", zone_id)
+            .with("uid", "test_uid")
+            .with("event_id_min", min)
+            .with("event_id_max", max)
+            .create()
+    };
+    // Zone 2 was written before id ranges were tracked
+    let metas = vec![zone(0, 100, 199), zone(1, 200, 299), zone(2, 0, u64::MAX)];
+    ZoneMeta::save("test_uid", &metas, &seg).unwrap();
+
+    let base_dir = shard_dir.to_path_buf();
+    let pruner = EventIdPruner {
+        base_dir: &base_dir,
+        caches: None,
+    };
+
+    let ids = |op, v| zone_ids(&pruner, op, v);
+    assert_eq!(
+        ids(CompareOp::Gt, ScalarValue::Int64(199)),
+        Some(vec![1, 2])
+    );
+    assert_eq!(
+        ids(CompareOp::Gte, ScalarValue::Int64(199)),
+        Some(vec![0, 1, 2])
+    );
+    assert_eq!(
+        ids(CompareOp::Lt, ScalarValue::Int64(200)),
+        Some(vec![0, 2])
+    );
+    assert_eq!(ids(CompareOp::Lte, ScalarValue::Int64(99)), Some(vec![2]));
+    assert_eq!(
+        ids(CompareOp::Eq, ScalarValue::Utf8("250".into())),
+        Some(vec![1, 2])
+    );
+
+    // Values that are not ids leave the decision to a full scan
+    assert_eq!(ids(CompareOp::Gt, ScalarValue::Utf8("abc".into())), None);
+    assert_eq!(ids(CompareOp::Gt, ScalarValue::Int64(-1)), None);
+}
+
+#[test]
+fn missing_zone_meta_yields_none() {
+    let tmp = tempdir().unwrap();
+    let base_dir = tmp.path().to_path_buf();
+    let pruner = EventIdPruner {
+        base_dir: &base_dir,
+        caches: None,
+    };
+    assert_eq!(
+        zone_ids(&pruner, CompareOp::Gt, ScalarValue::Int64(1)),
+        None
+    );
+}
@@ -1,4 +1,5 @@
 pub mod enum_pruner;
+pub mod event_id_pruner;
 pub mod materialization_pruner;
 pub mod prune_args;
 pub mod pruner_kind;
@@ -14,6 +15,8 @@ pub use temporal_pruner::TemporalPruner;
 #[cfg(test)]
 mod enum_pruner_test;
 #[cfg(test)]
+mod event_id_pruner_test;
+#[cfg(test)]
 mod materialization_pruner_test;
 #[cfg(test)]
 mod range_pruner_test;
@@ -40,6 +40,8 @@ impl<'a> RangePruner<'a> {
                     return None;
                 }
 
+                // Decimal literals encode by value, not mantissa, so they bound a column
+                // whose segments were written at other scales
                 if let Some(bytes) = surf_encoding::encode_value(value).as_deref() {
                     let zones = match op {
                         CompareOp::Gt => zsf.zones_overlapping_ge(bytes, false, args.segment_id),
@@ -256,3 +256,52 @@ fn temporal_pruner_handles_string_datetime_values() {
     let out = pruner.apply_temporal_only(&args).unwrap();
     assert!(out.iter().any(|z| z.zone_id == 3));
 }
+
+#[test]
+fn temporal_pruner_uses_index_of_queried_time_dimension() {
+    let tmp = tempfile::tempdir().unwrap();
+    let seg_dir = tmp.path().join("00004");
+    fs::create_dir_all(&seg_dir).unwrap();
+    let uid = "u";
+
+    // Zone 1 happened early but was recorded late; zone 2 the other way round
+    write_field_calendar(&seg_dir, uid, "valid_at", &[(1, 100, 150), (2, 500, 550)]);
+    write_field_temporal_slab(
+        &seg_dir,
+        uid,
+        "valid_at",
+        &[(1, &[100, 150]), (2, &[500, 550])],
+    );
+    write_field_calendar(
+        &seg_dir,
+        uid,
+        "recorded_at",
+        &[(1, 900, 950), (2, 300, 350)],
+    );
+    write_field_temporal_slab(
+        &seg_dir,
+        uid,
+        "recorded_at",
+        &[(1, &[900, 950]), (2, &[300, 350])],
+    );
+
+    let base_dir = tmp.path().to_path_buf();
+    let artifacts = artifacts_for(&base_dir);
+    let pruner = TemporalPruner { artifacts };
+
+    let val = ScalarValue::from(json!(400u64));
+    let zones_for = |column: &str| -> Vec<u32> {
+        let args = PruneArgs {
+            segment_id: "00004",
+            uid,
+            column,
+            value: Some(&val),
+            op: Some(&CompareOp::Gte),
+        };
+        let out = pruner.apply_temporal_only(&args).unwrap();
+        out.iter().map(|z| z.zone_id).collect()
+    };
+
+    assert_eq!(zones_for("valid_at"), vec![2]);
+    assert_eq!(zones_for("recorded_at"), vec![1]);
+}
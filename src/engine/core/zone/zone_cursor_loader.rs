@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -21,6 +21,8 @@ pub struct ZoneCursorLoader {
     base_dir: PathBuf,
     /// QueryCaches instance for caching decompressed column blocks
     caches: QueryCaches,
+    /// Zones not to load, keyed by segment label
+    skipped_zones: HashMap<String, HashSet<u32>>,
 }
 
 #[derive(Debug)]
@@ -44,9 +46,16 @@ impl ZoneCursorLoader {
             registry,
             base_dir,
             caches,
+            skipped_zones: HashMap::new(),
         }
     }
 
+    /// Leaves out the given zones, keyed by segment label (e.g. zones past retention).
+    pub fn with_skipped_zones(mut self, skipped_zones: HashMap<String, HashSet<u32>>) -> Self {
+        self.skipped_zones = skipped_zones;
+        self
+    }
+
     pub async fn load_all(&self) -> Result<LoadedZoneCursors, QueryExecutionError> {
         let (schema, event_type_name) = {
             let reg = self.registry.read().await;
@@ -118,7 +127,11 @@ impl ZoneCursorLoader {
             let zone_metas =
                 ZoneMeta::load(&zones_path).map_err(|e| ZoneMetaError::Other(e.to_string()))?;
 
+            let skipped = self.skipped_zones.get(segment_id);
             for zone in &zone_metas {
+                if skipped.is_some_and(|zones| zones.contains(&zone.zone_id)) {
+                    continue;
+                }
                 let parsed_segment_id = match segment_id.parse::<u64>() {
                     Ok(id) => id,
                     Err(_) => {
@@ -254,10 +267,13 @@ impl ZoneCursorLoader {
 
 fn field_type_to_physical_type(field_type: &FieldType) -> PhysicalType {
     match field_type {
-        FieldType::I64 | FieldType::Timestamp | FieldType::Date => PhysicalType::I64,
+        FieldType::I64 | FieldType::Timestamp | FieldType::Date | FieldType::Time => {
+            PhysicalType::I64
+        }
         FieldType::U64 => PhysicalType::U64,
         FieldType::F64 => PhysicalType::F64,
         FieldType::Bool => PhysicalType::Bool,
+        FieldType::Uuid => PhysicalType::Uuid,
         FieldType::Optional(inner) => field_type_to_physical_type(inner.as_ref()),
         _ => PhysicalType::VarBytes,
     }
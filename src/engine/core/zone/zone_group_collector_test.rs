@@ -67,6 +67,14 @@ fn create_test_plan_with_segments(segment_ids: Vec<String>) -> QueryPlan {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     TEMP_DIR.with(|tempdir| {
@@ -1,3 +1,5 @@
+use crate::engine::core::read::aggregate::partial::AggPartial;
+use crate::engine::core::read::aggregate::zone_summary_plan::ZoneSummaryPlan;
 use crate::engine::core::read::event_scope::EventScope;
 use crate::engine::core::{
     CandidateZone, ExecutionStep, QueryCaches, QueryPlan, SegmentZoneId, ZoneCollector, ZoneFilter,
@@ -11,6 +13,8 @@ pub struct ZoneHydrator<'a> {
     steps: Vec<ExecutionStep<'a>>,
     caches: Option<&'a QueryCaches>,
     zone_filter: Option<ZoneFilter>,
+    zone_summaries: Option<&'a ZoneSummaryPlan>,
+    columns: Option<Vec<String>>,
 }
 
 impl<'a> ZoneHydrator<'a> {
@@ -20,10 +24,18 @@ impl<'a> ZoneHydrator<'a> {
             steps,
             caches: None,
             zone_filter: None,
+            zone_summaries: None,
+            columns: None,
         }
     }
 
     pub async fn hydrate(self) -> Vec<CandidateZone> {
+        self.hydrate_with_summaries().await.0
+    }
+
+    /// Like `hydrate`, but zones answerable from zone summaries are left out and
+    /// returned as one partial aggregate instead.
+    pub async fn hydrate_with_summaries(self) -> (Vec<CandidateZone>, Option<AggPartial>) {
         let hydrate_start = std::time::Instant::now();
         if tracing::enabled!(tracing::Level::DEBUG) {
             let plan_debug = format!("{:?}", self.plan);
@@ -53,6 +65,17 @@ impl<'a> ZoneHydrator<'a> {
         }
         let zones_after_filter = candidate_zones.len();
 
+        // Zones covered by summaries never need their columns
+        let summarized = match (self.zone_summaries, self.plan.event_type_uid().await) {
+            (Some(summary_plan), Some(uid)) => summary_plan.apply(
+                &mut candidate_zones,
+                &self.plan.segment_base_dir,
+                &uid,
+                self.caches,
+            ),
+            _ => None,
+        };
+
         if tracing::enabled!(tracing::Level::INFO) {
             info!(
                 target: "sneldb::zone_hydrator",
@@ -63,7 +86,10 @@ impl<'a> ZoneHydrator<'a> {
             );
         }
 
-        let columns = self.plan.columns_to_load().await;
+        let columns = match &self.columns {
+            Some(columns) => columns.clone(),
+            None => self.plan.columns_to_load().await,
+        };
         let mut zones_by_uid: std::collections::HashMap<String, Vec<usize>> =
             std::collections::HashMap::new();
         for (idx, zone) in candidate_zones.iter().enumerate() {
@@ -177,7 +203,7 @@ impl<'a> ZoneHydrator<'a> {
             );
         }
 
-        candidate_zones
+        (candidate_zones, summarized)
     }
 
     pub fn with_caches(mut self, caches: Option<&'a QueryCaches>) -> Self {
@@ -185,6 +211,17 @@ impl<'a> ZoneHydrator<'a> {
         self
     }
 
+    pub fn with_zone_summaries(mut self, plan: Option<&'a ZoneSummaryPlan>) -> Self {
+        self.zone_summaries = plan;
+        self
+    }
+
+    /// Loads these columns instead of every column the plan needs.
+    pub fn with_columns(mut self, columns: Option<Vec<String>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
     pub fn with_allowed_zones(
         mut self,
         allowed: Option<std::collections::HashSet<(String, u32)>>,
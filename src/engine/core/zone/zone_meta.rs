@@ -1,9 +1,10 @@
+use crate::command::types::CompareOp;
 use crate::engine::core::{Event, ZonePlan};
 use crate::engine::errors::{StoreError, ZoneMetaError};
 use crate::shared::storage_header::{BinaryHeader, FileKind};
 use serde::{Deserialize, Serialize};
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::{BufReader, BufWriter, Read};
 use std::path::Path;
 use tracing::{debug, error, trace, warn};
 
@@ -18,6 +19,43 @@ pub struct ZoneMeta {
     pub timestamp_max: u64,
     #[serde(default)]
     pub created_at: u64,
+    /// Smallest and largest event id in the zone. Zones written before ids were
+    /// tracked cover the whole id range.
+    pub event_id_min: u64,
+    pub event_id_max: u64,
+}
+
+/// `.zones` format version; version 1 files predate the event id range.
+const ZONE_META_VERSION: u16 = 2;
+
+/// Zone meta as written by version 1.
+#[derive(Deserialize)]
+struct ZoneMetaV1 {
+    zone_id: u32,
+    uid: String,
+    segment_id: u64,
+    start_row: u32,
+    end_row: u32,
+    timestamp_min: u64,
+    timestamp_max: u64,
+    created_at: u64,
+}
+
+impl From<ZoneMetaV1> for ZoneMeta {
+    fn from(v1: ZoneMetaV1) -> Self {
+        Self {
+            zone_id: v1.zone_id,
+            uid: v1.uid,
+            segment_id: v1.segment_id,
+            start_row: v1.start_row,
+            end_row: v1.end_row,
+            timestamp_min: v1.timestamp_min,
+            timestamp_max: v1.timestamp_max,
+            created_at: v1.created_at,
+            event_id_min: 0,
+            event_id_max: u64::MAX,
+        }
+    }
 }
 
 impl ZoneMeta {
@@ -35,8 +73,7 @@ impl ZoneMeta {
         if header.magic != FileKind::ZoneMeta.magic() {
             return Err(ZoneMetaError::Other("invalid magic for .zones".into()));
         }
-        let reader = BufReader::new(file);
-        let zones: Vec<ZoneMeta> = bincode::deserialize_from(reader)?;
+        let zones = Self::deserialize(header.version, BufReader::new(file))?;
 
         if tracing::enabled!(tracing::Level::DEBUG) {
             debug!(
@@ -49,6 +86,36 @@ impl ZoneMeta {
         Ok(zones)
     }
 
+    /// Parses a `.zones` file already held in memory, header included.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Vec<ZoneMeta>, ZoneMetaError> {
+        let header = BinaryHeader::read_from(bytes)?;
+        if header.magic != FileKind::ZoneMeta.magic() {
+            return Err(ZoneMetaError::Other("invalid magic for .zones".into()));
+        }
+        Self::deserialize(header.version, &bytes[BinaryHeader::TOTAL_LEN..])
+    }
+
+    fn deserialize(version: u16, reader: impl Read) -> Result<Vec<ZoneMeta>, ZoneMetaError> {
+        if version < 2 {
+            let zones: Vec<ZoneMetaV1> = bincode::deserialize_from(reader)?;
+            return Ok(zones.into_iter().map(ZoneMeta::from).collect());
+        }
+        Ok(bincode::deserialize_from(reader)?)
+    }
+
+    /// Whether the zone may hold an event whose id compares to `value` as `op`
+    /// does. Exact for the id range: only zones that cannot match return false.
+    pub fn may_contain_event_id(&self, op: &CompareOp, value: u64) -> bool {
+        match op {
+            CompareOp::Eq => self.event_id_min <= value && value <= self.event_id_max,
+            CompareOp::Gt => self.event_id_max > value,
+            CompareOp::Gte => self.event_id_max >= value,
+            CompareOp::Lt => self.event_id_min < value,
+            CompareOp::Lte => self.event_id_min <= value,
+            _ => true,
+        }
+    }
+
     pub fn save(uid: &str, zones: &[ZoneMeta], segment_dir: &Path) -> Result<(), ZoneMetaError> {
         let path = segment_dir.join(format!("{}.zones", uid));
 
@@ -63,7 +130,7 @@ impl ZoneMeta {
         }
 
         let mut file = File::create(&path)?;
-        let header = BinaryHeader::new(FileKind::ZoneMeta.magic(), 1, 0);
+        let header = BinaryHeader::new(FileKind::ZoneMeta.magic(), ZONE_META_VERSION, 0);
         header.write_to(&mut file)?;
         let writer = BufWriter::new(file);
         bincode::serialize_into(writer, zones)?;
@@ -103,7 +170,7 @@ impl ZoneMeta {
         let mut file = tokio::fs::File::create(&path).await?;
 
         // Write header
-        let header = BinaryHeader::new(FileKind::ZoneMeta.magic(), 1, 0);
+        let header = BinaryHeader::new(FileKind::ZoneMeta.magic(), ZONE_META_VERSION, 0);
         let mut header_buf = Vec::with_capacity(BinaryHeader::TOTAL_LEN);
         header.write_to(&mut header_buf)?;
         file.write_all(&header_buf).await?;
@@ -152,6 +219,13 @@ impl ZoneMeta {
         }
 
         let sorted_by_time = Event::order_by(&zone_plan.events, "timestamp");
+        let ids = zone_plan.events.iter().map(|e| e.event_id().raw());
+        // Events without an id get a synthetic one when read, so the range is unknown
+        let (event_id_min, event_id_max) = if ids.clone().any(|id| id == 0) {
+            (0, u64::MAX)
+        } else {
+            (ids.clone().min().unwrap(), ids.max().unwrap())
+        };
 
         Ok(ZoneMeta {
             zone_id: zone_plan.id,
@@ -162,6 +236,8 @@ impl ZoneMeta {
             uid: zone_plan.uid.clone(),
             segment_id: zone_plan.segment_id,
             created_at: zone_plan.created_at,
+            event_id_min,
+            event_id_max,
         })
     }
 
@@ -1,4 +1,6 @@
+use crate::command::types::CompareOp;
 use crate::engine::core::ZoneMeta;
+use crate::shared::storage_header::{BinaryHeader, FileKind};
 use crate::test_helpers::factory::Factory;
 
 #[test]
@@ -62,3 +64,63 @@ fn test_zone_meta_save_and_load_round_trip() {
     assert_eq!(loaded.len(), 1);
     assert_eq!(loaded[0], meta);
 }
+
+#[test]
+fn test_zone_meta_build_tracks_event_id_range() {
+    let mut zone_plan = Factory::zone_plan().create();
+    zone_plan.events = [30u64, 10, 20]
+        .into_iter()
+        .map(|id| Factory::event().with("event_id", id).create())
+        .collect();
+
+    let meta = ZoneMeta::build(&zone_plan).unwrap();
+    assert_eq!((meta.event_id_min, meta.event_id_max), (10, 30));
+
+    assert!(meta.may_contain_event_id(&CompareOp::Eq, 20));
+    assert!(!meta.may_contain_event_id(&CompareOp::Eq, 31));
+    assert!(meta.may_contain_event_id(&CompareOp::Gt, 29));
+    assert!(!meta.may_contain_event_id(&CompareOp::Gt, 30));
+    assert!(meta.may_contain_event_id(&CompareOp::Gte, 30));
+    assert!(meta.may_contain_event_id(&CompareOp::Lt, 11));
+    assert!(!meta.may_contain_event_id(&CompareOp::Lt, 10));
+    assert!(meta.may_contain_event_id(&CompareOp::Lte, 10));
+    assert!(meta.may_contain_event_id(&CompareOp::Neq, 10));
+
+    // An event without an id leaves the range open
+    zone_plan.events = vec![
+        Factory::event().with("event_id", 5u64).create(),
+        Factory::event().with("event_id", 0u64).create(),
+    ];
+    let meta = ZoneMeta::build(&zone_plan).unwrap();
+    assert_eq!((meta.event_id_min, meta.event_id_max), (0, u64::MAX));
+}
+
+#[test]
+fn test_zone_meta_loads_version_1_files_with_open_id_range() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let path = tmp_dir.path().join("uid.zones");
+
+    // Version 1 layout: the fields before the event id range
+    let legacy: Vec<(u32, String, u64, u32, u32, u64, u64, u64)> =
+        vec![(0, "uid".to_string(), 7, 0, 9, 100, 200, 300)];
+    let mut bytes = Vec::new();
+    BinaryHeader::new(FileKind::ZoneMeta.magic(), 1, 0)
+        .write_to(&mut bytes)
+        .unwrap();
+    bytes.extend(bincode::serialize(&legacy).unwrap());
+    std::fs::write(&path, &bytes).unwrap();
+
+    for loaded in [
+        ZoneMeta::load(&path).unwrap(),
+        ZoneMeta::from_bytes(&bytes).unwrap(),
+    ] {
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].segment_id, 7);
+        assert_eq!(loaded[0].timestamp_max, 200);
+        assert_eq!(loaded[0].created_at, 300);
+        assert_eq!(
+            (loaded[0].event_id_min, loaded[0].event_id_max),
+            (0, u64::MAX)
+        );
+    }
+}
@@ -2,18 +2,32 @@ use std::path::Path;
 
 use tracing::debug;
 
+use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::engine::core::zone::zone_summary::ZoneSummaryIndex;
 use crate::engine::core::{ZoneMeta, ZonePlan};
 use crate::engine::errors::StoreError;
 
-/// Writes zone metadata (the `{uid}.zones` file) derived from `ZonePlan`s
+/// Writes zone metadata (the `{uid}.zones` file) derived from `ZonePlan`s,
+/// plus per-zone aggregate summaries (`{uid}.zsum`) when any are configured
 pub struct ZoneMetadataWriter<'a> {
     pub uid: &'a str,
     pub segment_dir: &'a Path,
+    summaries: &'a [AggregateOpSpec],
 }
 
 impl<'a> ZoneMetadataWriter<'a> {
     pub fn new(uid: &'a str, segment_dir: &'a Path) -> Self {
-        Self { uid, segment_dir }
+        Self {
+            uid,
+            segment_dir,
+            summaries: &[],
+        }
+    }
+
+    /// Aggregates to precompute for every zone; empty means no `.zsum` file.
+    pub fn with_summaries(mut self, specs: &'a [AggregateOpSpec]) -> Self {
+        self.summaries = specs;
+        self
     }
 
     /// Build `ZoneMeta` entries for all plans and persist them to disk
@@ -29,7 +43,7 @@ impl<'a> ZoneMetadataWriter<'a> {
         let zone_meta = ZoneMeta::build_all(zone_plans);
         ZoneMeta::save(self.uid, &zone_meta, self.segment_dir)?;
 
-        Ok(())
+        self.write_summaries(zone_plans)
     }
 
     /// Build `ZoneMeta` entries for all plans and persist them to disk (async)
@@ -45,6 +59,24 @@ impl<'a> ZoneMetadataWriter<'a> {
         let zone_meta = ZoneMeta::build_all(zone_plans);
         ZoneMeta::save_async(self.uid, &zone_meta, self.segment_dir).await?;
 
-        Ok(())
+        self.write_summaries(zone_plans)
+    }
+
+    fn write_summaries(&self, zone_plans: &[ZonePlan]) -> Result<(), StoreError> {
+        if self.summaries.is_empty() {
+            return Ok(());
+        }
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            debug!(
+                target: "sneldb::flush",
+                uid = self.uid,
+                specs = self.summaries.len(),
+                "Writing .zsum zone summaries"
+            );
+        }
+
+        ZoneSummaryIndex::build_from_zones(zone_plans, self.summaries)
+            .save(self.uid, self.segment_dir)
+            .map_err(|e| StoreError::FlushFailed(format!("Failed to save zone summaries: {}", e)))
     }
 }
@@ -16,22 +16,43 @@ impl<'a> ZoneStepPlanner<'a> {
             return (0..steps.len()).map(|i| (i, None)).collect();
         }
 
-        // Find context step; if absent, no reordering/pruning
+        // Find context step; if absent, only reorder by selectivity
         if let Some(ctx_idx) = steps.iter().position(|s| s.filter.is_context_id()) {
             // First run ctx over all segments; consumers of this plan will compute pruned list
             let mut plan: Vec<(usize, Option<Vec<String>>)> = Vec::with_capacity(steps.len());
             plan.push((ctx_idx, None));
             // Subsequent steps will run against pruned segments; we signal with Some(vec) later
             // Here we leave None placeholders; ZoneCollector will fill the actual subset once ctx runs
-            for (i, _s) in steps.iter().enumerate() {
-                if i == ctx_idx {
-                    continue;
-                }
+            let rest = (0..steps.len()).filter(|i| *i != ctx_idx);
+            for i in self.by_selectivity(steps, rest) {
                 plan.push((i, None));
             }
             plan
         } else {
-            (0..steps.len()).map(|i| (i, None)).collect()
+            self.by_selectivity(steps, 0..steps.len())
+                .into_iter()
+                .map(|i| (i, None))
+                .collect()
         }
     }
+
+    /// Orders step indices most selective first. Without histograms every step is
+    /// assumed equally selective, so the original order is kept.
+    fn by_selectivity(
+        &self,
+        steps: &[ExecutionStep<'a>],
+        indices: impl Iterator<Item = usize>,
+    ) -> Vec<usize> {
+        let mut indices: Vec<usize> = indices.collect();
+        let estimator = &self.plan.selectivity;
+        if !estimator.has_histograms() {
+            return indices;
+        }
+        let estimates: Vec<f64> = steps
+            .iter()
+            .map(|s| estimator.estimate_group(&s.filter))
+            .collect();
+        indices.sort_by(|a, b| estimates[*a].total_cmp(&estimates[*b]));
+        indices
+    }
 }
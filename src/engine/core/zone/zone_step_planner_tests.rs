@@ -325,3 +325,81 @@ async fn planner_preserves_when_context_already_first() {
         vec![0, 1]
     );
 }
+
+#[tokio::test]
+// AND with histograms: planner runs the most selective step first
+async fn planner_orders_and_steps_by_selectivity() {
+    use crate::engine::core::zone::field_histogram::{FieldHistogram, FieldHistogramIndex};
+    use crate::engine::types::ScalarValue;
+    use std::collections::HashMap;
+
+    let schema = SchemaRegistryFactory::new();
+    let registry = schema.registry();
+    let event_type = "orders";
+    schema
+        .define_with_fields(event_type, &[("id", "int"), ("amount", "int")])
+        .await
+        .unwrap();
+
+    let command = CommandFactory::query()
+        .with_event_type(event_type)
+        .with_where_clause(Expr::And(
+            Box::new(Expr::Compare {
+                field: "amount".into(),
+                op: CompareOp::Gt,
+                value: json!(0),
+            }),
+            Box::new(Expr::Compare {
+                field: "id".into(),
+                op: CompareOp::Eq,
+                value: json!(5),
+            }),
+        ))
+        .create();
+
+    let mut plan = QueryPlanFactory::new()
+        .with_command(command)
+        .with_registry(Arc::clone(&registry))
+        .with_segment_base_dir(std::env::temp_dir())
+        .with_segment_ids(vec!["00001".into()])
+        .create()
+        .await;
+
+    let values: Vec<ScalarValue> = (0..100).map(ScalarValue::Int64).collect();
+    let histogram = |vals: Vec<ScalarValue>| FieldHistogram::build(vals, 100, 32).unwrap();
+    plan.selectivity.insert(
+        "00001",
+        FieldHistogramIndex {
+            row_count: 100,
+            fields: HashMap::from([
+                ("id".to_string(), histogram(values.clone())),
+                ("amount".to_string(), histogram(values)),
+            ]),
+        },
+    );
+
+    let uid = plan.event_type_uid().await.expect("uid");
+    let fp_amount = FilterGroupFactory::new()
+        .with_column("amount")
+        .with_operation(CompareOp::Gt)
+        .with_value(json!(0))
+        .with_uid(&uid)
+        .create();
+    let fp_id = FilterGroupFactory::new()
+        .with_column("id")
+        .with_operation(CompareOp::Eq)
+        .with_value(json!(5))
+        .with_uid(&uid)
+        .create();
+
+    let steps = vec![
+        ExecutionStep::new(fp_amount, &plan),
+        ExecutionStep::new(fp_id, &plan),
+    ];
+    let planner = ZoneStepPlanner::new(&plan);
+    let order = planner.plan(&steps);
+    assert_eq!(
+        order.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+        vec![1, 0]
+    );
+}
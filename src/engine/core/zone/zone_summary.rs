@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+use crate::engine::core::ZonePlan;
+use crate::engine::core::read::aggregate::ops::AggregatorImpl;
+use crate::engine::core::read::aggregate::partial::{AggState, snapshot_aggregator};
+use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::shared::config::{CONFIG, ZoneSummaryOp};
+use crate::shared::storage_header::{BinaryHeader, FileKind};
+
+/// Partial aggregates of every zone for one uid in a segment, persisted as `{uid}.zsum`.
+/// Each zone's states align with `specs`, which always include `CountAll`.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ZoneSummaryIndex {
+    pub specs: Vec<AggregateOpSpec>,
+    /// zone_id -> states
+    pub zones: HashMap<u32, Vec<AggState>>,
+}
+
+impl ZoneSummaryIndex {
+    /// Summaries configured for `event_type` under `engine.zone_summaries`.
+    pub fn configured_specs(event_type: &str) -> Vec<AggregateOpSpec> {
+        let Some(fields) = CONFIG.engine.zone_summaries.get(event_type) else {
+            return Vec::new();
+        };
+        let mut specs = Vec::new();
+        for cfg in fields {
+            for op in &cfg.ops {
+                let field = cfg.field.clone();
+                let spec = match op {
+                    ZoneSummaryOp::Count => AggregateOpSpec::CountField { field },
+                    ZoneSummaryOp::Sum => AggregateOpSpec::Total { field },
+                    ZoneSummaryOp::Avg => AggregateOpSpec::Avg { field },
+                    ZoneSummaryOp::Min => AggregateOpSpec::Min { field },
+                    ZoneSummaryOp::Max => AggregateOpSpec::Max { field },
+                };
+                if !specs.contains(&spec) {
+                    specs.push(spec);
+                }
+            }
+        }
+        specs
+    }
+
+    /// Aggregate every zone over `specs` in a single pass over its events.
+    pub fn build_from_zones(zones: &[ZonePlan], specs: &[AggregateOpSpec]) -> Self {
+        let mut all_specs = vec![AggregateOpSpec::CountAll];
+        for spec in specs {
+            // COUNT UNIQUE keeps every distinct value, and COUNT DISTINCT and PERCENTILE
+            // a sketch per zone, which defeats the point of a summary
+            let unbounded = matches!(
+                spec,
+                AggregateOpSpec::CountUnique { .. }
+                    | AggregateOpSpec::CountDistinct { .. }
+                    | AggregateOpSpec::Percentile { .. }
+            );
+            if !unbounded && !all_specs.contains(spec) {
+                all_specs.push(spec.clone());
+            }
+        }
+
+        let zones = zones
+            .iter()
+            .map(|zone| {
+                let mut aggs: Vec<AggregatorImpl> =
+                    all_specs.iter().map(AggregatorImpl::from_spec).collect();
+                for event in &zone.events {
+                    for agg in aggs.iter_mut() {
+                        agg.update_from_event(event);
+                    }
+                }
+                (zone.id, aggs.iter().map(snapshot_aggregator).collect())
+            })
+            .collect();
+
+        Self {
+            specs: all_specs,
+            zones,
+        }
+    }
+
+    /// Position of each requested spec in the stored states, or `None` if any is missing.
+    pub fn positions(&self, specs: &[AggregateOpSpec]) -> Option<Vec<usize>> {
+        specs
+            .iter()
+            .map(|spec| self.specs.iter().position(|s| s == spec))
+            .collect()
+    }
+
+    pub fn zone(&self, zone_id: u32) -> Option<&[AggState]> {
+        self.zones.get(&zone_id).map(Vec::as_slice)
+    }
+
+    /// Persist summaries for a uid as `{uid}.zsum` with header and a bincode payload.
+    pub fn save(&self, uid: &str, segment_dir: &Path) -> std::io::Result<()> {
+        let path = segment_dir.join(format!("{}.zsum", uid));
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)?;
+        BinaryHeader::new(FileKind::ZoneSummary.magic(), 1, 0).write_to(&mut f)?;
+        let bytes = bincode::serialize(self).map_err(std::io::Error::other)?;
+        f.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Load the summary file for uid.
+    pub fn load(uid: &str, segment_dir: &Path) -> std::io::Result<Self> {
+        let path = segment_dir.join(format!("{}.zsum", uid));
+        let bytes = std::fs::read(path)?;
+        let header = BinaryHeader::read_from(&mut bytes.as_slice())?;
+        if header.magic != FileKind::ZoneSummary.magic() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid magic for .zsum",
+            ));
+        }
+        bincode::deserialize(&bytes[BinaryHeader::TOTAL_LEN..]).map_err(std::io::Error::other)
+    }
+}
@@ -0,0 +1,128 @@
+use crate::engine::core::read::aggregate::partial::AggState;
+use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
+use crate::engine::core::zone::zone_summary::ZoneSummaryIndex;
+use crate::engine::core::{ZonePlan, ZoneWriter};
+use crate::test_helpers::factories::{EventFactory, SchemaRegistryFactory};
+use serde_json::json;
+
+fn plans(uid: &str, amounts: &[i64], rows_per_zone: usize) -> Vec<ZonePlan> {
+    let events: Vec<_> = amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| {
+            EventFactory::new()
+                .with("event_type", "order")
+                .with("timestamp", 100 + i as u64)
+                .with("payload", json!({ "amount": amount }))
+                .create()
+        })
+        .collect();
+    ZonePlan::build_all(&events, rows_per_zone, uid.to_string(), 1).unwrap()
+}
+
+#[test]
+fn build_from_zones_aggregates_each_zone_separately() {
+    let specs = vec![
+        AggregateOpSpec::Total {
+            field: "amount".into(),
+        },
+        AggregateOpSpec::Min {
+            field: "amount".into(),
+        },
+        AggregateOpSpec::Avg {
+            field: "amount".into(),
+        },
+    ];
+    let index = ZoneSummaryIndex::build_from_zones(&plans("uid", &[5, 7, 1, 3, 9], 2), &specs);
+
+    // COUNT is always kept so plain counts can use the summaries too
+    assert_eq!(index.specs[0], AggregateOpSpec::CountAll);
+    assert_eq!(index.zones.len(), 3);
+
+    let positions = index
+        .positions(&[
+            AggregateOpSpec::CountAll,
+            specs[0].clone(),
+            specs[1].clone(),
+        ])
+        .unwrap();
+    let zone = index.zone(1).unwrap();
+    let states: Vec<_> = positions.iter().map(|&i| zone[i].clone()).collect();
+    assert_eq!(
+        states,
+        vec![
+            AggState::CountAll { count: 2 },
+            AggState::Sum { sum: 4 },
+            AggState::Min {
+                min_num: Some(1),
+                min_str: None
+            },
+        ]
+    );
+    let last = index.zone(2).unwrap();
+    assert_eq!(
+        last[index.positions(&specs[2..]).unwrap()[0]],
+        AggState::Avg { sum: 9, count: 1 }
+    );
+}
+
+#[test]
+fn build_from_zones_skips_count_unique() {
+    let specs = vec![AggregateOpSpec::CountUnique {
+        field: "amount".into(),
+    }];
+    let index = ZoneSummaryIndex::build_from_zones(&plans("uid", &[1, 2], 2), &specs);
+
+    assert_eq!(index.specs, vec![AggregateOpSpec::CountAll]);
+    assert!(index.positions(&specs).is_none());
+}
+
+#[test]
+fn save_and_load_roundtrip() {
+    let tmp = tempfile::tempdir().unwrap();
+    let specs = vec![AggregateOpSpec::Max {
+        field: "amount".into(),
+    }];
+    let index = ZoneSummaryIndex::build_from_zones(&plans("uid", &[4, 8, 6], 1), &specs);
+
+    index.save("uid", tmp.path()).unwrap();
+    let loaded = ZoneSummaryIndex::load("uid", tmp.path()).unwrap();
+
+    assert_eq!(loaded, index);
+}
+
+#[tokio::test]
+async fn zone_writer_writes_summaries_only_when_requested() {
+    let schema_factory = SchemaRegistryFactory::new();
+    schema_factory
+        .define_with_fields("order", &[("context_id", "string"), ("amount", "int")])
+        .await
+        .unwrap();
+    let registry = schema_factory.registry();
+    let uid = registry.read().await.get_uid("order").unwrap();
+    let plans = plans(&uid, &[1, 2, 3], 2);
+
+    let plain = tempfile::tempdir().unwrap();
+    ZoneWriter::new(&uid, plain.path(), registry.clone())
+        .write_all(&plans)
+        .await
+        .unwrap();
+    assert!(!plain.path().join(format!("{}.zsum", uid)).exists());
+
+    let summarized = tempfile::tempdir().unwrap();
+    ZoneWriter::new(&uid, summarized.path(), registry.clone())
+        .with_zone_summaries(vec![AggregateOpSpec::Total {
+            field: "amount".into(),
+        }])
+        .write_all(&plans)
+        .await
+        .unwrap();
+    let index = ZoneSummaryIndex::load(&uid, summarized.path()).unwrap();
+    let sum_at = index
+        .positions(&[AggregateOpSpec::Total {
+            field: "amount".into(),
+        }])
+        .unwrap()[0];
+    assert_eq!(index.zone(0).unwrap()[sum_at], AggState::Sum { sum: 3 });
+    assert_eq!(index.zone(1).unwrap()[sum_at], AggState::Sum { sum: 3 });
+}
@@ -63,4 +63,15 @@ impl<'a> ZoneValueLoader<'a> {
             );
         }
     }
+
+    /// Loads further columns into one zone, keeping the ones it already holds.
+    /// Returns the decompressed bytes loaded.
+    pub fn load_more_values(&self, zone: &mut CandidateZone, columns: &[String]) -> usize {
+        let loader = ColumnLoader::new(self.segment_base_dir.clone(), self.uid.clone())
+            .with_caches(self.caches);
+        let values = loader.load_all_columns(zone, columns);
+        let bytes = values.values().map(|values| values.block.size).sum();
+        zone.values.extend(values);
+        bytes
+    }
 }
@@ -1,16 +1,23 @@
+use crate::command::types::FieldIndexKind;
 use crate::engine::core::ColumnWriter;
 use crate::engine::core::FieldXorFilter;
 use crate::engine::core::column::type_catalog::ColumnTypeCatalog;
+use crate::engine::core::compaction::codec_policy::CodecPolicy;
+use crate::engine::core::filter::fuse_filter::XorFilterSizing;
 use crate::engine::core::filter::zone_surf_filter::ZoneSurfFilter;
+use crate::engine::core::read::aggregate::plan::AggregateOpSpec;
 use crate::engine::core::read::catalog::{IndexKind, SegmentIndexCatalog};
+use crate::engine::core::segment::segment_id::SegmentId;
 use crate::engine::core::time::{CalendarDir, TemporalIndexBuilder};
 use crate::engine::core::zone::enum_bitmap_index::EnumBitmapBuilder;
+use crate::engine::core::zone::field_histogram::FieldHistogramIndex;
 use crate::engine::core::zone::index_build_planner::{BuildPlan, IndexBuildPlanner};
 use crate::engine::core::zone::index_build_policy::IndexBuildPolicy;
 use crate::engine::core::zone::rlte_index::RlteIndex;
 use crate::engine::core::zone::zone_meta::ZoneMeta;
 use crate::engine::core::zone::zone_metadata_writer::ZoneMetadataWriter;
-use crate::engine::core::zone::zone_xor_index::build_all_zxf_filtered;
+use crate::engine::core::zone::zone_summary::ZoneSummaryIndex;
+use crate::engine::core::zone::zone_xor_index::build_all_zxf_filtered_with_sizing;
 use crate::engine::core::{ZoneIndex, ZonePlan};
 use crate::engine::errors::StoreError;
 use crate::engine::schema::registry::SchemaRegistry;
@@ -25,6 +32,8 @@ pub struct ZoneWriter<'a> {
     pub segment_dir: &'a Path,
     pub registry: Arc<RwLock<SchemaRegistry>>,
     type_catalog: Option<ColumnTypeCatalog>,
+    xor_sizing: XorFilterSizing,
+    zone_summaries: Option<Vec<AggregateOpSpec>>,
 }
 
 impl<'a> ZoneWriter<'a> {
@@ -34,6 +43,8 @@ impl<'a> ZoneWriter<'a> {
             segment_dir,
             registry,
             type_catalog: None,
+            xor_sizing: XorFilterSizing::default(),
+            zone_summaries: None,
         }
     }
 
@@ -42,6 +53,18 @@ impl<'a> ZoneWriter<'a> {
         self
     }
 
+    /// Sets how the fingerprint width of `.xf` and `.zxf` filters is chosen.
+    pub fn with_xor_sizing(mut self, sizing: XorFilterSizing) -> Self {
+        self.xor_sizing = sizing;
+        self
+    }
+
+    /// Overrides the per-zone summaries otherwise taken from `engine.zone_summaries`.
+    pub fn with_zone_summaries(mut self, specs: Vec<AggregateOpSpec>) -> Self {
+        self.zone_summaries = Some(specs);
+        self
+    }
+
     pub async fn write_all(&self, zone_plans: &[ZonePlan]) -> Result<(), StoreError> {
         if tracing::enabled!(tracing::Level::INFO) {
             info!(
@@ -53,12 +76,19 @@ impl<'a> ZoneWriter<'a> {
             );
         }
 
-        // Write .zones metadata (delegated, async)
-        let metadata_writer = ZoneMetadataWriter::new(self.uid, self.segment_dir);
+        // Write .zones metadata and .zsum summaries (delegated, async)
+        let summaries = match &self.zone_summaries {
+            Some(specs) => specs.clone(),
+            None => ZoneSummaryIndex::configured_specs(&zone_plans[0].event_type),
+        };
+        let metadata_writer =
+            ZoneMetadataWriter::new(self.uid, self.segment_dir).with_summaries(&summaries);
         metadata_writer.write_async(zone_plans).await?;
 
-        // Write .col files
-        let mut writer = ColumnWriter::new(self.segment_dir.to_path_buf(), self.registry.clone());
+        // Write .col files, compressed as configured for the segment's level
+        let level = SegmentId::from(zone_plans[0].segment_id as u32).level();
+        let mut writer = ColumnWriter::new(self.segment_dir.to_path_buf(), self.registry.clone())
+            .with_codec(CodecPolicy::from_config().codec_for_level(level));
         if let Some(catalog) = &self.type_catalog {
             writer = writer.with_type_hints(catalog.clone());
         }
@@ -144,9 +174,13 @@ impl<'a> ZoneWriter<'a> {
                     }
                 })
                 .collect();
-            FieldXorFilter::build_all_filtered(zone_plans, self.segment_dir, &allowed).map_err(
-                |e| StoreError::FlushFailed(format!("Failed to build XOR filters: {}", e)),
-            )?;
+            FieldXorFilter::build_all_filtered_with_sizing(
+                zone_plans,
+                self.segment_dir,
+                &allowed,
+                self.xor_sizing,
+            )
+            .map_err(|e| StoreError::FlushFailed(format!("Failed to build XOR filters: {}", e)))?;
         }
 
         // Build per-zone XOR index (.zxf)
@@ -170,10 +204,14 @@ impl<'a> ZoneWriter<'a> {
                     }
                 })
                 .collect();
-            if let Err(e) = build_all_zxf_filtered(zone_plans, self.segment_dir, &allowed) {
-                if tracing::enabled!(tracing::Level::DEBUG) {
-                    debug!(target: "sneldb::flush", uid = self.uid, error = %e, "Skipping .zxf due to error");
-                }
+            if let Err(e) = build_all_zxf_filtered_with_sizing(
+                zone_plans,
+                self.segment_dir,
+                &allowed,
+                self.xor_sizing,
+            ) && tracing::enabled!(tracing::Level::DEBUG)
+            {
+                debug!(target: "sneldb::flush", uid = self.uid, error = %e, "Skipping .zxf due to error");
             }
         }
 
@@ -248,6 +286,36 @@ impl<'a> ZoneWriter<'a> {
             }
         }
 
+        // Build per-field histograms for planner selectivity (best-effort)
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            debug!(
+                target: "sneldb::flush",
+                uid = self.uid,
+                "Building field histograms"
+            );
+        }
+        let wants_histogram = |field: &str| {
+            schema
+                .as_ref()
+                .is_none_or(|s| s.builds_index(field, FieldIndexKind::Histogram))
+        };
+        match std::panic::catch_unwind(|| {
+            FieldHistogramIndex::build_from_zones_where(zone_plans, wants_histogram)
+        }) {
+            Ok(histograms) => {
+                if let Err(e) = histograms.save(self.uid, self.segment_dir)
+                    && tracing::enabled!(tracing::Level::DEBUG)
+                {
+                    debug!(target: "sneldb::flush", uid = self.uid, error = %e, "Skipping histograms due to IO error");
+                }
+            }
+            Err(_) => {
+                if tracing::enabled!(tracing::Level::DEBUG) {
+                    debug!(target: "sneldb::flush", uid = self.uid, "Skipping histograms due to build panic");
+                }
+            }
+        }
+
         // Build Enum Bitmap Indexes for enum fields (best-effort)
         if tracing::enabled!(tracing::Level::DEBUG) {
             debug!(
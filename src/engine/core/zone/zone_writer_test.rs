@@ -1,12 +1,15 @@
+use crate::command::types::FieldIndexKind;
 use crate::engine::core::ColumnReader;
 use crate::engine::core::column::format::PhysicalType;
 use crate::engine::core::column::type_catalog::ColumnTypeCatalog;
+use crate::engine::core::read::catalog::{IndexKind, SegmentIndexCatalog};
 use crate::engine::core::time::ZoneTemporalIndex;
+use crate::engine::core::zone::field_histogram::FieldHistogramIndex;
 use crate::engine::core::zone::rlte_index::RlteIndex;
 use crate::engine::core::zone::zone_xor_index::ZoneXorFilterIndex;
 use crate::engine::core::{FieldXorFilter, ZoneIndex, ZoneMeta, ZonePlanner, ZoneWriter};
 use crate::engine::types::ScalarValue;
-use crate::test_helpers::factories::{EventFactory, SchemaRegistryFactory};
+use crate::test_helpers::factories::{EventFactory, MiniSchemaFactory, SchemaRegistryFactory};
 use serde_json::json;
 
 #[tokio::test]
@@ -349,3 +352,84 @@ async fn test_zone_writer_type_catalog_does_not_override_schema_types() {
     // schema wins: still Bool
     assert_eq!(snapshot.physical_type(), PhysicalType::Bool);
 }
+
+#[tokio::test]
+async fn test_zone_writer_builds_only_configured_indexes() {
+    let tmp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let segment_dir = tmp_dir.path();
+
+    let schema_factory = SchemaRegistryFactory::new();
+    let registry = schema_factory.registry();
+
+    let event_type = "orders";
+    let schema = MiniSchemaFactory::empty()
+        .with("amount", "int")
+        .with("score", "int")
+        .with("note", "string")
+        .with("shipped_at", "datetime")
+        .with_enum("plan", &["free", "pro"])
+        .with_indexes("amount", &[FieldIndexKind::Xor])
+        .with_indexes("note", &[])
+        .with_indexes("shipped_at", &[])
+        .with_indexes("plan", &[FieldIndexKind::Histogram])
+        .create();
+    registry.write().await.define(event_type, schema).unwrap();
+    let uid = registry.read().await.get_uid(event_type).unwrap();
+
+    let events: Vec<_> = [(10, "a", "free"), (20, "b", "pro"), (30, "c", "pro")]
+        .iter()
+        .map(|(amount, note, plan)| {
+            EventFactory::new()
+                .with("event_type", event_type)
+                .with("context_id", format!("ctx-{}", note))
+                .with(
+                    "payload",
+                    json!({
+                        "amount": amount,
+                        "score": amount * 2,
+                        "note": note,
+                        "shipped_at": 1_700_000_000u64 + *amount as u64,
+                        "plan": plan,
+                    }),
+                )
+                .create()
+        })
+        .collect();
+
+    let plans = ZonePlanner::new(&uid, 7).plan(&events).unwrap();
+    ZoneWriter::new(&uid, segment_dir, registry.clone())
+        .write_all(&plans)
+        .await
+        .expect("ZoneWriter failed");
+
+    let file = |field: &str, ext: &str| segment_dir.join(format!("{}_{}.{}", uid, field, ext));
+    assert!(file("amount", "zxf").exists());
+    assert!(file("amount", "xf").exists());
+    assert!(!file("amount", "zsrf").exists());
+    assert!(
+        file("score", "zsrf").exists(),
+        "unconfigured fields keep defaults"
+    );
+    for ext in ["xf", "zxf", "zsrf"] {
+        assert!(!file("note", ext).exists(), "note.{} built", ext);
+    }
+    assert!(!file("shipped_at", "cal").exists());
+    assert!(!file("shipped_at", "tfi").exists());
+    assert!(!file("plan", "ebm").exists());
+    assert!(
+        file("timestamp", "cal").exists(),
+        "core timestamp keeps its calendar"
+    );
+
+    let histograms = FieldHistogramIndex::load(&uid, segment_dir).unwrap();
+    assert!(histograms.get("plan").is_some());
+    assert!(histograms.get("score").is_some());
+    assert!(histograms.get("amount").is_none());
+    assert!(histograms.get("note").is_none());
+
+    let catalog = SegmentIndexCatalog::load(&segment_dir.join(format!("{}.icx", uid))).unwrap();
+    assert!(!catalog.field_kinds["amount"].contains(IndexKind::ZONE_SURF));
+    assert!(catalog.field_kinds["amount"].contains(IndexKind::ZONE_XOR_INDEX));
+    assert!(!catalog.field_kinds["shipped_at"].contains(IndexKind::FIELD_CALENDAR));
+    assert!(!catalog.field_kinds["plan"].contains(IndexKind::ENUM_BITMAP));
+}
@@ -5,19 +5,22 @@ use std::path::{Path, PathBuf};
 
 use memmap2::MmapOptions;
 use tracing::{debug, info, warn};
-use xorf::{BinaryFuse8, Filter};
 
 use crate::engine::core::ZonePlan;
+use crate::engine::core::filter::fuse_filter::{
+    FuseFilter, XOR_FILE_VERSION_FIXED, XOR_FILE_VERSION_SIZED, XorFilterSizing, XorFingerprint,
+    fingerprint_from_byte,
+};
 use crate::engine::types::ScalarValue;
 use crate::shared::hash::stable_hash64;
-use crate::shared::storage_header::{BinaryHeader, FileKind, open_and_header_offset};
+use crate::shared::storage_header::{BinaryHeader, FileKind};
 
 #[derive(Debug, Clone)]
 pub struct ZoneXorFilterIndex {
     pub field: String,
     pub uid: String,
-    /// zone_id -> BinaryFuse8 filter
-    pub filters: HashMap<u32, BinaryFuse8>,
+    /// zone_id -> binary fuse filter
+    pub filters: HashMap<u32, FuseFilter>,
 }
 
 impl ZoneXorFilterIndex {
@@ -29,8 +32,8 @@ impl ZoneXorFilterIndex {
         }
     }
 
-    pub fn put_zone_filter(&mut self, zone_id: u32, filter: BinaryFuse8) {
-        self.filters.insert(zone_id, filter);
+    pub fn put_zone_filter(&mut self, zone_id: u32, filter: impl Into<FuseFilter>) {
+        self.filters.insert(zone_id, filter.into());
     }
 
     pub fn contains_in_zone(&self, zone_id: u32, value: &ScalarValue) -> bool {
@@ -66,6 +69,17 @@ impl ZoneXorFilterIndex {
         uid: &str,
         field: &str,
         zones: &[ZonePlan],
+    ) -> Option<ZoneXorFilterIndex> {
+        Self::build_for_field_with_sizing(uid, field, zones, XorFilterSizing::Fixed)
+    }
+
+    /// Like [`Self::build_for_field`], sizing each zone's filter with `sizing`
+    /// from that zone's distinct values.
+    pub fn build_for_field_with_sizing(
+        uid: &str,
+        field: &str,
+        zones: &[ZonePlan],
+        sizing: XorFilterSizing,
     ) -> Option<ZoneXorFilterIndex> {
         let mut index = ZoneXorFilterIndex::new(uid.to_string(), field.to_string());
 
@@ -99,7 +113,8 @@ impl ZoneXorFilterIndex {
             // Convert HashSet to Vec for iterator (order doesn't matter)
             let hashes_vec: Vec<u64> = unique_hashes.into_iter().collect();
             let hashes_count = hashes_vec.len();
-            match BinaryFuse8::try_from_iterator(hashes_vec.into_iter()) {
+            let fingerprint = sizing.fingerprint_for(hashes_count);
+            match FuseFilter::build(&hashes_vec, fingerprint) {
                 Ok(filter) => index.put_zone_filter(zone.id, filter),
                 Err(e) => {
                     // BinaryFuse8 can fail to construct even with valid, deduplicated hashes
@@ -129,6 +144,9 @@ impl ZoneXorFilterIndex {
 
     /// Save as: header | u32 zone_count | [entries...]
     /// Each entry: u32 zone_id | u32 blob_len | blob_bytes
+    ///
+    /// When any filter is wider than 8 bits the file is written as version 2,
+    /// where each entry carries its width: u32 zone_id | u8 bits | u32 blob_len | blob_bytes
     pub fn save(&self, path: &Path) -> std::io::Result<()> {
         if tracing::enabled!(tracing::Level::DEBUG) {
             debug!(target: "sneldb::zxf", path = %path.display(), uid = %self.uid, field = %self.field, zone_count = self.filters.len(), "Saving .zxf");
@@ -139,14 +157,25 @@ impl ZoneXorFilterIndex {
             .write(true)
             .truncate(true)
             .open(path)?;
-        let header = BinaryHeader::new(FileKind::ZoneXorFilter.magic(), 1, 0);
+        let sized = self
+            .filters
+            .values()
+            .any(|filter| filter.fingerprint() != XorFingerprint::Bits8);
+        let version = if sized {
+            XOR_FILE_VERSION_SIZED
+        } else {
+            XOR_FILE_VERSION_FIXED
+        };
+        let header = BinaryHeader::new(FileKind::ZoneXorFilter.magic(), version, 0);
         header.write_to(&mut file)?;
 
         file.write_all(&(self.filters.len() as u32).to_le_bytes())?;
         for (zone_id, filter) in &self.filters {
-            let blob = bincode::serialize(filter)
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            let blob = filter.to_bytes()?;
             file.write_all(&zone_id.to_le_bytes())?;
+            if sized {
+                file.write_all(&[filter.fingerprint().bits()])?;
+            }
             file.write_all(&(blob.len() as u32).to_le_bytes())?;
             file.write_all(&blob)?;
         }
@@ -154,10 +183,24 @@ impl ZoneXorFilterIndex {
     }
 
     pub fn load(path: &Path) -> std::io::Result<Self> {
-        let (file, offset) = open_and_header_offset(path, FileKind::ZoneXorFilter.magic())?;
+        let mut file = std::fs::File::open(path)?;
+        let header = BinaryHeader::read_from(&mut file)?;
+        if header.magic != FileKind::ZoneXorFilter.magic() {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid magic"));
+        }
+        let sized = match header.version {
+            XOR_FILE_VERSION_FIXED => false,
+            XOR_FILE_VERSION_SIZED => true,
+            other => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unsupported .zxf version: {}", other),
+                ));
+            }
+        };
         let mmap = unsafe { MmapOptions::new().map(&file)? };
 
-        let mut pos = offset as usize;
+        let mut pos = BinaryHeader::TOTAL_LEN;
         if mmap.len() < pos + 4 {
             return Err(Error::new(
                 ErrorKind::UnexpectedEof,
@@ -169,7 +212,7 @@ impl ZoneXorFilterIndex {
 
         // We do not store uid/field inside the file; derive from filename if needed
         let (uid, field) = parse_uid_field_from_filename(path);
-        let mut filters: HashMap<u32, BinaryFuse8> = HashMap::with_capacity(count as usize);
+        let mut filters: HashMap<u32, FuseFilter> = HashMap::with_capacity(count as usize);
         for _ in 0..count {
             if mmap.len() < pos + 8 {
                 return Err(Error::new(
@@ -179,6 +222,19 @@ impl ZoneXorFilterIndex {
             }
             let zone_id = u32::from_le_bytes(mmap[pos..pos + 4].try_into().unwrap());
             pos += 4;
+            let fingerprint = if sized {
+                let bits = mmap[pos];
+                pos += 1;
+                if mmap.len() < pos + 4 {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "Incomplete .zxf entry header",
+                    ));
+                }
+                fingerprint_from_byte(bits)?
+            } else {
+                XorFingerprint::Bits8
+            };
             let len = u32::from_le_bytes(mmap[pos..pos + 4].try_into().unwrap()) as usize;
             pos += 4;
 
@@ -191,9 +247,7 @@ impl ZoneXorFilterIndex {
             let buf = &mmap[pos..pos + len];
             pos += len;
 
-            let filter: BinaryFuse8 =
-                bincode::deserialize(buf).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
-            filters.insert(zone_id, filter);
+            filters.insert(zone_id, FuseFilter::from_bytes(fingerprint, buf)?);
         }
 
         Ok(ZoneXorFilterIndex {
@@ -233,6 +287,21 @@ pub fn build_all_zxf_filtered(
     zone_plans: &[ZonePlan],
     segment_dir: &Path,
     allowed_fields: &std::collections::HashSet<String>,
+) -> std::io::Result<()> {
+    build_all_zxf_filtered_with_sizing(
+        zone_plans,
+        segment_dir,
+        allowed_fields,
+        XorFilterSizing::Fixed,
+    )
+}
+
+/// Like [`build_all_zxf_filtered`], sizing zone filters with `sizing`.
+pub fn build_all_zxf_filtered_with_sizing(
+    zone_plans: &[ZonePlan],
+    segment_dir: &Path,
+    allowed_fields: &std::collections::HashSet<String>,
+    sizing: XorFilterSizing,
 ) -> std::io::Result<()> {
     if zone_plans.is_empty() {
         return Ok(());
@@ -251,7 +320,9 @@ pub fn build_all_zxf_filtered(
         if !allowed_fields.contains(&field) {
             continue;
         }
-        if let Some(index) = ZoneXorFilterIndex::build_for_field(&uid, &field, zone_plans) {
+        if let Some(index) =
+            ZoneXorFilterIndex::build_for_field_with_sizing(&uid, &field, zone_plans, sizing)
+        {
             let path = ZoneXorFilterIndex::file_path(segment_dir, &uid, &field);
             index.save(&path)?;
             if tracing::enabled!(tracing::Level::INFO) {
@@ -210,3 +210,55 @@ fn zxf_file_path_naming() {
     let p = ZoneXorFilterIndex::file_path(std::path::Path::new("/tmp/seg"), "uZ", "fQ");
     assert_eq!(p, std::path::Path::new("/tmp/seg/uZ_fQ.zxf"));
 }
+
+#[test]
+fn sized_zxf_records_width_per_zone() {
+    use crate::engine::core::filter::fuse_filter::{XorFilterSizing, XorFingerprint};
+    use crate::engine::core::zone::zone_xor_index::build_all_zxf_filtered_with_sizing;
+
+    let wide: Vec<_> = (0..300)
+        .map(|i| {
+            EventFactory::new()
+                .with("payload", json!({ "sku": format!("sku-{}", i) }))
+                .create()
+        })
+        .collect();
+    let narrow: Vec<_> = ["a", "b"]
+        .iter()
+        .map(|sku| {
+            EventFactory::new()
+                .with("payload", json!({ "sku": sku }))
+                .create()
+        })
+        .collect();
+    let z0 = ZonePlanFactory::new()
+        .with("id", 0)
+        .with("uid", "u09")
+        .with("events", json!(wide))
+        .create();
+    let z1 = ZonePlanFactory::new()
+        .with("id", 1)
+        .with("uid", "u09")
+        .with("events", json!(narrow))
+        .create();
+
+    let dir = tempfile::tempdir().unwrap();
+    let allowed: HashSet<String> = HashSet::from(["sku".to_string()]);
+    build_all_zxf_filtered_with_sizing(
+        &[z0, z1],
+        dir.path(),
+        &allowed,
+        XorFilterSizing::ByDistinctCount,
+    )
+    .unwrap();
+
+    let idx = ZoneXorFilterIndex::load(&dir.path().join("u09_sku.zxf")).unwrap();
+    assert_eq!(idx.filters[&0].fingerprint(), XorFingerprint::Bits16);
+    assert_eq!(idx.filters[&1].fingerprint(), XorFingerprint::Bits8);
+    assert!(idx.contains_in_zone(0, &ScalarValue::from(json!("sku-299"))));
+    assert!(idx.contains_in_zone(1, &ScalarValue::from(json!("b"))));
+    assert_eq!(
+        idx.zones_maybe_containing(&ScalarValue::from(json!("a"))),
+        vec![1]
+    );
+}
@@ -1,7 +1,7 @@
 use super::run::define_schema;
 use crate::engine::schema::FieldType;
 use crate::engine::schema::registry::{MiniSchema, SchemaRegistry};
-use std::collections::HashMap;
+use indexmap::IndexMap;
 use tempfile;
 
 #[tokio::test]
@@ -9,10 +9,13 @@ async fn test_define_schema_success() {
     crate::logging::init_for_tests();
     let tmpfile = tempfile::NamedTempFile::new().unwrap();
     let mut registry = SchemaRegistry::new_with_path(tmpfile.path().to_path_buf()).unwrap();
-    let mut fields = HashMap::new();
+    let mut fields = IndexMap::new();
     fields.insert("field1".to_string(), FieldType::String);
     let schema = MiniSchema {
         fields: fields.clone(),
+        time_field: None,
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
     };
     let result = define_schema(&mut registry, "test_event", 1, schema.clone()).await;
     assert!(result.is_ok(), "define_schema failed: {:?}", result);
@@ -23,10 +26,13 @@ async fn test_define_schema_success() {
 async fn test_define_schema_duplicate() {
     let tmpfile = tempfile::NamedTempFile::new().unwrap();
     let mut registry = SchemaRegistry::new_with_path(tmpfile.path().to_path_buf()).unwrap();
-    let mut fields = HashMap::new();
+    let mut fields = IndexMap::new();
     fields.insert("field1".to_string(), FieldType::String);
     let schema = MiniSchema {
         fields: fields.clone(),
+        time_field: None,
+        temporal_fields: Vec::new(),
+        indexes: IndexMap::new(),
     };
     let _ = define_schema(&mut registry, "test_event", 1, schema.clone()).await;
     let result = define_schema(&mut registry, "test_event", 1, schema).await;
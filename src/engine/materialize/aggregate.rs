@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::engine::core::read::aggregate::partial::{AggPartial, AggState, GroupKey};
+use crate::engine::core::read::aggregate::plan::AggregatePlan;
+use crate::engine::core::read::flow::operators::ColumnConverter;
+use crate::engine::core::read::flow::{BatchError, BatchPool, BatchSchema, ColumnBatch};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::core::read::sink::AggregateSink;
+use crate::engine::types::ScalarValue;
+
+use super::MaterializationError;
+use super::high_water::HighWaterMark;
+use super::store::MaterializedStore;
+
+/// Mergeable aggregate state of a materialized aggregate query.
+///
+/// Source events are folded in with the same aggregators a shard runs, and
+/// the resulting partials are merged into the held groups, so absorbing the
+/// events of several refreshes gives the states a single full recompute
+/// would. A stored state frame holds one row per group: the bucket and group
+/// values, then one JSON-encoded [`AggState`] per aggregate.
+pub struct AggregateState {
+    partial: AggPartial,
+}
+
+impl AggregateState {
+    pub fn new(plan: AggregatePlan) -> Self {
+        Self {
+            partial: AggPartial {
+                specs: plan.ops,
+                group_by: plan.group_by,
+                time_bucket: plan.time_bucket,
+                groups: HashMap::new(),
+            },
+        }
+    }
+
+    /// Loads the state stored in `store` with the high-water mark it covers.
+    /// Both come from the same manifest, so a refresh committed after the
+    /// store was opened never pairs a newer state with an older mark.
+    pub fn from_store(
+        store: &MaterializedStore,
+        plan: AggregatePlan,
+    ) -> Result<(Self, HighWaterMark), MaterializationError> {
+        let mut state = Self::new(plan);
+        for meta in store.frames() {
+            state.load(store.read_frame(meta)?.as_ref())?;
+        }
+        let high_water = store
+            .frames()
+            .last()
+            .map(|meta| meta.high_water_mark)
+            .unwrap_or_default();
+        Ok((state, high_water))
+    }
+
+    /// Column layout of the stored state frames for `plan`.
+    pub fn state_schema(plan: &AggregatePlan) -> Result<BatchSchema, MaterializationError> {
+        let column = |name: String, logical_type: &str| ColumnSpec {
+            name,
+            logical_type: logical_type.to_string(),
+        };
+        let mut columns = Vec::new();
+        if plan.time_bucket.is_some() {
+            columns.push(column("bucket".into(), "Integer"));
+        }
+        for field in plan.group_by.iter().flatten() {
+            columns.push(column(field.clone(), "String"));
+        }
+        for idx in 0..plan.ops.len() {
+            columns.push(column(format!("agg_{idx}_state"), "String"));
+        }
+        BatchSchema::new(columns).map_err(|e| MaterializationError::Batch(e.to_string()))
+    }
+
+    pub fn group_count(&self) -> usize {
+        self.partial.groups.len()
+    }
+
+    /// Folds a batch of source events into the groups, bucketing on `time_field`.
+    pub fn absorb(
+        &mut self,
+        batch: &ColumnBatch,
+        time_field: &str,
+    ) -> Result<(), MaterializationError> {
+        let column_names: Vec<String> = batch
+            .schema()
+            .columns()
+            .iter()
+            .map(|column| column.name.clone())
+            .collect();
+
+        let mut sink = AggregateSink::from_plan(&self.plan());
+        sink.time_field = time_field.to_string();
+        sink.initialize_column_indices(&column_names);
+
+        let needed = ColumnConverter::determine_needed_columns(&sink);
+        let columns = ColumnConverter::convert(batch, &column_names, &needed)
+            .map_err(|e| MaterializationError::Batch(e.to_string()))?;
+        sink.on_column_slice(0, batch.len(), &columns);
+
+        self.partial.merge(&sink.into_partial());
+        Ok(())
+    }
+
+    /// Merges the groups of a stored state frame.
+    pub fn load(&mut self, batch: &ColumnBatch) -> Result<(), MaterializationError> {
+        let group_width = self.partial.group_by.as_ref().map_or(0, Vec::len);
+        let state_start = usize::from(self.partial.time_bucket.is_some()) + group_width;
+
+        let mut loaded = HashMap::with_capacity(batch.len());
+        for row_idx in 0..batch.len() {
+            let row = batch
+                .row(row_idx)
+                .map_err(|e| MaterializationError::Batch(e.to_string()))?;
+            if row.len() != state_start + self.partial.specs.len() {
+                return Err(MaterializationError::Corrupt(format!(
+                    "Aggregate state row has {} columns, expected {}",
+                    row.len(),
+                    state_start + self.partial.specs.len()
+                )));
+            }
+
+            let bucket = if self.partial.time_bucket.is_some() {
+                row[0].as_u64()
+            } else {
+                None
+            };
+            let groups = row[state_start - group_width..state_start]
+                .iter()
+                .map(|value| value.as_str().filter(|s| !s.is_empty()).map(str::to_string))
+                .collect();
+            let states = row[state_start..]
+                .iter()
+                .map(|value| {
+                    let json = value.as_str().ok_or_else(|| {
+                        MaterializationError::Corrupt("Aggregate state is not a string".into())
+                    })?;
+                    Ok(serde_json::from_str::<AggState>(json)?)
+                })
+                .collect::<Result<Vec<_>, MaterializationError>>()?;
+            loaded.insert(GroupKey { bucket, groups }, states);
+        }
+
+        self.partial.merge(&AggPartial {
+            specs: self.partial.specs.clone(),
+            group_by: self.partial.group_by.clone(),
+            time_bucket: self.partial.time_bucket.clone(),
+            groups: loaded,
+        });
+        Ok(())
+    }
+
+    /// Encodes every group as one row of a state frame.
+    pub fn to_batch(&self, schema: &BatchSchema) -> Result<ColumnBatch, MaterializationError> {
+        let batch_err = |e: BatchError| MaterializationError::Batch(e.to_string());
+        let pool = BatchPool::new(self.group_count().max(1)).map_err(batch_err)?;
+        let mut builder = pool.acquire(Arc::new(schema.clone()));
+
+        for (key, states) in &self.partial.groups {
+            let mut row = Vec::with_capacity(schema.column_count());
+            if self.partial.time_bucket.is_some() {
+                row.push(
+                    key.bucket
+                        .map_or(ScalarValue::Null, |b| ScalarValue::Int64(b as i64)),
+                );
+            }
+            row.extend(
+                key.groups
+                    .iter()
+                    .map(|group| group.clone().map_or(ScalarValue::Null, ScalarValue::Utf8)),
+            );
+            for state in states {
+                row.push(ScalarValue::Utf8(serde_json::to_string(state)?));
+            }
+            builder.push_row(&row).map_err(batch_err)?;
+        }
+
+        builder.finish().map_err(batch_err)
+    }
+
+    /// The held groups as the partial a shard emits for the same query.
+    pub fn into_partial(self) -> AggPartial {
+        self.partial
+    }
+
+    pub fn plan(&self) -> AggregatePlan {
+        AggregatePlan {
+            ops: self.partial.specs.clone(),
+            group_by: self.partial.group_by.clone(),
+            time_bucket: self.partial.time_bucket.clone(),
+        }
+    }
+}
+
+/// Highest `(time_field, event_id)` among the events of `batch`.
+pub(crate) fn batch_high_water(batch: &ColumnBatch, time_field: &str) -> HighWaterMark {
+    let position = |name: &str| {
+        batch
+            .schema()
+            .columns()
+            .iter()
+            .position(|column| column.name == name)
+    };
+    let mut mark = HighWaterMark::default();
+    let (Some(time_idx), Some(event_idx)) = (position(time_field), position("event_id")) else {
+        return mark;
+    };
+    let (Ok(times), Ok(event_ids)) = (batch.column(time_idx), batch.column(event_idx)) else {
+        return mark;
+    };
+    for (time, event_id) in times.iter().zip(event_ids.iter()) {
+        if let (Some(ts), Some(id)) = (time.as_u64(), event_id.as_u64()) {
+            mark.advance(ts, id);
+        }
+    }
+    mark
+}
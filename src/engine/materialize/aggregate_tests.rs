@@ -0,0 +1,188 @@
+use super::aggregate::AggregateState;
+use super::high_water::HighWaterMark;
+use super::sink::MaterializedSink;
+use super::store::MaterializedStore;
+use crate::command::types::TimeGranularity;
+use crate::engine::core::read::aggregate::partial::AggPartial;
+use crate::engine::core::read::aggregate::plan::{AggregateOpSpec, AggregatePlan};
+use crate::engine::core::read::flow::{BatchPool, BatchSchema, ColumnBatch};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::types::ScalarValue;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn event_schema() -> Arc<BatchSchema> {
+    let column = |name: &str, logical_type: &str| ColumnSpec {
+        name: name.into(),
+        logical_type: logical_type.into(),
+    };
+    Arc::new(
+        BatchSchema::new(vec![
+            column("timestamp", "Timestamp"),
+            column("event_id", "Integer"),
+            column("country", "String"),
+            column("user", "String"),
+            column("amount", "Integer"),
+        ])
+        .unwrap(),
+    )
+}
+
+fn plan(time_bucket: Option<TimeGranularity>) -> AggregatePlan {
+    AggregatePlan {
+        ops: vec![
+            AggregateOpSpec::CountAll,
+            AggregateOpSpec::Total {
+                field: "amount".into(),
+            },
+            AggregateOpSpec::Avg {
+                field: "amount".into(),
+            },
+            AggregateOpSpec::CountUnique {
+                field: "user".into(),
+            },
+            AggregateOpSpec::Max {
+                field: "amount".into(),
+            },
+        ],
+        group_by: Some(vec!["country".into()]),
+        time_bucket,
+    }
+}
+
+/// Events of (timestamp, event_id, country, user, amount).
+fn events() -> Vec<(i64, i64, &'static str, &'static str, i64)> {
+    vec![
+        (1_700_000_000, 1, "US", "ann", 10),
+        (1_700_000_000, 2, "DE", "bob", 5),
+        (1_700_003_600, 3, "US", "cat", 7),
+        (1_700_003_600, 4, "US", "ann", 30),
+        (1_700_007_200, 5, "DE", "dan", 2),
+        (1_700_007_200, 6, "FR", "eve", 9),
+    ]
+}
+
+fn batch_of(events: &[(i64, i64, &str, &str, i64)]) -> ColumnBatch {
+    let pool = BatchPool::new(events.len().max(1)).unwrap();
+    let mut builder = pool.acquire(event_schema());
+    for (ts, id, country, user, amount) in events {
+        builder
+            .push_row(&[
+                ScalarValue::Timestamp(*ts),
+                ScalarValue::Int64(*id),
+                ScalarValue::Utf8(country.to_string()),
+                ScalarValue::Utf8(user.to_string()),
+                ScalarValue::Int64(*amount),
+            ])
+            .unwrap();
+    }
+    builder.finish().unwrap()
+}
+
+fn stored_state(dir: &std::path::Path, plan: AggregatePlan) -> AggPartial {
+    let store = MaterializedStore::open(dir).unwrap();
+    let mut state = AggregateState::new(plan);
+    for meta in store.frames() {
+        state.load(&store.read_frame(meta).unwrap()).unwrap();
+    }
+    state.into_partial()
+}
+
+#[test]
+fn state_frame_round_trips_every_group() {
+    let plan = plan(Some(TimeGranularity::Hour));
+    let mut state = AggregateState::new(plan.clone());
+    state.absorb(&batch_of(&events()), "timestamp").unwrap();
+
+    let schema = AggregateState::state_schema(&plan).unwrap();
+    let batch = state.to_batch(&schema).unwrap();
+    assert_eq!(batch.len(), state.group_count());
+
+    let mut loaded = AggregateState::new(plan);
+    loaded.load(&batch).unwrap();
+    assert_eq!(loaded.into_partial(), state.into_partial());
+}
+
+#[test]
+fn incremental_commits_match_a_full_recompute() {
+    for bucket in [None, Some(TimeGranularity::Hour)] {
+        let all = events();
+
+        let full_dir = tempdir().unwrap();
+        let store = MaterializedStore::open(full_dir.path()).unwrap();
+        let mut full =
+            MaterializedSink::aggregating(store, plan(bucket.clone()), "timestamp").unwrap();
+        full.append(&batch_of(&all)).unwrap();
+        full.commit().unwrap();
+
+        let dir = tempdir().unwrap();
+        for chunk in all.chunks(2) {
+            // Reopen for every refresh so each one starts from the stored state
+            let store = MaterializedStore::open(dir.path()).unwrap();
+            let mut sink =
+                MaterializedSink::aggregating(store, plan(bucket.clone()), "timestamp").unwrap();
+            sink.append(&batch_of(chunk)).unwrap();
+            sink.commit().unwrap();
+            assert_eq!(sink.last_rows_appended(), chunk.len() as u64);
+        }
+
+        assert_eq!(
+            stored_state(dir.path(), plan(bucket.clone())),
+            stored_state(full_dir.path(), plan(bucket))
+        );
+        let store = MaterializedStore::open(dir.path()).unwrap();
+        assert_eq!(store.frames().len(), 1);
+        assert_eq!(
+            store.frames()[0].high_water_mark,
+            HighWaterMark::new(1_700_007_200, 6)
+        );
+    }
+}
+
+#[test]
+fn high_water_mark_advances_only_on_commit() {
+    let dir = tempdir().unwrap();
+    let all = events();
+
+    let store = MaterializedStore::open(dir.path()).unwrap();
+    let mut sink = MaterializedSink::aggregating(store, plan(None), "timestamp").unwrap();
+    sink.append(&batch_of(&all[..3])).unwrap();
+    sink.commit().unwrap();
+    assert_eq!(sink.high_water_mark(), HighWaterMark::new(1_700_003_600, 3));
+    assert_eq!(sink.total_rows(), 2);
+
+    // Merged but never committed: neither the state nor the mark moves
+    sink.append(&batch_of(&all[3..])).unwrap();
+    assert_eq!(sink.high_water_mark(), HighWaterMark::new(1_700_003_600, 3));
+    drop(sink);
+
+    let store = MaterializedStore::open(dir.path()).unwrap();
+    let sink = MaterializedSink::aggregating(store, plan(None), "timestamp").unwrap();
+    assert_eq!(sink.high_water_mark(), HighWaterMark::new(1_700_003_600, 3));
+
+    let mut expected = AggregateState::new(plan(None));
+    expected.absorb(&batch_of(&all[..3]), "timestamp").unwrap();
+    assert_eq!(
+        stored_state(dir.path(), plan(None)),
+        expected.into_partial()
+    );
+}
+
+#[test]
+fn commit_without_new_events_keeps_the_stored_frame() {
+    let dir = tempdir().unwrap();
+    let store = MaterializedStore::open(dir.path()).unwrap();
+    let mut sink = MaterializedSink::aggregating(store, plan(None), "timestamp").unwrap();
+    sink.commit().unwrap();
+    assert!(sink.high_water_mark().is_zero());
+
+    sink.append(&batch_of(&events())).unwrap();
+    sink.commit().unwrap();
+    let file_name = sink.into_store().frames()[0].file_name.clone();
+
+    let store = MaterializedStore::open(dir.path()).unwrap();
+    let mut sink = MaterializedSink::aggregating(store, plan(None), "timestamp").unwrap();
+    sink.commit().unwrap();
+    assert_eq!(sink.last_rows_appended(), 0);
+    assert_eq!(sink.into_store().frames()[0].file_name, file_name);
+}
@@ -1,6 +1,6 @@
 use crate::engine::materialize::MaterializationError;
 use crate::engine::materialize::catalog::{
-    CatalogIndex, EntryFile, IndexFile, MaterializationEntry,
+    CatalogIndex, EntryFile, IndexFile, IndexOp, MaterializationEntry,
 };
 use lru::LruCache;
 use once_cell::sync::Lazy;
@@ -224,6 +224,34 @@ impl GlobalMaterializationCatalogCache {
         }
     }
 
+    /// Applies `op` to the cached index for `index_path`, if it is cached, so
+    /// readers see a change without the index being reloaded. Runs under the
+    /// index's singleflight lock: a load racing with the change either
+    /// finishes first and gets `op` applied, or starts after the change was
+    /// written and reads it from disk.
+    pub fn apply_index_op(&self, index_path: &Path, op: &IndexOp) {
+        let key = CatalogIndexCacheKey {
+            path: index_path.to_path_buf(),
+        };
+
+        let lock_arc = {
+            let mut map = self.index_inflight.lock().unwrap();
+            map.entry(key.clone())
+                .or_insert_with(|| Arc::new(Mutex::new(())))
+                .clone()
+        };
+        let _loader_guard = lock_arc.lock().unwrap();
+
+        if let Ok(mut guard) = self.index_cache.lock()
+            && let Some(index_arc) = guard.get_mut(&key)
+        {
+            Arc::make_mut(index_arc).apply(op);
+        }
+
+        let mut map = self.index_inflight.lock().unwrap();
+        map.remove(&key);
+    }
+
     /// Invalidate an entry cache for a given path
     pub fn invalidate_entry(&self, entry_path: &Path) {
         let key = EntryCacheKey {
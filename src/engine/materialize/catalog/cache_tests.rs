@@ -1,7 +1,7 @@
 use super::cache::{CacheOutcome, GlobalMaterializationCatalogCache};
 use super::entry::MaterializationEntry;
 use super::entry_file::{EntryFile, entry_file_path};
-use super::index::{CatalogIndex, IndexFile};
+use super::index::{CatalogIndex, IndexEntry, IndexFile, IndexOp};
 use crate::command::types::MaterializedQuerySpec;
 use crate::engine::materialize::MaterializationError;
 use crate::test_helpers::factories::CommandFactory;
@@ -304,3 +304,33 @@ fn cache_instance_returns_singleton() {
     // Same memory address (same static)
     assert_eq!(cache1.stats().index_hits, cache2.stats().index_hits);
 }
+
+#[test]
+fn cache_apply_index_op_updates_cached_index_in_place() -> Result<(), MaterializationError> {
+    let cache = get_test_cache();
+    let dir = tempdir().unwrap();
+    let index_path = dir.path().join("catalog.mcat");
+    IndexFile::new(index_path.clone()).persist(&CatalogIndex::new())?;
+
+    let (before, _) = cache.get_or_load_index(&index_path)?;
+    let op = IndexOp::Add(IndexEntry {
+        name: "entry1".to_string(),
+        entry_path: dir.path().join("entry1"),
+        created_at: 1,
+    });
+    cache.apply_index_op(&index_path, &op);
+
+    // Readers holding the previous index keep it unchanged
+    assert!(before.is_empty());
+    let (after, outcome) = cache.get_or_load_index(&index_path)?;
+    assert_eq!(outcome, CacheOutcome::Hit);
+    assert!(after.contains("entry1"));
+
+    // An index that is not cached is left to be loaded from disk
+    cache.invalidate_index(&index_path);
+    cache.apply_index_op(&index_path, &op);
+    let (reloaded, outcome) = cache.get_or_load_index(&index_path)?;
+    assert_eq!(outcome, CacheOutcome::Miss);
+    assert!(reloaded.is_empty());
+    Ok(())
+}
@@ -4,8 +4,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::command::types::MaterializedQuerySpec;
 use crate::engine::materialize::MaterializationError;
-use crate::engine::materialize::high_water::HighWaterMark;
+use crate::engine::materialize::high_water::{HighWaterMark, HighWaterPolicy};
 use crate::engine::materialize::spec::MaterializedQuerySpecExt;
+use crate::engine::materialize::store::{FrameCodec, FrameSize};
 use crate::shared::time::now;
 
 use super::policy::RetentionPolicy;
@@ -38,6 +39,16 @@ pub struct MaterializationEntry {
     pub updated_at: u64,
     #[serde(default)]
     pub retention: Option<RetentionPolicy>,
+    /// Compression of newly written frames. Each frame records its own codec,
+    /// so changing it leaves existing frames readable.
+    #[serde(default)]
+    pub codec: FrameCodec,
+    /// Target size of newly written frames.
+    #[serde(default)]
+    pub frame_size: FrameSize,
+    /// How often a refresh persists its progress.
+    #[serde(default)]
+    pub high_water_policy: HighWaterPolicy,
 }
 
 impl MaterializationEntry {
@@ -58,6 +69,9 @@ impl MaterializationEntry {
             created_at: now(),
             updated_at: now(),
             retention: None,
+            codec: FrameCodec::default(),
+            frame_size: FrameSize::default(),
+            high_water_policy: HighWaterPolicy::default(),
         })
     }
 
@@ -69,6 +83,18 @@ impl MaterializationEntry {
         self.retention = Some(policy);
     }
 
+    pub fn set_codec(&mut self, codec: FrameCodec) {
+        self.codec = codec;
+    }
+
+    pub fn set_frame_size(&mut self, frame_size: FrameSize) {
+        self.frame_size = frame_size;
+    }
+
+    pub fn set_high_water_policy(&mut self, policy: HighWaterPolicy) {
+        self.high_water_policy = policy;
+    }
+
     pub fn telemetry_summary(&self) -> MaterializationTelemetry {
         MaterializationTelemetry {
             row_count: self.row_count,
@@ -7,6 +7,13 @@ use crate::shared::storage_header::{BinaryHeader, FileKind};
 use tracing::warn;
 
 use super::entry::MaterializationEntry;
+use crate::engine::materialize::high_water::HighWaterPolicy;
+use crate::engine::materialize::store::{FrameCodec, FrameSize};
+
+/// Entry file format version. Version 1 entries end before the frame codec,
+/// version 2 entries before the frame size, version 3 entries before the
+/// high-water policy.
+const ENTRY_VERSION: u16 = 4;
 
 /// Handles loading and persisting individual materialization entry files
 pub struct EntryFile {
@@ -44,25 +51,38 @@ impl EntryFile {
         }
 
         // Validate header from buffer
-        {
+        let version = {
             use std::io::Cursor;
             let mut cursor = Cursor::new(&buf[..BinaryHeader::TOTAL_LEN]);
-            if let Err(err) = self.validate_header(&mut cursor) {
-                warn!(
-                    error = %err,
-                    path = %self.path.display(),
-                    "entry header invalid"
-                );
-                return Err(MaterializationError::Corrupt(format!(
-                    "Invalid entry file header: {}",
-                    err
-                )));
+            match self.validate_header(&mut cursor) {
+                Ok(version) => version,
+                Err(err) => {
+                    warn!(
+                        error = %err,
+                        path = %self.path.display(),
+                        "entry header invalid"
+                    );
+                    return Err(MaterializationError::Corrupt(format!(
+                        "Invalid entry file header: {}",
+                        err
+                    )));
+                }
             }
-        }
+        };
 
         // Skip header when deserializing
-        let data = &buf[BinaryHeader::TOTAL_LEN..];
-        bincode::deserialize::<MaterializationEntry>(data).map_err(|e| {
+        let mut data = buf.split_off(BinaryHeader::TOTAL_LEN);
+        if version == 1 {
+            // Materializations created before the codec choice wrote LZ4 frames
+            data.extend(bincode::serialize(&FrameCodec::Lz4)?);
+        }
+        if version <= 2 {
+            data.extend(bincode::serialize(&FrameSize::default())?);
+        }
+        if version <= 3 {
+            data.extend(bincode::serialize(&HighWaterPolicy::default())?);
+        }
+        bincode::deserialize::<MaterializationEntry>(&data).map_err(|e| {
             MaterializationError::Corrupt(format!("Failed to deserialize entry: {}", e))
         })
     }
@@ -83,7 +103,11 @@ impl EntryFile {
             .open(&tmp_path)?;
 
         // Write header
-        let header = BinaryHeader::new(FileKind::MaterializationCatalogEntry.magic(), 1, 0);
+        let header = BinaryHeader::new(
+            FileKind::MaterializationCatalogEntry.magic(),
+            ENTRY_VERSION,
+            0,
+        );
         header
             .write_to(&mut file)
             .map_err(|e| MaterializationError::Header(e.to_string()))?;
@@ -102,7 +126,7 @@ impl EntryFile {
     fn validate_header<R: std::io::Read>(
         &self,
         reader: &mut R,
-    ) -> Result<(), MaterializationError> {
+    ) -> Result<u16, MaterializationError> {
         let header = BinaryHeader::read_from(reader)?;
         let expected_magic = FileKind::MaterializationCatalogEntry.magic();
 
@@ -112,14 +136,14 @@ impl EntryFile {
             ));
         }
 
-        if header.version != 1 {
+        if header.version == 0 || header.version > ENTRY_VERSION {
             return Err(MaterializationError::Header(format!(
                 "unsupported version {}",
                 header.version
             )));
         }
 
-        Ok(())
+        Ok(header.version)
     }
 }
 
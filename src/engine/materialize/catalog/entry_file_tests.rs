@@ -2,6 +2,8 @@ use super::entry::MaterializationEntry;
 use super::entry_file::{EntryFile, entry_file_path};
 use crate::command::types::MaterializedQuerySpec;
 use crate::engine::materialize::MaterializationError;
+use crate::engine::materialize::high_water::HighWaterPolicy;
+use crate::engine::materialize::store::{FrameCodec, FrameSize};
 use crate::shared::storage_header::{BinaryHeader, FileKind};
 use crate::test_helpers::factories::CommandFactory;
 use std::fs;
@@ -167,3 +169,121 @@ fn entry_file_path_handles_nested_directories() {
     let path = entry_file_path(root, "nested/deep/entry");
     assert!(path.ends_with("nested/deep/entry/entry.mcatentry"));
 }
+
+#[test]
+fn entry_file_persists_frame_codec() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    let entry_file = EntryFile::new(dir.path().join("entry.mcatentry"));
+
+    let mut entry = make_entry(dir.path(), "cold_view");
+    entry.set_codec(FrameCodec::Zstd);
+    entry_file.persist(&entry)?;
+
+    assert_eq!(entry_file.load()?.codec, FrameCodec::Zstd);
+    Ok(())
+}
+
+#[test]
+fn entry_file_loads_version_one_entries_as_lz4() -> Result<(), MaterializationError> {
+    use std::io::Write;
+
+    let dir = tempdir().unwrap();
+    let entry_path = dir.path().join("entry.mcatentry");
+
+    // Version 1 entries were written without the trailing codec, frame size
+    // and high-water policy fields
+    let mut entry = make_entry(dir.path(), "legacy");
+    entry.row_count = 7;
+    let mut serialized = bincode::serialize(&entry)?;
+    serialized.truncate(
+        serialized.len()
+            - bincode::serialize(&FrameCodec::Lz4)?.len()
+            - bincode::serialize(&FrameSize::default())?.len()
+            - bincode::serialize(&HighWaterPolicy::default())?.len(),
+    );
+    let mut file = fs::File::create(&entry_path)?;
+    BinaryHeader::new(FileKind::MaterializationCatalogEntry.magic(), 1, 0).write_to(&mut file)?;
+    file.write_all(&serialized)?;
+
+    let loaded = EntryFile::new(entry_path).load()?;
+    assert_eq!(loaded.row_count, 7);
+    assert_eq!(loaded.codec, FrameCodec::Lz4);
+    Ok(())
+}
+
+#[test]
+fn entry_file_persists_frame_size() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    let entry_file = EntryFile::new(dir.path().join("entry.mcatentry"));
+
+    let mut entry = make_entry(dir.path(), "big_frames");
+    entry.set_frame_size(FrameSize::Bytes(4 << 20));
+    entry_file.persist(&entry)?;
+
+    assert_eq!(entry_file.load()?.frame_size, FrameSize::Bytes(4 << 20));
+    Ok(())
+}
+
+#[test]
+fn entry_file_loads_version_two_entries_with_default_frame_size() -> Result<(), MaterializationError>
+{
+    use std::io::Write;
+
+    let dir = tempdir().unwrap();
+    let entry_path = dir.path().join("entry.mcatentry");
+
+    let mut entry = make_entry(dir.path(), "legacy");
+    entry.set_codec(FrameCodec::Zstd);
+    let mut serialized = bincode::serialize(&entry)?;
+    serialized.truncate(
+        serialized.len()
+            - bincode::serialize(&FrameSize::default())?.len()
+            - bincode::serialize(&HighWaterPolicy::default())?.len(),
+    );
+    let mut file = fs::File::create(&entry_path)?;
+    BinaryHeader::new(FileKind::MaterializationCatalogEntry.magic(), 2, 0).write_to(&mut file)?;
+    file.write_all(&serialized)?;
+
+    let loaded = EntryFile::new(entry_path).load()?;
+    assert_eq!(loaded.codec, FrameCodec::Zstd);
+    assert_eq!(loaded.frame_size, FrameSize::default());
+    Ok(())
+}
+
+#[test]
+fn entry_file_persists_high_water_policy() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    let entry_file = EntryFile::new(dir.path().join("entry.mcatentry"));
+
+    let mut entry = make_entry(dir.path(), "checkpointed");
+    entry.set_high_water_policy(HighWaterPolicy::EverySeconds(30));
+    entry_file.persist(&entry)?;
+
+    assert_eq!(
+        entry_file.load()?.high_water_policy,
+        HighWaterPolicy::EverySeconds(30)
+    );
+    Ok(())
+}
+
+#[test]
+fn entry_file_loads_version_three_entries_with_default_high_water_policy()
+-> Result<(), MaterializationError> {
+    use std::io::Write;
+
+    let dir = tempdir().unwrap();
+    let entry_path = dir.path().join("entry.mcatentry");
+
+    let mut entry = make_entry(dir.path(), "legacy");
+    entry.set_frame_size(FrameSize::Rows(64));
+    let mut serialized = bincode::serialize(&entry)?;
+    serialized.truncate(serialized.len() - bincode::serialize(&HighWaterPolicy::default())?.len());
+    let mut file = fs::File::create(&entry_path)?;
+    BinaryHeader::new(FileKind::MaterializationCatalogEntry.magic(), 3, 0).write_to(&mut file)?;
+    file.write_all(&serialized)?;
+
+    let loaded = EntryFile::new(entry_path).load()?;
+    assert_eq!(loaded.frame_size, FrameSize::Rows(64));
+    assert_eq!(loaded.high_water_policy, HighWaterPolicy::EveryFrame);
+    Ok(())
+}
@@ -1,7 +1,8 @@
+use crc32fast::Hasher as Crc32Hasher;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
-use std::io::{Read, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 use crate::engine::materialize::MaterializationError;
@@ -9,14 +10,30 @@ use crate::shared::storage_header::{BinaryHeader, FileKind};
 use crate::shared::time::now;
 use tracing::{error, warn};
 
+const INDEX_LOG_VERSION: u16 = 1;
+const MAX_LOG_RECORD_LEN: usize = 64 * 1024;
+
+/// Once the index log grows past this many bytes, the next change folds it
+/// into a new snapshot.
+pub const INDEX_LOG_COMPACT_BYTES: u64 = 64 * 1024;
+
 /// Lightweight index entry mapping name to entry file path
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IndexEntry {
     pub name: String,
     pub entry_path: PathBuf,
     pub created_at: u64,
 }
 
+/// One change to the index, as recorded in the index log. Applying an op
+/// sets the state of one name, so replaying ops the snapshot already holds
+/// changes nothing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndexOp {
+    Add(IndexEntry),
+    Remove { name: String },
+}
+
 /// Catalog index file storing name -> entry path mappings
 /// This is small and fast to load.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +102,22 @@ impl CatalogIndex {
         removed
     }
 
+    pub fn apply(&mut self, op: &IndexOp) {
+        match op {
+            IndexOp::Add(entry) => {
+                if let Some(existing) = self.entries.iter_mut().find(|e| e.name == entry.name) {
+                    *existing = entry.clone();
+                } else {
+                    self.entries.push(entry.clone());
+                }
+                self.updated_at = now();
+            }
+            IndexOp::Remove { name } => {
+                self.remove(name);
+            }
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.entries.len()
     }
@@ -100,22 +133,42 @@ impl Default for CatalogIndex {
     }
 }
 
-/// Handles loading and persisting the catalog index file
+/// Handles loading and persisting the catalog index file.
+///
+/// The index is kept as a snapshot (`catalog.mcat`) plus a log of the
+/// changes made since (`catalog.mlog`). A change appends one checksummed
+/// record to the log; loading replays the log over the snapshot, stopping
+/// at the first record a crash left incomplete. `persist` writes a new
+/// snapshot and clears the log.
 #[derive(Debug)]
 pub struct IndexFile {
     path: PathBuf,
+    log_path: PathBuf,
 }
 
 impl IndexFile {
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        let log_path = path.with_extension("mlog");
+        Self { path, log_path }
     }
 
     pub fn path(&self) -> &Path {
         &self.path
     }
 
+    pub fn log_path(&self) -> &Path {
+        &self.log_path
+    }
+
     pub fn load(&self) -> Result<CatalogIndex, MaterializationError> {
+        let mut index = self.load_snapshot()?;
+        for op in self.read_log()?.0 {
+            index.apply(&op);
+        }
+        Ok(index)
+    }
+
+    fn load_snapshot(&self) -> Result<CatalogIndex, MaterializationError> {
         if !self.path.exists() {
             return Ok(CatalogIndex::new());
         }
@@ -131,7 +184,7 @@ impl IndexFile {
                     "index read failed; starting with empty index"
                 );
                 drop(file);
-                self.backup_corrupt();
+                backup_corrupt(&self.path, "");
                 return Ok(CatalogIndex::new());
             }
         }
@@ -150,7 +203,7 @@ impl IndexFile {
                     path = %self.path.display(),
                     "index header invalid; starting with empty index"
                 );
-                self.backup_corrupt();
+                backup_corrupt(&self.path, "");
                 return Ok(CatalogIndex::new());
             }
         }
@@ -165,12 +218,15 @@ impl IndexFile {
                     path = %self.path.display(),
                     "index deserialize failed; starting with empty index"
                 );
-                self.backup_corrupt();
+                backup_corrupt(&self.path, "");
                 Ok(CatalogIndex::new())
             }
         }
     }
 
+    /// Writes `index` as the new snapshot and clears the log it supersedes.
+    /// A crash between the two leaves a log whose ops the snapshot already
+    /// holds, which replaying leaves unchanged.
     pub fn persist(&self, index: &CatalogIndex) -> Result<(), MaterializationError> {
         let mut tmp_path = self.path.clone();
         tmp_path.set_extension("tmp");
@@ -195,9 +251,104 @@ impl IndexFile {
 
         // Atomic rename
         fs::rename(&tmp_path, &self.path)?;
+        sync_parent(&self.path)?;
+
+        if self.log_path.exists() {
+            fs::remove_file(&self.log_path)?;
+            sync_parent(&self.log_path)?;
+        }
         Ok(())
     }
 
+    /// Appends `op` to the log and syncs it, returning the log's new length
+    /// in bytes. A record left incomplete by a crash is cut off first, so
+    /// the new one is reachable on replay. Callers must not append to the
+    /// same index concurrently.
+    pub fn append(&self, op: &IndexOp) -> Result<u64, MaterializationError> {
+        let payload = bincode::serialize(op)?;
+        if payload.len() > MAX_LOG_RECORD_LEN {
+            return Err(MaterializationError::Corrupt(
+                "index log record too large".into(),
+            ));
+        }
+
+        let valid_len = self.read_log()?.1;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .open(&self.log_path)?;
+        if valid_len == 0 {
+            file.set_len(0)?;
+            BinaryHeader::new(
+                FileKind::MaterializationCatalogLog.magic(),
+                INDEX_LOG_VERSION,
+                0,
+            )
+            .write_to(&mut file)
+            .map_err(|e| MaterializationError::Header(e.to_string()))?;
+        } else {
+            file.set_len(valid_len)?;
+            file.seek(SeekFrom::Start(valid_len))?;
+        }
+
+        let mut record = Vec::with_capacity(8 + payload.len());
+        record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        record.extend_from_slice(&compute_crc(&payload).to_le_bytes());
+        record.extend_from_slice(&payload);
+        file.write_all(&record)?;
+        file.sync_all()?;
+
+        Ok(file.stream_position()?)
+    }
+
+    /// Ops in the log, in order, and the length of the log up to the end of
+    /// the last complete record (0 when there is no usable log).
+    fn read_log(&self) -> Result<(Vec<IndexOp>, u64), MaterializationError> {
+        if !self.log_path.exists() {
+            return Ok((Vec::new(), 0));
+        }
+        let mut buf = Vec::new();
+        File::open(&self.log_path)?.read_to_end(&mut buf)?;
+        if buf.len() < BinaryHeader::TOTAL_LEN {
+            return Ok((Vec::new(), 0));
+        }
+
+        let header = BinaryHeader::read_from(&mut &buf[..BinaryHeader::TOTAL_LEN]);
+        let header_ok = header.as_ref().is_ok_and(|h| {
+            h.magic == FileKind::MaterializationCatalogLog.magic() && h.version == INDEX_LOG_VERSION
+        });
+        if !header_ok {
+            warn!(
+                path = %self.log_path.display(),
+                "index log header invalid; ignoring log"
+            );
+            backup_corrupt(&self.log_path, "mlog-");
+            return Ok((Vec::new(), 0));
+        }
+
+        let mut ops = Vec::new();
+        let mut offset = BinaryHeader::TOTAL_LEN;
+        while offset < buf.len() {
+            match decode_record(&buf[offset..]) {
+                Some((op, len)) => {
+                    ops.push(op);
+                    offset += len;
+                }
+                None => {
+                    warn!(
+                        path = %self.log_path.display(),
+                        offset,
+                        "index log ends in an incomplete record; ignoring it"
+                    );
+                    break;
+                }
+            }
+        }
+        Ok((ops, offset as u64))
+    }
+
     fn validate_header<R: std::io::Read>(
         &self,
         reader: &mut R,
@@ -220,22 +371,53 @@ impl IndexFile {
 
         Ok(())
     }
+}
 
-    fn backup_corrupt(&self) {
-        let mut backup = self.path.clone();
-        backup.set_extension(format!("corrupt-{}", now()));
-        if let Err(rename_err) = fs::rename(&self.path, &backup) {
-            error!(
-                error = %rename_err,
-                original = %self.path.display(),
-                backup = %backup.display(),
-                "failed to rename corrupt index"
-            );
-        } else {
-            warn!(
-                backup = %backup.display(),
-                "moved corrupt index to backup"
-            );
-        }
+/// Decodes the record at the start of `buf`, returning it with its length,
+/// or `None` if it is truncated or fails its checksum.
+fn decode_record(buf: &[u8]) -> Option<(IndexOp, usize)> {
+    let len = u32::from_le_bytes(buf.get(0..4)?.try_into().ok()?) as usize;
+    let crc = u32::from_le_bytes(buf.get(4..8)?.try_into().ok()?);
+    if len == 0 || len > MAX_LOG_RECORD_LEN {
+        return None;
+    }
+    let payload = buf.get(8..8 + len)?;
+    if compute_crc(payload) != crc {
+        return None;
+    }
+    let op = bincode::deserialize(payload).ok()?;
+    Some((op, 8 + len))
+}
+
+fn compute_crc(bytes: &[u8]) -> u32 {
+    let mut hasher = Crc32Hasher::new();
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+/// Makes a rename or removal in `path`'s directory durable.
+fn sync_parent(path: &Path) -> Result<(), MaterializationError> {
+    if let Some(dir) = path.parent() {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Moves a corrupt file aside as `<stem>.<label>corrupt-<now>`.
+fn backup_corrupt(path: &Path, label: &str) {
+    let mut backup = path.to_path_buf();
+    backup.set_extension(format!("{}corrupt-{}", label, now()));
+    if let Err(rename_err) = fs::rename(path, &backup) {
+        error!(
+            error = %rename_err,
+            original = %path.display(),
+            backup = %backup.display(),
+            "failed to rename corrupt index"
+        );
+    } else {
+        warn!(
+            backup = %backup.display(),
+            "moved corrupt index to backup"
+        );
     }
 }
@@ -1,4 +1,4 @@
-use super::index::{CatalogIndex, IndexEntry, IndexFile};
+use super::index::{CatalogIndex, IndexEntry, IndexFile, IndexOp};
 use crate::engine::materialize::MaterializationError;
 use crate::shared::storage_header::{BinaryHeader, FileKind};
 use crate::shared::time;
@@ -225,3 +225,123 @@ fn catalog_index_default_equals_new() {
     assert_eq!(index1.len(), index2.len());
     assert_eq!(index1.version, index2.version);
 }
+
+#[test]
+fn index_file_load_replays_log_over_snapshot() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    let index_file = IndexFile::new(dir.path().join("catalog.mcat"));
+
+    let mut index = CatalogIndex::new();
+    index.add("kept".to_string(), PathBuf::from("kept"));
+    index.add("dropped".to_string(), PathBuf::from("dropped"));
+    index_file.persist(&index)?;
+    let snapshot = fs::read(index_file.path()).unwrap();
+
+    index_file.append(&IndexOp::Remove {
+        name: "dropped".to_string(),
+    })?;
+    index_file.append(&IndexOp::Add(make_index_entry(
+        "added",
+        PathBuf::from("added"),
+    )))?;
+
+    // Changes go to the log; the snapshot is not rewritten
+    assert_eq!(fs::read(index_file.path()).unwrap(), snapshot);
+    assert_eq!(
+        index_file.log_path(),
+        dir.path().join("catalog.mlog").as_path()
+    );
+
+    let loaded = index_file.load()?;
+    let mut names: Vec<_> = loaded.entries.iter().map(|e| e.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["added", "kept"]);
+    Ok(())
+}
+
+#[test]
+fn index_file_ignores_and_cuts_off_torn_log_record() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    let index_file = IndexFile::new(dir.path().join("catalog.mcat"));
+    index_file.append(&IndexOp::Add(make_index_entry("a", PathBuf::from("a"))))?;
+    let valid_len = index_file.append(&IndexOp::Add(make_index_entry("b", PathBuf::from("b"))))?;
+
+    // A crash mid-append leaves part of a record behind
+    let mut bytes = fs::read(index_file.log_path()).unwrap();
+    bytes.extend_from_slice(&[42, 0, 0, 0, 7, 7]);
+    fs::write(index_file.log_path(), &bytes).unwrap();
+
+    assert_eq!(index_file.load()?.len(), 2);
+
+    let len = index_file.append(&IndexOp::Add(make_index_entry("c", PathBuf::from("c"))))?;
+    assert!(len > valid_len);
+    assert_eq!(fs::metadata(index_file.log_path()).unwrap().len(), len);
+    let loaded = index_file.load()?;
+    assert_eq!(loaded.len(), 3);
+    assert!(loaded.contains("c"));
+    Ok(())
+}
+
+#[test]
+fn index_file_log_record_with_bad_checksum_ends_replay() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    let index_file = IndexFile::new(dir.path().join("catalog.mcat"));
+    index_file.append(&IndexOp::Add(make_index_entry("a", PathBuf::from("a"))))?;
+    index_file.append(&IndexOp::Add(make_index_entry("b", PathBuf::from("b"))))?;
+
+    let mut bytes = fs::read(index_file.log_path()).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xFF;
+    fs::write(index_file.log_path(), &bytes).unwrap();
+
+    let loaded = index_file.load()?;
+    assert!(loaded.contains("a"));
+    assert!(!loaded.contains("b"));
+    Ok(())
+}
+
+#[test]
+fn index_file_persist_clears_log_and_replay_is_idempotent() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    let index_file = IndexFile::new(dir.path().join("catalog.mcat"));
+    let ops = [
+        IndexOp::Add(make_index_entry("a", PathBuf::from("a"))),
+        IndexOp::Add(make_index_entry("b", PathBuf::from("b"))),
+        IndexOp::Remove {
+            name: "a".to_string(),
+        },
+    ];
+    for op in &ops {
+        index_file.append(op)?;
+    }
+    let log = fs::read(index_file.log_path()).unwrap();
+
+    let index = index_file.load()?;
+    index_file.persist(&index)?;
+    assert!(!index_file.log_path().exists());
+
+    // A crash after the snapshot was renamed but before the log was removed
+    fs::write(index_file.log_path(), &log).unwrap();
+    let loaded = index_file.load()?;
+    assert_eq!(loaded.len(), 1);
+    assert!(loaded.contains("b"));
+    Ok(())
+}
+
+#[test]
+fn catalog_index_apply_keeps_entry_created_at() {
+    let mut index = CatalogIndex::new();
+    let entry = IndexEntry {
+        name: "a".to_string(),
+        entry_path: PathBuf::from("a"),
+        created_at: 7,
+    };
+    index.apply(&IndexOp::Add(entry.clone()));
+    index.apply(&IndexOp::Add(entry.clone()));
+    assert_eq!(index.entries, vec![entry]);
+
+    index.apply(&IndexOp::Remove {
+        name: "a".to_string(),
+    });
+    assert!(index.is_empty());
+}
@@ -13,7 +13,7 @@ pub use cache::{
 };
 pub use entry::MaterializationEntry;
 pub use entry_file::{EntryFile, entry_file_path};
-pub use index::{CatalogIndex, IndexEntry, IndexFile};
+pub use index::{CatalogIndex, INDEX_LOG_COMPACT_BYTES, IndexEntry, IndexFile, IndexOp};
 pub use policy::RetentionPolicy;
 pub use schema::SchemaSnapshot;
 pub use storage::MaterializationCatalog;
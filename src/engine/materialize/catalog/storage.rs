@@ -1,24 +1,38 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
 
 use super::cache::GlobalMaterializationCatalogCache;
 use super::entry::MaterializationEntry;
 use super::entry_file::{EntryFile, entry_file_path};
-use super::index::IndexFile;
+use super::index::{INDEX_LOG_COMPACT_BYTES, IndexEntry, IndexFile, IndexOp};
 use crate::engine::materialize::MaterializationError;
+use crate::shared::time::now;
+
+/// Serializes index changes, so concurrent catalog handles never interleave
+/// log appends or compact a log another handle is appending to.
+static INDEX_WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+fn index_write_lock() -> MutexGuard<'static, ()> {
+    INDEX_WRITE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
 
 /// MaterializationCatalog manages materialization entries using a per-entry file system.
 ///
 /// Architecture:
 /// - Lightweight index file (`catalog.mcat`) stores name -> entry path mappings
+/// - Index log (`catalog.mlog`) records changes since the index was last
+///   written; it is folded into the index once it grows large
 /// - Individual entry files (`materializations/<name>/entry.mcatentry`) store full entry data
 /// - Global cache provides fast access to index and entries
 ///
 /// Performance:
 /// - O(1) index load (small file, cached)
 /// - O(1) entry load (on-demand, cached)
-/// - Incremental updates (only update changed entry + index)
+/// - Incremental updates (only update changed entry + append to index log)
 /// - Scales to thousands of materializations
 #[derive(Debug)]
 pub struct MaterializationCatalog {
@@ -40,12 +54,13 @@ impl MaterializationCatalog {
 
         let index_file = IndexFile::new(index_path.clone());
 
-        // Load index file, creating empty index if it doesn't exist
-        let index = index_file.load()?;
-
-        // Ensure index is persisted if it was just created
-        if index.is_empty() {
-            index_file.persist(&index)?;
+        // Create the index if it doesn't exist, keeping any logged changes
+        if !index_path.exists() {
+            let _guard = index_write_lock();
+            let index = index_file.load()?;
+            if !index_path.exists() {
+                index_file.persist(&index)?;
+            }
         }
 
         Ok(Self {
@@ -99,9 +114,8 @@ impl MaterializationCatalog {
     ///
     /// This creates the entry file and updates the index.
     pub fn insert(&mut self, entry: MaterializationEntry) -> Result<(), MaterializationError> {
-        // Load index (clone to get mutable copy)
-        let index_arc = self.cache.get_or_load_index(&self.index_path)?.0;
-        let mut index = (*index_arc).clone();
+        let _guard = index_write_lock();
+        let index = self.cache.get_or_load_index(&self.index_path)?.0;
 
         // Check if already exists
         if index.contains(&entry.name) {
@@ -114,11 +128,11 @@ impl MaterializationCatalog {
         entry_file.persist(&entry)?;
 
         // Update index
-        index.add(entry.name.clone(), entry_path.clone());
-        self.index_file.persist(&index)?;
-
-        // Invalidate caches
-        self.cache.invalidate_index(&self.index_path);
+        self.record(IndexOp::Add(IndexEntry {
+            name: entry.name.clone(),
+            entry_path: entry_path.clone(),
+            created_at: now(),
+        }))?;
         self.cache.invalidate_entry(&entry_path);
 
         Ok(())
@@ -128,9 +142,8 @@ impl MaterializationCatalog {
     ///
     /// This updates the entry file and index if needed.
     pub fn upsert(&mut self, entry: MaterializationEntry) -> Result<(), MaterializationError> {
-        // Load index (clone to get mutable copy)
-        let index_arc = self.cache.get_or_load_index(&self.index_path)?.0;
-        let mut index = (*index_arc).clone();
+        let _guard = index_write_lock();
+        let index = self.cache.get_or_load_index(&self.index_path)?.0;
         let is_new = !index.contains(&entry.name);
 
         // Persist entry file
@@ -140,9 +153,11 @@ impl MaterializationCatalog {
 
         // Update index if new
         if is_new {
-            index.add(entry.name.clone(), entry_path.clone());
-            self.index_file.persist(&index)?;
-            self.cache.invalidate_index(&self.index_path);
+            self.record(IndexOp::Add(IndexEntry {
+                name: entry.name.clone(),
+                entry_path: entry_path.clone(),
+                created_at: now(),
+            }))?;
         }
 
         // Invalidate entry cache
@@ -158,9 +173,8 @@ impl MaterializationCatalog {
         &mut self,
         name: &str,
     ) -> Result<Option<MaterializationEntry>, MaterializationError> {
-        // Load index (clone to get mutable copy)
-        let index_arc = self.cache.get_or_load_index(&self.index_path)?.0;
-        let mut index = (*index_arc).clone();
+        let _guard = index_write_lock();
+        let index = self.cache.get_or_load_index(&self.index_path)?.0;
 
         // Get entry path
         let entry_path = match index.get_path(name) {
@@ -184,16 +198,28 @@ impl MaterializationCatalog {
         }
 
         // Update index
-        index.remove(name);
-        self.index_file.persist(&index)?;
-
-        // Invalidate caches
-        self.cache.invalidate_index(&self.index_path);
+        self.record(IndexOp::Remove {
+            name: name.to_string(),
+        })?;
         self.cache.invalidate_entry(&entry_path);
 
         Ok(Some(entry))
     }
 
+    /// Appends `op` to the index log and applies it to the cached index,
+    /// folding the log into a new index file once it grows past
+    /// [`INDEX_LOG_COMPACT_BYTES`]. Callers hold the index write lock.
+    fn record(&self, op: IndexOp) -> Result<(), MaterializationError> {
+        let log_len = self.index_file.append(&op)?;
+        self.cache.apply_index_op(&self.index_path, &op);
+
+        if log_len >= INDEX_LOG_COMPACT_BYTES {
+            let index = self.index_file.load()?;
+            self.index_file.persist(&index)?;
+        }
+        Ok(())
+    }
+
     pub fn root_dir(&self) -> &Path {
         &self.root_dir
     }
@@ -1,4 +1,5 @@
 use super::entry::MaterializationEntry;
+use super::index::INDEX_LOG_COMPACT_BYTES;
 use super::storage::MaterializationCatalog;
 use crate::command::types::MaterializedQuerySpec;
 use crate::engine::materialize::MaterializationError;
@@ -297,3 +298,69 @@ fn catalog_handles_large_number_of_entries() -> Result<(), MaterializationError>
     assert_eq!(loaded.unwrap().name, "entry_50");
     Ok(())
 }
+
+#[test]
+fn changes_append_to_index_log_until_it_is_compacted() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    let mut catalog = MaterializationCatalog::load(dir.path())?;
+    let index_path = catalog.catalog_path().to_path_buf();
+    let log_path = index_path.with_extension("mlog");
+    let snapshot = std::fs::read(&index_path).unwrap();
+
+    catalog.insert(new_entry("first", catalog.root_dir()))?;
+    assert_eq!(std::fs::read(&index_path).unwrap(), snapshot);
+    assert!(log_path.exists());
+
+    // Keep adding until the log is folded into the index file
+    let mut inserted = 1;
+    while log_path.exists() {
+        assert!(
+            std::fs::metadata(&log_path).unwrap().len() < INDEX_LOG_COMPACT_BYTES,
+            "log was not compacted"
+        );
+        catalog.insert(new_entry(
+            &format!("entry_{}", inserted),
+            catalog.root_dir(),
+        ))?;
+        inserted += 1;
+    }
+    assert_ne!(std::fs::read(&index_path).unwrap(), snapshot);
+
+    super::cache::GlobalMaterializationCatalogCache::instance().invalidate_index(&index_path);
+    let reloaded = MaterializationCatalog::load(dir.path())?;
+    assert_eq!(reloaded.list_names()?.len(), inserted);
+    Ok(())
+}
+
+#[test]
+fn concurrent_handles_do_not_lose_index_changes() -> Result<(), MaterializationError> {
+    let dir = tempdir().unwrap();
+    MaterializationCatalog::load(dir.path())?;
+
+    std::thread::scope(|scope| {
+        for worker in 0..8 {
+            let base = dir.path();
+            scope.spawn(move || {
+                let mut catalog = MaterializationCatalog::load(base).unwrap();
+                for i in 0..10 {
+                    let entry = new_entry(&format!("w{}_{}", worker, i), catalog.root_dir());
+                    catalog.insert(entry).unwrap();
+                }
+                catalog.remove(&format!("w{}_0", worker)).unwrap();
+            });
+        }
+    });
+
+    let catalog = MaterializationCatalog::load(dir.path())?;
+    assert_eq!(catalog.list_names()?.len(), 72);
+
+    // Recovery from disk alone sees the same state
+    super::cache::GlobalMaterializationCatalogCache::instance()
+        .invalidate_index(catalog.catalog_path());
+    let mut names = catalog.list_names()?;
+    names.sort();
+    assert_eq!(names.len(), 72);
+    assert!(!names.contains(&"w3_0".to_string()));
+    assert!(names.contains(&"w3_9".to_string()));
+    Ok(())
+}
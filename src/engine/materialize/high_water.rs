@@ -29,3 +29,32 @@ impl HighWaterMark {
         self.timestamp == 0 && self.event_id == 0
     }
 }
+
+/// When a sink persists its high-water mark during a refresh. The mark is
+/// stored with the frames it covers, so persisting it means writing the rows
+/// buffered so far as a frame: a crash loses at most the events since the
+/// last persist, which the next refresh reads again. Persisting more often
+/// reprocesses less after a crash at the cost of more, smaller frames and
+/// more manifest writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum HighWaterPolicy {
+    /// Whenever a full frame is written, and at the end of the refresh.
+    #[default]
+    EveryFrame,
+    /// Also once this many events were appended since the last persist.
+    EveryEvents(u64),
+    /// Also on the first append this many seconds after the last persist.
+    EverySeconds(u64),
+}
+
+impl HighWaterPolicy {
+    /// Whether a sink that took `events` events over `elapsed` since it last
+    /// persisted its mark should persist it now.
+    pub fn is_due(&self, events: u64, elapsed: std::time::Duration) -> bool {
+        match *self {
+            HighWaterPolicy::EveryFrame => false,
+            HighWaterPolicy::EveryEvents(limit) => events >= limit.max(1),
+            HighWaterPolicy::EverySeconds(secs) => events > 0 && elapsed.as_secs() >= secs,
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use super::high_water::HighWaterMark;
+use super::high_water::{HighWaterMark, HighWaterPolicy};
+use std::time::Duration;
 
 #[test]
 fn new_sets_timestamp_and_event_id() {
@@ -48,3 +49,17 @@ fn is_zero_detects_zero_mark() {
     assert!(!HighWaterMark::new(1, 0).is_zero());
     assert!(!HighWaterMark::new(0, 1).is_zero());
 }
+
+#[test]
+fn policy_is_due_by_events_or_elapsed_time() {
+    let second = Duration::from_secs(1);
+    assert!(!HighWaterPolicy::EveryFrame.is_due(u64::MAX, second * 3600));
+
+    assert!(!HighWaterPolicy::EveryEvents(10).is_due(9, second));
+    assert!(HighWaterPolicy::EveryEvents(10).is_due(10, Duration::ZERO));
+
+    assert!(!HighWaterPolicy::EverySeconds(5).is_due(100, second * 4));
+    assert!(HighWaterPolicy::EverySeconds(5).is_due(1, second * 5));
+    // Nothing to persist
+    assert!(!HighWaterPolicy::EverySeconds(5).is_due(0, second * 60));
+}
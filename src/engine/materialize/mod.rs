@@ -1,3 +1,4 @@
+mod aggregate;
 pub mod catalog;
 mod error;
 mod high_water;
@@ -6,6 +7,8 @@ mod source;
 mod spec;
 mod store;
 
+#[cfg(test)]
+mod aggregate_tests;
 #[cfg(test)]
 mod high_water_tests;
 #[cfg(test)]
@@ -15,12 +18,14 @@ mod source_tests;
 #[cfg(test)]
 mod spec_tests;
 
+pub use aggregate::AggregateState;
 pub use catalog::{MaterializationCatalog, MaterializationEntry, SchemaSnapshot};
 pub use error::MaterializationError;
-pub use high_water::HighWaterMark;
+pub use high_water::{HighWaterMark, HighWaterPolicy};
 pub use sink::MaterializedSink;
 pub use source::MaterializedSource;
 pub use spec::MaterializedQuerySpecExt;
 pub use store::{
-    MaterializedStore, StoredFrameMeta, batch_schema_to_snapshots, schema_to_batch_schema,
+    CompactionSummary, FrameIssue, FrameReader, FrameSize, IssueSeverity, MaterializedStore,
+    StoredFrameMeta, VerifyReport, batch_schema_to_snapshots, schema_to_batch_schema, verify_store,
 };
@@ -1,7 +1,13 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::engine::core::read::aggregate::plan::AggregatePlan;
 use crate::engine::core::read::flow::{BatchSchema, ColumnBatch};
+use crate::engine::types::ScalarValue;
 
+use super::aggregate::{AggregateState, batch_high_water};
 use super::catalog::RetentionPolicy;
-use super::high_water::HighWaterMark;
+use super::high_water::{HighWaterMark, HighWaterPolicy};
 use super::store::{MaterializedStore, batch_schema_to_snapshots};
 use super::{MaterializationError, SchemaSnapshot};
 
@@ -16,6 +22,53 @@ pub struct MaterializedSink {
     total_bytes: u64,
     last_rows_appended: u64,
     last_bytes_appended: u64,
+    aggregate: Option<PendingAggregate>,
+    pending_rows: Option<PendingRows>,
+    high_water_policy: HighWaterPolicy,
+    events_since_persist: u64,
+    last_persist: Instant,
+}
+
+/// Rows appended to a row sink that do not fill a frame yet.
+struct PendingRows {
+    schema: Arc<BatchSchema>,
+    columns: Vec<Vec<ScalarValue>>,
+    rows_per_frame: usize,
+}
+
+impl PendingRows {
+    fn len(&self) -> usize {
+        self.columns.first().map_or(0, Vec::len)
+    }
+
+    fn extend(&mut self, batch: &ColumnBatch) {
+        for (pending, column) in self.columns.iter_mut().zip(batch.columns_ref()) {
+            pending.extend_from_slice(column);
+        }
+    }
+
+    /// Removes the first `rows` rows as a batch.
+    fn take(&mut self, rows: usize) -> Result<ColumnBatch, MaterializationError> {
+        let columns = self
+            .columns
+            .iter_mut()
+            .map(|column| {
+                let rest = column.split_off(rows);
+                std::mem::replace(column, rest)
+            })
+            .collect();
+        ColumnBatch::new(Arc::clone(&self.schema), columns, rows, None)
+            .map_err(|e| MaterializationError::Batch(e.to_string()))
+    }
+}
+
+/// Aggregate state held by a sink built with [`MaterializedSink::aggregating`],
+/// with the events merged into it since the last commit.
+struct PendingAggregate {
+    state: AggregateState,
+    time_field: String,
+    high_water: HighWaterMark,
+    rows: u64,
 }
 
 impl MaterializedSink {
@@ -40,11 +93,44 @@ impl MaterializedSink {
             total_bytes: 0,
             last_rows_appended: 0,
             last_bytes_appended: 0,
+            aggregate: None,
+            pending_rows: None,
+            high_water_policy: HighWaterPolicy::default(),
+            events_since_persist: 0,
+            last_persist: Instant::now(),
         };
         sink.bootstrap_from_manifest();
         Ok(sink)
     }
 
+    /// Builds a sink whose frames hold the mergeable state of `plan` rather
+    /// than rows. Appended batches are source events, merged into the state
+    /// loaded from the store; nothing is written until [`Self::commit`].
+    pub fn aggregating(
+        store: MaterializedStore,
+        plan: AggregatePlan,
+        time_field: impl Into<String>,
+    ) -> Result<Self, MaterializationError> {
+        let schema = AggregateState::state_schema(&plan)?;
+        let mut sink = Self::from_batch_schema(store, &schema)?;
+
+        let (state, _) = AggregateState::from_store(&sink.store, plan)?;
+        sink.aggregate = Some(PendingAggregate {
+            state,
+            time_field: time_field.into(),
+            high_water: sink.high_water,
+            rows: 0,
+        });
+        Ok(sink)
+    }
+
+    /// Persists the high-water mark as often as `policy` asks, on top of
+    /// every full frame.
+    pub fn with_high_water_policy(mut self, policy: HighWaterPolicy) -> Self {
+        self.high_water_policy = policy;
+        self
+    }
+
     pub fn from_batch_schema(
         store: MaterializedStore,
         schema: &BatchSchema,
@@ -58,8 +144,54 @@ impl MaterializedSink {
             return Ok(());
         }
 
+        if let Some(pending) = self.aggregate.as_mut() {
+            pending.state.absorb(batch, &pending.time_field)?;
+            let mark = batch_high_water(batch, &pending.time_field);
+            pending.high_water.advance(mark.timestamp, mark.event_id);
+            pending.rows = pending.rows.saturating_add(batch.len() as u64);
+            return self.persist_if_due(batch.len());
+        }
+
         self.schema_guard.expect_batch(batch.schema())?;
 
+        let rows_per_frame = self.store.frame_size().rows_for(batch);
+        let pending = self.pending_rows.get_or_insert_with(|| PendingRows {
+            schema: Arc::new(batch.schema().clone()),
+            columns: vec![Vec::new(); batch.schema().column_count()],
+            rows_per_frame,
+        });
+        pending.rows_per_frame = rows_per_frame;
+        pending.extend(batch);
+
+        while let Some(pending) = self.pending_rows.as_mut() {
+            if pending.len() < pending.rows_per_frame {
+                break;
+            }
+            let frame = pending.take(pending.rows_per_frame)?;
+            self.write_frame(&frame)?;
+        }
+        self.persist_if_due(batch.len())
+    }
+
+    /// Counts `events` toward the high-water policy and commits what the
+    /// sink holds once the policy is due.
+    fn persist_if_due(&mut self, events: usize) -> Result<(), MaterializationError> {
+        self.events_since_persist = self.events_since_persist.saturating_add(events as u64);
+        if self
+            .high_water_policy
+            .is_due(self.events_since_persist, self.last_persist.elapsed())
+        {
+            self.commit()?;
+        }
+        Ok(())
+    }
+
+    fn mark_persisted(&mut self) {
+        self.events_since_persist = 0;
+        self.last_persist = Instant::now();
+    }
+
+    fn write_frame(&mut self, batch: &ColumnBatch) -> Result<(), MaterializationError> {
         let meta = self
             .store
             .append_batch(self.schema_guard.snapshots(), batch)?;
@@ -68,8 +200,59 @@ impl MaterializedSink {
         let bytes_added = meta.compressed_len as u64;
         self.total_rows = self.total_rows.saturating_add(rows_added);
         self.total_bytes = self.total_bytes.saturating_add(bytes_added);
-        self.last_rows_appended = rows_added;
-        self.last_bytes_appended = bytes_added;
+        self.last_rows_appended = self.last_rows_appended.saturating_add(rows_added);
+        self.last_bytes_appended = self.last_bytes_appended.saturating_add(bytes_added);
+        if self
+            .pending_rows
+            .as_ref()
+            .is_none_or(|pending| pending.len() == 0)
+        {
+            self.mark_persisted();
+        }
+        Ok(())
+    }
+
+    /// Writes what the sink still holds. For row sinks these are the rows
+    /// short of a full frame, written as one smaller frame; dropping the sink
+    /// without committing discards them.
+    ///
+    /// For aggregating sinks, persists the state merged since the last
+    /// commit as the store's only frame, and only then advances the
+    /// high-water mark to the newest merged event. Does nothing when no
+    /// events were appended. `last_rows_appended` counts the merged events.
+    ///
+    /// The sink stays usable; later appends start a new frame.
+    pub fn commit(&mut self) -> Result<(), MaterializationError> {
+        if let Some(mut pending) = self.pending_rows.take() {
+            let rows = pending.len();
+            if rows > 0 {
+                let frame = pending.take(rows)?;
+                self.write_frame(&frame)?;
+            }
+            self.mark_persisted();
+            return Ok(());
+        }
+        let Some(pending) = self.aggregate.as_mut() else {
+            return Ok(());
+        };
+        if pending.rows == 0 {
+            return Ok(());
+        }
+
+        let schema = AggregateState::state_schema(&pending.state.plan())?;
+        let batch = pending.state.to_batch(&schema)?;
+        let meta = self.store.replace_with_batch(
+            self.schema_guard.snapshots(),
+            &batch,
+            pending.high_water,
+        )?;
+
+        self.high_water = meta.high_water_mark;
+        self.last_rows_appended = self.last_rows_appended.saturating_add(pending.rows);
+        self.last_bytes_appended = meta.compressed_len as u64;
+        pending.rows = 0;
+        self.recompute_totals();
+        self.mark_persisted();
         Ok(())
     }
 
@@ -93,6 +276,7 @@ impl MaterializedSink {
         self.store
     }
 
+    /// Rows written to the store since the sink was opened.
     pub fn last_rows_appended(&self) -> u64 {
         self.last_rows_appended
     }
@@ -1,8 +1,8 @@
 use super::sink::MaterializedSink;
-use super::store::{MaterializedStore, batch_schema_to_snapshots};
+use super::store::{FrameSize, MaterializedStore, batch_schema_to_snapshots};
 use crate::engine::core::read::flow::{BatchPool, BatchSchema};
 use crate::engine::core::read::result::ColumnSpec;
-use crate::engine::materialize::high_water::HighWaterMark;
+use crate::engine::materialize::high_water::{HighWaterMark, HighWaterPolicy};
 use crate::engine::materialize::{MaterializationError, SchemaSnapshot};
 use crate::engine::types::ScalarValue;
 use serde_json::json;
@@ -50,6 +50,7 @@ fn sink_appends_and_tracks_stats() {
     let batch = builder.finish().unwrap();
 
     sink.append(&batch).unwrap();
+    sink.commit().unwrap();
     assert_eq!(sink.total_rows(), 1);
     assert_eq!(
         sink.high_water_mark(),
@@ -79,6 +80,7 @@ fn sink_rejects_schema_mismatch_with_existing_frames() {
         ])
         .unwrap();
     sink.append(&builder.finish().unwrap()).unwrap();
+    sink.commit().unwrap();
     let store = sink.into_store();
 
     let mut snapshots = batch_schema_to_snapshots(&schema_arc);
@@ -129,3 +131,116 @@ fn sink_rejects_mismatched_batch_schema() {
     let err = sink.append(&batch).unwrap_err();
     assert!(matches!(err, MaterializationError::Corrupt(_)));
 }
+
+#[test]
+fn sink_writes_frames_of_the_configured_size() {
+    let dir = tempdir().unwrap();
+    let store = MaterializedStore::open(dir.path())
+        .unwrap()
+        .with_frame_size(FrameSize::Rows(4));
+    let schema_arc = Arc::new(build_schema());
+    let mut sink = MaterializedSink::from_batch_schema(store, &schema_arc).unwrap();
+
+    // Batches of 3, 3 and 5 rows make frames of 4, 4 and the remaining 3
+    let pool = BatchPool::new(8).unwrap();
+    let mut next_id = 1_u64;
+    for rows in [3, 3, 5] {
+        let mut builder = pool.acquire(Arc::clone(&schema_arc));
+        for _ in 0..rows {
+            builder
+                .push_row(&[
+                    ScalarValue::from(json!(1_700_000_000_u64 + next_id)),
+                    ScalarValue::from(json!("ctx")),
+                    ScalarValue::from(json!(next_id)),
+                ])
+                .unwrap();
+            next_id += 1;
+        }
+        sink.append(&builder.finish().unwrap()).unwrap();
+    }
+    assert_eq!(sink.total_rows(), 8);
+    assert_eq!(sink.high_water_mark(), HighWaterMark::new(1_700_000_008, 8));
+
+    sink.commit().unwrap();
+    assert_eq!(sink.total_rows(), 11);
+    assert_eq!(sink.last_rows_appended(), 11);
+    assert_eq!(
+        sink.high_water_mark(),
+        HighWaterMark::new(1_700_000_011, 11)
+    );
+
+    let store = sink.into_store();
+    let frames = store.frames();
+    assert_eq!(
+        frames.iter().map(|f| f.row_count).collect::<Vec<_>>(),
+        vec![4, 4, 3]
+    );
+    assert_eq!(
+        frames
+            .iter()
+            .map(|f| (f.min_timestamp, f.max_timestamp))
+            .collect::<Vec<_>>(),
+        vec![
+            (1_700_000_001, 1_700_000_004),
+            (1_700_000_005, 1_700_000_008),
+            (1_700_000_009, 1_700_000_011),
+        ]
+    );
+    assert_eq!(
+        frames[1].high_water_mark,
+        HighWaterMark::new(1_700_000_008, 8)
+    );
+}
+
+#[test]
+fn sink_persists_high_water_mark_as_often_as_the_policy_asks() {
+    let dir = tempdir().unwrap();
+    let store = MaterializedStore::open(dir.path())
+        .unwrap()
+        .with_frame_size(FrameSize::Rows(100));
+    let schema_arc = Arc::new(build_schema());
+    let mut sink = MaterializedSink::from_batch_schema(store, &schema_arc)
+        .unwrap()
+        .with_high_water_policy(HighWaterPolicy::EveryEvents(5));
+
+    let pool = BatchPool::new(8).unwrap();
+    let mut next_id = 1_u64;
+    let mut append = |sink: &mut MaterializedSink, rows: u64| {
+        let mut builder = pool.acquire(Arc::clone(&schema_arc));
+        for _ in 0..rows {
+            builder
+                .push_row(&[
+                    ScalarValue::from(json!(1_700_000_000_u64 + next_id)),
+                    ScalarValue::from(json!("ctx")),
+                    ScalarValue::from(json!(next_id)),
+                ])
+                .unwrap();
+            next_id += 1;
+        }
+        sink.append(&builder.finish().unwrap()).unwrap();
+    };
+
+    // Nothing is persisted, and the mark does not move, until 5 events arrived
+    append(&mut sink, 3);
+    assert_eq!(sink.high_water_mark(), HighWaterMark::default());
+    append(&mut sink, 3);
+    assert_eq!(sink.high_water_mark(), HighWaterMark::new(1_700_000_006, 6));
+    append(&mut sink, 4);
+    assert_eq!(sink.high_water_mark(), HighWaterMark::new(1_700_000_006, 6));
+
+    // A crash now loses only the 4 events since the last persist
+    drop(sink);
+    let store = MaterializedStore::open(dir.path()).unwrap();
+    assert_eq!(
+        store
+            .frames()
+            .iter()
+            .map(|f| f.row_count)
+            .collect::<Vec<_>>(),
+        vec![6]
+    );
+    assert_eq!(
+        store.frames()[0].high_water_mark,
+        HighWaterMark::new(1_700_000_006, 6)
+    );
+}
@@ -2,21 +2,59 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use crate::engine::core::read::flow::{BatchSender, FlowContext, FlowOperatorError, FlowSource};
+use crate::engine::core::read::aggregate::plan::AggregatePlan;
+use crate::engine::core::read::flow::operators::{PartialConverter, aggregate_output_schema};
+use crate::engine::core::read::flow::{
+    BatchSchema, BatchSender, FlowContext, FlowOperatorError, FlowSource,
+};
 
 use super::MaterializationError;
 use super::StoredFrameMeta;
-use super::store::MaterializedStore;
+use super::aggregate::AggregateState;
+use super::store::{FrameReader, MaterializedStore};
 
 pub struct MaterializedSource {
     store: MaterializedStore,
     frames: Vec<StoredFrameMeta>,
+    aggregate: Option<AggregatePlan>,
+    projection: Option<Vec<String>>,
+    limit: Option<usize>,
 }
 
 impl MaterializedSource {
     pub fn new(store: MaterializedStore) -> Self {
         let frames = store.frames().to_vec();
-        Self { store, frames }
+        Self {
+            store,
+            frames,
+            aggregate: None,
+            projection: None,
+            limit: None,
+        }
+    }
+
+    /// Emits only `columns`, in that order; the others are never decoded.
+    /// Ignored for aggregated stores.
+    pub fn with_projection(mut self, columns: Vec<String>) -> Self {
+        self.projection = Some(columns);
+        self
+    }
+
+    /// Stops after `limit` rows without reading the remaining frames.
+    /// Ignored for aggregated stores.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Serves a store written by an aggregating sink: the stored state of
+    /// `plan` is emitted as the per-group partials a shard produces, with the
+    /// layout of [`aggregate_output_schema`], for the aggregate merger to
+    /// finalize.
+    pub fn aggregated(store: MaterializedStore, plan: AggregatePlan) -> Self {
+        let mut source = Self::new(store);
+        source.aggregate = Some(plan);
+        source
     }
 
     pub fn frames(&self) -> &[StoredFrameMeta] {
@@ -33,10 +71,37 @@ impl FlowSource for MaterializedSource {
     async fn run(
         mut self,
         output: BatchSender,
-        _ctx: Arc<FlowContext>,
+        ctx: Arc<FlowContext>,
     ) -> Result<(), FlowOperatorError> {
-        for meta in self.frames.into_iter() {
-            let batch_arc = self.store.read_frame(&meta).map_err(materialize_err)?;
+        if let Some(plan) = self.aggregate.take() {
+            let schema = BatchSchema::new(aggregate_output_schema(&plan))
+                .map_err(|e| FlowOperatorError::Batch(e.to_string()))?;
+            let mut state = AggregateState::new(plan);
+            for meta in &self.frames {
+                let batch = self.store.read_frame(meta).map_err(materialize_err)?;
+                state.load(&batch).map_err(materialize_err)?;
+            }
+            if state.group_count() == 0 {
+                return Ok(());
+            }
+            return PartialConverter::to_batches(
+                state.into_partial(),
+                Arc::new(schema),
+                ctx,
+                output,
+            )
+            .await;
+        }
+
+        let mut reader = FrameReader::new(&self.store, self.frames);
+        if let Some(columns) = self.projection {
+            reader = reader.with_projection(columns);
+        }
+        if let Some(limit) = self.limit {
+            reader = reader.with_limit(limit);
+        }
+        for batch in reader {
+            let batch_arc = batch.map_err(materialize_err)?;
             // Send Arc directly - zero copy on cache hits!
             output
                 .send(batch_arc)
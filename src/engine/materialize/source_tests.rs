@@ -66,6 +66,7 @@ async fn materialized_source_streams_frames() {
         ])
         .unwrap();
     sink.append(&builder.finish().unwrap()).unwrap();
+    sink.commit().unwrap();
     drop(sink);
 
     let store = MaterializedStore::open(dir.path()).unwrap();
@@ -99,3 +100,51 @@ async fn materialized_source_streams_frames() {
     assert_eq!(collected[0][1], json!("ctx-a"));
     assert_eq!(collected[1][2], json!(2));
 }
+
+#[tokio::test]
+async fn materialized_source_applies_projection_and_limit() {
+    let dir = tempdir().unwrap();
+    let store = MaterializedStore::open(dir.path()).unwrap();
+    let schema_arc = Arc::new(build_schema());
+    let mut sink = MaterializedSink::from_batch_schema(store, &schema_arc).unwrap();
+
+    let pool = BatchPool::new(8).unwrap();
+    for id in 1..=3_u64 {
+        let mut builder = pool.acquire(Arc::clone(&schema_arc));
+        builder
+            .push_row(&[
+                ScalarValue::from(json!(1_700_000_000_u64 + id)),
+                ScalarValue::from(json!(format!("ctx-{id}"))),
+                ScalarValue::from(json!(id)),
+            ])
+            .unwrap();
+        sink.append(&builder.finish().unwrap()).unwrap();
+        sink.commit().unwrap();
+    }
+    drop(sink);
+
+    let store = MaterializedStore::open(dir.path()).unwrap();
+    let source = MaterializedSource::new(store)
+        .with_projection(vec!["context_id".into()])
+        .with_limit(2);
+
+    let metrics = FlowMetrics::new();
+    let ctx = Arc::new(FlowContext::new(
+        16,
+        BatchPool::new(16).unwrap(),
+        Arc::clone(&metrics),
+        Option::<&str>::None,
+        FlowTelemetry::default(),
+    ));
+    let (sender, mut receiver) = FlowChannel::bounded(8, metrics);
+    let handle = tokio::spawn(async move { source.run(sender, ctx).await });
+
+    let mut collected = Vec::new();
+    while let Some(batch) = receiver.recv().await {
+        assert_eq!(batch.schema().column_count(), 1);
+        collected.extend(batch_to_rows(&batch));
+    }
+    handle.await.unwrap().expect("materialized source run");
+
+    assert_eq!(collected, vec![vec![json!("ctx-1")], vec![json!("ctx-2")]]);
+}
@@ -2,6 +2,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
 use crate::command::types::{Command, MaterializedQuerySpec};
+use crate::engine::core::read::aggregate::plan::AggregatePlan;
 use crate::engine::materialize::high_water::HighWaterMark;
 use crate::shared::time::{TimeKind, TimeParser};
 use serde_json;
@@ -13,6 +14,11 @@ pub trait MaterializedQuerySpecExt {
     fn query(&self) -> &Command;
     fn cloned_query(&self) -> Command;
     fn plan_hash(&self) -> Result<u64, MaterializationError>;
+    /// Aggregates of the remembered query, when it is an aggregate view.
+    fn aggregate_plan(&self) -> Option<AggregatePlan>;
+    /// Query for the source rows newer than `watermark`. For an aggregate
+    /// view these are the raw events, with aggregation, ordering and limits
+    /// removed, so they can be merged into the stored state.
     fn delta_command(
         &self,
         watermark: Option<HighWaterMark>,
@@ -39,11 +45,18 @@ impl MaterializedQuerySpecExt for MaterializedQuerySpec {
         Ok(hasher.finish())
     }
 
+    fn aggregate_plan(&self) -> Option<AggregatePlan> {
+        AggregatePlan::from_command(self.query())
+    }
+
     fn delta_command(
         &self,
         watermark: Option<HighWaterMark>,
     ) -> Result<Command, MaterializationError> {
         let mut command = self.cloned_query();
+        if self.aggregate_plan().is_some() {
+            strip_aggregation(&mut command);
+        }
 
         let Some(watermark) = watermark else {
             return Ok(command);
@@ -65,6 +78,30 @@ impl MaterializedQuerySpecExt for MaterializedQuerySpec {
     }
 }
 
+fn strip_aggregation(command: &mut Command) {
+    if let Command::Query {
+        aggs,
+        time_bucket,
+        group_by,
+        order_by,
+        limit,
+        offset,
+        return_fields,
+        with_total,
+        ..
+    } = command
+    {
+        *aggs = None;
+        *time_bucket = None;
+        *group_by = None;
+        *order_by = None;
+        *limit = None;
+        *offset = None;
+        *return_fields = None;
+        *with_total = false;
+    }
+}
+
 fn should_update_since(existing: Option<&str>, watermark_ts: u64) -> bool {
     match existing.and_then(parse_since_epoch) {
         Some(existing_ts) => existing_ts < watermark_ts,
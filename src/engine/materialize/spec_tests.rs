@@ -95,3 +95,41 @@ fn delta_command_preserves_newer_existing_since() -> Result<(), MaterializationE
     assert_eq!(since, Some("2024-01-01T00:00:00Z".to_string()));
     Ok(())
 }
+
+#[test]
+fn delta_command_for_aggregate_view_selects_raw_events() -> Result<(), MaterializationError> {
+    let spec = MaterializedQuerySpec {
+        name: "orders_by_country".into(),
+        query: Box::new(
+            CommandFactory::query()
+                .with_event_type("orders")
+                .add_count()
+                .add_total("amount")
+                .with_group_by(vec!["country"])
+                .with_order_by("count", true)
+                .with_limit(10)
+                .create(),
+        ),
+    };
+    assert_eq!(spec.aggregate_plan().unwrap().ops.len(), 2);
+    assert!(build_spec().aggregate_plan().is_none());
+
+    let command = spec.delta_command(Some(HighWaterMark::new(1_700_000_000, 7)))?;
+    let Command::Query {
+        event_type,
+        since,
+        aggs,
+        group_by,
+        order_by,
+        limit,
+        ..
+    } = command
+    else {
+        panic!("expected query command");
+    };
+    assert_eq!(event_type, "orders");
+    assert_eq!(since, Some("1700000000".to_string()));
+    assert!(aggs.is_none() && group_by.is_none());
+    assert!(order_by.is_none() && limit.is_none());
+    Ok(())
+}
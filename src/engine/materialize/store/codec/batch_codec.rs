@@ -2,7 +2,7 @@ use crate::engine::core::read::flow::ColumnBatch;
 use crate::engine::materialize::MaterializationError;
 use crate::engine::materialize::catalog::SchemaSnapshot;
 
-use super::compression::{Compressor, Decompressor};
+use super::compression::{Compressor, Decompressor, FrameCodec};
 use super::decoder::Decoder;
 use super::encoder::Encoder;
 use super::types::EncodedFrame;
@@ -21,12 +21,30 @@ pub trait BatchCodec {
         meta: &StoredFrameMeta,
         data: FrameData,
     ) -> Result<ColumnBatch, MaterializationError>;
+
+    /// Decodes only the columns at `columns`, in that order.
+    fn decode_columns(
+        &self,
+        meta: &StoredFrameMeta,
+        data: FrameData,
+        columns: &[usize],
+    ) -> Result<ColumnBatch, MaterializationError>;
 }
 
+/// Encodes frames with the chosen [`FrameCodec`]. Decoding follows the codec
+/// recorded in each frame, so frames written with another codec still decode.
 #[derive(Debug, Default, Clone, Copy)]
-pub struct Lz4BatchCodec;
+pub struct FrameBatchCodec {
+    codec: FrameCodec,
+}
 
-impl BatchCodec for Lz4BatchCodec {
+impl FrameBatchCodec {
+    pub fn new(codec: FrameCodec) -> Self {
+        Self { codec }
+    }
+}
+
+impl BatchCodec for FrameBatchCodec {
     fn encode(
         &self,
         schema: &[SchemaSnapshot],
@@ -53,7 +71,7 @@ impl BatchCodec for Lz4BatchCodec {
             buffer.extend_from_slice(col_data);
         }
 
-        let compressed = Compressor::compress(&buffer)?;
+        let compressed = Compressor::compress(self.codec, &buffer)?;
 
         Ok(EncodedFrame {
             schema: schema.to_vec(),
@@ -63,6 +81,7 @@ impl BatchCodec for Lz4BatchCodec {
             max_timestamp: encoded.max_timestamp,
             max_event_id: encoded.max_event_id,
             null_bitmap_len: encoded.null_bitmap.len() as u32,
+            codec: self.codec,
             compressed,
             uncompressed_len: buffer.len() as u32,
         })
@@ -73,21 +92,37 @@ impl BatchCodec for Lz4BatchCodec {
         meta: &StoredFrameMeta,
         data: FrameData,
     ) -> Result<ColumnBatch, MaterializationError> {
-        let uncompressed_len = data.header.uncompressed_len as usize;
-
-        // Use thread-local buffer pool for zero-allocation decompression
-        // This reuses buffers across multiple frame decompressions on the same thread
-        let payload = Decompressor::decompress_with_pool(&data.compressed, uncompressed_len)?;
-
-        if payload.len() != uncompressed_len {
-            return Err(MaterializationError::Corrupt(format!(
-                "Payload length mismatch for frame {} (expected {}, got {})",
-                meta.file_name,
-                uncompressed_len,
-                payload.len()
-            )));
-        }
-
+        let payload = decompress(meta, &data)?;
         Decoder::decode(meta, payload, &data.header)
     }
+
+    fn decode_columns(
+        &self,
+        meta: &StoredFrameMeta,
+        data: FrameData,
+        columns: &[usize],
+    ) -> Result<ColumnBatch, MaterializationError> {
+        let payload = decompress(meta, &data)?;
+        Decoder::decode_columns(meta, payload, &data.header, Some(columns))
+    }
+}
+
+fn decompress(meta: &StoredFrameMeta, data: &FrameData) -> Result<Vec<u8>, MaterializationError> {
+    let uncompressed_len = data.header.uncompressed_len as usize;
+
+    // Use thread-local buffer pool for zero-allocation decompression
+    // This reuses buffers across multiple frame decompressions on the same thread
+    let payload =
+        Decompressor::decompress_with_pool(data.codec, &data.compressed, uncompressed_len)?;
+
+    if payload.len() != uncompressed_len {
+        return Err(MaterializationError::Corrupt(format!(
+            "Payload length mismatch for frame {} (expected {}, got {})",
+            meta.file_name,
+            uncompressed_len,
+            payload.len()
+        )));
+    }
+
+    Ok(payload)
 }
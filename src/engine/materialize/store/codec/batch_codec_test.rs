@@ -1,7 +1,7 @@
-use super::batch_codec::{BatchCodec, Lz4BatchCodec};
+use super::batch_codec::{BatchCodec, FrameBatchCodec};
 use crate::engine::core::read::flow::{BatchPool, BatchSchema, ColumnBatch};
 use crate::engine::core::read::result::ColumnSpec;
-use crate::engine::materialize::store::codec::batch_schema_to_snapshots;
+use crate::engine::materialize::store::codec::{FrameCodec, batch_schema_to_snapshots};
 use crate::engine::materialize::store::frame::data::FrameData;
 use crate::engine::materialize::store::frame::header::FrameHeader;
 use crate::engine::materialize::store::frame::metadata::StoredFrameMeta;
@@ -44,7 +44,7 @@ fn encode_creates_encoded_frame() {
     let snapshots = batch_schema_to_snapshots(&schema);
     let batch = build_batch(&schema, vec![(1700000000, 1), (1700000001, 2)]);
 
-    let codec = Lz4BatchCodec::default();
+    let codec = FrameBatchCodec::default();
     let encoded = codec.encode(&snapshots, &batch).unwrap();
 
     assert_eq!(encoded.row_count, 2);
@@ -62,7 +62,7 @@ fn encode_decode_roundtrip() {
     let snapshots = batch_schema_to_snapshots(&schema);
     let original = build_batch(&schema, vec![(1700000000, 1), (1700000001, 2)]);
 
-    let codec = Lz4BatchCodec::default();
+    let codec = FrameBatchCodec::default();
     let encoded = codec.encode(&snapshots, &original).unwrap();
 
     let mut crc = Crc32Hasher::new();
@@ -84,6 +84,7 @@ fn encode_decode_roundtrip() {
 
     let frame_data = FrameData {
         header: header.clone(),
+        codec: FrameCodec::Lz4,
         compressed: encoded.compressed.clone(),
     };
 
@@ -152,7 +153,7 @@ fn encode_decode_handles_null_values() {
         .unwrap();
     let original = builder.finish().unwrap();
 
-    let codec = Lz4BatchCodec::default();
+    let codec = FrameBatchCodec::default();
     let encoded = codec.encode(&snapshots, &original).unwrap();
 
     let mut crc = Crc32Hasher::new();
@@ -174,6 +175,7 @@ fn encode_decode_handles_null_values() {
 
     let frame_data = FrameData {
         header,
+        codec: FrameCodec::Lz4,
         compressed: encoded.compressed,
     };
 
@@ -248,7 +250,7 @@ fn encode_decode_handles_all_types() {
         .unwrap();
     let original = builder.finish().unwrap();
 
-    let codec = Lz4BatchCodec::default();
+    let codec = FrameBatchCodec::default();
     let encoded = codec.encode(&snapshots, &original).unwrap();
 
     let mut crc = Crc32Hasher::new();
@@ -270,6 +272,7 @@ fn encode_decode_handles_all_types() {
 
     let frame_data = FrameData {
         header,
+        codec: FrameCodec::Lz4,
         compressed: encoded.compressed,
     };
 
@@ -311,7 +314,7 @@ fn decode_fails_on_payload_length_mismatch() {
     let snapshots = batch_schema_to_snapshots(&schema);
     let batch = build_batch(&schema, vec![(1000, 1)]);
 
-    let codec = Lz4BatchCodec::default();
+    let codec = FrameBatchCodec::default();
     let encoded = codec.encode(&snapshots, &batch).unwrap();
 
     let mut crc = Crc32Hasher::new();
@@ -333,6 +336,7 @@ fn decode_fails_on_payload_length_mismatch() {
 
     let frame_data = FrameData {
         header,
+        codec: FrameCodec::Lz4,
         compressed: encoded.compressed,
     };
 
@@ -375,7 +379,7 @@ fn encode_compresses_data() {
         .unwrap();
     let batch = builder.finish().unwrap();
 
-    let codec = Lz4BatchCodec::default();
+    let codec = FrameBatchCodec::default();
     let encoded = codec.encode(&snapshots, &batch).unwrap();
 
     // Compressed size should be smaller than uncompressed
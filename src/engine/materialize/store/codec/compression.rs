@@ -1,9 +1,49 @@
 use std::cell::RefCell;
 
+use serde::{Deserialize, Serialize};
+
 use crate::engine::core::column::compression::Lz4Codec;
-use crate::engine::core::column::compression::compression_codec::CompressionCodec;
+pub use crate::engine::core::column::compression::compression_codec::ALGO_ZSTD;
+use crate::engine::core::column::compression::compression_codec::{ALGO_LZ4, CompressionCodec};
 use crate::engine::materialize::MaterializationError;
 
+/// Zstd level for materialized frames, trading encode speed for ratio since
+/// frames are written once and read many times.
+const ZSTD_LEVEL: i32 = 9;
+
+/// Compression applied to the payload of a materialized frame.
+///
+/// The codec is chosen per materialization, but every frame records its own
+/// codec id, so a store can hold frames of both codecs and each decodes
+/// regardless of the current choice.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameCodec {
+    #[default]
+    Lz4,
+    Zstd,
+}
+
+impl FrameCodec {
+    pub fn id(&self) -> u16 {
+        match self {
+            FrameCodec::Lz4 => ALGO_LZ4,
+            FrameCodec::Zstd => ALGO_ZSTD,
+        }
+    }
+
+    /// Frames written before codec ids were recorded carry 0 and are LZ4.
+    pub fn from_id(id: u16) -> Result<Self, MaterializationError> {
+        match id {
+            0 | ALGO_LZ4 => Ok(FrameCodec::Lz4),
+            ALGO_ZSTD => Ok(FrameCodec::Zstd),
+            other => Err(MaterializationError::Corrupt(format!(
+                "Unknown frame codec id {other}"
+            ))),
+        }
+    }
+}
+
 // Thread-local buffer pool for decompression to avoid allocations
 thread_local! {
     static DECOMPRESS_BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::new());
@@ -13,6 +53,7 @@ pub struct Decompressor;
 
 impl Decompressor {
     pub fn decompress_with_pool(
+        codec: FrameCodec,
         compressed: &[u8],
         uncompressed_len: usize,
     ) -> Result<Vec<u8>, MaterializationError> {
@@ -28,6 +69,13 @@ impl Decompressor {
             }
             buf.resize(uncompressed_len, 0);
 
+            if codec == FrameCodec::Zstd {
+                let written = zstd::bulk::decompress_to_buffer(compressed, buf.as_mut_slice())
+                    .map_err(|e| MaterializationError::Corrupt(format!("Zstd decompress: {e}")))?;
+                buf.truncate(written);
+                return Ok(buf);
+            }
+
             // LZ4 compress() uses compress_prepend_size which adds a 4-byte size header.
             // decompress_into() expects the data WITHOUT the size header, so we need to strip it.
             if compressed.len() < 4 {
@@ -52,10 +100,13 @@ impl Decompressor {
 pub struct Compressor;
 
 impl Compressor {
-    pub fn compress(data: &[u8]) -> Result<Vec<u8>, MaterializationError> {
-        let codec = Lz4Codec;
-        codec
-            .compress(data)
-            .map_err(|e| MaterializationError::Corrupt(format!("LZ4 compress: {e}")))
+    pub fn compress(codec: FrameCodec, data: &[u8]) -> Result<Vec<u8>, MaterializationError> {
+        match codec {
+            FrameCodec::Lz4 => Lz4Codec
+                .compress(data)
+                .map_err(|e| MaterializationError::Corrupt(format!("LZ4 compress: {e}"))),
+            FrameCodec::Zstd => zstd::bulk::compress(data, ZSTD_LEVEL)
+                .map_err(|e| MaterializationError::Corrupt(format!("Zstd compress: {e}"))),
+        }
     }
 }
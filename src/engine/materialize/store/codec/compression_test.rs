@@ -1,10 +1,10 @@
-use super::compression::{Compressor, Decompressor};
+use super::compression::{Compressor, Decompressor, FrameCodec};
 use crate::engine::materialize::MaterializationError;
 
 #[test]
 fn compress_compresses_data() {
     let data = b"hello world this is a test string that should compress well";
-    let compressed = Compressor::compress(data).unwrap();
+    let compressed = Compressor::compress(FrameCodec::Lz4, data).unwrap();
 
     // Compressed data should be smaller (or at least different)
     assert!(compressed.len() > 0);
@@ -14,7 +14,7 @@ fn compress_compresses_data() {
 #[test]
 fn compress_handles_empty_data() {
     let data = b"";
-    let compressed = Compressor::compress(data).unwrap();
+    let compressed = Compressor::compress(FrameCodec::Lz4, data).unwrap();
 
     // Even empty data should produce some compressed output (LZ4 header)
     assert!(compressed.len() >= 4); // At least size header
@@ -23,7 +23,7 @@ fn compress_handles_empty_data() {
 #[test]
 fn compress_handles_large_data() {
     let data: Vec<u8> = (0..10000).map(|i| (i % 256) as u8).collect();
-    let compressed = Compressor::compress(&data).unwrap();
+    let compressed = Compressor::compress(FrameCodec::Lz4, &data).unwrap();
 
     assert!(compressed.len() > 0);
     assert!(compressed.len() < data.len() * 2); // Should not expand too much
@@ -32,9 +32,10 @@ fn compress_handles_large_data() {
 #[test]
 fn decompress_with_pool_decompresses_data() {
     let original = b"hello world this is a test string";
-    let compressed = Compressor::compress(original).unwrap();
+    let compressed = Compressor::compress(FrameCodec::Lz4, original).unwrap();
 
-    let decompressed = Decompressor::decompress_with_pool(&compressed, original.len()).unwrap();
+    let decompressed =
+        Decompressor::decompress_with_pool(FrameCodec::Lz4, &compressed, original.len()).unwrap();
 
     assert_eq!(decompressed, original);
 }
@@ -46,8 +47,10 @@ fn decompress_with_pool_roundtrip() {
     let test_cases: Vec<&[u8]> = vec![b"short", b"medium length string", &data1, &data2];
 
     for original in test_cases {
-        let compressed = Compressor::compress(original).unwrap();
-        let decompressed = Decompressor::decompress_with_pool(&compressed, original.len()).unwrap();
+        let compressed = Compressor::compress(FrameCodec::Lz4, original).unwrap();
+        let decompressed =
+            Decompressor::decompress_with_pool(FrameCodec::Lz4, &compressed, original.len())
+                .unwrap();
         assert_eq!(decompressed.as_slice(), original);
     }
 }
@@ -56,7 +59,7 @@ fn decompress_with_pool_roundtrip() {
 fn decompress_with_pool_fails_on_too_short_compressed_data() {
     let data = vec![1, 2, 3]; // Less than 4 bytes (LZ4 header)
 
-    let err = Decompressor::decompress_with_pool(&data, 100).unwrap_err();
+    let err = Decompressor::decompress_with_pool(FrameCodec::Lz4, &data, 100).unwrap_err();
     assert!(matches!(err, MaterializationError::Corrupt(_)));
     assert!(err.to_string().contains("Compressed data too short"));
 }
@@ -67,14 +70,17 @@ fn decompress_with_pool_reuses_buffer() {
     let data2 = b"second compression test";
     let data3 = b"third compression test";
 
-    let compressed1 = Compressor::compress(data1).unwrap();
-    let compressed2 = Compressor::compress(data2).unwrap();
-    let compressed3 = Compressor::compress(data3).unwrap();
+    let compressed1 = Compressor::compress(FrameCodec::Lz4, data1).unwrap();
+    let compressed2 = Compressor::compress(FrameCodec::Lz4, data2).unwrap();
+    let compressed3 = Compressor::compress(FrameCodec::Lz4, data3).unwrap();
 
     // Decompress multiple times - should reuse thread-local buffer
-    let decompressed1 = Decompressor::decompress_with_pool(&compressed1, data1.len()).unwrap();
-    let decompressed2 = Decompressor::decompress_with_pool(&compressed2, data2.len()).unwrap();
-    let decompressed3 = Decompressor::decompress_with_pool(&compressed3, data3.len()).unwrap();
+    let decompressed1 =
+        Decompressor::decompress_with_pool(FrameCodec::Lz4, &compressed1, data1.len()).unwrap();
+    let decompressed2 =
+        Decompressor::decompress_with_pool(FrameCodec::Lz4, &compressed2, data2.len()).unwrap();
+    let decompressed3 =
+        Decompressor::decompress_with_pool(FrameCodec::Lz4, &compressed3, data3.len()).unwrap();
 
     assert_eq!(decompressed1.as_slice(), data1);
     assert_eq!(decompressed2.as_slice(), data2);
@@ -85,7 +91,7 @@ fn decompress_with_pool_reuses_buffer() {
 fn compress_handles_repeated_patterns() {
     // Highly compressible data
     let data = b"AAAA".repeat(1000);
-    let compressed = Compressor::compress(&data).unwrap();
+    let compressed = Compressor::compress(FrameCodec::Lz4, &data).unwrap();
 
     // Should compress significantly
     assert!(compressed.len() < data.len());
@@ -94,8 +100,26 @@ fn compress_handles_repeated_patterns() {
 #[test]
 fn decompress_with_pool_handles_zero_length() {
     let data = b"";
-    let compressed = Compressor::compress(data).unwrap();
+    let compressed = Compressor::compress(FrameCodec::Lz4, data).unwrap();
 
-    let decompressed = Decompressor::decompress_with_pool(&compressed, 0).unwrap();
+    let decompressed = Decompressor::decompress_with_pool(FrameCodec::Lz4, &compressed, 0).unwrap();
     assert_eq!(decompressed.len(), 0);
 }
+
+#[test]
+fn zstd_roundtrip_and_ids() {
+    let data = b"materialized frame payload ".repeat(200);
+    let compressed = Compressor::compress(FrameCodec::Zstd, &data).unwrap();
+    assert!(compressed.len() < data.len());
+
+    let decompressed =
+        Decompressor::decompress_with_pool(FrameCodec::Zstd, &compressed, data.len()).unwrap();
+    assert_eq!(decompressed, data);
+
+    for codec in [FrameCodec::Lz4, FrameCodec::Zstd] {
+        assert_eq!(FrameCodec::from_id(codec.id()).unwrap(), codec);
+    }
+    // Frames written before codec ids were recorded
+    assert_eq!(FrameCodec::from_id(0).unwrap(), FrameCodec::Lz4);
+    assert!(FrameCodec::from_id(7).is_err());
+}
@@ -20,10 +20,29 @@ impl Decoder {
         payload: Vec<u8>,
         header: &FrameHeader,
     ) -> Result<ColumnBatch, MaterializationError> {
-        let schema = SchemaConverter::to_batch_schema(&meta.schema)?;
-        let schema_arc = Arc::new(schema);
+        Self::decode_columns(meta, payload, header, None)
+    }
+
+    /// Decodes only the columns at the indices in `projection`, in that
+    /// order. The bytes of the other columns are skipped, not parsed.
+    pub fn decode_columns(
+        meta: &StoredFrameMeta,
+        payload: Vec<u8>,
+        header: &FrameHeader,
+        projection: Option<&[usize]>,
+    ) -> Result<ColumnBatch, MaterializationError> {
         let row_count = header.row_count as usize;
         let column_count = header.column_count as usize;
+        if let Some(&bad) = projection
+            .into_iter()
+            .flatten()
+            .find(|&&idx| idx >= column_count)
+        {
+            return Err(MaterializationError::Corrupt(format!(
+                "Frame {} has no column {bad}",
+                meta.file_name
+            )));
+        }
 
         let null_len = header.null_bitmap_len as usize;
 
@@ -100,6 +119,16 @@ impl Decoder {
                 None
             };
 
+            if projection.is_some_and(|wanted| !wanted.contains(&col_idx)) {
+                for row in 0..row_count {
+                    let idx = row * column_count + col_idx;
+                    if !NullBitmap::is_null(null_bitmap, idx) {
+                        cursor = Self::skip_value(cursor, logical_type, length_table, row)?;
+                    }
+                }
+                continue;
+            }
+
             // Decode all rows for this column at once
             for row in 0..row_count {
                 let idx = row * column_count + col_idx;
@@ -197,7 +226,41 @@ impl Decoder {
             )));
         }
 
-        ColumnBatch::new(schema_arc, columns, row_count, None)
+        let (snapshots, columns) = match projection {
+            None => (meta.schema.clone(), columns),
+            Some(wanted) => wanted
+                .iter()
+                .map(|&idx| (meta.schema[idx].clone(), std::mem::take(&mut columns[idx])))
+                .unzip(),
+        };
+        let schema = SchemaConverter::to_batch_schema(&snapshots)?;
+        ColumnBatch::new(Arc::new(schema), columns, row_count, None)
             .map_err(|e| MaterializationError::Batch(e.to_string()))
     }
+
+    /// Advances past one non-null value, laid out as [`Self::decode_columns`]
+    /// reads it.
+    fn skip_value<'a>(
+        cursor: &'a [u8],
+        logical_type: &str,
+        length_table: Option<&Vec<u32>>,
+        row: usize,
+    ) -> Result<&'a [u8], MaterializationError> {
+        let len = match (logical_type, length_table) {
+            ("Timestamp" | "Integer" | "Float", _) => 8,
+            ("Boolean", _) => 1,
+            (_, Some(length_table)) => length_table[row] as usize,
+            (_, None) => {
+                if cursor.len() < 4 {
+                    return Err(MaterializationError::Corrupt(
+                        "Insufficient bytes for value length".into(),
+                    ));
+                }
+                4 + u32::from_le_bytes(cursor[..4].try_into().unwrap()) as usize
+            }
+        };
+        cursor
+            .get(len..)
+            .ok_or_else(|| MaterializationError::Corrupt("Insufficient bytes to skip value".into()))
+    }
 }
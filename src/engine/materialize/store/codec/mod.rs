@@ -25,6 +25,7 @@ mod utils_test;
 #[cfg(test)]
 mod value_codec_test;
 
-pub use batch_codec::{BatchCodec, Lz4BatchCodec};
+pub use batch_codec::{BatchCodec, FrameBatchCodec};
+pub use compression::FrameCodec;
 pub use schema::{batch_schema_to_snapshots, schema_hash, schema_to_batch_schema};
 pub use types::EncodedFrame;
@@ -1,5 +1,7 @@
 use crate::engine::materialize::catalog::SchemaSnapshot;
 
+use super::compression::FrameCodec;
+
 pub struct EncodedFrame {
     pub schema: Vec<SchemaSnapshot>,
     pub schema_hash: u64,
@@ -8,6 +10,7 @@ pub struct EncodedFrame {
     pub max_timestamp: u64,
     pub max_event_id: u64,
     pub null_bitmap_len: u32,
+    pub codec: FrameCodec,
     pub compressed: Vec<u8>,
     pub uncompressed_len: u32,
 }
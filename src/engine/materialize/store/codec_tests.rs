@@ -1,5 +1,5 @@
 use super::codec::{
-    BatchCodec, EncodedFrame, Lz4BatchCodec, batch_schema_to_snapshots, schema_hash,
+    BatchCodec, EncodedFrame, FrameBatchCodec, FrameCodec, batch_schema_to_snapshots, schema_hash,
 };
 use super::frame::data::FrameData;
 use super::frame::header::FrameHeader;
@@ -58,7 +58,7 @@ fn lz4_codec_roundtrip() {
     let snapshots = batch_schema_to_snapshots(&schema);
     let batch = build_batch(&schema);
 
-    let codec = Lz4BatchCodec::default();
+    let codec = FrameBatchCodec::default();
     let encoded: EncodedFrame = codec.encode(&snapshots, &batch).unwrap();
 
     let mut crc = Crc32Hasher::new();
@@ -79,6 +79,7 @@ fn lz4_codec_roundtrip() {
     };
     let frame_data = FrameData {
         header: header.clone(),
+        codec: FrameCodec::Lz4,
         compressed: encoded.compressed.clone(),
     };
 
@@ -0,0 +1,119 @@
+//! Manifest compaction for the materialized store.
+//!
+//! Compaction drops manifest references to frames whose files are gone (e.g.
+//! deleted by retention just before a crash) and coalesces runs of small
+//! adjacent frames into frames of the store's [`FrameSize`], so the manifest
+//! stays short.
+//!
+//! It is crash-safe: coalesced frames are written and synced first, then the
+//! new manifest is written to a temporary file, synced, and renamed over the
+//! old one. A crash before the rename leaves the old manifest, whose frames
+//! are all still on disk; the new frame files are orphans.
+//!
+//! Readers open the manifest on their own and are never blocked. A frame
+//! replaced by a coalesced one is only deleted by the next compaction, so a
+//! reader that loaded the previous manifest can still read it meanwhile.
+//! That sweep also removes orphans left by a crashed compaction.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::engine::core::read::flow::{BatchPool, ColumnBatch};
+use crate::engine::materialize::MaterializationError;
+
+use super::frame::metadata::StoredFrameMeta;
+use super::frame::storage::FrameStorage;
+use super::frame_size::FrameSize;
+
+/// An append compacts the manifest once this many frames can be coalesced.
+pub const AUTO_COMPACT_FRAMES: usize = 64;
+
+/// What a compaction changed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompactionSummary {
+    pub frames_before: usize,
+    pub frames_after: usize,
+    /// References to frames whose file no longer exists.
+    pub missing_dropped: usize,
+    /// Frames rewritten into coalesced ones.
+    pub frames_coalesced: usize,
+    /// Unreferenced frame files deleted from earlier compactions.
+    pub files_removed: usize,
+}
+
+/// Runs of at least two adjacent frames sharing a schema that together fit
+/// in one frame of `target`.
+pub fn coalesce_runs(frames: &[StoredFrameMeta], target: FrameSize) -> Vec<Range<usize>> {
+    let limit = target.limit();
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < frames.len() {
+        let mut end = start + 1;
+        let mut size = target.measure(&frames[start]);
+        while let Some(next) = frames.get(end) {
+            size = size.saturating_add(target.measure(next));
+            if next.schema_hash != frames[start].schema_hash || size > limit {
+                break;
+            }
+            end += 1;
+        }
+        if end - start >= 2 {
+            runs.push(start..end);
+        }
+        start = end;
+    }
+    runs
+}
+
+/// Number of frames a compaction to `target` would coalesce.
+pub fn coalescible_frames(frames: &[StoredFrameMeta], target: FrameSize) -> usize {
+    coalesce_runs(frames, target)
+        .iter()
+        .map(|run| run.len())
+        .sum()
+}
+
+/// Frame files no manifest entry refers to. Files numbered at or past
+/// `next_frame_index` are skipped: they may belong to an append in flight.
+pub fn orphan_files(
+    storage: &FrameStorage,
+    frames: &[StoredFrameMeta],
+    next_frame_index: u64,
+) -> Result<Vec<String>, MaterializationError> {
+    let mut orphans = Vec::new();
+    for dir_entry in std::fs::read_dir(storage.path())? {
+        let file_name = dir_entry?.file_name().to_string_lossy().into_owned();
+        let Some(index) = file_name
+            .strip_suffix(".mat")
+            .and_then(|stem| stem.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        if index < next_frame_index && !frames.iter().any(|meta| meta.file_name == file_name) {
+            orphans.push(file_name);
+        }
+    }
+    orphans.sort();
+    Ok(orphans)
+}
+
+/// Appends the rows of `batches` into one batch.
+pub fn concat_batches(batches: &[Arc<ColumnBatch>]) -> Result<ColumnBatch, MaterializationError> {
+    let batch_err =
+        |e: crate::engine::core::read::flow::BatchError| MaterializationError::Batch(e.to_string());
+    let first = batches
+        .first()
+        .ok_or_else(|| MaterializationError::Corrupt("No frames to coalesce".into()))?;
+    let rows: usize = batches.iter().map(|batch| batch.len()).sum();
+
+    let pool = BatchPool::new(rows.max(1)).map_err(batch_err)?;
+    let mut builder = pool.acquire(Arc::new(first.schema().clone()));
+    for batch in batches {
+        for row_idx in 0..batch.len() {
+            builder
+                .push_row(&batch.row(row_idx).map_err(batch_err)?)
+                .map_err(batch_err)?;
+        }
+    }
+    builder.finish().map_err(batch_err)
+}
@@ -0,0 +1,210 @@
+use super::compaction::coalesce_runs;
+use super::frame_size::DEFAULT_FRAME_ROWS;
+use super::{FrameSize, MaterializedStore, StoredFrameMeta, batch_schema_to_snapshots};
+use crate::engine::core::read::flow::{BatchPool, BatchSchema, ColumnBatch};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::materialize::catalog::SchemaSnapshot;
+use crate::engine::materialize::high_water::HighWaterMark;
+use crate::engine::types::ScalarValue;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn make_meta(file_name: &str, row_count: u32, schema_hash: u64) -> StoredFrameMeta {
+    StoredFrameMeta {
+        file_name: file_name.into(),
+        schema: vec![SchemaSnapshot::new("timestamp", "Timestamp")],
+        schema_hash,
+        row_count,
+        min_timestamp: 0,
+        max_timestamp: 0,
+        max_event_id: 0,
+        compressed_len: 32,
+        uncompressed_len: 64,
+        null_bitmap_len: 8,
+        high_water_mark: HighWaterMark::default(),
+    }
+}
+
+fn build_batch(first_id: u64, rows: u64) -> (Arc<BatchSchema>, ColumnBatch) {
+    let schema = Arc::new(
+        BatchSchema::new(vec![
+            ColumnSpec {
+                name: "timestamp".into(),
+                logical_type: "Timestamp".into(),
+            },
+            ColumnSpec {
+                name: "event_id".into(),
+                logical_type: "Integer".into(),
+            },
+        ])
+        .unwrap(),
+    );
+    let pool = BatchPool::new(rows as usize).unwrap();
+    let mut builder = pool.acquire(Arc::clone(&schema));
+    for id in first_id..first_id + rows {
+        builder
+            .push_row(&[
+                ScalarValue::from(json!(1_700_000_000 + id)),
+                ScalarValue::from(json!(id)),
+            ])
+            .unwrap();
+    }
+    (schema, builder.finish().unwrap())
+}
+
+/// Appends `frames` single-row frames with event ids 1..=frames.
+fn fill_store(dir: &Path, frames: u64) -> MaterializedStore {
+    let mut store = MaterializedStore::open(dir).unwrap();
+    for id in 1..=frames {
+        let (schema, batch) = build_batch(id, 1);
+        store
+            .append_batch(&batch_schema_to_snapshots(&schema), &batch)
+            .unwrap();
+    }
+    store
+}
+
+fn stored_ids(store: &MaterializedStore) -> Vec<u64> {
+    let mut ids = Vec::new();
+    for meta in store.frames() {
+        let batch = store.read_frame(meta).unwrap();
+        let column = batch.column(1).unwrap();
+        ids.extend(column.iter().map(|v| v.to_json().as_u64().unwrap()));
+    }
+    ids
+}
+
+fn frame_names(store: &MaterializedStore) -> Vec<String> {
+    store
+        .frames()
+        .iter()
+        .map(|meta| meta.file_name.clone())
+        .collect()
+}
+
+fn frame_files(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir.join("frames"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn coalesce_runs_groups_adjacent_small_frames_with_same_schema() {
+    let frames = vec![
+        make_meta("000000.mat", 10, 1),
+        make_meta("000001.mat", 10, 1),
+        make_meta("000002.mat", DEFAULT_FRAME_ROWS, 1),
+        make_meta("000003.mat", 10, 1),
+        make_meta("000004.mat", 10, 2),
+        make_meta("000005.mat", 10, 2),
+        make_meta("000006.mat", 10, 2),
+    ];
+
+    assert_eq!(
+        coalesce_runs(&frames, FrameSize::default()),
+        vec![0..2, 4..7]
+    );
+    assert_eq!(
+        coalesce_runs(&frames, FrameSize::Rows(25)),
+        vec![0..2, 4..6]
+    );
+}
+
+#[test]
+fn compaction_coalesces_small_frames_and_keeps_rows_and_high_water() {
+    let dir = tempdir().unwrap();
+    let mut store = fill_store(dir.path(), 5);
+    let high_water = store.frames().last().unwrap().high_water_mark;
+
+    let summary = store.compact_manifest().unwrap();
+
+    assert_eq!(summary.frames_before, 5);
+    assert_eq!(summary.frames_after, 1);
+    assert_eq!(summary.frames_coalesced, 5);
+    assert_eq!(store.frames()[0].row_count, 5);
+    assert_eq!(store.frames()[0].high_water_mark, high_water);
+    assert_eq!(stored_ids(&store), vec![1, 2, 3, 4, 5]);
+
+    let reopened = MaterializedStore::open(dir.path()).unwrap();
+    assert_eq!(frame_names(&reopened), frame_names(&store));
+    assert_eq!(stored_ids(&reopened), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn compaction_drops_references_to_missing_frames() {
+    let dir = tempdir().unwrap();
+    let mut store = fill_store(dir.path(), 3);
+    let lost = store.frames()[0].file_name.clone();
+    std::fs::remove_file(dir.path().join("frames").join(&lost)).unwrap();
+
+    let summary = store.compact_manifest().unwrap();
+
+    assert_eq!(summary.missing_dropped, 1);
+    assert!(store.frames().iter().all(|meta| meta.file_name != lost));
+    assert_eq!(stored_ids(&store), vec![2, 3]);
+}
+
+#[test]
+fn replaced_frames_stay_readable_until_the_next_compaction() {
+    let dir = tempdir().unwrap();
+    let mut store = fill_store(dir.path(), 3);
+    let reader = MaterializedStore::open(dir.path()).unwrap();
+
+    store.compact_manifest().unwrap();
+    assert_eq!(stored_ids(&reader), vec![1, 2, 3]);
+
+    // The next sweep deletes the frames the first compaction replaced
+    let summary = store.compact_manifest().unwrap();
+    assert_eq!(summary.files_removed, 3);
+    assert_eq!(
+        frame_files(dir.path()),
+        vec![store.frames()[0].file_name.clone()]
+    );
+}
+
+#[test]
+fn crash_mid_compaction_leaves_every_frame_readable() {
+    let dir = tempdir().unwrap();
+    let store = fill_store(dir.path(), 4);
+    let before = frame_names(&store);
+
+    // Simulate a compaction that wrote its coalesced frame and a partial
+    // manifest, then crashed before the rename
+    let (schema, batch) = build_batch(1, 4);
+    let mut crashed = MaterializedStore::open(dir.path().join("scratch")).unwrap();
+    crashed
+        .append_batch(&batch_schema_to_snapshots(&schema), &batch)
+        .unwrap();
+    std::fs::copy(
+        dir.path().join("scratch/frames/000000.mat"),
+        dir.path().join("frames/000004.mat"),
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("manifest.tmp"), b"partial").unwrap();
+    drop(store);
+
+    let mut reopened = MaterializedStore::open(dir.path()).unwrap();
+    assert_eq!(frame_names(&reopened), before);
+    assert_eq!(stored_ids(&reopened), vec![1, 2, 3, 4]);
+
+    // Compacting again ignores the leftovers and yields the same rows
+    let summary = reopened.compact_manifest().unwrap();
+    assert_eq!(summary.frames_after, 1);
+    assert_eq!(stored_ids(&reopened), vec![1, 2, 3, 4]);
+    let reopened = MaterializedStore::open(dir.path()).unwrap();
+    assert_eq!(stored_ids(&reopened), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn appends_compact_automatically_once_enough_frames_are_small() {
+    let dir = tempdir().unwrap();
+    let store = fill_store(dir.path(), 64);
+
+    assert_eq!(store.frames().len(), 1);
+    assert_eq!(stored_ids(&store), (1..=64).collect::<Vec<_>>());
+}
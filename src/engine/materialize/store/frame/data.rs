@@ -1,7 +1,10 @@
 use super::header::FrameHeader;
+use crate::engine::materialize::store::codec::FrameCodec;
 
 #[derive(Debug, Clone)]
 pub struct FrameData {
     pub header: FrameHeader,
+    /// Codec the payload was compressed with, read from the frame file.
+    pub codec: FrameCodec,
     pub compressed: Vec<u8>,
 }
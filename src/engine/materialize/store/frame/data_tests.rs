@@ -1,5 +1,6 @@
 use super::data::FrameData;
 use super::header::FrameHeader;
+use crate::engine::materialize::store::codec::FrameCodec;
 
 fn sample_header() -> FrameHeader {
     FrameHeader {
@@ -23,6 +24,7 @@ fn frame_data_retains_header_and_payload() {
 
     let data = FrameData {
         header: header.clone(),
+        codec: FrameCodec::Lz4,
         compressed: payload.clone(),
     };
 
@@ -5,6 +5,7 @@ use std::path::Path;
 use crc32fast::Hasher as Crc32Hasher;
 
 use crate::engine::materialize::MaterializationError;
+use crate::engine::materialize::store::codec::FrameCodec;
 use crate::shared::storage_header::{BinaryHeader, FileKind};
 
 use super::data::FrameData;
@@ -24,7 +25,7 @@ impl<'a> FrameReader<'a> {
         let frame_path = self.frame_dir.join(&meta.file_name);
         let mut file = File::open(&frame_path)?;
 
-        let header = self.read_and_validate_header(&mut file, meta.schema_hash)?;
+        let (header, codec) = self.read_and_validate_header(&mut file, meta.schema_hash)?;
 
         let mut compressed = vec![0u8; header.compressed_len as usize];
         file.read_exact(&mut compressed)?;
@@ -39,14 +40,18 @@ impl<'a> FrameReader<'a> {
             )));
         }
 
-        Ok(FrameData { header, compressed })
+        Ok(FrameData {
+            header,
+            codec,
+            compressed,
+        })
     }
 
     fn read_and_validate_header(
         &self,
         file: &mut File,
         expected_hash: u64,
-    ) -> Result<FrameHeader, MaterializationError> {
+    ) -> Result<(FrameHeader, FrameCodec), MaterializationError> {
         let header = BinaryHeader::read_from(&mut *file)?;
         if header.magic != FileKind::MaterializedFrame.magic() {
             return Err(MaterializationError::Header("Invalid frame magic".into()));
@@ -57,6 +62,7 @@ impl<'a> FrameReader<'a> {
                 header.version
             )));
         }
+        let codec = FrameCodec::from_id(header.flags)?;
 
         let frame_header = FrameHeader::read_from(&mut *file)?;
         if frame_header.schema_hash != expected_hash {
@@ -64,6 +70,6 @@ impl<'a> FrameReader<'a> {
                 "Schema hash mismatch while reading frame".into(),
             ));
         }
-        Ok(frame_header)
+        Ok((frame_header, codec))
     }
 }
@@ -2,7 +2,7 @@ use super::reader::FrameReader;
 use super::storage::FrameStorage;
 use crate::engine::materialize::MaterializationError;
 use crate::engine::materialize::catalog::SchemaSnapshot;
-use crate::engine::materialize::store::codec::EncodedFrame;
+use crate::engine::materialize::store::codec::{EncodedFrame, FrameCodec};
 use tempfile::tempdir;
 
 fn build_encoded_frame() -> EncodedFrame {
@@ -14,6 +14,7 @@ fn build_encoded_frame() -> EncodedFrame {
         max_timestamp: 1_700_000_500,
         max_event_id: 42,
         null_bitmap_len: 2,
+        codec: FrameCodec::Lz4,
         compressed: vec![10, 20, 30, 40],
         uncompressed_len: 256,
     }
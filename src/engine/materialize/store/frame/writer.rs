@@ -35,9 +35,14 @@ impl<'a> FrameWriter<'a> {
             .truncate(true)
             .open(&frame_path)?;
 
-        BinaryHeader::new(FileKind::MaterializedFrame.magic(), FRAME_VERSION as u16, 0)
-            .write_to(&mut file)
-            .map_err(|e| MaterializationError::Header(e.to_string()))?;
+        // The header flags carry the codec id so each frame decodes on its own
+        BinaryHeader::new(
+            FileKind::MaterializedFrame.magic(),
+            FRAME_VERSION as u16,
+            frame.codec.id(),
+        )
+        .write_to(&mut file)
+        .map_err(|e| MaterializationError::Header(e.to_string()))?;
 
         let mut crc = Crc32Hasher::new();
         crc.update(&frame.compressed);
@@ -3,7 +3,7 @@ use super::writer::FrameWriter;
 use crate::engine::materialize::MaterializationError;
 use crate::engine::materialize::catalog::SchemaSnapshot;
 use crate::engine::materialize::high_water::HighWaterMark;
-use crate::engine::materialize::store::codec::EncodedFrame;
+use crate::engine::materialize::store::codec::{EncodedFrame, FrameCodec};
 use tempfile::tempdir;
 
 fn build_encoded_frame() -> EncodedFrame {
@@ -16,6 +16,7 @@ fn build_encoded_frame() -> EncodedFrame {
         max_timestamp: 1_700_000_300,
         max_event_id: 55,
         null_bitmap_len: 4,
+        codec: FrameCodec::Lz4,
         compressed: vec![1, 2, 3, 4, 5, 6],
         uncompressed_len: 128,
     }
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::engine::core::read::flow::ColumnBatch;
+
+use super::frame::metadata::StoredFrameMeta;
+
+/// Rows per frame unless a materialization chooses otherwise.
+pub const DEFAULT_FRAME_ROWS: u32 = 8192;
+
+/// Target size of the frames written to a materialized store. Small frames
+/// bloat the manifest; large ones make each refresh rewrite more than it
+/// appended. Frames are filled up to the target; the last frame of a refresh
+/// may be smaller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameSize {
+    /// At most this many rows per frame.
+    Rows(u32),
+    /// At most about this many uncompressed bytes per frame, and at least
+    /// one row.
+    Bytes(u64),
+}
+
+impl Default for FrameSize {
+    fn default() -> Self {
+        FrameSize::Rows(DEFAULT_FRAME_ROWS)
+    }
+}
+
+impl FrameSize {
+    /// Rows per frame for rows the size of those in `batch`.
+    pub fn rows_for(&self, batch: &ColumnBatch) -> usize {
+        match *self {
+            FrameSize::Rows(rows) => (rows as usize).max(1),
+            FrameSize::Bytes(bytes) => {
+                let row_bytes = (batch.approx_bytes() / batch.len().max(1)).max(1);
+                (bytes as usize / row_bytes).max(1)
+            }
+        }
+    }
+
+    /// How much of the target `meta` takes up, in the target's unit.
+    pub fn measure(&self, meta: &StoredFrameMeta) -> u64 {
+        match self {
+            FrameSize::Rows(_) => u64::from(meta.row_count),
+            FrameSize::Bytes(_) => u64::from(meta.uncompressed_len),
+        }
+    }
+
+    pub fn limit(&self) -> u64 {
+        match *self {
+            FrameSize::Rows(rows) => u64::from(rows),
+            FrameSize::Bytes(bytes) => bytes,
+        }
+    }
+}
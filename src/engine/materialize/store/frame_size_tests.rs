@@ -0,0 +1,36 @@
+use super::FrameSize;
+use super::frame_size::DEFAULT_FRAME_ROWS;
+use crate::engine::core::read::flow::{BatchSchema, ColumnBatch};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::types::ScalarValue;
+use std::sync::Arc;
+
+fn string_batch(rows: usize, value_len: usize) -> ColumnBatch {
+    let schema = Arc::new(
+        BatchSchema::new(vec![ColumnSpec {
+            name: "payload".into(),
+            logical_type: "String".into(),
+        }])
+        .unwrap(),
+    );
+    let values = vec![ScalarValue::Utf8("x".repeat(value_len)); rows];
+    ColumnBatch::new(schema, vec![values], rows, None).unwrap()
+}
+
+#[test]
+fn rows_target_ignores_row_width() {
+    assert_eq!(FrameSize::default(), FrameSize::Rows(DEFAULT_FRAME_ROWS));
+    assert_eq!(FrameSize::Rows(100).rows_for(&string_batch(4, 10)), 100);
+    assert_eq!(FrameSize::Rows(100).rows_for(&string_batch(4, 10_000)), 100);
+    assert_eq!(FrameSize::Rows(0).rows_for(&string_batch(4, 10)), 1);
+}
+
+#[test]
+fn bytes_target_scales_with_row_width() {
+    let narrow = FrameSize::Bytes(1 << 20).rows_for(&string_batch(4, 10));
+    let wide = FrameSize::Bytes(1 << 20).rows_for(&string_batch(4, 10_000));
+
+    assert!(narrow > wide);
+    assert!(wide >= 1);
+    assert_eq!(FrameSize::Bytes(1).rows_for(&string_batch(4, 10_000)), 1);
+}
@@ -1,4 +1,4 @@
-use super::codec::{BatchCodec, Lz4BatchCodec, batch_schema_to_snapshots};
+use super::codec::{BatchCodec, FrameBatchCodec, FrameCodec, batch_schema_to_snapshots};
 use super::frame::storage::FrameStorage;
 use crate::engine::core::read::flow::{BatchPool, BatchSchema};
 use crate::engine::core::read::result::ColumnSpec;
@@ -37,7 +37,7 @@ fn frame_writer_reader_roundtrip() {
         .unwrap();
     let batch = builder.finish().unwrap();
 
-    let codec = Lz4BatchCodec::default();
+    let codec = FrameBatchCodec::default();
     let encoded = codec.encode(&snapshots, &batch).unwrap();
 
     let storage = FrameStorage::create(dir.path()).unwrap();
@@ -51,3 +51,41 @@ fn frame_writer_reader_roundtrip() {
     assert_eq!(decoded.len(), 1);
     assert_eq!(decoded.column(1).unwrap()[0], ScalarValue::from(json!(42)));
 }
+
+#[test]
+fn frames_decode_with_their_own_codec() {
+    let dir = tempdir().unwrap();
+    let schema = Arc::new(build_schema());
+    let snapshots = batch_schema_to_snapshots(&schema);
+    let storage = FrameStorage::create(dir.path()).unwrap();
+
+    let pool = BatchPool::new(8).unwrap();
+    let mut metas = Vec::new();
+    for (index, codec) in [FrameCodec::Lz4, FrameCodec::Zstd].into_iter().enumerate() {
+        let mut builder = pool.acquire(Arc::clone(&schema));
+        builder
+            .push_row(&[
+                ScalarValue::from(json!(1_700_000_000_u64)),
+                ScalarValue::from(json!(index as u64)),
+            ])
+            .unwrap();
+        let batch = builder.finish().unwrap();
+        let encoded = FrameBatchCodec::new(codec)
+            .encode(&snapshots, &batch)
+            .unwrap();
+        metas.push(storage.writer().write(index as u64, &encoded).unwrap());
+    }
+
+    // A decoder configured for LZ4 still reads the Zstd frame
+    let decoder = FrameBatchCodec::default();
+    for (index, meta) in metas.iter().enumerate() {
+        let data = storage.reader().read(meta).unwrap();
+        let expected = [FrameCodec::Lz4, FrameCodec::Zstd][index];
+        assert_eq!(data.codec, expected);
+        let decoded = decoder.decode(meta, data).unwrap();
+        assert_eq!(
+            decoded.column(1).unwrap()[0],
+            ScalarValue::from(json!(index as u64))
+        );
+    }
+}
@@ -28,6 +28,11 @@ impl ManifestState {
         self.frames.push(meta);
     }
 
+    /// Swaps in `frames`, returning the ones they replace.
+    pub fn replace_frames(&mut self, frames: Vec<StoredFrameMeta>) -> Vec<StoredFrameMeta> {
+        std::mem::replace(&mut self.frames, frames)
+    }
+
     pub fn next_frame_index(&self) -> u64 {
         self.next_frame_index
     }
@@ -86,6 +91,16 @@ impl ManifestStore {
     pub fn persist(&self, state: &ManifestState) -> Result<(), MaterializationError> {
         persist_manifest(&self.path, state)
     }
+
+    /// Reads the manifest as currently persisted, including changes made
+    /// through other handles on the same store.
+    pub fn reload(&self) -> Result<ManifestState, MaterializationError> {
+        if self.path.exists() {
+            load_manifest(&self.path)
+        } else {
+            Ok(ManifestState::default())
+        }
+    }
 }
 
 fn load_manifest(path: &Path) -> Result<ManifestState, MaterializationError> {
@@ -142,5 +157,9 @@ fn persist_manifest(path: &Path, state: &ManifestState) -> Result<(), Materializ
     file.sync_all()?;
 
     fs::rename(&tmp, path)?;
+    // Make the rename itself durable
+    if let Some(dir) = path.parent() {
+        File::open(dir)?.sync_all()?;
+    }
     Ok(())
 }
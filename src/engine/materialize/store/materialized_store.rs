@@ -6,21 +6,30 @@ use crate::engine::materialize::MaterializationError;
 use crate::engine::materialize::catalog::{
     MaterializationTelemetry, RetentionPolicy, SchemaSnapshot,
 };
+use crate::engine::materialize::high_water::HighWaterMark;
 
 use crate::engine::core::read::cache::GlobalMaterializedFrameCache;
 
-use super::codec::{BatchCodec, Lz4BatchCodec, schema_hash};
+use super::codec::{BatchCodec, FrameBatchCodec, FrameCodec, schema_hash};
+use super::compaction::{
+    AUTO_COMPACT_FRAMES, CompactionSummary, coalesce_runs, coalescible_frames, concat_batches,
+    orphan_files,
+};
 use super::frame::metadata::StoredFrameMeta;
 use super::frame::storage::FrameStorage;
+use super::frame_size::FrameSize;
 use super::manifest::{ManifestState, ManifestStore};
+use super::reader::FrameReader;
 use super::retention::RetentionEnforcer;
 use super::telemetry::TelemetryTracker;
+use tracing::warn;
 
 pub struct MaterializedStore {
     frame_storage: FrameStorage,
     manifest_store: ManifestStore,
     manifest: ManifestState,
     codec: Box<dyn BatchCodec + Send + Sync>,
+    frame_size: FrameSize,
 }
 
 impl MaterializedStore {
@@ -38,7 +47,8 @@ impl MaterializedStore {
             frame_storage,
             manifest_store,
             manifest: manifest_state,
-            codec: Box::new(Lz4BatchCodec::default()),
+            codec: Box::new(FrameBatchCodec::default()),
+            frame_size: FrameSize::default(),
         })
     }
 
@@ -47,6 +57,22 @@ impl MaterializedStore {
         self
     }
 
+    /// Compresses new frames with `codec`; existing frames keep theirs.
+    pub fn with_frame_codec(self, codec: FrameCodec) -> Self {
+        self.with_codec(Box::new(FrameBatchCodec::new(codec)))
+    }
+
+    /// Sizes the frames sinks write and compaction coalesces into. Existing
+    /// frames keep their size until compacted.
+    pub fn with_frame_size(mut self, frame_size: FrameSize) -> Self {
+        self.frame_size = frame_size;
+        self
+    }
+
+    pub fn frame_size(&self) -> FrameSize {
+        self.frame_size
+    }
+
     pub fn frames(&self) -> &[StoredFrameMeta] {
         self.manifest.frames()
     }
@@ -85,6 +111,160 @@ impl MaterializedStore {
 
         self.persist_manifest()?;
 
+        if coalescible_frames(self.manifest.frames(), self.frame_size) >= AUTO_COMPACT_FRAMES
+            && let Err(err) = self.compact_manifest()
+        {
+            warn!(
+                target: "sneldb::materialize",
+                frame_dir = %self.frame_dir().display(),
+                error = %err,
+                "Manifest compaction failed"
+            );
+        }
+
+        Ok(meta)
+    }
+
+    /// Rewrites the manifest without references to missing frames and with
+    /// runs of small frames coalesced, then deletes the frame files earlier
+    /// compactions left behind. Appends compact on their own once enough
+    /// frames can be coalesced; this runs it on demand. On failure the
+    /// persisted manifest is left as it was.
+    pub fn compact_manifest(&mut self) -> Result<CompactionSummary, MaterializationError> {
+        // Start from the persisted manifest so appends made through other
+        // handles since this one was opened are kept
+        self.manifest = self.manifest_store.reload()?;
+        let mut summary = CompactionSummary {
+            frames_before: self.manifest.frames().len(),
+            ..Default::default()
+        };
+
+        let cache = GlobalMaterializedFrameCache::instance();
+        let orphans = orphan_files(
+            &self.frame_storage,
+            self.manifest.frames(),
+            self.manifest.next_frame_index(),
+        )?;
+        for file_name in &orphans {
+            self.frame_storage.remove(file_name);
+            cache.invalidate_frame(self.frame_storage.path(), file_name);
+        }
+        summary.files_removed = orphans.len();
+
+        let live: Vec<StoredFrameMeta> = self
+            .manifest
+            .frames()
+            .iter()
+            .filter(|meta| self.frame_storage.path().join(&meta.file_name).exists())
+            .cloned()
+            .collect();
+        summary.missing_dropped = summary.frames_before - live.len();
+
+        let mut written: Vec<StoredFrameMeta> = Vec::new();
+        let compacted = self.coalesce(&live, &mut written);
+        let compacted = match compacted {
+            Ok(compacted) => compacted,
+            Err(err) => {
+                for meta in &written {
+                    self.frame_storage.remove(&meta.file_name);
+                }
+                self.manifest = self.manifest_store.reload()?;
+                return Err(err);
+            }
+        };
+        summary.frames_coalesced = compacted.1;
+        summary.frames_after = compacted.0.len();
+
+        if summary.missing_dropped == 0 && summary.frames_coalesced == 0 {
+            return Ok(summary);
+        }
+
+        let previous = self.manifest.replace_frames(compacted.0);
+        if let Err(err) = self.persist_manifest() {
+            self.manifest.replace_frames(previous);
+            for meta in &written {
+                self.frame_storage.remove(&meta.file_name);
+            }
+            return Err(err);
+        }
+        Ok(summary)
+    }
+
+    /// Writes one frame per coalescible run of `live`, recording each in
+    /// `written`. Returns the resulting frame list and how many frames it
+    /// replaced.
+    fn coalesce(
+        &mut self,
+        live: &[StoredFrameMeta],
+        written: &mut Vec<StoredFrameMeta>,
+    ) -> Result<(Vec<StoredFrameMeta>, usize), MaterializationError> {
+        let runs = coalesce_runs(live, self.frame_size);
+        let mut frames = Vec::with_capacity(live.len());
+        let mut coalesced = 0;
+        let mut next = 0;
+        for run in runs {
+            frames.extend_from_slice(&live[next..run.start]);
+            let batches = live[run.clone()]
+                .iter()
+                .map(|meta| self.read_frame(meta))
+                .collect::<Result<Vec<_>, _>>()?;
+            let batch = concat_batches(&batches)?;
+
+            // Keep the high-water mark of the newest frame in the run
+            let last = &live[run.end - 1];
+            let mut encoded = self.codec.encode(&last.schema, &batch)?;
+            encoded.max_timestamp = last.high_water_mark.timestamp;
+            encoded.max_event_id = last.high_water_mark.event_id;
+            let index = self.manifest.next_frame_index();
+            let meta = self.frame_storage.writer().write(index, &encoded)?;
+            self.manifest.bump_frame_index();
+
+            written.push(meta.clone());
+            frames.push(meta);
+            coalesced += run.len();
+            next = run.end;
+        }
+        frames.extend_from_slice(&live[next..]);
+        Ok((frames, coalesced))
+    }
+
+    /// Writes `batch` as the store's only frame, stamped with `high_water`.
+    ///
+    /// The frames it replaces stay readable until the manifest pointing at
+    /// the new frame is persisted, so a failed write leaves the previous
+    /// contents and high-water mark in place. Retention does not apply.
+    pub fn replace_with_batch(
+        &mut self,
+        schema: &[SchemaSnapshot],
+        batch: &ColumnBatch,
+        high_water: HighWaterMark,
+    ) -> Result<StoredFrameMeta, MaterializationError> {
+        if schema.is_empty() {
+            return Err(MaterializationError::Corrupt(
+                "Materialized store requires non-empty schema".into(),
+            ));
+        }
+
+        let mut encoded = self.codec.encode(schema, batch)?;
+        encoded.max_timestamp = high_water.timestamp;
+        encoded.max_event_id = high_water.event_id;
+        let index = self.manifest.next_frame_index();
+        let meta = self.frame_storage.writer().write(index, &encoded)?;
+
+        self.manifest.bump_frame_index();
+        let previous = self.manifest.replace_frames(vec![meta.clone()]);
+        if let Err(err) = self.persist_manifest() {
+            self.manifest.replace_frames(previous);
+            self.frame_storage.remove(&meta.file_name);
+            return Err(err);
+        }
+
+        let cache = GlobalMaterializedFrameCache::instance();
+        for frame in previous {
+            self.frame_storage.remove(&frame.file_name);
+            cache.invalidate_frame(self.frame_storage.path(), &frame.file_name);
+        }
+
         Ok(meta)
     }
 
@@ -107,6 +287,29 @@ impl MaterializedStore {
         Ok(batch_arc)
     }
 
+    /// Reads only the columns at `columns` of a frame, in that order. Unlike
+    /// [`Self::read_frame`] this bypasses the frame cache, which holds whole
+    /// frames, unless every column is requested in stored order.
+    pub fn read_frame_columns(
+        &self,
+        meta: &StoredFrameMeta,
+        columns: &[usize],
+    ) -> Result<Arc<ColumnBatch>, MaterializationError> {
+        if columns.iter().copied().eq(0..meta.schema.len()) {
+            return self.read_frame(meta);
+        }
+        let frame_data = self.frame_storage.reader().read(meta)?;
+        Ok(Arc::new(
+            self.codec.decode_columns(meta, frame_data, columns)?,
+        ))
+    }
+
+    /// Streams the stored frames in order, decoding each only when it is
+    /// reached.
+    pub fn stream(&self) -> FrameReader<'_> {
+        FrameReader::new(self, self.manifest.frames().to_vec())
+    }
+
     pub fn frame_dir(&self) -> &Path {
         self.frame_storage.path()
     }
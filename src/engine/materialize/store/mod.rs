@@ -1,23 +1,39 @@
 mod codec;
+mod compaction;
 mod frame;
+mod frame_size;
 mod manifest;
 mod materialized_store;
+mod reader;
 mod retention;
 mod telemetry;
+mod verify;
 
 #[cfg(test)]
 mod codec_tests;
 #[cfg(test)]
+mod compaction_tests;
+#[cfg(test)]
+mod frame_size_tests;
+#[cfg(test)]
 mod frame_tests;
 #[cfg(test)]
 mod manifest_tests;
 #[cfg(test)]
 mod materialized_store_tests;
 #[cfg(test)]
+mod reader_tests;
+#[cfg(test)]
 mod retention_tests;
 #[cfg(test)]
 mod telemetry_tests;
+#[cfg(test)]
+mod verify_tests;
 
-pub use codec::{batch_schema_to_snapshots, schema_hash, schema_to_batch_schema};
+pub use codec::{FrameCodec, batch_schema_to_snapshots, schema_hash, schema_to_batch_schema};
+pub use compaction::CompactionSummary;
 pub use frame::metadata::StoredFrameMeta;
+pub use frame_size::FrameSize;
 pub use materialized_store::MaterializedStore;
+pub use reader::FrameReader;
+pub use verify::{FrameIssue, IssueSeverity, VerifyReport, verify_store};
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use crate::engine::core::read::flow::ColumnBatch;
+use crate::engine::materialize::MaterializationError;
+
+use super::frame::metadata::StoredFrameMeta;
+use super::materialized_store::MaterializedStore;
+
+/// Yields a store's frames as batches, in manifest order, decoding one frame
+/// per call to `next`. Only the projected columns are decoded, and reading
+/// stops once the row limit is reached.
+pub struct FrameReader<'a> {
+    store: &'a MaterializedStore,
+    frames: std::vec::IntoIter<StoredFrameMeta>,
+    projection: Option<Vec<String>>,
+    remaining: Option<usize>,
+}
+
+impl<'a> FrameReader<'a> {
+    pub fn new(store: &'a MaterializedStore, frames: Vec<StoredFrameMeta>) -> Self {
+        Self {
+            store,
+            frames: frames.into_iter(),
+            projection: None,
+            remaining: None,
+        }
+    }
+
+    /// Decodes only `columns`, in that order.
+    pub fn with_projection(mut self, columns: Vec<String>) -> Self {
+        self.projection = Some(columns);
+        self
+    }
+
+    /// Stops after `limit` rows, cutting the last batch short if needed.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.remaining = Some(limit);
+        self
+    }
+
+    fn read(&self, meta: &StoredFrameMeta) -> Result<Arc<ColumnBatch>, MaterializationError> {
+        let Some(projection) = &self.projection else {
+            return self.store.read_frame(meta);
+        };
+        let columns = projection
+            .iter()
+            .map(|name| {
+                meta.schema
+                    .iter()
+                    .position(|column| &column.name == name)
+                    .ok_or_else(|| {
+                        MaterializationError::Batch(format!(
+                            "Unknown column '{name}' in frame {}",
+                            meta.file_name
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.store.read_frame_columns(meta, &columns)
+    }
+}
+
+impl Iterator for FrameReader<'_> {
+    type Item = Result<Arc<ColumnBatch>, MaterializationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        let meta = loop {
+            let meta = self.frames.next()?;
+            if meta.row_count > 0 {
+                break meta;
+            }
+        };
+
+        let batch = match self.read(&meta) {
+            Ok(batch) => batch,
+            Err(err) => {
+                // A failed frame ends the stream
+                self.frames = Vec::new().into_iter();
+                return Some(Err(err));
+            }
+        };
+        let Some(remaining) = self.remaining.as_mut() else {
+            return Some(Ok(batch));
+        };
+        if batch.len() <= *remaining {
+            *remaining -= batch.len();
+            return Some(Ok(batch));
+        }
+        let keep = *remaining;
+        *remaining = 0;
+        Some(truncate(&batch, keep))
+    }
+}
+
+fn truncate(batch: &ColumnBatch, rows: usize) -> Result<Arc<ColumnBatch>, MaterializationError> {
+    let columns = batch
+        .columns_ref()
+        .iter()
+        .map(|column| column[..rows].to_vec())
+        .collect();
+    ColumnBatch::new(Arc::new(batch.schema().clone()), columns, rows, None)
+        .map(Arc::new)
+        .map_err(|e| MaterializationError::Batch(e.to_string()))
+}
@@ -0,0 +1,141 @@
+use super::{MaterializedStore, batch_schema_to_snapshots};
+use crate::engine::core::read::flow::{BatchPool, BatchSchema, ColumnBatch};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::types::ScalarValue;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn build_schema() -> Arc<BatchSchema> {
+    Arc::new(
+        BatchSchema::new(vec![
+            ColumnSpec {
+                name: "timestamp".into(),
+                logical_type: "Timestamp".into(),
+            },
+            ColumnSpec {
+                name: "context_id".into(),
+                logical_type: "String".into(),
+            },
+            ColumnSpec {
+                name: "score".into(),
+                logical_type: "Number".into(),
+            },
+            ColumnSpec {
+                name: "event_id".into(),
+                logical_type: "Integer".into(),
+            },
+        ])
+        .expect("valid schema"),
+    )
+}
+
+/// Appends one frame per entry of `frame_sizes`; event ids count up from 1.
+fn populate_store(dir: &Path, frame_sizes: &[u64]) -> MaterializedStore {
+    let mut store = MaterializedStore::open(dir).unwrap();
+    let schema = build_schema();
+    let mut next_id = 1_u64;
+    for &rows in frame_sizes {
+        let pool = BatchPool::new(rows as usize).unwrap();
+        let mut builder = pool.acquire(Arc::clone(&schema));
+        for _ in 0..rows {
+            let context = if next_id % 2 == 0 {
+                ScalarValue::Null
+            } else {
+                ScalarValue::from(json!(format!("ctx-{next_id}")))
+            };
+            builder
+                .push_row(&[
+                    ScalarValue::from(json!(1_700_000_000 + next_id)),
+                    context,
+                    ScalarValue::from(json!(next_id * 10)),
+                    ScalarValue::from(json!(next_id)),
+                ])
+                .unwrap();
+            next_id += 1;
+        }
+        store
+            .append_batch(
+                &batch_schema_to_snapshots(&schema),
+                &builder.finish().unwrap(),
+            )
+            .unwrap();
+    }
+    store
+}
+
+fn column_values(batch: &ColumnBatch, idx: usize) -> Vec<serde_json::Value> {
+    batch
+        .column(idx)
+        .unwrap()
+        .iter()
+        .map(|value| value.to_json())
+        .collect()
+}
+
+#[test]
+fn reader_streams_frames_in_order() {
+    let dir = tempdir().unwrap();
+    let store = populate_store(dir.path(), &[2, 3, 1]);
+
+    let batches: Vec<_> = store.stream().map(Result::unwrap).collect();
+
+    assert_eq!(
+        batches.iter().map(|b| b.len()).collect::<Vec<_>>(),
+        vec![2, 3, 1]
+    );
+    let ids: Vec<_> = batches.iter().flat_map(|b| column_values(b, 3)).collect();
+    assert_eq!(ids, (1..=6).map(|id| json!(id)).collect::<Vec<_>>());
+}
+
+#[test]
+fn reader_decodes_only_projected_columns_in_requested_order() {
+    let dir = tempdir().unwrap();
+    let store = populate_store(dir.path(), &[3]);
+
+    let batches: Vec<_> = store
+        .stream()
+        .with_projection(vec!["event_id".into(), "score".into()])
+        .map(Result::unwrap)
+        .collect();
+
+    let batch = &batches[0];
+    let names: Vec<_> = batch.schema().columns().iter().map(|c| &c.name).collect();
+    assert_eq!(names, vec!["event_id", "score"]);
+    assert_eq!(column_values(batch, 0), vec![json!(1), json!(2), json!(3)]);
+    assert_eq!(
+        column_values(batch, 1),
+        vec![json!(10), json!(20), json!(30)]
+    );
+}
+
+#[test]
+fn reader_stops_at_limit_without_reading_later_frames() {
+    let dir = tempdir().unwrap();
+    let store = populate_store(dir.path(), &[2, 2, 2]);
+    let last = store.frames()[2].file_name.clone();
+    std::fs::remove_file(dir.path().join("frames").join(last)).unwrap();
+
+    let batches: Vec<_> = store.stream().with_limit(3).map(Result::unwrap).collect();
+
+    assert_eq!(
+        batches.iter().map(|b| b.len()).collect::<Vec<_>>(),
+        vec![2, 1]
+    );
+    assert_eq!(column_values(&batches[1], 3), vec![json!(3)]);
+}
+
+#[test]
+fn reader_reports_unknown_projected_column() {
+    let dir = tempdir().unwrap();
+    let store = populate_store(dir.path(), &[1, 1]);
+
+    let results: Vec<_> = store
+        .stream()
+        .with_projection(vec!["missing".into()])
+        .collect();
+
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+}
@@ -0,0 +1,199 @@
+//! Read-only consistency check of a materialized store against its catalog
+//! entry. Findings are either recoverable (leftover files that are safe to
+//! delete) or fatal (frames the manifest relies on that cannot be read).
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+use crate::engine::materialize::MaterializationError;
+use crate::engine::materialize::catalog::SchemaSnapshot;
+
+use super::codec::{BatchCodec, FrameBatchCodec, schema_hash};
+use super::frame::metadata::StoredFrameMeta;
+use super::frame::reader::FrameReader;
+use super::manifest::ManifestStore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueSeverity {
+    /// Nothing refers to the file; deleting it loses no data.
+    Recoverable,
+    /// Data the manifest points at is gone or unreadable.
+    Fatal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameIssue {
+    /// Referenced by the manifest but not on disk.
+    Missing { file_name: String },
+    /// On disk but not referenced by the manifest.
+    Orphan { file_name: String },
+    /// A manifest write that never completed.
+    StaleManifestTmp,
+    /// Fails its checksum, header or decode checks.
+    Corrupt { file_name: String, reason: String },
+    /// Decodes, but its schema is not the one the catalog records.
+    SchemaMismatch { file_name: String, reason: String },
+}
+
+impl FrameIssue {
+    pub fn severity(&self) -> IssueSeverity {
+        match self {
+            FrameIssue::Orphan { .. } | FrameIssue::StaleManifestTmp => IssueSeverity::Recoverable,
+            FrameIssue::Missing { .. }
+            | FrameIssue::Corrupt { .. }
+            | FrameIssue::SchemaMismatch { .. } => IssueSeverity::Fatal,
+        }
+    }
+}
+
+impl fmt::Display for FrameIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameIssue::Missing { file_name } => {
+                write!(
+                    f,
+                    "frame {file_name} is in the manifest but missing on disk"
+                )
+            }
+            FrameIssue::Orphan { file_name } => write!(
+                f,
+                "frame {file_name} is not in the manifest (safe to delete)"
+            ),
+            FrameIssue::StaleManifestTmp => write!(
+                f,
+                "manifest.tmp is left from an interrupted write (safe to delete)"
+            ),
+            FrameIssue::Corrupt { file_name, reason } => {
+                write!(f, "frame {file_name} is corrupt: {reason}")
+            }
+            FrameIssue::SchemaMismatch { file_name, reason } => {
+                write!(
+                    f,
+                    "frame {file_name} does not match the catalog schema: {reason}"
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub frames_checked: usize,
+    pub rows_checked: u64,
+    pub issues: Vec<FrameIssue>,
+}
+
+impl VerifyReport {
+    pub fn count(&self, severity: IssueSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity() == severity)
+            .count()
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Checks the store under `dir` without writing to it: every frame in the
+/// manifest must exist, pass its checksum, decode, and carry `schema`; every
+/// frame file must be in the manifest. An empty `schema` skips the schema
+/// comparison. A store that was never written has nothing to check. An unreadable manifest is an error, since no frame can
+/// be checked without it.
+pub fn verify_store(
+    dir: &Path,
+    schema: &[SchemaSnapshot],
+) -> Result<VerifyReport, MaterializationError> {
+    let (_, manifest) = ManifestStore::open(dir.join("manifest.bin"))?;
+    let frame_dir = dir.join("frames");
+    let reader = FrameReader::new(&frame_dir);
+    let codec = FrameBatchCodec::default();
+    let mut report = VerifyReport::default();
+
+    for meta in manifest.frames() {
+        report.frames_checked += 1;
+        if !frame_dir.join(&meta.file_name).exists() {
+            report.issues.push(FrameIssue::Missing {
+                file_name: meta.file_name.clone(),
+            });
+            continue;
+        }
+        if let Some(reason) = schema_drift(meta, schema) {
+            report.issues.push(FrameIssue::SchemaMismatch {
+                file_name: meta.file_name.clone(),
+                reason,
+            });
+            continue;
+        }
+        let checked = reader.read(meta).and_then(|data| {
+            if data.header.row_count != meta.row_count {
+                return Err(MaterializationError::Corrupt(format!(
+                    "header has {} rows, manifest {}",
+                    data.header.row_count, meta.row_count
+                )));
+            }
+            codec.decode(meta, data)
+        });
+        match checked {
+            Ok(batch) => report.rows_checked += batch.len() as u64,
+            Err(err) => report.issues.push(FrameIssue::Corrupt {
+                file_name: meta.file_name.clone(),
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    let referenced: HashSet<&str> = manifest
+        .frames()
+        .iter()
+        .map(|meta| meta.file_name.as_str())
+        .collect();
+    if frame_dir.exists() {
+        let mut orphans = Vec::new();
+        for dir_entry in std::fs::read_dir(&frame_dir)? {
+            let file_name = dir_entry?.file_name().to_string_lossy().into_owned();
+            if file_name.ends_with(".mat") && !referenced.contains(file_name.as_str()) {
+                orphans.push(file_name);
+            }
+        }
+        orphans.sort();
+        report.issues.extend(
+            orphans
+                .into_iter()
+                .map(|file_name| FrameIssue::Orphan { file_name }),
+        );
+    }
+    if dir.join("manifest.tmp").exists() {
+        report.issues.push(FrameIssue::StaleManifestTmp);
+    }
+
+    Ok(report)
+}
+
+fn schema_drift(meta: &StoredFrameMeta, schema: &[SchemaSnapshot]) -> Option<String> {
+    if schema_hash(&meta.schema) != meta.schema_hash {
+        return Some("schema hash does not match the frame's schema".into());
+    }
+    if schema.is_empty() {
+        return None;
+    }
+    if meta.schema.len() != schema.len() {
+        return Some(format!(
+            "{} columns, catalog has {}",
+            meta.schema.len(),
+            schema.len()
+        ));
+    }
+    meta.schema
+        .iter()
+        .zip(schema)
+        .find(|(frame, catalog)| frame != catalog)
+        .map(|(frame, catalog)| {
+            format!(
+                "column {} {}, catalog has {} {}",
+                frame.name, frame.logical_type, catalog.name, catalog.logical_type
+            )
+        })
+}
@@ -0,0 +1,135 @@
+use super::{
+    FrameIssue, IssueSeverity, MaterializedStore, batch_schema_to_snapshots, verify_store,
+};
+use crate::engine::core::read::flow::{BatchPool, BatchSchema};
+use crate::engine::core::read::result::ColumnSpec;
+use crate::engine::materialize::catalog::SchemaSnapshot;
+use crate::engine::types::ScalarValue;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn build_schema() -> Arc<BatchSchema> {
+    Arc::new(
+        BatchSchema::new(vec![
+            ColumnSpec {
+                name: "timestamp".into(),
+                logical_type: "Timestamp".into(),
+            },
+            ColumnSpec {
+                name: "event_id".into(),
+                logical_type: "Integer".into(),
+            },
+        ])
+        .unwrap(),
+    )
+}
+
+/// Writes `frames` two-row frames and returns the schema they were written with.
+fn populate_store(dir: &Path, frames: u64) -> Vec<SchemaSnapshot> {
+    let schema = build_schema();
+    let snapshots = batch_schema_to_snapshots(&schema);
+    let mut store = MaterializedStore::open(dir).unwrap();
+    let pool = BatchPool::new(2).unwrap();
+    for frame in 0..frames {
+        let mut builder = pool.acquire(Arc::clone(&schema));
+        for id in [frame * 2 + 1, frame * 2 + 2] {
+            builder
+                .push_row(&[
+                    ScalarValue::from(json!(1_700_000_000 + id)),
+                    ScalarValue::from(json!(id)),
+                ])
+                .unwrap();
+        }
+        store
+            .append_batch(&snapshots, &builder.finish().unwrap())
+            .unwrap();
+    }
+    snapshots
+}
+
+#[test]
+fn verify_reports_a_consistent_store_as_healthy() {
+    let dir = tempdir().unwrap();
+    let schema = populate_store(dir.path(), 3);
+
+    let report = verify_store(dir.path(), &schema).unwrap();
+
+    assert!(report.is_healthy());
+    assert_eq!(report.frames_checked, 3);
+    assert_eq!(report.rows_checked, 6);
+}
+
+#[test]
+fn verify_tells_missing_frames_from_orphans() {
+    let dir = tempdir().unwrap();
+    let schema = populate_store(dir.path(), 2);
+    let frames = dir.path().join("frames");
+    std::fs::remove_file(frames.join("000000.mat")).unwrap();
+    std::fs::copy(frames.join("000001.mat"), frames.join("000007.mat")).unwrap();
+    std::fs::write(dir.path().join("manifest.tmp"), b"partial").unwrap();
+
+    let report = verify_store(dir.path(), &schema).unwrap();
+
+    assert_eq!(
+        report.issues,
+        vec![
+            FrameIssue::Missing {
+                file_name: "000000.mat".into()
+            },
+            FrameIssue::Orphan {
+                file_name: "000007.mat".into()
+            },
+            FrameIssue::StaleManifestTmp,
+        ]
+    );
+    assert_eq!(report.count(IssueSeverity::Fatal), 1);
+    assert_eq!(report.count(IssueSeverity::Recoverable), 2);
+    assert_eq!(report.rows_checked, 2);
+}
+
+#[test]
+fn verify_flags_checksum_failures_as_fatal() {
+    let dir = tempdir().unwrap();
+    let schema = populate_store(dir.path(), 1);
+    let path = dir.path().join("frames/000000.mat");
+    let mut bytes = std::fs::read(&path).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    std::fs::write(&path, bytes).unwrap();
+
+    let report = verify_store(dir.path(), &schema).unwrap();
+
+    assert_eq!(report.issues.len(), 1);
+    assert!(matches!(
+        &report.issues[0],
+        FrameIssue::Corrupt { reason, .. } if reason.contains("Checksum")
+    ));
+    assert_eq!(report.issues[0].severity(), IssueSeverity::Fatal);
+}
+
+#[test]
+fn verify_flags_frames_whose_schema_differs_from_the_catalog() {
+    let dir = tempdir().unwrap();
+    let mut schema = populate_store(dir.path(), 1);
+    schema[1] = SchemaSnapshot::new("event_id", "String");
+
+    let report = verify_store(dir.path(), &schema).unwrap();
+
+    assert!(matches!(
+        &report.issues[..],
+        [FrameIssue::SchemaMismatch { .. }]
+    ));
+}
+
+#[test]
+fn verify_does_not_create_a_missing_store() {
+    let dir = tempdir().unwrap();
+    let store_dir = dir.path().join("never_written");
+
+    let report = verify_store(&store_dir, &[]).unwrap();
+
+    assert!(report.is_healthy());
+    assert!(!store_dir.exists());
+}
@@ -0,0 +1,253 @@
+use crate::engine::core::memory::passive_buffer_set::PassiveBufferSet;
+use crate::engine::core::read::cache::{ColumnValueCacheKey, GlobalColumnValueCache};
+use crate::engine::core::{
+    ColumnReader, Event, EventId, InflightSegments, MemTable, SegmentIndex, ZoneMeta,
+};
+use crate::engine::errors::QueryExecutionError;
+use crate::engine::schema::registry::SchemaRegistry;
+use crate::engine::types::ScalarValue;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+const LOG_TARGET: &str = "sneldb::event_lookup";
+
+/// Entry point used by shard workers to fetch a single event by id without a
+/// predicate scan.
+///
+/// The active and passive MemTables are checked first. Flushed events are then
+/// resolved through the segment index: only zones whose time range can hold the
+/// id are considered, and only their `event_id` column is read until the row is
+/// found. The row's other columns go through the column value cache when it is
+/// enabled. Returns `Ok(None)` when the event is not on this shard, e.g. because it
+/// never existed or was removed by compaction.
+pub async fn lookup_event(
+    id: EventId,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    segment_base_dir: &Path,
+    memtable: &MemTable,
+    passive_buffers: &Arc<PassiveBufferSet>,
+    inflight_segments: Option<&InflightSegments>,
+) -> Result<Option<Event>, QueryExecutionError> {
+    if let Some(event) = memtable.iter().find(|event| event.event_id() == id) {
+        return Ok(Some(event.clone()));
+    }
+    for passive in passive_buffers.non_empty().await {
+        let passive = passive.lock().await;
+        if let Some(event) = passive.iter().find(|event| event.event_id() == id) {
+            return Ok(Some(event.clone()));
+        }
+    }
+
+    // A compaction may retire a segment between loading the index and reading it;
+    // its rows then live in the merged segment, so look once more with a fresh index.
+    for _ in 0..2 {
+        let (candidates, schemas) =
+            segment_candidates(segment_base_dir, registry, inflight_segments).await?;
+        let base_dir = segment_base_dir.to_path_buf();
+        let lookup = tokio::task::spawn_blocking(move || {
+            find_in_segments(id, &base_dir, &candidates, &schemas)
+        })
+        .await
+        .map_err(|e| QueryExecutionError::ColRead(e.to_string()))??;
+
+        match lookup {
+            SegmentLookup::Found(event) => return Ok(Some(*event)),
+            SegmentLookup::NotFound => return Ok(None),
+            SegmentLookup::Retired => {
+                debug!(target: LOG_TARGET, %id, "Segment retired during lookup, retrying");
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Schema details needed to rebuild an event from its columns.
+struct UidSchema {
+    event_type: String,
+    fields: Vec<String>,
+}
+
+enum SegmentLookup {
+    Found(Box<Event>),
+    NotFound,
+    /// Not found, but a segment disappeared while it was being searched
+    Retired,
+}
+
+async fn segment_candidates(
+    segment_base_dir: &Path,
+    registry: &Arc<RwLock<SchemaRegistry>>,
+    inflight_segments: Option<&InflightSegments>,
+) -> Result<(Vec<(String, Vec<String>)>, BTreeMap<String, UidSchema>), QueryExecutionError> {
+    let index = SegmentIndex::load(segment_base_dir)
+        .await
+        .map_err(|e| QueryExecutionError::ColRead(e.to_string()))?;
+
+    // Segments still being written are served from the passive MemTables
+    let candidates: Vec<(String, Vec<String>)> = index
+        .iter_all()
+        .map(|entry| (entry.label(), entry.uids.clone()))
+        .filter(|(label, _)| !inflight_segments.is_some_and(|inflight| inflight.contains(label)))
+        .collect();
+
+    let registry = registry.read().await;
+    let schemas = candidates
+        .iter()
+        .flat_map(|(_, uids)| uids.iter())
+        .filter_map(|uid| {
+            let schema = registry.get_schema_by_uid(uid)?;
+            let event_type = registry.get_event_type_by_uid(uid)?;
+            Some((
+                uid.clone(),
+                UidSchema {
+                    event_type,
+                    fields: schema.fields().cloned().collect(),
+                },
+            ))
+        })
+        .collect();
+    Ok((candidates, schemas))
+}
+
+fn find_in_segments(
+    id: EventId,
+    base_dir: &Path,
+    candidates: &[(String, Vec<String>)],
+    schemas: &BTreeMap<String, UidSchema>,
+) -> Result<SegmentLookup, QueryExecutionError> {
+    // Events are stamped before their id is generated, so a zone whose oldest
+    // event is newer than the id cannot contain it.
+    let id_secs = id.created_at_millis() / 1000;
+    let mut retired = false;
+
+    for (label, uids) in candidates {
+        let segment_dir = base_dir.join(label);
+        for uid in uids {
+            let Some(schema) = schemas.get(uid) else {
+                continue;
+            };
+            let zones_path = segment_dir.join(format!("{}.zones", uid));
+            let zones = match ZoneMeta::load(&zones_path) {
+                Ok(zones) => zones,
+                Err(_) if !segment_dir.exists() => {
+                    retired = true;
+                    break;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            for zone in zones.iter().filter(|zone| zone.timestamp_min <= id_secs) {
+                let row = match find_row(id, &segment_dir, label, uid, zone.zone_id) {
+                    Ok(row) => row,
+                    Err(_) if !segment_dir.exists() => {
+                        retired = true;
+                        break;
+                    }
+                    Err(e) => return Err(e),
+                };
+                if let Some(row) = row {
+                    debug!(
+                        target: LOG_TARGET,
+                        %id,
+                        segment = %label,
+                        uid = %uid,
+                        zone_id = zone.zone_id,
+                        row,
+                        "Resolved event id to zone row"
+                    );
+                    let event =
+                        load_event(id, &segment_dir, label, uid, zone.zone_id, row, schema)?;
+                    return Ok(SegmentLookup::Found(Box::new(event)));
+                }
+            }
+        }
+    }
+
+    Ok(if retired {
+        SegmentLookup::Retired
+    } else {
+        SegmentLookup::NotFound
+    })
+}
+
+fn find_row(
+    id: EventId,
+    segment_dir: &Path,
+    segment_id: &str,
+    uid: &str,
+    zone_id: u32,
+) -> Result<Option<usize>, QueryExecutionError> {
+    let values = ColumnReader::load_for_zone_snapshot(
+        segment_dir,
+        segment_id,
+        uid,
+        "event_id",
+        zone_id,
+        None,
+    )?
+    .into_values();
+    Ok((0..values.len()).find(|&idx| {
+        values
+            .get_u64_at(idx)
+            .or_else(|| values.get_i64_at(idx).map(|v| v as u64))
+            .or_else(|| values.get_str_at(idx).and_then(|s| s.parse::<u64>().ok()))
+            == Some(id.raw())
+    }))
+}
+
+fn load_event(
+    id: EventId,
+    segment_dir: &Path,
+    segment_id: &str,
+    uid: &str,
+    zone_id: u32,
+    row: usize,
+    schema: &UidSchema,
+) -> Result<Event, QueryExecutionError> {
+    let column = |field: &str| {
+        ColumnReader::load_for_zone_snapshot(segment_dir, segment_id, uid, field, zone_id, None)
+    };
+    // Hot rows are served from the value cache without decoding their columns
+    let cache = GlobalColumnValueCache::instance();
+    let key = |field: &str| ColumnValueCacheKey::new(segment_dir, uid, field, zone_id, row);
+    let string_at = |field: &str| -> Result<String, QueryExecutionError> {
+        let value = cache.get_or_load(key(field), || {
+            Ok::<_, QueryExecutionError>(ScalarValue::Utf8(
+                column(field)?
+                    .into_strings()
+                    .into_iter()
+                    .nth(row)
+                    .unwrap_or_default(),
+            ))
+        })?;
+        Ok(match value {
+            ScalarValue::Utf8(s) => s,
+            _ => String::new(),
+        })
+    };
+
+    let mut payload = BTreeMap::new();
+    for field in &schema.fields {
+        let value = cache.get_or_load(key(field), || {
+            Ok::<_, QueryExecutionError>(
+                column(field)?
+                    .into_scalar_values()
+                    .into_iter()
+                    .nth(row)
+                    .unwrap_or(ScalarValue::Null),
+            )
+        })?;
+        payload.insert(field.clone(), value);
+    }
+
+    Ok(Event {
+        event_type: schema.event_type.clone(),
+        context_id: string_at("context_id")?,
+        timestamp: string_at("timestamp")?.parse::<u64>().unwrap_or_default(),
+        id,
+        payload,
+    })
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use tempfile::TempDir;
+
+use crate::engine::core::memory::passive_buffer_set::PassiveBufferSet;
+use crate::engine::core::{EventId, EventIdGenerator, MemTable};
+use crate::engine::query::event_lookup::lookup_event;
+use crate::test_helpers::factories::{EventFactory, MemTableFactory, SchemaRegistryFactory};
+
+fn event_with_id(id: EventId, context_id: &str) -> crate::engine::core::Event {
+    EventFactory::new()
+        .with("event_type", "test_event")
+        .with("context_id", context_id)
+        .with("event_id", id.raw())
+        .with("payload", json!({ "value": 1 }))
+        .create()
+}
+
+#[tokio::test]
+async fn lookup_finds_events_in_active_and_passive_memtables() {
+    let registry = SchemaRegistryFactory::new().registry();
+    let tmp_dir = TempDir::new().expect("temp dir");
+    let mut generator = EventIdGenerator::new();
+    let active_id = generator.next(0);
+    let passive_id = generator.next(0);
+
+    let memtable = MemTableFactory::new()
+        .with_events(vec![event_with_id(active_id, "active")])
+        .create()
+        .expect("memtable");
+    let passive_source = MemTableFactory::new()
+        .with_events(vec![event_with_id(passive_id, "passive")])
+        .create()
+        .expect("memtable");
+    let passive_buffers = Arc::new(PassiveBufferSet::new(4));
+    passive_buffers.add_from(&passive_source).await;
+
+    let active = lookup_event(
+        active_id,
+        &registry,
+        tmp_dir.path(),
+        &memtable,
+        &passive_buffers,
+        None,
+    )
+    .await
+    .expect("lookup");
+    assert_eq!(active.map(|e| e.context_id), Some("active".to_string()));
+
+    let passive = lookup_event(
+        passive_id,
+        &registry,
+        tmp_dir.path(),
+        &memtable,
+        &passive_buffers,
+        None,
+    )
+    .await
+    .expect("lookup");
+    assert_eq!(passive.map(|e| e.context_id), Some("passive".to_string()));
+}
+
+#[tokio::test]
+async fn lookup_returns_none_for_unknown_id() {
+    let registry = SchemaRegistryFactory::new().registry();
+    let tmp_dir = TempDir::new().expect("temp dir");
+    let memtable = MemTable::new(4);
+    let passive_buffers = Arc::new(PassiveBufferSet::new(4));
+
+    let found = lookup_event(
+        EventIdGenerator::new().next(0),
+        &registry,
+        tmp_dir.path(),
+        &memtable,
+        &passive_buffers,
+        None,
+    )
+    .await
+    .expect("lookup");
+    assert!(found.is_none());
+}
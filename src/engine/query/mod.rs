@@ -1,8 +1,11 @@
+pub mod event_lookup;
 pub mod execution_engine;
 pub mod rlte_planner;
 pub mod scan;
 pub mod streaming;
 
+#[cfg(test)]
+mod event_lookup_test;
 #[cfg(test)]
 mod rlte_planner_tests;
 #[cfg(test)]
@@ -1,6 +1,7 @@
 use crate::command::types::Command;
 use crate::engine::core::memory::passive_buffer_set::PassiveBufferSet;
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
+use crate::engine::core::read::flow::{QueryCancellation, QueryMemoryBudget, QueryScanStats};
 use crate::engine::core::{InflightSegments, MemTable};
 use crate::engine::errors::QueryExecutionError;
 use crate::engine::query::streaming::StreamingScan;
@@ -9,6 +10,25 @@ use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Per-query handles a shard scan runs under: the memory budget it reserves
+/// against, the statistics it reports to and the signal that stops it.
+#[derive(Clone)]
+pub struct ScanControls {
+    pub memory: Arc<QueryMemoryBudget>,
+    pub stats: Arc<QueryScanStats>,
+    pub cancellation: Arc<QueryCancellation>,
+}
+
+impl Default for ScanControls {
+    fn default() -> Self {
+        Self {
+            memory: QueryMemoryBudget::unlimited(),
+            stats: QueryScanStats::new(),
+            cancellation: QueryCancellation::new(),
+        }
+    }
+}
+
 /// Entry point used by shard workers to start a scan and return the
 /// resulting flow handle back to the coordinator.
 pub async fn scan(
@@ -20,6 +40,7 @@ pub async fn scan(
     memtable: &MemTable,
     passive_buffers: &Arc<PassiveBufferSet>,
     inflight_segments: Option<InflightSegments>,
+    controls: ScanControls,
 ) -> Result<ShardFlowHandle, QueryExecutionError> {
     let scan = StreamingScan::new(
         command,
@@ -31,6 +52,9 @@ pub async fn scan(
         passive_buffers,
         inflight_segments,
     )
-    .await?;
+    .await?
+    .with_memory_budget(controls.memory)
+    .with_scan_stats(controls.stats)
+    .with_cancellation(controls.cancellation);
     scan.execute().await
 }
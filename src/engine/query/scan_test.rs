@@ -8,7 +8,7 @@ use tokio::time::timeout;
 
 use crate::engine::core::MemTable;
 use crate::engine::core::memory::passive_buffer_set::PassiveBufferSet;
-use crate::engine::query::scan::scan;
+use crate::engine::query::scan::{ScanControls, scan};
 use crate::test_helpers::factories::{
     CommandFactory, EventFactory, MemTableFactory, SchemaRegistryFactory,
 };
@@ -72,6 +72,7 @@ async fn scan_emits_memtable_rows() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await
     .expect("scan should succeed");
@@ -143,6 +144,7 @@ async fn scan_with_metadata() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await;
 
@@ -187,6 +189,7 @@ async fn scan_with_empty_memtable() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await
     .expect("scan should succeed even with empty memtable");
@@ -245,6 +248,7 @@ async fn scan_supports_aggregate_queries() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await;
 
@@ -306,6 +310,7 @@ async fn scan_with_limit() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await
     .expect("scan with limit should succeed");
@@ -381,6 +386,7 @@ async fn scan_with_context_id_filter() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await
     .expect("scan with context filter should succeed");
@@ -438,6 +444,7 @@ async fn scan_handles_missing_schema() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await;
 
@@ -506,6 +513,7 @@ async fn scan_with_multiple_segments() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await
     .expect("scan with multiple segments should succeed");
@@ -563,6 +571,7 @@ async fn scan_returns_valid_schema() {
         &memtable,
         &passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await
     .expect("scan should succeed");
@@ -2,10 +2,20 @@ use std::sync::Arc;
 
 use crate::engine::core::memory::passive_buffer_set::PassiveBufferSet;
 use crate::engine::core::read::cache::query_caches::QueryCaches;
-use crate::engine::core::read::flow::{BatchPool, FlowContext, FlowMetrics, FlowTelemetry};
+use crate::engine::core::read::flow::{
+    BatchPool, FlowContext, FlowMetrics, FlowTelemetry, OperatorProfiler, QueryCancellation,
+    QueryMemoryBudget, QueryScanStats,
+};
 use crate::engine::core::{MemTable, QueryPlan};
 use crate::engine::errors::QueryExecutionError;
 
+/// Plan metadata key that enables the sampling operator profiler for a query.
+pub const PROFILE_METADATA_KEY: &str = "profile_operators";
+
+/// Plan metadata key that makes a query read its sources in a fixed order
+/// (see `query.deterministic`).
+pub const DETERMINISTIC_METADATA_KEY: &str = "deterministic";
+
 /// Shared context for orchestrating streaming scans. Encapsulates the query plan,
 /// flow configuration, cached resources, and the passive memtable snapshot used
 /// by the streaming operators.
@@ -16,6 +26,7 @@ pub struct StreamingContext {
     passive_snapshot: Vec<Arc<tokio::sync::Mutex<MemTable>>>,
     batch_size: usize,
     effective_limit: Option<usize>,
+    deterministic: bool,
 }
 
 impl StreamingContext {
@@ -27,6 +38,17 @@ impl StreamingContext {
         let pool = BatchPool::new(batch_size)
             .map_err(|err| QueryExecutionError::ExprEval(err.to_string()))?;
         let metrics = FlowMetrics::new();
+        let deterministic = plan
+            .metadata
+            .get(DETERMINISTIC_METADATA_KEY)
+            .map(String::as_str)
+            == Some("true");
+        // The profiler samples on a timer, so it is never started in deterministic mode
+        if !deterministic
+            && plan.metadata.get(PROFILE_METADATA_KEY).map(String::as_str) == Some("true")
+        {
+            metrics.enable_profiling(OperatorProfiler::start(OperatorProfiler::DEFAULT_INTERVAL));
+        }
         let flow_ctx = Arc::new(FlowContext::new(
             batch_size,
             pool,
@@ -46,9 +68,31 @@ impl StreamingContext {
             passive_snapshot,
             batch_size,
             effective_limit,
+            deterministic,
         })
     }
 
+    /// Replaces the flow's unlimited memory budget with the query's budget.
+    pub fn with_memory_budget(mut self, budget: Arc<QueryMemoryBudget>) -> Self {
+        let flow_ctx = FlowContext::clone(&self.flow_ctx).with_memory_budget(budget);
+        self.flow_ctx = Arc::new(flow_ctx);
+        self
+    }
+
+    /// Records the scan's work into the query-wide statistics.
+    pub fn with_scan_stats(mut self, stats: Arc<QueryScanStats>) -> Self {
+        let flow_ctx = FlowContext::clone(&self.flow_ctx).with_scan_stats(stats);
+        self.flow_ctx = Arc::new(flow_ctx);
+        self
+    }
+
+    /// Shares the query's stop signal with the flow's readers.
+    pub fn with_cancellation(mut self, cancellation: Arc<QueryCancellation>) -> Self {
+        let flow_ctx = FlowContext::clone(&self.flow_ctx).with_cancellation(cancellation);
+        self.flow_ctx = Arc::new(flow_ctx);
+        self
+    }
+
     pub fn plan(&self) -> &QueryPlan {
         self.plan.as_ref()
     }
@@ -73,6 +117,11 @@ impl StreamingContext {
         self.effective_limit
     }
 
+    /// Whether sources must be drained one after the other in a fixed order.
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
     pub fn metrics(&self) -> Arc<FlowMetrics> {
         Arc::clone(self.flow_ctx.metrics())
     }
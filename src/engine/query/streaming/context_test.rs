@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::engine::core::memory::passive_buffer_set::PassiveBufferSet;
-use crate::engine::query::streaming::context::StreamingContext;
+use crate::engine::query::streaming::context::{PROFILE_METADATA_KEY, StreamingContext};
 use crate::test_helpers::factories::{
     CommandFactory, EventFactory, MemTableFactory, QueryPlanFactory, SchemaRegistryFactory,
 };
@@ -88,3 +88,34 @@ async fn new_handles_missing_passives_and_limit() {
     assert!(ctx.passive_refs().is_empty());
     assert_eq!(ctx.plan().event_type(), "stream_event");
 }
+
+#[tokio::test]
+async fn new_enables_profiler_from_plan_metadata() {
+    let registry_factory = SchemaRegistryFactory::new();
+    registry_factory
+        .define_with_fields("stream_event", &[("context_id", "string")])
+        .await
+        .expect("schema defined");
+
+    let command = CommandFactory::query()
+        .with_event_type("stream_event")
+        .create();
+    let mut plan = QueryPlanFactory::new()
+        .with_command(command)
+        .with_registry(registry_factory.registry())
+        .create()
+        .await;
+
+    let passive_buffers = Arc::new(PassiveBufferSet::new(4));
+
+    let ctx = StreamingContext::new(Arc::new(plan.clone()), &passive_buffers, 32)
+        .await
+        .expect("context initializes");
+    assert!(ctx.metrics().profiler().is_none());
+
+    plan.set_metadata(PROFILE_METADATA_KEY.to_string(), "true".to_string());
+    let ctx = StreamingContext::new(Arc::new(plan), &passive_buffers, 32)
+        .await
+        .expect("context initializes");
+    assert!(ctx.metrics().profiler().is_some());
+}
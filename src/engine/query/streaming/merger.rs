@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::engine::core::read::flow::operators::MemTableSource;
 use crate::engine::core::read::flow::shard_pipeline::{DEFAULT_MEMTABLE_COLUMNS, ShardFlowHandle};
 use crate::engine::core::read::flow::{
-    BatchReceiver, BatchSchema, FlowChannel, OrderedStreamMerger,
+    BatchReceiver, BatchSchema, FlowChannel, OperatorKind, OrderedStreamMerger,
 };
 use crate::engine::errors::QueryExecutionError;
 use tokio::task::JoinHandle;
@@ -75,10 +75,26 @@ impl ShardFlowMerger {
                 .map_err(QueryExecutionError::ExprEval)?;
                 tasks.push(merger_handle);
             }
+        } else if ctx.is_deterministic() {
+            // Memtable first, then segments in zone order, regardless of which
+            // producer is ready first
+            tasks.push(tokio::spawn(async move {
+                let metrics = Arc::clone(merged_tx.metrics());
+                let _scope = metrics.operator_scope(OperatorKind::Merge);
+                for mut receiver in receivers {
+                    while let Some(batch) = receiver.recv().await {
+                        if merged_tx.send(batch).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }));
         } else {
             for mut receiver in receivers {
                 let tx_clone = merged_tx.clone();
                 tasks.push(tokio::spawn(async move {
+                    let metrics = Arc::clone(tx_clone.metrics());
+                    let _scope = metrics.operator_scope(OperatorKind::Merge);
                     while let Some(batch) = receiver.recv().await {
                         if tx_clone.send(batch).await.is_err() {
                             break;
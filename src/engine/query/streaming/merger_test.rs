@@ -11,12 +11,19 @@ use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
 use crate::engine::core::read::flow::{BatchPool, BatchSchema, FlowChannel, FlowMetrics};
 use crate::engine::core::read::result::ColumnSpec;
 use crate::engine::errors::QueryExecutionError;
-use crate::engine::query::streaming::context::StreamingContext;
+use crate::engine::query::streaming::context::{DETERMINISTIC_METADATA_KEY, StreamingContext};
 use crate::engine::query::streaming::merger::ShardFlowMerger;
 use crate::engine::types::ScalarValue;
 use crate::test_helpers::factories::{CommandFactory, QueryPlanFactory, SchemaRegistryFactory};
 
 async fn build_context(command: Command) -> StreamingContext {
+    build_context_with_metadata(command, &[]).await
+}
+
+async fn build_context_with_metadata(
+    command: Command,
+    metadata: &[(&str, &str)],
+) -> StreamingContext {
     let registry_factory = SchemaRegistryFactory::new();
     registry_factory
         .define_with_fields(
@@ -31,11 +38,14 @@ async fn build_context(command: Command) -> StreamingContext {
         .await
         .expect("schema defined");
 
-    let plan = QueryPlanFactory::new()
+    let mut plan = QueryPlanFactory::new()
         .with_command(command)
         .with_registry(registry_factory.registry())
         .create()
         .await;
+    for (key, value) in metadata {
+        plan.set_metadata(key.to_string(), value.to_string());
+    }
 
     let passive_buffers = Arc::new(PassiveBufferSet::new(0));
     StreamingContext::new(Arc::new(plan), &passive_buffers, 8)
@@ -62,12 +72,22 @@ fn sample_schema(column_name: &str) -> Arc<BatchSchema> {
 async fn build_handle(
     schema: Arc<BatchSchema>,
     rows: Vec<Vec<serde_json::Value>>,
+) -> ShardFlowHandle {
+    build_delayed_handle(schema, rows, Duration::ZERO).await
+}
+
+/// A handle whose producer waits `delay` before sending its rows.
+async fn build_delayed_handle(
+    schema: Arc<BatchSchema>,
+    rows: Vec<Vec<serde_json::Value>>,
+    delay: Duration,
 ) -> ShardFlowHandle {
     let metrics = Arc::new(FlowMetrics::new());
     let (tx, rx) = FlowChannel::bounded(4, Arc::clone(&metrics));
     let schema_for_task = Arc::clone(&schema);
 
     let task = tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
         let pool = BatchPool::new(4).expect("pool");
         let mut builder = pool.acquire(schema_for_task);
         for row in rows {
@@ -178,6 +198,49 @@ async fn merge_concatenates_when_unordered() {
     assert_eq!(values, vec![2, 7]);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn merge_drains_sources_in_order_when_deterministic() {
+    let command = CommandFactory::query()
+        .with_event_type("stream_event")
+        .create();
+    let context =
+        build_context_with_metadata(command, &[(DETERMINISTIC_METADATA_KEY, "true")]).await;
+    assert!(context.is_deterministic());
+
+    let schema = sample_schema("value");
+    // The first source is the slowest; its rows must still come first
+    let handle_a = build_delayed_handle(
+        Arc::clone(&schema),
+        vec![vec![json!("ctx-a"), json!(9)]],
+        Duration::from_millis(50),
+    )
+    .await;
+    let handle_b = build_handle(Arc::clone(&schema), vec![vec![json!("ctx-b"), json!(3)]]).await;
+
+    let merged = ShardFlowMerger::merge(&context, vec![handle_a, handle_b])
+        .await
+        .expect("merge");
+
+    let value_idx = merged
+        .schema
+        .columns()
+        .iter()
+        .position(|col| col.name == "value")
+        .expect("value column");
+
+    let mut receiver = merged.receiver;
+    let mut values = Vec::new();
+    while let Some(batch) = timeout(Duration::from_secs(1), receiver.recv())
+        .await
+        .expect("timeout")
+    {
+        let column = batch.column(value_idx).expect("value data");
+        values.extend(column.into_iter().filter_map(|value| value.as_i64()));
+    }
+
+    assert_eq!(values, vec![9, 3]);
+}
+
 #[tokio::test]
 async fn merge_infers_schema_when_handles_absent() {
     let command = CommandFactory::query()
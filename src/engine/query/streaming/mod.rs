@@ -3,6 +3,7 @@ pub mod context;
 pub mod merger;
 pub mod scan;
 
+pub use context::{DETERMINISTIC_METADATA_KEY, PROFILE_METADATA_KEY};
 pub use scan::StreamingScan;
 
 #[cfg(test)]
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use crate::command::types::Command;
 use crate::engine::core::memory::passive_buffer_set::PassiveBufferSet;
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
+use crate::engine::core::read::flow::{QueryCancellation, QueryMemoryBudget, QueryScanStats};
 use crate::engine::core::{InflightSegments, MemTable, QueryPlan};
 use crate::engine::errors::QueryExecutionError;
 use crate::engine::schema::registry::SchemaRegistry;
@@ -56,6 +57,24 @@ impl<'a> StreamingScan<'a> {
         Ok(Self { memtable, context })
     }
 
+    /// Accounts the scan's operators against the query-wide memory budget.
+    pub fn with_memory_budget(mut self, budget: Arc<QueryMemoryBudget>) -> Self {
+        self.context = self.context.with_memory_budget(budget);
+        self
+    }
+
+    /// Records rows read and cache lookups into the query-wide statistics.
+    pub fn with_scan_stats(mut self, stats: Arc<QueryScanStats>) -> Self {
+        self.context = self.context.with_scan_stats(stats);
+        self
+    }
+
+    /// Stops the scan's readers when the query is cancelled.
+    pub fn with_cancellation(mut self, cancellation: Arc<QueryCancellation>) -> Self {
+        self.context = self.context.with_cancellation(cancellation);
+        self
+    }
+
     pub async fn execute(&self) -> Result<ShardFlowHandle, QueryExecutionError> {
         let builders = FlowBuilders::new(self.memtable);
         let mut handles = Vec::new();
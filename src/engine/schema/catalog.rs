@@ -0,0 +1,96 @@
+use crate::engine::schema::registry::MiniSchema;
+use crate::engine::schema::types::FieldType;
+use serde::{Deserialize, Serialize};
+
+/// Version of the exported catalog document; imports reject other versions.
+pub const CATALOG_VERSION: u32 = 1;
+
+/// Every schema of a registry as one document, used to copy definitions
+/// between environments with `EXPORT SCHEMAS` / `IMPORT SCHEMAS`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaCatalog {
+    pub version: u32,
+    /// Sorted by event type so equal registries export equal documents
+    pub schemas: Vec<CatalogEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub event_type: String,
+    /// Kept for new event types so their segments and WAL entries line up
+    /// across environments; existing event types keep their own uid
+    pub uid: String,
+    pub schema: MiniSchema,
+}
+
+/// What importing one catalog entry did to the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportAction {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+impl ImportAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportAction::Created => "created",
+            ImportAction::Updated => "updated",
+            ImportAction::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// Why replacing `existing` with `new` would misread data already stored
+/// under `existing`: dropped fields, changed field types and a moved default
+/// time field. New fields, appended enum variants, fields made nullable and
+/// index changes are compatible.
+pub fn compatibility_conflicts(existing: &MiniSchema, new: &MiniSchema) -> Vec<String> {
+    let mut conflicts = Vec::new();
+    for (name, old_ty) in &existing.fields {
+        match new.field_type(name) {
+            None => conflicts.push(format!("field '{}' would be removed", name)),
+            Some(new_ty) if !is_widening(old_ty, new_ty) => conflicts.push(format!(
+                "field '{}' would change type from {} to {}",
+                name,
+                describe(old_ty),
+                describe(new_ty)
+            )),
+            Some(_) => {}
+        }
+    }
+    if existing.time_field != new.time_field {
+        conflicts.push(format!(
+            "time field would change from {} to {}",
+            existing.time_field.as_deref().unwrap_or("timestamp"),
+            new.time_field.as_deref().unwrap_or("timestamp")
+        ));
+    }
+    conflicts
+}
+
+/// Whether values stored as `old` still read correctly as `new`.
+fn is_widening(old: &FieldType, new: &FieldType) -> bool {
+    match (old, new) {
+        (FieldType::Enum(old), FieldType::Enum(new)) => new.variants.starts_with(&old.variants),
+        (FieldType::Optional(old), FieldType::Optional(new)) => is_widening(old, new),
+        (old, FieldType::Optional(new)) => is_widening(old, new),
+        (old, new) => old == new,
+    }
+}
+
+fn describe(ty: &FieldType) -> String {
+    match ty {
+        FieldType::String => "string".to_string(),
+        FieldType::U64 => "u64".to_string(),
+        FieldType::I64 => "i64".to_string(),
+        FieldType::F64 => "f64".to_string(),
+        FieldType::Bool => "bool".to_string(),
+        FieldType::Timestamp => "datetime".to_string(),
+        FieldType::Date => "date".to_string(),
+        FieldType::Time => "time".to_string(),
+        FieldType::Uuid => "uuid".to_string(),
+        FieldType::Optional(inner) => format!("{} | null", describe(inner)),
+        FieldType::Enum(e) => format!("enum [{}]", e.variants.join(", ")),
+    }
+}
@@ -0,0 +1,188 @@
+use crate::command::types::FieldIndexKind;
+use crate::engine::schema::SchemaRegistry;
+use crate::engine::schema::catalog::{
+    CATALOG_VERSION, ImportAction, SchemaCatalog, compatibility_conflicts,
+};
+use crate::engine::schema::errors::SchemaError;
+use crate::test_helpers::factories::MiniSchemaFactory;
+use tempfile::tempdir;
+
+#[test]
+fn compatibility_allows_additive_changes_only() {
+    let existing = MiniSchemaFactory::empty()
+        .with("amount", "int")
+        .with("at", "datetime")
+        .with_enum("plan", &["free", "pro"])
+        .with_time_field("at")
+        .create();
+
+    let widened = MiniSchemaFactory::empty()
+        .with_optional("amount", "int")
+        .with("at", "datetime")
+        .with_enum("plan", &["free", "pro", "team"])
+        .with("note", "string")
+        .with_time_field("at")
+        .with_indexes("amount", &[FieldIndexKind::Xor])
+        .create();
+    assert!(compatibility_conflicts(&existing, &widened).is_empty());
+
+    let broken = MiniSchemaFactory::empty()
+        .with("amount", "string")
+        .with_enum("plan", &["pro", "free"])
+        .create();
+    assert_eq!(
+        compatibility_conflicts(&existing, &broken),
+        vec![
+            "field 'amount' would change type from i64 to string".to_string(),
+            "field 'at' would be removed".to_string(),
+            "field 'plan' would change type from enum [free, pro] to enum [pro, free]".to_string(),
+            "time field would change from at to timestamp".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn export_then_import_round_trips_and_is_idempotent() {
+    let source_dir = tempdir().unwrap();
+    let mut source = SchemaRegistry::new_with_path(source_dir.path().join("schemas.bin")).unwrap();
+    source
+        .define(
+            "order",
+            MiniSchemaFactory::empty()
+                .with("amount", "int")
+                .with("at", "datetime")
+                .with_enum("status", &["open", "paid"])
+                .with_time_field("at")
+                .with_indexes("amount", &[FieldIndexKind::Histogram])
+                .create(),
+        )
+        .unwrap();
+    source
+        .define("login", MiniSchemaFactory::new().create())
+        .unwrap();
+
+    let catalog = source.export_catalog();
+    assert_eq!(catalog.version, CATALOG_VERSION);
+    let names: Vec<&str> = catalog
+        .schemas
+        .iter()
+        .map(|e| e.event_type.as_str())
+        .collect();
+    assert_eq!(names, vec!["login", "order"]);
+
+    // Survives the JSON document used by the commands
+    let json = serde_json::to_string(&catalog).unwrap();
+    let parsed: SchemaCatalog = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, catalog);
+
+    let target_dir = tempdir().unwrap();
+    let target_path = target_dir.path().join("schemas.bin");
+    let mut target = SchemaRegistry::new_with_path(target_path.clone()).unwrap();
+    let actions = target.import_catalog(&parsed).await.unwrap();
+    assert_eq!(
+        actions,
+        vec![
+            ("login".to_string(), ImportAction::Created),
+            ("order".to_string(), ImportAction::Created),
+        ]
+    );
+    assert_eq!(target.export_catalog(), catalog);
+
+    let size = std::fs::metadata(&target_path).unwrap().len();
+    let again = target.import_catalog(&parsed).await.unwrap();
+    assert!(again.iter().all(|(_, a)| *a == ImportAction::Unchanged));
+    assert_eq!(std::fs::metadata(&target_path).unwrap().len(), size);
+
+    let reloaded = SchemaRegistry::new_with_path(target_path).unwrap();
+    assert_eq!(reloaded.export_catalog(), catalog);
+}
+
+#[tokio::test]
+async fn import_updates_in_place_and_rejects_conflicts_as_a_whole() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("schemas.bin");
+    let mut registry = SchemaRegistry::new_with_path(path.clone()).unwrap();
+    registry
+        .define(
+            "order",
+            MiniSchemaFactory::empty().with("amount", "int").create(),
+        )
+        .unwrap();
+    registry
+        .define(
+            "refund",
+            MiniSchemaFactory::empty().with("amount", "int").create(),
+        )
+        .unwrap();
+    let order_uid = registry.get_uid("order").unwrap();
+    let refund_uid = registry.get_uid("refund").unwrap();
+
+    let mut catalog = registry.export_catalog();
+    catalog.schemas[0].uid = "other-env-uid".to_string();
+    catalog.schemas[0].schema = MiniSchemaFactory::empty()
+        .with("amount", "int")
+        .with("currency", "string")
+        .create();
+    let mut fresh = catalog.schemas[1].clone();
+    fresh.event_type = "payout".to_string();
+    fresh.uid = "payout-uid".to_string();
+    catalog.schemas.push(fresh);
+
+    // A removed field on one entry blocks every other entry
+    let mut conflicting = catalog.clone();
+    conflicting.schemas[1].schema = MiniSchemaFactory::empty().with("total", "int").create();
+    conflicting.schemas[2].uid = refund_uid.clone();
+    let size = std::fs::metadata(&path).unwrap().len();
+    match registry.import_catalog(&conflicting).await {
+        Err(SchemaError::ImportConflicts(conflicts)) => assert_eq!(
+            conflicts,
+            vec![
+                "'refund': field 'amount' would be removed".to_string(),
+                format!("'payout': uid '{}' already belongs to 'refund'", refund_uid),
+            ]
+        ),
+        other => panic!("expected conflicts, got {:?}", other),
+    }
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), size);
+    assert!(registry.get("payout").is_none());
+    assert!(
+        !registry
+            .get("order")
+            .unwrap()
+            .fields
+            .contains_key("currency")
+    );
+
+    let actions = registry.import_catalog(&catalog).await.unwrap();
+    assert_eq!(
+        actions,
+        vec![
+            ("order".to_string(), ImportAction::Updated),
+            ("refund".to_string(), ImportAction::Unchanged),
+            ("payout".to_string(), ImportAction::Created),
+        ]
+    );
+    // Existing event types keep their uid so stored segments stay readable
+    assert_eq!(registry.get_uid("order"), Some(order_uid.clone()));
+    assert_eq!(registry.get_uid("payout"), Some("payout-uid".to_string()));
+
+    let reloaded = SchemaRegistry::new_with_path(path).unwrap();
+    assert!(
+        reloaded
+            .get("order")
+            .unwrap()
+            .fields
+            .contains_key("currency")
+    );
+    assert_eq!(
+        reloaded.get_event_type_by_uid(&order_uid),
+        Some("order".to_string())
+    );
+
+    let mut wrong_version = catalog;
+    wrong_version.version = CATALOG_VERSION + 1;
+    assert!(matches!(
+        registry.import_catalog(&wrong_version).await,
+        Err(SchemaError::ImportConflicts(_))
+    ));
+}
@@ -5,9 +5,21 @@ pub enum SchemaError {
     /// Tried to define a schema that already exists
     AlreadyDefined(String),
 
+    /// New event type matches an existing one once names are normalized: (new, existing)
+    EventTypeCollision(String, String),
+
+    /// Catalog import rejected as a whole; one message per conflicting entry
+    ImportConflicts(Vec<String>),
+
     /// Schema is empty
     EmptySchema,
 
+    /// Declared time field is missing or has a non-temporal type
+    InvalidTimeField(String),
+
+    /// Configured index names an unknown field or does not suit its type
+    InvalidIndexConfig(String),
+
     /// Failed to serialize schema to disk
     SerializationFailed(String),
 
@@ -45,7 +57,17 @@ impl fmt::Display for SchemaError {
             SchemaError::AlreadyDefined(name) => {
                 write!(f, "Schema for event_type '{}' already defined", name)
             }
+            SchemaError::EventTypeCollision(name, existing) => write!(
+                f,
+                "Event type '{}' matches existing event type '{}' under name normalization",
+                name, existing
+            ),
+            SchemaError::ImportConflicts(conflicts) => {
+                write!(f, "Import rejected: {}", conflicts.join("; "))
+            }
             SchemaError::EmptySchema => write!(f, "Schema cannot be empty"),
+            SchemaError::InvalidTimeField(e) => write!(f, "Invalid time field: {}", e),
+            SchemaError::InvalidIndexConfig(e) => write!(f, "Invalid index configuration: {}", e),
             SchemaError::SerializationFailed(e) => write!(f, "Serialization error: {}", e),
             SchemaError::IoWriteFailed(e) => write!(f, "I/O write error: {}", e),
             SchemaError::IoReadFailed(e) => write!(f, "I/O read error: {}", e),
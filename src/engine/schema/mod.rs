@@ -1,3 +1,4 @@
+pub mod catalog;
 pub mod errors;
 pub mod normalization;
 pub mod registry;
@@ -5,10 +6,12 @@ pub mod store;
 pub mod types;
 
 pub use errors::SchemaError;
-pub use normalization::PayloadTimeNormalizer;
+pub use normalization::{EventTypeNormalizer, PayloadTimeNormalizer};
 pub use registry::{MiniSchema, SchemaRegistry};
 pub use types::{EnumType, FieldType};
 
+#[cfg(test)]
+mod catalog_test;
 #[cfg(test)]
 mod normalization_test;
 #[cfg(test)]
@@ -1,51 +1,178 @@
+use crate::command::types::{Command, EventSequence};
 use crate::engine::schema::FieldType;
-use crate::engine::schema::registry::MiniSchema;
-use crate::shared::time::{TimeKind, TimeParser};
+use crate::engine::schema::registry::{MiniSchema, SchemaRegistry};
+use crate::engine::types::uuid;
+use crate::shared::config::CONFIG;
+use crate::shared::time::TimeParser;
 
 pub struct PayloadTimeNormalizer<'a> {
     schema: &'a MiniSchema,
+    ingest_time: Option<u64>,
 }
 
 impl<'a> PayloadTimeNormalizer<'a> {
     pub fn new(schema: &'a MiniSchema) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            ingest_time: None,
+        }
+    }
+
+    /// Epoch seconds stored in the schema's declared time fields when the payload lacks them.
+    pub fn with_ingest_time(mut self, ingest_time: u64) -> Self {
+        self.ingest_time = Some(ingest_time);
+        self
     }
 
-    /// Walk payload according to schema and normalize time-typed fields to epoch seconds (i64).
+    /// Walk payload according to schema and normalize time-typed fields: datetimes
+    /// to epoch seconds, dates to the epoch seconds of their midnight UTC and times
+    /// to milliseconds since midnight. UUIDs are lowercased to their canonical form.
     pub fn normalize(&self, payload: &mut serde_json::Value) -> Result<(), String> {
         let obj = payload
             .as_object_mut()
             .ok_or_else(|| "Payload must be a JSON object".to_string())?;
 
-        for (field, field_type) in &self.schema.fields {
-            match field_type {
-                FieldType::Timestamp => {
-                    if let Some(v) = obj.get_mut(field) {
-                        TimeParser::normalize_json_value(v, TimeKind::DateTime)?;
-                    }
+        if let Some(ingest_time) = self.ingest_time {
+            for field in self.schema.declared_time_fields() {
+                let missing = obj.get(field).is_none_or(serde_json::Value::is_null);
+                if missing {
+                    obj.insert(field.clone(), serde_json::Value::from(ingest_time));
                 }
-                FieldType::Date => {
-                    if let Some(v) = obj.get_mut(field) {
-                        TimeParser::normalize_json_value(v, TimeKind::Date)?;
-                    }
+            }
+        }
+
+        for (field, field_type) in &self.schema.fields {
+            if field_type.is_uuid() {
+                if let Some(v) = obj.get_mut(field)
+                    && let Some(bytes) = v.as_str().and_then(uuid::parse)
+                {
+                    *v = serde_json::Value::String(uuid::format(&bytes));
                 }
-                FieldType::Optional(inner) => {
-                    if matches!(**inner, FieldType::Timestamp | FieldType::Date) {
-                        if let Some(v) = obj.get_mut(field) {
-                            let kind = if matches!(**inner, FieldType::Timestamp) {
-                                TimeKind::DateTime
-                            } else {
-                                TimeKind::Date
-                            };
-                            if !v.is_null() {
-                                TimeParser::normalize_json_value(v, kind)?;
-                            }
-                        }
-                    }
+                continue;
+            }
+            // Dates keep their day only and times their time of day
+            let Some(kind) = field_type.time_kind() else {
+                continue;
+            };
+            if let Some(v) = obj.get_mut(field) {
+                let optional = matches!(field_type, FieldType::Optional(_));
+                if !(optional && v.is_null()) {
+                    TimeParser::normalize_json_value(v, kind)?;
                 }
-                _ => {}
             }
         }
         Ok(())
     }
 }
+
+/// How event type names are matched against defined schemas. The default
+/// matches exactly; `[schema]` can enable case folding and separator removal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventTypeNormalizer {
+    pub fold_case: bool,
+    pub ignore_separators: bool,
+}
+
+impl EventTypeNormalizer {
+    pub fn from_config() -> Self {
+        Self {
+            fold_case: CONFIG.schema.fold_event_type_case,
+            ignore_separators: CONFIG.schema.ignore_event_type_separators,
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        !self.fold_case && !self.ignore_separators
+    }
+
+    /// The name two event types share when they match, e.g. `usersignup`
+    /// for `User_Signup` with both options on.
+    pub fn key(&self, name: &str) -> String {
+        let mut key = String::with_capacity(name.len());
+        for c in name
+            .chars()
+            .filter(|c| !(self.ignore_separators && is_separator(*c)))
+        {
+            if self.fold_case {
+                key.extend(c.to_lowercase());
+            } else {
+                key.push(c);
+            }
+        }
+        key
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '_' | '-' | '.') || c.is_whitespace()
+}
+
+/// Rewrites the event types `cmd` names to the spelling they were defined
+/// with. `None` when matching is exact or nothing needs rewriting, so the
+/// caller keeps the original command. `DEFINE` is left alone: the registry
+/// rejects names that collide instead.
+pub fn resolve_event_types(cmd: &Command, registry: &SchemaRegistry) -> Option<Command> {
+    if registry.event_type_normalizer().is_exact() {
+        return None;
+    }
+    let mut resolved = cmd.clone();
+    let mut changed = false;
+    visit_event_types(&mut resolved, &mut |name| {
+        if let Some(defined) = registry.resolve_event_type(name)
+            && defined != name
+        {
+            *name = defined.to_string();
+            changed = true;
+        }
+    });
+    changed.then_some(resolved)
+}
+
+fn visit_event_types(cmd: &mut Command, visit: &mut impl FnMut(&mut String)) {
+    match cmd {
+        Command::Store { event_type, .. }
+        | Command::InspectZone { event_type, .. }
+        | Command::ShowIndexes { event_type, .. }
+        | Command::Replay {
+            event_type: Some(event_type),
+            ..
+        }
+        | Command::InspectWal {
+            event_type: Some(event_type),
+            ..
+        } => visit(event_type),
+        Command::Query {
+            event_type,
+            event_sequence,
+            ..
+        } => {
+            visit(event_type);
+            visit_sequence(event_sequence, visit);
+        }
+        Command::Compare { queries } | Command::Union { queries, .. } => {
+            for query in queries {
+                visit(&mut query.event_type);
+                visit_sequence(&mut query.event_sequence, visit);
+            }
+        }
+        Command::Explain { query, .. } => visit_event_types(query, visit),
+        Command::RememberQuery { spec } => visit_event_types(&mut spec.query, visit),
+        Command::Batch(commands) => {
+            for command in commands {
+                visit_event_types(command, visit);
+            }
+        }
+        Command::GrantPermission { event_types, .. }
+        | Command::RevokePermission { event_types, .. } => event_types.iter_mut().for_each(visit),
+        _ => {}
+    }
+}
+
+fn visit_sequence(sequence: &mut Option<EventSequence>, visit: &mut impl FnMut(&mut String)) {
+    if let Some(sequence) = sequence {
+        visit(&mut sequence.head.event);
+        for (_, target) in &mut sequence.links {
+            visit(&mut target.event);
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use crate::engine::schema::PayloadTimeNormalizer;
+use crate::command::parser::command::parse_command;
+use crate::engine::schema::normalization::resolve_event_types;
+use crate::engine::schema::{EventTypeNormalizer, PayloadTimeNormalizer, SchemaRegistry};
 use crate::test_helpers::factories::MiniSchemaFactory;
 use chrono::{TimeZone, Utc};
+use tempfile::tempdir;
 
 #[test]
 fn normalizes_timestamp_and_date_fields_from_strings() {
@@ -39,6 +42,39 @@ fn normalizes_timestamp_and_date_fields_from_strings() {
     assert_eq!(payload["extra"], serde_json::json!(1));
 }
 
+#[test]
+fn dates_drop_the_time_of_day_and_times_the_date() {
+    let schema = MiniSchemaFactory::empty()
+        .with("d", "date")
+        .with("t", "time")
+        .with_optional("opt_t", "time")
+        .create();
+
+    let mut payload = serde_json::json!({
+        "d": "2025-09-07T12:34:56Z",
+        "t": "12:34:56",
+        "opt_t": null,
+    });
+    PayloadTimeNormalizer::new(&schema)
+        .normalize(&mut payload)
+        .expect("normalize should succeed");
+
+    let expected_d = Utc
+        .with_ymd_and_hms(2025, 9, 7, 0, 0, 0)
+        .single()
+        .unwrap()
+        .timestamp();
+    assert_eq!(payload["d"], serde_json::json!(expected_d));
+    assert_eq!(payload["t"], serde_json::json!(45_296_000));
+    assert_eq!(payload["opt_t"], serde_json::Value::Null);
+
+    let mut payload = serde_json::json!({ "d": "2025-09-07", "t": "2025-09-07" });
+    let err = PayloadTimeNormalizer::new(&schema)
+        .normalize(&mut payload)
+        .unwrap_err();
+    assert!(err.contains("Invalid time string"), "{err}");
+}
+
 #[test]
 fn normalizes_numeric_units_and_numeric_strings() {
     let schema = MiniSchemaFactory::empty()
@@ -137,3 +173,119 @@ fn payload_must_be_object() {
         .expect_err("should return error when payload not object");
     assert!(err.contains("Payload must be a JSON object"));
 }
+
+#[test]
+fn fills_missing_declared_time_field_with_ingest_time() {
+    let schema = MiniSchemaFactory::empty()
+        .with("event_time", "datetime")
+        .with("name", "string")
+        .with_time_field("event_time")
+        .create();
+
+    let mut missing = serde_json::json!({ "name": "alice" });
+    PayloadTimeNormalizer::new(&schema)
+        .with_ingest_time(1_700_000_000)
+        .normalize(&mut missing)
+        .expect("normalize should succeed");
+    assert_eq!(missing["event_time"], serde_json::json!(1_700_000_000u64));
+
+    let mut present = serde_json::json!({ "name": "bob", "event_time": "2025-09-07T00:00:00Z" });
+    PayloadTimeNormalizer::new(&schema)
+        .with_ingest_time(1_700_000_000)
+        .normalize(&mut present)
+        .expect("normalize should succeed");
+    let expected = Utc
+        .with_ymd_and_hms(2025, 9, 7, 0, 0, 0)
+        .single()
+        .unwrap()
+        .timestamp();
+    assert_eq!(present["event_time"], serde_json::json!(expected));
+}
+
+#[test]
+fn fills_each_missing_declared_time_field_with_ingest_time() {
+    let schema = MiniSchemaFactory::empty()
+        .with("valid_at", "datetime")
+        .with("recorded_at", "u64")
+        .with_time_field("valid_at")
+        .with_temporal_field("recorded_at")
+        .create();
+
+    let mut payload = serde_json::json!({ "valid_at": 1_600_000_000u64 });
+    PayloadTimeNormalizer::new(&schema)
+        .with_ingest_time(1_700_000_000)
+        .normalize(&mut payload)
+        .expect("normalize should succeed");
+    assert_eq!(payload["valid_at"], serde_json::json!(1_600_000_000i64));
+    assert_eq!(payload["recorded_at"], serde_json::json!(1_700_000_000u64));
+}
+
+#[test]
+fn event_type_keys_follow_enabled_options() {
+    let exact = EventTypeNormalizer::default();
+    assert!(exact.is_exact());
+    assert_eq!(exact.key("User_Signup"), "User_Signup");
+
+    let case = EventTypeNormalizer {
+        fold_case: true,
+        ignore_separators: false,
+    };
+    assert_eq!(case.key("User_Signup"), "user_signup");
+
+    let separators = EventTypeNormalizer {
+        fold_case: false,
+        ignore_separators: true,
+    };
+    assert_eq!(separators.key("User_Sign-up. v2"), "UserSignupv2");
+
+    let both = EventTypeNormalizer {
+        fold_case: true,
+        ignore_separators: true,
+    };
+    for name in ["UserSignup", "usersignup", "user_signup", "User-Signup"] {
+        assert_eq!(both.key(name), "usersignup");
+    }
+}
+
+#[test]
+fn resolve_event_types_rewrites_every_named_event_type() {
+    let dir = tempdir().unwrap();
+    let mut registry = SchemaRegistry::new_with_path(dir.path().join("schemas.bin")).unwrap();
+    for name in ["user_signup", "order_created"] {
+        registry
+            .define(name, MiniSchemaFactory::new().create())
+            .unwrap();
+    }
+
+    let query =
+        parse_command("QUERY UserSignup FOLLOWED BY OrderCreated LINKED BY user_id").unwrap();
+    assert_eq!(
+        resolve_event_types(&query, &registry),
+        None,
+        "exact by default"
+    );
+
+    let registry = registry.with_event_type_normalizer(EventTypeNormalizer {
+        fold_case: true,
+        ignore_separators: true,
+    });
+    assert_eq!(
+        resolve_event_types(&query, &registry),
+        Some(
+            parse_command("QUERY user_signup FOLLOWED BY order_created LINKED BY user_id").unwrap()
+        )
+    );
+    assert_eq!(
+        resolve_event_types(
+            &parse_command("STORE UserSignup FOR ctx PAYLOAD {\"id\": 1}").unwrap(),
+            &registry
+        ),
+        Some(parse_command("STORE user_signup FOR ctx PAYLOAD {\"id\": 1}").unwrap())
+    );
+
+    // Defined spellings and unknown names are left as they are
+    for unchanged in ["QUERY user_signup", "QUERY refund", "PING"] {
+        let cmd = parse_command(unchanged).unwrap();
+        assert_eq!(resolve_event_types(&cmd, &registry), None, "{}", unchanged);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::command::types::FieldIndexKind;
+use crate::engine::schema::EventTypeNormalizer;
 use crate::engine::schema::errors::SchemaError;
 use crate::engine::schema::registry::SchemaRegistry;
 use crate::test_helpers::factories::MiniSchemaFactory;
@@ -328,3 +330,262 @@ fn define_with_optional_field_persists_and_reloads() {
     let loaded2 = registry2.get("user_profile").unwrap();
     assert_eq!(loaded2, &schema);
 }
+
+#[test]
+fn define_with_time_field_persists_and_reloads() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("schemas.bin");
+
+    let mut registry = SchemaRegistry::new_with_path(path.clone()).unwrap();
+    let schema = MiniSchemaFactory::empty()
+        .with("ts", "u64")
+        .with("amount", "int")
+        .with_time_field("ts")
+        .create();
+
+    registry
+        .define("payment", schema.clone())
+        .expect("define schema should succeed");
+    assert_eq!(registry.time_field("payment"), Some("ts"));
+    assert!(registry.get("payment").unwrap().is_temporal_field("ts"));
+    assert!(!registry.get("payment").unwrap().is_temporal_field("amount"));
+
+    let registry2 = SchemaRegistry::new_with_path(path).unwrap();
+    assert_eq!(registry2.get("payment").unwrap(), &schema);
+    assert_eq!(registry2.time_field("payment"), Some("ts"));
+}
+
+#[test]
+fn define_rejects_invalid_time_field() {
+    let dir = tempdir().unwrap();
+    let mut registry = SchemaRegistry::new_with_path(dir.path().join("schemas.bin")).unwrap();
+
+    let non_temporal = MiniSchemaFactory::empty()
+        .with("name", "string")
+        .with_time_field("name")
+        .create();
+    assert!(matches!(
+        registry.define("named", non_temporal),
+        Err(SchemaError::InvalidTimeField(_))
+    ));
+
+    let missing = MiniSchemaFactory::empty()
+        .with("name", "string")
+        .with_time_field("event_time")
+        .create();
+    assert!(matches!(
+        registry.define("named", missing),
+        Err(SchemaError::InvalidTimeField(_))
+    ));
+    assert!(registry.get("named").is_none());
+}
+
+#[test]
+fn define_with_multiple_time_fields_persists_and_reloads() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("schemas.bin");
+
+    let mut registry = SchemaRegistry::new_with_path(path.clone()).unwrap();
+    let schema = MiniSchemaFactory::empty()
+        .with("valid_at", "datetime")
+        .with("recorded_at", "u64")
+        .with("amount", "int")
+        .with_time_field("valid_at")
+        .with_temporal_field("recorded_at")
+        .create();
+
+    registry
+        .define("shipment", schema.clone())
+        .expect("define schema should succeed");
+    let defined = registry.get("shipment").unwrap();
+    assert!(defined.is_temporal_field("recorded_at"));
+    assert!(!defined.is_temporal_field("amount"));
+    assert_eq!(registry.time_field("shipment"), Some("valid_at"));
+
+    let registry2 = SchemaRegistry::new_with_path(path).unwrap();
+    assert_eq!(registry2.get("shipment").unwrap(), &schema);
+}
+
+#[test]
+fn define_rejects_invalid_additional_time_field() {
+    let dir = tempdir().unwrap();
+    let mut registry = SchemaRegistry::new_with_path(dir.path().join("schemas.bin")).unwrap();
+
+    let non_temporal = MiniSchemaFactory::empty()
+        .with("valid_at", "datetime")
+        .with("note", "string")
+        .with_time_field("valid_at")
+        .with_temporal_field("note")
+        .create();
+    assert!(matches!(
+        registry.define("shipment", non_temporal),
+        Err(SchemaError::InvalidTimeField(_))
+    ));
+
+    let duplicated = MiniSchemaFactory::empty()
+        .with("valid_at", "datetime")
+        .with_time_field("valid_at")
+        .with_temporal_field("valid_at")
+        .create();
+    assert!(matches!(
+        registry.define("shipment", duplicated),
+        Err(SchemaError::InvalidTimeField(_))
+    ));
+
+    let without_default = MiniSchemaFactory::empty()
+        .with("recorded_at", "datetime")
+        .with_temporal_field("recorded_at")
+        .create();
+    assert!(matches!(
+        registry.define("shipment", without_default),
+        Err(SchemaError::InvalidTimeField(_))
+    ));
+    assert!(registry.get("shipment").is_none());
+}
+
+#[test]
+fn define_with_indexes_persists_and_reloads() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("schemas.bin");
+
+    let mut registry = SchemaRegistry::new_with_path(path.clone()).unwrap();
+    let schema = MiniSchemaFactory::empty()
+        .with("amount", "int")
+        .with("note", "string")
+        .with_enum("plan", &["free", "pro"])
+        .with_indexes("amount", &[FieldIndexKind::Surf])
+        .with_indexes("note", &[])
+        .create();
+
+    registry
+        .define("order", schema.clone())
+        .expect("define schema should succeed");
+    let defined = registry.get("order").unwrap();
+    assert!(defined.builds_index("amount", FieldIndexKind::Surf));
+    assert!(!defined.builds_index("amount", FieldIndexKind::Xor));
+    assert!(!defined.builds_index("note", FieldIndexKind::Histogram));
+    assert!(defined.builds_index("plan", FieldIndexKind::Enum));
+
+    let registry2 = SchemaRegistry::new_with_path(path).unwrap();
+    assert_eq!(registry2.get("order").unwrap(), &schema);
+}
+
+#[test]
+fn define_rejects_invalid_indexes() {
+    let dir = tempdir().unwrap();
+    let mut registry = SchemaRegistry::new_with_path(dir.path().join("schemas.bin")).unwrap();
+
+    let invalid = [
+        MiniSchemaFactory::empty()
+            .with("amount", "int")
+            .with_indexes("missing", &[FieldIndexKind::Surf]),
+        MiniSchemaFactory::empty()
+            .with("amount", "int")
+            .with_indexes("amount", &[FieldIndexKind::Enum]),
+        MiniSchemaFactory::empty()
+            .with("note", "string")
+            .with_indexes("note", &[FieldIndexKind::Temporal]),
+        MiniSchemaFactory::empty()
+            .with("valid_at", "datetime")
+            .with_time_field("valid_at")
+            .with_indexes("valid_at", &[FieldIndexKind::Histogram]),
+    ];
+    for schema in invalid {
+        assert!(matches!(
+            registry.define("order", schema.create()),
+            Err(SchemaError::InvalidIndexConfig(_))
+        ));
+    }
+    assert!(registry.get("order").is_none());
+}
+
+#[test]
+fn event_type_names_match_exactly_by_default() {
+    let dir = tempdir().unwrap();
+    let mut registry = SchemaRegistry::new_with_path(dir.path().join("schemas.bin")).unwrap();
+    registry
+        .define("user_signup", MiniSchemaFactory::new().create())
+        .unwrap();
+
+    assert!(registry.event_type_normalizer().is_exact());
+    assert_eq!(
+        registry.resolve_event_type("user_signup"),
+        Some("user_signup")
+    );
+    assert_eq!(registry.resolve_event_type("UserSignup"), None);
+    registry
+        .define("UserSignup", MiniSchemaFactory::new().create())
+        .unwrap();
+    assert_eq!(
+        registry.resolve_event_type("UserSignup"),
+        Some("UserSignup")
+    );
+}
+
+#[test]
+fn normalized_event_type_names_resolve_and_reject_collisions() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("schemas.bin");
+    let normalizer = EventTypeNormalizer {
+        fold_case: true,
+        ignore_separators: true,
+    };
+    let mut registry = SchemaRegistry::new_with_path(path.clone())
+        .unwrap()
+        .with_event_type_normalizer(normalizer);
+    registry
+        .define("user_signup", MiniSchemaFactory::new().create())
+        .unwrap();
+
+    for name in ["user_signup", "UserSignup", "usersignup", "User-Signup"] {
+        assert_eq!(registry.resolve_event_type(name), Some("user_signup"));
+    }
+    assert_eq!(registry.resolve_event_type("user_login"), None);
+
+    let result = registry.define("UserSignup", MiniSchemaFactory::new().create());
+    assert!(
+        matches!(result, Err(SchemaError::EventTypeCollision(ref new, ref existing))
+            if new == "UserSignup" && existing == "user_signup"),
+        "got: {:?}",
+        result
+    );
+    assert!(!registry.has_schema("UserSignup"));
+
+    // Reloaded schemas are matched too
+    let reloaded = SchemaRegistry::new_with_path(path)
+        .unwrap()
+        .with_event_type_normalizer(normalizer);
+    assert_eq!(
+        reloaded.resolve_event_type("USERSIGNUP"),
+        Some("user_signup")
+    );
+}
+
+#[test]
+fn event_types_defined_before_normalization_only_resolve_exactly_when_ambiguous() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("schemas.bin");
+    let mut registry = SchemaRegistry::new_with_path(path.clone()).unwrap();
+    registry
+        .define("UserSignup", MiniSchemaFactory::new().create())
+        .unwrap();
+    registry
+        .define("usersignup", MiniSchemaFactory::new().create())
+        .unwrap();
+
+    let registry = SchemaRegistry::new_with_path(path)
+        .unwrap()
+        .with_event_type_normalizer(EventTypeNormalizer {
+            fold_case: true,
+            ignore_separators: false,
+        });
+    assert_eq!(
+        registry.resolve_event_type("UserSignup"),
+        Some("UserSignup")
+    );
+    assert_eq!(
+        registry.resolve_event_type("usersignup"),
+        Some("usersignup")
+    );
+    assert_eq!(registry.resolve_event_type("USERSIGNUP"), None);
+}
@@ -1,8 +1,13 @@
-use crate::command::types::{FieldSpec, MiniSchema as CommandMiniSchema};
+use crate::command::types::{FieldIndexKind, FieldSpec, MiniSchema as CommandMiniSchema};
+use crate::engine::schema::catalog::{
+    CATALOG_VERSION, CatalogEntry, ImportAction, SchemaCatalog, compatibility_conflicts,
+};
 use crate::engine::schema::errors::SchemaError;
+use crate::engine::schema::normalization::EventTypeNormalizer;
 use crate::engine::schema::store::SchemaStore;
 use crate::engine::schema::types::{EnumType, FieldType};
 use crate::shared::config::CONFIG;
+use indexmap::IndexMap;
 use rand::{Rng, distributions::Alphanumeric};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -10,7 +15,16 @@ use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MiniSchema {
-    pub fields: HashMap<String, FieldType>,
+    /// Fields in declaration order; query results list payload columns this way
+    pub fields: IndexMap<String, FieldType>,
+    /// Payload field holding the event time; `None` means the core `timestamp`
+    pub time_field: Option<String>,
+    /// Further declared time dimensions (e.g. ingest time next to valid time);
+    /// indexed like `time_field` but only used when a query names them
+    pub temporal_fields: Vec<String>,
+    /// Index artifacts chosen per field; fields not listed get the defaults for their type.
+    /// Read only at flush, so changes apply to segments flushed afterwards.
+    pub indexes: IndexMap<String, Vec<FieldIndexKind>>,
 }
 
 impl MiniSchema {
@@ -29,6 +43,110 @@ impl MiniSchema {
     pub fn is_enum_field(&self, name: &str) -> bool {
         self.field_type(name).map_or(false, FieldType::is_enum)
     }
+
+    /// Time fields declared with `USING`, the default one first.
+    pub fn declared_time_fields(&self) -> impl Iterator<Item = &String> {
+        self.time_field.iter().chain(self.temporal_fields.iter())
+    }
+
+    /// Whether `name` gets temporal indexes: datetime/date fields and the declared time fields.
+    pub fn is_temporal_field(&self, name: &str) -> bool {
+        if self.declared_time_fields().any(|f| f == name) {
+            return true;
+        }
+        match self.field_type(name) {
+            Some(FieldType::Timestamp | FieldType::Date) => true,
+            Some(FieldType::Optional(inner)) => {
+                matches!(**inner, FieldType::Timestamp | FieldType::Date)
+            }
+            _ => false,
+        }
+    }
+
+    /// Index kinds configured for `name`, or `None` when it uses the defaults.
+    pub fn configured_indexes(&self, name: &str) -> Option<&[FieldIndexKind]> {
+        self.indexes.get(name).map(Vec::as_slice)
+    }
+
+    /// Whether a flush should build `kind` for `name`, given that the type supports it.
+    pub fn builds_index(&self, name: &str, kind: FieldIndexKind) -> bool {
+        self.configured_indexes(name)
+            .is_none_or(|kinds| kinds.contains(&kind))
+    }
+
+    /// Checks that configured indexes name schema fields and suit their types.
+    fn validate_indexes(&self) -> Result<(), SchemaError> {
+        for (name, kinds) in &self.indexes {
+            let Some(ty) = self.field_type(name) else {
+                return Err(SchemaError::InvalidIndexConfig(format!(
+                    "'{}' is not a field of the schema",
+                    name
+                )));
+            };
+            for kind in kinds {
+                let supported = match kind {
+                    FieldIndexKind::Enum => ty.is_enum(),
+                    FieldIndexKind::Temporal => self.is_temporal_field(name),
+                    FieldIndexKind::Surf | FieldIndexKind::Xor | FieldIndexKind::Histogram => true,
+                };
+                if !supported {
+                    return Err(SchemaError::InvalidIndexConfig(format!(
+                        "'{}' index does not apply to field '{}'",
+                        kind.as_str(),
+                        name
+                    )));
+                }
+            }
+            // Time-range pruning of SINCE and time buckets relies on these
+            if self.declared_time_fields().any(|f| f == name)
+                && !kinds.contains(&FieldIndexKind::Temporal)
+            {
+                return Err(SchemaError::InvalidIndexConfig(format!(
+                    "time field '{}' must keep its 'temporal' index",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that every declared time field exists and can hold epoch seconds.
+    fn validate_time_fields(&self) -> Result<(), SchemaError> {
+        let mut seen = HashSet::new();
+        for name in self.declared_time_fields() {
+            if !seen.insert(name) {
+                return Err(SchemaError::InvalidTimeField(format!(
+                    "'{}' is declared more than once",
+                    name
+                )));
+            }
+            let ty = match self.field_type(name) {
+                Some(FieldType::Optional(inner)) => Some(&**inner),
+                other => other,
+            };
+            match ty {
+                Some(FieldType::Timestamp | FieldType::Date | FieldType::U64 | FieldType::I64) => {}
+                Some(_) => {
+                    return Err(SchemaError::InvalidTimeField(format!(
+                        "'{}' must be a datetime, date or integer field",
+                        name
+                    )));
+                }
+                None => {
+                    return Err(SchemaError::InvalidTimeField(format!(
+                        "'{}' is not a field of the schema",
+                        name
+                    )));
+                }
+            }
+        }
+        if self.time_field.is_none() && !self.temporal_fields.is_empty() {
+            return Err(SchemaError::InvalidTimeField(
+                "additional time fields require a default time field".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +162,9 @@ pub struct SchemaRegistry {
     uid_map: HashMap<String, String>,
     reverse_uid_map: HashMap<String, String>,
     store: SchemaStore,
+    normalizer: EventTypeNormalizer,
+    /// Defined event types by normalized name; empty when matching is exact
+    normalized: HashMap<String, Vec<String>>,
 }
 
 impl SchemaRegistry {
@@ -62,18 +183,84 @@ impl SchemaRegistry {
             uid_map: HashMap::new(),
             reverse_uid_map: HashMap::new(),
             store,
+            normalizer: EventTypeNormalizer::from_config(),
+            normalized: HashMap::new(),
         };
         registry.load_all()?;
         Ok(registry)
     }
 
+    /// Replaces how event type names are matched and re-indexes the defined ones.
+    pub fn with_event_type_normalizer(mut self, normalizer: EventTypeNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self.normalized.clear();
+        let names: Vec<String> = self.schemas.keys().cloned().collect();
+        for name in names {
+            self.index_normalized(&name);
+        }
+        self
+    }
+
+    pub fn event_type_normalizer(&self) -> EventTypeNormalizer {
+        self.normalizer
+    }
+
+    /// The defined event type `name` refers to: itself when defined, otherwise
+    /// the one schema it matches under the configured normalization. Names that
+    /// match several schemas only resolve exactly.
+    pub fn resolve_event_type<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        if self.schemas.contains_key(name) {
+            return Some(name);
+        }
+        if self.normalizer.is_exact() {
+            return None;
+        }
+        match self.normalized.get(&self.normalizer.key(name))?.as_slice() {
+            [defined] => Some(defined.as_str()),
+            _ => None,
+        }
+    }
+
+    fn check_collision(&self, event_type: &str) -> Result<(), SchemaError> {
+        if self.normalizer.is_exact() {
+            return Ok(());
+        }
+        match self
+            .normalized
+            .get(&self.normalizer.key(event_type))
+            .and_then(|names| names.first())
+        {
+            Some(existing) => Err(SchemaError::EventTypeCollision(
+                event_type.to_string(),
+                existing.clone(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn index_normalized(&mut self, event_type: &str) {
+        if self.normalizer.is_exact() {
+            return;
+        }
+        let names = self
+            .normalized
+            .entry(self.normalizer.key(event_type))
+            .or_default();
+        if !names.iter().any(|n| n == event_type) {
+            names.push(event_type.to_string());
+        }
+    }
+
     pub fn define(&mut self, event_type: &str, schema: MiniSchema) -> Result<(), SchemaError> {
         if self.schemas.contains_key(event_type) {
             return Err(SchemaError::AlreadyDefined(event_type.to_string()));
         }
+        self.check_collision(event_type)?;
         if schema.fields.is_empty() {
             return Err(SchemaError::EmptySchema);
         }
+        schema.validate_time_fields()?;
+        schema.validate_indexes()?;
 
         let uid: String = rand::thread_rng()
             .sample_iter(&Alphanumeric)
@@ -101,9 +288,12 @@ impl SchemaRegistry {
         if self.schemas.contains_key(event_type) {
             return Err(SchemaError::AlreadyDefined(event_type.to_string()));
         }
+        self.check_collision(event_type)?;
         if schema.fields.is_empty() {
             return Err(SchemaError::EmptySchema);
         }
+        schema.validate_time_fields()?;
+        schema.validate_indexes()?;
 
         let uid: String = rand::thread_rng()
             .sample_iter(&Alphanumeric)
@@ -128,6 +318,147 @@ impl SchemaRegistry {
         Ok(())
     }
 
+    /// All schemas as one catalog document, sorted by event type.
+    pub fn export_catalog(&self) -> SchemaCatalog {
+        let mut schemas: Vec<CatalogEntry> = self
+            .schemas
+            .iter()
+            .map(|(event_type, schema)| CatalogEntry {
+                event_type: event_type.clone(),
+                uid: self.uid_map.get(event_type).cloned().unwrap_or_default(),
+                schema: schema.clone(),
+            })
+            .collect();
+        schemas.sort_by(|a, b| a.event_type.cmp(&b.event_type));
+        SchemaCatalog {
+            version: CATALOG_VERSION,
+            schemas,
+        }
+    }
+
+    /// Creates and updates schemas so every catalog entry is defined as given.
+    /// Event types missing from the catalog are left alone. All entries are
+    /// checked first and every conflict is reported together; nothing is
+    /// written unless the whole catalog applies. Entries matching the current
+    /// schema are unchanged, so re-running an import writes nothing.
+    pub async fn import_catalog(
+        &mut self,
+        catalog: &SchemaCatalog,
+    ) -> Result<Vec<(String, ImportAction)>, SchemaError> {
+        let mut conflicts = Vec::new();
+        if catalog.version != CATALOG_VERSION {
+            return Err(SchemaError::ImportConflicts(vec![format!(
+                "unsupported catalog version {}, expected {}",
+                catalog.version, CATALOG_VERSION
+            )]));
+        }
+
+        let mut seen_types = HashSet::new();
+        let mut seen_uids = HashSet::new();
+        let mut seen_keys = HashMap::new();
+        let mut actions = Vec::with_capacity(catalog.schemas.len());
+        let mut records = Vec::new();
+        for entry in &catalog.schemas {
+            let event_type = entry.event_type.as_str();
+            if !seen_types.insert(event_type) {
+                conflicts.push(format!("'{}': listed more than once", event_type));
+                continue;
+            }
+            if entry.schema.fields.is_empty() {
+                conflicts.push(format!("'{}': {}", event_type, SchemaError::EmptySchema));
+                continue;
+            }
+            if let Err(e) = entry
+                .schema
+                .validate_time_fields()
+                .and_then(|_| entry.schema.validate_indexes())
+            {
+                conflicts.push(format!("'{}': {}", event_type, e));
+                continue;
+            }
+
+            let action = match (self.schemas.get(event_type), self.uid_map.get(event_type)) {
+                (Some(existing), Some(uid)) => {
+                    if *existing == entry.schema {
+                        ImportAction::Unchanged
+                    } else {
+                        let found = compatibility_conflicts(existing, &entry.schema);
+                        if !found.is_empty() {
+                            conflicts.extend(
+                                found
+                                    .into_iter()
+                                    .map(|c| format!("'{}': {}", event_type, c)),
+                            );
+                            continue;
+                        }
+                        records.push(SchemaRecord {
+                            uid: uid.clone(),
+                            event_type: event_type.to_string(),
+                            schema: entry.schema.clone(),
+                        });
+                        ImportAction::Updated
+                    }
+                }
+                _ => {
+                    if entry.uid.is_empty() || !seen_uids.insert(entry.uid.as_str()) {
+                        conflicts.push(format!("'{}': missing or duplicate uid", event_type));
+                        continue;
+                    }
+                    if let Some(owner) = self.reverse_uid_map.get(&entry.uid) {
+                        conflicts.push(format!(
+                            "'{}': uid '{}' already belongs to '{}'",
+                            event_type, entry.uid, owner
+                        ));
+                        continue;
+                    }
+                    if let Err(e) = self.check_collision(event_type) {
+                        conflicts.push(format!("'{}': {}", event_type, e));
+                        continue;
+                    }
+                    if !self.normalizer.is_exact()
+                        && let Some(other) =
+                            seen_keys.insert(self.normalizer.key(event_type), event_type)
+                    {
+                        conflicts.push(format!(
+                            "'{}': {}",
+                            event_type,
+                            SchemaError::EventTypeCollision(
+                                event_type.to_string(),
+                                other.to_string()
+                            )
+                        ));
+                        continue;
+                    }
+                    records.push(SchemaRecord {
+                        uid: entry.uid.clone(),
+                        event_type: event_type.to_string(),
+                        schema: entry.schema.clone(),
+                    });
+                    ImportAction::Created
+                }
+            };
+            actions.push((event_type.to_string(), action));
+        }
+
+        if !conflicts.is_empty() {
+            return Err(SchemaError::ImportConflicts(conflicts));
+        }
+        if records.is_empty() {
+            return Ok(actions);
+        }
+
+        let store = self.store.clone();
+        let to_write = records.clone();
+        tokio::task::spawn_blocking(move || store.append_all(&to_write))
+            .await
+            .map_err(|e| SchemaError::IoWriteFailed(format!("spawn_blocking failed: {}", e)))??;
+
+        for record in records {
+            self.register_record(record);
+        }
+        Ok(actions)
+    }
+
     pub fn get(&self, event_type: &str) -> Option<&MiniSchema> {
         self.schemas.get(event_type)
     }
@@ -155,6 +486,13 @@ impl SchemaRegistry {
             .and_then(|event_type| self.schemas.get(event_type))
     }
 
+    /// Declared time field of `event_type`, if its schema declares one.
+    pub fn time_field(&self, event_type: &str) -> Option<&str> {
+        self.schemas
+            .get(event_type)
+            .and_then(|s| s.time_field.as_deref())
+    }
+
     pub fn is_enum_field_by_uid(&self, uid: &str, field: &str) -> bool {
         self.get_schema_by_uid(uid)
             .map(|s| s.is_enum_field(field))
@@ -173,13 +511,14 @@ impl SchemaRegistry {
             .insert(record.event_type.clone(), record.schema);
         self.uid_map
             .insert(record.event_type.clone(), record.uid.clone());
+        self.index_normalized(&record.event_type);
         self.reverse_uid_map.insert(record.uid, record.event_type);
     }
 }
 
 impl From<CommandMiniSchema> for MiniSchema {
     fn from(cmd_schema: CommandMiniSchema) -> Self {
-        let mut fields: HashMap<String, FieldType> = HashMap::new();
+        let mut fields: IndexMap<String, FieldType> = IndexMap::new();
         for (name, spec) in cmd_schema.fields.into_iter() {
             match spec {
                 FieldSpec::Primitive(s) => {
@@ -196,6 +535,11 @@ impl From<CommandMiniSchema> for MiniSchema {
                 }
             }
         }
-        Self { fields }
+        Self {
+            fields,
+            time_field: cmd_schema.time_field,
+            temporal_fields: cmd_schema.temporal_fields,
+            indexes: cmd_schema.indexes,
+        }
     }
 }
@@ -1,7 +1,8 @@
 use crate::engine::schema::errors::SchemaError;
 use crate::engine::schema::registry::SchemaRecord;
 use crate::engine::schema::store::types::{
-    MAX_RECORD_LEN_BYTES, RecordReadResult, SchemaStoreDiagnostics,
+    DefaultIndexesSchemaRecord, LegacySchemaRecord, MAX_RECORD_LEN_BYTES, RecordReadResult,
+    SchemaStoreDiagnostics, SingleTimeFieldSchemaRecord,
 };
 use crate::engine::schema::store::writer::compute_crc32;
 use crate::shared::storage_header::BinaryHeader;
@@ -111,8 +112,17 @@ pub fn read_single_record(
         return Ok(RecordReadResult::Corrupted);
     }
 
-    // Deserialize record
-    match bincode::deserialize::<SchemaRecord>(&buf) {
+    // Deserialize record, falling back to older layouts (newest first)
+    let decoded = bincode::deserialize::<SchemaRecord>(&buf).or_else(|e| {
+        bincode::deserialize::<DefaultIndexesSchemaRecord>(&buf)
+            .map(SchemaRecord::from)
+            .or_else(|_| {
+                bincode::deserialize::<SingleTimeFieldSchemaRecord>(&buf).map(SchemaRecord::from)
+            })
+            .or_else(|_| bincode::deserialize::<LegacySchemaRecord>(&buf).map(SchemaRecord::from))
+            .map_err(|_| e)
+    });
+    match decoded {
         Ok(record) => Ok(RecordReadResult::Valid(record)),
         Err(e) => {
             record_skipped_record(
@@ -294,3 +294,149 @@ fn read_records_handles_empty_file_after_header() {
 
     assert_eq!(records.len(), 0);
 }
+
+#[test]
+fn read_single_record_reads_record_without_time_field() {
+    // Layout of records written before schemas could declare a time field
+    #[derive(serde::Serialize)]
+    struct OldRecord {
+        uid: String,
+        event_type: String,
+        fields: std::collections::HashMap<String, crate::engine::schema::FieldType>,
+    }
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("test.bin");
+    let mut file = File::create(&path).unwrap();
+
+    let old = OldRecord {
+        uid: "uid-1".to_string(),
+        event_type: "legacy_event".to_string(),
+        fields: [("id".to_string(), crate::engine::schema::FieldType::I64)].into(),
+    };
+    let encoded = bincode::serialize(&old).unwrap();
+    file.write_all(&(encoded.len() as u32).to_le_bytes())
+        .unwrap();
+    file.write_all(&compute_crc32(&encoded).to_le_bytes())
+        .unwrap();
+    file.write_all(&encoded).unwrap();
+    drop(file);
+
+    let mut file = File::open(&path).unwrap();
+    let mut offset = 0u64;
+    let mut diagnostics = None;
+    let result = read_single_record(&mut file, &mut offset, &mut diagnostics).unwrap();
+
+    match result {
+        RecordReadResult::Valid(decoded) => {
+            assert_eq!(decoded.event_type, "legacy_event");
+            assert_eq!(decoded.schema.fields.len(), 1);
+            assert_eq!(decoded.schema.time_field, None);
+        }
+        _ => panic!("Expected Valid record"),
+    }
+}
+
+#[test]
+fn read_single_record_reads_record_with_single_time_field() {
+    // Layout of records written when schemas had only one time field
+    #[derive(serde::Serialize)]
+    struct OldRecord {
+        uid: String,
+        event_type: String,
+        fields: std::collections::HashMap<String, crate::engine::schema::FieldType>,
+        time_field: Option<String>,
+    }
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("test.bin");
+    let mut file = File::create(&path).unwrap();
+
+    let old = OldRecord {
+        uid: "uid-2".to_string(),
+        event_type: "timed_event".to_string(),
+        fields: [("ts".to_string(), crate::engine::schema::FieldType::U64)].into(),
+        time_field: Some("ts".to_string()),
+    };
+    let encoded = bincode::serialize(&old).unwrap();
+    file.write_all(&(encoded.len() as u32).to_le_bytes())
+        .unwrap();
+    file.write_all(&compute_crc32(&encoded).to_le_bytes())
+        .unwrap();
+    file.write_all(&encoded).unwrap();
+    drop(file);
+
+    let mut file = File::open(&path).unwrap();
+    let mut offset = 0u64;
+    let mut diagnostics = None;
+    let result = read_single_record(&mut file, &mut offset, &mut diagnostics).unwrap();
+
+    match result {
+        RecordReadResult::Valid(decoded) => {
+            assert_eq!(decoded.event_type, "timed_event");
+            assert_eq!(decoded.schema.time_field.as_deref(), Some("ts"));
+            assert!(decoded.schema.temporal_fields.is_empty());
+        }
+        _ => panic!("Expected Valid record"),
+    }
+}
+
+#[test]
+fn read_single_record_reads_record_without_index_config() {
+    // Layout of records written before schemas could choose their indexes
+    #[derive(serde::Serialize)]
+    struct OldRecord {
+        uid: String,
+        event_type: String,
+        fields: indexmap::IndexMap<String, crate::engine::schema::FieldType>,
+        time_field: Option<String>,
+        temporal_fields: Vec<String>,
+    }
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("test.bin");
+    let mut file = File::create(&path).unwrap();
+
+    let old = OldRecord {
+        uid: "uid-3".to_string(),
+        event_type: "shipment".to_string(),
+        fields: [
+            (
+                "valid_at".to_string(),
+                crate::engine::schema::FieldType::U64,
+            ),
+            (
+                "recorded_at".to_string(),
+                crate::engine::schema::FieldType::U64,
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        time_field: Some("valid_at".to_string()),
+        temporal_fields: vec!["recorded_at".to_string()],
+    };
+    let encoded = bincode::serialize(&old).unwrap();
+    file.write_all(&(encoded.len() as u32).to_le_bytes())
+        .unwrap();
+    file.write_all(&compute_crc32(&encoded).to_le_bytes())
+        .unwrap();
+    file.write_all(&encoded).unwrap();
+    drop(file);
+
+    let mut file = File::open(&path).unwrap();
+    let mut offset = 0u64;
+    let mut diagnostics = None;
+    let result = read_single_record(&mut file, &mut offset, &mut diagnostics).unwrap();
+
+    match result {
+        RecordReadResult::Valid(decoded) => {
+            assert_eq!(decoded.event_type, "shipment");
+            assert_eq!(
+                decoded.schema.temporal_fields,
+                vec!["recorded_at".to_string()]
+            );
+            assert!(decoded.schema.indexes.is_empty());
+        }
+        _ => panic!("Expected Valid record"),
+    }
+}
@@ -65,6 +65,44 @@ impl SchemaStore {
         Ok(())
     }
 
+    /// Appends `records` all-or-nothing. The store is copied to a temporary
+    /// sibling, extended there and renamed over the original, so a crash leaves
+    /// either every record or none of them.
+    pub fn append_all(&self, records: &[SchemaRecord]) -> Result<(), SchemaError> {
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.file_path)
+            .map_err(|e| SchemaError::IoWriteFailed(e.to_string()))?;
+        let _guard = FileLockGuard::new_exclusive(file)?;
+
+        let mut tmp_name = self.file_path.file_name().unwrap_or_default().to_owned();
+        tmp_name.push(".tmp");
+        let tmp_path = self.file_path.with_file_name(tmp_name);
+        std::fs::copy(&self.file_path, &tmp_path)
+            .map_err(|e| SchemaError::IoWriteFailed(format!("Copying store: {}", e)))?;
+
+        let written = (|| {
+            let mut tmp = OpenOptions::new()
+                .append(true)
+                .open(&tmp_path)
+                .map_err(|e| SchemaError::IoWriteFailed(e.to_string()))?;
+            ensure_header(&mut tmp)?;
+            for record in records {
+                write_record(&mut tmp, record)?;
+            }
+            // Always synced: the rename must not publish a file whose data is still in flight
+            tmp.sync_all()
+                .map_err(|e| SchemaError::IoWriteFailed(e.to_string()))?;
+            std::fs::rename(&tmp_path, &self.file_path)
+                .map_err(|e| SchemaError::IoWriteFailed(format!("Replacing store: {}", e)))
+        })();
+        if written.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+        }
+        written
+    }
+
     pub fn load(&self) -> Result<Vec<SchemaRecord>, SchemaError> {
         let Some(file) = self.open_file_for_read()? else {
             return Ok(vec![]);
@@ -724,3 +724,35 @@ fn e2e_repair_prevents_overwriting_source() {
         assert!(format!("{}", e).contains("must differ"));
     }
 }
+
+#[test]
+fn append_all_adds_every_record_and_leaves_no_temp_file() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("schemas.bin");
+    let store = SchemaStore::new(path.clone()).unwrap();
+
+    store
+        .append(&SchemaRecordFactory::new("existing").create())
+        .unwrap();
+    let batch = vec![
+        SchemaRecordFactory::new("first").with_uid("uid-1").create(),
+        SchemaRecordFactory::new("second")
+            .with_uid("uid-2")
+            .create(),
+    ];
+    store.append_all(&batch).unwrap();
+
+    let loaded: Vec<String> = store
+        .load()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.event_type)
+        .collect();
+    assert_eq!(loaded, vec!["existing", "first", "second"]);
+    assert!(!dir.path().join("schemas.bin.tmp").exists());
+
+    // Also works on a store that does not exist yet
+    let fresh = SchemaStore::new(dir.path().join("fresh.bin")).unwrap();
+    fresh.append_all(&batch).unwrap();
+    assert_eq!(fresh.load().unwrap().len(), 2);
+}
@@ -1,4 +1,7 @@
-use crate::engine::schema::registry::SchemaRecord;
+use crate::engine::schema::registry::{MiniSchema, SchemaRecord};
+use crate::engine::schema::types::FieldType;
+use indexmap::IndexMap;
+use serde::Deserialize;
 
 /// Result of reading a single record.
 pub enum RecordReadResult {
@@ -32,3 +35,75 @@ impl Default for SchemaStoreOptions {
 
 pub const SCHEMA_STORE_VERSION: u16 = 1;
 pub const MAX_RECORD_LEN_BYTES: u32 = 10 * 1024;
+
+/// Record layout written before schemas could declare a time field.
+#[derive(Deserialize)]
+pub struct LegacySchemaRecord {
+    pub uid: String,
+    pub event_type: String,
+    pub fields: IndexMap<String, FieldType>,
+}
+
+/// Record layout written when schemas could declare only a single time field.
+#[derive(Deserialize)]
+pub struct SingleTimeFieldSchemaRecord {
+    pub uid: String,
+    pub event_type: String,
+    pub fields: IndexMap<String, FieldType>,
+    pub time_field: Option<String>,
+}
+
+/// Record layout written before schemas could choose their index artifacts.
+#[derive(Deserialize)]
+pub struct DefaultIndexesSchemaRecord {
+    pub uid: String,
+    pub event_type: String,
+    pub fields: IndexMap<String, FieldType>,
+    pub time_field: Option<String>,
+    pub temporal_fields: Vec<String>,
+}
+
+impl From<DefaultIndexesSchemaRecord> for SchemaRecord {
+    fn from(record: DefaultIndexesSchemaRecord) -> Self {
+        Self {
+            uid: record.uid,
+            event_type: record.event_type,
+            schema: MiniSchema {
+                fields: record.fields,
+                time_field: record.time_field,
+                temporal_fields: record.temporal_fields,
+                indexes: IndexMap::new(),
+            },
+        }
+    }
+}
+
+impl From<SingleTimeFieldSchemaRecord> for SchemaRecord {
+    fn from(record: SingleTimeFieldSchemaRecord) -> Self {
+        Self {
+            uid: record.uid,
+            event_type: record.event_type,
+            schema: MiniSchema {
+                fields: record.fields,
+                time_field: record.time_field,
+                temporal_fields: Vec::new(),
+                indexes: IndexMap::new(),
+            },
+        }
+    }
+}
+
+impl From<LegacySchemaRecord> for SchemaRecord {
+    fn from(legacy: LegacySchemaRecord) -> Self {
+        Self {
+            uid: legacy.uid,
+            event_type: legacy.event_type,
+            schema: MiniSchema {
+                fields: legacy.fields,
+                time_field: None,
+                temporal_fields: Vec::new(),
+                indexes: IndexMap::new(),
+            },
+        }
+    }
+}
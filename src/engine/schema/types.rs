@@ -1,3 +1,4 @@
+use crate::shared::time::TimeKind;
 use serde::{Deserialize, Serialize};
 
 /// Enumerated field type.
@@ -39,6 +40,10 @@ pub enum FieldType {
     Timestamp,
     /// Logical date (midnight UTC in epoch seconds)
     Date,
+    /// Time of day (milliseconds since midnight)
+    Time,
+    /// UUID in canonical hyphenated form, stored as 16 bytes
+    Uuid,
     Optional(Box<FieldType>),
     Enum(EnumType),
 }
@@ -55,6 +60,8 @@ impl FieldType {
             // logical time fields
             "datetime" | "timestamp" => Some(FieldType::Timestamp),
             "date" => Some(FieldType::Date),
+            "time" => Some(FieldType::Time),
+            "uuid" => Some(FieldType::Uuid),
             _ => None,
         }
     }
@@ -83,6 +90,27 @@ impl FieldType {
         }
     }
 
+    /// How values of a datetime, date or time field, nullable or not, are
+    /// parsed and normalized; `None` for other types.
+    pub fn time_kind(&self) -> Option<TimeKind> {
+        match self {
+            FieldType::Timestamp => Some(TimeKind::DateTime),
+            FieldType::Date => Some(TimeKind::Date),
+            FieldType::Time => Some(TimeKind::Time),
+            FieldType::Optional(inner) => inner.time_kind(),
+            _ => None,
+        }
+    }
+
+    /// Whether values are UUIDs, nullable or not.
+    pub fn is_uuid(&self) -> bool {
+        match self {
+            FieldType::Uuid => true,
+            FieldType::Optional(inner) => inner.is_uuid(),
+            _ => false,
+        }
+    }
+
     pub fn is_enum(&self) -> bool {
         matches!(self, FieldType::Enum(_))
     }
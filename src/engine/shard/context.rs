@@ -119,4 +119,13 @@ impl ShardContext {
     pub fn next_event_id(&mut self) -> EventId {
         self.event_id_gen.next(self.id as u16)
     }
+
+    /// Closes the WAL segment holding the events of the MemTable being swapped
+    /// out, returning the first log id that must outlive its flush.
+    pub async fn rotate_wal(&self) -> Option<u64> {
+        if !CONFIG.wal.enabled {
+            return None;
+        }
+        self.wal.as_ref()?.rotate().await
+    }
 }
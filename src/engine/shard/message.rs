@@ -1,6 +1,8 @@
-use crate::command::types::Command;
-use crate::engine::core::Event;
+use crate::command::types::{Command, RebuildIndexKind};
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
+use crate::engine::core::read::flow::{QueryCancellation, QueryMemoryBudget, QueryScanStats};
+use crate::engine::core::segment::index_rebuild::RebuildOutcome;
+use crate::engine::core::{Event, EventId};
 use crate::engine::schema::registry::SchemaRegistry;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -22,8 +24,44 @@ pub enum ShardMessage {
         metadata: Option<HashMap<String, String>>,
         response: oneshot::Sender<Result<ShardFlowHandle, String>>,
         registry: Arc<RwLock<SchemaRegistry>>,
+        memory: Arc<QueryMemoryBudget>,
+        stats: Arc<QueryScanStats>,
+        cancellation: Arc<QueryCancellation>,
+    },
+    GetEvent {
+        id: EventId,
+        registry: Arc<RwLock<SchemaRegistry>>,
+        response: oneshot::Sender<Result<Option<Event>, String>>,
+    },
+    /// Counts buffered, not yet flushed events of a type within inclusive timestamp bounds.
+    CountBuffered {
+        event_type: String,
+        context_id: Option<String>,
+        ts_min: u64,
+        ts_max: u64,
+        response: oneshot::Sender<BufferedCount>,
+    },
+    /// Earliest and latest timestamp of the buffered, not yet flushed events of a type.
+    BufferedTimeSpan {
+        event_type: String,
+        response: oneshot::Sender<Option<(u64, u64)>>,
+    },
+    /// Rebuilds index artifacts of one segment (by label) or all of them, in
+    /// the background so the shard keeps serving.
+    RebuildIndexes {
+        kinds: Vec<RebuildIndexKind>,
+        segment: Option<String>,
+        registry: Arc<RwLock<SchemaRegistry>>,
+        response: oneshot::Sender<Vec<RebuildOutcome>>,
     },
     Shutdown {
         completion: oneshot::Sender<Result<(), String>>,
     },
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferedCount {
+    pub rows: u64,
+    /// A passive buffer is still being flushed, so its rows may also be in a segment
+    pub flush_pending: bool,
+}
@@ -1,3 +1,4 @@
+use crate::engine::core::utils::worker_pools::spawn_query;
 use crate::engine::shard::context::ShardContext;
 use crate::engine::shard::message::ShardMessage;
 use crate::engine::shard::worker::run_worker_loop;
@@ -12,6 +13,7 @@ pub struct Shard {
     pub id: usize,
     pub tx: Sender<ShardMessage>,
     pub base_dir: PathBuf,
+    pub wal_dir: PathBuf,
 }
 
 #[derive(Debug, Clone)]
@@ -47,8 +49,9 @@ impl Shard {
             "Shard context created"
         );
 
-        // Spawn worker loop for shard
-        tokio::spawn(async move {
+        // Spawn worker loop for shard on the query pool; flushes it triggers run on
+        // the background pool (see FlushManager)
+        spawn_query(async move {
             info!(target: "shard::types", shard_id = id, "Shard worker started");
             run_worker_loop(ctx, rx).await;
             info!(target: "shard::types", shard_id = id, "Shard worker exited");
@@ -60,7 +63,12 @@ impl Shard {
         info!(target: "shard::types", shard_id = id, "Shard spawned successfully");
 
         (
-            Shard { id, tx, base_dir },
+            Shard {
+                id,
+                tx,
+                base_dir,
+                wal_dir,
+            },
             ShardSharedState {
                 flush_lock,
                 segment_ids,
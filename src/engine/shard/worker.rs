@@ -1,11 +1,15 @@
 use crate::command::types::Command;
-use crate::engine::core::Event;
 use crate::engine::core::MemTable;
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
-use crate::engine::query::scan::scan;
+use crate::engine::core::read::flow::{QueryCancellation, QueryMemoryBudget, QueryScanStats};
+use crate::engine::core::segment::index_rebuild::SegmentIndexRebuilder;
+use crate::engine::core::utils::worker_pools::spawn_background;
+use crate::engine::core::{Event, EventId};
+use crate::engine::query::event_lookup::lookup_event;
+use crate::engine::query::scan::{ScanControls, scan};
 use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::context::ShardContext;
-use crate::engine::shard::message::ShardMessage;
+use crate::engine::shard::message::{BufferedCount, ShardMessage};
 use crate::engine::store::insert::insert_and_maybe_flush;
 use std::sync::Arc;
 use tokio::sync::{mpsc::Receiver, oneshot};
@@ -14,7 +18,8 @@ use tracing::{debug, error, info};
 const LOG_TARGET: &str = "engine::shard::worker";
 
 /// Main worker loop for a shard.
-/// Processes messages: Store, QueryStream, Flush, Shutdown.
+/// Processes messages: Store, QueryStream, GetEvent, CountBuffered, Flush,
+/// RebuildIndexes, Shutdown.
 pub async fn run_worker_loop(mut ctx: ShardContext, mut rx: Receiver<ShardMessage>) {
     let id = ctx.id;
     info!(target: LOG_TARGET, shard_id = id, "Shard worker started");
@@ -32,13 +37,36 @@ pub async fn run_worker_loop(mut ctx: ShardContext, mut rx: Receiver<ShardMessag
                 metadata,
                 response,
                 registry,
+                memory,
+                stats,
+                cancellation,
             } => {
                 debug!(target: LOG_TARGET, shard_id = id, "Received QueryStream message");
-                let result = on_query_streaming(command, metadata, &ctx, &registry).await;
+                let result = on_query_streaming(
+                    command,
+                    metadata,
+                    &ctx,
+                    &registry,
+                    memory,
+                    stats,
+                    cancellation,
+                )
+                .await;
                 if response.send(result).is_err() {
                     error!(target: LOG_TARGET, shard_id = id, "Streaming response receiver dropped");
                 }
             }
+            ShardMessage::GetEvent {
+                id: event_id,
+                registry,
+                response,
+            } => {
+                debug!(target: LOG_TARGET, shard_id = id, %event_id, "Received GetEvent message");
+                let result = on_get_event(event_id, &ctx, &registry).await;
+                if response.send(result).is_err() {
+                    error!(target: LOG_TARGET, shard_id = id, "GetEvent response receiver dropped");
+                }
+            }
             ShardMessage::Flush {
                 registry,
                 completion,
@@ -66,6 +94,52 @@ pub async fn run_worker_loop(mut ctx: ShardContext, mut rx: Receiver<ShardMessag
                     }
                 }
             }
+            ShardMessage::CountBuffered {
+                event_type,
+                context_id,
+                ts_min,
+                ts_max,
+                response,
+            } => {
+                debug!(target: LOG_TARGET, shard_id = id, "Received CountBuffered message");
+                let count =
+                    on_count_buffered(&event_type, context_id.as_deref(), ts_min, ts_max, &ctx)
+                        .await;
+                if response.send(count).is_err() {
+                    error!(target: LOG_TARGET, shard_id = id, "CountBuffered response receiver dropped");
+                }
+            }
+            ShardMessage::BufferedTimeSpan {
+                event_type,
+                response,
+            } => {
+                debug!(target: LOG_TARGET, shard_id = id, "Received BufferedTimeSpan message");
+                let span = on_buffered_time_span(&event_type, &ctx).await;
+                if response.send(span).is_err() {
+                    error!(target: LOG_TARGET, shard_id = id, "BufferedTimeSpan response receiver dropped");
+                }
+            }
+            ShardMessage::RebuildIndexes {
+                kinds,
+                segment,
+                registry,
+                response,
+            } => {
+                debug!(target: LOG_TARGET, shard_id = id, ?kinds, ?segment, "Received RebuildIndexes message");
+                let rebuilder = SegmentIndexRebuilder::new(
+                    ctx.base_dir.clone(),
+                    Arc::clone(&ctx.segment_ids),
+                    Arc::clone(&ctx.flush_coordination_lock),
+                    registry,
+                    &kinds,
+                );
+                spawn_background(async move {
+                    let outcomes = rebuilder.run(segment.as_deref()).await;
+                    if response.send(outcomes).is_err() {
+                        error!(target: LOG_TARGET, shard_id = id, "RebuildIndexes response receiver dropped");
+                    }
+                });
+            }
             ShardMessage::Shutdown { completion } => {
                 debug!(target: LOG_TARGET, shard_id = id, "Received Shutdown message");
                 let result = on_shutdown(&mut ctx).await;
@@ -105,6 +179,9 @@ async fn on_query_streaming(
     metadata: Option<std::collections::HashMap<String, String>>,
     ctx: &ShardContext,
     registry: &Arc<tokio::sync::RwLock<SchemaRegistry>>,
+    memory: Arc<QueryMemoryBudget>,
+    stats: Arc<QueryScanStats>,
+    cancellation: Arc<QueryCancellation>,
 ) -> Result<ShardFlowHandle, String> {
     scan(
         &command,
@@ -115,11 +192,69 @@ async fn on_query_streaming(
         &ctx.memtable,
         &ctx.passive_buffers,
         Some(ctx.inflight_segments.clone()),
+        ScanControls {
+            memory,
+            stats,
+            cancellation,
+        },
     )
     .await
     .map_err(|e| e.to_string())
 }
 
+/// Handles GetEvent messages.
+async fn on_get_event(
+    id: EventId,
+    ctx: &ShardContext,
+    registry: &Arc<tokio::sync::RwLock<SchemaRegistry>>,
+) -> Result<Option<Event>, String> {
+    lookup_event(
+        id,
+        registry,
+        &ctx.base_dir,
+        &ctx.memtable,
+        &ctx.passive_buffers,
+        Some(&ctx.inflight_segments),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Handles CountBuffered messages.
+async fn on_count_buffered(
+    event_type: &str,
+    context_id: Option<&str>,
+    ts_min: u64,
+    ts_max: u64,
+    ctx: &ShardContext,
+) -> BufferedCount {
+    let passive = ctx.passive_buffers.non_empty().await;
+    let mut rows = ctx
+        .memtable
+        .count_matching(event_type, context_id, ts_min, ts_max);
+    for buffer in &passive {
+        rows += buffer
+            .lock()
+            .await
+            .count_matching(event_type, context_id, ts_min, ts_max);
+    }
+    BufferedCount {
+        rows,
+        flush_pending: !passive.is_empty(),
+    }
+}
+
+/// Handles BufferedTimeSpan messages.
+async fn on_buffered_time_span(event_type: &str, ctx: &ShardContext) -> Option<(u64, u64)> {
+    let mut span = ctx.memtable.time_span(event_type);
+    for buffer in &ctx.passive_buffers.non_empty().await {
+        if let Some((lo, hi)) = buffer.lock().await.time_span(event_type) {
+            span = Some(span.map_or((lo, hi), |(a, b)| (a.min(lo), b.max(hi))));
+        }
+    }
+    span
+}
+
 /// Handles Flush messages.
 async fn on_flush(
     ctx: &mut ShardContext,
@@ -131,6 +266,7 @@ async fn on_flush(
     // Move current memtable to a new passive buffer
     let passive = ctx.passive_buffers.add_from(&ctx.memtable).await;
     let flushed_mem = std::mem::replace(&mut ctx.memtable, MemTable::new(capacity));
+    let wal_cutoff = ctx.rotate_wal().await;
 
     // Use the existing flush manager from context
     info!(target: LOG_TARGET, shard_id = ctx.id, "Queueing memtable for flush");
@@ -143,6 +279,7 @@ async fn on_flush(
             segment_id,
             Arc::clone(&passive),
             flush_id,
+            wal_cutoff,
             Some(completion_tx),
         )
         .await
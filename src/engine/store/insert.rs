@@ -52,6 +52,7 @@ pub async fn insert_and_maybe_flush(
 
         let passive_arc = ctx.passive_buffers.add_from(&ctx.memtable).await;
         let flushed_mem = std::mem::replace(&mut ctx.memtable, MemTable::new(capacity));
+        let wal_cutoff = ctx.rotate_wal().await;
 
         debug!(
             target: "sneldb::store",
@@ -68,6 +69,7 @@ pub async fn insert_and_maybe_flush(
                 current_segment_id,
                 Arc::clone(&passive_arc),
                 flush_id,
+                wal_cutoff,
                 None,
             )
             .await?;
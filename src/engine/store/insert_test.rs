@@ -1,4 +1,4 @@
-use crate::engine::query::scan::scan;
+use crate::engine::query::scan::{ScanControls, scan};
 use crate::engine::store::insert::insert_and_maybe_flush;
 use crate::test_helpers::factories::{
     CommandFactory, EventFactory, SchemaRegistryFactory, ShardContextFactory,
@@ -177,6 +177,7 @@ async fn test_insert_and_maybe_flush_e2e() {
         &ctx.memtable,
         &ctx.passive_buffers,
         None,
+        ScanControls::default(),
     )
     .await
     .expect("scan should succeed");
@@ -0,0 +1,173 @@
+use crate::engine::types::{CoerceError, LogicalType, ScalarValue};
+
+fn utf8(s: &str) -> ScalarValue {
+    ScalarValue::Utf8(s.to_string())
+}
+
+#[test]
+fn strings_coerce_to_the_type_they_are_written_as() {
+    assert_eq!(
+        utf8("42").coerce_to(LogicalType::Integer),
+        Ok(ScalarValue::Int64(42))
+    );
+    assert_eq!(
+        utf8(" -7 ").coerce_to(LogicalType::Integer),
+        Ok(ScalarValue::Int64(-7))
+    );
+    assert_eq!(
+        utf8("1700000000").coerce_to(LogicalType::Timestamp),
+        Ok(ScalarValue::Timestamp(1_700_000_000))
+    );
+    assert_eq!(
+        utf8("2.5").coerce_to(LogicalType::Float),
+        Ok(ScalarValue::Float64(2.5))
+    );
+    assert_eq!(
+        utf8("TRUE").coerce_to(LogicalType::Boolean),
+        Ok(ScalarValue::Boolean(true))
+    );
+    assert_eq!(
+        utf8("2024-03-05").coerce_to(LogicalType::Date),
+        Ok(ScalarValue::Date(19_787))
+    );
+    assert_eq!(
+        utf8("1.5").coerce_to(LogicalType::Decimal {
+            precision: 5,
+            scale: 2
+        }),
+        Ok(ScalarValue::Decimal(150, 2))
+    );
+    assert_eq!(
+        utf8("hello").coerce_to(LogicalType::String),
+        Ok(utf8("hello"))
+    );
+}
+
+#[test]
+fn string_to_integer_overflow_is_out_of_range() {
+    assert_eq!(
+        utf8("9223372036854775808").coerce_to(LogicalType::Integer),
+        Err(CoerceError::OutOfRange {
+            value: "9223372036854775808".to_string(),
+            to: LogicalType::Integer,
+        })
+    );
+    assert!(matches!(
+        utf8("-9223372036854775809").coerce_to(LogicalType::Integer),
+        Err(CoerceError::OutOfRange { .. })
+    ));
+    assert_eq!(
+        utf8("9223372036854775807").coerce_to(LogicalType::Integer),
+        Ok(ScalarValue::Int64(i64::MAX))
+    );
+    let err = utf8("99999999999999999999")
+        .coerce_to(LogicalType::Integer)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "99999999999999999999 is out of range for Integer"
+    );
+}
+
+#[test]
+fn malformed_strings_are_unparsable() {
+    for (text, target) in [
+        ("4.2", LogicalType::Integer),
+        ("forty", LogicalType::Integer),
+        ("NaN", LogicalType::Float),
+        ("yes", LogicalType::Boolean),
+        ("2024-02-30", LogicalType::Date),
+        ("not-a-uuid", LogicalType::Uuid),
+    ] {
+        assert_eq!(
+            utf8(text).coerce_to(target),
+            Err(CoerceError::Unparsable {
+                value: text.to_string(),
+                to: target,
+            }),
+            "{} as {}",
+            text,
+            target
+        );
+    }
+    assert!(matches!(
+        utf8("1e400").coerce_to(LogicalType::Float),
+        Err(CoerceError::OutOfRange { .. })
+    ));
+}
+
+#[test]
+fn integers_widen_only_when_exact() {
+    assert_eq!(
+        ScalarValue::Int64(1 << 53).coerce_to(LogicalType::Float),
+        Ok(ScalarValue::Float64(9_007_199_254_740_992.0))
+    );
+    assert!(matches!(
+        ScalarValue::Int64((1 << 53) + 1).coerce_to(LogicalType::Float),
+        Err(CoerceError::Inexact { .. })
+    ));
+    assert!(matches!(
+        ScalarValue::Int64(i64::MAX).coerce_to(LogicalType::Float),
+        Err(CoerceError::Inexact { .. })
+    ));
+    assert_eq!(
+        ScalarValue::Int64(1_700_000_000).coerce_to(LogicalType::Timestamp),
+        Ok(ScalarValue::Timestamp(1_700_000_000))
+    );
+    assert_eq!(
+        ScalarValue::Int64(12).coerce_to(LogicalType::Decimal {
+            precision: 4,
+            scale: 2
+        }),
+        Ok(ScalarValue::Decimal(1200, 2))
+    );
+    assert!(matches!(
+        ScalarValue::Int64(123).coerce_to(LogicalType::Decimal {
+            precision: 4,
+            scale: 2
+        }),
+        Err(CoerceError::OutOfRange { .. })
+    ));
+}
+
+#[test]
+fn floats_narrow_to_integers_only_when_integral() {
+    assert_eq!(
+        ScalarValue::Float64(-3.0).coerce_to(LogicalType::Integer),
+        Ok(ScalarValue::Int64(-3))
+    );
+    assert!(matches!(
+        ScalarValue::Float64(3.5).coerce_to(LogicalType::Integer),
+        Err(CoerceError::Inexact { .. })
+    ));
+    assert!(matches!(
+        ScalarValue::Float64(1e19).coerce_to(LogicalType::Integer),
+        Err(CoerceError::OutOfRange { .. })
+    ));
+    assert!(matches!(
+        ScalarValue::Decimal(1234, 3).coerce_to(LogicalType::Decimal {
+            precision: 10,
+            scale: 2
+        }),
+        Err(CoerceError::Inexact { .. })
+    ));
+}
+
+#[test]
+fn other_pairs_are_unsupported_and_null_passes_through() {
+    assert_eq!(
+        ScalarValue::Boolean(true).coerce_to(LogicalType::Integer),
+        Err(CoerceError::Unsupported {
+            from: LogicalType::Boolean,
+            to: LogicalType::Integer,
+        })
+    );
+    assert!(matches!(
+        ScalarValue::Int64(1).coerce_to(LogicalType::String),
+        Err(CoerceError::Unsupported { .. })
+    ));
+    assert_eq!(
+        ScalarValue::Null.coerce_to(LogicalType::Integer),
+        Ok(ScalarValue::Null)
+    );
+}
@@ -0,0 +1,103 @@
+//! Exact helpers for `ScalarValue::Decimal`: an `i128` mantissa worth
+//! `mantissa / 10^scale`. Values of different scales are compared and
+//! encoded through the same split into an integer part and a fraction, so
+//! `1.5` at scale 1 and `1.50` at scale 2 stay equal without rescaling, which
+//! could overflow at high precision.
+
+use std::cmp::Ordering;
+
+/// Largest precision, and so scale, a Decimal128 holds.
+pub const MAX_PRECISION: u8 = 38;
+
+/// `10^exp` for `exp <= MAX_PRECISION`.
+pub fn pow10(exp: u8) -> i128 {
+    10i128.pow(u32::from(exp.min(MAX_PRECISION)))
+}
+
+/// The floor integer part and the fraction in units of `10^-MAX_PRECISION`.
+/// Ordering these pairs orders the values, whatever their scales.
+pub fn split(mantissa: i128, scale: u8) -> (i128, u128) {
+    let unit = pow10(scale);
+    let integer = mantissa.div_euclid(unit);
+    // Below 10^scale, so the product stays below 10^38
+    let fraction =
+        mantissa.rem_euclid(unit) as u128 * pow10(MAX_PRECISION - scale.min(MAX_PRECISION)) as u128;
+    (integer, fraction)
+}
+
+pub fn compare(a: (i128, u8), b: (i128, u8)) -> Ordering {
+    if a.1 == b.1 {
+        return a.0.cmp(&b.0);
+    }
+    split(a.0, a.1).cmp(&split(b.0, b.1))
+}
+
+/// Drops trailing fractional zeros: `(1500, 3)` becomes `(15, 1)`.
+pub fn normalize(mantissa: i128, scale: u8) -> (i128, u8) {
+    let (mut mantissa, mut scale) = (mantissa, scale);
+    while scale > 0 && mantissa % 10 == 0 {
+        mantissa /= 10;
+        scale -= 1;
+    }
+    (mantissa, scale)
+}
+
+/// Plain notation with exactly `scale` fractional digits: `(-5, 2)` is `-0.05`.
+pub fn format(mantissa: i128, scale: u8) -> String {
+    let digits = mantissa.unsigned_abs().to_string();
+    let sign = if mantissa < 0 { "-" } else { "" };
+    let scale = usize::from(scale);
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// Parses plain notation (`-12.340`), keeping every written fractional digit
+/// as scale. `None` for exponents, other characters or more than 38 digits.
+pub fn parse(s: &str) -> Option<(i128, u8)> {
+    let s = s.trim();
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !integer
+        .bytes()
+        .chain(fraction.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let significant = integer.trim_start_matches('0').len() + fraction.len();
+    if significant > usize::from(MAX_PRECISION) || fraction.len() > usize::from(MAX_PRECISION) {
+        return None;
+    }
+    let mut mantissa: i128 = 0;
+    for b in integer.bytes().chain(fraction.bytes()) {
+        mantissa = mantissa * 10 + i128::from(b - b'0');
+    }
+    Some((
+        if negative { -mantissa } else { mantissa },
+        fraction.len() as u8,
+    ))
+}
+
+/// The nearest `f64`, correctly rounded.
+pub fn to_f64(mantissa: i128, scale: u8) -> f64 {
+    format(mantissa, scale).parse().unwrap_or(f64::NAN)
+}
+
+/// Order-preserving bytes for SuRF keys, equal for equal values of any scale.
+pub fn encode_ordered(mantissa: i128, scale: u8) -> Vec<u8> {
+    let (integer, fraction) = split(mantissa, scale);
+    let mut out = Vec::with_capacity(32);
+    out.extend_from_slice(&((integer as u128) ^ (1u128 << 127)).to_be_bytes());
+    out.extend_from_slice(&fraction.to_be_bytes());
+    out
+}
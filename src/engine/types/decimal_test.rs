@@ -0,0 +1,139 @@
+use crate::engine::types::decimal;
+use crate::engine::types::{LogicalType, ScalarValue};
+use arrow_schema::DataType;
+use serde_json::json;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+#[test]
+fn format_and_parse_round_trip_every_digit() {
+    let cases = [
+        ((12345, 2), "123.45"),
+        ((-5, 2), "-0.05"),
+        ((7, 0), "7"),
+        ((0, 3), "0.000"),
+        (
+            (10i128.pow(38) - 1, 38),
+            "0.99999999999999999999999999999999999999",
+        ),
+    ];
+    for ((m, s), text) in cases {
+        assert_eq!(decimal::format(m, s), text);
+        assert_eq!(decimal::parse(text), Some((m, s)));
+    }
+    assert_eq!(decimal::parse("+.5"), Some((5, 1)));
+    assert_eq!(decimal::parse("1e3"), None);
+    assert_eq!(decimal::parse("."), None);
+    assert_eq!(decimal::parse(&"9".repeat(39)), None);
+}
+
+#[test]
+fn compare_is_exact_across_scales() {
+    assert_eq!(decimal::compare((15, 1), (150, 2)), Ordering::Equal);
+    assert_eq!(decimal::compare((-5, 2), (-1, 1)), Ordering::Greater);
+    assert_eq!(decimal::compare((-15, 1), (-149, 2)), Ordering::Less);
+
+    // Rescaling either side to the other's scale would overflow i128
+    let big = (i128::MAX / 10, 0);
+    let tiny_above = (1, 38);
+    assert_eq!(decimal::compare(big, tiny_above), Ordering::Greater);
+    assert_eq!(
+        decimal::compare((10i128.pow(37) + 1, 37), (1, 0)),
+        Ordering::Greater
+    );
+    assert_eq!(
+        decimal::compare((10i128.pow(37), 37), (1, 0)),
+        Ordering::Equal
+    );
+}
+
+#[test]
+fn equal_decimals_of_different_scales_hash_and_compare_equal() {
+    let a = ScalarValue::Decimal(1500, 3);
+    let b = ScalarValue::Decimal(15, 1);
+    assert_eq!(a, b);
+    assert_eq!(a.compare(&b), Ordering::Equal);
+    let set: HashSet<ScalarValue> = [a, b].into_iter().collect();
+    assert_eq!(set.len(), 1);
+    assert_ne!(ScalarValue::Decimal(15, 1), ScalarValue::Decimal(16, 1));
+}
+
+#[test]
+fn compare_against_integers_and_strings_is_exact() {
+    // 0.1 + 0.2 in f64 is not 0.3; as decimals it is
+    let sum = ScalarValue::Decimal(1 + 2, 1);
+    assert_eq!(
+        sum.compare(&ScalarValue::Utf8("0.30".into())),
+        Ordering::Equal
+    );
+    assert_eq!(
+        ScalarValue::Decimal(20_000_000_000_000_000_001, 2)
+            .compare(&ScalarValue::Int64(200_000_000_000_000_000)),
+        Ordering::Greater
+    );
+    assert_eq!(
+        ScalarValue::Int64(3).compare(&ScalarValue::Decimal(2999, 3)),
+        Ordering::Greater
+    );
+}
+
+#[test]
+fn conversions_keep_the_exact_value() {
+    let value = ScalarValue::Decimal(123_456_789_012_345_678_901, 3);
+    assert_eq!(value.to_string_repr(), "123456789012345678.901");
+    assert_eq!(value.to_json(), json!("123456789012345678.901"));
+    assert_eq!(
+        serde_json::to_string(&value).unwrap(),
+        "\"123456789012345678.901\""
+    );
+    assert_eq!(ScalarValue::Decimal(-125, 2).as_f64(), Some(-1.25));
+    assert_eq!(ScalarValue::Decimal(1, 1).as_f64(), Some(0.1));
+    assert_eq!(
+        value.logical_type(),
+        LogicalType::Decimal {
+            precision: 38,
+            scale: 3
+        }
+    );
+}
+
+#[test]
+fn logical_type_parses_displays_and_maps_to_decimal128() {
+    let ty = LogicalType::from_str("Decimal(18,4)").unwrap();
+    assert_eq!(
+        ty,
+        LogicalType::Decimal {
+            precision: 18,
+            scale: 4
+        }
+    );
+    assert_eq!(ty.to_string(), "Decimal(18,4)");
+    assert_eq!(ty.to_arrow_data_type(), DataType::Decimal128(18, 4));
+
+    assert!(LogicalType::from_str("Decimal(39,2)").is_err());
+    assert!(LogicalType::from_str("Decimal(4,5)").is_err());
+    assert!(LogicalType::from_str("Decimal(0,0)").is_err());
+}
+
+#[test]
+fn encode_ordered_sorts_like_compare() {
+    let mut values = vec![
+        (-5, 2),
+        (12, 0),
+        (-25, 1),
+        (14999, 4),
+        (1500, 3),
+        (0, 2),
+        (i128::MIN + 1, 0),
+        (i128::MAX, 38),
+    ];
+    let mut by_bytes = values.clone();
+    by_bytes.sort_by_key(|(m, s)| decimal::encode_ordered(*m, *s));
+    values.sort_by(|a, b| decimal::compare(*a, *b));
+    assert_eq!(by_bytes, values);
+    assert_eq!(
+        decimal::encode_ordered(-15, 1),
+        decimal::encode_ordered(-1500, 3)
+    );
+}
@@ -1,10 +1,25 @@
 use std::fmt;
+use std::num::IntErrorKind;
 use std::str::FromStr;
 
 use arrow_schema::DataType;
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Number, Value as JsonValue};
+use thiserror::Error;
+
+pub mod decimal;
+pub mod temporal;
+pub mod uuid;
+
+#[cfg(test)]
+mod coerce_test;
+#[cfg(test)]
+mod decimal_test;
+#[cfg(test)]
+mod temporal_test;
+#[cfg(test)]
+mod uuid_test;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LogicalType {
@@ -13,9 +28,20 @@ pub enum LogicalType {
     Integer,
     Float,
     Timestamp,
+    /// Calendar day without a time of day
+    Date,
+    /// Time of day without a date, to the millisecond
+    Time,
     String,
     Json,
     Binary,
+    /// 128-bit UUID, stored as its 16 bytes
+    Uuid,
+    /// Exact number of up to `precision` digits, `scale` of them fractional
+    Decimal {
+        precision: u8,
+        scale: u8,
+    },
 }
 
 impl LogicalType {
@@ -26,9 +52,13 @@ impl LogicalType {
             LogicalType::Integer => "Integer",
             LogicalType::Float => "Float",
             LogicalType::Timestamp => "Timestamp",
+            LogicalType::Date => "Date",
+            LogicalType::Time => "Time",
             LogicalType::String => "String",
             LogicalType::Json => "JSON",
             LogicalType::Binary => "Binary",
+            LogicalType::Uuid => "Uuid",
+            LogicalType::Decimal { .. } => "Decimal",
         }
     }
 
@@ -41,15 +71,26 @@ impl LogicalType {
             LogicalType::Timestamp => {
                 DataType::Timestamp(arrow_schema::TimeUnit::Millisecond, None)
             }
+            LogicalType::Date => DataType::Date32,
+            LogicalType::Time => DataType::Time32(arrow_schema::TimeUnit::Millisecond),
             LogicalType::String | LogicalType::Json => DataType::LargeUtf8,
             LogicalType::Binary => DataType::LargeBinary,
+            LogicalType::Uuid => DataType::FixedSizeBinary(16),
+            LogicalType::Decimal { precision, scale } => {
+                DataType::Decimal128(*precision, *scale as i8)
+            }
         }
     }
 }
 
 impl fmt::Display for LogicalType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.as_str())
+        match self {
+            LogicalType::Decimal { precision, scale } => {
+                write!(f, "Decimal({},{})", precision, scale)
+            }
+            other => f.write_str(other.as_str()),
+        }
     }
 }
 
@@ -62,15 +103,31 @@ impl FromStr for LogicalType {
             "Integer" | "Number" => Ok(LogicalType::Integer),
             "Float" => Ok(LogicalType::Float),
             "Timestamp" => Ok(LogicalType::Timestamp),
+            "Date" => Ok(LogicalType::Date),
+            "Time" => Ok(LogicalType::Time),
             "String" => Ok(LogicalType::String),
             "JSON" | "Object" | "Array" => Ok(LogicalType::Json),
             "Binary" => Ok(LogicalType::Binary),
+            "Uuid" | "UUID" => Ok(LogicalType::Uuid),
             "Null" => Ok(LogicalType::Null),
-            _ => Err(()),
+            _ => parse_decimal_type(s).ok_or(()),
         }
     }
 }
 
+/// `Decimal(precision,scale)`, with 1 <= precision <= 38 and scale <= precision.
+fn parse_decimal_type(s: &str) -> Option<LogicalType> {
+    let args = s.strip_prefix("Decimal(")?.strip_suffix(')')?;
+    let (precision, scale) = args.split_once(',')?;
+    let precision: u8 = precision.trim().parse().ok()?;
+    let scale: u8 = scale.trim().parse().ok()?;
+    (1..=decimal::MAX_PRECISION)
+        .contains(&precision)
+        .then_some(())
+        .filter(|_| scale <= precision)
+        .map(|_| LogicalType::Decimal { precision, scale })
+}
+
 impl From<&str> for LogicalType {
     fn from(value: &str) -> Self {
         LogicalType::from_str(value).unwrap_or(LogicalType::String)
@@ -89,15 +146,61 @@ impl From<LogicalType> for DataType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Why [`ScalarValue::coerce_to`] could not convert a value.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CoerceError {
+    /// No conversion between the two types keeps every value intact
+    #[error("cannot convert {from} to {to}")]
+    Unsupported { from: LogicalType, to: LogicalType },
+    /// The string is not written as a value of the target type
+    #[error("'{value}' is not a valid {to}")]
+    Unparsable { value: String, to: LogicalType },
+    /// The value lies outside the range of the target type
+    #[error("{value} is out of range for {to}")]
+    OutOfRange { value: String, to: LogicalType },
+    /// The target type would only hold a rounded value
+    #[error("{value} cannot be represented exactly as {to}")]
+    Inexact { value: String, to: LogicalType },
+}
+
+#[derive(Debug, Clone)]
 pub enum ScalarValue {
     Null,
     Boolean(bool),
     Int64(i64),
     Float64(f64),
     Timestamp(i64),
+    /// Days since 1970-01-01
+    Date(i32),
+    /// Milliseconds since midnight
+    Time(i32),
     Utf8(String),
     Binary(Vec<u8>),
+    /// Exact decimal: the mantissa and its scale, worth `mantissa / 10^scale`
+    Decimal(i128, u8),
+    /// UUID bytes in canonical order
+    Uuid([u8; 16]),
+}
+
+impl PartialEq for ScalarValue {
+    fn eq(&self, other: &Self) -> bool {
+        use ScalarValue::*;
+        match (self, other) {
+            (Null, Null) => true,
+            (Boolean(a), Boolean(b)) => a == b,
+            (Int64(a), Int64(b)) => a == b,
+            (Float64(a), Float64(b)) => a == b,
+            (Timestamp(a), Timestamp(b)) => a == b,
+            (Date(a), Date(b)) => a == b,
+            (Time(a), Time(b)) => a == b,
+            (Utf8(a), Utf8(b)) => a == b,
+            (Binary(a), Binary(b)) => a == b,
+            (Uuid(a), Uuid(b)) => a == b,
+            // Equal values of different scales are equal: 1.5 == 1.50
+            (Decimal(ma, sa), Decimal(mb, sb)) => decimal::compare((*ma, *sa), (*mb, *sb)).is_eq(),
+            _ => false,
+        }
+    }
 }
 
 impl Eq for ScalarValue {}
@@ -119,6 +222,11 @@ impl std::hash::Hash for ScalarValue {
             ScalarValue::Timestamp(t) => (4u8, t).hash(state),
             ScalarValue::Utf8(s) => (5u8, s).hash(state),
             ScalarValue::Binary(b) => (6u8, b).hash(state),
+            // Hashed without trailing zeros, so values equal across scales hash alike
+            ScalarValue::Decimal(m, s) => (7u8, decimal::normalize(*m, *s)).hash(state),
+            ScalarValue::Date(d) => (8u8, d).hash(state),
+            ScalarValue::Time(t) => (9u8, t).hash(state),
+            ScalarValue::Uuid(u) => (10u8, u).hash(state),
         }
     }
 }
@@ -131,8 +239,15 @@ impl ScalarValue {
             ScalarValue::Int64(_) => LogicalType::Integer,
             ScalarValue::Float64(_) => LogicalType::Float,
             ScalarValue::Timestamp(_) => LogicalType::Timestamp,
+            ScalarValue::Date(_) => LogicalType::Date,
+            ScalarValue::Time(_) => LogicalType::Time,
             ScalarValue::Utf8(_) => LogicalType::String,
             ScalarValue::Binary(_) => LogicalType::Binary,
+            ScalarValue::Uuid(_) => LogicalType::Uuid,
+            ScalarValue::Decimal(_, scale) => LogicalType::Decimal {
+                precision: decimal::MAX_PRECISION,
+                scale: *scale,
+            },
         }
     }
 
@@ -171,6 +286,20 @@ impl ScalarValue {
                 JsonValue::String(s.clone())
             }
             ScalarValue::Binary(bytes) => JsonValue::String(BASE64_STANDARD.encode(bytes)),
+            // A string: JSON numbers are read back as f64, which would lose digits
+            ScalarValue::Decimal(m, s) => JsonValue::String(decimal::format(*m, *s)),
+            ScalarValue::Date(d) => JsonValue::String(temporal::format_date(*d)),
+            ScalarValue::Time(t) => JsonValue::String(temporal::format_time(*t)),
+            ScalarValue::Uuid(u) => JsonValue::String(uuid::format(u)),
+        }
+    }
+
+    /// Like [`Self::to_json`], but every `Utf8` value stays a JSON string:
+    /// nothing that merely looks like JSON is re-parsed (`RAW STRINGS`).
+    pub fn to_json_raw(&self) -> JsonValue {
+        match self {
+            ScalarValue::Utf8(s) => JsonValue::String(s.clone()),
+            other => other.to_json(),
         }
     }
 
@@ -189,6 +318,9 @@ impl ScalarValue {
         match self {
             ScalarValue::Int64(i) => Some(*i),
             ScalarValue::Timestamp(ts) => Some(*ts),
+            // In the units fields store: midnight epoch seconds, milliseconds
+            ScalarValue::Date(d) => Some(temporal::epoch_seconds_of_day(*d)),
+            ScalarValue::Time(t) => Some(i64::from(*t)),
             ScalarValue::Utf8(s) => s.parse::<i64>().ok(),
             _ => None,
         }
@@ -198,6 +330,8 @@ impl ScalarValue {
         match self {
             ScalarValue::Int64(i) if *i >= 0 => Some(*i as u64),
             ScalarValue::Timestamp(ts) if *ts >= 0 => Some(*ts as u64),
+            ScalarValue::Date(d) if *d >= 0 => Some(temporal::epoch_seconds_of_day(*d) as u64),
+            ScalarValue::Time(t) if *t >= 0 => Some(*t as u64),
             ScalarValue::Utf8(s) => s.parse::<u64>().ok(),
             _ => None,
         }
@@ -208,7 +342,51 @@ impl ScalarValue {
             ScalarValue::Float64(f) => Some(*f),
             ScalarValue::Int64(i) => Some(*i as f64),
             ScalarValue::Timestamp(ts) => Some(*ts as f64),
+            ScalarValue::Date(_) | ScalarValue::Time(_) => self.as_i64().map(|v| v as f64),
             ScalarValue::Utf8(s) => s.parse::<f64>().ok(),
+            ScalarValue::Decimal(m, s) => Some(decimal::to_f64(*m, *s)),
+            _ => None,
+        }
+    }
+
+    /// Mantissa and scale of a value that is exactly a decimal: decimals,
+    /// integers, and strings in plain decimal notation.
+    pub fn as_decimal(&self) -> Option<(i128, u8)> {
+        match self {
+            ScalarValue::Decimal(m, s) => Some((*m, *s)),
+            ScalarValue::Int64(i) | ScalarValue::Timestamp(i) => Some((i128::from(*i), 0)),
+            ScalarValue::Utf8(s) => decimal::parse(s),
+            _ => None,
+        }
+    }
+
+    /// Days since the epoch of dates, `YYYY-MM-DD` strings and the midnight
+    /// epoch seconds Date fields store.
+    pub fn as_date(&self) -> Option<i32> {
+        match self {
+            ScalarValue::Date(d) => Some(*d),
+            ScalarValue::Int64(secs) => temporal::days_from_epoch_seconds(*secs),
+            ScalarValue::Utf8(s) => temporal::parse_date(s),
+            _ => None,
+        }
+    }
+
+    /// Milliseconds since midnight of times, `HH:MM[:SS[.fff]]` strings and
+    /// the milliseconds Time fields store.
+    pub fn as_time(&self) -> Option<i32> {
+        match self {
+            ScalarValue::Time(t) => Some(*t),
+            ScalarValue::Int64(millis) => temporal::time_from_millis(*millis),
+            ScalarValue::Utf8(s) => temporal::parse_time(s),
+            _ => None,
+        }
+    }
+
+    /// Bytes of UUIDs and of strings in canonical hyphenated form.
+    pub fn as_uuid(&self) -> Option<[u8; 16]> {
+        match self {
+            ScalarValue::Uuid(u) => Some(*u),
+            ScalarValue::Utf8(s) => uuid::parse(s),
             _ => None,
         }
     }
@@ -237,6 +415,106 @@ impl ScalarValue {
             ScalarValue::Timestamp(ts) => ts.to_string(),
             ScalarValue::Utf8(s) => s.clone(),
             ScalarValue::Binary(bytes) => BASE64_STANDARD.encode(bytes),
+            ScalarValue::Decimal(m, s) => decimal::format(*m, *s),
+            ScalarValue::Date(d) => temporal::format_date(*d),
+            ScalarValue::Time(t) => temporal::format_time(*t),
+            ScalarValue::Uuid(u) => uuid::format(u),
+        }
+    }
+
+    /// Converts to `target` when that loses nothing: strings written as a
+    /// value of the target type, integers to floats, timestamps or decimals,
+    /// and integral floats to integers. Null stays null for every target.
+    pub fn coerce_to(&self, target: LogicalType) -> Result<ScalarValue, CoerceError> {
+        let out_of_range = || CoerceError::OutOfRange {
+            value: self.to_string_repr(),
+            to: target,
+        };
+        let inexact = || CoerceError::Inexact {
+            value: self.to_string_repr(),
+            to: target,
+        };
+        let unparsable = || CoerceError::Unparsable {
+            value: self.to_string_repr(),
+            to: target,
+        };
+        match (self, target) {
+            (ScalarValue::Null, _) => Ok(ScalarValue::Null),
+            (ScalarValue::Decimal(m, s), LogicalType::Decimal { precision, scale }) => {
+                rescale_decimal(*m, *s, precision, scale, inexact, out_of_range)
+            }
+            (value, target) if value.logical_type() == target => Ok(value.clone()),
+
+            (ScalarValue::Utf8(s), LogicalType::Integer | LogicalType::Timestamp) => {
+                let i = s.trim().parse::<i64>().map_err(|e| match e.kind() {
+                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => out_of_range(),
+                    _ => unparsable(),
+                })?;
+                Ok(if target == LogicalType::Integer {
+                    ScalarValue::Int64(i)
+                } else {
+                    ScalarValue::Timestamp(i)
+                })
+            }
+            (ScalarValue::Utf8(s), LogicalType::Float) => {
+                let f = s.trim().parse::<f64>().map_err(|_| unparsable())?;
+                if f.is_nan() {
+                    Err(unparsable())
+                } else if f.is_infinite() {
+                    Err(out_of_range())
+                } else {
+                    Ok(ScalarValue::Float64(f))
+                }
+            }
+            (ScalarValue::Utf8(s), LogicalType::Boolean) => {
+                match s.trim().to_ascii_lowercase().as_str() {
+                    "true" => Ok(ScalarValue::Boolean(true)),
+                    "false" => Ok(ScalarValue::Boolean(false)),
+                    _ => Err(unparsable()),
+                }
+            }
+            (ScalarValue::Utf8(s), LogicalType::Date) => temporal::parse_date(s)
+                .map(ScalarValue::Date)
+                .ok_or_else(unparsable),
+            (ScalarValue::Utf8(s), LogicalType::Time) => temporal::parse_time(s)
+                .map(ScalarValue::Time)
+                .ok_or_else(unparsable),
+            (ScalarValue::Utf8(s), LogicalType::Uuid) => {
+                uuid::parse(s).map(ScalarValue::Uuid).ok_or_else(unparsable)
+            }
+            (ScalarValue::Utf8(s), LogicalType::Decimal { precision, scale }) => {
+                let (m, s) = decimal::parse(s).ok_or_else(unparsable)?;
+                rescale_decimal(m, s, precision, scale, inexact, out_of_range)
+            }
+
+            (ScalarValue::Int64(i), LogicalType::Float) => {
+                let f = *i as f64;
+                // 2^63 rounds back to itself, so compare wider than i64
+                if f as i128 == i128::from(*i) {
+                    Ok(ScalarValue::Float64(f))
+                } else {
+                    Err(inexact())
+                }
+            }
+            (ScalarValue::Int64(i), LogicalType::Timestamp) => Ok(ScalarValue::Timestamp(*i)),
+            (ScalarValue::Timestamp(ts), LogicalType::Integer) => Ok(ScalarValue::Int64(*ts)),
+            (ScalarValue::Int64(i), LogicalType::Decimal { precision, scale }) => {
+                rescale_decimal(i128::from(*i), 0, precision, scale, inexact, out_of_range)
+            }
+            (ScalarValue::Float64(f), LogicalType::Integer) => {
+                if !f.is_finite() || f.fract() != 0.0 {
+                    Err(inexact())
+                } else if *f < -(2f64.powi(63)) || *f >= 2f64.powi(63) {
+                    Err(out_of_range())
+                } else {
+                    Ok(ScalarValue::Int64(*f as i64))
+                }
+            }
+
+            (value, target) => Err(CoerceError::Unsupported {
+                from: value.logical_type(),
+                to: target,
+            }),
         }
     }
 
@@ -245,6 +523,29 @@ impl ScalarValue {
     pub fn compare(&self, other: &Self) -> std::cmp::Ordering {
         use std::cmp::Ordering;
 
+        // Decimals compare exactly against anything that is exactly a decimal
+        if (matches!(self, ScalarValue::Decimal(..)) || matches!(other, ScalarValue::Decimal(..)))
+            && let (Some(a), Some(b)) = (self.as_decimal(), other.as_decimal())
+        {
+            return decimal::compare(a, b);
+        }
+        // Dates and times also compare against strings written as such
+        if (matches!(self, ScalarValue::Date(_)) || matches!(other, ScalarValue::Date(_)))
+            && let (Some(a), Some(b)) = (self.as_date(), other.as_date())
+        {
+            return a.cmp(&b);
+        }
+        if (matches!(self, ScalarValue::Time(_)) || matches!(other, ScalarValue::Time(_)))
+            && let (Some(a), Some(b)) = (self.as_time(), other.as_time())
+        {
+            return a.cmp(&b);
+        }
+        // UUIDs order by their bytes, which is also their canonical text order
+        if (matches!(self, ScalarValue::Uuid(_)) || matches!(other, ScalarValue::Uuid(_)))
+            && let (Some(a), Some(b)) = (self.as_uuid(), other.as_uuid())
+        {
+            return a.cmp(&b);
+        }
         // Try u64 first (for consistency with existing comparison functions)
         if let (Some(va), Some(vb)) = (self.as_u64(), other.as_u64()) {
             return va.cmp(&vb);
@@ -270,6 +571,33 @@ impl ScalarValue {
     }
 }
 
+/// `mantissa / 10^scale` at `target_scale`, if that keeps every digit and
+/// fits in `precision` digits.
+fn rescale_decimal(
+    mantissa: i128,
+    scale: u8,
+    precision: u8,
+    target_scale: u8,
+    inexact: impl FnOnce() -> CoerceError,
+    out_of_range: impl FnOnce() -> CoerceError,
+) -> Result<ScalarValue, CoerceError> {
+    let rescaled = if target_scale >= scale {
+        mantissa.checked_mul(decimal::pow10(target_scale - scale))
+    } else {
+        let unit = decimal::pow10(scale - target_scale);
+        if mantissa % unit != 0 {
+            return Err(inexact());
+        }
+        Some(mantissa / unit)
+    };
+    match rescaled {
+        Some(m) if m.unsigned_abs() < decimal::pow10(precision) as u128 => {
+            Ok(ScalarValue::Decimal(m, target_scale))
+        }
+        _ => Err(out_of_range()),
+    }
+}
+
 impl From<JsonValue> for ScalarValue {
     fn from(value: JsonValue) -> Self {
         match value {
@@ -325,6 +653,10 @@ impl Serialize for ScalarValue {
                 serializer.serialize_str(s)
             }
             ScalarValue::Binary(bytes) => serializer.serialize_str(&BASE64_STANDARD.encode(bytes)),
+            ScalarValue::Decimal(m, s) => serializer.serialize_str(&decimal::format(*m, *s)),
+            ScalarValue::Date(d) => serializer.serialize_str(&temporal::format_date(*d)),
+            ScalarValue::Time(t) => serializer.serialize_str(&temporal::format_time(*t)),
+            ScalarValue::Uuid(u) => serializer.serialize_str(&uuid::format(u)),
         }
     }
 }
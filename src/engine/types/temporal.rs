@@ -0,0 +1,69 @@
+//! Helpers for `ScalarValue::Date`, days since 1970-01-01, and
+//! `ScalarValue::Time`, milliseconds since midnight. Date fields are stored as
+//! the epoch seconds of their midnight UTC, so a day count converts to and
+//! from storage exactly.
+
+use chrono::{NaiveDate, NaiveTime, Timelike};
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// The day holding epoch second `secs`, rounding down before 1970.
+pub fn days_from_epoch_seconds(secs: i64) -> Option<i32> {
+    i32::try_from(secs.div_euclid(SECONDS_PER_DAY)).ok()
+}
+
+/// Epoch seconds of the day's midnight UTC, as Date fields store it.
+pub fn epoch_seconds_of_day(days: i32) -> i64 {
+    i64::from(days) * SECONDS_PER_DAY
+}
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid epoch")
+}
+
+pub fn days_of_date(date: NaiveDate) -> Option<i32> {
+    i32::try_from(date.signed_duration_since(epoch()).num_days()).ok()
+}
+
+/// `YYYY-MM-DD`.
+pub fn format_date(days: i32) -> String {
+    epoch()
+        .checked_add_signed(chrono::Duration::days(i64::from(days)))
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| days.to_string())
+}
+
+pub fn parse_date(s: &str) -> Option<i32> {
+    days_of_date(NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()?)
+}
+
+/// `HH:MM:SS.mmm`.
+pub fn format_time(millis: i32) -> String {
+    let millis = i64::from(millis).rem_euclid(MILLIS_PER_DAY);
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1_000 % 60,
+        millis % 1_000
+    )
+}
+
+/// `HH:MM`, `HH:MM:SS` or `HH:MM:SS.fff`; digits past milliseconds are dropped.
+pub fn parse_time(s: &str) -> Option<i32> {
+    let s = s.trim();
+    let time = ["%H:%M:%S%.f", "%H:%M"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(s, format).ok())?;
+    // Leap seconds show up as nanoseconds past 1s; keep them in the same second
+    let millis = (time.nanosecond() / 1_000_000).min(999);
+    Some((time.num_seconds_from_midnight() * 1_000 + millis) as i32)
+}
+
+/// Milliseconds since midnight for a whole-day count of milliseconds.
+pub fn time_from_millis(millis: i64) -> Option<i32> {
+    (0..MILLIS_PER_DAY)
+        .contains(&millis)
+        .then_some(millis as i32)
+}
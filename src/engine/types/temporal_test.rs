@@ -0,0 +1,93 @@
+use crate::engine::types::temporal;
+use crate::engine::types::{LogicalType, ScalarValue};
+use arrow_schema::{DataType, TimeUnit};
+use serde_json::json;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+#[test]
+fn dates_format_and_parse_as_days_since_epoch() {
+    for (days, text) in [
+        (0, "1970-01-01"),
+        (19_787, "2024-03-05"),
+        (-1, "1969-12-31"),
+    ] {
+        assert_eq!(temporal::format_date(days), text);
+        assert_eq!(temporal::parse_date(text), Some(days));
+    }
+    assert_eq!(temporal::parse_date("2024-02-30"), None);
+    assert_eq!(
+        temporal::days_from_epoch_seconds(1_709_659_800),
+        Some(19_787)
+    );
+    assert_eq!(temporal::days_from_epoch_seconds(-1), Some(-1));
+    assert_eq!(temporal::epoch_seconds_of_day(19_787), 1_709_596_800);
+}
+
+#[test]
+fn times_format_and_parse_as_milliseconds_since_midnight() {
+    for (millis, text) in [
+        (0, "00:00:00.000"),
+        (52_215_250, "14:30:15.250"),
+        (86_399_999, "23:59:59.999"),
+    ] {
+        assert_eq!(temporal::format_time(millis), text);
+        assert_eq!(temporal::parse_time(text), Some(millis));
+    }
+    assert_eq!(temporal::parse_time("14:30"), Some(52_200_000));
+    assert_eq!(temporal::parse_time("14:30:15.123456"), Some(52_215_123));
+    assert_eq!(temporal::parse_time("25:00"), None);
+    assert_eq!(temporal::time_from_millis(86_400_000), None);
+}
+
+#[test]
+fn date_and_time_logical_types_map_to_arrow() {
+    assert_eq!(LogicalType::from_str("Date"), Ok(LogicalType::Date));
+    assert_eq!(LogicalType::from_str("Time"), Ok(LogicalType::Time));
+    assert_eq!(LogicalType::Date.to_string(), "Date");
+    assert_eq!(LogicalType::Date.to_arrow_data_type(), DataType::Date32);
+    assert_eq!(
+        LogicalType::Time.to_arrow_data_type(),
+        DataType::Time32(TimeUnit::Millisecond)
+    );
+    assert_eq!(ScalarValue::Date(1).logical_type(), LogicalType::Date);
+    assert_eq!(ScalarValue::Time(1).logical_type(), LogicalType::Time);
+}
+
+#[test]
+fn dates_and_times_render_in_iso_notation() {
+    let date = ScalarValue::Date(19_787);
+    let time = ScalarValue::Time(52_215_250);
+    assert_eq!(date.to_json(), json!("2024-03-05"));
+    assert_eq!(time.to_json(), json!("14:30:15.250"));
+    assert_eq!(serde_json::to_string(&date).unwrap(), "\"2024-03-05\"");
+    assert_eq!(time.to_string_repr(), "14:30:15.250");
+}
+
+#[test]
+fn dates_and_times_compare_in_storage_units_and_against_strings() {
+    let date = ScalarValue::Date(19_787);
+    // Date fields store the epoch seconds of their midnight UTC
+    assert_eq!(date.as_i64(), Some(1_709_596_800));
+    assert_eq!(
+        date.compare(&ScalarValue::Int64(1_709_596_800)),
+        Ordering::Equal
+    );
+    assert_eq!(
+        date.compare(&ScalarValue::Utf8("2024-03-06".into())),
+        Ordering::Less
+    );
+    assert_eq!(ScalarValue::Date(-1).compare(&date), Ordering::Less);
+
+    let time = ScalarValue::Time(52_200_000);
+    assert_eq!(time.as_i64(), Some(52_200_000));
+    assert_eq!(
+        time.compare(&ScalarValue::Utf8("09:00".into())),
+        Ordering::Greater
+    );
+    assert_eq!(
+        time.compare(&ScalarValue::Utf8("14:30:00".into())),
+        Ordering::Equal
+    );
+    assert_ne!(ScalarValue::Date(1), ScalarValue::Time(1));
+}
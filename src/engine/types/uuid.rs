@@ -0,0 +1,75 @@
+//! Helpers for `ScalarValue::Uuid`: the 16 bytes of a UUID in the order its
+//! canonical form writes them, `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`.
+
+/// Length of the canonical hyphenated form.
+pub const CANONICAL_LEN: usize = 36;
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Whether a hyphen sits at byte `i` of the canonical form.
+#[inline]
+fn is_hyphen_at(i: usize) -> bool {
+    matches!(i, 8 | 13 | 18 | 23)
+}
+
+#[inline]
+fn hex_value(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Parses the canonical hyphenated form, in either case.
+pub fn parse(s: &str) -> Option<[u8; 16]> {
+    let text = s.as_bytes();
+    if text.len() != CANONICAL_LEN {
+        return None;
+    }
+    let mut out = [0u8; 16];
+    let mut nibbles = 0usize;
+    for (i, &b) in text.iter().enumerate() {
+        if is_hyphen_at(i) {
+            if b != b'-' {
+                return None;
+            }
+            continue;
+        }
+        let v = hex_value(b)?;
+        out[nibbles / 2] |= if nibbles.is_multiple_of(2) { v << 4 } else { v };
+        nibbles += 1;
+    }
+    Some(out)
+}
+
+/// Whether `s` is exactly what [`format`] writes: canonical and lowercase.
+pub fn is_canonical(s: &str) -> bool {
+    s.len() == CANONICAL_LEN
+        && s.bytes().enumerate().all(|(i, b)| {
+            if is_hyphen_at(i) {
+                b == b'-'
+            } else {
+                b.is_ascii_digit() || (b'a'..=b'f').contains(&b)
+            }
+        })
+}
+
+/// Appends the canonical lowercase form to `out` without allocating.
+pub fn write_canonical(bytes: &[u8; 16], out: &mut String) {
+    for (i, b) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        out.push(HEX[usize::from(b >> 4)] as char);
+        out.push(HEX[usize::from(b & 0x0f)] as char);
+    }
+}
+
+/// The canonical lowercase form.
+pub fn format(bytes: &[u8; 16]) -> String {
+    let mut out = String::with_capacity(CANONICAL_LEN);
+    write_canonical(bytes, &mut out);
+    out
+}
@@ -0,0 +1,63 @@
+use crate::engine::types::uuid;
+use crate::engine::types::{LogicalType, ScalarValue};
+use arrow_schema::DataType;
+use serde_json::json;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+const CANONICAL: &str = "550e8400-e29b-41d4-a716-446655440000";
+const BYTES: [u8; 16] = [
+    0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4, 0xa7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00, 0x00,
+];
+
+#[test]
+fn parses_and_formats_the_canonical_form() {
+    assert_eq!(uuid::parse(CANONICAL), Some(BYTES));
+    assert_eq!(uuid::parse(&CANONICAL.to_uppercase()), Some(BYTES));
+    assert_eq!(uuid::format(&BYTES), CANONICAL);
+    assert!(uuid::is_canonical(CANONICAL));
+    assert!(!uuid::is_canonical(&CANONICAL.to_uppercase()));
+}
+
+#[test]
+fn rejects_other_spellings() {
+    for text in [
+        "550e8400e29b41d4a716446655440000",
+        "{550e8400-e29b-41d4-a716-446655440000}",
+        "550e8400-e29b-41d4-a716-44665544000g",
+        "550e8400-e29b41d4-a716-4466554400000",
+        " 550e8400-e29b-41d4-a716-446655440000",
+        "",
+    ] {
+        assert_eq!(uuid::parse(text), None, "{text}");
+        assert!(!uuid::is_canonical(text), "{text}");
+    }
+}
+
+#[test]
+fn logical_type_parses_and_maps_to_fixed_size_binary() {
+    assert_eq!(LogicalType::from_str("Uuid"), Ok(LogicalType::Uuid));
+    assert_eq!(LogicalType::from_str("UUID"), Ok(LogicalType::Uuid));
+    assert_eq!(LogicalType::Uuid.to_string(), "Uuid");
+    assert_eq!(
+        LogicalType::Uuid.to_arrow_data_type(),
+        DataType::FixedSizeBinary(16)
+    );
+    assert_eq!(ScalarValue::Uuid(BYTES).logical_type(), LogicalType::Uuid);
+}
+
+#[test]
+fn scalar_uuids_render_canonically_and_compare_with_strings() {
+    let value = ScalarValue::Uuid(BYTES);
+    assert_eq!(value.to_string_repr(), CANONICAL);
+    assert_eq!(value.to_json(), json!(CANONICAL));
+    assert_eq!(serde_json::to_value(&value).unwrap(), json!(CANONICAL));
+
+    let upper = ScalarValue::Utf8(CANONICAL.to_uppercase());
+    assert_eq!(upper.as_uuid(), Some(BYTES));
+    assert_eq!(value.compare(&upper), Ordering::Equal);
+    let mut next = BYTES;
+    next[15] = 1;
+    assert_eq!(value.compare(&ScalarValue::Uuid(next)), Ordering::Less);
+    assert_ne!(value, ScalarValue::Uuid(next));
+}
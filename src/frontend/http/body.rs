@@ -0,0 +1,81 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, combinators::BoxBody};
+use hyper::body::{Body, Frame};
+use tokio::io::{AsyncRead, DuplexStream, ReadBuf};
+
+/// Body of every HTTP response: either buffered whole, or streamed.
+pub type HttpBody = BoxBody<Bytes, io::Error>;
+
+/// Bytes a streamed body reads per chunk, and holds in flight between the
+/// command writing it and the client reading it.
+pub(crate) const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+
+/// A body sent in one piece.
+pub fn full_body(data: Vec<u8>) -> HttpBody {
+    Full::new(Bytes::from(data))
+        .map_err(|never| match never {})
+        .boxed()
+}
+
+/// Body fed by a command writing into the other end of `reader`, starting
+/// with the `first` chunk already read from it. The pipe is bounded, so a
+/// client that reads slowly holds up the writer; the body ends when the
+/// writer is dropped.
+pub fn streaming_body(first: Bytes, reader: DuplexStream) -> HttpBody {
+    StreamingBody {
+        first: Some(first).filter(|chunk| !chunk.is_empty()),
+        reader,
+        buf: vec![0; STREAM_CHUNK_BYTES].into_boxed_slice(),
+        done: false,
+    }
+    .boxed()
+}
+
+struct StreamingBody {
+    first: Option<Bytes>,
+    reader: DuplexStream,
+    buf: Box<[u8]>,
+    done: bool,
+}
+
+impl Body for StreamingBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, io::Error>>> {
+        let this = self.get_mut();
+        if let Some(first) = this.first.take() {
+            return Poll::Ready(Some(Ok(Frame::data(first))));
+        }
+        if this.done {
+            return Poll::Ready(None);
+        }
+        let mut buf = ReadBuf::new(&mut this.buf);
+        match Pin::new(&mut this.reader).poll_read(cx, &mut buf) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(e)) => {
+                this.done = true;
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Ready(Ok(())) if buf.filled().is_empty() => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Ready(Ok(())) => {
+                let chunk = Bytes::copy_from_slice(buf.filled());
+                Poll::Ready(Some(Ok(Frame::data(chunk))))
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done && self.first.is_none()
+    }
+}
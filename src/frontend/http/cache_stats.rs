@@ -0,0 +1,29 @@
+use serde_json::{Value, json};
+
+use crate::engine::core::read::cache::{
+    CacheCounters, GlobalColumnBlockCache, GlobalZoneIndexCache, GlobalZoneSurfCache,
+    GlobalZoneXorFilterCache,
+};
+
+/// Counters of the process-wide read caches, as served by `GET /stats/cache`.
+/// Only atomics are read, so polling it never holds up a query.
+pub fn snapshot() -> Value {
+    json!({
+        "column_block": counters_json(GlobalColumnBlockCache::instance().counters()),
+        "zone_index": counters_json(GlobalZoneIndexCache::instance().counters()),
+        "zone_surf": counters_json(GlobalZoneSurfCache::instance().counters()),
+        "zone_xor_filter": counters_json(GlobalZoneXorFilterCache::instance().counters()),
+    })
+}
+
+pub(crate) fn counters_json(counters: CacheCounters) -> Value {
+    json!({
+        "hits": counters.hits,
+        "misses": counters.misses,
+        "reloads": counters.reloads,
+        "evictions": counters.evictions,
+        "hit_ratio": counters.hit_ratio(),
+        "current_bytes": counters.current_bytes,
+        "capacity_bytes": counters.capacity_bytes,
+    })
+}
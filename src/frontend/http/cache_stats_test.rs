@@ -0,0 +1,53 @@
+use serde_json::json;
+
+use crate::engine::core::read::cache::{CacheCounters, GlobalZoneSurfCache};
+use crate::frontend::http::cache_stats::{counters_json, snapshot};
+
+#[test]
+fn snapshot_covers_every_read_cache() {
+    let stats = snapshot();
+    for cache in ["column_block", "zone_index", "zone_surf", "zone_xor_filter"] {
+        let counters = &stats[cache];
+        for field in ["hits", "misses", "reloads", "evictions", "hit_ratio"] {
+            assert!(counters[field].is_number(), "{cache}.{field} missing");
+        }
+    }
+    // The zone index cache is bounded by entry count and reports no bytes
+    assert!(stats["zone_index"]["current_bytes"].is_null());
+    assert!(stats["column_block"]["capacity_bytes"].is_number());
+}
+
+#[test]
+fn counters_render_with_hit_ratio() {
+    let counters = CacheCounters {
+        hits: 3,
+        misses: 1,
+        reloads: 0,
+        evictions: 2,
+        current_bytes: Some(4096),
+        capacity_bytes: Some(8192),
+    };
+    assert_eq!(
+        counters_json(counters),
+        json!({
+            "hits": 3,
+            "misses": 1,
+            "reloads": 0,
+            "evictions": 2,
+            "hit_ratio": 0.75,
+            "current_bytes": 4096,
+            "capacity_bytes": 8192,
+        })
+    );
+}
+
+#[test]
+fn surf_counters_match_stats_without_the_lock() {
+    let cache = GlobalZoneSurfCache::new(1024 * 1024);
+    let counters = cache.counters();
+    let stats = cache.stats();
+    assert_eq!(counters.hits, stats.hits);
+    assert_eq!(counters.misses, stats.misses);
+    assert_eq!(counters.current_bytes, Some(stats.current_bytes));
+    assert_eq!(counters.capacity_bytes, Some(1024 * 1024));
+}
@@ -0,0 +1,202 @@
+use std::io::{self, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use flate2::Compression;
+use flate2::write::{GzEncoder, ZlibEncoder};
+use hyper::header;
+use hyper::http::HeaderMap;
+use tokio::io::AsyncWrite;
+
+use crate::shared::config::CONFIG;
+
+const DEFAULT_MIN_BYTES: usize = 1024;
+
+/// Uncompressed bytes kept from the start of a response, enough to read the
+/// status of an error response.
+pub(crate) const HEAD_BYTES: usize = 500;
+
+/// A `Content-Encoding` the HTTP frontend can produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentCoding {
+    Gzip,
+    /// zlib-wrapped deflate, as HTTP defines `deflate`
+    Deflate,
+}
+
+impl ContentCoding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentCoding::Gzip => "gzip",
+            ContentCoding::Deflate => "deflate",
+        }
+    }
+
+    /// Picks the coding of a response from the request's `Accept-Encoding`.
+    /// `None` when compression is off or the client accepts neither coding.
+    pub fn negotiate(headers: &HeaderMap) -> Option<Self> {
+        if !enabled() {
+            return None;
+        }
+        Self::from_accept_encoding(headers)
+    }
+
+    /// Parses `Accept-Encoding`, honoring q-values and `*`. Gzip wins a tie.
+    pub(crate) fn from_accept_encoding(headers: &HeaderMap) -> Option<Self> {
+        let (mut gzip, mut deflate, mut wildcard) = (None, None, None);
+        for value in headers.get_all(header::ACCEPT_ENCODING) {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            for item in value.split(',') {
+                let mut parts = item.split(';');
+                let name = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map(|q| q.trim().parse::<f32>().unwrap_or(0.0))
+                    .unwrap_or(1.0);
+                match name.as_str() {
+                    "gzip" | "x-gzip" => gzip = Some(quality),
+                    "deflate" => deflate = Some(quality),
+                    "*" => wildcard = Some(quality),
+                    _ => {}
+                }
+            }
+        }
+        let gzip = gzip.or(wildcard).unwrap_or(0.0);
+        let deflate = deflate.or(wildcard).unwrap_or(0.0);
+        if gzip > 0.0 && gzip >= deflate {
+            Some(ContentCoding::Gzip)
+        } else if deflate > 0.0 {
+            Some(ContentCoding::Deflate)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether `http.compression` is on; it is unless set to false.
+pub fn enabled() -> bool {
+    CONFIG.http.as_ref().is_none_or(|cfg| cfg.compression)
+}
+
+/// Responses smaller than `http.compression_min_bytes` go out uncompressed.
+pub fn min_bytes() -> usize {
+    CONFIG
+        .http
+        .as_ref()
+        .and_then(|cfg| cfg.compression_min_bytes)
+        .unwrap_or(DEFAULT_MIN_BYTES)
+}
+
+enum Encoder {
+    Gzip(GzEncoder<Vec<u8>>),
+    Deflate(ZlibEncoder<Vec<u8>>),
+}
+
+impl Encoder {
+    fn new(coding: ContentCoding) -> Self {
+        match coding {
+            ContentCoding::Gzip => {
+                Encoder::Gzip(GzEncoder::new(Vec::new(), Compression::default()))
+            }
+            ContentCoding::Deflate => {
+                Encoder::Deflate(ZlibEncoder::new(Vec::new(), Compression::default()))
+            }
+        }
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.write_all(buf),
+            Encoder::Deflate(encoder) => encoder.write_all(buf),
+        }
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.finish(),
+            Encoder::Deflate(encoder) => encoder.finish(),
+        }
+    }
+}
+
+/// Response body that compresses command output as it is written.
+///
+/// Output is held as written until it reaches `min_bytes`; from then on it
+/// goes through the encoder, so the frames of a large query result are
+/// compressed one by one instead of being buffered whole. Flushes are
+/// ignored: the body is only sent once complete, and flushing the encoder
+/// would just cost compression ratio.
+pub struct CompressingWriter {
+    coding: Option<ContentCoding>,
+    min_bytes: usize,
+    pending: Vec<u8>,
+    encoder: Option<Encoder>,
+    head: Vec<u8>,
+}
+
+impl CompressingWriter {
+    /// With `coding` unset, output is only collected.
+    pub fn new(coding: Option<ContentCoding>, min_bytes: usize) -> Self {
+        Self {
+            coding,
+            min_bytes,
+            pending: Vec::new(),
+            encoder: None,
+            head: Vec::new(),
+        }
+    }
+
+    /// Up to [`HEAD_BYTES`] of the output as written, before compression.
+    pub fn head(&self) -> &[u8] {
+        &self.head
+    }
+
+    /// Returns the body and the coding it is in; `None` when it stayed
+    /// below the threshold and is uncompressed.
+    pub fn finish(self) -> io::Result<(Vec<u8>, Option<ContentCoding>)> {
+        match self.encoder {
+            Some(encoder) => Ok((encoder.finish()?, self.coding)),
+            None => Ok((self.pending, None)),
+        }
+    }
+
+    fn write_bytes(&mut self, buf: &[u8]) -> io::Result<()> {
+        if self.head.len() < HEAD_BYTES {
+            let take = (HEAD_BYTES - self.head.len()).min(buf.len());
+            self.head.extend_from_slice(&buf[..take]);
+        }
+        if let Some(encoder) = &mut self.encoder {
+            return encoder.write_all(buf);
+        }
+        self.pending.extend_from_slice(buf);
+        if let Some(coding) = self.coding
+            && self.pending.len() >= self.min_bytes
+        {
+            let mut encoder = Encoder::new(coding);
+            encoder.write_all(&self.pending)?;
+            self.pending = Vec::new();
+            self.encoder = Some(encoder);
+        }
+        Ok(())
+    }
+}
+
+impl AsyncWrite for CompressingWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().write_bytes(buf).map(|()| buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::Read;
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+use hyper::http::{HeaderMap, HeaderValue};
+use tokio::io::AsyncWriteExt;
+
+use crate::frontend::http::compression::{CompressingWriter, ContentCoding, HEAD_BYTES};
+
+fn accept(value: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("Accept-Encoding", HeaderValue::from_static(value));
+    headers
+}
+
+fn frames(count: usize) -> Vec<Vec<u8>> {
+    (0..count)
+        .map(|i| format!("{{\"type\":\"row\",\"id\":{i},\"name\":\"event-{i}\"}}\n").into_bytes())
+        .collect()
+}
+
+async fn write_frames(writer: &mut CompressingWriter, frames: &[Vec<u8>]) {
+    for frame in frames {
+        writer.write_all(frame).await.unwrap();
+        writer.flush().await.unwrap();
+    }
+}
+
+#[test]
+fn accept_encoding_prefers_gzip() {
+    assert_eq!(
+        ContentCoding::from_accept_encoding(&accept("gzip, deflate, br")),
+        Some(ContentCoding::Gzip)
+    );
+    assert_eq!(
+        ContentCoding::from_accept_encoding(&accept("deflate")),
+        Some(ContentCoding::Deflate)
+    );
+    assert_eq!(
+        ContentCoding::from_accept_encoding(&accept("*")),
+        Some(ContentCoding::Gzip)
+    );
+}
+
+#[test]
+fn accept_encoding_honors_quality_values() {
+    assert_eq!(
+        ContentCoding::from_accept_encoding(&accept("gzip;q=0.2, deflate;q=0.8")),
+        Some(ContentCoding::Deflate)
+    );
+    assert_eq!(
+        ContentCoding::from_accept_encoding(&accept("gzip;q=0, *;q=0.5")),
+        Some(ContentCoding::Deflate)
+    );
+    assert_eq!(
+        ContentCoding::from_accept_encoding(&accept("br, identity")),
+        None
+    );
+    assert_eq!(ContentCoding::from_accept_encoding(&HeaderMap::new()), None);
+}
+
+#[tokio::test]
+async fn output_below_threshold_stays_uncompressed() {
+    let frames = frames(3);
+    let mut writer = CompressingWriter::new(Some(ContentCoding::Gzip), 1 << 20);
+    write_frames(&mut writer, &frames).await;
+
+    let (body, coding) = writer.finish().unwrap();
+    assert_eq!(coding, None);
+    assert_eq!(body, frames.concat());
+}
+
+#[tokio::test]
+async fn output_past_threshold_is_gzipped() {
+    let frames = frames(500);
+    let mut writer = CompressingWriter::new(Some(ContentCoding::Gzip), 1024);
+    write_frames(&mut writer, &frames).await;
+    let raw = frames.concat();
+    assert_eq!(writer.head(), &raw[..HEAD_BYTES]);
+
+    let (body, coding) = writer.finish().unwrap();
+    assert_eq!(coding, Some(ContentCoding::Gzip));
+    assert!(body.len() < raw.len() / 2);
+    let mut decoded = Vec::new();
+    GzDecoder::new(body.as_slice())
+        .read_to_end(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, raw);
+}
+
+#[tokio::test]
+async fn deflate_uses_zlib_framing() {
+    let frames = frames(100);
+    let mut writer = CompressingWriter::new(Some(ContentCoding::Deflate), 0);
+    write_frames(&mut writer, &frames).await;
+
+    let (body, coding) = writer.finish().unwrap();
+    assert_eq!(coding, Some(ContentCoding::Deflate));
+    let mut decoded = Vec::new();
+    ZlibDecoder::new(body.as_slice())
+        .read_to_end(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, frames.concat());
+}
+
+#[tokio::test]
+async fn no_coding_only_collects() {
+    let frames = frames(500);
+    let mut writer = CompressingWriter::new(None, 0);
+    write_frames(&mut writer, &frames).await;
+
+    let (body, coding) = writer.finish().unwrap();
+    assert_eq!(coding, None);
+    assert_eq!(body, frames.concat());
+}
@@ -1,24 +1,101 @@
 use crate::command::dispatcher::dispatch_command;
 use crate::command::parser::parse_command;
 use crate::command::types::Command;
-use crate::engine::auth::AuthManager;
+use crate::engine::auth::{AuthManager, BYPASS_USER_ID};
 use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
+use crate::frontend::http::body::{HttpBody, STREAM_CHUNK_BYTES, full_body, streaming_body};
+use crate::frontend::http::cache_stats;
+use crate::frontend::http::compression::{self, CompressingWriter, ContentCoding};
 use crate::frontend::http::json_command::JsonCommand;
 use crate::frontend::server_state::ServerState;
 use crate::shared::config::CONFIG;
+use crate::shared::response::render::{Renderer, StreamingFormat};
 use crate::shared::response::{
-    ArrowRenderer, JsonRenderer, Response as ResponseType, StatusCode as ResponseStatusCode,
-    render::Renderer, unix::UnixRenderer,
+    ArrowRenderer, CsvRenderer, ErrorCode, JsonRenderer, MessagePackRenderer, NdjsonRenderer,
+    Response as ResponseType, StatusCode as ResponseStatusCode, unix::UnixRenderer,
 };
 use bytes::Bytes;
-use http_body_util::{BodyExt, Full};
-use hyper::http::HeaderMap;
+use http_body_util::BodyExt;
+use hyper::http::{HeaderMap, Uri};
 use hyper::{Request, Response, StatusCode, body::Incoming, header};
+use serde::Deserialize;
 use std::{convert::Infallible, sync::Arc, time::Instant};
+use tokio::io::AsyncReadExt;
 use tokio::sync::RwLock;
 use tracing::info;
 
+/// Response header carrying the machine-readable error code on error responses.
+pub(crate) const ERROR_CODE_HEADER: &str = "X-Error-Code";
+
+/// Response header telling whether the failed request may be retried unchanged.
+pub(crate) const ERROR_RETRYABLE_HEADER: &str = "X-Error-Retryable";
+
+/// Sets the error code headers on an error response.
+pub(crate) trait ErrorCodeHeaders {
+    fn error_code(self, code: ErrorCode) -> Self;
+}
+
+impl ErrorCodeHeaders for hyper::http::response::Builder {
+    fn error_code(self, code: ErrorCode) -> Self {
+        self.header(ERROR_CODE_HEADER, code.as_str())
+            .header(ERROR_RETRYABLE_HEADER, code.is_retryable().to_string())
+    }
+}
+
+/// Output formats a request may ask for with `?format=`.
+const OUTPUT_FORMATS: [&str; 6] = ["json", "arrow", "csv", "msgpack", "ndjson", "text"];
+
+/// The output format of a request: its `format` query parameter, overriding
+/// `server.output_format`.
+pub(crate) fn output_format(uri: &Uri) -> Result<String, String> {
+    let requested = uri.query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("format="))
+    });
+    match requested {
+        None => Ok(CONFIG.server.output_format.clone()),
+        Some(format) if OUTPUT_FORMATS.contains(&format) => Ok(format.to_string()),
+        Some(format) => Err(format!(
+            "Unknown format '{format}'. Use: {}",
+            OUTPUT_FORMATS.join(", ")
+        )),
+    }
+}
+
+/// Renderer of `/command` responses; anything but a structured format is text.
+fn line_renderer(format: &str) -> Arc<dyn Renderer + Send + Sync> {
+    match format {
+        "json" => Arc::new(JsonRenderer),
+        "arrow" => Arc::new(ArrowRenderer),
+        "csv" => Arc::new(CsvRenderer::from_config()),
+        "msgpack" => Arc::new(MessagePackRenderer),
+        "ndjson" => Arc::new(NdjsonRenderer),
+        _ => Arc::new(UnixRenderer),
+    }
+}
+
+/// Renderer of `/json-command` responses, which default to JSON.
+fn json_renderer(format: &str) -> Arc<dyn Renderer + Send + Sync> {
+    match format {
+        "arrow" => Arc::new(ArrowRenderer),
+        "csv" => Arc::new(CsvRenderer::from_config()),
+        "msgpack" => Arc::new(MessagePackRenderer),
+        "ndjson" => Arc::new(NdjsonRenderer),
+        _ => Arc::new(JsonRenderer),
+    }
+}
+
+/// Error responses are JSON, except from the MessagePack renderer.
+fn error_content_type(renderer: &dyn Renderer) -> &'static str {
+    if renderer.streaming_format() == StreamingFormat::MsgPack {
+        "application/msgpack"
+    } else {
+        "application/json"
+    }
+}
+
 pub(crate) fn is_protected_context(context_id: &str) -> bool {
     context_id.starts_with("__system_")
 }
@@ -33,7 +110,7 @@ pub(crate) fn command_targets_protected_context(cmd: &Command) -> bool {
             .unwrap_or(false),
         Replay { context_id, .. } => is_protected_context(context_id),
         RememberQuery { spec } => command_targets_protected_context(&spec.query),
-        Compare { queries } => queries.iter().any(|q| {
+        Compare { queries } | Union { queries, .. } => queries.iter().any(|q| {
             q.context_id
                 .as_deref()
                 .map(is_protected_context)
@@ -204,7 +281,7 @@ pub async fn handle_line_command(
     shard_manager: Arc<ShardManager>,
     server_state: Arc<ServerState>,
     auth_manager: Option<Arc<AuthManager>>,
-) -> Result<Response<Full<Bytes>>, Infallible> {
+) -> Result<Response<HttpBody>, Infallible> {
     if req.method() != hyper::Method::POST {
         return method_not_allowed();
     }
@@ -217,15 +294,19 @@ pub async fn handle_line_command(
 
     // Extract auth headers before consuming the request body
     let auth_from_headers = extract_auth_from_headers(&req);
+    let coding = ContentCoding::negotiate(req.headers());
+    let format = match output_format(req.uri()) {
+        Ok(format) => format,
+        Err(e) => {
+            let renderer = line_renderer(&CONFIG.server.output_format);
+            return render_error(&e, StatusCode::BAD_REQUEST, renderer);
+        }
+    };
 
     // Use to_bytes() directly for more efficient body collection
     let body = req.into_body().collect().await.unwrap().to_bytes();
     let input = String::from_utf8_lossy(&body).trim().to_string();
-    let renderer: Arc<dyn Renderer + Send + Sync> = match CONFIG.server.output_format.as_str() {
-        "json" => Arc::new(JsonRenderer),
-        "arrow" => Arc::new(ArrowRenderer),
-        _ => Arc::new(UnixRenderer),
-    };
+    let renderer = line_renderer(&format);
 
     if input.is_empty() {
         return render_error("Empty command", StatusCode::BAD_REQUEST, renderer);
@@ -249,6 +330,21 @@ pub async fn handle_line_command(
 
     match parse_command(command_to_parse) {
         Ok(cmd) => {
+            // Queries hold their execution slot until the response is built
+            let _permit = match server_state
+                .admit(&cmd, authenticated_user_id.as_deref())
+                .await
+            {
+                Ok(permit) => permit,
+                Err(e) => {
+                    return render_coded_error(
+                        &e.to_string(),
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        e.code(),
+                        renderer,
+                    );
+                }
+            };
             // Increment pending operations before dispatch
             server_state.increment_pending();
             let start = Instant::now();
@@ -259,6 +355,8 @@ pub async fn handle_line_command(
                 auth_manager.as_ref(),
                 authenticated_user_id.as_deref(),
                 renderer,
+                &format,
+                coding,
             )
             .await;
             let execution_time_ms = start.elapsed().as_secs_f64() * 1000.0;
@@ -266,7 +364,12 @@ pub async fn handle_line_command(
             server_state.decrement_pending();
             add_execution_time_header(result, execution_time_ms)
         }
-        Err(e) => render_error(&e.to_string(), StatusCode::BAD_REQUEST, renderer),
+        Err(e) => render_coded_error(
+            &e.to_string(),
+            StatusCode::BAD_REQUEST,
+            e.code(),
+            renderer,
+        ),
     }
 }
 
@@ -276,7 +379,7 @@ pub async fn handle_json_command(
     shard_manager: Arc<ShardManager>,
     server_state: Arc<ServerState>,
     auth_manager: Option<Arc<AuthManager>>,
-) -> Result<Response<Full<Bytes>>, Infallible> {
+) -> Result<Response<HttpBody>, Infallible> {
     if req.method() != hyper::Method::POST {
         return method_not_allowed();
     }
@@ -289,14 +392,19 @@ pub async fn handle_json_command(
 
     // Extract auth headers before consuming the request body
     let auth_from_headers = extract_auth_from_headers(&req);
+    let coding = ContentCoding::negotiate(req.headers());
+    let format = match output_format(req.uri()) {
+        Ok(format) => format,
+        Err(e) => {
+            let renderer = json_renderer(&CONFIG.server.output_format);
+            return render_error(&e, StatusCode::BAD_REQUEST, renderer);
+        }
+    };
 
     // Use to_bytes() directly for more efficient body collection
     let body = req.into_body().collect().await.unwrap().to_bytes();
     let body_str = String::from_utf8_lossy(&body);
-    let renderer: Arc<dyn Renderer + Send + Sync> = match CONFIG.server.output_format.as_str() {
-        "arrow" => Arc::new(ArrowRenderer),
-        _ => Arc::new(JsonRenderer),
-    };
+    let renderer = json_renderer(&format);
 
     match sonic_rs::from_slice::<JsonCommand>(&body) {
         Ok(json_cmd) => {
@@ -325,10 +433,11 @@ pub async fn handle_json_command(
                             Ok(_) => {
                                 // Authentication successful, proceed
                             }
-                            Err(_) => {
-                                return render_error(
+                            Err(e) => {
+                                return render_coded_error(
                                     "Authentication failed",
                                     StatusCode::UNAUTHORIZED,
+                                    ErrorCode::from(&e),
                                     renderer,
                                 );
                             }
@@ -346,6 +455,21 @@ pub async fn handle_json_command(
 
             let cmd: Command = json_cmd.into();
             info!("Received JSON command: {:?}", cmd);
+            // Queries hold their execution slot until the response is built
+            let _permit = match server_state
+                .admit(&cmd, authenticated_user_id.as_deref())
+                .await
+            {
+                Ok(permit) => permit,
+                Err(e) => {
+                    return render_coded_error(
+                        &e.to_string(),
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        e.code(),
+                        renderer,
+                    );
+                }
+            };
             // Increment pending operations before dispatch
             server_state.increment_pending();
             let start = Instant::now();
@@ -356,6 +480,8 @@ pub async fn handle_json_command(
                 auth_manager.as_ref(),
                 authenticated_user_id.as_deref(),
                 renderer,
+                &format,
+                coding,
             )
             .await;
             let execution_time_ms = start.elapsed().as_secs_f64() * 1000.0;
@@ -363,14 +489,64 @@ pub async fn handle_json_command(
             server_state.decrement_pending();
             add_execution_time_header(result, execution_time_ms)
         }
-        Err(e) => render_error(
+        Err(e) => render_coded_error(
             &format!("Invalid JSON command: {e}"),
             StatusCode::BAD_REQUEST,
+            ErrorCode::ParseError,
             renderer,
         ),
     }
 }
 
+/// `GET /stats/cache`: hit, miss and eviction counters and byte usage of the
+/// read caches, as JSON. Admin only; with signed headers, the signature is
+/// over the request path.
+pub async fn handle_cache_stats(
+    req: Request<Incoming>,
+    auth_manager: Option<Arc<AuthManager>>,
+) -> Result<Response<HttpBody>, Infallible> {
+    if req.method() != hyper::Method::GET {
+        return method_not_allowed();
+    }
+    if !is_authorized(&req) {
+        return unauthorized();
+    }
+
+    let client_ip = extract_client_ip(&req);
+    let auth_from_headers = extract_auth_from_headers(&req);
+    let renderer: Arc<dyn Renderer + Send + Sync> = Arc::new(JsonRenderer);
+    let path = req.uri().path();
+    let Some((_, user_id)) = check_auth_with_headers(
+        path,
+        auth_from_headers,
+        auth_manager.as_ref(),
+        client_ip.as_deref(),
+    )
+    .await
+    else {
+        return render_error("Authentication failed", StatusCode::UNAUTHORIZED, renderer);
+    };
+    if let Some(auth_mgr) = &auth_manager
+        && user_id != BYPASS_USER_ID
+        && !auth_mgr.is_admin(&user_id).await
+    {
+        return render_error(
+            "Only admin users can view cache stats",
+            StatusCode::FORBIDDEN,
+            renderer,
+        );
+    }
+
+    let mut body = serde_json::to_vec(&cache_stats::snapshot()).unwrap_or_default();
+    body.push(b'\n');
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(full_body(body))
+        .unwrap())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn dispatch_and_respond(
     cmd: Command,
     registry: Arc<RwLock<SchemaRegistry>>,
@@ -378,7 +554,9 @@ pub(crate) async fn dispatch_and_respond(
     auth_manager: Option<&Arc<AuthManager>>,
     user_id: Option<&str>,
     renderer: Arc<dyn Renderer + Send + Sync>,
-) -> Result<Response<Full<Bytes>>, Infallible> {
+    format: &str,
+    coding: Option<ContentCoding>,
+) -> Result<Response<HttpBody>, Infallible> {
     if command_targets_protected_context(&cmd) {
         let resp = ResponseType::error(
             ResponseStatusCode::Forbidden,
@@ -386,12 +564,29 @@ pub(crate) async fn dispatch_and_respond(
         );
         return Ok(Response::builder()
             .status(StatusCode::FORBIDDEN)
-            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .header(
+                hyper::header::CONTENT_TYPE,
+                error_content_type(renderer.as_ref()),
+            )
+            .error_code(ErrorCode::PermissionDenied)
             .body(full_body(renderer.render(&resp)))
             .unwrap());
     }
 
-    let mut output = Vec::new();
+    if renderer.streaming_format() == StreamingFormat::Ndjson {
+        return stream_and_respond(
+            cmd,
+            registry,
+            shard_manager,
+            auth_manager.cloned(),
+            user_id.map(str::to_string),
+            renderer,
+        )
+        .await;
+    }
+
+    // Query output is compressed frame by frame as the response writer produces it
+    let mut output = CompressingWriter::new(coding, compression::min_bytes());
     let result = dispatch_command(
         &cmd,
         &mut output,
@@ -406,44 +601,130 @@ pub(crate) async fn dispatch_and_respond(
     if let Err(e) = result {
         return Ok(Response::builder()
             .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .error_code(ErrorCode::Internal)
             .body(full_body(format!("Execution error: {}", e).into_bytes()))
             .unwrap());
     }
 
     // Extract HTTP status code from response
-    // Error responses are always JSON (even with ArrowRenderer) and contain a "status" field
-    let http_status = extract_http_status_from_response(&output);
+    // Error responses are JSON (even with ArrowRenderer) or MessagePack, and contain a "status" field
+    let msgpack = renderer.streaming_format() == StreamingFormat::MsgPack;
+    let http_status = if msgpack {
+        extract_http_status_from_msgpack(output.head())
+    } else {
+        extract_http_status_from_response(output.head())
+    };
 
     // Set content type based on output format, but note that error responses
     // from ArrowRenderer are JSON even when output_format is "arrow"
     let content_type = if http_status != hyper::StatusCode::OK {
-        // Error responses are always JSON (even from ArrowRenderer)
-        "application/json"
+        error_content_type(renderer.as_ref())
     } else {
-        match CONFIG.server.output_format.as_str() {
+        match format {
             "json" => "application/json",
             "arrow" => "application/vnd.apache.arrow.stream",
+            "csv" => "text/csv",
+            "msgpack" => "application/msgpack",
             _ => "text/plain",
         }
     };
 
+    let (body, coding) = match output.finish() {
+        Ok(finished) => finished,
+        Err(e) => {
+            return Ok(Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .error_code(ErrorCode::Internal)
+                .body(full_body(format!("Compression error: {}", e).into_bytes()))
+                .unwrap());
+        }
+    };
+
+    let mut builder = Response::builder()
+        .status(http_status)
+        .header(hyper::header::CONTENT_TYPE, content_type);
+    if compression::enabled() {
+        builder = builder.header(hyper::header::VARY, "Accept-Encoding");
+    }
+    if let Some(coding) = coding {
+        builder = builder.header(hyper::header::CONTENT_ENCODING, coding.as_str());
+    }
+    Ok(builder.body(full_body(body)).unwrap())
+}
+
+/// Sends the output of `cmd` as the command writes it, instead of buffering
+/// the whole response. The command runs in its own task and writes into a
+/// bounded pipe that the response body reads from, so a slow client slows
+/// the query down rather than piling its results up in memory. The status
+/// is read from the first chunk: errors raised before any row is written
+/// still get their HTTP status. Streamed bodies are not compressed.
+async fn stream_and_respond(
+    cmd: Command,
+    registry: Arc<RwLock<SchemaRegistry>>,
+    shard_manager: Arc<ShardManager>,
+    auth_manager: Option<Arc<AuthManager>>,
+    user_id: Option<String>,
+    renderer: Arc<dyn Renderer + Send + Sync>,
+) -> Result<Response<HttpBody>, Infallible> {
+    let (mut writer, mut reader) = tokio::io::duplex(STREAM_CHUNK_BYTES);
+    let command_renderer = Arc::clone(&renderer);
+    let command = tokio::spawn(async move {
+        dispatch_command(
+            &cmd,
+            &mut writer,
+            &shard_manager,
+            &registry,
+            auth_manager.as_ref(),
+            user_id.as_deref(),
+            command_renderer.as_ref(),
+        )
+        .await
+    });
+
+    let mut first = vec![0; STREAM_CHUNK_BYTES];
+    let read = reader.read(&mut first).await.unwrap_or(0);
+    if read == 0 {
+        // Nothing was written: the command finished without output, or failed
+        let error = match command.await {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(e) = error {
+            return Ok(Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .error_code(ErrorCode::Internal)
+                .body(full_body(format!("Execution error: {}", e).into_bytes()))
+                .unwrap());
+        }
+    }
+    first.truncate(read);
+
+    let http_status = extract_http_status_from_ndjson(&first);
+    let content_type = if http_status != hyper::StatusCode::OK {
+        error_content_type(renderer.as_ref())
+    } else {
+        "application/x-ndjson"
+    };
     Ok(Response::builder()
         .status(http_status)
         .header(hyper::header::CONTENT_TYPE, content_type)
-        .body(full_body(output))
+        .body(streaming_body(Bytes::from(first), reader))
         .unwrap())
 }
 
-fn unauthorized() -> Result<Response<Full<Bytes>>, Infallible> {
+fn unauthorized() -> Result<Response<HttpBody>, Infallible> {
     Ok(Response::builder()
         .status(StatusCode::UNAUTHORIZED)
+        .error_code(ErrorCode::Unauthenticated)
         .body(full_body(b"Unauthorized".to_vec()))
         .unwrap())
 }
 
-fn method_not_allowed() -> Result<Response<Full<Bytes>>, Infallible> {
+fn method_not_allowed() -> Result<Response<HttpBody>, Infallible> {
     Ok(Response::builder()
         .status(StatusCode::METHOD_NOT_ALLOWED)
+        .error_code(ErrorCode::InvalidRequest)
         .body(full_body(b"Method Not Allowed".to_vec()))
         .unwrap())
 }
@@ -452,22 +733,34 @@ fn render_error(
     msg: &str,
     status: StatusCode,
     renderer: Arc<dyn Renderer + Send + Sync>,
-) -> Result<Response<Full<Bytes>>, Infallible> {
-    let resp = ResponseType::error(ResponseStatusCode::from(status), msg.to_string());
+) -> Result<Response<HttpBody>, Infallible> {
+    let code = ErrorCode::from_status(ResponseStatusCode::from(status));
+    render_coded_error(msg, status, code, renderer)
+}
+
+fn render_coded_error(
+    msg: &str,
+    status: StatusCode,
+    code: ErrorCode,
+    renderer: Arc<dyn Renderer + Send + Sync>,
+) -> Result<Response<HttpBody>, Infallible> {
+    let resp =
+        ResponseType::error_with_code(ResponseStatusCode::from(status), code, msg.to_string());
     let body = renderer.render(&resp);
     Ok(Response::builder()
         .status(status)
-        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .header(
+            hyper::header::CONTENT_TYPE,
+            error_content_type(renderer.as_ref()),
+        )
+        .error_code(code)
         .body(full_body(body))
         .unwrap())
 }
 
-fn full_body(data: Vec<u8>) -> Full<Bytes> {
-    Full::new(Bytes::from(data))
-}
-
 /// Extract HTTP status code from response bytes
 /// Error responses are always JSON (even with ArrowRenderer) and contain a "status" field
+/// Reads at most the first 500 bytes, so the uncompressed head of a response is enough
 /// Successful responses may be Arrow binary format or large JSON, so we default to OK
 fn extract_http_status_from_response(output: &[u8]) -> hyper::StatusCode {
     // Early return for non-JSON responses (Arrow, plain text, etc.)
@@ -477,9 +770,11 @@ fn extract_http_status_from_response(output: &[u8]) -> hyper::StatusCode {
 
     // Fast path: Check if response likely contains error status field
     // Error responses typically start with {"status": or have "status" near the beginning
-    // Only parse if we see the pattern "status" in the first 50 bytes
+    // Only parse if we see the pattern "status" in the first 50 bytes.
+    // Small responses skip this check: the Arrow fallback sorts keys, so "code" and
+    // "message" may push "status" past the window.
     let check_len = output.len().min(50);
-    if !output[..check_len].windows(6).any(|w| w == b"status") {
+    if output.len() >= 500 && !output[..check_len].windows(6).any(|w| w == b"status") {
         // No "status" field found, assume success
         return hyper::StatusCode::OK;
     }
@@ -507,6 +802,38 @@ fn extract_http_status_from_response(output: &[u8]) -> hyper::StatusCode {
     hyper::StatusCode::OK
 }
 
+/// Extract HTTP status code from NDJSON output
+/// Rows are objects too, so only a first line shaped like a complete response
+/// (with "status", "message" and "results") is read as an error
+fn extract_http_status_from_ndjson(output: &[u8]) -> hyper::StatusCode {
+    let first_line = output.split(|&b| b == b'\n').next().unwrap_or_default();
+    let Ok(json) = sonic_rs::from_slice::<serde_json::Value>(first_line) else {
+        return hyper::StatusCode::OK;
+    };
+    match json.get("status").and_then(|status| status.as_u64()) {
+        Some(status) if json.get("message").is_some() && json.get("results").is_some() => {
+            map_status_code_to_http(status)
+        }
+        _ => hyper::StatusCode::OK,
+    }
+}
+
+/// Extract HTTP status code from a MessagePack response
+/// Complete responses are a map with a "status" field; streamed query frames
+/// have none, or are cut off in `output`, and count as OK
+fn extract_http_status_from_msgpack(output: &[u8]) -> hyper::StatusCode {
+    #[derive(Deserialize)]
+    struct StatusField {
+        status: Option<u64>,
+    }
+    match rmp_serde::from_slice::<StatusField>(output) {
+        Ok(StatusField {
+            status: Some(status),
+        }) => map_status_code_to_http(status),
+        _ => hyper::StatusCode::OK,
+    }
+}
+
 /// Map internal status code number to HTTP status code
 fn map_status_code_to_http(status: u64) -> hyper::StatusCode {
     match status {
@@ -522,9 +849,9 @@ fn map_status_code_to_http(status: u64) -> hyper::StatusCode {
 }
 
 fn add_execution_time_header(
-    response: Result<Response<Full<Bytes>>, Infallible>,
+    response: Result<Response<HttpBody>, Infallible>,
     execution_time_ms: f64,
-) -> Result<Response<Full<Bytes>>, Infallible> {
+) -> Result<Response<HttpBody>, Infallible> {
     match response {
         Ok(mut resp) => {
             let headers = resp.headers_mut();
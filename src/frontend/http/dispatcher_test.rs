@@ -3,11 +3,12 @@ use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
 use crate::frontend::http::dispatcher::{
     command_targets_protected_context, dispatch_and_respond, extract_client_ip_from_header_map,
+    output_format,
 };
 use crate::logging::init_for_tests;
 use crate::shared::response::{JsonRenderer, render::Renderer};
 use hyper::StatusCode;
-use hyper::http::{HeaderMap, HeaderValue};
+use hyper::http::{HeaderMap, HeaderValue, Uri};
 use std::sync::Arc;
 use tempfile::tempdir;
 use tokio::sync::RwLock;
@@ -181,13 +182,140 @@ async fn test_protected_context_rejected() {
         time_bucket: None,
         group_by: None,
         event_sequence: None,
+        latest_per: None,
+        dedup: None,
+        omit_nulls: false,
+        with_total: false,
+        raw_strings: false,
+        disabled_pruners: Vec::new(),
+        allow_full_scan: false,
+        unnest: None,
     };
 
     assert!(command_targets_protected_context(&cmd));
 
-    let response = dispatch_and_respond(cmd, registry, shard_manager, None, None, test_renderer())
+    let response = dispatch_and_respond(
+        cmd,
+        registry,
+        shard_manager,
+        None,
+        None,
+        test_renderer(),
+        "json",
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response.headers()["X-Error-Code"], "PERMISSION_DENIED");
+    assert_eq!(response.headers()["X-Error-Retryable"], "false");
+}
+
+#[test]
+fn test_output_format_from_query_parameter() {
+    let uri = |s: &'static str| Uri::from_static(s);
+
+    assert_eq!(
+        output_format(&uri("/command?format=msgpack")).unwrap(),
+        "msgpack"
+    );
+    assert_eq!(
+        output_format(&uri("/command?token=abc&format=csv")).unwrap(),
+        "csv"
+    );
+    assert_eq!(
+        output_format(&uri("/command")).unwrap(),
+        crate::shared::config::CONFIG.server.output_format
+    );
+    let err = output_format(&uri("/command?format=xml")).unwrap_err();
+    assert!(err.contains("Unknown format 'xml'"));
+}
+
+#[tokio::test]
+async fn test_ndjson_responses_stream_rows_and_keep_error_status() {
+    use crate::command::handlers::store;
+    use crate::shared::response::NdjsonRenderer;
+    use crate::test_helpers::factories::{CommandFactory, SchemaRegistryFactory};
+    use http_body_util::BodyExt;
+    init_for_tests();
+
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+    let factory = SchemaRegistryFactory::new();
+    factory
+        .define_with_fields("http_nd_event", &[("id", "int")])
+        .await
+        .unwrap();
+    let registry = factory.registry();
+
+    for id in 1..=2 {
+        let store_cmd = CommandFactory::store()
+            .with_event_type("http_nd_event")
+            .with_context_id("ctx-http")
+            .with_payload(serde_json::json!({ "id": id }))
+            .create();
+        let mut sink = Vec::new();
+        store::handle(
+            &store_cmd,
+            &shard_manager,
+            &registry,
+            None,
+            None,
+            &mut sink,
+            &JsonRenderer,
+        )
         .await
         .unwrap();
+    }
 
-    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let replay = |context_id: &str| {
+        CommandFactory::replay()
+            .with_event_type("http_nd_event")
+            .with_context_id(context_id)
+            .create()
+    };
+    let renderer: Arc<dyn Renderer + Send + Sync> = Arc::new(NdjsonRenderer);
+
+    let response = dispatch_and_respond(
+        replay("ctx-http"),
+        Arc::clone(&registry),
+        Arc::clone(&shard_manager),
+        None,
+        None,
+        Arc::clone(&renderer),
+        "ndjson",
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["Content-Type"], "application/x-ndjson");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let ids: Vec<i64> = body
+        .lines()
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line).unwrap()["id"]
+                .as_i64()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(ids, vec![1, 2]);
+
+    let response = dispatch_and_respond(
+        replay(" "),
+        registry,
+        shard_manager,
+        None,
+        None,
+        renderer,
+        "ndjson",
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()["Content-Type"], "application/json");
 }
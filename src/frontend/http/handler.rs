@@ -3,13 +3,15 @@ use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
 use crate::frontend::server_state::ServerState;
 use crate::shared::config::CONFIG;
-use bytes::Bytes;
-use http_body_util::Full;
+use crate::shared::response::ErrorCode;
 use hyper::{Request, Response, StatusCode, body::Incoming};
 use std::{convert::Infallible, sync::Arc};
 use tokio::sync::RwLock;
 
-use super::dispatcher::{handle_json_command, handle_line_command};
+use super::body::{HttpBody, full_body};
+use super::dispatcher::{
+    ErrorCodeHeaders, handle_cache_stats, handle_json_command, handle_line_command,
+};
 use super::static_files::{serve_asset, serve_index};
 
 struct HttpHandler {
@@ -34,14 +36,15 @@ impl HttpHandler {
         }
     }
 
-    fn not_found() -> Response<Full<Bytes>> {
+    fn not_found() -> Response<HttpBody> {
         Response::builder()
             .status(StatusCode::NOT_FOUND)
+            .error_code(ErrorCode::NotFound)
             .body(full_body(b"Not Found".to_vec()))
             .unwrap()
     }
 
-    fn serve_playground(path: &str) -> Option<Response<Full<Bytes>>> {
+    fn serve_playground(path: &str) -> Option<Response<HttpBody>> {
         if !CONFIG.playground.enabled {
             return None;
         }
@@ -55,7 +58,7 @@ impl HttpHandler {
         }
     }
 
-    async fn handle(&self, req: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+    async fn handle(&self, req: Request<Incoming>) -> Result<Response<HttpBody>, Infallible> {
         // Use path directly without allocation - avoid to_string()
         let path = req.uri().path();
 
@@ -69,6 +72,7 @@ impl HttpHandler {
                 return Ok(Response::builder()
                     .status(hyper::StatusCode::SERVICE_UNAVAILABLE)
                     .header(hyper::header::CONTENT_TYPE, "text/plain")
+                    .error_code(ErrorCode::NotReady)
                     .body(full_body(b"Server is shutting down".to_vec()))
                     .unwrap());
             }
@@ -77,6 +81,7 @@ impl HttpHandler {
                 return Ok(Response::builder()
                     .status(hyper::StatusCode::SERVICE_UNAVAILABLE)
                     .header(hyper::header::CONTENT_TYPE, "text/plain")
+                    .error_code(ErrorCode::Overloaded)
                     .body(full_body(
                         b"Server is under pressure, please retry later".to_vec(),
                     ))
@@ -111,6 +116,7 @@ impl HttpHandler {
                 )
                 .await
             }
+            "/stats/cache" => handle_cache_stats(req, self.auth_manager.clone()).await,
             _ => Ok(Self::not_found()),
         }
     }
@@ -122,11 +128,7 @@ pub async fn handle_request(
     shard_manager: Arc<ShardManager>,
     server_state: Arc<ServerState>,
     auth_manager: Option<Arc<AuthManager>>,
-) -> Result<Response<Full<Bytes>>, Infallible> {
+) -> Result<Response<HttpBody>, Infallible> {
     let handler = HttpHandler::new(registry, shard_manager, server_state, auth_manager);
     handler.handle(req).await
 }
-
-fn full_body(data: Vec<u8>) -> Full<Bytes> {
-    Full::new(Bytes::from(data))
-}
@@ -27,12 +27,27 @@ pub enum JsonCommand {
         limit: Option<u32>,
         offset: Option<u32>,
         order_by: Option<OrderSpec>,
+        #[serde(default)]
+        omit_nulls: bool,
+        #[serde(default)]
+        with_total: bool,
+        #[serde(default)]
+        raw_strings: bool,
+        /// JSON form of the `/* allow_full_scan */` hint
+        #[serde(default)]
+        allow_full_scan: bool,
     },
     Replay {
         event_type: Option<String>,
         context_id: String,
         since: Option<String>,
         time_field: Option<String>,
+        #[serde(default)]
+        desc: bool,
+        limit: Option<u32>,
+    },
+    GetEvent {
+        id: u64,
     },
     Ping,
     Flush,
@@ -69,6 +84,10 @@ impl From<JsonCommand> for Command {
                 limit,
                 offset,
                 order_by,
+                omit_nulls,
+                with_total,
+                raw_strings,
+                allow_full_scan,
             } => Command::Query {
                 event_type,
                 context_id,
@@ -86,19 +105,32 @@ impl From<JsonCommand> for Command {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls,
+                with_total,
+                raw_strings,
+                disabled_pruners: Vec::new(),
+                allow_full_scan,
+                unnest: None,
             },
             JsonCommand::Replay {
                 event_type,
                 context_id,
                 since,
                 time_field,
+                desc,
+                limit,
             } => Command::Replay {
                 event_type,
                 context_id,
                 since,
                 time_field,
                 return_fields: None,
+                desc,
+                limit,
             },
+            JsonCommand::GetEvent { id } => Command::GetEvent { id },
             JsonCommand::Ping => Command::Ping,
             JsonCommand::Flush => Command::Flush,
             JsonCommand::Batch(cmds) => Command::Batch(cmds.into_iter().map(Into::into).collect()),
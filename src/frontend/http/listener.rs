@@ -8,6 +8,7 @@ use tracing::{debug, info, warn};
 
 use crate::frontend::context::FrontendContext;
 use crate::shared::config::CONFIG;
+use crate::shared::log_sampler::LogSampler;
 
 use super::handler::handle_request;
 
@@ -129,13 +130,17 @@ pub async fn run_http_server(ctx: Arc<FrontendContext>) -> anyhow::Result<()> {
             Err(_) => {
                 // Connection limit reached - drop the connection
                 // Client will retry and hopefully get through when capacity is available
-                warn!(
-                    "Connection limit reached (active={}/{}, max={}), dropping connection from {}",
-                    max_connections - available,
-                    max_connections,
-                    max_connections,
-                    peer_addr
-                );
+                static CONNECTION_LIMIT_LOG: LogSampler = LogSampler::new();
+                if let Some(dropped) = CONNECTION_LIMIT_LOG.admit(tracing::Level::WARN) {
+                    warn!(
+                        dropped,
+                        "Connection limit reached (active={}/{}, max={}), dropping connection from {}",
+                        max_connections - available,
+                        max_connections,
+                        max_connections,
+                        peer_addr
+                    );
+                }
                 continue;
             }
         };
@@ -1,8 +1,15 @@
+pub mod body;
+pub mod cache_stats;
+pub mod compression;
 pub mod dispatcher;
 pub mod handler;
 pub mod json_command;
 pub mod listener;
 pub mod static_files;
 
+#[cfg(test)]
+mod cache_stats_test;
+#[cfg(test)]
+mod compression_test;
 #[cfg(test)]
 mod dispatcher_test;
@@ -1,13 +1,18 @@
 pub mod context;
 pub mod http;
+pub mod query_admission;
 pub mod server_state;
 pub mod tcp;
 pub mod unix;
 pub mod ws;
 
+#[cfg(test)]
+mod query_admission_test;
 #[cfg(test)]
 mod server_state_test;
 
+use crate::engine::core::read::cache::warm_start;
+use crate::shared::config::CacheWarmStart;
 use context::FrontendContext;
 use std::sync::Arc;
 use tokio::signal;
@@ -78,6 +83,10 @@ pub async fn start_all() -> anyhow::Result<()> {
                 }
             }
 
+            if warm_start::configured_mode() != CacheWarmStart::Off {
+                warm_start::save_hot_keys(&warm_start::configured_path());
+            }
+
             info!("Graceful shutdown complete");
         })
     };
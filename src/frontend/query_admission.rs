@@ -0,0 +1,221 @@
+use crate::command::types::Command;
+use crate::shared::config::CONFIG;
+use crate::shared::response::ErrorCode;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits on queries executing at once, from `[query.concurrency]`.
+#[derive(Debug, Clone, Default)]
+pub struct QueryConcurrencyLimits {
+    /// Queries executing at once across all users; `None` = unlimited
+    pub max_in_flight: Option<usize>,
+    /// Queries one user may execute at once; `None` = unlimited
+    pub max_in_flight_per_user: Option<usize>,
+    /// Per-user replacements of `max_in_flight_per_user`
+    pub users: HashMap<String, usize>,
+    /// Queries that may wait for a slot; 0 rejects as soon as the limits are reached
+    pub max_queued: usize,
+    /// How long a queued query waits before it fails
+    pub queue_timeout: Duration,
+}
+
+impl QueryConcurrencyLimits {
+    /// Limits from `[query.concurrency]`; unlimited when the section is missing.
+    pub fn from_config() -> Self {
+        let Some(cfg) = CONFIG
+            .query
+            .as_ref()
+            .and_then(|cfg| cfg.concurrency.as_ref())
+        else {
+            return Self::default();
+        };
+        Self {
+            max_in_flight: cfg.max_in_flight,
+            max_in_flight_per_user: cfg.max_in_flight_per_user,
+            users: cfg
+                .users
+                .iter()
+                .filter_map(|(user, limit)| limit.max_in_flight.map(|max| (user.clone(), max)))
+                .collect(),
+            max_queued: cfg.max_queued,
+            queue_timeout: Duration::from_millis(cfg.queue_timeout_ms),
+        }
+    }
+
+    fn user_limit(&self, user_id: &str) -> Option<usize> {
+        self.users
+            .get(user_id)
+            .copied()
+            .or(self.max_in_flight_per_user)
+    }
+}
+
+/// Why a query was not admitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdmissionError {
+    /// Every slot is taken and the queue is full (or disabled)
+    Busy,
+    /// The query was queued but no slot freed up within the queue timeout
+    TimedOut(Duration),
+}
+
+impl AdmissionError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AdmissionError::Busy => ErrorCode::Overloaded,
+            AdmissionError::TimedOut(_) => ErrorCode::Timeout,
+        }
+    }
+}
+
+impl fmt::Display for AdmissionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdmissionError::Busy => write!(
+                f,
+                "Server busy: too many queries running, please retry later"
+            ),
+            AdmissionError::TimedOut(waited) => write!(
+                f,
+                "Server busy: query waited {}ms for an execution slot",
+                waited.as_millis()
+            ),
+        }
+    }
+}
+
+/// Slots held by an admitted query; released when dropped.
+#[derive(Debug)]
+pub struct QueryPermit {
+    _user: Option<OwnedSemaphorePermit>,
+    _global: Option<OwnedSemaphorePermit>,
+}
+
+/// Admits query commands within the concurrency limits, queueing the overflow
+/// up to `max_queued`. Other commands are never limited, so admin and health
+/// commands keep working while queries pile up.
+#[derive(Debug)]
+pub struct QueryAdmission {
+    limits: QueryConcurrencyLimits,
+    global: Option<Arc<Semaphore>>,
+    users: Mutex<HashMap<String, Arc<Semaphore>>>,
+    queued: AtomicUsize,
+}
+
+impl QueryAdmission {
+    pub fn new(limits: QueryConcurrencyLimits) -> Self {
+        Self {
+            global: limits
+                .max_in_flight
+                .map(|max| Arc::new(Semaphore::new(max))),
+            limits,
+            users: Mutex::new(HashMap::new()),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn from_config() -> Self {
+        Self::new(QueryConcurrencyLimits::from_config())
+    }
+
+    /// Commands that execute a query and count against the limits.
+    pub fn is_limited(cmd: &Command) -> bool {
+        matches!(
+            cmd,
+            Command::Query { .. }
+                | Command::Explain { analyze: true, .. }
+                | Command::Compare { .. }
+                | Command::Union { .. }
+                | Command::Replay { .. }
+        )
+    }
+
+    /// Queries currently waiting for a slot.
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    /// Takes a slot for a query of `user_id`, waiting in the queue when the
+    /// limits are reached and the queue has room.
+    pub async fn admit(&self, user_id: Option<&str>) -> Result<QueryPermit, AdmissionError> {
+        let user = user_id.and_then(|user_id| self.user_semaphore(user_id));
+        if let Some(permit) = self.try_admit(user.as_ref()) {
+            return Ok(permit);
+        }
+
+        if self.queued.fetch_add(1, Ordering::AcqRel) >= self.limits.max_queued {
+            self.queued.fetch_sub(1, Ordering::AcqRel);
+            return Err(AdmissionError::Busy);
+        }
+        let _queued = QueuedGuard(&self.queued);
+
+        let timeout = self.limits.queue_timeout;
+        tokio::time::timeout(timeout, self.acquire(user.as_ref()))
+            .await
+            .map_err(|_| AdmissionError::TimedOut(timeout))
+    }
+
+    fn try_admit(&self, user: Option<&Arc<Semaphore>>) -> Option<QueryPermit> {
+        let user = match user {
+            Some(sem) => Some(Arc::clone(sem).try_acquire_owned().ok()?),
+            None => None,
+        };
+        let global = match &self.global {
+            Some(sem) => Some(Arc::clone(sem).try_acquire_owned().ok()?),
+            None => None,
+        };
+        Some(QueryPermit {
+            _user: user,
+            _global: global,
+        })
+    }
+
+    /// Waits for the user's slot first, so a user at their own limit does not
+    /// hold a global slot others could use.
+    async fn acquire(&self, user: Option<&Arc<Semaphore>>) -> QueryPermit {
+        let user = match user {
+            Some(sem) => Some(
+                Arc::clone(sem)
+                    .acquire_owned()
+                    .await
+                    .expect("query semaphores are never closed"),
+            ),
+            None => None,
+        };
+        let global = match &self.global {
+            Some(sem) => Some(
+                Arc::clone(sem)
+                    .acquire_owned()
+                    .await
+                    .expect("query semaphores are never closed"),
+            ),
+            None => None,
+        };
+        QueryPermit {
+            _user: user,
+            _global: global,
+        }
+    }
+
+    fn user_semaphore(&self, user_id: &str) -> Option<Arc<Semaphore>> {
+        let limit = self.limits.user_limit(user_id)?;
+        let mut users = self.users.lock().unwrap_or_else(|e| e.into_inner());
+        Some(Arc::clone(
+            users
+                .entry(user_id.to_string())
+                .or_insert_with(|| Arc::new(Semaphore::new(limit))),
+        ))
+    }
+}
+
+struct QueuedGuard<'a>(&'a AtomicUsize);
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
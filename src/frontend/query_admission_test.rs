@@ -0,0 +1,115 @@
+use crate::command::types::Command;
+use crate::frontend::query_admission::{AdmissionError, QueryAdmission, QueryConcurrencyLimits};
+use crate::shared::response::ErrorCode;
+use crate::test_helpers::factories::CommandFactory;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn limits(
+    max_in_flight: Option<usize>,
+    per_user: Option<usize>,
+    max_queued: usize,
+) -> QueryConcurrencyLimits {
+    QueryConcurrencyLimits {
+        max_in_flight,
+        max_in_flight_per_user: per_user,
+        users: HashMap::new(),
+        max_queued,
+        queue_timeout: Duration::from_millis(50),
+    }
+}
+
+#[test]
+fn only_query_commands_are_limited() {
+    assert!(QueryAdmission::is_limited(
+        &CommandFactory::query().create()
+    ));
+    assert!(!QueryAdmission::is_limited(&Command::Ping));
+    assert!(!QueryAdmission::is_limited(&Command::Flush));
+    assert!(!QueryAdmission::is_limited(&Command::ShowStats));
+
+    let explain = |analyze| Command::Explain {
+        query: Box::new(CommandFactory::query().create()),
+        analyze,
+    };
+    assert!(QueryAdmission::is_limited(&explain(true)));
+    assert!(!QueryAdmission::is_limited(&explain(false)));
+}
+
+#[tokio::test]
+async fn rejects_beyond_limit_without_queue() {
+    let admission = QueryAdmission::new(limits(Some(1), None, 0));
+
+    let held = admission.admit(Some("alice")).await.unwrap();
+    let err = admission.admit(Some("bob")).await.unwrap_err();
+    assert_eq!(err, AdmissionError::Busy);
+    assert_eq!(err.code(), ErrorCode::Overloaded);
+    assert!(err.to_string().starts_with("Server busy"));
+
+    drop(held);
+    assert!(admission.admit(Some("bob")).await.is_ok());
+}
+
+#[tokio::test]
+async fn queued_query_runs_once_a_slot_frees_up() {
+    let admission = Arc::new(QueryAdmission::new(QueryConcurrencyLimits {
+        queue_timeout: Duration::from_secs(5),
+        ..limits(Some(1), None, 1)
+    }));
+    let held = admission.admit(None).await.unwrap();
+
+    let waiter = {
+        let admission = Arc::clone(&admission);
+        tokio::spawn(async move { admission.admit(None).await.map(|_| ()) })
+    };
+    while admission.queued() == 0 {
+        tokio::task::yield_now().await;
+    }
+    // The queue holds one query; the next one is turned away
+    assert_eq!(
+        admission.admit(None).await.unwrap_err(),
+        AdmissionError::Busy
+    );
+
+    drop(held);
+    assert!(waiter.await.unwrap().is_ok());
+    assert_eq!(admission.queued(), 0);
+}
+
+#[tokio::test]
+async fn queued_query_gives_up_after_its_timeout() {
+    let admission = QueryAdmission::new(limits(Some(1), None, 4));
+    let _held = admission.admit(None).await.unwrap();
+
+    let err = admission.admit(None).await.unwrap_err();
+    assert_eq!(err, AdmissionError::TimedOut(Duration::from_millis(50)));
+    assert_eq!(err.code(), ErrorCode::Timeout);
+    assert_eq!(admission.queued(), 0);
+}
+
+#[tokio::test]
+async fn per_user_limit_leaves_other_users_running() {
+    let mut config = limits(Some(10), Some(1), 0);
+    config.users.insert("etl".to_string(), 2);
+    let admission = QueryAdmission::new(config);
+
+    let _alice = admission.admit(Some("alice")).await.unwrap();
+    assert_eq!(
+        admission.admit(Some("alice")).await.unwrap_err(),
+        AdmissionError::Busy
+    );
+    assert!(admission.admit(Some("bob")).await.is_ok());
+
+    // The override replaces the default per-user limit
+    let _first = admission.admit(Some("etl")).await.unwrap();
+    let _second = admission.admit(Some("etl")).await.unwrap();
+    assert!(admission.admit(Some("etl")).await.is_err());
+
+    // Without limits every query is admitted
+    let unlimited = QueryAdmission::new(QueryConcurrencyLimits::default());
+    let permits: Vec<_> = (0..100).map(|_| unlimited.admit(None)).collect();
+    for permit in permits {
+        assert!(permit.await.is_ok());
+    }
+}
@@ -1,12 +1,17 @@
+use crate::command::types::Command;
 use crate::engine::shard::manager::ShardManager;
+use crate::frontend::query_admission::{
+    AdmissionError, QueryAdmission, QueryConcurrencyLimits, QueryPermit,
+};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-/// Manages server-wide state: shutdown flag and backpressure detection
+/// Manages server-wide state: shutdown flag, backpressure detection and query admission
 #[derive(Clone)]
 pub struct ServerState {
     shutdown: Arc<AtomicBool>,
     pending_operations: Arc<AtomicUsize>,
+    query_admission: Arc<QueryAdmission>,
     shard_manager: Arc<ShardManager>,
     backpressure_threshold: u8,
     channel_capacity: usize,
@@ -20,12 +25,37 @@ impl ServerState {
         Self {
             shutdown: Arc::new(AtomicBool::new(false)),
             pending_operations: Arc::new(AtomicUsize::new(0)),
+            query_admission: Arc::new(QueryAdmission::from_config()),
             shard_manager,
             backpressure_threshold,
             channel_capacity: DEFAULT_CHANNEL_CAPACITY,
         }
     }
 
+    /// Replaces the query concurrency limits taken from `[query.concurrency]`.
+    pub fn with_query_limits(mut self, limits: QueryConcurrencyLimits) -> Self {
+        self.query_admission = Arc::new(QueryAdmission::new(limits));
+        self
+    }
+
+    /// Admits `cmd` for execution. Query commands take a slot, held until the
+    /// returned permit is dropped; other commands pass straight through.
+    pub async fn admit(
+        &self,
+        cmd: &Command,
+        user_id: Option<&str>,
+    ) -> Result<Option<QueryPermit>, AdmissionError> {
+        if !QueryAdmission::is_limited(cmd) {
+            return Ok(None);
+        }
+        self.query_admission.admit(user_id).await.map(Some)
+    }
+
+    /// Returns the number of queries waiting for an execution slot
+    pub fn queued_queries(&self) -> usize {
+        self.query_admission.queued()
+    }
+
     /// Returns true if the server is shutting down
     pub fn is_shutting_down(&self) -> bool {
         self.shutdown.load(Ordering::Acquire)
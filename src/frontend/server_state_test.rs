@@ -274,3 +274,52 @@ async fn test_server_state_clone_shares_state() {
     // Both should see the same backpressure state
     assert_eq!(server_state.is_under_pressure(), clone2.is_under_pressure());
 }
+
+#[tokio::test]
+async fn test_server_state_admit_limits_queries_only() {
+    use crate::command::types::Command;
+    use crate::frontend::query_admission::{AdmissionError, QueryConcurrencyLimits};
+    use crate::test_helpers::factories::CommandFactory;
+
+    let base_dir = tempdir().unwrap();
+    let wal_dir = tempdir().unwrap();
+    let shard_manager = Arc::new(
+        ShardManager::new(
+            1,
+            base_dir.path().to_path_buf(),
+            wal_dir.path().to_path_buf(),
+        )
+        .await,
+    );
+    let server_state =
+        ServerState::new(shard_manager, 80).with_query_limits(QueryConcurrencyLimits {
+            max_in_flight: Some(1),
+            ..QueryConcurrencyLimits::default()
+        });
+    let query = CommandFactory::query().create();
+
+    let held = server_state.admit(&query, Some("alice")).await.unwrap();
+    assert!(held.is_some());
+    assert_eq!(
+        server_state.admit(&query, Some("bob")).await.unwrap_err(),
+        AdmissionError::Busy
+    );
+    // Health and admin commands bypass the limit
+    assert!(
+        server_state
+            .admit(&Command::Ping, None)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        server_state
+            .admit(&Command::Flush, Some("bob"))
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    drop(held);
+    assert!(server_state.admit(&query, Some("bob")).await.is_ok());
+}
@@ -0,0 +1,77 @@
+use crate::shared::response::render::Renderer;
+use crate::shared::response::unix::UnixRenderer;
+use crate::shared::response::{ErrorCode, MessagePackRenderer, Response, StatusCode};
+
+/// Encoding of the responses on a TCP connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WireFormat {
+    /// Plain text lines; what every connection starts with
+    #[default]
+    Text,
+    /// MessagePack maps shaped like the JSON output, for compact clients
+    MsgPack,
+}
+
+impl WireFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            WireFormat::Text => "TEXT",
+            WireFormat::MsgPack => "MSGPACK",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("TEXT") {
+            Some(WireFormat::Text)
+        } else if name.eq_ignore_ascii_case("MSGPACK") {
+            Some(WireFormat::MsgPack)
+        } else {
+            None
+        }
+    }
+
+    pub fn renderer(self) -> &'static dyn Renderer {
+        match self {
+            WireFormat::Text => &UnixRenderer,
+            WireFormat::MsgPack => &MessagePackRenderer,
+        }
+    }
+
+    /// An error the listener answers itself, without dispatching a command:
+    /// an `ERROR: ...` line, or an error response carrying `status`.
+    pub fn error(self, status: StatusCode, code: ErrorCode, message: &str) -> Vec<u8> {
+        match self {
+            WireFormat::Text => code.text_line(message).into_bytes(),
+            WireFormat::MsgPack => {
+                MessagePackRenderer.render(&Response::error_with_code(status, code, message))
+            }
+        }
+    }
+
+    /// A one-line acknowledgement such as `OK TOKEN <token>`.
+    pub fn ok(self, line: &str) -> Vec<u8> {
+        match self {
+            WireFormat::Text => format!("{line}\n").into_bytes(),
+            WireFormat::MsgPack => {
+                MessagePackRenderer.render(&Response::ok_lines([line.to_string()]))
+            }
+        }
+    }
+}
+
+/// Parses the `FORMAT TEXT|MSGPACK` handshake. `None` when the command is
+/// not a handshake at all.
+pub fn parse_format_handshake(command: &str) -> Option<Result<WireFormat, String>> {
+    let mut words = command.split_whitespace();
+    if !words.next()?.eq_ignore_ascii_case("FORMAT") {
+        return None;
+    }
+    let name = words.next().unwrap_or("");
+    Some(match (WireFormat::from_name(name), words.next()) {
+        (Some(format), None) => Ok(format),
+        _ => Err(format!(
+            "Invalid FORMAT '{}'. Use: FORMAT TEXT|MSGPACK",
+            command.trim()
+        )),
+    })
+}
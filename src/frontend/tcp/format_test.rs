@@ -0,0 +1,56 @@
+use serde_json::{Value, json};
+
+use crate::frontend::tcp::format::{WireFormat, parse_format_handshake};
+use crate::shared::response::render::StreamingFormat;
+use crate::shared::response::{ErrorCode, StatusCode};
+
+#[test]
+fn handshake_names_a_known_format() {
+    assert_eq!(
+        parse_format_handshake("FORMAT MSGPACK"),
+        Some(Ok(WireFormat::MsgPack))
+    );
+    assert_eq!(
+        parse_format_handshake("format text\r"),
+        Some(Ok(WireFormat::Text))
+    );
+    assert!(matches!(parse_format_handshake("FORMAT XML"), Some(Err(_))));
+    assert!(matches!(parse_format_handshake("FORMAT"), Some(Err(_))));
+    assert!(matches!(
+        parse_format_handshake("FORMAT MSGPACK TEXT"),
+        Some(Err(_))
+    ));
+    assert_eq!(parse_format_handshake("QUERY orders"), None);
+    assert_eq!(parse_format_handshake(""), None);
+}
+
+#[test]
+fn text_replies_are_lines() {
+    assert_eq!(WireFormat::Text.ok("OK FORMAT TEXT"), b"OK FORMAT TEXT\n");
+    assert_eq!(
+        WireFormat::Text.error(StatusCode::BadRequest, ErrorCode::ParseError, "bad"),
+        b"ERROR: bad [PARSE_ERROR]\n"
+    );
+}
+
+#[test]
+fn msgpack_replies_are_responses() {
+    assert_eq!(
+        WireFormat::MsgPack.renderer().streaming_format(),
+        StreamingFormat::MsgPack
+    );
+
+    let ok: Value = rmp_serde::from_slice(&WireFormat::MsgPack.ok("OK FORMAT MSGPACK")).unwrap();
+    assert_eq!(ok["status"], 200);
+    assert_eq!(ok["results"], json!(["OK FORMAT MSGPACK"]));
+
+    let error: Value = rmp_serde::from_slice(&WireFormat::MsgPack.error(
+        StatusCode::Unauthorized,
+        ErrorCode::Unauthenticated,
+        "Authentication failed",
+    ))
+    .unwrap();
+    assert_eq!(error["status"], 401);
+    assert_eq!(error["code"], "UNAUTHENTICATED");
+    assert_eq!(error["message"], "Authentication failed");
+}
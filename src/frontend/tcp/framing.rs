@@ -0,0 +1,122 @@
+use std::fmt;
+use std::io;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Byte that ends each command sent over a TCP connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Delimiter {
+    /// `\n`; what every connection starts with
+    #[default]
+    Newline,
+    /// `\0`, for clients whose commands carry raw newlines
+    Nul,
+}
+
+impl Delimiter {
+    pub fn byte(self) -> u8 {
+        match self {
+            Delimiter::Newline => b'\n',
+            Delimiter::Nul => b'\0',
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Delimiter::Newline => "NEWLINE",
+            Delimiter::Nul => "NUL",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("NEWLINE") || name.eq_ignore_ascii_case("LF") {
+            Some(Delimiter::Newline)
+        } else if name.eq_ignore_ascii_case("NUL") {
+            Some(Delimiter::Nul)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parses the `DELIMITER NEWLINE|NUL` handshake. `None` when the command is
+/// not a handshake at all.
+pub fn parse_handshake(command: &str) -> Option<Result<Delimiter, String>> {
+    let mut words = command.split_whitespace();
+    if !words.next()?.eq_ignore_ascii_case("DELIMITER") {
+        return None;
+    }
+    let name = words.next().unwrap_or("");
+    Some(match (Delimiter::from_name(name), words.next()) {
+        (Some(delimiter), None) => Ok(delimiter),
+        _ => Err(format!(
+            "Invalid DELIMITER '{}'. Use: DELIMITER NEWLINE|NUL",
+            command.trim()
+        )),
+    })
+}
+
+/// A frame that cannot be taken as a command. Its bytes are dropped; the
+/// connection goes on with the next frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramingError {
+    /// A NUL byte in a newline-delimited command, typically from a client
+    /// sending NUL-delimited commands without the handshake
+    NulInCommand,
+    /// The connection closed partway through a NUL-delimited command
+    Unterminated,
+    InvalidUtf8,
+}
+
+impl fmt::Display for FramingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FramingError::NulInCommand => f.write_str(
+                "Command contains a NUL byte; send DELIMITER NUL first to delimit commands with NUL",
+            ),
+            FramingError::Unterminated => {
+                f.write_str("Connection closed before the command's NUL delimiter")
+            }
+            FramingError::InvalidUtf8 => f.write_str("Command is not valid UTF-8"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Frame {
+    Command(String),
+    Malformed(FramingError),
+    End,
+}
+
+/// Reads the next command, without its delimiter. With the newline delimiter
+/// a last command cut short by the connection closing still counts, as it
+/// always has; with NUL it is malformed.
+pub async fn read_frame<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    delimiter: Delimiter,
+    buf: &mut Vec<u8>,
+) -> io::Result<Frame> {
+    buf.clear();
+    if reader.read_until(delimiter.byte(), buf).await? == 0 {
+        return Ok(Frame::End);
+    }
+
+    if buf.last() == Some(&delimiter.byte()) {
+        buf.pop();
+    } else if delimiter == Delimiter::Nul {
+        return Ok(Frame::Malformed(FramingError::Unterminated));
+    }
+    if delimiter == Delimiter::Newline && buf.contains(&b'\0') {
+        return Ok(Frame::Malformed(FramingError::NulInCommand));
+    }
+    Ok(match std::str::from_utf8(buf) {
+        Ok(command) => Frame::Command(command.to_string()),
+        Err(_) => Frame::Malformed(FramingError::InvalidUtf8),
+    })
+}
+
+/// Whether `buffered` already holds a whole command.
+pub fn has_buffered_command(buffered: &[u8], delimiter: Delimiter) -> bool {
+    buffered.contains(&delimiter.byte())
+}
@@ -0,0 +1,83 @@
+use crate::frontend::tcp::framing::{
+    Delimiter, Frame, FramingError, has_buffered_command, parse_handshake, read_frame,
+};
+
+async fn frames(mut input: &[u8], delimiter: Delimiter) -> Vec<Frame> {
+    let mut buf = Vec::new();
+    let mut frames = Vec::new();
+    loop {
+        let frame = read_frame(&mut input, delimiter, &mut buf).await.unwrap();
+        if frame == Frame::End {
+            return frames;
+        }
+        frames.push(frame);
+    }
+}
+
+fn command(text: &str) -> Frame {
+    Frame::Command(text.to_string())
+}
+
+#[test]
+fn handshake_names_a_known_delimiter() {
+    assert_eq!(parse_handshake("DELIMITER NUL"), Some(Ok(Delimiter::Nul)));
+    assert_eq!(
+        parse_handshake("delimiter newline\r"),
+        Some(Ok(Delimiter::Newline))
+    );
+    assert!(matches!(parse_handshake("DELIMITER TAB"), Some(Err(_))));
+    assert!(matches!(parse_handshake("DELIMITER"), Some(Err(_))));
+    assert!(matches!(parse_handshake("DELIMITER NUL NUL"), Some(Err(_))));
+    assert_eq!(parse_handshake("QUERY orders"), None);
+    assert_eq!(parse_handshake(""), None);
+}
+
+#[tokio::test]
+async fn newline_frames_keep_a_last_unterminated_command() {
+    assert_eq!(
+        frames(b"PING\nFLUSH\nQUERY orders", Delimiter::Newline).await,
+        vec![command("PING"), command("FLUSH"), command("QUERY orders")]
+    );
+}
+
+#[tokio::test]
+async fn nul_frames_may_hold_newlines() {
+    let input = b"STORE note FOR c1 PAYLOAD {\"text\":\"a\nb\"}\0PING\n\0";
+    assert_eq!(
+        frames(input, Delimiter::Nul).await,
+        vec![
+            command("STORE note FOR c1 PAYLOAD {\"text\":\"a\nb\"}"),
+            command("PING\n"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn malformed_frames_are_reported_not_merged() {
+    // NUL-delimited commands sent without the handshake
+    assert_eq!(
+        frames(b"PING\0FLUSH\0\nPING\n", Delimiter::Newline).await,
+        vec![
+            Frame::Malformed(FramingError::NulInCommand),
+            command("PING")
+        ]
+    );
+    assert_eq!(
+        frames(b"PING\0FLUSH", Delimiter::Nul).await,
+        vec![
+            command("PING"),
+            Frame::Malformed(FramingError::Unterminated)
+        ]
+    );
+    assert_eq!(
+        frames(b"PING \xff\nPING\n", Delimiter::Newline).await,
+        vec![Frame::Malformed(FramingError::InvalidUtf8), command("PING")]
+    );
+}
+
+#[test]
+fn buffered_commands_are_found_by_their_delimiter() {
+    assert!(has_buffered_command(b"PING\nFL", Delimiter::Newline));
+    assert!(!has_buffered_command(b"PING\nFL", Delimiter::Nul));
+    assert!(has_buffered_command(b"PING\0", Delimiter::Nul));
+}
@@ -1,14 +1,25 @@
 use crate::command::dispatcher::dispatch_command;
 use crate::command::parser::parse_command;
-use crate::engine::auth::AuthManager;
+use crate::engine::auth::{AuthManager, ClientCertIdentity, MAX_SESSION_TOKEN_LENGTH};
 use crate::frontend::context::FrontendContext;
+use crate::frontend::tcp::format::{WireFormat, parse_format_handshake};
+use crate::frontend::tcp::framing::{
+    Delimiter, Frame, FramingError, has_buffered_command, parse_handshake, read_frame,
+};
+use crate::frontend::tcp::tls;
 use crate::shared::config::CONFIG;
-use crate::shared::response::unix::UnixRenderer;
+use crate::shared::log_sampler::LogSampler;
+use crate::shared::response::{ErrorCode, StatusCode};
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
 use tracing::{info, warn};
 
+/// Largest number of pipelined lines whose signatures are verified together
+const MAX_PIPELINED_BATCH: usize = 256;
+
 /// Case-insensitive byte comparison helper
 #[inline]
 pub(crate) fn bytes_eq_ignore_ascii_case(a: &[u8], b: &[u8]) -> bool {
@@ -23,6 +34,8 @@ pub(crate) struct TcpAuthState {
     user_id: Option<String>,
     auth_manager: Option<Arc<AuthManager>>,
     client_ip: String,
+    /// The user comes from a verified client certificate, so commands need no signature
+    cert_authenticated: bool,
 }
 
 impl TcpAuthState {
@@ -31,18 +44,37 @@ impl TcpAuthState {
             user_id: None,
             auth_manager,
             client_ip,
+            cert_authenticated: false,
         }
     }
 
+    /// Authenticate the connection with the client certificate its TLS handshake verified.
+    /// The certificate must map to an active user through `[auth] client_cert_users`.
+    async fn authenticate_client_cert(
+        &mut self,
+        identity: &ClientCertIdentity,
+    ) -> Result<(), String> {
+        let Some(auth_mgr) = &self.auth_manager else {
+            return Ok(());
+        };
+        let (user_id, _token) = auth_mgr
+            .authenticate_client_cert(identity)
+            .await
+            .map_err(|e| format!("Client certificate rejected: {}", e))?;
+        self.user_id = Some(user_id);
+        self.cert_authenticated = true;
+        Ok(())
+    }
+
     /// Authenticate the connection using AUTH command
     /// Format: AUTH user_id:signature
     /// Signature should be computed as: HMAC-SHA256(secret_key, user_id)
     ///
     /// # Security
     /// - Rate limiting is applied per IP address on initial authentication
-    /// - After successful auth, subsequent commands skip rate limiting for full throughput
+    /// - After successful auth, subsequent commands are only rate limited when they fail
     /// - Returns a session token for high-throughput WebSocket authentication
-    async fn authenticate(&mut self, input: &str) -> Result<String, String> {
+    pub(crate) async fn authenticate(&mut self, input: &str) -> Result<String, String> {
         let parts: Vec<&str> = input.splitn(2, ' ').collect();
         if parts.len() != 2 {
             return Err("Invalid AUTH format. Use: AUTH user_id:signature".to_string());
@@ -70,6 +102,7 @@ impl TcpAuthState {
         {
             Ok(_) => {
                 self.user_id = Some(user_id.to_string());
+                self.cert_authenticated = false;
                 // Generate session token for high-throughput authentication
                 let token = auth_mgr.generate_session_token(user_id).await;
                 Ok(token)
@@ -149,25 +182,32 @@ pub(crate) async fn check_auth<'a>(
         let (command_without_token, token_part) = trimmed.split_at(token_pos);
         let token = token_part.strip_prefix(" TOKEN ")?.trim();
 
-        tracing::warn!(
-            target: "sneldb::auth",
-            command_preview = &command_without_token[..command_without_token.len().min(50)],
-            token_len = token.len(),
-            "Detected TOKEN format in command"
-        );
+        static TOKEN_DETECTED_LOG: LogSampler = LogSampler::new();
+        if let Some(dropped) = TOKEN_DETECTED_LOG.admit(tracing::Level::WARN) {
+            tracing::warn!(
+                target: "sneldb::auth",
+                command_preview = &command_without_token[..command_without_token.len().min(50)],
+                token_len = token.len(),
+                dropped,
+                "Detected TOKEN format in command"
+            );
+        }
 
-        // Basic validation: token should be hex string (64 chars for 32 bytes)
-        // Allow any length hex string for flexibility, but validate it's not empty
-        if !token.is_empty() && token.len() <= 128 {
+        // Basic validation: opaque tokens are 64 hex chars, stateless ones longer
+        if !token.is_empty() && token.len() <= MAX_SESSION_TOKEN_LENGTH {
             let command_trimmed = command_without_token.trim();
 
             // Validate token (fast O(1) hash lookup)
             if let Some(user_id) = auth_mgr.validate_session_token(token).await {
-                tracing::warn!(
-                    target: "sneldb::auth",
-                    user_id = user_id,
-                    "TOKEN auth succeeded"
-                );
+                static TOKEN_AUTH_LOG: LogSampler = LogSampler::new();
+                if let Some(dropped) = TOKEN_AUTH_LOG.admit(tracing::Level::WARN) {
+                    tracing::warn!(
+                        target: "sneldb::auth",
+                        user_id = user_id,
+                        dropped,
+                        "TOKEN auth succeeded"
+                    );
+                }
                 return Some((command_trimmed, true, Some(user_id), None));
             }
             // Token invalid or expired - fall through to other auth methods
@@ -185,6 +225,13 @@ pub(crate) async fn check_auth<'a>(
         // If token format is invalid, fall through to other auth methods
     }
 
+    // A verified client certificate authenticates every command of its connection
+    if auth_state.cert_authenticated
+        && let Some(user_id) = auth_state.user_id()
+    {
+        return Some((trimmed, true, Some(user_id.to_string()), None));
+    }
+
     // Try connection-scoped authentication first
     if let Some(user_id) = auth_state.user_id() {
         // For authenticated connections, check if it's in the short format: signature:command
@@ -194,10 +241,14 @@ pub(crate) async fn check_auth<'a>(
             let command_part = &trimmed[colon_pos + 1..];
             let command_part_trimmed = command_part.trim();
 
-            // For authenticated connections, skip rate limiting (allows full throughput)
-            // Connection is already authenticated via AUTH command (which was rate limited)
+            // Only failures are rate limited, so authenticated connections keep full throughput
             match auth_mgr
-                .verify_signature(command_part_trimmed, user_id, potential_signature, None)
+                .verify_signature(
+                    command_part_trimmed,
+                    user_id,
+                    potential_signature,
+                    Some(&auth_state.client_ip),
+                )
                 .await
             {
                 Ok(_) => {
@@ -234,11 +285,413 @@ pub(crate) async fn check_auth<'a>(
     }
 }
 
+/// Authenticates pipelined `signature:command` lines of an authenticated connection with
+/// one batch verification. Returns `None` when the lines have to go through `check_auth`
+/// one by one instead (AUTH, TOKEN or misplaced handshake lines, unauthenticated or
+/// certificate-authenticated connection, auth bypass).
+/// Otherwise every line gets the command to run, or `None` if its signature failed.
+pub(crate) async fn check_auth_batch<'a>(
+    lines: &'a [String],
+    auth_state: &TcpAuthState,
+) -> Option<Vec<Option<&'a str>>> {
+    if CONFIG.auth.as_ref().map(|a| a.bypass_auth).unwrap_or(false) {
+        return None;
+    }
+    verify_pipelined(lines, auth_state).await
+}
+
+/// The signature checks of `check_auth_batch`, past the auth bypass.
+pub(crate) async fn verify_pipelined<'a>(
+    lines: &'a [String],
+    auth_state: &TcpAuthState,
+) -> Option<Vec<Option<&'a str>>> {
+    if auth_state.cert_authenticated {
+        return None;
+    }
+    let auth_mgr = auth_state.auth_manager.as_ref()?;
+    let user_id = auth_state.user_id()?;
+
+    let mut items = Vec::with_capacity(lines.len());
+    for line in lines {
+        let trimmed = line.trim();
+        let bytes = trimmed.as_bytes();
+        if (bytes.len() >= 5 && bytes_eq_ignore_ascii_case(&bytes[..5], b"AUTH "))
+            || trimmed.contains(" TOKEN ")
+            || misplaced_handshake(trimmed).is_some()
+        {
+            return None;
+        }
+        let (signature, command) = trimmed.split_once(':')?;
+        items.push((command.trim(), signature));
+    }
+
+    let results = auth_mgr
+        .verify_signature_batch(user_id, &items, Some(&auth_state.client_ip))
+        .await;
+    Some(
+        items
+            .iter()
+            .zip(results)
+            .map(|((command, _), result)| result.ok().map(|_| *command))
+            .collect(),
+    )
+}
+
+/// Error for a DELIMITER or FORMAT handshake sent after negotiation ended.
+fn misplaced_handshake(trimmed: &str) -> Option<&'static str> {
+    if parse_handshake(trimmed).is_some() {
+        Some("DELIMITER must be the first command on a connection")
+    } else if parse_format_handshake(trimmed).is_some() {
+        Some("FORMAT must be sent before any other command but DELIMITER")
+    } else {
+        None
+    }
+}
+
+/// Authenticates and runs one line, writing the response to `writer`.
+async fn handle_line<W: AsyncWrite + Unpin>(
+    trimmed: &str,
+    auth_state: &mut TcpAuthState,
+    writer: &mut W,
+    ctx: &FrontendContext,
+    wire_format: WireFormat,
+) {
+    if let Some(message) = misplaced_handshake(trimmed) {
+        let _ = writer
+            .write_all(&wire_format.error(
+                StatusCode::BadRequest,
+                ErrorCode::InvalidRequest,
+                message,
+            ))
+            .await;
+        let _ = writer.flush().await;
+        return;
+    }
+
+    match check_auth(trimmed, auth_state).await {
+        Some(("OK", _, _, Some(token))) => {
+            // AUTH command succeeded - return token
+            let _ = writer
+                .write_all(&wire_format.ok(&format!("OK TOKEN {}", token)))
+                .await;
+            let _ = writer.flush().await;
+        }
+        Some(("OK", _, _, None)) => {
+            // AUTH command succeeded (no token - should not happen)
+            let _ = writer.write_all(&wire_format.ok("OK")).await;
+            let _ = writer.flush().await;
+        }
+        Some((command_to_parse, _, authenticated_user_id, _)) => {
+            run_command(
+                command_to_parse,
+                authenticated_user_id.as_deref(),
+                writer,
+                ctx,
+                wire_format,
+            )
+            .await
+        }
+        None => write_auth_failed(writer, wire_format).await,
+    }
+}
+
+/// Parses and dispatches an authenticated command.
+async fn run_command<W: AsyncWrite + Unpin>(
+    command: &str,
+    user_id: Option<&str>,
+    writer: &mut W,
+    ctx: &FrontendContext,
+    wire_format: WireFormat,
+) {
+    match parse_command(command) {
+        Ok(cmd) => {
+            // Queries hold their execution slot until dispatch returns
+            let _permit = match ctx.server_state.admit(&cmd, user_id).await {
+                Ok(permit) => permit,
+                Err(e) => {
+                    let _ = writer
+                        .write_all(&wire_format.error(
+                            StatusCode::ServiceUnavailable,
+                            e.code(),
+                            &e.to_string(),
+                        ))
+                        .await;
+                    let _ = writer.flush().await;
+                    return;
+                }
+            };
+
+            // Increment pending operations before dispatch
+            ctx.server_state.increment_pending();
+
+            let result = dispatch_command(
+                &cmd,
+                writer,
+                &ctx.shard_manager,
+                &ctx.registry,
+                ctx.auth_manager.as_ref(),
+                user_id,
+                wire_format.renderer(),
+            )
+            .await;
+
+            // Decrement after dispatch completes
+            ctx.server_state.decrement_pending();
+
+            if let Err(e) = result {
+                tracing::error!("Dispatch error: {e}");
+            }
+        }
+        Err(e) => {
+            let _ = writer
+                .write_all(&wire_format.error(StatusCode::BadRequest, e.code(), &e.to_string()))
+                .await;
+        }
+    }
+}
+
+async fn write_framing_error<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    error: FramingError,
+    wire_format: WireFormat,
+) {
+    let _ = writer
+        .write_all(&wire_format.error(
+            StatusCode::BadRequest,
+            ErrorCode::InvalidRequest,
+            &error.to_string(),
+        ))
+        .await;
+    let _ = writer.flush().await;
+}
+
+async fn write_auth_failed<W: AsyncWrite + Unpin>(writer: &mut W, wire_format: WireFormat) {
+    let _ = writer
+        .write_all(&wire_format.error(
+            StatusCode::Unauthorized,
+            ErrorCode::Unauthenticated,
+            "Authentication failed",
+        ))
+        .await;
+    let _ = writer.flush().await;
+}
+
+/// Reads and answers the commands of one accepted connection until it closes.
+async fn serve_connection<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: S,
+    mut auth_state: TcpAuthState,
+    ctx: Arc<FrontendContext>,
+) {
+    let mut reader = BufReader::new(stream);
+    let mut buf = Vec::new();
+    let mut delimiter = Delimiter::default();
+    let mut wire_format = WireFormat::default();
+    let mut first_command = true;
+    let mut negotiating = true;
+
+    loop {
+        let line = match read_frame(&mut reader, delimiter, &mut buf)
+            .await
+            .unwrap_or(Frame::End)
+        {
+            Frame::Command(line) => line,
+            Frame::Malformed(error) => {
+                first_command = false;
+                negotiating = false;
+                write_framing_error(reader.get_mut(), error, wire_format).await;
+                continue;
+            }
+            Frame::End => break,
+        };
+
+        // Check shutdown and backpressure before processing each command
+        if ctx.server_state.is_shutting_down() {
+            let writer = reader.get_mut();
+            let _ = writer
+                .write_all(&wire_format.error(
+                    StatusCode::ServiceUnavailable,
+                    ErrorCode::NotReady,
+                    "Server is shutting down",
+                ))
+                .await;
+            let _ = writer.flush().await;
+            break;
+        }
+
+        if ctx.server_state.is_under_pressure() {
+            let writer = reader.get_mut();
+            let _ = writer
+                .write_all(&wire_format.error(
+                    StatusCode::ServiceUnavailable,
+                    ErrorCode::Overloaded,
+                    "Server is under pressure, please retry later",
+                ))
+                .await;
+            let _ = writer.flush().await;
+            continue;
+        }
+
+        // The delimiter can only change before anything else was sent
+        if std::mem::take(&mut first_command)
+            && let Some(handshake) = parse_handshake(&line)
+        {
+            let writer = reader.get_mut();
+            let reply = match handshake {
+                Ok(negotiated) => {
+                    delimiter = negotiated;
+                    format!("OK DELIMITER {}\n", negotiated.name())
+                }
+                Err(message) => ErrorCode::InvalidRequest.text_line(&message),
+            };
+            let _ = writer.write_all(reply.as_bytes()).await;
+            let _ = writer.flush().await;
+            continue;
+        }
+
+        // The response format can change until the first regular command
+        if negotiating && let Some(handshake) = parse_format_handshake(&line) {
+            let reply = match handshake {
+                Ok(negotiated) => {
+                    wire_format = negotiated;
+                    wire_format.ok(&format!("OK FORMAT {}", negotiated.name()))
+                }
+                Err(message) => {
+                    wire_format.error(StatusCode::BadRequest, ErrorCode::InvalidRequest, &message)
+                }
+            };
+            let writer = reader.get_mut();
+            let _ = writer.write_all(&reply).await;
+            let _ = writer.flush().await;
+            continue;
+        }
+        negotiating = false;
+
+        // Commands an authenticated client already pipelined behind this one are
+        // verified together
+        let mut pipelined = Vec::new();
+        let mut malformed = None;
+        if auth_state.user_id().is_some() {
+            while pipelined.len() + 1 < MAX_PIPELINED_BATCH
+                && has_buffered_command(reader.buffer(), delimiter)
+            {
+                let mut next = Vec::new();
+                match read_frame(&mut reader, delimiter, &mut next)
+                    .await
+                    .unwrap_or(Frame::End)
+                {
+                    Frame::Command(next) => pipelined.push(next),
+                    Frame::Malformed(error) => {
+                        malformed = Some(error);
+                        break;
+                    }
+                    Frame::End => break,
+                }
+            }
+        }
+        if pipelined.is_empty() {
+            handle_line(
+                line.trim(),
+                &mut auth_state,
+                reader.get_mut(),
+                &ctx,
+                wire_format,
+            )
+            .await;
+        } else {
+            pipelined.insert(0, line);
+            match check_auth_batch(&pipelined, &auth_state).await {
+                Some(results) => {
+                    let user_id = auth_state.user_id().map(str::to_string);
+                    for result in results {
+                        match result {
+                            Some(command) => {
+                                run_command(
+                                    command,
+                                    user_id.as_deref(),
+                                    reader.get_mut(),
+                                    &ctx,
+                                    wire_format,
+                                )
+                                .await
+                            }
+                            None => write_auth_failed(reader.get_mut(), wire_format).await,
+                        }
+                    }
+                }
+                None => {
+                    for line in &pipelined {
+                        handle_line(
+                            line.trim(),
+                            &mut auth_state,
+                            reader.get_mut(),
+                            &ctx,
+                            wire_format,
+                        )
+                        .await;
+                    }
+                }
+            }
+        }
+        if let Some(error) = malformed {
+            write_framing_error(reader.get_mut(), error, wire_format).await;
+        }
+    }
+}
+
+/// Runs the TLS handshake on an accepted connection, then serves it. A verified client
+/// certificate authenticates the connection as its mapped user; a certificate mapped to
+/// no active user ends the connection. A failed handshake only ends this connection.
+pub(crate) async fn serve_tls_connection<S: AsyncRead + AsyncWrite + Unpin>(
+    acceptor: TlsAcceptor,
+    stream: S,
+    peer_addr: SocketAddr,
+    ctx: Arc<FrontendContext>,
+) {
+    let mut tls_stream = match acceptor.accept(stream).await {
+        Ok(tls_stream) => tls_stream,
+        Err(e) => {
+            warn!(
+                target: "sneldb::tcp",
+                peer = %peer_addr,
+                error = %e,
+                "TLS handshake failed, dropping connection"
+            );
+            return;
+        }
+    };
+
+    let mut auth_state = TcpAuthState::new(ctx.auth_manager.clone(), peer_addr.ip().to_string());
+    if let Some(identity) = tls::client_cert_identity(tls_stream.get_ref().1)
+        && let Err(e) = auth_state.authenticate_client_cert(&identity).await
+    {
+        warn!(
+            target: "sneldb::tcp",
+            peer = %peer_addr,
+            error = %e,
+            "Client certificate not accepted, dropping connection"
+        );
+        write_auth_failed(&mut tls_stream, WireFormat::default()).await;
+        return;
+    }
+    serve_connection(tls_stream, auth_state, ctx).await;
+}
+
 pub async fn run_tcp_server(ctx: Arc<FrontendContext>) -> anyhow::Result<()> {
     let addr = &CONFIG.server.tcp_addr;
 
+    let tls_acceptor = CONFIG
+        .tcp
+        .as_ref()
+        .and_then(|tcp| tcp.tls.as_ref())
+        .map(tls::build_acceptor)
+        .transpose()?;
+
     let listener = TcpListener::bind(addr).await?;
-    info!("TCP listener active on {}", addr);
+    if tls_acceptor.is_some() {
+        info!("TCP listener active on {} (TLS)", addr);
+    } else {
+        info!("TCP listener active on {}", addr);
+    }
 
     loop {
         // Check shutdown before accepting new connections
@@ -274,105 +727,15 @@ pub async fn run_tcp_server(ctx: Arc<FrontendContext>) -> anyhow::Result<()> {
         };
 
         let client_ip = peer_addr.ip().to_string();
-        let shard_manager = ctx.shard_manager.clone();
-        let registry = ctx.registry.clone();
-        let server_state = ctx.server_state.clone();
-        let auth_manager = ctx.auth_manager.clone();
+        let ctx = Arc::clone(&ctx);
+        let tls_acceptor = tls_acceptor.clone();
 
         tokio::spawn(async move {
-            let mut reader = BufReader::new(stream);
-            let mut line = String::new();
-            let mut auth_state = TcpAuthState::new(auth_manager.clone(), client_ip);
-
-            loop {
-                line.clear();
-                let n = reader.read_line(&mut line).await.unwrap_or(0);
-                if n == 0 {
-                    break;
-                }
-
-                // Check shutdown and backpressure before processing each command
-                if server_state.is_shutting_down() {
-                    let writer = reader.get_mut();
-                    let _ = writer.write_all(b"ERROR: Server is shutting down\n").await;
-                    let _ = writer.flush().await;
-                    break;
-                }
-
-                if server_state.is_under_pressure() {
-                    let writer = reader.get_mut();
-                    let _ = writer
-                        .write_all(b"ERROR: Server is under pressure, please retry later\n")
-                        .await;
-                    let _ = writer.flush().await;
-                    continue;
-                }
-
-                let trimmed = line.trim();
-
-                // Check authentication before parsing
-                match check_auth(trimmed, &mut auth_state).await {
-                    Some(("OK", _, _, Some(token))) => {
-                        // AUTH command succeeded - return token
-                        let writer = reader.get_mut();
-                        let _ = writer
-                            .write_all(format!("OK TOKEN {}\n", token).as_bytes())
-                            .await;
-                        let _ = writer.flush().await;
-                        continue;
-                    }
-                    Some(("OK", _, _, None)) => {
-                        // AUTH command succeeded (no token - should not happen)
-                        let writer = reader.get_mut();
-                        let _ = writer.write_all(b"OK\n").await;
-                        let _ = writer.flush().await;
-                        continue;
-                    }
-                    Some((command_to_parse, _, authenticated_user_id, _)) => {
-                        match parse_command(command_to_parse) {
-                            Ok(cmd) => {
-                                // Increment pending operations before dispatch
-                                server_state.increment_pending();
-
-                                let result = dispatch_command(
-                                    &cmd,
-                                    reader.get_mut(),
-                                    &shard_manager,
-                                    &registry,
-                                    auth_manager.as_ref(),
-                                    authenticated_user_id.as_deref(),
-                                    &UnixRenderer,
-                                )
-                                .await;
-
-                                // Decrement after dispatch completes
-                                server_state.decrement_pending();
-
-                                if let Err(e) = result {
-                                    tracing::error!("Dispatch error: {e}");
-                                }
-                            }
-                            Err(e) => {
-                                let _ = reader
-                                    .get_mut()
-                                    .write_all(format!("ERROR: {e}\n").as_bytes())
-                                    .await;
-                            }
-                        }
-                    }
-                    None => {
-                        let writer = reader.get_mut();
-                        // Check if it was an AUTH command that failed
-                        if trimmed.to_uppercase().starts_with("AUTH ") {
-                            let _ = writer
-                                .write_all(format!("ERROR: Authentication failed\n").as_bytes())
-                                .await;
-                        } else {
-                            let _ = writer.write_all(b"ERROR: Authentication failed\n").await;
-                        }
-                        let _ = writer.flush().await;
-                        continue;
-                    }
+            match tls_acceptor {
+                Some(acceptor) => serve_tls_connection(acceptor, stream, peer_addr, ctx).await,
+                None => {
+                    let auth_state = TcpAuthState::new(ctx.auth_manager.clone(), client_ip);
+                    serve_connection(stream, auth_state, ctx).await;
                 }
             }
         });
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tempfile::tempdir;
+
+use crate::engine::auth::{AuthError, AuthManager, LockoutPolicy};
+use crate::engine::shard::manager::ShardManager;
+use crate::frontend::tcp::listener::{TcpAuthState, verify_pipelined};
+use crate::logging::init_for_tests;
+
+const CLIENT_IP: &str = "10.0.0.7";
+
+fn compute_hmac(message: &str, secret_key: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret_key.as_bytes()).unwrap();
+    mac.update(message.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// A connection from `CLIENT_IP` authenticated as `signer`, whose failures lock the
+/// account after two attempts from the same address.
+async fn authenticated_connection() -> (Arc<AuthManager>, TcpAuthState) {
+    let base_dir = tempdir().unwrap().into_path();
+    let wal_dir = tempdir().unwrap().into_path();
+    let shard_manager = Arc::new(ShardManager::new(1, base_dir, wal_dir).await);
+    let auth_manager = Arc::new(AuthManager::new(shard_manager).with_lockout(Some(
+        LockoutPolicy {
+            max_failures: 2,
+            window_secs: 300,
+            cooldown_secs: 900,
+            per_source: true,
+        },
+    )));
+    auth_manager
+        .create_user("signer".to_string(), Some("secret".to_string()))
+        .await
+        .unwrap();
+
+    let mut auth_state = TcpAuthState::new(Some(Arc::clone(&auth_manager)), CLIENT_IP.into());
+    auth_state
+        .authenticate(&format!("AUTH signer:{}", compute_hmac("signer", "secret")))
+        .await
+        .unwrap();
+    (auth_manager, auth_state)
+}
+
+#[tokio::test]
+async fn pipelined_failures_count_against_the_connection_address() {
+    init_for_tests();
+
+    let (auth_manager, auth_state) = authenticated_connection().await;
+    // One failure from the connection's address, then one more inside a batch
+    let _ = auth_manager
+        .verify_signature("PING", "signer", "bad", Some(CLIENT_IP))
+        .await;
+    let lines = vec!["bad:PING".to_string()];
+    let results = verify_pipelined(&lines, &auth_state).await.unwrap();
+    assert_eq!(results, vec![None]);
+
+    let good = compute_hmac("PING", "secret");
+    assert!(matches!(
+        auth_manager
+            .verify_signature("PING", "signer", &good, Some(CLIENT_IP))
+            .await,
+        Err(AuthError::AccountLocked)
+    ));
+}
+
+#[tokio::test]
+async fn pipelined_signed_commands_run_when_verified() {
+    init_for_tests();
+
+    let (_auth_manager, auth_state) = authenticated_connection().await;
+    let lines = vec![
+        format!("{}:PING", compute_hmac("PING", "secret")),
+        "bad:FLUSH".to_string(),
+    ];
+
+    let results = verify_pipelined(&lines, &auth_state).await.unwrap();
+    assert_eq!(results, vec![Some("PING"), None]);
+}
+
+#[tokio::test]
+async fn pipelined_handshakes_go_through_the_single_line_path() {
+    init_for_tests();
+
+    let (_auth_manager, auth_state) = authenticated_connection().await;
+    for handshake in ["DELIMITER NUL", "FORMAT MSGPACK"] {
+        let lines = vec![
+            format!("{}:PING", compute_hmac("PING", "secret")),
+            handshake.to_string(),
+        ];
+        assert!(verify_pipelined(&lines, &auth_state).await.is_none());
+    }
+}
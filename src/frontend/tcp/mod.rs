@@ -1 +1,13 @@
+pub mod format;
+pub mod framing;
 pub mod listener;
+pub mod tls;
+
+#[cfg(test)]
+mod format_test;
+#[cfg(test)]
+mod framing_test;
+#[cfg(test)]
+mod listener_test;
+#[cfg(test)]
+mod tls_test;
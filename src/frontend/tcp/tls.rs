@@ -0,0 +1,139 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use anyhow::{Context, bail};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::crypto::ring;
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig, ServerConnection};
+use x509_parser::objects::{oid_registry, oid2abbrev};
+
+use crate::engine::auth::ClientCertIdentity;
+use crate::shared::config::TcpTlsConfig;
+
+/// Builds the acceptor that terminates TLS on accepted TCP connections from
+/// the PEM certificate chain and private key named in `[tcp.tls]`. With a
+/// `client_ca_path` the handshake also requires a client certificate signed by
+/// one of those CAs.
+pub fn build_acceptor(config: &TcpTlsConfig) -> anyhow::Result<TlsAcceptor> {
+    let cert_file = File::open(&config.cert_path)
+        .with_context(|| format!("Failed to open TLS certificate {}", config.cert_path))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(cert_file))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to read TLS certificate {}", config.cert_path))?;
+    if certs.is_empty() {
+        bail!("No certificate found in {}", config.cert_path);
+    }
+
+    let key_file = File::open(&config.key_path)
+        .with_context(|| format!("Failed to open TLS key {}", config.key_path))?;
+    let key = rustls_pemfile::private_key(&mut BufReader::new(key_file))
+        .with_context(|| format!("Failed to read TLS key {}", config.key_path))?
+        .with_context(|| format!("No private key found in {}", config.key_path))?;
+
+    // The provider is named so another crate enabling a second backend cannot
+    // make the process-wide default ambiguous
+    let provider = Arc::new(ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()?;
+    let builder = match &config.client_ca_path {
+        Some(path) => {
+            let verifier =
+                WebPkiClientVerifier::builder_with_provider(Arc::new(load_roots(path)?), provider)
+                    .build()
+                    .with_context(|| format!("Unusable client CA bundle {}", path))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let server_config = builder
+        .with_single_cert(certs, key)
+        .context("TLS certificate and key do not form a usable identity")?;
+
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+/// Reads the CAs trusted to sign client certificates.
+fn load_roots(path: &str) -> anyhow::Result<RootCertStore> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open client CA bundle {}", path))?;
+    let mut roots = RootCertStore::empty();
+    for cert in rustls_pemfile::certs(&mut BufReader::new(file)) {
+        let cert = cert.with_context(|| format!("Failed to read client CA bundle {}", path))?;
+        roots
+            .add(cert)
+            .with_context(|| format!("Invalid CA certificate in {}", path))?;
+    }
+    if roots.is_empty() {
+        bail!("No certificate found in {}", path);
+    }
+    Ok(roots)
+}
+
+/// Identity of the client certificate a finished handshake verified, if the
+/// client presented one.
+pub fn client_cert_identity(conn: &ServerConnection) -> Option<ClientCertIdentity> {
+    cert_identity(conn.peer_certificates()?.first()?)
+}
+
+/// Identity of a DER-encoded certificate whose chain was already verified. The
+/// CN is read from the parsed subject, never from its string form, so a comma
+/// or `CN=` inside another attribute's value cannot pass for one. With several
+/// CNs the most specific one counts.
+pub(crate) fn cert_identity(der: &[u8]) -> Option<ClientCertIdentity> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let subject = cert.subject();
+    let common_name = subject
+        .iter_common_name()
+        .last()
+        .and_then(|attr| attr.as_str().ok())
+        .map(str::to_string);
+    Some(ClientCertIdentity::verified(
+        subject_dn(subject),
+        common_name,
+    ))
+}
+
+/// Formats a subject as an RFC 4514 string ("CN=ingest,O=Acme"): the most
+/// specific component first, attribute types by their short names, special
+/// characters in values escaped.
+fn subject_dn(name: &x509_parser::x509::X509Name<'_>) -> String {
+    let rdns: Vec<String> =
+        name.iter_rdn()
+            .map(|rdn| {
+                rdn.iter()
+                    .map(|attr| {
+                        let key = oid2abbrev(attr.attr_type(), oid_registry())
+                            .map(str::to_string)
+                            .unwrap_or_else(|_| attr.attr_type().to_id_string());
+                        let value = attr.as_str().map(str::to_string).unwrap_or_else(|_| {
+                            String::from_utf8_lossy(attr.as_slice()).into_owned()
+                        });
+                        format!("{}={}", key, escape_dn_value(&value))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("+")
+            })
+            .collect();
+    rdns.into_iter().rev().collect::<Vec<_>>().join(",")
+}
+
+/// Escapes an attribute value as RFC 4514 section 2.4 requires.
+pub(crate) fn escape_dn_value(value: &str) -> String {
+    let last = value.chars().count().saturating_sub(1);
+    let mut escaped = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '"' | '+' | ',' | ';' | '<' | '>' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '#' if i == 0 => escaped.push_str("\\#"),
+            ' ' if i == 0 || i == last => escaped.push_str("\\ "),
+            '\0' => escaped.push_str("\\00"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -5,7 +5,7 @@ use crate::engine::schema::SchemaRegistry;
 use crate::engine::shard::manager::ShardManager;
 use crate::shared::config::CONFIG;
 use crate::shared::response::render::Renderer;
-use crate::shared::response::{Response, StatusCode};
+use crate::shared::response::{ErrorCode, Response, StatusCode};
 use std::io::ErrorKind;
 use std::sync::Arc;
 use tokio::io::AsyncWriteExt;
@@ -96,8 +96,9 @@ where
             let (command_to_parse, authenticated_user_id) = match self.check_auth(input).await {
                 Some((cmd, uid)) => (cmd, uid),
                 None => {
-                    let resp = Response::error(
+                    let resp = Response::error_with_code(
                         StatusCode::BadRequest,
+                        ErrorCode::Unauthenticated,
                         "Authentication failed".to_string(),
                     );
                     if let Err(e) = self.writer.write_all(&self.renderer.render(&resp)).await {
@@ -132,7 +133,11 @@ where
                     }
                 }
                 Err(e) => {
-                    let resp = Response::error(StatusCode::BadRequest, e.to_string());
+                    let resp = Response::error_with_code(
+                        StatusCode::BadRequest,
+                        e.code(),
+                        e.to_string(),
+                    );
                     if let Err(e) = self.writer.write_all(&self.renderer.render(&resp)).await {
                         if e.kind() == ErrorKind::BrokenPipe {
                             tracing::info!("[PID {}] Client disconnected", self.pid);
@@ -7,6 +7,9 @@ use crate::frontend::context::FrontendContext;
 use crate::frontend::unix::connection::Connection;
 use crate::shared::config::CONFIG;
 use crate::shared::response::ArrowRenderer;
+use crate::shared::response::CsvRenderer;
+use crate::shared::response::MessagePackRenderer;
+use crate::shared::response::NdjsonRenderer;
 use crate::shared::response::json::JsonRenderer;
 use crate::shared::response::render::Renderer;
 use crate::shared::response::unix::UnixRenderer;
@@ -42,6 +45,9 @@ pub async fn run_server(ctx: Arc<FrontendContext>) -> anyhow::Result<()> {
                     match CONFIG.server.output_format.as_str() {
                         "json" => Arc::new(JsonRenderer),
                         "arrow" => Arc::new(ArrowRenderer),
+                        "csv" => Arc::new(CsvRenderer::from_config()),
+                        "msgpack" => Arc::new(MessagePackRenderer),
+                        "ndjson" => Arc::new(NdjsonRenderer),
                         _ => Arc::new(UnixRenderer),
                     };
 
@@ -1,8 +1,10 @@
 use crate::command::dispatcher::dispatch_command;
 use crate::command::parser::parse_command;
+use crate::engine::auth::MAX_SESSION_TOKEN_LENGTH;
 use crate::frontend::context::FrontendContext;
 use crate::frontend::tcp::listener::{TcpAuthState, check_auth};
 use crate::shared::config::CONFIG;
+use crate::shared::response::ErrorCode;
 use crate::shared::response::unix::UnixRenderer;
 use futures_util::{SinkExt, StreamExt};
 use std::net::SocketAddr;
@@ -133,14 +135,15 @@ async fn handle_ws_connection(stream: TcpStream, peer_addr: SocketAddr, ctx: Arc
             Ok(Message::Text(text)) => {
                 if server_state.is_shutting_down() {
                     let _ = tx.send(Message::Text(
-                        "ERROR: Server is shutting down\n".to_string(),
+                        ErrorCode::NotReady.text_line("Server is shutting down"),
                     ));
                     break;
                 }
 
                 if server_state.is_under_pressure() {
                     let _ = tx.send(Message::Text(
-                        "ERROR: Server is under pressure, please retry later\n".to_string(),
+                        ErrorCode::Overloaded
+                            .text_line("Server is under pressure, please retry later"),
                     ));
                     continue;
                 }
@@ -166,7 +169,7 @@ async fn handle_ws_connection(stream: TcpStream, peer_addr: SocketAddr, ctx: Arc
                         let (command_without_token, token_part) = trimmed.split_at(token_pos);
                         let token = token_part.strip_prefix(" TOKEN ").unwrap_or("").trim();
 
-                        if !token.is_empty() && token.len() <= 128 {
+                        if !token.is_empty() && token.len() <= MAX_SESSION_TOKEN_LENGTH {
                             // Validate token directly (no lock needed for TOKEN auth)
                             if let Some(ref auth_mgr) = auth_manager_clone {
                                 if let Some(user_id) = auth_mgr.validate_session_token(token).await
@@ -185,6 +188,19 @@ async fn handle_ws_connection(stream: TcpStream, peer_addr: SocketAddr, ctx: Arc
                                                 target: "sneldb::ws",
                                                 "Command parsed, dispatching"
                                             );
+                                            // Queries hold their execution slot until dispatch returns
+                                            let _permit = match server_state_clone
+                                                .admit(&cmd, Some(user_id.as_str()))
+                                                .await
+                                            {
+                                                Ok(permit) => permit,
+                                                Err(e) => {
+                                                    let _ = tx_clone.try_send(Message::Text(
+                                                        e.code().text_line(&e.to_string()),
+                                                    ));
+                                                    return;
+                                                }
+                                            };
                                             server_state_clone.increment_pending();
 
                                             let mut buffer = WsResponseBuffer::default();
@@ -216,14 +232,18 @@ async fn handle_ws_connection(stream: TcpStream, peer_addr: SocketAddr, ctx: Arc
                                                 }
                                                 Err(e) => {
                                                     let _ = tx_clone.try_send(Message::Text(
-                                                        format!("ERROR: Dispatch error: {}\n", e),
+                                                        ErrorCode::Internal.text_line(&format!(
+                                                            "Dispatch error: {}",
+                                                            e
+                                                        )),
                                                     ));
                                                 }
                                             }
                                         }
                                         Err(e) => {
-                                            let _ = tx_clone
-                                                .try_send(Message::Text(format!("ERROR: {e}\n")));
+                                            let _ = tx_clone.try_send(Message::Text(
+                                                e.code().text_line(&e.to_string()),
+                                            ));
                                         }
                                     }
                                     return; // Fast path complete, exit early
@@ -255,6 +275,19 @@ async fn handle_ws_connection(stream: TcpStream, peer_addr: SocketAddr, ctx: Arc
                                         target: "sneldb::ws",
                                         "Command parsed, dispatching"
                                     );
+                                    // Queries hold their execution slot until dispatch returns
+                                    let _permit = match server_state_clone
+                                        .admit(&cmd, authenticated_user_id.as_deref())
+                                        .await
+                                    {
+                                        Ok(permit) => permit,
+                                        Err(e) => {
+                                            let _ = tx_clone.try_send(Message::Text(
+                                                e.code().text_line(&e.to_string()),
+                                            ));
+                                            return;
+                                        }
+                                    };
                                     server_state_clone.increment_pending();
 
                                     let mut buffer = WsResponseBuffer::default();
@@ -290,16 +323,17 @@ async fn handle_ws_connection(stream: TcpStream, peer_addr: SocketAddr, ctx: Arc
                                         }
                                         Err(e) => {
                                             // Always try to send errors (but don't block)
-                                            let _ = tx_clone.try_send(Message::Text(format!(
-                                                "ERROR: Dispatch error: {}\n",
-                                                e
-                                            )));
+                                            let _ = tx_clone.try_send(Message::Text(
+                                                ErrorCode::Internal
+                                                    .text_line(&format!("Dispatch error: {}", e)),
+                                            ));
                                         }
                                     }
                                 }
                                 Err(e) => {
-                                    let _ =
-                                        tx_clone.try_send(Message::Text(format!("ERROR: {e}\n")));
+                                    let _ = tx_clone.try_send(Message::Text(
+                                        e.code().text_line(&e.to_string()),
+                                    ));
                                 }
                             }
                         }
@@ -309,8 +343,9 @@ async fn handle_ws_connection(stream: TcpStream, peer_addr: SocketAddr, ctx: Arc
                                 command_preview = &trimmed[..trimmed.len().min(80)],
                                 "Authentication failed for command"
                             );
-                            let _ = tx_clone
-                                .send(Message::Text("ERROR: Authentication failed\n".to_string()));
+                            let _ = tx_clone.send(Message::Text(
+                                ErrorCode::Unauthenticated.text_line("Authentication failed"),
+                            ));
                         }
                     }
                 });
@@ -321,12 +356,12 @@ async fn handle_ws_connection(stream: TcpStream, peer_addr: SocketAddr, ctx: Arc
             Ok(Message::Close(_)) => break,
             Ok(Message::Binary(_)) => {
                 let _ = tx.send(Message::Text(
-                    "ERROR: Binary frames are not supported\n".to_string(),
+                    ErrorCode::InvalidRequest.text_line("Binary frames are not supported"),
                 ));
             }
             Ok(Message::Frame(_)) => {
                 let _ = tx.send(Message::Text(
-                    "ERROR: Fragmented frames are not supported\n".to_string(),
+                    ErrorCode::InvalidRequest.text_line("Fragmented frames are not supported"),
                 ));
             }
             Ok(Message::Pong(_)) => {}
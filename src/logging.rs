@@ -1,8 +1,10 @@
 use tracing_subscriber::fmt;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
 
-use crate::shared::config::CONFIG;
-use tracing::info;
+use crate::shared::config::{CONFIG, LogFormat};
+use tracing::{Subscriber, info};
 
 pub fn init() -> anyhow::Result<()> {
     info!("Initializing logging");
@@ -14,13 +16,10 @@ pub fn init() -> anyhow::Result<()> {
         .file_level
         .parse::<tracing_subscriber::filter::LevelFilter>()?;
 
-    let stdout_layer = fmt::layer().with_ansi(true).with_filter(stdout_filter);
+    let stdout_layer = output_layer(cfg.format, true, std::io::stdout).with_filter(stdout_filter);
 
     let file_appender = tracing_appender::rolling::daily(&cfg.log_dir, "sneldb.log");
-    let file_layer = fmt::layer()
-        .with_ansi(false)
-        .with_writer(file_appender)
-        .with_filter(file_filter);
+    let file_layer = output_layer(cfg.format, false, file_appender).with_filter(file_filter);
 
     tracing_subscriber::registry()
         .with(stdout_layer)
@@ -31,6 +30,28 @@ pub fn init() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Formats events as text, or as JSON objects carrying the timestamp, level,
+/// target, the event's fields and the fields of its enclosing spans.
+fn output_layer<S, W>(
+    format: LogFormat,
+    ansi: bool,
+    writer: W,
+) -> Box<dyn tracing_subscriber::Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_ansi(ansi).with_writer(writer).boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
 #[cfg(test)]
 pub fn init_for_tests() {
     use std::sync::Once;
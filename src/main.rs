@@ -1,15 +1,27 @@
 #![feature(portable_simd)]
 use snel_db::engine::core::read::cache::{
-    GlobalColumnBlockCache, GlobalZoneIndexCache, GlobalZoneSurfCache,
+    GlobalColumnBlockCache, GlobalColumnValueCache, GlobalPinnedSegmentCache, GlobalZoneIndexCache,
+    GlobalZoneSurfCache, IdentInterner, warm_start,
 };
 use snel_db::engine::core::utils::system_info_cache::get_system_info_cache;
+use snel_db::engine::core::utils::worker_pools::{ThreadCounts, WorkerPools};
 use snel_db::frontend::start_all;
 use snel_db::logging;
-use snel_db::shared::config::CONFIG;
+use snel_db::shared::config::{CONFIG, CacheWarmStart};
 use tracing::info;
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<()> {
+    // The main runtime only carries frontends and connection tasks; queries and
+    // flush/compaction get their own pools, so it takes the I/O share of the cores
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(ThreadCounts::configured().io)
+        .thread_name("sneldb-io")
+        .enable_all()
+        .build()?
+        .block_on(run())
+}
+
+async fn run() -> anyhow::Result<()> {
     info!("Starting SnelDB");
     logging::init()?;
 
@@ -29,6 +41,43 @@ async fn main() -> anyhow::Result<()> {
         if let Some(bytes) = q.zone_surf_cache_max_bytes {
             GlobalZoneSurfCache::instance().resize_bytes(bytes);
         }
+        if let Some(admission) = q.zone_surf_cache_admission {
+            GlobalZoneSurfCache::instance().set_admission(admission);
+        }
+        if let Some(bytes) = q.pinned_segment_max_bytes {
+            GlobalPinnedSegmentCache::instance().set_max_segment_bytes(bytes);
+        }
+        if let Some(bytes) = q.pinned_segment_cache_max_bytes {
+            GlobalPinnedSegmentCache::instance().resize_bytes(bytes);
+        }
+        if let Some(bytes) = q.column_value_cache_max_bytes {
+            GlobalColumnValueCache::instance().resize_bytes(bytes);
+        }
+        for interner in [IdentInterner::uids(), IdentInterner::fields()] {
+            if let Some(entries) = q.ident_intern_max_entries {
+                interner.resize(entries);
+            }
+            if let Some(eviction) = q.ident_intern_eviction {
+                interner.set_eviction(eviction);
+            }
+        }
+    }
+
+    // Separate thread pools for queries and for flush/compaction, created before
+    // any shard is spawned so every shard task lands on its pool
+    WorkerPools::init()?;
+
+    // Load the cache keys saved at the last graceful shutdown
+    match warm_start::configured_mode() {
+        CacheWarmStart::Off => {}
+        CacheWarmStart::Blocking => {
+            warm_start::warm_from_file(&warm_start::configured_path());
+        }
+        CacheWarmStart::Background => {
+            tokio::task::spawn_blocking(|| {
+                warm_start::warm_from_file(&warm_start::configured_path())
+            });
+        }
     }
 
     tracing::info!("SnelDB is starting...");
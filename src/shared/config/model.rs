@@ -1,4 +1,5 @@
 use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
 use std::fmt;
 
 use crate::shared::datetime::time::TimeConfig;
@@ -231,12 +232,31 @@ pub struct Settings {
     pub playground: PlaygroundConfig,
     pub auth: Option<AuthConfig>,
     pub query: Option<QueryConfig>,
+    pub ingest: Option<IngestConfig>,
+    pub retention: Option<RetentionConfig>,
     pub time: Option<TimeConfig>,
+    pub tcp: Option<TcpConfig>,
+    pub http: Option<HttpConfig>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SchemaConfig {
     pub def_dir: String,
+    /// Match event type names regardless of case (`UserSignup` finds `usersignup`).
+    /// Defaults to false: names match exactly.
+    #[serde(default)]
+    pub fold_event_type_case: bool,
+    /// Ignore `_`, `-`, `.` and whitespace when matching event type names
+    /// (`user_signup` finds `usersignup`). Defaults to false.
+    #[serde(default)]
+    pub ignore_event_type_separators: bool,
+    /// Max number of schemas the registry holds; further DEFINEs are rejected.
+    /// Defaults to unlimited.
+    #[serde(default)]
+    pub max_schemas: Option<usize>,
+    /// Max number of payload fields a single schema may declare. Defaults to unlimited.
+    #[serde(default)]
+    pub max_fields_per_schema: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -255,6 +275,14 @@ pub struct WalConfig {
     pub archive_dir: String,
     pub compression_level: i32,
     pub compression_algorithm: String,
+    /// Rotate to a new WAL segment once the current one reaches this size.
+    /// Accepts human-readable sizes (e.g., "64MB"); unset = no size trigger.
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub segment_size_bytes: Option<usize>,
+    /// Rotate to a new WAL segment after this many entries. Defaults to the
+    /// MemTable capacity when `segment_size_bytes` is also unset.
+    #[serde(default)]
+    pub segment_max_entries: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -280,6 +308,110 @@ pub struct EngineConfig {
     pub compaction_max_shard_concurrency: usize,
     /// System info cache refresh interval in seconds (default 5)
     pub system_info_refresh_interval: Option<u64>,
+    /// Worker threads for the main runtime running frontends and connections
+    /// (default: an eighth of the cores, at least 1)
+    pub io_threads: Option<usize>,
+    /// Worker threads for foreground query work (default: cores left after I/O and background threads)
+    pub query_threads: Option<usize>,
+    /// Worker threads for flush and compaction (default: a quarter of the cores, at least 1)
+    pub background_threads: Option<usize>,
+    /// Per-zone aggregates precomputed at flush, keyed by event type
+    #[serde(default)]
+    pub zone_summaries: HashMap<String, Vec<ZoneSummaryConfig>>,
+    /// MemTable structure keyed by event type; event types without one are `ordered`
+    #[serde(default)]
+    pub memtable_layouts: HashMap<String, MemTableLayout>,
+    /// Column block codec by segment level; levels below the first entry use LZ4
+    #[serde(default, deserialize_with = "parse_compaction_codecs")]
+    pub compaction_codecs: Vec<CompactionCodecConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ZoneSummaryConfig {
+    pub field: String,
+    pub ops: Vec<ZoneSummaryOp>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ZoneSummaryOp {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemTableLayout {
+    /// Contexts kept sorted: scans read them in order and flushes need no sort
+    #[default]
+    Ordered,
+    /// Contexts hashed in arrival order, for constant-time context lookups
+    Hash,
+}
+
+/// Codec for the column blocks of segments at `from_level` and above, up to
+/// the next configured level.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CompactionCodecConfig {
+    pub from_level: u32,
+    pub codec: SegmentCodec,
+    /// Zstd level (default 3); LZ4 has no levels
+    #[serde(default)]
+    pub compression_level: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SegmentCodec {
+    Lz4,
+    Zstd,
+}
+
+/// Reads `engine.compaction_codecs`, rejecting repeated levels and compression
+/// levels the codec does not support. Entries come back sorted by level.
+pub fn parse_compaction_codecs<'de, D>(
+    deserializer: D,
+) -> Result<Vec<CompactionCodecConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    let mut tiers = Vec::<CompactionCodecConfig>::deserialize(deserializer)?;
+    tiers.sort_by_key(|tier| tier.from_level);
+    for pair in tiers.windows(2) {
+        if pair[0].from_level == pair[1].from_level {
+            return Err(D::Error::custom(format!(
+                "compaction_codecs: level {} is configured twice",
+                pair[0].from_level
+            )));
+        }
+    }
+    for tier in &tiers {
+        match (tier.codec, tier.compression_level) {
+            (SegmentCodec::Lz4, Some(_)) => {
+                return Err(D::Error::custom(format!(
+                    "compaction_codecs: level {}: lz4 takes no compression_level",
+                    tier.from_level
+                )));
+            }
+            (SegmentCodec::Zstd, Some(level))
+                if !zstd::compression_level_range().contains(&level) =>
+            {
+                return Err(D::Error::custom(format!(
+                    "compaction_codecs: level {}: zstd compression_level {} is outside {:?}",
+                    tier.from_level,
+                    level,
+                    zstd::compression_level_range()
+                )));
+            }
+            _ => {}
+        }
+    }
+    Ok(tiers)
 }
 
 #[derive(Debug, Deserialize)]
@@ -291,9 +423,289 @@ pub struct QueryConfig {
     /// Zone surf cache size in bytes. Can be specified as human-readable string (e.g., "4GB", "256MB") or integer (bytes).
     #[serde(deserialize_with = "parse_optional_size_bytes")]
     pub zone_surf_cache_max_bytes: Option<usize>,
+    /// Which loaded zone SuRF filters are cached. Defaults to `always`.
+    pub zone_surf_cache_admission: Option<CacheAdmission>,
+    /// Segments whose files total at most this many bytes are pinned in memory after
+    /// their first read. Can be specified as human-readable string (e.g., "1MB") or integer (bytes).
+    /// Unset = no pinning.
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub pinned_segment_max_bytes: Option<usize>,
+    /// Total byte budget of pinned segments; least recently used segments are unpinned first.
+    /// Can be specified as human-readable string (e.g., "64MB") or integer (bytes). Unset = no pinning.
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub pinned_segment_cache_max_bytes: Option<usize>,
+    /// Byte budget of decoded values kept for point lookups (`GET EVENT`).
+    /// Can be specified as human-readable string (e.g., "16MB") or integer (bytes). Unset = off.
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub column_value_cache_max_bytes: Option<usize>,
+    /// Save the hottest zone index, SuRF and XOR filter cache keys on graceful
+    /// shutdown and load them again on startup. Defaults to `off`.
+    pub cache_warm_start: Option<CacheWarmStart>,
+    /// Max identifiers (event type UIDs, field names) each intern table keeps for
+    /// building cache keys. Defaults to 65536.
+    pub ident_intern_max_entries: Option<usize>,
+    /// What happens when an intern table is full. Defaults to `lru`.
+    pub ident_intern_eviction: Option<InternEviction>,
+    /// How rows with equal ORDER BY values are ordered. Defaults to `event_id`.
+    pub order_tiebreaker: Option<OrderTiebreaker>,
+    /// Hash function of the tables a query builds in memory: aggregate groups,
+    /// COUNT UNIQUE sets, sequence joins and IN lists. Defaults to `ahash`.
+    pub hasher: Option<QueryHasher>,
+    /// Group keys each grouped aggregation caches to skip rebuilding them per row;
+    /// least recently used keys are evicted past it, 0 disables the cache.
+    /// Defaults to 1000.
+    pub group_key_cache_max_entries: Option<usize>,
+    /// Distinct values each `COUNT UNIQUE` group keeps exactly; above it the count
+    /// is estimated with a fixed-size sketch. Defaults to 10000.
+    pub count_unique_exact_limit: Option<usize>,
+    /// Relative standard error targeted by the HyperLogLog sketches of
+    /// `COUNT(DISTINCT ...)` and estimated `COUNT UNIQUE` groups (e.g. 0.01 for 1%).
+    /// Defaults to about 0.8% (2^14 registers).
+    pub count_distinct_relative_error: Option<f64>,
+    /// Batch buffers each pool keeps for reuse. Defaults to 16.
+    pub batch_pool_max_buffers: Option<usize>,
+    /// Largest batch buffer a pool keeps for reuse; larger ones are freed. Can be
+    /// specified as human-readable string (e.g., "16MB") or integer (bytes). Defaults to 16MB.
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub batch_pool_max_buffer_bytes: Option<usize>,
+    /// Memory a `DEDUP BY` may hold for the rows it keeps; above it the query
+    /// fails. Can be specified as human-readable string (e.g., "256MB") or
+    /// integer (bytes). Defaults to 256MB.
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub dedup_max_bytes: Option<usize>,
     /// Batch size for streaming JSON responses (0 = per-row, >0 = batched)
     /// Defaults to 1000 if not specified
     pub streaming_batch_size: Option<usize>,
+    /// Flush streamed output to the client once this many encoded bytes are buffered.
+    /// Can be specified as human-readable string (e.g., "64KB") or integer (bytes). Defaults to 64KB.
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub streaming_flush_bytes: Option<usize>,
+    /// Max time (ms) encoded output may wait in the buffer before it is flushed.
+    /// Unset = flush only on the byte threshold and at end of stream.
+    pub streaming_max_linger_ms: Option<u64>,
+    /// Sample which flow operators are active while each query runs and log the breakdown
+    /// under `sneldb::query::profile`. Defaults to false.
+    pub profile_operators: Option<bool>,
+    /// Run every query deterministically, for reproducing bugs and property tests:
+    /// shard and source outputs are read one after the other in a fixed order,
+    /// ORDER BY ties always break by event id, and operator sampling, timed output
+    /// flushes and materialized view rewrites are off. Slower; not meant for
+    /// production. Defaults to false.
+    pub deterministic: Option<bool>,
+    /// Answer aggregate queries from a materialized view that gives the same
+    /// result, topped up with the events past its high-water mark. Defaults to true.
+    pub use_materialized_views: Option<bool>,
+    /// Store a `_sneldb_query_metrics` event (query hash, latency, rows scanned and
+    /// returned, cache lookups) for every completed query. Defaults to false.
+    pub metrics_events: Option<bool>,
+    /// Record, for every query, the columns each reader hydrates next to those the
+    /// projection planner requested, and log a warning under `sneldb::projection`
+    /// when they diverge. `EXPLAIN ANALYZE` always records them. Defaults to false.
+    pub verify_projection: Option<bool>,
+    /// Check each column block against the CRC32 its `.zfc` entry recorded as
+    /// it is decompressed for a query, and log or fail on a mismatch.
+    /// Defaults to off.
+    pub verify_block_checksums: Option<ChecksumVerification>,
+    /// Load a segment scan's filter columns first and the other columns only
+    /// into zones where some row passes the filter. Defaults to true.
+    pub late_materialization: Option<bool>,
+    /// Size limits checked while parsing, before the planner runs. Unset = defaults.
+    #[serde(default)]
+    pub parse_limits: Option<ParseLimitsConfig>,
+    /// Complexity budget enforced on every query after planning. Unset = no limits.
+    #[serde(default)]
+    pub complexity: Option<QueryComplexityConfig>,
+    /// Memory budget per query for aggregation, join, and sort state. Unset = unlimited.
+    #[serde(default)]
+    pub memory: Option<QueryMemoryConfig>,
+    /// Caps on queries executing at once, server-wide and per user. Unset = unlimited.
+    #[serde(default)]
+    pub concurrency: Option<QueryConcurrencyConfig>,
+    /// Max time a query may run, from planning until its last row is sent. Unset or 0 = unlimited.
+    #[serde(default)]
+    pub query_timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheWarmStart {
+    /// Start with cold caches and save nothing on shutdown
+    Off,
+    /// Load the saved keys before accepting connections
+    Blocking,
+    /// Accept connections right away and load the saved keys in the background
+    Background,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InternEviction {
+    /// Drop the least recently used identifier to make room
+    Lru,
+    /// Keep the table as is; new identifiers get a one-off id that is never cached
+    Bypass,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumVerification {
+    /// Read blocks without checking them
+    Off,
+    /// Log a corrupt block and read it anyway
+    Warn,
+    /// Log a corrupt block and fail the query reading it
+    Fail,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheAdmission {
+    /// Cache every filter on its first load
+    Always,
+    /// Cache a filter only on its second load within a recent window, so
+    /// filters read by a single scan do not displace frequently read ones
+    Frequency,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderTiebreaker {
+    /// Ingest order: the per-shard sequence carried in each event id
+    EventId,
+    /// No tiebreak; equal rows come out in whatever order the sort leaves them
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryHasher {
+    /// AHash: fast, resists collisions well enough for trusted input
+    Ahash,
+    /// SipHash-1-3 with random keys: slower, hardened against inputs crafted to collide
+    Sip,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ParseLimitsConfig {
+    /// Max number of values in a single IN list. Defaults to 100000.
+    pub max_in_list: Option<usize>,
+    /// Max nesting depth of a condition, counting AND, OR, NOT and parentheses.
+    /// Defaults to 256.
+    pub max_condition_depth: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct QueryComplexityConfig {
+    /// Max number of predicates in the WHERE clause
+    pub max_conditions: Option<usize>,
+    /// Max number of values in a single IN list
+    pub max_in_list: Option<usize>,
+    /// Max number of linked events in a sequence query (FOLLOWED BY / PRECEDED BY)
+    pub max_joins: Option<usize>,
+    /// Max number of zones the query is estimated to scan
+    pub max_candidate_zones: Option<usize>,
+    /// What happens to a query over `max_candidate_zones`. Defaults to `reject`.
+    pub on_excess_zones: Option<ExcessZonesAction>,
+    /// Per-role overrides; when a user has several roles, the most permissive value wins
+    #[serde(default)]
+    pub roles: HashMap<String, ComplexityLimitsConfig>,
+    /// Per-user overrides, applied after role overrides
+    #[serde(default)]
+    pub users: HashMap<String, ComplexityLimitsConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ComplexityLimitsConfig {
+    pub max_conditions: Option<usize>,
+    pub max_in_list: Option<usize>,
+    pub max_joins: Option<usize>,
+    pub max_candidate_zones: Option<usize>,
+    pub on_excess_zones: Option<ExcessZonesAction>,
+}
+
+/// Response to a query estimated to scan more than `max_candidate_zones`.
+/// Ordered from least to most permissive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExcessZonesAction {
+    /// Reject the query
+    #[default]
+    Reject,
+    /// Run it only when it carries the `/* allow_full_scan */` hint
+    RequireHint,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct QueryMemoryConfig {
+    /// Max bytes a single query may hold. Can be specified as human-readable string (e.g., "512MB") or integer (bytes).
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub max_bytes: Option<usize>,
+    /// Per-user overrides of `max_bytes`
+    #[serde(default)]
+    pub users: HashMap<String, QueryMemoryLimitConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct QueryMemoryLimitConfig {
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct QueryConcurrencyConfig {
+    /// Queries executing at once across all users
+    pub max_in_flight: Option<usize>,
+    /// Queries a single user may execute at once
+    pub max_in_flight_per_user: Option<usize>,
+    /// Queries that may wait for a slot; further ones are rejected. 0 = reject at once
+    #[serde(default)]
+    pub max_queued: usize,
+    /// How long a queued query waits for a slot before it fails
+    #[serde(default = "default_query_queue_timeout_ms")]
+    pub queue_timeout_ms: u64,
+    /// Per-user overrides of `max_in_flight_per_user`
+    #[serde(default)]
+    pub users: HashMap<String, QueryConcurrencyLimitConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct QueryConcurrencyLimitConfig {
+    pub max_in_flight: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct IngestConfig {
+    /// Max size of a single event payload, measured as serialized JSON. Can be specified as human-readable string (e.g., "1MB") or integer (bytes).
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub max_payload_bytes: Option<usize>,
+    /// Reject a whole BATCH when any event in it is oversized, instead of only those events
+    #[serde(default)]
+    pub strict_batches: bool,
+    /// Per-event-type overrides of `max_payload_bytes`
+    #[serde(default)]
+    pub schemas: HashMap<String, IngestLimitConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IngestLimitConfig {
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub max_payload_bytes: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RetentionConfig {
+    /// Retention deletes data: no window takes effect unless this is set
+    #[serde(default)]
+    pub enabled: bool,
+    /// Retention windows keyed by event type; event types without one keep everything
+    #[serde(default)]
+    pub schemas: HashMap<String, RetentionWindowConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionWindowConfig {
+    /// Events whose time is older than this many days are dropped at compaction
+    pub max_age_days: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -308,6 +720,45 @@ pub struct ServerConfig {
     /// Backpressure threshold: percentage of shard channel capacity before rejecting requests (0-100)
     #[serde(default = "default_backpressure_threshold")]
     pub backpressure_threshold: u8,
+    /// How null values are written with `output_format = "csv"`
+    /// Default: "" (an empty field)
+    #[serde(default)]
+    pub csv_null: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TcpConfig {
+    /// Terminate TLS on the TCP listener; connections are plaintext when unset
+    pub tls: Option<TcpTlsConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TcpTlsConfig {
+    /// PEM file with the server certificate, followed by any intermediates
+    pub cert_path: String,
+    /// PEM file with the private key of the certificate (PKCS#8, PKCS#1 or SEC1)
+    pub key_path: String,
+    /// PEM file with the CAs trusted to sign client certificates. When set, every
+    /// client must present a certificate chaining to one of them, and the certificate
+    /// authenticates the connection as the user mapped in `[auth] client_cert_users`.
+    #[serde(default)]
+    pub client_ca_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HttpConfig {
+    /// Compress response bodies for clients sending `Accept-Encoding: gzip` or `deflate`.
+    /// Default: true
+    #[serde(default = "default_http_compression")]
+    pub compression: bool,
+    /// Responses smaller than this are sent uncompressed. Can be specified as
+    /// human-readable string (e.g., "1KB") or integer (bytes). Default: 1KB
+    #[serde(default, deserialize_with = "parse_optional_size_bytes")]
+    pub compression_min_bytes: Option<usize>,
+}
+
+fn default_http_compression() -> bool {
+    true
 }
 
 fn default_backpressure_threshold() -> u8 {
@@ -325,6 +776,30 @@ pub struct LoggingConfig {
     pub log_dir: String,
     pub stdout_level: String,
     pub file_level: String,
+    /// "text" for human-readable lines, "json" for one JSON object per event
+    /// Default: "text"
+    #[serde(default)]
+    pub format: LogFormat,
+    /// Minimum interval between two occurrences of a sampled log line; 0 disables sampling
+    /// Default: 1000
+    #[serde(default = "default_log_sample_interval_ms")]
+    pub sample_interval_ms: u64,
+}
+
+fn default_query_queue_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_log_sample_interval_ms() -> u64 {
+    1000
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
 }
 
 #[derive(Debug, Deserialize)]
@@ -358,12 +833,76 @@ pub struct AuthConfig {
     /// Default: 300 seconds (5 minutes)
     #[serde(default = "default_session_token_expiry")]
     pub session_token_expiry_seconds: u64,
+    /// Session token format: "opaque" tokens live in this process's session store;
+    /// "stateless" tokens are signed and any node with the same `session_signing_key` accepts them
+    /// Default: "opaque"
+    #[serde(default)]
+    pub session_token_format: SessionTokenFormat,
+    /// Shared HMAC key for stateless session tokens, at least 32 bytes
+    pub session_signing_key: Option<String>,
+    /// Maps client certificate subjects to SnelDB user IDs for mutual-TLS authentication.
+    /// Keys may be a full subject DN ("CN=ingest,O=Acme") or a bare common name ("ingest").
+    /// Certificates without a matching entry are rejected.
+    #[serde(default)]
+    pub client_cert_users: HashMap<String, String>,
+    /// Consecutive signature failures within `lockout_window_seconds` that lock an account.
+    /// Default: 0 (lockout disabled)
+    #[serde(default)]
+    pub lockout_max_failures: u32,
+    /// Window in which failures count towards a lockout
+    /// Default: 300 seconds
+    #[serde(default = "default_lockout_window")]
+    pub lockout_window_seconds: u64,
+    /// How long a locked account stays locked unless an admin unlocks it
+    /// Default: 900 seconds
+    #[serde(default = "default_lockout_cooldown")]
+    pub lockout_cooldown_seconds: u64,
+    /// Count failures separately per client address instead of per user only
+    /// Default: false
+    #[serde(default)]
+    pub lockout_per_source: bool,
+    /// Allowed difference between a stamped request's timestamp and server time
+    /// Default: 30 seconds
+    #[serde(default = "default_replay_max_skew")]
+    pub replay_max_skew_seconds: u64,
+    /// Maximum number of recently seen nonces kept to reject replays
+    /// Default: 100000
+    #[serde(default = "default_replay_nonce_capacity")]
+    pub replay_nonce_capacity: usize,
+    /// Reject signatures without a timestamp and nonce
+    /// Default: false (plain signatures are still accepted)
+    #[serde(default)]
+    pub replay_require_nonce: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionTokenFormat {
+    #[default]
+    Opaque,
+    Stateless,
 }
 
 fn default_session_token_expiry() -> u64 {
     300 // Default to 5 minutes
 }
 
+fn default_lockout_window() -> u64 {
+    300 // Default to a 5 minute failure window
+}
+
+fn default_lockout_cooldown() -> u64 {
+    900 // Default to 15 minutes locked
+}
+
+fn default_replay_max_skew() -> u64 {
+    30 // Default to 30 seconds of clock skew
+}
+
+fn default_replay_nonce_capacity() -> usize {
+    100_000 // Default to 100k remembered nonces
+}
+
 fn default_bypass_auth() -> bool {
     false // Default to requiring authentication
 }
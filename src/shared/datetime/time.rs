@@ -1,7 +1,36 @@
 use crate::shared::config::CONFIG;
-use chrono::Weekday;
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone, Weekday};
 use chrono_tz::Tz;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+static DEFAULT_TIMEZONE: Lazy<Tz> = Lazy::new(|| TimeConfig::from_app_config().resolve_timezone());
+
+/// Server-wide timezone from `[time] timezone`, used wherever a time carries no
+/// offset of its own: calendar bucketing, date and datetime literals without an
+/// offset, and formatted timestamps. UTC when unset or not a known zone name.
+pub fn default_timezone() -> Tz {
+    *DEFAULT_TIMEZONE
+}
+
+/// The instant a wall-clock time names in `tz`. Around DST changes a wall-clock
+/// time can occur twice, and the earlier one is taken, or be skipped by the
+/// clocks, and it is then read with the offset in force before the jump (02:30 on
+/// a night the clocks go from 02:00 to 03:00 becomes 03:30).
+pub fn resolve_local(tz: &Tz, local: NaiveDateTime) -> DateTime<Tz> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => dt,
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        LocalResult::None => {
+            // A day earlier is safely before the transition
+            let before = tz
+                .offset_from_utc_datetime(&(local - Duration::days(1)))
+                .fix();
+            tz.from_utc_datetime(&(local - Duration::seconds(before.local_minus_utc() as i64)))
+        }
+    }
+}
 
 /// Global time configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -32,6 +61,18 @@ impl TimeConfig {
             .and_then(|tz_str| tz_str.parse().ok())
     }
 
+    /// The configured timezone, or UTC when unset or not a known zone name.
+    pub fn resolve_timezone(&self) -> Tz {
+        match (&self.timezone, self.parse_timezone()) {
+            (_, Some(tz)) => tz,
+            (None, None) => Tz::UTC,
+            (Some(name), None) => {
+                warn!(target: "sneldb::time", timezone = %name, "Unknown timezone, using UTC");
+                Tz::UTC
+            }
+        }
+    }
+
     /// Create from application configuration
     pub fn from_app_config() -> Self {
         CONFIG.time.clone().unwrap_or_else(|| Self::default())
@@ -1,107 +1,85 @@
-use super::time::TimeConfig;
-use crate::command::types::TimeGranularity;
-use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use super::time::{TimeConfig, default_timezone, resolve_local};
+use crate::command::types::{CalendarUnit, TimeGranularity};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc, Weekday};
 use chrono_tz::Tz;
+use std::cell::RefCell;
 
 /// Calendar-aware time bucketing implementation
 pub struct CalendarTimeBucketer {
     config: TimeConfig,
-    // Cache parsed timezone to avoid repeated parsing
-    // This is safe because TimeConfig is typically set at startup and doesn't change
-    cached_tz: Option<Tz>,
+    // Parsed once at construction; UTC when the config names no valid zone
+    tz: Tz,
 }
 
 impl CalendarTimeBucketer {
     pub fn new(config: TimeConfig) -> Self {
-        // Parse and cache timezone once at construction
-        // This avoids repeated timezone parsing on every bucket_of call
-        let cached_tz = config.parse_timezone();
-        Self { config, cached_tz }
+        let tz = config.resolve_timezone();
+        Self { config, tz }
+    }
+
+    /// Bucketer for an explicit zone whose weeks are ISO weeks, starting on Monday.
+    pub fn iso(tz: Tz) -> Self {
+        let config = TimeConfig {
+            timezone: Some(tz.name().to_string()),
+            week_start: Weekday::Mon,
+            use_calendar_bucketing: true,
+        };
+        Self { config, tz }
     }
 
     /// Calculate the bucket start timestamp for a given granularity
-    /// Uses cached timezone for performance (no mutex contention)
     pub fn bucket_of(&self, ts: u64, gran: &TimeGranularity) -> u64 {
-        // Use cached timezone - no mutex needed since it's immutable after construction
-        if let Some(ref tz) = self.cached_tz {
-            let dt = DateTime::from_timestamp(ts as i64, 0)
-                .unwrap_or_else(|| Utc.timestamp_opt(0, 0).single().unwrap())
-                .with_timezone(tz);
-
-            let bucket_dt = match gran {
-                TimeGranularity::Hour => self.bucket_hour(dt),
-                TimeGranularity::Day => self.bucket_day(dt),
-                TimeGranularity::Week => self.bucket_week(dt),
-                TimeGranularity::Month => self.bucket_month(dt),
-                TimeGranularity::Year => self.bucket_year(dt),
-            };
-
-            bucket_dt.timestamp() as u64
-        } else {
-            let dt = DateTime::from_timestamp(ts as i64, 0)
-                .unwrap_or_else(|| Utc.timestamp_opt(0, 0).single().unwrap());
-
-            let bucket_dt = match gran {
-                TimeGranularity::Hour => self.bucket_hour(dt),
-                TimeGranularity::Day => self.bucket_day(dt),
-                TimeGranularity::Week => self.bucket_week(dt),
-                TimeGranularity::Month => self.bucket_month(dt),
-                TimeGranularity::Year => self.bucket_year(dt),
-            };
-
-            bucket_dt.timestamp() as u64
-        }
+        let dt = DateTime::from_timestamp(ts as i64, 0)
+            .unwrap_or_else(|| Utc.timestamp_opt(0, 0).single().unwrap())
+            .with_timezone(&self.tz);
+
+        let bucket_dt = match gran {
+            TimeGranularity::Hour => self.bucket_hour(dt),
+            TimeGranularity::Day => self.bucket_day(dt),
+            TimeGranularity::Week => self.bucket_week(dt),
+            TimeGranularity::Month => self.bucket_month(dt),
+            TimeGranularity::Year => self.bucket_year(dt),
+            TimeGranularity::Interval {
+                seconds, origin, ..
+            } => return interval_bucket_of(ts, *seconds, *origin),
+            TimeGranularity::Calendar { unit, timezone } => {
+                return calendar_bucket_of(ts, *unit, timezone.as_deref());
+            }
+        };
+
+        bucket_dt.timestamp() as u64
     }
 
-    fn bucket_hour<T: TimeZone>(&self, dt: DateTime<T>) -> DateTime<T> {
-        dt.date_naive()
-            .and_hms_opt(dt.hour(), 0, 0)
-            .unwrap()
-            .and_local_timezone(dt.timezone())
-            .unwrap()
+    fn bucket_hour(&self, dt: DateTime<Tz>) -> DateTime<Tz> {
+        // Truncate the instant rather than rebuild the local time, so the two
+        // 01:00 hours of a DST fall-back night stay separate buckets
+        dt - Duration::seconds((dt.minute() * 60 + dt.second()) as i64)
     }
 
-    fn bucket_day<T: TimeZone>(&self, dt: DateTime<T>) -> DateTime<T> {
-        dt.date_naive()
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_local_timezone(dt.timezone())
-            .unwrap()
+    /// Local midnight starting `date`, or the first instant of that day when the
+    /// clocks skip midnight.
+    fn start_of(&self, date: NaiveDate) -> DateTime<Tz> {
+        resolve_local(&self.tz, date.and_hms_opt(0, 0, 0).unwrap())
     }
 
-    fn bucket_week<T: TimeZone>(&self, dt: DateTime<T>) -> DateTime<T> {
+    fn bucket_day(&self, dt: DateTime<Tz>) -> DateTime<Tz> {
+        self.start_of(dt.date_naive())
+    }
+
+    fn bucket_week(&self, dt: DateTime<Tz>) -> DateTime<Tz> {
         let days_since_week_start = (dt.weekday().num_days_from_monday()
             + (7 - self.config.week_start.num_days_from_monday()))
             % 7;
 
-        let week_start = dt.date_naive() - chrono::Duration::days(days_since_week_start as i64);
-        week_start
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_local_timezone(dt.timezone())
-            .unwrap()
-    }
-
-    fn bucket_month<T: TimeZone>(&self, dt: DateTime<T>) -> DateTime<T> {
-        dt.date_naive()
-            .with_day(1)
-            .unwrap()
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_local_timezone(dt.timezone())
-            .unwrap()
-    }
-
-    fn bucket_year<T: TimeZone>(&self, dt: DateTime<T>) -> DateTime<T> {
-        dt.date_naive()
-            .with_month(1)
-            .unwrap()
-            .with_day(1)
-            .unwrap()
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_local_timezone(dt.timezone())
-            .unwrap()
+        self.start_of(dt.date_naive() - Duration::days(days_since_week_start as i64))
+    }
+
+    fn bucket_month(&self, dt: DateTime<Tz>) -> DateTime<Tz> {
+        self.start_of(dt.date_naive().with_day(1).unwrap())
+    }
+
+    fn bucket_year(&self, dt: DateTime<Tz>) -> DateTime<Tz> {
+        self.start_of(dt.date_naive().with_month(1).unwrap().with_day(1).unwrap())
     }
 }
 
@@ -113,9 +91,50 @@ pub fn naive_bucket_of(ts: u64, gran: &TimeGranularity) -> u64 {
         TimeGranularity::Week => (ts / 604_800) * 604_800,
         TimeGranularity::Month => (ts / 2_592_000) * 2_592_000, // naive 30-day month bucket
         TimeGranularity::Year => (ts / 31_536_000) * 31_536_000, // naive 365-day year bucket
+        TimeGranularity::Interval {
+            seconds, origin, ..
+        } => interval_bucket_of(ts, *seconds, *origin),
+        // Calendar units have no naive form
+        TimeGranularity::Calendar { unit, timezone } => {
+            calendar_bucket_of(ts, *unit, timezone.as_deref())
+        }
     }
 }
 
+/// Start of the calendar `unit` holding `ts` in `timezone`, or in the server's
+/// default zone when `None`. Weeks are ISO weeks, starting on Monday. The
+/// bucketer of the last zone used is kept per thread, as grouping asks for
+/// the same zone row after row.
+pub fn calendar_bucket_of(ts: u64, unit: CalendarUnit, timezone: Option<&str>) -> u64 {
+    thread_local! {
+        static BUCKETER: RefCell<Option<(Option<String>, CalendarTimeBucketer)>> =
+            const { RefCell::new(None) };
+    }
+    BUCKETER.with(|cell| {
+        let mut cached = cell.borrow_mut();
+        if cached
+            .as_ref()
+            .is_none_or(|(name, _)| name.as_deref() != timezone)
+        {
+            let tz = timezone
+                .and_then(|name| name.parse::<Tz>().ok())
+                .unwrap_or_else(default_timezone);
+            *cached = Some((timezone.map(str::to_string), CalendarTimeBucketer::iso(tz)));
+        }
+        let (_, bucketer) = cached.as_ref().expect("bucketer cached above");
+        bucketer.bucket_of(ts, &unit.granularity())
+    })
+}
+
+/// Start of the `seconds`-wide bucket holding `ts`, counting buckets from
+/// `origin` in both directions. Plain arithmetic on epoch seconds, so every
+/// bucket has the same width whatever the time zone's DST shifts.
+pub fn interval_bucket_of(ts: u64, seconds: u64, origin: u64) -> u64 {
+    let width = seconds.max(1) as i128;
+    let offset = (ts as i128 - origin as i128).div_euclid(width) * width;
+    (origin as i128 + offset).max(0) as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,6 +154,89 @@ mod tests {
         assert_eq!(bucket, expected);
     }
 
+    #[test]
+    fn test_interval_bucketing_is_fixed_width() {
+        // 15 minute buckets from the epoch
+        assert_eq!(interval_bucket_of(1_000, 900, 0), 900);
+        assert_eq!(interval_bucket_of(1_800, 900, 0), 1_800);
+        // Aligned to an origin, including timestamps before it
+        assert_eq!(interval_bucket_of(1_000, 900, 100), 1_000);
+        assert_eq!(interval_bucket_of(999, 900, 100), 100);
+        assert_eq!(interval_bucket_of(50, 900, 100), 0);
+        assert_eq!(interval_bucket_of(5_000, 3_600, 4_000), 4_000);
+        assert_eq!(interval_bucket_of(3_000, 3_600, 4_000), 400);
+
+        // A DST change does not move hour intervals, unlike calendar hours
+        let bucketer = CalendarTimeBucketer::new(TimeConfig {
+            timezone: Some("America/New_York".to_string()),
+            week_start: chrono::Weekday::Mon,
+            use_calendar_bucketing: true,
+        });
+        let gran = TimeGranularity::Interval {
+            seconds: 3 * 3_600,
+            origin: 0,
+            fill: false,
+        };
+        // 2024-03-10 06:30 and 08:30 UTC, around the spring-forward gap
+        for ts in [1_710_052_200u64, 1_710_059_400] {
+            assert_eq!(bucketer.bucket_of(ts, &gran), ts - ts % 10_800);
+            assert_eq!(naive_bucket_of(ts, &gran), ts - ts % 10_800);
+        }
+    }
+
+    #[test]
+    fn test_calendar_units_in_explicit_timezone() {
+        let amsterdam = Some("Europe/Amsterdam");
+        // 2024-03-01 00:00 in Amsterdam (CET) is 2024-02-29 23:00 UTC
+        let march_1 = 1_709_247_600;
+        assert_eq!(
+            calendar_bucket_of(march_1, CalendarUnit::Month, amsterdam),
+            march_1
+        );
+        // A second earlier is still February there: 2024-02-01 00:00 CET
+        assert_eq!(
+            calendar_bucket_of(march_1 - 1, CalendarUnit::Month, amsterdam),
+            1_706_742_000
+        );
+        // In UTC the same instant is still February 29
+        assert_eq!(
+            calendar_bucket_of(march_1, CalendarUnit::Month, Some("UTC")),
+            1_706_745_600
+        );
+        // Summer months start at 22:00 UTC (CEST)
+        assert_eq!(
+            calendar_bucket_of(1_719_784_800, CalendarUnit::Month, amsterdam),
+            1_719_784_800
+        );
+        // 2024 starts at 2023-12-31 23:00 UTC there
+        assert_eq!(
+            calendar_bucket_of(1_709_247_600, CalendarUnit::Year, amsterdam),
+            1_704_063_600
+        );
+    }
+
+    #[test]
+    fn test_calendar_weeks_are_iso_weeks() {
+        // Sunday 2024-01-07 12:00 UTC belongs to the ISO week of Monday 2024-01-01
+        let sunday_noon = 1_704_628_800;
+        assert_eq!(
+            calendar_bucket_of(sunday_noon, CalendarUnit::Week, Some("UTC")),
+            1_704_067_200
+        );
+        // Whatever the week start of the granularity's calendar bucketer
+        let gran = TimeGranularity::Calendar {
+            unit: CalendarUnit::Week,
+            timezone: Some("UTC".to_string()),
+        };
+        let sunday_weeks = CalendarTimeBucketer::new(TimeConfig {
+            timezone: None,
+            week_start: Weekday::Sun,
+            use_calendar_bucketing: true,
+        });
+        assert_eq!(sunday_weeks.bucket_of(sunday_noon, &gran), 1_704_067_200);
+        assert_eq!(naive_bucket_of(sunday_noon, &gran), 1_704_067_200);
+    }
+
     #[test]
     fn test_calendar_week_bucketing() {
         let config = TimeConfig {
@@ -386,6 +386,44 @@ fn calendar_bucketing_dst_transition() {
     assert_ne!(before_bucket, after_bucket);
 }
 
+#[test]
+fn calendar_bucketing_keeps_repeated_fall_back_hours_apart() {
+    let bucketer = create_bucketer(Some("US/Eastern"), Weekday::Mon, true);
+
+    // November 3, 2024: 01:00-02:00 happens first in EDT, then again in EST
+    let first = TimestampFactory::utc_datetime(2024, 11, 3, 5, 30, 0); // 01:30 EDT
+    let second = TimestampFactory::utc_datetime(2024, 11, 3, 6, 30, 0); // 01:30 EST
+    assert_eq!(
+        bucketer.bucket_of(first, &TimeGranularity::Hour),
+        TimestampFactory::utc_datetime(2024, 11, 3, 5, 0, 0)
+    );
+    assert_eq!(
+        bucketer.bucket_of(second, &TimeGranularity::Hour),
+        TimestampFactory::utc_datetime(2024, 11, 3, 6, 0, 0)
+    );
+
+    // Both fall on the same local day, which starts at midnight EDT
+    let midnight = TimestampFactory::utc_datetime(2024, 11, 3, 4, 0, 0);
+    assert_eq!(bucketer.bucket_of(first, &TimeGranularity::Day), midnight);
+    assert_eq!(bucketer.bucket_of(second, &TimeGranularity::Day), midnight);
+}
+
+#[test]
+fn calendar_bucketing_day_starts_when_midnight_is_skipped() {
+    // Chile springs forward at midnight: September 8, 2024 starts at 01:00 (-03)
+    let bucketer = create_bucketer(Some("America/Santiago"), Weekday::Mon, true);
+    let noon = TimestampFactory::utc_datetime(2024, 9, 8, 15, 0, 0);
+    let day_start = TimestampFactory::utc_datetime(2024, 9, 8, 4, 0, 0);
+
+    assert_eq!(bucketer.bucket_of(noon, &TimeGranularity::Day), day_start);
+    // September 8, 2024 is a Sunday
+    let sunday_bucketer = create_bucketer(Some("America/Santiago"), Weekday::Sun, true);
+    assert_eq!(
+        sunday_bucketer.bucket_of(noon, &TimeGranularity::Week),
+        day_start
+    );
+}
+
 // ============================================================================
 // Edge Cases and Error Handling
 // ============================================================================
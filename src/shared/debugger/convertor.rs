@@ -8,7 +8,7 @@ use crate::engine::core::FieldXorFilter;
 use crate::engine::core::ZoneIndex;
 use crate::engine::core::ZoneMeta;
 use crate::engine::core::column::compression::compressed_column_index::CompressedColumnIndex;
-use crate::engine::core::column::compression::compression_codec::{CompressionCodec, Lz4Codec};
+use crate::engine::core::column::compression::compression_codec::codec_for_algo;
 use crate::engine::core::zone::enum_bitmap_index::EnumBitmapIndex;
 use crate::engine::schema::registry::{MiniSchema, SchemaRecord, SchemaRegistry};
 use crate::shared::storage_header::{BinaryHeader, FileKind};
@@ -137,7 +137,6 @@ pub fn main() {
                 }
             }
 
-            let codec = Lz4Codec;
             let mut combined: HashMap<u32, Vec<String>> = HashMap::new();
 
             // Process each zone in sorted order
@@ -161,14 +160,15 @@ pub fn main() {
                 }
 
                 // Decompress
-                let uncompressed =
-                    match codec.decompress(&compressed_bytes, entry.uncomp_len as usize) {
-                        Ok(data) => data,
-                        Err(e) => {
-                            eprintln!("Failed to decompress zone {}: {}", zone_id, e);
-                            std::process::exit(1);
-                        }
-                    };
+                let uncompressed = match codec_for_algo(entry.codec).and_then(|codec| {
+                    codec.decompress(&compressed_bytes, entry.uncomp_len as usize)
+                }) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        eprintln!("Failed to decompress zone {}: {}", zone_id, e);
+                        std::process::exit(1);
+                    }
+                };
 
                 // Parse uncompressed data: [u16 len][bytes] repeated
                 let mut cursor = 0;
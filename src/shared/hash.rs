@@ -1,5 +1,11 @@
+use ahash::{AHasher, RandomState as AHashRandomState};
+use once_cell::sync::Lazy;
 use rustc_hash::FxHasher;
-use std::hash::{Hash, Hasher};
+use std::collections::hash_map::{DefaultHasher, RandomState as SipRandomState};
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use crate::shared::config::{CONFIG, QueryHasher};
 
 /// Deterministic 64-bit hash for persisted filter keys.
 /// Uses fixed keys to guarantee stability across processes and runs.
@@ -10,9 +16,132 @@ pub fn stable_hash64<T: Hash>(value: &T) -> u64 {
     hasher.finish()
 }
 
+/// `HashMap` built by a query, hashed with `query.hasher`.
+pub type QueryHashMap<K, V> = HashMap<K, V, QueryHashState>;
+
+/// `HashSet` built by a query, hashed with `query.hasher`.
+pub type QueryHashSet<T> = HashSet<T, QueryHashState>;
+
+static CONFIGURED_HASHER: Lazy<QueryHasher> = Lazy::new(|| {
+    CONFIG
+        .query
+        .as_ref()
+        .and_then(|cfg| cfg.hasher)
+        .unwrap_or(QueryHasher::Ahash)
+});
+
+/// The hash function set by `query.hasher`; ahash unless configured otherwise.
+pub fn configured_query_hasher() -> QueryHasher {
+    *CONFIGURED_HASHER
+}
+
+/// Hash state of the tables a query builds in memory. Every state draws fresh
+/// random keys, so hashes are only comparable between clones of the same state:
+/// anything hashed ahead of a lookup (such as group key prehashes) must use the
+/// table's own state.
+#[derive(Clone, Debug)]
+pub enum QueryHashState {
+    Ahash(AHashRandomState),
+    Sip(SipRandomState),
+}
+
+impl QueryHashState {
+    pub fn new(hasher: QueryHasher) -> Self {
+        match hasher {
+            QueryHasher::Ahash => Self::Ahash(AHashRandomState::new()),
+            QueryHasher::Sip => Self::Sip(SipRandomState::new()),
+        }
+    }
+
+    pub fn hasher(&self) -> QueryHasher {
+        match self {
+            Self::Ahash(_) => QueryHasher::Ahash,
+            Self::Sip(_) => QueryHasher::Sip,
+        }
+    }
+}
+
+impl Default for QueryHashState {
+    fn default() -> Self {
+        Self::new(configured_query_hasher())
+    }
+}
+
+impl BuildHasher for QueryHashState {
+    type Hasher = QueryStateHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> Self::Hasher {
+        match self {
+            Self::Ahash(state) => QueryStateHasher::Ahash(state.build_hasher()),
+            Self::Sip(state) => QueryStateHasher::Sip(state.build_hasher()),
+        }
+    }
+}
+
+/// Hasher produced by a [`QueryHashState`].
+#[derive(Clone, Debug)]
+pub enum QueryStateHasher {
+    Ahash(AHasher),
+    Sip(DefaultHasher),
+}
+
+impl Hasher for QueryStateHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        match self {
+            Self::Ahash(h) => h.finish(),
+            Self::Sip(h) => h.finish(),
+        }
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Ahash(h) => h.write(bytes),
+            Self::Sip(h) => h.write(bytes),
+        }
+    }
+
+    // AHasher mixes integers without going through bytes; keep that fast path
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        match self {
+            Self::Ahash(h) => h.write_u8(i),
+            Self::Sip(h) => h.write_u8(i),
+        }
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        match self {
+            Self::Ahash(h) => h.write_u32(i),
+            Self::Sip(h) => h.write_u32(i),
+        }
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        match self {
+            Self::Ahash(h) => h.write_u64(i),
+            Self::Sip(h) => h.write_u64(i),
+        }
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        match self {
+            Self::Ahash(h) => h.write_usize(i),
+            Self::Sip(h) => h.write_usize(i),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::stable_hash64;
+    use super::{QueryHashSet, QueryHashState, stable_hash64};
+    use crate::shared::config::QueryHasher;
+    use std::hash::BuildHasher;
 
     #[test]
     fn stable_hash64_is_deterministic() {
@@ -22,4 +151,23 @@ mod tests {
         // Different input hashes differently
         assert_ne!(a, stable_hash64(&"banana"));
     }
+
+    #[test]
+    fn query_hash_state_is_consistent_within_clones() {
+        for hasher in [QueryHasher::Ahash, QueryHasher::Sip] {
+            let state = QueryHashState::new(hasher);
+            assert_eq!(state.hasher(), hasher);
+            assert_eq!(
+                state.hash_one("apple"),
+                state.clone().hash_one("apple"),
+                "{:?}",
+                hasher
+            );
+
+            let mut set = QueryHashSet::with_hasher(state);
+            set.extend(["a", "b", "a"]);
+            assert_eq!(set.len(), 2);
+            assert!(set.contains("b"));
+        }
+    }
 }
@@ -0,0 +1,84 @@
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::Level;
+
+use crate::shared::config::CONFIG;
+
+static CLOCK_START: Lazy<Instant> = Lazy::new(Instant::now);
+static CONFIGURED_INTERVAL_MS: Lazy<u64> = Lazy::new(|| CONFIG.logging.sample_interval_ms);
+
+/// Lets a repetitive log line through at most once per interval and counts the
+/// occurrences dropped in between. Meant to live in a `static` at the call site:
+///
+/// ```ignore
+/// static RATE_LIMITED: LogSampler = LogSampler::new();
+/// if let Some(dropped) = RATE_LIMITED.admit(Level::WARN) {
+///     warn!(target: "sneldb::auth", dropped, "Rate limit exceeded");
+/// }
+/// ```
+///
+/// ERROR-level lines are never sampled.
+#[derive(Debug)]
+pub struct LogSampler {
+    /// `None` uses `logging.sample_interval_ms`
+    interval_ms: Option<u64>,
+    /// Milliseconds since `CLOCK_START` before which lines are dropped
+    next_emit_ms: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl LogSampler {
+    /// A sampler using the configured interval.
+    pub const fn new() -> Self {
+        Self {
+            interval_ms: None,
+            next_emit_ms: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    pub const fn with_interval(interval: Duration) -> Self {
+        Self {
+            interval_ms: Some(interval.as_millis() as u64),
+            next_emit_ms: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of lines dropped since the last one emitted if this one
+    /// should be logged, `None` if it is dropped.
+    pub fn admit(&self, level: Level) -> Option<u64> {
+        self.admit_at(level, CLOCK_START.elapsed().as_millis() as u64)
+    }
+
+    /// `admit` at `now_ms` milliseconds on the sampler's clock.
+    pub(crate) fn admit_at(&self, level: Level, now_ms: u64) -> Option<u64> {
+        let interval_ms = self.interval_ms.unwrap_or(*CONFIGURED_INTERVAL_MS);
+        if level == Level::ERROR || interval_ms == 0 {
+            return Some(self.dropped.swap(0, Ordering::Relaxed));
+        }
+        let next = self.next_emit_ms.load(Ordering::Relaxed);
+        if now_ms >= next
+            && self
+                .next_emit_ms
+                .compare_exchange(
+                    next,
+                    now_ms.saturating_add(interval_ms),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+        {
+            return Some(self.dropped.swap(0, Ordering::Relaxed));
+        }
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+}
+
+impl Default for LogSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,35 @@
+use crate::shared::log_sampler::LogSampler;
+use std::time::Duration;
+use tracing::Level;
+
+#[test]
+fn emits_once_per_interval_with_dropped_count() {
+    let sampler = LogSampler::with_interval(Duration::from_millis(1000));
+
+    assert_eq!(sampler.admit_at(Level::WARN, 0), Some(0));
+    assert_eq!(sampler.admit_at(Level::WARN, 10), None);
+    assert_eq!(sampler.admit_at(Level::INFO, 999), None);
+    assert_eq!(sampler.admit_at(Level::WARN, 1000), Some(2));
+    assert_eq!(sampler.admit_at(Level::WARN, 1500), None);
+    assert_eq!(sampler.admit_at(Level::WARN, 2500), Some(1));
+}
+
+#[test]
+fn never_samples_errors() {
+    let sampler = LogSampler::with_interval(Duration::from_millis(1000));
+
+    assert_eq!(sampler.admit_at(Level::WARN, 0), Some(0));
+    assert_eq!(sampler.admit_at(Level::WARN, 1), None);
+    // Errors always pass and report the lines dropped before them
+    assert_eq!(sampler.admit_at(Level::ERROR, 2), Some(1));
+    assert_eq!(sampler.admit_at(Level::ERROR, 3), Some(0));
+}
+
+#[test]
+fn zero_interval_disables_sampling() {
+    let sampler = LogSampler::with_interval(Duration::ZERO);
+
+    for now in 0..3 {
+        assert_eq!(sampler.admit_at(Level::DEBUG, now), Some(0));
+    }
+}
@@ -2,12 +2,18 @@ pub mod config;
 pub mod datetime;
 pub mod debugger;
 pub mod hash;
+pub mod log_sampler;
 pub mod path;
 pub mod response;
 pub mod storage_header;
 pub mod time;
+pub mod usage;
 
+#[cfg(test)]
+pub mod log_sampler_tests;
 #[cfg(test)]
 pub mod storage_header_tests;
 #[cfg(test)]
 pub mod time_tests;
+#[cfg(test)]
+pub mod usage_tests;
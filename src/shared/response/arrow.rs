@@ -22,6 +22,10 @@ impl Renderer for ArrowRenderer {
         // For non-streaming responses we fall back to compact JSON to keep clients informed.
         let mut payload = serde_json::Map::new();
         payload.insert("status".into(), response.status.code().into());
+        if let Some(code) = response.error_code {
+            payload.insert("code".into(), code.as_str().into());
+            payload.insert("retryable".into(), code.is_retryable().into());
+        }
         payload.insert("message".into(), response.message.clone().into());
         payload.insert(
             "count".into(),
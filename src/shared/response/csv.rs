@@ -0,0 +1,161 @@
+use serde_json::Value;
+
+use crate::engine::types::ScalarValue;
+use crate::shared::config::CONFIG;
+use crate::shared::response::json::JsonRenderer;
+use crate::shared::response::render::{Renderer, StreamingFormat};
+use crate::shared::response::types::{Response, ResponseBody, StatusCode};
+
+/// Renders query results as RFC 4180 CSV: a header row with the column
+/// names, then one CRLF-terminated record per row. Values are formatted as
+/// in JSON output (timestamps as epoch numbers, floats in shortest form).
+pub struct CsvRenderer {
+    /// Written in place of null values
+    null_value: String,
+}
+
+impl CsvRenderer {
+    pub fn new(null_value: impl Into<String>) -> Self {
+        Self {
+            null_value: null_value.into(),
+        }
+    }
+
+    /// Uses the null representation from `server.csv_null`.
+    pub fn from_config() -> Self {
+        Self::new(CONFIG.server.csv_null.clone())
+    }
+
+    fn write_record<'v>(
+        &self,
+        fields: impl Iterator<Item = Field<'v>>,
+        to_json: fn(&ScalarValue) -> Value,
+        out: &mut Vec<u8>,
+    ) {
+        for (i, field) in fields.enumerate() {
+            if i > 0 {
+                out.push(b',');
+            }
+            match field {
+                Field::Text(text) => self.write_text(text, out),
+                Field::Value(value) => self.write_value(value, to_json, out),
+            }
+        }
+        out.extend_from_slice(b"\r\n");
+    }
+
+    fn write_value(
+        &self,
+        value: &ScalarValue,
+        to_json: fn(&ScalarValue) -> Value,
+        out: &mut Vec<u8>,
+    ) {
+        match to_json(value) {
+            Value::Null => write_field(&self.null_value, out),
+            Value::String(s) => self.write_text(&s, out),
+            Value::Bool(b) => out.extend_from_slice(if b { b"true" } else { b"false" }),
+            Value::Number(n) => out.extend_from_slice(n.to_string().as_bytes()),
+            nested => write_field(&nested.to_string(), out),
+        }
+    }
+
+    /// Quotes empty strings when nulls are written as empty fields, so the two
+    /// stay distinguishable.
+    fn write_text(&self, text: &str, out: &mut Vec<u8>) {
+        if text.is_empty() && self.null_value.is_empty() {
+            out.extend_from_slice(b"\"\"");
+        } else {
+            write_field(text, out);
+        }
+    }
+}
+
+impl Default for CsvRenderer {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+enum Field<'a> {
+    Text(&'a str),
+    Value(&'a ScalarValue),
+}
+
+/// Writes `field`, quoted with doubled inner quotes when it holds a
+/// delimiter, quote or line break.
+fn write_field(field: &str, out: &mut Vec<u8>) {
+    if field.contains([',', '"', '\r', '\n']) {
+        out.push(b'"');
+        out.extend_from_slice(field.replace('"', "\"\"").as_bytes());
+        out.push(b'"');
+    } else {
+        out.extend_from_slice(field.as_bytes());
+    }
+}
+
+impl Renderer for CsvRenderer {
+    fn render(&self, response: &Response) -> Vec<u8> {
+        // Only tables map onto CSV; errors and other responses fall back to
+        // JSON, as with the Arrow format
+        match &response.body {
+            ResponseBody::Table { columns, rows } if response.status == StatusCode::Ok => {
+                let mut buf = Vec::new();
+                self.write_record(
+                    columns.iter().map(|(name, _)| Field::Text(name)),
+                    ScalarValue::to_json,
+                    &mut buf,
+                );
+                for row in rows {
+                    self.write_record(row.iter().map(Field::Value), ScalarValue::to_json, &mut buf);
+                }
+                buf
+            }
+            _ => JsonRenderer.render(response),
+        }
+    }
+
+    fn streaming_format(&self) -> StreamingFormat {
+        StreamingFormat::Csv
+    }
+
+    fn stream_schema(&self, columns: &[(String, String)], out: &mut Vec<u8>) {
+        self.write_record(
+            columns.iter().map(|(name, _)| Field::Text(name)),
+            ScalarValue::to_json,
+            out,
+        );
+    }
+
+    fn stream_row(&self, _columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        self.write_record(values.iter().map(Field::Value), ScalarValue::to_json, out);
+    }
+
+    fn stream_row_raw_strings(&self, _columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        self.write_record(
+            values.iter().map(Field::Value),
+            ScalarValue::to_json_raw,
+            out,
+        );
+    }
+
+    fn stream_batch(&self, _columns: &[&str], batch: &[Vec<ScalarValue>], out: &mut Vec<u8>) {
+        for row in batch {
+            self.write_record(row.iter().map(Field::Value), ScalarValue::to_json, out);
+        }
+    }
+
+    fn stream_batch_raw_strings(
+        &self,
+        _columns: &[&str],
+        batch: &[Vec<ScalarValue>],
+        out: &mut Vec<u8>,
+    ) {
+        for row in batch {
+            self.write_record(row.iter().map(Field::Value), ScalarValue::to_json_raw, out);
+        }
+    }
+
+    fn stream_end(&self, _row_count: usize, _out: &mut Vec<u8>) {
+        // CSV has no trailer; the stream simply ends after the last record
+    }
+}
@@ -0,0 +1,97 @@
+use crate::engine::types::ScalarValue;
+use crate::shared::response::render::{Renderer, StreamingFormat};
+use crate::shared::response::{CsvRenderer, Response, StatusCode};
+
+fn record(renderer: &CsvRenderer, values: Vec<ScalarValue>) -> String {
+    let mut out = Vec::new();
+    renderer.stream_row(&[], &values, &mut out);
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn quotes_fields_per_rfc_4180() {
+    let renderer = CsvRenderer::default();
+    assert_eq!(renderer.streaming_format(), StreamingFormat::Csv);
+
+    let mut out = Vec::new();
+    renderer.stream_schema(
+        &[
+            ("name".to_string(), "String".to_string()),
+            ("a,b".to_string(), "Integer".to_string()),
+        ],
+        &mut out,
+    );
+    assert_eq!(String::from_utf8(out).unwrap(), "name,\"a,b\"\r\n");
+
+    assert_eq!(
+        record(
+            &renderer,
+            vec![
+                ScalarValue::Utf8("plain".into()),
+                ScalarValue::Utf8("say \"hi\"".into()),
+                ScalarValue::Utf8("two\nlines".into()),
+                ScalarValue::Utf8("cr\rhere".into()),
+            ]
+        ),
+        "plain,\"say \"\"hi\"\"\",\"two\nlines\",\"cr\rhere\"\r\n"
+    );
+}
+
+#[test]
+fn formats_values_like_json_output() {
+    let renderer = CsvRenderer::default();
+    assert_eq!(
+        record(
+            &renderer,
+            vec![
+                ScalarValue::Int64(-3),
+                ScalarValue::Float64(2.5),
+                ScalarValue::Boolean(true),
+                ScalarValue::Timestamp(1_700_000_000),
+                ScalarValue::Utf8("{\"k\":[1,2]}".into()),
+            ]
+        ),
+        "-3,2.5,true,1700000000,\"{\"\"k\"\":[1,2]}\"\r\n"
+    );
+}
+
+#[test]
+fn raw_strings_are_written_as_stored() {
+    let renderer = CsvRenderer::default();
+    let values = vec![ScalarValue::Utf8("{\"k\": [1, 2]}".into())];
+
+    assert_eq!(
+        record(&renderer, values.clone()),
+        "\"{\"\"k\"\":[1,2]}\"\r\n"
+    );
+    let mut out = Vec::new();
+    renderer.stream_row_raw_strings(&[], &values, &mut out);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\"{\"\"k\"\": [1, 2]}\"\r\n"
+    );
+}
+
+#[test]
+fn null_representation_is_configurable() {
+    let values = vec![ScalarValue::Null, ScalarValue::Utf8(String::new())];
+
+    // Empty strings are quoted so they differ from empty nulls
+    assert_eq!(record(&CsvRenderer::default(), values.clone()), ",\"\"\r\n");
+    assert_eq!(record(&CsvRenderer::new("NULL"), values), "NULL,\r\n");
+}
+
+#[test]
+fn tables_render_as_csv_and_other_responses_as_json() {
+    let renderer = CsvRenderer::default();
+    let table = Response::ok_table(
+        vec![("event_type".to_string(), "String".to_string())],
+        vec![vec![ScalarValue::Utf8("signup".into())]],
+        1,
+    );
+    assert_eq!(renderer.render(&table), b"event_type\r\nsignup\r\n");
+
+    let error = renderer.render(&Response::error(StatusCode::BadRequest, "bad"));
+    let json: serde_json::Value = serde_json::from_slice(&error).unwrap();
+    assert_eq!(json["status"], 400);
+}
@@ -0,0 +1,172 @@
+use crate::engine::auth::AuthError;
+use crate::shared::response::types::StatusCode;
+use std::fmt;
+
+/// Version of the error code taxonomy. Bumped whenever a code is added.
+/// Existing codes are never renamed, removed, or reused for a different meaning.
+pub const ERROR_CODES_VERSION: u32 = 5;
+
+/// Stable, machine-readable error codes included alongside the human message in every
+/// error response. Message text may change freely; clients should match on these codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// The command could not be parsed.
+    ParseError,
+    /// The command parsed but is semantically invalid (bad arguments, missing fields, etc.).
+    InvalidRequest,
+    /// The referenced event type has no schema defined.
+    UnknownSchema,
+    /// A value does not match the type declared in the schema.
+    TypeMismatch,
+    /// The request carries no valid credentials.
+    Unauthenticated,
+    /// The authenticated user lacks the permission required for the command.
+    PermissionDenied,
+    /// Too many requests or failed authentication attempts; retry later.
+    RateLimited,
+    /// The command did not complete within its time limit.
+    Timeout,
+    /// The server is starting up or shutting down and cannot serve the request.
+    NotReady,
+    /// The server is under backpressure; retry later.
+    Overloaded,
+    /// The requested resource does not exist.
+    NotFound,
+    /// An unexpected internal failure.
+    Internal,
+    /// The query exceeds a configured complexity limit.
+    QueryTooComplex,
+    /// The query was aborted because it exceeded its memory budget.
+    QueryMemoryLimitExceeded,
+    /// An event payload exceeds the configured size limit.
+    PayloadTooLarge,
+    /// Defining the schema would exceed the configured schema or field count limit.
+    SchemaLimitExceeded,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 16] = [
+        ErrorCode::ParseError,
+        ErrorCode::InvalidRequest,
+        ErrorCode::UnknownSchema,
+        ErrorCode::TypeMismatch,
+        ErrorCode::Unauthenticated,
+        ErrorCode::PermissionDenied,
+        ErrorCode::RateLimited,
+        ErrorCode::Timeout,
+        ErrorCode::NotReady,
+        ErrorCode::Overloaded,
+        ErrorCode::NotFound,
+        ErrorCode::Internal,
+        ErrorCode::QueryTooComplex,
+        ErrorCode::QueryMemoryLimitExceeded,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::SchemaLimitExceeded,
+    ];
+
+    /// Wire representation of the code. Stable across releases.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::ParseError => "PARSE_ERROR",
+            ErrorCode::InvalidRequest => "INVALID_REQUEST",
+            ErrorCode::UnknownSchema => "UNKNOWN_SCHEMA",
+            ErrorCode::TypeMismatch => "TYPE_MISMATCH",
+            ErrorCode::Unauthenticated => "UNAUTHENTICATED",
+            ErrorCode::PermissionDenied => "PERMISSION_DENIED",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::Timeout => "TIMEOUT",
+            ErrorCode::NotReady => "NOT_READY",
+            ErrorCode::Overloaded => "OVERLOADED",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::Internal => "INTERNAL",
+            ErrorCode::QueryTooComplex => "QUERY_TOO_COMPLEX",
+            ErrorCode::QueryMemoryLimitExceeded => "QUERY_MEMORY_LIMIT_EXCEEDED",
+            ErrorCode::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
+            ErrorCode::SchemaLimitExceeded => "SCHEMA_LIMIT_EXCEEDED",
+        }
+    }
+
+    pub fn parse(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == code)
+    }
+
+    /// Whether the same request may succeed when sent again unchanged. Transient
+    /// conditions and unexpected internal failures are retryable; errors caused
+    /// by the request itself are not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ErrorCode::RateLimited
+            | ErrorCode::Timeout
+            | ErrorCode::NotReady
+            | ErrorCode::Overloaded
+            | ErrorCode::Internal => true,
+            ErrorCode::ParseError
+            | ErrorCode::InvalidRequest
+            | ErrorCode::UnknownSchema
+            | ErrorCode::TypeMismatch
+            | ErrorCode::Unauthenticated
+            | ErrorCode::PermissionDenied
+            | ErrorCode::NotFound
+            | ErrorCode::QueryTooComplex
+            | ErrorCode::QueryMemoryLimitExceeded
+            | ErrorCode::PayloadTooLarge
+            | ErrorCode::SchemaLimitExceeded => false,
+        }
+    }
+
+    /// Default code for a status when the call site does not provide a more specific one.
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::Ok | StatusCode::InternalError => ErrorCode::Internal,
+            StatusCode::BadRequest => ErrorCode::InvalidRequest,
+            StatusCode::Unauthorized => ErrorCode::Unauthenticated,
+            StatusCode::Forbidden => ErrorCode::PermissionDenied,
+            StatusCode::NotFound => ErrorCode::NotFound,
+            StatusCode::ServiceUnavailable => ErrorCode::Overloaded,
+        }
+    }
+
+    /// Tag appended to plain-text error lines: `[<CODE>]`, followed by
+    /// `[RETRYABLE]` when the error is retryable.
+    pub fn text_tag(&self) -> String {
+        if self.is_retryable() {
+            format!("[{}] [RETRYABLE]", self.as_str())
+        } else {
+            format!("[{}]", self.as_str())
+        }
+    }
+
+    /// Formats a single-line error for the plain-text TCP/WebSocket protocol:
+    /// `ERROR: <message> <tag>`, see [`ErrorCode::text_tag`].
+    pub fn text_line(&self, message: &str) -> String {
+        format!("ERROR: {} {}\n", message, self.text_tag())
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&AuthError> for ErrorCode {
+    fn from(error: &AuthError) -> Self {
+        match error {
+            AuthError::RateLimitExceeded | AuthError::AccountLocked => ErrorCode::RateLimited,
+            AuthError::AuthenticationFailed
+            | AuthError::StaleRequest
+            | AuthError::ReplayedRequest
+            | AuthError::UntrustedClientCertificate
+            | AuthError::UnmappedClientCertificate(_) => ErrorCode::Unauthenticated,
+            AuthError::UserNotFound(_) => ErrorCode::NotFound,
+            AuthError::DatabaseError(_) => ErrorCode::Internal,
+            AuthError::UserExists
+            | AuthError::InvalidUserId
+            | AuthError::UserIdTooLong { .. }
+            | AuthError::SecretKeyTooLong { .. }
+            | AuthError::SecretKeyUnchanged
+            | AuthError::SignatureTooLong { .. }
+            | AuthError::UserInactive(_) => ErrorCode::InvalidRequest,
+        }
+    }
+}
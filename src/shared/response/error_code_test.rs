@@ -0,0 +1,125 @@
+use crate::engine::auth::AuthError;
+use crate::shared::response::render::Renderer;
+use crate::shared::response::{
+    ArrowRenderer, ErrorCode, JsonRenderer, Response, StatusCode, UnixRenderer,
+};
+
+#[test]
+fn error_code_strings_round_trip() {
+    for code in ErrorCode::ALL {
+        assert_eq!(ErrorCode::parse(code.as_str()), Some(code));
+    }
+    assert_eq!(ErrorCode::parse("NOPE"), None);
+}
+
+#[test]
+fn transient_errors_are_retryable() {
+    for code in [
+        ErrorCode::Timeout,
+        ErrorCode::Overloaded,
+        ErrorCode::NotReady,
+        ErrorCode::RateLimited,
+        ErrorCode::Internal,
+    ] {
+        assert!(code.is_retryable(), "{code} should be retryable");
+    }
+    for code in [
+        ErrorCode::ParseError,
+        ErrorCode::TypeMismatch,
+        ErrorCode::PermissionDenied,
+        ErrorCode::QueryTooComplex,
+    ] {
+        assert!(!code.is_retryable(), "{code} should not be retryable");
+    }
+
+    // Errors without a specific code inherit the classification of the status default
+    assert!(
+        Response::error(StatusCode::InternalError, "boom")
+            .error_code
+            .unwrap()
+            .is_retryable()
+    );
+    assert!(
+        !Response::error(StatusCode::BadRequest, "bad")
+            .error_code
+            .unwrap()
+            .is_retryable()
+    );
+}
+
+#[test]
+fn error_defaults_code_from_status() {
+    let resp = Response::error(StatusCode::Forbidden, "denied");
+    assert_eq!(resp.error_code, Some(ErrorCode::PermissionDenied));
+
+    let resp = Response::error(StatusCode::ServiceUnavailable, "busy");
+    assert_eq!(resp.error_code, Some(ErrorCode::Overloaded));
+
+    let ok = Response::ok_lines(vec!["fine".to_string()]);
+    assert_eq!(ok.error_code, None);
+}
+
+#[test]
+fn auth_errors_map_to_codes() {
+    assert_eq!(
+        ErrorCode::from(&AuthError::RateLimitExceeded),
+        ErrorCode::RateLimited
+    );
+    assert_eq!(
+        ErrorCode::from(&AuthError::AuthenticationFailed),
+        ErrorCode::Unauthenticated
+    );
+}
+
+#[test]
+fn json_renderer_includes_code_only_on_errors() {
+    let err = Response::error_with_code(StatusCode::BadRequest, ErrorCode::ParseError, "bad");
+    let body = String::from_utf8(JsonRenderer.render(&err)).unwrap();
+    assert!(body.contains("\"code\":\"PARSE_ERROR\""));
+    assert!(body.contains("\"retryable\":false"));
+    assert!(body.contains("\"message\":\"bad\""));
+
+    let busy = Response::error(StatusCode::ServiceUnavailable, "busy");
+    let body = String::from_utf8(JsonRenderer.render(&busy)).unwrap();
+    assert!(body.contains("\"retryable\":true"));
+
+    let ok = Response::ok_lines(vec!["fine".to_string()]);
+    let body = String::from_utf8(JsonRenderer.render(&ok)).unwrap();
+    assert!(!body.contains("\"code\""));
+    assert!(!body.contains("\"retryable\""));
+}
+
+#[test]
+fn arrow_renderer_includes_code_on_errors() {
+    let err = Response::error_with_code(StatusCode::BadRequest, ErrorCode::TypeMismatch, "bad");
+    let body = String::from_utf8(ArrowRenderer.render(&err)).unwrap();
+    assert!(body.contains("\"code\":\"TYPE_MISMATCH\""));
+    assert!(body.contains("\"retryable\":false"));
+}
+
+#[test]
+fn unix_renderer_tags_status_line() {
+    let err = Response::error_with_code(StatusCode::Forbidden, ErrorCode::PermissionDenied, "no");
+    let body = String::from_utf8(UnixRenderer.render(&err)).unwrap();
+    assert_eq!(body.lines().next(), Some("403 no [PERMISSION_DENIED]"));
+
+    let err = Response::error_with_code(StatusCode::InternalError, ErrorCode::Timeout, "slow");
+    let body = String::from_utf8(UnixRenderer.render(&err)).unwrap();
+    assert_eq!(body.lines().next(), Some("500 slow [TIMEOUT] [RETRYABLE]"));
+
+    let ok = Response::ok_lines(vec!["fine".to_string()]);
+    let body = String::from_utf8(UnixRenderer.render(&ok)).unwrap();
+    assert_eq!(body.lines().next(), Some("200 OK"));
+}
+
+#[test]
+fn text_line_appends_code() {
+    assert_eq!(
+        ErrorCode::NotReady.text_line("Server is shutting down"),
+        "ERROR: Server is shutting down [NOT_READY] [RETRYABLE]\n"
+    );
+    assert_eq!(
+        ErrorCode::ParseError.text_line("Unexpected token"),
+        "ERROR: Unexpected token [PARSE_ERROR]\n"
+    );
+}
@@ -12,6 +12,10 @@ pub struct JsonRenderer;
 struct JsonResponse<'a> {
     count: usize,
     status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retryable: Option<bool>,
     message: &'a str,
     results: &'a [Value],
 }
@@ -21,6 +25,8 @@ struct SchemaFrame<'a> {
     #[serde(rename = "type")]
     frame_type: &'static str,
     columns: &'a [ColumnRef<'a>],
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    omit_nulls: bool,
 }
 
 // Frame structure that avoids Value cloning by serializing values by reference
@@ -53,6 +59,14 @@ impl<'a> Serialize for RowValuesNoClone<'a> {
     }
 }
 
+#[derive(Serialize)]
+struct TotalFrame {
+    #[serde(rename = "type")]
+    frame_type: &'static str,
+    count: u64,
+    estimate: bool,
+}
+
 #[derive(Serialize)]
 struct EndFrame {
     #[serde(rename = "type")]
@@ -118,6 +132,8 @@ impl Renderer for JsonRenderer {
         let payload = JsonResponse {
             count: response.count,
             status: response.status.code(),
+            code: response.error_code.map(|c| c.as_str()),
+            retryable: response.error_code.map(|c| c.is_retryable()),
             message: &response.message,
             results: &results,
         };
@@ -136,81 +152,49 @@ impl Renderer for JsonRenderer {
     }
 
     fn stream_schema(&self, columns: &[(String, String)], out: &mut Vec<u8>) {
-        out.clear();
-
-        // Convert columns to ColumnRef slices
-        let column_refs: Vec<ColumnRef> = columns
-            .iter()
-            .map(|(name, logical_type)| ColumnRef {
-                name: name.as_str(),
-                logical_type: logical_type.as_str(),
-            })
-            .collect();
-
-        let frame = SchemaFrame {
-            frame_type: "schema",
-            columns: &column_refs,
-        };
-
-        // Serialize directly into out
-        if sonic_rs::to_writer(&mut *out, &frame).is_err() {
-            out.clear();
-            out.extend_from_slice(b"{\"type\":\"schema\",\"columns\":[]}\n");
-            return;
-        }
+        encode_schema(columns, false, out);
+    }
 
-        out.push(b'\n');
+    fn stream_schema_omitting_nulls(&self, columns: &[(String, String)], out: &mut Vec<u8>) {
+        encode_schema(columns, true, out);
     }
 
-    fn stream_row(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+    fn stream_total(&self, rows: u64, estimate: bool, out: &mut Vec<u8>) {
         out.clear();
 
-        // Convert ScalarValues to JSON Values at render time
-        let json_values: Vec<Value> = values.iter().map(|v| v.to_json()).collect();
-        let row_values = RowValuesNoClone {
-            columns,
-            values: json_values,
-            _phantom: std::marker::PhantomData,
-        };
-
-        let frame = RowFrameNoClone {
-            frame_type: "row",
-            values: row_values,
+        let frame = TotalFrame {
+            frame_type: "total",
+            count: rows,
+            estimate,
         };
 
-        // Serialize directly into out - sonic-rs will serialize RowValuesNoClone
-        // which serializes each Value by reference, avoiding cloning
         if sonic_rs::to_writer(&mut *out, &frame).is_err() {
             out.clear();
-            out.extend_from_slice(b"{\"type\":\"row\",\"values\":{}}\n");
             return;
         }
 
         out.push(b'\n');
     }
 
-    fn stream_batch(&self, _columns: &[&str], batch: &[Vec<ScalarValue>], out: &mut Vec<u8>) {
-        out.clear();
-
-        // Convert ScalarValues to JSON Values at render time
-        let json_batch: Vec<Vec<Value>> = batch
-            .iter()
-            .map(|row| row.iter().map(|v| v.to_json()).collect())
-            .collect();
+    fn stream_row(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        encode_row(columns, values, ScalarValue::to_json, out);
+    }
 
-        // Serialize batch as array of arrays (more efficient than per-row objects)
-        let batch_frame = BatchFrame {
-            frame_type: "batch",
-            rows: BatchRows { rows: &json_batch },
-        };
+    fn stream_row_raw_strings(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        encode_row(columns, values, ScalarValue::to_json_raw, out);
+    }
 
-        if sonic_rs::to_writer(&mut *out, &batch_frame).is_err() {
-            out.clear();
-            out.extend_from_slice(b"{\"type\":\"batch\",\"rows\":[]}\n");
-            return;
-        }
+    fn stream_batch(&self, _columns: &[&str], batch: &[Vec<ScalarValue>], out: &mut Vec<u8>) {
+        encode_batch(batch, ScalarValue::to_json, out);
+    }
 
-        out.push(b'\n');
+    fn stream_batch_raw_strings(
+        &self,
+        _columns: &[&str],
+        batch: &[Vec<ScalarValue>],
+        out: &mut Vec<u8>,
+    ) {
+        encode_batch(batch, ScalarValue::to_json_raw, out);
     }
 
     fn stream_column_batch(&self, _batch: &ColumnBatch, _out: &mut Vec<u8>) {
@@ -261,3 +245,87 @@ fn estimate_json_size(response: &Response) -> usize {
 
     size
 }
+
+fn encode_schema(columns: &[(String, String)], omit_nulls: bool, out: &mut Vec<u8>) {
+    out.clear();
+
+    // Convert columns to ColumnRef slices
+    let column_refs: Vec<ColumnRef> = columns
+        .iter()
+        .map(|(name, logical_type)| ColumnRef {
+            name: name.as_str(),
+            logical_type: logical_type.as_str(),
+        })
+        .collect();
+
+    let frame = SchemaFrame {
+        frame_type: "schema",
+        columns: &column_refs,
+        omit_nulls,
+    };
+
+    // Serialize directly into out
+    if sonic_rs::to_writer(&mut *out, &frame).is_err() {
+        out.clear();
+        out.extend_from_slice(b"{\"type\":\"schema\",\"columns\":[]}\n");
+        return;
+    }
+
+    out.push(b'\n');
+}
+
+fn encode_row(
+    columns: &[&str],
+    values: &[ScalarValue],
+    to_json: fn(&ScalarValue) -> Value,
+    out: &mut Vec<u8>,
+) {
+    out.clear();
+
+    // Convert ScalarValues to JSON Values at render time
+    let json_values: Vec<Value> = values.iter().map(to_json).collect();
+    let row_values = RowValuesNoClone {
+        columns,
+        values: json_values,
+        _phantom: std::marker::PhantomData,
+    };
+
+    let frame = RowFrameNoClone {
+        frame_type: "row",
+        values: row_values,
+    };
+
+    // Serialize directly into out - sonic-rs will serialize RowValuesNoClone
+    // which serializes each Value by reference, avoiding cloning
+    if sonic_rs::to_writer(&mut *out, &frame).is_err() {
+        out.clear();
+        out.extend_from_slice(b"{\"type\":\"row\",\"values\":{}}\n");
+        return;
+    }
+
+    out.push(b'\n');
+}
+
+fn encode_batch(batch: &[Vec<ScalarValue>], to_json: fn(&ScalarValue) -> Value, out: &mut Vec<u8>) {
+    out.clear();
+
+    // Convert ScalarValues to JSON Values at render time
+    let json_batch: Vec<Vec<Value>> = batch
+        .iter()
+        .map(|row| row.iter().map(to_json).collect())
+        .collect();
+
+    // Serialize batch as array of arrays (more efficient than per-row objects)
+    let batch_frame = BatchFrame {
+        frame_type: "batch",
+        rows: BatchRows { rows: &json_batch },
+    };
+
+    if sonic_rs::to_writer(&mut *out, &batch_frame).is_err() {
+        out.clear();
+        out.extend_from_slice(b"{\"type\":\"batch\",\"rows\":[]}\n");
+        return;
+    }
+
+    out.push(b'\n');
+}
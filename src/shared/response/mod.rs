@@ -1,12 +1,29 @@
 pub mod arrow;
+pub mod csv;
+pub mod error_code;
 pub mod json;
+pub mod msgpack;
+pub mod ndjson;
 pub mod render;
 pub mod types;
 pub mod unix;
 
+pub use error_code::{ERROR_CODES_VERSION, ErrorCode};
 pub use types::{Response, StatusCode};
 
 pub use arrow::ArrowRenderer;
 pub use arrow::ArrowStreamEncoder;
+pub use csv::CsvRenderer;
 pub use json::JsonRenderer;
+pub use msgpack::MessagePackRenderer;
+pub use ndjson::NdjsonRenderer;
 pub use unix::UnixRenderer;
+
+#[cfg(test)]
+mod csv_test;
+#[cfg(test)]
+mod error_code_test;
+#[cfg(test)]
+mod msgpack_test;
+#[cfg(test)]
+mod ndjson_test;
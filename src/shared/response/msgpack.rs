@@ -0,0 +1,306 @@
+use crate::engine::types::ScalarValue;
+use crate::shared::response::render::{Renderer, StreamingFormat};
+use crate::shared::response::types::{Response, ResponseBody};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+
+/// Renders responses as MessagePack, with the same frames and field names as
+/// [`JsonRenderer`](super::JsonRenderer). Every frame is one self-delimiting
+/// map, so streamed frames are simply concatenated. Binary values are written
+/// as msgpack `bin` instead of base64 strings.
+pub struct MessagePackRenderer;
+
+#[derive(Serialize)]
+struct MsgPackResponse<'a> {
+    count: usize,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retryable: Option<bool>,
+    message: &'a str,
+    results: Results<'a>,
+}
+
+#[derive(Serialize)]
+struct SchemaFrame<'a> {
+    #[serde(rename = "type")]
+    frame_type: &'static str,
+    columns: Vec<ColumnRef<'a>>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    omit_nulls: bool,
+}
+
+#[derive(Serialize)]
+struct ColumnRef<'a> {
+    name: &'a str,
+    logical_type: &'a str,
+}
+
+#[derive(Serialize)]
+struct TableColumn<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    logical_type: &'a str,
+}
+
+#[derive(Serialize)]
+struct TotalFrame {
+    #[serde(rename = "type")]
+    frame_type: &'static str,
+    count: u64,
+    estimate: bool,
+}
+
+#[derive(Serialize)]
+struct RowFrame<'a> {
+    #[serde(rename = "type")]
+    frame_type: &'static str,
+    values: RowValues<'a>,
+}
+
+#[derive(Serialize)]
+struct BatchFrame<'a> {
+    #[serde(rename = "type")]
+    frame_type: &'static str,
+    rows: Rows<'a>,
+}
+
+#[derive(Serialize)]
+struct EndFrame {
+    #[serde(rename = "type")]
+    frame_type: &'static str,
+    row_count: usize,
+}
+
+/// A value as msgpack. Binary goes out as `bin`; everything else takes the
+/// shape it has in JSON output.
+struct MsgPackValue<'a> {
+    value: &'a ScalarValue,
+    raw_strings: bool,
+}
+
+impl Serialize for MsgPackValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.value {
+            ScalarValue::Null => serializer.serialize_unit(),
+            ScalarValue::Boolean(b) => serializer.serialize_bool(*b),
+            ScalarValue::Int64(i) | ScalarValue::Timestamp(i) => serializer.serialize_i64(*i),
+            ScalarValue::Binary(bytes) => serializer.serialize_bytes(bytes),
+            ScalarValue::Utf8(s) if self.raw_strings => serializer.serialize_str(s),
+            value => value.to_json().serialize(serializer),
+        }
+    }
+}
+
+struct Row<'a> {
+    values: &'a [ScalarValue],
+    raw_strings: bool,
+}
+
+impl Serialize for Row<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.values.len()))?;
+        for value in self.values {
+            seq.serialize_element(&MsgPackValue {
+                value,
+                raw_strings: self.raw_strings,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct Rows<'a> {
+    rows: &'a [Vec<ScalarValue>],
+    raw_strings: bool,
+}
+
+impl Serialize for Rows<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.rows.iter().map(|values| Row {
+            values,
+            raw_strings: self.raw_strings,
+        }))
+    }
+}
+
+struct RowValues<'a> {
+    columns: &'a [&'a str],
+    values: &'a [ScalarValue],
+    raw_strings: bool,
+}
+
+impl Serialize for RowValues<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (name, value) in self.columns.iter().zip(self.values) {
+            map.serialize_entry(
+                name,
+                &MsgPackValue {
+                    value,
+                    raw_strings: self.raw_strings,
+                },
+            )?;
+        }
+        map.end()
+    }
+}
+
+/// The `results` of a complete response, shaped as in JSON output.
+struct Results<'a>(&'a ResponseBody);
+
+impl Serialize for Results<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            ResponseBody::Lines(lines) => serializer.collect_seq(lines),
+            ResponseBody::ScalarArray(values) => {
+                serializer.collect_seq(values.iter().map(|value| MsgPackValue {
+                    value,
+                    raw_strings: false,
+                }))
+            }
+            ResponseBody::Table { columns, rows } => {
+                let mut table = serializer.serialize_seq(Some(1))?;
+                table.serialize_element(&TableResult {
+                    columns: columns
+                        .iter()
+                        .map(|(name, logical_type)| TableColumn { name, logical_type })
+                        .collect(),
+                    rows: Rows {
+                        rows,
+                        raw_strings: false,
+                    },
+                })?;
+                table.end()
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TableResult<'a> {
+    columns: Vec<TableColumn<'a>>,
+    rows: Rows<'a>,
+}
+
+/// Encodes `frame` into `out` as a map keyed by field names, or leaves `out`
+/// empty if it cannot be encoded.
+fn encode<T: Serialize>(frame: &T, out: &mut Vec<u8>) {
+    out.clear();
+    if rmp_serde::encode::write_named(out, frame).is_err() {
+        out.clear();
+    }
+}
+
+impl Renderer for MessagePackRenderer {
+    fn render(&self, response: &Response) -> Vec<u8> {
+        let payload = MsgPackResponse {
+            count: response.count,
+            status: response.status.code(),
+            code: response.error_code.map(|c| c.as_str()),
+            retryable: response.error_code.map(|c| c.is_retryable()),
+            message: &response.message,
+            results: Results(&response.body),
+        };
+        let mut buf = Vec::new();
+        encode(&payload, &mut buf);
+        if buf.is_empty() {
+            let fallback = MsgPackResponse {
+                count: 0,
+                status: 500,
+                code: None,
+                retryable: None,
+                message: "Failed to serialize MessagePack",
+                results: Results(&ResponseBody::Lines(Vec::new())),
+            };
+            encode(&fallback, &mut buf);
+        }
+        buf
+    }
+
+    fn streaming_format(&self) -> StreamingFormat {
+        StreamingFormat::MsgPack
+    }
+
+    fn stream_schema(&self, columns: &[(String, String)], out: &mut Vec<u8>) {
+        encode_schema(columns, false, out);
+    }
+
+    fn stream_schema_omitting_nulls(&self, columns: &[(String, String)], out: &mut Vec<u8>) {
+        encode_schema(columns, true, out);
+    }
+
+    fn stream_total(&self, rows: u64, estimate: bool, out: &mut Vec<u8>) {
+        let frame = TotalFrame {
+            frame_type: "total",
+            count: rows,
+            estimate,
+        };
+        encode(&frame, out);
+    }
+
+    fn stream_row(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        encode_row(columns, values, false, out);
+    }
+
+    fn stream_row_raw_strings(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        encode_row(columns, values, true, out);
+    }
+
+    fn stream_batch(&self, _columns: &[&str], batch: &[Vec<ScalarValue>], out: &mut Vec<u8>) {
+        encode_batch(batch, false, out);
+    }
+
+    fn stream_batch_raw_strings(
+        &self,
+        _columns: &[&str],
+        batch: &[Vec<ScalarValue>],
+        out: &mut Vec<u8>,
+    ) {
+        encode_batch(batch, true, out);
+    }
+
+    fn stream_end(&self, row_count: usize, out: &mut Vec<u8>) {
+        let frame = EndFrame {
+            frame_type: "end",
+            row_count,
+        };
+        encode(&frame, out);
+    }
+}
+
+fn encode_schema(columns: &[(String, String)], omit_nulls: bool, out: &mut Vec<u8>) {
+    let frame = SchemaFrame {
+        frame_type: "schema",
+        columns: columns
+            .iter()
+            .map(|(name, logical_type)| ColumnRef { name, logical_type })
+            .collect(),
+        omit_nulls,
+    };
+    encode(&frame, out);
+}
+
+fn encode_row(columns: &[&str], values: &[ScalarValue], raw_strings: bool, out: &mut Vec<u8>) {
+    let frame = RowFrame {
+        frame_type: "row",
+        values: RowValues {
+            columns,
+            values,
+            raw_strings,
+        },
+    };
+    encode(&frame, out);
+}
+
+fn encode_batch(batch: &[Vec<ScalarValue>], raw_strings: bool, out: &mut Vec<u8>) {
+    let frame = BatchFrame {
+        frame_type: "batch",
+        rows: Rows {
+            rows: batch,
+            raw_strings,
+        },
+    };
+    encode(&frame, out);
+}
@@ -0,0 +1,105 @@
+use serde_json::{Value, json};
+
+use crate::engine::types::ScalarValue;
+use crate::shared::response::render::{Renderer, StreamingFormat};
+use crate::shared::response::{ErrorCode, MessagePackRenderer, Response, StatusCode};
+
+fn decode(bytes: &[u8]) -> Value {
+    rmp_serde::from_slice(bytes).expect("valid msgpack")
+}
+
+#[test]
+fn streams_json_shaped_frames() {
+    let renderer = MessagePackRenderer;
+    assert_eq!(renderer.streaming_format(), StreamingFormat::MsgPack);
+
+    let mut out = Vec::new();
+    renderer.stream_schema(&[("id".to_string(), "Integer".to_string())], &mut out);
+    assert_eq!(
+        decode(&out),
+        json!({"type": "schema", "columns": [{"name": "id", "logical_type": "Integer"}]})
+    );
+
+    renderer.stream_row(
+        &["id", "name", "score"],
+        &[
+            ScalarValue::Int64(7),
+            ScalarValue::Utf8("ada".into()),
+            ScalarValue::Null,
+        ],
+        &mut out,
+    );
+    assert_eq!(
+        decode(&out),
+        json!({"type": "row", "values": {"id": 7, "name": "ada", "score": null}})
+    );
+
+    renderer.stream_batch(
+        &["id", "ok"],
+        &[
+            vec![ScalarValue::Int64(1), ScalarValue::Boolean(true)],
+            vec![ScalarValue::Int64(2), ScalarValue::Boolean(false)],
+        ],
+        &mut out,
+    );
+    assert_eq!(
+        decode(&out),
+        json!({"type": "batch", "rows": [[1, true], [2, false]]})
+    );
+
+    renderer.stream_end(2, &mut out);
+    assert_eq!(decode(&out), json!({"type": "end", "row_count": 2}));
+}
+
+#[test]
+fn binary_values_are_msgpack_bin() {
+    let renderer = MessagePackRenderer;
+    let mut out = Vec::new();
+    renderer.stream_batch(
+        &["blob"],
+        &[vec![ScalarValue::Binary(vec![1, 2, 3])]],
+        &mut out,
+    );
+    // bin 8 with a length of 3, not a base64 string
+    assert!(out.windows(5).any(|w| w == [0xc4, 3, 1, 2, 3]));
+    assert!(!out.windows(4).any(|w| w == b"AQID"));
+}
+
+#[test]
+fn raw_strings_keep_json_looking_strings() {
+    let renderer = MessagePackRenderer;
+    let values = [ScalarValue::Utf8("{\"k\":1}".into())];
+    let mut out = Vec::new();
+
+    renderer.stream_row(&["payload"], &values, &mut out);
+    assert_eq!(decode(&out)["values"]["payload"], json!({"k": 1}));
+
+    renderer.stream_row_raw_strings(&["payload"], &values, &mut out);
+    assert_eq!(decode(&out)["values"]["payload"], json!("{\"k\":1}"));
+}
+
+#[test]
+fn renders_error_responses_with_code() {
+    let response =
+        Response::error_with_code(StatusCode::BadRequest, ErrorCode::ParseError, "bad query");
+    let decoded = decode(&MessagePackRenderer.render(&response));
+    assert_eq!(decoded["status"], 400);
+    assert_eq!(decoded["code"], "PARSE_ERROR");
+    assert_eq!(decoded["retryable"], false);
+    assert_eq!(decoded["message"], "bad query");
+}
+
+#[test]
+fn renders_tables_like_json() {
+    let response = Response::ok_table(
+        vec![("name".to_string(), "String".to_string())],
+        vec![vec![ScalarValue::Utf8("a".into())]],
+        1,
+    );
+    let decoded = decode(&MessagePackRenderer.render(&response));
+    assert_eq!(decoded["status"], 200);
+    assert_eq!(
+        decoded["results"],
+        json!([{"columns": [{"name": "name", "type": "String"}], "rows": [["a"]]}])
+    );
+}
@@ -0,0 +1,104 @@
+use serde::Serialize;
+use serde::ser::SerializeMap;
+use serde_json::Value;
+
+use crate::engine::types::ScalarValue;
+use crate::shared::response::json::JsonRenderer;
+use crate::shared::response::render::{Renderer, StreamingFormat};
+use crate::shared::response::types::{Response, ResponseBody, StatusCode};
+
+/// Renders query results as newline-delimited JSON: one object per row,
+/// keyed by column name, and nothing else. There is no schema, total or end
+/// frame, so a client can process each line on its own and the stream just
+/// ends after the last row. Values are formatted as in JSON output.
+pub struct NdjsonRenderer;
+
+/// A row as a JSON object of its column values.
+struct RowObject<'a> {
+    columns: &'a [&'a str],
+    values: &'a [ScalarValue],
+    to_json: fn(&ScalarValue) -> Value,
+}
+
+impl Serialize for RowObject<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (name, value) in self.columns.iter().zip(self.values) {
+            map.serialize_entry(name, &(self.to_json)(value))?;
+        }
+        map.end()
+    }
+}
+
+/// Appends one row as a line to `out`.
+fn write_line(
+    columns: &[&str],
+    values: &[ScalarValue],
+    to_json: fn(&ScalarValue) -> Value,
+    out: &mut Vec<u8>,
+) {
+    let row = RowObject {
+        columns,
+        values,
+        to_json,
+    };
+    if sonic_rs::to_writer(&mut *out, &row).is_err() {
+        out.extend_from_slice(b"{}");
+    }
+    out.push(b'\n');
+}
+
+impl Renderer for NdjsonRenderer {
+    fn render(&self, response: &Response) -> Vec<u8> {
+        // Tables become one line per row; errors and other responses are a
+        // single JSON line, so a failed stream still ends on a parseable line
+        match &response.body {
+            ResponseBody::Table { columns, rows } if response.status == StatusCode::Ok => {
+                let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+                let mut buf = Vec::new();
+                for row in rows {
+                    write_line(&names, row, ScalarValue::to_json, &mut buf);
+                }
+                buf
+            }
+            _ => JsonRenderer.render(response),
+        }
+    }
+
+    fn streaming_format(&self) -> StreamingFormat {
+        StreamingFormat::Ndjson
+    }
+
+    fn stream_schema(&self, _columns: &[(String, String)], _out: &mut Vec<u8>) {
+        // Every line names its own columns
+    }
+
+    fn stream_row(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        write_line(columns, values, ScalarValue::to_json, out);
+    }
+
+    fn stream_row_raw_strings(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        write_line(columns, values, ScalarValue::to_json_raw, out);
+    }
+
+    fn stream_batch(&self, columns: &[&str], batch: &[Vec<ScalarValue>], out: &mut Vec<u8>) {
+        for row in batch {
+            write_line(columns, row, ScalarValue::to_json, out);
+        }
+    }
+
+    fn stream_batch_raw_strings(
+        &self,
+        columns: &[&str],
+        batch: &[Vec<ScalarValue>],
+        out: &mut Vec<u8>,
+    ) {
+        for row in batch {
+            write_line(columns, row, ScalarValue::to_json_raw, out);
+        }
+    }
+
+    fn stream_end(&self, _row_count: usize, _out: &mut Vec<u8>) {
+        // No trailer: the stream ends after the last line
+    }
+}
@@ -0,0 +1,95 @@
+use serde_json::{Value, json};
+
+use crate::engine::types::ScalarValue;
+use crate::shared::response::render::{Renderer, StreamingFormat};
+use crate::shared::response::{ErrorCode, NdjsonRenderer, Response, StatusCode};
+
+fn lines(out: &[u8]) -> Vec<Value> {
+    std::str::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is JSON"))
+        .collect()
+}
+
+#[test]
+fn streams_one_object_per_row_without_frames() {
+    let renderer = NdjsonRenderer;
+    assert_eq!(renderer.streaming_format(), StreamingFormat::Ndjson);
+
+    let mut out = Vec::new();
+    renderer.stream_schema(&[("id".to_string(), "Integer".to_string())], &mut out);
+    renderer.stream_total(2, false, &mut out);
+    assert!(out.is_empty());
+
+    renderer.stream_row(
+        &["id", "name"],
+        &[ScalarValue::Int64(1), ScalarValue::Utf8("ada".into())],
+        &mut out,
+    );
+    renderer.stream_batch(
+        &["id", "name"],
+        &[
+            vec![ScalarValue::Int64(2), ScalarValue::Null],
+            vec![ScalarValue::Int64(3), ScalarValue::Utf8("bo".into())],
+        ],
+        &mut out,
+    );
+    let before_end = out.len();
+    renderer.stream_end(3, &mut out);
+    assert_eq!(out.len(), before_end);
+
+    assert!(out.ends_with(b"}\n"));
+    assert_eq!(
+        lines(&out),
+        vec![
+            json!({"id": 1, "name": "ada"}),
+            json!({"id": 2, "name": null}),
+            json!({"id": 3, "name": "bo"}),
+        ]
+    );
+}
+
+#[test]
+fn raw_strings_keep_json_looking_strings() {
+    let renderer = NdjsonRenderer;
+    let values = [ScalarValue::Utf8("{\"k\":1}".into())];
+
+    let mut out = Vec::new();
+    renderer.stream_row(&["payload"], &values, &mut out);
+    renderer.stream_row_raw_strings(&["payload"], &values, &mut out);
+    assert_eq!(
+        lines(&out),
+        vec![
+            json!({"payload": {"k": 1}}),
+            json!({"payload": "{\"k\":1}"})
+        ]
+    );
+}
+
+#[test]
+fn errors_are_a_single_json_line() {
+    let response =
+        Response::error_with_code(StatusCode::BadRequest, ErrorCode::ParseError, "bad query");
+    let out = NdjsonRenderer.render(&response);
+    let decoded = lines(&out);
+    assert_eq!(decoded.len(), 1);
+    assert_eq!(decoded[0]["status"], 400);
+    assert_eq!(decoded[0]["code"], "PARSE_ERROR");
+}
+
+#[test]
+fn tables_render_as_row_lines() {
+    let response = Response::ok_table(
+        vec![("name".to_string(), "String".to_string())],
+        vec![
+            vec![ScalarValue::Utf8("a".into())],
+            vec![ScalarValue::Utf8("b".into())],
+        ],
+        2,
+    );
+    assert_eq!(
+        lines(&NdjsonRenderer.render(&response)),
+        vec![json!({"name": "a"}), json!({"name": "b"})]
+    );
+}
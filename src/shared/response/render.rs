@@ -6,6 +6,11 @@ use crate::shared::response::types::Response;
 pub enum StreamingFormat {
     Json,
     Arrow,
+    Csv,
+    /// Row frames like `Json`, encoded as MessagePack
+    MsgPack,
+    /// One JSON object per row, without schema or end frames
+    Ndjson,
 }
 
 /// A trait that defines how to serialize a `Response` for a given transport.
@@ -22,6 +27,16 @@ pub trait Renderer: Send + Sync {
         unreachable!("stream_schema called on renderer without support")
     }
 
+    /// Encode the schema frame for a response whose row frames leave out null
+    /// fields: a column listed here but missing from a row is null.
+    fn stream_schema_omitting_nulls(&self, columns: &[(String, String)], out: &mut Vec<u8>) {
+        self.stream_schema(columns, out)
+    }
+
+    /// Encode the frame carrying a `WITH TOTAL` row count. Renderers without
+    /// a place for it leave `out` empty.
+    fn stream_total(&self, _rows: u64, _estimate: bool, _out: &mut Vec<u8>) {}
+
     /// Encode a single row frame for a streaming query response into the provided buffer.
     fn stream_row(&self, _columns: &[&str], _values: &[ScalarValue], _out: &mut Vec<u8>) {
         unreachable!("stream_row called on renderer without support")
@@ -34,6 +49,22 @@ pub trait Renderer: Send + Sync {
         unreachable!("stream_batch called on renderer without support")
     }
 
+    /// Like `stream_row`, writing string values verbatim instead of re-parsing
+    /// those that look like JSON (`RAW STRINGS`).
+    fn stream_row_raw_strings(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        self.stream_row(columns, values, out)
+    }
+
+    /// Like `stream_batch`, writing string values verbatim (`RAW STRINGS`).
+    fn stream_batch_raw_strings(
+        &self,
+        columns: &[&str],
+        batch: &[Vec<ScalarValue>],
+        out: &mut Vec<u8>,
+    ) {
+        self.stream_batch(columns, batch, out)
+    }
+
     /// Encode a ColumnBatch directly (most efficient for Arrow format).
     fn stream_column_batch(&self, _batch: &ColumnBatch, _out: &mut Vec<u8>) {
         unreachable!("stream_column_batch called on renderer without support")
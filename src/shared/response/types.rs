@@ -59,6 +59,7 @@ impl From<hyper::StatusCode> for StatusCode {
     }
 }
 use crate::engine::types::ScalarValue;
+use crate::shared::response::error_code::ErrorCode;
 
 #[derive(Debug, Clone)]
 pub enum ResponseBody {
@@ -76,6 +77,8 @@ pub struct Response {
     pub message: String,
     pub body: ResponseBody,
     pub count: usize,
+    /// Machine-readable error code; always set for error responses.
+    pub error_code: Option<ErrorCode>,
 }
 
 impl Response {
@@ -85,6 +88,7 @@ impl Response {
             count: 1,
             message: "OK".to_string(),
             body: ResponseBody::Lines(lines.into_iter().collect()),
+            error_code: None,
         }
     }
 
@@ -94,6 +98,7 @@ impl Response {
             count: count,
             message: "OK".to_string(),
             body: ResponseBody::ScalarArray(rows),
+            error_code: None,
         }
     }
 
@@ -107,15 +112,22 @@ impl Response {
             count,
             message: "OK".to_string(),
             body: ResponseBody::Table { columns, rows },
+            error_code: None,
         }
     }
 
+    /// Builds an error response with the default error code for the status.
     pub fn error(code: StatusCode, message: impl ToString) -> Self {
+        Self::error_with_code(code, ErrorCode::from_status(code), message)
+    }
+
+    pub fn error_with_code(status: StatusCode, code: ErrorCode, message: impl ToString) -> Self {
         Self {
-            status: code,
+            status,
             count: 0,
             message: message.to_string(),
             body: ResponseBody::Lines(vec![]),
+            error_code: Some(code),
         }
     }
 }
@@ -23,10 +23,17 @@ impl Renderer for UnixRenderer {
         let estimated_size = estimate_response_size(response);
         let mut output = Vec::with_capacity(estimated_size);
 
-        // Header line: 200 OK
-        output.extend_from_slice(
-            format!("{} {}\n", response.status.code(), response.message).as_bytes(),
-        );
+        // Header line: 200 OK, or for errors: 400 <message> [<CODE>], see ErrorCode::text_tag
+        let header = match response.error_code {
+            Some(code) => format!(
+                "{} {} {}\n",
+                response.status.code(),
+                response.message,
+                code.text_tag()
+            ),
+            None => format!("{} {}\n", response.status.code(), response.message),
+        };
+        output.extend_from_slice(header.as_bytes());
 
         match &response.body {
             ResponseBody::Lines(lines) => {
@@ -83,72 +90,47 @@ impl Renderer for UnixRenderer {
     }
 
     fn stream_schema(&self, columns: &[(String, String)], out: &mut Vec<u8>) {
+        encode_schema(columns, false, out);
+    }
+
+    fn stream_schema_omitting_nulls(&self, columns: &[(String, String)], out: &mut Vec<u8>) {
+        encode_schema(columns, true, out);
+    }
+
+    fn stream_total(&self, rows: u64, estimate: bool, out: &mut Vec<u8>) {
         out.clear();
         let mut serializer = JsonSerializer::new(&mut *out);
         let mut map =
-            SerdeSerializer::serialize_map(&mut serializer, Some(2)).expect("serialize schema map");
-        map.serialize_entry("type", "schema")
-            .expect("serialize schema type");
-        map.serialize_entry("columns", &ColumnList(columns))
-            .expect("serialize schema columns");
-        SerializeMap::end(map).expect("finish schema map");
+            SerdeSerializer::serialize_map(&mut serializer, Some(3)).expect("serialize total map");
+        map.serialize_entry("type", "total")
+            .expect("serialize total type");
+        map.serialize_entry("count", &rows)
+            .expect("serialize total count");
+        map.serialize_entry("estimate", &estimate)
+            .expect("serialize total estimate");
+        SerializeMap::end(map).expect("finish total map");
         out.push(b'\n');
     }
 
     fn stream_row(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
-        out.clear();
-
-        // Convert ScalarValues to JSON Values at render time
-        let json_values: Vec<Value> = values.iter().map(|v| v.to_json()).collect();
-        let json_refs: Vec<&Value> = json_values.iter().collect();
-        let row_values = RowValues {
-            columns,
-            values: &json_refs,
-        };
-
-        let frame = UnixRowFrameNoClone {
-            frame_type: "row",
-            values: row_values,
-        };
-
-        if sonic_rs::to_writer(&mut *out, &frame).is_err() {
-            out.clear();
-            out.extend_from_slice(b"{\"type\":\"row\",\"values\":{}}\n");
-            return;
-        }
+        encode_row(columns, values, ScalarValue::to_json, out);
+    }
 
-        out.push(b'\n');
+    fn stream_row_raw_strings(&self, columns: &[&str], values: &[ScalarValue], out: &mut Vec<u8>) {
+        encode_row(columns, values, ScalarValue::to_json_raw, out);
     }
 
     fn stream_batch(&self, _columns: &[&str], batch: &[Vec<ScalarValue>], out: &mut Vec<u8>) {
-        out.clear();
-
-        // Convert ScalarValues to JSON Values at render time
-        let json_batch: Vec<Vec<Value>> = batch
-            .iter()
-            .map(|row| row.iter().map(|v| v.to_json()).collect())
-            .collect();
-
-        // Serialize batch as array of arrays (same format as JsonRenderer)
-        #[derive(serde::Serialize)]
-        struct UnixBatchFrame {
-            #[serde(rename = "type")]
-            frame_type: &'static str,
-            rows: Vec<Vec<Value>>,
-        }
-
-        let batch_frame = UnixBatchFrame {
-            frame_type: "batch",
-            rows: json_batch,
-        };
-
-        if sonic_rs::to_writer(&mut *out, &batch_frame).is_err() {
-            out.clear();
-            out.extend_from_slice(b"{\"type\":\"batch\",\"rows\":[]}\n");
-            return;
-        }
+        encode_batch(batch, ScalarValue::to_json, out);
+    }
 
-        out.push(b'\n');
+    fn stream_batch_raw_strings(
+        &self,
+        _columns: &[&str],
+        batch: &[Vec<ScalarValue>],
+        out: &mut Vec<u8>,
+    ) {
+        encode_batch(batch, ScalarValue::to_json_raw, out);
     }
 
     fn stream_column_batch(&self, _batch: &ColumnBatch, _out: &mut Vec<u8>) {
@@ -238,3 +220,82 @@ impl<'a> Serialize for RowValues<'a> {
         map.end()
     }
 }
+
+fn encode_schema(columns: &[(String, String)], omit_nulls: bool, out: &mut Vec<u8>) {
+    out.clear();
+    let mut serializer = JsonSerializer::new(&mut *out);
+    let entries = if omit_nulls { 3 } else { 2 };
+    let mut map = SerdeSerializer::serialize_map(&mut serializer, Some(entries))
+        .expect("serialize schema map");
+    map.serialize_entry("type", "schema")
+        .expect("serialize schema type");
+    map.serialize_entry("columns", &ColumnList(columns))
+        .expect("serialize schema columns");
+    if omit_nulls {
+        map.serialize_entry("omit_nulls", &true)
+            .expect("serialize schema null mode");
+    }
+    SerializeMap::end(map).expect("finish schema map");
+    out.push(b'\n');
+}
+
+fn encode_row(
+    columns: &[&str],
+    values: &[ScalarValue],
+    to_json: fn(&ScalarValue) -> Value,
+    out: &mut Vec<u8>,
+) {
+    out.clear();
+
+    // Convert ScalarValues to JSON Values at render time
+    let json_values: Vec<Value> = values.iter().map(to_json).collect();
+    let json_refs: Vec<&Value> = json_values.iter().collect();
+    let row_values = RowValues {
+        columns,
+        values: &json_refs,
+    };
+
+    let frame = UnixRowFrameNoClone {
+        frame_type: "row",
+        values: row_values,
+    };
+
+    if sonic_rs::to_writer(&mut *out, &frame).is_err() {
+        out.clear();
+        out.extend_from_slice(b"{\"type\":\"row\",\"values\":{}}\n");
+        return;
+    }
+
+    out.push(b'\n');
+}
+
+fn encode_batch(batch: &[Vec<ScalarValue>], to_json: fn(&ScalarValue) -> Value, out: &mut Vec<u8>) {
+    out.clear();
+
+    // Convert ScalarValues to JSON Values at render time
+    let json_batch: Vec<Vec<Value>> = batch
+        .iter()
+        .map(|row| row.iter().map(to_json).collect())
+        .collect();
+
+    // Serialize batch as array of arrays (same format as JsonRenderer)
+    #[derive(serde::Serialize)]
+    struct UnixBatchFrame {
+        #[serde(rename = "type")]
+        frame_type: &'static str,
+        rows: Vec<Vec<Value>>,
+    }
+
+    let batch_frame = UnixBatchFrame {
+        frame_type: "batch",
+        rows: json_batch,
+    };
+
+    if sonic_rs::to_writer(&mut *out, &batch_frame).is_err() {
+        out.clear();
+        out.extend_from_slice(b"{\"type\":\"batch\",\"rows\":[]}\n");
+        return;
+    }
+
+    out.push(b'\n');
+}
@@ -120,14 +120,18 @@ pub enum FileKind {
     EventSnapshotMeta,
     ZoneSurfFilter,
     ZoneRlte,
+    FieldHistogram,
+    ZoneSummary,
     CalendarDir,
     TemporalIndex,
     IndexCatalog,
     MaterializationCatalog,
+    MaterializationCatalogLog,
     MaterializationCatalogEntry,
     MaterializedManifest,
     MaterializedFrame,
     AuthWal,
+    CacheWarmStart,
 }
 
 impl FileKind {
@@ -147,14 +151,18 @@ impl FileKind {
             FileKind::EventSnapshotMeta => *b"EVDBSMT\0",
             FileKind::ZoneSurfFilter => *b"EVDBZSF\0",
             FileKind::ZoneRlte => *b"EVDBZRT\0",
+            FileKind::FieldHistogram => *b"EVDBHST\0",
+            FileKind::ZoneSummary => *b"EVDBZSM\0",
             FileKind::CalendarDir => *b"EVDBCAL\0",
             FileKind::TemporalIndex => *b"EVDBTFI\0",
             FileKind::IndexCatalog => *b"EVDBICX\0",
             FileKind::MaterializationCatalog => *b"EVDBMCL\0",
+            FileKind::MaterializationCatalogLog => *b"EVDBMCG\0",
             FileKind::MaterializationCatalogEntry => *b"EVDBMCE\0",
             FileKind::MaterializedManifest => *b"EVDBMMF\0",
             FileKind::MaterializedFrame => *b"EVDBMFR\0",
             FileKind::AuthWal => *b"EVDBAUT\0",
+            FileKind::CacheWarmStart => *b"EVDBCWS\0",
         }
     }
 }
@@ -244,13 +244,17 @@ fn filekind_all_magic_values() {
         (FileKind::EventSnapshotMeta, *b"EVDBSMT\0"),
         (FileKind::ZoneSurfFilter, *b"EVDBZSF\0"),
         (FileKind::ZoneRlte, *b"EVDBZRT\0"),
+        (FileKind::FieldHistogram, *b"EVDBHST\0"),
+        (FileKind::ZoneSummary, *b"EVDBZSM\0"),
         (FileKind::CalendarDir, *b"EVDBCAL\0"),
         (FileKind::TemporalIndex, *b"EVDBTFI\0"),
         (FileKind::IndexCatalog, *b"EVDBICX\0"),
         (FileKind::MaterializationCatalog, *b"EVDBMCL\0"),
+        (FileKind::MaterializationCatalogLog, *b"EVDBMCG\0"),
         (FileKind::MaterializationCatalogEntry, *b"EVDBMCE\0"),
         (FileKind::MaterializedManifest, *b"EVDBMMF\0"),
         (FileKind::MaterializedFrame, *b"EVDBMFR\0"),
+        (FileKind::CacheWarmStart, *b"EVDBCWS\0"),
     ];
 
     for (kind, expected) in expected_magics {
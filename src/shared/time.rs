@@ -1,13 +1,18 @@
-use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use crate::engine::types::temporal;
+use crate::shared::datetime::time::{default_timezone, resolve_local};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Logical kind of time field.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimeKind {
-    /// Full datetime with timezone information; normalized to UTC seconds.
+    /// Full datetime; normalized to UTC seconds.
     DateTime,
-    /// Calendar date without time; normalized to midnight UTC seconds.
+    /// Calendar date without time; normalized to the seconds of its midnight UTC.
     Date,
+    /// Time of day without a date; normalized to milliseconds since midnight.
+    Time,
 }
 
 /// Utility for parsing and normalizing time inputs to epoch seconds (i64).
@@ -15,21 +20,64 @@ pub struct TimeParser;
 
 impl TimeParser {
     /// Parse a string representing a time instant into epoch seconds (UTC).
-    /// Supports RFC3339/ISO-8601 and date-only (YYYY-MM-DD).
+    /// Supports RFC3339/ISO-8601, datetimes without an offset and date-only
+    /// (YYYY-MM-DD); the last two are read in the default timezone.
+    /// `Date` keeps the calendar day only and `Time` reads a time of day; see
+    /// [`TimeKind`] for what each returns.
     pub fn parse_str_to_epoch_seconds(input: &str, kind: TimeKind) -> Option<i64> {
+        Self::parse_str_to_epoch_seconds_in(input, kind, &default_timezone())
+    }
+
+    /// Like `parse_str_to_epoch_seconds`, reading times without an offset in `tz`.
+    /// An explicit offset in the input always wins.
+    pub fn parse_str_to_epoch_seconds_in(input: &str, kind: TimeKind, tz: &Tz) -> Option<i64> {
         let s = input.trim();
+        match kind {
+            TimeKind::DateTime => Self::parse_instant(s, tz),
+            TimeKind::Date => Self::parse_day(s),
+            TimeKind::Time => temporal::parse_time(s).map(i64::from),
+        }
+    }
+
+    /// The day as written, whatever time of day or offset follows it, at
+    /// midnight UTC. Numbers are epoch instants, and give their UTC day.
+    fn parse_day(s: &str) -> Option<i64> {
+        let date = if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+            dt.date_naive()
+        } else if let Some(local) = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        {
+            local.date()
+        } else if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            date
+        } else {
+            let secs = Self::normalize_integer_epoch(s.parse::<i128>().ok()?)?;
+            return Self::truncate_to_day(secs);
+        };
+        Some(temporal::epoch_seconds_of_day(temporal::days_of_date(
+            date,
+        )?))
+    }
+
+    fn truncate_to_day(secs: i64) -> Option<i64> {
+        temporal::days_from_epoch_seconds(secs).map(temporal::epoch_seconds_of_day)
+    }
+
+    fn parse_instant(s: &str, tz: &Tz) -> Option<i64> {
         // Try RFC3339/ISO-8601 first
         if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
-            let utc = dt.with_timezone(&Utc);
-            return Some(utc.timestamp());
+            return Some(dt.timestamp());
+        }
+        // Datetime without an offset: wall-clock time in `tz`
+        for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+            if let Ok(local) = NaiveDateTime::parse_from_str(s, format) {
+                return Some(resolve_local(tz, local).timestamp());
+            }
         }
-        // Try date-only (YYYY-MM-DD)
+        // Try date-only (YYYY-MM-DD): midnight in `tz`
         if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
-            let dt = match kind {
-                TimeKind::Date => Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?),
-                TimeKind::DateTime => Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?),
-            };
-            return Some(dt.timestamp());
+            return Some(resolve_local(tz, date.and_hms_opt(0, 0, 0)?).timestamp());
         }
         // Fallback: numeric string
         if let Ok(num) = s.parse::<i128>() {
@@ -38,32 +86,43 @@ impl TimeParser {
         None
     }
 
-    /// Normalize a JSON value representing time to epoch seconds in-place.
+    /// Normalize a JSON value representing time in-place, to what a field of
+    /// `kind` stores.
     /// Returns Err with a human-readable message if the value cannot be parsed.
     pub fn normalize_json_value(
         value: &mut serde_json::Value,
         kind: TimeKind,
     ) -> Result<(), String> {
         match value {
+            // Times of day are given in milliseconds, never as instants
+            serde_json::Value::Number(n) if kind == TimeKind::Time => {
+                let millis = n
+                    .as_i64()
+                    .and_then(temporal::time_from_millis)
+                    .ok_or_else(|| format!("Time of day must be milliseconds of a day: {n}"))?;
+                *value = serde_json::Value::Number(millis.into());
+                Ok(())
+            }
             serde_json::Value::Number(n) => {
-                if let Some(i) = n.as_i64() {
-                    let norm = Self::normalize_integer_epoch(i as i128)
-                        .ok_or_else(|| format!("Unrecognized integer time magnitude: {i}"))?;
-                    *value = serde_json::Value::Number(norm.into());
-                    Ok(())
+                let secs = if let Some(i) = n.as_i64() {
+                    Self::normalize_integer_epoch(i as i128)
+                        .ok_or_else(|| format!("Unrecognized integer time magnitude: {i}"))?
                 } else if let Some(u) = n.as_u64() {
-                    let norm = Self::normalize_integer_epoch(u as i128)
-                        .ok_or_else(|| format!("Unrecognized integer time magnitude: {u}"))?;
-                    *value = serde_json::Value::Number(norm.into());
-                    Ok(())
+                    Self::normalize_integer_epoch(u as i128)
+                        .ok_or_else(|| format!("Unrecognized integer time magnitude: {u}"))?
                 } else if let Some(f) = n.as_f64() {
                     // Treat float as seconds.
-                    let secs = f.floor() as i64;
-                    *value = serde_json::Value::Number(secs.into());
-                    Ok(())
+                    f.floor() as i64
                 } else {
-                    Err("Unsupported numeric time value".to_string())
-                }
+                    return Err("Unsupported numeric time value".to_string());
+                };
+                let norm = match kind {
+                    TimeKind::Date => Self::truncate_to_day(secs)
+                        .ok_or_else(|| format!("Date out of range: {n}"))?,
+                    _ => secs,
+                };
+                *value = serde_json::Value::Number(norm.into());
+                Ok(())
             }
             serde_json::Value::String(s) => {
                 let parsed = Self::parse_str_to_epoch_seconds(s, kind)
@@ -114,9 +173,14 @@ pub fn now() -> u64 {
         .as_secs()
 }
 
-/// Format a Unix timestamp to a human-readable string (RFC3339)
+/// Format a Unix timestamp to a human-readable string (RFC3339) in the default timezone
 pub fn format_timestamp(timestamp: u64) -> String {
-    let dt = Utc.timestamp_opt(timestamp as i64, 0);
+    format_timestamp_in(timestamp, &default_timezone())
+}
+
+/// Format a Unix timestamp as RFC3339 with the offset `tz` has at that instant
+pub fn format_timestamp_in(timestamp: u64, tz: &Tz) -> String {
+    let dt = tz.timestamp_opt(timestamp as i64, 0);
     match dt.single() {
         Some(dt) => dt.to_rfc3339(),
         None => format!("<invalid timestamp: {}>", timestamp),
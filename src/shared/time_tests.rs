@@ -1,5 +1,6 @@
-use crate::shared::time::{TimeKind, TimeParser, format_timestamp, now};
+use crate::shared::time::{TimeKind, TimeParser, format_timestamp, format_timestamp_in, now};
 use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[test]
@@ -235,3 +236,101 @@ fn test_format_timestamp_consistency() {
 
     assert_eq!(formatted1, formatted2, "Formatting should be deterministic");
 }
+
+#[test]
+fn times_without_offset_are_read_in_the_given_timezone() {
+    let tz: Tz = "Europe/Amsterdam".parse().unwrap();
+    let parse = |s: &str| TimeParser::parse_str_to_epoch_seconds_in(s, TimeKind::DateTime, &tz);
+    let utc = |y, m, d, h, min| {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0)
+            .single()
+            .unwrap()
+            .timestamp()
+    };
+
+    // Summer time, UTC+2
+    assert_eq!(parse("2024-07-01T12:00:00"), Some(utc(2024, 7, 1, 10, 0)));
+    assert_eq!(parse("2024-07-01 12:00:00"), Some(utc(2024, 7, 1, 10, 0)));
+    assert_eq!(parse("2024-07-01"), Some(utc(2024, 6, 30, 22, 0)));
+    // Dates are days, not instants: the timezone does not shift them
+    assert_eq!(
+        TimeParser::parse_str_to_epoch_seconds_in("2024-07-01", TimeKind::Date, &tz),
+        Some(utc(2024, 7, 1, 0, 0))
+    );
+    // An explicit offset wins over the timezone
+    assert_eq!(
+        parse("2024-07-01T12:00:00+00:00"),
+        Some(utc(2024, 7, 1, 12, 0))
+    );
+
+    // Clocks skip 02:00-03:00: read with the winter offset, landing at 03:30 summer time
+    assert_eq!(parse("2024-03-31T02:30:00"), Some(utc(2024, 3, 31, 1, 30)));
+    // 02:30 happens twice: the earlier, still summer time, is taken
+    assert_eq!(parse("2024-10-27T02:30:00"), Some(utc(2024, 10, 27, 0, 30)));
+}
+
+#[test]
+fn format_timestamp_in_uses_the_offset_of_the_instant() {
+    let tz: Tz = "America/New_York".parse().unwrap();
+    assert_eq!(format_timestamp_in(0, &tz), "1969-12-31T19:00:00-05:00");
+    let july = Utc
+        .with_ymd_and_hms(2024, 7, 1, 12, 0, 0)
+        .single()
+        .unwrap()
+        .timestamp() as u64;
+    assert_eq!(format_timestamp_in(july, &tz), "2024-07-01T08:00:00-04:00");
+    assert_eq!(
+        format_timestamp_in(july, &Tz::UTC),
+        "2024-07-01T12:00:00+00:00"
+    );
+}
+
+#[test]
+fn dates_keep_the_day_as_written() {
+    let midnight = |y, m, d| {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0)
+            .single()
+            .unwrap()
+            .timestamp()
+    };
+    let parse = |s: &str| TimeParser::parse_str_to_epoch_seconds(s, TimeKind::Date);
+
+    assert_eq!(parse("2024-03-05"), Some(midnight(2024, 3, 5)));
+    assert_eq!(parse("2024-03-05T17:30:00Z"), Some(midnight(2024, 3, 5)));
+    // The day in the written offset, though it is already the 6th in UTC
+    assert_eq!(
+        parse("2024-03-05T23:30:00-05:00"),
+        Some(midnight(2024, 3, 5))
+    );
+    assert_eq!(parse("2024-03-05 08:00:00"), Some(midnight(2024, 3, 5)));
+    // Epoch instants give their UTC day, before 1970 too
+    assert_eq!(parse("1709659800"), Some(midnight(2024, 3, 5)));
+    assert_eq!(parse("-1"), Some(midnight(1969, 12, 31)));
+
+    let mut v = serde_json::json!(1_709_659_800_000u64);
+    TimeParser::normalize_json_value(&mut v, TimeKind::Date).unwrap();
+    assert_eq!(v, serde_json::json!(midnight(2024, 3, 5)));
+}
+
+#[test]
+fn times_of_day_normalize_to_milliseconds() {
+    let parse = |s: &str| TimeParser::parse_str_to_epoch_seconds(s, TimeKind::Time);
+    assert_eq!(parse("00:00"), Some(0));
+    assert_eq!(parse("14:30"), Some(52_200_000));
+    assert_eq!(parse("14:30:15"), Some(52_215_000));
+    assert_eq!(parse("23:59:59.999"), Some(86_399_999));
+    assert_eq!(parse("24:00"), None);
+    assert_eq!(parse("2024-03-05"), None);
+
+    let mut v = serde_json::json!("08:15:00.250");
+    TimeParser::normalize_json_value(&mut v, TimeKind::Time).unwrap();
+    assert_eq!(v, serde_json::json!(29_700_250));
+
+    // Numbers are milliseconds since midnight, not instants
+    let mut v = serde_json::json!(1_000);
+    TimeParser::normalize_json_value(&mut v, TimeKind::Time).unwrap();
+    assert_eq!(v, serde_json::json!(1_000));
+    let mut v = serde_json::json!(86_400_000);
+    let err = TimeParser::normalize_json_value(&mut v, TimeKind::Time).unwrap_err();
+    assert!(err.contains("milliseconds"), "{err}");
+}
@@ -0,0 +1,154 @@
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Key for work done on connections without an authenticated user.
+pub const ANONYMOUS_USER: &str = "<anonymous>";
+
+static GLOBAL_LEDGER: Lazy<UsageLedger> = Lazy::new(UsageLedger::new);
+
+/// Read work attributed to one user: every query counts, whether it
+/// succeeded, failed or was cancelled by the client going away.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadUsage {
+    pub queries: u64,
+    pub failed_queries: u64,
+    pub rows_scanned: u64,
+    pub bytes_scanned: u64,
+    pub rows_returned: u64,
+    pub cpu_time: Duration,
+}
+
+/// Write work attributed to one user: events accepted by STORE and BATCH.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WriteUsage {
+    pub events_stored: u64,
+    pub bytes_written: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UserUsage {
+    pub read: ReadUsage,
+    pub write: WriteUsage,
+}
+
+/// Resource usage accumulated per user since startup or the last reset,
+/// reported by SHOW USAGE. Recording takes a shard lock of the map and a
+/// handful of relaxed atomic adds, once per query or store command.
+#[derive(Debug, Default)]
+pub struct UsageLedger {
+    users: DashMap<String, UsageCounters>,
+}
+
+impl UsageLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ledger the server records into.
+    pub fn global() -> &'static UsageLedger {
+        &GLOBAL_LEDGER
+    }
+
+    pub fn record_read(&self, user_id: Option<&str>, usage: &ReadUsage) {
+        self.with_counters(user_id, |counters| counters.add_read(usage));
+    }
+
+    pub fn record_write(&self, user_id: Option<&str>, usage: &WriteUsage) {
+        self.with_counters(user_id, |counters| counters.add_write(usage));
+    }
+
+    /// Usage of every user seen so far, ordered by user id.
+    pub fn snapshot(&self) -> Vec<(String, UserUsage)> {
+        self.collect(UsageCounters::snapshot)
+    }
+
+    /// Zeroes every user's usage and returns what it was. Work recorded while
+    /// resetting lands either in the returned usage or after the reset, never
+    /// in neither.
+    pub fn reset(&self) -> Vec<(String, UserUsage)> {
+        self.collect(UsageCounters::take)
+    }
+
+    fn with_counters(&self, user_id: Option<&str>, f: impl FnOnce(&UsageCounters)) {
+        let user_id = user_id.unwrap_or(ANONYMOUS_USER);
+        // The shared lookup keeps known users off the map's write lock
+        if let Some(counters) = self.users.get(user_id) {
+            f(&counters);
+            return;
+        }
+        f(&self.users.entry(user_id.to_string()).or_default());
+    }
+
+    fn collect(&self, read: fn(&UsageCounters) -> UserUsage) -> Vec<(String, UserUsage)> {
+        let mut users: Vec<(String, UserUsage)> = self
+            .users
+            .iter()
+            .map(|entry| (entry.key().clone(), read(entry.value())))
+            .collect();
+        users.sort_by(|a, b| a.0.cmp(&b.0));
+        users
+    }
+}
+
+/// Relaxed atomics: the counters are statistics and order nothing else.
+#[derive(Debug, Default)]
+struct UsageCounters {
+    queries: AtomicU64,
+    failed_queries: AtomicU64,
+    rows_scanned: AtomicU64,
+    bytes_scanned: AtomicU64,
+    rows_returned: AtomicU64,
+    cpu_nanos: AtomicU64,
+    events_stored: AtomicU64,
+    bytes_written: AtomicU64,
+}
+
+impl UsageCounters {
+    fn add_read(&self, usage: &ReadUsage) {
+        self.queries.fetch_add(usage.queries, Ordering::Relaxed);
+        self.failed_queries
+            .fetch_add(usage.failed_queries, Ordering::Relaxed);
+        self.rows_scanned
+            .fetch_add(usage.rows_scanned, Ordering::Relaxed);
+        self.bytes_scanned
+            .fetch_add(usage.bytes_scanned, Ordering::Relaxed);
+        self.rows_returned
+            .fetch_add(usage.rows_returned, Ordering::Relaxed);
+        self.cpu_nanos
+            .fetch_add(usage.cpu_time.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    fn add_write(&self, usage: &WriteUsage) {
+        self.events_stored
+            .fetch_add(usage.events_stored, Ordering::Relaxed);
+        self.bytes_written
+            .fetch_add(usage.bytes_written, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> UserUsage {
+        self.read_with(|counter| counter.load(Ordering::Relaxed))
+    }
+
+    fn take(&self) -> UserUsage {
+        self.read_with(|counter| counter.swap(0, Ordering::Relaxed))
+    }
+
+    fn read_with(&self, read: impl Fn(&AtomicU64) -> u64) -> UserUsage {
+        UserUsage {
+            read: ReadUsage {
+                queries: read(&self.queries),
+                failed_queries: read(&self.failed_queries),
+                rows_scanned: read(&self.rows_scanned),
+                bytes_scanned: read(&self.bytes_scanned),
+                rows_returned: read(&self.rows_returned),
+                cpu_time: Duration::from_nanos(read(&self.cpu_nanos)),
+            },
+            write: WriteUsage {
+                events_stored: read(&self.events_stored),
+                bytes_written: read(&self.bytes_written),
+            },
+        }
+    }
+}
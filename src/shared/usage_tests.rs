@@ -0,0 +1,82 @@
+use crate::shared::usage::{ANONYMOUS_USER, ReadUsage, UsageLedger, UserUsage, WriteUsage};
+use std::time::Duration;
+
+fn read(rows_scanned: u64, failed: bool) -> ReadUsage {
+    ReadUsage {
+        queries: 1,
+        failed_queries: u64::from(failed),
+        rows_scanned,
+        bytes_scanned: rows_scanned * 8,
+        rows_returned: if failed { 0 } else { 2 },
+        cpu_time: Duration::from_millis(3),
+    }
+}
+
+#[test]
+fn usage_accumulates_per_user() {
+    let ledger = UsageLedger::new();
+    ledger.record_read(Some("bob"), &read(10, false));
+    ledger.record_read(Some("alice"), &read(100, false));
+    ledger.record_read(Some("alice"), &read(50, true));
+    ledger.record_write(
+        Some("bob"),
+        &WriteUsage {
+            events_stored: 3,
+            bytes_written: 90,
+        },
+    );
+
+    let users = ledger.snapshot();
+    let names: Vec<&str> = users.iter().map(|(user, _)| user.as_str()).collect();
+    assert_eq!(names, vec!["alice", "bob"]);
+
+    let alice = users[0].1;
+    assert_eq!(
+        alice.read,
+        ReadUsage {
+            queries: 2,
+            failed_queries: 1,
+            rows_scanned: 150,
+            bytes_scanned: 1200,
+            rows_returned: 2,
+            cpu_time: Duration::from_millis(6),
+        }
+    );
+    assert_eq!(alice.write, WriteUsage::default());
+
+    let bob = users[1].1;
+    assert_eq!(bob.read.queries, 1);
+    assert_eq!(
+        bob.write,
+        WriteUsage {
+            events_stored: 3,
+            bytes_written: 90,
+        }
+    );
+}
+
+#[test]
+fn usage_without_a_user_is_anonymous() {
+    let ledger = UsageLedger::new();
+    ledger.record_read(None, &read(1, false));
+
+    let users = ledger.snapshot();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].0, ANONYMOUS_USER);
+}
+
+#[test]
+fn reset_returns_usage_and_zeroes_it() {
+    let ledger = UsageLedger::new();
+    ledger.record_read(Some("alice"), &read(10, false));
+
+    let before = ledger.reset();
+    assert_eq!(before[0].1.read.rows_scanned, 10);
+    assert_eq!(
+        ledger.snapshot(),
+        vec![("alice".to_string(), UserUsage::default())]
+    );
+
+    ledger.record_read(Some("alice"), &read(5, false));
+    assert_eq!(ledger.snapshot()[0].1.read.rows_scanned, 5);
+}
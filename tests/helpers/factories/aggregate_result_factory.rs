@@ -45,7 +45,7 @@ impl AggregateResultFactory {
     ) -> Self {
         let key = GroupKey {
             bucket,
-            groups: groups.iter().map(|s| s.to_string()).collect(),
+            groups: groups.iter().map(|s| Some(s.to_string())).collect(),
         };
         self.groups.insert(key, states);
         self
@@ -63,7 +63,7 @@ impl AggregateResultFactory {
         }
         let key = GroupKey {
             bucket,
-            groups: vec![group.to_string()],
+            groups: vec![Some(group.to_string())],
         };
         self.groups
             .insert(key, vec![AggState::CountUnique { values: set }]);
@@ -1,5 +1,5 @@
 use crate::command::types::{
-    AggSpec, Command, Expr, FieldSpec, MiniSchema, OrderSpec, TimeGranularity,
+    AggSpec, Command, Expr, FieldSpec, MiniSchema, OrderSpec, PrunerKind, TimeGranularity,
 };
 use serde_json::{Value, json};
 
@@ -11,6 +11,9 @@ impl CommandFactory {
     pub fn define() -> Self {
         let schema = MiniSchema {
             fields: [("id".into(), FieldSpec::Primitive("int".into()))].into(),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: Default::default(),
         };
         Self {
             inner: Command::Define {
@@ -48,6 +51,14 @@ impl CommandFactory {
                 time_bucket: None,
                 group_by: None,
                 event_sequence: None,
+                latest_per: None,
+                dedup: None,
+                omit_nulls: false,
+                with_total: false,
+                raw_strings: false,
+                disabled_pruners: Vec::new(),
+                allow_full_scan: false,
+                unnest: None,
                 time_field: None,
                 sequence_time_field: None,
             },
@@ -62,6 +73,8 @@ impl CommandFactory {
                 since: Some("2023-01-01T00:00:00Z".into()),
                 return_fields: None,
                 time_field: None,
+                desc: false,
+                limit: None,
             },
         }
     }
@@ -113,12 +126,20 @@ impl CommandFactory {
     }
 
     pub fn with_limit(mut self, limit: u32) -> Self {
-        if let Command::Query { limit: l, .. } = &mut self.inner {
+        if let Command::Query { limit: l, .. } | Command::Replay { limit: l, .. } = &mut self.inner
+        {
             *l = Some(limit);
         }
         self
     }
 
+    pub fn with_desc(mut self, value: bool) -> Self {
+        if let Command::Replay { desc, .. } = &mut self.inner {
+            *desc = value;
+        }
+        self
+    }
+
     pub fn with_offset(mut self, offset: u32) -> Self {
         if let Command::Query { offset: o, .. } = &mut self.inner {
             *o = Some(offset);
@@ -264,6 +285,16 @@ impl CommandFactory {
         self
     }
 
+    pub fn with_disabled_pruners(mut self, kinds: Vec<PrunerKind>) -> Self {
+        if let Command::Query {
+            disabled_pruners, ..
+        } = &mut self.inner
+        {
+            *disabled_pruners = kinds;
+        }
+        self
+    }
+
     pub fn create(self) -> Command {
         self.inner
     }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::engine::core::column::compression::compression_codec::ALGO_LZ4;
 use crate::engine::core::column::compression::{CompressedColumnIndex, ZoneBlockEntry};
 
 pub struct CompressedColumnIndexFactory {
@@ -30,6 +31,8 @@ impl CompressedColumnIndexFactory {
                 comp_len,
                 uncomp_len,
                 num_rows,
+                codec: ALGO_LZ4,
+                checksum: None,
             },
         );
         Self {
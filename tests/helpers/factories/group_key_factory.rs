@@ -2,7 +2,7 @@ use crate::engine::core::read::aggregate::partial::GroupKey;
 
 pub struct GroupKeyFactory {
     bucket: Option<u64>,
-    groups: Vec<String>,
+    groups: Vec<Option<String>>,
 }
 
 impl GroupKeyFactory {
@@ -24,12 +24,12 @@ impl GroupKeyFactory {
     }
 
     pub fn with_groups(mut self, groups: &[&str]) -> Self {
-        self.groups = groups.iter().map(|s| s.to_string()).collect();
+        self.groups = groups.iter().map(|s| Some(s.to_string())).collect();
         self
     }
 
     pub fn add_group(mut self, group: &str) -> Self {
-        self.groups.push(group.to_string());
+        self.groups.push(Some(group.to_string()));
         self
     }
 
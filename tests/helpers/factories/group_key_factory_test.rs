@@ -7,9 +7,12 @@ fn builds_group_key_with_bucket_and_groups() {
         .with_groups(&["US", "CA"])
         .create();
     assert_eq!(gk.bucket, Some(1725148800));
-    assert_eq!(gk.groups, vec!["US", "CA"]);
+    assert_eq!(
+        gk.groups,
+        vec![Some("US".to_string()), Some("CA".to_string())]
+    );
 
     let gk2 = GroupKeyFactory::us_in_month(1725148800);
     assert_eq!(gk2.bucket, Some(1725148800));
-    assert_eq!(gk2.groups, vec!["US"]);
+    assert_eq!(gk2.groups, vec![Some("US".to_string())]);
 }
@@ -1,18 +1,27 @@
+use crate::command::types::FieldIndexKind;
 use crate::engine::schema::registry::MiniSchema;
 use crate::engine::schema::{EnumType, FieldType};
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 pub struct MiniSchemaFactory {
-    fields: HashMap<String, FieldType>,
+    fields: IndexMap<String, FieldType>,
+    time_field: Option<String>,
+    temporal_fields: Vec<String>,
+    indexes: IndexMap<String, Vec<FieldIndexKind>>,
 }
 
 impl MiniSchemaFactory {
     pub fn new() -> Self {
-        let mut fields = HashMap::new();
+        let mut fields = IndexMap::new();
         fields.insert("username".to_string(), FieldType::String);
         // map legacy "datetime" to string for tests
         fields.insert("created_at".to_string(), FieldType::String);
-        Self { fields }
+        Self {
+            fields,
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
+        }
     }
 
     pub fn with(mut self, key: &str, value: &str) -> Self {
@@ -37,20 +46,41 @@ impl MiniSchemaFactory {
         self
     }
 
+    pub fn with_time_field(mut self, key: &str) -> Self {
+        self.time_field = Some(key.to_string());
+        self
+    }
+
+    pub fn with_temporal_field(mut self, key: &str) -> Self {
+        self.temporal_fields.push(key.to_string());
+        self
+    }
+
+    pub fn with_indexes(mut self, key: &str, kinds: &[FieldIndexKind]) -> Self {
+        self.indexes.insert(key.to_string(), kinds.to_vec());
+        self
+    }
+
     pub fn without(mut self, key: &str) -> Self {
-        self.fields.remove(key);
+        self.fields.shift_remove(key);
         self
     }
 
     pub fn empty() -> Self {
         Self {
-            fields: HashMap::new(),
+            fields: IndexMap::new(),
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
         }
     }
 
     pub fn create(self) -> MiniSchema {
         MiniSchema {
             fields: self.fields,
+            time_field: self.time_field,
+            temporal_fields: self.temporal_fields,
+            indexes: self.indexes,
         }
     }
 }
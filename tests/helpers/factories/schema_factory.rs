@@ -1,7 +1,7 @@
 use crate::engine::schema::FieldType;
 use crate::engine::schema::errors::SchemaError;
 use crate::engine::schema::registry::{MiniSchema, SchemaRegistry};
-use std::collections::HashMap;
+use indexmap::IndexMap;
 use std::sync::Arc;
 use tempfile::TempDir;
 use tokio::sync::RwLock;
@@ -35,12 +35,17 @@ impl SchemaRegistryFactory {
         event_type: &str,
         fields: &[(&str, &str)],
     ) -> Result<(), SchemaError> {
-        let mut map = HashMap::new();
+        let mut map = IndexMap::new();
         for (k, v) in fields {
             let ft = FieldType::from_spec_with_nullable(v).unwrap_or(FieldType::String);
             map.insert(k.to_string(), ft);
         }
-        let mini = MiniSchema { fields: map };
+        let mini = MiniSchema {
+            fields: map,
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
+        };
         self.registry.write().await.define(event_type, mini)
     }
 
@@ -49,11 +54,16 @@ impl SchemaRegistryFactory {
         event_type: &str,
         fields: &[(&str, FieldType)],
     ) -> Result<(), SchemaError> {
-        let mut map = HashMap::new();
+        let mut map = IndexMap::new();
         for (k, v) in fields {
             map.insert((*k).to_string(), v.clone());
         }
-        let mini = MiniSchema { fields: map };
+        let mini = MiniSchema {
+            fields: map,
+            time_field: None,
+            temporal_fields: Vec::new(),
+            indexes: IndexMap::new(),
+        };
         self.registry.write().await.define(event_type, mini)
     }
 }
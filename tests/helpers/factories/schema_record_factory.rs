@@ -1,16 +1,16 @@
 use crate::engine::schema::FieldType;
 use crate::engine::schema::registry::{MiniSchema, SchemaRecord};
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 pub struct SchemaRecordFactory {
     uid: String,
     event_type: String,
-    fields: HashMap<String, FieldType>,
+    fields: IndexMap<String, FieldType>,
 }
 
 impl SchemaRecordFactory {
     pub fn new(event_type: &str) -> Self {
-        let mut fields = HashMap::new();
+        let mut fields = IndexMap::new();
         fields.insert("field1".to_string(), FieldType::String);
         fields.insert("field2".to_string(), FieldType::U64);
 
@@ -33,7 +33,7 @@ impl SchemaRecordFactory {
     }
 
     pub fn without_field(mut self, key: &str) -> Self {
-        self.fields.remove(key);
+        self.fields.shift_remove(key);
         self
     }
 
@@ -43,6 +43,9 @@ impl SchemaRecordFactory {
             event_type: self.event_type,
             schema: MiniSchema {
                 fields: self.fields,
+                time_field: None,
+                temporal_fields: Vec::new(),
+                indexes: IndexMap::new(),
             },
         }
     }
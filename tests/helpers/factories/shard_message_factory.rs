@@ -1,6 +1,7 @@
 use crate::command::types::Command;
 use crate::engine::core::Event;
 use crate::engine::core::read::flow::shard_pipeline::ShardFlowHandle;
+use crate::engine::core::read::flow::{QueryCancellation, QueryMemoryBudget, QueryScanStats};
 use crate::engine::schema::registry::SchemaRegistry;
 use crate::engine::shard::message::ShardMessage;
 use std::sync::Arc;
@@ -44,6 +45,9 @@ impl ShardMessageFactory {
                 metadata: None,
                 response: tx,
                 registry: Arc::clone(&self.registry),
+                memory: QueryMemoryBudget::unlimited(),
+                stats: QueryScanStats::new(),
+                cancellation: QueryCancellation::new(),
             },
             rx,
         )
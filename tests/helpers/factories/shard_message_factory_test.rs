@@ -59,6 +59,9 @@ async fn test_shard_message_factory_variants() {
             metadata: _,
             response: _,
             registry: reg,
+            memory: _,
+            stats: _,
+            cancellation: _,
         } => {
             assert_eq!(format!("{:?}", c), format!("{:?}", cmd));
             assert!(Arc::ptr_eq(&reg, &registry));
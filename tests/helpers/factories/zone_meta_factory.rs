@@ -38,6 +38,16 @@ impl ZoneMetaFactory {
                 .get("created_at")
                 .and_then(|v| v.as_u64())
                 .unwrap_or(0),
+            event_id_min: self
+                .params
+                .get("event_id_min")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0),
+            event_id_max: self
+                .params
+                .get("event_id_max")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(u64::MAX),
         }
     }
 }